@@ -240,7 +240,9 @@ mod reflection {
             "safe_lang",
             "safe_media",
                 "safe_audio",
+                "safe_doc",
                 "safe_font",
+                "safe_motion",
                 "safe_visual",
             "safe_num",
             "safe_org",
@@ -301,13 +303,15 @@ mod reflection {
         ref_flags: &["media··"],
         features: &["media",
             "audio",
+            "doc",
             "font",
-            "visual", "color", "draw", "image",
+            "motion",
+            "visual", "color", "draw", "image", "video",
         ]
     };
         pub const VISUAL: FlagsFeatures = FlagsFeatures {
             ref_flags: &["visual··"],
-            features: &["visual", "color", "draw", "image"]
+            features: &["visual", "color", "draw", "image", "video"]
         };
     pub const NUM: FlagsFeatures = FlagsFeatures {
         ref_flags: &["num··"],
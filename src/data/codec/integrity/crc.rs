@@ -0,0 +1,117 @@
+// devela/src/data/codec/integrity/crc.rs
+//
+//! Defines [`Crc32`].
+//
+
+use crate::{ConstInit, Hasher, whilst};
+
+#[doc = crate::_tags!(hash)]
+/// CRC-32 checksum.
+#[doc = crate::_doc_meta!{
+    location("data/codec", struct Crc32),
+    test_size_of(Crc32 = 4|32),
+}]
+///
+/// The cyclic redundancy check used by PNG, gzip, and ZIP,
+/// with the reflected polynomial `0xEDB8_8320` (CRC-32/ISO-HDLC).
+///
+/// Like [`Adler32`][crate::Adler32], CRC-32 is **byte-order sensitive**,
+/// and supports incremental updates via [`write_bytes`][Self::write_bytes].
+///
+/// For details, see <https://en.wikipedia.org/wiki/Cyclic_redundancy_check>.
+#[derive(Debug, Copy, Clone)]
+pub struct Crc32 {
+    /// The running remainder, without the final inversion.
+    state: u32,
+}
+
+#[rustfmt::skip]
+impl ConstInit for Crc32 { const INIT: Self = Self::new(); }
+#[rustfmt::skip]
+impl Default for Crc32 { fn default() -> Self { Self::new() } }
+#[rustfmt::skip]
+impl Hasher for Crc32 {
+    fn finish(&self) -> u64 { u64::from(self.checksum()) }
+    fn write(&mut self, bytes: &[u8]) { self.write_bytes(bytes); }
+}
+
+/// Compile-time friendly API.
+#[rustfmt::skip]
+impl Crc32 {
+    /// The reflected generator polynomial.
+    pub const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+    /// The lookup table for byte-wise updates.
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        whilst! { i in 0..256; {
+            let mut c = i as u32;
+            whilst! { _k in 0..8; {
+                c = if c & 1 != 0 { Self::POLYNOMIAL ^ (c >> 1) } else { c >> 1 };
+            }}
+            table[i] = c;
+        }}
+        table
+    };
+
+    /* constructors */
+
+    /// Creates a new CRC-32 state, for which the checksum is `0`.
+    pub const fn new() -> Self { Self { state: u32::MAX } }
+
+    /// Builds a checksum state from a previously computed CRC-32 value.
+    pub const fn from_checksum(sum: u32) -> Self { Self { state: !sum } }
+
+    /* */
+
+    /// Returns the current CRC-32 checksum.
+    pub const fn checksum(&self) -> u32 { !self.state }
+
+    /// Computes the CRC-32 checksum of a byte slice.
+    ///
+    /// This is a convenience wrapper that does not require
+    /// constructing a [`Crc32`] value explicitly.
+    pub const fn checksum_bytes(bytes: &[u8]) -> u32 {
+        let mut new = Crc32::new();
+        new.write_bytes(bytes);
+        new.checksum()
+    }
+
+    /// Updates the checksum with the given bytes.
+    ///
+    /// This method may be called multiple times to checksum data streams.
+    pub const fn write_bytes(&mut self, bytes: &[u8]) {
+        let mut c = self.state;
+        whilst! { i in 0..bytes.len(); {
+            c = Self::TABLE[((c ^ bytes[i] as u32) & 0xFF) as usize] ^ (c >> 8);
+        }}
+        self.state = c;
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod tests {
+    use crate::Crc32;
+
+    #[test]
+    fn checksum_bytes() {
+        assert_eq![0x0000_0000, Crc32::checksum_bytes(b"")];
+        assert_eq![0xE8B7_BE43, Crc32::checksum_bytes(b"a")];
+        assert_eq![0x3524_41C2, Crc32::checksum_bytes(b"abc")];
+        assert_eq![0xCBF4_3926, Crc32::checksum_bytes(b"123456789")];
+        assert_eq![0x414F_A339, Crc32::checksum_bytes(b"The quick brown fox jumps over the lazy dog")];
+    }
+
+    #[test]
+    fn write_bytes() {
+        let full = Crc32::checksum_bytes(b"HelloWorld");
+        let mut st = Crc32::new();
+        st.write_bytes(b"Hello");
+        st.write_bytes(b"World");
+        assert_eq!(full, st.checksum());
+        let mut resumed = Crc32::from_checksum(Crc32::checksum_bytes(b"Hello"));
+        resumed.write_bytes(b"World");
+        assert_eq!(full, resumed.checksum());
+    }
+}
@@ -7,13 +7,13 @@
 //
 
 mod adler; // Adler32
-// mod crc; // Crc32 WIP
+mod crc; // Crc32
 
 crate::structural_mods! { // _mods
     _mods {
         pub use super::{
             adler::*,
-            // crc::*,
+            crc::*,
         };
     }
 }
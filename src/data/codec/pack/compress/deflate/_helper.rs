@@ -0,0 +1,428 @@
+// devela/src/data/codec/pack/compress/deflate/_helper.rs
+//
+//! Bit streams, Huffman tables, and the block coders behind [`Deflate`][crate::Deflate].
+//
+// TOC
+// - tables
+// - struct BitReader
+// - struct Huffman
+// - fn inflate
+// - struct BitWriter
+// - struct Matcher
+// - fn deflate
+
+use crate::{DeflateError, is};
+use DeflateError as E;
+
+/* tables (RFC 1951, § 3.2.5) */
+
+/// Base match length for each length symbol `257..=285`.
+const LEN_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+/// Extra bits for each length symbol `257..=285`.
+const LEN_EXTRA: [u8; 29] =
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+/// Base distance for each distance symbol.
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits for each distance symbol.
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// The order in which code length code lengths are stored.
+const CLEN_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// The largest amount of input bytes coded into one block.
+///
+/// Keeps every block representable as a single stored block.
+pub(super) const BLOCK_LEN: usize = 65535;
+/// The farthest a back-reference can reach.
+const WINDOW: usize = 32768;
+/// The shortest match length.
+const MIN_MATCH: usize = 3;
+/// The longest match length.
+const MAX_MATCH: usize = 258;
+
+/* reading */
+
+/// Reads bits least-significant first, pulling bytes only as needed.
+pub(super) struct BitReader<I> {
+    src: I,
+    bits: u32,
+    nbits: u32,
+}
+impl<I: Iterator<Item = u8>> BitReader<I> {
+    pub(super) const fn new(src: I) -> Self {
+        Self { src, bits: 0, nbits: 0 }
+    }
+    /// Reads `n <= 16` bits.
+    pub(super) fn bits(&mut self, n: u32) -> Result<u32, DeflateError> {
+        while self.nbits < n {
+            let byte = self.src.next().ok_or(E::UnexpectedEnd)?;
+            self.bits |= u32::from(byte) << self.nbits;
+            self.nbits += 8;
+        }
+        let v = self.bits & ((1 << n) - 1);
+        self.bits >>= n;
+        self.nbits -= n;
+        Ok(v)
+    }
+    /// Discards the bits left in the current byte.
+    pub(super) fn align(&mut self) {
+        (self.bits, self.nbits) = (0, 0);
+    }
+    /// Reads a whole byte, after [`align`][Self::align].
+    pub(super) fn byte(&mut self) -> Result<u8, DeflateError> {
+        self.src.next().ok_or(E::UnexpectedEnd)
+    }
+}
+
+/// A canonical Huffman decoding table.
+struct Huffman {
+    /// The number of codes of each length.
+    counts: [u16; 16],
+    /// The symbols ordered by code.
+    symbols: [u16; 288],
+}
+impl Huffman {
+    /// Builds the table from the code length of each symbol.
+    ///
+    /// Incomplete codes are accepted, and fail only when an unused code is read.
+    fn new(lengths: &[u8]) -> Result<Self, DeflateError> {
+        let mut counts = [0_u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut left = 1_i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            is![left < 0, return Err(E::InvalidCodeLengths)];
+        }
+        let mut offsets = [0_u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = [0_u16; 288];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+    /// Builds the fixed literal/length and distance tables.
+    fn fixed() -> (Self, Self) {
+        let mut lengths = [8_u8; 288];
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        let lit = Self::new(&lengths).expect("valid fixed lengths");
+        let dist = Self::new(&[5; 30]).expect("valid fixed lengths");
+        (lit, dist)
+    }
+    /// Decodes one symbol, reading its code bit by bit.
+    fn decode<I: Iterator<Item = u8>>(&self, r: &mut BitReader<I>) -> Result<u16, DeflateError> {
+        let (mut code, mut first, mut index) = (0_i32, 0_i32, 0_i32);
+        for &count in &self.counts[1..] {
+            code |= r.bits(1)? as i32;
+            let count = i32::from(count);
+            is![code - first < count, return Ok(self.symbols[(index + code - first) as usize])];
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(E::InvalidSymbol)
+    }
+}
+
+/// Decompresses a raw DEFLATE stream into `out`, returning the written length.
+pub(super) fn inflate<I: Iterator<Item = u8>>(
+    r: &mut BitReader<I>,
+    out: &mut [u8],
+) -> Result<usize, DeflateError> {
+    let mut pos = 0;
+    loop {
+        let last = r.bits(1)? == 1;
+        match r.bits(2)? {
+            0 => inflate_stored(r, out, &mut pos)?,
+            1 => {
+                let (lit, dist) = Huffman::fixed();
+                inflate_codes(r, &lit, &dist, out, &mut pos)?;
+            }
+            2 => {
+                let (lit, dist) = read_dynamic(r)?;
+                inflate_codes(r, &lit, &dist, out, &mut pos)?;
+            }
+            _ => return Err(E::InvalidBlockType),
+        }
+        is![last, return Ok(pos)];
+    }
+}
+fn inflate_stored<I: Iterator<Item = u8>>(
+    r: &mut BitReader<I>,
+    out: &mut [u8],
+    pos: &mut usize,
+) -> Result<(), DeflateError> {
+    r.align();
+    let len = u16::from_le_bytes([r.byte()?, r.byte()?]);
+    let nlen = u16::from_le_bytes([r.byte()?, r.byte()?]);
+    is![len != !nlen, return Err(E::InvalidStoredLength)];
+    let dst = out.get_mut(*pos..*pos + len as usize).ok_or(E::NotEnoughSpace)?;
+    for byte in dst {
+        *byte = r.byte()?;
+    }
+    *pos += len as usize;
+    Ok(())
+}
+fn read_dynamic<I: Iterator<Item = u8>>(
+    r: &mut BitReader<I>,
+) -> Result<(Huffman, Huffman), DeflateError> {
+    let nlen = r.bits(5)? as usize + 257;
+    let ndist = r.bits(5)? as usize + 1;
+    let ncode = r.bits(4)? as usize + 4;
+    is![nlen > 286 || ndist > 30, return Err(E::InvalidCodeLengths)];
+    let mut lengths = [0_u8; 316];
+    for &i in &CLEN_ORDER[..ncode] {
+        lengths[i] = r.bits(3)? as u8;
+    }
+    let clen = Huffman::new(&lengths[..19])?;
+    lengths[..19].fill(0);
+    let mut i = 0;
+    while i < nlen + ndist {
+        let symbol = clen.decode(r)?;
+        let (value, repeat) = match symbol {
+            0..16 => (symbol as u8, 1),
+            16 => {
+                is![i == 0, return Err(E::InvalidCodeLengths)];
+                (lengths[i - 1], 3 + r.bits(2)? as usize)
+            }
+            17 => (0, 3 + r.bits(3)? as usize),
+            _ => (0, 11 + r.bits(7)? as usize),
+        };
+        is![i + repeat > nlen + ndist, return Err(E::InvalidCodeLengths)];
+        lengths[i..i + repeat].fill(value);
+        i += repeat;
+    }
+    is![lengths[256] == 0, return Err(E::InvalidCodeLengths)];
+    Ok((Huffman::new(&lengths[..nlen])?, Huffman::new(&lengths[nlen..nlen + ndist])?))
+}
+fn inflate_codes<I: Iterator<Item = u8>>(
+    r: &mut BitReader<I>,
+    lit: &Huffman,
+    dist: &Huffman,
+    out: &mut [u8],
+    pos: &mut usize,
+) -> Result<(), DeflateError> {
+    loop {
+        let symbol = lit.decode(r)? as usize;
+        if symbol < 256 {
+            *out.get_mut(*pos).ok_or(E::NotEnoughSpace)? = symbol as u8;
+            *pos += 1;
+        } else if symbol == 256 {
+            return Ok(());
+        } else {
+            let symbol = symbol - 257;
+            is![symbol >= 29, return Err(E::InvalidSymbol)];
+            let len = LEN_BASE[symbol] as usize + r.bits(LEN_EXTRA[symbol].into())? as usize;
+            let symbol = dist.decode(r)? as usize;
+            is![symbol >= 30, return Err(E::InvalidSymbol)];
+            let d = DIST_BASE[symbol] as usize + r.bits(DIST_EXTRA[symbol].into())? as usize;
+            is![d > *pos, return Err(E::InvalidDistance)];
+            is![*pos + len > out.len(), return Err(E::NotEnoughSpace)];
+            // byte by byte, since the source may overlap the copied bytes
+            for i in *pos..*pos + len {
+                out[i] = out[i - d];
+            }
+            *pos += len;
+        }
+    }
+}
+
+/* writing */
+
+/// Writes bits least-significant first, counting the bytes that don't fit.
+pub(super) struct BitWriter<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+    bits: u64,
+    nbits: u32,
+}
+/// A restorable [`BitWriter`] position.
+#[derive(Clone, Copy)]
+struct BitMark {
+    pos: usize,
+    bits: u64,
+    nbits: u32,
+}
+impl<'a> BitWriter<'a> {
+    pub(super) const fn new(out: &'a mut [u8]) -> Self {
+        Self { out, pos: 0, bits: 0, nbits: 0 }
+    }
+    /// Returns the number of bytes written, including those that didn't fit.
+    pub(super) const fn pos(&self) -> usize {
+        self.pos
+    }
+    /// Returns whether some written bytes didn't fit in the buffer.
+    pub(super) const fn overflowed(&self) -> bool {
+        self.pos > self.out.len()
+    }
+    const fn bit_len(&self) -> usize {
+        self.pos * 8 + self.nbits as usize
+    }
+    const fn mark(&self) -> BitMark {
+        BitMark { pos: self.pos, bits: self.bits, nbits: self.nbits }
+    }
+    const fn restore(&mut self, m: BitMark) {
+        (self.pos, self.bits, self.nbits) = (m.pos, m.bits, m.nbits);
+    }
+    pub(super) fn byte(&mut self, byte: u8) {
+        if let Some(b) = self.out.get_mut(self.pos) {
+            *b = byte;
+        }
+        self.pos += 1;
+    }
+    /// Writes the `n` lowest bits of `v`.
+    fn bits(&mut self, v: u32, n: u32) {
+        self.bits |= u64::from(v) << self.nbits;
+        self.nbits += n;
+        while self.nbits >= 8 {
+            self.byte(self.bits as u8);
+            self.bits >>= 8;
+            self.nbits -= 8;
+        }
+    }
+    /// Writes a Huffman code of `n` bits, most-significant first.
+    fn code(&mut self, code: u32, n: u32) {
+        self.bits(code.reverse_bits() >> (32 - n), n);
+    }
+    /// Pads the current byte with zero bits.
+    pub(super) fn align(&mut self) {
+        is![self.nbits > 0, self.bits(0, 8 - self.nbits)];
+    }
+    /// Writes a literal/length symbol with the fixed code.
+    fn fixed_literal(&mut self, symbol: u32) {
+        match symbol {
+            0..144 => self.code(0x30 + symbol, 8),
+            144..256 => self.code(0x190 + symbol - 144, 9),
+            256..280 => self.code(symbol - 256, 7),
+            _ => self.code(0xC0 + symbol - 280, 8),
+        }
+    }
+    /// Writes a match with the fixed codes.
+    fn fixed_match(&mut self, len: usize, dist: usize) {
+        let l = LEN_BASE.iter().rposition(|&b| b as usize <= len).unwrap_or(0);
+        self.fixed_literal(257 + l as u32);
+        self.bits((len - LEN_BASE[l] as usize) as u32, LEN_EXTRA[l].into());
+        let d = DIST_BASE.iter().rposition(|&b| b as usize <= dist).unwrap_or(0);
+        self.code(d as u32, 5);
+        self.bits((dist - DIST_BASE[d] as usize) as u32, DIST_EXTRA[d].into());
+    }
+}
+
+/// Finds earlier occurrences of the upcoming bytes through hash chains.
+struct Matcher {
+    /// The most recent position + 1 for each hash, or `0`.
+    head: [usize; 1 << Self::HASH_BITS],
+    /// The distance to the previous position with the same hash, or `0`.
+    prev: [u16; WINDOW],
+}
+impl Matcher {
+    const HASH_BITS: u32 = 12;
+    /// The number of candidates examined per position.
+    const MAX_CHAIN: usize = 64;
+
+    const fn new() -> Self {
+        Self { head: [0; 1 << Self::HASH_BITS], prev: [0; WINDOW] }
+    }
+    const fn hash(b: &[u8], i: usize) -> usize {
+        let v = (b[i] as u32) << 16 | (b[i + 1] as u32) << 8 | b[i + 2] as u32;
+        (v.wrapping_mul(0x9E37_79B1) >> (32 - Self::HASH_BITS)) as usize
+    }
+    fn insert(&mut self, b: &[u8], i: usize) {
+        is![i + MIN_MATCH > b.len(), return];
+        let h = Self::hash(b, i);
+        let dist = (i + 1).wrapping_sub(self.head[h]);
+        self.prev[i % WINDOW] = is![self.head[h] > 0 && dist < WINDOW, dist as u16, 0];
+        self.head[h] = i + 1;
+    }
+    /// Returns the longest match `(len, dist)` for position `i`, not reaching `end`.
+    fn find(&self, b: &[u8], i: usize, end: usize) -> Option<(usize, usize)> {
+        let max = MAX_MATCH.min(end - i);
+        is![max < MIN_MATCH, return None];
+        let (mut best_len, mut best_dist) = (0, 0);
+        let mut cand = self.head[Self::hash(b, i)];
+        let mut chain = Self::MAX_CHAIN;
+        while cand > 0 && chain > 0 {
+            let c = cand - 1;
+            is![i - c >= WINDOW, break];
+            if b[c + best_len] == b[i + best_len] {
+                let len = b[c..c + max].iter().zip(&b[i..i + max]).take_while(|(x, y)| x == y);
+                let len = len.count();
+                if len > best_len {
+                    (best_len, best_dist) = (len, i - c);
+                    is![len == max, break];
+                }
+            }
+            let step = self.prev[c % WINDOW] as usize;
+            is![step == 0, break];
+            cand -= step;
+            chain -= 1;
+        }
+        is![best_len >= MIN_MATCH, Some((best_len, best_dist)), None]
+    }
+}
+
+/// Compresses `input` as a raw DEFLATE stream.
+///
+/// Each block uses the fixed Huffman codes,
+/// or is stored as is when that would be shorter.
+pub(super) fn deflate(input: &[u8], w: &mut BitWriter) {
+    let mut m = Matcher::new();
+    let mut start = 0;
+    loop {
+        let end = input.len().min(start + BLOCK_LEN);
+        let last = end == input.len();
+        let mark = w.mark();
+        w.bits(last.into(), 1);
+        w.bits(1, 2);
+        let mut i = start;
+        while i < end {
+            if let Some((len, dist)) = m.find(input, i, end) {
+                w.fixed_match(len, dist);
+                for k in i..i + len {
+                    m.insert(input, k);
+                }
+                i += len;
+            } else {
+                m.insert(input, i);
+                w.fixed_literal(input[i].into());
+                i += 1;
+            }
+        }
+        w.fixed_literal(256);
+        let stored_bits =
+            (mark.pos * 8 + mark.nbits as usize + 3).next_multiple_of(8) + 32 + (end - start) * 8;
+        if w.bit_len() > stored_bits {
+            w.restore(mark);
+            w.bits(last.into(), 1);
+            w.bits(0, 2);
+            w.align();
+            let len = (end - start) as u16;
+            for byte in len.to_le_bytes().into_iter().chain((!len).to_le_bytes()) {
+                w.byte(byte);
+            }
+            for &byte in &input[start..end] {
+                w.byte(byte);
+            }
+        }
+        is![last, break];
+        start = end;
+    }
+    w.align();
+}
@@ -0,0 +1,101 @@
+// devela/src/data/codec/pack/compress/deflate/_test.rs
+
+use crate::{Deflate, DeflateError};
+
+const LOREM: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, \
+quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat.";
+/// `LOREM` compressed by zlib at level 9, using a dynamic Huffman block.
+const LOREM_ZLIB: [u8; 156] = [
+    0x78, 0xda, 0x25, 0x8f, 0x51, 0x8e, 0x03, 0x31, 0x08, 0x43, 0xaf, 0xe2, 0x03, 0x54, 0x3d, 0x49,
+    0x7f, 0xf7, 0x00, 0x34, 0x41, 0x95, 0xa5, 0x10, 0x66, 0x02, 0x59, 0xed, 0xf1, 0x97, 0xe9, 0xfc,
+    0x81, 0xb0, 0x9f, 0xcd, 0xcb, 0x97, 0x1a, 0x78, 0xc4, 0x36, 0x74, 0x1f, 0xbe, 0x10, 0x4c, 0x88,
+    0x69, 0x3e, 0xd0, 0x7c, 0x86, 0xb6, 0xd4, 0xdc, 0x0b, 0xd2, 0x79, 0x30, 0x1a, 0xe7, 0x07, 0x3a,
+    0x58, 0xc7, 0xd0, 0x5e, 0x06, 0x28, 0x77, 0x98, 0x77, 0xa4, 0xda, 0x51, 0x66, 0xce, 0xc6, 0xce,
+    0xbe, 0x67, 0x62, 0x27, 0x86, 0xbc, 0x0b, 0x0f, 0xcd, 0x1b, 0xad, 0x30, 0xf9, 0x4c, 0x81, 0x0c,
+    0x9e, 0x5b, 0x9e, 0xf8, 0x49, 0xe8, 0xa4, 0x15, 0x1b, 0xc6, 0x6b, 0xf8, 0xad, 0x55, 0xec, 0x81,
+    0x73, 0x33, 0x30, 0x3d, 0x72, 0xed, 0x0e, 0xfd, 0xd3, 0xd5, 0x98, 0x92, 0xf4, 0x89, 0x3d, 0x86,
+    0x58, 0xf3, 0x9b, 0x7c, 0x89, 0x18, 0xbc, 0x92, 0xbe, 0x48, 0x1e, 0x25, 0x86, 0x4a, 0x15, 0xb7,
+    0xea, 0xe4, 0xf7, 0x03, 0x15, 0x95, 0xcf, 0x7f, 0xae, 0x3a, 0x55, 0x80,
+];
+/// `b"stored"` compressed by zlib at level 0.
+const STORED_ZLIB: [u8; 17] = [
+    0x78, 0x01, 0x01, 0x06, 0x00, 0xf9, 0xff, 0x73, 0x74, 0x6f, 0x72, 0x65, 0x64, 0x09, 0x3c, 0x02,
+    0x92,
+];
+
+/// Returns `len` pseudo-random bytes, compressible when `runs` is true.
+fn sample(len: usize, runs: bool) -> [u8; 200_000] {
+    let (mut buf, mut x) = ([0; 200_000], 0x2545_f491_u32);
+    for (i, b) in buf[..len].iter_mut().enumerate() {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        *b = if runs { b"abcd"[(x % 4) as usize] ^ ((i / 1000) as u8 & 1) } else { x as u8 };
+    }
+    buf
+}
+fn round_trip(data: &[u8]) -> usize {
+    let mut packed = [0; 201_000];
+    let len = Deflate::encode_zlib(data, &mut packed).unwrap();
+    assert![len <= Deflate::encoded_len_max_zlib(data.len())];
+    let mut out = [0; 200_000];
+    let n = Deflate::decode_zlib(&packed[..len], &mut out).unwrap();
+    assert_eq![&out[..n], data];
+    // a buffer one byte short fails
+    assert_eq![
+        Deflate::encode_zlib(data, &mut packed[..len - 1]),
+        Err(DeflateError::NotEnoughSpace)
+    ];
+    len
+}
+
+#[test]
+fn decode_reference() {
+    let mut out = [0; 256];
+    assert_eq![Deflate::decode_zlib(&LOREM_ZLIB, &mut out), Ok(LOREM.len())];
+    assert_eq![&out[..LOREM.len()], LOREM];
+    assert_eq![Deflate::decode_zlib(&STORED_ZLIB, &mut out), Ok(6)];
+    assert_eq![&out[..6], b"stored"];
+    // split across regions
+    let (a, b) = LOREM_ZLIB.split_at(40);
+    let n = Deflate::decode_zlib_iter(a.iter().chain(b).copied(), &mut out).unwrap();
+    assert_eq![&out[..n], LOREM];
+    // raw stream
+    assert_eq![Deflate::decode(&LOREM_ZLIB[2..], &mut out), Ok(LOREM.len())];
+}
+#[test]
+fn decode_errors() {
+    let mut out = [0; 256];
+    assert_eq![
+        Deflate::decode_zlib(&LOREM_ZLIB[..100], &mut out),
+        Err(DeflateError::UnexpectedEnd)
+    ];
+    assert_eq![
+        Deflate::decode_zlib(&LOREM_ZLIB, &mut out[..100]),
+        Err(DeflateError::NotEnoughSpace)
+    ];
+    let mut bad = LOREM_ZLIB;
+    bad[155] ^= 1;
+    assert_eq![Deflate::decode_zlib(&bad, &mut out), Err(DeflateError::ChecksumMismatch)];
+    bad[1] ^= 1;
+    assert_eq![Deflate::decode_zlib(&bad, &mut out), Err(DeflateError::InvalidHeader)];
+    assert_eq![Deflate::decode(&[0b111], &mut out), Err(DeflateError::InvalidBlockType)];
+    assert_eq![Deflate::decode(&[1, 1, 0, 0, 0], &mut out), Err(DeflateError::InvalidStoredLength)];
+    // a fixed block whose first code is a match with distance 1
+    assert_eq![Deflate::decode(&[0x03, 0x02, 0x00], &mut out), Err(DeflateError::InvalidDistance)];
+}
+#[test]
+fn encode_round_trip() {
+    assert_eq![round_trip(b""), 8];
+    round_trip(b"a");
+    assert![round_trip(LOREM) < LOREM.len()];
+    // repetitive data spanning several blocks
+    let data = sample(150_000, true);
+    assert![round_trip(&data[..150_000]) < 75_000];
+    // incompressible data falls back to stored blocks
+    let data = sample(140_000, false);
+    assert_eq![round_trip(&data[..140_000]), Deflate::encoded_len_max_zlib(140_000)];
+    // long runs of a single byte
+    assert![round_trip(&[7; 100_000]) < 1000];
+}
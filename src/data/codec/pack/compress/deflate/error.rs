@@ -0,0 +1,41 @@
+// devela/src/data/codec/pack/compress/deflate/error.rs
+//
+//! Defines [`DeflateError`].
+//
+
+#[doc = crate::_tags!(data codec error)]
+/// DEFLATE and zlib coding error.
+#[doc = crate::_doc_meta!{location("data/codec/pack")}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeflateError {
+    /// The compressed stream ended before its final block.
+    UnexpectedEnd,
+    /// A block header declares the reserved block type `3`.
+    InvalidBlockType,
+    /// A stored block length doesn't match its one's complement.
+    InvalidStoredLength,
+    /// The code lengths of a dynamic block don't describe valid Huffman codes.
+    InvalidCodeLengths,
+    /// The stream contains an undefined or unused code.
+    InvalidSymbol,
+    /// A back-reference reaches before the start of the output.
+    InvalidDistance,
+    /// The zlib header is malformed, uses a preset dictionary,
+    /// or declares a method other than DEFLATE.
+    InvalidHeader,
+    /// The zlib Adler-32 checksum doesn't match the decompressed data.
+    ChecksumMismatch,
+    /// The destination buffer is too small.
+    NotEnoughSpace,
+}
+crate::impl_trait![fmt::Display+Error for DeflateError |self, f| match self {
+    Self::UnexpectedEnd => f.write_str("incomplete DEFLATE stream"),
+    Self::InvalidBlockType => f.write_str("invalid DEFLATE block type"),
+    Self::InvalidStoredLength => f.write_str("invalid DEFLATE stored block length"),
+    Self::InvalidCodeLengths => f.write_str("invalid DEFLATE Huffman code lengths"),
+    Self::InvalidSymbol => f.write_str("invalid DEFLATE symbol"),
+    Self::InvalidDistance => f.write_str("DEFLATE distance reaches before the output start"),
+    Self::InvalidHeader => f.write_str("invalid or unsupported zlib header"),
+    Self::ChecksumMismatch => f.write_str("zlib checksum mismatch"),
+    Self::NotEnoughSpace => f.write_str("not enough space to write DEFLATE data"),
+}];
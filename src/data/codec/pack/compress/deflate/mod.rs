@@ -0,0 +1,21 @@
+// devela/src/data/codec/pack/compress/deflate/mod.rs
+//
+//! DEFLATE and zlib compressed streams.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (BitReader, BitWriter, Huffman, Matcher, deflate, inflate)
+
+mod error; // DeflateError
+mod namespace; // Deflate
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            error::*,
+            namespace::*,
+        };
+    }
+}
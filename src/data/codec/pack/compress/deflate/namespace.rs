@@ -0,0 +1,140 @@
+// devela/src/data/codec/pack/compress/deflate/namespace.rs
+//
+//! Defines [`Deflate`].
+//
+
+use super::_helper::{BLOCK_LEN, BitReader, BitWriter, deflate, inflate};
+use crate::{Adler32, DeflateError, is};
+
+#[doc = crate::_tags!(data codec)]
+/// DEFLATE compression, raw or wrapped in a zlib stream.
+#[doc = crate::_doc_meta!{location("data/codec/pack")}]
+///
+/// DEFLATE ([RFC 1951]) combines LZ77 back-references with Huffman coding,
+/// and is the compression used by PNG, gzip, and ZIP.
+/// zlib ([RFC 1950]) wraps it with a two-byte header and an [`Adler32`] checksum.
+///
+/// Both directions work over caller-provided buffers without allocating.
+///
+/// Decoding supports stored, fixed, and dynamic Huffman blocks.
+/// The `_iter` variants read compressed data split across several regions,
+/// like the `IDAT` chunks of a PNG image.
+///
+/// Current limitations:
+/// - The encoder uses greedy matching and the fixed Huffman codes,
+///   falling back to stored blocks for incompressible data.
+/// - Decoding needs the whole output to fit in the destination buffer.
+///
+/// [RFC 1950]: https://www.rfc-editor.org/rfc/rfc1950
+/// [RFC 1951]: https://www.rfc-editor.org/rfc/rfc1951
+///
+/// # Example
+/// ```
+/// # use devela::Deflate;
+/// let data = b"a cat, a hat, a bat, a cat, a hat, a bat";
+/// let mut packed = [0; 64];
+/// let len = Deflate::encode_zlib(data, &mut packed)?;
+/// assert![len < data.len()];
+///
+/// let mut unpacked = [0; 64];
+/// let n = Deflate::decode_zlib(&packed[..len], &mut unpacked)?;
+/// assert_eq![&unpacked[..n], data];
+/// # Ok::<(), devela::DeflateError>(())
+/// ```
+#[derive(Debug)]
+pub struct Deflate;
+
+impl Deflate {
+    /// The zlib header written by the encoder: 32 KiB window, fastest level.
+    pub const ZLIB_HEADER: [u8; 2] = [0x78, 0x01];
+
+    /* queries */
+
+    /// Returns the largest raw DEFLATE length [`encode`][Self::encode]
+    /// can produce for `len` input bytes.
+    #[must_use]
+    pub const fn encoded_len_max(len: usize) -> usize {
+        let blocks = is![len == 0, 1, len.div_ceil(BLOCK_LEN)];
+        len + 5 * blocks
+    }
+    /// Returns the largest zlib length [`encode_zlib`][Self::encode_zlib]
+    /// can produce for `len` input bytes.
+    #[must_use]
+    pub const fn encoded_len_max_zlib(len: usize) -> usize {
+        Self::encoded_len_max(len) + 6
+    }
+
+    /* decode */
+
+    /// Decompresses a raw DEFLATE stream into `out`.
+    ///
+    /// Returns the number of bytes written. Bytes after the final block are ignored.
+    pub fn decode(bytes: &[u8], out: &mut [u8]) -> Result<usize, DeflateError> {
+        Self::decode_iter(bytes.iter().copied(), out)
+    }
+    /// Decompresses a raw DEFLATE stream, read byte by byte, into `out`.
+    ///
+    /// Returns the number of bytes written.
+    pub fn decode_iter<I>(bytes: I, out: &mut [u8]) -> Result<usize, DeflateError>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        inflate(&mut BitReader::new(bytes.into_iter()), out)
+    }
+    /// Decompresses a zlib stream into `out`, verifying its checksum.
+    ///
+    /// Returns the number of bytes written.
+    pub fn decode_zlib(bytes: &[u8], out: &mut [u8]) -> Result<usize, DeflateError> {
+        Self::decode_zlib_iter(bytes.iter().copied(), out)
+    }
+    /// Decompresses a zlib stream, read byte by byte, into `out`, verifying its checksum.
+    ///
+    /// Returns the number of bytes written.
+    pub fn decode_zlib_iter<I>(bytes: I, out: &mut [u8]) -> Result<usize, DeflateError>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let mut r = BitReader::new(bytes.into_iter());
+        let (cmf, flg) = (r.byte()?, r.byte()?);
+        let check = u16::from_be_bytes([cmf, flg]) % 31;
+        is![
+            cmf & 0x0F != 8 || cmf >> 4 > 7 || flg & 0x20 != 0 || check != 0,
+            return Err(DeflateError::InvalidHeader)
+        ];
+        let len = inflate(&mut r, out)?;
+        r.align();
+        let sum = u32::from_be_bytes([r.byte()?, r.byte()?, r.byte()?, r.byte()?]);
+        is![
+            sum != Adler32::checksum_bytes(&out[..len]),
+            return Err(DeflateError::ChecksumMismatch)
+        ];
+        Ok(len)
+    }
+
+    /* encode */
+
+    /// Compresses `bytes` as a raw DEFLATE stream into `out`.
+    ///
+    /// Returns the number of bytes written. A buffer of
+    /// [`encoded_len_max`][Self::encoded_len_max] bytes is always enough.
+    pub fn encode(bytes: &[u8], out: &mut [u8]) -> Result<usize, DeflateError> {
+        let mut w = BitWriter::new(out);
+        deflate(bytes, &mut w);
+        is![w.overflowed(), Err(DeflateError::NotEnoughSpace), Ok(w.pos())]
+    }
+    /// Compresses `bytes` as a zlib stream into `out`.
+    ///
+    /// Returns the number of bytes written. A buffer of
+    /// [`encoded_len_max_zlib`][Self::encoded_len_max_zlib] bytes is always enough.
+    pub fn encode_zlib(bytes: &[u8], out: &mut [u8]) -> Result<usize, DeflateError> {
+        let mut w = BitWriter::new(out);
+        for byte in Self::ZLIB_HEADER {
+            w.byte(byte);
+        }
+        deflate(bytes, &mut w);
+        for byte in Adler32::checksum_bytes(bytes).to_be_bytes() {
+            w.byte(byte);
+        }
+        is![w.overflowed(), Err(DeflateError::NotEnoughSpace), Ok(w.pos())]
+    }
+}
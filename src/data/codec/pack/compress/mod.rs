@@ -3,6 +3,7 @@
 //! Size-reducing codecs.
 //
 
+mod deflate; // Deflate, DeflateError
 mod mode; // CompressionMode
 
 // mod rle; // WIP Run-length encoding and similar techniques
@@ -13,6 +14,7 @@ mod mode; // CompressionMode
 crate::structural_mods! { // _mods
     _mods {
         pub use super::{
+            deflate::_all::*,
             mode::*,
             // rle::*,
         };
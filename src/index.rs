@@ -165,7 +165,7 @@
         // positive safety
         "safe", "safe_build", "safe_code", "safe_data",
         "safe_geom", "safe_lang", "safe_media", "safe_audio",
-        "safe_color", "safe_draw", "safe_font", "safe_image",
+        "safe_color", "safe_doc", "safe_draw", "safe_font", "safe_image", "safe_motion",
         "safe_num", "safe_org", "safe_phys", "safe_time",
         "safe_run", "safe_sys", "safe_io", "safe_mem",
         "safe_text", "safe_ui", "safe_vita", "safe_work",
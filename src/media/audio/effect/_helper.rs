@@ -0,0 +1,48 @@
+// devela/src/media/audio/effect/_helper.rs
+//
+//! Effect math shared by the audio processors.
+//
+// The logarithm and exponential reduce their argument by powers of two,
+// so that their series converge in a few terms for any audio level.
+
+use crate::{Float, is};
+use ::core::f32::consts::{LN_2, LOG2_E, SQRT_2};
+
+/// Returns the natural logarithm of `x`, or negative infinity if it's not positive.
+pub(super) const fn ln(x: f32) -> f32 {
+    is![x <= 0.0 || x.is_nan(), return f32::NEG_INFINITY];
+    let bits = x.to_bits();
+    let mut e = ((bits >> 23) & 0xFF) as i32 - 127;
+    let mut m = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    if m > SQRT_2 {
+        m *= 0.5;
+        e += 1;
+    }
+    e as f32 * LN_2 + Float(m).ln_series(5).0
+}
+
+/// Returns the exponential of `x`.
+pub(super) const fn exp(x: f32) -> f32 {
+    is![x < -87.0, return 0.0];
+    is![x > 88.0, return f32::INFINITY];
+    let k = Float(x * LOG2_E).const_round().0;
+    let scale = f32::from_bits(((k as i32 + 127) as u32) << 23);
+    Float((x * LOG2_E - k) * LN_2).exp_series(8).0 * scale
+}
+
+/// Returns the sine and cosine of `radians`.
+pub(super) const fn sin_cos(radians: f32) -> (f32, f32) {
+    let (s, c) = Float(radians).sin_cos_minimax();
+    (s.0, c.0)
+}
+
+/// Returns the square root of `x`.
+pub(super) const fn sqrt(x: f32) -> f32 {
+    Float(x).sqrt_nr().0
+}
+
+/// Returns the coefficient of a one-pole smoother reaching 1/e of its target after `secs`.
+pub(super) const fn smoothing(secs: f32, sample_rate: u32) -> f32 {
+    let samples = secs * sample_rate as f32;
+    is![samples < 1.0, 0.0, exp(-1.0 / samples)]
+}
@@ -0,0 +1,273 @@
+// devela/src/media/audio/effect/_test.rs
+
+#![allow(clippy::float_cmp, reason = "the expected samples are exact")]
+
+use crate::{AudioBiquad, AudioChannels as Ch, AudioCompressor, AudioDelay, AudioEffect};
+use crate::{AudioEffectError, AudioGain, AudioMixer, AudioRemix, AudioResampler, AudioReverb};
+use crate::{AudioVoice, PcmBuf, PcmSample, PcmSource};
+use ::core::f32::consts::{FRAC_1_SQRT_2, TAU};
+
+fn pcm(data: &mut [f32], channels: Ch, rate: u32) -> PcmBuf<f32, &mut [f32]> {
+    PcmBuf::from_parts(data, PcmSample::F32, channels, rate)
+}
+fn pcm_ref(data: &[f32], channels: Ch, rate: u32) -> PcmBuf<f32, &[f32]> {
+    PcmBuf::from_parts(data, PcmSample::F32, channels, rate)
+}
+fn close(a: f32, b: f32, tolerance: f32) -> bool {
+    (a - b).abs() <= tolerance
+}
+/// Returns the peak level of a sine of `freq` Hz after filtering it, once settled.
+fn filtered_peak(mut filter: AudioBiquad, freq: f32) -> f32 {
+    let mut samples = [0.0; 4_800];
+    for (i, s) in samples.iter_mut().enumerate() {
+        *s = sin(TAU * freq * i as f32 / 48_000.0);
+    }
+    filter.process(&mut pcm(&mut samples, Ch::Mono, 48_000));
+    samples[2_400..].iter().fold(0.0, |peak, s: &f32| peak.max(s.abs()))
+}
+fn sin(x: f32) -> f32 {
+    crate::Float(x).sin_minimax().0
+}
+
+#[test]
+fn gain_pans_by_role() {
+    let center = AudioGain::new(2.0, 0.0).channel_gains(Ch::Surround5_1);
+    assert_eq![center, [2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 0.0, 0.0]];
+    let left = AudioGain::new(1.0, -1.0).channel_gains(Ch::Surround5_1);
+    // L, C, R, Ls, Rs, LFE
+    assert![close(left[0], 2.0_f32.sqrt(), 1e-6) && close(left[3], 2.0_f32.sqrt(), 1e-6)];
+    assert![close(left[2], 0.0, 1e-6) && close(left[4], 0.0, 1e-6)];
+    assert_eq![(left[1], left[5]), (1.0, 1.0)];
+    // mono is never panned
+    assert_eq![AudioGain::new(0.5, 1.0).channel_gains(Ch::Mono)[0], 0.5];
+    // the pan keeps the power
+    let (l, r) = AudioGain::new(1.0, 0.3).side_gains();
+    assert![close(l * l + r * r, 2.0, 1e-5)];
+
+    assert![close(AudioGain::db_to_linear(-6.0), 0.501_187, 1e-5)];
+    assert![close(AudioGain::db_to_linear(20.0), 10.0, 1e-4)];
+    assert![close(AudioGain::linear_to_db(0.1), -20.0, 1e-4)];
+    assert![close(AudioGain::from_db(-60.0).db(), -60.0, 1e-3)];
+    assert_eq![AudioGain::linear_to_db(0.0), f32::NEG_INFINITY];
+
+    let mut samples = [1.0, 1.0, 1.0, 1.0, 1.0];
+    AudioGain::new(0.5, 0.0).process(&mut pcm(&mut samples, Ch::Stereo, 8_000));
+    // the incomplete frame is left unchanged
+    assert_eq![samples, [0.5, 0.5, 0.5, 0.5, 1.0]];
+}
+
+#[test]
+fn biquads_shape_the_response() {
+    let low = AudioBiquad::low_pass(48_000, 1_000.0, FRAC_1_SQRT_2);
+    assert![close(filtered_peak(low, 100.0), 1.0, 0.01)];
+    assert![filtered_peak(low, 10_000.0) < 0.02];
+    let high = AudioBiquad::high_pass(48_000, 1_000.0, FRAC_1_SQRT_2);
+    assert![filtered_peak(high, 100.0) < 0.02];
+    assert![close(filtered_peak(high, 10_000.0), 1.0, 0.01)];
+    let band = AudioBiquad::band_pass(48_000, 1_000.0, 2.0);
+    assert![close(filtered_peak(band, 1_000.0), 1.0, 0.01)];
+    assert![filtered_peak(band, 10_000.0) < 0.1];
+    let notch = AudioBiquad::notch(48_000, 1_000.0, 2.0);
+    assert![filtered_peak(notch, 1_000.0) < 0.01];
+    let all = AudioBiquad::all_pass(48_000, 1_000.0, FRAC_1_SQRT_2);
+    assert![close(filtered_peak(all, 1_000.0), 1.0, 0.01)];
+    // +6 dB
+    let peak = AudioBiquad::peaking(48_000, 1_000.0, 1.0, 6.0);
+    assert![close(filtered_peak(peak, 1_000.0), 1.995, 0.01)];
+    assert![close(filtered_peak(peak, 20.0), 1.0, 0.01)];
+    let shelf = AudioBiquad::low_shelf(48_000, 1_000.0, FRAC_1_SQRT_2, -6.0);
+    assert![close(filtered_peak(shelf, 50.0), 0.501, 0.01)];
+    assert![close(filtered_peak(shelf, 15_000.0), 1.0, 0.01)];
+    let shelf = AudioBiquad::high_shelf(48_000, 1_000.0, FRAC_1_SQRT_2, -6.0);
+    assert![close(filtered_peak(shelf, 50.0), 1.0, 0.01)];
+    assert![close(filtered_peak(shelf, 15_000.0), 0.501, 0.01)];
+    assert_eq![AudioBiquad::low_pass(0, 1_000.0, 1.0), AudioBiquad::IDENTITY];
+}
+
+#[test]
+fn biquads_keep_state_per_channel() {
+    let mut lp = AudioBiquad::low_pass(8_000, 100.0, FRAC_1_SQRT_2);
+    let mut samples = [1.0, 0.0, 1.0, 0.0, 1.0, 0.0];
+    lp.process(&mut pcm(&mut samples, Ch::Stereo, 8_000));
+    assert![samples[0] > 0.0 && samples[2] > samples[0]];
+    assert_eq![(samples[1], samples[3], samples[5]), (0.0, 0.0, 0.0)];
+    lp.reset();
+    assert_eq![lp.process_sample(0, 0.0), 0.0];
+}
+
+#[test]
+fn delays_and_reverbs_by_layout() {
+    let mut line = [0.0; 4];
+    let mut delay = AudioDelay::new(&mut line, Ch::Stereo, 0.0, 0.5);
+    assert_eq![delay.frames(), 2];
+    let mut samples = [1.0, -1.0, 0.0, 0.0, 0.0, 0.0];
+    delay.process(&mut pcm(&mut samples, Ch::Stereo, 8_000));
+    assert_eq![samples, [1.0, -1.0, 0.0, 0.0, 0.5, -0.5]];
+    // other layouts are left unchanged
+    let mut mono = [1.0, 1.0];
+    delay.process(&mut pcm(&mut mono, Ch::Mono, 8_000));
+    assert_eq![mono, [1.0, 1.0]];
+    assert_eq![AudioDelay::line_len(48_000, Ch::Stereo, 0.25), 24_000];
+
+    let len = AudioReverb::line_len(8_000, Ch::Stereo);
+    let mut line = [0.0; 8_192];
+    assert_eq![
+        AudioReverb::new(&mut line[..len - 1], 8_000, Ch::Stereo).err(),
+        Some(AudioEffectError::NotEnoughSpace)
+    ];
+    assert_eq![
+        AudioReverb::new(&mut line, 0, Ch::Stereo).err(),
+        Some(AudioEffectError::InvalidSpec)
+    ];
+    let mut reverb = AudioReverb::new(&mut line[..len], 8_000, Ch::Stereo).unwrap();
+    let mut samples = [0.0; 4_000];
+    (samples[0], samples[1]) = (1.0, 1.0);
+    reverb.process(&mut pcm(&mut samples, Ch::Stereo, 8_000));
+    // both channels ring, differently, and decay
+    let (left, right) = (samples[1_000..].iter().step_by(2), samples[1_001..].iter().step_by(2));
+    assert![left.clone().any(|s| s.abs() > 1e-4) && right.clone().any(|s| s.abs() > 1e-4)];
+    assert![left.zip(right).any(|(l, r)| l != r)];
+    assert![samples.iter().all(|s| s.abs() < 1.0)];
+    reverb.reset();
+    let mut silence = [0.0; 64];
+    reverb.process(&mut pcm(&mut silence, Ch::Stereo, 8_000));
+    assert_eq![silence, [0.0; 64]];
+}
+
+#[test]
+fn compressors_reduce_loud_frames() {
+    let mut comp = AudioCompressor::new(8_000, -6.0, 2.0, 0.0, 0.0).with_makeup_db(3.0);
+    let mut samples = [0.25, 0.125, -1.0, 0.5];
+    comp.process(&mut pcm(&mut samples, Ch::Stereo, 8_000));
+    // the quiet frame only gets the makeup, and both channels of the loud one are reduced
+    let (makeup, reduced) = (AudioGain::db_to_linear(3.0), AudioGain::db_to_linear(-3.0));
+    assert![close(samples[0], 0.25 * makeup, 1e-4) && close(samples[1], 0.125 * makeup, 1e-4)];
+    assert![close(samples[2], -reduced * makeup, 1e-4)];
+    assert![close(samples[3], 0.5 * reduced * makeup, 1e-4)];
+    assert![close(comp.reduction_db(), -3.0, 1e-3)];
+    // a slow attack lets the peaks through
+    let mut slow = AudioCompressor::new(8_000, -6.0, 2.0, 0.01, 1.0);
+    let mut samples = [1.0; 400];
+    slow.process(&mut pcm(&mut samples, Ch::Mono, 8_000));
+    assert![samples[0] == 1.0 && close(samples[399], reduced, 1e-2)];
+}
+
+#[test]
+fn remixes_by_role() {
+    let down = AudioRemix::new(Ch::Surround5_1, Ch::Stereo);
+    let mut out = [0.0; 2];
+    // L, C, R, Ls, Rs, LFE
+    down.remix_frame(&[1.0, 1.0, 0.0, 1.0, 0.0, 1.0], &mut out);
+    assert![close(out[0], 1.0 + 2.0 * FRAC_1_SQRT_2, 1e-6)];
+    assert![close(out[1], FRAC_1_SQRT_2, 1e-6)];
+
+    let down = AudioRemix::new(Ch::Surround7_1, Ch::Surround5_1);
+    let mut out = [0.0; 6];
+    // L, C, R, Ls, Rs, Lb, Rb, LFE
+    down.remix_frame(&[0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.5], &mut out);
+    assert![close(out[3], FRAC_1_SQRT_2, 1e-6) && close(out[4], FRAC_1_SQRT_2, 1e-6)];
+    assert_eq![out[5], 0.5];
+
+    let up = AudioRemix::new(Ch::Mono, Ch::Surround5_1);
+    up.remix_frame(&[0.5], &mut out);
+    assert_eq![out, [0.0, 0.5, 0.0, 0.0, 0.0, 0.0]];
+    let up = AudioRemix::new(Ch::Mono, Ch::Stereo2_1);
+    up.remix_frame(&[0.5], &mut out[..3]);
+    assert_eq![out[..3], [0.5, 0.5, 0.0]];
+    let up = AudioRemix::new(Ch::Stereo, Ch::Surround5_1);
+    up.remix_frame(&[0.25, 0.75], &mut out);
+    assert_eq![out, [0.25, 0.0, 0.75, 0.0, 0.0, 0.0]];
+
+    let src = pcm_ref(&[1.0, 2.0, 3.0], Ch::Stereo, 8_000);
+    let mut out = [0.0; 4];
+    let remix = AudioRemix::new(Ch::Stereo, Ch::Mono);
+    let err = remix.process(src, &mut pcm(&mut out, Ch::Mono, 8_000));
+    assert_eq![err, Err(AudioEffectError::IncompleteFrames)];
+    let src = pcm_ref(&[1.0; 4], Ch::Stereo, 8_000);
+    let err = remix.process(src, &mut pcm(&mut out, Ch::Stereo, 8_000));
+    assert_eq![err, Err(AudioEffectError::MismatchedChannels)];
+    let err = remix.process(src, &mut pcm(&mut out, Ch::Mono, 16_000));
+    assert_eq![err, Err(AudioEffectError::MismatchedRate)];
+    let err = remix.process(src, &mut pcm(&mut out[..1], Ch::Mono, 8_000));
+    assert_eq![err, Err(AudioEffectError::NotEnoughSpace)];
+}
+
+#[test]
+fn resamples_linearly_and_by_sinc() {
+    assert_eq![AudioResampler::output_frames(441, 44_100, 48_000), 480];
+    assert_eq![AudioResampler::output_frames(3, 48_000, 8_000), 1];
+    assert_eq![AudioResampler::output_frames(3, 0, 8_000), 0];
+
+    // downsampling by half, with and without interpolation
+    let src = pcm_ref(&[0.0, 10.0, 0.0, 10.0, 0.0, 20.0], Ch::Stereo, 4);
+    let mut out = [0.0; 4];
+    let frames = AudioResampler::LINEAR.process(src, &mut pcm(&mut out, Ch::Stereo, 2));
+    assert_eq![(frames, out), (Ok(2), [0.0, 10.0, 0.0, 20.0])];
+
+    // a constant level is kept by the normalized kernel
+    let src = [0.5; 300];
+    let mut out = [0.0; 400];
+    let frames = AudioResampler::SINC
+        .process(pcm_ref(&src, Ch::Mono, 30_000), &mut pcm(&mut out, Ch::Mono, 40_000));
+    assert_eq![frames, Ok(400)];
+    assert![out.iter().all(|s| close(*s, 0.5, 1e-4))];
+
+    // a low sine keeps its shape, while a tone above the new Nyquist is filtered out
+    let sine = |freq: f32, rate: f32, i: usize| sin(TAU * freq * i as f32 / rate);
+    let mut src = [0.0; 960];
+    src.iter_mut().enumerate().for_each(|(i, s)| *s = sine(440.0, 48_000.0, i));
+    let mut out = [0.0; 882];
+    let src_pcm = pcm_ref(&src, Ch::Mono, 48_000);
+    let frames = AudioResampler::SINC.process(src_pcm, &mut pcm(&mut out, Ch::Mono, 44_100));
+    assert_eq![frames, Ok(882)];
+    assert![(100..780).all(|i| close(out[i], sine(440.0, 44_100.0, i), 2e-3))];
+    src.iter_mut().enumerate().for_each(|(i, s)| *s = sine(20_000.0, 48_000.0, i));
+    let mut out = [0.0; 320];
+    let src_pcm = pcm_ref(&src, Ch::Mono, 48_000);
+    let frames = AudioResampler::SINC.process(src_pcm, &mut pcm(&mut out, Ch::Mono, 16_000));
+    assert_eq![frames, Ok(320)];
+    assert![out[40..280].iter().all(|s| s.abs() < 0.05)];
+
+    let src = pcm_ref(&[0.0; 4], Ch::Mono, 8_000);
+    let err = AudioResampler::SINC.process(src, &mut pcm(&mut out[..7], Ch::Mono, 16_000));
+    assert_eq![err, Err(AudioEffectError::NotEnoughSpace)];
+    let err = AudioResampler::SINC.process(src, &mut pcm(&mut out, Ch::Stereo, 16_000));
+    assert_eq![err, Err(AudioEffectError::MismatchedChannels)];
+    let err = AudioResampler::SINC.process(src, &mut pcm(&mut out, Ch::Mono, 0));
+    assert_eq![err, Err(AudioEffectError::InvalidSpec)];
+}
+
+#[test]
+fn mixes_voices() {
+    let left = [1.0; 4];
+    let stereo = [0.25, 0.5, 0.25, 0.5];
+    let mut mixer = AudioMixer::<2>::new(Ch::Stereo, 8_000);
+    mixer.gain = 0.5;
+    let hard_left = AudioGain::new(1.0, -1.0);
+    let looped = AudioVoice::new(pcm_ref(&left[..1], Ch::Mono, 8_000)).with_looping(true);
+    assert_eq![mixer.play(looped.with_gain(hard_left)), Ok(0)];
+    assert_eq![mixer.play(AudioVoice::new(pcm_ref(&stereo, Ch::Stereo, 8_000))), Ok(1)];
+    let voice = AudioVoice::new(pcm_ref(&left, Ch::Mono, 8_000));
+    assert_eq![mixer.play(voice), Err(AudioEffectError::NoFreeVoice)];
+    let voice = AudioVoice::new(pcm_ref(&left, Ch::Mono, 16_000));
+    assert_eq![mixer.play(voice), Err(AudioEffectError::MismatchedRate)];
+
+    let mut out = [0.0; 8];
+    let spec = mixer.spec();
+    assert_eq![mixer.read(PcmBuf::new(&mut out[..], spec)), Ok(4)];
+    let side = 0.5 * 2.0_f32.sqrt();
+    assert![close(out[0], side + 0.125, 1e-6) && close(out[1], 0.25, 1e-6)];
+    assert![close(out[4], side, 1e-6) && close(out[5], 0.0, 1e-6)];
+    // the finished voice frees its slot
+    assert_eq![mixer.active_count(), 1];
+    assert!(mixer.voice(1).is_none());
+    assert_eq![mixer.voice(0).map(AudioVoice::position), Some(1)];
+    assert![mixer.stop(0).is_some_and(|v| v.looping)];
+    assert_eq![mixer.read(PcmBuf::new(&mut out[..], spec)), Ok(4)];
+    assert_eq![out, [0.0; 8]];
+
+    let err = mixer.read(pcm(&mut out, Ch::Mono, 8_000));
+    assert_eq![err, Err(AudioEffectError::MismatchedChannels)];
+    let err = mixer.read(pcm(&mut out[..3], Ch::Stereo, 8_000));
+    assert_eq![err, Err(AudioEffectError::IncompleteFrames)];
+}
@@ -0,0 +1,193 @@
+// devela/src/media/audio/effect/biquad.rs
+//
+//! Defines [`AudioBiquad`].
+//
+// https://www.w3.org/TR/audio-eq-cookbook/
+
+use super::_helper::{exp, sin_cos, sqrt};
+use crate::{_impl_init, AudioChannels, AudioEffect, Float, PcmBuf, is};
+use ::core::f32::consts::{LN_10, TAU};
+
+#[doc = crate::_tags!(audio)]
+/// A second-order IIR filter, with independent state for each channel.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(AudioBiquad = 84|672)}]
+///
+/// The constructors design the filters of the *Audio EQ Cookbook*, for a cutoff
+/// or center frequency in Hertz and a quality factor `q`, where `0.707` gives
+/// a maximally flat response. The frequency is kept below the Nyquist frequency.
+///
+/// Filters for a zero sample rate pass the signal unchanged.
+///
+/// # Example
+/// ```
+/// # use devela::AudioBiquad;
+/// let mut lp = AudioBiquad::low_pass(48_000, 100.0, 0.707);
+/// // a fast alternating signal is removed, while its average goes through
+/// let mut last = 0.0;
+/// for i in 0..4_800 {
+///     last = lp.process_sample(0, if i % 2 == 0 { 1.5 } else { -0.5 });
+/// }
+/// assert![(last - 0.5).abs() < 1e-3];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioBiquad {
+    /// The normalized coefficients `[b0, b1, b2, a1, a2]`.
+    coeffs: [f32; 5],
+    /// The two state variables of each channel.
+    state: [[f32; 2]; AudioChannels::MAX_COUNT],
+}
+_impl_init![Self::IDENTITY => AudioBiquad];
+
+impl Default for AudioBiquad {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl AudioBiquad {
+    /// A filter that passes the signal unchanged.
+    pub const IDENTITY: Self = Self::from_coefficients([1.0, 0.0, 0.0, 0.0, 0.0]);
+
+    /// Returns a filter from its normalized coefficients `[b0, b1, b2, a1, a2]`.
+    #[must_use]
+    pub const fn from_coefficients(coeffs: [f32; 5]) -> Self {
+        Self {
+            coeffs,
+            state: [[0.0; 2]; AudioChannels::MAX_COUNT],
+        }
+    }
+    /// Returns the normalized coefficients `[b0, b1, b2, a1, a2]`.
+    #[must_use]
+    pub const fn coefficients(&self) -> [f32; 5] {
+        self.coeffs
+    }
+
+    /// Returns a low-pass filter.
+    #[must_use]
+    pub const fn low_pass(sample_rate: u32, freq: f32, q: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        let b = (1.0 - cos) / 2.0;
+        Self::normalized([b, 2.0 * b, b], [1.0 + alpha, -2.0 * cos, 1.0 - alpha])
+    }
+    /// Returns a high-pass filter.
+    #[must_use]
+    pub const fn high_pass(sample_rate: u32, freq: f32, q: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        let b = (1.0 + cos) / 2.0;
+        Self::normalized([b, -2.0 * b, b], [1.0 + alpha, -2.0 * cos, 1.0 - alpha])
+    }
+    /// Returns a band-pass filter, with a peak gain of 0 dB.
+    #[must_use]
+    pub const fn band_pass(sample_rate: u32, freq: f32, q: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        Self::normalized([alpha, 0.0, -alpha], [1.0 + alpha, -2.0 * cos, 1.0 - alpha])
+    }
+    /// Returns a notch filter.
+    #[must_use]
+    pub const fn notch(sample_rate: u32, freq: f32, q: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        Self::normalized([1.0, -2.0 * cos, 1.0], [1.0 + alpha, -2.0 * cos, 1.0 - alpha])
+    }
+    /// Returns an all-pass filter, which only shifts the phase.
+    #[must_use]
+    pub const fn all_pass(sample_rate: u32, freq: f32, q: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        let (b0, b1, b2) = (1.0 - alpha, -2.0 * cos, 1.0 + alpha);
+        Self::normalized([b0, b1, b2], [b2, b1, b0])
+    }
+    /// Returns a peaking equalizer that boosts or cuts `gain_db` decibels around `freq`.
+    #[must_use]
+    pub const fn peaking(sample_rate: u32, freq: f32, q: f32, gain_db: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        let a = Self::amplitude(gain_db);
+        Self::normalized(
+            [1.0 + alpha * a, -2.0 * cos, 1.0 - alpha * a],
+            [1.0 + alpha / a, -2.0 * cos, 1.0 - alpha / a],
+        )
+    }
+    /// Returns a low-shelf filter that boosts or cuts `gain_db` decibels below `freq`.
+    #[must_use]
+    pub const fn low_shelf(sample_rate: u32, freq: f32, q: f32, gain_db: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        let a = Self::amplitude(gain_db);
+        let (p, m, k) = (a + 1.0, a - 1.0, 2.0 * sqrt(a) * alpha);
+        Self::normalized(
+            [a * (p - m * cos + k), 2.0 * a * (m - p * cos), a * (p - m * cos - k)],
+            [p + m * cos + k, -2.0 * (m + p * cos), p + m * cos - k],
+        )
+    }
+    /// Returns a high-shelf filter that boosts or cuts `gain_db` decibels above `freq`.
+    #[must_use]
+    pub const fn high_shelf(sample_rate: u32, freq: f32, q: f32, gain_db: f32) -> Self {
+        let Some((cos, alpha)) = Self::angle(sample_rate, freq, q) else {
+            return Self::IDENTITY;
+        };
+        let a = Self::amplitude(gain_db);
+        let (p, m, k) = (a + 1.0, a - 1.0, 2.0 * sqrt(a) * alpha);
+        Self::normalized(
+            [a * (p + m * cos + k), -2.0 * a * (m + p * cos), a * (p + m * cos - k)],
+            [p - m * cos + k, 2.0 * (m - p * cos), p - m * cos - k],
+        )
+    }
+
+    /// Filters a single sample of the given `channel`.
+    ///
+    /// # Panics
+    /// Panics if `channel >= AudioChannels::MAX_COUNT`.
+    pub const fn process_sample(&mut self, channel: usize, x: f32) -> f32 {
+        let [b0, b1, b2, a1, a2] = self.coeffs;
+        let s = &mut self.state[channel];
+        let y = b0 * x + s[0];
+        s[0] = b1 * x - a1 * y + s[1];
+        s[1] = b2 * x - a2 * y;
+        y
+    }
+
+    /* helpers */
+
+    /// Returns the cosine of the angular frequency, and the bandwidth term alpha.
+    const fn angle(sample_rate: u32, freq: f32, q: f32) -> Option<(f32, f32)> {
+        is![sample_rate == 0, return None];
+        let rate = sample_rate as f32;
+        let freq = Float(freq).clamp(1e-3, rate * 0.499).0;
+        let (sin, cos) = sin_cos(TAU * freq / rate);
+        Some((cos, sin / (2.0 * Float(q).max(1e-3).0)))
+    }
+    /// Returns the amplitude of the shelving and peaking filters.
+    const fn amplitude(gain_db: f32) -> f32 {
+        exp(gain_db * (LN_10 / 40.0))
+    }
+    /// Returns a filter dividing its coefficients by `a0`.
+    const fn normalized(b: [f32; 3], a: [f32; 3]) -> Self {
+        let a0 = a[0];
+        Self::from_coefficients([b[0] / a0, b[1] / a0, b[2] / a0, a[1] / a0, a[2] / a0])
+    }
+}
+
+impl AudioEffect for AudioBiquad {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        let count = pcm.channel_count();
+        for frame in pcm.data_mut().chunks_exact_mut(count) {
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                *sample = self.process_sample(channel, *sample);
+            }
+        }
+    }
+    fn reset(&mut self) {
+        self.state = [[0.0; 2]; AudioChannels::MAX_COUNT];
+    }
+}
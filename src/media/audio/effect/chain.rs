@@ -0,0 +1,94 @@
+// devela/src/media/audio/effect/chain.rs
+//
+//! Defines [`AudioEffect`].
+//
+
+use crate::PcmBuf;
+
+#[doc = crate::_tags!(audio)]
+/// An audio processor that transforms interleaved `f32` frames in place.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Effects are configured for a sample rate, and process buffers of any
+/// channel layout, keeping separate state for each channel.
+/// Samples after the last complete frame are left unchanged.
+///
+/// Effects are chained by grouping them in tuples, which process them in order,
+/// and bypassed by wrapping them in an `Option` set to `None`.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioBiquad, AudioChannels, AudioEffect, AudioGain, PcmBuf, PcmSample};
+/// let mut chain = (AudioGain::new(0.5, 0.0), AudioBiquad::low_pass(48_000, 1_000.0, 0.707));
+/// let mut samples = [1.0_f32; 64];
+/// let mut pcm = PcmBuf::from_parts(&mut samples[..], PcmSample::F32, AudioChannels::Mono, 48_000);
+/// chain.process(&mut pcm);
+/// assert![samples[0] > 0.0 && samples[0] < 0.5];
+/// assert![(samples[63] - 0.5).abs() < 1e-3];
+/// ```
+pub trait AudioEffect {
+    /// Processes the complete interleaved frames of `pcm` in place.
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>);
+
+    /// Clears the internal state, like filter memories and delay lines.
+    fn reset(&mut self) {}
+}
+
+impl<E: AudioEffect + ?Sized> AudioEffect for &mut E {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        (**self).process(pcm);
+    }
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+impl<E: AudioEffect> AudioEffect for Option<E> {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        if let Some(effect) = self {
+            effect.process(pcm);
+        }
+    }
+    fn reset(&mut self) {
+        if let Some(effect) = self {
+            effect.reset();
+        }
+    }
+}
+impl<E: AudioEffect> AudioEffect for [E] {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        self.iter_mut().for_each(|effect| effect.process(pcm));
+    }
+    fn reset(&mut self) {
+        self.iter_mut().for_each(AudioEffect::reset);
+    }
+}
+impl<E: AudioEffect, const N: usize> AudioEffect for [E; N] {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        self.as_mut_slice().process(pcm);
+    }
+    fn reset(&mut self) {
+        self.as_mut_slice().reset();
+    }
+}
+
+/// Implements [`AudioEffect`] for tuples of effects, processed in order.
+macro_rules! impl_effect_chain {
+    ($($E:ident $i:tt),+) => {
+        impl<$($E: AudioEffect),+> AudioEffect for ($($E,)+) {
+            fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+                $( self.$i.process(pcm); )+
+            }
+            fn reset(&mut self) {
+                $( self.$i.reset(); )+
+            }
+        }
+    };
+}
+impl_effect_chain![A 0];
+impl_effect_chain![A 0, B 1];
+impl_effect_chain![A 0, B 1, C 2];
+impl_effect_chain![A 0, B 1, C 2, D 3];
+impl_effect_chain![A 0, B 1, C 2, D 3, E 4];
+impl_effect_chain![A 0, B 1, C 2, D 3, E 4, F 5];
+impl_effect_chain![A 0, B 1, C 2, D 3, E 4, F 5, G 6];
+impl_effect_chain![A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7];
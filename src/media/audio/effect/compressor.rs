@@ -0,0 +1,108 @@
+// devela/src/media/audio/effect/compressor.rs
+//
+//! Defines [`AudioCompressor`].
+//
+
+use super::_helper::{exp, ln, smoothing};
+use crate::{AudioEffect, Float, PcmBuf, is};
+use ::core::f32::consts::LN_10;
+
+#[doc = crate::_tags!(audio)]
+/// A dynamic range compressor with linked channels.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(AudioCompressor = 24|192)}]
+///
+/// An envelope follows the peak level of each frame, rising with the attack
+/// time and falling with the release time. Whenever it exceeds the threshold,
+/// the excess is divided by the ratio, and every channel gets the same gain.
+/// The makeup gain is applied afterwards.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, AudioCompressor, AudioEffect, PcmBuf, PcmSample};
+/// // a 4:1 ratio over -20 dB, with an instant attack
+/// let mut comp = AudioCompressor::new(48_000, -20.0, 4.0, 0.0, 0.1);
+/// let mut samples = [1.0; 16];
+/// let mut pcm = PcmBuf::from_parts(&mut samples[..], PcmSample::F32, AudioChannels::Mono, 48_000);
+/// comp.process(&mut pcm);
+/// // 0 dB goes down to -15 dB
+/// assert![(samples[15] - 0.177_8).abs() < 1e-3];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioCompressor {
+    threshold_db: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup: f32,
+    envelope: f32,
+}
+
+impl AudioCompressor {
+    /// Returns a compressor for the given sample rate.
+    ///
+    /// The threshold is in decibels, the attack and release times in seconds,
+    /// and the ratio is kept at least at 1.
+    #[must_use]
+    pub const fn new(
+        sample_rate: u32,
+        threshold_db: f32,
+        ratio: f32,
+        attack: f32,
+        release: f32,
+    ) -> Self {
+        Self {
+            threshold_db,
+            ratio: Float(ratio).max(1.0).0,
+            attack: smoothing(attack, sample_rate),
+            release: smoothing(release, sample_rate),
+            makeup: 1.0,
+            envelope: 0.0,
+        }
+    }
+    /// Sets the makeup gain, in decibels.
+    #[must_use]
+    pub const fn with_makeup_db(mut self, db: f32) -> Self {
+        self.makeup = exp(db * (LN_10 / 20.0));
+        self
+    }
+
+    /// Returns the threshold, in decibels.
+    #[must_use]
+    pub const fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+    /// Returns the compression ratio.
+    #[must_use]
+    pub const fn ratio(&self) -> f32 {
+        self.ratio
+    }
+    /// Returns the current gain reduction, in decibels.
+    #[must_use]
+    pub const fn reduction_db(&self) -> f32 {
+        Self::reduction(self.threshold_db, self.ratio, self.envelope)
+    }
+
+    /// Returns the gain reduction in decibels for an `envelope` level.
+    const fn reduction(threshold_db: f32, ratio: f32, envelope: f32) -> f32 {
+        is![envelope <= 0.0, return 0.0];
+        let over = ln(envelope) * (20.0 / LN_10) - threshold_db;
+        is![over > 0.0, over * (1.0 / ratio - 1.0), 0.0]
+    }
+}
+
+impl AudioEffect for AudioCompressor {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        let count = pcm.channel_count();
+        for frame in pcm.data_mut().chunks_exact_mut(count) {
+            let peak = frame.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+            let coeff = is![peak > self.envelope, self.attack, self.release];
+            self.envelope = peak + (self.envelope - peak) * coeff;
+            let reduction = Self::reduction(self.threshold_db, self.ratio, self.envelope);
+            let gain = exp(reduction * (LN_10 / 20.0)) * self.makeup;
+            frame.iter_mut().for_each(|sample| *sample *= gain);
+        }
+    }
+    fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+}
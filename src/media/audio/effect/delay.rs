@@ -0,0 +1,88 @@
+// devela/src/media/audio/effect/delay.rs
+//
+//! Defines [`AudioDelay`].
+//
+
+use crate::{AudioChannels, AudioEffect, Float, PcmBuf, is};
+
+#[doc = crate::_tags!(audio)]
+/// A feedback delay over a caller-provided delay line.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// The line stores interleaved frames, and its length in frames is the delay.
+/// Use [`line_len`][Self::line_len] to size it for a given time.
+///
+/// Each output is the dry input plus the delayed signal scaled by `mix`,
+/// while `feedback` scales the delayed signal fed back into the line.
+/// Buffers with a different channel layout are left unchanged.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, AudioDelay, AudioEffect, PcmBuf, PcmSample};
+/// let mut line = [0.0; 2];
+/// let mut delay = AudioDelay::new(&mut line, AudioChannels::Mono, 0.5, 1.0);
+/// let mut samples = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let mut pcm = PcmBuf::from_parts(&mut samples[..], PcmSample::F32, AudioChannels::Mono, 10);
+/// delay.process(&mut pcm);
+/// assert_eq![samples, [1.0, 0.0, 1.0, 0.0, 0.5, 0.0]];
+/// ```
+#[derive(Debug)]
+pub struct AudioDelay<'a> {
+    line: &'a mut [f32],
+    channels: AudioChannels,
+    /// The frame of the line to read and write next.
+    pos: usize,
+    /// The gain of the signal fed back into the line.
+    pub feedback: f32,
+    /// The gain of the delayed signal added to the output.
+    pub mix: f32,
+}
+
+impl<'a> AudioDelay<'a> {
+    /// Returns a delay over `line` for the given channel layout.
+    ///
+    /// The feedback is clamped below 1, to keep the delay stable.
+    pub fn new(line: &'a mut [f32], channels: AudioChannels, feedback: f32, mix: f32) -> Self {
+        line.fill(0.0);
+        let feedback = Float(feedback).clamp(-0.999, 0.999).0;
+        Self { line, channels, pos: 0, feedback, mix }
+    }
+
+    /// Returns the line length that delays `channels` by `secs` seconds.
+    #[must_use]
+    pub const fn line_len(sample_rate: u32, channels: AudioChannels, secs: f32) -> usize {
+        let frames = Float(secs * sample_rate as f32).const_round().0;
+        is![frames < 1.0, 1, frames as usize] * channels.channels() as usize
+    }
+
+    /// Returns the delay in frames.
+    #[must_use]
+    pub const fn frames(&self) -> usize {
+        self.line.len() / self.channels.channels() as usize
+    }
+    /// Returns the channel layout.
+    #[must_use]
+    pub const fn channels(&self) -> AudioChannels {
+        self.channels
+    }
+}
+
+impl AudioEffect for AudioDelay<'_> {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        let (count, frames) = (pcm.channel_count(), self.frames());
+        is![pcm.channels() != self.channels || frames == 0, return];
+        for frame in pcm.data_mut().chunks_exact_mut(count) {
+            let delayed = &mut self.line[self.pos * count..][..count];
+            for (sample, slot) in frame.iter_mut().zip(delayed) {
+                let (dry, wet) = (*sample, *slot);
+                *slot = dry + wet * self.feedback;
+                *sample = dry + wet * self.mix;
+            }
+            self.pos = (self.pos + 1) % frames;
+        }
+    }
+    fn reset(&mut self) {
+        self.line.fill(0.0);
+        self.pos = 0;
+    }
+}
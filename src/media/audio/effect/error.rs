@@ -0,0 +1,37 @@
+// devela/src/media/audio/effect/error.rs
+//
+//! Defines [`AudioEffectError`].
+//
+
+#[doc = crate::_tags!(audio error)]
+/// Audio processing error.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(AudioEffectError = 1|8)}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AudioEffectError {
+    /// The PCM stream specification is incomplete or invalid.
+    InvalidSpec,
+
+    /// The channel layout doesn't match the one of the processor.
+    MismatchedChannels,
+
+    /// The sample rate doesn't match the one of the processor.
+    MismatchedRate,
+
+    /// The sample length is not a multiple of the channel count.
+    IncompleteFrames,
+
+    /// The destination buffer is too small.
+    NotEnoughSpace,
+
+    /// Every voice of the mixer is already playing.
+    NoFreeVoice,
+}
+
+crate::impl_trait![fmt::Display+Error for AudioEffectError |self, f| match self {
+    Self::InvalidSpec => f.write_str("invalid PCM stream specification"),
+    Self::MismatchedChannels => f.write_str("PCM channel layout does not match the processor"),
+    Self::MismatchedRate => f.write_str("PCM sample rate does not match the processor"),
+    Self::IncompleteFrames => f.write_str("PCM sample length is not a multiple of its channels"),
+    Self::NotEnoughSpace => f.write_str("not enough space to write the processed PCM data"),
+    Self::NoFreeVoice => f.write_str("every voice of the mixer is already playing"),
+}];
@@ -0,0 +1,111 @@
+// devela/src/media/audio/effect/gain.rs
+//
+//! Defines [`AudioGain`].
+//
+
+use super::_helper::{exp, ln, sin_cos};
+use crate::{_impl_init, AudioChannel, AudioChannels, AudioEffect, Float, PcmBuf, is, whilst};
+use ::core::f32::consts::{FRAC_PI_4, LN_10, SQRT_2};
+
+#[doc = crate::_tags!(audio)]
+/// Gain and stereo panning.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(AudioGain = 8|64)}]
+///
+/// The pan moves the sound between the left and right channel roles
+/// (`L`, `Ls`, `Lb` and `R`, `Rs`, `Rb`), while the rest of the channels,
+/// and mono buffers, only get the gain.
+///
+/// Panning follows a constant-power law normalized to unity at the center,
+/// so that a sound panned fully to one side gets +3 dB on that side.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, AudioGain};
+/// let gains = AudioGain::new(0.5, 1.0).channel_gains(AudioChannels::Stereo);
+/// assert![gains[0].abs() < 1e-4];
+/// assert![(gains[1] - 0.5 * 2.0_f32.sqrt()).abs() < 1e-4];
+/// assert_eq![AudioGain::from_db(0.0), AudioGain::UNITY];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioGain {
+    /// The linear gain.
+    pub gain: f32,
+    /// The pan from -1 (left) to 1 (right).
+    pub pan: f32,
+}
+_impl_init![Self::UNITY => AudioGain];
+
+impl Default for AudioGain {
+    fn default() -> Self {
+        Self::UNITY
+    }
+}
+
+impl AudioGain {
+    /// Unity gain, centered.
+    pub const UNITY: Self = Self::new(1.0, 0.0);
+
+    /// Returns a new gain and pan, clamping the pan between -1 and 1.
+    #[must_use]
+    pub const fn new(gain: f32, pan: f32) -> Self {
+        Self { gain, pan: Float(pan).clamp(-1.0, 1.0).0 }
+    }
+    /// Returns a centered gain of `db` decibels.
+    #[must_use]
+    pub const fn from_db(db: f32) -> Self {
+        Self::new(Self::db_to_linear(db), 0.0)
+    }
+    /// Returns the gain in decibels.
+    #[must_use]
+    pub const fn db(self) -> f32 {
+        Self::linear_to_db(self.gain)
+    }
+
+    /// Converts decibels into a linear amplitude gain.
+    #[must_use]
+    pub const fn db_to_linear(db: f32) -> f32 {
+        exp(db * (LN_10 / 20.0))
+    }
+    /// Converts a linear amplitude gain into decibels.
+    ///
+    /// Returns negative infinity for silence.
+    #[must_use]
+    pub const fn linear_to_db(gain: f32) -> f32 {
+        ln(gain.abs()) * (20.0 / LN_10)
+    }
+
+    /// Returns the gains of the left and right sides.
+    #[must_use]
+    pub const fn side_gains(self) -> (f32, f32) {
+        is![self.pan == 0.0, return (self.gain, self.gain)];
+        let (sin, cos) = sin_cos((self.pan + 1.0) * FRAC_PI_4);
+        (self.gain * cos * SQRT_2, self.gain * sin * SQRT_2)
+    }
+    /// Returns the gain of each channel of the given layout, in order.
+    ///
+    /// The gains after the channel count are zero.
+    #[must_use]
+    pub const fn channel_gains(self, channels: AudioChannels) -> [f32; AudioChannels::MAX_COUNT] {
+        let mut gains = [0.0; AudioChannels::MAX_COUNT];
+        let roles = channels.channels_expanded();
+        let (left, right) = is![roles.len() == 1, (self.gain, self.gain), self.side_gains()];
+        whilst![i in 0..roles.len(); {
+            gains[i] = match roles[i] {
+                AudioChannel::L | AudioChannel::Ls | AudioChannel::Lb => left,
+                AudioChannel::R | AudioChannel::Rs | AudioChannel::Rb => right,
+                AudioChannel::C | AudioChannel::LFE => self.gain,
+            };
+        }];
+        gains
+    }
+}
+
+impl AudioEffect for AudioGain {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        let (channels, count) = (pcm.channels(), pcm.channel_count());
+        let gains = self.channel_gains(channels);
+        for frame in pcm.data_mut().chunks_exact_mut(count) {
+            frame.iter_mut().zip(gains).for_each(|(sample, gain)| *sample *= gain);
+        }
+    }
+}
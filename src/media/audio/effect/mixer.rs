@@ -0,0 +1,219 @@
+// devela/src/media/audio/effect/mixer.rs
+//
+//! Defines [`AudioMixer`], [`AudioVoice`].
+//
+
+use crate::{AudioChannels, AudioEffectError, AudioGain, AudioRemix, PcmBuf, PcmLayout};
+use crate::{PcmSample, PcmSource, PcmSpec, PcmStream, is};
+
+#[doc = crate::_tags!(audio)]
+/// A sound played by an [`AudioMixer`].
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// It borrows interleaved `f32` frames at the sample rate of the mixer,
+/// in any channel layout, which is remixed into the one of the mixer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioVoice<'a> {
+    pcm: PcmBuf<f32, &'a [f32]>,
+    /// The frame to play next.
+    pos: usize,
+    /// The gain and pan.
+    pub gain: AudioGain,
+    /// Whether to restart from the beginning after the last frame.
+    pub looping: bool,
+}
+
+impl<'a> AudioVoice<'a> {
+    /// Returns a voice playing `pcm` once, at unity gain.
+    pub const fn new(pcm: PcmBuf<f32, &'a [f32]>) -> Self {
+        Self {
+            pcm,
+            pos: 0,
+            gain: AudioGain::UNITY,
+            looping: false,
+        }
+    }
+    /// Sets the gain and pan.
+    #[must_use]
+    pub const fn with_gain(mut self, gain: AudioGain) -> Self {
+        self.gain = gain;
+        self
+    }
+    /// Sets whether to loop.
+    #[must_use]
+    pub const fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Returns the frames being played.
+    pub const fn pcm(&self) -> PcmBuf<f32, &'a [f32]> {
+        self.pcm
+    }
+    /// Returns the frame to play next.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+    /// Sets the frame to play next.
+    pub const fn set_position(&mut self, frame: usize) {
+        self.pos = frame;
+    }
+    /// Returns whether every frame has been played, which never happens when looping.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        let frames = self.frames();
+        !(self.looping && frames > 0) && self.pos >= frames
+    }
+
+    /// Returns the number of complete frames.
+    const fn frames(&self) -> usize {
+        self.pcm.samples_len() / self.pcm.channel_count()
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// Mixes up to `N` voices into a stream of interleaved `f32` frames.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Voices are remixed into the channel layout of the mixer, scaled by their
+/// gain and pan and by the master gain, and summed without clipping.
+/// Finished voices are removed, freeing their slot.
+///
+/// The mixed frames are read through [`PcmSource`], and can be further
+/// processed by an [`AudioEffect`][crate::AudioEffect] chain.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, AudioGain, AudioMixer, AudioVoice};
+/// # use devela::{PcmBuf, PcmSample, PcmSource};
+/// let beep = PcmBuf::from_parts(&[0.5; 3][..], PcmSample::F32, AudioChannels::Mono, 8_000);
+/// let mut mixer = AudioMixer::<4>::new(AudioChannels::Stereo, 8_000);
+/// mixer.play(AudioVoice::new(beep)).unwrap();
+/// mixer.play(AudioVoice::new(beep).with_gain(AudioGain::new(0.5, 0.0))).unwrap();
+///
+/// let mut out = [0.0; 8];
+/// let spec = mixer.spec();
+/// assert_eq![mixer.read(PcmBuf::new(&mut out[..], spec)), Ok(4)];
+/// assert_eq![out, [0.75, 0.75, 0.75, 0.75, 0.75, 0.75, 0.0, 0.0]];
+/// assert_eq![mixer.active_count(), 0];
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioMixer<'a, const N: usize> {
+    spec: PcmSpec,
+    voices: [Option<(AudioVoice<'a>, AudioRemix)>; N],
+    /// The master gain.
+    pub gain: f32,
+}
+
+impl<'a, const N: usize> AudioMixer<'a, N> {
+    /// Returns a silent mixer with the given channel layout and sample rate.
+    #[must_use]
+    pub const fn new(channels: AudioChannels, sample_rate: u32) -> Self {
+        let spec = PcmSpec::new(PcmSample::F32, channels, sample_rate);
+        Self { spec, voices: [None; N], gain: 1.0 }
+    }
+    /// Returns the specification of the mixed stream.
+    #[must_use]
+    pub const fn spec(&self) -> PcmSpec {
+        self.spec
+    }
+
+    /// Starts playing a voice, and returns its slot.
+    ///
+    /// # Errors
+    /// Returns [`AudioEffectError::MismatchedRate`] if its sample rate differs,
+    /// [`AudioEffectError::IncompleteFrames`] if it has incomplete frames,
+    /// or [`AudioEffectError::NoFreeVoice`] if every slot is playing.
+    pub fn play(&mut self, voice: AudioVoice<'a>) -> Result<usize, AudioEffectError> {
+        let pcm = voice.pcm;
+        if pcm.sample_rate() != self.spec.sample_rate {
+            return Err(AudioEffectError::MismatchedRate);
+        }
+        is![!pcm.has_complete_frames(), return Err(AudioEffectError::IncompleteFrames)];
+        let slot = self.voices.iter().position(Option::is_none);
+        let slot = slot.ok_or(AudioEffectError::NoFreeVoice)?;
+        self.voices[slot] = Some((voice, AudioRemix::new(pcm.channels(), self.spec.channels)));
+        Ok(slot)
+    }
+    /// Stops the voice at `slot`, and returns it.
+    pub fn stop(&mut self, slot: usize) -> Option<AudioVoice<'a>> {
+        self.voices.get_mut(slot)?.take().map(|(voice, _)| voice)
+    }
+    /// Stops every voice.
+    pub fn stop_all(&mut self) {
+        self.voices = [None; N];
+    }
+
+    /// Returns the voice playing at `slot`.
+    #[must_use]
+    pub fn voice(&self, slot: usize) -> Option<&AudioVoice<'a>> {
+        self.voices.get(slot)?.as_ref().map(|(voice, _)| voice)
+    }
+    /// Returns the voice playing at `slot`, exclusively.
+    #[must_use]
+    pub fn voice_mut(&mut self, slot: usize) -> Option<&mut AudioVoice<'a>> {
+        self.voices.get_mut(slot)?.as_mut().map(|(voice, _)| voice)
+    }
+    /// Returns the number of voices playing.
+    #[must_use]
+    pub fn active_count(&self) -> usize {
+        self.voices.iter().filter(|v| v.is_some()).count()
+    }
+
+    /// Mixes the playing voices into `out`, overwriting it.
+    fn mix(&mut self, out: &mut [f32]) {
+        let channels = self.spec.channels;
+        let count = self.spec.channel_count();
+        out.fill(0.0);
+        for slot in &mut self.voices {
+            let Some((voice, remix)) = slot else { continue };
+            let gains = voice.gain.channel_gains(channels).map(|g| g * self.gain);
+            let (frames, src_count) = (voice.frames(), voice.pcm.channel_count());
+            let mut remixed = [0.0; AudioChannels::MAX_COUNT];
+            for frame in out.chunks_exact_mut(count) {
+                if voice.pos >= frames {
+                    is![voice.looping && frames > 0, voice.pos = 0, break];
+                }
+                remix.remix_frame(&voice.pcm.data[voice.pos * src_count..], &mut remixed);
+                for ((out, sample), gain) in frame.iter_mut().zip(remixed).zip(gains) {
+                    *out += sample * gain;
+                }
+                voice.pos += 1;
+            }
+            is![voice.is_finished(), *slot = None];
+        }
+    }
+}
+
+impl<const N: usize> PcmStream for AudioMixer<'_, N> {
+    type Error = AudioEffectError;
+    fn pcm_spec(&self) -> Option<PcmSpec> {
+        Some(self.spec)
+    }
+    fn pcm_layout(&self) -> Option<PcmLayout> {
+        Some(PcmLayout::Interleaved)
+    }
+}
+impl<const N: usize> PcmSource<f32> for AudioMixer<'_, N> {
+    /// Mixes every requested frame, which are silent after the voices finish.
+    ///
+    /// # Errors
+    /// Returns [`AudioEffectError::MismatchedChannels`] or [`AudioEffectError::MismatchedRate`]
+    /// if `pcm` doesn't match the mixer spec, or [`AudioEffectError::IncompleteFrames`]
+    /// if it has incomplete frames.
+    fn read(&mut self, mut pcm: PcmBuf<f32, &mut [f32]>) -> Result<usize, Self::Error> {
+        if pcm.channels() != self.spec.channels {
+            return Err(AudioEffectError::MismatchedChannels);
+        }
+        if pcm.sample_rate() != self.spec.sample_rate {
+            return Err(AudioEffectError::MismatchedRate);
+        }
+        let frames = pcm.frames().ok_or(AudioEffectError::IncompleteFrames)?;
+        self.mix(pcm.data_mut());
+        Ok(frames)
+    }
+    fn read_all(&mut self, pcm: PcmBuf<f32, &mut [f32]>) -> Result<(), Self::Error> {
+        self.read(pcm).map(|_| ())
+    }
+}
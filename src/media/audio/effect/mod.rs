@@ -0,0 +1,37 @@
+// devela/src/media/audio/effect/mod.rs
+//
+//! Audio signal transformations and effects.
+//
+// Every processor works on interleaved `f32` frames, without allocating.
+
+#[cfg(test)]
+mod _test;
+mod _helper; // effect math
+
+mod biquad; // AudioBiquad
+mod chain; // AudioEffect
+mod compressor; // AudioCompressor
+mod delay; // AudioDelay
+mod error; // AudioEffectError
+mod gain; // AudioGain
+mod mixer; // AudioMixer, AudioVoice
+mod remix; // AudioRemix
+mod resample; // AudioResampler
+mod reverb; // AudioReverb
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            biquad::*,
+            chain::*,
+            compressor::*,
+            delay::*,
+            error::*,
+            gain::*,
+            mixer::*,
+            remix::*,
+            resample::*,
+            reverb::*,
+        };
+    }
+}
@@ -0,0 +1,152 @@
+// devela/src/media/audio/effect/remix.rs
+//
+//! Defines [`AudioRemix`].
+//
+
+use crate::{AudioChannel, AudioChannels, AudioEffectError, PcmBuf, is, whilst};
+use ::core::f32::consts::FRAC_1_SQRT_2 as HALF_POWER;
+
+#[doc = crate::_tags!(audio)]
+/// Up-mixes or down-mixes interleaved frames between channel layouts.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(AudioRemix = 260|2080)}]
+///
+/// Channels are matched by their [`AudioChannel`] role, and the roles missing
+/// in the target layout are folded into the nearest ones at -3 dB:
+/// - the center goes into both left and right,
+/// - the surrounds into the same side, and the backs into the surrounds if present,
+/// - the low-frequency effects channel is dropped.
+///
+/// A mono source goes into the center if present, or else into both left and right,
+/// while a mono target gets the average of the stereo fold.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels as Ch, AudioRemix, PcmBuf, PcmSample};
+/// let remix = AudioRemix::new(Ch::Stereo, Ch::Mono);
+/// let src = PcmBuf::from_parts(&[1.0, 0.5, 0.0, 1.0][..], PcmSample::F32, Ch::Stereo, 8_000);
+/// let mut out = [0.0; 2];
+/// let mut dst = PcmBuf::from_parts(&mut out[..], PcmSample::F32, Ch::Mono, 8_000);
+/// assert_eq![remix.process(src, &mut dst), Ok(2)];
+/// assert_eq![out, [0.75, 0.5]];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioRemix {
+    from: AudioChannels,
+    to: AudioChannels,
+    /// The gain of each source channel (columns) in each target channel (rows).
+    matrix: [[f32; AudioChannels::MAX_COUNT]; AudioChannels::MAX_COUNT],
+}
+
+impl AudioRemix {
+    /// Returns the remix from one channel layout to another.
+    #[must_use]
+    pub const fn new(from: AudioChannels, to: AudioChannels) -> Self {
+        use AudioChannel::{C, L, LFE, Lb, Ls, R, Rb, Rs};
+        let (src, dst) = (from.channels_expanded(), to.channels_expanded());
+        let mut m = [[0.0; AudioChannels::MAX_COUNT]; AudioChannels::MAX_COUNT];
+        let (l, r) = (or(position(dst, L), 0), or(position(dst, R), 0));
+        if src.len() == 1 {
+            if dst.len() == 1 {
+                m[0][0] = 1.0;
+            } else if let Some(c) = position(dst, C) {
+                m[c][0] = 1.0;
+            } else {
+                (m[l][0], m[r][0]) = (1.0, 1.0);
+            }
+            return Self { from, to, matrix: m };
+        }
+        whilst![s in 0..src.len(); {
+            let role = src[s];
+            if dst.len() == 1 {
+                let (left, right) = match role {
+                    L => (1.0, 0.0),
+                    R => (0.0, 1.0),
+                    C => (HALF_POWER, HALF_POWER),
+                    Ls | Lb => (HALF_POWER, 0.0),
+                    Rs | Rb => (0.0, HALF_POWER),
+                    LFE => (0.0, 0.0),
+                };
+                m[0][s] = (left + right) / 2.0;
+            } else if let Some(d) = position(dst, role) {
+                m[d][s] = 1.0;
+            } else {
+                match role {
+                    C => (m[l][s], m[r][s]) = (HALF_POWER, HALF_POWER),
+                    Ls => m[l][s] = HALF_POWER,
+                    Rs => m[r][s] = HALF_POWER,
+                    Lb => m[or(position(dst, Ls), l)][s] = HALF_POWER,
+                    Rb => m[or(position(dst, Rs), r)][s] = HALF_POWER,
+                    L | R | LFE => {}
+                }
+            }
+        }];
+        Self { from, to, matrix: m }
+    }
+
+    /// Returns the source channel layout.
+    #[must_use]
+    pub const fn from(&self) -> AudioChannels {
+        self.from
+    }
+    /// Returns the target channel layout.
+    #[must_use]
+    pub const fn to(&self) -> AudioChannels {
+        self.to
+    }
+    /// Returns the gain of each source channel (columns) in each target channel (rows).
+    #[must_use]
+    pub const fn matrix(&self) -> &[[f32; AudioChannels::MAX_COUNT]; AudioChannels::MAX_COUNT] {
+        &self.matrix
+    }
+
+    /// Remixes a single frame from `src` into `dst`.
+    ///
+    /// Only the channels of both layouts present in each frame are used.
+    pub fn remix_frame(&self, src: &[f32], dst: &mut [f32]) {
+        let src = &src[..src.len().min(self.from.channels() as usize)];
+        for (out, row) in dst.iter_mut().zip(&self.matrix).take(self.to.channels() as usize) {
+            *out = src.iter().zip(row).map(|(s, gain)| s * gain).sum();
+        }
+    }
+
+    /// Remixes the frames of `src` into `dst`, returning the number of frames.
+    ///
+    /// # Errors
+    /// Returns [`AudioEffectError::MismatchedChannels`] if the layouts differ from the remix,
+    /// [`AudioEffectError::MismatchedRate`] if the sample rates differ,
+    /// [`AudioEffectError::IncompleteFrames`] if `src` has incomplete frames,
+    /// or [`AudioEffectError::NotEnoughSpace`] if `dst` can't hold every frame.
+    pub fn process(
+        &self,
+        src: PcmBuf<f32, &[f32]>,
+        dst: &mut PcmBuf<f32, &mut [f32]>,
+    ) -> Result<usize, AudioEffectError> {
+        if src.channels() != self.from || dst.channels() != self.to {
+            return Err(AudioEffectError::MismatchedChannels);
+        }
+        is![src.sample_rate() != dst.sample_rate(), return Err(AudioEffectError::MismatchedRate)];
+        let frames = src.frames().ok_or(AudioEffectError::IncompleteFrames)?;
+        let (from, to) = (src.channel_count(), dst.channel_count());
+        is![dst.samples_len() < frames * to, return Err(AudioEffectError::NotEnoughSpace)];
+        let out = dst.data_mut().chunks_exact_mut(to);
+        for (s, d) in src.data().chunks_exact(from).zip(out) {
+            self.remix_frame(s, d);
+        }
+        Ok(frames)
+    }
+}
+
+/// Returns the position of `role` in `roles`.
+const fn position(roles: &[AudioChannel], role: AudioChannel) -> Option<usize> {
+    whilst![i in 0..roles.len(); {
+        is![roles[i] as u8 == role as u8, return Some(i)];
+    }];
+    None
+}
+/// Returns the found position, or the fallback one.
+const fn or(position: Option<usize>, fallback: usize) -> usize {
+    match position {
+        Some(p) => p,
+        None => fallback,
+    }
+}
@@ -0,0 +1,131 @@
+// devela/src/media/audio/effect/resample.rs
+//
+//! Defines [`AudioResampler`].
+//
+
+use super::_helper::sin_cos;
+use crate::{AudioChannels, AudioEffectError, Float, PcmBuf, is};
+use ::core::f32::consts::PI;
+
+#[doc = crate::_tags!(audio)]
+/// A sample-rate converter, by linear or windowed-sinc interpolation.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(AudioResampler = 2|16)}]
+///
+/// Linear interpolation is cheap, but lets through some aliasing and dulls the highs.
+/// Windowed-sinc interpolation uses a Hann-windowed sinc kernel with the given
+/// half width in input samples, which is widened when downsampling so that it also
+/// filters out the frequencies above the new Nyquist frequency.
+///
+/// The kernel weights are normalized, which preserves the level at the edges.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, AudioResampler, PcmBuf, PcmSample};
+/// let src = PcmBuf::from_parts(&[0.0, 1.0, 2.0][..], PcmSample::F32, AudioChannels::Mono, 1);
+/// let mut out = [0.0; 6];
+/// let mut dst = PcmBuf::from_parts(&mut out[..], PcmSample::F32, AudioChannels::Mono, 2);
+/// assert_eq![AudioResampler::LINEAR.process(src, &mut dst), Ok(6)];
+/// assert_eq![out, [0.0, 0.5, 1.0, 1.5, 2.0, 2.0]];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AudioResampler {
+    /// The half width of the sinc kernel, or 0 for linear interpolation.
+    half_width: u16,
+}
+
+impl AudioResampler {
+    /// Linear interpolation.
+    pub const LINEAR: Self = Self { half_width: 0 };
+    /// Windowed-sinc interpolation with a half width of 16 samples.
+    pub const SINC: Self = Self::sinc(16);
+
+    /// Returns a windowed-sinc converter with the given half width, of at least 1.
+    #[must_use]
+    pub const fn sinc(half_width: u16) -> Self {
+        Self { half_width: is![half_width == 0, 1, half_width] }
+    }
+    /// Returns the half width of the sinc kernel, or 0 for linear interpolation.
+    #[must_use]
+    pub const fn half_width(self) -> u16 {
+        self.half_width
+    }
+    /// Returns whether it interpolates linearly.
+    #[must_use]
+    pub const fn is_linear(self) -> bool {
+        self.half_width == 0
+    }
+
+    /// Returns the number of frames that `input_frames` are converted into.
+    #[must_use]
+    pub const fn output_frames(input_frames: usize, from_rate: u32, to_rate: u32) -> usize {
+        is![from_rate == 0, return 0];
+        let (from, to) = (from_rate as u128, to_rate as u128);
+        ((input_frames as u128 * to).div_ceil(from)) as usize
+    }
+
+    /// Converts the frames of `src` into the sample rate of `dst`,
+    /// returning the number of frames written.
+    ///
+    /// # Errors
+    /// Returns [`AudioEffectError::InvalidSpec`] if a sample rate is zero,
+    /// [`AudioEffectError::MismatchedChannels`] if the channel layouts differ,
+    /// [`AudioEffectError::IncompleteFrames`] if `src` has incomplete frames,
+    /// or [`AudioEffectError::NotEnoughSpace`] if `dst` can't hold
+    /// [`output_frames`][Self::output_frames].
+    pub fn process(
+        self,
+        src: PcmBuf<f32, &[f32]>,
+        dst: &mut PcmBuf<f32, &mut [f32]>,
+    ) -> Result<usize, AudioEffectError> {
+        let (from, to) = (src.sample_rate(), dst.sample_rate());
+        is![from == 0 || to == 0, return Err(AudioEffectError::InvalidSpec)];
+        is![src.channels() != dst.channels(), return Err(AudioEffectError::MismatchedChannels)];
+        let input = src.frames().ok_or(AudioEffectError::IncompleteFrames)?;
+        let (count, frames) = (src.channel_count(), Self::output_frames(input, from, to));
+        is![dst.samples_len() < frames * count, return Err(AudioEffectError::NotEnoughSpace)];
+        let (src, out) = (src.data(), dst.data_mut());
+        if from == to {
+            out[..src.len()].copy_from_slice(src);
+            return Ok(frames);
+        }
+        // the kernel is widened by the downsampling ratio, to lower its cutoff
+        let cutoff = is![to < from, to as f32 / from as f32, 1.0];
+        let reach = Float(f32::from(self.half_width) / cutoff).const_ceil().0 as isize;
+        for (i, frame) in out.chunks_exact_mut(count).take(frames).enumerate() {
+            // the position in the source: index + frac
+            let num = i as u64 * u64::from(from);
+            let index = (num / u64::from(to)) as usize;
+            let frac = (num % u64::from(to)) as f32 / to as f32;
+            if self.is_linear() {
+                let next = (index + 1).min(input - 1);
+                for (c, out) in frame.iter_mut().enumerate() {
+                    let (a, b) = (src[index * count + c], src[next * count + c]);
+                    *out = a + (b - a) * frac;
+                }
+                continue;
+            }
+            let mut acc = [0.0_f32; AudioChannels::MAX_COUNT];
+            let mut total = 0.0;
+            for k in (index as isize - reach + 1)..=(index as isize + reach) {
+                is![k < 0 || k as usize >= input, continue];
+                let d = (index as isize - k) as f32 + frac;
+                let weight = kernel(d, cutoff, reach as f32);
+                total += weight;
+                let taps = &src[k as usize * count..][..count];
+                acc.iter_mut().zip(taps).for_each(|(acc, s)| *acc += s * weight);
+            }
+            let scale = is![total.abs() > 1e-9, 1.0 / total, 0.0];
+            frame.iter_mut().zip(acc).for_each(|(out, acc)| *out = acc * scale);
+        }
+        Ok(frames)
+    }
+}
+
+/// Returns the weight of the Hann-windowed sinc kernel at a distance `d`.
+fn kernel(d: f32, cutoff: f32, reach: f32) -> f32 {
+    is![d.abs() >= reach, return 0.0];
+    let x = PI * cutoff * d;
+    let sinc = is![x.abs() < 1e-6, 1.0, sin_cos(x).0 / x];
+    let window = 0.5 + 0.5 * sin_cos(PI * d / reach).1;
+    cutoff * sinc * window
+}
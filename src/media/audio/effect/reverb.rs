@@ -0,0 +1,166 @@
+// devela/src/media/audio/effect/reverb.rs
+//
+//! Defines [`AudioReverb`].
+//
+// https://ccrma.stanford.edu/~jos/pasp/Freeverb.html
+
+use crate::{AudioChannels, AudioEffect, AudioEffectError, Float, PcmBuf, is, whilst};
+
+/// The lengths of the comb and all-pass filters at 44100 Hz, and the channel spread.
+const COMBS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALLPASSES: [usize; 4] = [556, 441, 341, 225];
+const SPREAD: usize = 23;
+const FILTERS: usize = COMBS.len() + ALLPASSES.len();
+
+#[doc = crate::_tags!(audio)]
+/// A Schroeder-Moorer reverberator over caller-provided delay lines.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Each channel goes through eight parallel damped comb filters and four
+/// serial all-pass filters, following the tuning of Freeverb,
+/// with slightly longer filters for each successive channel.
+///
+/// Use [`line_len`][Self::line_len] to size the delay lines.
+/// Buffers with a different channel layout are left unchanged.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, AudioEffect, AudioReverb, PcmBuf, PcmSample};
+/// let mut line = [0.0; 8192];
+/// let len = AudioReverb::line_len(8_000, AudioChannels::Mono);
+/// let mut reverb = AudioReverb::new(&mut line[..len], 8_000, AudioChannels::Mono).unwrap();
+/// let mut samples = [0.0; 2000];
+/// samples[0] = 1.0;
+/// let mut pcm = PcmBuf::from_parts(&mut samples[..], PcmSample::F32, AudioChannels::Mono, 8_000);
+/// reverb.process(&mut pcm);
+/// // the impulse keeps ringing
+/// assert![samples[1000..].iter().any(|s| s.abs() > 1e-4)];
+/// ```
+#[derive(Debug)]
+pub struct AudioReverb<'a> {
+    line: &'a mut [f32],
+    channels: AudioChannels,
+    /// The scaled lengths of the filters of the first channel, and the scaled spread.
+    lens: [usize; FILTERS],
+    spread: usize,
+    /// The position of each filter of each channel.
+    pos: [[usize; FILTERS]; AudioChannels::MAX_COUNT],
+    /// The low-pass memory of each comb filter of each channel.
+    damped: [[f32; COMBS.len()]; AudioChannels::MAX_COUNT],
+    /// The room size, from 0 to 1, which sets the decay time.
+    pub room: f32,
+    /// The damping of high frequencies, from 0 to 1.
+    pub damping: f32,
+    /// The balance between the dry (0) and the reverberated (1) signal.
+    pub mix: f32,
+}
+
+impl<'a> AudioReverb<'a> {
+    /// Returns a reverberator for the given sample rate and channel layout,
+    /// with a medium room, half damping and a third of reverberated signal.
+    ///
+    /// # Errors
+    /// Returns [`AudioEffectError::InvalidSpec`] if the sample rate is zero,
+    /// or [`AudioEffectError::NotEnoughSpace`] if `line` is shorter than
+    /// [`line_len`][Self::line_len].
+    pub fn new(
+        line: &'a mut [f32],
+        sample_rate: u32,
+        channels: AudioChannels,
+    ) -> Result<Self, AudioEffectError> {
+        is![sample_rate == 0, return Err(AudioEffectError::InvalidSpec)];
+        is![line.len() < Self::line_len(sample_rate, channels), {
+            return Err(AudioEffectError::NotEnoughSpace);
+        }];
+        line.fill(0.0);
+        let (lens, spread) = Self::lens(sample_rate);
+        Ok(Self {
+            line,
+            channels,
+            lens,
+            spread,
+            pos: [[0; FILTERS]; AudioChannels::MAX_COUNT],
+            damped: [[0.0; COMBS.len()]; AudioChannels::MAX_COUNT],
+            room: 0.5,
+            damping: 0.5,
+            mix: 1.0 / 3.0,
+        })
+    }
+
+    /// Returns the length of the delay lines needed for the given sample rate and channels.
+    #[must_use]
+    pub const fn line_len(sample_rate: u32, channels: AudioChannels) -> usize {
+        let (lens, spread) = Self::lens(sample_rate);
+        let (mut len, count) = (0, channels.channels() as usize);
+        whilst![k in 0..FILTERS; { len += lens[k] * count; }];
+        len + spread * FILTERS * (count * (count - 1) / 2)
+    }
+
+    /// Returns the channel layout.
+    #[must_use]
+    pub const fn channels(&self) -> AudioChannels {
+        self.channels
+    }
+
+    /* helpers */
+
+    /// Returns the filter lengths and channel spread scaled to the sample rate.
+    const fn lens(sample_rate: u32) -> ([usize; FILTERS], usize) {
+        const fn scale(len: usize, sample_rate: u32) -> usize {
+            let scaled = Float(len as f32 * sample_rate as f32 / 44_100.0).const_round().0;
+            is![scaled < 1.0, 1, scaled as usize]
+        }
+        let mut lens = [0; FILTERS];
+        whilst![k in 0..COMBS.len(); { lens[k] = scale(COMBS[k], sample_rate); }];
+        whilst![k in 0..ALLPASSES.len(); {
+            lens[COMBS.len() + k] = scale(ALLPASSES[k], sample_rate);
+        }];
+        (lens, scale(SPREAD, sample_rate))
+    }
+}
+
+impl AudioEffect for AudioReverb<'_> {
+    fn process(&mut self, pcm: &mut PcmBuf<f32, &mut [f32]>) {
+        let count = pcm.channel_count();
+        is![pcm.channels() != self.channels, return];
+        let feedback = Float(self.room).clamp(0.0, 1.0).0 * 0.28 + 0.7;
+        let damp = Float(self.damping).clamp(0.0, 1.0).0 * 0.4;
+        let mix = Float(self.mix).clamp(0.0, 1.0).0;
+
+        // the start of the line of each filter of each channel
+        let mut starts = [[0; FILTERS]; AudioChannels::MAX_COUNT];
+        let mut at = 0;
+        for (c, channel) in starts.iter_mut().enumerate().take(count) {
+            for (k, start) in channel.iter_mut().enumerate() {
+                *start = at;
+                at += self.lens[k] + c * self.spread;
+            }
+        }
+        for frame in pcm.data_mut().chunks_exact_mut(count) {
+            for (c, sample) in frame.iter_mut().enumerate() {
+                let (input, mut wet) = (*sample * 0.015, 0.0);
+                for (k, start) in starts[c].iter().enumerate() {
+                    let (len, pos) = (self.lens[k] + c * self.spread, self.pos[c][k]);
+                    let slot = &mut self.line[start + pos];
+                    let delayed = *slot;
+                    if k < COMBS.len() {
+                        let damped = &mut self.damped[c][k];
+                        *damped = delayed * (1.0 - damp) + *damped * damp;
+                        *slot = input + *damped * feedback;
+                        wet += delayed;
+                    } else {
+                        *slot = wet + delayed * 0.5;
+                        wet = delayed - wet;
+                    }
+                    self.pos[c][k] = is![pos + 1 == len, 0, pos + 1];
+                }
+                *sample = *sample * (1.0 - mix) + wet * 3.0 * mix;
+            }
+        }
+    }
+    fn reset(&mut self) {
+        self.line.fill(0.0);
+        self.pos = [[0; FILTERS]; AudioChannels::MAX_COUNT];
+        self.damped = [[0.0; COMBS.len()]; AudioChannels::MAX_COUNT];
+    }
+}
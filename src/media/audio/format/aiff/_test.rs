@@ -0,0 +1,255 @@
+// devela/src/media/audio/format/aiff/_test.rs
+//
+// TOC
+// - mod fixture
+// - mod rate
+// - mod parse
+// - mod encode
+// - mod any
+// - owned
+// - file_io
+
+#![allow(clippy::float_cmp, reason = "the expected samples are exact")]
+
+use super::*;
+use crate::{
+    AudioChannels, PcmBuf, PcmFormatError, PcmFormatReader, PcmSample, PcmSource, PcmSpec,
+};
+
+/* fixtures */
+
+mod fixture {
+    /// AIFF-C, little-endian `sowt`, 16 bits, stereo, 44.1 kHz, 2 frames.
+    pub(super) const SOWT_STEREO_44K: &[u8] = b"FORM\0\0\0\x48AIFC\
+      FVER\0\0\0\x04\xA2\x80\x51\x40\
+      COMM\0\0\0\x18\
+      \0\x02\
+      \0\0\0\x02\
+      \0\x10\
+      \x40\x0E\xAC\x44\0\0\0\0\0\0\
+      sowt\0\0\
+      SSND\0\0\0\x10\
+      \0\0\0\0\0\0\0\0\
+      \0\0\0\x40\xFF\x7F\0\x80";
+    /// AIFF, 8 bits, mono, 8 kHz, 3 frames, with an unknown chunk and a pad byte.
+    pub(super) const I8_MONO_8K_PADDED: &[u8] = b"FORM\0\0\0\x3CAIFF\
+      NAME\0\0\0\x01x\0\
+      COMM\0\0\0\x12\
+      \0\x01\
+      \0\0\0\x03\
+      \0\x08\
+      \x40\x0B\xFA\0\0\0\0\0\0\0\
+      SSND\0\0\0\x0B\
+      \0\0\0\0\0\0\0\0\
+      \x00\x40\xC0\0";
+}
+
+/* sample rate */
+
+mod rate {
+    use super::*;
+
+    #[test]
+    fn encodes_extended_floats() {
+        assert_eq!(PcmAiff::encode_rate(44_100), *b"\x40\x0E\xAC\x44\0\0\0\0\0\0");
+        assert_eq!(PcmAiff::encode_rate(8_000), *b"\x40\x0B\xFA\0\0\0\0\0\0\0");
+        assert_eq!(PcmAiff::encode_rate(1), *b"\x3F\xFF\x80\0\0\0\0\0\0\0");
+    }
+    #[test]
+    fn round_trips() {
+        for rate in [1, 8_000, 11_025, 22_050, 44_100, 48_000, 96_000, 192_000, u32::MAX] {
+            assert_eq!(PcmAiff::decode_rate(PcmAiff::encode_rate(rate)), Ok(rate));
+        }
+    }
+    #[test]
+    fn rejects_invalid_rates() {
+        let negative = *b"\xC0\x0E\xAC\x44\0\0\0\0\0\0";
+        assert_eq!(PcmAiff::decode_rate(negative), Err(PcmAiffError::InvalidSampleRate));
+        assert_eq!(PcmAiff::decode_rate([0; 10]), Err(PcmAiffError::InvalidSampleRate));
+        let huge = *b"\x40\x40\x80\0\0\0\0\0\0\0"; // 2^65
+        assert_eq!(PcmAiff::decode_rate(huge), Err(PcmAiffError::InvalidSampleRate));
+    }
+}
+
+/* parsing */
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn parses_little_endian_aifc() {
+        let aiff = PcmAiff::parse(fixture::SOWT_STEREO_44K).unwrap();
+        assert_eq!(aiff.spec(), PcmSpec::new(PcmSample::I16, AudioChannels::Stereo, 44_100));
+        assert_eq!(aiff.compression(), PcmAiff::SOWT);
+        assert!(!aiff.is_big_endian());
+        assert_eq!(aiff.frames(), 2);
+        let mut le = [0u8; 8];
+        assert_eq!(aiff.copy_le_into(&mut le), Ok(8));
+        assert_eq!(le, *b"\0\0\0\x40\xFF\x7F\0\x80");
+    }
+    #[test]
+    fn skips_unknown_chunks() {
+        let aiff = PcmAiff::parse(fixture::I8_MONO_8K_PADDED).unwrap();
+        assert_eq!(aiff.spec(), PcmSpec::new(PcmSample::I8, AudioChannels::Mono, 8_000));
+        assert_eq!(aiff.compression(), PcmAiff::NONE);
+        assert_eq!(aiff.data_bytes(), b"\x00\x40\xC0");
+    }
+    #[test]
+    fn reads_normalized_samples() {
+        let mut reader = PcmAiff::parse(fixture::I8_MONO_8K_PADDED).unwrap().into_reader();
+        let mut out = [0.0f32; 3];
+        reader.read_all(PcmBuf::from_interleaved_mut(&mut out, reader.spec())).unwrap();
+        assert_eq!(out, [0.0, 0.5, -0.5]);
+
+        let mut reader = PcmAiff::parse(fixture::SOWT_STEREO_44K).unwrap().into_reader();
+        let mut out = [0.0f32; 4];
+        reader.read_all(PcmBuf::from_interleaved_mut(&mut out, reader.spec())).unwrap();
+        assert_eq!(out, [0.0, 0.5, 32_767.0 / 32_768.0, -1.0]);
+    }
+    #[test]
+    fn rejects_malformed_files() {
+        use PcmAiffError as E;
+        assert_eq!(PcmAiff::parse(b"RIFF\0\0\0\x04WAVE"), Err(E::NotAiff));
+        let bytes = fixture::I8_MONO_8K_PADDED;
+        assert_eq!(PcmAiff::parse(&bytes[..bytes.len() - 6]), Err(E::Truncated));
+        // COMM without SSND.
+        let mut head = [0u8; 48];
+        head.copy_from_slice(&bytes[..48]);
+        head[7] = 40;
+        assert_eq!(PcmAiff::parse(&head), Err(E::MissingSsnd));
+        // Declares more frames than stored.
+        let mut long = [0u8; 68];
+        long.copy_from_slice(bytes);
+        long[35] = 4;
+        assert_eq!(PcmAiff::parse(&long), Err(E::InvalidDataLength));
+    }
+    #[test]
+    fn rejects_unsupported_compression() {
+        let mut bytes = [0u8; 80];
+        bytes.copy_from_slice(fixture::SOWT_STEREO_44K);
+        bytes[50..54].copy_from_slice(b"ulaw");
+        let tag = crate::BinTag4::new(*b"ulaw");
+        assert_eq!(PcmAiff::parse(&bytes), Err(PcmAiffError::UnsupportedCompression(tag)));
+    }
+}
+
+/* encoding */
+
+mod encode {
+    use super::*;
+
+    fn round_trip(spec: PcmSpec, le_data: &[u8], compression: crate::BinTag4) {
+        let mut bytes = [0u8; 128];
+        let len = PcmAiff::write_len(spec, le_data.len()).unwrap();
+        assert_eq!(PcmAiff::write_into(&mut bytes, spec, le_data), Ok(len));
+        let aiff = PcmAiff::parse(&bytes[..len]).unwrap();
+        assert_eq!(aiff.spec(), spec);
+        assert_eq!(aiff.compression(), compression);
+        assert_eq!(aiff.data_len(), le_data.len());
+        let mut le = [0u8; 64];
+        aiff.copy_le_into(&mut le).unwrap();
+        assert_eq!(&le[..le_data.len()], le_data);
+    }
+
+    #[test]
+    fn writes_plain_aiff() {
+        let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Stereo, 44_100);
+        let mut bytes = [0u8; 64];
+        let len = PcmAiff::write_into(&mut bytes, spec, &[0x01, 0x02, 0x03, 0x04]).unwrap();
+        assert_eq!(&bytes[..12], b"FORM\0\0\0\x32AIFF");
+        // Samples are stored big-endian.
+        assert_eq!(&bytes[len - 4..len], &[0x02, 0x01, 0x04, 0x03]);
+        round_trip(spec, &[0x01, 0x02, 0x03, 0x04], PcmAiff::NONE);
+    }
+    #[test]
+    fn pads_odd_sample_data() {
+        let spec = PcmSpec::new(PcmSample::I24, AudioChannels::Mono, 48_000);
+        let data = [0x01, 0x02, 0x03];
+        assert_eq!(PcmAiff::write_len(spec, data.len()), Ok(12 + 26 + 16 + 3 + 1));
+        round_trip(spec, &data, PcmAiff::NONE);
+    }
+    #[test]
+    fn writes_aifc_for_float_and_unsigned() {
+        let spec = PcmSpec::new(PcmSample::F32, AudioChannels::Mono, 22_050);
+        let mut bytes = [0u8; 128];
+        let len = PcmAiff::write_into(&mut bytes, spec, &0.5f32.to_le_bytes()).unwrap();
+        assert_eq!(&bytes[8..16], b"AIFCFVER");
+        assert_eq!(&bytes[len - 4..len], &0.5f32.to_be_bytes());
+        round_trip(spec, &0.5f32.to_le_bytes(), PcmAiff::FL32);
+
+        let spec = PcmSpec::new(PcmSample::F64, AudioChannels::Stereo, 96_000);
+        let mut data = [0u8; 16];
+        data[..8].copy_from_slice(&(-0.25f64).to_le_bytes());
+        round_trip(spec, &data, PcmAiff::FL64);
+
+        let spec = PcmSpec::new(PcmSample::U8, AudioChannels::Mono, 8_000);
+        round_trip(spec, &[0x80, 0xFF, 0x00], PcmAiff::RAW);
+    }
+    #[test]
+    fn rejects_bad_inputs() {
+        let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Mono, 8_000);
+        let mut small = [0u8; 40];
+        assert_eq!(
+            PcmAiff::write_into(&mut small, spec, &[0; 2]),
+            Err(PcmAiffError::NotEnoughSpace)
+        );
+        let mut bytes = [0u8; 64];
+        let err = PcmAiff::write_into(&mut bytes, spec, &[0; 3]);
+        assert_eq!(err, Err(PcmAiffError::Raw(crate::PcmRawError::InvalidDataLength)));
+    }
+}
+
+/* format detection */
+
+mod any {
+    use super::*;
+
+    #[test]
+    fn opens_detected_format() {
+        let mut reader = PcmFormatReader::open(fixture::SOWT_STEREO_44K).unwrap();
+        assert!(matches!(reader, PcmFormatReader::Aiff(_)));
+        let mut out = [0.0f32; 4];
+        reader.read_all(PcmBuf::from_interleaved_mut(&mut out, reader.spec())).unwrap();
+        assert_eq!(out[1], 0.5);
+        assert_eq!(PcmFormatReader::open(b"OggS\0\x02").err(), Some(PcmFormatError::Unrecognized));
+        assert_eq!(
+            PcmFormatReader::open(b"FORM\0\0\0\x04AIFF").err(),
+            Some(PcmFormatError::Aiff(PcmAiffError::MissingComm))
+        );
+    }
+}
+
+/* allocation */
+
+#[cfg(feature = "alloc")]
+mod owned {
+    use super::*;
+
+    #[test]
+    fn round_trips_owned() {
+        let spec = PcmSpec::new(PcmSample::I32, AudioChannels::Mono, 16_000);
+        let bytes = PcmAiff::to_vec(spec, &[1, 2, 3, 4]).unwrap();
+        let aiff = PcmAiff::from_vec(bytes).unwrap();
+        assert_eq!(aiff.spec(), spec);
+        assert_eq!(aiff.data_bytes(), &[4, 3, 2, 1]);
+        assert_eq!(aiff.as_borrowed().frames(), 1);
+    }
+}
+
+#[cfg(not(miri))]
+#[cfg(feature = "std")]
+mod file_io {
+    use super::*;
+    use crate::{Env, Fs};
+
+    #[test]
+    fn writes_and_reparses_file() {
+        let path = Env::temp_dir().join("devela_test_pcm_aiff_write.aiff");
+        let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Mono, 8_000);
+        PcmAiff::to_file(&path, spec, &[0x00, 0x80]).unwrap();
+        let aiff = PcmAiff::from_file(&path).unwrap();
+        assert_eq!(aiff.spec(), spec);
+        assert_eq!(aiff.data_bytes(), &[0x80, 0x00]);
+        let _ = Fs::remove_file(path);
+    }
+}
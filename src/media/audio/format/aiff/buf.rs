@@ -0,0 +1,131 @@
+// devela/src/media/audio/format/aiff/buf.rs
+//
+//! Defines [`PcmAiffBuf`].
+//
+
+#[cfg(feature = "alloc")]
+use crate::Vec;
+use crate::{BinTag4, PcmAiff, PcmAiffError, PcmRawBuf, PcmRawReader, PcmSpec};
+
+#[doc = crate::_tags!(audio data)]
+/// AIFF or AIFF-C byte buffer over borrowed or owned storage.
+#[doc = crate::_doc_meta!{
+    location("media/audio"),
+    #[cfg(target_pointer_width = "32")]
+    test_size_of(PcmAiffBuf_Slice_x32: PcmAiffBuf<&[u8]> = 32|256),
+    #[cfg(target_pointer_width = "64")]
+    test_size_of(PcmAiffBuf_Slice_x64: PcmAiffBuf<&[u8]> = 48|384),
+}]
+///
+/// Stores the parsed sound format next to the original bytes,
+/// with the span of the sample data found during parsing.
+///
+/// Samples are usually big-endian. Use [`reader`](Self::reader) to read them
+/// as `f32`, or [`copy_le_into`](Self::copy_le_into) to get little-endian
+/// bytes for the typed helpers of [`PcmRaw`][crate::PcmRaw].
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PcmAiffBuf<B> {
+    bytes: B,
+    spec: PcmSpec,
+    compression: BinTag4,
+    bits_per_sample: u16,
+    data_offset: usize,
+    data_len: usize,
+}
+#[rustfmt::skip]
+impl<B> PcmAiffBuf<B> {
+    /// Creates an AIFF buffer from already-validated parts.
+    pub(crate) const fn _new(bytes: B, spec: PcmSpec, compression: BinTag4, bits_per_sample: u16,
+        data_offset: usize, data_len: usize) -> Self {
+        Self { bytes, spec, compression, bits_per_sample, data_offset, data_len }
+    }
+    /// Returns the stream metadata.
+    pub const fn spec(&self) -> PcmSpec { self.spec }
+    /// Returns the compression type, which is [`PcmAiff::NONE`] for plain AIFF.
+    pub const fn compression(&self) -> BinTag4 { self.compression }
+    /// Returns the declared sample size in bits.
+    ///
+    /// It can be smaller than the bits of [`spec`](Self::spec),
+    /// whose samples hold it left-justified.
+    #[must_use]
+    pub const fn bits_per_sample(&self) -> u16 { self.bits_per_sample }
+    /// Returns whether samples are stored big-endian.
+    #[must_use]
+    pub const fn is_big_endian(&self) -> bool { !self.compression.eq(PcmAiff::SOWT) }
+    /// Returns the byte offset of the sample data.
+    #[must_use]
+    pub const fn data_offset(&self) -> usize { self.data_offset }
+    /// Returns the byte length of the sample data.
+    #[must_use]
+    pub const fn data_len(&self) -> usize { self.data_len }
+    /// Returns the number of interleaved PCM frames.
+    #[must_use]
+    pub const fn frames(&self) -> usize { self.data_len / self.spec.frame_bytes() }
+}
+#[rustfmt::skip]
+impl<B: AsRef<[u8]>> PcmAiffBuf<B> {
+    /// Returns the full AIFF byte region.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] { self.bytes.as_ref() }
+
+    /// Returns the sample data, in the stored byte order.
+    #[must_use]
+    pub fn data_bytes(&self) -> &[u8] {
+        &self.bytes.as_ref()[self.data_offset..self.data_offset + self.data_len]
+    }
+
+    /// Returns whether there are no sample frames.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.data_len == 0 }
+
+    /// Copies the sample data into `dst` as little-endian raw PCM.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns [`PcmAiffError::NotEnoughSpace`] if `dst` is too small.
+    pub fn copy_le_into(&self, dst: &mut [u8]) -> Result<usize, PcmAiffError> {
+        let data = self.data_bytes();
+        let dst = dst.get_mut(..data.len()).ok_or(PcmAiffError::NotEnoughSpace)?;
+        dst.copy_from_slice(data);
+        if self.is_big_endian() {
+            PcmAiff::swap_samples(dst, self.spec.sample.bytes());
+        }
+        Ok(data.len())
+    }
+
+    /// Returns a reader of normalized `f32` samples.
+    pub fn reader(&self) -> PcmRawReader<'_> {
+        let raw = PcmRawBuf::_new(self.data_bytes(), self.spec);
+        if self.is_big_endian() { PcmRawReader::new_be(raw) } else { PcmRawReader::new(raw) }
+    }
+}
+#[rustfmt::skip]
+impl<'a> PcmAiffBuf<&'a [u8]> {
+    /// Returns the full borrowed AIFF byte region.
+    ///
+    /// This exists as a const-friendly alternative to [`bytes`](Self::bytes).
+    #[must_use]
+    pub const fn bytes_const(&self) -> &'a [u8] { self.bytes }
+
+    /// Returns a reader of normalized `f32` samples, borrowing the original bytes.
+    pub const fn into_reader(self) -> PcmRawReader<'a> {
+        let (_, data) = self.bytes.split_at(self.data_offset);
+        let (data, _) = data.split_at(self.data_len);
+        let raw = PcmRawBuf::_new(data, self.spec);
+        if self.is_big_endian() { PcmRawReader::new_be(raw) } else { PcmRawReader::new(raw) }
+    }
+}
+#[rustfmt::skip]
+#[cfg(feature = "alloc")]
+impl PcmAiffBuf<Vec<u8>> {
+    /// Returns this owned AIFF buffer as a borrowed AIFF buffer.
+    pub fn as_borrowed(&self) -> PcmAiffBuf<&[u8]> {
+        PcmAiffBuf::_new(self.bytes.as_slice(), self.spec, self.compression,
+            self.bits_per_sample, self.data_offset, self.data_len)
+    }
+    /// Returns the owned full AIFF byte region.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> { self.bytes }
+}
@@ -0,0 +1,91 @@
+// devela/src/media/audio/format/aiff/error.rs
+//
+//! Defines [`PcmAiffError`].
+//
+
+use crate::{BinTag4, PcmRawError};
+#[cfg(feature = "std")]
+use crate::{IoError, IoErrorKind};
+
+#[doc = crate::_tags!(audio error)]
+/// AIFF and AIFF-C encoding and decoding error.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(PcmAiffError = 6|48)}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PcmAiffError {
+    /// Raw PCM byte/sample conversion failed.
+    Raw(PcmRawError),
+
+    /// The data doesn't start with a `FORM` chunk of type `AIFF` or `AIFC`.
+    NotAiff,
+
+    /// A chunk extends past the end of the data.
+    Truncated,
+
+    /// The required `COMM` chunk was not found.
+    MissingComm,
+
+    /// The required `SSND` chunk was not found.
+    MissingSsnd,
+
+    /// The `COMM` chunk is shorter than its fields.
+    InvalidComm,
+
+    /// The AIFF-C compression type is not supported.
+    UnsupportedCompression(BinTag4),
+
+    /// The channel count is zero or cannot map to current PCM metadata.
+    UnsupportedChannelCount(u16),
+
+    /// The sample size is not supported for the compression type.
+    UnsupportedBitsPerSample(u16),
+
+    /// The sample rate is negative, fractional below 1 Hz, or too large.
+    InvalidSampleRate,
+
+    /// The `SSND` chunk holds fewer bytes than the declared frames.
+    InvalidDataLength,
+
+    /// A size computation overflowed.
+    Overflow,
+
+    /// The destination buffer is too small.
+    NotEnoughSpace,
+
+    /// The encoded file would exceed the 32-bit chunk size fields.
+    SizeOutOfRange,
+
+    /// Reading or writing the file failed.
+    #[cfg(feature = "std")]
+    Io(IoErrorKind),
+}
+crate::impl_trait![fmt::Display+Error for PcmAiffError |self, f| match self {
+    Self::Raw(err) => write!(f, "raw PCM operation failed: {err}"),
+    Self::NotAiff => f.write_str("not a `FORM` chunk of type `AIFF` or `AIFC`"),
+    Self::Truncated => f.write_str("AIFF chunk extends past the end of the data"),
+    Self::MissingComm => f.write_str("missing required `COMM` chunk"),
+    Self::MissingSsnd => f.write_str("missing required `SSND` chunk"),
+    Self::InvalidComm => f.write_str("`COMM` chunk is too short"),
+    Self::UnsupportedCompression(tag) => {
+        write!(f, "unsupported AIFF-C compression type: {:?}", tag.bytes())
+    }
+    Self::UnsupportedChannelCount(chans) => write!(f, "unsupported AIFF channel count: {chans}"),
+    Self::UnsupportedBitsPerSample(bits) => write!(f, "unsupported AIFF sample size: {bits}"),
+    Self::InvalidSampleRate => f.write_str("invalid AIFF sample rate"),
+    Self::InvalidDataLength => f.write_str("`SSND` chunk is shorter than the declared frames"),
+    Self::Overflow => f.write_str("AIFF chunk size computation overflowed"),
+    Self::NotEnoughSpace => f.write_str("not enough space to write AIFF data"),
+    Self::SizeOutOfRange => f.write_str("encoded AIFF data exceeds 32-bit size limits"),
+    #[cfg(feature = "std")]
+    Self::Io(err) => write!(f, "AIFF file operation failed: {err}"),
+}];
+impl From<PcmRawError> for PcmAiffError {
+    fn from(err: PcmRawError) -> Self {
+        Self::Raw(err)
+    }
+}
+#[cfg(feature = "std")]
+impl From<IoError> for PcmAiffError {
+    fn from(err: IoError) -> Self {
+        Self::Io(err.kind())
+    }
+}
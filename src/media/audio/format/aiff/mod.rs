@@ -0,0 +1,27 @@
+// devela/src/media/audio/format/aiff/mod.rs
+//
+//! AIFF and AIFF-C support for PCM-family audio.
+//!
+//! This module parses and writes the big-endian IFF containers of the Audio
+//! Interchange File Format, for uncompressed integer and float payloads.
+//!
+//! The audio payload remains raw interleaved bytes, usually big-endian.
+//! Use [`PcmAiffBuf::reader`] or [`PcmAiffBuf::copy_le_into`] to get samples.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod buf; // PcmAiffBuf
+mod error; // PcmAiffError
+mod namespace; // PcmAiff
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            buf::*,
+            error::*,
+            namespace::*,
+        };
+    }
+}
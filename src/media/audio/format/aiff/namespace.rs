@@ -0,0 +1,360 @@
+// devela/src/media/audio/format/aiff/namespace.rs
+//
+//! Defines [`PcmAiff`].
+//
+
+use crate::{
+    AudioChannels, BinTag4, PcmAiffBuf, PcmAiffError, PcmSample, PcmSpec, Riff, is, read_at,
+    unwrap, whilst, write_at,
+};
+#[cfg(feature = "std")]
+use crate::{Fs, Path};
+#[cfg(feature = "alloc")]
+use crate::{Vec, vec_};
+
+#[doc = crate::_tags!(audio codec)]
+/// AIFF and AIFF-C operations for PCM-family audio.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+/// `PcmAiff` is a namespace for working with the big-endian IFF containers
+/// of Apple's Audio Interchange File Format.
+///
+/// It supports:
+/// - parsing borrowed, owned, and file-backed AIFF and AIFF-C bytes,
+/// - the uncompressed AIFF-C types `NONE`, `twos`, `sowt`, `raw `, `fl32` and `fl64`,
+/// - writing integer PCM as AIFF, and unsigned or float PCM as AIFF-C.
+///
+/// The writer takes the little-endian raw PCM produced by [`PcmRaw`],
+/// and stores it big-endian.
+///
+/// [`PcmRaw`]: crate::PcmRaw
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, PcmAiff, PcmAiffError, PcmRaw, PcmSample, PcmSpec};
+/// # fn main() -> Result<(), PcmAiffError> {
+/// let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Stereo, 44_100);
+/// let samples = [0i16, 0, i16::MAX, i16::MIN];
+/// let mut pcm = [0u8; 8];
+/// PcmRaw::encode_i16_le_into(&mut pcm, spec, &samples)?;
+///
+/// let mut aiff_bytes = [0u8; 64];
+/// let written = PcmAiff::write_into(&mut aiff_bytes, spec, &pcm)?;
+/// assert_eq!(written, PcmAiff::write_len(spec, pcm.len())?);
+///
+/// let aiff = PcmAiff::parse(&aiff_bytes[..written])?;
+/// assert_eq!(aiff.spec(), spec);
+/// assert_eq!(aiff.frames(), 2);
+///
+/// let mut le = [0u8; 8];
+/// aiff.copy_le_into(&mut le)?;
+/// assert_eq!(le, pcm);
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct PcmAiff;
+
+/// # Parsing
+impl PcmAiff {
+    /// Parses a borrowed AIFF or AIFF-C byte region.
+    ///
+    /// This validates the `FORM` container, finds the first `COMM` and `SSND`
+    /// chunks, and returns a byte-backed view into the original input.
+    pub const fn parse(bytes: &[u8]) -> Result<PcmAiffBuf<&[u8]>, PcmAiffError> {
+        let p = unwrap![ok? Self::parse_parts(bytes)];
+        Ok(PcmAiffBuf::_new(bytes, p.spec, p.compression, p.bits, p.data_offset, p.data_len))
+    }
+    /// Parses owned AIFF or AIFF-C bytes.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(bytes: Vec<u8>) -> Result<PcmAiffBuf<Vec<u8>>, PcmAiffError> {
+        let p = Self::parse_parts(bytes.as_slice())?;
+        Ok(PcmAiffBuf::_new(bytes, p.spec, p.compression, p.bits, p.data_offset, p.data_len))
+    }
+    /// Reads and parses an AIFF or AIFF-C file.
+    #[cfg(feature = "std")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<PcmAiffBuf<Vec<u8>>, PcmAiffError> {
+        Self::from_vec(Fs::read(path)?)
+    }
+
+    /// Returns whether `bytes` starts with an AIFF or AIFF-C `FORM` header.
+    #[must_use]
+    pub const fn is_aiff(bytes: &[u8]) -> bool {
+        if bytes.len() < Riff::ROOT_HEADER_LEN {
+            return false;
+        }
+        let form = BinTag4::new(read_at![bytes, 8, @4]);
+        BinTag4::new(read_at![bytes, 0, @4]).eq(Self::FORM)
+            && (form.eq(Self::AIFF) || form.eq(Self::AIFC))
+    }
+
+    /// Decodes an 80-bit extended precision sample rate, rounded to the nearest Hertz.
+    ///
+    /// # Errors
+    /// Returns [`PcmAiffError::InvalidSampleRate`] if the rate is negative,
+    /// rounds to zero, or doesn't fit in a `u32`.
+    pub const fn decode_rate(ext: [u8; 10]) -> Result<u32, PcmAiffError> {
+        let exp = u16::from_be_bytes([ext[0], ext[1]]);
+        let mantissa = u64::from_be_bytes(read_at![ext, 2, @8]);
+        is![exp & 0x8000 != 0 || exp < 0x3FFF, return Err(PcmAiffError::InvalidSampleRate)];
+        // The value is `mantissa * 2^(exp - 0x3FFF - 63)`.
+        let shift = 0x3FFF + 63 - exp as i32;
+        is![shift < 32 || shift > 63, return Err(PcmAiffError::InvalidSampleRate)];
+        let rate = (mantissa >> shift) + ((mantissa >> (shift - 1)) & 1);
+        is![rate == 0 || rate > u32::MAX as u64, return Err(PcmAiffError::InvalidSampleRate)];
+        Ok(rate as u32)
+    }
+    /// Encodes a sample rate as an 80-bit extended precision number.
+    #[must_use]
+    pub const fn encode_rate(rate: u32) -> [u8; 10] {
+        is![rate == 0, return [0; 10]];
+        let zeros = (rate as u64).leading_zeros();
+        let exp = (0x3FFF + 63 - zeros as u16).to_be_bytes();
+        let mantissa = ((rate as u64) << zeros).to_be_bytes();
+        let mut ext = [0; 10];
+        write_at![ext, 0, exp[0], exp[1]];
+        write_at![ext, 2, @8 mantissa];
+        ext
+    }
+
+    const fn parse_parts(bytes: &[u8]) -> Result<PcmAiffParts, PcmAiffError> {
+        use PcmAiffError as E;
+        is![!Self::is_aiff(bytes), return Err(E::NotAiff)];
+        let form_len = u32::from_be_bytes(read_at![bytes, 4, @4]) as usize;
+        let Some(end) = form_len.checked_add(Riff::CHUNK_HEADER_LEN) else {
+            return Err(E::Overflow);
+        };
+        is![end > bytes.len() || form_len < Riff::FORM_TYPE_LEN, return Err(E::Truncated)];
+        let aifc = BinTag4::new(read_at![bytes, 8, @4]).eq(Self::AIFC);
+        let mut comm: Option<(usize, usize)> = None;
+        let mut ssnd: Option<(usize, usize)> = None;
+        let mut offset = Riff::ROOT_HEADER_LEN;
+        while offset + Riff::CHUNK_HEADER_LEN <= end && (comm.is_none() || ssnd.is_none()) {
+            let id = BinTag4::new(read_at![bytes, offset, @4]);
+            let len = u32::from_be_bytes(read_at![bytes, offset + 4, @4]) as usize;
+            let data = offset + Riff::CHUNK_HEADER_LEN;
+            is![len > end - data, return Err(E::Truncated)];
+            if id.eq(Self::COMM) && comm.is_none() {
+                comm = Some((data, len));
+            } else if id.eq(Self::SSND) && ssnd.is_none() {
+                ssnd = Some((data, len));
+            }
+            offset = data + len + Riff::pad_len(len);
+        }
+        let Some((comm, comm_len)) = comm else { return Err(E::MissingComm) };
+        let Some((ssnd, ssnd_len)) = ssnd else { return Err(E::MissingSsnd) };
+
+        is![comm_len < 18 || (aifc && comm_len < 22), return Err(E::InvalidComm)];
+        let channels = u16::from_be_bytes(read_at![bytes, comm, @2]);
+        let frames = u32::from_be_bytes(read_at![bytes, comm + 2, @4]) as usize;
+        let bits = u16::from_be_bytes(read_at![bytes, comm + 6, @2]);
+        let (exp, mantissa) = (read_at![bytes, comm + 8, @2], read_at![bytes, comm + 10, @8]);
+        let rate = unwrap![ok? Self::decode_rate([exp[0], exp[1], mantissa[0], mantissa[1],
+            mantissa[2], mantissa[3], mantissa[4], mantissa[5], mantissa[6], mantissa[7]])];
+        let compression = is![aifc, BinTag4::new(read_at![bytes, comm + 18, @4]), Self::NONE];
+        let sample = unwrap![ok? Self::sample_for(compression, bits)];
+        is![channels > 8, return Err(E::UnsupportedChannelCount(channels))];
+        let lfe = matches!(channels, 6 | 8);
+        let Some(layout) = AudioChannels::from_total_lfe(channels as u8, lfe) else {
+            return Err(E::UnsupportedChannelCount(channels));
+        };
+        let spec = PcmSpec::new(sample, layout, rate);
+
+        is![ssnd_len < 8, return Err(E::InvalidDataLength)];
+        let skip = u32::from_be_bytes(read_at![bytes, ssnd, @4]) as usize;
+        let Some(data_len) = frames.checked_mul(spec.frame_bytes()) else {
+            return Err(E::Overflow);
+        };
+        is![
+            skip > ssnd_len - 8 || data_len > ssnd_len - 8 - skip,
+            return Err(E::InvalidDataLength)
+        ];
+        let data_offset = ssnd + 8 + skip;
+        Ok(PcmAiffParts { spec, compression, bits, data_offset, data_len })
+    }
+    /// Returns the sample encoding of a compression type and sample size.
+    const fn sample_for(compression: BinTag4, bits: u16) -> Result<PcmSample, PcmAiffError> {
+        let c = compression;
+        if c.eq(Self::NONE) || c.eq(Self::TWOS) || c.eq(Self::SOWT) {
+            match bits {
+                1..=8 => Ok(PcmSample::I8),
+                9..=16 => Ok(PcmSample::I16),
+                17..=24 => Ok(PcmSample::I24),
+                25..=32 => Ok(PcmSample::I32),
+                _ => Err(PcmAiffError::UnsupportedBitsPerSample(bits)),
+            }
+        } else if c.eq(Self::RAW) {
+            is![bits == 8, Ok(PcmSample::U8), Err(PcmAiffError::UnsupportedBitsPerSample(bits))]
+        } else if c.eq(Self::FL32) || c.eq_bytes(*b"FL32") {
+            is![bits == 32, Ok(PcmSample::F32), Err(PcmAiffError::UnsupportedBitsPerSample(bits))]
+        } else if c.eq(Self::FL64) || c.eq_bytes(*b"FL64") {
+            is![bits == 64, Ok(PcmSample::F64), Err(PcmAiffError::UnsupportedBitsPerSample(bits))]
+        } else {
+            Err(PcmAiffError::UnsupportedCompression(compression))
+        }
+    }
+}
+/// # Writing
+impl PcmAiff {
+    /// Returns the number of bytes needed to write a minimal AIFF or AIFF-C container.
+    pub const fn write_len(spec: PcmSpec, data_len: usize) -> Result<usize, PcmAiffError> {
+        use PcmAiffError as E;
+        is![!spec.is_valid(), return Err(E::Raw(crate::PcmRawError::InvalidSpec))];
+        is![
+            !spec.has_complete_frames_for_data_len(data_len),
+            return Err(E::Raw(crate::PcmRawError::InvalidDataLength))
+        ];
+        let Some(ssnd_len) = data_len.checked_add(8) else { return Err(E::Overflow) };
+        is![!Riff::fits_u32(ssnd_len), return Err(E::SizeOutOfRange)];
+        let chunks = Self::header_chunks_len(Self::is_aifc_sample(spec.sample));
+        let Some(ssnd_chunk) = Riff::chunk_len(ssnd_len) else {
+            return Err(E::Overflow);
+        };
+        let Some(subchunks) = ssnd_chunk.checked_add(chunks) else {
+            return Err(E::Overflow);
+        };
+        let Some(len) = Riff::form_len(subchunks) else { return Err(E::Overflow) };
+        is![!Riff::fits_u32(len - Riff::CHUNK_HEADER_LEN), return Err(E::SizeOutOfRange)];
+        Ok(len)
+    }
+    /// Writes a minimal AIFF or AIFF-C container into `dst`.
+    ///
+    /// `data` must contain little-endian raw interleaved PCM matching `spec`.
+    /// Integer samples are written as AIFF, and unsigned
+    /// or floating-point samples as AIFF-C.
+    ///
+    /// Returns the number of bytes written.
+    pub const fn write_into(
+        dst: &mut [u8],
+        spec: PcmSpec,
+        data: &[u8],
+    ) -> Result<usize, PcmAiffError> {
+        let len = unwrap![ok? Self::write_len(spec, data.len())];
+        is![dst.len() < len, return Err(PcmAiffError::NotEnoughSpace)];
+        let aifc = Self::is_aifc_sample(spec.sample);
+        let frames = (data.len() / spec.frame_bytes()) as u32;
+        let mut off = 0;
+        off += Self::write_header(dst, off, Self::FORM, len - Riff::CHUNK_HEADER_LEN);
+        write_at![dst, +=off, @4 is![aifc, Self::AIFC, Self::AIFF].bytes()];
+        if aifc {
+            off += Self::write_header(dst, off, Self::FVER, 4);
+            write_at![dst, +=off, @4 Self::AIFC_VERSION.to_be_bytes()];
+        }
+        off += Self::write_header(dst, off, Self::COMM, is![aifc, 24, 18]);
+        write_at![dst, +=off, @2 (spec.channel_count() as u16).to_be_bytes()];
+        write_at![dst, +=off, @4 frames.to_be_bytes()];
+        write_at![dst, +=off, @2 (spec.sample.bits() as u16).to_be_bytes()];
+        let rate = Self::encode_rate(spec.sample_rate);
+        whilst! { i in 0..10; { dst[off + i] = rate[i]; }}
+        off += 10;
+        if aifc {
+            let compression = match spec.sample {
+                PcmSample::U8 => Self::RAW,
+                PcmSample::F32 => Self::FL32,
+                _ => Self::FL64,
+            };
+            // The compression type, then an empty padded name.
+            write_at![dst, +=off, @4 compression.bytes()];
+            write_at![dst, +=off, 0, 0];
+        }
+        off += Self::write_header(dst, off, Self::SSND, data.len() + 8);
+        write_at![dst, +=off, 0, 0, 0, 0, 0, 0, 0, 0];
+        whilst! { i in 0..data.len(); { dst[off + i] = data[i]; }}
+        let (_, samples) = dst.split_at_mut(off);
+        let (samples, _) = samples.split_at_mut(data.len());
+        Self::swap_samples(samples, spec.sample.bytes());
+        off += data.len();
+        is![Riff::pad_len(data.len()) != 0, {
+            dst[off] = 0;
+            off += 1;
+        }];
+        debug_assert!(off == len);
+        Ok(off)
+    }
+    /// Encodes a minimal AIFF or AIFF-C file into an allocated byte vector.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(spec: PcmSpec, data: &[u8]) -> Result<Vec<u8>, PcmAiffError> {
+        let len = Self::write_len(spec, data.len())?;
+        let mut out = vec_![0u8; len];
+        let written = Self::write_into(&mut out, spec, data)?;
+        debug_assert_eq!(written, len);
+        Ok(out)
+    }
+    /// Encodes and writes a minimal AIFF or AIFF-C file.
+    #[cfg(feature = "std")]
+    pub fn to_file<P: AsRef<Path>>(
+        path: P,
+        spec: PcmSpec,
+        data: &[u8],
+    ) -> Result<(), PcmAiffError> {
+        let bytes = Self::to_vec(spec, data)?;
+        Fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Reverses the byte order of every `width`-byte sample in `bytes`.
+    pub(crate) const fn swap_samples(bytes: &mut [u8], width: usize) {
+        is![width < 2, return];
+        let mut i = 0;
+        while i + width <= bytes.len() {
+            let (mut a, mut b) = (i, i + width - 1);
+            while a < b {
+                bytes.swap(a, b);
+                a += 1;
+                b -= 1;
+            }
+            i += width;
+        }
+    }
+    /// Returns whether a sample encoding needs an AIFF-C compression type.
+    const fn is_aifc_sample(sample: PcmSample) -> bool {
+        matches!(sample, PcmSample::U8 | PcmSample::F32 | PcmSample::F64)
+    }
+    /// Returns the length of the chunks before `SSND`.
+    const fn header_chunks_len(aifc: bool) -> usize {
+        is![aifc, (8 + 4) + (8 + 24), 8 + 18]
+    }
+    /// Writes a big-endian chunk header at `off`, which must fit.
+    const fn write_header(dst: &mut [u8], off: usize, id: BinTag4, len: usize) -> usize {
+        write_at![dst, off, @4 id.bytes()];
+        write_at![dst, off + 4, @4 (len as u32).to_be_bytes()];
+        Riff::CHUNK_HEADER_LEN
+    }
+}
+/// # Constants
+impl PcmAiff {
+    /// The IFF container chunk id.
+    pub const FORM: BinTag4 = BinTag4::new(*b"FORM");
+    /// The AIFF form type.
+    pub const AIFF: BinTag4 = BinTag4::new(*b"AIFF");
+    /// The AIFF-C form type.
+    pub const AIFC: BinTag4 = BinTag4::new(*b"AIFC");
+    /// The common chunk id, with the sound format.
+    pub const COMM: BinTag4 = BinTag4::new(*b"COMM");
+    /// The sound data chunk id.
+    pub const SSND: BinTag4 = BinTag4::new(*b"SSND");
+    /// The AIFF-C format version chunk id.
+    pub const FVER: BinTag4 = BinTag4::new(*b"FVER");
+    /// The AIFF-C format version timestamp.
+    pub const AIFC_VERSION: u32 = 0xA280_5140;
+
+    /// Uncompressed big-endian signed integers.
+    pub const NONE: BinTag4 = BinTag4::new(*b"NONE");
+    /// Uncompressed big-endian signed integers, QuickTime style.
+    pub const TWOS: BinTag4 = BinTag4::new(*b"twos");
+    /// Uncompressed little-endian signed integers.
+    pub const SOWT: BinTag4 = BinTag4::new(*b"sowt");
+    /// Uncompressed unsigned 8-bit integers.
+    pub const RAW: BinTag4 = BinTag4::new(*b"raw ");
+    /// Big-endian 32-bit floats.
+    pub const FL32: BinTag4 = BinTag4::new(*b"fl32");
+    /// Big-endian 64-bit floats.
+    pub const FL64: BinTag4 = BinTag4::new(*b"fl64");
+}
+
+#[derive(Clone, Copy)]
+struct PcmAiffParts {
+    spec: PcmSpec,
+    compression: BinTag4,
+    bits: u16,
+    data_offset: usize,
+    data_len: usize,
+}
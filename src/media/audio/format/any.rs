@@ -0,0 +1,211 @@
+// devela/src/media/audio/format/any.rs
+//
+//! Defines [`PcmFormat`], [`PcmFormatReader`], [`PcmFormatError`].
+//
+
+use crate::{
+    PcmAiff, PcmAiffError, PcmBuf, PcmFlacError, PcmLayout, PcmRawBuf, PcmRawError, PcmRawReader,
+    PcmSource, PcmSpec, PcmStream, PcmWav, PcmWavError,
+};
+#[cfg(feature = "alloc")]
+use crate::{PcmFlac, PcmFlacReader, Vec};
+
+#[doc = crate::_tags!(audio codec)]
+/// An audio container that can be opened as a [`PcmSource`].
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Implemented by the format namespaces, so that code generic over the format
+/// can probe and open any supported container the same way.
+/// Use [`PcmFormatReader`] to open whichever format is detected.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, PcmAiff, PcmFormat, PcmSample, PcmSpec, PcmStream, PcmWav};
+/// fn rate_of<F: PcmFormat>(bytes: &[u8]) -> Option<u32> {
+///     if !F::probe(bytes) { return None; }
+///     Some(F::open(bytes).ok()?.pcm_spec()?.sample_rate)
+/// }
+/// let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Mono, 22_050);
+/// let mut bytes = [0u8; 64];
+/// let len = PcmAiff::write_into(&mut bytes, spec, &[0; 4]).unwrap();
+///
+/// assert_eq!(rate_of::<PcmWav>(&bytes[..len]), None);
+/// assert_eq!(rate_of::<PcmAiff>(&bytes[..len]), Some(22_050));
+/// ```
+pub trait PcmFormat {
+    /// Error returned when opening the container.
+    type Error;
+    /// Reader of normalized `f32` samples.
+    type Reader<'a>: PcmSource<f32>;
+
+    /// Returns whether `bytes` start like this format.
+    fn probe(bytes: &[u8]) -> bool;
+    /// Parses `bytes` and returns a reader of their samples.
+    fn open(bytes: &[u8]) -> Result<Self::Reader<'_>, Self::Error>;
+}
+
+impl PcmFormat for PcmWav {
+    type Error = PcmWavError;
+    type Reader<'a> = PcmRawReader<'a>;
+    fn probe(bytes: &[u8]) -> bool {
+        bytes.len() >= 12 && bytes[..4] == *b"RIFF" && bytes[8..12] == *b"WAVE"
+    }
+    fn open(bytes: &[u8]) -> Result<Self::Reader<'_>, Self::Error> {
+        let wav = PcmWav::parse(bytes)?;
+        let (offset, len) = wav.data_span();
+        let raw = PcmRawBuf::_new(&bytes[offset..offset + len], wav.spec()?);
+        Ok(PcmRawReader::new(raw))
+    }
+}
+impl PcmFormat for PcmAiff {
+    type Error = PcmAiffError;
+    type Reader<'a> = PcmRawReader<'a>;
+    fn probe(bytes: &[u8]) -> bool {
+        PcmAiff::is_aiff(bytes)
+    }
+    fn open(bytes: &[u8]) -> Result<Self::Reader<'_>, Self::Error> {
+        Ok(PcmAiff::parse(bytes)?.into_reader())
+    }
+}
+#[cfg(feature = "alloc")]
+impl PcmFormat for PcmFlac {
+    type Error = PcmFlacError;
+    type Reader<'a> = PcmFlacReader<'a, Vec<i32>>;
+    fn probe(bytes: &[u8]) -> bool {
+        PcmFlac::is_flac(bytes)
+    }
+    fn open(bytes: &[u8]) -> Result<Self::Reader<'_>, Self::Error> {
+        PcmFlac::reader(bytes)
+    }
+}
+
+#[doc = crate::_tags!(audio codec)]
+/// A reader over any supported audio container.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// [`open`](Self::open) probes the bytes for each [`PcmFormat`] and opens
+/// the first that matches. FLAC is only recognized with the `alloc` feature.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, PcmBuf, PcmFormatReader, PcmSample, PcmSource, PcmSpec, PcmWav};
+/// let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Mono, 8_000);
+/// let mut bytes = [0u8; 64];
+/// let fmt = devela::PcmWavFmt::from_spec(spec).unwrap();
+/// let len = PcmWav::write_into(&mut bytes, fmt, &[0x00, 0x40, 0x00, 0xC0]).unwrap();
+///
+/// let mut reader = PcmFormatReader::open(&bytes[..len]).unwrap();
+/// let mut out = [0.0f32; 2];
+/// reader.read_all(PcmBuf::from_interleaved_mut(&mut out, reader.spec())).unwrap();
+/// assert_eq!(out, [0.5, -0.5]);
+/// ```
+#[derive(Debug)]
+pub enum PcmFormatReader<'a> {
+    /// A RIFF/WAVE container.
+    Wav(PcmRawReader<'a>),
+    /// An AIFF or AIFF-C container.
+    Aiff(PcmRawReader<'a>),
+    /// A FLAC stream.
+    #[cfg(feature = "alloc")]
+    Flac(PcmFlacReader<'a, Vec<i32>>),
+}
+
+impl<'a> PcmFormatReader<'a> {
+    /// Detects the container format of `bytes` and opens a reader.
+    ///
+    /// # Errors
+    /// Returns [`PcmFormatError::Unrecognized`] if no format matches,
+    /// or the error from opening the detected format.
+    pub fn open(bytes: &'a [u8]) -> Result<Self, PcmFormatError> {
+        if PcmWav::probe(bytes) {
+            return Ok(Self::Wav(PcmWav::open(bytes)?));
+        }
+        if PcmAiff::probe(bytes) {
+            return Ok(Self::Aiff(PcmAiff::open(bytes)?));
+        }
+        #[cfg(feature = "alloc")]
+        if PcmFlac::probe(bytes) {
+            return Ok(Self::Flac(PcmFlac::open(bytes)?));
+        }
+        Err(PcmFormatError::Unrecognized)
+    }
+
+    /// Returns the stream metadata.
+    pub fn spec(&self) -> PcmSpec {
+        match self {
+            Self::Wav(r) | Self::Aiff(r) => r.spec(),
+            #[cfg(feature = "alloc")]
+            Self::Flac(r) => r.spec(),
+        }
+    }
+}
+
+impl PcmStream for PcmFormatReader<'_> {
+    type Error = PcmFormatError;
+    fn pcm_spec(&self) -> Option<PcmSpec> {
+        Some(self.spec())
+    }
+    fn pcm_layout(&self) -> Option<PcmLayout> {
+        Some(PcmLayout::Interleaved)
+    }
+}
+impl PcmSource<f32> for PcmFormatReader<'_> {
+    fn read(&mut self, pcm: PcmBuf<f32, &mut [f32]>) -> Result<usize, Self::Error> {
+        match self {
+            Self::Wav(r) | Self::Aiff(r) => Ok(r.read(pcm)?),
+            #[cfg(feature = "alloc")]
+            Self::Flac(r) => Ok(r.read(pcm)?),
+        }
+    }
+    fn read_all(&mut self, pcm: PcmBuf<f32, &mut [f32]>) -> Result<(), Self::Error> {
+        match self {
+            Self::Wav(r) | Self::Aiff(r) => Ok(r.read_all(pcm)?),
+            #[cfg(feature = "alloc")]
+            Self::Flac(r) => Ok(r.read_all(pcm)?),
+        }
+    }
+}
+
+#[doc = crate::_tags!(audio error)]
+/// Error from opening or reading a [`PcmFormatReader`].
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(PcmFormatError = 6|48)}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PcmFormatError {
+    /// The bytes don't match any supported format.
+    Unrecognized,
+    /// Reading raw PCM samples failed.
+    Raw(PcmRawError),
+    /// Opening a WAVE container failed.
+    Wav(PcmWavError),
+    /// Opening an AIFF container failed.
+    Aiff(PcmAiffError),
+    /// Opening or decoding a FLAC stream failed.
+    Flac(PcmFlacError),
+}
+crate::impl_trait![fmt::Display+Error for PcmFormatError |self, f| match self {
+    Self::Unrecognized => f.write_str("unrecognized audio format"),
+    Self::Raw(err) => write!(f, "raw PCM operation failed: {err}"),
+    Self::Wav(err) => write!(f, "WAVE operation failed: {err}"),
+    Self::Aiff(err) => write!(f, "AIFF operation failed: {err}"),
+    Self::Flac(err) => write!(f, "FLAC operation failed: {err}"),
+}];
+impl From<PcmRawError> for PcmFormatError {
+    fn from(err: PcmRawError) -> Self {
+        Self::Raw(err)
+    }
+}
+impl From<PcmWavError> for PcmFormatError {
+    fn from(err: PcmWavError) -> Self {
+        Self::Wav(err)
+    }
+}
+impl From<PcmAiffError> for PcmFormatError {
+    fn from(err: PcmAiffError) -> Self {
+        Self::Aiff(err)
+    }
+}
+impl From<PcmFlacError> for PcmFormatError {
+    fn from(err: PcmFlacError) -> Self {
+        Self::Flac(err)
+    }
+}
@@ -0,0 +1,258 @@
+// devela/src/media/audio/format/flac/_test/mod.rs
+//
+// TOC
+// - mod fixture
+// - mod info
+// - mod decode
+// - mod integrity
+// - mod reader
+// - owned
+
+#![allow(clippy::float_cmp, reason = "the expected samples are exact")]
+
+use super::*;
+use crate::{AudioChannels, PcmBuf, PcmSample, PcmSource, PcmSpec};
+
+/* fixtures */
+
+mod fixture {
+    /// Stereo, 16 bits, 44.1 kHz, 2000 frames in fixed blocks of 576 frames.
+    ///
+    /// Has one frame for each channel assignment, fixed and LPC subframes,
+    /// 4 and 5-bit Rice parameters with escaped partitions, and skips
+    /// `VORBIS_COMMENT` and `PADDING` metadata blocks.
+    pub(super) const STEREO_I16: &[u8] = include_bytes!("stereo_i16.flac");
+    /// Mono, 24 bits, 48 kHz, 5581 frames in variable blocks of 192 to 4096 frames.
+    ///
+    /// Has constant and verbatim subframes, wasted bits, a 8th order LPC,
+    /// explicit block sizes, and sample rates in kHz and tens of Hz.
+    pub(super) const MONO_I24: &[u8] = include_bytes!("mono_i24.flac");
+
+    /// Offset of the first frame of `STEREO_I16`.
+    pub(super) const STEREO_FIRST_FRAME: usize = 4 + (4 + 34) + (4 + 14) + (4 + 10);
+}
+
+/* parsing */
+
+mod info {
+    use super::*;
+
+    #[test]
+    fn parses_stream_info() {
+        let info = PcmFlac::info(fixture::STEREO_I16).unwrap();
+        assert_eq!((info.min_block_size, info.max_block_size), (272, 576));
+        assert_eq!((info.sample_rate, info.channels, info.bits_per_sample), (44_100, 2, 16));
+        assert_eq!(info.total_frames, 2000);
+        assert!(info.has_md5());
+        assert_eq!(info.max_block_samples(), 1152);
+        assert_eq!(info.spec(), PcmSpec::new(PcmSample::I16, AudioChannels::Stereo, 44_100));
+
+        let info = PcmFlac::info(fixture::MONO_I24).unwrap();
+        assert_eq!(info.spec(), PcmSpec::new(PcmSample::I24, AudioChannels::Mono, 48_000));
+        assert_eq!(info.total_frames, 5581);
+    }
+    #[test]
+    fn rejects_non_flac() {
+        assert_eq!(PcmFlac::info(b"RIFF\0\0\0\0WAVE"), Err(PcmFlacError::NotFlac));
+        assert!(!PcmFlac::is_flac(b"fLa"));
+    }
+    #[test]
+    fn rejects_truncated_metadata() {
+        assert_eq!(PcmFlac::info(&fixture::STEREO_I16[..30]), Err(PcmFlacError::Truncated));
+    }
+    #[test]
+    fn rejects_missing_stream_info() {
+        // A lone last PADDING block.
+        let bytes = b"fLaC\x81\0\0\x02\0\0";
+        assert_eq!(PcmFlac::info(bytes), Err(PcmFlacError::InvalidStreamInfo));
+    }
+}
+
+/* decoding */
+
+mod decode {
+    use super::*;
+
+    #[test]
+    fn decodes_stereo_channel_assignments() {
+        let mut out = [0i32; 2000 * 2];
+        let (info, frames) = PcmFlac::decode_into(fixture::STEREO_I16, &mut out).unwrap();
+        assert_eq!(frames, 2000);
+        assert_eq!(info.channels, 2);
+        assert_eq!(out[..8], [0, 7573, 860, 7991, 1702, 8338, 2512, 8612]);
+        assert_eq!(out[3996..], [-5037, 3360, -4381, 4124]);
+        let left: i64 = out.iter().step_by(2).map(|&s| s as i64).sum();
+        let right: i64 = out.iter().skip(1).step_by(2).map(|&s| s as i64).sum();
+        assert_eq!((left, right), (10_212, -57_316));
+    }
+    #[test]
+    fn decodes_variable_blocks() {
+        let mut decoder = PcmFlac::decoder(fixture::MONO_I24).unwrap();
+        let mut block = [0i32; 4096];
+        let mut sizes = [0; 5];
+        let mut sum = 0i64;
+        for size in &mut sizes {
+            *size = decoder.decode_block(&mut block).unwrap();
+            sum += block[..*size].iter().map(|&s| s as i64).sum::<i64>();
+            if *size == 256 {
+                assert!(block[..256].iter().all(|&s| s == -12345));
+            }
+        }
+        assert_eq!(sizes, [192, 1000, 256, 4096, 37]);
+        assert_eq!(block[34..37], [-2_753_418, -2_791_161, -2_828_427]);
+        assert_eq!(sum, 1_124_742_743);
+        assert_eq!(decoder.decode_block(&mut block), Ok(0));
+        assert!(decoder.is_finished());
+        assert_eq!(decoder.frames_decoded(), 5581);
+    }
+    #[test]
+    fn rewinds() {
+        let mut decoder = PcmFlac::decoder(fixture::MONO_I24).unwrap();
+        let mut block = [0i32; 4096];
+        decoder.decode_block(&mut block).unwrap();
+        decoder.rewind();
+        assert_eq!(decoder.frames_decoded(), 0);
+        assert_eq!(decoder.decode_block(&mut block), Ok(192));
+        assert_eq!(block[..3], [0, 52_358, 104_707]);
+    }
+    #[test]
+    fn rejects_small_destination() {
+        let mut decoder = PcmFlac::decoder(fixture::STEREO_I16).unwrap();
+        let mut block = [0i32; 1151];
+        assert_eq!(decoder.decode_block(&mut block), Err(PcmFlacError::NotEnoughSpace));
+    }
+}
+
+/* integrity */
+
+mod integrity {
+    use super::*;
+
+    fn decode_all(bytes: &[u8]) -> Result<usize, PcmFlacError> {
+        let mut out = [0i32; 2000 * 2];
+        PcmFlac::decode_into(bytes, &mut out).map(|(_, frames)| frames)
+    }
+
+    #[test]
+    fn detects_md5_mismatch() {
+        let mut bytes = [0u8; 8192];
+        let bytes = &mut bytes[..fixture::STEREO_I16.len()];
+        bytes.copy_from_slice(fixture::STEREO_I16);
+        // The MD5 signature is at the end of STREAMINFO.
+        bytes[4 + 4 + 18] ^= 1;
+        assert_eq!(decode_all(bytes), Err(PcmFlacError::Md5Mismatch));
+
+        let mut decoder = PcmFlac::decoder(bytes).unwrap();
+        decoder.set_verify_md5(false);
+        let mut block = [0i32; 1152];
+        while decoder.decode_block(&mut block).unwrap() != 0 {}
+    }
+    #[test]
+    fn detects_crc_mismatches() {
+        let mut bytes = [0u8; 8192];
+        let bytes = &mut bytes[..fixture::STEREO_I16.len()];
+        bytes.copy_from_slice(fixture::STEREO_I16);
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert_eq!(decode_all(bytes), Err(PcmFlacError::FrameCrcMismatch));
+        bytes[last] ^= 1;
+        bytes[fixture::STEREO_FIRST_FRAME + 5] ^= 1;
+        assert_eq!(decode_all(bytes), Err(PcmFlacError::HeaderCrcMismatch));
+        bytes[fixture::STEREO_FIRST_FRAME] = 0;
+        assert_eq!(decode_all(bytes), Err(PcmFlacError::LostSync));
+    }
+    #[test]
+    fn detects_truncation() {
+        let len = fixture::STEREO_I16.len();
+        assert_eq!(decode_all(&fixture::STEREO_I16[..len - 10]), Err(PcmFlacError::Truncated));
+        // Frames missing at the end of the stream.
+        let mono = fixture::MONO_I24;
+        let mut decoder = PcmFlac::decoder(&mono[..mono.len() - 120]).unwrap();
+        let mut block = [0i32; 4096];
+        for _ in 0..4 {
+            decoder.decode_block(&mut block).unwrap();
+        }
+        assert_eq!(decoder.decode_block(&mut block), Err(PcmFlacError::Truncated));
+    }
+}
+
+/* reader */
+
+mod reader {
+    use super::*;
+
+    #[test]
+    fn reads_normalized_samples() {
+        let mut ints = [0i32; 2000 * 2];
+        let _ = PcmFlac::decode_into(fixture::STEREO_I16, &mut ints).unwrap();
+        let mut block = [0i32; 1152];
+        let mut reader = PcmFlac::reader_with(fixture::STEREO_I16, &mut block[..]).unwrap();
+        let spec = reader.spec();
+        let mut out = [0.0f32; 2 * 300];
+        let mut at = 0;
+        loop {
+            let frames = reader.read(PcmBuf::from_interleaved_mut(&mut out, spec)).unwrap();
+            if frames == 0 {
+                break;
+            }
+            for (i, &s) in out[..frames * 2].iter().enumerate() {
+                assert_eq!(s, ints[at + i] as f32 / 32_768.0);
+            }
+            at += frames * 2;
+            assert_eq!(reader.position(), at as u64 / 2);
+        }
+        assert_eq!(at, ints.len());
+    }
+    #[test]
+    fn read_all_fails_past_the_end() {
+        let mut block = [0i32; 4096];
+        let mut reader = PcmFlac::reader_with(fixture::MONO_I24, &mut block[..]).unwrap();
+        let spec = reader.spec();
+        let mut out = [0.0f32; 5000];
+        reader.read_all(PcmBuf::from_interleaved_mut(&mut out, spec)).unwrap();
+        assert_eq!(out[2], 104_707.0 / 8_388_608.0);
+        let err = reader.read_all(PcmBuf::from_interleaved_mut(&mut out, spec));
+        assert_eq!(err, Err(PcmFlacError::UnexpectedEnd));
+        reader.rewind();
+        assert_eq!(reader.position(), 0);
+    }
+    #[test]
+    fn rejects_mismatched_buffers() {
+        let mut block = [0i32; 1024];
+        assert_eq!(
+            PcmFlac::reader_with(fixture::STEREO_I16, &mut block[..]).err(),
+            Some(PcmFlacError::NotEnoughSpace)
+        );
+        let mut block = [0i32; 1152];
+        let mut reader = PcmFlac::reader_with(fixture::STEREO_I16, &mut block[..]).unwrap();
+        let mono = PcmSpec::new(PcmSample::F32, AudioChannels::Mono, 44_100);
+        let mut out = [0.0f32; 4];
+        let err = reader.read(PcmBuf::from_interleaved_mut(&mut out, mono));
+        assert_eq!(err, Err(PcmFlacError::MismatchedSpec));
+    }
+}
+
+/* allocation */
+
+#[cfg(feature = "alloc")]
+mod owned {
+    use super::*;
+    use crate::{PcmFormat, PcmFormatReader};
+
+    #[test]
+    fn decodes_to_vec() {
+        let (info, samples) = PcmFlac::to_vec(fixture::MONO_I24).unwrap();
+        assert_eq!(samples.len() as u64, info.total_frames);
+        assert_eq!(samples[1200], -12345);
+    }
+    #[test]
+    fn opens_as_pcm_format() {
+        assert!(PcmFlac::probe(fixture::STEREO_I16));
+        let mut reader = PcmFormatReader::open(fixture::STEREO_I16).unwrap();
+        assert!(matches!(reader, PcmFormatReader::Flac(_)));
+        let spec = reader.spec();
+        let mut out = [0.0f32; 4];
+        reader.read_all(PcmBuf::from_interleaved_mut(&mut out, spec)).unwrap();
+        assert_eq!(out, [0.0, 7573.0 / 32_768.0, 860.0 / 32_768.0, 7991.0 / 32_768.0]);
+    }
+}
@@ -0,0 +1,453 @@
+// devela/src/media/audio/format/flac/decoder.rs
+//
+//! Defines [`PcmFlacDecoder`].
+//
+// TOC
+// - struct PcmFlacDecoder
+// - struct FlacBits
+// - fn decode_subframe
+// - fn decode_residual
+// - checksums
+
+use crate::{PcmFlacError, PcmFlacInfo, PcmSpec, is, whilst};
+use md5::FlacMd5;
+
+mod md5 {
+    #![allow(dead_code, reason = "only the streaming digest API is used")]
+    crate::digest! {
+        /// MD5 state for FLAC audio signatures.
+        pub(super) struct FlacMd5: Md5
+    }
+}
+
+#[doc = crate::_tags!(audio codec)]
+/// Block-by-block FLAC stream decoder.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Decodes each frame into interleaved `i32` samples that keep the original
+/// range of [`bits_per_sample`](PcmFlacInfo::bits_per_sample).
+///
+/// Supports every block size, sample rate and bit depth up to 32 bits,
+/// the constant, verbatim, fixed and LPC subframes, and the independent,
+/// left/side, side/right and mid/side channel assignments.
+///
+/// Every frame header and frame is checked against its CRC, and the whole audio
+/// is checked against the `STREAMINFO` MD5 signature once the stream ends.
+///
+/// Created with [`PcmFlac::decoder`].
+#[derive(Clone, Debug)]
+pub struct PcmFlacDecoder<'a> {
+    bytes: &'a [u8],
+    info: PcmFlacInfo,
+    /// Byte offset of the first frame.
+    first: usize,
+    /// Byte offset of the next frame.
+    pos: usize,
+    /// Frames decoded so far.
+    decoded: u64,
+    md5: FlacMd5,
+    verify: bool,
+    finished: bool,
+}
+
+impl<'a> PcmFlacDecoder<'a> {
+    /// Creates a decoder for the frames of `bytes` starting at offset `first`.
+    pub(crate) const fn _new(bytes: &'a [u8], info: PcmFlacInfo, first: usize) -> Self {
+        Self {
+            bytes,
+            info,
+            first,
+            pos: first,
+            decoded: 0,
+            md5: FlacMd5::new(),
+            verify: info.has_md5(),
+            finished: false,
+        }
+    }
+
+    /// Returns the parsed `STREAMINFO` metadata.
+    pub const fn info(&self) -> PcmFlacInfo {
+        self.info
+    }
+    /// Returns the stream metadata for the decoded samples.
+    pub const fn spec(&self) -> PcmSpec {
+        self.info.spec()
+    }
+    /// Returns the number of frames decoded so far.
+    #[must_use]
+    pub const fn frames_decoded(&self) -> u64 {
+        self.decoded
+    }
+    /// Returns whether the end of the stream has been reached.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// Sets whether to check the MD5 signature at the end of the stream.
+    ///
+    /// It's enabled by default when the stream carries a signature.
+    /// It has no effect once decoding has started.
+    pub const fn set_verify_md5(&mut self, verify: bool) {
+        is![self.decoded == 0, self.verify = verify && self.info.has_md5()];
+    }
+    /// Moves back to the first frame.
+    pub const fn rewind(&mut self) {
+        *self = Self::_new(self.bytes, self.info, self.first);
+    }
+
+    /// Decodes the next block into `out` as interleaved samples.
+    ///
+    /// Returns the number of frames decoded, or `0` at the end of the stream.
+    ///
+    /// # Errors
+    /// Returns [`PcmFlacError::NotEnoughSpace`] if `out` can't hold the block,
+    /// [`PcmFlacError::Md5Mismatch`] when the stream ends with a wrong signature,
+    /// or another error if the frame is malformed.
+    pub fn decode_block(&mut self, out: &mut [i32]) -> Result<usize, PcmFlacError> {
+        is![self.finished, return Ok(0)];
+        let total = self.info.total_frames;
+        if self.pos >= self.bytes.len() || (total != 0 && self.decoded >= total) {
+            self.finished = true;
+            is![total != 0 && self.decoded < total, return Err(PcmFlacError::Truncated)];
+            if self.verify && self.md5.finalize().0 != self.info.md5 {
+                return Err(PcmFlacError::Md5Mismatch);
+            }
+            return Ok(0);
+        }
+        let frame = &self.bytes[self.pos..];
+        let mut bits = FlacBits::new(frame);
+        let (block_size, assignment) = self.read_header(&mut bits)?;
+        let channels = self.info.channels as usize;
+        let bps = self.info.bits_per_sample as u32;
+        let Some(out) = out.get_mut(..block_size * channels) else {
+            return Err(PcmFlacError::NotEnoughSpace);
+        };
+        for ch in 0..channels {
+            // The side channel carries one more bit.
+            let side = matches!((assignment, ch), (8 | 10, 1) | (9, 0));
+            decode_subframe(&mut bits, out, ch, channels, bps + side as u32)?;
+        }
+        bits.align();
+        let len = bits.byte_pos();
+        let crc = bits.read(16)? as u16;
+        is![crc16(&frame[..len]) != crc, return Err(PcmFlacError::FrameCrcMismatch)];
+        decorrelate(out, assignment);
+        is![self.verify, self.update_md5(out)];
+        self.pos += len + 2;
+        self.decoded += block_size as u64;
+        Ok(block_size)
+    }
+
+    /// Reads a frame header, returning its block size and channel assignment.
+    fn read_header(&self, bits: &mut FlacBits) -> Result<(usize, u32), PcmFlacError> {
+        use PcmFlacError as E;
+        // 14-bit sync code and a reserved zero bit.
+        is![bits.read(15)? != 0x7FFC, return Err(E::LostSync)];
+        let _variable_blocking = bits.read(1)?;
+        let size_code = bits.read(4)?;
+        let rate_code = bits.read(4)?;
+        let assignment = bits.read(4)?;
+        let bps_code = bits.read(3)?;
+        is![bits.read(1)? != 0, return Err(E::InvalidFrameHeader)];
+        // UTF-8 like coded frame or sample number.
+        let extra = match (bits.read(8)? as u8).leading_ones() as u8 {
+            0 => 0,
+            n @ 2..=7 => n - 1,
+            _ => return Err(E::InvalidFrameHeader),
+        };
+        for _ in 0..extra {
+            is![bits.read(8)? & 0xC0 != 0x80, return Err(E::InvalidFrameHeader)];
+        }
+        let block_size = match size_code {
+            0 => return Err(E::InvalidFrameHeader),
+            1 => 192,
+            2..=5 => 576 << (size_code - 2),
+            6 => bits.read(8)? as usize + 1,
+            7 => bits.read(16)? as usize + 1,
+            _ => 256 << (size_code - 8),
+        };
+        match rate_code {
+            12 => _ = bits.read(8)?,
+            13 | 14 => _ = bits.read(16)?,
+            15 => return Err(E::InvalidFrameHeader),
+            _ => {}
+        }
+        let channels = match assignment {
+            0..=7 => assignment as u8 + 1,
+            8..=10 => 2,
+            _ => return Err(E::InvalidFrameHeader),
+        };
+        is![channels != self.info.channels, return Err(E::InvalidFrameHeader)];
+        let bps = match bps_code {
+            0 => self.info.bits_per_sample,
+            1 => 8,
+            2 => 12,
+            4 => 16,
+            5 => 20,
+            6 => 24,
+            7 => 32,
+            _ => return Err(E::InvalidFrameHeader),
+        };
+        is![bps != self.info.bits_per_sample, return Err(E::InvalidFrameHeader)];
+        is![bps == 32 && assignment > 7, return Err(E::UnsupportedBitsPerSample(bps))];
+        let len = bits.byte_pos();
+        is![crc8(&bits.bytes[..len]) != bits.read(8)? as u8, return Err(E::HeaderCrcMismatch)];
+        Ok((block_size, assignment))
+    }
+
+    /// Feeds decoded samples to the MD5 state, as packed little-endian bytes.
+    fn update_md5(&mut self, samples: &[i32]) {
+        let width = (self.info.bits_per_sample as usize).div_ceil(8);
+        let mut buf = [0u8; 256];
+        let mut len = 0;
+        for sample in samples {
+            buf[len..len + width].copy_from_slice(&sample.to_le_bytes()[..width]);
+            len += width;
+            if len + width > buf.len() {
+                // Can't fail before hashing 2^61 bytes.
+                let _ = self.md5.update(&buf[..len]);
+                len = 0;
+            }
+        }
+        let _ = self.md5.update(&buf[..len]);
+    }
+}
+
+/* bit reader */
+
+/// Big-endian bit reader over a FLAC frame.
+struct FlacBits<'a> {
+    bytes: &'a [u8],
+    /// Bit offset of the next read.
+    bit: usize,
+}
+impl<'a> FlacBits<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, bit: 0 }
+    }
+    /// Returns the offset of the current byte.
+    const fn byte_pos(&self) -> usize {
+        self.bit / 8
+    }
+    /// Skips to the next byte boundary.
+    const fn align(&mut self) {
+        self.bit = self.bit.next_multiple_of(8);
+    }
+    /// Reads up to 32 bits as an unsigned integer.
+    fn read(&mut self, n: u32) -> Result<u32, PcmFlacError> {
+        let end = self.bit + n as usize;
+        is![end > self.bytes.len() * 8, return Err(PcmFlacError::Truncated)];
+        let mut value = 0u64;
+        let mut bit = self.bit;
+        while bit < end {
+            let skip = bit % 8;
+            let take = (8 - skip).min(end - bit);
+            let byte = (self.bytes[bit / 8] >> (8 - skip - take)) as u64;
+            value = (value << take) | (byte & ((1 << take) - 1));
+            bit += take;
+        }
+        self.bit = end;
+        Ok(value as u32)
+    }
+    /// Reads up to 32 bits as a two's complement integer.
+    fn read_signed(&mut self, n: u32) -> Result<i32, PcmFlacError> {
+        is![n == 0, return Ok(0)];
+        let shift = 32 - n;
+        Ok(((self.read(n)? << shift) as i32) >> shift)
+    }
+    /// Reads a unary-coded number: a run of zeros ended by a one.
+    fn unary(&mut self) -> Result<u32, PcmFlacError> {
+        let mut zeros = 0;
+        loop {
+            let Some(&byte) = self.bytes.get(self.bit / 8) else {
+                return Err(PcmFlacError::Truncated);
+            };
+            let skip = self.bit % 8;
+            let rest = byte << skip;
+            if rest == 0 {
+                zeros += 8 - skip as u32;
+                self.bit += 8 - skip;
+            } else {
+                let z = rest.leading_zeros();
+                self.bit += z as usize + 1;
+                return Ok(zeros + z);
+            }
+        }
+    }
+}
+
+/* subframes */
+
+/// Coefficients of the fixed polynomial predictors, by order.
+const FIXED_COEFFICIENTS: [[i32; 4]; 5] =
+    [[0, 0, 0, 0], [1, 0, 0, 0], [2, -1, 0, 0], [3, -3, 1, 0], [4, -6, 4, -1]];
+
+/// Decodes one channel subframe into every `stride`th sample of `out`, from `ch`.
+fn decode_subframe(
+    bits: &mut FlacBits,
+    out: &mut [i32],
+    ch: usize,
+    stride: usize,
+    bps: u32,
+) -> Result<(), PcmFlacError> {
+    use PcmFlacError as E;
+    let n = out.len() / stride;
+    is![bits.read(1)? != 0, return Err(E::InvalidSubframe)];
+    let kind = bits.read(6)?;
+    let wasted = is![bits.read(1)? == 1, bits.unary()? + 1, 0];
+    is![wasted >= bps, return Err(E::InvalidSubframe)];
+    let bps = bps - wasted;
+    let at = |i: usize| i * stride + ch;
+    match kind {
+        0 => {
+            let value = bits.read_signed(bps)?;
+            for i in 0..n {
+                out[at(i)] = value;
+            }
+        }
+        1 => {
+            for i in 0..n {
+                out[at(i)] = bits.read_signed(bps)?;
+            }
+        }
+        8..=12 => {
+            let order = (kind - 8) as usize;
+            is![order > n, return Err(E::InvalidSubframe)];
+            for i in 0..order {
+                out[at(i)] = bits.read_signed(bps)?;
+            }
+            decode_residual(bits, out, ch, stride, order)?;
+            let coefs = &FIXED_COEFFICIENTS[order][..order];
+            predict(out, ch, stride, coefs, 0);
+        }
+        32..=63 => {
+            let order = (kind - 31) as usize;
+            is![order > n, return Err(E::InvalidSubframe)];
+            for i in 0..order {
+                out[at(i)] = bits.read_signed(bps)?;
+            }
+            let precision = bits.read(4)? + 1;
+            is![precision == 16, return Err(E::InvalidSubframe)];
+            let shift = bits.read_signed(5)?;
+            is![shift < 0, return Err(E::InvalidSubframe)];
+            let mut coefs = [0i32; 32];
+            for coef in &mut coefs[..order] {
+                *coef = bits.read_signed(precision)?;
+            }
+            decode_residual(bits, out, ch, stride, order)?;
+            predict(out, ch, stride, &coefs[..order], shift as u32);
+        }
+        _ => return Err(E::InvalidSubframe),
+    }
+    if wasted != 0 {
+        for i in 0..n {
+            out[at(i)] = out[at(i)].wrapping_shl(wasted);
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the Rice-coded residual after the `order` warm-up samples.
+fn decode_residual(
+    bits: &mut FlacBits,
+    out: &mut [i32],
+    ch: usize,
+    stride: usize,
+    order: usize,
+) -> Result<(), PcmFlacError> {
+    use PcmFlacError as E;
+    let n = out.len() / stride;
+    let (param_bits, escape) = match bits.read(2)? {
+        0 => (4, 0xF),
+        1 => (5, 0x1F),
+        _ => return Err(E::InvalidResidual),
+    };
+    let partition_order = bits.read(4)?;
+    let partitions = 1usize << partition_order;
+    is![!n.is_multiple_of(partitions), return Err(E::InvalidResidual)];
+    let partition_len = n >> partition_order;
+    is![partition_len < order, return Err(E::InvalidResidual)];
+    let mut i = order;
+    for p in 0..partitions {
+        let count = is![p == 0, partition_len - order, partition_len];
+        let k = bits.read(param_bits)?;
+        if k == escape {
+            let width = bits.read(5)?;
+            for _ in 0..count {
+                out[i * stride + ch] = bits.read_signed(width)?;
+                i += 1;
+            }
+        } else {
+            for _ in 0..count {
+                let q = bits.unary()?;
+                is![q > u32::MAX >> k, return Err(E::InvalidResidual)];
+                let folded = (q << k) | bits.read(k)?;
+                out[i * stride + ch] = (folded >> 1) as i32 ^ -((folded & 1) as i32);
+                i += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds the linear prediction to the residual stored after the warm-up samples.
+fn predict(out: &mut [i32], ch: usize, stride: usize, coefs: &[i32], shift: u32) {
+    let n = out.len() / stride;
+    for i in coefs.len()..n {
+        let mut sum = 0i64;
+        for (j, &coef) in coefs.iter().enumerate() {
+            sum += coef as i64 * out[(i - 1 - j) * stride + ch] as i64;
+        }
+        let at = i * stride + ch;
+        out[at] = (out[at] as i64 + (sum >> shift)) as i32;
+    }
+}
+
+/// Restores left and right from an inter-channel stereo assignment.
+fn decorrelate(out: &mut [i32], assignment: u32) {
+    if assignment < 8 {
+        return;
+    }
+    for frame in out.chunks_exact_mut(2) {
+        let (a, b) = (frame[0] as i64, frame[1] as i64);
+        let (left, right) = match assignment {
+            8 => (a, a - b),
+            9 => (a + b, b),
+            _ => {
+                let mid = (a << 1) | (b & 1);
+                ((mid + b) >> 1, (mid - b) >> 1)
+            }
+        };
+        frame[0] = left as i32;
+        frame[1] = right as i32;
+    }
+}
+
+/* checksums */
+
+/// CRC-8 with polynomial `0x07`, over frame headers.
+fn crc8(bytes: &[u8]) -> u8 {
+    const TABLE: [u8; 256] = {
+        let mut table = [0; 256];
+        whilst! { i in 0..256; {
+            let mut c = i as u8;
+            whilst! { _k in 0..8; { c = is![c & 0x80 != 0, (c << 1) ^ 0x07, c << 1]; }}
+            table[i] = c;
+        }}
+        table
+    };
+    bytes.iter().fold(0, |crc, &b| TABLE[(crc ^ b) as usize])
+}
+/// CRC-16 with polynomial `0x8005`, over whole frames.
+fn crc16(bytes: &[u8]) -> u16 {
+    const TABLE: [u16; 256] = {
+        let mut table = [0; 256];
+        whilst! { i in 0..256; {
+            let mut c = (i as u16) << 8;
+            whilst! { _k in 0..8; { c = is![c & 0x8000 != 0, (c << 1) ^ 0x8005, c << 1]; }}
+            table[i] = c;
+        }}
+        table
+    };
+    bytes.iter().fold(0, |crc, &b| (crc << 8) ^ TABLE[((crc >> 8) as u8 ^ b) as usize])
+}
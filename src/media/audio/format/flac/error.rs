@@ -0,0 +1,72 @@
+// devela/src/media/audio/format/flac/error.rs
+//
+//! Defines [`PcmFlacError`].
+//
+
+#[doc = crate::_tags!(audio error)]
+/// FLAC decoding error.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(PcmFlacError = 2|16)}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PcmFlacError {
+    /// The stream doesn't start with the `fLaC` marker.
+    NotFlac,
+
+    /// The data ends in the middle of a metadata block or frame.
+    Truncated,
+
+    /// The first metadata block is not a valid `STREAMINFO`.
+    InvalidStreamInfo,
+
+    /// A metadata block header is invalid.
+    InvalidMetadata,
+
+    /// A frame doesn't start with the frame sync code.
+    LostSync,
+
+    /// A frame header is malformed or disagrees with `STREAMINFO`.
+    InvalidFrameHeader,
+
+    /// The CRC-8 of a frame header doesn't match.
+    HeaderCrcMismatch,
+
+    /// The CRC-16 of a frame doesn't match.
+    FrameCrcMismatch,
+
+    /// A subframe is malformed.
+    InvalidSubframe,
+
+    /// A residual block is malformed.
+    InvalidResidual,
+
+    /// The bits per sample are not supported.
+    UnsupportedBitsPerSample(u8),
+
+    /// The decoded audio doesn't match the MD5 signature in `STREAMINFO`.
+    Md5Mismatch,
+
+    /// The destination buffer is too small for a block.
+    NotEnoughSpace,
+
+    /// A destination buffer doesn't match the stream channels or rate.
+    MismatchedSpec,
+
+    /// Fewer frames are left than requested.
+    UnexpectedEnd,
+}
+crate::impl_trait![fmt::Display+Error for PcmFlacError |self, f| match self {
+    Self::NotFlac => f.write_str("missing `fLaC` stream marker"),
+    Self::Truncated => f.write_str("FLAC data ends unexpectedly"),
+    Self::InvalidStreamInfo => f.write_str("missing or invalid FLAC `STREAMINFO` block"),
+    Self::InvalidMetadata => f.write_str("invalid FLAC metadata block header"),
+    Self::LostSync => f.write_str("FLAC frame sync code not found"),
+    Self::InvalidFrameHeader => f.write_str("invalid FLAC frame header"),
+    Self::HeaderCrcMismatch => f.write_str("FLAC frame header CRC-8 mismatch"),
+    Self::FrameCrcMismatch => f.write_str("FLAC frame CRC-16 mismatch"),
+    Self::InvalidSubframe => f.write_str("invalid FLAC subframe"),
+    Self::InvalidResidual => f.write_str("invalid FLAC residual"),
+    Self::UnsupportedBitsPerSample(bits) => write!(f, "unsupported FLAC bits per sample: {bits}"),
+    Self::Md5Mismatch => f.write_str("decoded FLAC audio doesn't match its MD5 signature"),
+    Self::NotEnoughSpace => f.write_str("not enough space to decode a FLAC block"),
+    Self::MismatchedSpec => f.write_str("buffer doesn't match the FLAC stream spec"),
+    Self::UnexpectedEnd => f.write_str("fewer FLAC frames left than requested"),
+}];
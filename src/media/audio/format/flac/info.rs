@@ -0,0 +1,113 @@
+// devela/src/media/audio/format/flac/info.rs
+//
+//! Defines [`PcmFlacInfo`].
+//
+
+use crate::{AudioChannels, PcmFlacError, PcmSample, PcmSpec, is, read_at};
+
+#[doc = crate::_tags!(audio parser)]
+/// Parsed FLAC `STREAMINFO` metadata block.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(PcmFlacInfo = 48|384)}]
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PcmFlacInfo {
+    /// Minimum block size in frames, except for the last block.
+    pub min_block_size: u16,
+    /// Maximum block size in frames.
+    pub max_block_size: u16,
+    /// Minimum encoded frame size in bytes, or `0` if unknown.
+    pub min_frame_size: u32,
+    /// Maximum encoded frame size in bytes, or `0` if unknown.
+    pub max_frame_size: u32,
+    /// Samples per second.
+    pub sample_rate: u32,
+    /// Number of channels, from 1 to 8.
+    pub channels: u8,
+    /// Bits per sample, from 4 to 32.
+    pub bits_per_sample: u8,
+    /// Total number of frames, or `0` if unknown.
+    pub total_frames: u64,
+    /// MD5 signature of the decoded audio, or all zeros if unknown.
+    pub md5: [u8; 16],
+}
+
+impl PcmFlacInfo {
+    /// The length of the `STREAMINFO` payload.
+    pub const LEN: usize = 34;
+
+    /// Parses a `STREAMINFO` payload.
+    pub const fn parse(bytes: &[u8]) -> Result<Self, PcmFlacError> {
+        is![bytes.len() != Self::LEN, return Err(PcmFlacError::InvalidStreamInfo)];
+        let packed = u64::from_be_bytes(read_at![bytes, 10, @8]);
+        let info = Self {
+            min_block_size: u16::from_be_bytes(read_at![bytes, 0, @2]),
+            max_block_size: u16::from_be_bytes(read_at![bytes, 2, @2]),
+            min_frame_size: u32::from_be_bytes([0, bytes[4], bytes[5], bytes[6]]),
+            max_frame_size: u32::from_be_bytes([0, bytes[7], bytes[8], bytes[9]]),
+            sample_rate: (packed >> 44) as u32,
+            channels: ((packed >> 41) & 0x7) as u8 + 1,
+            bits_per_sample: ((packed >> 36) & 0x1F) as u8 + 1,
+            total_frames: packed & 0xF_FFFF_FFFF,
+            md5: read_at![bytes, 18, @16],
+        };
+        is![
+            info.min_block_size < 16 || info.max_block_size < info.min_block_size,
+            return Err(PcmFlacError::InvalidStreamInfo)
+        ];
+        is![info.sample_rate == 0, return Err(PcmFlacError::InvalidStreamInfo)];
+        is![
+            info.bits_per_sample < 4,
+            return Err(PcmFlacError::UnsupportedBitsPerSample(info.bits_per_sample))
+        ];
+        Ok(info)
+    }
+
+    /// Returns whether the stream carries an MD5 signature.
+    #[must_use]
+    pub const fn has_md5(&self) -> bool {
+        let mut i = 0;
+        while i < 16 {
+            is![self.md5[i] != 0, return true];
+            i += 1;
+        }
+        false
+    }
+
+    /// Returns the number of interleaved samples needed to hold the largest block.
+    #[must_use]
+    pub const fn max_block_samples(&self) -> usize {
+        self.max_block_size as usize * self.channels as usize
+    }
+
+    /// Returns the channel layout, following the FLAC channel order.
+    pub const fn audio_channels(&self) -> AudioChannels {
+        match self.channels {
+            1 => AudioChannels::Mono,
+            2 => AudioChannels::Stereo,
+            3 => AudioChannels::Surround3_0,
+            4 => AudioChannels::Surround4_0,
+            5 => AudioChannels::Surround5_0,
+            6 => AudioChannels::Surround5_1,
+            7 => AudioChannels::Surround7_0,
+            _ => AudioChannels::Surround7_1,
+        }
+    }
+
+    /// Returns the narrowest [`PcmSample`] integer that holds the decoded samples.
+    ///
+    /// Decoded samples keep their original range, i.e. 12-bit samples are
+    /// returned as `i16` values between `-2048` and `2047`.
+    pub const fn sample(&self) -> PcmSample {
+        match self.bits_per_sample {
+            0..=8 => PcmSample::I8,
+            9..=16 => PcmSample::I16,
+            17..=24 => PcmSample::I24,
+            _ => PcmSample::I32,
+        }
+    }
+
+    /// Maps this stream info to [`PcmSpec`] metadata.
+    pub const fn spec(&self) -> PcmSpec {
+        PcmSpec::new(self.sample(), self.audio_channels(), self.sample_rate)
+    }
+}
@@ -0,0 +1,30 @@
+// devela/src/media/audio/format/flac/mod.rs
+//
+//! FLAC decoding.
+//!
+//! This module decodes Free Lossless Audio Codec streams into interleaved
+//! integer samples, or normalized `f32` samples through [`PcmSource`].
+//!
+//! [`PcmSource`]: crate::PcmSource
+//
+
+#[cfg(test)]
+mod _test;
+
+mod decoder; // PcmFlacDecoder
+mod error; // PcmFlacError
+mod info; // PcmFlacInfo
+mod namespace; // PcmFlac
+mod reader; // PcmFlacReader
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            decoder::*,
+            error::*,
+            info::*,
+            namespace::*,
+            reader::*,
+        };
+    }
+}
@@ -0,0 +1,189 @@
+// devela/src/media/audio/format/flac/namespace.rs
+//
+//! Defines [`PcmFlac`].
+//
+
+use crate::{PcmFlacDecoder, PcmFlacError, PcmFlacInfo, PcmFlacReader, is, unwrap};
+#[cfg(feature = "alloc")]
+use crate::{Vec, vec_};
+
+#[doc = crate::_tags!(audio codec)]
+/// FLAC operations for lossless audio.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+/// `PcmFlac` is a namespace for decoding borrowed FLAC streams.
+///
+/// It supports:
+/// - parsing the `STREAMINFO` metadata, skipping every other metadata block,
+/// - decoding blocks of interleaved `i32` samples with [`PcmFlacDecoder`],
+/// - decoding whole streams into caller-provided or allocated storage,
+/// - reading normalized `f32` samples with [`PcmFlacReader`].
+///
+/// Encoding is not supported.
+///
+/// # Example
+/// ```
+/// # use devela::{PcmFlac, PcmFlacError};
+/// # fn main() -> Result<(), PcmFlacError> {
+/// # let bytes = include_bytes!("_test/mono_i24.flac");
+/// let info = PcmFlac::info(bytes)?;
+/// assert_eq!((info.channels, info.bits_per_sample, info.sample_rate), (1, 24, 48_000));
+///
+/// let mut decoder = PcmFlac::decoder(bytes)?;
+/// let mut block = [0i32; 4096];
+/// let mut frames = 0;
+/// loop {
+///     let n = decoder.decode_block(&mut block)?;
+///     if n == 0 { break; }
+///     frames += n as u64;
+/// }
+/// assert_eq!(frames, info.total_frames);
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct PcmFlac;
+
+/// # Parsing
+impl PcmFlac {
+    /// The stream marker that starts every FLAC stream.
+    pub const MAGIC: [u8; 4] = *b"fLaC";
+
+    /// Returns whether `bytes` starts with the FLAC stream marker.
+    #[must_use]
+    pub const fn is_flac(bytes: &[u8]) -> bool {
+        bytes.len() >= 4
+            && bytes[0] == Self::MAGIC[0]
+            && bytes[1] == Self::MAGIC[1]
+            && bytes[2] == Self::MAGIC[2]
+            && bytes[3] == Self::MAGIC[3]
+    }
+
+    /// Parses the `STREAMINFO` metadata of a FLAC stream.
+    pub const fn info(bytes: &[u8]) -> Result<PcmFlacInfo, PcmFlacError> {
+        match Self::parse_metadata(bytes) {
+            Ok((info, _)) => Ok(info),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parses the metadata of a FLAC stream and returns a decoder for its frames.
+    pub const fn decoder(bytes: &[u8]) -> Result<PcmFlacDecoder<'_>, PcmFlacError> {
+        let (info, first) = unwrap![ok? Self::parse_metadata(bytes)];
+        Ok(PcmFlacDecoder::_new(bytes, info, first))
+    }
+
+    /// Returns a reader that decodes into the caller-provided `block` storage.
+    ///
+    /// # Errors
+    /// Returns [`PcmFlacError::NotEnoughSpace`] if `block` can't hold
+    /// [`max_block_samples`](PcmFlacInfo::max_block_samples).
+    pub fn reader_with<B: AsMut<[i32]>>(
+        bytes: &[u8],
+        mut block: B,
+    ) -> Result<PcmFlacReader<'_, B>, PcmFlacError> {
+        let decoder = Self::decoder(bytes)?;
+        let needed = decoder.info().max_block_samples();
+        is![block.as_mut().len() < needed, return Err(PcmFlacError::NotEnoughSpace)];
+        Ok(PcmFlacReader::_new(decoder, block))
+    }
+
+    /// Returns a reader that allocates the storage for the largest block.
+    #[cfg(feature = "alloc")]
+    pub fn reader(bytes: &[u8]) -> Result<PcmFlacReader<'_, Vec<i32>>, PcmFlacError> {
+        let decoder = Self::decoder(bytes)?;
+        let block = vec_![0; decoder.info().max_block_samples()];
+        Ok(PcmFlacReader::_new(decoder, block))
+    }
+
+    /// Walks the metadata blocks, returning the stream info and the offset of the first frame.
+    const fn parse_metadata(bytes: &[u8]) -> Result<(PcmFlacInfo, usize), PcmFlacError> {
+        use PcmFlacError as E;
+        is![!Self::is_flac(bytes), return Err(E::NotFlac)];
+        let mut offset = Self::MAGIC.len();
+        let mut info: Option<PcmFlacInfo> = None;
+        loop {
+            is![offset + 4 > bytes.len(), return Err(E::Truncated)];
+            let header = bytes[offset];
+            let (last, kind) = (header & 0x80 != 0, header & 0x7F);
+            let len =
+                u32::from_be_bytes([0, bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+                    as usize;
+            offset += 4;
+            is![offset + len > bytes.len(), return Err(E::Truncated)];
+            is![kind == Self::BLOCK_INVALID, return Err(E::InvalidMetadata)];
+            match (kind, info.is_some()) {
+                (Self::BLOCK_STREAMINFO, false) => {
+                    let (_, data) = bytes.split_at(offset);
+                    let (data, _) = data.split_at(len);
+                    info = Some(unwrap![ok? PcmFlacInfo::parse(data)]);
+                }
+                (Self::BLOCK_STREAMINFO, true) | (_, false) => return Err(E::InvalidStreamInfo),
+                _ => {}
+            }
+            offset += len;
+            is![last, break];
+        }
+        match info {
+            Some(info) => Ok((info, offset)),
+            None => Err(E::InvalidStreamInfo),
+        }
+    }
+}
+/// # Decoding
+impl PcmFlac {
+    /// Decodes a whole FLAC stream into `out` as interleaved samples.
+    ///
+    /// Returns the stream info and the number of frames decoded.
+    ///
+    /// # Errors
+    /// Returns [`PcmFlacError::NotEnoughSpace`] if `out` can't hold the next block,
+    /// or any error from [`PcmFlacDecoder::decode_block`].
+    pub fn decode_into(
+        bytes: &[u8],
+        out: &mut [i32],
+    ) -> Result<(PcmFlacInfo, usize), PcmFlacError> {
+        let mut decoder = Self::decoder(bytes)?;
+        let channels = decoder.info().channels as usize;
+        let mut samples = 0;
+        loop {
+            let frames = decoder.decode_block(&mut out[samples..])?;
+            is![frames == 0, break];
+            samples += frames * channels;
+        }
+        Ok((decoder.info(), samples / channels))
+    }
+
+    /// Decodes a whole FLAC stream into an allocated vector of interleaved samples.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(bytes: &[u8]) -> Result<(PcmFlacInfo, Vec<i32>), PcmFlacError> {
+        let mut decoder = Self::decoder(bytes)?;
+        let info = decoder.info();
+        let total = usize::try_from(info.total_frames).unwrap_or(0);
+        let mut out = Vec::with_capacity(total * info.channels as usize);
+        let mut block = vec_![0; info.max_block_samples()];
+        loop {
+            let frames = decoder.decode_block(&mut block)?;
+            is![frames == 0, break];
+            out.extend_from_slice(&block[..frames * info.channels as usize]);
+        }
+        Ok((info, out))
+    }
+}
+/// # Constants
+impl PcmFlac {
+    /// The `STREAMINFO` metadata block type.
+    pub const BLOCK_STREAMINFO: u8 = 0;
+    /// The `PADDING` metadata block type.
+    pub const BLOCK_PADDING: u8 = 1;
+    /// The `APPLICATION` metadata block type.
+    pub const BLOCK_APPLICATION: u8 = 2;
+    /// The `SEEKTABLE` metadata block type.
+    pub const BLOCK_SEEKTABLE: u8 = 3;
+    /// The `VORBIS_COMMENT` metadata block type.
+    pub const BLOCK_VORBIS_COMMENT: u8 = 4;
+    /// The `CUESHEET` metadata block type.
+    pub const BLOCK_CUESHEET: u8 = 5;
+    /// The `PICTURE` metadata block type.
+    pub const BLOCK_PICTURE: u8 = 6;
+    /// The forbidden metadata block type.
+    pub const BLOCK_INVALID: u8 = 127;
+}
@@ -0,0 +1,111 @@
+// devela/src/media/audio/format/flac/reader.rs
+//
+//! Defines [`PcmFlacReader`].
+//
+
+use crate::{PcmBuf, PcmFlacDecoder, PcmFlacError, PcmLayout, PcmSource, PcmSpec, PcmStream, is};
+
+#[doc = crate::_tags!(audio codec)]
+/// Sequential `f32` reader over a FLAC stream.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Decodes one block at a time into the `B` storage, which must hold
+/// [`max_block_samples`](crate::PcmFlacInfo::max_block_samples) samples,
+/// and hands out normalized `f32` samples through [`PcmSource`].
+///
+/// Created with [`PcmFlac::reader`][crate::PcmFlac::reader], or without allocation
+/// with [`PcmFlac::reader_with`][crate::PcmFlac::reader_with].
+#[derive(Clone, Debug)]
+pub struct PcmFlacReader<'a, B> {
+    decoder: PcmFlacDecoder<'a>,
+    block: B,
+    /// Samples in the current block.
+    len: usize,
+    /// Samples of the current block already read.
+    at: usize,
+}
+
+impl<'a, B: AsMut<[i32]>> PcmFlacReader<'a, B> {
+    /// Creates a reader from a decoder and its block storage.
+    pub(crate) const fn _new(decoder: PcmFlacDecoder<'a>, block: B) -> Self {
+        Self { decoder, block, len: 0, at: 0 }
+    }
+
+    /// Returns the underlying decoder.
+    pub const fn decoder(&self) -> &PcmFlacDecoder<'a> {
+        &self.decoder
+    }
+    /// Returns the stream metadata.
+    pub const fn spec(&self) -> PcmSpec {
+        self.decoder.spec()
+    }
+    /// Returns the number of frames already read.
+    #[must_use]
+    pub const fn position(&self) -> u64 {
+        let pending = (self.len - self.at) / self.decoder.info().channels as usize;
+        self.decoder.frames_decoded() - pending as u64
+    }
+    /// Moves back to the first frame.
+    pub const fn rewind(&mut self) {
+        self.decoder.rewind();
+        self.len = 0;
+        self.at = 0;
+    }
+    /// Returns the block storage.
+    pub fn into_block(self) -> B {
+        self.block
+    }
+}
+
+impl<B: AsMut<[i32]>> PcmStream for PcmFlacReader<'_, B> {
+    type Error = PcmFlacError;
+    fn pcm_spec(&self) -> Option<PcmSpec> {
+        Some(self.decoder.spec())
+    }
+    fn pcm_layout(&self) -> Option<PcmLayout> {
+        Some(PcmLayout::Interleaved)
+    }
+}
+impl<B: AsMut<[i32]>> PcmSource<f32> for PcmFlacReader<'_, B> {
+    /// Reads up to the requested frames, returning `0` at the end of the stream.
+    ///
+    /// # Errors
+    /// Returns [`PcmFlacError::MismatchedSpec`] if `pcm` doesn't match the stream
+    /// or has incomplete frames, or any error from decoding a block.
+    fn read(&mut self, mut pcm: PcmBuf<f32, &mut [f32]>) -> Result<usize, Self::Error> {
+        let spec = self.decoder.spec();
+        is![pcm.channels() != spec.channels, return Err(PcmFlacError::MismatchedSpec)];
+        is![pcm.sample_rate() != spec.sample_rate, return Err(PcmFlacError::MismatchedSpec)];
+        let frames = pcm.frames().ok_or(PcmFlacError::MismatchedSpec)?;
+        let scale = 1.0 / (1u64 << (self.decoder.info().bits_per_sample - 1)) as f32;
+        let out = &mut pcm.data_mut()[..frames * spec.channel_count()];
+        let mut written = 0;
+        while written < out.len() {
+            if self.at == self.len {
+                let block = self.block.as_mut();
+                let decoded = self.decoder.decode_block(block)?;
+                is![decoded == 0, break];
+                self.len = decoded * spec.channel_count();
+                self.at = 0;
+            }
+            let n = (self.len - self.at).min(out.len() - written);
+            let block = &self.block.as_mut()[self.at..self.at + n];
+            for (dst, &src) in out[written..written + n].iter_mut().zip(block) {
+                *dst = src as f32 * scale;
+            }
+            self.at += n;
+            written += n;
+        }
+        Ok(written / spec.channel_count())
+    }
+    /// Reads exactly the requested frames.
+    ///
+    /// # Errors
+    /// Like [`read`](Self::read), and [`PcmFlacError::UnexpectedEnd`]
+    /// after reading the remaining frames if fewer are left.
+    fn read_all(&mut self, pcm: PcmBuf<f32, &mut [f32]>) -> Result<(), Self::Error> {
+        let wanted = pcm.frames().ok_or(PcmFlacError::MismatchedSpec)?;
+        is![self.read(pcm)? < wanted, return Err(PcmFlacError::UnexpectedEnd)];
+        Ok(())
+    }
+}
@@ -5,8 +5,9 @@
 #![doc = crate::_doc!(flat:"media")]
 #![doc = crate::_doc!(hr)]
 
-// mod aiff; // PcmAiff
-// mod flac;
+mod aiff; // Audio Interchange File Format
+mod any; // PcmFormat, PcmFormatReader
+mod flac; // Free Lossless Audio Codec
 // mod mp3; // Mp3Decoder
 // mod mulaw; // MuLaw
 // mod ogg;
@@ -16,6 +17,9 @@ mod wav; // Waveform Audio File Format
 crate::structural_mods! { // _mods
     _mods {
         pub use super::{
+            aiff::*,
+            any::*,
+            flac::*,
             // mp3::*,
             // mulaw::*,
             raw::*,
@@ -28,6 +28,9 @@ pub enum PcmRawError {
     /// The destination buffer is too small.
     NotEnoughSpace,
 
+    /// Fewer frames are left than requested.
+    UnexpectedEnd,
+
     /// Reading or writing a file failed.
     #[cfg(feature = "std")]
     Io(IoErrorKind),
@@ -43,6 +46,7 @@ crate::impl_trait![fmt::Display+Error for PcmRawError |self, f| match self {
     }
     Self::SampleOutOfRange => f.write_str("PCM sample value is outside the target range"),
     Self::NotEnoughSpace => f.write_str("not enough space to write raw PCM data"),
+    Self::UnexpectedEnd => f.write_str("fewer raw PCM frames left than requested"),
     #[cfg(feature = "std")]
     Self::Io(err) => write!(f, "raw PCM file operation failed: {err}"),
 }];
@@ -9,6 +9,7 @@ mod _test;
 mod buf; // PcmRawBuf
 mod error; // PcmRawError
 mod namespace; // PcmRaw
+mod reader; // PcmRawReader
 
 crate::structural_mods! { // _mods
     _mods {
@@ -16,6 +17,7 @@ crate::structural_mods! { // _mods
             buf::*,
             error::*,
             namespace::*,
+            reader::*,
         };
     }
 }
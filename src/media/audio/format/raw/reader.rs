@@ -0,0 +1,160 @@
+// devela/src/media/audio/format/raw/reader.rs
+//
+//! Defines [`PcmRawReader`].
+//
+
+use crate::{PcmBuf, PcmLayout, PcmRawBuf, PcmRawError, PcmSample, PcmSource, PcmSpec, PcmStream};
+use crate::{is, read_at};
+
+#[doc = crate::_tags!(audio)]
+/// Sequential `f32` reader over raw interleaved PCM bytes.
+#[doc = crate::_doc_meta!{
+    location("media/audio"),
+    #[cfg(target_pointer_width = "32")]
+    test_size_of(PcmRawReader = 24|192),
+    #[cfg(target_pointer_width = "64")]
+    test_size_of(PcmRawReader = 40|320),
+}]
+///
+/// Converts every sample encoding to normalized `f32` through [`PcmSource`],
+/// so raw bytes from any container can be consumed the same way.
+///
+/// Samples are little-endian unless created with [`new_be`](Self::new_be),
+/// as used by big-endian containers like AIFF.
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, PcmBuf, PcmRaw, PcmRawReader, PcmSample, PcmSource, PcmSpec};
+/// let spec = PcmSpec::new(PcmSample::I16, AudioChannels::Mono, 8_000);
+/// let raw = PcmRaw::from_bytes(&[0x00, 0x40, 0x00, 0xC0], spec).unwrap();
+/// let mut reader = PcmRawReader::new(raw);
+///
+/// let mut out = [0.0f32; 4];
+/// let frames = reader.read(PcmBuf::from_interleaved_mut(&mut out, spec)).unwrap();
+/// assert_eq!(frames, 2);
+/// assert_eq!(out[..2], [0.5, -0.5]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PcmRawReader<'a> {
+    bytes: &'a [u8],
+    spec: PcmSpec,
+    big_endian: bool,
+    /// Byte offset of the next frame.
+    pos: usize,
+}
+
+impl<'a> PcmRawReader<'a> {
+    /// Creates a reader over little-endian raw PCM.
+    pub const fn new(raw: PcmRawBuf<&'a [u8]>) -> Self {
+        Self {
+            bytes: raw.bytes_const(),
+            spec: raw.spec(),
+            big_endian: false,
+            pos: 0,
+        }
+    }
+    /// Creates a reader over big-endian raw PCM.
+    pub const fn new_be(raw: PcmRawBuf<&'a [u8]>) -> Self {
+        Self {
+            bytes: raw.bytes_const(),
+            spec: raw.spec(),
+            big_endian: true,
+            pos: 0,
+        }
+    }
+
+    /// Returns the stream metadata.
+    pub const fn spec(&self) -> PcmSpec {
+        self.spec
+    }
+    /// Returns whether samples are stored big-endian.
+    #[must_use]
+    pub const fn is_big_endian(&self) -> bool {
+        self.big_endian
+    }
+    /// Returns the total number of frames.
+    #[must_use]
+    pub const fn frames(&self) -> usize {
+        self.bytes.len() / self.spec.frame_bytes()
+    }
+    /// Returns the number of frames already read.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.pos / self.spec.frame_bytes()
+    }
+    /// Returns the number of frames left to read.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.frames() - self.position()
+    }
+    /// Moves back to the first frame.
+    pub const fn rewind(&mut self) {
+        self.pos = 0;
+    }
+
+    /// Converts the sample at the start of `bytes` to normalized `f32`.
+    const fn sample_f32(bytes: &[u8], sample: PcmSample, be: bool) -> f32 {
+        macro_rules! int {
+            ($T:ty, $n:tt) => {{
+                let b = read_at![bytes, 0, @$n];
+                is![be, <$T>::from_be_bytes(b), <$T>::from_le_bytes(b)]
+            }};
+        }
+        match sample {
+            PcmSample::U8 => (bytes[0] as f32 - 128.0) / 128.0,
+            PcmSample::I8 => bytes[0] as i8 as f32 / 128.0,
+            PcmSample::I16 => int!(i16, 2) as f32 / 32_768.0,
+            PcmSample::I24 => {
+                let (hi, mid, lo) =
+                    is![be, (bytes[0], bytes[1], bytes[2]), (bytes[2], bytes[1], bytes[0])];
+                let v = i32::from_be_bytes([hi, mid, lo, 0]) >> 8;
+                v as f32 / 8_388_608.0
+            }
+            PcmSample::I32 => int!(i32, 4) as f32 / 2_147_483_648.0,
+            PcmSample::F32 => int!(f32, 4),
+            PcmSample::F64 => int!(f64, 8) as f32,
+        }
+    }
+}
+
+impl PcmStream for PcmRawReader<'_> {
+    type Error = PcmRawError;
+    fn pcm_spec(&self) -> Option<PcmSpec> {
+        Some(self.spec)
+    }
+    fn pcm_layout(&self) -> Option<PcmLayout> {
+        Some(PcmLayout::Interleaved)
+    }
+}
+impl PcmSource<f32> for PcmRawReader<'_> {
+    /// Reads up to the requested frames, returning `0` at the end of the data.
+    ///
+    /// # Errors
+    /// Returns [`PcmRawError::InvalidSpec`] if the channels or rate of `pcm` don't match,
+    /// or [`PcmRawError::InvalidDataLength`] if it has incomplete frames.
+    fn read(&mut self, mut pcm: PcmBuf<f32, &mut [f32]>) -> Result<usize, Self::Error> {
+        let spec = self.spec;
+        is![pcm.channels() != spec.channels, return Err(PcmRawError::InvalidSpec)];
+        is![pcm.sample_rate() != spec.sample_rate, return Err(PcmRawError::InvalidSpec)];
+        let wanted = pcm.frames().ok_or(PcmRawError::InvalidDataLength)?;
+        let frames = wanted.min(self.remaining());
+        let width = spec.sample.bytes();
+        let samples = frames * spec.channel_count();
+        for (i, out) in pcm.data_mut()[..samples].iter_mut().enumerate() {
+            let at = self.pos + i * width;
+            *out = Self::sample_f32(&self.bytes[at..at + width], spec.sample, self.big_endian);
+        }
+        self.pos += samples * width;
+        Ok(frames)
+    }
+    /// Reads exactly the requested frames.
+    ///
+    /// # Errors
+    /// Like [`read`](Self::read), and [`PcmRawError::UnexpectedEnd`]
+    /// without consuming anything if fewer frames are left.
+    fn read_all(&mut self, pcm: PcmBuf<f32, &mut [f32]>) -> Result<(), Self::Error> {
+        let wanted = pcm.frames().ok_or(PcmRawError::InvalidDataLength)?;
+        is![wanted > self.remaining(), return Err(PcmRawError::UnexpectedEnd)];
+        self.read(pcm).map(|_| ())
+    }
+}
@@ -0,0 +1,197 @@
+// devela/src/media/audio/music/_test.rs
+
+use crate::{MusicChord, MusicChordKind, MusicDuration, MusicEvent, MusicInterval, MusicMode};
+use crate::{MusicNote, MusicPitchClass as P, MusicScale, MusicTuning};
+
+fn note(name: &str) -> MusicNote {
+    MusicNote::from_name(name).unwrap()
+}
+
+#[test]
+fn pitch_classes_parse_and_wrap() {
+    for (i, class) in P::ALL.iter().enumerate() {
+        assert_eq!(class.index() as usize, i);
+        assert_eq!(P::from_name(class.name_sharp()), Some(*class));
+        assert_eq!(P::from_name(class.name_flat()), Some(*class));
+    }
+    assert_eq!(P::from_name("B#"), Some(P::C));
+    assert_eq!(P::from_name("Fbb"), Some(P::Ds));
+    assert_eq!(P::from_name("H"), None);
+    assert_eq!(P::from_name("C#4"), None);
+    assert_eq!(P::from_index(-1), P::B);
+    assert_eq!(P::C.transpose(-13), P::B);
+    assert_eq!(P::D.semitones_to(P::C), 10);
+}
+#[test]
+fn notes_map_to_midi() {
+    assert_eq!(note("C-1").midi(), Some(0));
+    assert_eq!(note("G9").midi(), Some(127));
+    assert_eq!(note("G#9").midi(), None);
+    assert_eq!(note("Cb-1").midi(), None);
+    assert_eq!(note("C4"), MusicNote::MIDDLE_C);
+    assert_eq!(note("Bb-1").key(), 10);
+    assert_eq!(note("B#3"), MusicNote::MIDDLE_C);
+    for midi in 0..=127 {
+        let n = MusicNote::from_midi(midi);
+        assert_eq!(note(&n.to_string()), n);
+        assert_eq!(MusicNote::new(n.class(), n.octave()), n);
+    }
+    assert_eq!(MusicNote::from_key(-1).to_string(), "B-2");
+    assert_eq!(MusicNote::from_key(i16::MAX).transpose(1).key(), i16::MAX);
+    for bad in ["", "C", "C-", "4", "C4x", "C1000", "c4"] {
+        assert_eq!(MusicNote::from_name(bad), None, "{bad}");
+    }
+}
+#[test]
+fn intervals_name_and_invert() {
+    let i = MusicInterval::between(note("E4"), note("C4"));
+    assert_eq!(i.semitones(), -4);
+    assert!(i.is_descending());
+    assert_eq!(i.simple(), MusicInterval::MAJOR_THIRD);
+    assert_eq!(i.to_string(), "-M3");
+    assert_eq!(MusicInterval::new(19).to_string(), "P5+8");
+    assert!(MusicInterval::new(19).is_compound() && !MusicInterval::OCTAVE.is_compound());
+    assert_eq!(MusicInterval::OCTAVE.to_string(), "P8");
+    assert_eq!(MusicInterval::new(-24).to_string(), "-P8+8");
+    assert_eq!(MusicInterval::new(13).to_string(), "m2+8");
+    assert_eq!(MusicInterval::TRITONE.invert(), MusicInterval::TRITONE);
+    assert_eq!(MusicInterval::UNISON.invert(), MusicInterval::UNISON);
+    assert_eq!(MusicInterval::MINOR_THIRD.invert(), MusicInterval::MAJOR_SIXTH);
+    assert_eq!(MusicInterval::MINOR_SEVENTH.name(), "minor seventh");
+    assert_eq!(MusicInterval::PERFECT_FIFTH.just_ratio(), (3, 2));
+    assert_eq!(MusicInterval::OCTAVE.name(), "octave");
+    assert_eq!(MusicInterval::OCTAVE.just_ratio(), (2, 1));
+    assert_eq!(MusicInterval::new(24).just_ratio(), (4, 1));
+    assert_eq!(MusicInterval::new(19).just_ratio(), (3, 1));
+    assert_eq!(MusicInterval::new(-19).just_ratio(), (3, 1));
+    assert_eq!(MusicInterval::new(i8::MIN).just_ratio(), (8 << 10, 5));
+    let far = MusicInterval::between(MusicNote::from_key(0), MusicNote::from_key(1000));
+    assert_eq!(far.semitones(), i8::MAX);
+}
+#[test]
+fn scales_span_an_octave() {
+    use MusicMode as M;
+    for mode in [
+        M::Major,
+        M::Ionian,
+        M::Dorian,
+        M::Phrygian,
+        M::Lydian,
+        M::Mixolydian,
+        M::Minor,
+        M::Aeolian,
+        M::Locrian,
+        M::HarmonicMinor,
+        M::MelodicMinor,
+        M::MajorPentatonic,
+        M::MinorPentatonic,
+        M::Blues,
+        M::WholeTone,
+        M::Chromatic,
+    ] {
+        assert_eq!(mode.steps().iter().map(|&s| s as u32).sum::<u32>(), 12, "{mode}");
+        assert_eq!(mode.semitones(mode.degrees() as i32), 12);
+    }
+    let a_minor = MusicScale::new(P::A, M::Minor);
+    let c_major = MusicScale::new(P::C, M::Major);
+    for class in P::ALL {
+        assert_eq!(a_minor.contains(class), c_major.contains(class));
+    }
+    assert_eq!(a_minor.degree(2), P::C);
+    assert_eq!(a_minor.degree_of(P::G), Some(6));
+    assert_eq!(a_minor.to_string(), "A minor");
+    let blues = MusicScale::new(P::E, M::Blues);
+    assert_eq!(blues.degrees(), 6);
+    assert_eq!(blues.degree(3), P::As);
+    let a3 = note("A3");
+    assert_eq!(a_minor.note_at(a3, 9), note("C5"));
+    assert_eq!(a_minor.note_at(a3, -2), note("F3"));
+}
+#[test]
+fn chords_voice_and_identify() {
+    use MusicChordKind as K;
+    for kind in K::ALL {
+        for root in P::ALL {
+            for inversion in 0..kind.note_count() as u8 {
+                let chord = MusicChord::new(root, kind, inversion);
+                let (notes, len) = chord.notes(3);
+                assert!(notes[..len].windows(2).all(|w| w[0] < w[1]));
+                assert_eq!(notes[0].class(), chord.bass());
+                // chords sharing pitch classes may be named differently
+                let found = MusicChord::identify(&notes[..len]).unwrap();
+                assert_eq!(found.bass(), chord.bass());
+                let classes = |c: MusicChord| {
+                    let (notes, len) = c.notes(0);
+                    notes[..len].iter().fold(0_u16, |m, n| m | 1 << n.class().index())
+                };
+                assert_eq!(classes(found), classes(chord), "{chord} as {found}");
+                assert!(inversion != 0 || found == chord, "{chord} as {found}");
+            }
+        }
+    }
+    // the same pitch classes, named by the bass
+    let c6 = [note("C3"), note("E3"), note("G3"), note("A3")];
+    assert_eq!(MusicChord::identify(&c6), Some(MusicChord::new(P::C, K::Major6, 0)));
+    let am7_over_c = [note("A3"), note("C3"), note("E4"), note("G4"), note("C5")];
+    assert_eq!(MusicChord::identify(&am7_over_c), Some(MusicChord::new(P::C, K::Major6, 0)));
+    let am7 = [note("A2"), note("C3"), note("E4"), note("G4")];
+    assert_eq!(MusicChord::identify(&am7).unwrap().to_string(), "Am7");
+    let g7 = [note("F3"), note("G3"), note("B3"), note("D4")];
+    assert_eq!(MusicChord::identify(&g7).unwrap().to_string(), "G7/F");
+    assert_eq!(MusicChord::new(P::Cs, K::HalfDiminished7, 5).inversion, 1);
+    assert_eq!(MusicChord::identify(&[]), None);
+    assert_eq!(MusicChord::identify(&[note("C4"), note("Db4")]), None);
+}
+#[test]
+fn tunings_map_frequencies() {
+    let et = MusicTuning::EQUAL;
+    let table = et.midi_table();
+    for (midi, freq) in table.iter().enumerate().skip(1) {
+        let ratio = freq / table[midi - 1];
+        assert!((ratio - 1.059_463_1).abs() < 1e-5, "{midi}");
+    }
+    assert!((table[69] - 440.0).abs() < 1e-3);
+    assert!((table[81] - 880.0).abs() < 1e-3);
+    assert!((table[0] - 8.175_799).abs() < 1e-5);
+
+    let baroque = MusicTuning::equal(415.0);
+    assert!((baroque.frequency(note("A3")) - 207.5).abs() < 1e-3);
+
+    // just intonation over D, pitched by its A4
+    let just = MusicTuning::just(P::D, MusicNote::A4, 440.0);
+    assert!((just.frequency(note("D4")) - 440.0 * 2.0 / 3.0).abs() < 1e-3);
+    assert!((just.frequency(note("F#5")) - 440.0 * 4.0 / 3.0 * 5.0 / 4.0).abs() < 1e-3);
+    assert!((just.frequency(note("D2")) - 440.0 / 6.0).abs() < 1e-3);
+
+    let pythagorean = MusicTuning::pythagorean(P::C, MusicNote::MIDDLE_C, 256.0);
+    assert!((pythagorean.frequency(note("G4")) - 384.0).abs() < 1e-3);
+    assert!((pythagorean.frequency(note("D4")) - 288.0).abs() < 1e-3);
+    assert!((pythagorean.frequency(note("F3")) - 256.0 * 2.0 / 3.0).abs() < 1e-3);
+    assert!((pythagorean.ratios()[6] - 729.0 / 512.0).abs() < 1e-6);
+
+    let cents = core::array::from_fn(|i| i as f32 * 100.0);
+    let custom = MusicTuning::from_cents(P::C, cents, MusicNote::A4, 440.0);
+    assert!((custom.frequency(MusicNote::MIDDLE_C) - 261.625_58).abs() < 1e-3);
+}
+#[test]
+fn durations_stay_reduced() {
+    type D = MusicDuration;
+    assert_eq!(D::new(2, 8), Some(D::QUARTER));
+    assert_eq!(D::new(0, 3), D::new(0, 1));
+    assert_eq!(D::new(1, 0), None);
+    assert_eq!(D::HALF.dotted(), D::new(6, 8).unwrap());
+    assert_eq!(D::QUARTER.tuplet(5, 4).to_string(), "1/5");
+    assert_eq!(D::QUARTER.tuplet(0, 4), D::QUARTER);
+    let bar = [D::HALF, D::QUARTER, D::EIGHTH, D::SIXTEENTH, D::THIRTY_SECOND, D::THIRTY_SECOND];
+    let total = bar.iter().try_fold(D::new(0, 1).unwrap(), |sum, d| sum.checked_add(*d));
+    assert_eq!(total, Some(D::WHOLE));
+    assert_eq!(D::new(u32::MAX, 1).unwrap().checked_add(D::WHOLE), None);
+    assert_eq!(D::new(u32::MAX, 1).unwrap().dotted(), D::new(u32::MAX, 1).unwrap());
+    assert_eq!(D::EIGHTH.tuplet(3, 2).ticks(480), 160);
+    assert_eq!(D::WHOLE.checked_mul(u32::MAX).unwrap().ticks(480), u32::MAX);
+    assert!((D::QUARTER.dotted().seconds(90.0) - 1.0).abs() < 1e-6);
+
+    let events = [MusicEvent::note(note("E4"), D::QUARTER, 90), MusicEvent::rest(D::HALF)];
+    assert!(!events[0].is_rest() && events[1].is_rest());
+    assert_eq!(events[1].velocity, 0);
+}
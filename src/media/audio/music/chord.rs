@@ -0,0 +1,232 @@
+// devela/src/media/audio/music/chord.rs
+//
+//! Defines [`MusicChordKind`], [`MusicChord`].
+//
+// https://en.wikipedia.org/wiki/Chord_(music)
+
+use crate::{MusicNote, MusicPitchClass, impl_trait, is};
+
+#[doc = crate::_tags!(audio)]
+/// The quality of a chord, as the intervals of its notes above the root.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MusicChordKind {
+    /// A major triad.
+    #[default]
+    Major,
+    /// A minor triad.
+    Minor,
+    /// A diminished triad.
+    Diminished,
+    /// An augmented triad.
+    Augmented,
+    /// A suspended triad with a major second instead of the third.
+    Sus2,
+    /// A suspended triad with a perfect fourth instead of the third.
+    Sus4,
+    /// A major triad with a major seventh.
+    Major7,
+    /// A minor triad with a minor seventh.
+    Minor7,
+    /// A major triad with a minor seventh.
+    Dominant7,
+    /// A diminished triad with a minor seventh.
+    HalfDiminished7,
+    /// A diminished triad with a diminished seventh.
+    Diminished7,
+    /// A minor triad with a major seventh.
+    MinorMajor7,
+    /// A major triad with a major sixth.
+    Major6,
+    /// A minor triad with a major sixth.
+    Minor6,
+    /// A root and its fifth, without a third.
+    Power,
+}
+
+#[rustfmt::skip]
+impl MusicChordKind {
+    /// Every kind of chord, in the order [`MusicChord::identify`] tries them.
+    pub const ALL: [Self; 15] = {
+        use MusicChordKind as K;
+        [K::Major, K::Minor, K::Diminished, K::Augmented, K::Sus2, K::Sus4, K::Major7,
+            K::Minor7, K::Dominant7, K::HalfDiminished7, K::Diminished7, K::MinorMajor7,
+            K::Major6, K::Minor6, K::Power]
+    };
+
+    /// Returns the semitones of each note above the root, starting with the root.
+    #[must_use]
+    pub const fn intervals(self) -> &'static [u8] {
+        use MusicChordKind as K;
+        match self {
+            K::Major => &[0, 4, 7],
+            K::Minor => &[0, 3, 7],
+            K::Diminished => &[0, 3, 6],
+            K::Augmented => &[0, 4, 8],
+            K::Sus2 => &[0, 2, 7],
+            K::Sus4 => &[0, 5, 7],
+            K::Major7 => &[0, 4, 7, 11],
+            K::Minor7 => &[0, 3, 7, 10],
+            K::Dominant7 => &[0, 4, 7, 10],
+            K::HalfDiminished7 => &[0, 3, 6, 10],
+            K::Diminished7 => &[0, 3, 6, 9],
+            K::MinorMajor7 => &[0, 3, 7, 11],
+            K::Major6 => &[0, 4, 7, 9],
+            K::Minor6 => &[0, 3, 7, 9],
+            K::Power => &[0, 7],
+        }
+    }
+    /// Returns the number of notes.
+    #[must_use]
+    pub const fn note_count(self) -> usize { self.intervals().len() }
+
+    /// Returns the suffix that follows the root in the chord symbol, like `"m7"`.
+    #[must_use]
+    pub const fn suffix(self) -> &'static str {
+        use MusicChordKind as K;
+        match self {
+            K::Major => "", K::Minor => "m", K::Diminished => "dim", K::Augmented => "aug",
+            K::Sus2 => "sus2", K::Sus4 => "sus4", K::Major7 => "maj7", K::Minor7 => "m7",
+            K::Dominant7 => "7", K::HalfDiminished7 => "m7b5", K::Diminished7 => "dim7",
+            K::MinorMajor7 => "mMaj7", K::Major6 => "6", K::Minor6 => "m6", K::Power => "5",
+        }
+    }
+
+    /// Returns the set of pitch classes over `root`, as a bit per semitone above C.
+    const fn mask(self, root: MusicPitchClass) -> u16 {
+        let (intervals, mut mask, mut i) = (self.intervals(), 0, 0);
+        while i < intervals.len() {
+            mask |= 1 << root.transpose(intervals[i] as i32).index();
+            i += 1;
+        }
+        mask
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A chord, as a root, a kind and an inversion.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// The inversion is the index of the note of [`MusicChordKind::intervals`]
+/// in the bass, so 0 is the root position.
+///
+/// It's displayed with its chord symbol, followed by its bass when inverted,
+/// like `C#m7` or `C/E`.
+///
+/// # Example
+/// ```
+/// # use devela::{MusicChord, MusicChordKind as K, MusicNote, MusicPitchClass as P};
+/// let notes = ["E3", "G3", "C4"].map(|n| MusicNote::from_name(n).unwrap());
+/// let chord = MusicChord::identify(&notes).unwrap();
+/// assert_eq![chord, MusicChord::new(P::C, K::Major, 1)];
+/// assert_eq![chord.bass(), P::E];
+/// assert_eq![chord.to_string(), "C/E"];
+///
+/// let (notes, len) = MusicChord::new(P::A, K::Minor7, 0).notes(3);
+/// assert_eq![notes[..len].iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+///     ["A3", "C4", "E4", "G4"]];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MusicChord {
+    /// The root.
+    pub root: MusicPitchClass,
+    /// The kind.
+    pub kind: MusicChordKind,
+    /// The index of the note in the bass.
+    pub inversion: u8,
+}
+impl_trait![fmt::Display for MusicChord |self, f| {
+    write!(f, "{}{}", self.root, self.kind.suffix())?;
+    is![self.inversion != 0, write!(f, "/{}", self.bass())?];
+    Ok(())
+}];
+
+impl MusicChord {
+    /// Returns a chord of `kind` over `root`, with the note of index `inversion` in the bass.
+    ///
+    /// The inversion wraps around the number of notes of the `kind`.
+    #[must_use]
+    pub const fn new(root: MusicPitchClass, kind: MusicChordKind, inversion: u8) -> Self {
+        Self {
+            root,
+            kind,
+            inversion: inversion % kind.note_count() as u8,
+        }
+    }
+
+    /// Returns the pitch class in the bass.
+    #[must_use]
+    pub const fn bass(self) -> MusicPitchClass {
+        self.root.transpose(self.kind.intervals()[self.inversion as usize] as i32)
+    }
+
+    /// Returns the notes in close position, ascending from the bass in `octave`,
+    /// and how many of them there are.
+    #[must_use]
+    pub const fn notes(self, octave: i8) -> ([MusicNote; 4], usize) {
+        let (intervals, inversion) = (self.kind.intervals(), self.inversion as usize);
+        let bass = MusicNote::new(self.bass(), octave);
+        let mut notes = [bass; 4];
+        let mut i = 1;
+        while i < intervals.len() {
+            let index = (inversion + i) % intervals.len();
+            let above = intervals[index] as i16 - intervals[inversion] as i16;
+            notes[i] = bass.transpose(is![above < 0, above + 12, above]);
+            i += 1;
+        }
+        (notes, intervals.len())
+    }
+
+    /// Identifies the chord formed by the pitch classes of `notes`, in any octave.
+    ///
+    /// The lowest note is taken as the bass, and chords rooted on it are preferred,
+    /// so the same pitch classes may be named differently depending on the voicing.
+    /// Returns `None` if they don't form any [`MusicChordKind`].
+    #[must_use]
+    pub const fn identify(notes: &[MusicNote]) -> Option<Self> {
+        is![notes.is_empty(), return None];
+        let (mut mask, mut lowest, mut i) = (0_u16, notes[0], 1);
+        mask |= 1 << lowest.class().index();
+        while i < notes.len() {
+            mask |= 1 << notes[i].class().index();
+            if notes[i].key() < lowest.key() {
+                lowest = notes[i];
+            }
+            i += 1;
+        }
+        let bass = lowest.class();
+        if let Some(chord) = Self::rooted(bass, bass, mask) {
+            return Some(chord);
+        }
+        let mut r = 0;
+        while r < 12 {
+            let root = MusicPitchClass::from_index(r);
+            if mask & (1 << r) != 0
+                && r != bass.index() as i32
+                && let Some(chord) = Self::rooted(root, bass, mask)
+            {
+                return Some(chord);
+            }
+            r += 1;
+        }
+        None
+    }
+
+    /// Returns the first kind of chord over `root` with the pitch classes of `mask`.
+    const fn rooted(root: MusicPitchClass, bass: MusicPitchClass, mask: u16) -> Option<Self> {
+        let mut k = 0;
+        while k < MusicChordKind::ALL.len() {
+            let kind = MusicChordKind::ALL[k];
+            if kind.mask(root) == mask {
+                let (intervals, target) = (kind.intervals(), root.semitones_to(bass));
+                let mut inversion = 0;
+                while intervals[inversion] != target {
+                    inversion += 1;
+                }
+                return Some(Self { root, kind, inversion: inversion as u8 });
+            }
+            k += 1;
+        }
+        None
+    }
+}
@@ -0,0 +1,198 @@
+// devela/src/media/audio/music/interval.rs
+//
+//! Defines [`MusicInterval`].
+//
+// https://en.wikipedia.org/wiki/Interval_(music)
+
+use crate::{MusicNote, impl_trait, is};
+
+#[doc = crate::_tags!(audio)]
+/// The distance between two notes, in semitones.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// It's negative when descending. Intervals larger than an octave are compound,
+/// and named after the interval they add to their whole octaves,
+/// with whole octaves named after the octave itself.
+///
+/// It's displayed with its short name, like `m3`, `P5` or `P8`,
+/// preceded by `-` when descending and followed by `+8` for each extra octave.
+///
+/// # Example
+/// ```
+/// # use devela::{MusicInterval as I, MusicNote};
+/// let (c4, g4) = (MusicNote::MIDDLE_C, MusicNote::from_midi(67));
+/// assert_eq![I::between(c4, g4), I::PERFECT_FIFTH];
+/// assert_eq![I::PERFECT_FIFTH.invert(), I::PERFECT_FOURTH];
+/// assert_eq![I::new(16).to_string(), "M3+8"];
+/// assert_eq![I::new(24).to_string(), "P8+8"];
+/// assert_eq![I::MAJOR_THIRD.just_ratio(), (5, 4)];
+/// assert_eq![I::new(16).just_ratio(), (5, 2)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MusicInterval {
+    semitones: i8,
+}
+impl_trait![fmt::Display for MusicInterval |self, f| {
+    is![self.semitones < 0, f.write_str("-")?];
+    f.write_str(self.short_name())?;
+    for _ in 0..self.extra_octaves() {
+        f.write_str("+8")?;
+    }
+    Ok(())
+}];
+
+#[rustfmt::skip]
+impl MusicInterval {
+    /// 0 semitones.
+    pub const UNISON: Self = Self::new(0);
+    /// 1 semitone.
+    pub const MINOR_SECOND: Self = Self::new(1);
+    /// 2 semitones.
+    pub const MAJOR_SECOND: Self = Self::new(2);
+    /// 3 semitones.
+    pub const MINOR_THIRD: Self = Self::new(3);
+    /// 4 semitones.
+    pub const MAJOR_THIRD: Self = Self::new(4);
+    /// 5 semitones.
+    pub const PERFECT_FOURTH: Self = Self::new(5);
+    /// 6 semitones, the augmented fourth or diminished fifth.
+    pub const TRITONE: Self = Self::new(6);
+    /// 7 semitones.
+    pub const PERFECT_FIFTH: Self = Self::new(7);
+    /// 8 semitones.
+    pub const MINOR_SIXTH: Self = Self::new(8);
+    /// 9 semitones.
+    pub const MAJOR_SIXTH: Self = Self::new(9);
+    /// 10 semitones.
+    pub const MINOR_SEVENTH: Self = Self::new(10);
+    /// 11 semitones.
+    pub const MAJOR_SEVENTH: Self = Self::new(11);
+    /// 12 semitones.
+    pub const OCTAVE: Self = Self::new(12);
+}
+
+impl MusicInterval {
+    /// Returns an interval of `semitones`, descending if negative.
+    #[must_use]
+    pub const fn new(semitones: i8) -> Self {
+        Self { semitones }
+    }
+    /// Returns the interval from `from` to `to`, saturating.
+    #[must_use]
+    pub const fn between(from: MusicNote, to: MusicNote) -> Self {
+        let semitones = to.key() as i32 - from.key() as i32;
+        let semitones = is![
+            semitones > i8::MAX as i32,
+            i8::MAX,
+            is![semitones < i8::MIN as i32, i8::MIN, semitones as i8]
+        ];
+        Self { semitones }
+    }
+
+    /// Returns the number of semitones, negative if descending.
+    #[must_use]
+    pub const fn semitones(self) -> i8 {
+        self.semitones
+    }
+    /// Returns the number of whole octaves spanned.
+    #[must_use]
+    pub const fn octaves(self) -> u8 {
+        self.semitones.unsigned_abs() / 12
+    }
+    /// Returns whether it spans more than an octave.
+    #[must_use]
+    pub const fn is_compound(self) -> bool {
+        self.semitones.unsigned_abs() > 12
+    }
+    /// Returns whether it's descending.
+    #[must_use]
+    pub const fn is_descending(self) -> bool {
+        self.semitones < 0
+    }
+
+    /// Returns the ascending interval within an octave, from 0 to 11 semitones.
+    #[must_use]
+    pub const fn simple(self) -> Self {
+        Self {
+            semitones: (self.semitones.unsigned_abs() % 12) as i8,
+        }
+    }
+    /// Returns the inversion of the [`simple`][Self::simple] interval,
+    /// which completes it to an octave.
+    #[must_use]
+    pub const fn invert(self) -> Self {
+        Self { semitones: (12 - self.simple().semitones) % 12 }
+    }
+
+    /// Returns the short name of the interval within its last octave,
+    /// like `P1`, `m3`, `M3`, `TT`, `P5` or `P8`.
+    ///
+    /// Non-zero whole octaves are named `P8`.
+    #[must_use]
+    pub const fn short_name(self) -> &'static str {
+        ["P1", "m2", "M2", "m3", "M3", "P4", "TT", "P5", "m6", "M6", "m7", "M7", "P8"]
+            [self.degree()]
+    }
+    /// Returns the name of the interval within its last octave, like `"minor third"`.
+    ///
+    /// Non-zero whole octaves are named `"octave"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        [
+            "unison",
+            "minor second",
+            "major second",
+            "minor third",
+            "major third",
+            "perfect fourth",
+            "tritone",
+            "perfect fifth",
+            "minor sixth",
+            "major sixth",
+            "minor seventh",
+            "major seventh",
+            "octave",
+        ][self.degree()]
+    }
+
+    /// Returns the frequency ratio of the interval in 5-limit just intonation,
+    /// as a numerator and denominator.
+    ///
+    /// The ratio is the one of the ascending interval. Compound intervals double
+    /// the ratio within their last octave once per octave below it,
+    /// so that an octave is `2/1` and two octaves are `4/1`.
+    #[must_use]
+    pub const fn just_ratio(self) -> (u32, u32) {
+        let (num, den): (u32, u32) = [
+            (1, 1),
+            (16, 15),
+            (9, 8),
+            (6, 5),
+            (5, 4),
+            (4, 3),
+            (45, 32),
+            (3, 2),
+            (8, 5),
+            (5, 3),
+            (9, 5),
+            (15, 8),
+            (2, 1),
+        ][self.degree()];
+        // doubles it for each extra octave, in lowest terms
+        let (octaves, twos) = (self.extra_octaves() as u32, den.trailing_zeros());
+        let halved = is![twos < octaves, twos, octaves];
+        (num << (octaves - halved), den >> halved)
+    }
+
+    /* helpers */
+
+    /// Returns the semitones within the last octave, from 1 to 12 unless it's a unison.
+    const fn degree(self) -> usize {
+        let n = self.semitones.unsigned_abs() as usize;
+        is![n == 0, 0, (n - 1) % 12 + 1]
+    }
+    /// Returns the number of whole octaves before the last one.
+    const fn extra_octaves(self) -> u8 {
+        self.semitones.unsigned_abs().saturating_sub(1) / 12
+    }
+}
@@ -0,0 +1,29 @@
+// devela/src/media/audio/music/mod.rs
+//
+#![doc = crate::_DOC_MEDIA_AUDIO_MUSIC!()] // public
+#![doc = crate::_doc!(modules: crate::media::audio; music)]
+#![doc = crate::_doc!(flat:"media")]
+#![doc = crate::_doc!(hr)]
+
+#[cfg(test)]
+mod _test;
+
+mod chord; // MusicChord, MusicChordKind
+mod interval; // MusicInterval
+mod pitch; // MusicNote, MusicPitchClass
+mod rhythm; // MusicDuration, MusicEvent
+mod scale; // MusicMode, MusicScale
+mod tuning; // MusicTuning
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            chord::*,
+            interval::*,
+            pitch::*,
+            rhythm::*,
+            scale::*,
+            tuning::*,
+        };
+    }
+}
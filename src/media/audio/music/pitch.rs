@@ -0,0 +1,230 @@
+// devela/src/media/audio/music/pitch.rs
+//
+//! Defines [`MusicPitchClass`], [`MusicNote`].
+//
+// https://en.wikipedia.org/wiki/Scientific_pitch_notation
+
+use crate::{impl_trait, is};
+
+#[doc = crate::_tags!(audio)]
+/// One of the 12 pitch classes of the chromatic scale, regardless of octave.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// Enharmonic spellings share a class, so `C#` and `Db` are both [`Cs`][Self::Cs].
+/// It's displayed with sharps.
+///
+/// # Example
+/// ```
+/// # use devela::MusicPitchClass as P;
+/// assert_eq![P::from_name("Db"), Some(P::Cs)];
+/// assert_eq![P::A.transpose(3), P::C];
+/// assert_eq![P::G.semitones_to(P::D), 7];
+/// assert_eq![P::As.name_flat(), "Bb"];
+/// ```
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MusicPitchClass {
+    /// C.
+    #[default]
+    C = 0,
+    /// C sharp, or D flat.
+    Cs,
+    /// D.
+    D,
+    /// D sharp, or E flat.
+    Ds,
+    /// E.
+    E,
+    /// F.
+    F,
+    /// F sharp, or G flat.
+    Fs,
+    /// G.
+    G,
+    /// G sharp, or A flat.
+    Gs,
+    /// A.
+    A,
+    /// A sharp, or B flat.
+    As,
+    /// B.
+    B,
+}
+impl_trait![fmt::Display for MusicPitchClass |self, f| f.write_str(self.name_sharp())];
+
+impl MusicPitchClass {
+    /// Every pitch class, in ascending order from C.
+    pub const ALL: [Self; 12] = {
+        use MusicPitchClass as P;
+        [P::C, P::Cs, P::D, P::Ds, P::E, P::F, P::Fs, P::G, P::Gs, P::A, P::As, P::B]
+    };
+
+    /// Returns the pitch class of `index` semitones above C, wrapping around the octave.
+    #[must_use]
+    pub const fn from_index(index: i32) -> Self {
+        Self::ALL[index.rem_euclid(12) as usize]
+    }
+    /// Returns the number of semitones above C, from 0 to 11.
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the pitch class `semitones` above, or below if negative.
+    #[must_use]
+    pub const fn transpose(self, semitones: i32) -> Self {
+        Self::from_index(self as i32 + semitones.rem_euclid(12))
+    }
+    /// Returns the number of semitones up from `self` to `other`, from 0 to 11.
+    #[must_use]
+    pub const fn semitones_to(self, other: Self) -> u8 {
+        (other as i32 - self as i32).rem_euclid(12) as u8
+    }
+
+    /// Returns the name spelled with a sharp, like `"C#"`.
+    #[must_use]
+    pub const fn name_sharp(self) -> &'static str {
+        ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"][self as usize]
+    }
+    /// Returns the name spelled with a flat, like `"Db"`.
+    #[must_use]
+    pub const fn name_flat(self) -> &'static str {
+        ["C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab", "A", "Bb", "B"][self as usize]
+    }
+
+    /// Parses a letter from `A` to `G`, followed by any number of `#` or `b` accidentals.
+    ///
+    /// Returns `None` if `name` isn't exactly that.
+    #[must_use]
+    pub const fn from_name(name: &str) -> Option<Self> {
+        match Self::parse(name.as_bytes()) {
+            Some((offset, len)) if len == name.len() => Some(Self::from_index(offset)),
+            _ => None,
+        }
+    }
+
+    /// Parses a pitch class at the start of `bytes`.
+    ///
+    /// Returns its offset from C, not wrapped around the octave, and its length.
+    const fn parse(bytes: &[u8]) -> Option<(i32, usize)> {
+        is![bytes.is_empty(), return None];
+        let mut index = match bytes[0] {
+            b'C' => 0,
+            b'D' => 2,
+            b'E' => 4,
+            b'F' => 5,
+            b'G' => 7,
+            b'A' => 9,
+            b'B' => 11,
+            _ => return None,
+        };
+        let mut len = 1;
+        while len < bytes.len() {
+            match bytes[len] {
+                b'#' => index += 1,
+                b'b' => index -= 1,
+                _ => break,
+            }
+            len += 1;
+        }
+        Some((index, len))
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A note, as a pitch class in an octave.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// It's stored as its number of semitones above `C-1`, which matches the
+/// MIDI note number between `C-1` (0) and `G9` (127). Octaves follow scientific
+/// pitch notation, where the middle C is `C4`, and the tuning `A` is `A4`.
+///
+/// It's displayed with sharps, like `C#4`.
+///
+/// # Example
+/// ```
+/// # use devela::{MusicNote, MusicPitchClass};
+/// let a4 = MusicNote::from_name("A4").unwrap();
+/// assert_eq![a4.midi(), Some(69)];
+/// assert_eq![a4.transpose(3), MusicNote::new(MusicPitchClass::C, 5)];
+/// assert_eq![MusicNote::from_midi(61).to_string(), "C#4"];
+/// assert_eq![MusicNote::from_name("Cb4").unwrap().octave(), 3];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MusicNote {
+    key: i16,
+}
+impl_trait![fmt::Display for MusicNote |self, f| {
+    write!(f, "{}{}", self.class(), self.octave())
+}];
+
+impl MusicNote {
+    /// The middle C, `C4`.
+    pub const MIDDLE_C: Self = Self { key: 60 };
+    /// The usual tuning reference, `A4`.
+    pub const A4: Self = Self { key: 69 };
+
+    /// Returns the note of `class` in `octave`.
+    #[must_use]
+    pub const fn new(class: MusicPitchClass, octave: i8) -> Self {
+        Self { key: (octave as i16 + 1) * 12 + class as i16 }
+    }
+    /// Returns the note `key` semitones above `C-1`.
+    #[must_use]
+    pub const fn from_key(key: i16) -> Self {
+        Self { key }
+    }
+    /// Returns the note of a MIDI note number.
+    #[must_use]
+    pub const fn from_midi(number: u8) -> Self {
+        Self { key: number as i16 }
+    }
+
+    /// Returns the number of semitones above `C-1`.
+    #[must_use]
+    pub const fn key(self) -> i16 {
+        self.key
+    }
+    /// Returns the MIDI note number, or `None` if it's above 127 or below 0.
+    #[must_use]
+    pub const fn midi(self) -> Option<u8> {
+        is![self.key >= 0 && self.key <= 127, Some(self.key as u8), None]
+    }
+    /// Returns the pitch class.
+    #[must_use]
+    pub const fn class(self) -> MusicPitchClass {
+        MusicPitchClass::from_index(self.key as i32)
+    }
+    /// Returns the octave, in scientific pitch notation.
+    #[must_use]
+    pub const fn octave(self) -> i8 {
+        (self.key.div_euclid(12) - 1) as i8
+    }
+
+    /// Returns the note `semitones` above, or below if negative, saturating.
+    #[must_use]
+    pub const fn transpose(self, semitones: i16) -> Self {
+        Self { key: self.key.saturating_add(semitones) }
+    }
+
+    /// Parses a [`MusicPitchClass`] name followed by a possibly negative octave, like `Bb-1`.
+    ///
+    /// The accidentals may cross the octave boundary, so `Cb4` is `B3`.
+    /// Returns `None` if `name` isn't exactly that.
+    #[must_use]
+    pub const fn from_name(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+        let Some((offset, len)) = MusicPitchClass::parse(bytes) else { return None };
+        let negative = len < bytes.len() && bytes[len] == b'-';
+        let mut i = len + negative as usize;
+        is![i == bytes.len() || bytes.len() - i > 3, return None];
+        let mut octave = 0_i16;
+        while i < bytes.len() {
+            is![!bytes[i].is_ascii_digit(), return None];
+            octave = octave * 10 + (bytes[i] - b'0') as i16;
+            i += 1;
+        }
+        octave = is![negative, -octave, octave];
+        Some(Self { key: (octave + 1) * 12 + offset as i16 })
+    }
+}
@@ -0,0 +1,180 @@
+// devela/src/media/audio/music/rhythm.rs
+//
+//! Defines [`MusicDuration`], [`MusicEvent`].
+//
+// https://en.wikipedia.org/wiki/Note_value
+
+use crate::{MusicNote, impl_trait, is};
+
+#[doc = crate::_tags!(audio time)]
+/// A rhythmic duration, as a fraction of a whole note.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// It's always kept reduced, so equal durations compare as equal.
+/// It's displayed as a fraction, like `3/8`.
+///
+/// # Example
+/// ```
+/// # use devela::MusicDuration as D;
+/// assert_eq![D::QUARTER.dotted(), D::new(3, 8).unwrap()];
+/// assert_eq![D::EIGHTH.tuplet(3, 2).checked_mul(3), Some(D::QUARTER)];
+/// assert_eq![D::HALF.checked_add(D::QUARTER).unwrap().ticks(480), 1440];
+/// assert_eq![D::HALF.seconds(120.0), 1.0];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MusicDuration {
+    num: u32,
+    den: u32,
+}
+impl_trait![fmt::Display for MusicDuration |self, f| write!(f, "{}/{}", self.num, self.den)];
+
+impl Default for MusicDuration {
+    /// Returns a [`QUARTER`][Self::QUARTER] note.
+    fn default() -> Self {
+        Self::QUARTER
+    }
+}
+
+impl MusicDuration {
+    /// A whole note.
+    pub const WHOLE: Self = Self { num: 1, den: 1 };
+    /// A half note.
+    pub const HALF: Self = Self { num: 1, den: 2 };
+    /// A quarter note, usually a beat.
+    pub const QUARTER: Self = Self { num: 1, den: 4 };
+    /// An eighth note.
+    pub const EIGHTH: Self = Self { num: 1, den: 8 };
+    /// A sixteenth note.
+    pub const SIXTEENTH: Self = Self { num: 1, den: 16 };
+    /// A thirty-second note.
+    pub const THIRTY_SECOND: Self = Self { num: 1, den: 32 };
+
+    /// Returns a duration of `num / den` whole notes, or `None` if `den` is 0.
+    #[must_use]
+    pub const fn new(num: u32, den: u32) -> Option<Self> {
+        is![den == 0, None, Some(Self::reduced(num as u64, den as u64).unwrap())]
+    }
+
+    /// Returns the numerator of the reduced fraction.
+    #[must_use]
+    pub const fn num(self) -> u32 {
+        self.num
+    }
+    /// Returns the denominator of the reduced fraction.
+    #[must_use]
+    pub const fn den(self) -> u32 {
+        self.den
+    }
+    /// Returns whether it lasts nothing.
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// Returns the duration lengthened by half, saturating.
+    #[must_use]
+    pub const fn dotted(self) -> Self {
+        self.scale(3, 2)
+    }
+    /// Returns the duration of each of `count` notes played in the time of `normal`,
+    /// like a triplet of 3 notes in the time of 2, saturating.
+    ///
+    /// Returns the same duration if `count` is 0.
+    #[must_use]
+    pub const fn tuplet(self, count: u32, normal: u32) -> Self {
+        is![count == 0, self, self.scale(normal, count)]
+    }
+
+    /// Returns the sum of both durations, or `None` if it doesn't fit.
+    #[must_use]
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        let num = self.num as u64 * other.den as u64 + other.num as u64 * self.den as u64;
+        Self::reduced(num, self.den as u64 * other.den as u64)
+    }
+    /// Returns the duration repeated `times`, or `None` if it doesn't fit.
+    #[must_use]
+    pub const fn checked_mul(self, times: u32) -> Option<Self> {
+        Self::reduced(self.num as u64 * times as u64, self.den as u64)
+    }
+
+    /// Returns the number of ticks at `ppq` pulses per quarter note, rounded down
+    /// and saturating, as in the timing of a Standard MIDI File.
+    #[must_use]
+    pub const fn ticks(self, ppq: u32) -> u32 {
+        let ticks = self.num as u64 * 4 * ppq as u64 / self.den as u64;
+        is![ticks > u32::MAX as u64, u32::MAX, ticks as u32]
+    }
+    /// Returns the number of seconds at `bpm` quarter notes per minute.
+    #[must_use]
+    pub const fn seconds(self, bpm: f32) -> f32 {
+        self.num as f32 * 4.0 / self.den as f32 * 60.0 / bpm
+    }
+
+    /// Returns the duration multiplied by `num / den`, saturating.
+    const fn scale(self, num: u32, den: u32) -> Self {
+        let (num, den) = (self.num as u64 * num as u64, self.den as u64 * den as u64);
+        match Self::reduced(num, den) {
+            Some(duration) => duration,
+            None => Self { num: u32::MAX, den: 1 },
+        }
+    }
+    /// Reduces `num / den`, or returns `None` if it doesn't fit.
+    const fn reduced(num: u64, den: u64) -> Option<Self> {
+        let divisor = is![num == 0, den, gcd(num, den)];
+        let (num, den) = (num / divisor, den / divisor);
+        is![num > u32::MAX as u64 || den > u32::MAX as u64, return None];
+        Some(Self { num: num as u32, den: den as u32 })
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[doc = crate::_tags!(audio time)]
+/// A note or a rest of some duration, the unit of a simple score.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// # Example
+/// ```
+/// # use devela::{MusicDuration, MusicEvent, MusicNote};
+/// let melody = [
+///     MusicEvent::note(MusicNote::MIDDLE_C, MusicDuration::QUARTER, 100),
+///     MusicEvent::rest(MusicDuration::QUARTER),
+///     MusicEvent::note(MusicNote::A4, MusicDuration::HALF, 80),
+/// ];
+/// let ticks: u32 = melody.iter().map(|e| e.duration.ticks(96)).sum();
+/// assert_eq![ticks, 384];
+/// assert![melody[1].is_rest()];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MusicEvent {
+    /// The note played, or `None` for a rest.
+    pub note: Option<MusicNote>,
+    /// How long it lasts.
+    pub duration: MusicDuration,
+    /// How strongly it's played, from 0 to 127 as in MIDI.
+    pub velocity: u8,
+}
+
+impl MusicEvent {
+    /// Returns an event playing `note` for `duration` at `velocity`.
+    #[must_use]
+    pub const fn note(note: MusicNote, duration: MusicDuration, velocity: u8) -> Self {
+        Self { note: Some(note), duration, velocity }
+    }
+    /// Returns a rest of `duration`.
+    #[must_use]
+    pub const fn rest(duration: MusicDuration) -> Self {
+        Self { note: None, duration, velocity: 0 }
+    }
+    /// Returns whether it's a rest.
+    #[must_use]
+    pub const fn is_rest(&self) -> bool {
+        self.note.is_none()
+    }
+}
@@ -0,0 +1,180 @@
+// devela/src/media/audio/music/scale.rs
+//
+//! Defines [`MusicMode`], [`MusicScale`].
+//
+// https://en.wikipedia.org/wiki/Mode_(music)
+
+use crate::{MusicNote, MusicPitchClass, impl_trait, is};
+
+#[doc = crate::_tags!(audio)]
+/// A pattern of steps that divides the octave into a scale.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// The diatonic modes are rotations of the major scale.
+/// [`Major`][Self::Major] and [`Minor`][Self::Minor] are the same
+/// as [`Ionian`][Self::Ionian] and [`Aeolian`][Self::Aeolian].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MusicMode {
+    /// The major scale, the same as [`Ionian`][Self::Ionian].
+    #[default]
+    Major,
+    /// The first diatonic mode, the same as [`Major`][Self::Major].
+    Ionian,
+    /// The second diatonic mode, a minor scale with a major sixth.
+    Dorian,
+    /// The third diatonic mode, a minor scale with a minor second.
+    Phrygian,
+    /// The fourth diatonic mode, a major scale with an augmented fourth.
+    Lydian,
+    /// The fifth diatonic mode, a major scale with a minor seventh.
+    Mixolydian,
+    /// The natural minor scale, the same as [`Aeolian`][Self::Aeolian].
+    Minor,
+    /// The sixth diatonic mode, the same as [`Minor`][Self::Minor].
+    Aeolian,
+    /// The seventh diatonic mode, a minor scale with a minor second and a diminished fifth.
+    Locrian,
+    /// The natural minor scale with a major seventh.
+    HarmonicMinor,
+    /// The natural minor scale with a major sixth and seventh, as it ascends.
+    MelodicMinor,
+    /// The major scale without its fourth and seventh.
+    MajorPentatonic,
+    /// The natural minor scale without its second and sixth.
+    MinorPentatonic,
+    /// The minor pentatonic scale with a diminished fifth.
+    Blues,
+    /// Six whole tones.
+    WholeTone,
+    /// All twelve semitones.
+    Chromatic,
+}
+impl_trait![fmt::Display for MusicMode |self, f| f.write_str(self.name())];
+
+#[rustfmt::skip]
+impl MusicMode {
+    /// Returns the semitones between consecutive degrees, adding up to an octave.
+    #[must_use]
+    pub const fn steps(self) -> &'static [u8] {
+        use MusicMode as M;
+        match self {
+            M::Major | M::Ionian => &[2, 2, 1, 2, 2, 2, 1],
+            M::Dorian => &[2, 1, 2, 2, 2, 1, 2],
+            M::Phrygian => &[1, 2, 2, 2, 1, 2, 2],
+            M::Lydian => &[2, 2, 2, 1, 2, 2, 1],
+            M::Mixolydian => &[2, 2, 1, 2, 2, 1, 2],
+            M::Minor | M::Aeolian => &[2, 1, 2, 2, 1, 2, 2],
+            M::Locrian => &[1, 2, 2, 1, 2, 2, 2],
+            M::HarmonicMinor => &[2, 1, 2, 2, 1, 3, 1],
+            M::MelodicMinor => &[2, 1, 2, 2, 2, 2, 1],
+            M::MajorPentatonic => &[2, 2, 3, 2, 3],
+            M::MinorPentatonic => &[3, 2, 2, 3, 2],
+            M::Blues => &[3, 2, 1, 1, 3, 2],
+            M::WholeTone => &[2, 2, 2, 2, 2, 2],
+            M::Chromatic => &[1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+        }
+    }
+    /// Returns the number of degrees in an octave.
+    #[must_use]
+    pub const fn degrees(self) -> usize { self.steps().len() }
+
+    /// Returns the semitones of `degree` above the tonic, counting the tonic as 0.
+    ///
+    /// Degrees beyond the octave, or negative, continue into the next or previous ones.
+    #[must_use]
+    pub const fn semitones(self, degree: i32) -> i32 {
+        let (steps, len) = (self.steps(), self.degrees() as i32);
+        let (octave, degree) = (degree.div_euclid(len), degree.rem_euclid(len) as usize);
+        let (mut semitones, mut i) = (octave * 12, 0);
+        while i < degree { semitones += steps[i] as i32; i += 1; }
+        semitones
+    }
+
+    /// Returns the name, like `"harmonic minor"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        use MusicMode as M;
+        match self {
+            M::Major => "major", M::Ionian => "ionian", M::Dorian => "dorian",
+            M::Phrygian => "phrygian", M::Lydian => "lydian", M::Mixolydian => "mixolydian",
+            M::Minor => "minor", M::Aeolian => "aeolian", M::Locrian => "locrian",
+            M::HarmonicMinor => "harmonic minor", M::MelodicMinor => "melodic minor",
+            M::MajorPentatonic => "major pentatonic", M::MinorPentatonic => "minor pentatonic",
+            M::Blues => "blues", M::WholeTone => "whole tone", M::Chromatic => "chromatic",
+        }
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A [`MusicMode`] starting on a tonic pitch class.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// It's displayed like `A minor`.
+///
+/// # Example
+/// ```
+/// # use devela::{MusicMode, MusicNote, MusicPitchClass as P, MusicScale};
+/// let d_dorian = MusicScale::new(P::D, MusicMode::Dorian);
+/// assert_eq![d_dorian.degree(5), P::B];
+/// assert![!d_dorian.contains(P::Fs)];
+///
+/// let c4 = MusicNote::MIDDLE_C;
+/// let c_major = MusicScale::new(P::C, MusicMode::Major);
+/// assert_eq![c_major.note_at(c4, 7), MusicNote::new(P::C, 5)];
+/// assert_eq![c_major.note_at(c4, -1), MusicNote::new(P::B, 3)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MusicScale {
+    /// The tonic, the first degree.
+    pub root: MusicPitchClass,
+    /// The pattern of steps.
+    pub mode: MusicMode,
+}
+impl_trait![fmt::Display for MusicScale |self, f| write!(f, "{} {}", self.root, self.mode)];
+
+impl MusicScale {
+    /// Returns the scale of `mode` starting on `root`.
+    #[must_use]
+    pub const fn new(root: MusicPitchClass, mode: MusicMode) -> Self {
+        Self { root, mode }
+    }
+
+    /// Returns the number of degrees in an octave.
+    #[must_use]
+    pub const fn degrees(self) -> usize {
+        self.mode.degrees()
+    }
+
+    /// Returns the pitch class of `degree`, counting the tonic as 0.
+    #[must_use]
+    pub const fn degree(self, degree: i32) -> MusicPitchClass {
+        self.root.transpose(self.mode.semitones(degree))
+    }
+
+    /// Returns the note of `degree` above the `tonic` note, or below if negative.
+    ///
+    /// The `tonic` is expected to be of the [`root`][Self::root] pitch class.
+    #[must_use]
+    pub const fn note_at(self, tonic: MusicNote, degree: i32) -> MusicNote {
+        tonic.transpose(self.mode.semitones(degree) as i16)
+    }
+
+    /// Returns the degree of `class`, counting the tonic as 0,
+    /// or `None` if it's not in the scale.
+    #[must_use]
+    pub const fn degree_of(self, class: MusicPitchClass) -> Option<usize> {
+        let (target, steps) = (self.root.semitones_to(class), self.mode.steps());
+        let (mut semitones, mut i) = (0, 0);
+        while i < steps.len() {
+            is![semitones == target, return Some(i)];
+            semitones += steps[i];
+            i += 1;
+        }
+        None
+    }
+    /// Returns whether `class` is in the scale.
+    #[must_use]
+    pub const fn contains(self, class: MusicPitchClass) -> bool {
+        self.degree_of(class).is_some()
+    }
+}
@@ -0,0 +1,196 @@
+// devela/src/media/audio/music/tuning.rs
+//
+//! Defines [`MusicTuning`].
+//
+// https://en.wikipedia.org/wiki/Musical_tuning
+
+use crate::{Float, MusicInterval, MusicNote, MusicPitchClass, is};
+
+#[doc = crate::_tags!(audio)]
+/// A tuning table that maps notes to frequencies.
+#[doc = crate::_doc_meta!{location("media/audio/music")}]
+///
+/// It holds the frequency ratio above its root pitch class of each of the
+/// 12 semitones of the octave, repeated over every octave, and is pitched
+/// by giving the frequency of a reference note.
+///
+/// # Example
+/// ```
+/// # use devela::{MusicNote, MusicPitchClass as P, MusicTuning};
+/// let et = MusicTuning::EQUAL;
+/// assert![(et.frequency(MusicNote::A4) - 440.0).abs() < 1e-3];
+/// assert![(et.frequency(MusicNote::MIDDLE_C) - 261.626).abs() < 1e-3];
+///
+/// // Just intonation over C, pitched by its A4.
+/// let just = MusicTuning::just(P::C, MusicNote::A4, 440.0);
+/// let e4 = MusicNote::from_name("E4").unwrap();
+/// assert![(just.frequency(e4) - 330.0).abs() < 1e-3];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusicTuning {
+    root: MusicPitchClass,
+    ratios: [f32; 12],
+    reference: MusicNote,
+    frequency: f32,
+}
+
+impl Default for MusicTuning {
+    /// Returns [`MusicTuning::EQUAL`].
+    fn default() -> Self {
+        Self::EQUAL
+    }
+}
+
+impl MusicTuning {
+    /// Twelve-tone equal temperament, with `A4` at 440 Hz.
+    pub const EQUAL: Self = Self::equal(440.0);
+
+    /// Returns twelve-tone equal temperament, with `A4` at `frequency`.
+    #[must_use]
+    pub const fn equal(frequency: f32) -> Self {
+        let mut cents = [0.0; 12];
+        let mut i = 0;
+        while i < 12 {
+            cents[i] = i as f32 * 100.0;
+            i += 1;
+        }
+        Self::from_cents(MusicPitchClass::C, cents, MusicNote::A4, frequency)
+    }
+
+    /// Returns 5-limit just intonation over `root`,
+    /// with the `reference` note at `frequency`.
+    ///
+    /// Its ratios are the ones of [`MusicInterval::just_ratio`].
+    #[must_use]
+    pub const fn just(root: MusicPitchClass, reference: MusicNote, frequency: f32) -> Self {
+        let mut ratios = [0.0; 12];
+        let mut i = 0;
+        while i < 12 {
+            let (num, den) = MusicInterval::new(i as i8).just_ratio();
+            ratios[i] = num as f32 / den as f32;
+            i += 1;
+        }
+        Self::from_ratios(root, ratios, reference, frequency)
+    }
+
+    /// Returns Pythagorean tuning over `root`, with the `reference` note at `frequency`.
+    ///
+    /// Its ratios stack pure fifths of `3/2`, from 5 below the root to 6 above it.
+    #[must_use]
+    pub const fn pythagorean(root: MusicPitchClass, reference: MusicNote, frequency: f32) -> Self {
+        let mut ratios = [0.0; 12];
+        let mut i = 0;
+        while i < 12 {
+            let fifths = (i as i32 * 7) % 12;
+            let fifths = is![fifths > 6, fifths - 12, fifths];
+            let mut ratio = 1.0_f64;
+            let mut f = 0;
+            while f < fifths.abs() {
+                ratio = is![fifths > 0, ratio * 1.5, ratio / 1.5];
+                f += 1;
+            }
+            while ratio >= 2.0 {
+                ratio /= 2.0;
+            }
+            while ratio < 1.0 {
+                ratio *= 2.0;
+            }
+            ratios[i] = ratio as f32;
+            i += 1;
+        }
+        Self::from_ratios(root, ratios, reference, frequency)
+    }
+
+    /// Returns a tuning of the given `ratios` over `root` for each semitone above it,
+    /// with the `reference` note at `frequency`.
+    ///
+    /// The first ratio is expected to be `1.0`, and the rest to ascend below `2.0`.
+    #[must_use]
+    pub const fn from_ratios(
+        root: MusicPitchClass,
+        ratios: [f32; 12],
+        reference: MusicNote,
+        frequency: f32,
+    ) -> Self {
+        Self { root, ratios, reference, frequency }
+    }
+
+    /// Returns a tuning of the given `cents` over `root` for each semitone above it,
+    /// with the `reference` note at `frequency`.
+    ///
+    /// A cent is a hundredth of an equal-tempered semitone.
+    #[must_use]
+    pub const fn from_cents(
+        root: MusicPitchClass,
+        cents: [f32; 12],
+        reference: MusicNote,
+        frequency: f32,
+    ) -> Self {
+        let mut ratios = [0.0; 12];
+        let mut i = 0;
+        while i < 12 {
+            let octaves = Float(cents[i] / 1200.0);
+            ratios[i] = octaves.exp2_series(octaves.exp2_series_terms()).0;
+            i += 1;
+        }
+        Self::from_ratios(root, ratios, reference, frequency)
+    }
+
+    /// Returns the same tuning, with the `reference` note at `frequency`.
+    #[must_use]
+    pub const fn with_reference(self, reference: MusicNote, frequency: f32) -> Self {
+        Self { reference, frequency, ..self }
+    }
+
+    /// Returns the root pitch class of the ratios.
+    #[must_use]
+    pub const fn root(&self) -> MusicPitchClass {
+        self.root
+    }
+    /// Returns the ratios of each semitone above the root.
+    #[must_use]
+    pub const fn ratios(&self) -> &[f32; 12] {
+        &self.ratios
+    }
+    /// Returns the reference note.
+    #[must_use]
+    pub const fn reference(&self) -> MusicNote {
+        self.reference
+    }
+    /// Returns the frequency of the reference note.
+    #[must_use]
+    pub const fn reference_frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    /// Returns the frequency of `note`, in Hz.
+    #[must_use]
+    pub const fn frequency(&self, note: MusicNote) -> f32 {
+        // the root note at or below the reference, and its frequency
+        let offset = self.root.semitones_to(self.reference.class());
+        let root_key = self.reference.key() as i32 - offset as i32;
+        let root_frequency = self.frequency / self.ratios[offset as usize];
+
+        let above = note.key() as i32 - root_key;
+        let (octaves, semitone) = (above.div_euclid(12), above.rem_euclid(12));
+        let mut frequency = root_frequency * self.ratios[semitone as usize];
+        let mut o = 0;
+        while o < octaves.abs() {
+            frequency = is![octaves > 0, frequency * 2.0, frequency / 2.0];
+            o += 1;
+        }
+        frequency
+    }
+
+    /// Returns the frequencies of the 128 MIDI note numbers, in Hz.
+    #[must_use]
+    pub const fn midi_table(&self) -> [f32; 128] {
+        let mut table = [0.0; 128];
+        let mut i = 0;
+        while i < 128 {
+            table[i] = self.frequency(MusicNote::from_midi(i as u8));
+            i += 1;
+        }
+        table
+    }
+}
@@ -0,0 +1,45 @@
+// devela/src/media/audio/synth/_helper.rs
+//
+//! Synthesis math shared by the generators.
+//
+
+use crate::{Float, is};
+use ::core::f32::consts::TAU;
+
+/// Returns the sine of a phase in turns.
+pub(super) const fn sin_turns(turns: f32) -> f32 {
+    Float(TAU * turns).sin_minimax().0
+}
+
+/// Returns the number of samples lasting `secs` seconds, of at least 1.
+pub(super) const fn samples(secs: f32, sample_rate: u32) -> f32 {
+    let samples = secs * sample_rate as f32;
+    is![samples < 1.0, 1.0, samples]
+}
+
+/// The polynomial band-limited step residual at phase `t` for a phase step `dt`,
+/// for a step of height 2 at phase 0.
+pub(super) const fn poly_blep(t: f32, dt: f32) -> f32 {
+    if t < dt {
+        let x = t / dt;
+        x + x - x * x - 1.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt;
+        x * x + x + x + 1.0
+    } else {
+        0.0
+    }
+}
+/// The polynomial band-limited ramp residual at phase `t` for a phase step `dt`,
+/// for a slope change of 2 per sample at phase 0.
+pub(super) const fn poly_blamp(t: f32, dt: f32) -> f32 {
+    if t < dt {
+        let x = t / dt - 1.0;
+        -x * x * x / 3.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt + 1.0;
+        x * x * x / 3.0
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,193 @@
+// devela/src/media/audio/synth/_test.rs
+
+#![allow(clippy::float_cmp, reason = "exact levels of linear stages")]
+
+use crate::{AudioChannels, AudioEffectError, PcmBuf, PcmSource, PcmSpec, Phase, PhaseStep};
+use crate::{SignalNext, SynthAdsr, SynthEnvelope, SynthFmOperator, SynthFmVoice, SynthLfo};
+use crate::{SynthNoise, SynthNoiseColor, SynthOsc, SynthOscVoice, SynthPoly, SynthSegment};
+use crate::{SynthVoice, SynthWave, SynthWavetable};
+
+#[test]
+fn oscillators_are_periodic_and_bounded() {
+    for wave in [SynthWave::Sine, SynthWave::Saw, SynthWave::Square, SynthWave::Triangle] {
+        // a period of exactly 128 samples
+        let mut osc = SynthOsc::new(wave, 375.0, 48_000);
+        let first: [f32; 128] = core::array::from_fn(|_| osc.next_sample());
+        let second: [f32; 128] = core::array::from_fn(|_| osc.next_sample());
+        for (a, b) in first.iter().zip(&second) {
+            assert!((a - b).abs() < 1e-3, "{wave:?}");
+        }
+        assert!(first.iter().all(|s| s.abs() <= 1.1), "{wave:?}");
+        let mean = first.iter().sum::<f32>() / 128.0;
+        assert!(mean.abs() < 0.02, "{wave:?} mean {mean}");
+    }
+    let quarter = Phase(u32::MAX / 4 + 1);
+    assert!((SynthWave::Sine.at(quarter) - 1.0).abs() < 1e-4);
+    assert!((SynthWave::Triangle.at(quarter) - 1.0).abs() < 1e-4);
+    assert_eq!(SynthWave::Square.at(quarter), 1.0);
+}
+#[test]
+fn band_limiting_smooths_discontinuities() {
+    let step = PhaseStep::from_freq(1_000.0, 8_000);
+    for wave in [SynthWave::Saw, SynthWave::Square] {
+        // just before and after the discontinuity at the wrap
+        let before = Phase(u32::MAX - step.raw() / 2);
+        let after = Phase(step.raw() / 2);
+        let naive = (wave.at(before) - wave.at(after)).abs();
+        let limited =
+            (wave.at_band_limited(before, step) - wave.at_band_limited(after, step)).abs();
+        assert!(limited < naive * 0.8, "{wave:?}: {limited} vs {naive}");
+    }
+    // far from any discontinuity, both agree
+    let middle = Phase(u32::MAX / 8 * 3);
+    let (naive, limited) =
+        (SynthWave::Saw.at(middle), SynthWave::Saw.at_band_limited(middle, step));
+    assert!((naive - limited).abs() < 1e-6);
+}
+#[test]
+fn wavetables_interpolate_harmonics() {
+    let mut table = [0.0; 64];
+    SynthWavetable::fill_harmonics(&mut table, &[1.0]);
+    assert!((table[16] - 1.0).abs() < 1e-4);
+    assert!(table[0].abs() < 1e-6);
+    let mut wt = SynthWavetable::new(&table, 750.0, 48_000);
+    // the table is stepped 1 entry per sample
+    let samples: [f32; 64] = core::array::from_fn(|_| wt.next_sample());
+    for (s, t) in samples.iter().zip(&table) {
+        assert!((s - t).abs() < 1e-4);
+    }
+    let half = SynthWavetable::new(&table, 1.0, 1).at(Phase(1 << 25));
+    assert!((half - (table[0] + table[1]) / 2.0).abs() < 1e-5);
+}
+#[test]
+fn noise_is_deterministic_and_bounded() {
+    for color in [SynthNoiseColor::White, SynthNoiseColor::Pink, SynthNoiseColor::Brown] {
+        let (mut a, mut b) = (SynthNoise::new(color, 7), SynthNoise::new(color, 7));
+        let mut energy = 0.0;
+        for _ in 0..4096 {
+            let s = a.next();
+            assert_eq!(s, b.next());
+            assert!((-1.0..=1.0).contains(&s), "{color:?}");
+            energy += s * s;
+        }
+        assert!(energy > 4096.0 * 0.005, "{color:?} is silent");
+    }
+    let mut other = SynthNoise::new(SynthNoiseColor::White, 8);
+    assert_ne!(SynthNoise::new(SynthNoiseColor::White, 7).next(), other.next());
+}
+#[test]
+fn adsr_follows_its_stages() {
+    let mut env = SynthAdsr::new(10, 0.2, 0.2, 0.5, 0.4);
+    assert!(!env.is_active());
+    env.note_on();
+    let levels: [f32; 5] = core::array::from_fn(|_| env.next());
+    assert_eq!(levels, [0.5, 1.0, 0.75, 0.5, 0.5]);
+    assert!(env.is_active() && env.is_gated());
+    env.note_off();
+    let levels: [f32; 5] = core::array::from_fn(|_| env.next());
+    assert_eq!(levels, [0.375, 0.25, 0.125, 0.0, 0.0]);
+    assert!(!env.is_active());
+    // a one-shot without sustain finishes by itself
+    let mut env = SynthAdsr::new(10, 0.0, 0.1, 0.0, 0.0);
+    env.note_on();
+    let _ = (env.next(), env.next());
+    assert!(!env.is_active());
+}
+#[test]
+fn envelopes_hold_at_their_sustain_segment() {
+    let segments =
+        [SynthSegment::new(1.0, 0.1), SynthSegment::new(0.5, 0.1), SynthSegment::new(0.0, 0.2)];
+    let mut env = SynthEnvelope::new(&segments, Some(1), 10);
+    env.note_on();
+    let levels: [f32; 4] = core::array::from_fn(|_| env.next());
+    assert_eq!(levels, [1.0, 0.5, 0.5, 0.5]);
+    env.note_off();
+    let levels: [f32; 3] = core::array::from_fn(|_| env.next());
+    assert_eq!(levels, [0.25, 0.0, 0.0]);
+    assert!(!env.is_active());
+}
+#[test]
+fn lfos_and_fm_operators_modulate() {
+    let mut lfo = SynthLfo::new(SynthWave::Square, 1.0, 4).with_depth(0.25, 1.0);
+    let values: [f32; 4] = core::array::from_fn(|_| lfo.next());
+    assert_eq!(values, [1.25, 1.25, 0.75, 0.75]);
+
+    let env = SynthAdsr::new(48_000, 0.0, 0.0, 1.0, 0.0);
+    let mut carrier = SynthFmOperator::new(1.0, 1.0, env);
+    carrier.note_on(480.0, 48_000);
+    let plain: [f32; 100] = core::array::from_fn(|_| carrier.next_sample(0.0));
+    carrier.note_on(480.0, 48_000);
+    let shifted: [f32; 100] = core::array::from_fn(|_| carrier.next_sample(0.25));
+    // a quarter turn of modulation turns the sine into a cosine
+    assert!((plain[25] - shifted[0]).abs() < 1e-3);
+    carrier.note_off();
+    let _ = carrier.next_sample(0.0);
+    assert!(!carrier.is_active());
+
+    let modulator = SynthFmOperator::new(2.0, 0.5, env);
+    let mut voice = SynthFmVoice::new(carrier, modulator);
+    voice.note_on(480.0, 1.0);
+    let samples: [f32; 100] = core::array::from_fn(|_| voice.next_sample());
+    assert!(samples.iter().any(|s| s.abs() > 0.9));
+    assert!(samples.iter().zip(&plain).any(|(a, b)| (a - b).abs() > 0.1));
+}
+#[test]
+fn poly_allocates_and_steals_voices() {
+    let env = SynthAdsr::new(8_000, 0.0, 0.0, 1.0, 0.0);
+    let mut synth = SynthPoly::<_, 2>::new(
+        SynthOscVoice::new(SynthWave::Sine, env),
+        AudioChannels::Mono,
+        8_000,
+    );
+    assert!((SynthPoly::<SynthOscVoice, 2>::note_freq(69) - 440.0).abs() < 1e-3);
+    assert!((SynthPoly::<SynthOscVoice, 2>::note_freq(81) - 880.0).abs() < 1e-2);
+    assert_eq!(synth.note_on(60, 1.0), Some(0));
+    assert_eq!(synth.note_on(64, 1.0), Some(1));
+    // the oldest note is stolen
+    assert_eq!(synth.note_on(67, 1.0), Some(0));
+    synth.note_off(60); // no longer playing
+    assert_eq!(synth.active_count(), 2);
+    synth.note_off(67);
+    let mut out = [0.0; 8];
+    let spec = synth.spec();
+    assert_eq!(synth.read(PcmBuf::new(&mut out[..], spec)), Ok(8));
+    assert_eq!(synth.active_count(), 1);
+    assert_eq!(synth.note_on(72, 1.0), Some(0));
+    synth.all_notes_off();
+    assert_eq!(synth.read(PcmBuf::new(&mut out[..], spec)), Ok(8));
+    assert_eq!(synth.active_count(), 0);
+    assert_eq!(
+        SynthPoly::<_, 0>::new(synth.voices()[0], AudioChannels::Mono, 8_000).note_on(60, 1.0),
+        None
+    );
+}
+#[test]
+fn poly_rejects_mismatched_buffers() {
+    let env = SynthAdsr::new(8_000, 0.0, 0.0, 1.0, 0.0);
+    let mut synth = SynthPoly::<_, 1>::new(
+        SynthOscVoice::new(SynthWave::Square, env),
+        AudioChannels::Stereo,
+        8_000,
+    );
+    let mut out = [0.0; 5];
+    let spec = synth.spec();
+    let mono = PcmSpec { channels: AudioChannels::Mono, ..spec };
+    let slow = PcmSpec { sample_rate: 4_000, ..spec };
+    assert_eq!(
+        synth.read(PcmBuf::new(&mut out[..], mono)),
+        Err(AudioEffectError::MismatchedChannels)
+    );
+    assert_eq!(synth.read(PcmBuf::new(&mut out[..], slow)), Err(AudioEffectError::MismatchedRate));
+    assert_eq!(
+        synth.read(PcmBuf::new(&mut out[..], spec)),
+        Err(AudioEffectError::IncompleteFrames)
+    );
+    // a centered mono voice is spread equally into both channels
+    synth.note_on(69, 0.5);
+    let mut out = [0.0; 8];
+    assert_eq!(synth.read(PcmBuf::new(&mut out[..], spec)), Ok(4));
+    for frame in out.chunks_exact(2) {
+        assert_eq!(frame[0], frame[1]);
+    }
+    assert!(out.iter().all(|s| s.abs() <= 0.5));
+}
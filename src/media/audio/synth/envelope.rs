@@ -0,0 +1,298 @@
+// devela/src/media/audio/synth/envelope.rs
+//
+//! Defines [`SynthAdsr`], [`SynthEnvelope`], [`SynthSegment`].
+//
+
+use super::_helper::samples;
+use crate::{_impl_init, SignalNext, is};
+
+#[doc = crate::_tags!(audio)]
+/// An attack-decay-sustain-release envelope with linear segments.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(SynthAdsr = 32|256)}]
+///
+/// While the gate is on, the level rises to 1 during the attack, falls to the
+/// sustain level during the decay, and holds there. When the gate is turned off,
+/// it falls from wherever it is to 0 during the release.
+///
+/// A zero sustain level ends the envelope after the decay, like a one-shot.
+/// Times are in seconds.
+///
+/// # Example
+/// ```
+/// # use devela::{SignalNext, SynthAdsr};
+/// let mut env = SynthAdsr::new(10, 0.2, 0.2, 0.5, 0.4);
+/// env.note_on();
+/// let attack: [f32; 4] = core::array::from_fn(|_| env.next());
+/// assert_eq![attack, [0.5, 1.0, 0.75, 0.5]];
+/// env.note_off();
+/// let release: [f32; 5] = core::array::from_fn(|_| env.next());
+/// assert_eq![release, [0.375, 0.25, 0.125, 0.0, 0.0]];
+/// assert![!env.is_active()];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthAdsr {
+    /// The lengths of the attack, decay and release, in samples.
+    attack: f32,
+    decay: f32,
+    release: f32,
+    sustain: f32,
+    /// The current stage: 0 idle, 1 attack, 2 decay, 3 sustain, 4 release.
+    stage: u8,
+    level: f32,
+    /// The level change per sample of the current stage.
+    step: f32,
+    sample_rate: u32,
+}
+_impl_init![Self::new(0, 0.0, 0.0, 1.0, 0.0) => SynthAdsr];
+
+impl SynthAdsr {
+    const IDLE: u8 = 0;
+    const ATTACK: u8 = 1;
+    const DECAY: u8 = 2;
+    const SUSTAIN: u8 = 3;
+    const RELEASE: u8 = 4;
+
+    /// Returns an idle envelope, clamping the sustain level between 0 and 1.
+    #[must_use]
+    pub const fn new(
+        sample_rate: u32,
+        attack: f32,
+        decay: f32,
+        sustain: f32,
+        release: f32,
+    ) -> Self {
+        Self {
+            attack: samples(attack, sample_rate),
+            decay: samples(decay, sample_rate),
+            release: samples(release, sample_rate),
+            sustain: sustain.clamp(0.0, 1.0),
+            stage: Self::IDLE,
+            level: 0.0,
+            step: 0.0,
+            sample_rate,
+        }
+    }
+    /// Returns the sample rate.
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+    /// Returns the sustain level.
+    #[must_use]
+    pub const fn sustain(&self) -> f32 {
+        self.sustain
+    }
+    /// Returns the current level.
+    #[must_use]
+    pub const fn level(&self) -> f32 {
+        self.level
+    }
+    /// Returns whether the envelope is sounding, until the end of its release.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.stage != Self::IDLE
+    }
+    /// Returns whether the gate is on.
+    #[must_use]
+    pub const fn is_gated(&self) -> bool {
+        matches![self.stage, Self::ATTACK | Self::DECAY | Self::SUSTAIN]
+    }
+
+    /// Turns the gate on, attacking from the current level.
+    pub const fn note_on(&mut self) {
+        self.stage = Self::ATTACK;
+        self.step = 1.0 / self.attack;
+    }
+    /// Turns the gate off, releasing from the current level.
+    pub const fn note_off(&mut self) {
+        is![!self.is_active(), return];
+        self.stage = Self::RELEASE;
+        self.step = -self.level / self.release;
+    }
+    /// Silences the envelope immediately.
+    pub const fn reset(&mut self) {
+        self.stage = Self::IDLE;
+        self.level = 0.0;
+    }
+
+    /// Returns the next level.
+    pub const fn next_level(&mut self) -> f32 {
+        match self.stage {
+            Self::ATTACK => {
+                self.level += self.step;
+                if self.level >= 1.0 {
+                    self.level = 1.0;
+                    self.stage = Self::DECAY;
+                    self.step = (self.sustain - 1.0) / self.decay;
+                }
+            }
+            Self::DECAY => {
+                self.level += self.step;
+                if self.level <= self.sustain {
+                    self.level = self.sustain;
+                    self.stage = is![self.sustain > 0.0, Self::SUSTAIN, Self::IDLE];
+                }
+            }
+            Self::RELEASE => {
+                self.level += self.step;
+                if self.level <= 0.0 {
+                    self.level = 0.0;
+                    self.stage = Self::IDLE;
+                }
+            }
+            _ => {}
+        }
+        self.level
+    }
+}
+
+impl SignalNext for SynthAdsr {
+    type Sample = f32;
+    fn next(&mut self) -> f32 {
+        self.next_level()
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A segment of a [`SynthEnvelope`], reaching a level after some time.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(SynthSegment = 8|64)}]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthSegment {
+    /// The level reached at the end of the segment.
+    pub level: f32,
+    /// The duration in seconds.
+    pub secs: f32,
+}
+impl SynthSegment {
+    /// Returns a segment reaching `level` in `secs` seconds.
+    #[must_use]
+    pub const fn new(level: f32, secs: f32) -> Self {
+        Self { level, secs }
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A multi-segment envelope with linear segments over a borrowed list.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// While the gate is on, the segments are followed in order, each starting
+/// from the level where the previous one ended, up to the end of the sustain
+/// segment, where the level holds. Turning the gate off skips to the segment
+/// after the sustain one. Without a sustain segment, every segment is followed,
+/// which suits one-shot sounds.
+///
+/// The envelope becomes inactive after its last segment, holding its level.
+///
+/// # Example
+/// ```
+/// # use devela::{SignalNext, SynthEnvelope, SynthSegment as S};
+/// // a percussive pitch drop: from 1 to 0.25 and then to 0
+/// let segments = [S::new(1.0, 0.0), S::new(0.25, 0.3), S::new(0.0, 0.1)];
+/// let mut env = SynthEnvelope::new(&segments, None, 10);
+/// env.note_on();
+/// let levels: [f32; 6] = core::array::from_fn(|_| env.next());
+/// assert_eq![levels, [1.0, 0.75, 0.5, 0.25, 0.0, 0.0]];
+/// assert![!env.is_active()];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthEnvelope<'a> {
+    segments: &'a [SynthSegment],
+    sustain: Option<usize>,
+    sample_rate: u32,
+    /// The current segment, which is past the end when finished.
+    index: usize,
+    gated: bool,
+    level: f32,
+    /// The level change per sample of the current segment.
+    step: f32,
+}
+
+impl<'a> SynthEnvelope<'a> {
+    /// Returns an idle envelope over `segments`, holding at the end of `sustain`.
+    #[must_use]
+    pub const fn new(
+        segments: &'a [SynthSegment],
+        sustain: Option<usize>,
+        sample_rate: u32,
+    ) -> Self {
+        let len = segments.len();
+        Self {
+            segments,
+            sustain,
+            sample_rate,
+            index: len,
+            gated: false,
+            level: 0.0,
+            step: 0.0,
+        }
+    }
+    /// Returns the segments.
+    #[must_use]
+    pub const fn segments(&self) -> &'a [SynthSegment] {
+        self.segments
+    }
+    /// Returns the current level.
+    #[must_use]
+    pub const fn level(&self) -> f32 {
+        self.level
+    }
+    /// Returns whether the envelope is still following its segments.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.index < self.segments.len()
+    }
+
+    /// Turns the gate on, starting the first segment from the current level.
+    pub const fn note_on(&mut self) {
+        self.gated = true;
+        self.start(0);
+    }
+    /// Turns the gate off, skipping to the segment after the sustain one.
+    pub const fn note_off(&mut self) {
+        self.gated = false;
+        if let Some(sustain) = self.sustain
+            && self.index <= sustain
+        {
+            self.start(sustain + 1);
+        }
+    }
+    /// Silences the envelope immediately.
+    pub const fn reset(&mut self) {
+        self.index = self.segments.len();
+        self.gated = false;
+        self.level = 0.0;
+    }
+
+    /// Returns the next level.
+    pub const fn next_level(&mut self) -> f32 {
+        is![!self.is_active(), return self.level];
+        let target = self.segments[self.index].level;
+        self.level += self.step;
+        let reached = is![self.step >= 0.0, self.level >= target, self.level <= target];
+        if reached {
+            self.level = target;
+            let holds = matches![self.sustain, Some(s) if s == self.index] && self.gated;
+            is![!holds, self.start(self.index + 1), self.step = 0.0];
+        }
+        self.level
+    }
+
+    /// Starts the segment at `index`, from the current level.
+    const fn start(&mut self, index: usize) {
+        self.index = index;
+        if index < self.segments.len() {
+            let segment = self.segments[index];
+            let samples = segment.secs * self.sample_rate as f32;
+            self.step = is![samples < 1.0, segment.level - self.level, {
+                (segment.level - self.level) / samples
+            }];
+        }
+    }
+}
+
+impl SignalNext for SynthEnvelope<'_> {
+    type Sample = f32;
+    fn next(&mut self) -> f32 {
+        self.next_level()
+    }
+}
@@ -0,0 +1,91 @@
+// devela/src/media/audio/synth/fm.rs
+//
+//! Defines [`SynthFmOperator`].
+//
+
+use super::_helper::sin_turns;
+use crate::{Phase, PhaseAccum, PhaseStep, SynthAdsr};
+
+#[doc = crate::_tags!(audio wave)]
+/// A frequency modulation operator: a sine oscillator with its own envelope.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Its frequency is a `ratio` of the note frequency, and its phase is offset
+/// by the modulation input, in turns, and by its own previous output scaled
+/// by `feedback`. Operators are chained by feeding the output of the
+/// modulators into the carriers.
+///
+/// # Example
+/// ```
+/// # use devela::{SynthAdsr, SynthFmOperator};
+/// let env = SynthAdsr::new(8_000, 0.0, 0.0, 1.0, 0.0);
+/// let mut modulator = SynthFmOperator::new(2.0, 0.5, env);
+/// let mut carrier = SynthFmOperator::new(1.0, 1.0, env);
+/// modulator.note_on(440.0, 8_000);
+/// carrier.note_on(440.0, 8_000);
+/// let sample = carrier.next_sample(modulator.next_sample(0.0));
+/// assert![sample.abs() <= 1.0];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthFmOperator {
+    /// The phase and its step.
+    pub accum: PhaseAccum,
+    /// The ratio of its frequency to the note frequency.
+    pub ratio: f32,
+    /// The output level, which for modulators is the modulation index in turns.
+    pub level: f32,
+    /// The amount of its own output fed back into its phase.
+    pub feedback: f32,
+    /// The envelope of its level.
+    pub envelope: SynthAdsr,
+    /// The last two outputs, averaged for a stable feedback.
+    last: [f32; 2],
+}
+
+impl SynthFmOperator {
+    /// Returns an operator with the given frequency ratio, level and envelope.
+    #[must_use]
+    pub const fn new(ratio: f32, level: f32, envelope: SynthAdsr) -> Self {
+        let accum = PhaseAccum::new(Phase::ZERO, PhaseStep::ZERO);
+        Self {
+            accum,
+            ratio,
+            level,
+            feedback: 0.0,
+            envelope,
+            last: [0.0; 2],
+        }
+    }
+    /// Sets the amount of feedback.
+    #[must_use]
+    pub const fn with_feedback(mut self, feedback: f32) -> Self {
+        self.feedback = feedback;
+        self
+    }
+
+    /// Starts a note at `freq` Hz, restarting the phase and the envelope.
+    pub const fn note_on(&mut self, freq: f32, sample_rate: u32) {
+        self.accum =
+            PhaseAccum::new(Phase::ZERO, PhaseStep::from_freq(freq * self.ratio, sample_rate));
+        self.last = [0.0; 2];
+        self.envelope.note_on();
+    }
+    /// Releases the note.
+    pub const fn note_off(&mut self) {
+        self.envelope.note_off();
+    }
+    /// Returns whether its envelope is sounding.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.envelope.is_active()
+    }
+
+    /// Returns the next sample, with its phase offset by `modulation` turns.
+    pub const fn next_sample(&mut self, modulation: f32) -> f32 {
+        let feedback = self.feedback * (self.last[0] + self.last[1]) * 0.5;
+        let phase = self.accum.next_phase().as_f32() + modulation + feedback;
+        let out = sin_turns(phase) * self.level * self.envelope.next_level();
+        self.last = [self.last[1], out];
+        out
+    }
+}
@@ -0,0 +1,65 @@
+// devela/src/media/audio/synth/lfo.rs
+//
+//! Defines [`SynthLfo`].
+//
+
+use crate::{Phase, PhaseAccum, PhaseStep, SignalNext, SynthWave};
+
+#[doc = crate::_tags!(audio wave)]
+/// A low-frequency oscillator for modulation.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(SynthLfo = 20|160)}]
+///
+/// It emits `offset + depth * wave`, using the naive waveform,
+/// for modulating pitch, gain, pan or filter cutoffs.
+///
+/// # Example
+/// ```
+/// # use devela::{SignalNext, SynthLfo, SynthWave};
+/// // a vibrato of ±2% at 5 Hz
+/// let mut lfo = SynthLfo::new(SynthWave::Triangle, 5.0, 20).with_depth(0.02, 1.0);
+/// let levels: [f32; 4] = core::array::from_fn(|_| lfo.next());
+/// let expected = [1.0, 1.02, 1.0, 0.98];
+/// assert![levels.iter().zip(expected).all(|(l, e)| (l - e).abs() < 1e-6)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthLfo {
+    /// The waveform.
+    pub wave: SynthWave,
+    /// The phase and its step.
+    pub accum: PhaseAccum,
+    /// The amplitude of the waveform.
+    pub depth: f32,
+    /// The value around which it oscillates.
+    pub offset: f32,
+}
+
+impl SynthLfo {
+    /// Returns an oscillator at `freq` Hz between -1 and 1, starting at phase zero.
+    #[must_use]
+    pub const fn new(wave: SynthWave, freq: f32, sample_rate: u32) -> Self {
+        let accum = PhaseAccum::new(Phase::ZERO, PhaseStep::from_freq(freq, sample_rate));
+        Self { wave, accum, depth: 1.0, offset: 0.0 }
+    }
+    /// Sets the depth and the offset.
+    #[must_use]
+    pub const fn with_depth(mut self, depth: f32, offset: f32) -> Self {
+        self.depth = depth;
+        self.offset = offset;
+        self
+    }
+    /// Sets the frequency, keeping the phase.
+    pub const fn set_freq(&mut self, freq: f32, sample_rate: u32) {
+        self.accum.step = PhaseStep::from_freq(freq, sample_rate);
+    }
+    /// Returns the next value.
+    pub const fn next_value(&mut self) -> f32 {
+        self.offset + self.depth * self.wave.at(self.accum.next_phase())
+    }
+}
+
+impl SignalNext for SynthLfo {
+    type Sample = f32;
+    fn next(&mut self) -> f32 {
+        self.next_value()
+    }
+}
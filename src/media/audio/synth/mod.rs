@@ -0,0 +1,33 @@
+// devela/src/media/audio/synth/mod.rs
+//
+//! Sound synthesis, generators, envelopes, and modulation.
+//
+// Every generator emits `f32` samples through `SignalNext`, driven by a `PhaseAccum`.
+
+#[cfg(test)]
+mod _test;
+mod _helper; // synthesis math
+
+mod envelope; // SynthAdsr, SynthEnvelope, SynthSegment
+mod fm; // SynthFmOperator
+mod lfo; // SynthLfo
+mod noise; // SynthNoise, SynthNoiseColor
+mod osc; // SynthOsc, SynthWave
+mod poly; // SynthPoly
+mod voice; // SynthVoice, SynthFmVoice, SynthOscVoice
+mod wavetable; // SynthWavetable
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            envelope::*,
+            fm::*,
+            lfo::*,
+            noise::*,
+            osc::*,
+            poly::*,
+            voice::*,
+            wavetable::*,
+        };
+    }
+}
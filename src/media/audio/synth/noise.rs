@@ -0,0 +1,88 @@
+// devela/src/media/audio/synth/noise.rs
+//
+//! Defines [`SynthNoise`], [`SynthNoiseColor`].
+//
+// https://www.firstpr.com.au/dsp/pink-noise/
+
+use crate::{_impl_init, Float, Pcg32, SignalNext};
+
+#[doc = crate::_tags!(audio rand)]
+/// The spectral color of a [`SynthNoise`].
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(SynthNoiseColor = 1|8)}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SynthNoiseColor {
+    /// Equal power at every frequency.
+    #[default]
+    White,
+    /// Power falling by 3 dB per octave.
+    Pink,
+    /// Power falling by 6 dB per octave.
+    Brown,
+}
+_impl_init![Self::White => SynthNoiseColor];
+
+#[doc = crate::_tags!(audio rand)]
+/// A seeded noise generator.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// White noise is uniform between -1 and 1. Pink noise filters it with
+/// Paul Kellet's economy filter, and brown noise integrates it with a leak.
+/// Both are scaled to a similar loudness, and clamped between -1 and 1.
+///
+/// The same seed always emits the same samples.
+///
+/// # Example
+/// ```
+/// # use devela::{SignalNext, SynthNoise, SynthNoiseColor};
+/// let mut noise = SynthNoise::new(SynthNoiseColor::Pink, 42);
+/// let samples: [f32; 64] = core::array::from_fn(|_| noise.next());
+/// assert![samples.iter().all(|s| s.abs() <= 1.0)];
+/// assert_eq![SynthNoise::new(SynthNoiseColor::Pink, 42).next(), samples[0]];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SynthNoise {
+    color: SynthNoiseColor,
+    rng: Pcg32,
+    /// The filter memories.
+    state: [f32; 3],
+}
+
+impl SynthNoise {
+    /// Returns a generator of the given color, seeded by `seed`.
+    #[must_use]
+    pub const fn new(color: SynthNoiseColor, seed: u64) -> Self {
+        Self { color, rng: Pcg32::new(seed, 0), state: [0.0; 3] }
+    }
+    /// Returns the color.
+    #[must_use]
+    pub const fn color(&self) -> SynthNoiseColor {
+        self.color
+    }
+
+    /// Returns the next sample.
+    pub const fn next_sample(&mut self) -> f32 {
+        let white = (self.rng.next_u32() >> 8) as f32 * (2.0 / (1 << 24) as f32) - 1.0;
+        let s = &mut self.state;
+        let sample = match self.color {
+            SynthNoiseColor::White => return white,
+            SynthNoiseColor::Pink => {
+                s[0] = 0.997_65 * s[0] + white * 0.099_046;
+                s[1] = 0.963 * s[1] + white * 0.296_516_4;
+                s[2] = 0.57 * s[2] + white * 1.052_691_3;
+                (s[0] + s[1] + s[2] + white * 0.184_8) * 0.25
+            }
+            SynthNoiseColor::Brown => {
+                s[0] = (s[0] + 0.02 * white) / 1.02;
+                s[0] * 3.5
+            }
+        };
+        Float(sample).clamp(-1.0, 1.0).0
+    }
+}
+
+impl SignalNext for SynthNoise {
+    type Sample = f32;
+    fn next(&mut self) -> f32 {
+        self.next_sample()
+    }
+}
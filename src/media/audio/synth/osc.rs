@@ -0,0 +1,131 @@
+// devela/src/media/audio/synth/osc.rs
+//
+//! Defines [`SynthOsc`], [`SynthWave`].
+//
+
+use super::_helper::{poly_blamp, poly_blep, sin_turns};
+use crate::{_impl_init, Phase, PhaseAccum, PhaseStep, SignalAt, SignalNext, is};
+
+#[doc = crate::_tags!(audio wave)]
+/// The shape of a periodic waveform.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(SynthWave = 1|8)}]
+///
+/// Every shape has a unit peak amplitude and no DC offset. Sine and triangle
+/// start at zero, rising; saw rises from -1 to 1; square starts at 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SynthWave {
+    /// A pure tone.
+    #[default]
+    Sine,
+    /// A rising ramp, with every harmonic.
+    Saw,
+    /// A symmetric pulse, with the odd harmonics.
+    Square,
+    /// A triangle, with softer odd harmonics.
+    Triangle,
+}
+_impl_init![Self::Sine => SynthWave];
+
+impl SynthWave {
+    /// Returns the naive value of the waveform at `phase`.
+    ///
+    /// This aliases at audio rates, so it's only suited for low frequencies,
+    /// like the ones of an [`SynthLfo`][crate::SynthLfo].
+    #[must_use]
+    pub const fn at(self, phase: Phase) -> f32 {
+        let t = phase.as_f32();
+        match self {
+            Self::Sine => sin_turns(t),
+            Self::Saw => 2.0 * t - 1.0,
+            Self::Square => is![t < 0.5, 1.0, -1.0],
+            Self::Triangle => 1.0 - 4.0 * (wrap(t + 0.25) - 0.5).abs(),
+        }
+    }
+
+    /// Returns the band-limited value of the waveform at `phase`,
+    /// advancing by `step` each sample.
+    ///
+    /// The discontinuities are smoothed by polynomial band-limited steps (PolyBLEP)
+    /// and ramps (PolyBLAMP), which removes most of the aliasing.
+    #[must_use]
+    pub const fn at_band_limited(self, phase: Phase, step: PhaseStep) -> f32 {
+        let (t, dt) = (phase.as_f32(), step.as_f32());
+        let naive = self.at(phase);
+        if dt <= 0.0 || dt >= 0.5 {
+            return naive;
+        }
+        match self {
+            Self::Sine => naive,
+            Self::Saw => naive - poly_blep(t, dt),
+            Self::Square => naive + poly_blep(t, dt) - poly_blep(wrap(t + 0.5), dt),
+            Self::Triangle => {
+                let corners = poly_blamp(wrap(t + 0.25), dt) - poly_blamp(wrap(t + 0.75), dt);
+                naive + 4.0 * dt * corners
+            }
+        }
+    }
+}
+
+impl SignalAt<Phase> for SynthWave {
+    type Sample = f32;
+    fn at(&self, phase: Phase) -> f32 {
+        SynthWave::at(*self, phase)
+    }
+}
+
+#[doc = crate::_tags!(audio wave)]
+/// A band-limited oscillator.
+#[doc = crate::_doc_meta!{location("media/audio"), test_size_of(SynthOsc = 12|96)}]
+///
+/// It emits one sample per step through [`SignalNext`],
+/// using [`SynthWave::at_band_limited`].
+///
+/// # Example
+/// ```
+/// # use devela::{SignalNext, SynthOsc, SynthWave};
+/// let mut osc = SynthOsc::new(SynthWave::Square, 1_000.0, 8_000);
+/// let samples: [f32; 8] = core::array::from_fn(|_| osc.next());
+/// assert![samples[1] > 0.99 && samples[5] < -0.99];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SynthOsc {
+    /// The waveform.
+    pub wave: SynthWave,
+    /// The phase and its step.
+    pub accum: PhaseAccum,
+}
+_impl_init![Self::new(SynthWave::Sine, 0.0, 0) => SynthOsc];
+
+impl SynthOsc {
+    /// Returns an oscillator at `freq` Hz, starting at phase zero.
+    #[must_use]
+    pub const fn new(wave: SynthWave, freq: f32, sample_rate: u32) -> Self {
+        let accum = PhaseAccum::new(Phase::ZERO, PhaseStep::from_freq(freq, sample_rate));
+        Self { wave, accum }
+    }
+    /// Sets the frequency, keeping the phase.
+    pub const fn set_freq(&mut self, freq: f32, sample_rate: u32) {
+        self.accum.step = PhaseStep::from_freq(freq, sample_rate);
+    }
+    /// Restarts from phase zero.
+    pub const fn reset(&mut self) {
+        self.accum.phase = Phase::ZERO;
+    }
+    /// Returns the next sample.
+    pub const fn next_sample(&mut self) -> f32 {
+        let step = self.accum.step;
+        self.wave.at_band_limited(self.accum.next_phase(), step)
+    }
+}
+
+impl SignalNext for SynthOsc {
+    type Sample = f32;
+    fn next(&mut self) -> f32 {
+        self.next_sample()
+    }
+}
+
+/// Wraps turns into `[0, 1)`.
+pub(super) const fn wrap(t: f32) -> f32 {
+    is![t >= 1.0, t - 1.0, t]
+}
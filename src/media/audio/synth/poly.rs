@@ -0,0 +1,166 @@
+// devela/src/media/audio/synth/poly.rs
+//
+//! Defines [`SynthPoly`].
+//
+
+use crate::{AudioChannels, AudioEffectError, AudioGain, AudioRemix, Float, PcmBuf, PcmLayout};
+use crate::{PcmSample, PcmSource, PcmSpec, PcmStream, SynthVoice, is};
+
+#[doc = crate::_tags!(audio)]
+/// A polyphonic synthesizer of `N` voices, played by MIDI note numbers.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Each note is given to a silent voice, or else steals the oldest note.
+/// Notes are tuned in twelve-tone equal temperament, with the note 69 at 440 Hz.
+///
+/// The mono voices are summed, scaled by the gain and pan, and spread
+/// into the channel layout as a mono [`AudioRemix`] does.
+/// The rendered frames are read through [`PcmSource`].
+///
+/// # Example
+/// ```
+/// # use devela::{AudioChannels, PcmBuf, PcmSource, SynthAdsr, SynthOscVoice, SynthPoly, SynthWave};
+/// let voice = SynthOscVoice::new(SynthWave::Triangle, SynthAdsr::new(8_000, 0.0, 0.0, 1.0, 0.01));
+/// let mut synth = SynthPoly::<_, 4>::new(voice, AudioChannels::Stereo, 8_000);
+/// synth.note_on(60, 1.0);
+/// synth.note_on(64, 1.0);
+/// synth.note_off(60);
+/// let mut out = [0.0; 512];
+/// let spec = synth.spec();
+/// assert_eq![synth.read(PcmBuf::new(&mut out[..], spec)), Ok(256)];
+/// assert![out.iter().any(|s| s.abs() > 0.5)];
+/// assert_eq![synth.active_count(), 1];
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SynthPoly<V, const N: usize> {
+    spec: PcmSpec,
+    voices: [V; N],
+    /// The note held by each voice, if its gate is on.
+    notes: [Option<u8>; N],
+    /// The order in which each voice was started.
+    ages: [u64; N],
+    started: u64,
+    /// The gain and pan.
+    pub gain: AudioGain,
+}
+
+impl<V: SynthVoice + Clone, const N: usize> SynthPoly<V, N> {
+    /// Returns a silent synthesizer with `N` copies of `voice`.
+    ///
+    /// The voice must be configured for the same sample rate.
+    pub fn new(voice: V, channels: AudioChannels, sample_rate: u32) -> Self {
+        Self {
+            spec: PcmSpec::new(PcmSample::F32, channels, sample_rate),
+            voices: ::core::array::from_fn(|_| voice.clone()),
+            notes: [None; N],
+            ages: [0; N],
+            started: 0,
+            gain: AudioGain::UNITY,
+        }
+    }
+}
+impl<V: SynthVoice, const N: usize> SynthPoly<V, N> {
+    /// Returns the frequency of a MIDI note number, in Hertz.
+    #[must_use]
+    pub const fn note_freq(note: u8) -> f32 {
+        let octaves = (note as f32 - 69.0) / 12.0;
+        440.0 * Float(octaves).exp2_series(Float(octaves).exp2_series_terms()).0
+    }
+
+    /// Returns the specification of the rendered stream.
+    #[must_use]
+    pub const fn spec(&self) -> PcmSpec {
+        self.spec
+    }
+    /// Returns the voices.
+    #[must_use]
+    pub const fn voices(&self) -> &[V; N] {
+        &self.voices
+    }
+    /// Returns the voices, exclusively.
+    #[must_use]
+    pub const fn voices_mut(&mut self) -> &mut [V; N] {
+        &mut self.voices
+    }
+    /// Returns the number of voices sounding.
+    #[must_use]
+    pub fn active_count(&self) -> usize {
+        self.voices.iter().filter(|v| v.is_active()).count()
+    }
+
+    /// Starts playing `note` with a `velocity` between 0 and 1,
+    /// and returns the index of its voice.
+    ///
+    /// Returns `None` if there are no voices.
+    pub fn note_on(&mut self, note: u8, velocity: f32) -> Option<usize> {
+        let free = self.voices.iter().position(|v| !v.is_active());
+        let oldest = || (0..N).min_by_key(|&i| self.ages[i]);
+        let index = free.or_else(oldest)?;
+        self.started += 1;
+        self.ages[index] = self.started;
+        self.notes[index] = Some(note);
+        self.voices[index].note_on(Self::note_freq(note), Float(velocity).clamp(0.0, 1.0).0);
+        Some(index)
+    }
+    /// Releases every voice playing `note`.
+    pub fn note_off(&mut self, note: u8) {
+        for (voice, held) in self.voices.iter_mut().zip(&mut self.notes) {
+            if *held == Some(note) {
+                voice.note_off();
+                *held = None;
+            }
+        }
+    }
+    /// Releases every voice.
+    pub fn all_notes_off(&mut self) {
+        self.voices.iter_mut().for_each(SynthVoice::note_off);
+        self.notes = [None; N];
+    }
+
+    /// Renders the next frames into `out`, overwriting it.
+    fn render(&mut self, out: &mut [f32]) {
+        let count = self.spec.channel_count();
+        let remix = AudioRemix::new(AudioChannels::Mono, self.spec.channels);
+        let mut gains = self.gain.channel_gains(self.spec.channels);
+        gains.iter_mut().zip(remix.matrix()).for_each(|(gain, row)| *gain *= row[0]);
+        for frame in out.chunks_exact_mut(count) {
+            let mut sum = 0.0;
+            for voice in &mut self.voices {
+                is![voice.is_active(), sum += voice.next_sample()];
+            }
+            frame.iter_mut().zip(gains).for_each(|(out, gain)| *out = sum * gain);
+        }
+    }
+}
+
+impl<V: SynthVoice, const N: usize> PcmStream for SynthPoly<V, N> {
+    type Error = AudioEffectError;
+    fn pcm_spec(&self) -> Option<PcmSpec> {
+        Some(self.spec)
+    }
+    fn pcm_layout(&self) -> Option<PcmLayout> {
+        Some(PcmLayout::Interleaved)
+    }
+}
+impl<V: SynthVoice, const N: usize> PcmSource<f32> for SynthPoly<V, N> {
+    /// Renders every requested frame.
+    ///
+    /// # Errors
+    /// Returns [`AudioEffectError::MismatchedChannels`] or [`AudioEffectError::MismatchedRate`]
+    /// if `pcm` doesn't match the synthesizer spec, or [`AudioEffectError::IncompleteFrames`]
+    /// if it has incomplete frames.
+    fn read(&mut self, mut pcm: PcmBuf<f32, &mut [f32]>) -> Result<usize, Self::Error> {
+        if pcm.channels() != self.spec.channels {
+            return Err(AudioEffectError::MismatchedChannels);
+        }
+        if pcm.sample_rate() != self.spec.sample_rate {
+            return Err(AudioEffectError::MismatchedRate);
+        }
+        let frames = pcm.frames().ok_or(AudioEffectError::IncompleteFrames)?;
+        self.render(pcm.data_mut());
+        Ok(frames)
+    }
+    fn read_all(&mut self, pcm: PcmBuf<f32, &mut [f32]>) -> Result<(), Self::Error> {
+        self.read(pcm).map(|_| ())
+    }
+}
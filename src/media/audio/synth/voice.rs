@@ -0,0 +1,124 @@
+// devela/src/media/audio/synth/voice.rs
+//
+//! Defines [`SynthVoice`], [`SynthFmVoice`], [`SynthOscVoice`].
+//
+
+use crate::{SynthAdsr, SynthFmOperator, SynthOsc, SynthWave};
+
+#[doc = crate::_tags!(audio)]
+/// A monophonic sound generator played by notes.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// Voices are configured for a sample rate, and played by a [`SynthPoly`][crate::SynthPoly].
+pub trait SynthVoice {
+    /// Starts a note at `freq` Hz, with a `velocity` between 0 and 1.
+    fn note_on(&mut self, freq: f32, velocity: f32);
+    /// Releases the current note.
+    fn note_off(&mut self);
+    /// Returns whether the voice is still sounding.
+    fn is_active(&self) -> bool;
+    /// Returns the next sample.
+    fn next_sample(&mut self) -> f32;
+}
+
+#[doc = crate::_tags!(audio)]
+/// A voice made of an oscillator shaped by an envelope.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// # Example
+/// ```
+/// # use devela::{SynthAdsr, SynthOscVoice, SynthVoice, SynthWave};
+/// let env = SynthAdsr::new(8_000, 0.01, 0.1, 0.5, 0.2);
+/// let mut voice = SynthOscVoice::new(SynthWave::Saw, env);
+/// voice.note_on(220.0, 1.0);
+/// assert![voice.is_active()];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthOscVoice {
+    /// The oscillator.
+    pub osc: SynthOsc,
+    /// The envelope of its level.
+    pub envelope: SynthAdsr,
+    velocity: f32,
+}
+impl SynthOscVoice {
+    /// Returns a silent voice, at the sample rate of the envelope.
+    #[must_use]
+    pub const fn new(wave: SynthWave, envelope: SynthAdsr) -> Self {
+        Self {
+            osc: SynthOsc::new(wave, 0.0, 0),
+            envelope,
+            velocity: 0.0,
+        }
+    }
+}
+impl SynthVoice for SynthOscVoice {
+    fn note_on(&mut self, freq: f32, velocity: f32) {
+        self.osc.set_freq(freq, self.envelope.sample_rate());
+        self.osc.reset();
+        self.velocity = velocity;
+        self.envelope.note_on();
+    }
+    fn note_off(&mut self) {
+        self.envelope.note_off();
+    }
+    fn is_active(&self) -> bool {
+        self.envelope.is_active()
+    }
+    fn next_sample(&mut self) -> f32 {
+        self.osc.next_sample() * self.envelope.next_level() * self.velocity
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A two-operator frequency modulation voice.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// The modulator offsets the phase of the carrier. The voice sounds while
+/// the envelope of the carrier does.
+///
+/// # Example
+/// ```
+/// # use devela::{SynthAdsr, SynthFmOperator, SynthFmVoice, SynthVoice};
+/// // a bell: an inharmonic modulator decaying faster than the carrier
+/// let carrier = SynthFmOperator::new(1.0, 1.0, SynthAdsr::new(8_000, 0.0, 1.5, 0.0, 0.5));
+/// let modulator = SynthFmOperator::new(3.5, 0.6, SynthAdsr::new(8_000, 0.0, 0.8, 0.0, 0.5));
+/// let mut bell = SynthFmVoice::new(carrier, modulator);
+/// bell.note_on(440.0, 1.0);
+/// let samples: [f32; 32] = core::array::from_fn(|_| bell.next_sample());
+/// assert![samples.iter().any(|s| s.abs() > 0.1)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthFmVoice {
+    /// The operator that is heard.
+    pub carrier: SynthFmOperator,
+    /// The operator that modulates the carrier.
+    pub modulator: SynthFmOperator,
+    velocity: f32,
+}
+impl SynthFmVoice {
+    /// Returns a silent voice, at the sample rate of the carrier envelope.
+    #[must_use]
+    pub const fn new(carrier: SynthFmOperator, modulator: SynthFmOperator) -> Self {
+        Self { carrier, modulator, velocity: 0.0 }
+    }
+}
+impl SynthVoice for SynthFmVoice {
+    fn note_on(&mut self, freq: f32, velocity: f32) {
+        let rate = self.carrier.envelope.sample_rate();
+        self.carrier.note_on(freq, rate);
+        self.modulator.note_on(freq, rate);
+        self.velocity = velocity;
+    }
+    fn note_off(&mut self) {
+        self.carrier.note_off();
+        self.modulator.note_off();
+    }
+    fn is_active(&self) -> bool {
+        self.carrier.is_active()
+    }
+    fn next_sample(&mut self) -> f32 {
+        let modulation = self.modulator.next_sample(0.0);
+        self.carrier.next_sample(modulation) * self.velocity
+    }
+}
@@ -0,0 +1,92 @@
+// devela/src/media/audio/synth/wavetable.rs
+//
+//! Defines [`SynthWavetable`].
+//
+
+use super::_helper::sin_turns;
+use crate::{Float, Phase, PhaseAccum, PhaseStep, SignalNext};
+
+#[doc = crate::_tags!(audio wave)]
+/// An oscillator reading a single-cycle waveform from a caller-provided table.
+#[doc = crate::_doc_meta!{location("media/audio")}]
+///
+/// The table is read with linear interpolation, wrapping around its end.
+/// Tables built with [`fill_harmonics`][Self::fill_harmonics] are band-limited
+/// as long as their highest harmonic stays below the Nyquist frequency.
+///
+/// An empty table emits silence.
+///
+/// # Example
+/// ```
+/// # use devela::{SignalNext, SynthWavetable};
+/// let mut table = [0.0; 64];
+/// // a square wave with its first three odd harmonics
+/// SynthWavetable::fill_harmonics(&mut table, &[1.0, 0.0, 1.0 / 3.0, 0.0, 1.0 / 5.0]);
+/// let mut osc = SynthWavetable::new(&table, 100.0, 6_400);
+/// assert_eq![osc.next(), 0.0];
+/// assert_eq![osc.next(), table[1]];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SynthWavetable<'a> {
+    table: &'a [f32],
+    /// The phase and its step.
+    pub accum: PhaseAccum,
+}
+
+impl<'a> SynthWavetable<'a> {
+    /// Returns an oscillator over `table` at `freq` Hz, starting at phase zero.
+    #[must_use]
+    pub const fn new(table: &'a [f32], freq: f32, sample_rate: u32) -> Self {
+        let accum = PhaseAccum::new(Phase::ZERO, PhaseStep::from_freq(freq, sample_rate));
+        Self { table, accum }
+    }
+    /// Returns the table.
+    #[must_use]
+    pub const fn table(&self) -> &'a [f32] {
+        self.table
+    }
+    /// Sets the frequency, keeping the phase.
+    pub const fn set_freq(&mut self, freq: f32, sample_rate: u32) {
+        self.accum.step = PhaseStep::from_freq(freq, sample_rate);
+    }
+
+    /// Returns the interpolated value of the table at `phase`.
+    #[must_use]
+    pub const fn at(&self, phase: Phase) -> f32 {
+        let len = self.table.len();
+        if len == 0 {
+            return 0.0;
+        }
+        let pos = phase.raw() as u64 * len as u64;
+        let index = (pos >> 32) as usize;
+        let frac = (pos & 0xFFFF_FFFF) as f32 * (1.0 / (u32::MAX as f32 + 1.0));
+        let (a, b) = (self.table[index], self.table[(index + 1) % len]);
+        a + (b - a) * frac
+    }
+    /// Returns the next sample.
+    pub const fn next_sample(&mut self) -> f32 {
+        let phase = self.accum.next_phase();
+        self.at(phase)
+    }
+
+    /// Fills `table` with one cycle of a sum of sine harmonics,
+    /// where `amplitudes[k]` is the amplitude of the harmonic `k + 1`.
+    pub fn fill_harmonics(table: &mut [f32], amplitudes: &[f32]) {
+        let len = table.len() as f32;
+        for (i, sample) in table.iter_mut().enumerate() {
+            let t = i as f32 / len;
+            *sample = amplitudes.iter().enumerate().fold(0.0, |sum, (k, amplitude)| {
+                // the product is wrapped, to keep the sine argument small
+                let turns = Float((k + 1) as f32 * t).const_fract().0;
+                sum + amplitude * sin_turns(turns)
+            });
+        }
+    }
+}
+
+impl SignalNext for SynthWavetable<'_> {
+    type Sample = f32;
+    fn next(&mut self) -> f32 {
+        self.next_sample()
+    }
+}
@@ -1,16 +1,19 @@
 // devela/src/media/doc/mod.rs
 //
-#![doc = crate::_DOC_MEDIA_DOC!()]
+#![doc = crate::_DOC_MEDIA_DOC!()] // public
 #![doc = crate::_doc!(modules: crate::media; doc)]
 #![doc = crate::_doc!(flat:"media")]
 #![doc = crate::_doc!(hr)]
+//
+// safety
+#![cfg_attr(feature = "safe_doc", forbid(unsafe_code))]
 
-// mod pdf; // WIP: PdfError, PdfWriter…
+#[cfg(feature = "alloc")]
+mod pdf; // PdfError, PdfFont, PdfImage, PdfPage, PdfWriter…
 
 crate::structural_mods! { // _mods
     _mods {
-        // pub use super::{
-        //     pdf::*,
-        // };
+        #[cfg(feature = "alloc")]
+        pub use super::pdf::_all::*;
     }
 }
@@ -0,0 +1,114 @@
+// devela/src/media/doc/pdf/_helper.rs
+//
+//! Defines (`pdf_*`).
+//
+
+use crate::{Vec, is};
+
+/// Appends the decimal digits of `n`.
+pub(super) fn pdf_int(out: &mut Vec<u8>, n: u64) {
+    let mut digits = [0; 20];
+    let mut i = digits.len();
+    let mut n = n;
+    loop {
+        i -= 1;
+        digits[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    out.extend_from_slice(&digits[i..]);
+}
+
+/// Appends a real number, rounded to thousandths and without an exponent.
+///
+/// Non-finite numbers are written as `0`.
+pub(super) fn pdf_real(out: &mut Vec<u8>, n: f32) {
+    pdf_real_digits(out, n, 3);
+}
+/// Appends a real number, rounded to up to 9 `decimals` and without an exponent.
+///
+/// Non-finite numbers are written as `0`.
+pub(super) fn pdf_real_digits(out: &mut Vec<u8>, n: f32, decimals: u32) {
+    let unit = 10_u64.pow(decimals.min(9));
+    let scaled = f64::from(n) * unit as f64;
+    // rounds half away from zero, and saturates the infinities, that are then dropped
+    let scaled = is![n.is_finite(), is![scaled < 0., scaled - 0.5, scaled + 0.5] as i64, 0];
+    is![scaled < 0, out.push(b'-')];
+    let scaled = scaled.unsigned_abs();
+    pdf_int(out, scaled / unit);
+    let (mut frac, mut div) = (scaled % unit, unit / 10);
+    is![frac != 0, out.push(b'.')];
+    while frac != 0 {
+        out.push(b'0' + (frac / div) as u8);
+        (frac, div) = (frac % div, div / 10);
+    }
+}
+/// Appends the numbers separated by spaces, followed by a space and an operator.
+pub(super) fn pdf_op(out: &mut Vec<u8>, operands: &[f32], op: &[u8]) {
+    for &n in operands {
+        pdf_real(out, n);
+        out.push(b' ');
+    }
+    out.extend_from_slice(op);
+    out.push(b'\n');
+}
+/// Appends an indirect reference to the object `id`.
+pub(super) fn pdf_ref(out: &mut Vec<u8>, id: u32) {
+    pdf_int(out, u64::from(id));
+    out.extend_from_slice(b" 0 R");
+}
+
+/// Appends a literal string of `bytes`, escaping delimiters and non-printable bytes.
+pub(super) fn pdf_string(out: &mut Vec<u8>, bytes: impl Iterator<Item = u8>) {
+    out.push(b'(');
+    for b in bytes {
+        match b {
+            b'(' | b')' | b'\\' => out.extend_from_slice(&[b'\\', b]),
+            b' '..=b'~' => out.push(b),
+            _ => out.extend_from_slice(&[
+                b'\\',
+                b'0' + (b >> 6),
+                b'0' + (b >> 3 & 7),
+                b'0' + (b & 7),
+            ]),
+        }
+    }
+    out.push(b')');
+}
+
+/// Returns the byte of `c` in the `WinAnsiEncoding` of the standard fonts, if any.
+pub(super) const fn pdf_win_ansi(c: char) -> Option<u8> {
+    Some(match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => c as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        _ => return None,
+    })
+}
@@ -0,0 +1,196 @@
+// devela/src/media/doc/pdf/_test.rs
+
+use super::{pdf_real, pdf_string, pdf_win_ansi};
+use crate::{Extent2, PdfError, PdfFont, PdfPage, PdfWriter, RasterFillRule, RasterView};
+use crate::{Vec, ext, pos, vec_ as vec};
+
+/// A dense raster of `[u8; 4]` pixels.
+struct Pixels(Extent2<u32>, Vec<[u8; 4]>);
+impl RasterView for Pixels {
+    type Sample = [u8; 4];
+    fn raster_extent(&self) -> Extent2<u32> {
+        self.0
+    }
+    fn raster_samples(&self) -> &[[u8; 4]] {
+        &self.1
+    }
+}
+
+/// Returns the position of the first occurrence of `needle`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+/// Returns the data of the stream whose dictionary ends with `dict_end`, and its position.
+fn stream<'a>(bytes: &'a [u8], dict_end: &[u8], len: usize) -> (usize, &'a [u8]) {
+    let at = find(bytes, dict_end).unwrap();
+    (at, &bytes[at + dict_end.len()..][..len])
+}
+/// Returns the decimal number at the start of `bytes`.
+fn parse(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .fold(0, |n, b| n * 10 + u64::from(b - b'0'))
+}
+
+#[test]
+fn reals_and_strings() {
+    let real = |n| {
+        let mut out = Vec::new();
+        pdf_real(&mut out, n);
+        out
+    };
+    assert_eq![real(12.0), b"12"];
+    assert_eq![real(-0.25), b"-0.25"];
+    assert_eq![real(1.0 / 3.0), b"0.333"];
+    assert_eq![real(0.0004), b"0"];
+    assert_eq![real(-0.0004), b"0"];
+    assert_eq![real(1e9), b"1000000000"];
+    assert_eq![real(f32::NAN), b"0"];
+
+    let mut out = Vec::new();
+    pdf_string(&mut out, b"a(b)\\c\n\xE9".iter().copied());
+    assert_eq![out, b"(a\\(b\\)\\\\c\\012\\351)"];
+
+    assert_eq![pdf_win_ansi('A'), Some(b'A')];
+    assert_eq![pdf_win_ansi('é'), Some(0xE9)];
+    assert_eq![pdf_win_ansi('€'), Some(0x80)];
+    assert_eq![pdf_win_ansi('\n'), None];
+    assert_eq![pdf_win_ansi('Ω'), None];
+    assert_eq![PdfFont::Helvetica.encode('Ω'), b'?'];
+    assert_eq![PdfFont::Symbol.encode('a'), b'a'];
+}
+
+#[test]
+fn page_content() {
+    let mut page = PdfPage::new(ext![100.0, 50.0]);
+    page.set_fill_rgb([1.0, 0.5, 0.0])
+        .rect(pos![10.0, 10.0], ext![20.0, 5.0])
+        .fill(RasterFillRule::EvenOdd)
+        .move_to(pos![0.0, 0.0])
+        .quad_to(pos![3.0, 6.0], pos![6.0, 0.0])
+        .stroke();
+    assert_eq![page.content(), b"1 0.5 0 rg\n10 10 20 5 re\nf*\n0 0 m\n2 4 4 4 6 0 c\nS\n"];
+}
+
+#[test]
+fn cross_references() {
+    let mut pdf = PdfWriter::new(Vec::new()).unwrap().with_compression(false);
+    let mut page = PdfPage::new(PdfPage::LETTER);
+    page.text(PdfFont::Courier, 10.0, pos![72.0, 700.0], "first").text(
+        PdfFont::TimesItalic,
+        10.0,
+        pos![72.0, 680.0],
+        "second",
+    );
+    pdf.add_page(&page).unwrap();
+    let mut page = PdfPage::new(PdfPage::A5);
+    page.text(PdfFont::Courier, 10.0, pos![72.0, 500.0], "third");
+    pdf.add_page(&page).unwrap();
+    assert_eq![pdf.page_count(), 2];
+    let len = pdf.len();
+    let bytes = pdf.finish().unwrap();
+    assert![bytes.len() as u64 > len];
+
+    // Courier is written once, for both pages
+    assert_eq![bytes.windows(16).filter(|w| w == b"/BaseFont /Couri").count(), 1];
+    assert![find(&bytes, b"/Count 2").is_some()];
+
+    let xref = find(&bytes, b"\nxref\n").unwrap() + 1;
+    let startxref = find(&bytes, b"startxref\n").unwrap() + 10;
+    assert_eq![parse(&bytes[startxref..]), xref as u64];
+    let size = parse(&bytes[xref + 7..]);
+    let entries = &bytes[find(&bytes[xref..], b"65535 f \n").unwrap() + xref + 9..];
+    for id in 1..size {
+        let entry = &entries[(id as usize - 1) * 20..][..20];
+        assert_eq![&entry[10..], b" 00000 n \n"];
+        let offset = parse(entry) as usize;
+        let head = crate::format!("{id} 0 obj\n");
+        assert![bytes[offset..].starts_with(head.as_bytes()), "object {id}"];
+    }
+}
+
+#[test]
+fn images() {
+    let mut pdf = PdfWriter::new(Vec::new()).unwrap().with_compression(false);
+    let short = Pixels(Extent2::new([2, 2]), vec![[0; 4]; 3]);
+    assert_eq![pdf.add_image(&short), Err(PdfError::InvalidImage)];
+    let empty = Pixels(Extent2::new([0, 2]), Vec::new());
+    assert_eq![pdf.add_image(&empty), Err(PdfError::InvalidImage)];
+
+    let pixels = Pixels(Extent2::new([2, 1]), vec![[255, 0, 0, 255], [0, 0, 255, 128]]);
+    let image = pdf.add_image(&pixels).unwrap();
+    assert_eq![image.extent(), Extent2::new([2, 1])];
+    let mut page = PdfPage::new(PdfPage::A4);
+    page.image(&image, pos![0.0, 0.0], ext![20.0, 10.0]);
+    pdf.add_page(&page).unwrap();
+    let bytes = pdf.finish().unwrap();
+
+    // the soft mask is written before the image that refers to it
+    let gray = b"/DeviceGray /BitsPerComponent 8 /Length 2 >>\nstream\n";
+    let (mask_at, mask) = stream(&bytes, gray, 2);
+    assert_eq![mask, [255, 128]];
+    let rgb = b"/DeviceRGB /BitsPerComponent 8 /SMask 3 0 R /Length 6 >>\nstream\n";
+    let (image_at, image) = stream(&bytes, rgb, 6);
+    assert_eq![image, [255, 0, 0, 0, 0, 255]];
+    assert![find(&bytes, b"3 0 obj\n").unwrap() < mask_at && mask_at < image_at];
+    assert![find(&bytes, b"/XObject << /Im4 4 0 R >>").is_some()];
+    assert![find(&bytes, b"q\n20 0 0 10 0 0 cm\n/Im4 Do\nQ\n").is_some()];
+}
+
+#[test]
+fn compression() {
+    let mut page = PdfPage::new(PdfPage::A4);
+    for _ in 0..100 {
+        page.rect(pos![10.0, 10.0], ext![20.0, 20.0]).fill(RasterFillRule::NonZero);
+    }
+    let mut pdf = PdfWriter::new(Vec::new()).unwrap();
+    pdf.add_page(&page).unwrap();
+    let compressed = pdf.finish().unwrap();
+    let mut pdf = PdfWriter::new(Vec::new()).unwrap().with_compression(false);
+    pdf.add_page(&page).unwrap();
+    let plain = pdf.finish().unwrap();
+    assert![find(&compressed, b"/Filter /FlateDecode").is_some()];
+    assert![find(&plain, b"/Filter").is_none()];
+    assert![compressed.len() < plain.len()];
+}
+
+#[test]
+#[cfg(feature = "font")]
+fn bitmap_fonts() {
+    use crate::FontBitmapView;
+    // two 3×2 glyphs
+    const FONT: FontBitmapView<'static> = FontBitmapView {
+        scalars_le: &[0x3F, 0, 0, 0, 0x41, 0, 0, 0], // ?, A
+        bitmaps: &[0xA0, 0x40, 0x40, 0xE0],
+        glyph_stride: 2,
+        width: 3,
+        height: 2,
+        row_stride: 1,
+        bounds_x: 0,
+        bounds_y: -1,
+        advance_x: 4,
+        line_advance: 3,
+        ascent: 1,
+        descent: 1,
+        default_character: Some('?'),
+    };
+    let mut pdf = PdfWriter::new(Vec::new()).unwrap().with_compression(false);
+    let font = pdf.add_bitmap_font(&FONT, "AZA").unwrap();
+    assert_eq![font.chars(), ['?', 'A']];
+    assert![(font.text_width("AZ", 10.0) - 40.0).abs() < 1e-5];
+
+    let mut page = PdfPage::new(PdfPage::A4);
+    page.bitmap_text(&font, 10.0, pos![5.0, 5.0], "A(Z");
+    // the missing characters are drawn with the fallback glyph
+    assert![page.content().ends_with(b"(\\002\\001\\001) Tj\nET\n")];
+    pdf.add_page(&page).unwrap();
+    let bytes = pdf.finish().unwrap();
+
+    assert![find(&bytes, b"/CharProcs << /g0 3 0 R /g1 4 0 R /g2 5 0 R >>").is_some()];
+    assert![find(&bytes, b"/FontMatrix [0.5 0 0 0.5 0 0]").is_some()];
+    assert![find(&bytes, b"/Widths [4 4 4]").is_some()];
+    assert![find(&bytes, b"/Font << /B6 6 0 R >>").is_some()];
+    let glyph = b"4 0 0 -1 3 1 d1\nq\n3 0 0 2 0 -1 cm\nBI /W 3 /H 2 /IM true /BPC 1 /D [1 0] ID\n";
+    assert![find(&bytes, &[&glyph[..], &[0x40, 0xE0], b"\nEI Q\n"].concat()).is_some()];
+}
@@ -0,0 +1,38 @@
+// devela/src/media/doc/pdf/error.rs
+//
+//! Defines [`PdfError`], [`PdfResult`].
+//
+
+use crate::{IoError, IoErrorKind};
+
+#[doc = crate::_tags!(result)]
+/// A PDF-related result.
+#[doc = crate::_doc_meta!{location("media/doc")}]
+pub type PdfResult<T> = crate::Result<T, PdfError>;
+
+#[doc = crate::_tags!(error)]
+/// An error encountered while writing a PDF document.
+#[doc = crate::_doc_meta!{location("media/doc")}]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PdfError {
+    /// Writing to the output failed.
+    Io(IoErrorKind),
+    /// The raster is empty, or has fewer samples than its extent.
+    InvalidImage,
+    /// The bitmap font has more characters than a font can encode.
+    TooManyGlyphs,
+    /// The document is too long for the offsets of its cross-reference table.
+    SizeOutOfRange,
+}
+crate::impl_trait![fmt::Display+Error for PdfError |self, f| match self {
+    Self::Io(err) => write!(f, "PDF output failed: {err:?}"),
+    Self::InvalidImage => f.write_str("the raster is empty or shorter than its extent"),
+    Self::TooManyGlyphs => f.write_str("too many characters for a PDF bitmap font"),
+    Self::SizeOutOfRange => f.write_str("the PDF document exceeds its cross-reference offsets"),
+}];
+impl From<IoError> for PdfError {
+    fn from(err: IoError) -> Self {
+        Self::Io(err.kind())
+    }
+}
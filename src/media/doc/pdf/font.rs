@@ -0,0 +1,173 @@
+// devela/src/media/doc/pdf/font.rs
+//
+//! Defines [`PdfFont`], [`PdfBitmapFont`].
+//
+
+#[cfg(feature = "font")]
+use crate::Vec;
+use crate::{is, unwrap};
+
+#[doc = crate::_tags!(font)]
+/// One of the 14 standard fonts that every PDF reader provides.
+#[doc = crate::_doc_meta!{location("media/doc")}]
+///
+/// They are referenced by name, without embedding any font data.
+/// Text is encoded in `WinAnsiEncoding`, which covers Latin-1 and a few more
+/// typographic characters, except for [`Symbol`][Self::Symbol] and
+/// [`ZapfDingbats`][Self::ZapfDingbats], which use their own encodings
+/// and receive the characters below U+0100 as they are.
+/// Characters that can't be encoded are written as `?`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PdfFont {
+    /// Helvetica.
+    #[default]
+    Helvetica,
+    /// Helvetica bold.
+    HelveticaBold,
+    /// Helvetica oblique.
+    HelveticaOblique,
+    /// Helvetica bold oblique.
+    HelveticaBoldOblique,
+    /// Times roman.
+    TimesRoman,
+    /// Times bold.
+    TimesBold,
+    /// Times italic.
+    TimesItalic,
+    /// Times bold italic.
+    TimesBoldItalic,
+    /// Courier, monospaced.
+    Courier,
+    /// Courier bold, monospaced.
+    CourierBold,
+    /// Courier oblique, monospaced.
+    CourierOblique,
+    /// Courier bold oblique, monospaced.
+    CourierBoldOblique,
+    /// Symbol, with greek letters and mathematical symbols.
+    Symbol,
+    /// ITC Zapf Dingbats, with ornaments.
+    ZapfDingbats,
+}
+
+impl PdfFont {
+    /// All the standard fonts.
+    pub const ALL: [PdfFont; 14] = [
+        PdfFont::Helvetica,
+        PdfFont::HelveticaBold,
+        PdfFont::HelveticaOblique,
+        PdfFont::HelveticaBoldOblique,
+        PdfFont::TimesRoman,
+        PdfFont::TimesBold,
+        PdfFont::TimesItalic,
+        PdfFont::TimesBoldItalic,
+        PdfFont::Courier,
+        PdfFont::CourierBold,
+        PdfFont::CourierOblique,
+        PdfFont::CourierBoldOblique,
+        PdfFont::Symbol,
+        PdfFont::ZapfDingbats,
+    ];
+
+    /// Returns the PostScript name of the font.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            PdfFont::Helvetica => "Helvetica",
+            PdfFont::HelveticaBold => "Helvetica-Bold",
+            PdfFont::HelveticaOblique => "Helvetica-Oblique",
+            PdfFont::HelveticaBoldOblique => "Helvetica-BoldOblique",
+            PdfFont::TimesRoman => "Times-Roman",
+            PdfFont::TimesBold => "Times-Bold",
+            PdfFont::TimesItalic => "Times-Italic",
+            PdfFont::TimesBoldItalic => "Times-BoldItalic",
+            PdfFont::Courier => "Courier",
+            PdfFont::CourierBold => "Courier-Bold",
+            PdfFont::CourierOblique => "Courier-Oblique",
+            PdfFont::CourierBoldOblique => "Courier-BoldOblique",
+            PdfFont::Symbol => "Symbol",
+            PdfFont::ZapfDingbats => "ZapfDingbats",
+        }
+    }
+    /// Returns whether the font uses its own encoding instead of `WinAnsiEncoding`.
+    #[must_use]
+    pub const fn is_symbolic(self) -> bool {
+        matches![self, PdfFont::Symbol | PdfFont::ZapfDingbats]
+    }
+    /// Returns the advance of every glyph of the Courier fonts, in thousandths of the font size.
+    ///
+    /// Returns `None` for proportional fonts.
+    #[must_use]
+    pub const fn monospace_advance(self) -> Option<u16> {
+        match self {
+            PdfFont::Courier
+            | PdfFont::CourierBold
+            | PdfFont::CourierOblique
+            | PdfFont::CourierBoldOblique => Some(600),
+            _ => None,
+        }
+    }
+
+    /// Returns the position of the font in [`ALL`][Self::ALL].
+    pub(super) const fn index(self) -> usize {
+        self as usize
+    }
+    /// Returns the byte encoding `c`.
+    pub(super) const fn encode(self, c: char) -> u8 {
+        if self.is_symbolic() {
+            is![(c as u32) < 0x100, c as u8, b'?']
+        } else {
+            unwrap![some_or super::pdf_win_ansi(c), b'?']
+        }
+    }
+}
+
+#[doc = crate::_tags!(font)]
+/// A bitmap font embedded in a PDF document.
+#[doc = crate::_doc_meta!{location("media/doc")}]
+///
+/// Returned by [`PdfWriter::add_bitmap_font`][crate::PdfWriter::add_bitmap_font],
+/// to draw text with [`PdfPage::bitmap_text`][crate::PdfPage::bitmap_text].
+/// It only belongs to the document that embedded it.
+///
+/// The font is embedded as a Type 3 font with the glyphs of the chosen characters,
+/// each drawn as an image mask, so that the text stays selectable and searchable.
+/// At a font size of `S`, a pixel of the bitmap measures `S / height` points.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg(feature = "font")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "font")))]
+pub struct PdfBitmapFont {
+    pub(super) id: u32,
+    /// The embedded characters, sorted, whose codes are their position plus one.
+    pub(super) chars: Vec<char>,
+    /// The code of the fallback character, or 0, the blank glyph.
+    pub(super) default_code: u8,
+    pub(super) height: u16,
+    pub(super) advance: u16,
+}
+
+#[cfg(feature = "font")]
+impl PdfBitmapFont {
+    /// Returns the embedded characters, in increasing order.
+    #[must_use]
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+    /// Returns the width of `text` at the given font `size`, in points.
+    #[must_use]
+    pub fn text_width(&self, text: &str, size: f32) -> f32 {
+        let advance = f32::from(self.advance) * size / f32::from(self.height);
+        text.chars().count() as f32 * advance
+    }
+
+    /// Returns the code of `c`.
+    ///
+    /// Characters that aren't embedded use the fallback character,
+    /// or a blank glyph of the same advance.
+    pub(super) fn encode(&self, c: char) -> u8 {
+        match self.chars.binary_search(&c) {
+            Ok(i) => i as u8 + 1,
+            Err(_) => self.default_code,
+        }
+    }
+}
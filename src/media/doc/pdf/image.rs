@@ -0,0 +1,26 @@
+// devela/src/media/doc/pdf/image.rs
+//
+//! Defines [`PdfImage`].
+//
+
+use crate::Extent2;
+
+#[doc = crate::_tags!(image)]
+/// A raster image embedded in a PDF document.
+#[doc = crate::_doc_meta!{location("media/doc")}]
+///
+/// Returned by [`PdfWriter::add_image`][crate::PdfWriter::add_image],
+/// to be drawn any number of times with [`PdfPage::image`][crate::PdfPage::image].
+/// It only belongs to the document that embedded it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PdfImage {
+    pub(super) id: u32,
+    pub(super) extent: Extent2<u32>,
+}
+
+impl PdfImage {
+    /// Returns the extent of the image, in pixels.
+    pub const fn extent(&self) -> Extent2<u32> {
+        self.extent
+    }
+}
@@ -0,0 +1,29 @@
+// devela/src/media/doc/pdf/mod.rs
+//
+#![doc = crate::_DOC_MEDIA_DOC_PDF!()]
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (pdf_*)
+
+mod error; // PdfError, PdfResult
+mod font; // PdfBitmapFont, PdfFont
+mod image; // PdfImage
+mod page; // PdfPage
+mod writer; // PdfWriter
+
+use _helper::*;
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            error::*,
+            font::*,
+            image::*,
+            page::*,
+            writer::*,
+        };
+    }
+}
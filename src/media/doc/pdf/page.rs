@@ -0,0 +1,279 @@
+// devela/src/media/doc/pdf/page.rs
+//
+//! Defines [`PdfPage`].
+//
+
+use super::{pdf_int, pdf_op, pdf_real, pdf_string};
+#[cfg(feature = "font")]
+use crate::PdfBitmapFont;
+use crate::{Extent2, PdfFont, PdfImage, Position2, RasterFillRule, RasterStroke, Vec, is};
+use crate::{RasterLineCap as Cap, RasterLineJoin as Join};
+
+#[doc = crate::_tags!(geom)]
+/// The content of a page of a PDF document, built with drawing operations.
+#[doc = crate::_doc_meta!{location("media/doc")}]
+///
+/// Coordinates are in points, 1/72 of an inch, with the origin at the bottom-left
+/// corner of the page and y growing upwards, unless [transformed][Self::transform].
+///
+/// Paths are built with [`move_to`][Self::move_to] and the following methods,
+/// and then painted with [`fill`][Self::fill], [`stroke`][Self::stroke]
+/// or [`fill_stroke`][Self::fill_stroke], which also end them.
+/// Colors are given as normalized channels, from 0 to 1.
+///
+/// Once built, the page is written with [`PdfWriter::add_page`][crate::PdfWriter::add_page].
+///
+/// # Example
+/// ```
+/// # use devela::{PdfFont, PdfPage, RasterFillRule, RasterStroke, ext, pos};
+/// let mut page = PdfPage::new(PdfPage::A4);
+/// page.set_fill_rgb([0.2, 0.4, 0.8])
+///     .rect(pos![72.0, 600.0], ext![200.0, 100.0])
+///     .fill(RasterFillRule::NonZero)
+///     .set_stroke(&RasterStroke::new(2.0))
+///     .move_to(pos![72.0, 580.0])
+///     .line_to(pos![272.0, 580.0])
+///     .stroke()
+///     .set_fill_gray(0.0)
+///     .text(PdfFont::Helvetica, 12.0, pos![72.0, 560.0], "Total (net): 42 €");
+/// assert![page.content().ends_with(b"BT\n/F1 12 Tf\n72 560 Td\n(Total \\(net\\): 42 \\200) Tj\nET\n")];
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PdfPage {
+    size: Extent2<f32>,
+    content: Vec<u8>,
+    /// The standard fonts used, by their index.
+    pub(super) fonts: u16,
+    /// The bitmap fonts used, by their object id.
+    #[cfg(feature = "font")]
+    pub(super) bitmap_fonts: Vec<u32>,
+    /// The images used, by their object id.
+    pub(super) images: Vec<u32>,
+    /// The current point, to convert quadratic curves.
+    current: [f32; 2],
+}
+
+impl PdfPage {
+    /// The size of an ISO A4 page, 210 × 297 mm.
+    pub const A4: Extent2<f32> = Extent2::new([595.276, 841.89]);
+    /// The size of an ISO A5 page, 148 × 210 mm.
+    pub const A5: Extent2<f32> = Extent2::new([419.528, 595.276]);
+    /// The size of a US letter page, 8.5 × 11 inches.
+    pub const LETTER: Extent2<f32> = Extent2::new([612.0, 792.0]);
+    /// The size of a US legal page, 8.5 × 14 inches.
+    pub const LEGAL: Extent2<f32> = Extent2::new([612.0, 1008.0]);
+
+    /// Returns an empty page of the given `size`, in points.
+    #[must_use]
+    pub fn new(size: Extent2<f32>) -> Self {
+        Self { size, ..Self::default() }
+    }
+    /// Returns the size of the page, in points.
+    pub const fn size(&self) -> Extent2<f32> {
+        self.size
+    }
+    /// Returns the content stream built so far.
+    #[must_use]
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    /* graphics state */
+
+    /// Saves the graphics state, to be restored by [`restore`][Self::restore].
+    pub fn save(&mut self) -> &mut Self {
+        self.op(&[], b"q")
+    }
+    /// Restores the last saved graphics state.
+    pub fn restore(&mut self) -> &mut Self {
+        self.op(&[], b"Q")
+    }
+    /// Transforms the coordinates of the following operations
+    /// by the matrix `[a, b, c, d, e, f]`, mapping `(x, y)` to
+    /// `(a·x + c·y + e, b·x + d·y + f)`.
+    pub fn transform(&mut self, matrix: [f32; 6]) -> &mut Self {
+        self.op(&matrix, b"cm")
+    }
+    /// Sets the color of fills and text to a gray level.
+    pub fn set_fill_gray(&mut self, gray: f32) -> &mut Self {
+        self.op(&[gray], b"g")
+    }
+    /// Sets the color of fills and text.
+    pub fn set_fill_rgb(&mut self, rgb: [f32; 3]) -> &mut Self {
+        self.op(&rgb, b"rg")
+    }
+    /// Sets the color of strokes to a gray level.
+    pub fn set_stroke_gray(&mut self, gray: f32) -> &mut Self {
+        self.op(&[gray], b"G")
+    }
+    /// Sets the color of strokes.
+    pub fn set_stroke_rgb(&mut self, rgb: [f32; 3]) -> &mut Self {
+        self.op(&rgb, b"RG")
+    }
+    /// Sets the width, caps, joins and miter limit of strokes.
+    pub fn set_stroke(&mut self, stroke: &RasterStroke) -> &mut Self {
+        let cap = match stroke.cap {
+            Cap::Butt => 0.,
+            Cap::Round => 1.,
+            Cap::Square => 2.,
+        };
+        let join = match stroke.join {
+            Join::Miter => 0.,
+            Join::Round => 1.,
+            Join::Bevel => 2.,
+        };
+        // the miter limit of PDF is relative to the whole width, and at least 1
+        self.op(&[stroke.width], b"w").op(&[cap], b"J").op(&[join], b"j");
+        self.op(&[(stroke.miter_limit / 2.0).max(1.0)], b"M")
+    }
+    /// Sets the lengths of the alternating dashes and gaps of strokes,
+    /// starting at `phase` along them. An empty `pattern` draws solid strokes.
+    pub fn set_dash(&mut self, pattern: &[f32], phase: f32) -> &mut Self {
+        self.content.push(b'[');
+        for (i, &len) in pattern.iter().enumerate() {
+            is![i > 0, self.content.push(b' ')];
+            pdf_real(&mut self.content, len);
+        }
+        self.content.extend_from_slice(b"] ");
+        self.op(&[phase], b"d")
+    }
+
+    /* paths */
+
+    /// Starts a new contour at `to`.
+    pub fn move_to(&mut self, to: Position2<f32>) -> &mut Self {
+        self.current = to.dim;
+        self.op(&to.dim, b"m")
+    }
+    /// Adds a line from the current point to `to`.
+    pub fn line_to(&mut self, to: Position2<f32>) -> &mut Self {
+        self.current = to.dim;
+        self.op(&to.dim, b"l")
+    }
+    /// Adds a quadratic Bézier curve from the current point to `to`.
+    pub fn quad_to(&mut self, control: Position2<f32>, to: Position2<f32>) -> &mut Self {
+        let ([x0, y0], [cx, cy], [x, y]) = (self.current, control.dim, to.dim);
+        let c1 = Position2::new([x0 + (cx - x0) * 2. / 3., y0 + (cy - y0) * 2. / 3.]);
+        let c2 = Position2::new([x + (cx - x) * 2. / 3., y + (cy - y) * 2. / 3.]);
+        self.cubic_to(c1, c2, to)
+    }
+    /// Adds a cubic Bézier curve from the current point to `to`.
+    pub fn cubic_to(
+        &mut self,
+        c1: Position2<f32>,
+        c2: Position2<f32>,
+        to: Position2<f32>,
+    ) -> &mut Self {
+        let ([x1, y1], [x2, y2], [x, y]) = (c1.dim, c2.dim, to.dim);
+        self.current = to.dim;
+        self.op(&[x1, y1, x2, y2, x, y], b"c")
+    }
+    /// Closes the current contour with a line to its start.
+    pub fn close(&mut self) -> &mut Self {
+        self.op(&[], b"h")
+    }
+    /// Adds a closed rectangle with its bottom-left corner at `origin`.
+    pub fn rect(&mut self, origin: Position2<f32>, size: Extent2<f32>) -> &mut Self {
+        let ([x, y], [w, h]) = (origin.dim, size.dim);
+        self.current = [x, y];
+        self.op(&[x, y, w, h], b"re")
+    }
+    /// Adds a closed ellipse, approximated by 4 cubic curves.
+    pub fn ellipse(&mut self, center: Position2<f32>, radii: Extent2<f32>) -> &mut Self {
+        /// The distance of the controls of a quarter circle of radius 1 from its ends.
+        const K: f32 = 0.552_284_8;
+        let ([cx, cy], [rx, ry]) = (center.dim, radii.dim);
+        let (kx, ky) = (rx * K, ry * K);
+        let p = |x, y| Position2::new([x, y]);
+        self.move_to(p(cx + rx, cy));
+        self.cubic_to(p(cx + rx, cy + ky), p(cx + kx, cy + ry), p(cx, cy + ry));
+        self.cubic_to(p(cx - kx, cy + ry), p(cx - rx, cy + ky), p(cx - rx, cy));
+        self.cubic_to(p(cx - rx, cy - ky), p(cx - kx, cy - ry), p(cx, cy - ry));
+        self.cubic_to(p(cx + kx, cy - ry), p(cx + rx, cy - ky), p(cx + rx, cy));
+        self.close()
+    }
+    /// Adds a closed circle, approximated by 4 cubic curves.
+    pub fn circle(&mut self, center: Position2<f32>, radius: f32) -> &mut Self {
+        self.ellipse(center, Extent2::new([radius, radius]))
+    }
+
+    /* painting */
+
+    /// Fills the path with the fill color, and ends it.
+    pub fn fill(&mut self, rule: RasterFillRule) -> &mut Self {
+        self.op(&[], is![rule == RasterFillRule::EvenOdd, b"f*", b"f"])
+    }
+    /// Strokes the path with the stroke color, and ends it.
+    pub fn stroke(&mut self) -> &mut Self {
+        self.op(&[], b"S")
+    }
+    /// Fills and then strokes the path, and ends it.
+    pub fn fill_stroke(&mut self, rule: RasterFillRule) -> &mut Self {
+        self.op(&[], is![rule == RasterFillRule::EvenOdd, b"B*", b"B"])
+    }
+    /// Intersects the clipping region with the path, and ends it.
+    ///
+    /// The clipping region is restored along with the graphics state.
+    pub fn clip(&mut self, rule: RasterFillRule) -> &mut Self {
+        self.op(&[], is![rule == RasterFillRule::EvenOdd, b"W* n", b"W n"])
+    }
+
+    /* text and images */
+
+    /// Draws a line of `text` with one of the standard fonts,
+    /// starting at the baseline point `at`, with the fill color.
+    pub fn text(&mut self, font: PdfFont, size: f32, at: Position2<f32>, text: &str) -> &mut Self {
+        self.fonts |= 1 << font.index();
+        self.content.extend_from_slice(b"BT\n/F");
+        pdf_int(&mut self.content, font.index() as u64 + 1);
+        self.content.push(b' ');
+        self.op(&[size], b"Tf").op(&at.dim, b"Td");
+        pdf_string(&mut self.content, text.chars().map(|c| font.encode(c)));
+        self.content.extend_from_slice(b" Tj\nET\n");
+        self
+    }
+    /// Draws a line of `text` with an embedded bitmap font,
+    /// starting at the baseline point `at`, with the fill color.
+    #[cfg(feature = "font")]
+    #[cfg_attr(nightly_doc, doc(cfg(feature = "font")))]
+    pub fn bitmap_text(
+        &mut self,
+        font: &PdfBitmapFont,
+        size: f32,
+        at: Position2<f32>,
+        text: &str,
+    ) -> &mut Self {
+        is![!self.bitmap_fonts.contains(&font.id), self.bitmap_fonts.push(font.id)];
+        self.content.extend_from_slice(b"BT\n/B");
+        pdf_int(&mut self.content, u64::from(font.id));
+        self.content.push(b' ');
+        self.op(&[size], b"Tf").op(&at.dim, b"Td");
+        pdf_string(&mut self.content, text.chars().map(|c| font.encode(c)));
+        self.content.extend_from_slice(b" Tj\nET\n");
+        self
+    }
+    /// Draws an embedded image stretched over the rectangle
+    /// with its bottom-left corner at `origin`.
+    pub fn image(
+        &mut self,
+        image: &PdfImage,
+        origin: Position2<f32>,
+        size: Extent2<f32>,
+    ) -> &mut Self {
+        is![!self.images.contains(&image.id), self.images.push(image.id)];
+        let ([x, y], [w, h]) = (origin.dim, size.dim);
+        self.op(&[], b"q").op(&[w, 0., 0., h, x, y], b"cm");
+        self.content.extend_from_slice(b"/Im");
+        pdf_int(&mut self.content, u64::from(image.id));
+        self.content.extend_from_slice(b" Do\n");
+        self.op(&[], b"Q")
+    }
+
+    /* helpers */
+
+    /// Appends an operator with its operands.
+    fn op(&mut self, operands: &[f32], op: &[u8]) -> &mut Self {
+        pdf_op(&mut self.content, operands, op);
+        self
+    }
+}
@@ -0,0 +1,450 @@
+// devela/src/media/doc/pdf/writer.rs
+//
+//! Defines [`PdfWriter`].
+//
+
+use super::{pdf_int, pdf_real, pdf_ref};
+use crate::PdfError::{InvalidImage, SizeOutOfRange};
+use crate::{
+    Deflate, Extent2, IoWrite, PdfFont, PdfImage, PdfPage, PdfResult, RasterPixel, RasterView,
+};
+use crate::{Vec, is, vec_ as vec};
+#[cfg(feature = "font")]
+use {
+    super::{pdf_op, pdf_real_digits},
+    crate::{FontBitmapView, PdfBitmapFont, PdfError::TooManyGlyphs},
+};
+
+/// The object id of the document catalog.
+const CATALOG: u32 = 1;
+/// The object id of the root of the page tree.
+const PAGES: u32 = 2;
+/// The largest offset of a cross-reference table entry.
+const OFFSET_MAX: u64 = 9_999_999_999;
+
+#[doc = crate::_tags!(codec io)]
+/// A writer of PDF documents, streamed through an [`IoWrite`].
+#[doc = crate::_doc_meta!{location("media/doc")}]
+///
+/// Images and fonts are embedded first, and then referenced by any number of
+/// [`PdfPage`]s, which are written one at a time. The document is completed
+/// by [`finish`][Self::finish], which writes its page tree and its
+/// cross-reference table.
+///
+/// Content and image streams are compressed with
+/// [`Deflate`], unless disabled with [`with_compression`][Self::with_compression].
+///
+/// Current limitations:
+/// - Text is drawn without kerning, and the metrics of the proportional
+///   standard fonts aren't available, so their text width can't be measured.
+/// - Images are embedded as 8-bit gray or RGB, with an optional soft mask
+///   for their straight alpha.
+///
+/// # Example
+/// ```
+/// # use devela::{PdfFont, PdfPage, PdfWriter, Vec, pos};
+/// let mut pdf = PdfWriter::new(Vec::new())?.with_compression(false);
+/// let mut page = PdfPage::new(PdfPage::A4);
+/// page.text(PdfFont::HelveticaBold, 24.0, pos![72.0, 770.0], "Quarterly report");
+/// pdf.add_page(&page)?;
+/// let bytes = pdf.finish()?;
+/// assert![bytes.starts_with(b"%PDF-1.4\n")];
+/// assert![bytes.ends_with(b"%%EOF\n")];
+/// # Ok::<(), devela::PdfError>(())
+/// ```
+#[derive(Debug)]
+pub struct PdfWriter<W: IoWrite> {
+    out: W,
+    /// The number of bytes written.
+    pos: u64,
+    /// The offset of each object, by id minus 1.
+    offsets: Vec<u64>,
+    /// The object ids of the pages.
+    pages: Vec<u32>,
+    /// The object ids of the standard fonts, or 0 if not written yet.
+    fonts: [u32; 14],
+    compress: bool,
+}
+
+impl<W: IoWrite> PdfWriter<W> {
+    /// Returns a writer of a new document into `out`, writing its header.
+    ///
+    /// # Errors
+    /// Returns [`Io`][crate::PdfError::Io] if writing fails.
+    pub fn new(out: W) -> PdfResult<Self> {
+        let mut w = Self {
+            out,
+            pos: 0,
+            offsets: vec![0; 2], // the catalog and the page tree, written last
+            pages: Vec::new(),
+            fonts: [0; 14],
+            compress: true,
+        };
+        // the binary comment marks the file as binary for transfer programs
+        w.put(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n")?;
+        Ok(w)
+    }
+    /// Returns the writer with the compression of the following streams enabled or not.
+    #[must_use]
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Returns the number of pages written.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+    /// Returns the number of bytes written.
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        self.pos
+    }
+    /// Returns `true` if nothing has been written.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
+    /// Embeds a raster image, to be drawn in the following pages.
+    ///
+    /// Samples of 1 to 4 channels are embedded as gray, gray with alpha,
+    /// RGB, and RGBA, with alpha as a soft mask. Each channel is quantized to 8 bits.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImage`] if the raster is empty or shorter than its extent,
+    /// or [`Io`][crate::PdfError::Io] if writing fails.
+    ///
+    /// [`InvalidImage`]: crate::PdfError::InvalidImage
+    pub fn add_image<R>(&mut self, raster: &R) -> PdfResult<PdfImage>
+    where
+        R: RasterView + ?Sized,
+        R::Sample: RasterPixel,
+    {
+        let extent = raster.raster_extent();
+        let [w, h] = extent.dim;
+        let len = (w as usize).checked_mul(h as usize).ok_or(InvalidImage)?;
+        let samples = raster.raster_samples().get(..len).ok_or(InvalidImage)?;
+        is![len == 0, return Err(InvalidImage)];
+        let channels = R::Sample::CHANNELS;
+        let (colors, alpha) = (is![channels >= 3, 3, 1], channels % 2 == 0);
+        let quantize = |c: f32| (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+
+        let mask = if alpha {
+            let data = samples.iter().map(|s| quantize(s.to_channels()[channels - 1]));
+            let id = self.new_object();
+            self.image_stream(id, extent, 1, None, &data.collect::<Vec<_>>())?;
+            Some(id)
+        } else {
+            None
+        };
+        let mut data = Vec::with_capacity(len * colors);
+        for s in samples {
+            data.extend(s.to_channels()[..colors].iter().map(|&c| quantize(c)));
+        }
+        let id = self.new_object();
+        self.image_stream(id, extent, colors, mask, &data)?;
+        Ok(PdfImage { id, extent })
+    }
+
+    /// Embeds the glyphs of the given `chars` of a bitmap font,
+    /// to draw text in the following pages.
+    ///
+    /// Characters without a glyph are skipped. The fallback character of the font
+    /// is always embedded, if any, and used for the characters that aren't.
+    ///
+    /// # Errors
+    /// Returns [`TooManyGlyphs`] if there are more than 255 glyphs to embed,
+    /// or [`Io`][crate::PdfError::Io] if writing fails.
+    ///
+    /// [`TooManyGlyphs`]: crate::PdfError::TooManyGlyphs
+    #[cfg(feature = "font")]
+    #[cfg_attr(nightly_doc, doc(cfg(feature = "font")))]
+    pub fn add_bitmap_font(
+        &mut self,
+        font: &FontBitmapView,
+        chars: &str,
+    ) -> PdfResult<PdfBitmapFont> {
+        let mut embedded: Vec<char> = chars.chars().filter(|&c| font.has_glyph(c)).collect();
+        embedded.extend(font.default_character());
+        embedded.sort_unstable();
+        embedded.dedup();
+        is![embedded.len() > 255, return Err(TooManyGlyphs)];
+        let default_code = font
+            .default_character()
+            .map_or(0, |d| embedded.binary_search(&d).map_or(0, |i| i as u8 + 1));
+
+        // the glyph procedures, the first one being blank
+        let (w, h) = (font.width(), font.height());
+        let [bx, by] = [font.bounds().x(), font.bounds().y()].map(f32::from);
+        let bbox = [bx, by, bx + f32::from(w), by + f32::from(h)];
+        let mut procs = Vec::with_capacity(embedded.len() + 1);
+        for glyph in [None].into_iter().chain(embedded.iter().map(|&c| font.glyph(c))) {
+            let mut data = Vec::new();
+            pdf_int(&mut data, u64::from(font.advance_x()));
+            data.extend_from_slice(b" 0 ");
+            for n in bbox {
+                pdf_real(&mut data, n);
+                data.push(b' ');
+            }
+            data.extend_from_slice(b"d1\n");
+            if let Some(glyph) = glyph.filter(|_| w > 0 && h > 0) {
+                data.extend_from_slice(b"q\n");
+                pdf_op(&mut data, &[f32::from(w), 0., 0., f32::from(h), bx, by], b"cm");
+                data.extend_from_slice(b"BI /W ");
+                pdf_int(&mut data, u64::from(w));
+                data.extend_from_slice(b" /H ");
+                pdf_int(&mut data, u64::from(h));
+                data.extend_from_slice(b" /IM true /BPC 1 /D [1 0] ID\n");
+                let row_len = usize::from(w).div_ceil(8);
+                for y in 0..usize::from(h) {
+                    let row = glyph.row(y).and_then(|row| row.get(..row_len));
+                    data.extend_from_slice(row.unwrap_or(&vec![0; row_len]));
+                }
+                data.extend_from_slice(b"\nEI Q\n");
+            }
+            let id = self.new_object();
+            self.stream(id, b"", &data)?;
+            procs.push(id);
+        }
+
+        let id = self.new_object();
+        let mut dict = Vec::new();
+        dict.extend_from_slice(b"<< /Type /Font /Subtype /Type3 /FontBBox [");
+        for (i, n) in bbox.into_iter().enumerate() {
+            is![i > 0, dict.push(b' ')];
+            pdf_real(&mut dict, n);
+        }
+        dict.extend_from_slice(b"] /FontMatrix [");
+        let scale = 1.0 / f32::from(h.max(1));
+        for (i, n) in [scale, 0., 0., scale, 0., 0.].into_iter().enumerate() {
+            is![i > 0, dict.push(b' ')];
+            pdf_real_digits(&mut dict, n, 6);
+        }
+        dict.extend_from_slice(b"]\n/CharProcs <<");
+        for (code, &proc) in procs.iter().enumerate() {
+            dict.extend_from_slice(b" /g");
+            pdf_int(&mut dict, code as u64);
+            dict.push(b' ');
+            pdf_ref(&mut dict, proc);
+        }
+        dict.extend_from_slice(b" >>\n/Encoding << /Type /Encoding /Differences [0");
+        for code in 0..procs.len() {
+            dict.extend_from_slice(b" /g");
+            pdf_int(&mut dict, code as u64);
+        }
+        dict.extend_from_slice(b"] >>\n/FirstChar 0 /LastChar ");
+        pdf_int(&mut dict, procs.len() as u64 - 1);
+        dict.extend_from_slice(b" /Widths [");
+        for code in 0..procs.len() {
+            is![code > 0, dict.push(b' ')];
+            pdf_int(&mut dict, u64::from(font.advance_x()));
+        }
+        dict.extend_from_slice(b"]\n/Resources << /ProcSet [/PDF /ImageB] >> >>");
+        self.object(id, &dict)?;
+        Ok(PdfBitmapFont {
+            id,
+            chars: embedded,
+            default_code,
+            height: h,
+            advance: font.advance_x(),
+        })
+    }
+
+    /// Writes a page, along with the standard fonts it uses for the first time.
+    ///
+    /// # Errors
+    /// Returns [`Io`][crate::PdfError::Io] if writing fails.
+    pub fn add_page(&mut self, page: &PdfPage) -> PdfResult<()> {
+        let mut fonts = Vec::new();
+        for font in PdfFont::ALL.into_iter().filter(|f| page.fonts & (1 << f.index()) != 0) {
+            let i = font.index();
+            if self.fonts[i] == 0 {
+                self.fonts[i] = self.new_object();
+                let mut dict = Vec::new();
+                dict.extend_from_slice(b"<< /Type /Font /Subtype /Type1 /BaseFont /");
+                dict.extend_from_slice(font.name().as_bytes());
+                is![!font.is_symbolic(), dict.extend_from_slice(b" /Encoding /WinAnsiEncoding")];
+                dict.extend_from_slice(b" >>");
+                self.object(self.fonts[i], &dict)?;
+            }
+            fonts.push((b'F', i as u32 + 1, self.fonts[i]));
+        }
+        #[cfg(feature = "font")]
+        fonts.extend(page.bitmap_fonts.iter().map(|&id| (b'B', id, id)));
+
+        let contents = self.new_object();
+        self.stream(contents, b"", page.content())?;
+
+        let id = self.new_object();
+        let mut dict = Vec::new();
+        dict.extend_from_slice(b"<< /Type /Page /Parent ");
+        pdf_ref(&mut dict, PAGES);
+        dict.extend_from_slice(b" /MediaBox [0 0 ");
+        pdf_real(&mut dict, page.size().dim[0]);
+        dict.push(b' ');
+        pdf_real(&mut dict, page.size().dim[1]);
+        dict.extend_from_slice(b"]\n/Resources <<");
+        if !fonts.is_empty() {
+            dict.extend_from_slice(b" /Font <<");
+            for (prefix, name, font) in fonts {
+                dict.extend_from_slice(&[b' ', b'/', prefix]);
+                pdf_int(&mut dict, u64::from(name));
+                dict.push(b' ');
+                pdf_ref(&mut dict, font);
+            }
+            dict.extend_from_slice(b" >>");
+        }
+        if !page.images.is_empty() {
+            dict.extend_from_slice(b" /XObject <<");
+            for &image in &page.images {
+                dict.extend_from_slice(b" /Im");
+                pdf_int(&mut dict, u64::from(image));
+                dict.push(b' ');
+                pdf_ref(&mut dict, image);
+            }
+            dict.extend_from_slice(b" >>");
+        }
+        dict.extend_from_slice(b" >>\n/Contents ");
+        pdf_ref(&mut dict, contents);
+        dict.extend_from_slice(b" >>");
+        self.object(id, &dict)?;
+        self.pages.push(id);
+        Ok(())
+    }
+
+    /// Completes the document, returning the output.
+    ///
+    /// # Errors
+    /// Returns [`SizeOutOfRange`] if the document is too long to be referenced,
+    /// or [`Io`][crate::PdfError::Io] if writing fails.
+    ///
+    /// [`SizeOutOfRange`]: crate::PdfError::SizeOutOfRange
+    pub fn finish(mut self) -> PdfResult<W> {
+        let mut dict = Vec::new();
+        dict.extend_from_slice(b"<< /Type /Pages /Kids [");
+        for (i, &page) in self.pages.iter().enumerate() {
+            is![i > 0, dict.push(b' ')];
+            pdf_ref(&mut dict, page);
+        }
+        dict.extend_from_slice(b"] /Count ");
+        pdf_int(&mut dict, self.pages.len() as u64);
+        dict.extend_from_slice(b" >>");
+        self.object(PAGES, &dict)?;
+        dict.clear();
+        dict.extend_from_slice(b"<< /Type /Catalog /Pages ");
+        pdf_ref(&mut dict, PAGES);
+        dict.extend_from_slice(b" >>");
+        self.object(CATALOG, &dict)?;
+
+        let xref = self.pos;
+        is![xref > OFFSET_MAX, return Err(SizeOutOfRange)];
+        let size = self.offsets.len() as u64 + 1;
+        let mut table = Vec::with_capacity(size as usize * 20 + 64);
+        table.extend_from_slice(b"xref\n0 ");
+        pdf_int(&mut table, size);
+        table.extend_from_slice(b"\n0000000000 65535 f \n");
+        for &offset in &self.offsets {
+            let mut digits = [b'0'; 10];
+            let mut n = offset;
+            for d in digits.iter_mut().rev() {
+                *d = b'0' + (n % 10) as u8;
+                n /= 10;
+            }
+            table.extend_from_slice(&digits);
+            table.extend_from_slice(b" 00000 n \n");
+        }
+        table.extend_from_slice(b"trailer\n<< /Size ");
+        pdf_int(&mut table, size);
+        table.extend_from_slice(b" /Root ");
+        pdf_ref(&mut table, CATALOG);
+        table.extend_from_slice(b" >>\nstartxref\n");
+        pdf_int(&mut table, xref);
+        table.extend_from_slice(b"\n%%EOF\n");
+        self.put(&table)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /* helpers */
+
+    /// Writes `bytes`.
+    fn put(&mut self, bytes: &[u8]) -> PdfResult<()> {
+        self.out.write_all(bytes)?;
+        self.pos += bytes.len() as u64;
+        Ok(())
+    }
+    /// Returns the id of a new object, to be written later.
+    fn new_object(&mut self) -> u32 {
+        self.offsets.push(0);
+        self.offsets.len() as u32
+    }
+    /// Writes the start of the object `id`, recording its offset.
+    fn begin(&mut self, id: u32) -> PdfResult<()> {
+        is![self.pos > OFFSET_MAX, return Err(SizeOutOfRange)];
+        self.offsets[id as usize - 1] = self.pos;
+        let mut head = Vec::with_capacity(16);
+        pdf_int(&mut head, u64::from(id));
+        head.extend_from_slice(b" 0 obj\n");
+        self.put(&head)
+    }
+    /// Writes the object `id` with the given `value`.
+    fn object(&mut self, id: u32, value: &[u8]) -> PdfResult<()> {
+        self.begin(id)?;
+        self.put(value)?;
+        self.put(b"\nendobj\n")
+    }
+    /// Writes the stream object `id` with the given `data`,
+    /// and the entries of its dictionary besides its length and filter.
+    fn stream(&mut self, id: u32, entries: &[u8], data: &[u8]) -> PdfResult<()> {
+        let mut compressed = Vec::new();
+        if self.compress {
+            compressed.resize(Deflate::encoded_len_max_zlib(data.len()), 0);
+            let len = Deflate::encode_zlib(data, &mut compressed).unwrap_or(usize::MAX);
+            compressed.truncate(is![len < data.len(), len, 0]);
+        }
+        let (data, filter) = if compressed.is_empty() {
+            (data, &b""[..])
+        } else {
+            (&compressed[..], &b" /Filter /FlateDecode"[..])
+        };
+        let mut dict = Vec::with_capacity(entries.len() + 48);
+        dict.extend_from_slice(b"<<");
+        dict.extend_from_slice(entries);
+        dict.extend_from_slice(filter);
+        dict.extend_from_slice(b" /Length ");
+        pdf_int(&mut dict, data.len() as u64);
+        dict.extend_from_slice(b" >>\nstream\n");
+        self.begin(id)?;
+        self.put(&dict)?;
+        self.put(data)?;
+        self.put(b"\nendstream\nendobj\n")
+    }
+    /// Writes the image object `id`, of 1 or 3 color channels of 8 bits.
+    fn image_stream(
+        &mut self,
+        id: u32,
+        extent: Extent2<u32>,
+        colors: usize,
+        mask: Option<u32>,
+        data: &[u8],
+    ) -> PdfResult<()> {
+        let mut entries = Vec::new();
+        entries.extend_from_slice(b" /Type /XObject /Subtype /Image /Width ");
+        pdf_int(&mut entries, u64::from(extent.dim[0]));
+        entries.extend_from_slice(b" /Height ");
+        pdf_int(&mut entries, u64::from(extent.dim[1]));
+        entries.extend_from_slice(is![
+            colors == 3,
+            b" /ColorSpace /DeviceRGB",
+            b" /ColorSpace /DeviceGray"
+        ]);
+        entries.extend_from_slice(b" /BitsPerComponent 8");
+        if let Some(mask) = mask {
+            entries.extend_from_slice(b" /SMask ");
+            pdf_ref(&mut entries, mask);
+        }
+        self.stream(id, &entries, data)
+    }
+}
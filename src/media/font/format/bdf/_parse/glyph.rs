@@ -41,6 +41,17 @@ pub(crate) struct BdfBitmap<'a> {
     /// Number of bytes encoded by each row.
     pub(crate) row_bytes: usize,
 }
+impl<'a> BdfBitmap<'a> {
+    /// Returns the decoded bytes of each row, from the top.
+    pub(crate) fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = u8> + 'a> + 'a {
+        let token = |line: &'a [u8]| line.split(|b| matches!(b, b' ' | b'\t' | b'\r')).next();
+        let rows = self.source.split(|&b| b == b'\n').filter_map(token);
+        rows.filter(|row| !row.is_empty()).map(|row| {
+            let digit = |d| hex_value(d).unwrap_or(0);
+            row.chunks_exact(2).map(move |pair| (digit(pair[0]) << 4) | digit(pair[1]))
+        })
+    }
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct BdfGlyph<'a> {
@@ -51,6 +62,13 @@ pub(crate) struct BdfGlyph<'a> {
     pub(crate) bitmap: BdfBitmap<'a>,
 }
 impl<'a> BdfGlyph<'a> {
+    /// Returns the horizontal device advance, if declared.
+    pub(crate) const fn advance(&self) -> Option<i32> {
+        match self.metrics.dwidth {
+            Some([x, _]) => Some(x),
+            None => None,
+        }
+    }
     pub(super) const fn read(
         reader: &mut BdfReader<'a>,
         header: &BdfHeader<'a>,
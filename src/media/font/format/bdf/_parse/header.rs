@@ -118,6 +118,19 @@ impl<'a> BdfHeader<'a> {
     ) -> BdfResult<BdfMetrics> {
         local.with_defaults(self.global_metrics).validate(self.metrics_set, line)
     }
+    /// Returns the value of the integer property `name` within `bytes`, if declared.
+    pub(crate) fn integer_property(&self, bytes: &'a [u8], name: &[u8]) -> Option<i64> {
+        let section = self.properties?;
+        let mut reader = BdfReader::new(bytes.get(section.start..section.end)?);
+        while let Ok(Some(line)) = reader.next() {
+            is! { !line.is(name), continue }
+            let mut fields = line.fields();
+            if let (Ok(value), Ok(())) = (fields.i64(), fields.finish()) {
+                return Some(value);
+            }
+        }
+        None
+    }
     pub(super) const fn supports_vertical_metrics(&self) -> bool {
         matches!(self.version, BdfVersion::V2_2)
     }
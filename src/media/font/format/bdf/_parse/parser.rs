@@ -20,7 +20,7 @@ impl<'a> BdfParser<'a> {
         let header = bdf_try!(BdfHeader::read_from(&mut reader));
         Ok(Self { reader, header, glyph_index: 0 })
     }
-    pub(super) const fn header(&self) -> &BdfHeader<'a> {
+    pub(crate) const fn header(&self) -> &BdfHeader<'a> {
         &self.header
     }
     pub(super) const fn glyph_index(&self) -> usize {
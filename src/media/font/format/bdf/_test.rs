@@ -220,3 +220,104 @@ CHARS 0
         assert!(Bdf::glyph_count(INVALID).is_err());
     }
 }
+
+mod write {
+    use super::*;
+    use crate::{Dvbf, Fonts, Region2};
+
+    #[test]
+    fn writes_valid_fonts() {
+        let mut buf = [0; 1024];
+        let len = Bdf::write(&Fonts::TERMIVELA_8_16, "termivela", &['A'..='B'], &mut buf).unwrap();
+        let bdf = &buf[..len];
+        assert_eq!(Bdf::validate(bdf), Ok(()));
+        assert_eq!(Bdf::glyph_count(bdf), Ok(2));
+        assert_eq!(Bdf::bounds(bdf), Ok(Region2::from_xy_wh(0, -4, 8, 16)));
+        assert!(bdf.starts_with(b"STARTFONT 2.1\nFONT termivela\nSIZE 16 72 72\n"));
+        assert!(bdf.ends_with(b"ENDCHAR\nENDFONT\n"));
+        let glyph = b"STARTCHAR U+0042\nENCODING 66\nSWIDTH 500 0\nDWIDTH 8 0\nBBX 8 16 0 -4\n";
+        assert!(bdf.windows(glyph.len()).any(|w| w == glyph));
+    }
+    #[test]
+    fn round_trips_through_dvbf() {
+        let font = Fonts::TERMIVELA_8_16;
+        let mut bdf = [0; 16 * 1024];
+        let len = Bdf::write(&font, "termivela", &[' '..='~', '\u{FFFD}'..='\u{FFFD}'], &mut bdf);
+        let mut dvbf = [0; 4096];
+        let len = Bdf::to_dvbf(&bdf[..len.unwrap()], &[], &mut dvbf).unwrap();
+        let subset = Dvbf::read(&dvbf[..len]).unwrap();
+        assert_eq!(subset.glyph_count(), 96);
+        for index in 0..subset.glyph_count() {
+            let glyph = subset.glyph_at(index).unwrap();
+            assert_eq!(Some(glyph), font.glyph(glyph.character()));
+        }
+        assert_eq!((subset.bounds(), subset.advance_x()), (font.bounds(), font.advance_x()));
+        assert_eq!((subset.ascent(), subset.descent()), (font.ascent(), font.descent()));
+        assert_eq!(subset.line_advance(), font.line_advance());
+        assert_eq!(subset.default_character(), Some('\u{FFFD}'));
+    }
+    #[test]
+    fn converts_glyphs_into_the_font_bounds() {
+        const FONT: &[u8] = b"\
+STARTFONT 2.1
+FONT test
+SIZE 8 72 72
+FONTBOUNDINGBOX 4 4 -1 -1
+STARTPROPERTIES 2
+FONT_ASCENT 3
+DEFAULT_CHAR 66
+ENDPROPERTIES
+CHARS 3
+STARTCHAR A
+ENCODING 65
+SWIDTH 500 0
+DWIDTH 5 0
+BBX 2 2 0 0
+BITMAP
+C0
+40
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+SWIDTH 500 0
+DWIDTH 3 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+STARTCHAR B
+ENCODING 66
+SWIDTH 500 0
+DWIDTH 4 0
+BBX 1 1 -1 -1
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+        let mut buf = [0; 128];
+        let len = Bdf::to_dvbf(FONT, &[], &mut buf).unwrap();
+        let font = Dvbf::read(&buf[..len]).unwrap();
+        assert_eq!(font.glyph_count(), 2);
+        assert_eq!(font.glyph('A').unwrap().bitmap(), [0, 0x60, 0x20, 0]);
+        assert_eq!(font.glyph('B').unwrap().bitmap(), [0, 0, 0, 0x80]);
+        assert_eq!(font.bounds(), Region2::from_xy_wh(-1, -1, 4, 4));
+        assert_eq!((font.ascent(), font.descent(), font.line_advance()), (3, 1, 4));
+        assert_eq!((font.advance_x(), font.default_character()), (5, Some('B')));
+
+        let len = Bdf::to_dvbf(FONT, &['A'..='A'], &mut buf).unwrap();
+        let font = Dvbf::read(&buf[..len]).unwrap();
+        assert_eq!((font.glyph_count(), font.default_character()), (1, None));
+        assert_eq!(Bdf::to_dvbf(FONT, &['a'..='z'], &mut buf), Err(BdfError::NoGlyphs));
+    }
+    #[test]
+    fn rejects_invalid_names_and_short_buffers() {
+        let font = Fonts::TERMIVELA_8_16;
+        let mut buf = [0; 1024];
+        assert_eq!(Bdf::write(&font, "", &[], &mut buf), Err(BdfError::InvalidName));
+        assert_eq!(Bdf::write(&font, "a\nb", &[], &mut buf), Err(BdfError::InvalidName));
+        let len = Bdf::write(&font, "t", &['A'..='B'], &mut buf).unwrap();
+        let error = Bdf::write(&font, "t", &['A'..='B'], &mut buf[..len - 1]);
+        assert_eq!(error, Err(BdfError::InsufficientBuffer { needed: len, available: len - 1 }));
+    }
+}
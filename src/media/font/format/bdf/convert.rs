@@ -0,0 +1,116 @@
+// devela/src/media/font/format/bdf/convert.rs
+//
+//! Implements [`Bdf::to_dvbf`].
+//
+
+use super::_parse::{BdfGlyph, BdfParser};
+use crate::{Bdf, BdfError, DvbfError, DvbfMetrics, DvbfWriter, in_char_ranges, is};
+use ::core::ops::RangeInclusive;
+
+impl Bdf {
+    /// Converts the glyphs of a BDF font within `ranges` into a DVBF strike in `buf`,
+    /// returning the number of bytes written.
+    ///
+    /// An empty `ranges` keeps every encoded glyph. Unencoded glyphs are skipped.
+    ///
+    /// Every glyph is placed within the font bounding box, which becomes the DVBF cell,
+    /// and advances by the widest advance. The ascent, descent and default character
+    /// are taken from the `FONT_ASCENT`, `FONT_DESCENT` and `DEFAULT_CHAR` properties,
+    /// with the ascent and descent defaulting to the extent of the bounding box.
+    ///
+    /// The result can be read back with [`Dvbf::read`][crate::Dvbf::read].
+    ///
+    /// # Errors
+    /// Returns the errors of [`Bdf::validate`], [`BdfError::NoGlyphs`] if no glyph
+    /// is within `ranges`, [`BdfError::UnsupportedMetrics`] if the metrics don't fit
+    /// in a DVBF strike, or [`BdfError::InsufficientBuffer`] if `buf` is too short.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Bdf, Dvbf, Fonts};
+    /// let font = Fonts::TERMIVELA_8_16;
+    /// let mut bdf = [0; 4096];
+    /// let len = Bdf::write(&font, "termivela", &['a'..='c'], &mut bdf).unwrap();
+    /// let mut dvbf = [0; 512];
+    /// let len = Bdf::to_dvbf(&bdf[..len], &[], &mut dvbf).unwrap();
+    /// let abc = Dvbf::read(&dvbf[..len]).unwrap();
+    /// assert_eq![abc.glyph('b'), font.glyph('b')];
+    /// ```
+    pub fn to_dvbf(
+        bytes: &[u8],
+        ranges: &[RangeInclusive<char>],
+        buf: &mut [u8],
+    ) -> Result<usize, BdfError> {
+        Self::validate(bytes)?;
+        let encoded = |glyph: &BdfGlyph| {
+            let c = u32::try_from(glyph.encoding.primary).ok().and_then(char::from_u32);
+            c.filter(|&c| in_char_ranges(c, ranges))
+        };
+
+        // measure the widest advance of the kept glyphs
+        let mut parser = BdfParser::new(bytes)?;
+        let header = *parser.header();
+        let (mut count, mut advance) = (0, 0);
+        while let Some(glyph) = parser.next_glyph()? {
+            is![encoded(&glyph).is_none(), continue];
+            count += 1;
+            advance = advance.max(glyph.advance().unwrap_or(0));
+        }
+        is![count == 0, return Err(BdfError::NoGlyphs)];
+
+        let bounds = header.bounds;
+        let (fx, fy) = (bounds.x(), bounds.y());
+        let (fw, fh) = (bounds.w(), bounds.h());
+        let small = |v: i64| u16::try_from(v).map_err(|_| BdfError::UnsupportedMetrics);
+        let property = |name: &[u8], default: i64| {
+            header.integer_property(bytes, name).unwrap_or(default).max(0)
+        };
+        let ascent = property(b"FONT_ASCENT", i64::from(fy) + i64::from(fh));
+        let descent = property(b"FONT_DESCENT", -i64::from(fy));
+        let line = is![ascent + descent == 0, i64::from(fh), ascent + descent];
+        let advance = is![advance > 0, i64::from(advance), i64::from(fw)];
+        let metrics = DvbfMetrics {
+            width: small(fw.into())?,
+            height: small(fh.into())?,
+            bounds_x: i16::try_from(fx).map_err(|_| BdfError::UnsupportedMetrics)?,
+            bounds_y: i16::try_from(fy).map_err(|_| BdfError::UnsupportedMetrics)?,
+            advance_x: small(advance)?,
+            line_advance: small(line)?,
+            ascent: small(ascent)?,
+            descent: small(descent)?,
+        };
+        let default = header.integer_property(bytes, b"DEFAULT_CHAR");
+        let default = default.and_then(|c| u32::try_from(c).ok()).and_then(char::from_u32);
+
+        // draw every kept glyph into its cell
+        let mut writer = DvbfWriter::new(buf, metrics, count).map_err(|e| match e {
+            DvbfError::InsufficientBuffer { needed, available } => {
+                BdfError::insufficient_buffer(needed, available)
+            }
+            _ => BdfError::UnsupportedMetrics,
+        })?;
+        let row_stride = writer.row_stride();
+        let top = i64::from(fy) + i64::from(fh);
+        let mut parser = BdfParser::new(bytes)?;
+        while let Some(glyph) = parser.next_glyph()? {
+            let Some(c) = encoded(&glyph) else { continue };
+            let record = writer.push(c);
+            let (gx, gy, gh) = (glyph.bounds.x(), glyph.bounds.y(), glyph.bounds.h());
+            let col = i64::from(gx) - i64::from(fx);
+            let row = top - (i64::from(gy) + i64::from(gh));
+            for (y, bytes) in glyph.bitmap.rows().enumerate() {
+                let py = row + y as i64;
+                is![py < 0 || py >= i64::from(fh), continue];
+                for (i, byte) in bytes.enumerate() {
+                    for bit in (0..8).filter(|bit| byte & (0x80 >> bit) != 0) {
+                        let px = col + (i * 8 + bit) as i64;
+                        is![px < 0 || px >= i64::from(fw), continue];
+                        let (px, py) = (px as usize, py as usize);
+                        record[py * row_stride + px / 8] |= 0x80 >> (px % 8);
+                    }
+                }
+            }
+        }
+        writer.finish(default).map_err(|_| BdfError::UnsupportedMetrics)
+    }
+}
@@ -6,7 +6,7 @@
 use crate::{Debug, Display, Error, FmtResult, Formatter, Version, write};
 
 #[doc = crate::_tags!(font error_composite)]
-/// An error encountered while parsing BDF data.
+/// An error encountered while parsing or writing BDF data.
 #[doc = crate::_doc_meta!{location("media/font")}]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -31,6 +31,23 @@ pub enum BdfError {
 
     /// The declared BDF version is syntactically valid but unsupported.
     UnsupportedVersion(Version),
+
+    /// There are no glyphs to write.
+    NoGlyphs,
+
+    /// The font name is empty or contains control characters.
+    InvalidName,
+
+    /// The font metrics can't be represented in the target format.
+    UnsupportedMetrics,
+
+    /// The output buffer is too short for the encoded font.
+    InsufficientBuffer {
+        /// The number of bytes needed.
+        needed: usize,
+        /// The number of bytes available.
+        available: usize,
+    },
 }
 impl Error for BdfError {}
 impl Display for BdfError {
@@ -48,6 +65,12 @@ impl Display for BdfError {
             Self::UnsupportedVersion(version) => {
                 write!(f, "unsupported BDF version {version}")
             }
+            Self::NoGlyphs => f.write_str("no BDF glyphs to write"),
+            Self::InvalidName => f.write_str("invalid BDF font name"),
+            Self::UnsupportedMetrics => f.write_str("unsupported BDF font metrics"),
+            Self::InsufficientBuffer { needed, available } => {
+                write!(f, "insufficient BDF buffer: needed {needed} bytes, available {available}")
+            }
         }
     }
 }
@@ -61,4 +84,7 @@ impl BdfError {
     pub(crate) const fn unexpected_eof(line: u32) -> Self {
         Self::UnexpectedEof { line }
     }
+    pub(crate) const fn insufficient_buffer(needed: usize, available: usize) -> Self {
+        Self::InsufficientBuffer { needed, available }
+    }
 }
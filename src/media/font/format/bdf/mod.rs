@@ -8,8 +8,10 @@ mod _test;
 
 mod _parse;
 
+mod convert; // Bdf::to_dvbf
 mod error; // BdfError
 mod namespace; // Bdf
+mod write; // Bdf::write
 
 crate::structural_mods! { // _mods
     _mods {
@@ -12,8 +12,8 @@ use crate::{BdfError, Debug, Region2, Version, unwrap};
 ///
 /// BDF is a line-oriented textual format for one bitmap-font strike.
 ///
-/// The initial implementation exposes allocation-free header inspection.
-/// Complete validation and decoding are added by later parser stages.
+/// The implementation exposes allocation-free header inspection and validation,
+/// conversion to [`Dvbf`][crate::Dvbf] strikes, and writing from bitmap fonts.
 #[derive(Debug)]
 pub struct Bdf;
 
@@ -0,0 +1,114 @@
+// devela/src/media/font/format/bdf/write.rs
+//
+//! Implements [`Bdf::write`].
+//
+
+use crate::{Bdf, BdfError, FmtResult, FmtWrite, FontBitmapView, GlyphBitmapView};
+use crate::{in_char_ranges, is, write, writeln};
+use ::core::ops::RangeInclusive;
+
+impl Bdf {
+    /// Writes the glyphs of `font` within `ranges` as a BDF 2.1 font named `name`
+    /// into `buf`, returning the number of bytes written.
+    ///
+    /// An empty `ranges` keeps every glyph. Each glyph is written with the bounding
+    /// box and advance of the whole strike, and is named after its code point.
+    /// The ascent, descent and default character are kept as properties.
+    ///
+    /// # Errors
+    /// Returns [`BdfError::InvalidName`] if `name` is empty or contains control
+    /// characters, [`BdfError::NoGlyphs`] if no glyph is within `ranges`,
+    /// or [`BdfError::InsufficientBuffer`] if `buf` is too short.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Bdf, Fonts};
+    /// let mut buf = [0; 4096];
+    /// let len = Bdf::write(&Fonts::TERMIVELA_8_16, "termivela", &['A'..='C'], &mut buf).unwrap();
+    /// assert![Bdf::validate(&buf[..len]).is_ok()];
+    /// assert_eq![Bdf::glyph_count(&buf[..len]), Ok(3)];
+    /// ```
+    pub fn write(
+        font: &FontBitmapView,
+        name: &str,
+        ranges: &[RangeInclusive<char>],
+        buf: &mut [u8],
+    ) -> Result<usize, BdfError> {
+        is![
+            name.is_empty() || name.chars().any(char::is_control),
+            return Err(BdfError::InvalidName)
+        ];
+        let kept = (0..font.glyph_count()).filter_map(|i| font.glyph_at(i));
+        let kept = kept.filter(|glyph| in_char_ranges(glyph.character(), ranges));
+        let count = kept.clone().count();
+        is![count == 0, return Err(BdfError::NoGlyphs)];
+        let default = font.default_character().filter(|&c| in_char_ranges(c, ranges));
+
+        let mut out = Output { buf, len: 0 };
+        // writing to `Output` never fails
+        let _ = write_font(&mut out, font, name, kept, count, default);
+        is![
+            out.len > out.buf.len(),
+            return Err(BdfError::insufficient_buffer(out.len, out.buf.len()))
+        ];
+        Ok(out.len)
+    }
+}
+
+/// Writes the BDF source of the given glyphs of `font`.
+fn write_font<'a>(
+    o: &mut Output,
+    font: &FontBitmapView<'a>,
+    name: &str,
+    glyphs: impl Iterator<Item = GlyphBitmapView<'a>>,
+    count: usize,
+    default: Option<char>,
+) -> FmtResult<()> {
+    let (width, height) = (font.width(), font.height());
+    let (x, y) = (font.bounds().x(), font.bounds().y());
+    let (ascent, descent, advance) = (font.ascent(), font.descent(), font.advance_x());
+    let size = u32::from(ascent) + u32::from(descent);
+    let size = is![size == 0, u32::from(height), size];
+    let swidth = (u32::from(advance) * 1000 + size / 2) / size;
+    let row_bytes = width.div_ceil(8) as usize;
+    // the padding bits at the end of each row must be clear
+    let last_mask = 0xFF_u8 << ((8 - width % 8) % 8);
+
+    writeln!(o, "STARTFONT 2.1\nFONT {name}\nSIZE {size} 72 72")?;
+    writeln!(o, "FONTBOUNDINGBOX {width} {height} {x} {y}")?;
+    writeln!(o, "STARTPROPERTIES {}", is![default.is_some(), 3, 2])?;
+    writeln!(o, "FONT_ASCENT {ascent}\nFONT_DESCENT {descent}")?;
+    if let Some(c) = default {
+        writeln!(o, "DEFAULT_CHAR {}", c as u32)?;
+    }
+    writeln!(o, "ENDPROPERTIES\nCHARS {count}")?;
+    for glyph in glyphs {
+        let c = glyph.scalar();
+        writeln!(o, "STARTCHAR U+{c:04X}\nENCODING {c}")?;
+        writeln!(o, "SWIDTH {swidth} 0\nDWIDTH {advance} 0")?;
+        writeln!(o, "BBX {width} {height} {x} {y}\nBITMAP")?;
+        for row in (0..height as usize).filter_map(|y| glyph.row(y)) {
+            for (i, byte) in row[..row_bytes].iter().enumerate() {
+                write!(o, "{:02X}", is![i + 1 == row_bytes, byte & last_mask, *byte])?;
+            }
+            writeln!(o)?;
+        }
+        o.write_str("ENDCHAR\n")?;
+    }
+    o.write_str("ENDFONT\n")
+}
+
+/// Writes as much text as fits in a buffer, while counting all of it.
+struct Output<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+}
+impl FmtWrite for Output<'_> {
+    fn write_str(&mut self, s: &str) -> FmtResult<()> {
+        if let Some(dst) = self.buf.get_mut(self.len..self.len + s.len()) {
+            dst.copy_from_slice(s.as_bytes());
+        }
+        self.len += s.len();
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 //
 //! Tests the DVBF binary contract independently of external font assets.
 
-use crate::{Dvbf, DvbfError, FontBitmapView, Version};
+use crate::{Dvbf, DvbfError, FontBitmapView, Fonts, Version};
 
 const HEADER_LEN: usize = Dvbf::HEADER_BYTES as usize;
 const MINIMAL_LEN: usize = HEADER_LEN + 4 + 1;
@@ -330,3 +330,40 @@ fn rejects_unmapped_default_scalar() {
     write_u32(&mut bytes, 56, 0x42);
     assert_error(&bytes, DvbfError::MissingDefaultGlyph(0x42));
 }
+
+#[test]
+fn writes_the_same_font() {
+    const REGULAR: &[u8] =
+        include_bytes!("../../../../../assets/font/termivela/termivela-8x16-regular.dvbf");
+    let mut buf = [0; REGULAR.len()];
+    assert_eq!(Dvbf::write(&Fonts::TERMIVELA_8_16, &[], &mut buf), Ok(REGULAR.len()));
+    assert_eq!(buf, REGULAR);
+}
+#[test]
+fn writes_subsets() {
+    let font = Fonts::TERMIVELA_8_16;
+    let mut buf = [0; 1024];
+    let len = Dvbf::write(&font, &['A'..='Z', '0'..='1'], &mut buf).unwrap();
+    assert_eq!(len, HEADER_LEN + 28 * (4 + 16));
+    let subset = Dvbf::read(&buf[..len]).unwrap();
+    assert_eq!(subset.glyph_count(), 28);
+    assert_eq!(subset.character_at(0), Some('0'));
+    assert_eq!(subset.glyph('Q'), font.glyph('Q'));
+    assert_eq!(subset.glyph('a'), None);
+    assert_eq!((subset.bounds(), subset.ascent()), (font.bounds(), font.ascent()));
+    // the default glyph is outside the ranges
+    assert_eq!(subset.default_character(), None);
+    let len = Dvbf::write(&font, &['\u{FFFD}'..='\u{FFFD}'], &mut buf).unwrap();
+    assert_eq!(Dvbf::read(&buf[..len]).unwrap().default_character(), Some('\u{FFFD}'));
+}
+#[test]
+fn rejects_writing_no_glyphs_or_short_buffers() {
+    let font = Fonts::TERMIVELA_8_16;
+    let mut buf = [0; 128];
+    assert_eq!(
+        Dvbf::write(&font, &['\u{10FFF0}'..='\u{10FFFF}'], &mut buf),
+        Err(DvbfError::NoGlyphs)
+    );
+    let error = Dvbf::write(&font, &['a'..='e'], &mut buf);
+    assert_eq!(error, Err(DvbfError::InsufficientBuffer { needed: 164, available: 128 }));
+}
@@ -0,0 +1,106 @@
+// devela/src/media/font/format/dvbf/bake.rs
+//
+//! Implements [`Dvbf::bake`].
+//
+
+use crate::{
+    Dvbf, DvbfError, DvbfMetrics, DvbfWriter, Float, FontOutlineView, GlyphRasterizer, OtfError, is,
+};
+
+impl Dvbf {
+    /// Bakes the given characters of an outline font into a DVBF strike written to `buf`,
+    /// returning the number of bytes written.
+    ///
+    /// Glyphs are rasterized at `size` pixels per em, and a pixel is set
+    /// when at least half of it is covered.
+    ///
+    /// Since DVBF strikes have fixed metrics, every glyph is placed in a cell that
+    /// encloses all of them, and advances by the widest advance, so that
+    /// proportional fonts are baked as monospaced ones.
+    ///
+    /// `chars` must be in strictly increasing order. Characters the font doesn't map
+    /// are skipped, and `default` is recorded only if its glyph is baked.
+    ///
+    /// The result can be read back with [`Dvbf::read`].
+    ///
+    /// # Errors
+    /// Returns [`OtfError::InvalidCharacters`] if `chars` are unsorted or none is mapped,
+    /// the errors of [`GlyphRasterizer::rasterize`], or [`OtfError::InsufficientBuffer`]
+    /// if `buf` is too short, or the strike is too large to be encoded.
+    pub fn bake<const N: usize>(
+        font: &FontOutlineView,
+        size: f32,
+        chars: impl IntoIterator<Item = char, IntoIter: Clone>,
+        default: Option<char>,
+        rasterizer: &mut GlyphRasterizer<'_, N>,
+        buf: &mut [u8],
+    ) -> Result<usize, OtfError> {
+        let chars = chars.into_iter();
+        let round = |v: f32| Float(v).const_round().0 as i32;
+        let scale = font.scale(size);
+        let too_large = OtfError::insufficient_buffer(usize::MAX, buf.len());
+
+        // measure the cell that encloses every glyph
+        let (mut count, mut previous) = (0_u32, None);
+        let (mut min, mut max, mut advance) = ([i32::MAX; 2], [i32::MIN; 2], 1);
+        for c in chars.clone() {
+            is![previous.is_some_and(|p| p >= c), return Err(OtfError::InvalidCharacters)];
+            previous = Some(c);
+            let Some(glyph) = font.glyph_index(c) else { continue };
+            count += 1;
+            advance = advance.max(round(font.advance_width(glyph) as f32 * scale));
+            let bounds = rasterizer.bounds(font, glyph, size)?;
+            if bounds.w() > 0 && bounds.h() > 0 {
+                let (x, y) = (bounds.x(), bounds.y());
+                (min[0], min[1]) = (min[0].min(x), min[1].min(y));
+                max[0] = max[0].max(x.saturating_add_unsigned(bounds.w()));
+                max[1] = max[1].max(y.saturating_add_unsigned(bounds.h()));
+            }
+        }
+        is![count == 0, return Err(OtfError::InvalidCharacters)];
+        // a strike of empty glyphs keeps a single pixel above the baseline
+        is![min[0] > max[0], (min, max) = ([0, -1], [1, 0])];
+
+        let small = |v: i32| u16::try_from(v).map_err(|_| too_large);
+        let (width, height) = (small(max[0] - min[0])?, small(max[1] - min[1])?);
+        let bounds_x = i16::try_from(min[0]).map_err(|_| too_large)?;
+        let bounds_y = i16::try_from(-max[1]).map_err(|_| too_large)?;
+        let ascent = small(round(font.ascender() as f32 * scale).max(0))?;
+        let descent = small(round(-font.descender() as f32 * scale).max(0))?;
+        let line = round(font.line_advance() as f32 * scale).max(ascent as i32 + descent as i32);
+        let (line_advance, advance_x) = (small(line.max(1))?, small(advance)?);
+        let metrics = DvbfMetrics {
+            width,
+            height,
+            bounds_x,
+            bounds_y,
+            advance_x,
+            line_advance,
+            ascent,
+            descent,
+        };
+        let mut writer = DvbfWriter::new(buf, metrics, count as usize).map_err(|e| match e {
+            DvbfError::InsufficientBuffer { needed, available } => {
+                OtfError::insufficient_buffer(needed, available)
+            }
+            _ => too_large,
+        })?;
+
+        // write every bitmap record
+        let row_stride = writer.row_stride();
+        for c in chars {
+            let Some(glyph) = font.glyph_index(c) else { continue };
+            let record = writer.push(c);
+            let mask = rasterizer.rasterize(font, glyph, size)?;
+            let offset = [mask.bounds().x() - min[0], mask.bounds().y() - min[1]];
+            for y in 0..mask.height() {
+                for x in 0..mask.width() {
+                    is![mask.get(x, y).is_none_or(|c| c.get() < 128), continue];
+                    let (px, py) = (offset[0] as usize + x, offset[1] as usize + y);
+                    record[py * row_stride + px / 8] |= 0x80 >> (px % 8);
+                }
+            }
+        }
+        writer.finish(default).map_err(|_| too_large)
+    }
+}
@@ -7,7 +7,7 @@
 use crate::{Debug, Display, Error, FmtResult, Formatter, Version};
 
 #[doc = crate::_tags!(font error_composite)]
-/// An error encountered while validating or reading DVBF data.
+/// An error encountered while validating, reading or writing DVBF data.
 #[doc = crate::_doc_meta!{location("media/font")}]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum DvbfError {
@@ -52,6 +52,15 @@ pub enum DvbfError {
     InvalidDefaultScalar(u32),
     /// The declared default scalar has no corresponding glyph.
     MissingDefaultGlyph(u32),
+    /// There are no glyphs to write.
+    NoGlyphs,
+    /// The output buffer is too short for the encoded font.
+    InsufficientBuffer {
+        /// The number of bytes needed.
+        needed: usize,
+        /// The number of bytes available.
+        available: usize,
+    },
 }
 
 impl Error for DvbfError {}
@@ -80,6 +89,10 @@ impl Display for DvbfError {
             }
             E::InvalidDefaultScalar(cp) => write!(f, "DVBF default scalar {cp:#x} is invalid"),
             E::MissingDefaultGlyph(cp) => write!(f, "DVBF default scalar {cp:#x} has no glyph"),
+            E::NoGlyphs => f.write_str("no glyphs to write as DVBF"),
+            E::InsufficientBuffer { needed, available } => {
+                write!(f, "insufficient DVBF buffer: needed {needed} bytes, available {available}")
+            }
         }
     }
 }
@@ -88,4 +101,7 @@ impl DvbfError {
     pub const fn invalid_scalar(index: u32, scalar: u32) -> Self {
         Self::InvalidScalar { index, scalar }
     }
+    pub const fn insufficient_buffer(needed: usize, available: usize) -> Self {
+        Self::InsufficientBuffer { needed, available }
+    }
 }
@@ -6,14 +6,19 @@
 #[cfg(test)]
 mod _test;
 
+mod bake; // Dvbf::bake
 mod define; // Dvbf
 mod error; // DvbfError
+mod write; // Dvbf::write, (DvbfMetrics, DvbfWriter, in_char_ranges)
 
-crate::structural_mods! { // _mods
+crate::structural_mods! { // _mods, _crate_internals
     _mods {
         pub use super::{
             define::*,
             error::*,
         };
     }
+    _crate_internals {
+        pub(crate) use super::write::{DvbfMetrics, DvbfWriter, in_char_ranges};
+    }
 }
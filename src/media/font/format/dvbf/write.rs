@@ -0,0 +1,245 @@
+// devela/src/media/font/format/dvbf/write.rs
+//
+//! Implements [`Dvbf::write`], and defines the private `DvbfWriter`.
+//
+
+use crate::{Dvbf, DvbfError, FontBitmapView, is};
+use ::core::ops::RangeInclusive;
+
+impl Dvbf {
+    /// Writes the glyphs of `font` within `ranges` into a DVBF strike in `buf`,
+    /// returning the number of bytes written.
+    ///
+    /// An empty `ranges` keeps every glyph, which re-encodes the font.
+    /// The default character is kept only when its glyph is kept.
+    ///
+    /// The result can be read back with [`Dvbf::read`].
+    ///
+    /// # Errors
+    /// Returns [`DvbfError::NoGlyphs`] if no glyph is within `ranges`,
+    /// or [`DvbfError::InsufficientBuffer`] if `buf` is too short.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Dvbf, Fonts};
+    /// let mut buf = [0; 2048];
+    /// let len = Dvbf::write(&Fonts::TERMIVELA_8_16, &['0'..='9'], &mut buf).unwrap();
+    /// let digits = Dvbf::read(&buf[..len]).unwrap();
+    /// assert_eq![digits.glyph_count(), 10];
+    /// assert_eq![digits.glyph('7'), Fonts::TERMIVELA_8_16.glyph('7')];
+    /// ```
+    pub fn write(
+        font: &FontBitmapView,
+        ranges: &[RangeInclusive<char>],
+        buf: &mut [u8],
+    ) -> Result<usize, DvbfError> {
+        let kept = (0..font.glyph_count()).filter_map(|i| font.glyph_at(i));
+        let kept = kept.filter(|glyph| in_char_ranges(glyph.character(), ranges));
+        let mut writer = DvbfWriter::new(buf, DvbfMetrics::of(font), kept.clone().count())?;
+        let row_stride = writer.row_stride();
+        for glyph in kept {
+            let record = writer.push(glyph.character());
+            for (y, row) in record.chunks_exact_mut(row_stride).enumerate() {
+                glyph.row(y).inspect(|src| row.copy_from_slice(&src[..row_stride]));
+            }
+        }
+        writer.finish(font.default_character())
+    }
+}
+
+/// Returns whether `c` is within any of the `ranges`, or whether they are empty.
+pub(crate) fn in_char_ranges(c: char, ranges: &[RangeInclusive<char>]) -> bool {
+    ranges.is_empty() || ranges.iter().any(|range| range.contains(&c))
+}
+
+/// The fixed metrics of a DVBF strike.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DvbfMetrics {
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+    pub(crate) bounds_x: i16,
+    pub(crate) bounds_y: i16,
+    pub(crate) advance_x: u16,
+    pub(crate) line_advance: u16,
+    pub(crate) ascent: u16,
+    pub(crate) descent: u16,
+}
+impl DvbfMetrics {
+    /// Returns the metrics of a bitmap font.
+    pub(crate) const fn of(font: &FontBitmapView) -> Self {
+        Self {
+            width: font.width,
+            height: font.height,
+            bounds_x: font.bounds_x,
+            bounds_y: font.bounds_y,
+            advance_x: font.advance_x,
+            line_advance: font.line_advance,
+            ascent: font.ascent,
+            descent: font.descent,
+        }
+    }
+}
+
+/// Writes a DVBF strike from glyphs pushed in any order.
+///
+/// Room is reserved for the given maximum number of glyphs. On finishing,
+/// the glyphs are sorted by scalar, keeping the first of any duplicates,
+/// and the bitmap records are moved right after the used scalar table.
+#[derive(Debug)]
+pub(crate) struct DvbfWriter<'b> {
+    buf: &'b mut [u8],
+    metrics: DvbfMetrics,
+    row_stride: usize,
+    glyph_stride: usize,
+    max: usize,
+    len: usize,
+}
+impl<'b> DvbfWriter<'b> {
+    const HEADER: usize = Dvbf::HEADER_BYTES as usize;
+
+    /// Starts writing a strike of up to `max` glyphs into `buf`.
+    pub(crate) fn new(
+        buf: &'b mut [u8],
+        metrics: DvbfMetrics,
+        max: usize,
+    ) -> Result<Self, DvbfError> {
+        is![max == 0, return Err(DvbfError::NoGlyphs)];
+        let m = metrics;
+        if m.width == 0 || m.height == 0 || m.advance_x == 0 || m.line_advance == 0 {
+            return Err(DvbfError::InvalidMetrics);
+        }
+        if m.ascent.checked_add(m.descent).is_none_or(|h| m.line_advance < h) {
+            return Err(DvbfError::InvalidMetrics);
+        }
+        let row_stride = m.width.div_ceil(8) as usize;
+        let glyph_stride = row_stride * m.height as usize;
+        let needed = (glyph_stride + 4).checked_mul(max).and_then(|n| n.checked_add(Self::HEADER));
+        let needed =
+            needed.filter(|&n| u32::try_from(n).is_ok()).ok_or(DvbfError::InvalidLayout)?;
+        is![buf.len() < needed, return Err(DvbfError::insufficient_buffer(needed, buf.len()))];
+        buf[..needed].fill(0);
+        Ok(Self {
+            buf,
+            metrics,
+            row_stride,
+            glyph_stride,
+            max,
+            len: 0,
+        })
+    }
+
+    /// Returns the number of bytes of each bitmap row.
+    pub(crate) const fn row_stride(&self) -> usize {
+        self.row_stride
+    }
+
+    /// Adds a glyph for `c`, and returns its blank bitmap record.
+    ///
+    /// # Panics
+    /// Panics if the maximum number of glyphs has already been pushed.
+    pub(crate) fn push(&mut self, c: char) -> &mut [u8] {
+        assert![self.len < self.max, "too many DVBF glyphs"];
+        let index = self.len;
+        self.len += 1;
+        self.buf[Self::HEADER + index * 4..][..4].copy_from_slice(&(c as u32).to_le_bytes());
+        let start = self.bitmaps() + index * self.glyph_stride;
+        &mut self.buf[start..start + self.glyph_stride]
+    }
+
+    /// Sorts the glyphs, writes the header, and returns the length of the strike.
+    ///
+    /// The `default` character is recorded only if it has a glyph.
+    pub(crate) fn finish(mut self, default: Option<char>) -> Result<usize, DvbfError> {
+        is![self.len == 0, return Err(DvbfError::NoGlyphs)];
+        // a stable insertion sort, which is linear over already sorted glyphs
+        for i in 1..self.len {
+            let mut j = i;
+            while j > 0 && self.scalar(j - 1) > self.scalar(j) {
+                self.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+        let mut count = 0;
+        for i in 0..self.len {
+            is![count > 0 && self.scalar(i) == self.scalar(count - 1), continue];
+            if i != count {
+                let (scalar, gs) = (Self::HEADER + i * 4, self.glyph_stride);
+                self.buf.copy_within(scalar..scalar + 4, Self::HEADER + count * 4);
+                let record = self.bitmaps() + i * gs;
+                self.buf.copy_within(record..record + gs, self.bitmaps() + count * gs);
+            }
+            count += 1;
+        }
+        let scalars = &self.buf[Self::HEADER..Self::HEADER + count * 4];
+        let has = |c: char| scalars.chunks_exact(4).any(|s| s == (c as u32).to_le_bytes());
+        let default = default.filter(|&c| has(c)).map_or(Dvbf::NO_SCALAR, |c| c as u32);
+
+        let (from, to) = (self.bitmaps(), Self::HEADER + count * 4);
+        let total = to + count * self.glyph_stride;
+        self.buf.copy_within(from..from + count * self.glyph_stride, to);
+        let m = self.metrics;
+        let mut header = Header { buf: self.buf, at: 0 };
+        header.bytes(&Dvbf::MAGIC);
+        header.u16(Dvbf::VERSION.major);
+        header.u16(Dvbf::VERSION.minor);
+        header.u16(Dvbf::VERSION.patch);
+        header.u16(Dvbf::HEADER_BYTES);
+        header.u32(0); // flags
+        header.u32(total as u32);
+        header.u32(count as u32);
+        header.u32(Self::HEADER as u32);
+        header.u32(to as u32);
+        header.u32(self.glyph_stride as u32);
+        header.u16(m.width);
+        header.u16(m.height);
+        header.u16(self.row_stride as u16);
+        header.bytes(&[1, 0]); // bit depth, reserved
+        header.u16(m.bounds_x as u16);
+        header.u16(m.bounds_y as u16);
+        header.u16(m.advance_x);
+        header.u16(m.line_advance);
+        header.u16(m.ascent);
+        header.u16(m.descent);
+        header.u32(default);
+        header.u32(0); // reserved
+        Ok(total)
+    }
+
+    /* helpers */
+
+    /// Returns the offset of the reserved bitmap records.
+    const fn bitmaps(&self) -> usize {
+        Self::HEADER + self.max * 4
+    }
+    fn scalar(&self, index: usize) -> u32 {
+        let at = Self::HEADER + index * 4;
+        u32::from_le_bytes([self.buf[at], self.buf[at + 1], self.buf[at + 2], self.buf[at + 3]])
+    }
+    fn swap(&mut self, a: usize, b: usize) {
+        for k in 0..4 {
+            self.buf.swap(Self::HEADER + a * 4 + k, Self::HEADER + b * 4 + k);
+        }
+        let (bitmaps, gs) = (self.bitmaps(), self.glyph_stride);
+        for k in 0..gs {
+            self.buf.swap(bitmaps + a * gs + k, bitmaps + b * gs + k);
+        }
+    }
+}
+
+/// Writes the little-endian fields of a DVBF header.
+struct Header<'b> {
+    buf: &'b mut [u8],
+    at: usize,
+}
+impl Header<'_> {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf[self.at..][..bytes.len()].copy_from_slice(bytes);
+        self.at += bytes.len();
+    }
+    fn u16(&mut self, v: u16) {
+        self.bytes(&v.to_le_bytes());
+    }
+    fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+}
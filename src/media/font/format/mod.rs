@@ -5,12 +5,19 @@
 
 mod bdf; // Bdf
 mod dvbf; // Dvbf
+mod otf; // Otf
+mod psf; // Psf, PsfView
 
-crate::structural_mods! { // _mods
+crate::structural_mods! { // _mods, _crate_internals
     _mods {
         pub use super::{
             bdf::_all::*,
             dvbf::_all::*,
+            otf::_all::*,
+            psf::_all::*,
         };
     }
+    _crate_internals {
+        pub(crate) use super::dvbf::_crate_internals::*;
+    }
 }
@@ -0,0 +1,144 @@
+// devela/src/media/font/format/otf/_helper.rs
+//
+//! Bounds-checked big-endian reads, and the outline builder.
+//
+
+use crate::{FontOutlineView, OtfError, Position2, RasterPath, Slice, is, unwrap};
+
+/* reads */
+
+/// Returns the byte at `offset`, or `None` if it is out of bounds.
+pub(super) const fn u8_at(bytes: &[u8], offset: usize) -> Option<u8> {
+    is![offset < bytes.len(), Some(bytes[offset]), None]
+}
+/// Returns the big-endian `u16` at `offset`, or `None` if it is out of bounds.
+pub(super) const fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    is![offset >= bytes.len() || bytes.len() - offset < 2, return None];
+    Some(u16::from_be_bytes([bytes[offset], bytes[offset + 1]]))
+}
+/// Returns the big-endian `i16` at `offset`, or `None` if it is out of bounds.
+pub(super) const fn i16_at(bytes: &[u8], offset: usize) -> Option<i16> {
+    is![offset >= bytes.len() || bytes.len() - offset < 2, return None];
+    Some(i16::from_be_bytes([bytes[offset], bytes[offset + 1]]))
+}
+/// Returns the big-endian `u32` at `offset`, or `None` if it is out of bounds.
+pub(super) const fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    is![offset >= bytes.len() || bytes.len() - offset < 4, return None];
+    let b = [bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]];
+    Some(u32::from_be_bytes(b))
+}
+/// Returns the big-endian unsigned integer of `size` bytes at `offset`.
+pub(super) const fn uint_at(bytes: &[u8], offset: usize, size: usize) -> Option<u32> {
+    is![size == 0 || size > 4 || offset >= bytes.len() || bytes.len() - offset < size, {
+        return None;
+    }];
+    let mut value = 0;
+    let mut i = 0;
+    while i < size {
+        value = (value << 8) | bytes[offset + i] as u32;
+        i += 1;
+    }
+    Some(value)
+}
+/// Returns the `len` bytes at `offset`, or `None` if they are out of bounds.
+pub(super) const fn bytes_at(bytes: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    Slice::range_checked(bytes, offset, unwrap![some? offset.checked_add(len)])
+}
+/// Returns the bytes from `offset` to the end, or `None` if it is out of bounds.
+pub(super) const fn bytes_from(bytes: &[u8], offset: usize) -> Option<&[u8]> {
+    Slice::range_from_checked(bytes, offset)
+}
+
+/* outlines */
+
+/// Appends outline contours in font units to a raster path.
+///
+/// Coordinates are scaled and flipped, so that y grows downwards from `origin`.
+pub(super) struct OutlineBuilder<'p, const N: usize> {
+    path: &'p mut RasterPath<N>,
+    scale: f32,
+    origin: [f32; 2],
+    /// The affine transform applied before scaling, as `[xx, yx, xy, yy, dx, dy]`.
+    transform: [f32; 6],
+    /// Whether a contour is open.
+    open: bool,
+}
+impl<'p, const N: usize> OutlineBuilder<'p, N> {
+    /// The identity transform.
+    pub(super) const IDENTITY: [f32; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+    pub(super) const fn new(path: &'p mut RasterPath<N>, scale: f32, origin: [f32; 2]) -> Self {
+        Self {
+            path,
+            scale,
+            origin,
+            transform: Self::IDENTITY,
+            open: false,
+        }
+    }
+    /// Returns the current transform.
+    pub(super) const fn transform(&self) -> [f32; 6] {
+        self.transform
+    }
+    /// Sets the transform applied to the following points.
+    pub(super) const fn set_transform(&mut self, transform: [f32; 6]) {
+        self.transform = transform;
+    }
+
+    pub(super) fn move_to(&mut self, p: [f32; 2]) -> Result<(), OtfError> {
+        self.close();
+        self.open = true;
+        Self::map(self.path.move_to(self.point(p)))
+    }
+    pub(super) fn line_to(&mut self, p: [f32; 2]) -> Result<(), OtfError> {
+        Self::map(self.path.line_to(self.point(p)))
+    }
+    pub(super) fn quad_to(&mut self, c: [f32; 2], p: [f32; 2]) -> Result<(), OtfError> {
+        Self::map(self.path.quad_to(self.point(c), self.point(p)))
+    }
+    pub(super) fn cubic_to(
+        &mut self,
+        c0: [f32; 2],
+        c1: [f32; 2],
+        p: [f32; 2],
+    ) -> Result<(), OtfError> {
+        Self::map(self.path.cubic_to(self.point(c0), self.point(c1), self.point(p)))
+    }
+    /// Closes the open contour, if any.
+    pub(super) const fn close(&mut self) {
+        is![self.open, self.path.close()];
+        self.open = false;
+    }
+
+    /// Transforms, scales and flips a point in font units.
+    const fn point(&self, [x, y]: [f32; 2]) -> Position2<f32> {
+        let [xx, yx, xy, yy, dx, dy] = self.transform;
+        let (x, y) = (xx * x + xy * y + dx, yx * x + yy * y + dy);
+        Position2::new([self.origin[0] + x * self.scale, self.origin[1] - y * self.scale])
+    }
+    fn map(result: Result<(), crate::NotEnoughSpace>) -> Result<(), OtfError> {
+        result.map_err(|_| OtfError::PathCapacity)
+    }
+}
+
+impl FontOutlineView<'_> {
+    /// Appends the contours of `glyph`, scaled by `scale` pixels per font unit,
+    /// with its origin at `origin`.
+    pub(crate) fn append_outline<const N: usize>(
+        &self,
+        glyph: u16,
+        scale: f32,
+        origin: [f32; 2],
+        path: &mut RasterPath<N>,
+    ) -> Result<(), OtfError> {
+        is![glyph >= self.glyph_count, return Err(OtfError::InvalidGlyph(glyph))];
+        let mut out = OutlineBuilder::new(path, scale, origin);
+        is![
+            self.is_cff,
+            self.cff_outline(glyph, &mut out)?,
+            self.glyf_outline(glyph, &mut out, 0)?
+        ];
+        out.close();
+        Ok(())
+    }
+}
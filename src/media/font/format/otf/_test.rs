@@ -0,0 +1,364 @@
+// devela/src/media/font/format/otf/_test.rs
+//
+//! Tests OpenType parsing and rasterization with fonts built in place.
+
+use crate::{Coverage8, Dvbf, FontOutlineView, GlyphRasterizer, Otf, OtfError, Region2};
+
+/// A big-endian byte writer over a fixed buffer.
+struct Bytes<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+impl<const N: usize> Bytes<N> {
+    fn new() -> Self {
+        Self { buf: [0; N], len: 0 }
+    }
+    fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.buf[self.len..][..bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        self
+    }
+    fn u16(&mut self, v: u16) -> &mut Self {
+        self.bytes(&v.to_be_bytes())
+    }
+    fn i16(&mut self, v: i16) -> &mut Self {
+        self.bytes(&v.to_be_bytes())
+    }
+    fn u32(&mut self, v: u32) -> &mut Self {
+        self.bytes(&v.to_be_bytes())
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+/// Builds a font from its tables into `out`.
+fn sfnt(version: u32, tables: &[(&[u8; 4], &[u8])], out: &mut Bytes<4096>) {
+    out.u32(version).u16(tables.len() as u16).u16(0).u16(0).u16(0);
+    let mut offset = 12 + tables.len() * 16;
+    for (tag, data) in tables {
+        out.bytes(*tag).u32(0).u32(offset as u32).u32(data.len() as u32);
+        offset += data.len().next_multiple_of(4);
+    }
+    for (_, data) in tables {
+        out.bytes(data);
+        out.bytes(&[0; 3][..data.len().next_multiple_of(4) - data.len()]);
+    }
+}
+fn head(loc_format: i16) -> Bytes<64> {
+    let mut t = Bytes::new();
+    t.u32(0x0001_0000).u32(0).u32(0).u32(0x5F0F_3CF5).u16(0).u16(1000);
+    t.bytes(&[0; 16]).i16(0).i16(-100).i16(1000).i16(800);
+    t.u16(0).u16(8).i16(2).i16(loc_format).i16(0);
+    t
+}
+fn hhea(h_metrics: u16) -> Bytes<64> {
+    let mut t = Bytes::new();
+    t.u32(0x0001_0000).i16(800).i16(-200).i16(100).bytes(&[0; 24]).u16(h_metrics);
+    t
+}
+fn maxp(glyphs: u16) -> Bytes<64> {
+    let mut t = Bytes::new();
+    t.u32(0x0000_5000).u16(glyphs);
+    t
+}
+
+/// A TrueType font with 6 glyphs:
+/// - `A`: a square from `(100, 0)` to `(600, 700)`, with short and same coordinates.
+/// - `B`: a square from `(0, 0)` to `(800, 800)`, with a square hole from `200` to `600`.
+/// - `C`: a round shape from 4 off-curve points, within `100` and `600`.
+/// - `D`: a composite of `A` moved by `(200, 100)`, and `C` halved and moved by `(10, -10)`.
+/// - ` `: an empty glyph.
+fn truetype(out: &mut Bytes<4096>) {
+    let mut glyf = Bytes::<512>::new();
+    let mut loca = Bytes::<64>::new();
+    let glyph = |loca: &mut Bytes<64>, glyf: &mut Bytes<512>, data: &[u8]| {
+        loca.u16((glyf.len / 2) as u16);
+        glyf.bytes(data).bytes(&[0][..data.len() % 2]);
+    };
+    glyph(&mut loca, &mut glyf, &[]);
+    let mut a = Bytes::<64>::new();
+    a.i16(1).i16(100).i16(0).i16(600).i16(700).u16(3).u16(0);
+    a.bytes(&[0x33, 0x11, 0x21, 0x11]).bytes(&[100]).i16(500).i16(700).i16(-700);
+    glyph(&mut loca, &mut glyf, a.as_slice());
+    let mut b = Bytes::<128>::new();
+    b.i16(2).i16(0).i16(0).i16(800).i16(800).u16(3).u16(7).u16(0).bytes(&[0x09, 7]);
+    for x in [0, 0, 800, 0, -600, 400, 0, -400] {
+        b.i16(x);
+    }
+    for y in [0, 800, 0, -800, 200, 0, 400, 0] {
+        b.i16(y);
+    }
+    glyph(&mut loca, &mut glyf, b.as_slice());
+    let mut c = Bytes::<64>::new();
+    c.i16(1).i16(100).i16(100).i16(600).i16(600).u16(3).u16(0).bytes(&[0x08, 3]);
+    c.i16(100).i16(0).i16(500).i16(0).i16(100).i16(500).i16(0).i16(-500);
+    glyph(&mut loca, &mut glyf, c.as_slice());
+    let mut d = Bytes::<64>::new();
+    d.i16(-1).i16(60).i16(40).i16(800).i16(800);
+    d.u16(0x0023).u16(1).i16(200).i16(100);
+    d.u16(0x000A).u16(3).bytes(&[10, (-10_i8) as u8]).i16(0x2000);
+    glyph(&mut loca, &mut glyf, d.as_slice());
+    glyph(&mut loca, &mut glyf, &[]);
+    loca.u16((glyf.len / 2) as u16);
+
+    let mut hmtx = Bytes::<64>::new();
+    for (advance, lsb) in [(500, 0), (700, 100), (800, 0), (700, 100), (900, 60)] {
+        hmtx.u16(advance).i16(lsb);
+    }
+    hmtx.i16(7);
+
+    let mut cmap = Bytes::<128>::new();
+    cmap.u16(0).u16(1).u16(3).u16(1).u32(12);
+    // format 4: ' ' to 5, 'A'..='D' to 1..=4, and the final 0xFFFF segment
+    cmap.u16(4).u16(16 + 3 * 8).u16(0).u16(6).u16(4).u16(1).u16(2);
+    cmap.u16(0x20).u16(0x44).u16(0xFFFF).u16(0);
+    cmap.u16(0x20).u16(0x41).u16(0xFFFF);
+    cmap.u16(5_u16.wrapping_sub(0x20)).u16(1_u16.wrapping_sub(0x41)).u16(1);
+    cmap.u16(0).u16(0).u16(0);
+
+    let mut kern = Bytes::<64>::new();
+    kern.u16(0).u16(1).u16(0).u16(14 + 2 * 6).u16(0x0001).u16(2).u16(12).u16(1).u16(0);
+    kern.u16(1).u16(2).i16(-50).u16(1).u16(3).i16(30);
+
+    sfnt(
+        Otf::VERSION_TRUETYPE,
+        &[
+            (b"cmap", cmap.as_slice()),
+            (b"glyf", glyf.as_slice()),
+            (b"head", head(0).as_slice()),
+            (b"hhea", hhea(5).as_slice()),
+            (b"hmtx", hmtx.as_slice()),
+            (b"kern", kern.as_slice()),
+            (b"loca", loca.as_slice()),
+            (b"maxp", maxp(6).as_slice()),
+        ],
+        out,
+    );
+}
+
+/// A CFF font with 3 glyphs:
+/// - `A`: the same square as the TrueType one, drawn with an advance width operand.
+/// - `B`: a curved shape from `(100, 0)` to `(500, 0)`, drawn with hints and subroutines.
+fn cff(out: &mut Bytes<4096>) {
+    let int = |v: i32| {
+        let b = v.to_be_bytes();
+        [29, b[0], b[1], b[2], b[3]]
+    };
+    let global_subr: &[u8] = &[14]; // endchar
+    let local_subr: &[u8] = &[139, 248, 136, 247, 92, 247, 92, 247, 92, 251, 92, 8, 11];
+    let notdef: &[u8] = &[14];
+    let square: &[u8] = &[249, 80, 239, 139, 21, 248, 136, 249, 80, 252, 136, 6, 14];
+    let curve: &[u8] = &[239, 189, 1, 19, 0x80, 239, 139, 21, 32, 10, 139, 252, 136, 5, 32, 29];
+
+    let (gsubrs_at, charstrings_at) = (34, 34 + 5 + global_subr.len());
+    let charstrings_len = 3 + 4 + notdef.len() + square.len() + curve.len();
+    let private_at = charstrings_at + charstrings_len;
+    let mut t = Bytes::<512>::new();
+    t.bytes(&[1, 0, 4, 1]);
+    t.bytes(&[0, 1, 1, 1, 2, b'T']);
+    t.bytes(&[0, 1, 1, 1, 18]).bytes(&int(charstrings_at as i32)).bytes(&[17]);
+    t.bytes(&int(6)).bytes(&int(private_at as i32)).bytes(&[18]);
+    t.bytes(&[0, 0]);
+    assert_eq![t.len, gsubrs_at];
+    t.bytes(&[0, 1, 1, 1, 1 + global_subr.len() as u8]).bytes(global_subr);
+    let ends = [notdef.len(), notdef.len() + square.len(), charstrings_len - 3 - 4];
+    t.bytes(&[0, 3, 1, 1]).bytes(&ends.map(|e| 1 + e as u8));
+    t.bytes(notdef).bytes(square).bytes(curve);
+    assert_eq![t.len, private_at];
+    t.bytes(&int(6)).bytes(&[19]);
+    t.bytes(&[0, 1, 1, 1, 1 + local_subr.len() as u8]).bytes(local_subr);
+
+    let mut hmtx = Bytes::<16>::new();
+    hmtx.u16(500).i16(0).u16(700).i16(100).u16(600).i16(100);
+    let mut cmap = Bytes::<64>::new();
+    cmap.u16(0).u16(1).u16(3).u16(10).u32(12);
+    cmap.u16(12).u16(0).u32(28).u32(0).u32(1).u32(0x41).u32(0x42).u32(1);
+
+    sfnt(
+        Otf::VERSION_CFF,
+        &[
+            (b"CFF ", t.as_slice()),
+            (b"cmap", cmap.as_slice()),
+            (b"head", head(0).as_slice()),
+            (b"hhea", hhea(3).as_slice()),
+            (b"hmtx", hmtx.as_slice()),
+            (b"maxp", maxp(3).as_slice()),
+        ],
+        out,
+    );
+}
+
+fn read_font(build: fn(&mut Bytes<4096>), bytes: &mut Bytes<4096>) -> FontOutlineView<'_> {
+    build(bytes);
+    Otf::read(bytes.as_slice()).unwrap()
+}
+
+#[test]
+fn reads_truetype_metrics() {
+    let mut bytes = Bytes::new();
+    let font = read_font(truetype, &mut bytes);
+    assert_eq![(font.glyph_count(), font.units_per_em(), font.is_cff()), (6, 1000, false)];
+    assert_eq![(font.ascender(), font.descender(), font.line_gap()), (800, -200, 100)];
+    assert_eq![font.line_advance(), 1100];
+    assert_eq![font.bounds(), Region2::from_xy_wh(0, -100, 1000, 900)];
+    assert_eq![font.scale(10.0).to_bits(), 0.01_f32.to_bits()];
+
+    assert_eq![font.glyph_index(' '), Some(5)];
+    assert_eq![font.glyph_index('A'), Some(1)];
+    assert_eq![font.glyph_index('D'), Some(4)];
+    assert_eq![font.glyph_index('E'), None];
+    assert_eq![font.glyph_index('\u{FFFF}'), None];
+    assert![font.has_glyph('C') && !font.has_glyph('@')];
+
+    assert_eq![(font.advance_width(1), font.left_side_bearing(1)), (700, 100)];
+    // glyphs beyond the advance widths share the last one
+    assert_eq![(font.advance_width(5), font.left_side_bearing(5)), (900, 7)];
+    assert_eq![(font.advance_width(6), font.left_side_bearing(6)), (0, 0)];
+
+    assert![font.has_kerning()];
+    assert_eq![(font.kerning(1, 2), font.kerning(1, 3), font.kerning(2, 1)), (-50, 30, 0)];
+}
+
+#[test]
+fn rasterizes_truetype_glyphs() {
+    let mut bytes = Bytes::new();
+    let font = read_font(truetype, &mut bytes);
+    let (mut scratch, mut coverage) = ([0.0; 128], [Coverage8::ZERO; 128 * 128]);
+    let mut rasterizer = GlyphRasterizer::<1024>::new(&mut scratch, &mut coverage);
+
+    // short, same and long coordinates
+    let mask = rasterizer.rasterize(&font, 1, 100.0).unwrap();
+    assert_eq![mask.bounds(), Region2::from_xy_wh(10, -70, 50, 70)];
+    assert![mask.coverage().iter().all(|c| c.is_full())];
+    assert_eq![mask.row(69).map(<[_]>::len), Some(50)];
+    assert_eq![(mask.row(70), mask.get(50, 0)), (None, None)];
+
+    // a hole of opposite direction, with repeated flags
+    let mask = rasterizer.rasterize(&font, 2, 10.0).unwrap();
+    assert_eq![mask.bounds(), Region2::from_xy_wh(0, -8, 8, 8)];
+    assert_eq![
+        (mask.get(1, 1), mask.get(3, 3), mask.get(6, 5)),
+        (Some(Coverage8::FULL), Some(Coverage8::ZERO), Some(Coverage8::FULL))
+    ];
+
+    // a contour without on-curve points
+    let mask = rasterizer.rasterize(&font, 3, 100.0).unwrap();
+    assert_eq![mask.bounds(), Region2::from_xy_wh(10, -60, 50, 50)];
+    assert_eq![(mask.get(0, 0), mask.get(25, 25)), (Some(Coverage8::ZERO), Some(Coverage8::FULL))];
+    assert![mask.get(0, 25).is_some_and(|c| !c.is_zero())];
+
+    // a composite with offsets and scale
+    let bounds = rasterizer.bounds(&font, 4, 100.0).unwrap();
+    assert_eq![bounds, Region2::from_xy_wh(6, -80, 74, 76)];
+
+    // an empty glyph
+    let mask = rasterizer.rasterize(&font, 5, 100.0).unwrap();
+    assert![mask.is_empty() && mask.bounds() == Region2::from_xy_wh(0, 0, 0, 0)];
+    assert_eq![rasterizer.rasterize(&font, 6, 10.0), Err(OtfError::InvalidGlyph(6))];
+}
+
+#[test]
+fn rasterizer_errors() {
+    let mut bytes = Bytes::new();
+    let font = read_font(truetype, &mut bytes);
+    let (mut scratch, mut coverage) = ([0.0; 128], [Coverage8::ZERO; 16]);
+    let mut rasterizer = GlyphRasterizer::<1024>::new(&mut scratch, &mut coverage);
+    let error = OtfError::InsufficientBuffer { needed: 64, available: 16 };
+    assert_eq![rasterizer.rasterize(&font, 2, 10.0), Err(error)];
+
+    let (mut scratch, mut coverage) = ([0.0; 4], [Coverage8::ZERO; 64]);
+    let mut rasterizer = GlyphRasterizer::<1024>::new(&mut scratch, &mut coverage);
+    let error = OtfError::InsufficientBuffer { needed: 10, available: 4 };
+    assert_eq![rasterizer.rasterize(&font, 2, 10.0), Err(error)];
+
+    let (mut scratch, mut coverage) = ([0.0; 128], [Coverage8::ZERO; 128]);
+    let mut rasterizer = GlyphRasterizer::<4>::new(&mut scratch, &mut coverage);
+    assert_eq![rasterizer.rasterize(&font, 2, 10.0), Err(OtfError::PathCapacity)];
+}
+
+#[test]
+fn reads_and_rasterizes_cff() {
+    let mut bytes = Bytes::new();
+    let font = read_font(cff, &mut bytes);
+    assert_eq![(font.glyph_count(), font.is_cff(), font.has_kerning()), (3, true, false)];
+    assert_eq![
+        (font.glyph_index('A'), font.glyph_index('B'), font.glyph_index('C')),
+        (Some(1), Some(2), None)
+    ];
+    assert_eq![font.advance_width(2), 600];
+
+    let (mut scratch, mut coverage) = ([0.0; 128], [Coverage8::ZERO; 128 * 128]);
+    let mut rasterizer = GlyphRasterizer::<1024>::new(&mut scratch, &mut coverage);
+    let mask = rasterizer.rasterize(&font, 1, 100.0).unwrap();
+    assert_eq![mask.bounds(), Region2::from_xy_wh(10, -70, 50, 70)];
+    assert![mask.coverage().iter().all(|c| c.is_full())];
+
+    let mask = rasterizer.rasterize(&font, 2, 100.0).unwrap();
+    let bounds = mask.bounds();
+    assert_eq![(bounds.x(), bounds.w(), bounds.y() + bounds.h() as i32), (10, 40, 0)];
+    assert![bounds.y() < -50 && bounds.y() > -70];
+    assert_eq![mask.get(35, mask.height() - 1), Some(Coverage8::FULL)];
+    assert_eq![mask.get(0, 0), Some(Coverage8::ZERO)];
+
+    assert![rasterizer.rasterize(&font, 0, 100.0).unwrap().is_empty()];
+}
+
+#[test]
+fn read_errors() {
+    assert_eq![Otf::read(&[]), Err(OtfError::TooShort)];
+    assert_eq![Otf::read(b"ttcf\0\0\0\0"), Err(OtfError::InvalidMagic)];
+    assert_eq![Otf::read(&[0, 1, 0, 0, 0, 1]), Err(OtfError::TooShort)];
+
+    let mut bytes = Bytes::new();
+    truetype(&mut bytes);
+    let mut missing = Bytes::<4096>::new();
+    missing.bytes(bytes.as_slice());
+    missing.buf[12..16].copy_from_slice(b"cmaq");
+    assert_eq![Otf::read(missing.as_slice()), Err(OtfError::MissingTable(*b"cmap"))];
+
+    // a table beyond the end of the data
+    let len = bytes.len;
+    assert_eq![Otf::read(&bytes.buf[..len - 40]), Err(OtfError::InvalidTable(*b"maxp"))];
+
+    // no unicode subtable
+    let cmap_offset = u32::from_be_bytes(bytes.buf[20..24].try_into().unwrap()) as usize;
+    bytes.buf[cmap_offset + 4..][..4].copy_from_slice(&[0, 3, 0, 0]);
+    assert_eq![Otf::read(bytes.as_slice()), Err(OtfError::UnsupportedTable(*b"cmap"))];
+}
+
+#[test]
+fn bakes_dvbf() {
+    let mut bytes = Bytes::new();
+    let font = read_font(truetype, &mut bytes);
+    let (mut scratch, mut coverage) = ([0.0; 64], [Coverage8::ZERO; 64 * 64]);
+    let mut rasterizer = GlyphRasterizer::<1024>::new(&mut scratch, &mut coverage);
+    let mut buf = [0; 256];
+    let chars = [' ', '@', 'A', 'B', 'C', 'D'];
+    let len = Dvbf::bake(&font, 10.0, chars, Some('A'), &mut rasterizer, &mut buf).unwrap();
+    assert_eq![len, 64 + 5 * 4 + 5 * 8];
+
+    let strike = Dvbf::read(&buf[..len]).unwrap();
+    assert_eq![(strike.glyph_count(), strike.width(), strike.height()), (5, 8, 8)];
+    assert_eq![strike.bounds(), Region2::from_xy_wh(0, 0, 8, 8)];
+    assert_eq![(strike.advance_x(), strike.line_advance()), (9, 11)];
+    assert_eq![(strike.ascent(), strike.descent()), (8, 2)];
+    assert_eq![strike.default_character(), Some('A')];
+    assert![!strike.has_glyph('@')];
+    assert_eq![strike.glyph(' ').unwrap().bitmap(), &[0; 8]];
+    let a = strike.glyph('A').unwrap();
+    assert_eq![a.bitmap(), &[0, 0x7C, 0x7C, 0x7C, 0x7C, 0x7C, 0x7C, 0x7C]];
+    let b = strike.glyph('B').unwrap();
+    assert_eq![b.bitmap(), &[0xFF, 0xFF, 0xC3, 0xC3, 0xC3, 0xC3, 0xFF, 0xFF]];
+
+    // without the default glyph
+    let len = Dvbf::bake(&font, 10.0, 'A'..='B', Some('Z'), &mut rasterizer, &mut buf).unwrap();
+    assert_eq![Dvbf::read(&buf[..len]).unwrap().default_character(), None];
+
+    let error = Dvbf::bake(&font, 10.0, ['B', 'A'], None, &mut rasterizer, &mut buf);
+    assert_eq![error, Err(OtfError::InvalidCharacters)];
+    let error = Dvbf::bake(&font, 10.0, ['x', 'y'], None, &mut rasterizer, &mut buf);
+    assert_eq![error, Err(OtfError::InvalidCharacters)];
+    let error = Dvbf::bake(&font, 10.0, chars, None, &mut rasterizer, &mut buf[..100]);
+    assert_eq![error, Err(OtfError::InsufficientBuffer { needed: 124, available: 100 })];
+}
@@ -0,0 +1,408 @@
+// devela/src/media/font/format/otf/cff.rs
+//
+//! Compact Font Format cubic outlines, from the `CFF ` table.
+//
+// TOC
+// - fn read
+// - INDEX and DICT structures
+// - Type 2 charstrings
+
+use super::_helper::{OutlineBuilder, bytes_at, i16_at, u8_at, u16_at, u32_at, uint_at};
+use crate::{FontOutlineView, OtfError, is, unwrap};
+
+const TAG: [u8; 4] = *b"CFF ";
+
+/// Reads the charstrings, local and global subroutines INDEX structures of a CFF table.
+#[allow(clippy::type_complexity, reason = "the three indexes are returned together")]
+pub(super) const fn read(cff: &[u8], glyph_count: u16) -> Result<(&[u8], &[u8], &[u8]), OtfError> {
+    const INVALID: OtfError = OtfError::InvalidTable(TAG);
+    is![!matches!(u8_at(cff, 0), Some(1)), return Err(OtfError::UnsupportedTable(TAG))];
+    let names = unwrap![some_ok_or? u8_at(cff, 2), INVALID] as usize;
+    let top_dicts = names + unwrap![some_ok_or? index_len(cff, names), INVALID];
+    let strings = top_dicts + unwrap![some_ok_or? index_len(cff, top_dicts), INVALID];
+    let global_subrs = strings + unwrap![some_ok_or? index_len(cff, strings), INVALID];
+    let global_subrs = unwrap![some_ok_or? index_at(cff, global_subrs), INVALID];
+
+    let top_dicts = unwrap![some_ok_or? index_at(cff, top_dicts), INVALID];
+    let top = unwrap![some_ok_or? index_item(top_dicts, 0), INVALID];
+    // CID-keyed fonts select their local subroutines per glyph
+    is![unwrap![ok? dict_find(top, ROS)].is_some(), return Err(OtfError::UnsupportedTable(TAG))];
+    if let Some(([kind, _], _)) = unwrap![ok? dict_find(top, CHARSTRING_TYPE)] {
+        is![kind != 2, return Err(OtfError::UnsupportedTable(TAG))];
+    }
+    let ([charstrings, _], _) = unwrap![some_ok_or? unwrap![ok? dict_find(top, CHARSTRINGS)],
+        INVALID];
+    is![charstrings < 0, return Err(INVALID)];
+    let charstrings = unwrap![some_ok_or? index_at(cff, charstrings as usize), INVALID];
+    is![index_count(charstrings) < glyph_count as usize, return Err(INVALID)];
+
+    let mut subrs: &[u8] = &[];
+    if let Some(([size, offset], 2)) = unwrap![ok? dict_find(top, PRIVATE)] {
+        is![size < 0 || offset < 0, return Err(INVALID)];
+        let private = bytes_at(cff, offset as usize, size as usize);
+        let private = unwrap![some_ok_or? private, INVALID];
+        if let Some(([local, _], _)) = unwrap![ok? dict_find(private, SUBRS)] {
+            is![local < 0, return Err(INVALID)];
+            let at = offset as usize + local as usize;
+            subrs = unwrap![some_ok_or? index_at(cff, at), INVALID];
+        }
+    }
+    Ok((charstrings, subrs, global_subrs))
+}
+
+/* INDEX and DICT structures */
+
+/// The DICT operator of the charstrings INDEX offset.
+const CHARSTRINGS: u16 = 17;
+/// The DICT operator of the private DICT size and offset.
+const PRIVATE: u16 = 18;
+/// The DICT operator of the local subroutines INDEX offset, relative to the private DICT.
+const SUBRS: u16 = 19;
+/// The DICT operator of the charstring type.
+const CHARSTRING_TYPE: u16 = 1206;
+/// The DICT operator that makes a font CID-keyed.
+const ROS: u16 = 1230;
+
+/// Returns the byte length of the INDEX at `offset`.
+const fn index_len(data: &[u8], offset: usize) -> Option<usize> {
+    let count = unwrap![some? u16_at(data, offset)] as usize;
+    is![count == 0, return Some(2)];
+    let size = unwrap![some? u8_at(data, offset + 2)] as usize;
+    let last = unwrap![some? uint_at(data, offset + 3 + count * size, size)] as usize;
+    is![last == 0, return None];
+    Some(2 + (count + 1) * size + last)
+}
+/// Returns the INDEX at `offset`.
+const fn index_at(data: &[u8], offset: usize) -> Option<&[u8]> {
+    bytes_at(data, offset, unwrap![some? index_len(data, offset)])
+}
+/// Returns the number of objects of an INDEX.
+const fn index_count(index: &[u8]) -> usize {
+    unwrap![some_or u16_at(index, 0), 0] as usize
+}
+/// Returns the object at `i` of an INDEX.
+const fn index_item(index: &[u8], i: usize) -> Option<&[u8]> {
+    let count = index_count(index);
+    is![i >= count, return None];
+    let size = unwrap![some? u8_at(index, 2)] as usize;
+    let start = unwrap![some? uint_at(index, 3 + i * size, size)] as usize;
+    let end = unwrap![some? uint_at(index, 3 + (i + 1) * size, size)] as usize;
+    is![start == 0 || end < start, return None];
+    bytes_at(index, 2 + (count + 1) * size + start, end - start)
+}
+
+/// Finds the operator `op` in a DICT, returning its first two operands and their count.
+///
+/// Escaped operators are numbered `1200 + op`. Real operands are read as `0`.
+#[allow(clippy::type_complexity, reason = "a private lookup result")]
+const fn dict_find(dict: &[u8], op: u16) -> Result<Option<([i32; 2], usize)>, OtfError> {
+    const INVALID: OtfError = OtfError::InvalidTable(TAG);
+    let (mut operands, mut count, mut i) = ([0; 2], 0, 0);
+    while i < dict.len() {
+        let b0 = dict[i];
+        if b0 <= 21 {
+            let mut found = b0 as u16;
+            if b0 == 12 {
+                i += 1;
+                found = 1200 + unwrap![some_ok_or? u8_at(dict, i), INVALID] as u16;
+            }
+            is![found == op, return Ok(Some((operands, count)))];
+            (count, i) = (0, i + 1);
+            continue;
+        }
+        let (value, len) = match b0 {
+            28 => (unwrap![some_ok_or? i16_at(dict, i + 1), INVALID] as i32, 3),
+            29 => (unwrap![some_ok_or? u32_at(dict, i + 1), INVALID] as i32, 5),
+            30 => {
+                // a real number, packed in nibbles up to the 0xf terminator
+                let mut len = 1;
+                loop {
+                    let b = unwrap![some_ok_or? u8_at(dict, i + len), INVALID];
+                    len += 1;
+                    is![b & 0x0F == 0x0F || b >> 4 == 0x0F, break];
+                }
+                (0, len)
+            }
+            32..=246 => (b0 as i32 - 139, 1),
+            247..=254 => {
+                let b1 = unwrap![some_ok_or? u8_at(dict, i + 1), INVALID] as i32;
+                let v = (b0 as i32 - 247) * 256 + b1 + 108;
+                (is![b0 <= 250, v, -((b0 as i32 - 251) * 256 + b1 + 108)], 2)
+            }
+            _ => return Err(INVALID),
+        };
+        is![count < 2, operands[count] = value];
+        (count, i) = (count + 1, i + len);
+    }
+    Ok(None)
+}
+
+/* Type 2 charstrings */
+
+impl FontOutlineView<'_> {
+    /// Appends the outline of a CFF glyph.
+    pub(super) fn cff_outline<const N: usize>(
+        &self,
+        glyph: u16,
+        out: &mut OutlineBuilder<'_, N>,
+    ) -> Result<(), OtfError> {
+        let code = index_item(self.cff_charstrings, glyph as usize);
+        let code = code.ok_or(OtfError::InvalidGlyph(glyph))?;
+        let mut run = Charstring {
+            out,
+            subrs: self.cff_subrs,
+            global_subrs: self.cff_global_subrs,
+            invalid: OtfError::InvalidGlyph(glyph),
+            stack: [0.0; 48],
+            len: 0,
+            point: [0.0; 2],
+            stems: 0,
+            width_done: false,
+        };
+        run.run(code, 0)?;
+        run.out.close();
+        Ok(())
+    }
+}
+
+/// The state of a running Type 2 charstring.
+struct Charstring<'o, 'p, 'a, const N: usize> {
+    out: &'o mut OutlineBuilder<'p, N>,
+    subrs: &'a [u8],
+    global_subrs: &'a [u8],
+    /// The error of a malformed charstring.
+    invalid: OtfError,
+    stack: [f32; 48],
+    len: usize,
+    /// The current point.
+    point: [f32; 2],
+    /// The number of declared stem hints.
+    stems: usize,
+    /// Whether the optional advance width operand has been consumed.
+    width_done: bool,
+}
+impl<const N: usize> Charstring<'_, '_, '_, N> {
+    const STACK_MAX: usize = 48;
+    const CALL_DEPTH_MAX: u8 = 10;
+
+    /// Runs a charstring, returning `true` when it ends the glyph.
+    fn run(&mut self, code: &[u8], depth: u8) -> Result<bool, OtfError> {
+        is![depth > Self::CALL_DEPTH_MAX, return Err(self.invalid)];
+        let mut i = 0;
+        while i < code.len() {
+            let b0 = code[i];
+            i += 1;
+            match b0 {
+                28 => {
+                    let v = i16_at(code, i).ok_or(self.invalid)?;
+                    self.push(v as f32)?;
+                    i += 2;
+                }
+                32..=246 => self.push(b0 as f32 - 139.0)?,
+                247..=254 => {
+                    let b1 = u8_at(code, i).ok_or(self.invalid)? as f32;
+                    let v = (b0 as f32 - 247.0) * 256.0 + b1 + 108.0;
+                    self.push(is![b0 <= 250, v, -((b0 as f32 - 251.0) * 256.0 + b1 + 108.0)])?;
+                    i += 1;
+                }
+                255 => {
+                    let v = u32_at(code, i).ok_or(self.invalid)? as i32;
+                    self.push(v as f32 / 65_536.0)?;
+                    i += 4;
+                }
+                // hstem, vstem, hstemhm, vstemhm
+                1 | 3 | 18 | 23 => self.stem_hints(),
+                // hintmask, cntrmask
+                19 | 20 => {
+                    self.stem_hints();
+                    i += self.stems.div_ceil(8);
+                }
+                // callsubr, callgsubr
+                10 | 29 => {
+                    let subrs = is![b0 == 10, self.subrs, self.global_subrs];
+                    let count = index_count(subrs);
+                    let bias = is![count < 1240, 107, is![count < 33_900, 1131, 32_768]];
+                    let index = self.pop()? as i32 + bias;
+                    let subr = usize::try_from(index).ok().and_then(|i| index_item(subrs, i));
+                    is![self.run(subr.ok_or(self.invalid)?, depth + 1)?, return Ok(true)];
+                }
+                // return
+                11 => return Ok(false),
+                // endchar
+                14 => {
+                    self.width(is![self.len == 1 || self.len == 5, 1, 0]);
+                    self.out.close();
+                    return Ok(true);
+                }
+                12 => {
+                    let b1 = u8_at(code, i).ok_or(self.invalid)?;
+                    i += 1;
+                    self.flex(b1)?;
+                }
+                _ => self.path(b0)?,
+            }
+        }
+        Ok(false)
+    }
+
+    /// Runs a path construction operator.
+    fn path(&mut self, op: u8) -> Result<(), OtfError> {
+        let (s, len) = (self.stack, self.len);
+        match op {
+            // rmoveto
+            21 => {
+                self.width(len.saturating_sub(2));
+                self.move_by(s[len.saturating_sub(2)], s[len.saturating_sub(1)])?;
+            }
+            // hmoveto, vmoveto
+            22 | 4 => {
+                self.width(len.saturating_sub(1));
+                let d = s[len.saturating_sub(1)];
+                is![op == 22, self.move_by(d, 0.0)?, self.move_by(0.0, d)?];
+            }
+            // rlineto
+            5 => {
+                for p in s[..len].chunks_exact(2) {
+                    self.line_by(p[0], p[1])?;
+                }
+            }
+            // hlineto, vlineto: alternating, starting horizontal or vertical
+            6 | 7 => {
+                for (i, &d) in s[..len].iter().enumerate() {
+                    let horizontal = (i % 2 == 0) == (op == 6);
+                    is![horizontal, self.line_by(d, 0.0)?, self.line_by(0.0, d)?];
+                }
+            }
+            // rrcurveto
+            8 => {
+                for c in s[..len].chunks_exact(6) {
+                    self.curve_by([c[0], c[1], c[2], c[3], c[4], c[5]])?;
+                }
+            }
+            // rcurveline
+            24 => {
+                let curves = len.saturating_sub(2) / 6 * 6;
+                for c in s[..curves].chunks_exact(6) {
+                    self.curve_by([c[0], c[1], c[2], c[3], c[4], c[5]])?;
+                }
+                is![len >= curves + 2, self.line_by(s[curves], s[curves + 1])?];
+            }
+            // rlinecurve
+            25 => {
+                let lines = len.saturating_sub(6) / 2 * 2;
+                for p in s[..lines].chunks_exact(2) {
+                    self.line_by(p[0], p[1])?;
+                }
+                let c = &s[lines..len];
+                is![c.len() == 6, self.curve_by([c[0], c[1], c[2], c[3], c[4], c[5]])?];
+            }
+            // vvcurveto, hhcurveto: an optional first cross delta, then curves of 4
+            26 | 27 => {
+                let (mut cross, mut i) = (0.0, 0);
+                is![len % 2 == 1, (cross, i) = (s[0], 1)];
+                while i + 4 <= len {
+                    let [a, b, c, d] = [s[i], s[i + 1], s[i + 2], s[i + 3]];
+                    is![op == 26, self.curve_by([cross, a, b, c, 0.0, d])?, {
+                        self.curve_by([a, cross, b, c, d, 0.0])?;
+                    }];
+                    (cross, i) = (0.0, i + 4);
+                }
+            }
+            // vhcurveto, hvcurveto: alternating curves, the last with an optional delta
+            30 | 31 => {
+                let (mut horizontal, mut i) = (op == 31, 0);
+                while i + 4 <= len {
+                    let [a, b, c, d] = [s[i], s[i + 1], s[i + 2], s[i + 3]];
+                    let last = is![len - i == 5, s[i + 4], 0.0];
+                    is![horizontal, self.curve_by([a, 0.0, b, c, last, d])?, {
+                        self.curve_by([0.0, a, b, c, d, last])?;
+                    }];
+                    (horizontal, i) = (!horizontal, i + 4);
+                }
+            }
+            _ => return Err(self.invalid),
+        }
+        self.len = 0;
+        Ok(())
+    }
+
+    /// Runs an escaped flex operator, drawn as its two curves.
+    fn flex(&mut self, op: u8) -> Result<(), OtfError> {
+        let (s, [x0, y0]) = (self.stack, self.point);
+        match op {
+            // flex
+            35 if self.len == 13 => {
+                self.curve_by([s[0], s[1], s[2], s[3], s[4], s[5]])?;
+                self.curve_by([s[6], s[7], s[8], s[9], s[10], s[11]])?;
+            }
+            // hflex
+            34 if self.len == 7 => {
+                self.curve_by([s[0], 0.0, s[1], s[2], s[3], 0.0])?;
+                self.curve_by([s[4], 0.0, s[5], y0 - self.point[1], s[6], 0.0])?;
+            }
+            // hflex1
+            36 if self.len == 9 => {
+                self.curve_by([s[0], s[1], s[2], s[3], s[4], 0.0])?;
+                let dy = y0 - (self.point[1] + s[7]);
+                self.curve_by([s[5], 0.0, s[6], s[7], s[8], dy])?;
+            }
+            // flex1: the last point is on the starting line of its main direction
+            37 if self.len == 11 => {
+                let dx = s[0] + s[2] + s[4] + s[6] + s[8];
+                let dy = s[1] + s[3] + s[5] + s[7] + s[9];
+                self.curve_by([s[0], s[1], s[2], s[3], s[4], s[5]])?;
+                let c1 = [self.point[0] + s[6] + s[8], self.point[1] + s[7] + s[9]];
+                let end = is![dx.abs() > dy.abs(), [c1[0] + s[10], y0], [x0, c1[1] + s[10]]];
+                self.curve_by([s[6], s[7], s[8], s[9], end[0] - c1[0], end[1] - c1[1]])?;
+            }
+            _ => return Err(self.invalid),
+        }
+        self.len = 0;
+        Ok(())
+    }
+
+    /* helpers */
+
+    fn push(&mut self, v: f32) -> Result<(), OtfError> {
+        is![self.len == Self::STACK_MAX, return Err(self.invalid)];
+        self.stack[self.len] = v;
+        self.len += 1;
+        Ok(())
+    }
+    fn pop(&mut self) -> Result<f32, OtfError> {
+        is![self.len == 0, return Err(self.invalid)];
+        self.len -= 1;
+        Ok(self.stack[self.len])
+    }
+    /// Drops the advance width before the first stack-clearing operator, if present.
+    ///
+    /// `extra` is the number of operands beyond the ones the operator expects.
+    fn width(&mut self, extra: usize) {
+        if !self.width_done && extra % 2 == 1 {
+            self.stack.copy_within(1..self.len, 0);
+            self.len -= 1;
+        }
+        self.width_done = true;
+    }
+    /// Counts the stem hints in the stack.
+    fn stem_hints(&mut self) {
+        self.width(self.len % 2);
+        self.stems += self.len / 2;
+        self.len = 0;
+    }
+    fn move_by(&mut self, dx: f32, dy: f32) -> Result<(), OtfError> {
+        self.point = [self.point[0] + dx, self.point[1] + dy];
+        self.out.move_to(self.point)
+    }
+    fn line_by(&mut self, dx: f32, dy: f32) -> Result<(), OtfError> {
+        self.point = [self.point[0] + dx, self.point[1] + dy];
+        self.out.line_to(self.point)
+    }
+    /// Draws a curve from three relative points.
+    fn curve_by(&mut self, [x1, y1, x2, y2, x3, y3]: [f32; 6]) -> Result<(), OtfError> {
+        let c0 = [self.point[0] + x1, self.point[1] + y1];
+        let c1 = [c0[0] + x2, c0[1] + y2];
+        self.point = [c1[0] + x3, c1[1] + y3];
+        self.out.cubic_to(c0, c1, self.point)
+    }
+}
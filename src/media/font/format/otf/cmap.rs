@@ -0,0 +1,122 @@
+// devela/src/media/font/format/otf/cmap.rs
+//
+//! Character to glyph mapping, from the `cmap` table.
+//
+
+use super::_helper::{bytes_from, u8_at, u16_at, u32_at};
+use crate::{FontOutlineView, OtfError, is, unwrap};
+
+/// Selects the best supported Unicode subtable of a `cmap` table.
+///
+/// Returns the subtable data, up to the end of the table, and its format.
+pub(super) const fn select(cmap: &[u8]) -> Result<(&[u8], u16), OtfError> {
+    const TAG: [u8; 4] = *b"cmap";
+    let count = unwrap![some_ok_or? u16_at(cmap, 2), OtfError::InvalidTable(TAG)] as usize;
+    let (mut best, mut best_rank) = (0, 0);
+    let mut i = 0;
+    while i < count {
+        let record = 4 + i * 8;
+        let platform = unwrap![some_ok_or? u16_at(cmap, record), OtfError::InvalidTable(TAG)];
+        let encoding = unwrap![some_ok_or? u16_at(cmap, record + 2), OtfError::InvalidTable(TAG)];
+        let offset = unwrap![some_ok_or? u32_at(cmap, record + 4), OtfError::InvalidTable(TAG)];
+        let format =
+            unwrap![some_ok_or? u16_at(cmap, offset as usize), OtfError::InvalidTable(TAG)];
+        let unicode = matches!((platform, encoding), (0, _) | (3, 1 | 10));
+        let rank = match format {
+            12 if unicode => 4,
+            4 if unicode => 3,
+            6 | 0 if unicode => 2,
+            6 | 0 if platform == 1 && encoding == 0 => 1,
+            _ => 0,
+        };
+        is![rank > best_rank, (best, best_rank) = (offset as usize, rank)];
+        i += 1;
+    }
+    is![best_rank == 0, return Err(OtfError::UnsupportedTable(TAG))];
+    let table = unwrap![some_ok_or? bytes_from(cmap, best), OtfError::InvalidTable(TAG)];
+    let format = unwrap![some_ok_or? u16_at(table, 0), OtfError::InvalidTable(TAG)];
+    let fits = match format {
+        0 => table.len() >= 6 + 256,
+        4 => matches!(u16_at(table, 6), Some(n) if table.len() >= 16 + n as usize * 4),
+        6 => matches!(u16_at(table, 8), Some(n) if table.len() >= 10 + n as usize * 2),
+        _ => {
+            matches!(u32_at(table, 12), Some(n) if table.len() >= 16 && (table.len() - 16) / 12 >= n as usize)
+        }
+    };
+    is![fits, Ok((table, format)), Err(OtfError::InvalidTable(TAG))]
+}
+
+impl FontOutlineView<'_> {
+    /// Returns the glyph id mapped to the Unicode `scalar`, or `0` if it is unmapped.
+    pub(crate) const fn cmap_glyph(&self, scalar: u32) -> u16 {
+        let glyph = match self.cmap_format {
+            0 => is![
+                scalar < 256,
+                unwrap![some_or u8_at(self.cmap, 6 + scalar as usize), 0] as u16,
+                0
+            ],
+            4 => format4(self.cmap, scalar),
+            6 => format6(self.cmap, scalar),
+            _ => format12(self.cmap, scalar),
+        };
+        is![glyph < self.glyph_count, glyph, 0]
+    }
+}
+
+/// Looks up a segment mapping to delta values subtable.
+const fn format4(t: &[u8], c: u32) -> u16 {
+    is![c > 0xFFFF, return 0];
+    let segs = unwrap![some_or u16_at(t, 6), 0] as usize / 2;
+    let (ends, starts) = (14, 16 + segs * 2);
+    let (deltas, ranges) = (starts + segs * 2, starts + segs * 4);
+    // the first segment whose end is not lower than `c`
+    let (mut low, mut high) = (0, segs);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let end = unwrap![some_or u16_at(t, ends + mid * 2), 0] as u32;
+        if end < c {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    is![low == segs, return 0];
+    let start = unwrap![some_or u16_at(t, starts + low * 2), 0xFFFF] as u32;
+    is![c < start, return 0];
+    let delta = unwrap![some_or u16_at(t, deltas + low * 2), 0];
+    let range = unwrap![some_or u16_at(t, ranges + low * 2), 0] as usize;
+    if range == 0 {
+        (c as u16).wrapping_add(delta)
+    } else {
+        let at = ranges + low * 2 + range + (c - start) as usize * 2;
+        let glyph = unwrap![some_or u16_at(t, at), 0];
+        is![glyph == 0, 0, glyph.wrapping_add(delta)]
+    }
+}
+/// Looks up a trimmed table mapping subtable.
+const fn format6(t: &[u8], c: u32) -> u16 {
+    let first = unwrap![some_or u16_at(t, 6), 0] as u32;
+    let count = unwrap![some_or u16_at(t, 8), 0] as u32;
+    is![c < first || c - first >= count, return 0];
+    unwrap![some_or u16_at(t, 10 + (c - first) as usize * 2), 0]
+}
+/// Looks up a segmented coverage subtable.
+const fn format12(t: &[u8], c: u32) -> u16 {
+    let groups = unwrap![some_or u32_at(t, 12), 0] as usize;
+    let (mut low, mut high) = (0, groups);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let group = 16 + mid * 12;
+        let start = unwrap![some_or u32_at(t, group), u32::MAX];
+        let end = unwrap![some_or u32_at(t, group + 4), 0];
+        if c < start {
+            high = mid;
+        } else if c > end {
+            low = mid + 1;
+        } else {
+            let glyph = unwrap![some_or u32_at(t, group + 8), 0] as u64 + (c - start) as u64;
+            return is![glyph > u16::MAX as u64, 0, glyph as u16];
+        }
+    }
+    0
+}
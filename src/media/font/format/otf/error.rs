@@ -0,0 +1,77 @@
+// devela/src/media/font/format/otf/error.rs
+//
+//! Defines [`OtfError`].
+//
+
+use crate::{Debug, Display, Error, FmtResult, Formatter, write};
+
+#[doc = crate::_tags!(font error_composite)]
+/// An error encountered while reading, outlining or rasterizing OpenType data.
+#[doc = crate::_doc_meta!{location("media/font")}]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OtfError {
+    /// The input ends before the complete table directory.
+    TooShort,
+    /// The input does not begin with a supported sfnt version.
+    ///
+    /// Font collections are not supported.
+    InvalidMagic,
+    /// A required table is absent.
+    MissingTable([u8; 4]),
+    /// A table lies out of bounds, or contains malformed or inconsistent data.
+    InvalidTable([u8; 4]),
+    /// A table uses a valid but unsupported feature.
+    UnsupportedTable([u8; 4]),
+    /// The glyph id is out of range, or its outline data is malformed.
+    InvalidGlyph(u16),
+    /// The path has no space left for the vertices of an outline.
+    PathCapacity,
+    /// The provided buffer has fewer elements than required.
+    InsufficientBuffer {
+        /// The number of elements required to complete the operation.
+        needed: usize,
+        /// The number of elements available in the provided buffer.
+        available: usize,
+    },
+    /// The characters to convert are not in strictly increasing order,
+    /// or none of them is mapped to a glyph.
+    InvalidCharacters,
+}
+
+impl Error for OtfError {}
+impl Display for OtfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult<()> {
+        use OtfError as E;
+        match *self {
+            E::TooShort => f.write_str("the OpenType data is shorter than its table directory"),
+            E::InvalidMagic => f.write_str("invalid or unsupported OpenType sfnt version"),
+            E::MissingTable(tag) => write!(f, "missing OpenType table '{}'", Tag(tag)),
+            E::InvalidTable(tag) => write!(f, "invalid OpenType table '{}'", Tag(tag)),
+            E::UnsupportedTable(tag) => write!(f, "unsupported OpenType table '{}'", Tag(tag)),
+            E::InvalidGlyph(id) => write!(f, "invalid OpenType glyph {id}"),
+            E::PathCapacity => f.write_str("not enough path capacity for the glyph outline"),
+            E::InsufficientBuffer { needed, available } => {
+                write!(f, "insufficient buffer: needed {needed}, available {available}")
+            }
+            E::InvalidCharacters => f.write_str("the characters are unsorted or unmapped"),
+        }
+    }
+}
+impl OtfError {
+    pub(crate) const fn insufficient_buffer(needed: usize, available: usize) -> Self {
+        Self::InsufficientBuffer { needed, available }
+    }
+}
+
+/// Displays a table tag as its ASCII characters.
+struct Tag([u8; 4]);
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult<()> {
+        for &byte in &self.0 {
+            let c = if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '?' };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+// devela/src/media/font/format/otf/glyf.rs
+//
+//! TrueType quadratic outlines, from the `glyf` and `loca` tables.
+//
+
+use super::_helper::{OutlineBuilder, bytes_at, i16_at, u8_at, u16_at, u32_at};
+use crate::{FontOutlineView, OtfError, is, unwrap};
+
+/// The maximum nesting of composite glyphs.
+const COMPOSITE_DEPTH_MAX: u8 = 8;
+
+impl<'a> FontOutlineView<'a> {
+    /// Returns the data of a TrueType glyph, which is empty if it has no outline.
+    pub(crate) const fn glyf_data(&self, glyph: u16) -> Result<&'a [u8], OtfError> {
+        is![glyph >= self.glyph_count, return Err(OtfError::InvalidGlyph(glyph))];
+        let g = glyph as usize;
+        let (start, end) = if self.long_loca {
+            (u32_at(self.loca, g * 4), u32_at(self.loca, g * 4 + 4))
+        } else {
+            let (start, end) = (u16_at(self.loca, g * 2), u16_at(self.loca, g * 2 + 2));
+            let start = unwrap![some_ok_or? start, OtfError::InvalidTable(*b"loca")];
+            let end = unwrap![some_ok_or? end, OtfError::InvalidTable(*b"loca")];
+            (Some(start as u32 * 2), Some(end as u32 * 2))
+        };
+        let start = unwrap![some_ok_or? start, OtfError::InvalidTable(*b"loca")] as usize;
+        let end = unwrap![some_ok_or? end, OtfError::InvalidTable(*b"loca")] as usize;
+        is![end < start, return Err(OtfError::InvalidTable(*b"loca"))];
+        let data = bytes_at(self.glyf, start, end - start);
+        Ok(unwrap![some_ok_or? data, OtfError::InvalidGlyph(glyph)])
+    }
+
+    /// Appends the outline of a TrueType glyph.
+    pub(super) fn glyf_outline<const N: usize>(
+        &self,
+        glyph: u16,
+        out: &mut OutlineBuilder<'_, N>,
+        depth: u8,
+    ) -> Result<(), OtfError> {
+        let data = self.glyf_data(glyph)?;
+        is![data.is_empty(), return Ok(())];
+        let contours = i16_at(data, 0).ok_or(OtfError::InvalidGlyph(glyph))?;
+        if contours >= 0 {
+            simple_outline(data, contours as usize, glyph, out)?;
+        } else {
+            is![depth == COMPOSITE_DEPTH_MAX, return Err(OtfError::InvalidGlyph(glyph))];
+            self.composite_outline(glyph, data, out, depth)?;
+        }
+        Ok(())
+    }
+
+    /// Appends the transformed outlines of the components of a composite glyph.
+    fn composite_outline<const N: usize>(
+        &self,
+        glyph: u16,
+        data: &[u8],
+        out: &mut OutlineBuilder<'_, N>,
+        depth: u8,
+    ) -> Result<(), OtfError> {
+        const WORDS: u16 = 0x0001;
+        const XY_VALUES: u16 = 0x0002;
+        const SCALE: u16 = 0x0008;
+        const MORE: u16 = 0x0020;
+        const XY_SCALE: u16 = 0x0040;
+        const TWO_BY_TWO: u16 = 0x0080;
+        let invalid = OtfError::InvalidGlyph(glyph);
+        let parent = out.transform();
+        let mut at = 10;
+        loop {
+            let flags = u16_at(data, at).ok_or(invalid)?;
+            let component = u16_at(data, at + 2).ok_or(invalid)?;
+            at += 4;
+            let (dx, dy) = if flags & WORDS != 0 {
+                at += 4;
+                (i16_at(data, at - 4).ok_or(invalid)?, i16_at(data, at - 2).ok_or(invalid)?)
+            } else {
+                at += 2;
+                let (a, b) =
+                    (u8_at(data, at - 2).ok_or(invalid)?, u8_at(data, at - 1).ok_or(invalid)?);
+                (a as i8 as i16, b as i8 as i16)
+            };
+            // matching points instead of offsets is not supported, and leaves them in place
+            let (dx, dy) = is![flags & XY_VALUES != 0, (dx as f32, dy as f32), (0.0, 0.0)];
+            let f2dot14 = |at| i16_at(data, at).map(|v| v as f32 / 16_384.0).ok_or(invalid);
+            let [xx, yx, xy, yy] = if flags & SCALE != 0 {
+                at += 2;
+                let s = f2dot14(at - 2)?;
+                [s, 0.0, 0.0, s]
+            } else if flags & XY_SCALE != 0 {
+                at += 4;
+                [f2dot14(at - 4)?, 0.0, 0.0, f2dot14(at - 2)?]
+            } else if flags & TWO_BY_TWO != 0 {
+                at += 8;
+                [f2dot14(at - 8)?, f2dot14(at - 6)?, f2dot14(at - 4)?, f2dot14(at - 2)?]
+            } else {
+                [1.0, 0.0, 0.0, 1.0]
+            };
+            let [pxx, pyx, pxy, pyy, pdx, pdy] = parent;
+            out.set_transform([
+                pxx * xx + pxy * yx,
+                pyx * xx + pyy * yx,
+                pxx * xy + pxy * yy,
+                pyx * xy + pyy * yy,
+                pxx * dx + pxy * dy + pdx,
+                pyx * dx + pyy * dy + pdy,
+            ]);
+            let result = self.glyf_outline(component, out, depth + 1);
+            out.set_transform(parent);
+            result?;
+            is![flags & MORE == 0, break];
+        }
+        Ok(())
+    }
+}
+
+/// Appends the contours of the simple `glyph` with the given `data`.
+fn simple_outline<const N: usize>(
+    data: &[u8],
+    contours: usize,
+    glyph: u16,
+    out: &mut OutlineBuilder<'_, N>,
+) -> Result<(), OtfError> {
+    is![contours == 0, return Ok(())];
+    let invalid = OtfError::InvalidGlyph(glyph);
+    let points = u16_at(data, 10 + (contours - 1) * 2).ok_or(invalid)? as usize + 1;
+    let instructions = u16_at(data, 10 + contours * 2).ok_or(invalid)? as usize;
+    let flags = 12 + contours * 2 + instructions;
+    let mut points_iter = Points::new(data, flags, points).ok_or(invalid)?;
+    let mut first = 0;
+    for c in 0..contours {
+        let end = u16_at(data, 10 + c * 2).ok_or(invalid)? as usize;
+        is![end < first || end >= points, return Err(invalid)];
+        let mut contour = Contour::default();
+        for _ in first..=end {
+            let (point, on_curve) = points_iter.next_point().ok_or(invalid)?;
+            contour.push(out, point, on_curve)?;
+        }
+        contour.close(out)?;
+        first = end + 1;
+    }
+    Ok(())
+}
+
+/// Iterates the points of a simple glyph, in font units, with their on-curve flag.
+struct Points<'a> {
+    data: &'a [u8],
+    /// The offsets of the next flag, x and y coordinates.
+    at: [usize; 3],
+    flag: u8,
+    repeat: u8,
+    point: [i32; 2],
+}
+impl<'a> Points<'a> {
+    const ON_CURVE: u8 = 0x01;
+    const X_SHORT: u8 = 0x02;
+    const Y_SHORT: u8 = 0x04;
+    const REPEAT: u8 = 0x08;
+    const X_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_SAME_OR_POSITIVE: u8 = 0x20;
+
+    /// Locates the coordinates by walking through the flags of every point.
+    fn new(data: &'a [u8], flags: usize, points: usize) -> Option<Self> {
+        let (mut at, mut x_len, mut i) = (flags, 0, 0);
+        while i < points {
+            let flag = u8_at(data, at)?;
+            at += 1;
+            let mut count = 1;
+            if flag & Self::REPEAT != 0 {
+                count += u8_at(data, at)? as usize;
+                at += 1;
+            }
+            x_len += count * Self::coord_len(flag, Self::X_SHORT, Self::X_SAME_OR_POSITIVE);
+            i += count;
+        }
+        is![i > points, return None];
+        Some(Self {
+            data,
+            at: [flags, at, at + x_len],
+            flag: 0,
+            repeat: 0,
+            point: [0; 2],
+        })
+    }
+    const fn coord_len(flag: u8, short: u8, same: u8) -> usize {
+        is![flag & short != 0, 1, is![flag & same != 0, 0, 2]]
+    }
+    /// Reads a coordinate delta, advancing its offset.
+    fn delta(&mut self, axis: usize, short: u8, same: u8) -> Option<i32> {
+        let at = self.at[axis];
+        Some(if self.flag & short != 0 {
+            self.at[axis] += 1;
+            let v = u8_at(self.data, at)? as i32;
+            is![self.flag & same != 0, v, -v]
+        } else if self.flag & same != 0 {
+            0
+        } else {
+            self.at[axis] += 2;
+            i16_at(self.data, at)? as i32
+        })
+    }
+    fn next_point(&mut self) -> Option<([f32; 2], bool)> {
+        if self.repeat > 0 {
+            self.repeat -= 1;
+        } else {
+            self.flag = u8_at(self.data, self.at[0])?;
+            self.at[0] += 1;
+            if self.flag & Self::REPEAT != 0 {
+                self.repeat = u8_at(self.data, self.at[0])?;
+                self.at[0] += 1;
+            }
+        }
+        self.point[0] += self.delta(1, Self::X_SHORT, Self::X_SAME_OR_POSITIVE)?;
+        self.point[1] += self.delta(2, Self::Y_SHORT, Self::Y_SAME_OR_POSITIVE)?;
+        let [x, y] = self.point;
+        Some(([x as f32, y as f32], self.flag & Self::ON_CURVE != 0))
+    }
+}
+
+/// Converts the points of a quadratic contour into path segments as they arrive.
+///
+/// Consecutive off-curve points imply an on-curve point at their midpoint.
+/// A contour starting off-curve starts at its first on-curve or implied point,
+/// and its first point is drawn last.
+#[derive(Default)]
+struct Contour {
+    start: Option<[f32; 2]>,
+    /// The first point, if it is off-curve.
+    first_off: Option<[f32; 2]>,
+    /// The pending control point.
+    control: Option<[f32; 2]>,
+}
+impl Contour {
+    fn push<const N: usize>(
+        &mut self,
+        out: &mut OutlineBuilder<'_, N>,
+        p: [f32; 2],
+        on_curve: bool,
+    ) -> Result<(), OtfError> {
+        is![self.start.is_some(), return self.segment(out, p, on_curve)];
+        let start = match (self.first_off, on_curve) {
+            (_, true) => p,
+            (None, false) => {
+                self.first_off = Some(p);
+                return Ok(());
+            }
+            (Some(first), false) => {
+                self.control = Some(p);
+                mid(first, p)
+            }
+        };
+        self.start = Some(start);
+        out.move_to(start)
+    }
+    fn segment<const N: usize>(
+        &mut self,
+        out: &mut OutlineBuilder<'_, N>,
+        p: [f32; 2],
+        on_curve: bool,
+    ) -> Result<(), OtfError> {
+        match (self.control, on_curve) {
+            (Some(c), true) => out.quad_to(c, p)?,
+            (None, true) => out.line_to(p)?,
+            (Some(c), false) => out.quad_to(c, mid(c, p))?,
+            (None, false) => {}
+        }
+        self.control = is![on_curve, None, Some(p)];
+        Ok(())
+    }
+    fn close<const N: usize>(mut self, out: &mut OutlineBuilder<'_, N>) -> Result<(), OtfError> {
+        // a contour of a single off-curve point is empty
+        let Some(start) = self.start else { return Ok(()) };
+        if let Some(first) = self.first_off {
+            self.segment(out, first, false)?;
+        }
+        self.segment(out, start, true)?;
+        out.close();
+        Ok(())
+    }
+}
+
+/// Returns the midpoint between `a` and `b`.
+const fn mid(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [(a[0] + b[0]) * 0.5, (a[1] + b[1]) * 0.5]
+}
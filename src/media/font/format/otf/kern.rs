@@ -0,0 +1,52 @@
+// devela/src/media/font/format/otf/kern.rs
+//
+//! Pair kerning, from the `kern` table.
+//
+
+use super::_helper::{bytes_at, i16_at, u16_at, u32_at};
+use crate::{FontOutlineView, OtfError, is, unwrap};
+
+/// Selects the pairs of the first horizontal kerning subtable in format 0.
+///
+/// Returns the sorted pair records, which are empty if there is no such subtable.
+pub(super) const fn select(kern: &[u8]) -> Result<&[u8], OtfError> {
+    const TAG: [u8; 4] = *b"kern";
+    // only the OpenType version 0 is supported, ignoring the Apple version 1
+    is![!matches!(u16_at(kern, 0), Some(0)), return Ok(&[])];
+    let count = unwrap![some_ok_or? u16_at(kern, 2), OtfError::InvalidTable(TAG)];
+    let (mut offset, mut i) = (4, 0);
+    while i < count {
+        let len = unwrap![some_ok_or? u16_at(kern, offset + 2), OtfError::InvalidTable(TAG)];
+        let coverage = unwrap![some_ok_or? u16_at(kern, offset + 4), OtfError::InvalidTable(TAG)];
+        // format 0, horizontal, not minimum values nor cross-stream
+        if coverage >> 8 == 0 && coverage & 0b111 == 0b001 {
+            let pairs = unwrap![some_ok_or? u16_at(kern, offset + 6), OtfError::InvalidTable(TAG)];
+            let pairs = bytes_at(kern, offset + 14, pairs as usize * 6);
+            return Ok(unwrap![some_ok_or? pairs, OtfError::InvalidTable(TAG)]);
+        }
+        is![len < 6, return Err(OtfError::InvalidTable(TAG))];
+        offset += len as usize;
+        i += 1;
+    }
+    Ok(&[])
+}
+
+impl FontOutlineView<'_> {
+    /// Returns the kerning between a pair of glyphs, in font units.
+    pub(crate) const fn kern_pair(&self, left: u16, right: u16) -> i16 {
+        let key = ((left as u32) << 16) | right as u32;
+        let (mut low, mut high) = (0, self.kern.len() / 6);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let pair = unwrap![some_or u32_at(self.kern, mid * 6), 0];
+            if pair < key {
+                low = mid + 1;
+            } else if pair > key {
+                high = mid;
+            } else {
+                return unwrap![some_or i16_at(self.kern, mid * 6 + 4), 0];
+            }
+        }
+        0
+    }
+}
@@ -0,0 +1,26 @@
+// devela/src/media/font/format/otf/mod.rs
+//
+//! OpenType and TrueType scalable fonts.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper;
+
+mod cff; // CFF outlines
+mod cmap; // character to glyph mapping
+mod glyf; // TrueType outlines
+mod kern; // pair kerning
+
+mod error; // OtfError
+mod namespace; // Otf
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            error::*,
+            namespace::*,
+        };
+    }
+}
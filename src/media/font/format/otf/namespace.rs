@@ -0,0 +1,174 @@
+// devela/src/media/font/format/otf/namespace.rs
+//
+//! Defines [`Otf`].
+//
+
+use super::_helper::{bytes_at, i16_at, u16_at, u32_at};
+use super::{cff, cmap, kern};
+use crate::OtfError::{self, self as E};
+use crate::{Debug, FontOutlineView, is, unwrap};
+
+/// An OpenType reading result.
+type OtfResult<T> = crate::Result<T, OtfError>;
+
+#[doc = crate::_tags!(font codec)]
+/// OpenType and TrueType font format operations and constants.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// OpenType stores scalable glyph outlines in a set of tables, indexed by
+/// a directory at the start of the font data.
+///
+/// The supported tables are:
+/// - `head`, `hhea`, `maxp`, `hmtx`: the font-wide and horizontal metrics;
+/// - `cmap`: the character to glyph mapping, from subtable formats 0, 4, 6 and 12;
+/// - `glyf`, `loca`: TrueType quadratic outlines, including composite glyphs;
+/// - `CFF `: Compact Font Format cubic outlines, excluding CID-keyed fonts;
+/// - `kern`: the first horizontal kerning pairs subtable, when present.
+///
+/// [`Otf::read`] validates the table layout before returning a borrowed
+/// [`FontOutlineView`]. It performs no allocation or copying.
+///
+/// Hinting instructions, variations, font collections and `GPOS` positioning
+/// are not supported.
+#[derive(Debug)]
+pub struct Otf;
+
+impl Otf {
+    /// The sfnt version of fonts with TrueType outlines.
+    pub const VERSION_TRUETYPE: u32 = 0x0001_0000;
+    /// The legacy Apple sfnt version of fonts with TrueType outlines.
+    pub const VERSION_APPLE: u32 = u32::from_be_bytes(*b"true");
+    /// The sfnt version of fonts with CFF outlines.
+    pub const VERSION_CFF: u32 = u32::from_be_bytes(*b"OTTO");
+
+    /// The byte length of the table directory header.
+    const DIRECTORY_BYTES: usize = 12;
+    /// The byte length of every table record.
+    const RECORD_BYTES: usize = 16;
+
+    /// Reads and validates an OpenType font from `bytes`.
+    ///
+    /// The returned view borrows its tables directly from `bytes`.
+    ///
+    /// Validation covers the sfnt version, the table directory, the required tables
+    /// and their fixed-size fields, the selected `cmap` subtable, and the glyph
+    /// location or CFF index structures. Individual glyph outlines are validated
+    /// when they are decoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OtfError`] when the font is unsupported, malformed or inconsistent.
+    pub const fn read(bytes: &[u8]) -> OtfResult<FontOutlineView<'_>> {
+        let version = unwrap![some_ok_or? u32_at(bytes, 0), E::TooShort];
+        let is_cff = match version {
+            Self::VERSION_TRUETYPE | Self::VERSION_APPLE => false,
+            Self::VERSION_CFF => true,
+            _ => return Err(E::InvalidMagic),
+        };
+        let tables = unwrap![some_ok_or? u16_at(bytes, 4), E::TooShort] as usize;
+        if bytes.len() < Self::DIRECTORY_BYTES + tables * Self::RECORD_BYTES {
+            return Err(E::TooShort);
+        }
+
+        let head = unwrap![ok? Self::required(bytes, tables, *b"head")];
+        if head.len() < 54 || !matches!(u32_at(head, 12), Some(0x5F0F_3CF5)) {
+            return Err(E::InvalidTable(*b"head"));
+        }
+        let units_per_em = unwrap![some_ok_or? u16_at(head, 18), E::InvalidTable(*b"head")];
+        let loc_format = unwrap![some_ok_or? i16_at(head, 50), E::InvalidTable(*b"head")];
+        is![
+            units_per_em == 0 || !matches!(loc_format, 0 | 1),
+            return Err(E::InvalidTable(*b"head"))
+        ];
+        let mut bounds = [0; 4];
+        let mut i = 0;
+        while i < 4 {
+            bounds[i] = unwrap![some_ok_or? i16_at(head, 36 + i * 2), E::InvalidTable(*b"head")];
+            i += 1;
+        }
+
+        let maxp = unwrap![ok? Self::required(bytes, tables, *b"maxp")];
+        let glyph_count = unwrap![some_ok_or? u16_at(maxp, 4), E::InvalidTable(*b"maxp")];
+        is![glyph_count == 0, return Err(E::InvalidTable(*b"maxp"))];
+
+        let hhea = unwrap![ok? Self::required(bytes, tables, *b"hhea")];
+        is![hhea.len() < 36, return Err(E::InvalidTable(*b"hhea"))];
+        let ascender = unwrap![some_ok_or? i16_at(hhea, 4), E::InvalidTable(*b"hhea")];
+        let descender = unwrap![some_ok_or? i16_at(hhea, 6), E::InvalidTable(*b"hhea")];
+        let line_gap = unwrap![some_ok_or? i16_at(hhea, 8), E::InvalidTable(*b"hhea")];
+        let h_metrics = unwrap![some_ok_or? u16_at(hhea, 34), E::InvalidTable(*b"hhea")];
+        is![h_metrics == 0 || h_metrics > glyph_count, return Err(E::InvalidTable(*b"hhea"))];
+
+        let hmtx = unwrap![ok? Self::required(bytes, tables, *b"hmtx")];
+        is![hmtx.len() < h_metrics as usize * 4, return Err(E::InvalidTable(*b"hmtx"))];
+
+        let cmap = unwrap![ok? Self::required(bytes, tables, *b"cmap")];
+        let (cmap, cmap_format) = unwrap![ok? cmap::select(cmap)];
+
+        let kern = match unwrap![ok? Self::table(bytes, tables, *b"kern")] {
+            Some(kern) => unwrap![ok? kern::select(kern)],
+            None => &[],
+        };
+
+        let mut view = FontOutlineView {
+            cmap,
+            cmap_format,
+            hmtx,
+            h_metrics,
+            kern,
+            glyf: &[],
+            loca: &[],
+            long_loca: loc_format == 1,
+            cff_charstrings: &[],
+            cff_subrs: &[],
+            cff_global_subrs: &[],
+            is_cff,
+            units_per_em,
+            glyph_count,
+            ascender,
+            descender,
+            line_gap,
+            bounds,
+        };
+        if is_cff {
+            let cff = unwrap![ok? Self::required(bytes, tables, *b"CFF ")];
+            let tables = unwrap![ok? cff::read(cff, glyph_count)];
+            (view.cff_charstrings, view.cff_subrs, view.cff_global_subrs) = tables;
+        } else {
+            view.glyf = unwrap![ok? Self::required(bytes, tables, *b"glyf")];
+            view.loca = unwrap![ok? Self::required(bytes, tables, *b"loca")];
+            let entry = is![view.long_loca, 4, 2];
+            if view.loca.len() < (glyph_count as usize + 1) * entry {
+                return Err(E::InvalidTable(*b"loca"));
+            }
+        }
+        Ok(view)
+    }
+
+    /* helpers */
+
+    /// Returns the table with the given `tag`, or `None` if it is absent.
+    const fn table(bytes: &[u8], tables: usize, tag: [u8; 4]) -> OtfResult<Option<&[u8]>> {
+        let wanted = u32::from_be_bytes(tag);
+        let mut i = 0;
+        while i < tables {
+            let record = Self::DIRECTORY_BYTES + i * Self::RECORD_BYTES;
+            if matches!(u32_at(bytes, record), Some(t) if t == wanted) {
+                let offset = unwrap![some_ok_or? u32_at(bytes, record + 8), E::TooShort];
+                let len = unwrap![some_ok_or? u32_at(bytes, record + 12), E::TooShort];
+                let table = bytes_at(bytes, offset as usize, len as usize);
+                return Ok(Some(unwrap![some_ok_or? table, E::InvalidTable(tag)]));
+            }
+            i += 1;
+        }
+        Ok(None)
+    }
+    /// Returns the table with the given `tag`, or an error if it is absent.
+    const fn required(bytes: &[u8], tables: usize, tag: [u8; 4]) -> OtfResult<&[u8]> {
+        match Self::table(bytes, tables, tag) {
+            Ok(Some(table)) => Ok(table),
+            Ok(None) => Err(E::MissingTable(tag)),
+            Err(e) => Err(e),
+        }
+    }
+}
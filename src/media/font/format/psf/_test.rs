@@ -0,0 +1,124 @@
+// devela/src/media/font/format/psf/_test.rs
+
+use crate::{Dvbf, Fonts, Psf, PsfError};
+
+/// Returns a PSF1 font of 2-pixel-high glyphs, followed by `table`,
+/// with each glyph drawn with the low and high bytes of its index.
+fn psf1(mode: u8, table: &[u16]) -> ([u8; 2048], usize) {
+    let mut buf = [0; 2048];
+    buf[..4].copy_from_slice(&[0x36, 0x04, mode, 2]);
+    let count = if mode & 1 != 0 { 512 } else { 256 };
+    for index in 0..count {
+        let [lo, hi] = (index as u16).to_le_bytes();
+        buf[4 + index * 2..][..2].copy_from_slice(&[lo, hi]);
+    }
+    let mut at = 4 + count * 2;
+    for value in table {
+        buf[at..at + 2].copy_from_slice(&value.to_le_bytes());
+        at += 2;
+    }
+    (buf, at)
+}
+/// Returns a PSF1 table mapping `A` and `Α` to the glyph of `A`, and
+/// the sequence `é` to the glyph of `B`.
+fn psf1_table() -> [u16; 261] {
+    let mut table = [0xFFFF; 261];
+    table[0x41..0x44].copy_from_slice(&[0x41, 0x391, 0xFFFF]);
+    table[0x44..0x48].copy_from_slice(&[0xFFFE, 0x65, 0x301, 0xFFFF]);
+    table
+}
+
+#[test]
+fn reads_psf1() {
+    let (buf, len) = psf1(0x02, &psf1_table());
+    let font = Psf::read(&buf[..len]).unwrap();
+    assert_eq!((font.version(), font.glyph_count()), (1, 256));
+    assert_eq!((font.width(), font.height(), font.row_stride()), (8, 2, 1));
+    assert!(font.has_unicode_table());
+    assert_eq!(font.glyph_bitmap(0x41), Some(&[0x41, 0][..]));
+    assert_eq!(font.glyph_bitmap(256), None);
+    assert_eq!(font.glyph_index('A'), Some(0x41));
+    assert_eq!(font.glyph_index('\u{391}'), Some(0x41));
+    // sequences aren't mapped to single characters
+    assert_eq!(font.glyph_index('e'), None);
+    let mut mappings = font.mappings();
+    assert_eq!(mappings.next(), Some(('A', 0x41)));
+    assert_eq!(mappings.next(), Some(('\u{391}', 0x41)));
+    assert_eq!(mappings.next(), None);
+}
+#[test]
+fn reads_psf1_without_table() {
+    let (buf, len) = psf1(0x01, &[]);
+    let font = Psf::read(&buf[..len]).unwrap();
+    assert_eq!(font.glyph_count(), 512);
+    assert!(!font.has_unicode_table());
+    assert_eq!(font.glyph_index('\u{1FF}'), Some(511));
+    assert_eq!(font.glyph_index('\u{200}'), None);
+    assert_eq!(font.mappings().count(), 512);
+    assert_eq!(font.glyph_bitmap(0x1FF), Some(&[0xFF, 1][..]));
+}
+#[test]
+fn round_trips_through_psf2() {
+    const REGULAR: &[u8] =
+        include_bytes!("../../../../../assets/font/termivela/termivela-8x16-regular.dvbf");
+    let mut psf = [0; 32 * 1024];
+    let len = Psf::write(&Fonts::TERMIVELA_8_16, &[], &mut psf).unwrap();
+    let font = Psf::read(&psf[..len]).unwrap();
+    assert_eq!((font.version(), font.glyph_count()), (2, 1356));
+    assert_eq!(font.glyph_bitmap(font.glyph_index('A').unwrap()).map(<[u8]>::len), Some(16));
+    // the PSF cell gets the same metrics as Termivela
+    let mut dvbf = [0; REGULAR.len()];
+    assert_eq!(Psf::to_dvbf(&font, &[], &mut dvbf), Ok(REGULAR.len()));
+    assert_eq!(dvbf, REGULAR);
+}
+#[test]
+fn converts_subsets() {
+    let (buf, len) = psf1(0x02, &psf1_table());
+    let font = Psf::read(&buf[..len]).unwrap();
+    let mut dvbf = [0; 128];
+    let len = Psf::to_dvbf(&font, &['\u{300}'..='\u{3FF}'], &mut dvbf).unwrap();
+    let subset = Dvbf::read(&dvbf[..len]).unwrap();
+    assert_eq!(subset.glyph_count(), 1);
+    assert_eq!(subset.glyph('\u{391}').unwrap().bitmap(), [0x41, 0]);
+    assert_eq!((subset.ascent(), subset.descent(), subset.bounds().y()), (2, 0, 0));
+    assert_eq!(Psf::to_dvbf(&font, &['a'..='z'], &mut dvbf), Err(PsfError::NoGlyphs));
+
+    let mut psf = [0; 512];
+    let len = Psf::write(&Fonts::TERMIVELA_8_16, &['a'..='z'], &mut psf).unwrap();
+    assert_eq!(len, 32 + 26 * (16 + 2));
+    let font = Psf::read(&psf[..len]).unwrap();
+    assert_eq!(font.glyph_index('z'), Some(25));
+    assert_eq!(font.glyph_bitmap(25), Fonts::TERMIVELA_8_16.glyph('z').map(|g| g.bitmap()));
+}
+#[test]
+fn rejects_invalid_fonts() {
+    assert_eq!(Psf::read(b"\x36\x04\x00"), Err(PsfError::TooShort));
+    assert_eq!(Psf::read(b"PSF2"), Err(PsfError::InvalidMagic));
+    let (mut buf, len) = psf1(0x02, &psf1_table());
+    assert_eq!(Psf::read(&buf[..len - 2]), Err(PsfError::InvalidUnicodeTable));
+    assert_eq!(Psf::read(&buf[..300]), Err(PsfError::TooShort));
+    buf[2] = 0x08;
+    assert_eq!(Psf::read(&buf[..len]), Err(PsfError::InvalidHeader));
+
+    let mut psf = [0; 512];
+    let len = Psf::write(&Fonts::TERMIVELA_8_16, &['a'..='a'], &mut psf).unwrap();
+    psf[20..24].copy_from_slice(&15_u32.to_le_bytes());
+    assert_eq!(Psf::read(&psf[..len]), Err(PsfError::InvalidHeader));
+    psf[4] = 1;
+    assert_eq!(Psf::read(&psf[..len]), Err(PsfError::UnsupportedVersion(1)));
+    psf[4] = 0;
+    psf[len - 2] = 0xC0;
+    psf[20..24].copy_from_slice(&16_u32.to_le_bytes());
+    assert_eq!(Psf::read(&psf[..len]), Err(PsfError::InvalidUnicodeTable));
+}
+#[test]
+fn rejects_writing_no_glyphs_or_short_buffers() {
+    let font = Fonts::TERMIVELA_8_16;
+    let mut buf = [0; 64];
+    assert_eq!(
+        Psf::write(&font, &['\u{10FFF0}'..='\u{10FFFF}'], &mut buf),
+        Err(PsfError::NoGlyphs)
+    );
+    let error = Psf::write(&font, &['a'..='b'], &mut buf);
+    assert_eq!(error, Err(PsfError::InsufficientBuffer { needed: 68, available: 64 }));
+}
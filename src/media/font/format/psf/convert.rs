@@ -0,0 +1,72 @@
+// devela/src/media/font/format/psf/convert.rs
+//
+//! Implements [`Psf::to_dvbf`].
+//
+
+use crate::{DvbfError, DvbfMetrics, DvbfWriter, Psf, PsfError, PsfView, in_char_ranges};
+use ::core::ops::RangeInclusive;
+
+impl Psf {
+    /// Converts the glyphs of a PSF font mapped within `ranges` into a DVBF strike
+    /// in `buf`, returning the number of bytes written.
+    ///
+    /// An empty `ranges` keeps every mapped glyph. A glyph mapped to several
+    /// characters is stored once for each of them.
+    ///
+    /// Since PSF has no baseline, the bottom quarter of the cell is taken as the descent.
+    /// The replacement character `U+FFFD` becomes the default, when it's kept.
+    ///
+    /// The result can be read back with [`Dvbf::read`][crate::Dvbf::read].
+    ///
+    /// # Errors
+    /// Returns [`PsfError::NoGlyphs`] if no glyph is mapped within `ranges`,
+    /// [`PsfError::UnsupportedMetrics`] if the glyphs are too large for a DVBF strike,
+    /// or [`PsfError::InsufficientBuffer`] if `buf` is too short.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Dvbf, Fonts, Psf};
+    /// let font = Fonts::TERMIVELA_8_16;
+    /// let mut psf = [0; 8192];
+    /// let len = Psf::write(&font, &['0'..='9'], &mut psf).unwrap();
+    /// let psf = Psf::read(&psf[..len]).unwrap();
+    /// let mut dvbf = [0; 1024];
+    /// let len = Psf::to_dvbf(&psf, &['5'..='6'], &mut dvbf).unwrap();
+    /// let digits = Dvbf::read(&dvbf[..len]).unwrap();
+    /// assert_eq![digits.glyph_count(), 2];
+    /// assert_eq![digits.glyph('6'), font.glyph('6')];
+    /// ```
+    pub fn to_dvbf(
+        font: &PsfView,
+        ranges: &[RangeInclusive<char>],
+        buf: &mut [u8],
+    ) -> Result<usize, PsfError> {
+        let kept = font.mappings().filter(|&(c, _)| in_char_ranges(c, ranges));
+        let small = |v: u32| u16::try_from(v).map_err(|_| PsfError::UnsupportedMetrics);
+        let (width, height) = (small(font.width())?, small(font.height())?);
+        let descent = height / 4;
+        let metrics = DvbfMetrics {
+            width,
+            height,
+            bounds_x: 0,
+            bounds_y: -i16::try_from(descent).map_err(|_| PsfError::UnsupportedMetrics)?,
+            advance_x: width,
+            line_advance: height,
+            ascent: height - descent,
+            descent,
+        };
+        let mut writer =
+            DvbfWriter::new(buf, metrics, kept.clone().count()).map_err(|e| match e {
+                DvbfError::NoGlyphs => PsfError::NoGlyphs,
+                DvbfError::InsufficientBuffer { needed, available } => {
+                    PsfError::insufficient_buffer(needed, available)
+                }
+                _ => PsfError::UnsupportedMetrics,
+            })?;
+        for (c, index) in kept {
+            let bitmap = font.glyph_bitmap(index);
+            writer.push(c).copy_from_slice(bitmap.unwrap_or_default());
+        }
+        writer.finish(Some('\u{FFFD}')).map_err(|_| PsfError::UnsupportedMetrics)
+    }
+}
@@ -0,0 +1,58 @@
+// devela/src/media/font/format/psf/error.rs
+//
+//! Defines [`PsfError`].
+//
+
+use crate::{Debug, Display, Error, FmtResult, Formatter, write};
+
+#[doc = crate::_tags!(font error_composite)]
+/// An error encountered while reading or writing PSF data.
+#[doc = crate::_doc_meta!{location("media/font")}]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PsfError {
+    /// The input ends before the complete header or glyph bitmaps.
+    TooShort,
+    /// The input does not begin with a PSF1 or PSF2 magic number.
+    InvalidMagic,
+    /// The PSF2 version is not supported.
+    UnsupportedVersion(u32),
+    /// The header declares inconsistent sizes, or unsupported flags or modes.
+    InvalidHeader,
+    /// The Unicode table is truncated, or contains invalid scalars.
+    InvalidUnicodeTable,
+    /// The font dimensions can't be represented in the target format.
+    UnsupportedMetrics,
+    /// There are no glyphs to write.
+    NoGlyphs,
+    /// The output buffer is too short for the encoded font.
+    InsufficientBuffer {
+        /// The number of bytes needed.
+        needed: usize,
+        /// The number of bytes available.
+        available: usize,
+    },
+}
+impl Error for PsfError {}
+impl Display for PsfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult<()> {
+        use PsfError as E;
+        match *self {
+            E::TooShort => f.write_str("the PSF data is shorter than declared"),
+            E::InvalidMagic => f.write_str("invalid PSF magic"),
+            E::UnsupportedVersion(v) => write!(f, "unsupported PSF2 version {v}"),
+            E::InvalidHeader => f.write_str("invalid PSF header"),
+            E::InvalidUnicodeTable => f.write_str("invalid PSF unicode table"),
+            E::UnsupportedMetrics => f.write_str("unsupported PSF font metrics"),
+            E::NoGlyphs => f.write_str("no PSF glyphs to write"),
+            E::InsufficientBuffer { needed, available } => {
+                write!(f, "insufficient PSF buffer: needed {needed} bytes, available {available}")
+            }
+        }
+    }
+}
+impl PsfError {
+    pub(crate) const fn insufficient_buffer(needed: usize, available: usize) -> Self {
+        Self::InsufficientBuffer { needed, available }
+    }
+}
@@ -0,0 +1,23 @@
+// devela/src/media/font/format/psf/mod.rs
+//
+//! Linux console PC Screen Font.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod convert; // Psf::to_dvbf
+mod error; // PsfError
+mod namespace; // Psf
+mod view; // PsfView, PsfMappings
+mod write; // Psf::write
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            error::*,
+            namespace::*,
+            view::*,
+        };
+    }
+}
@@ -0,0 +1,108 @@
+// devela/src/media/font/format/psf/namespace.rs
+//
+//! Defines [`Psf`].
+//
+
+use crate::PsfError::{self, self as E};
+use crate::{Debug, PsfView, is};
+
+/// A PSF reading result.
+type PsfResult<T> = crate::Result<T, PsfError>;
+
+#[doc = crate::_tags!(font codec)]
+/// PC Screen Font format operations and constants.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// PSF stores the fixed-size monochrome glyphs of Linux console fonts,
+/// optionally followed by a table that maps Unicode scalars to glyphs.
+///
+/// Both versions are supported:
+/// - PSF1 fonts have 256 or 512 glyphs, 8 pixels wide,
+///   and a table of UCS-2 values;
+/// - PSF2 fonts have any number of glyphs of any size,
+///   and a table of UTF-8 sequences.
+///
+/// [`Psf::read`] validates the complete input before returning a borrowed
+/// [`PsfView`]. It performs no allocation or copying.
+///
+/// Glyphs mapped to sequences of several scalars are kept in the table,
+/// but are not reachable by single characters.
+#[derive(Debug)]
+pub struct Psf;
+
+impl Psf {
+    /// The two-byte PSF1 signature.
+    pub const MAGIC_1: [u8; 2] = [0x36, 0x04];
+    /// The four-byte PSF2 signature.
+    pub const MAGIC_2: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+
+    /// The byte length of a PSF1 header.
+    pub const HEADER_BYTES_1: usize = 4;
+    /// The byte length of a PSF2 header.
+    pub const HEADER_BYTES_2: usize = 32;
+
+    /// The PSF1 mode bit for fonts of 512 glyphs.
+    pub(crate) const MODE_512: u8 = 0x01;
+    /// The PSF1 mode bits for fonts with a Unicode table.
+    pub(crate) const MODE_TABLE: u8 = 0x02 | 0x04;
+    /// The PSF2 flag bit for fonts with a Unicode table.
+    pub(crate) const FLAG_TABLE: u32 = 0x01;
+
+    /// Reads and validates a PSF1 or PSF2 font from `bytes`.
+    ///
+    /// The returned view borrows its glyph bitmaps and Unicode table directly from `bytes`.
+    ///
+    /// Validation covers the signature, the header fields, the length of the bitmaps,
+    /// and the complete Unicode table, when present.
+    ///
+    /// # Errors
+    /// Returns [`PsfError`] when the font is unsupported, malformed or truncated.
+    pub fn read(bytes: &[u8]) -> PsfResult<PsfView<'_>> {
+        let u32_at = |at: usize| {
+            u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+        let (version, width, height, count, start, has_table) = if bytes.starts_with(&Self::MAGIC_1)
+        {
+            is![bytes.len() < Self::HEADER_BYTES_1, return Err(E::TooShort)];
+            let (mode, height) = (bytes[2], bytes[3]);
+            is![
+                mode & !(Self::MODE_512 | Self::MODE_TABLE) != 0 || height == 0,
+                return Err(E::InvalidHeader)
+            ];
+            let count = is![mode & Self::MODE_512 != 0, 512, 256];
+            (1, 8, u32::from(height), count, Self::HEADER_BYTES_1, mode & Self::MODE_TABLE != 0)
+        } else if bytes.starts_with(&Self::MAGIC_2) {
+            is![bytes.len() < Self::HEADER_BYTES_2, return Err(E::TooShort)];
+            let version = u32_at(4);
+            is![version != 0, return Err(E::UnsupportedVersion(version))];
+            let [header, flags, count, glyph_bytes, height, width] =
+                [8, 12, 16, 20, 24, 28].map(u32_at);
+            if flags & !Self::FLAG_TABLE != 0
+                || (header as usize) < Self::HEADER_BYTES_2
+                || count == 0
+                || width == 0
+                || height == 0
+                || u64::from(glyph_bytes) != u64::from(width.div_ceil(8)) * u64::from(height)
+            {
+                return Err(E::InvalidHeader);
+            }
+            (2, width, height, count, header as usize, flags & Self::FLAG_TABLE != 0)
+        } else {
+            return Err(E::InvalidMagic);
+        };
+        let glyph_bytes = width.div_ceil(8) as usize * height as usize;
+        let end = (count as usize).checked_mul(glyph_bytes).and_then(|n| n.checked_add(start));
+        let end = end.filter(|&end| end <= bytes.len()).ok_or(E::TooShort)?;
+        let table = is![has_table, Some(&bytes[end..]), None];
+        let font = PsfView {
+            version,
+            width,
+            height,
+            count,
+            glyphs: &bytes[start..end],
+            table,
+        };
+        font.validate_table()?;
+        Ok(font)
+    }
+}
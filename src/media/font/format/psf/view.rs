@@ -0,0 +1,165 @@
+// devela/src/media/font/format/psf/view.rs
+//
+//! Defines [`PsfView`] and [`PsfMappings`].
+//
+
+use crate::{Debug, IteratorFused, PsfError, is};
+
+#[doc = crate::_tags!(font)]
+/// A validated borrowed view over a PSF1 or PSF2 font.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// Created by [`Psf::read`][crate::Psf::read].
+///
+/// Glyph rows run from top to bottom. Pixels within each row run from left to right,
+/// beginning at the most-significant bit of each byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PsfView<'a> {
+    /// The format version, 1 or 2.
+    pub(crate) version: u8,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) count: u32,
+    /// The bitmaps of every glyph.
+    pub(crate) glyphs: &'a [u8],
+    /// The Unicode table, and any trailing data.
+    pub(crate) table: Option<&'a [u8]>,
+}
+
+#[rustfmt::skip]
+impl<'a> PsfView<'a> {
+    /// Returns the format version, 1 or 2.
+    pub const fn version(&self) -> u8 { self.version }
+    /// Returns the number of glyphs.
+    pub const fn glyph_count(&self) -> usize { self.count as usize }
+    /// Returns the width of every glyph, in pixels.
+    pub const fn width(&self) -> u32 { self.width }
+    /// Returns the height of every glyph, in pixels.
+    pub const fn height(&self) -> u32 { self.height }
+    /// Returns the number of bytes of each bitmap row.
+    pub const fn row_stride(&self) -> usize { self.width.div_ceil(8) as usize }
+    /// Returns whether the font maps Unicode scalars to its glyphs.
+    ///
+    /// Otherwise, each glyph stands for the scalar equal to its index.
+    pub const fn has_unicode_table(&self) -> bool { self.table.is_some() }
+
+    /// Returns the bitmap of the glyph at `index`,
+    /// with a length of `row_stride × height`.
+    pub fn glyph_bitmap(&self, index: usize) -> Option<&'a [u8]> {
+        let len = self.row_stride() * self.height as usize;
+        self.glyphs.get(index.checked_mul(len)?..)?.get(..len)
+    }
+    /// Returns the index of the glyph for `c`.
+    ///
+    /// The lookup performs a linear search over the Unicode table.
+    pub fn glyph_index(&self, c: char) -> Option<usize> {
+        if self.table.is_none() {
+            return Some(c as usize).filter(|&index| index < self.glyph_count());
+        }
+        self.mappings().find_map(|(m, index)| (m == c).then_some(index))
+    }
+    /// Returns an iterator over the characters mapped to each glyph index.
+    ///
+    /// Without a Unicode table, every glyph index is mapped to the scalar of
+    /// the same value.
+    pub const fn mappings(&self) -> PsfMappings<'a> {
+        PsfMappings { font: *self, at: 0, glyph: 0, sequence: false }
+    }
+
+    /// Parses the whole Unicode table, if present.
+    pub(crate) fn validate_table(&self) -> Result<(), PsfError> {
+        is![self.table.is_none(), return Ok(())];
+        let mut mappings = self.mappings();
+        while mappings.next_mapping()?.is_some() {}
+        Ok(())
+    }
+}
+
+#[doc = crate::_tags!(font iterator)]
+/// An iterator over the characters mapped to the glyphs of a [`PsfView`].
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// Yields each character with its glyph index, in table order.
+/// Sequences of several scalars are skipped.
+///
+/// Created by [`PsfView::mappings`].
+#[derive(Clone, Debug)]
+pub struct PsfMappings<'a> {
+    font: PsfView<'a>,
+    /// The byte offset into the table, or the next scalar without one.
+    at: usize,
+    /// The index of the glyph being read.
+    glyph: u32,
+    /// Whether the entries being read belong to a sequence.
+    sequence: bool,
+}
+impl PsfMappings<'_> {
+    /// Returns the next mapped character and glyph index.
+    fn next_mapping(&mut self) -> Result<Option<(char, usize)>, PsfError> {
+        let Some(table) = self.font.table else {
+            while (self.at as u32) < self.font.count {
+                let scalar = self.at as u32;
+                self.at += 1;
+                if let Some(c) = char::from_u32(scalar) {
+                    return Ok(Some((c, scalar as usize)));
+                }
+            }
+            return Ok(None);
+        };
+        while self.glyph < self.font.count {
+            let bytes = table.get(self.at..).unwrap_or_default();
+            let (entry, len) = Entry::read(self.font.version, bytes)?;
+            self.at += len;
+            match entry {
+                Entry::End => (self.glyph, self.sequence) = (self.glyph + 1, false),
+                Entry::Sequence => self.sequence = true,
+                Entry::Scalar(c) if !self.sequence => return Ok(Some((c, self.glyph as usize))),
+                Entry::Scalar(_) => {}
+            }
+        }
+        Ok(None)
+    }
+}
+impl Iterator for PsfMappings<'_> {
+    type Item = (char, usize);
+    fn next(&mut self) -> Option<(char, usize)> {
+        self.next_mapping().ok().flatten()
+    }
+}
+impl IteratorFused for PsfMappings<'_> {}
+
+/// An entry of the Unicode table.
+enum Entry {
+    /// The end of the entries of a glyph.
+    End,
+    /// The start of a sequence of several scalars.
+    Sequence,
+    /// A single scalar, or a part of a sequence.
+    Scalar(char),
+}
+impl Entry {
+    /// Reads the entry at the start of `bytes`, and returns it with its byte length.
+    fn read(version: u8, bytes: &[u8]) -> Result<(Self, usize), PsfError> {
+        use {Entry as N, PsfError::InvalidUnicodeTable as E};
+        if version == 1 {
+            let [lo, hi, ..] = *bytes else { return Err(E) };
+            let entry = match u16::from_le_bytes([lo, hi]) {
+                0xFFFF => N::End,
+                0xFFFE => N::Sequence,
+                v => N::Scalar(char::from_u32(v.into()).ok_or(E)?),
+            };
+            return Ok((entry, 2));
+        }
+        let len = match *bytes.first().ok_or(E)? {
+            0xFF => return Ok((N::End, 1)),
+            0xFE => return Ok((N::Sequence, 1)),
+            0x00..=0x7F => 1,
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return Err(E),
+        };
+        let text = bytes.get(..len).and_then(|b| ::core::str::from_utf8(b).ok()).ok_or(E)?;
+        Ok((N::Scalar(text.chars().next().ok_or(E)?), len))
+    }
+}
@@ -0,0 +1,67 @@
+// devela/src/media/font/format/psf/write.rs
+//
+//! Implements [`Psf::write`].
+//
+
+use crate::{FontBitmapView, Psf, PsfError, in_char_ranges, is};
+use ::core::ops::RangeInclusive;
+
+impl Psf {
+    /// Writes the glyphs of `font` within `ranges` as a PSF2 font into `buf`,
+    /// returning the number of bytes written.
+    ///
+    /// An empty `ranges` keeps every glyph. The glyphs are written in scalar order,
+    /// followed by a Unicode table that maps each one to its character.
+    ///
+    /// # Errors
+    /// Returns [`PsfError::NoGlyphs`] if no glyph is within `ranges`,
+    /// or [`PsfError::InsufficientBuffer`] if `buf` is too short.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Fonts, Psf};
+    /// let mut buf = [0; 8192];
+    /// let len = Psf::write(&Fonts::TERMIVELA_8_16, &['a'..='z'], &mut buf).unwrap();
+    /// let font = Psf::read(&buf[..len]).unwrap();
+    /// assert_eq![(font.version(), font.glyph_count()), (2, 26)];
+    /// assert_eq![font.glyph_index('c'), Some(2)];
+    /// ```
+    pub fn write(
+        font: &FontBitmapView,
+        ranges: &[RangeInclusive<char>],
+        buf: &mut [u8],
+    ) -> Result<usize, PsfError> {
+        let kept = (0..font.glyph_count()).filter_map(|i| font.glyph_at(i));
+        let kept = kept.filter(|glyph| in_char_ranges(glyph.character(), ranges));
+        let (count, table) = kept.clone().fold((0, 0), |(count, table), glyph| {
+            (count + 1, table + glyph.character().len_utf8() + 1)
+        });
+        is![count == 0, return Err(PsfError::NoGlyphs)];
+        let glyph_bytes = font.glyph_bitmap_len();
+        let glyphs = Self::HEADER_BYTES_2 + count * glyph_bytes;
+        let needed = glyphs + table;
+        is![buf.len() < needed, return Err(PsfError::insufficient_buffer(needed, buf.len()))];
+
+        let header = [
+            0,
+            Self::HEADER_BYTES_2 as u32,
+            Self::FLAG_TABLE,
+            count as u32,
+            glyph_bytes as u32,
+            u32::from(font.height()),
+            u32::from(font.width()),
+        ];
+        buf[..4].copy_from_slice(&Self::MAGIC_2);
+        for (field, value) in buf[4..Self::HEADER_BYTES_2].chunks_exact_mut(4).zip(header) {
+            field.copy_from_slice(&value.to_le_bytes());
+        }
+        let (mut bitmap, mut entry) = (Self::HEADER_BYTES_2, glyphs);
+        for glyph in kept {
+            buf[bitmap..bitmap + glyph_bytes].copy_from_slice(glyph.bitmap());
+            let c = glyph.character().encode_utf8(&mut buf[entry..]).len();
+            buf[entry + c] = 0xFF;
+            (bitmap, entry) = (bitmap + glyph_bytes, entry + c + 1);
+        }
+        Ok(needed)
+    }
+}
@@ -16,10 +16,12 @@ mod format; // Font storage and interchange formats
 // mod inspect; // Descriptive inspection and optional quality policies
 // mod metric; // Objective dimensions and placement
 mod namespace; // Fonts
-// mod outline; // Contour-based glyph representation
+mod outline; // Contour-based glyph representation
 // mod semantic; // Format-independent typographic meaning
+#[cfg(feature = "grapheme")]
+mod shape; // Text shaping into positioned glyph runs
 
-crate::structural_mods! { // _mods
+crate::structural_mods! { // _mods, _crate_internals
     _mods {
         pub use super::{
             art::_all::*,
@@ -30,8 +32,13 @@ crate::structural_mods! { // _mods
             // inspect::_all::*,
             // metric::_all::*,
             namespace::*,
-            // outline::_all::*,
+            outline::_all::*,
             // semantic::_all::*,
         };
+        #[cfg(feature = "grapheme")]
+        pub use super::shape::_all::*;
+    }
+    _crate_internals {
+        pub(crate) use super::format::_crate_internals::*;
     }
 }
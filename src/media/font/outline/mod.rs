@@ -0,0 +1,16 @@
+// devela/src/media/font/outline/mod.rs
+//
+//! Contour-based glyph representation.
+//
+
+mod raster; // GlyphRasterizer, GlyphMask
+mod view; // FontOutlineView
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            raster::*,
+            view::*,
+        };
+    }
+}
@@ -0,0 +1,142 @@
+// devela/src/media/font/outline/raster.rs
+//
+//! Defines [`GlyphRasterizer`] and [`GlyphMask`].
+//
+
+use crate::{Coverage8, Float, FontOutlineView, OtfError, Position2, RasterFillRule};
+use crate::{RasterGrid, RasterPath, Region2, Slice, ext, is};
+
+#[doc = crate::_tags!(font image)]
+/// Rasterizes outline glyphs into anti-aliased coverage masks of any size.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// It owns a [`RasterPath`] of up to `N` vertices, where glyph outlines are flattened,
+/// and borrows the buffers that the masks are rasterized into:
+/// - `scratch`, which needs two more elements than the mask width;
+/// - `coverage`, which needs the mask width times its height.
+///
+/// [`bounds`][Self::bounds] returns the mask size of a glyph before rasterizing it.
+///
+/// Glyphs are filled with the [non-zero][RasterFillRule::NonZero] rule,
+/// as both TrueType and CFF outlines expect.
+#[derive(Debug)]
+pub struct GlyphRasterizer<'b, const N: usize> {
+    path: RasterPath<N>,
+    scratch: &'b mut [f32],
+    coverage: &'b mut [Coverage8],
+}
+
+impl<'b, const N: usize> GlyphRasterizer<'b, N> {
+    /// Returns a rasterizer using the given buffers.
+    pub const fn new(scratch: &'b mut [f32], coverage: &'b mut [Coverage8]) -> Self {
+        Self { path: RasterPath::new(), scratch, coverage }
+    }
+    /// Returns the path holding the last flattened outline.
+    #[must_use]
+    pub const fn path(&self) -> &RasterPath<N> {
+        &self.path
+    }
+
+    /// Returns the pixel region covered by the mask of `glyph` at `size` pixels per em.
+    ///
+    /// The region is relative to the glyph origin on the baseline, with y growing
+    /// downwards, so its position is usually negative. It is empty for empty glyphs.
+    ///
+    /// # Errors
+    /// Returns [`OtfError::InvalidGlyph`] for invalid glyphs,
+    /// or [`OtfError::PathCapacity`] if the outline doesn't fit the path.
+    pub fn bounds(
+        &mut self,
+        font: &FontOutlineView,
+        glyph: u16,
+        size: f32,
+    ) -> Result<Region2<i32, u32>, OtfError> {
+        self.path.clear();
+        font.glyph_path(glyph, size, Position2::new([0.0; 2]), &mut self.path)?;
+        let Some([min, max]) = self.path.bounds() else {
+            return Ok(Region2::from_xy_wh(0, 0, 0, 0));
+        };
+        let floor = |v: f32| Float(v).const_floor().0 as i32;
+        let ceil = |v: f32| Float(v).const_ceil().0 as i32;
+        let (left, top) = (floor(min.dim[0]), floor(min.dim[1]));
+        let (right, bottom) = (ceil(max.dim[0]), ceil(max.dim[1]));
+        Ok(Region2::from_xy_wh(left, top, right.abs_diff(left), bottom.abs_diff(top)))
+    }
+
+    /// Rasterizes `glyph` at `size` pixels per em, returning its coverage mask.
+    ///
+    /// # Errors
+    /// Returns the errors of [`bounds`][Self::bounds], or [`OtfError::InsufficientBuffer`]
+    /// if the `scratch` or the `coverage` buffers are too short for the mask.
+    pub fn rasterize(
+        &mut self,
+        font: &FontOutlineView,
+        glyph: u16,
+        size: f32,
+    ) -> Result<GlyphMask<'_>, OtfError> {
+        let bounds = self.bounds(font, glyph, size)?;
+        let (w, h) = (bounds.w() as usize, bounds.h() as usize);
+        let overflow = OtfError::insufficient_buffer(usize::MAX, self.coverage.len());
+        let len = w.checked_mul(h).ok_or(overflow)?;
+        if self.coverage.len() < len {
+            return Err(OtfError::insufficient_buffer(len, self.coverage.len()));
+        }
+        let scratch_len = w + 2;
+        if self.scratch.len() < scratch_len {
+            return Err(OtfError::insufficient_buffer(scratch_len, self.scratch.len()));
+        }
+        let coverage = &mut self.coverage[..len];
+        coverage.fill(Coverage8::ZERO);
+        if len > 0 {
+            self.path.clear();
+            let origin = Position2::new([-bounds.x() as f32, -bounds.y() as f32]);
+            font.glyph_path(glyph, size, origin, &mut self.path)?;
+            let grid = RasterGrid::try_new(ext![w, h]).map_err(|_| overflow)?;
+            let spans = self.path.fill(grid, RasterFillRule::NonZero, self.scratch);
+            for span in spans.map_err(|_| OtfError::insufficient_buffer(scratch_len, 0))? {
+                let [x, y] = span.start().dim;
+                coverage[y * w + x..][..span.len()].fill(span.coverage());
+            }
+        }
+        Ok(GlyphMask { bounds, coverage: &self.coverage[..len] })
+    }
+}
+
+#[doc = crate::_tags!(font image)]
+/// The anti-aliased coverage mask of a rasterized glyph.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// Produced by [`GlyphRasterizer::rasterize`].
+///
+/// Rows run from top to bottom, and cells within each row from left to right.
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GlyphMask<'a> {
+    bounds: Region2<i32, u32>,
+    coverage: &'a [Coverage8],
+}
+#[rustfmt::skip]
+impl<'a> GlyphMask<'a> {
+    /// Returns the covered pixel region, relative to the glyph origin on the baseline,
+    /// with y growing downwards.
+    pub const fn bounds(&self) -> Region2<i32, u32> { self.bounds }
+    #[must_use] /// Returns the mask width, in pixels.
+    pub const fn width(&self) -> usize { self.bounds.w() as usize }
+    #[must_use] /// Returns the mask height, in pixels.
+    pub const fn height(&self) -> usize { self.bounds.h() as usize }
+    #[must_use] /// Returns whether the mask has no pixels.
+    pub const fn is_empty(&self) -> bool { self.coverage.is_empty() }
+    /// Returns the coverage of every pixel, row after row.
+    pub const fn coverage(&self) -> &'a [Coverage8] { self.coverage }
+
+    /// Returns the coverage of the pixels of `row`, indexed from the top.
+    pub const fn row(&self, row: usize) -> Option<&'a [Coverage8]> {
+        is![row >= self.height(), return None];
+        Some(Slice::range(self.coverage, row * self.width(), (row + 1) * self.width()))
+    }
+    /// Returns the coverage of the pixel at `(x, y)`, from the top-left corner.
+    pub const fn get(&self, x: usize, y: usize) -> Option<Coverage8> {
+        is![x >= self.width() || y >= self.height(), return None];
+        Some(self.coverage[y * self.width() + x])
+    }
+}
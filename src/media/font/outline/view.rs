@@ -0,0 +1,176 @@
+// devela/src/media/font/outline/view.rs
+//
+//! Defines [`FontOutlineView`].
+//
+
+use crate::{Debug, FmtResult, Formatter, OtfError, Position2, RasterPath, Region2};
+
+#[doc = crate::_tags!(font)]
+/// A validated, borrowed view over a scalable outline font.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// Obtained from [`Otf::read`][crate::Otf::read]. Glyphs are addressed by their
+/// glyph id, which [`glyph_index`][Self::glyph_index] maps from characters.
+///
+/// Metrics are in font units, which [`scale`][Self::scale] converts to pixels.
+/// Glyph outlines are appended to a [`RasterPath`] in pixels, with y growing
+/// downwards from the glyph origin on the baseline, ready to be filled.
+///
+/// The view borrows its backing storage and performs no allocation or copying.
+///
+/// # Example
+/// ```
+/// # use devela::{FontOutlineView, GlyphRasterizer, Otf};
+/// fn render(font_bytes: &[u8]) -> Result<(), devela::OtfError> {
+///     let font: FontOutlineView = Otf::read(font_bytes)?;
+///     let glyph = font.glyph_index('g').unwrap_or(0);
+///     let (mut scratch, mut coverage) = ([0.0; 64], [Default::default(); 64 * 64]);
+///     let mut rasterizer = GlyphRasterizer::<512>::new(&mut scratch, &mut coverage);
+///     let mask = rasterizer.rasterize(&font, glyph, 32.0)?;
+///     // the mask is drawn at (pen_x + left, baseline + top)
+///     let (left, top) = (mask.bounds().x(), mask.bounds().y());
+///     # let _ = (left, top);
+///     Ok(())
+/// }
+/// ```
+#[must_use]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontOutlineView<'a> {
+    /// The selected Unicode `cmap` subtable.
+    pub(crate) cmap: &'a [u8],
+    /// The format of the `cmap` subtable.
+    pub(crate) cmap_format: u16,
+    /// The horizontal metrics.
+    pub(crate) hmtx: &'a [u8],
+    /// The number of advance widths in `hmtx`.
+    pub(crate) h_metrics: u16,
+    /// The sorted kerning pair records, or empty.
+    pub(crate) kern: &'a [u8],
+    /// The TrueType glyph data, or empty.
+    pub(crate) glyf: &'a [u8],
+    /// The TrueType glyph locations, or empty.
+    pub(crate) loca: &'a [u8],
+    /// Whether the glyph locations are 32-bit offsets.
+    pub(crate) long_loca: bool,
+    /// The CFF charstrings INDEX, or empty.
+    pub(crate) cff_charstrings: &'a [u8],
+    /// The CFF local subroutines INDEX, or empty.
+    pub(crate) cff_subrs: &'a [u8],
+    /// The CFF global subroutines INDEX, or empty.
+    pub(crate) cff_global_subrs: &'a [u8],
+    /// Whether the outlines are CFF charstrings.
+    pub(crate) is_cff: bool,
+    /// The number of font units per em square.
+    pub(crate) units_per_em: u16,
+    /// The number of glyphs.
+    pub(crate) glyph_count: u16,
+    /// The typographic ascent above the baseline.
+    pub(crate) ascender: i16,
+    /// The typographic descent below the baseline, usually negative.
+    pub(crate) descender: i16,
+    /// The additional gap between lines.
+    pub(crate) line_gap: i16,
+    /// The bounds of every glyph, as `[x_min, y_min, x_max, y_max]`.
+    pub(crate) bounds: [i16; 4],
+}
+impl Debug for FontOutlineView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult<()> {
+        f.debug_struct("FontOutlineView")
+            .field("glyph_count", &self.glyph_count)
+            .field("units_per_em", &self.units_per_em)
+            .field("is_cff", &self.is_cff)
+            .field("ascender", &self.ascender)
+            .field("descender", &self.descender)
+            .field("line_gap", &self.line_gap)
+            .field("bounds", &self.bounds)
+            .field("kerning_pairs", &(self.kern.len() / 6))
+            .finish()
+    }
+}
+#[rustfmt::skip]
+impl<'a> FontOutlineView<'a> {
+    #[must_use] /// Returns the number of glyphs.
+    pub const fn glyph_count(&self) -> u16 { self.glyph_count }
+    #[must_use] /// Returns the number of font units per em square.
+    pub const fn units_per_em(&self) -> u16 { self.units_per_em }
+    #[must_use] /// Returns whether the outlines are cubic CFF charstrings, instead of TrueType.
+    pub const fn is_cff(&self) -> bool { self.is_cff }
+    #[must_use] /// Returns the typographic ascent above the baseline, in font units.
+    pub const fn ascender(&self) -> i16 { self.ascender }
+    #[must_use] /// Returns the typographic descent below the baseline, in font units.
+    ///
+    /// It is usually negative.
+    pub const fn descender(&self) -> i16 { self.descender }
+    #[must_use] /// Returns the additional gap between lines, in font units.
+    pub const fn line_gap(&self) -> i16 { self.line_gap }
+    #[must_use]
+    /// Returns the distance between consecutive baselines, in font units.
+    pub const fn line_advance(&self) -> i32 {
+        self.ascender as i32 - self.descender as i32 + self.line_gap as i32
+    }
+    /// Returns the region enclosing every glyph, in font units with y growing upwards.
+    pub const fn bounds(&self) -> Region2<i16, u16> {
+        let [x0, y0, x1, y1] = self.bounds;
+        Region2::from_xy_wh(x0, y0, x1.wrapping_sub(x0) as u16, y1.wrapping_sub(y0) as u16)
+    }
+    #[must_use]
+    /// Returns the number of pixels per font unit for a font `size` in pixels per em.
+    pub const fn scale(&self, size: f32) -> f32 { size / self.units_per_em as f32 }
+
+    /* glyphs */
+
+    /// Returns the glyph id mapped to `character`, or `None` if it is unmapped.
+    pub const fn glyph_index(&self, character: char) -> Option<u16> {
+        let glyph = self.cmap_glyph(character as u32);
+        if glyph == 0 { None } else { Some(glyph) }
+    }
+    #[must_use] /// Returns whether the font contains a glyph for `character`.
+    pub const fn has_glyph(&self, character: char) -> bool { self.cmap_glyph(character as u32) != 0 }
+
+    #[must_use]
+    /// Returns the horizontal advance of `glyph`, in font units.
+    ///
+    /// Returns `0` for glyph ids out of range.
+    pub const fn advance_width(&self, glyph: u16) -> u16 {
+        if glyph >= self.glyph_count { return 0; }
+        let metric = if glyph < self.h_metrics { glyph } else { self.h_metrics - 1 } as usize;
+        u16::from_be_bytes([self.hmtx[metric * 4], self.hmtx[metric * 4 + 1]])
+    }
+    #[must_use]
+    /// Returns the horizontal distance from the origin to the left edge of `glyph`,
+    /// in font units.
+    ///
+    /// Returns `0` for glyph ids out of range, or missing from the metrics.
+    pub const fn left_side_bearing(&self, glyph: u16) -> i16 {
+        let (g, n) = (glyph as usize, self.h_metrics as usize);
+        let at = if glyph < self.h_metrics { g * 4 + 2 } else { n * 4 + (g - n) * 2 };
+        if glyph >= self.glyph_count || at + 2 > self.hmtx.len() { return 0; }
+        i16::from_be_bytes([self.hmtx[at], self.hmtx[at + 1]])
+    }
+    #[must_use]
+    /// Returns the kerning adjustment between a `left` and a `right` glyph, in font units.
+    ///
+    /// It is added to the advance of the left glyph, and is `0` for unlisted pairs.
+    pub const fn kerning(&self, left: u16, right: u16) -> i16 { self.kern_pair(left, right) }
+    #[must_use] /// Returns whether the font contains kerning pairs.
+    pub const fn has_kerning(&self) -> bool { !self.kern.is_empty() }
+
+    /// Appends the outline of `glyph` to `path`, in pixels, for a font `size` in pixels per em.
+    ///
+    /// The glyph origin is placed at `origin`, with y growing downwards.
+    /// Empty glyphs, like spaces, append nothing.
+    ///
+    /// # Errors
+    /// Returns [`OtfError::InvalidGlyph`] if the glyph id is out of range or its data
+    /// is malformed, or [`OtfError::PathCapacity`] if the path gets full,
+    /// leaving the path with the contours appended until then.
+    pub fn glyph_path<const N: usize>(
+        &self,
+        glyph: u16,
+        size: f32,
+        origin: Position2<f32>,
+        path: &mut RasterPath<N>,
+    ) -> Result<(), OtfError> {
+        self.append_outline(glyph, self.scale(size), origin.dim, path)
+    }
+}
@@ -0,0 +1,114 @@
+// devela/src/media/font/shape/_test.rs
+
+use crate::{BidiLevel, FontShape, MismatchedCapacity, ShapedGlyph, TextShaper};
+use crate::{ConstInit, TextCohesion, TextSegment, TextSymbol, TextWrapIter, is};
+
+/// Maps ASCII, an acute accent, and the `fi` and `ffi` ligatures.
+struct Font;
+impl FontShape for Font {
+    fn shape_glyph(&self, c: char) -> Option<u32> {
+        (c.is_ascii() || matches![c, '\u{301}' | '\u{FB01}' | '\u{FB03}']).then_some(c as u32)
+    }
+    fn shape_advance(&self, glyph: u32) -> i32 {
+        match char::from_u32(glyph) {
+            Some('i') => 3,
+            Some(' ') => 4,
+            Some('\u{FB01}') => 7,
+            Some('\u{FB03}') => 9,
+            Some('\0') => 6,
+            _ => 5,
+        }
+    }
+    fn shape_kerning(&self, left: u32, right: u32) -> i32 {
+        is![(left, right) == ('A' as u32, 'V' as u32), -2, 0]
+    }
+}
+
+/// Shapes `text` and returns its glyph, cluster, position and advance tuples.
+fn shape<const N: usize>(
+    shaper: TextShaper,
+    text: &str,
+    level: BidiLevel,
+) -> [(u32, u32, i32, i32); N] {
+    let mut glyphs = [ShapedGlyph::INIT; N];
+    let len = shaper.shape(&Font, text, level, &mut glyphs).unwrap();
+    assert_eq![len, N];
+    glyphs.map(|g| (g.glyph, g.cluster, g.x, g.advance))
+}
+const LTR: BidiLevel = BidiLevel::LTR;
+
+#[test]
+fn clusters() {
+    let shaper = TextShaper::new();
+    let glyphs = shape(shaper, "e\u{301}x", LTR);
+    assert_eq![glyphs, [('e' as u32, 0, 0, 5), (0x301, 0, 0, 0), ('x' as u32, 2, 5, 5)]];
+    // unmapped extending characters are dropped, unmapped bases get the notdef glyph
+    assert_eq![shape(shaper, "e\u{302}", LTR), [('e' as u32, 0, 0, 5)]];
+    assert_eq![shape(shaper, "€a", LTR), [(0, 0, 0, 6), ('a' as u32, 1, 6, 5)]];
+}
+
+#[test]
+fn ligatures() {
+    let shaper = TextShaper::new();
+    assert_eq![shape(shaper, "fit", LTR), [(0xFB01, 0, 0, 7), ('t' as u32, 2, 7, 5)]];
+    assert_eq![shape(shaper, "ffi", LTR), [(0xFB03, 0, 0, 9)]];
+    // unmapped ligatures, and clusters of more than one scalar, stay apart
+    let [f1, f2, l] = shape(shaper, "ffl", LTR);
+    assert_eq![(f1.0, f2.0, l.0), ('f' as u32, 'f' as u32, 'l' as u32)];
+    assert_eq![shape::<3>(shaper, "f\u{301}i", LTR)[2], ('i' as u32, 2, 5, 3)];
+    // a shorter ligature is formed after a longer one fails
+    assert_eq![shape::<2>(shaper, "xfi", LTR)[1], (0xFB01, 1, 5, 7)];
+    let shaper = shaper.with_ligatures(false);
+    assert_eq![shape::<2>(shaper, "fi", LTR), [('f' as u32, 0, 0, 5), ('i' as u32, 1, 5, 3)]];
+}
+
+#[test]
+fn kerning() {
+    let (a, v) = ('A' as u32, 'V' as u32);
+    assert_eq![shape(TextShaper::new(), "AV", LTR), [(a, 0, 0, 3), (v, 1, 3, 5)]];
+    let shaper = TextShaper::new().with_kerning(false);
+    assert_eq![shape(shaper, "AV", LTR), [(a, 0, 0, 5), (v, 1, 5, 5)]];
+}
+
+#[test]
+fn right_to_left() {
+    let shaper = TextShaper::new();
+    let glyphs = shape(shaper, "ab(", BidiLevel::RTL);
+    let [a, b, close] = ['a', 'b', ')'].map(|c| c as u32);
+    assert_eq![glyphs, [(close, 2, 0, 5), (b, 1, 5, 5), (a, 0, 10, 5)]];
+    // marks stay after their base, at its position
+    let glyphs = shape(shaper, "a\u{301}b", BidiLevel::RTL);
+    assert_eq![glyphs, [(b, 2, 0, 5), (a, 0, 5, 5), (0x301, 0, 5, 0)]];
+}
+
+#[test]
+fn capacity() {
+    let mut glyphs = [ShapedGlyph::INIT; 2];
+    let error = TextShaper::new().shape(&Font, "a\u{301}b", LTR, &mut glyphs);
+    assert_eq![error, Err(MismatchedCapacity::too_small(2, 3))];
+    assert_eq![TextShaper::new().shape(&Font, "", LTR, &mut glyphs), Ok(0)];
+}
+
+#[test]
+fn layout() {
+    let text = "fit ab";
+    let mut glyphs = [ShapedGlyph::INIT; 8];
+    let len = TextShaper::new().shape(&Font, text, LTR, &mut glyphs).unwrap();
+    let glyphs = &glyphs[..len];
+    assert_eq![TextShaper::run_width(glyphs), 26];
+
+    let mut symbols = [TextSymbol::INIT; 8];
+    let len = TextShaper::fill_text_symbols(text, glyphs, &mut symbols);
+    assert_eq![symbols.map(|s| s.units)[..len], [7, 0, 5, 4, 5, 5]];
+    assert_eq![symbols[3].cohesion, TextCohesion::Breakable];
+
+    let mut segments = [TextSegment::INIT; 4];
+    let measured = text.chars().zip(symbols.iter().map(|s| s.units));
+    let len = TextSegment::segments_from_measured(measured, &mut segments);
+    let mut wrap = TextWrapIter::new(&segments[..len], 25);
+    assert_eq![wrap.next_line().unwrap().span.units, 12];
+    assert_eq![wrap.next_line().unwrap().span.units, 10];
+    assert_eq![wrap.next_line(), None];
+    let mut wrap = TextWrapIter::new(&segments[..len], 26);
+    assert_eq![wrap.next_line().unwrap().span.units, 26];
+}
@@ -0,0 +1,62 @@
+// devela/src/media/font/shape/font.rs
+//
+//! Defines [`FontShape`].
+//
+
+use crate::{FontBitmapView, FontOutlineView};
+
+#[doc = crate::_tags!(font)]
+/// A font that provides the metrics needed to shape text.
+#[doc = crate::_doc_meta!{location("media/font")}]
+///
+/// Glyphs are identified by font-specific ids, and measured in the units
+/// of the font: font units for outlines, and pixels for bitmaps.
+///
+/// See [`TextShaper`][crate::TextShaper].
+pub trait FontShape {
+    /// Returns the glyph id mapped to `c`, or `None` if it is unmapped.
+    fn shape_glyph(&self, c: char) -> Option<u32>;
+
+    /// Returns the horizontal advance of `glyph`.
+    fn shape_advance(&self, glyph: u32) -> i32;
+
+    /* provided */
+
+    /// Returns the glyph id used for unmapped characters.
+    fn shape_notdef(&self) -> u32 {
+        0
+    }
+    /// Returns the adjustment to the advance of a `left` glyph followed by a `right` one.
+    fn shape_kerning(&self, left: u32, right: u32) -> i32 {
+        let _ = (left, right);
+        0
+    }
+}
+
+impl FontShape for FontOutlineView<'_> {
+    fn shape_glyph(&self, c: char) -> Option<u32> {
+        self.glyph_index(c).map(u32::from)
+    }
+    fn shape_advance(&self, glyph: u32) -> i32 {
+        u16::try_from(glyph).map_or(0, |g| self.advance_width(g).into())
+    }
+    fn shape_kerning(&self, left: u32, right: u32) -> i32 {
+        match (u16::try_from(left), u16::try_from(right)) {
+            (Ok(l), Ok(r)) => self.kerning(l, r).into(),
+            _ => 0,
+        }
+    }
+}
+
+/// Glyph ids are scalar-sort indices, and the unmapped glyph is the default character.
+impl FontShape for FontBitmapView<'_> {
+    fn shape_glyph(&self, c: char) -> Option<u32> {
+        self.glyph_index(c).map(|i| i as u32)
+    }
+    fn shape_advance(&self, _glyph: u32) -> i32 {
+        self.advance_x().into()
+    }
+    fn shape_notdef(&self) -> u32 {
+        self.default_character().and_then(|c| self.shape_glyph(c)).unwrap_or(0)
+    }
+}
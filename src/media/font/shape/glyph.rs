@@ -0,0 +1,40 @@
+// devela/src/media/font/shape/glyph.rs
+//
+//! Defines [`ShapedGlyph`].
+//
+
+use crate::_impl_init;
+
+#[doc = crate::_tags!(font text)]
+/// A glyph positioned along a shaped run.
+#[doc = crate::_doc_meta!{
+    location("media/font"),
+    test_size_of(ShapedGlyph = 16|128),
+}]
+///
+/// Positions and advances are in the units of the shaping font.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ShapedGlyph {
+    /// The font-specific glyph id.
+    pub glyph: u32,
+    /// The index of the first Unicode scalar of the cluster it belongs to.
+    pub cluster: u32,
+    /// The horizontal position of its origin, from the start of the run.
+    pub x: i32,
+    /// The horizontal advance, including kerning.
+    ///
+    /// It is `0` for glyphs attached to the base of their cluster.
+    pub advance: i32,
+}
+_impl_init![Self::INIT => ShapedGlyph];
+
+impl ShapedGlyph {
+    /// An empty glyph at the start of the run.
+    pub const INIT: Self = Self { glyph: 0, cluster: 0, x: 0, advance: 0 };
+
+    /// Returns the horizontal position after its advance.
+    #[must_use]
+    pub const fn end(self) -> i32 {
+        self.x + self.advance
+    }
+}
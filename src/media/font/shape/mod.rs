@@ -0,0 +1,21 @@
+// devela/src/media/font/shape/mod.rs
+//
+//! Text shaping into positioned glyph runs.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod font; // FontShape
+mod glyph; // ShapedGlyph
+mod shaper; // TextShaper
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            font::*,
+            glyph::*,
+            shaper::*,
+        };
+    }
+}
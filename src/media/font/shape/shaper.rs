@@ -0,0 +1,310 @@
+// devela/src/media/font/shape/shaper.rs
+//
+//! Defines [`TextShaper`].
+//
+
+use crate::{_impl_init, Bidi, BidiLevel, FontShape, GraphemeBoundary, GraphemeMachine};
+use crate::{GraphemeScanner, MismatchedCapacity, ShapedGlyph, is};
+use crate::{TextCohesion, TextSymbol, TextUnit};
+
+#[doc = crate::_tags!(font text)]
+/// Shapes text into positioned glyph runs.
+#[doc = crate::_doc_meta!{
+    location("media/font"),
+    test_size_of(TextShaper = 2|16),
+}]
+///
+/// Each grapheme cluster becomes a base glyph that carries the advance,
+/// followed by the glyphs of its extending characters mapped by the font,
+/// which are attached to the base with no advance.
+///
+/// Optionally, the Latin `f` ligatures are formed when the font maps their
+/// presentation forms, and kerning is applied between consecutive base glyphs.
+///
+/// A run is shaped at a single bidirectional level, as resolved by [`Bidi`]:
+/// right-to-left runs mirror their characters and are returned in visual order.
+///
+/// Use [`fill_text_symbols`][Self::fill_text_symbols] to feed the shaped widths
+/// to [`TextLayout`][crate::TextLayout] and [`TextSegment`][crate::TextSegment].
+///
+/// # Example
+/// ```
+/// # use devela::{BidiLevel, Fonts, ShapedGlyph, TextShaper};
+/// let font = Fonts::TERMIVELA_8_16;
+/// let mut glyphs = [ShapedGlyph::INIT; 8];
+/// let len = TextShaper::new().shape(&font, "(a)", BidiLevel::RTL, &mut glyphs).unwrap();
+/// let glyph = |c| font.glyph_index(c).unwrap() as u32;
+/// // mirrored, and in visual order
+/// assert_eq![(glyphs[0].glyph, glyphs[0].cluster, glyphs[0].x), (glyph('('), 2, 0)];
+/// assert_eq![(glyphs[2].glyph, glyphs[2].cluster, glyphs[2].x), (glyph(')'), 0, 16)];
+/// assert_eq![TextShaper::run_width(&glyphs[..len]), 24];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextShaper {
+    ligatures: bool,
+    kerning: bool,
+}
+_impl_init![Self::new() => TextShaper];
+
+impl Default for TextShaper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextShaper {
+    /// Returns a shaper with ligatures and kerning enabled.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { ligatures: true, kerning: true }
+    }
+    /// Sets whether to form ligatures.
+    #[must_use]
+    pub const fn with_ligatures(mut self, ligatures: bool) -> Self {
+        self.ligatures = ligatures;
+        self
+    }
+    /// Sets whether to apply kerning.
+    #[must_use]
+    pub const fn with_kerning(mut self, kerning: bool) -> Self {
+        self.kerning = kerning;
+        self
+    }
+    /// Returns whether ligatures are formed.
+    #[must_use]
+    pub const fn ligatures(&self) -> bool {
+        self.ligatures
+    }
+    /// Returns whether kerning is applied.
+    #[must_use]
+    pub const fn kerning(&self) -> bool {
+        self.kerning
+    }
+
+    /// Shapes a single-level run of `text` into `out`, and returns the number of glyphs.
+    ///
+    /// Characters unmapped by the font get its [`shape_notdef`] glyph,
+    /// unless they extend a cluster, in which case they are dropped.
+    ///
+    /// The cluster of each glyph is the index of the first Unicode scalar
+    /// of its grapheme cluster, or of its ligature.
+    ///
+    /// # Errors
+    /// Returns [`MismatchedCapacity`] if `out` can't hold every glyph.
+    ///
+    /// [`shape_notdef`]: FontShape::shape_notdef
+    pub fn shape<F: FontShape + ?Sized>(
+        &self,
+        font: &F,
+        text: &str,
+        level: BidiLevel,
+        out: &mut [ShapedGlyph],
+    ) -> Result<usize, MismatchedCapacity> {
+        let mut run = Run { font, text, rtl: level.is_rtl(), out, len: 0 };
+        let mut pending = Pending { clusters: [Cluster::EMPTY; 3], len: 0 };
+        let mut current: Option<Cluster> = None;
+        let (mut byte, mut index) = (0, 0);
+        let mut machine = GraphemeMachine::new();
+        for (boundary, c) in GraphemeScanner::<char>::new(&mut machine, text) {
+            let len = c.len_utf8();
+            match &mut current {
+                Some(cluster) if boundary.eq(GraphemeBoundary::Continue) => {
+                    cluster.end += len;
+                    cluster.single = false;
+                }
+                _ => {
+                    let next = Cluster {
+                        start: byte,
+                        end: byte + len,
+                        index,
+                        first: c,
+                        single: true,
+                    };
+                    if let Some(cluster) = current.replace(next) {
+                        is![pending.len == 3, self.shape_front(&mut run, &mut pending)];
+                        pending.push(cluster);
+                    }
+                }
+            }
+            (byte, index) = (byte + len, index + 1);
+        }
+        if let Some(cluster) = current {
+            is![pending.len == 3, self.shape_front(&mut run, &mut pending)];
+            pending.push(cluster);
+        }
+        while pending.len > 0 {
+            self.shape_front(&mut run, &mut pending);
+        }
+
+        let (len, capacity) = (run.len, run.out.len());
+        is![len > capacity, return Err(MismatchedCapacity::too_small(capacity, len))];
+        let glyphs = &mut run.out[..len];
+        if run.rtl {
+            glyphs.reverse();
+            let mut start = 0;
+            for end in 1..=len {
+                if end == len || glyphs[end].cluster != glyphs[start].cluster {
+                    glyphs[start..end].reverse();
+                    start = end;
+                }
+            }
+        }
+        if self.kerning {
+            let mut prev: Option<usize> = None;
+            for i in 0..len {
+                is![i > 0 && glyphs[i].cluster == glyphs[i - 1].cluster, continue];
+                if let Some(p) = prev {
+                    glyphs[p].advance += font.shape_kerning(glyphs[p].glyph, glyphs[i].glyph);
+                }
+                prev = Some(i);
+            }
+        }
+        let (mut pen, mut base) = (0, 0);
+        for i in 0..len {
+            is![i == 0 || glyphs[i].cluster != glyphs[i - 1].cluster, base = pen];
+            glyphs[i].x = base;
+            pen += glyphs[i].advance;
+        }
+        Ok(len)
+    }
+
+    /// Returns the total advance of a shaped run.
+    #[must_use]
+    pub fn run_width(glyphs: &[ShapedGlyph]) -> i32 {
+        glyphs.iter().map(|g| g.advance).sum()
+    }
+
+    /// Fills `out` with one layout symbol per Unicode scalar of `text`,
+    /// measured by the advances of the `glyphs` shaped from it.
+    ///
+    /// Each symbol takes the advances of the glyphs of its cluster, so that
+    /// extending characters, and the rest of the characters of a ligature,
+    /// take no units. Negative advances count as zero.
+    /// Whitespace is `Breakable`, and everything else is `Atomic`.
+    ///
+    /// The units are those of the font. They can be fed to [`TextLayout`],
+    /// or paired with the characters of `text` for
+    /// [`TextSegment::segments_from_measured`].
+    ///
+    /// Returns the initialized prefix length.
+    ///
+    /// [`TextLayout`]: crate::TextLayout
+    /// [`TextSegment::segments_from_measured`]: crate::TextSegment::segments_from_measured
+    pub fn fill_text_symbols(text: &str, glyphs: &[ShapedGlyph], out: &mut [TextSymbol]) -> usize {
+        let mut len = 0;
+        for (symbol, c) in out.iter_mut().zip(text.chars()) {
+            let cohesion = is![c.is_whitespace(), TextCohesion::Breakable, TextCohesion::Atomic];
+            *symbol = TextSymbol::new(0, cohesion);
+            len += 1;
+        }
+        for glyph in glyphs {
+            if let Some(symbol) = out[..len].get_mut(glyph.cluster as usize) {
+                symbol.units += glyph.advance.max(0) as TextUnit;
+            }
+        }
+        len
+    }
+
+    /// Shapes the first pending cluster, or the ligature starting with it.
+    fn shape_front<F: FontShape + ?Sized>(self, run: &mut Run<F>, pending: &mut Pending) {
+        let clusters = &pending.clusters[..pending.len];
+        let ligature = is![self.ligatures, ligature(run.font, clusters), None];
+        let taken = if let Some((glyph, taken)) = ligature {
+            run.push(glyph, clusters[0].index, run.font.shape_advance(glyph));
+            taken
+        } else {
+            run.push_cluster(clusters[0]);
+            1
+        };
+        pending.clusters.copy_within(taken.., 0);
+        pending.len -= taken;
+    }
+}
+
+/// The standard Latin ligatures, longest first, with their presentation forms.
+const LIGATURES: [(&str, char); 5] = [
+    ("ffi", '\u{FB03}'),
+    ("ffl", '\u{FB04}'),
+    ("ff", '\u{FB00}'),
+    ("fi", '\u{FB01}'),
+    ("fl", '\u{FB02}'),
+];
+
+/// Returns the glyph of the ligature formed by the first `clusters`,
+/// and how many of them it takes.
+fn ligature<F: FontShape + ?Sized>(font: &F, clusters: &[Cluster]) -> Option<(u32, usize)> {
+    LIGATURES.iter().find_map(|(seq, form)| {
+        let n = seq.len();
+        let matched = n <= clusters.len()
+            && clusters.iter().zip(seq.chars()).all(|(cl, c)| cl.single && cl.first == c);
+        is![matched, font.shape_glyph(*form).map(|glyph| (glyph, n)), None]
+    })
+}
+
+/// A grapheme cluster of the text being shaped.
+#[derive(Clone, Copy)]
+struct Cluster {
+    /// The byte range of the cluster.
+    start: usize,
+    end: usize,
+    /// The index of its first Unicode scalar.
+    index: u32,
+    /// Its first Unicode scalar.
+    first: char,
+    /// Whether it consists of a single Unicode scalar.
+    single: bool,
+}
+impl Cluster {
+    const EMPTY: Self = Self {
+        start: 0,
+        end: 0,
+        index: 0,
+        first: '\0',
+        single: true,
+    };
+}
+
+/// The clusters waiting to be shaped, enough to form any ligature.
+struct Pending {
+    clusters: [Cluster; 3],
+    len: usize,
+}
+impl Pending {
+    fn push(&mut self, cluster: Cluster) {
+        self.clusters[self.len] = cluster;
+        self.len += 1;
+    }
+}
+
+/// The state of the run being shaped.
+struct Run<'a, F: ?Sized> {
+    font: &'a F,
+    text: &'a str,
+    rtl: bool,
+    out: &'a mut [ShapedGlyph],
+    /// The number of glyphs, which may exceed the capacity of `out`.
+    len: usize,
+}
+impl<F: FontShape + ?Sized> Run<'_, F> {
+    /// Pushes a glyph in logical order, counting it even if it doesn't fit.
+    fn push(&mut self, glyph: u32, cluster: u32, advance: i32) {
+        if let Some(slot) = self.out.get_mut(self.len) {
+            *slot = ShapedGlyph { glyph, cluster, x: 0, advance };
+        }
+        self.len += 1;
+    }
+    /// Pushes the glyphs of a cluster, mirrored if right-to-left.
+    fn push_cluster(&mut self, cluster: Cluster) {
+        for (i, c) in self.text[cluster.start..cluster.end].chars().enumerate() {
+            let mirrored = is![self.rtl, Bidi::mirrored(c), None];
+            let glyph = mirrored.and_then(|m| self.font.shape_glyph(m));
+            let glyph = glyph.or_else(|| self.font.shape_glyph(c));
+            if i == 0 {
+                let glyph = glyph.unwrap_or_else(|| self.font.shape_notdef());
+                self.push(glyph, cluster.index, self.font.shape_advance(glyph));
+            } else if let Some(glyph) = glyph {
+                self.push(glyph, cluster.index, 0);
+            }
+        }
+    }
+}
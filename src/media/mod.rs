@@ -9,16 +9,18 @@
 #![cfg_attr(feature = "safe_media", forbid(unsafe_code))]
 // docs
 crate::CONST! { pub(crate) _DOC_MEDIA_MODULES =
-    crate::_doc!(modules: crate; media: audio, font, visual); // compo, doc, motion
+    crate::_doc!(modules: crate; media: audio, doc, font, motion, visual); // compo
 }
 
 #[cfg(feature = "audio")]
 pub mod audio;
 // pub mod compo; // WIP
-// pub mod doc; // WIP
+#[cfg(feature = "doc")]
+pub mod doc;
 #[cfg(feature = "font")]
 pub mod font;
-// pub mod motion; // WIP
+#[cfg(feature = "motion")]
+pub mod motion;
 pub mod visual;
 
 crate::structural_mods! { // _pub_mods, _crate_internals, _hidden
@@ -26,14 +28,18 @@ crate::structural_mods! { // _pub_mods, _crate_internals, _hidden
         #[cfg(feature = "audio")]
         pub use super::audio::_all::*;
         // pub use super::compo::_all::*;
-        // pub use super::doc::_all::*;
+        #[cfg(feature = "doc")]
+        pub use super::doc::_all::*;
         #[cfg(feature = "font")]
         pub use super::font::_all::*;
-        // pub use super::motion::_all::*;
+        #[cfg(feature = "motion")]
+        pub use super::motion::_all::*;
         pub use super::visual::_all::*;
     }
     _crate_internals {
         pub(crate) use super::_DOC_MEDIA_MODULES;
+        #[cfg(feature = "font")]
+        pub(crate) use super::font::_crate_internals::*;
         pub(crate) use super::visual::_crate_internals::*;
     }
     _hidden {
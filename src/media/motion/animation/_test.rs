@@ -0,0 +1,117 @@
+// devela/src/media/motion/animation/_test.rs
+
+#![allow(clippy::float_cmp, reason = "the expected values are exact")]
+
+use crate::{Angle, Animation, Easing, EasingFamily, Keyframe, Keyframes, TimeDelta};
+use crate::{Interpolate, Timeline, Tween};
+
+fn ms(millis: i64) -> TimeDelta {
+    TimeDelta::from_millis(millis)
+}
+
+#[test]
+fn interpolate_values() {
+    assert_eq!(200_u8.interpolate(&100, 0.5), 150);
+    assert_eq!(0_u8.interpolate(&100, 3.0), 255);
+    assert_eq!(0_i8.interpolate(&100, -3.0), -128);
+    assert_eq!(1.0_f64.interpolate(&3.0, 0.75), 2.5);
+    let back = Angle::<f32>::from_deg(10.0).interpolate(&Angle::<f32>::from_deg(350.0), 0.25);
+    assert!((back.to_deg() - 5.0).abs() < 1e-3);
+    #[cfg(feature = "color")]
+    {
+        use crate::Rgba8;
+        let (clear, white) = (Rgba8::new(0, 0, 0, 0), Rgba8::new(255, 255, 255, 255));
+        assert_eq!(clear.interpolate(&white, 0.2), Rgba8::new(51, 51, 51, 51));
+    }
+    #[cfg(feature = "alg")]
+    {
+        let (a, b) = (crate::Vector::new([0.0_f32, 4.0]), crate::Vector::new([2.0, 0.0]));
+        assert_eq!(a.interpolate(&b, 0.5), crate::Vector::new([1.0, 2.0]));
+    }
+}
+#[test]
+fn tweens_clamp_their_time() {
+    let tween = Tween::new(-10_i32, 10, ms(200)).with_easing(Easing::InOut(EasingFamily::Quad));
+    assert_eq!(tween.sample(ms(0)), -10);
+    assert_eq!(tween.sample(ms(100)), 0);
+    assert_eq!(tween.sample(ms(200)), 10);
+    assert_eq!(tween.sample(ms(-50)), -10);
+    assert_eq!(tween.reversed().sample(ms(200)), -10);
+    assert_eq!(Tween::new(1, 2, TimeDelta::ZERO).sample(ms(0)), 2);
+}
+#[test]
+fn compose_animations() {
+    let up = Tween::new(0_i32, 100, ms(100));
+    let down = Tween::new(100_i32, 0, ms(100));
+    let seq = up.then(down).delay(ms(50));
+    assert_eq!(seq.duration(), Some(ms(250)));
+    assert_eq!(seq.sample(ms(25)), 0);
+    assert_eq!(seq.sample(ms(100)), 50);
+    assert_eq!(seq.sample(ms(200)), 50);
+    assert!(seq.is_finished(ms(250)));
+
+    let par = up.with(Tween::new(0.0_f32, 1.0, ms(400)));
+    assert_eq!(par.duration(), Some(ms(400)));
+    assert_eq!(par.sample(ms(200)), (100, 0.5));
+
+    let forever = up.repeat(None);
+    assert_eq!(forever.duration(), None);
+    assert_eq!(up.then(forever).duration(), None);
+    assert!(!forever.is_finished(ms(i64::MAX / 2)));
+    assert_eq!(forever.sample(ms(1_230)), 30);
+
+    let twice = up.repeat(2).yoyo(true);
+    assert_eq!(twice.duration(), Some(ms(200)));
+    assert_eq!(twice.sample(ms(75)), 75);
+    assert_eq!(twice.sample(ms(125)), 75);
+    assert_eq!(twice.sample(ms(500)), 0);
+    // repeated zero times, it's still played once
+    let none = up.repeat(0);
+    assert_eq!(none.duration(), Some(ms(100)));
+    assert!(!none.is_finished(ms(0)));
+    assert_eq!(none.sample(ms(50)), 50);
+    assert_eq!(none.sample(ms(500)), 100);
+}
+#[test]
+fn keyframes_hold_and_jump() {
+    let keys = [
+        Keyframe::new(ms(100), 0_i32),
+        Keyframe::new(ms(200), 10),
+        Keyframe::new(ms(200), 50).with_easing(Easing::Steps(2)),
+        Keyframe::new(ms(300), 100),
+    ];
+    let track = Keyframes::new(&keys).unwrap();
+    assert_eq!(track.duration(), Some(ms(300)));
+    assert_eq!(track.sample(ms(0)), 0);
+    assert_eq!(track.sample(ms(150)), 5);
+    assert_eq!(track.sample(ms(180)), 8);
+    assert_eq!(track.sample(ms(200)), 50);
+    assert_eq!(track.sample(ms(240)), 50);
+    assert_eq!(track.sample(ms(250)), 75);
+    assert_eq!(track.sample(ms(900)), 100);
+    assert!(Keyframes::<i32>::new(&[]).is_none());
+    assert_eq!(Keyframes::new(&keys[..1]).unwrap().sample(ms(500)), 0);
+}
+#[test]
+fn timelines_play_animations() {
+    let mut timeline = Timeline::new(Tween::new(0_u8, 100, ms(100)));
+    assert_eq!(timeline.progress(), Some(0.0));
+    assert_eq!(timeline.advance(ms(40)), 40);
+    timeline.set_paused(true);
+    assert_eq!(timeline.advance(ms(40)), 40);
+    timeline.set_paused(false);
+    timeline.set_speed(0.5);
+    assert_eq!(timeline.advance(ms(40)), 60);
+    timeline.seek(ms(1_000));
+    assert!(timeline.is_finished());
+    assert_eq!(timeline.time(), ms(100));
+    timeline.set_speed(-1.0);
+    assert_eq!(timeline.advance(ms(1_000)), 0);
+    assert_eq!(timeline.time(), TimeDelta::ZERO);
+
+    let mut forever = Timeline::new(Tween::new(0_u8, 100, ms(100)).repeat(None));
+    assert_eq!(forever.advance(ms(1_050)), 50);
+    assert_eq!(forever.progress(), None);
+    forever.reset();
+    assert_eq!(forever.value(), 0);
+}
@@ -0,0 +1,277 @@
+// devela/src/media/motion/animation/animation.rs
+//
+//! Defines [`Animation`], [`AnimationDelay`], [`AnimationPar`],
+//! [`AnimationRepeat`], [`AnimationSeq`].
+//
+// TOC
+// - trait Animation
+// - struct AnimationSeq
+// - struct AnimationPar
+// - struct AnimationDelay
+// - struct AnimationRepeat
+// - helpers
+
+use crate::{TimeDelta, is};
+
+#[doc = crate::_tags!(time)]
+/// A value that changes over a span of time.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It's sampled at any time since its start, which makes it stateless and
+/// seekable. Times before the start sample the initial value, and times
+/// after the end sample the final one.
+///
+/// Animations are composed by sequencing them with [`then`][Self::then],
+/// grouping them in parallel with [`with`][Self::with], delaying them
+/// with [`delay`][Self::delay] and repeating them with [`repeat`][Self::repeat].
+/// A [`Timeline`] plays them by advancing their time.
+///
+/// It's implemented by [`Tween`] and [`Keyframes`].
+///
+/// [`Keyframes`]: crate::Keyframes
+/// [`Timeline`]: crate::Timeline
+/// [`Tween`]: crate::Tween
+///
+/// # Example
+/// ```
+/// # use devela::{Animation, Easing, EasingFamily, TimeDelta, Tween};
+/// let ms = TimeDelta::from_millis;
+/// let grow = Tween::new(0.0_f32, 1.0, ms(100));
+/// let shrink = Tween::new(1.0_f32, 0.5, ms(100)).with_easing(Easing::Out(EasingFamily::Quad));
+/// let pulse = grow.then(shrink).repeat(3);
+///
+/// assert_eq![pulse.duration(), Some(ms(600))];
+/// assert_eq![pulse.sample(ms(50)), 0.5];
+/// assert_eq![pulse.sample(ms(250)), 0.5];
+/// assert_eq![pulse.sample(ms(1000)), 0.5];
+/// ```
+pub trait Animation {
+    /// The type of the animated value.
+    type Value;
+
+    /// Returns the duration, or `None` if it never ends.
+    fn duration(&self) -> Option<TimeDelta>;
+
+    /// Returns the value at `time` since the start.
+    fn sample(&self, time: TimeDelta) -> Self::Value;
+
+    /* provided */
+
+    /// Returns whether it has ended at `time`.
+    fn is_finished(&self, time: TimeDelta) -> bool {
+        self.duration().is_some_and(|duration| time >= duration)
+    }
+
+    /// Returns this animation followed by `next`.
+    fn then<B>(self, next: B) -> AnimationSeq<Self, B>
+    where
+        Self: Sized,
+        B: Animation<Value = Self::Value>,
+    {
+        AnimationSeq { first: self, next }
+    }
+
+    /// Returns this animation played at the same time as `other`,
+    /// sampling both values.
+    fn with<B: Animation>(self, other: B) -> AnimationPar<Self, B>
+    where
+        Self: Sized,
+    {
+        AnimationPar { a: self, b: other }
+    }
+
+    /// Returns this animation started after a `delay`.
+    fn delay(self, delay: TimeDelta) -> AnimationDelay<Self>
+    where
+        Self: Sized,
+    {
+        AnimationDelay { animation: self, delay }
+    }
+
+    /// Returns this animation played `times` times in total,
+    /// or forever if `times` is `None`.
+    ///
+    /// Playing it `0` times plays it once, like playing it `1` time.
+    fn repeat(self, times: impl Into<Option<u32>>) -> AnimationRepeat<Self>
+    where
+        Self: Sized,
+    {
+        AnimationRepeat { animation: self, times: times.into(), yoyo: false }
+    }
+}
+
+impl<A: Animation + ?Sized> Animation for &A {
+    type Value = A::Value;
+    fn duration(&self) -> Option<TimeDelta> {
+        (**self).duration()
+    }
+    fn sample(&self, time: TimeDelta) -> A::Value {
+        (**self).sample(time)
+    }
+}
+
+#[doc = crate::_tags!(time)]
+/// Two animations of the same value played one after the other.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It's created with [`Animation::then`].
+/// If the first one never ends, the next one never starts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnimationSeq<A, B> {
+    first: A,
+    next: B,
+}
+impl<A, B> AnimationSeq<A, B> {
+    /// Returns the animations, in order.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.next)
+    }
+}
+impl<A: Animation, B: Animation<Value = A::Value>> Animation for AnimationSeq<A, B> {
+    type Value = A::Value;
+    fn duration(&self) -> Option<TimeDelta> {
+        Some(self.first.duration()?.saturating_add(self.next.duration()?))
+    }
+    fn sample(&self, time: TimeDelta) -> A::Value {
+        match self.first.duration() {
+            Some(first) if time >= first => self.next.sample(time.saturating_sub(first)),
+            _ => self.first.sample(time),
+        }
+    }
+}
+
+#[doc = crate::_tags!(time)]
+/// Two animations played at the same time, sampled as a pair of values.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It's created with [`Animation::with`], and lasts as long as the longest one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnimationPar<A, B> {
+    a: A,
+    b: B,
+}
+impl<A, B> AnimationPar<A, B> {
+    /// Returns the animations.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+impl<A: Animation, B: Animation> Animation for AnimationPar<A, B> {
+    type Value = (A::Value, B::Value);
+    fn duration(&self) -> Option<TimeDelta> {
+        Some(self.a.duration()?.max(self.b.duration()?))
+    }
+    fn sample(&self, time: TimeDelta) -> Self::Value {
+        (self.a.sample(time), self.b.sample(time))
+    }
+}
+
+#[doc = crate::_tags!(time)]
+/// An animation started after a delay, holding its initial value until then.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It's created with [`Animation::delay`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnimationDelay<A> {
+    animation: A,
+    delay: TimeDelta,
+}
+impl<A> AnimationDelay<A> {
+    /// Returns the delayed animation.
+    pub fn into_inner(self) -> A {
+        self.animation
+    }
+    /// Returns the time it waits before starting.
+    pub const fn delay_time(&self) -> TimeDelta {
+        self.delay
+    }
+}
+impl<A: Animation> Animation for AnimationDelay<A> {
+    type Value = A::Value;
+    fn duration(&self) -> Option<TimeDelta> {
+        Some(self.animation.duration()?.saturating_add(self.delay))
+    }
+    fn sample(&self, time: TimeDelta) -> A::Value {
+        self.animation.sample(time.saturating_sub(self.delay))
+    }
+}
+
+#[doc = crate::_tags!(time)]
+/// An animation played a number of times, or forever.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It's created with [`Animation::repeat`]. With [`yoyo`][Self::yoyo],
+/// every other play runs backwards, so that it goes back and forth.
+///
+/// An animation that never ends, or that lasts nothing, is played once,
+/// and so is one repeated `0` times.
+///
+/// # Example
+/// ```
+/// # use devela::{Animation, TimeDelta, Tween};
+/// let ms = TimeDelta::from_millis;
+/// let blink = Tween::new(0_u8, 100, ms(10)).repeat(None).yoyo(true);
+/// assert_eq![blink.duration(), None];
+/// assert_eq![blink.sample(ms(5)), 50];
+/// assert_eq![blink.sample(ms(12)), 80];
+/// assert_eq![blink.sample(ms(1_000_020)), 0];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnimationRepeat<A> {
+    animation: A,
+    times: Option<u32>,
+    yoyo: bool,
+}
+impl<A> AnimationRepeat<A> {
+    /// Returns it playing every other time backwards, if `yoyo`.
+    pub fn yoyo(self, yoyo: bool) -> Self {
+        Self { yoyo, ..self }
+    }
+    /// Returns the repeated animation.
+    pub fn into_inner(self) -> A {
+        self.animation
+    }
+    /// Returns the number of times it was asked to be played, or `None` if forever.
+    ///
+    /// `Some(0)` is played once.
+    pub const fn times(&self) -> Option<u32> {
+        self.times
+    }
+    /// Returns whether every other play runs backwards.
+    pub const fn is_yoyo(&self) -> bool {
+        self.yoyo
+    }
+}
+impl<A: Animation> Animation for AnimationRepeat<A> {
+    type Value = A::Value;
+    fn duration(&self) -> Option<TimeDelta> {
+        let once = self.animation.duration()?;
+        is![once.is_zero(), return Some(once)];
+        let times = i32::try_from(self.times?.max(1)).unwrap_or(i32::MAX);
+        Some(once.saturating_mul(times))
+    }
+    fn sample(&self, time: TimeDelta) -> A::Value {
+        let Some(once) = self.animation.duration().filter(|d| d.is_positive()) else {
+            return self.animation.sample(time);
+        };
+        let (once_ns, time_ns) = (once.as_nanos(), time.as_nanos().max(0));
+        let (mut play, mut local) = (time_ns / once_ns, time_ns % once_ns);
+        if let Some(times) = self.times
+            && play >= i128::from(times.max(1))
+        {
+            (play, local) = (i128::from(times.max(1)) - 1, once_ns);
+        }
+        if self.yoyo && play % 2 == 1 {
+            local = once_ns - local;
+        }
+        self.animation.sample(delta_from_nanos(local))
+    }
+}
+
+/* helpers */
+
+/// Returns the time delta of a number of nanoseconds that fits in it.
+const fn delta_from_nanos(nanos: i128) -> TimeDelta {
+    const NANOS_PER_SEC: i128 = 1_000_000_000;
+    TimeDelta::new((nanos / NANOS_PER_SEC) as i64, (nanos % NANOS_PER_SEC) as i32)
+}
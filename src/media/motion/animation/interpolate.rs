@@ -0,0 +1,100 @@
+// devela/src/media/motion/animation/interpolate.rs
+//
+//! Defines [`Interpolate`].
+//
+
+#[cfg(feature = "alg")]
+use crate::Vector;
+use crate::{Angle, Float};
+#[cfg(feature = "color")]
+use crate::{Rgb, Rgba};
+
+#[doc = crate::_tags!(time)]
+/// Values that can be interpolated between two endpoints.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It's implemented for the primitive numbers, arrays of interpolable values,
+/// [`Angle`]s of floats along the shorter arc, `Vector`s by coordinate,
+/// and `Rgb` and `Rgba` colors by channel.
+///
+/// # Example
+/// ```
+/// # use devela::{Angle, Interpolate};
+/// assert_eq![10_u8.interpolate(&20, 0.25), 13];
+/// assert_eq![[0.0_f32, 10.0].interpolate(&[1.0, 20.0], 0.5), [0.5, 15.0]];
+///
+/// // from 350° to 10° through 0°
+/// let turn = Angle::<f32>::from_deg(350.0).interpolate(&Angle::<f32>::from_deg(10.0), 0.5);
+/// assert![(turn.to_deg() - 360.0).abs() < 1e-3];
+///
+/// # #[cfg(feature = "color")] {
+/// # use devela::Rgb8;
+/// let (red, blue) = (Rgb8::new(255, 0, 0), Rgb8::new(0, 0, 255));
+/// assert_eq![red.interpolate(&blue, 0.5), Rgb8::new(128, 0, 128)];
+/// # }
+/// ```
+pub trait Interpolate {
+    /// Returns the value at `t` of the way from `self` to `to`.
+    ///
+    /// `t` is usually in `0..=1`, and extrapolates outside of it.
+    #[must_use]
+    fn interpolate(&self, to: &Self, t: f32) -> Self;
+}
+
+macro_rules! impl_interpolate {
+    () => {
+        impl_interpolate![float: f32, f64];
+        impl_interpolate![int: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize];
+    };
+    (float: $($f:ty),+) => { $(
+        impl Interpolate for $f {
+            fn interpolate(&self, to: &Self, t: f32) -> Self {
+                self + (to - self) * t as $f
+            }
+        }
+        impl Interpolate for Angle<$f> {
+            /// Interpolates along the shorter arc, which may leave the result unnormalized.
+            fn interpolate(&self, to: &Self, t: f32) -> Self {
+                let arc = to.turn - self.turn;
+                let arc = arc - Float(arc).const_round().0;
+                Angle::new(self.turn + arc * t as $f)
+            }
+        }
+    )+ };
+    (int: $($t:ty),+) => { $(
+        /// Rounds to the nearest value, saturating.
+        impl Interpolate for $t {
+            fn interpolate(&self, to: &Self, t: f32) -> Self {
+                let (from, to) = (*self as f64, *to as f64);
+                Float(from + (to - from) * t as f64).const_round().0 as $t
+            }
+        }
+    )+ };
+}
+impl_interpolate!();
+
+impl<T: Interpolate, const N: usize> Interpolate for [T; N] {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        ::core::array::from_fn(|i| self[i].interpolate(&to[i], t))
+    }
+}
+#[cfg(feature = "color")]
+impl<T: Interpolate, const LINEAR: bool> Interpolate for Rgb<T, LINEAR> {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Rgb { c: self.c.interpolate(&to.c, t) }
+    }
+}
+#[cfg(feature = "color")]
+impl<T: Interpolate, const LINEAR: bool, const PREMUL: bool> Interpolate
+    for Rgba<T, LINEAR, PREMUL>
+{
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Rgba { c: self.c.interpolate(&to.c, t) }
+    }
+}
+#[cfg(feature = "alg")]
+impl<T: Interpolate, const D: usize> Interpolate for Vector<T, D> {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Vector { coords: self.coords.interpolate(&to.coords, t) }
+    }
+}
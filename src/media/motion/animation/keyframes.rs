@@ -0,0 +1,102 @@
+// devela/src/media/motion/animation/keyframes.rs
+//
+//! Defines [`Keyframe`], [`Keyframes`].
+//
+
+use crate::{Animation, Easing, Interpolate, TimeDelta, is};
+
+#[doc = crate::_tags!(time)]
+/// A value at a point in time of a [`Keyframes`] track.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Keyframe<T> {
+    /// The time since the start of the track.
+    pub time: TimeDelta,
+    /// The value at that time.
+    pub value: T,
+    /// The easing of the change towards the next keyframe.
+    pub easing: Easing,
+}
+
+impl<T> Keyframe<T> {
+    /// Returns a keyframe of `value` at `time`, changing linearly to the next one.
+    pub const fn new(time: TimeDelta, value: T) -> Self {
+        Self { time, value, easing: Easing::Linear }
+    }
+    /// Returns the same keyframe, changing to the next one shaped by `easing`.
+    pub fn with_easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+}
+
+#[doc = crate::_tags!(time)]
+/// A track of keyframes, interpolated between each pair of consecutive ones.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It borrows its keyframes, ordered by time, and lasts until the last one.
+/// Before the first keyframe it holds its value, and keyframes that share
+/// a time make it jump between their values.
+///
+/// # Example
+/// ```
+/// # use devela::{Animation, Easing, EasingFamily, Keyframe, Keyframes, TimeDelta};
+/// let ms = TimeDelta::from_millis;
+/// let keys = [
+///     Keyframe::new(ms(0), [0.0_f32, 0.0]),
+///     Keyframe::new(ms(100), [10.0, 0.0]).with_easing(Easing::In(EasingFamily::Quad)),
+///     Keyframe::new(ms(300), [10.0, 20.0]),
+/// ];
+/// let track = Keyframes::new(&keys).unwrap();
+/// assert_eq![track.duration(), Some(ms(300))];
+/// assert_eq![track.sample(ms(50)), [5.0, 0.0]];
+/// assert_eq![track.sample(ms(200)), [10.0, 5.0]];
+///
+/// let unordered = [Keyframe::new(ms(10), 1.0_f32), Keyframe::new(ms(0), 0.0)];
+/// assert![Keyframes::new(&unordered).is_none()];
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Keyframes<'a, T> {
+    keys: &'a [Keyframe<T>],
+}
+impl<T> Clone for Keyframes<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Keyframes<'_, T> {}
+
+impl<'a, T> Keyframes<'a, T> {
+    /// Returns a track of `keys`,
+    /// or `None` if there are none or they aren't ordered by time.
+    pub const fn new(keys: &'a [Keyframe<T>]) -> Option<Self> {
+        is![keys.is_empty(), return None];
+        let mut i = 1;
+        while i < keys.len() {
+            is![keys[i].time.as_nanos() < keys[i - 1].time.as_nanos(), return None];
+            i += 1;
+        }
+        Some(Self { keys })
+    }
+    /// Returns the keyframes.
+    pub const fn keys(&self) -> &'a [Keyframe<T>] {
+        self.keys
+    }
+}
+
+impl<T: Interpolate + Clone> Animation for Keyframes<'_, T> {
+    type Value = T;
+    fn duration(&self) -> Option<TimeDelta> {
+        self.keys.last().map(|key| key.time)
+    }
+    fn sample(&self, time: TimeDelta) -> T {
+        let next = self.keys.partition_point(|key| key.time <= time);
+        match (next.checked_sub(1).map(|i| &self.keys[i]), self.keys.get(next)) {
+            (Some(key), Some(next)) => {
+                let t = (time.saturating_sub(key.time)).div_delta_f32(next.time - key.time);
+                key.value.interpolate(&next.value, key.easing.ease(t))
+            }
+            (Some(key), None) | (None, Some(key)) => key.value.clone(),
+            (None, None) => unreachable!["a track is never empty"],
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// devela/src/media/motion/animation/mod.rs
+//
+//! Timed changes of values, composed and played over time.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod animation; // Animation, AnimationDelay, AnimationPar, AnimationRepeat, AnimationSeq
+mod interpolate; // Interpolate
+mod keyframes; // Keyframe, Keyframes
+mod timeline; // Timeline
+mod tween; // Tween
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            animation::*,
+            interpolate::*,
+            keyframes::*,
+            timeline::*,
+            tween::*,
+        };
+    }
+}
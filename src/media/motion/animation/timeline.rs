@@ -0,0 +1,119 @@
+// devela/src/media/motion/animation/timeline.rs
+//
+//! Defines [`Timeline`].
+//
+
+use crate::{Animation, TimeDelta, is};
+
+#[doc = crate::_tags!(time)]
+/// A player of an [`Animation`], advanced by the elapsed time of each frame.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// It keeps the current time of the animation, clamped to its duration,
+/// and can be paused, sped up, played backwards and seeked.
+///
+/// # Example
+/// ```
+/// # use devela::{Animation, TimeDelta, Timeline, Tween};
+/// let ms = TimeDelta::from_millis;
+/// let slide = Tween::new(0_i32, 100, ms(100));
+/// let appear = Tween::new(0.0_f32, 1.0, ms(50));
+/// let mut timeline = Timeline::new(slide.with(appear));
+///
+/// assert_eq![timeline.advance(ms(25)), (25, 0.5)];
+/// assert_eq![timeline.advance(ms(50)), (75, 1.0)];
+/// assert![!timeline.is_finished()];
+/// assert_eq![timeline.advance(ms(50)), (100, 1.0)];
+/// assert![timeline.is_finished()];
+///
+/// timeline.set_speed(-2.0);
+/// assert_eq![timeline.advance(ms(10)), (80, 1.0)];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timeline<A> {
+    animation: A,
+    time: TimeDelta,
+    speed: f32,
+    paused: bool,
+}
+
+impl<A> Timeline<A> {
+    /// Returns a timeline at the start of `animation`, playing at normal speed.
+    pub const fn new(animation: A) -> Self {
+        Self {
+            animation,
+            time: TimeDelta::ZERO,
+            speed: 1.0,
+            paused: false,
+        }
+    }
+
+    /// Returns the animation.
+    pub const fn animation(&self) -> &A {
+        &self.animation
+    }
+    /// Returns the animation, exclusively.
+    pub const fn animation_mut(&mut self) -> &mut A {
+        &mut self.animation
+    }
+    /// Returns the animation.
+    pub fn into_inner(self) -> A {
+        self.animation
+    }
+
+    /// Returns the current time.
+    pub const fn time(&self) -> TimeDelta {
+        self.time
+    }
+    /// Returns the speed, negative when playing backwards.
+    pub const fn speed(&self) -> f32 {
+        self.speed
+    }
+    /// Sets the speed, negative to play backwards.
+    pub const fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+    /// Returns whether it's paused.
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+    /// Pauses it or resumes it.
+    pub const fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+}
+
+impl<A: Animation> Timeline<A> {
+    /// Advances the time by `delta` scaled by the speed, unless paused,
+    /// and returns the current value.
+    pub fn advance(&mut self, delta: TimeDelta) -> A::Value {
+        if !self.paused {
+            self.seek(self.time.saturating_add(delta.mul_f32(self.speed)));
+        }
+        self.value()
+    }
+    /// Sets the current time, clamped to the duration of the animation.
+    pub fn seek(&mut self, time: TimeDelta) {
+        let time = time.max(TimeDelta::ZERO);
+        self.time = self.animation.duration().map_or(time, |duration| time.min(duration));
+    }
+    /// Seeks the start.
+    pub fn reset(&mut self) {
+        self.time = TimeDelta::ZERO;
+    }
+
+    /// Returns the value at the current time.
+    pub fn value(&self) -> A::Value {
+        self.animation.sample(self.time)
+    }
+    /// Returns whether it's at the end of the animation.
+    pub fn is_finished(&self) -> bool {
+        self.animation.is_finished(self.time)
+    }
+    /// Returns the fraction of the animation played, from `0` to `1`,
+    /// or `None` if it never ends.
+    pub fn progress(&self) -> Option<f32> {
+        let duration = self.animation.duration()?;
+        Some(is![duration.is_positive(), self.time.div_delta_f32(duration), 1.0])
+    }
+}
@@ -0,0 +1,79 @@
+// devela/src/media/motion/animation/tween.rs
+//
+//! Defines [`Tween`].
+//
+
+use crate::{Animation, Easing, Interpolate, TimeDelta, is};
+
+#[doc = crate::_tags!(time)]
+/// A change of a value from one endpoint to another, shaped by an easing curve.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// Any [`Interpolate`] value can be tweened, like numbers, colors, angles or vectors.
+///
+/// # Example
+/// ```
+/// # use devela::{Animation, Easing, EasingFamily, TimeDelta, Tween};
+/// let slide = Tween::new([0_i32, 0], [200, 100], TimeDelta::from_secs(2))
+///     .with_easing(Easing::In(EasingFamily::Quad));
+/// assert_eq![slide.sample(TimeDelta::from_secs(1)), [50, 25]];
+/// assert_eq![slide.sample(TimeDelta::from_secs(5)), [200, 100]];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: TimeDelta,
+    easing: Easing,
+}
+
+impl<T> Tween<T> {
+    /// Returns a linear change from `from` to `to` lasting `duration`.
+    pub const fn new(from: T, to: T, duration: TimeDelta) -> Self {
+        Self { from, to, duration, easing: Easing::Linear }
+    }
+    /// Returns the same change shaped by `easing`.
+    pub fn with_easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+    /// Returns the same change lasting `duration`.
+    pub fn with_duration(self, duration: TimeDelta) -> Self {
+        Self { duration, ..self }
+    }
+    /// Returns the change reversed, from `to` to `from`, with the same easing.
+    pub fn reversed(self) -> Self {
+        Self { from: self.to, to: self.from, ..self }
+    }
+
+    /// Returns the starting value.
+    pub const fn from(&self) -> &T {
+        &self.from
+    }
+    /// Returns the ending value.
+    pub const fn to(&self) -> &T {
+        &self.to
+    }
+    /// Returns the easing curve.
+    pub const fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Returns the eased progress at `time`, from `0` to `1` but for overshooting curves.
+    ///
+    /// A tween that lasts nothing is always at its end.
+    #[must_use]
+    pub const fn progress(&self, time: TimeDelta) -> f32 {
+        is![!self.duration.is_positive(), return 1.0];
+        self.easing.ease(time.div_delta_f32(self.duration))
+    }
+}
+
+impl<T: Interpolate> Animation for Tween<T> {
+    type Value = T;
+    fn duration(&self) -> Option<TimeDelta> {
+        Some(self.duration)
+    }
+    fn sample(&self, time: TimeDelta) -> T {
+        self.from.interpolate(&self.to, self.progress(time))
+    }
+}
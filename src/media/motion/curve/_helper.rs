@@ -0,0 +1,21 @@
+// devela/src/media/motion/curve/_helper.rs
+//
+//! Math shared by the easing curves.
+//
+
+use crate::{Float, is};
+
+/// Returns `2^x`, scaling the series over the fractional part by the integer part.
+pub(super) const fn exp2(x: f32) -> f32 {
+    is![x < -126.0, return 0.0];
+    is![x >= 128.0, return f32::INFINITY];
+    let int = Float(x).const_floor().0;
+    let fract = Float(x - int);
+    let scale = f32::from_bits(((int as i32 + 127) as u32) << 23);
+    fract.exp2_series(fract.exp2_series_terms()).0 * scale
+}
+
+/// Returns `e^x`.
+pub(super) const fn exp(x: f32) -> f32 {
+    exp2(x * ::core::f32::consts::LOG2_E)
+}
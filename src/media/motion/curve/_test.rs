@@ -0,0 +1,80 @@
+// devela/src/media/motion/curve/_test.rs
+
+#![allow(clippy::float_cmp, reason = "the expected values are exact")]
+
+use crate::{Easing, EasingBezier, EasingFamily as F, EasingSpring};
+
+fn near(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+#[test]
+fn easings_keep_their_endpoints() {
+    use F::*;
+    for family in [Quad, Cubic, Quart, Quint, Sine, Expo, Circ, Back, Elastic, Bounce] {
+        for easing in [Easing::In(family), Easing::Out(family), Easing::InOut(family)] {
+            assert!(near(easing.ease(0.0), 0.0), "{easing:?}");
+            assert!(near(easing.ease(1.0), 1.0), "{easing:?}");
+            assert!(near(easing.ease(-3.0), 0.0), "{easing:?}");
+            assert!(near(easing.ease(3.0), 1.0), "{easing:?}");
+        }
+        let half = Easing::InOut(family).ease(0.5);
+        assert!(near(half, 0.5), "{family:?} {half}");
+    }
+}
+#[test]
+fn easings_are_mirrored() {
+    let t = 0.3;
+    let (ease_in, ease_out) = (Easing::In(F::Quart), Easing::Out(F::Quart));
+    assert!(near(ease_out.ease(t), 1.0 - ease_in.ease(1.0 - t)));
+    let in_out = Easing::InOut(F::Sine);
+    assert!(near(in_out.ease(t), 1.0 - in_out.ease(1.0 - t)));
+    assert!(Easing::In(F::Back).ease(0.2) < 0.0);
+    assert!(Easing::Out(F::Elastic).ease(0.2) > 1.0);
+}
+#[test]
+fn steps_jump_at_the_end_of_each_step() {
+    let steps = Easing::Steps(4);
+    assert_eq!(steps.ease(0.24), 0.0);
+    assert_eq!(steps.ease(0.25), 0.25);
+    assert_eq!(steps.ease(0.99), 0.75);
+    assert_eq!(steps.ease(1.0), 1.0);
+    assert_eq!(Easing::Steps(0).ease(0.5), 0.0);
+}
+#[test]
+fn bezier_matches_css_curves() {
+    let linear = EasingBezier::new(0.25, 0.25, 0.75, 0.75);
+    for i in 0..=10 {
+        let t = i as f32 / 10.0;
+        assert!(near(linear.ease(t), t));
+    }
+    // reference values of `cubic-bezier(.25, .1, .25, 1)`
+    assert!(near(EasingBezier::EASE.ease(0.25), 0.4094));
+    assert!(near(EasingBezier::EASE.ease(0.5), 0.8024));
+    assert!(near(EasingBezier::EASE_IN.ease(0.5), 0.3153));
+    assert!(near(EasingBezier::EASE_OUT.ease(0.5), 0.6847));
+    assert_eq!(EasingBezier::new(-1.0, 0.0, 2.0, 1.0).points(), [0.0, 0.0, 1.0, 1.0]);
+}
+#[test]
+fn springs_settle_at_the_target() {
+    let critical = EasingSpring::critical(100.0, 1.0);
+    assert!(near(critical.damping_ratio(), 1.0));
+    let overdamped = EasingSpring::new(100.0, 40.0, 1.0);
+    for spring in
+        [EasingSpring::GENTLE, EasingSpring::WOBBLY, EasingSpring::STIFF, critical, overdamped]
+    {
+        assert_eq!(spring.position(0.0), 0.0);
+        let settle = spring.settling_time();
+        assert!(settle > 0.0 && settle.is_finite(), "{spring:?}");
+        for i in 0..8 {
+            let after = settle * (1.0 + i as f32 / 4.0);
+            assert!((spring.position(after) - 1.0).abs() <= EasingSpring::SETTLED * 1.01);
+        }
+        assert_eq!(spring.ease(1.0), 1.0);
+    }
+    // the underdamped spring overshoots, the others don't
+    let peak = |s: EasingSpring| (0..200).map(|i| s.position(i as f32 / 100.0)).fold(0.0, f32::max);
+    assert!(peak(EasingSpring::WOBBLY) > 1.1);
+    assert!(peak(critical) <= 1.0);
+    assert!(peak(overdamped) <= 1.0);
+}
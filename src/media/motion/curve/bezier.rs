@@ -0,0 +1,117 @@
+// devela/src/media/motion/curve/bezier.rs
+//
+//! Defines [`EasingBezier`].
+//
+// https://www.w3.org/TR/css-easing-1/#cubic-bezier-easing-functions
+
+use crate::{Float, is};
+
+#[doc = crate::_tags!(time)]
+/// A cubic Bézier easing curve, like the `cubic-bezier()` function of CSS.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// The curve starts at `(0, 0)` and ends at `(1, 1)`, shaped by two control points.
+/// Their `x` coordinates are clamped to `0..=1`, so that the curve is a function
+/// of time, while their `y` coordinates can overshoot.
+///
+/// # Example
+/// ```
+/// # use devela::EasingBezier;
+/// let linear = EasingBezier::new(0.0, 0.0, 1.0, 1.0);
+/// assert![(linear.ease(0.3) - 0.3).abs() < 1e-5];
+/// assert![EasingBezier::EASE_IN.ease(0.3) < 0.3];
+/// assert![EasingBezier::EASE_OUT.ease(0.3) > 0.3];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EasingBezier {
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+}
+
+impl Default for EasingBezier {
+    /// Returns [`EASE`][Self::EASE], the default of CSS transitions.
+    fn default() -> Self {
+        Self::EASE
+    }
+}
+
+impl EasingBezier {
+    /// The `ease` curve of CSS, `cubic-bezier(0.25, 0.1, 0.25, 1)`.
+    pub const EASE: Self = Self::new(0.25, 0.1, 0.25, 1.0);
+    /// The `ease-in` curve of CSS, `cubic-bezier(0.42, 0, 1, 1)`.
+    pub const EASE_IN: Self = Self::new(0.42, 0.0, 1.0, 1.0);
+    /// The `ease-out` curve of CSS, `cubic-bezier(0, 0, 0.58, 1)`.
+    pub const EASE_OUT: Self = Self::new(0.0, 0.0, 0.58, 1.0);
+    /// The `ease-in-out` curve of CSS, `cubic-bezier(0.42, 0, 0.58, 1)`.
+    pub const EASE_IN_OUT: Self = Self::new(0.42, 0.0, 0.58, 1.0);
+
+    /// Returns the curve with the control points `(x1, y1)` and `(x2, y2)`.
+    ///
+    /// The `x` coordinates are clamped to `0..=1`.
+    #[must_use]
+    pub const fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        let (x1, x2) = (Float(x1).clamp(0.0, 1.0).0, Float(x2).clamp(0.0, 1.0).0);
+        Self { x1, y1, x2, y2 }
+    }
+    /// Returns the control points, as `[x1, y1, x2, y2]`.
+    #[must_use]
+    pub const fn points(self) -> [f32; 4] {
+        [self.x1, self.y1, self.x2, self.y2]
+    }
+
+    /// Returns the `y` coordinate of the curve where its `x` coordinate is `progress`.
+    ///
+    /// The progress is clamped to `0..=1`.
+    #[must_use]
+    pub const fn ease(self, progress: f32) -> f32 {
+        let x = Float(progress).clamp(0.0, 1.0).0;
+        is![x <= 0.0 || x >= 1.0, return x];
+        bezier(self.y1, self.y2, self.solve(x))
+    }
+
+    /// Returns the parameter of the curve where its `x` coordinate is `x`.
+    ///
+    /// Tries a few Newton iterations, and else bisects, since the
+    /// `x` coordinate always increases with the parameter.
+    const fn solve(self, x: f32) -> f32 {
+        const EPSILON: f32 = 1e-6;
+        let mut s = x;
+        let mut i = 0;
+        while i < 8 {
+            let error = bezier(self.x1, self.x2, s) - x;
+            is![Float(error).abs().0 < EPSILON, return s];
+            let slope = bezier_slope(self.x1, self.x2, s);
+            is![Float(slope).abs().0 < EPSILON, break];
+            s -= error / slope;
+            i += 1;
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        s = x;
+        i = 0;
+        while i < 32 {
+            let error = bezier(self.x1, self.x2, s) - x;
+            is![Float(error).abs().0 < EPSILON, return s];
+            if error > 0.0 {
+                high = s;
+            } else {
+                low = s;
+            }
+            s = (low + high) / 2.0;
+            i += 1;
+        }
+        s
+    }
+}
+
+/// Evaluates a coordinate of the curve from `0` to `1` with the controls `p1` and `p2`.
+const fn bezier(p1: f32, p2: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+/// The derivative of [`bezier`] over its parameter.
+const fn bezier_slope(p1: f32, p2: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * p1 + 6.0 * r * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+}
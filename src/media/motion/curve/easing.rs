@@ -0,0 +1,154 @@
+// devela/src/media/motion/curve/easing.rs
+//
+//! Defines [`Easing`], [`EasingFamily`].
+//
+// https://easings.net/
+// https://robertpenner.com/easing/
+
+use super::_helper::exp2;
+use crate::{EasingBezier, EasingSpring, Float, is};
+use ::core::f32::consts::{FRAC_PI_2, TAU};
+
+#[doc = crate::_tags!(time)]
+/// An easing curve, mapping the linear progress of a change to its shaped progress.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// Every curve maps `0` to `0` and `1` to `1`, and the progress given is clamped to `0..=1`.
+/// In between, curves like [`Back`][EasingFamily::Back] or
+/// [`Elastic`][EasingFamily::Elastic] overshoot that range.
+///
+/// # Example
+/// ```
+/// # use devela::{Easing, EasingBezier, EasingFamily as F};
+/// assert_eq![Easing::In(F::Quad).ease(0.5), 0.25];
+/// assert_eq![Easing::Out(F::Quad).ease(0.5), 0.75];
+/// assert_eq![Easing::InOut(F::Cubic).ease(0.25), 0.0625];
+/// assert_eq![Easing::Steps(4).ease(0.6), 0.5];
+///
+/// let css = Easing::Bezier(EasingBezier::EASE_IN_OUT);
+/// assert![(css.ease(0.5) - 0.5).abs() < 1e-5];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slow, accelerating with the curve of the family.
+    In(EasingFamily),
+    /// Ends slow, the [`In`][Self::In] curve reversed in time.
+    Out(EasingFamily),
+    /// Starts and ends slow, the [`In`][Self::In] curve over the first half
+    /// and the [`Out`][Self::Out] curve over the second.
+    InOut(EasingFamily),
+    /// Jumps in a number of equal steps, at the end of each interval,
+    /// like the `steps(n)` function of CSS.
+    ///
+    /// Zero steps behave as one.
+    Steps(u16),
+    /// A cubic Bézier curve, like the `cubic-bezier()` function of CSS.
+    Bezier(EasingBezier),
+    /// A damped spring released towards the end.
+    Spring(EasingSpring),
+}
+
+impl Easing {
+    /// Returns the eased progress for a linear `progress`, clamped to `0..=1`.
+    #[must_use]
+    pub const fn ease(self, progress: f32) -> f32 {
+        let t = Float(progress).clamp(0.0, 1.0).0;
+        match self {
+            Easing::Linear => t,
+            Easing::In(family) => family.ease_in(t),
+            Easing::Out(family) => 1.0 - family.ease_in(1.0 - t),
+            Easing::InOut(family) => {
+                if t < 0.5 {
+                    family.ease_in(2.0 * t) / 2.0
+                } else {
+                    1.0 - family.ease_in(2.0 - 2.0 * t) / 2.0
+                }
+            }
+            Easing::Steps(steps) => {
+                let steps = is![steps == 0, 1.0, steps as f32];
+                Float(t * steps).const_floor().0 / steps
+            }
+            Easing::Bezier(bezier) => bezier.ease(t),
+            Easing::Spring(spring) => spring.ease(t),
+        }
+    }
+}
+
+#[doc = crate::_tags!(time)]
+/// The families of the classic easing curves of Robert Penner.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// Each one is defined by its accelerating curve, used by [`Easing::In`],
+/// and mirrored by [`Easing::Out`] and [`Easing::InOut`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EasingFamily {
+    /// A quadratic curve, `t²`.
+    Quad,
+    /// A cubic curve, `t³`.
+    #[default]
+    Cubic,
+    /// A quartic curve, `t⁴`.
+    Quart,
+    /// A quintic curve, `t⁵`.
+    Quint,
+    /// A quarter of a cosine wave.
+    Sine,
+    /// An exponential curve, `2^(10t - 10)`.
+    Expo,
+    /// A quarter of a circle.
+    Circ,
+    /// A cubic curve that backs up before going forward.
+    Back,
+    /// An exponentially growing sine wave, like a stretched rubber band.
+    Elastic,
+    /// A sequence of decreasing bounces.
+    Bounce,
+}
+
+impl EasingFamily {
+    /// Returns the accelerating curve at `t`, in `0..=1`.
+    #[must_use]
+    pub const fn ease_in(self, t: f32) -> f32 {
+        const BACK: f32 = 1.701_58;
+        match self {
+            Self::Quad => t * t,
+            Self::Cubic => t * t * t,
+            Self::Quart => t * t * t * t,
+            Self::Quint => t * t * t * t * t,
+            Self::Sine => 1.0 - Float(t * FRAC_PI_2).cos_minimax().0,
+            Self::Expo => is![t <= 0.0, 0.0, exp2(10.0 * t - 10.0)],
+            Self::Circ => 1.0 - Float(1.0 - t * t).sqrt_nr().0,
+            Self::Back => t * t * ((BACK + 1.0) * t - BACK),
+            Self::Elastic => {
+                if t <= 0.0 || t >= 1.0 {
+                    t
+                } else {
+                    let wave = Float((10.0 * t - 10.75) * TAU / 3.0).sin_minimax().0;
+                    -exp2(10.0 * t - 10.0) * wave
+                }
+            }
+            Self::Bounce => 1.0 - bounce_out(1.0 - t),
+        }
+    }
+}
+
+/// The decelerating bounces, as a sequence of parabolas.
+const fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984_375
+    }
+}
@@ -0,0 +1,22 @@
+// devela/src/media/motion/curve/mod.rs
+//
+//! Easing curves that shape the progress of a change.
+//
+
+#[cfg(test)]
+mod _test;
+mod _helper; // easing math
+
+mod bezier; // EasingBezier
+mod easing; // Easing, EasingFamily
+mod spring; // EasingSpring
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            bezier::*,
+            easing::*,
+            spring::*,
+        };
+    }
+}
@@ -0,0 +1,155 @@
+// devela/src/media/motion/curve/spring.rs
+//
+//! Defines [`EasingSpring`].
+//
+// https://en.wikipedia.org/wiki/Harmonic_oscillator#Damped_harmonic_oscillator
+
+use super::_helper::exp;
+use crate::{Float, is};
+
+#[doc = crate::_tags!(time)]
+/// A damped spring easing curve, a mass pulled from rest towards its target.
+#[doc = crate::_doc_meta!{location("media/motion")}]
+///
+/// The motion follows the analytic solution of a damped harmonic oscillator.
+/// Below critical damping it oscillates around the target, at critical damping
+/// it reaches it as fast as possible without overshooting, and above it it
+/// creeps towards it.
+///
+/// As an easing curve, the progress from `0` to `1` spans its
+/// [`settling_time`][Self::settling_time], ending exactly at the target.
+///
+/// # Example
+/// ```
+/// # use devela::EasingSpring;
+/// let bouncy = EasingSpring::new(170.0, 8.0, 1.0);
+/// assert![bouncy.damping_ratio() < 1.0];
+/// assert![bouncy.position(0.2) > 1.0]; // overshoots
+///
+/// let smooth = EasingSpring::critical(100.0, 1.0);
+/// assert![(smooth.damping_ratio() - 1.0).abs() < 1e-6];
+/// assert![(0..=100).all(|i| smooth.ease(i as f32 / 100.0) <= 1.0)];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EasingSpring {
+    stiffness: f32,
+    damping: f32,
+    mass: f32,
+}
+
+impl Default for EasingSpring {
+    /// Returns [`GENTLE`][Self::GENTLE].
+    fn default() -> Self {
+        Self::GENTLE
+    }
+}
+
+impl EasingSpring {
+    /// A gentle spring that overshoots a little, with a stiffness of 100 and a damping of 10.
+    pub const GENTLE: Self = Self::new(100.0, 10.0, 1.0);
+    /// A wobbly spring that oscillates for a while, with a stiffness of 180 and a damping of 6.
+    pub const WOBBLY: Self = Self::new(180.0, 6.0, 1.0);
+    /// A stiff spring that settles quickly, with a stiffness of 260 and a damping of 20.
+    pub const STIFF: Self = Self::new(260.0, 20.0, 1.0);
+
+    /// The fraction of the distance left at which the spring is considered settled.
+    pub const SETTLED: f32 = 0.001;
+
+    /// The distance of the damping ratio to 1 under which it's treated as critical.
+    const NEAR_CRITICAL: f32 = 1e-3;
+
+    /// Returns a spring of the given `stiffness`, `damping` and `mass`.
+    ///
+    /// Each one is made at least a tiny positive value.
+    #[must_use]
+    pub const fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        const MIN: f32 = 1e-6;
+        let stiffness = is![stiffness > MIN, stiffness, MIN];
+        let damping = is![damping > MIN, damping, MIN];
+        let mass = is![mass > MIN, mass, MIN];
+        Self { stiffness, damping, mass }
+    }
+    /// Returns a critically damped spring of the given `stiffness` and `mass`.
+    #[must_use]
+    pub const fn critical(stiffness: f32, mass: f32) -> Self {
+        let s = Self::new(stiffness, 1.0, mass);
+        let damping = 2.0 * Float(s.stiffness * s.mass).sqrt_nr().0;
+        Self { damping, ..s }
+    }
+
+    /// Returns the stiffness.
+    #[must_use]
+    pub const fn stiffness(self) -> f32 {
+        self.stiffness
+    }
+    /// Returns the damping.
+    #[must_use]
+    pub const fn damping(self) -> f32 {
+        self.damping
+    }
+    /// Returns the mass.
+    #[must_use]
+    pub const fn mass(self) -> f32 {
+        self.mass
+    }
+
+    /// Returns the undamped angular frequency `ω₀ = √(k/m)`, in radians per second.
+    #[must_use]
+    pub const fn angular_frequency(self) -> f32 {
+        Float(self.stiffness / self.mass).sqrt_nr().0
+    }
+    /// Returns the damping ratio `ζ = c / 2√(km)`, which is 1 at critical damping.
+    #[must_use]
+    pub const fn damping_ratio(self) -> f32 {
+        self.damping / (2.0 * Float(self.stiffness * self.mass).sqrt_nr().0)
+    }
+
+    /// Returns the position after `secs` seconds of moving from `0` towards `1`.
+    #[must_use]
+    pub const fn position(self, secs: f32) -> f32 {
+        is![secs <= 0.0, return 0.0];
+        let (w0, zeta) = (self.angular_frequency(), self.damping_ratio());
+        if zeta < 1.0 - Self::NEAR_CRITICAL {
+            let wd = w0 * Float(1.0 - zeta * zeta).sqrt_nr().0;
+            let (sin, cos) = Float(wd * secs).sin_cos_minimax();
+            1.0 - exp(-zeta * w0 * secs) * (cos.0 + zeta * w0 / wd * sin.0)
+        } else if zeta <= 1.0 + Self::NEAR_CRITICAL {
+            1.0 - exp(-w0 * secs) * (1.0 + w0 * secs)
+        } else {
+            let root = w0 * Float(zeta * zeta - 1.0).sqrt_nr().0;
+            let (r1, r2) = (-zeta * w0 + root, -zeta * w0 - root);
+            1.0 - (r2 * exp(r1 * secs) - r1 * exp(r2 * secs)) / (r2 - r1)
+        }
+    }
+
+    /// Returns the seconds it takes for the distance left to the target,
+    /// or the envelope of its oscillation, to fall below [`SETTLED`][Self::SETTLED].
+    #[must_use]
+    pub const fn settling_time(self) -> f32 {
+        // ln(1 / SETTLED)
+        const LN: f32 = 6.907_755;
+        let (w0, zeta) = (self.angular_frequency(), self.damping_ratio());
+        if zeta < 1.0 - Self::NEAR_CRITICAL {
+            LN / (zeta * w0)
+        } else {
+            // the slowest decay, refined for the linear factor near critical damping
+            let excess = zeta * zeta - 1.0;
+            let rate = w0 * (zeta - Float(is![excess > 0.0, excess, 0.0]).sqrt_nr().0);
+            let mut secs = LN / rate;
+            let mut i = 0;
+            while i < 16 && 1.0 - self.position(secs) > Self::SETTLED {
+                secs *= 1.125;
+                i += 1;
+            }
+            secs
+        }
+    }
+
+    /// Returns the position at a `progress` from `0` to `1` of its
+    /// [`settling_time`][Self::settling_time], ending exactly at `1`.
+    #[must_use]
+    pub const fn ease(self, progress: f32) -> f32 {
+        is![progress >= 1.0, return 1.0];
+        self.position(progress * self.settling_time())
+    }
+}
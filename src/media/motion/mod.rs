@@ -4,12 +4,15 @@
 #![doc = crate::_doc!(modules: crate::media; motion)]
 #![doc = crate::_doc!(flat:"media")]
 #![doc = crate::_doc!(hr)]
+//
+// safety
+#![cfg_attr(feature = "safe_motion", forbid(unsafe_code))]
 
-// mod animation;   // composition of timed changes
+mod animation; // composition of timed changes
 // mod object;      // primary motion
 
 // mod camera;      // secondary/view motion
-// mod curve;       // easing, envelopes, splines
+mod curve; // easing, envelopes, splines
 
 // mod gesture;     // expressive motion primitives
 // mod rhythm;      // pulses, meters, cycles, trigger grids
@@ -19,9 +22,9 @@
 
 crate::structural_mods! { // _mods
     _mods {
-        // pub use super::{
-        //     object::*,
-        //     camera::*,
-        // };
+        pub use super::{
+            animation::_all::*,
+            curve::_all::*,
+        };
     }
 }
@@ -48,7 +48,7 @@ macro_rules! _media_visual_color_impl_gamma {
             pub fn encode(self, v: $T) -> $T { v.powf(self.exp.recip()) }
             #[doc = _DOC_GAMMA_ENCODE!()]
             pub const fn const_encode(self, v: $T) -> $T {
-                let terms = Float(v).exp_series_terms();
+                let terms = Float(v).ln_series_terms();
                 Float(v).powf_series(self.exp.recip(), terms).0
             }
 
@@ -57,7 +57,7 @@ macro_rules! _media_visual_color_impl_gamma {
             pub fn decode(self, v: $T) -> $T { v.powf(self.exp) }
             #[doc = _DOC_GAMMA_ENCODE!()]
             pub const fn const_decode(self, v: $T) -> $T {
-                let terms = Float(v).exp_series_terms();
+                let terms = Float(v).ln_series_terms();
                 Float(v).powf_series(self.exp, terms).0
             }
 
@@ -72,7 +72,7 @@ macro_rules! _media_visual_color_impl_gamma {
             pub const fn const_encode_srgb(self, v: $T) -> $T {
                 if v <= 0.003_130_8 { 12.92 * v }
                 else {
-                    let terms = Float(v).exp_series_terms();
+                    let terms = Float(v).ln_series_terms();
                     1.055 * Float(v).powf_series(self.exp.recip(), terms).0 - 0.055
                 }
             }
@@ -85,8 +85,8 @@ macro_rules! _media_visual_color_impl_gamma {
             #[doc = _DOC_GAMMA_DECODE_SRGB!()]
             pub const fn const_decode_srgb(self, v: $T) -> $T {
                 if v <= 0.040_45 { v / 12.92 } else {
-                    let terms = Float(v).exp_series_terms();
-                    Float((v + 0.055) / (1.055)).powf_series(self.exp, terms).0
+                    let x = Float((v + 0.055) / (1.055));
+                    x.powf_series(self.exp, x.ln_series_terms()).0
                 }
             }
         }
@@ -11,34 +11,31 @@ mod _helper; // (TEMP Norm)
 
 mod color; // Color
 mod depth; // ColorDepth
+mod palette; // Palette, PaletteOctree
 
 #[cfg(feature = "color")]
 mod gamma; // Gamma
 #[cfg(feature = "color")]
 mod luminance; // Luma, Luminance
 #[cfg(feature = "color")]
-mod palette; // WIP
-#[cfg(feature = "color")]
 mod rgb; // Rgb[a][8|16|F32|F64], Rgb[a]Lin[F32|F64]
-
-// #[cfg(feature = "linear")]
-// mod xyz; // Xyz
+#[cfg(feature = "color")]
+mod space; // Hsl, Hsv, Xyz, Lab, Lch, Oklab, Oklch
 
 crate::structural_mods! { // _mods, _crate_internals
     _mods {
         pub use super::{
             color::Color,
             depth::*,
+            palette::_all::*,
         };
         #[cfg(feature = "color")]
         pub use super::{
             gamma::*,
             luminance::*,
-            palette::_all::*,
             rgb::_all::*,
+            space::_all::*,
         };
-        // #[cfg(feature = "linear")]
-        // pub use super::xyz::*;
     }
     _crate_internals {
         pub(crate) use super::{
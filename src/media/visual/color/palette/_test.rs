@@ -0,0 +1,106 @@
+// devela/src/media/visual/color/palette/_test.rs
+
+use crate::{NotEnoughSpace, Palette, PaletteOctree};
+
+/// A horizontal gray gradient of 64×4 RGB pixels.
+fn gradient() -> [u8; 64 * 4 * 3] {
+    let mut pixels = [0; 64 * 4 * 3];
+    for (i, px) in pixels.chunks_exact_mut(3).enumerate() {
+        px.fill((i % 64 * 4) as u8);
+    }
+    pixels
+}
+
+#[test]
+fn palette_basics() {
+    let mut p = Palette::<3>::from_colors(&[[1, 2, 3], [200, 0, 0]]).unwrap();
+    assert_eq![(p.len(), p.capacity(), p.is_full()), (2, 3, false)];
+    assert_eq![p.find_or_push([200, 0, 0]), Ok(1)];
+    assert_eq![p.find_or_push([0, 0, 250]), Ok(2)];
+    assert_eq![p.push([9, 9, 9]), Err(NotEnoughSpace(Some(1)))];
+    assert_eq![(p.get(2), p.get(3)), (Some([0, 0, 250]), None)];
+    assert_eq![p.nearest([180, 30, 20]), 1];
+    assert_eq![p.nearest([10, 10, 10]), 0];
+    assert![Palette::<1>::from_colors(&[[0; 3]; 2]).is_err()];
+    assert_eq![Palette::<4>::new().nearest([1, 2, 3]), 0];
+}
+
+#[test]
+fn median_cut() {
+    let mut colors = [[0; 3]; 64 * 4];
+    for (c, px) in colors.iter_mut().zip(gradient().chunks_exact(3)) {
+        c.copy_from_slice(px);
+    }
+    let p = Palette::<8>::median_cut(&mut colors, 4);
+    // the boxes keep the order of their splits
+    assert_eq![p.as_slice(), &[[30, 30, 30], [158, 158, 158], [222, 222, 222], [94, 94, 94]]];
+
+    // fewer distinct colors than asked for
+    let mut colors = [[5, 6, 7], [5, 6, 7], [90, 0, 0]];
+    let p = Palette::<8>::median_cut(&mut colors, 8);
+    assert_eq![p.as_slice(), &[[5, 6, 7], [90, 0, 0]]];
+    assert![Palette::<8>::median_cut(&mut [], 8).is_empty()];
+}
+
+#[test]
+fn octree() {
+    // exact colors are kept
+    let mut octree = PaletteOctree::<256>::new();
+    octree.add_pixels(&[0, 10, 20, 30, 10, 0, 255, 255], 2);
+    octree.add([1, 2, 3]);
+    assert_eq![octree.leaves(), 5];
+    let p = octree.palette::<16>(16);
+    let colors = [[0, 0, 0], [20, 20, 20], [10, 10, 10], [255, 255, 255], [1, 2, 3]];
+    assert_eq![p.as_slice(), &colors];
+
+    // a gradient reduced to 4 colors
+    let mut octree = PaletteOctree::<256>::new();
+    octree.add_pixels(&gradient(), 3);
+    assert_eq![octree.leaves(), 64];
+    let p = octree.palette::<4>(256);
+    assert_eq![p.as_slice(), &[[30, 30, 30], [94, 94, 94], [158, 158, 158], [222, 222, 222]]];
+
+    // a pool too small for every path
+    let mut octree = PaletteOctree::<12>::new();
+    octree.add_pixels(&gradient(), 3);
+    let p = octree.palette::<8>(8);
+    assert![!p.is_empty() && p.len() <= 8];
+    let mut octree = PaletteOctree::<1>::new();
+    octree.add_pixels(&[0, 0, 0, 100, 100, 100], 3);
+    assert_eq![octree.palette::<8>(8).as_slice(), &[[50, 50, 50]]];
+}
+
+#[test]
+fn dithering() {
+    let pixels = gradient();
+    let p = Palette::<2>::from_colors(&[[0, 0, 0], [255, 255, 255]]).unwrap();
+    let mut indices = [0; 64 * 4];
+    // the mean of the dithered pixels of each column follows the gradient
+    let column_means = |indices: &[u8]| {
+        let mut white = [0; 64];
+        for (i, &index) in indices.iter().enumerate() {
+            white[i % 64] += u32::from(index);
+        }
+        white
+    };
+
+    assert_eq![p.map_nearest(&pixels, 3, &mut indices), Ok(256)];
+    let nearest = column_means(&indices);
+    assert![nearest[..32].iter().all(|&w| w == 0) && nearest[32..].iter().all(|&w| w == 4)];
+
+    assert_eq![p.map_ordered(&pixels, 3, 64, &mut indices), Ok(256)];
+    let ordered = column_means(&indices);
+    assert_eq![(ordered[0], ordered[63]), (0, 4)];
+    assert![ordered[16..24].iter().any(|&w| w > 0) && ordered[40..48].iter().any(|&w| w < 4)];
+
+    let mut errors = [0; Palette::<2>::floyd_steinberg_len(64)];
+    assert_eq![p.map_floyd_steinberg(&pixels, 3, 64, &mut indices, &mut errors), Ok(256)];
+    let total: u32 = column_means(&indices).iter().sum();
+    let expected: u32 = (0..64).map(|x| x * 4 * 4).sum::<u32>() / 255;
+    assert![total.abs_diff(expected) <= 4];
+
+    // too small buffers
+    assert_eq![p.map_nearest(&pixels, 3, &mut indices[..255]), Err(NotEnoughSpace(Some(256)))];
+    let err = p.map_floyd_steinberg(&pixels, 3, 64, &mut indices, &mut errors[..10]);
+    assert_eq![err, Err(NotEnoughSpace(Some(396)))];
+}
@@ -0,0 +1,151 @@
+// devela/src/media/visual/color/palette/dither.rs
+//
+//! Implements mapping pixels to [`Palette`] indices, with and without dithering.
+//
+
+use super::palette_rgb;
+use crate::{NotEnoughSpace, Palette, is};
+
+/// The 4×4 Bayer threshold matrix.
+const BAYER4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Methods
+/// - map_nearest
+/// - map_ordered
+/// - map_floyd_steinberg
+/// - floyd_steinberg_len
+impl<const CAP: usize> Palette<CAP> {
+    /// Maps each pixel to the index of its [`nearest`][Self::nearest] color.
+    ///
+    /// `pixels` holds `channels` bytes per pixel, and `indices` needs one byte per pixel.
+    ///
+    /// Returns the number of pixels mapped.
+    ///
+    /// # Panics
+    /// Panics if `channels` is 0.
+    pub fn map_nearest(
+        &self,
+        pixels: &[u8],
+        channels: usize,
+        indices: &mut [u8],
+    ) -> Result<usize, NotEnoughSpace> {
+        let n = Self::check_indices(pixels, channels, indices)?;
+        for (i, px) in indices.iter_mut().zip(pixels.chunks_exact(channels)) {
+            *i = self.nearest(palette_rgb(px));
+        }
+        Ok(n)
+    }
+
+    /// Maps each pixel to a palette index with ordered dithering.
+    ///
+    /// Each pixel is offset by a 4×4 Bayer threshold before looking up its nearest color,
+    /// which spreads the quantization error in a regular pattern.
+    /// It needs no extra storage, and each pixel is mapped independently.
+    ///
+    /// `pixels` holds rows of `width` pixels of `channels` bytes,
+    /// and `indices` needs one byte per pixel.
+    ///
+    /// Returns the number of pixels mapped.
+    ///
+    /// # Panics
+    /// Panics if `channels` or `width` is 0.
+    pub fn map_ordered(
+        &self,
+        pixels: &[u8],
+        channels: usize,
+        width: usize,
+        indices: &mut [u8],
+    ) -> Result<usize, NotEnoughSpace> {
+        let n = Self::check_indices(pixels, channels, indices)?;
+        let spread = self.dither_spread();
+        for (i, (index, px)) in indices.iter_mut().zip(pixels.chunks_exact(channels)).enumerate() {
+            let (x, y) = (i % width, i / width);
+            // a threshold between -0.5 and 0.5 of the spread
+            let offset = (i32::from(BAYER4[y % 4][x % 4]) * 2 - 15) * spread / 32;
+            let rgb = palette_rgb(px).map(|v| (i32::from(v) + offset).clamp(0, 255) as u8);
+            *index = self.nearest(rgb);
+        }
+        Ok(n)
+    }
+
+    /// Maps each pixel to a palette index with Floyd–Steinberg error diffusion.
+    ///
+    /// The error of each pixel is distributed to its unvisited neighbors,
+    /// which gives smoother gradients than [`map_ordered`][Self::map_ordered].
+    ///
+    /// `pixels` holds rows of `width` pixels of `channels` bytes,
+    /// and `indices` needs one byte per pixel.
+    /// `errors` needs at least [`floyd_steinberg_len`][Self::floyd_steinberg_len] elements.
+    ///
+    /// Returns the number of pixels mapped.
+    ///
+    /// # Panics
+    /// Panics if `channels` or `width` is 0.
+    pub fn map_floyd_steinberg(
+        &self,
+        pixels: &[u8],
+        channels: usize,
+        width: usize,
+        indices: &mut [u8],
+        errors: &mut [i16],
+    ) -> Result<usize, NotEnoughSpace> {
+        let n = Self::check_indices(pixels, channels, indices)?;
+        let needed = Self::floyd_steinberg_len(width);
+        is![errors.len() < needed, return Err(NotEnoughSpace(Some(needed)))];
+        // the errors of the current and next rows, in sixteenths, with a column on each side
+        let (mut cur, mut next) = errors[..needed].split_at_mut(needed / 2);
+        cur.fill(0);
+        next.fill(0);
+        for (i, (index, px)) in indices.iter_mut().zip(pixels.chunks_exact(channels)).enumerate() {
+            let x = i % width;
+            if x == 0 && i > 0 {
+                core::mem::swap(&mut cur, &mut next);
+                next.fill(0);
+            }
+            let rgb = palette_rgb(px);
+            let mut target = [0; 3];
+            for c in 0..3 {
+                let v = i32::from(rgb[c]) + i32::from(cur[(x + 1) * 3 + c]) / 16;
+                target[c] = v.clamp(0, 255) as u8;
+            }
+            *index = self.nearest(target);
+            let color = self.get(*index as usize).unwrap_or(target);
+            for c in 0..3 {
+                let err = i16::from(target[c]) - i16::from(color[c]);
+                cur[(x + 2) * 3 + c] += err * 7;
+                next[x * 3 + c] += err * 3;
+                next[(x + 1) * 3 + c] += err * 5;
+                next[(x + 2) * 3 + c] += err;
+            }
+        }
+        Ok(n)
+    }
+
+    /// Returns the number of error elements needed by
+    /// [`map_floyd_steinberg`][Self::map_floyd_steinberg] for rows of `width` pixels.
+    #[must_use]
+    pub const fn floyd_steinberg_len(width: usize) -> usize {
+        (width + 2) * 3 * 2
+    }
+
+    /// Checks that there's an index for every pixel, returning the number of pixels.
+    fn check_indices(
+        pixels: &[u8],
+        channels: usize,
+        indices: &[u8],
+    ) -> Result<usize, NotEnoughSpace> {
+        let n = pixels.len() / channels;
+        is![indices.len() < n, return Err(NotEnoughSpace(Some(n)))];
+        Ok(n)
+    }
+
+    /// Returns the amplitude of ordered dithering, from the expected distance between colors.
+    fn dither_spread(&self) -> i32 {
+        // the side of a cube holding as many evenly spaced colors as the palette
+        let mut levels = 1;
+        while (levels + 1) * (levels + 1) * (levels + 1) <= self.len().max(1) {
+            levels += 1;
+        }
+        is![levels == 1, 255, 255 / (levels - 1) as i32]
+    }
+}
@@ -0,0 +1,79 @@
+// devela/src/media/visual/color/palette/median_cut.rs
+//
+//! Implements median-cut quantization for [`Palette`].
+//
+
+use crate::{Palette, is};
+
+impl<const CAP: usize> Palette<CAP> {
+    /// Quantizes a set of colors into a palette of up to `max_colors` colors,
+    /// using the median-cut algorithm.
+    ///
+    /// The colors are recursively split in two halves across the median of
+    /// the channel with the widest range, always splitting the box with the
+    /// widest range first. Each palette color is the mean of its box.
+    ///
+    /// The colors are reordered in place. They can be every pixel of an image,
+    /// or a sample of them; repeated colors weigh their box mean accordingly.
+    ///
+    /// At most `CAP` colors are returned, and fewer if there are fewer distinct colors.
+    #[must_use]
+    pub fn median_cut(colors: &mut [[u8; 3]], max_colors: usize) -> Self {
+        let mut palette = Self::new();
+        let max = max_colors.min(CAP);
+        is![colors.is_empty() || max == 0, return palette];
+
+        // each box is a range of colors, with its widest range and channel
+        let mut boxes = [(0, 0, 0_u8, 0_usize); CAP];
+        boxes[0] = Self::median_box(colors, 0, colors.len());
+        let mut len = 1;
+        while len < max {
+            let Some(widest) = (0..len).filter(|&b| boxes[b].2 > 0).max_by_key(|&b| boxes[b].2)
+            else {
+                break; // every box holds a single color
+            };
+            let (start, end, _, channel) = boxes[widest];
+            colors[start..end].sort_unstable_by_key(|c| c[channel]);
+            // split at the median, moved past equal values when possible
+            let mut mid = start + (end - start) / 2;
+            let v = colors[mid][channel];
+            while mid > start && colors[mid - 1][channel] == v {
+                mid -= 1;
+            }
+            if mid == start {
+                while mid < end && colors[mid][channel] == v {
+                    mid += 1;
+                }
+            }
+            boxes[widest] = Self::median_box(colors, start, mid);
+            boxes[len] = Self::median_box(colors, mid, end);
+            len += 1;
+        }
+        for &(start, end, _, _) in &boxes[..len] {
+            let mut sum = [0_u64; 3];
+            for c in &colors[start..end] {
+                for (s, v) in sum.iter_mut().zip(c) {
+                    *s += u64::from(*v);
+                }
+            }
+            let n = (end - start) as u64;
+            let _ = palette.push(sum.map(|s| ((s + n / 2) / n) as u8));
+        }
+        palette
+    }
+
+    /// Returns the box of the given range, with its widest channel range and channel.
+    fn median_box(colors: &[[u8; 3]], start: usize, end: usize) -> (usize, usize, u8, usize) {
+        let (mut min, mut max) = ([u8::MAX; 3], [0; 3]);
+        for c in &colors[start..end] {
+            for ch in 0..3 {
+                min[ch] = min[ch].min(c[ch]);
+                max[ch] = max[ch].max(c[ch]);
+            }
+        }
+        let (channel, range) = (0..3)
+            .map(|ch| (ch, max[ch].saturating_sub(min[ch])))
+            .fold((0, 0), |a, b| is![b.1 > a.1, b, a]);
+        (start, end, range, channel)
+    }
+}
@@ -1,22 +1,34 @@
 // devela/src/media/visual/color/palette/mod.rs
 //
-//!
+//! Color palettes, quantization and dithering.
 //
 
+#[cfg(test)]
+mod _test;
+
+mod dither; // Palette::map_*
+mod median_cut; // Palette::median_cut
+mod octree; // PaletteOctree
+mod palette; // Palette
+
 // mod ase;
 // mod gpl; // TODO WIP
 // mod hex;
 // mod kpl;
 // mod pal;
 
+use palette::palette_rgb;
+
 crate::structural_mods! { // _mods
     _mods {
-        // pub use super::{
-        //     // ase::*,
-        //     gpl::*,
-        //     // hex::*,
-        //     // kpl::*,
-        //     // pal::*,
-        // };
+        pub use super::{
+            octree::*,
+            palette::*,
+            // ase::*,
+            // gpl::*,
+            // hex::*,
+            // kpl::*,
+            // pal::*,
+        };
     }
 }
@@ -0,0 +1,246 @@
+// devela/src/media/visual/color/palette/octree.rs
+//
+//! Defines [`PaletteOctree`].
+//
+
+use super::palette_rgb;
+use crate::{Palette, is};
+
+/// The level of the leaves of an unreduced tree, one per bit of each channel.
+const MAX_DEPTH: u8 = 8;
+/// The level of a node in the free list.
+const FREE: u8 = u8::MAX;
+
+#[doc = crate::_tags!(color)]
+/// An octree color quantizer, with a fixed pool of `NODES` nodes.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Each color is added to the leaf of the path given by its bits, from the most
+/// significant, accumulating its sum and count in every node of the path.
+/// When the pool runs out of nodes, its deepest level is merged into its parents,
+/// and later colors stop at that level.
+///
+/// A [`Palette`] is then made of the mean colors of the leaves, after merging
+/// the least used branches until they fit.
+///
+/// Images with fewer distinct colors than the palette keep their exact colors,
+/// as long as the pool has room for their paths. A pool of 1024 nodes is usually
+/// enough for photographs, and `NODES` can't be more than 65536.
+///
+/// # Example
+/// ```
+/// # use devela::PaletteOctree;
+/// let pixels = [255, 0, 0, 250, 5, 0, 0, 0, 255, 0, 10, 250];
+/// let mut octree = PaletteOctree::<64>::new();
+/// octree.add_pixels(&pixels, 3);
+/// assert_eq![octree.leaves(), 4];
+///
+/// let palette = octree.palette::<4>(2);
+/// assert_eq![palette.as_slice(), &[[253, 3, 0], [0, 5, 253]]];
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PaletteOctree<const NODES: usize> {
+    nodes: [OctreeNode; NODES],
+    /// The number of nodes ever allocated.
+    len: usize,
+    /// The first node of the free list, or 0 if it's empty.
+    free: u16,
+    /// The number of leaves.
+    leaves: usize,
+    /// The level of the leaves of new paths.
+    depth: u8,
+}
+
+/// A node of a [`PaletteOctree`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+struct OctreeNode {
+    /// The indices of the children, or 0 for none.
+    ///
+    /// The first one links to the next free node while in the free list.
+    children: [u16; 8],
+    /// The sum of the colors of the subtree.
+    sum: [u64; 3],
+    /// The number of colors of the subtree.
+    count: u64,
+    /// The depth from the root, or [`FREE`].
+    level: u8,
+    leaf: bool,
+}
+
+impl<const NODES: usize> Default for PaletteOctree<NODES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const NODES: usize> PaletteOctree<NODES> {
+    /// Creates an empty octree.
+    ///
+    /// # Panics
+    /// Panics if `NODES` is 0 or more than 65536.
+    #[must_use]
+    pub fn new() -> Self {
+        assert![NODES > 0 && NODES <= u16::MAX as usize + 1];
+        Self {
+            nodes: [OctreeNode::default(); NODES],
+            len: 1,
+            free: 0,
+            leaves: 0,
+            depth: MAX_DEPTH,
+        }
+    }
+
+    /// Returns the number of leaves, which is the number of colors before reduction.
+    #[must_use]
+    pub const fn leaves(&self) -> usize {
+        self.leaves
+    }
+
+    /// Adds every pixel of `channels` bytes, of gray, gray and alpha, RGB, or RGBA.
+    ///
+    /// Alpha is ignored.
+    ///
+    /// # Panics
+    /// Panics if `channels` is 0.
+    pub fn add_pixels(&mut self, pixels: &[u8], channels: usize) {
+        for px in pixels.chunks_exact(channels) {
+            self.add(palette_rgb(px));
+        }
+    }
+
+    /// Adds a single color.
+    pub fn add(&mut self, rgb: [u8; 3]) {
+        'path: loop {
+            let mut node = 0;
+            loop {
+                let level = self.nodes[node].level;
+                if self.nodes[node].leaf || level == self.depth {
+                    if !self.nodes[node].leaf {
+                        // an inner node left without children when the pool ran out
+                        self.nodes[node].leaf = true;
+                        self.leaves += 1;
+                    }
+                    self.accumulate(rgb, level);
+                    return;
+                }
+                let shift = MAX_DEPTH - 1 - level;
+                let branch = usize::from((rgb[0] >> shift) & 1) << 2
+                    | usize::from((rgb[1] >> shift) & 1) << 1
+                    | usize::from((rgb[2] >> shift) & 1);
+                let child = self.nodes[node].children[branch];
+                if child != 0 {
+                    node = child as usize;
+                } else if let Some(child) = self.alloc(level + 1) {
+                    self.nodes[node].children[branch] = child;
+                    node = child as usize;
+                } else {
+                    self.reduce_level();
+                    continue 'path;
+                }
+            }
+        }
+    }
+
+    /// Returns a palette of up to `max_colors` colors, which is at most `CAP`.
+    ///
+    /// The tree is reduced until it has no more leaves than colors, by merging
+    /// the branches with the fewest colors among the deepest ones.
+    #[must_use]
+    pub fn palette<const CAP: usize>(&mut self, max_colors: usize) -> Palette<CAP> {
+        let max = max_colors.min(CAP).max(1);
+        while self.leaves > max {
+            self.reduce_one();
+        }
+        let mut palette = Palette::new();
+        for node in &self.nodes[..self.len] {
+            if node.leaf && node.level != FREE && node.count > 0 {
+                let n = node.count;
+                let _ = palette.push(node.sum.map(|s| ((s + n / 2) / n) as u8));
+            }
+        }
+        palette
+    }
+
+    /* helpers */
+
+    /// Adds the color to every node in its path, down to `level`.
+    fn accumulate(&mut self, rgb: [u8; 3], level: u8) {
+        let mut node = 0;
+        loop {
+            let n = &mut self.nodes[node];
+            n.count += 1;
+            for (s, v) in n.sum.iter_mut().zip(rgb) {
+                *s += u64::from(v);
+            }
+            is![n.level == level, return];
+            let shift = MAX_DEPTH - 1 - n.level;
+            let branch = usize::from((rgb[0] >> shift) & 1) << 2
+                | usize::from((rgb[1] >> shift) & 1) << 1
+                | usize::from((rgb[2] >> shift) & 1);
+            node = n.children[branch] as usize;
+        }
+    }
+
+    /// Returns a new node at `level`, which is a leaf at the current depth.
+    fn alloc(&mut self, level: u8) -> Option<u16> {
+        let index = if self.free != 0 {
+            let index = self.free;
+            self.free = self.nodes[index as usize].children[0];
+            index
+        } else if self.len < NODES {
+            self.len += 1;
+            (self.len - 1) as u16
+        } else {
+            return None;
+        };
+        let leaf = level == self.depth;
+        self.nodes[index as usize] = OctreeNode { level, leaf, ..Default::default() };
+        is![leaf, self.leaves += 1];
+        Some(index)
+    }
+
+    /// Turns an inner node into a leaf, freeing its children.
+    fn merge(&mut self, node: usize) {
+        let children = self.nodes[node].children;
+        for child in children.into_iter().filter(|&c| c != 0) {
+            is![self.nodes[child as usize].leaf, self.leaves -= 1];
+            self.nodes[child as usize] = OctreeNode { level: FREE, ..Default::default() };
+            self.nodes[child as usize].children[0] = self.free;
+            self.free = child;
+        }
+        let n = &mut self.nodes[node];
+        (n.children, n.leaf) = ([0; 8], true);
+        self.leaves += 1;
+    }
+
+    /// Returns the deepest level with inner nodes, whose children are all leaves.
+    fn deepest_inner_level(&self) -> Option<u8> {
+        self.nodes[..self.len]
+            .iter()
+            .filter(|n| !n.leaf && n.level != FREE && n.children != [0; 8])
+            .map(|n| n.level)
+            .max()
+    }
+
+    /// Merges every inner node of the deepest level, making it the new depth.
+    fn reduce_level(&mut self) {
+        let level = self.deepest_inner_level().unwrap_or(0);
+        for node in 0..self.len {
+            let n = &self.nodes[node];
+            is![!n.leaf && n.level == level && n.children != [0; 8], self.merge(node)];
+        }
+        self.depth = level;
+    }
+
+    /// Merges the inner node of the deepest level with the fewest colors.
+    fn reduce_one(&mut self) {
+        let Some(level) = self.deepest_inner_level() else { return };
+        let node = (0..self.len)
+            .filter(|&i| {
+                let n = &self.nodes[i];
+                !n.leaf && n.level == level && n.children != [0; 8]
+            })
+            .min_by_key(|&i| self.nodes[i].count);
+        is![let Some(node) = node, self.merge(node)];
+    }
+}
@@ -0,0 +1,142 @@
+// devela/src/media/visual/color/palette/palette.rs
+//
+//! Defines [`Palette`].
+//
+
+use crate::{NotEnoughSpace, is};
+
+#[doc = crate::_tags!(color)]
+/// A palette of up to `CAP` RGB colors, for indexed images.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// A palette can be built color by color, from the exact colors of an image
+/// with [`find_or_push`][Self::find_or_push], or by quantizing the colors of an
+/// image with [`median_cut`][Self::median_cut] or a [`PaletteOctree`][crate::PaletteOctree].
+///
+/// Pixels are mapped to palette indices by [`map_nearest`][Self::map_nearest],
+/// or with dithering by [`map_ordered`][Self::map_ordered]
+/// and [`map_floyd_steinberg`][Self::map_floyd_steinberg].
+///
+/// The pixels read by these methods are bytes of 1 to 4 channels:
+/// gray, gray and alpha, RGB, or RGBA. Alpha is ignored.
+///
+/// # Example
+/// ```
+/// # use devela::Palette;
+/// let pixels = [250, 10, 10, 240, 0, 0, 0, 0, 240, 10, 10, 250];
+/// let mut colors = [[0; 3]; 4];
+/// for (c, px) in colors.iter_mut().zip(pixels.chunks_exact(3)) {
+///     c.copy_from_slice(px);
+/// }
+/// let palette = Palette::<16>::median_cut(&mut colors, 2);
+/// assert_eq![palette.len(), 2];
+///
+/// let mut indices = [0; 4];
+/// palette.map_nearest(&pixels, 3, &mut indices)?;
+/// assert![indices[0] == indices[1] && indices[2] == indices[3] && indices[0] != indices[2]];
+/// # Ok::<(), devela::NotEnoughSpace>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Palette<const CAP: usize> {
+    colors: [[u8; 3]; CAP],
+    len: usize,
+}
+
+#[rustfmt::skip]
+impl<const CAP: usize> Default for Palette<CAP> { fn default() -> Self { Self::new() } }
+
+#[rustfmt::skip]
+impl<const CAP: usize> Palette<CAP> {
+    /// Creates an empty palette.
+    #[must_use]
+    pub const fn new() -> Self { Self { colors: [[0; 3]; CAP], len: 0 } }
+
+    /// Creates a palette with the given colors.
+    ///
+    /// Returns [`NotEnoughSpace`] if there are more than `CAP` colors.
+    pub const fn from_colors(colors: &[[u8; 3]]) -> Result<Self, NotEnoughSpace> {
+        is![colors.len() > CAP, return Err(NotEnoughSpace(Some(colors.len())))];
+        let mut palette = Self::new();
+        let mut i = 0;
+        while i < colors.len() {
+            palette.colors[i] = colors[i];
+            i += 1;
+        }
+        palette.len = colors.len();
+        Ok(palette)
+    }
+
+    /// Returns the number of colors.
+    #[must_use]
+    pub const fn len(&self) -> usize { self.len }
+    /// Returns `true` if there are no colors.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.len == 0 }
+    /// Returns `true` if there's no room for more colors.
+    #[must_use]
+    pub const fn is_full(&self) -> bool { self.len >= CAP }
+    /// Returns the maximum number of colors.
+    #[must_use]
+    pub const fn capacity(&self) -> usize { CAP }
+
+    /// Returns the colors as a slice.
+    #[must_use]
+    pub const fn as_slice(&self) -> &[[u8; 3]] { self.colors.split_at(self.len).0 }
+
+    /// Returns the color at `index`, if it exists.
+    #[must_use]
+    pub const fn get(&self, index: usize) -> Option<[u8; 3]> {
+        is![index < self.len, Some(self.colors[index]), None]
+    }
+
+    /// Adds a color without checking for duplicates, returning its index.
+    pub const fn push(&mut self, color: [u8; 3]) -> Result<usize, NotEnoughSpace> {
+        is![self.is_full(), return Err(NotEnoughSpace(Some(1)))];
+        self.colors[self.len] = color;
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+
+    /// Returns the index of the given color, if it's in the palette.
+    #[must_use]
+    pub const fn find(&self, color: [u8; 3]) -> Option<usize> {
+        let mut i = 0;
+        while i < self.len {
+            let c = self.colors[i];
+            is![c[0] == color[0] && c[1] == color[1] && c[2] == color[2], return Some(i)];
+            i += 1;
+        }
+        None
+    }
+    /// Returns the index of the given color, adding it if it's not in the palette.
+    pub const fn find_or_push(&mut self, color: [u8; 3]) -> Result<usize, NotEnoughSpace> {
+        if let Some(i) = self.find(color) { Ok(i) } else { self.push(color) }
+    }
+
+    /// Returns the index of the closest color, by squared RGB distance.
+    ///
+    /// Only the first 256 colors are considered. An empty palette returns 0.
+    #[must_use]
+    pub const fn nearest(&self, color: [u8; 3]) -> u8 {
+        let (mut best, mut best_dist) = (0, u32::MAX);
+        let mut i = 0;
+        while i < self.len && i < 256 {
+            let dist = palette_distance(self.colors[i], color);
+            is![dist == 0, return i as u8];
+            if dist < best_dist { (best, best_dist) = (i, dist); }
+            i += 1;
+        }
+        best as u8
+    }
+}
+
+/// Returns the RGB color of a pixel of 1 to 4 channels.
+pub(super) const fn palette_rgb(px: &[u8]) -> [u8; 3] {
+    is![px.len() < 3, [px[0], px[0], px[0]], [px[0], px[1], px[2]]]
+}
+
+/// Returns the squared distance between two colors.
+const fn palette_distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    let d = [a[0] as i32 - b[0] as i32, a[1] as i32 - b[1] as i32, a[2] as i32 - b[2] as i32];
+    (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]) as u32
+}
@@ -0,0 +1,338 @@
+// devela/src/media/visual/color/space/_helper.rs
+//
+//! Color space math shared by the perceptual color spaces.
+//
+// All the math is done in `f64`, and the spaces of `f32` convert to and from it.
+
+use crate::{Float, Gamma, is};
+
+/// The sRGB transfer curve.
+const SRGB: Gamma<f64> = Gamma::new(2.4);
+
+/// The D65 reference white in CIE XYZ, with a luminance of 1.
+pub(super) const WHITE_D65: [f64; 3] = [0.950_455_927_051_671_6, 1.0, 1.089_057_750_759_878_4];
+
+/// Linear sRGB → CIE XYZ (D65).
+const LINEAR_TO_XYZ: [[f64; 3]; 3] = [
+    [0.412_390_799_265_959_3, 0.357_584_339_383_878, 0.180_480_788_401_834_3],
+    [0.212_639_005_871_510_3, 0.715_168_678_767_756, 0.072_192_315_360_733_71],
+    [0.019_330_818_715_591_82, 0.119_194_779_794_625_98, 0.950_532_152_249_660_7],
+];
+/// CIE XYZ (D65) → linear sRGB.
+const XYZ_TO_LINEAR: [[f64; 3]; 3] = [
+    [3.240_969_941_904_522_6, -1.537_383_177_570_094, -0.498_610_760_293_003_4],
+    [-0.969_243_636_280_879_6, 1.875_967_501_507_720_2, 0.041_555_057_407_175_59],
+    [0.055_630_079_696_993_66, -0.203_976_958_888_976_52, 1.056_971_514_242_878_6],
+];
+
+/// Linear sRGB → Oklab cone responses.
+const LINEAR_TO_LMS: [[f64; 3]; 3] = [
+    [0.412_221_470_8, 0.536_332_536_3, 0.051_445_992_9],
+    [0.211_903_498_2, 0.680_699_545_1, 0.107_396_956_6],
+    [0.088_302_461_9, 0.281_718_837_6, 0.629_978_700_5],
+];
+/// Nonlinear cone responses → Oklab.
+const LMS_TO_OKLAB: [[f64; 3]; 3] = [
+    [0.210_454_255_3, 0.793_617_785, -0.004_072_046_8],
+    [1.977_998_495_1, -2.428_592_205, 0.450_593_709_9],
+    [0.025_904_037_1, 0.782_771_766_2, -0.808_675_766],
+];
+/// Oklab → nonlinear cone responses.
+const OKLAB_TO_LMS: [[f64; 3]; 3] = [
+    [1.0, 0.396_337_777_4, 0.215_803_757_3],
+    [1.0, -0.105_561_345_8, -0.063_854_172_8],
+    [1.0, -0.089_484_177_5, -1.291_485_548],
+];
+/// Oklab cone responses → linear sRGB.
+const LMS_TO_LINEAR: [[f64; 3]; 3] = [
+    [4.076_741_662_1, -3.307_711_591_3, 0.230_969_929_2],
+    [-1.268_438_004_6, 2.609_757_401_1, -0.341_319_396_5],
+    [-0.004_196_086_3, -0.703_418_614_7, 1.707_614_701],
+];
+
+/// The CIE lightness transition point and linear coefficient.
+const CIE_E: f64 = Gamma::<f64>::CIE_E;
+const CIE_K: f64 = Gamma::<f64>::CIE_K;
+
+/* transfer and basic math */
+
+/// Decodes gamma-encoded sRGB channels into linear ones.
+pub(super) const fn decode_srgb(c: [f64; 3]) -> [f64; 3] {
+    [SRGB.const_decode_srgb(c[0]), SRGB.const_decode_srgb(c[1]), SRGB.const_decode_srgb(c[2])]
+}
+/// Encodes linear sRGB channels into gamma-encoded ones.
+pub(super) const fn encode_srgb(c: [f64; 3]) -> [f64; 3] {
+    [SRGB.const_encode_srgb(c[0]), SRGB.const_encode_srgb(c[1]), SRGB.const_encode_srgb(c[2])]
+}
+
+/// Returns the product of the matrix `m` and the vector `v`.
+const fn mul(m: [[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    const fn row(r: [f64; 3], v: [f64; 3]) -> f64 {
+        r[0] * v[0] + r[1] * v[1] + r[2] * v[2]
+    }
+    [row(m[0], v), row(m[1], v), row(m[2], v)]
+}
+const fn cbrt(x: f64) -> f64 {
+    Float(x).cbrt_nr().0
+}
+pub(super) const fn sqrt(x: f64) -> f64 {
+    Float(x).sqrt_nr().0
+}
+const fn exp(x: f64) -> f64 {
+    Float(x).exp_series(Float(x).exp_series_terms()).0
+}
+/// Returns the sine and cosine of an angle in `degrees`.
+const fn sin_cos(degrees: f64) -> (f64, f64) {
+    let d = Float(degrees).rem_euclid(360.0).0;
+    let (s, c) = Float(is![d > 180.0, d - 360.0, d].to_radians()).sin_cos_minimax();
+    (s.0, c.0)
+}
+/// Returns the four-quadrant arctangent of `y` and `x`, in degrees between -180 and 180.
+const fn atan2(y: f64, x: f64) -> f64 {
+    let (ax, ay) = (x.abs(), y.abs());
+    if ax == 0.0 && ay == 0.0 {
+        return 0.0;
+    }
+    let swap = ay > ax;
+    let mut t = is![swap, ax / ay, ay / ax];
+    // halve the angle twice, so that the series converges fast: atan t = 2 atan(t/(1+√(1+t²)))
+    t /= 1.0 + sqrt(1.0 + t * t);
+    t /= 1.0 + sqrt(1.0 + t * t);
+    let (t2, mut power, mut sum, mut i) = (t * t, t, 0.0, 0);
+    while i < 12 {
+        sum += is![i % 2 == 0, power, -power] / (2 * i + 1) as f64;
+        power *= t2;
+        i += 1;
+    }
+    let mut a = 4.0 * sum.to_degrees();
+    is![swap, a = 90.0 - a];
+    is![x < 0.0, a = 180.0 - a];
+    is![y < 0.0, -a, a]
+}
+
+/* polar coordinates */
+
+/// Returns a hue in degrees between 0 and 360.
+pub(super) const fn hue(degrees: f64) -> f64 {
+    let h = Float(degrees).rem_euclid(360.0).0;
+    is![h >= 360.0, 0.0, h]
+}
+/// Converts rectangular `[l, a, b]` coordinates into polar `[l, chroma, hue]` ones.
+pub(super) const fn to_polar(c: [f64; 3]) -> [f64; 3] {
+    [c[0], sqrt(c[1] * c[1] + c[2] * c[2]), hue(atan2(c[2], c[1]))]
+}
+/// Converts polar `[l, chroma, hue]` coordinates into rectangular `[l, a, b]` ones.
+pub(super) const fn from_polar(c: [f64; 3]) -> [f64; 3] {
+    let (sin, cos) = sin_cos(c[2]);
+    [c[0], c[1] * cos, c[1] * sin]
+}
+
+/// Linearly interpolates each channel of `a` towards `b`.
+pub(super) const fn lerp(a: [f64; 3], b: [f64; 3], t: f64) -> [f64; 3] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+}
+/// Interpolates the hue `a` towards `b` by `t` along the shorter arc.
+pub(super) const fn lerp_hue(a: f64, b: f64, t: f64) -> f64 {
+    let mut d = b - a;
+    is![d > 180.0, d -= 360.0];
+    is![d < -180.0, d += 360.0];
+    hue(a + d * t)
+}
+/// Interpolates cylindrical coordinates, with the hue at index `h` and its chroma at index `c`.
+///
+/// The hue of a color whose chroma is not over `gray` is meaningless and follows the other one.
+pub(super) const fn lerp_polar(
+    a: [f64; 3],
+    b: [f64; 3],
+    t: f64,
+    [c, h]: [usize; 2],
+    gray: f64,
+) -> [f64; 3] {
+    let (mut ha, mut hb) = (a[h], b[h]);
+    is![a[c] <= gray, ha = hb];
+    is![b[c] <= gray, hb = ha];
+    let mut l = lerp(a, b, t);
+    l[h] = lerp_hue(ha, hb, t);
+    l
+}
+
+/* CIE XYZ, Lab */
+
+pub(super) const fn xyz_from_linear(c: [f64; 3]) -> [f64; 3] {
+    mul(LINEAR_TO_XYZ, c)
+}
+pub(super) const fn linear_from_xyz(c: [f64; 3]) -> [f64; 3] {
+    mul(XYZ_TO_LINEAR, c)
+}
+pub(super) const fn lab_from_xyz(c: [f64; 3]) -> [f64; 3] {
+    const fn f(t: f64) -> f64 {
+        is![t > CIE_E, cbrt(t), (CIE_K * t + 16.0) / 116.0]
+    }
+    let w = WHITE_D65;
+    let (fx, fy, fz) = (f(c[0] / w[0]), f(c[1] / w[1]), f(c[2] / w[2]));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+pub(super) const fn xyz_from_lab(c: [f64; 3]) -> [f64; 3] {
+    const fn f(t: f64) -> f64 {
+        let t3 = t * t * t;
+        is![t3 > CIE_E, t3, (116.0 * t - 16.0) / CIE_K]
+    }
+    let fy = (c[0] + 16.0) / 116.0;
+    let y = is![c[0] > CIE_K * CIE_E, fy * fy * fy, c[0] / CIE_K];
+    let w = WHITE_D65;
+    [f(fy + c[1] / 500.0) * w[0], y * w[1], f(fy - c[2] / 200.0) * w[2]]
+}
+
+/// Returns the CIEDE2000 color difference between the Lab colors `x` and `y`.
+pub(super) const fn delta_e_2000(x: [f64; 3], y: [f64; 3]) -> f64 {
+    const P25: f64 = 6_103_515_625.0; // 25⁷
+    const fn pow7(x: f64) -> f64 {
+        Float(x).const_powi(7).0
+    }
+    let ([l1, a1, b1], [l2, a2, b2]) = (x, y);
+    let c = (sqrt(a1 * a1 + b1 * b1) + sqrt(a2 * a2 + b2 * b2)) / 2.0;
+    let g = 0.5 * (1.0 - sqrt(pow7(c) / (pow7(c) + P25)));
+    let ([_, c1, h1], [_, c2, h2]) =
+        (to_polar([l1, a1 * (1.0 + g), b1]), to_polar([l2, a2 * (1.0 + g), b2]));
+
+    let chromatic = c1 * c2 != 0.0;
+    let mut dh = is![chromatic, h2 - h1, 0.0];
+    is![dh > 180.0, dh -= 360.0];
+    is![dh < -180.0, dh += 360.0];
+    let (dl, dc, dhh) = (l2 - l1, c2 - c1, 2.0 * sqrt(c1 * c2) * sin_cos(dh / 2.0).0);
+
+    let (l, c) = ((l1 + l2) / 2.0, (c1 + c2) / 2.0);
+    let h = if !chromatic {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let t = 1.0 - 0.17 * sin_cos(h - 30.0).1
+        + 0.24 * sin_cos(2.0 * h).1
+        + 0.32 * sin_cos(3.0 * h + 6.0).1
+        - 0.20 * sin_cos(4.0 * h - 63.0).1;
+    let theta = 30.0 * exp(-((h - 275.0) / 25.0) * ((h - 275.0) / 25.0));
+    let rc = 2.0 * sqrt(pow7(c) / (pow7(c) + P25));
+    let l50 = (l - 50.0) * (l - 50.0);
+    let sl = 1.0 + 0.015 * l50 / sqrt(20.0 + l50);
+    let (sc, sh) = (1.0 + 0.045 * c, 1.0 + 0.015 * c * t);
+    let rt = -sin_cos(2.0 * theta).0 * rc;
+    let (dl, dc, dhh) = (dl / sl, dc / sc, dhh / sh);
+    sqrt(dl * dl + dc * dc + dhh * dhh + rt * dc * dhh)
+}
+
+/* Oklab */
+
+pub(super) const fn oklab_from_linear(c: [f64; 3]) -> [f64; 3] {
+    let lms = mul(LINEAR_TO_LMS, c);
+    mul(LMS_TO_OKLAB, [cbrt(lms[0]), cbrt(lms[1]), cbrt(lms[2])])
+}
+pub(super) const fn linear_from_oklab(c: [f64; 3]) -> [f64; 3] {
+    let [l, m, s] = mul(OKLAB_TO_LMS, c);
+    mul(LMS_TO_LINEAR, [l * l * l, m * m * m, s * s * s])
+}
+/// Returns the euclidean distance between two colors.
+pub(super) const fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    sqrt(d[0] * d[0] + d[1] * d[1] + d[2] * d[2])
+}
+
+/// Returns whether the linear sRGB channels are within the sRGB gamut.
+pub(super) const fn in_gamut(c: [f64; 3]) -> bool {
+    const E: f64 = 1e-6;
+    let mut i = 0;
+    while i < 3 {
+        is![c[i] < -E || c[i] > 1.0 + E, return false];
+        i += 1;
+    }
+    true
+}
+/// Clamps the linear sRGB channels to the sRGB gamut.
+pub(super) const fn clip(c: [f64; 3]) -> [f64; 3] {
+    [c[0].clamp(0.0, 1.0), c[1].clamp(0.0, 1.0), c[2].clamp(0.0, 1.0)]
+}
+
+/// Maps an Oklch color into the sRGB gamut by reducing its chroma,
+/// following the CSS Color Module Level 4 algorithm.
+pub(super) const fn oklch_gamut_map(c: [f64; 3]) -> [f64; 3] {
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.000_1;
+    const fn clipped(lab: [f64; 3]) -> [f64; 3] {
+        oklab_from_linear(clip(linear_from_oklab(lab)))
+    }
+    let [l, chroma, h] = c;
+    is![l >= 1.0, return [1.0, 0.0, h]];
+    is![l <= 0.0, return [0.0, 0.0, h]];
+    let mut current = from_polar(c);
+    is![in_gamut(linear_from_oklab(current)), return c];
+
+    let (mut min, mut max, mut min_in_gamut) = (0.0, chroma, true);
+    let mut clip_lab = clipped(current);
+    is![distance(clip_lab, current) < JND, return to_polar(clip_lab)];
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        current = from_polar([l, chroma, h]);
+        if min_in_gamut && in_gamut(linear_from_oklab(current)) {
+            min = chroma;
+            continue;
+        }
+        clip_lab = clipped(current);
+        let e = distance(clip_lab, current);
+        if e < JND {
+            is![JND - e < EPSILON, break];
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    to_polar(clip_lab)
+}
+
+/* HSL, HSV */
+
+/// Returns the hue, the maximum, and the range of gamma-encoded sRGB channels.
+const fn hue_max_range(c: [f64; 3]) -> (f64, f64, f64) {
+    let [r, g, b] = c;
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let d = max - min;
+    #[expect(clippy::float_cmp, reason = "the maximum is one of the channels")]
+    let h = if d == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * Float((g - b) / d).rem_euclid(6.0).0
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (hue(h), max, d)
+}
+pub(super) const fn hsl_from_srgb(c: [f64; 3]) -> [f64; 3] {
+    let (h, max, d) = hue_max_range(c);
+    let l = max - d / 2.0;
+    let s = is![l <= 0.0 || l >= 1.0, 0.0, d / (1.0 - (2.0 * l - 1.0).abs())];
+    [h, s, l]
+}
+pub(super) const fn srgb_from_hsl(c: [f64; 3]) -> [f64; 3] {
+    const fn f(n: f64, [h, s, l]: [f64; 3]) -> f64 {
+        let k = Float(n + h / 30.0).rem_euclid(12.0).0;
+        l - s * l.min(1.0 - l) * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    }
+    [f(0.0, c), f(8.0, c), f(4.0, c)]
+}
+pub(super) const fn hsv_from_srgb(c: [f64; 3]) -> [f64; 3] {
+    let (h, max, d) = hue_max_range(c);
+    [h, is![max == 0.0, 0.0, d / max], max]
+}
+pub(super) const fn srgb_from_hsv(c: [f64; 3]) -> [f64; 3] {
+    const fn f(n: f64, [h, s, v]: [f64; 3]) -> f64 {
+        let k = Float(n + h / 60.0).rem_euclid(6.0).0;
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    }
+    [f(5.0, c), f(3.0, c), f(1.0, c)]
+}
@@ -0,0 +1,155 @@
+// devela/src/media/visual/color/space/_test.rs
+
+use crate::{Hsl, Hsv, Lab, Lch, Oklab, Oklch, Xyz};
+use crate::{Rgb8, Rgb16, RgbF32, RgbLinF64};
+
+#[track_caller]
+fn assert_near(a: [f64; 3], b: [f64; 3], tolerance: f64) {
+    let near = a.iter().zip(b).all(|(a, b)| (a - b).abs() <= tolerance);
+    assert![near, "{a:?} is not near {b:?} (tolerance {tolerance})"];
+}
+
+const RED: Rgb8 = Rgb8::new(255, 0, 0);
+
+#[test]
+fn reference_values() {
+    assert_near(Xyz::<f64>::from(Rgb8::new(255, 255, 255)).c, Xyz::<f64>::WHITE_D65.c, 1e-9);
+    assert_near(Lab::<f64>::from(Rgb8::new(255, 255, 255)).c, [100.0, 0.0, 0.0], 1e-6);
+    assert_near(Lab::<f64>::from(RED).c, [53.2408, 80.0925, 67.2032], 1e-2);
+    assert_near(Lch::<f64>::from(RED).c, [53.2408, 104.5518, 39.9990], 1e-2);
+    assert_near(Oklab::<f64>::from(RED).c, [0.627_955, 0.224_863, 0.125_846], 1e-5);
+    assert_near(Oklch::<f64>::from(RED).c, [0.627_955, 0.257_683, 29.2339], 1e-4);
+    assert_near(Hsl::<f64>::from(Rgb8::new(0, 128, 255)).c, [209.882, 1.0, 0.5], 1e-3);
+    assert_near(Hsv::<f64>::from(Rgb8::new(0, 128, 255)).c, [209.882, 1.0, 1.0], 1e-3);
+    let luminance = Xyz::<f32>::from(Rgb8::new(0, 255, 0)).luminance().luminance();
+    assert![(luminance - 0.715_169).abs() < 1e-5];
+}
+
+#[test]
+fn rgb_round_trips() {
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(17) {
+            for b in (0..=255).step_by(51) {
+                let rgb = Rgb8::new(r, g, b);
+                assert_eq![Rgb8::from(Hsl::<f32>::from(rgb)), rgb];
+                assert_eq![Rgb8::from(Hsv::<f32>::from(rgb)), rgb];
+                assert_eq![Rgb8::from(Xyz::<f32>::from(rgb)), rgb];
+                assert_eq![Rgb8::from(Lab::<f64>::from(rgb)), rgb];
+                assert_eq![Rgb8::from(Lch::<f64>::from(rgb)), rgb];
+                assert_eq![Rgb8::from(Oklab::<f32>::from(rgb)), rgb];
+                assert_eq![Rgb8::from(Oklch::<f64>::from(rgb)), rgb];
+            }
+        }
+    }
+    let rgb = Rgb16::new(1000, 30_000, 65_535);
+    assert_eq![Rgb16::from(Oklch::<f64>::from(rgb)), rgb];
+    let lin = RgbLinF64 { c: [0.2, 0.5, 0.9] };
+    assert_near(RgbLinF64::from(Lab::<f64>::from(lin)).c, lin.c, 1e-9);
+    let srgb = RgbF32::new(0.2, 0.5, 0.9);
+    let (a, b) = (Hsl::<f32>::from(srgb).c, Hsl::<f32>::from_srgb(srgb.c).c);
+    assert_near(a.map(f64::from), b.map(f64::from), 1e-4);
+}
+
+#[test]
+fn space_conversions() {
+    let lab = Lab::<f64>::new(50.0, 20.0, -30.0);
+    assert_near(lab.to_lch().to_lab().c, lab.c, 1e-9);
+    assert_near(lab.to_xyz().to_lab().c, lab.c, 1e-9);
+    let oklab = Oklab::<f64>::new(0.5, -0.1, 0.05);
+    assert_near(oklab.to_oklch().to_oklab().c, oklab.c, 1e-9);
+    let hsl = Hsl::<f64>::new(300.0, 0.5, 0.25);
+    assert_near(hsl.to_hsv().to_hsl().c, hsl.c, 1e-9);
+    assert_near(Hsl::<f64>::new(0.0, 1.0, 0.5).to_hsv().c, [0.0, 1.0, 1.0], 1e-9);
+}
+
+#[test]
+fn delta_e() {
+    // from Sharma, Wu, and Dalal: The CIEDE2000 color-difference formula
+    let pairs = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+        ([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+        ([22.7233, 20.0904, -46.694], [23.0331, 14.973, -42.5619], 2.0373),
+        ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+    ];
+    for (a, b, expected) in pairs {
+        let (a, b) = (Lab::<f64> { c: a }, Lab::<f64> { c: b });
+        assert![(a.delta_e_2000(b) - expected).abs() < 1e-4, "{a:?} {b:?}"];
+        assert![(b.delta_e_2000(a) - expected).abs() < 1e-4];
+        assert![(a.to_lch().delta_e_2000(b.to_lch()) - expected).abs() < 1e-4];
+    }
+    let (a, b) = (Lab::<f32>::new(50.0, 0.0, 0.0), Lab::<f32>::new(53.0, 4.0, 0.0));
+    assert_eq![a.delta_e_76(b).to_bits(), 5.0_f32.to_bits()];
+    let (a, b) = (Oklab::<f32>::new(0.5, 0.0, 0.0), Oklab::<f32>::new(0.5, 0.03, 0.04));
+    assert![(a.distance(b) - 0.05).abs() < 1e-6];
+}
+
+#[test]
+fn gamut_mapping() {
+    let inside = Oklch::<f64>::from(Rgb8::new(10, 200, 100));
+    assert![inside.is_in_srgb_gamut()];
+    assert_eq![inside.map_to_srgb_gamut(), inside];
+
+    let vivid = Oklch::<f64>::new(0.7, 0.4, 150.0);
+    assert![!vivid.is_in_srgb_gamut()];
+    let mapped = vivid.map_to_srgb_gamut();
+    assert![mapped.c() < vivid.c() && mapped.c() > 0.1];
+    // the result is clipped, so it can differ unnoticeably from its lightness and hue
+    assert![(mapped.l() - 0.7).abs() < 0.02 && (mapped.h() - 150.0).abs() < 5.0];
+    assert![mapped.is_in_srgb_gamut()];
+
+    assert_near(Oklch::<f64>::new(1.5, 0.3, 20.0).map_to_srgb_gamut().c, [1.0, 0.0, 20.0], 0.0);
+    assert_near(Oklch::<f64>::new(-0.1, 0.3, 20.0).map_to_srgb_gamut().c, [0.0, 0.0, 20.0], 0.0);
+}
+
+#[test]
+fn interpolation() {
+    let (a, b) = (Oklch::<f64>::new(0.5, 0.1, 350.0), Oklch::<f64>::new(0.7, 0.2, 30.0));
+    assert_near(a.lerp(b, 0.5).c, [0.6, 0.15, 10.0], 1e-9);
+    assert_near(b.lerp(a, 0.75).c, [0.55, 0.125, 0.0], 1e-9);
+    // gray takes the hue of the other color
+    let gray = Lch::<f64>::from(Rgb8::new(128, 128, 128));
+    let blue = Lch::<f64>::new(50.0, 60.0, 270.0);
+    assert_near([gray.lerp(blue, 0.5).h(), 0.0, 0.0], [270.0, 0.0, 0.0], 1e-9);
+    let mid = Hsl::<f64>::new(0.0, 0.0, 0.5).lerp(Hsl::<f64>::new(120.0, 1.0, 0.5), 0.5);
+    assert_near(mid.c, [120.0, 0.5, 0.5], 1e-9);
+    assert_near(
+        Oklab::<f64>::new(0.0, 0.1, 0.2).lerp(Oklab::<f64>::new(1.0, 0.3, 0.0), 0.25).c,
+        [0.25, 0.15, 0.15],
+        1e-12,
+    );
+}
+
+#[test]
+fn ramps() {
+    let (black, white) = (Oklab::<f32>::new(0.0, 0.0, 0.0), Oklab::<f32>::new(1.0, 0.0, 0.0));
+    let mut grays = [Rgb8::default(); 5];
+    black.ramp(white, &mut grays);
+    assert_eq![grays.map(|c| c.c[0]), [0, 34, 99, 174, 255]];
+    assert![grays.iter().all(|c| c.c[0] == c.c[1] && c.c[1] == c.c[2])];
+
+    // steps are perceptually even
+    let (from, to) = (Oklab::<f64>::from(RED), Oklab::<f64>::from(Rgb8::new(0, 0, 255)));
+    let mut ramp = [Oklab::default(); 9];
+    from.ramp(to, &mut ramp);
+    assert_near(ramp[0].c, from.c, 1e-12);
+    assert_near(ramp[8].c, to.c, 1e-12);
+    let step = ramp[0].distance(ramp[1]);
+    assert![ramp.windows(2).all(|w| (w[0].distance(w[1]) - step).abs() < 1e-9)];
+
+    // a lightness ramp keeps the hue
+    let (from, to) = (Oklch::<f64>::new(0.3, 0.1, 250.0), Oklch::<f64>::new(0.9, 0.1, 250.0));
+    let mut ramp = [Oklch::default(); 4];
+    from.ramp(to, &mut ramp);
+    for (c, l) in ramp.iter().zip([0.3, 0.5, 0.7, 0.9]) {
+        assert_near(c.c, [l, 0.1, 250.0], 1e-12);
+    }
+
+    let mut single = [Rgb8::default(); 1];
+    Oklch::<f32>::from(RED).ramp(Oklch::default(), &mut single);
+    assert_eq![single, [RED]];
+    from.ramp::<Rgb8>(to, &mut []);
+}
@@ -0,0 +1,133 @@
+// devela/src/media/visual/color/space/convert.rs
+//
+//! Implements conversions from and to every `Rgb` type, and interpolation,
+//! for every color space.
+//
+// TOC
+// - trait SrgbChannels
+// - macro impl_space!
+
+use super::_helper::*;
+use crate::{Hsl, Hsv, Lab, Lch, Norm, Oklab, Oklch, Xyz};
+use crate::{Rgb8, Rgb16, RgbF32, RgbF64, RgbLinF32, RgbLinF64};
+
+/// An `Rgb` color that converts from and to linear sRGB channels.
+trait SrgbChannels {
+    /// Returns the linear channels.
+    fn to_linear(self) -> [f64; 3];
+    /// Returns the color of the linear channels, clipped to the sRGB gamut.
+    fn from_linear(c: [f64; 3]) -> Self;
+}
+macro_rules! impl_srgb_channels {
+    () => {
+        impl_srgb_channels![encoded Rgb8: u8_to_f64, f64_to_u8];
+        impl_srgb_channels![encoded Rgb16: u16_to_f64, f64_to_u16];
+        impl_srgb_channels![encoded RgbF32: |c| f64::from(c), |c| c as f32];
+        impl_srgb_channels![encoded RgbF64: |c| c, |c| c];
+        impl_srgb_channels![linear RgbLinF32: |c| f64::from(c), |c| c as f32];
+        impl_srgb_channels![linear RgbLinF64: |c| c, |c| c];
+    };
+    (encoded $Rgb:ty: $to:ident, $from:ident) => {
+        impl_srgb_channels![encoded $Rgb: Norm::$to, Norm::$from];
+    };
+    (encoded $Rgb:ty: $to:expr, $from:expr) => {
+        impl SrgbChannels for $Rgb {
+            fn to_linear(self) -> [f64; 3] { decode_srgb(self.c.map($to)) }
+            fn from_linear(c: [f64; 3]) -> Self { Self { c: encode_srgb(clip(c)).map($from) } }
+        }
+    };
+    (linear $Rgb:ty: $to:expr, $from:expr) => {
+        impl SrgbChannels for $Rgb {
+            fn to_linear(self) -> [f64; 3] { self.c.map($to) }
+            fn from_linear(c: [f64; 3]) -> Self { Self { c: clip(c).map($from) } }
+        }
+    };
+}
+impl_srgb_channels!();
+
+/// Implements the common methods of a color space, and its conversions with every `Rgb` type.
+///
+/// - `$from`, `$to`: the functions converting from and to linear sRGB channels.
+/// - `$lerp`: the function interpolating its channels.
+macro_rules! impl_space {
+    () => {
+        impl_space![Hsl: |c| hsl_from_srgb(encode_srgb(c)), |c| decode_srgb(srgb_from_hsl(c)),
+            |a, b, t| lerp_polar(a, b, t, [1, 0], 0.0)];
+        impl_space![Hsv: |c| hsv_from_srgb(encode_srgb(c)), |c| decode_srgb(srgb_from_hsv(c)),
+            |a, b, t| lerp_polar(a, b, t, [1, 0], 0.0)];
+        impl_space![Xyz: |c| xyz_from_linear(c), |c| linear_from_xyz(c), |a, b, t| lerp(a, b, t)];
+        impl_space![Lab: |c| lab_from_xyz(xyz_from_linear(c)), |c| linear_from_xyz(xyz_from_lab(c)),
+            |a, b, t| lerp(a, b, t)];
+        impl_space![Lch: |c| to_polar(lab_from_xyz(xyz_from_linear(c))),
+            |c| linear_from_xyz(xyz_from_lab(from_polar(c))),
+            |a, b, t| lerp_polar(a, b, t, [1, 2], 0.01)];
+        impl_space![Oklab: |c| oklab_from_linear(c), |c| linear_from_oklab(c), |a, b, t| lerp(a, b, t)];
+        impl_space![Oklch: |c| to_polar(oklab_from_linear(c)), |c| linear_from_oklab(from_polar(c)),
+            |a, b, t| lerp_polar(a, b, t, [1, 2], 0.000_1)];
+    };
+    ($S:ident: |$c:ident| $from:expr, |$d:ident| $to:expr, |$a:ident, $b:ident, $t:ident| $lerp:expr) => {
+        impl_space![@$S<f32>: |$c| $from, |$d| $to, |$a, $b, $t| $lerp];
+        impl_space![@$S<f64>: |$c| $from, |$d| $to, |$a, $b, $t| $lerp];
+        impl_space![@rgb $S<f32>: Rgb8, Rgb16, RgbF32, RgbF64, RgbLinF32, RgbLinF64];
+        impl_space![@rgb $S<f64>: Rgb8, Rgb16, RgbF32, RgbF64, RgbLinF32, RgbLinF64];
+    };
+    (@$S:ident<$f:ty>: |$c:ident| $from:expr, |$d:ident| $to:expr,
+        |$a:ident, $b:ident, $t:ident| $lerp:expr) => {
+        /// # Conversions and interpolation
+        impl $S<$f> {
+            /// Returns the color of the given linear sRGB channels.
+            pub const fn from_linear_srgb(rgb: [$f; 3]) -> Self {
+                let $c = [rgb[0] as f64, rgb[1] as f64, rgb[2] as f64];
+                Self::_from_f64($from)
+            }
+            /// Returns the linear sRGB channels of the color, which can be out of `0..=1`
+            /// for colors outside of the sRGB gamut.
+            pub const fn to_linear_srgb(self) -> [$f; 3] {
+                let $d = self._to_f64();
+                let c = $to;
+                [c[0] as $f, c[1] as $f, c[2] as $f]
+            }
+
+            /// Returns the color interpolated towards `other` by `t`, usually in `0..=1`.
+            ///
+            /// Hues are interpolated along the shorter arc, following the other color
+            /// when a color is gray.
+            pub const fn lerp(self, other: Self, t: $f) -> Self {
+                let ($a, $b, $t) = (self._to_f64(), other._to_f64(), t as f64);
+                Self::_from_f64($lerp)
+            }
+            /// Fills a `ramp` of colors evenly interpolated from `self` to `to`, both included.
+            ///
+            /// Any color type convertible from this one can be used, like [`Rgb8`].
+            pub fn ramp<C: From<Self>>(self, to: Self, ramp: &mut [C]) {
+                let steps = ramp.len().saturating_sub(1).max(1) as $f;
+                for (i, color) in ramp.iter_mut().enumerate() {
+                    *color = self.lerp(to, i as $f / steps).into();
+                }
+            }
+
+            pub(super) const fn _from_f64(c: [f64; 3]) -> Self {
+                Self { c: [c[0] as $f, c[1] as $f, c[2] as $f] }
+            }
+            pub(super) const fn _to_f64(self) -> [f64; 3] {
+                [self.c[0] as f64, self.c[1] as f64, self.c[2] as f64]
+            }
+        }
+    };
+    (@rgb $S:ident<$f:ty>: $($Rgb:ty),+) => { $(
+        impl From<$Rgb> for $S<$f> {
+            fn from(from: $Rgb) -> Self {
+                let c = from.to_linear();
+                Self::from_linear_srgb([c[0] as $f, c[1] as $f, c[2] as $f])
+            }
+        }
+        /// Colors outside of the sRGB gamut are clipped.
+        impl From<$S<$f>> for $Rgb {
+            fn from(from: $S<$f>) -> Self {
+                let c = from.to_linear_srgb();
+                <$Rgb>::from_linear([c[0] as f64, c[1] as f64, c[2] as f64])
+            }
+        }
+    )+ };
+}
+impl_space!();
@@ -0,0 +1,136 @@
+// devela/src/media/visual/color/space/define.rs
+//
+//! Defines [`Hsl`], [`Hsv`], [`Xyz`], [`Lab`], [`Lch`], [`Oklab`], [`Oklch`].
+//
+
+#[doc = crate::_tags!(color)]
+/// A color in the [HSL] cylindrical model of gamma-encoded sRGB.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Hue is in degrees `0..360`, and saturation and lightness are in `0..=1`.
+///
+/// It's simple and fast, but not perceptually uniform: colors with the same lightness
+/// can look very different in brightness.
+///
+/// [HSL]: https://en.wikipedia.org/wiki/HSL_and_HSV
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Hsl<T> {
+    /// Color channels in order: [hue, saturation, lightness].
+    pub c: [T; 3],
+}
+
+#[doc = crate::_tags!(color)]
+/// A color in the [HSV] cylindrical model of gamma-encoded sRGB.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Hue is in degrees `0..360`, and saturation and value are in `0..=1`.
+///
+/// Like [`Hsl`] it's not perceptually uniform.
+///
+/// [HSV]: https://en.wikipedia.org/wiki/HSL_and_HSV
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Hsv<T> {
+    /// Color channels in order: [hue, saturation, value].
+    pub c: [T; 3],
+}
+
+#[doc = crate::_tags!(color)]
+/// A color in the [CIE 1931 XYZ] color space, relative to the D65 white.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Values are relative to a reference white of luminance `y = 1`,
+/// so `y` is the linear [`Luminance`][crate::Luminance].
+///
+/// [CIE 1931 XYZ]: https://en.wikipedia.org/wiki/CIE_1931_color_space
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Xyz<T> {
+    /// Color channels in order: [x, y, z].
+    pub c: [T; 3],
+}
+
+#[doc = crate::_tags!(color)]
+/// A color in the [CIELAB] color space (L\*a\*b\*), relative to the D65 white.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Lightness is in `0..=100`, while `a` (green–red) and `b` (blue–yellow)
+/// are about `-128..=128` for common colors.
+///
+/// Differences between colors are measured with [`delta_e_2000`][Self::delta_e_2000].
+///
+/// [CIELAB]: https://en.wikipedia.org/wiki/CIELAB_color_space
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Lab<T> {
+    /// Color channels in order: [lightness, a, b].
+    pub c: [T; 3],
+}
+
+#[doc = crate::_tags!(color)]
+/// A color in the [CIELCh] color space, the cylindrical form of [`Lab`].
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Lightness is in `0..=100`, chroma is about `0..=150`, and hue is in degrees `0..360`.
+///
+/// [CIELCh]: https://en.wikipedia.org/wiki/CIELAB_color_space#Cylindrical_model
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Lch<T> {
+    /// Color channels in order: [lightness, chroma, hue].
+    pub c: [T; 3],
+}
+
+#[doc = crate::_tags!(color)]
+/// A color in the [Oklab] perceptual color space.
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Lightness is in `0..=1`, while `a` (green–red) and `b` (blue–yellow)
+/// are about `-0.4..=0.4`.
+///
+/// Euclidean [distances][Self::distance] approximate perceived differences,
+/// and straight lines make even gradients.
+///
+/// [Oklab]: https://bottosson.github.io/posts/oklab/
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Oklab<T> {
+    /// Color channels in order: [lightness, a, b].
+    pub c: [T; 3],
+}
+
+#[doc = crate::_tags!(color)]
+/// A color in the [Oklch] color space, the cylindrical form of [`Oklab`].
+#[doc = crate::_doc_meta!{location("media/visual/color")}]
+///
+/// Lightness is in `0..=1`, chroma is about `0..=0.4`, and hue is in degrees `0..360`.
+///
+/// Varying the lightness of a color while keeping its chroma and hue makes
+/// perceptually even ramps, and colors out of the sRGB gamut can be
+/// [mapped][Self::map_to_srgb_gamut] back into it keeping their lightness and hue.
+///
+/// # Example
+/// ```
+/// # use devela::{Oklch, Rgb8};
+/// let brand = Oklch::<f32>::from(Rgb8::new(0x33, 0x66, 0xCC));
+/// let shade = |l| Oklch::<f32>::new(l, brand.c(), brand.h()).map_to_srgb_gamut();
+/// let mut shades = [Rgb8::default(); 5];
+/// shade(0.3).ramp(shade(0.9), &mut shades);
+/// assert![shades.windows(2).all(|w| w[0].c[2] < w[1].c[2])];
+/// ```
+///
+/// [Oklch]: https://bottosson.github.io/posts/oklab/#the-oklab-color-space
+#[repr(C)]
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Oklch<T> {
+    /// Color channels in order: [lightness, chroma, hue].
+    pub c: [T; 3],
+}
@@ -0,0 +1,77 @@
+// devela/src/media/visual/color/space/hsx.rs
+//
+//! Implements [`Hsl`] and [`Hsv`].
+//
+
+use super::_helper::*;
+use crate::{Hsl, Hsv};
+
+macro_rules! impl_hsx {
+    () => {
+        impl_hsx![f32, f64];
+    };
+    ($($f:ty),+) => { $(
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Hsl<$f> {
+            /// New `Hsl` with the given hue in degrees, saturation, and lightness.
+            pub const fn new(h: $f, s: $f, l: $f) -> Self { Self { c: [h, s, l] } }
+            /// The hue, in degrees.
+            pub const fn hue(self) -> $f { self.c[0] }
+            pub const fn h(self) -> $f { self.c[0] }
+            /// The saturation.
+            pub const fn saturation(self) -> $f { self.c[1] }
+            pub const fn s(self) -> $f { self.c[1] }
+            /// The lightness.
+            pub const fn lightness(self) -> $f { self.c[2] }
+            pub const fn l(self) -> $f { self.c[2] }
+        }
+        impl Hsl<$f> {
+            /// Returns the color of the given gamma-encoded sRGB channels.
+            pub const fn from_srgb(rgb: [$f; 3]) -> Self {
+                Self::_from_f64(hsl_from_srgb([rgb[0] as f64, rgb[1] as f64, rgb[2] as f64]))
+            }
+            /// Returns the gamma-encoded sRGB channels of the color.
+            pub const fn to_srgb(self) -> [$f; 3] {
+                let c = srgb_from_hsl(self._to_f64());
+                [c[0] as $f, c[1] as $f, c[2] as $f]
+            }
+            /// Converts the color to [`Hsv`].
+            pub const fn to_hsv(self) -> Hsv<$f> {
+                Hsv::<$f>::from_srgb(self.to_srgb())
+            }
+        }
+
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Hsv<$f> {
+            /// New `Hsv` with the given hue in degrees, saturation, and value.
+            pub const fn new(h: $f, s: $f, v: $f) -> Self { Self { c: [h, s, v] } }
+            /// The hue, in degrees.
+            pub const fn hue(self) -> $f { self.c[0] }
+            pub const fn h(self) -> $f { self.c[0] }
+            /// The saturation.
+            pub const fn saturation(self) -> $f { self.c[1] }
+            pub const fn s(self) -> $f { self.c[1] }
+            /// The value.
+            pub const fn value(self) -> $f { self.c[2] }
+            pub const fn v(self) -> $f { self.c[2] }
+        }
+        impl Hsv<$f> {
+            /// Returns the color of the given gamma-encoded sRGB channels.
+            pub const fn from_srgb(rgb: [$f; 3]) -> Self {
+                Self::_from_f64(hsv_from_srgb([rgb[0] as f64, rgb[1] as f64, rgb[2] as f64]))
+            }
+            /// Returns the gamma-encoded sRGB channels of the color.
+            pub const fn to_srgb(self) -> [$f; 3] {
+                let c = srgb_from_hsv(self._to_f64());
+                [c[0] as $f, c[1] as $f, c[2] as $f]
+            }
+            /// Converts the color to [`Hsl`].
+            pub const fn to_hsl(self) -> Hsl<$f> {
+                Hsl::<$f>::from_srgb(self.to_srgb())
+            }
+        }
+    )+ };
+}
+impl_hsx!();
@@ -0,0 +1,81 @@
+// devela/src/media/visual/color/space/lab.rs
+//
+//! Implements [`Lab`] and [`Lch`].
+//
+
+use super::_helper::*;
+use crate::{Lab, Lch, Xyz};
+
+macro_rules! impl_lab {
+    () => {
+        impl_lab![f32, f64];
+    };
+    ($($f:ty),+) => { $(
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Lab<$f> {
+            /// New `Lab` with the given lightness and `a`, `b` coordinates.
+            pub const fn new(l: $f, a: $f, b: $f) -> Self { Self { c: [l, a, b] } }
+            /// The lightness (L\*).
+            pub const fn lightness(self) -> $f { self.c[0] }
+            pub const fn l(self) -> $f { self.c[0] }
+            /// The green–red coordinate (a\*).
+            pub const fn a(self) -> $f { self.c[1] }
+            /// The blue–yellow coordinate (b\*).
+            pub const fn b(self) -> $f { self.c[2] }
+        }
+        impl Lab<$f> {
+            /// Converts the color to [`Xyz`].
+            pub const fn to_xyz(self) -> Xyz<$f> {
+                Xyz::<$f>::_from_f64(xyz_from_lab(self._to_f64()))
+            }
+            /// Converts the color to [`Lch`].
+            pub const fn to_lch(self) -> Lch<$f> {
+                Lch::<$f>::_from_f64(to_polar(self._to_f64()))
+            }
+
+            /// Returns the [CIEDE2000] color difference with `other`.
+            ///
+            /// A difference of about 1 is the smallest one that can be noticed,
+            /// and differences over 10 are between clearly distinct colors.
+            ///
+            /// [CIEDE2000]: https://en.wikipedia.org/wiki/Color_difference#CIEDE2000
+            pub const fn delta_e_2000(self, other: Self) -> $f {
+                delta_e_2000(self._to_f64(), other._to_f64()) as $f
+            }
+            /// Returns the CIE76 color difference with `other`, their euclidean distance.
+            ///
+            /// It's simpler but less uniform than [`delta_e_2000`][Self::delta_e_2000].
+            pub const fn delta_e_76(self, other: Self) -> $f {
+                distance(self._to_f64(), other._to_f64()) as $f
+            }
+        }
+
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Lch<$f> {
+            /// New `Lch` with the given lightness, chroma, and hue in degrees.
+            pub const fn new(l: $f, c: $f, h: $f) -> Self { Self { c: [l, c, h] } }
+            /// The lightness (L\*).
+            pub const fn lightness(self) -> $f { self.c[0] }
+            pub const fn l(self) -> $f { self.c[0] }
+            /// The chroma.
+            pub const fn chroma(self) -> $f { self.c[1] }
+            pub const fn c(self) -> $f { self.c[1] }
+            /// The hue, in degrees.
+            pub const fn hue(self) -> $f { self.c[2] }
+            pub const fn h(self) -> $f { self.c[2] }
+        }
+        impl Lch<$f> {
+            /// Converts the color to [`Lab`].
+            pub const fn to_lab(self) -> Lab<$f> {
+                Lab::<$f>::_from_f64(from_polar(self._to_f64()))
+            }
+            /// Returns the [CIEDE2000][Lab::delta_e_2000] color difference with `other`.
+            pub const fn delta_e_2000(self, other: Self) -> $f {
+                self.to_lab().delta_e_2000(other.to_lab())
+            }
+        }
+    )+ };
+}
+impl_lab!();
@@ -0,0 +1,21 @@
+// devela/src/media/visual/color/space/mod.rs
+//
+//! Perceptual and cylindrical color spaces.
+//
+
+#[cfg(test)]
+mod _test;
+mod _helper; // color space math
+
+mod convert; // conversions with Rgb and interpolation
+mod define; // Hsl, Hsv, Xyz, Lab, Lch, Oklab, Oklch
+mod hsx;
+mod lab;
+mod oklab;
+mod xyz;
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::define::*;
+    }
+}
@@ -0,0 +1,85 @@
+// devela/src/media/visual/color/space/oklab.rs
+//
+//! Implements [`Oklab`] and [`Oklch`].
+//
+
+use super::_helper::*;
+use crate::{Oklab, Oklch};
+
+macro_rules! impl_oklab {
+    () => {
+        impl_oklab![f32, f64];
+    };
+    ($($f:ty),+) => { $(
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Oklab<$f> {
+            /// New `Oklab` with the given lightness and `a`, `b` coordinates.
+            pub const fn new(l: $f, a: $f, b: $f) -> Self { Self { c: [l, a, b] } }
+            /// The lightness.
+            pub const fn lightness(self) -> $f { self.c[0] }
+            pub const fn l(self) -> $f { self.c[0] }
+            /// The green–red coordinate.
+            pub const fn a(self) -> $f { self.c[1] }
+            /// The blue–yellow coordinate.
+            pub const fn b(self) -> $f { self.c[2] }
+        }
+        impl Oklab<$f> {
+            /// Converts the color to [`Oklch`].
+            pub const fn to_oklch(self) -> Oklch<$f> {
+                Oklch::<$f>::_from_f64(to_polar(self._to_f64()))
+            }
+            /// Returns the perceptual difference with `other` (ΔEOK), their euclidean distance.
+            ///
+            /// A difference of about 0.02 is the smallest one that can be noticed.
+            pub const fn distance(self, other: Self) -> $f {
+                distance(self._to_f64(), other._to_f64()) as $f
+            }
+            /// Returns whether the color is within the sRGB gamut.
+            pub const fn is_in_srgb_gamut(self) -> bool {
+                in_gamut(linear_from_oklab(self._to_f64()))
+            }
+        }
+
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Oklch<$f> {
+            /// New `Oklch` with the given lightness, chroma, and hue in degrees.
+            pub const fn new(l: $f, c: $f, h: $f) -> Self { Self { c: [l, c, h] } }
+            /// The lightness.
+            pub const fn lightness(self) -> $f { self.c[0] }
+            pub const fn l(self) -> $f { self.c[0] }
+            /// The chroma.
+            pub const fn chroma(self) -> $f { self.c[1] }
+            pub const fn c(self) -> $f { self.c[1] }
+            /// The hue, in degrees.
+            pub const fn hue(self) -> $f { self.c[2] }
+            pub const fn h(self) -> $f { self.c[2] }
+        }
+        impl Oklch<$f> {
+            /// Converts the color to [`Oklab`].
+            pub const fn to_oklab(self) -> Oklab<$f> {
+                Oklab::<$f>::_from_f64(from_polar(self._to_f64()))
+            }
+            /// Returns the [perceptual difference][Oklab::distance] with `other`.
+            pub const fn distance(self, other: Self) -> $f {
+                self.to_oklab().distance(other.to_oklab())
+            }
+            /// Returns whether the color is within the sRGB gamut.
+            pub const fn is_in_srgb_gamut(self) -> bool {
+                self.to_oklab().is_in_srgb_gamut()
+            }
+            /// Returns the color mapped into the sRGB gamut.
+            ///
+            /// Colors within the gamut are returned unchanged. Otherwise their chroma
+            /// is reduced, keeping their lightness and hue, until clipping the result
+            /// makes an unnoticeable difference, as the [CSS Color 4] gamut mapping does.
+            ///
+            /// [CSS Color 4]: https://www.w3.org/TR/css-color-4/#binsearch
+            pub const fn map_to_srgb_gamut(self) -> Self {
+                Self::_from_f64(oklch_gamut_map(self._to_f64()))
+            }
+        }
+    )+ };
+}
+impl_oklab!();
@@ -0,0 +1,41 @@
+// devela/src/media/visual/color/space/xyz.rs
+//
+//! Implements [`Xyz`].
+//
+
+use super::_helper::*;
+use crate::{Lab, Luminance, Xyz};
+
+macro_rules! impl_xyz {
+    () => {
+        impl_xyz![f32, f64];
+    };
+    ($($f:ty),+) => { $(
+        #[allow(missing_docs)]
+        #[rustfmt::skip]
+        impl Xyz<$f> {
+            /// New `Xyz` with the given tristimulus values.
+            pub const fn new(x: $f, y: $f, z: $f) -> Self { Self { c: [x, y, z] } }
+            /// The X tristimulus value.
+            pub const fn x(self) -> $f { self.c[0] }
+            /// The Y tristimulus value, which is the luminance.
+            pub const fn y(self) -> $f { self.c[1] }
+            /// The Z tristimulus value.
+            pub const fn z(self) -> $f { self.c[2] }
+        }
+        impl Xyz<$f> {
+            /// The D65 reference white, the white point of sRGB.
+            pub const WHITE_D65: Self = Self::_from_f64(WHITE_D65);
+
+            /// Returns the linear luminance of the color.
+            pub const fn luminance(self) -> Luminance<$f> {
+                Luminance::<$f>::new(self.c[1])
+            }
+            /// Converts the color to [`Lab`].
+            pub const fn to_lab(self) -> Lab<$f> {
+                Lab::<$f>::_from_f64(lab_from_xyz(self._to_f64()))
+            }
+        }
+    )+ };
+}
+impl_xyz!();
@@ -3,7 +3,7 @@
 //! Defines [`Canvas`], [`CanvasRead`], [`CanvasTextel`].
 //
 
-use crate::{Extent, Position, RegionS2};
+use crate::{Coverage8, Extent, Position, RegionS2, is};
 
 #[doc = crate::_tags!(image)]
 /// A writable 2D drawing surface over abstract spatial units.
@@ -50,6 +50,22 @@ pub trait Canvas {
         rect: RegionS2<Self::Unit>,
         color: Self::Color,
     ) -> Result<(), Self::Error>;
+
+    /// Places `color` at `pos`, over the given `coverage` of its area.
+    ///
+    /// This is how anti-aliased rasterizers, like
+    /// [`RasterFillIter`][crate::RasterFillIter], paint their coverage.
+    ///
+    /// The default implementation places the whole color where at least half is covered.
+    /// Backends that can blend should mix the color in proportion to the coverage.
+    fn canvas_cover_color(
+        &mut self,
+        pos: Position<Self::Unit, 2>,
+        color: Self::Color,
+        coverage: Coverage8,
+    ) -> Result<(), Self::Error> {
+        is![coverage.get() >= 128, self.canvas_set_color(pos, color), Ok(())]
+    }
 }
 
 #[doc = crate::_tags!(image)]
@@ -6,7 +6,7 @@
 // use crate::Mismatch;
 #[cfg(any(feature = "std", all(not(feature = "std"), feature = "io")))]
 use crate::IoErrorKind;
-use crate::ParseIntErrorKind;
+use crate::{DeflateError, ParseIntErrorKind};
 
 #[doc = crate::_tags!(image result)]
 /// An image-related result.
@@ -36,6 +36,18 @@ pub enum ImageError {
     /// Invalid magic number
     InvalidMagicNumber,
 
+    /// Malformed or inconsistent encoded data.
+    InvalidData,
+
+    /// A stored checksum doesn't match the data.
+    InvalidChecksum,
+
+    /// The raster format or an encoded feature is not supported.
+    UnsupportedFormat,
+
+    /// A compressed stream could not be decoded.
+    Deflate(DeflateError),
+
     /* from std */
     ///
     InvalidParsedInteger(ParseIntErrorKind),
@@ -70,6 +82,10 @@ mod core_impls {
                 }
                 E::InvalidMagicNumber => write!(f, "Invalid magic number."),
                 E::InvalidPixel => write!(f, "Invalid pixel."),
+                E::InvalidData => write!(f, "Invalid encoded data."),
+                E::InvalidChecksum => write!(f, "Checksum mismatch."),
+                E::UnsupportedFormat => write!(f, "Unsupported format."),
+                E::Deflate(e) => write!(f, "Deflate error: {e}."),
                 //
                 E::InvalidParsedInteger(k) => write!(f, "Invalid parsed integer: {k:?}."),
                 E::FmtError => write!(f, "A core::fmt::Error."),
@@ -79,6 +95,11 @@ mod core_impls {
         }
     }
 
+    impl From<crate::DeflateError> for ImageError {
+        fn from(from: crate::DeflateError) -> Self {
+            Self::Deflate(from)
+        }
+    }
     impl From<crate::ParseIntError> for ImageError {
         fn from(from: crate::ParseIntError) -> Self {
             Self::InvalidParsedInteger(*from.kind())
@@ -0,0 +1,84 @@
+// devela/src/media/visual/image/format/_helper.rs
+//
+//! Defines [`EncodeCursor`], [`raster_rows`].
+//
+
+use crate::ImageError::{InsufficientBuffer, InvalidImageSize, UnsupportedFormat};
+use crate::{Boundary1d, ImageResult, RasterByteSlice, RasterViewBytes, is};
+
+/// A bounds-checked writer over the output of an encoder.
+#[derive(Debug)]
+pub(crate) struct EncodeCursor<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+    /// The length reported when the output is too short.
+    needed: usize,
+}
+impl<'a> EncodeCursor<'a> {
+    /// Creates a cursor at the start of `out`, reporting `needed` bytes if it overflows.
+    pub(crate) const fn new(out: &'a mut [u8], needed: usize) -> Self {
+        Self { out, pos: 0, needed }
+    }
+    /// Returns the number of bytes written.
+    pub(crate) const fn pos(&self) -> usize {
+        self.pos
+    }
+    /// Returns the whole output.
+    pub(crate) fn into_inner(self) -> &'a mut [u8] {
+        self.out
+    }
+    /// Writes a single byte.
+    pub(crate) fn byte(&mut self, byte: u8) -> ImageResult<()> {
+        self.bytes(&[byte])
+    }
+    /// Writes a slice of bytes.
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) -> ImageResult<()> {
+        let end = self.pos + bytes.len();
+        let (needed, available) = (self.needed.max(end), self.out.len());
+        let dst =
+            self.out.get_mut(self.pos..end).ok_or(InsufficientBuffer { needed, available })?;
+        dst.copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+    /// Returns the next `len` bytes to be written in place, and skips them.
+    #[cfg(feature = "video")]
+    pub(crate) fn reserve(&mut self, len: usize) -> ImageResult<&mut [u8]> {
+        let end = self.pos + len;
+        let (needed, available) = (self.needed.max(end), self.out.len());
+        let dst =
+            self.out.get_mut(self.pos..end).ok_or(InsufficientBuffer { needed, available })?;
+        self.pos = end;
+        Ok(dst)
+    }
+    /// Writes `len` zeroed bytes.
+    pub(crate) fn zeros(&mut self, len: usize) -> ImageResult<()> {
+        for _ in 0..len {
+            self.byte(0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the dense rows of a byte raster, from top to bottom.
+///
+/// Fails if the stored pixel width doesn't match the raster format,
+/// or if the bytes are shorter than the layout.
+pub(crate) fn raster_rows<B: AsRef<[u8]>>(
+    raster: &RasterByteSlice<B>,
+) -> ImageResult<impl DoubleEndedIterator<Item = &[u8]>> {
+    let layout = raster.layout();
+    let bpp = raster.format().stored_bytes_per_pixel().unwrap_or(0);
+    is![bpp == 0 || u16::from(layout.bytes_per_pixel) != bpp, return Err(UnsupportedFormat)];
+    let [w, h] = layout.extent.dim;
+    let (row_len, bpl) = (w as usize * bpp as usize, layout.bytes_per_line as usize);
+    let (bytes, upward) = (raster.raster_bytes(), layout.row_start == Boundary1d::Lower);
+    is![
+        layout.min_len_bytes().is_none_or(|len| bytes.len() < len) || bpl < row_len,
+        return Err(InvalidImageSize(Some((w as usize, h as usize))))
+    ];
+    Ok((0..h as usize).map(move |y| {
+        let line = is![upward, h as usize - 1 - y, y];
+        &bytes[line * bpl..][..row_len]
+    }))
+}
@@ -0,0 +1,156 @@
+// devela/src/media/visual/image/format/bmp/_helper.rs
+//
+//! Defines [`BmpHeader`].
+//
+
+use crate::ImageError::{InvalidData, InvalidImageSize, InvalidMagicNumber, UnsupportedFormat};
+use crate::{ImageResult, RasterFormat, is};
+
+/// The 2 magic bytes that start every bitmap file.
+pub(crate) const BMP_MAGIC: [u8; 2] = *b"BM";
+/// The length of the file header.
+pub(crate) const BMP_FILE_HEADER_LEN: usize = 14;
+/// The length of the `BITMAPINFOHEADER`.
+pub(crate) const BMP_INFO_HEADER_LEN: usize = 40;
+/// The length of the `BITMAPV4HEADER`.
+pub(crate) const BMP_V4_HEADER_LEN: usize = 108;
+
+/// Uncompressed pixels.
+pub(crate) const BMP_RGB: u32 = 0;
+/// Run-length encoded 8-bit indices.
+pub(crate) const BMP_RLE8: u32 = 1;
+/// Run-length encoded 4-bit indices.
+pub(crate) const BMP_RLE4: u32 = 2;
+/// Uncompressed pixels with explicit channel masks.
+pub(crate) const BMP_BITFIELDS: u32 = 3;
+/// Uncompressed pixels with explicit channel masks, including alpha.
+pub(crate) const BMP_ALPHABITFIELDS: u32 = 6;
+
+/// The parsed headers of a bitmap file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BmpHeader {
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// Whether the rows are stored top-down, instead of bottom-up.
+    pub top_down: bool,
+    /// The bits per stored pixel.
+    pub bpp: u16,
+    /// The compression method.
+    pub compression: u32,
+    /// The red, green, blue and alpha masks of 16 and 32-bit pixels.
+    pub masks: [u32; 4],
+    /// The offset of the color table.
+    pub palette_offset: usize,
+    /// The number of color table entries.
+    pub palette_len: usize,
+    /// The bytes per color table entry: 3 for OS/2 bitmaps, 4 otherwise.
+    pub palette_entry: usize,
+    /// The offset of the pixel data.
+    pub data_offset: usize,
+}
+impl BmpHeader {
+    /// Parses and validates the headers.
+    pub(crate) fn parse(bytes: &[u8]) -> ImageResult<Self> {
+        is![bytes.len() < BMP_FILE_HEADER_LEN + 16, return Err(InvalidData)];
+        is![bytes[..2] != BMP_MAGIC, return Err(InvalidMagicNumber)];
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at =
+            |i: usize| bytes.get(i..i + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+        let data_offset = u32_at(10).ok_or(InvalidData)? as usize;
+        let dib_len = u32_at(14).ok_or(InvalidData)? as usize;
+        let core = dib_len == 12;
+        is![!core && dib_len < BMP_INFO_HEADER_LEN, return Err(UnsupportedFormat)];
+        let dib_end = BMP_FILE_HEADER_LEN + dib_len;
+        is![bytes.len() < dib_end, return Err(InvalidData)];
+
+        let (width, height, planes, bpp, compression, colors_used) = if core {
+            let (w, h) = (i32::from(u16_at(18)), i32::from(u16_at(20)));
+            (w, h, u16_at(22), u16_at(24), BMP_RGB, 0)
+        } else {
+            let i32_at = |i| u32_at(i).map(|v| v as i32).ok_or(InvalidData);
+            let colors_used = u32_at(46).ok_or(InvalidData)? as usize;
+            (i32_at(18)?, i32_at(22)?, u16_at(26), u16_at(28), u32_at(30).unwrap_or(0), colors_used)
+        };
+        is![planes != 1, return Err(InvalidData)];
+        let (top_down, height) = (height < 0, height.unsigned_abs());
+        is![
+            width <= 0 || height == 0 || width > i32::MAX / 32,
+            return Err(InvalidImageSize(Some((width.unsigned_abs() as usize, height as usize))))
+        ];
+        let valid = match compression {
+            BMP_RGB => matches![bpp, 1 | 4 | 8 | 16 | 24 | 32],
+            BMP_RLE8 => bpp == 8 && !top_down,
+            BMP_RLE4 => bpp == 4 && !top_down,
+            BMP_BITFIELDS | BMP_ALPHABITFIELDS => matches![bpp, 16 | 32],
+            _ => false,
+        };
+        is![!valid, return Err(UnsupportedFormat)];
+
+        // the channel masks follow the info header, or are part of a later version of it
+        let mut palette_offset = dib_end;
+        let masks = match (compression, bpp) {
+            (BMP_BITFIELDS | BMP_ALPHABITFIELDS, _) => {
+                let at = BMP_FILE_HEADER_LEN + BMP_INFO_HEADER_LEN;
+                let mut masks = [0; 4];
+                let count = match (dib_len, compression) {
+                    (BMP_INFO_HEADER_LEN, BMP_BITFIELDS) => 3,
+                    (BMP_INFO_HEADER_LEN, _) => 4,
+                    (len, _) => is![len >= 56, 4, 3],
+                };
+                for (c, mask) in masks.iter_mut().enumerate().take(count) {
+                    *mask = u32_at(at + c * 4).ok_or(InvalidData)?;
+                }
+                is![dib_len == BMP_INFO_HEADER_LEN, palette_offset += count * 4];
+                masks
+            }
+            (_, 16) => [0x7C00, 0x03E0, 0x001F, 0],
+            _ => [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0],
+        };
+        is![masks[..3].contains(&0), return Err(InvalidData)];
+
+        // the color table may be shorter than declared, up to the pixel data
+        is![data_offset > bytes.len() || data_offset < palette_offset, return Err(InvalidData)];
+        let palette_entry = is![core, 3, 4];
+        let palette_len = match bpp {
+            1 | 4 | 8 => {
+                let declared = is![colors_used == 0, 1 << bpp, colors_used.min(1 << bpp)];
+                declared.min((data_offset - palette_offset) / palette_entry)
+            }
+            _ => 0,
+        };
+        Ok(Self {
+            width: width as u32,
+            height,
+            top_down,
+            bpp,
+            compression,
+            masks,
+            palette_offset,
+            palette_len,
+            palette_entry,
+            data_offset,
+        })
+    }
+    /// Whether the pixels are run-length encoded.
+    pub(crate) const fn is_rle(&self) -> bool {
+        matches![self.compression, BMP_RLE8 | BMP_RLE4]
+    }
+    /// Returns the stored bytes per row, padded to 4 bytes.
+    pub(crate) const fn stride(&self) -> usize {
+        (self.width as usize * self.bpp as usize).div_ceil(32) * 4
+    }
+    /// Returns the format of the decoded samples.
+    pub(crate) const fn format(&self) -> RasterFormat {
+        is![self.bpp >= 16 && self.masks[3] != 0, RasterFormat::RGBA8, RasterFormat::RGB8]
+    }
+}
+
+/// Returns the value of a masked channel of `px`, scaled to 8 bits.
+pub(crate) const fn bmp_channel(px: u32, mask: u32) -> u8 {
+    is![mask == 0, return 0];
+    let shift = mask.trailing_zeros();
+    let max = mask >> shift;
+    (((px & mask) >> shift) as u64 * 255 / max as u64) as u8
+}
@@ -0,0 +1,256 @@
+// devela/src/media/visual/image/format/bmp/_test.rs
+
+use crate::{
+    Bmp, BmpDecoder, Boundary1d, Extent2, ImageDecoder, ImageEncoder, ImageError, ImageInfo,
+    ImageResult, RasterByteSlice, RasterFormat, RasterLayout,
+};
+
+/// Writes a bitmap file with a `BITMAPINFOHEADER` into `buf`, returning its length.
+#[allow(clippy::too_many_arguments, reason = "test fixture")]
+fn bmp(
+    buf: &mut [u8],
+    (width, height): (i32, i32),
+    bpp: u16,
+    compression: u32,
+    masks: &[u32],
+    palette: &[[u8; 4]],
+    data: &[u8],
+) -> usize {
+    let data_offset = 14 + 40 + masks.len() * 4 + palette.len() * 4;
+    let len = data_offset + data.len();
+    buf[..2].copy_from_slice(b"BM");
+    buf[2..6].copy_from_slice(&(len as u32).to_le_bytes());
+    buf[6..10].fill(0);
+    buf[10..14].copy_from_slice(&(data_offset as u32).to_le_bytes());
+    buf[14..18].copy_from_slice(&40_u32.to_le_bytes());
+    buf[18..22].copy_from_slice(&width.to_le_bytes());
+    buf[22..26].copy_from_slice(&height.to_le_bytes());
+    buf[26..28].copy_from_slice(&1_u16.to_le_bytes());
+    buf[28..30].copy_from_slice(&bpp.to_le_bytes());
+    buf[30..34].copy_from_slice(&compression.to_le_bytes());
+    buf[34..46].fill(0);
+    buf[46..50].copy_from_slice(&(palette.len() as u32).to_le_bytes());
+    buf[50..54].fill(0);
+    let mut at = 54;
+    for mask in masks {
+        buf[at..at + 4].copy_from_slice(&mask.to_le_bytes());
+        at += 4;
+    }
+    for entry in palette {
+        buf[at..at + 4].copy_from_slice(entry);
+        at += 4;
+    }
+    buf[at..len].copy_from_slice(data);
+    len
+}
+
+/// Decodes a whole image into `out`, returning its info.
+fn decode(bytes: &[u8], out: &mut [u8]) -> ImageResult<ImageInfo> {
+    let mut d = BmpDecoder::new(bytes)?;
+    d.decode_into(out)?;
+    Ok(d.image_info())
+}
+
+// color table entries, stored as BGR0
+const C0: [u8; 4] = [9, 9, 9, 0];
+const C1: [u8; 4] = [0, 0, 255, 0];
+const C2: [u8; 4] = [0, 255, 0, 0];
+const RGB0: [u8; 3] = [9, 9, 9];
+const RGB1: [u8; 3] = [255, 0, 0];
+const RGB2: [u8; 3] = [0, 255, 0];
+
+#[test]
+fn decode_indexed() {
+    let mut buf = [0; 128];
+    let mut out = [0; 18];
+
+    // 1-bit, bottom-up: rows `101` and `011`
+    let len = bmp(&mut buf, (3, 2), 1, 0, &[], &[C0, C1], &[0xA0, 0, 0, 0, 0x60, 0, 0, 0]);
+    let info = decode(&buf[..len], &mut out).unwrap();
+    assert_eq![
+        info,
+        ImageInfo {
+            extent: Extent2::new([3, 2]),
+            format: RasterFormat::RGB8
+        }
+    ];
+    assert_eq![out, [RGB0, RGB1, RGB1, RGB1, RGB0, RGB1].as_flattened()];
+
+    // 4-bit, top-down
+    let len =
+        bmp(&mut buf, (3, -2), 4, 0, &[], &[C0, C1, C2], &[0x01, 0x20, 0, 0, 0x22, 0x10, 0, 0]);
+    let mut d = BmpDecoder::new(&buf[..len]).unwrap();
+    assert_eq![d.next_row(), Some(0)];
+    d.decode_into(&mut out).unwrap();
+    assert_eq![out, [RGB0, RGB1, RGB2, RGB2, RGB2, RGB1].as_flattened()];
+
+    // 8-bit, with an index outside of the color table
+    let len = bmp(&mut buf, (1, 1), 8, 0, &[], &[C0, C1], &[2, 0, 0, 0]);
+    assert_eq![decode(&buf[..len], &mut out), Err(ImageError::InvalidPixel)];
+}
+
+#[test]
+fn decode_rle() {
+    let mut buf = [0; 128];
+    let mut out = [0; 45];
+
+    // RLE8, 5×3, bottom-up
+    #[rustfmt::skip]
+    let data = [
+        2, 1, 0, 3, 0, 1, 2, 0, 0, 0, // y2: run, absolute with padding, end of line
+        0, 2, 2, 1,                   // y1: delta to (2, y0)
+        3, 2, 0, 1,                   // y0: run, end of bitmap
+    ];
+    let len = bmp(&mut buf, (5, 3), 8, 1, &[], &[C0, C1, C2], &data);
+    decode(&buf[..len], &mut out).unwrap();
+    let z = [0; 3];
+    #[rustfmt::skip]
+    assert_eq![out, [z, z, RGB2, RGB2, RGB2, z, z, z, z, z, RGB1, RGB1, RGB0, RGB1, RGB2]
+        .as_flattened()];
+
+    // RLE4, 5×2: absolute with alternating nibbles, runs, a missing end of line
+    #[rustfmt::skip]
+    let data = [
+        0, 3, 0x01, 0x20, 2, 0x22, 0, 0, // y1
+        5, 0x12, 0, 1,                   // y0
+    ];
+    let len = bmp(&mut buf, (5, 2), 4, 2, &[], &[C0, C1, C2], &data);
+    decode(&buf[..len], &mut out[..30]).unwrap();
+    #[rustfmt::skip]
+    assert_eq![&out[..30], [RGB1, RGB2, RGB1, RGB2, RGB1, RGB0, RGB1, RGB2, RGB2, RGB2]
+        .as_flattened()];
+
+    // truncated
+    let len = bmp(&mut buf, (5, 2), 4, 2, &[], &[C0, C1, C2], &data[..3]);
+    assert_eq![decode(&buf[..len], &mut out), Err(ImageError::InvalidData)];
+}
+
+#[test]
+fn decode_bitfields() {
+    let mut buf = [0; 128];
+    let mut out = [0; 8];
+
+    // ARGB4444, with alpha bit fields
+    let masks = [0x0F00, 0x00F0, 0x000F, 0xF000];
+    let len = bmp(&mut buf, (2, 1), 16, 6, &masks, &[], &[0x4C, 0xF8, 0xFF, 0x0F]);
+    let info = decode(&buf[..len], &mut out).unwrap();
+    assert_eq![info.format, RasterFormat::RGBA8];
+    assert_eq![out, [136, 68, 204, 255, 255, 255, 255, 0]];
+
+    // the default 5-5-5 masks
+    let len = bmp(&mut buf, (2, 1), 16, 0, &[], &[], &[0x1F, 0x7C, 0xE0, 0x03]);
+    let info = decode(&buf[..len], &mut out).unwrap();
+    assert_eq![info.format, RasterFormat::RGB8];
+    assert_eq![out[..6], [255, 0, 255, 0, 255, 0]];
+
+    // 32 bits, without alpha
+    let len = bmp(&mut buf, (1, 1), 32, 0, &[], &[], &[3, 2, 1, 99]);
+    assert_eq![decode(&buf[..len], &mut out).unwrap().format, RasterFormat::RGB8];
+    assert_eq![out[..3], [1, 2, 3]];
+}
+
+#[test]
+fn decode_core_header() {
+    // OS/2 1.x, 1-bit 2×1, with 3-byte color table entries
+    #[rustfmt::skip]
+    let bytes = [
+        b'B', b'M', 36, 0, 0, 0, 0, 0, 0, 0, 32, 0, 0, 0,
+        12, 0, 0, 0, 2, 0, 1, 0, 1, 0, 1, 0,
+        1, 2, 3, 4, 5, 6,
+        0x80, 0, 0, 0,
+    ];
+    let mut out = [0; 6];
+    let info = decode(&bytes, &mut out).unwrap();
+    assert_eq![info.extent, Extent2::new([2, 1])];
+    assert_eq![out, [6, 5, 4, 3, 2, 1]];
+}
+
+#[test]
+fn decode_errors() {
+    let mut buf = [0; 128];
+    let len = bmp(&mut buf, (1, 1), 24, 0, &[], &[], &[1, 2, 3, 0]);
+    let mut bad = buf;
+    bad[0] = b'X';
+    assert_eq![BmpDecoder::new(&bad[..len]).unwrap_err(), ImageError::InvalidMagicNumber];
+    assert_eq![BmpDecoder::new(&buf[..20]).unwrap_err(), ImageError::InvalidData];
+    let mut out = [0; 3];
+    assert_eq![decode(&buf[..len - 2], &mut out), Err(ImageError::InvalidData)];
+
+    // JPEG compression, and top-down run-length encoding
+    let len = bmp(&mut buf, (1, 1), 24, 4, &[], &[], &[0; 4]);
+    assert_eq![BmpDecoder::new(&buf[..len]).unwrap_err(), ImageError::UnsupportedFormat];
+    let len = bmp(&mut buf, (1, -1), 8, 1, &[], &[C0], &[0, 1]);
+    assert_eq![BmpDecoder::new(&buf[..len]).unwrap_err(), ImageError::UnsupportedFormat];
+    let len = bmp(&mut buf, (0, 1), 24, 0, &[], &[], &[]);
+    assert![matches![BmpDecoder::new(&buf[..len]), Err(ImageError::InvalidImageSize(_))]];
+}
+
+#[test]
+fn encode_round_trip() {
+    let extent = Extent2::new([5, 3]);
+    let mut pixels = [0; 5 * 3 * 4];
+    for (i, b) in pixels.iter_mut().enumerate() {
+        *b = (i * 17 % 256) as u8;
+    }
+    for (format, ch) in [(RasterFormat::RGB8, 3), (RasterFormat::RGBA8, 4)] {
+        let pixels = &pixels[..15 * ch];
+        let raster = RasterByteSlice::dense(format, extent, pixels).unwrap();
+        let mut bmp = [0; 256];
+        let len = Bmp::encode(&raster, &mut bmp).unwrap();
+        assert![len <= Bmp::encoded_len_max(ImageInfo { extent, format }).unwrap()];
+        assert_eq![u32::from_le_bytes([bmp[2], bmp[3], bmp[4], bmp[5]]) as usize, len];
+        let mut out = [0; 60];
+        let mut d = BmpDecoder::new(&bmp[..len]).unwrap();
+        assert_eq![d.image_info(), ImageInfo { extent, format }];
+        assert_eq![d.decode_into(&mut out).unwrap().bytes(), pixels];
+    }
+
+    // grayscale decodes through the color table
+    let gray = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 110, 120, 130, 140];
+    let raster = RasterByteSlice::dense(RasterFormat::GRAY8, extent, &gray).unwrap();
+    let mut bmp = [0; 2048];
+    let mut out = [0; 45];
+    let len = Bmp::encode(&raster, &mut bmp).unwrap();
+    decode(&bmp[..len], &mut out).unwrap();
+    for (px, g) in out.chunks_exact(3).zip(gray) {
+        assert_eq![px, [g; 3]];
+    }
+    let err = Bmp::encode(&raster, &mut bmp[..len - 1]).unwrap_err();
+    assert![matches![err, ImageError::InsufficientBuffer { .. }]];
+}
+
+#[test]
+fn encode_rle8() {
+    // runs, short and long literals, and a row longer than 255 pixels
+    let extent = Extent2::new([300, 3]);
+    let mut gray = [0; 900];
+    for (i, g) in gray.iter_mut().enumerate() {
+        *g = match i % 300 {
+            0..20 => 7,
+            20..22 => i as u8,
+            22..25 => 8,
+            25..40 => (i * 31) as u8,
+            _ => 200,
+        };
+    }
+    // bottom-up source, with padding
+    let layout = RasterLayout::interleaved(extent, 1, 304, Boundary1d::Lower);
+    let mut stored = [0xEE; 304 * 3];
+    for y in 0..3 {
+        let line = 2 - y;
+        stored[line * 304..][..300].copy_from_slice(&gray[y * 300..][..300]);
+    }
+    let raster = RasterByteSlice::new(RasterFormat::GRAY8, layout, &stored).unwrap();
+    let max = Bmp::encoded_len_max(ImageInfo { extent, format: RasterFormat::GRAY8 }).unwrap();
+    let mut bmp = [0; 4096];
+    let len = Bmp::encode_rle8(&raster, &mut bmp).unwrap();
+    assert![len <= max && len < 1078 + 300];
+
+    let mut out = [0; 2700];
+    decode(&bmp[..len], &mut out).unwrap();
+    for (px, g) in out.chunks_exact(3).zip(gray) {
+        assert_eq![px, [g; 3]];
+    }
+    let rgb = RasterByteSlice::dense(RasterFormat::RGB8, Extent2::new([1, 1]), &[0; 3]).unwrap();
+    assert_eq![Bmp::encode_rle8(&rgb, &mut bmp), Err(ImageError::UnsupportedFormat)];
+}
@@ -0,0 +1,183 @@
+// devela/src/media/visual/image/format/bmp/decoder.rs
+//
+//! Defines [`BmpDecoder`].
+//
+
+use super::{BMP_RLE8, BmpHeader, bmp_channel};
+use crate::ImageError::{InsufficientBuffer, InvalidData, InvalidPixel};
+use crate::{Extent2, ImageDecoder, ImageInfo, ImageResult, is};
+
+#[doc = crate::_tags!(image codec)]
+/// A streaming decoder for <abbr title="Bitmap">BMP</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// Decodes rows in their stored order, which is usually bottom-up,
+/// into the formats listed by [`Bmp`][crate::Bmp].
+///
+/// See [`ImageDecoder`] for the decoding methods.
+#[derive(Clone, Debug)]
+pub struct BmpDecoder<'a> {
+    bytes: &'a [u8],
+    header: BmpHeader,
+    info: ImageInfo,
+    /// The number of rows already decoded.
+    rows: u32,
+    /* run-length decoding state */
+    /// The position of the next run-length code.
+    pos: usize,
+    /// The number of blank rows before the next coded row.
+    skip_rows: u32,
+    /// The number of blank pixels at the start of the next coded row.
+    skip_x: u32,
+    /// Whether the end of the bitmap has been reached.
+    ended: bool,
+}
+
+impl<'a> BmpDecoder<'a> {
+    /// Parses the headers of a bitmap file.
+    pub fn new(bytes: &'a [u8]) -> ImageResult<Self> {
+        let header = BmpHeader::parse(bytes)?;
+        let extent = Extent2::new([header.width, header.height]);
+        Ok(Self {
+            bytes,
+            header,
+            info: ImageInfo { extent, format: header.format() },
+            rows: 0,
+            pos: header.data_offset,
+            skip_rows: 0,
+            skip_x: 0,
+            ended: false,
+        })
+    }
+
+    /// Writes the color of the palette `index` into `px`.
+    fn put_index(&self, index: usize, px: &mut [u8]) -> ImageResult<()> {
+        let h = &self.header;
+        is![index >= h.palette_len, return Err(InvalidPixel)];
+        let bgr = &self.bytes[h.palette_offset + index * h.palette_entry..][..3];
+        px[..3].copy_from_slice(&[bgr[2], bgr[1], bgr[0]]);
+        Ok(())
+    }
+    /// Decodes a stored uncompressed row.
+    fn decode_stored(&self, row: &mut [u8], bpp: usize) -> ImageResult<()> {
+        let h = &self.header;
+        let at = h.data_offset + self.rows as usize * h.stride();
+        let src = self.bytes.get(at..at + h.stride()).ok_or(InvalidData)?;
+        let depth = h.bpp as usize;
+        for (x, px) in row.chunks_exact_mut(bpp).enumerate() {
+            match depth {
+                1 | 4 | 8 => {
+                    let bit = x * depth;
+                    let index = (src[bit / 8] >> (8 - depth - bit % 8)) & ((1 << depth) - 1) as u8;
+                    self.put_index(index as usize, px)?;
+                }
+                24 => px.copy_from_slice(&[src[x * 3 + 2], src[x * 3 + 1], src[x * 3]]),
+                _ => {
+                    let v = if depth == 16 {
+                        u32::from(u16::from_le_bytes([src[x * 2], src[x * 2 + 1]]))
+                    } else {
+                        u32::from_le_bytes([
+                            src[x * 4],
+                            src[x * 4 + 1],
+                            src[x * 4 + 2],
+                            src[x * 4 + 3],
+                        ])
+                    };
+                    for (c, mask) in h.masks.iter().enumerate().take(bpp) {
+                        px[c] = bmp_channel(v, *mask);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Returns the next byte of the run-length codes.
+    fn byte(&mut self) -> ImageResult<u8> {
+        let byte = *self.bytes.get(self.pos).ok_or(InvalidData)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+    /// Decodes a run-length encoded row, leaving skipped pixels as zeros.
+    fn decode_rle(&mut self, row: &mut [u8]) -> ImageResult<()> {
+        row.fill(0);
+        if self.skip_rows > 0 {
+            self.skip_rows -= 1;
+            return Ok(());
+        }
+        is![self.ended, return Ok(())];
+        let (w, rle8) = (self.header.width as usize, self.header.compression == BMP_RLE8);
+        let mut x = core::mem::take(&mut self.skip_x) as usize;
+        let mut put = |this: &Self, x: &mut usize, index: u8| {
+            is![*x < w, this.put_index(index as usize, &mut row[*x * 3..*x * 3 + 3])?];
+            *x += 1;
+            Ok::<_, crate::ImageError>(())
+        };
+        loop {
+            // tolerate a missing end of line after a full row
+            let at_eol = self.bytes.get(self.pos..self.pos + 2).is_some_and(|c| c == [0, 0]);
+            is![x >= w && !at_eol, return Ok(())];
+            let (count, value) = (self.byte()?, self.byte()?);
+            if count > 0 {
+                for i in 0..count {
+                    let index = is![rle8, value, value >> is![i % 2 == 0, 4, 0] & 0x0F];
+                    put(self, &mut x, index)?;
+                }
+                continue;
+            }
+            match value {
+                0 => return Ok(()),
+                1 => {
+                    self.ended = true;
+                    return Ok(());
+                }
+                2 => {
+                    let (dx, dy) = (self.byte()?, self.byte()?);
+                    x += dx as usize;
+                    if dy > 0 {
+                        (self.skip_rows, self.skip_x) = (u32::from(dy) - 1, x as u32);
+                        return Ok(());
+                    }
+                }
+                len => {
+                    let mut byte = 0;
+                    for i in 0..len {
+                        let index = if rle8 {
+                            self.byte()?
+                        } else {
+                            is![i % 2 == 0, byte = self.byte()?];
+                            byte >> is![i % 2 == 0, 4, 0] & 0x0F
+                        };
+                        put(self, &mut x, index)?;
+                    }
+                    // absolute runs are padded to 16 bits
+                    let used = is![rle8, len as usize, (len as usize).div_ceil(2)];
+                    is![used % 2 == 1, self.pos += 1];
+                }
+            }
+        }
+    }
+}
+
+impl ImageDecoder for BmpDecoder<'_> {
+    fn image_info(&self) -> ImageInfo {
+        self.info
+    }
+    fn next_row(&self) -> Option<u32> {
+        let h = self.info.extent.dim[1];
+        is![self.rows >= h, return None];
+        Some(is![self.header.top_down, self.rows, h - 1 - self.rows])
+    }
+    fn decode_row(&mut self, row: &mut [u8]) -> ImageResult<Option<u32>> {
+        let Some(y) = self.next_row() else { return Ok(None) };
+        let (len, available) = (self.row_len(), row.len());
+        let row = row.get_mut(..len).ok_or(InsufficientBuffer { needed: len, available })?;
+        if self.header.is_rle() {
+            self.decode_rle(row)?;
+        } else {
+            let bpp = self.info.format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+            self.decode_stored(row, bpp)?;
+        }
+        self.rows += 1;
+        Ok(Some(y))
+    }
+}
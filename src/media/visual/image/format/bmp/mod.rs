@@ -0,0 +1,24 @@
+// devela/src/media/visual/image/format/bmp/mod.rs
+//
+//! The Windows and OS/2 bitmap format.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (BmpHeader, BMP_*)
+
+mod decoder; // BmpDecoder
+mod namespace; // Bmp
+
+crate::structural_mods! { // _mods, _crate_internals
+    _mods {
+        pub use super::{
+            decoder::*,
+            namespace::*,
+        };
+    }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
+}
@@ -0,0 +1,221 @@
+// devela/src/media/visual/image/format/bmp/namespace.rs
+//
+//! Defines [`Bmp`].
+//
+
+use super::{BMP_BITFIELDS, BMP_FILE_HEADER_LEN, BMP_INFO_HEADER_LEN, BMP_MAGIC, BMP_RGB};
+use super::{BMP_RLE8, BMP_V4_HEADER_LEN};
+use crate::ImageError::{InvalidImageSize, UnsupportedFormat};
+use crate::{
+    EncodeCursor, ImageEncoder, ImageInfo, ImageResult, RasterByteSlice, RasterFormat, is,
+    raster_rows,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// Encoding and decoding for <abbr title="Bitmap">BMP</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// BMP is the device-independent bitmap format of Windows and OS/2,
+/// storing rows bottom-up by default, each padded to 4 bytes.
+///
+/// | Bits per pixel | Compression | Decoded format | Encoded from |
+/// | --- | --- | --- | --- |
+/// | 1, 4, 8 | none | [`RGB8`], from the color table | [`GRAY8`], as 8 bits |
+/// | 4, 8 | RLE4, RLE8 | [`RGB8`], from the color table | [`GRAY8`], as RLE8 |
+/// | 16, 32 | none, or bit fields | [`RGB8`] | — |
+/// | 16, 32 | bit fields with alpha | [`RGBA8`] | [`RGBA8`], as 32 bits |
+/// | 24 | none | [`RGB8`] | [`RGB8`] |
+///
+/// Every header version is read, from the OS/2 `BITMAPCOREHEADER` to the
+/// `BITMAPV5HEADER`. Pixels skipped by run-length deltas decode as zeros.
+///
+/// Decoding is done by [`BmpDecoder`][crate::BmpDecoder], row by row.
+/// Encoding is done by the [`ImageEncoder`] implementation, which writes
+/// uncompressed bottom-up images, or by [`encode_rle8`][Self::encode_rle8].
+/// Grayscale images are written with a grayscale color table,
+/// and images with alpha with a `BITMAPV4HEADER`.
+///
+/// Current limitations:
+/// - Embedded color profiles and the color space endpoints are ignored.
+/// - JPEG and PNG compressed bitmaps are not supported.
+///
+/// [`GRAY8`]: RasterFormat::GRAY8
+/// [`RGB8`]: RasterFormat::RGB8
+/// [`RGBA8`]: RasterFormat::RGBA8
+///
+/// # Example
+/// ```
+/// # use devela::{Bmp, BmpDecoder, Extent2, ImageDecoder, ImageEncoder};
+/// # use devela::{RasterByteSlice, RasterFormat};
+/// let pixels = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+/// let extent = Extent2::new([2, 2]);
+/// let raster = RasterByteSlice::dense(RasterFormat::RGB8, extent, &pixels).unwrap();
+///
+/// let mut bmp = [0; 128];
+/// let len = Bmp::encode(&raster, &mut bmp)?;
+///
+/// let mut decoder = BmpDecoder::new(&bmp[..len])?;
+/// assert_eq![decoder.next_row(), Some(1)]; // bottom-up
+/// let mut out = [0; 12];
+/// let decoded = decoder.decode_into(&mut out)?;
+/// assert_eq![decoded.bytes(), &pixels];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Bmp;
+
+impl Bmp {
+    /// The pixels per meter written by the encoder, which is 72 DPI.
+    const PIXELS_PER_METER: u32 = 2835;
+
+    /// Encodes a grayscale raster as an 8-bit RLE8 bitmap into `out`.
+    ///
+    /// Only accepts [`GRAY8`][RasterFormat::GRAY8] rasters, and never writes more
+    /// than [`encoded_len_max`][ImageEncoder::encoded_len_max] bytes.
+    ///
+    /// Returns the number of bytes written.
+    pub fn encode_rle8<B: AsRef<[u8]>>(
+        raster: &RasterByteSlice<B>,
+        out: &mut [u8],
+    ) -> ImageResult<usize> {
+        is![raster.format() != RasterFormat::GRAY8, return Err(UnsupportedFormat)];
+        let mut c = Self::encode_header(raster, out, BMP_RLE8)?;
+        for row in raster_rows(raster)?.rev() {
+            let mut x = 0;
+            while x < row.len() {
+                let mut len = 1;
+                while x + len < row.len() && len < 255 && row[x + len] == row[x] {
+                    len += 1;
+                }
+                if len > 1 {
+                    c.bytes(&[len as u8, row[x]])?;
+                    x += len;
+                    continue;
+                }
+                // literal pixels, until a repeated pixel starts
+                while x + len < row.len()
+                    && len < 255
+                    && (x + len + 1 == row.len() || row[x + len] != row[x + len + 1])
+                {
+                    len += 1;
+                }
+                if len < 3 {
+                    // absolute mode needs at least 3 pixels
+                    for &px in &row[x..x + len] {
+                        c.bytes(&[1, px])?;
+                    }
+                } else {
+                    c.bytes(&[0, len as u8])?;
+                    c.bytes(&row[x..x + len])?;
+                    is![len % 2 == 1, c.byte(0)?];
+                }
+                x += len;
+            }
+            c.bytes(&[0, 0])?; // end of line
+        }
+        c.bytes(&[0, 1])?; // end of bitmap
+        Self::finish(c)
+    }
+
+    /// Writes the headers and color table, returning the cursor at the pixel data.
+    fn encode_header<'o, B: AsRef<[u8]>>(
+        raster: &RasterByteSlice<B>,
+        out: &'o mut [u8],
+        compression: u32,
+    ) -> ImageResult<EncodeCursor<'o>> {
+        let (format, extent) = (raster.format(), raster.extent());
+        let needed = Self::encoded_len_max(ImageInfo { extent, format })?;
+        let [w, h] = extent.dim;
+        is![
+            w == 0 || h == 0 || w > i32::MAX as u32 / 32 || h > i32::MAX as u32,
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        let (bpp, dib_len, palette_len) = match format {
+            RasterFormat::GRAY8 => (8_u16, BMP_INFO_HEADER_LEN, 256),
+            RasterFormat::RGB8 => (24, BMP_INFO_HEADER_LEN, 0),
+            _ => (32, BMP_V4_HEADER_LEN, 0),
+        };
+        let compression = is![bpp == 32, BMP_BITFIELDS, compression];
+        let data_offset = BMP_FILE_HEADER_LEN + dib_len + palette_len * 4;
+        let image_len = is![compression == BMP_RLE8, 0, Self::stride(w, bpp) * h as usize];
+
+        let mut c = EncodeCursor::new(out, needed);
+        c.bytes(&BMP_MAGIC)?;
+        c.zeros(8)?; // file size, written by `finish`, and reserved
+        c.bytes(&(data_offset as u32).to_le_bytes())?;
+        c.bytes(&(dib_len as u32).to_le_bytes())?;
+        c.bytes(&w.to_le_bytes())?;
+        c.bytes(&h.to_le_bytes())?; // positive: bottom-up
+        c.bytes(&1_u16.to_le_bytes())?;
+        c.bytes(&bpp.to_le_bytes())?;
+        c.bytes(&compression.to_le_bytes())?;
+        c.bytes(&(image_len as u32).to_le_bytes())?;
+        c.bytes(&Self::PIXELS_PER_METER.to_le_bytes())?;
+        c.bytes(&Self::PIXELS_PER_METER.to_le_bytes())?;
+        c.bytes(&(palette_len as u32).to_le_bytes())?;
+        c.zeros(4)?; // all colors are important
+        if dib_len == BMP_V4_HEADER_LEN {
+            for mask in [0x00FF_0000_u32, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000] {
+                c.bytes(&mask.to_le_bytes())?;
+            }
+            c.bytes(b"BGRs")?; // LCS_sRGB
+            c.zeros(36 + 12)?; // endpoints and gamma, unused by sRGB
+        }
+        for gray in 0..palette_len {
+            c.bytes(&[gray as u8, gray as u8, gray as u8, 0])?;
+        }
+        Ok(c)
+    }
+    /// Writes the file size, returning it.
+    fn finish(c: EncodeCursor<'_>) -> ImageResult<usize> {
+        let len = c.pos();
+        let out = c.into_inner();
+        out[2..6].copy_from_slice(&(len as u32).to_le_bytes());
+        Ok(len)
+    }
+    /// Returns the stored bytes per row, padded to 4 bytes.
+    const fn stride(width: u32, bpp: u16) -> usize {
+        (width as usize * bpp as usize).div_ceil(32) * 4
+    }
+}
+
+impl ImageEncoder for Bmp {
+    fn supports_format(format: RasterFormat) -> bool {
+        matches![format, RasterFormat::GRAY8 | RasterFormat::RGB8 | RasterFormat::RGBA8]
+    }
+    /// Returns the number of bytes [`encode`][Self::encode] can write at most,
+    /// which is also enough for [`encode_rle8`][Bmp::encode_rle8].
+    fn encoded_len_max(info: ImageInfo) -> ImageResult<usize> {
+        let (bpp, headers) = match info.format {
+            RasterFormat::GRAY8 => (8, BMP_FILE_HEADER_LEN + BMP_INFO_HEADER_LEN + 256 * 4),
+            RasterFormat::RGB8 => (24, BMP_FILE_HEADER_LEN + BMP_INFO_HEADER_LEN),
+            RasterFormat::RGBA8 => (32, BMP_FILE_HEADER_LEN + BMP_V4_HEADER_LEN),
+            _ => return Err(UnsupportedFormat),
+        };
+        let [w, h] = info.extent.dim;
+        // run-length encoding takes at most 2 bytes per pixel, plus the end of each line
+        let rle_row = (w as usize).checked_mul(2).and_then(|n| n.checked_add(2));
+        let row =
+            rle_row.map(|rle| is![bpp == 8, rle.max(Self::stride(w, bpp)), Self::stride(w, bpp)]);
+        row.and_then(|row| row.checked_mul(h as usize))
+            .and_then(|len| len.checked_add(headers + 2))
+            .ok_or(InvalidImageSize(Some((w as usize, h as usize))))
+    }
+    fn encode<B: AsRef<[u8]>>(raster: &RasterByteSlice<B>, out: &mut [u8]) -> ImageResult<usize> {
+        let mut c = Self::encode_header(raster, out, BMP_RGB)?;
+        let n = raster.format().stored_bytes_per_pixel().unwrap_or(1) as usize;
+        let padding = Self::stride(raster.extent().dim[0], n as u16 * 8)
+            - raster.extent().dim[0] as usize * n;
+        for row in raster_rows(raster)?.rev() {
+            for px in row.chunks_exact(n) {
+                match n {
+                    1 => c.byte(px[0])?,
+                    3 => c.bytes(&[px[2], px[1], px[0]])?,
+                    _ => c.bytes(&[px[2], px[1], px[0], px[3]])?,
+                }
+            }
+            c.zeros(padding)?;
+        }
+        Self::finish(c)
+    }
+}
@@ -0,0 +1,123 @@
+// devela/src/media/visual/image/format/codec.rs
+//
+//! Defines [`ImageDecoder`], [`ImageEncoder`].
+//
+
+use crate::ImageError::{InsufficientBuffer, InvalidImageSize, UnsupportedFormat};
+use crate::{
+    Boundary1d, ImageInfo, ImageResult, RasterBufBytes, RasterByteSlice, RasterFormat, is,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// An image decoder that reports its [`ImageInfo`] up front, and decodes row by row.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// A decoder is created from the encoded bytes by its own constructor,
+/// which parses the header, so that the destination can be sized
+/// from [`image_info`][Self::image_info] before decoding anything.
+///
+/// Rows are decoded in the order they are stored, which is not always top-down.
+/// Each decoded row is dense, in the format reported by the image info.
+///
+/// See [`QoiDecoder`][crate::QoiDecoder], [`BmpDecoder`][crate::BmpDecoder],
+/// and [`TgaDecoder`][crate::TgaDecoder].
+pub trait ImageDecoder {
+    /// Returns the extent of the image, and the format of the decoded samples.
+    fn image_info(&self) -> ImageInfo;
+
+    /// Returns the vertical position of the next row to decode, counting from the top,
+    /// or `None` once all the rows have been decoded.
+    fn next_row(&self) -> Option<u32>;
+
+    /// Decodes the next stored row into the start of `row`.
+    ///
+    /// Returns the vertical position of the row, counting from the top,
+    /// or `None` once all the rows have been decoded.
+    ///
+    /// `row` needs at least [`row_len`][Self::row_len] bytes.
+    fn decode_row(&mut self, row: &mut [u8]) -> ImageResult<Option<u32>>;
+
+    /* provided */
+
+    /// Returns the number of bytes of one decoded row.
+    fn row_len(&self) -> usize {
+        let info = self.image_info();
+        let bpp = info.format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+        info.extent.dim[0] as usize * bpp
+    }
+    /// Returns the number of bytes of the whole decoded image.
+    fn decoded_len(&self) -> ImageResult<usize> {
+        let [w, h] = self.image_info().extent.dim;
+        self.row_len()
+            .checked_mul(h as usize)
+            .ok_or(InvalidImageSize(Some((w as usize, h as usize))))
+    }
+    /// Decodes the remaining rows into a dense, top-down raster over `out`.
+    ///
+    /// `out` needs at least [`decoded_len`][Self::decoded_len] bytes.
+    fn decode_into<'o>(&mut self, out: &'o mut [u8]) -> ImageResult<RasterByteSlice<&'o mut [u8]>>
+    where
+        Self: Sized,
+    {
+        let info = self.image_info();
+        let needed = self.decoded_len()?;
+        is![out.len() < needed, return Err(InsufficientBuffer { needed, available: out.len() })];
+        let [w, h] = info.extent.dim;
+        let mut raster = RasterByteSlice::dense_mut(info.format, info.extent, &mut out[..needed])
+            .ok_or(InvalidImageSize(Some((w as usize, h as usize))))?;
+        self.decode_raster(&mut raster)?;
+        Ok(raster)
+    }
+    /// Decodes the remaining rows into an existing byte raster.
+    ///
+    /// The raster must have the extent of the image and the stored pixel width
+    /// of its format, whose sample layout it is assumed to share.
+    /// Any row order and row padding are supported.
+    fn decode_raster<R: RasterBufBytes + ?Sized>(&mut self, raster: &mut R) -> ImageResult<()>
+    where
+        Self: Sized,
+    {
+        let info = self.image_info();
+        let [w, h] = info.extent.dim;
+        is![
+            raster.raster_extent_bytes() != info.extent,
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        let bpp = info.format.stored_bytes_per_pixel().unwrap_or(0) as usize;
+        is![raster.raster_bytes_per_pixel_bytes() != bpp, return Err(UnsupportedFormat)];
+        let (row_len, bpl) = (self.row_len(), raster.raster_bytes_per_line());
+        let upward = raster.raster_row_start_bytes() == Boundary1d::Lower;
+        let bytes = raster.raster_bytes_mut();
+        while let Some(y) = self.next_row() {
+            is![y >= h, return Err(InvalidImageSize(Some((w as usize, h as usize))))];
+            let line = is![upward, h - 1 - y, y] as usize * bpl;
+            let needed = line + row_len;
+            let available = bytes.len();
+            let row =
+                bytes.get_mut(line..needed).ok_or(InsufficientBuffer { needed, available })?;
+            self.decode_row(row)?;
+        }
+        Ok(())
+    }
+}
+
+#[doc = crate::_tags!(image codec)]
+/// An image encoder that writes a whole raster into caller-provided storage.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// See [`Qoi`][crate::Qoi], [`Bmp`][crate::Bmp], and [`Tga`][crate::Tga].
+pub trait ImageEncoder {
+    /// Returns the number of bytes [`encode`][Self::encode] can write at most
+    /// for an image with the given `info`.
+    ///
+    /// Returns [`UnsupportedFormat`] if the format can't be encoded.
+    fn encoded_len_max(info: ImageInfo) -> ImageResult<usize>;
+
+    /// Returns whether the encoder accepts rasters of the given `format`.
+    fn supports_format(format: RasterFormat) -> bool;
+
+    /// Encodes a raster into `out`, returning the number of bytes written.
+    ///
+    /// Rasters of any row order and row padding are accepted.
+    fn encode<B: AsRef<[u8]>>(raster: &RasterByteSlice<B>, out: &mut [u8]) -> ImageResult<usize>;
+}
@@ -0,0 +1,339 @@
+// devela/src/media/visual/image/format/gif/_helper.rs
+//
+//! Defines [`GifLzwDecoder`], [`GifLzwEncoder`], and GIF constants.
+//
+
+use crate::ImageError::InvalidData;
+use crate::{EncodeCursor, ImageResult, Range, is};
+
+/// The signature of version 87a.
+pub(crate) const GIF_MAGIC_87: [u8; 6] = *b"GIF87a";
+/// The signature of version 89a, which adds extensions.
+pub(crate) const GIF_MAGIC_89: [u8; 6] = *b"GIF89a";
+/// The length of the header and logical screen descriptor.
+pub(crate) const GIF_HEADER_LEN: usize = 13;
+/// The introducer of an extension block.
+pub(crate) const GIF_EXTENSION: u8 = 0x21;
+/// The label of a graphic control extension.
+pub(crate) const GIF_CONTROL: u8 = 0xF9;
+/// The label of an application extension.
+pub(crate) const GIF_APPLICATION: u8 = 0xFF;
+/// The identifiers of the application extensions holding the loop count.
+pub(crate) const GIF_LOOP_APPS: [&[u8; 11]; 2] = [b"NETSCAPE2.0", b"ANIMEXTS1.0"];
+/// The separator of an image descriptor.
+pub(crate) const GIF_IMAGE: u8 = 0x2C;
+/// The trailer that ends the file.
+pub(crate) const GIF_TRAILER: u8 = 0x3B;
+/// The flag of a color table in the screen or image descriptor.
+pub(crate) const GIF_TABLE_FLAG: u8 = 0x80;
+/// The flag of an interlaced image in the image descriptor.
+pub(crate) const GIF_INTERLACED: u8 = 0x40;
+/// The maximum number of codes of the LZW dictionary.
+pub(crate) const GIF_MAX_CODES: usize = 4096;
+
+/// Returns the number of bits of a color table with `len` entries, from 1 to 8.
+pub(crate) const fn gif_table_bits(len: usize) -> u8 {
+    let mut bits = 1;
+    while bits < 8 && (1 << bits) < len {
+        bits += 1;
+    }
+    bits
+}
+
+/// Returns the vertical position of the `n`th stored row of an interlaced image.
+///
+/// Rows are stored in 4 passes: every 8th row from 0, every 8th row from 4,
+/// every 4th row from 2, and every 2nd row from 1.
+pub(crate) const fn gif_interlaced_row(n: u32, height: u32) -> u32 {
+    let p1 = height.div_ceil(8);
+    let p2 = (height + 3) / 8;
+    let p3 = (height + 1) / 4;
+    if n < p1 {
+        n * 8
+    } else if n < p1 + p2 {
+        (n - p1) * 8 + 4
+    } else if n < p1 + p2 + p3 {
+        (n - p1 - p2) * 4 + 2
+    } else {
+        (n - p1 - p2 - p3) * 2 + 1
+    }
+}
+
+/// Returns the offset after the data sub-blocks starting at `pos`.
+pub(crate) fn gif_skip_blocks(bytes: &[u8], mut pos: usize) -> ImageResult<usize> {
+    loop {
+        let len = *bytes.get(pos).ok_or(InvalidData)? as usize;
+        pos += 1 + len;
+        is![len == 0, return Ok(pos)];
+    }
+}
+
+/// Returns the byte ranges of the rows of a rectangle `[x, y, w, h]`
+/// of an RGBA8 canvas of `[width, height]`, clipped to the canvas.
+pub(crate) fn gif_rect_rows(
+    [x, y, w, h]: [u32; 4],
+    [width, height]: [u32; 2],
+) -> impl Iterator<Item = Range<usize>> {
+    let (x0, x1) = (x.min(width) as usize, (x + w).min(width) as usize);
+    let rows = y.min(height) as usize..(y + h).min(height) as usize;
+    rows.map(move |y| {
+        let start = (y * width as usize + x0) * 4;
+        Range { start, end: start + (x1 - x0) * 4 }
+    })
+}
+
+/// Returns the maximum length of the data sub-blocks of the LZW codes of `pixels` pixels.
+pub(crate) const fn gif_lzw_len_max(pixels: usize) -> Option<usize> {
+    // at most one code of 12 bits per pixel, plus the clear codes and the end
+    let Some(codes) = pixels.checked_add(pixels / 1024 + 3) else { return None };
+    let Some(bits) = codes.checked_mul(12) else { return None };
+    let bytes = bits.div_ceil(8);
+    // the minimum code size, the sub-block lengths, and the terminator
+    bytes.checked_add(bytes.div_ceil(255) + 2)
+}
+
+/// A streaming LZW decoder over GIF data sub-blocks.
+#[derive(Clone, Debug)]
+pub(crate) struct GifLzwDecoder {
+    /// The previous code of each string.
+    prefix: [u16; GIF_MAX_CODES],
+    /// The last byte of each string.
+    suffix: [u8; GIF_MAX_CODES],
+    /// The pending bytes of the current string, last first.
+    stack: [u8; GIF_MAX_CODES],
+    stack_len: usize,
+    min_size: u8,
+    code_size: u8,
+    next_code: u16,
+    prev: Option<u16>,
+    /* input */
+    bits: u32,
+    nbits: u8,
+    /// The position of the next byte.
+    pos: usize,
+    /// The bytes left in the current sub-block.
+    block_left: usize,
+}
+impl GifLzwDecoder {
+    /// Returns a decoder, to be started by [`start`][Self::start].
+    pub(crate) const fn new() -> Self {
+        Self {
+            prefix: [0; GIF_MAX_CODES],
+            suffix: [0; GIF_MAX_CODES],
+            stack: [0; GIF_MAX_CODES],
+            stack_len: 0,
+            min_size: 2,
+            code_size: 3,
+            next_code: 6,
+            prev: None,
+            bits: 0,
+            nbits: 0,
+            pos: 0,
+            block_left: 0,
+        }
+    }
+    /// Starts decoding the image data at `pos`, which begins with the minimum code size.
+    pub(crate) fn start(&mut self, bytes: &[u8], pos: usize) -> ImageResult<()> {
+        let min_size = *bytes.get(pos).ok_or(InvalidData)?;
+        is![!(1..=11).contains(&min_size), return Err(InvalidData)];
+        (self.min_size, self.pos, self.block_left) = (min_size, pos + 1, 0);
+        (self.bits, self.nbits, self.stack_len) = (0, 0, 0);
+        self.reset();
+        Ok(())
+    }
+    /// Clears the dictionary.
+    const fn reset(&mut self) {
+        self.code_size = self.min_size + 1;
+        self.next_code = (1 << self.min_size) + 2;
+        self.prev = None;
+    }
+    /// Reads the next code.
+    fn read_code(&mut self, bytes: &[u8]) -> ImageResult<u16> {
+        while self.nbits < self.code_size {
+            if self.block_left == 0 {
+                self.block_left = *bytes.get(self.pos).ok_or(InvalidData)? as usize;
+                self.pos += 1;
+                is![self.block_left == 0, return Err(InvalidData)]; // the data ended early
+            }
+            let byte = *bytes.get(self.pos).ok_or(InvalidData)?;
+            (self.pos, self.block_left) = (self.pos + 1, self.block_left - 1);
+            self.bits |= u32::from(byte) << self.nbits;
+            self.nbits += 8;
+        }
+        let code = self.bits & ((1 << self.code_size) - 1);
+        self.bits >>= self.code_size;
+        self.nbits -= self.code_size;
+        Ok(code as u16)
+    }
+    /// Pushes the bytes of the string of `code` into the stack, last first.
+    fn push_string(&mut self, mut code: u16) {
+        let clear = 1 << self.min_size;
+        while code > clear {
+            self.stack[self.stack_len] = self.suffix[code as usize];
+            self.stack_len += 1;
+            code = self.prefix[code as usize];
+        }
+        self.stack[self.stack_len] = code as u8;
+        self.stack_len += 1;
+    }
+    /// Decodes the next color index.
+    ///
+    /// Fails if the data ends before it.
+    pub(crate) fn next_index(&mut self, bytes: &[u8]) -> ImageResult<u8> {
+        if self.stack_len > 0 {
+            self.stack_len -= 1;
+            return Ok(self.stack[self.stack_len]);
+        }
+        let clear = 1 << self.min_size;
+        let code = loop {
+            let code = self.read_code(bytes)?;
+            is![code != clear, break code];
+            self.reset();
+        };
+        is![code == clear + 1, return Err(InvalidData)]; // the end, before the last pixel
+        let Some(prev) = self.prev else {
+            is![code > clear, return Err(InvalidData)];
+            self.prev = Some(code);
+            return Ok(code as u8);
+        };
+        if code < self.next_code {
+            self.push_string(code);
+        } else if code == self.next_code {
+            // the previous string followed by its own first byte
+            self.stack_len = 1;
+            self.push_string(prev);
+            self.stack[0] = self.stack[self.stack_len - 1];
+        } else {
+            return Err(InvalidData);
+        }
+        if (self.next_code as usize) < GIF_MAX_CODES {
+            let next = self.next_code as usize;
+            (self.prefix[next], self.suffix[next]) = (prev, self.stack[self.stack_len - 1]);
+            self.next_code += 1;
+            is![self.next_code == 1 << self.code_size && self.code_size < 12, self.code_size += 1];
+        }
+        self.prev = Some(code);
+        self.stack_len -= 1;
+        Ok(self.stack[self.stack_len])
+    }
+}
+
+/// A streaming LZW encoder into GIF data sub-blocks.
+#[derive(Clone, Debug)]
+pub(crate) struct GifLzwEncoder {
+    /// The first extension of each string.
+    child: [u16; GIF_MAX_CODES],
+    /// The next extension of the same string as each string.
+    sibling: [u16; GIF_MAX_CODES],
+    /// The last byte of each string.
+    suffix: [u8; GIF_MAX_CODES],
+    min_size: u8,
+    code_size: u8,
+    next_code: u16,
+    /// The code of the string matched so far.
+    prefix: Option<u16>,
+    /* output */
+    bits: u32,
+    nbits: u8,
+    block: [u8; 255],
+    block_len: usize,
+}
+impl GifLzwEncoder {
+    /// Returns an encoder, to be started by [`start`][Self::start].
+    pub(crate) const fn new() -> Self {
+        Self {
+            child: [0; GIF_MAX_CODES],
+            sibling: [0; GIF_MAX_CODES],
+            suffix: [0; GIF_MAX_CODES],
+            min_size: 2,
+            code_size: 3,
+            next_code: 6,
+            prefix: None,
+            bits: 0,
+            nbits: 0,
+            block: [0; 255],
+            block_len: 0,
+        }
+    }
+    /// Starts the image data for a color table of `table_bits` bits.
+    pub(crate) fn start(&mut self, c: &mut EncodeCursor<'_>, table_bits: u8) -> ImageResult<()> {
+        self.min_size = table_bits.max(2);
+        self.code_size = self.min_size + 1;
+        (self.prefix, self.bits, self.nbits, self.block_len) = (None, 0, 0, 0);
+        c.byte(self.min_size)?;
+        self.clear(c)
+    }
+    /// Writes a clear code and clears the dictionary.
+    fn clear(&mut self, c: &mut EncodeCursor<'_>) -> ImageResult<()> {
+        self.emit(c, 1 << self.min_size)?;
+        self.code_size = self.min_size + 1;
+        self.next_code = (1 << self.min_size) + 2;
+        self.child[..1 << self.min_size].fill(0);
+        Ok(())
+    }
+    /// Encodes the next color index.
+    pub(crate) fn push(&mut self, c: &mut EncodeCursor<'_>, index: u8) -> ImageResult<()> {
+        let Some(prefix) = self.prefix else {
+            self.prefix = Some(u16::from(index));
+            return Ok(());
+        };
+        let mut code = self.child[prefix as usize];
+        while code != 0 {
+            if self.suffix[code as usize] == index {
+                self.prefix = Some(code);
+                return Ok(());
+            }
+            code = self.sibling[code as usize];
+        }
+        self.emit(c, prefix)?;
+        // like most encoders, clear before the last code to spare old decoders
+        if (self.next_code as usize) < GIF_MAX_CODES - 1 {
+            let next = self.next_code as usize;
+            (self.suffix[next], self.child[next]) = (index, 0);
+            self.sibling[next] = self.child[prefix as usize];
+            self.child[prefix as usize] = self.next_code;
+            self.next_code += 1;
+            is![self.next_code > 1 << self.code_size && self.code_size < 12, self.code_size += 1];
+        } else {
+            self.clear(c)?;
+        }
+        self.prefix = Some(u16::from(index));
+        Ok(())
+    }
+    /// Writes the last codes and the block terminator.
+    pub(crate) fn finish(&mut self, c: &mut EncodeCursor<'_>) -> ImageResult<()> {
+        if let Some(prefix) = self.prefix.take() {
+            self.emit(c, prefix)?;
+            // the decoder adds one more string after reading it
+            is![self.next_code == 1 << self.code_size && self.code_size < 12, self.code_size += 1];
+        }
+        self.emit(c, (1 << self.min_size) + 1)?;
+        is![self.nbits > 0, self.output(c, self.bits as u8)?];
+        if self.block_len > 0 {
+            c.byte(self.block_len as u8)?;
+            c.bytes(&self.block[..self.block_len])?;
+        }
+        c.byte(0)
+    }
+    /// Writes a code with the current code size.
+    fn emit(&mut self, c: &mut EncodeCursor<'_>, code: u16) -> ImageResult<()> {
+        self.bits |= u32::from(code) << self.nbits;
+        self.nbits += self.code_size;
+        while self.nbits >= 8 {
+            self.output(c, self.bits as u8)?;
+            (self.bits, self.nbits) = (self.bits >> 8, self.nbits - 8);
+        }
+        Ok(())
+    }
+    /// Writes a byte into the current sub-block.
+    fn output(&mut self, c: &mut EncodeCursor<'_>, byte: u8) -> ImageResult<()> {
+        self.block[self.block_len] = byte;
+        self.block_len += 1;
+        if self.block_len == 255 {
+            c.byte(255)?;
+            c.bytes(&self.block)?;
+            self.block_len = 0;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+// devela/src/media/visual/image/format/gif/_test.rs
+
+use crate::{
+    Extent2, Gif, GifDecoder, GifDisposal, GifEncoder, GifFrame, ImageDecoder, ImageEncoder,
+    ImageError, ImageInfo, RasterByteSlice, RasterFormat,
+};
+
+/// The smallest transparent pixel, as commonly embedded in web pages.
+const PIXEL: [u8; 43] = [
+    b'G', b'I', b'F', b'8', b'9', b'a', 1, 0, 1, 0, 0x80, 0, 0, // header, 2 colors
+    0, 0, 0, 0xFF, 0xFF, 0xFF, // color table
+    0x21, 0xF9, 4, 1, 0, 0, 0, 0, // graphic control: transparent index 0
+    0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0, // image descriptor
+    2, 2, 0x44, 1, 0, // clear, 0, end
+    0x3B,
+];
+
+/// Fills `pixels` with runs, repetitions, and noise of 256 indices.
+fn pattern(pixels: &mut [u8]) {
+    let mut seed = 0x2545_F491_u32;
+    for (i, px) in pixels.iter_mut().enumerate() {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        *px = match i % 1000 {
+            0..300 => 7,
+            300..600 => (i % 3) as u8,
+            _ => seed as u8,
+        };
+    }
+}
+
+#[test]
+fn decode_pixel() {
+    let mut d = GifDecoder::new(&PIXEL).unwrap();
+    let frame = d.frame();
+    assert_eq![
+        (frame.transparent, frame.disposal, frame.delay),
+        (Some(0), GifDisposal::Unspecified, 0)
+    ];
+    assert_eq![
+        d.image_info(),
+        ImageInfo {
+            extent: Extent2::new([1, 1]),
+            format: RasterFormat::RGBA8
+        }
+    ];
+    assert_eq![(d.frame_span().offset(), d.frame_span().end()), (19, 42)];
+    assert_eq![(d.background_color(), d.loop_count()), (Some([0, 0, 0]), None)];
+    assert_eq![d.palette(), &[0, 0, 0, 255, 255, 255]];
+    let mut out = [9; 4];
+    assert_eq![d.decode_row(&mut out), Ok(Some(0))];
+    assert_eq![out, [0; 4]];
+    assert_eq![d.decode_row(&mut out), Ok(None)];
+    assert_eq![d.next_frame(), Ok(None)];
+}
+
+#[test]
+fn decode_errors() {
+    let mut bad = PIXEL;
+    bad[0] = b'J';
+    assert_eq![GifDecoder::new(&bad).unwrap_err(), ImageError::InvalidMagicNumber];
+    // no color table
+    let mut bad = PIXEL;
+    bad[10] = 0;
+    assert_eq![GifDecoder::new(&bad).unwrap_err(), ImageError::InvalidData];
+    // the trailer without frames
+    let mut bad = PIXEL;
+    bad[19] = 0x3B;
+    assert_eq![GifDecoder::new(&bad).unwrap_err(), ImageError::InvalidData];
+    // truncated
+    assert_eq![GifDecoder::new(&PIXEL[..40]).unwrap_err(), ImageError::InvalidData];
+    // the end code before the pixel
+    let mut bad = PIXEL;
+    bad[39] = 0b0010_1100;
+    let mut d = GifDecoder::new(&bad).unwrap();
+    assert_eq![d.decode_row(&mut [0; 4]), Err(ImageError::InvalidData)];
+    // an index out of the color table
+    let mut bad = PIXEL;
+    bad[39] = 0x5C;
+    let mut d = GifDecoder::new(&bad).unwrap();
+    assert_eq![d.decode_row(&mut [0; 4]), Err(ImageError::InvalidPixel)];
+}
+
+#[test]
+fn lzw_round_trip() {
+    // long enough to fill the dictionary several times
+    let extent = Extent2::new([250, 120]);
+    let mut indices = [0; 250 * 120];
+    pattern(&mut indices);
+    let max = GifEncoder::HEADER_LEN_MAX + GifEncoder::frame_len_max(extent).unwrap() + 1;
+    let mut gif = [0; 250 * 120 * 2];
+    assert![max <= gif.len()];
+
+    for (bits, interlaced) in [(8, false), (8, true), (1, false), (3, true)] {
+        let colors = 1 << bits;
+        let palette = [[1, 2, 3]; 256];
+        let indices = indices.map(|i| (usize::from(i) % colors) as u8);
+        let mut e = GifEncoder::new(&mut gif, extent, &palette[..colors], Some(3)).unwrap();
+        let mut frame = GifFrame::new(0, extent);
+        frame.interlaced = interlaced;
+        let span = e.add_frame(&frame, None, &indices).unwrap();
+        let len = e.finish().unwrap();
+        assert![len <= max && span.end() == len - 1];
+
+        let mut d = GifDecoder::new(&gif[..len]).unwrap();
+        assert_eq![(d.loop_count(), d.frame().interlaced), (Some(3), interlaced)];
+        assert_eq![d.frame_span(), span];
+        let mut row = [0; 250];
+        let mut order = [0; 120];
+        for y_at in &mut order {
+            let y = d.decode_indices(&mut row).unwrap().unwrap();
+            *y_at = y;
+            assert_eq![row, indices[y as usize * 250..][..250]];
+        }
+        assert_eq![d.decode_indices(&mut row), Ok(None)];
+        if interlaced {
+            assert_eq![order[..3], [0, 8, 16]];
+            assert_eq![order[14..17], [112, 4, 12]];
+            assert_eq![order[119], 119];
+        }
+    }
+}
+
+#[test]
+fn compose_frames() {
+    // a 4×2 screen: a red background frame, then a frame at (1, 0) of 2×2 with a hole,
+    // restored to the previous state, then a frame at (3, 1) clipped to the screen
+    let palette = [[255, 0, 0], [0, 255, 0], [0, 0, 255]];
+    let mut gif = [0; 512];
+    let mut e = GifEncoder::new(&mut gif, Extent2::new([4, 2]), &palette, Some(0)).unwrap();
+    let mut f0 = GifFrame::new(0, Extent2::new([4, 2]));
+    f0.disposal = GifDisposal::Keep;
+    e.add_frame(&f0, None, &[0; 8]).unwrap();
+    let mut f1 = GifFrame::new(1, Extent2::new([2, 2]));
+    (f1.left, f1.delay, f1.transparent) = (1, 7, Some(2));
+    f1.disposal = GifDisposal::Previous;
+    e.add_frame(&f1, None, &[1, 2, 1, 1]).unwrap();
+    let mut f2 = GifFrame::new(2, Extent2::new([2, 2]));
+    (f2.left, f2.top, f2.disposal) = (3, 1, GifDisposal::Background);
+    e.add_frame(&f2, Some(&[[9, 9, 9], [0, 0, 0]]), &[0, 1, 1, 1]).unwrap();
+    let len = e.finish().unwrap();
+
+    const R: [u8; 4] = [255, 0, 0, 255];
+    const G: [u8; 4] = [0, 255, 0, 255];
+    const Z: [u8; 4] = [0; 4];
+    let mut d = GifDecoder::new(&gif[..len]).unwrap();
+    let (mut canvas, mut backup) = ([0; 4 * 2 * 4], [0; 4 * 2 * 4]);
+    let expected = [
+        [R, R, R, R, R, R, R, R],
+        [R, G, R, R, R, G, G, R],
+        [R, R, R, R, R, R, R, [9, 9, 9, 255]],
+    ];
+    for (i, expected) in expected.iter().enumerate() {
+        d.compose(&mut canvas, Some(&mut backup)).unwrap();
+        assert_eq![canvas, expected.as_flattened(), "frame {i}"];
+        assert_eq![d.next_frame().unwrap().is_some(), i < 2];
+    }
+    assert_eq![d.frame().info.index, 2];
+    // the last frame is disposed of by the next compose, with nothing left to draw
+    d.compose(&mut canvas, None).unwrap();
+    assert_eq![canvas[7 * 4..], Z];
+
+    // frames as delays
+    let mut d = GifDecoder::new(&gif[..len]).unwrap();
+    let delays = [d.frame().delay_ms(), d.next_frame().unwrap().unwrap().delay_ms()];
+    assert_eq![delays, [0, 70]];
+    assert![d.compose(&mut [0; 31], None).is_err()];
+}
+
+#[test]
+fn encode_raster() {
+    // exact colors, with transparency
+    let pixels = [1, 2, 3, 255, 4, 5, 6, 0, 7, 8, 9, 200, 1, 2, 3, 255, 9, 9, 9, 0, 0, 0, 0, 128];
+    let extent = Extent2::new([3, 2]);
+    let raster = RasterByteSlice::dense(RasterFormat::RGBA8, extent, &pixels).unwrap();
+    let mut gif = [0; 2048];
+    let len = Gif::encode(&raster, &mut gif).unwrap();
+    let mut out = [0; 24];
+    let mut d = GifDecoder::new(&gif[..len]).unwrap();
+    assert_eq![d.frame().transparent, Some(3)];
+    d.decode_into(&mut out).unwrap();
+    let mut expected = pixels;
+    for px in expected.chunks_exact_mut(4) {
+        if px[3] < 128 {
+            px.fill(0);
+        } else {
+            px[3] = 255;
+        }
+    }
+    assert_eq![out, expected];
+
+    // grayscale
+    let gray: [u8; 6] = [0, 50, 100, 150, 200, 255];
+    let raster = RasterByteSlice::dense(RasterFormat::GRAY8, extent, &gray).unwrap();
+    let len = Gif::encode(&raster, &mut gif).unwrap();
+    let mut d = GifDecoder::new(&gif[..len]).unwrap();
+    d.decode_into(&mut out).unwrap();
+    assert![out.chunks_exact(4).zip(gray).all(|(px, g)| px == [g, g, g, 255])];
+
+    // more colors than fit, quantized
+    let extent = Extent2::new([64, 64]);
+    let mut rgb = [0; 64 * 64 * 3];
+    for (i, px) in rgb.chunks_exact_mut(3).enumerate() {
+        px.copy_from_slice(&[(i % 64 * 4) as u8, (i / 64 * 4) as u8, 128]);
+    }
+    let raster = RasterByteSlice::dense(RasterFormat::RGB8, extent, &rgb).unwrap();
+    let max = Gif::encoded_len_max(ImageInfo { extent, format: RasterFormat::RGB8 }).unwrap();
+    let mut gif = [0; 64 * 64 * 2 + 1024];
+    assert![max <= gif.len()];
+    let len = Gif::encode(&raster, &mut gif).unwrap();
+    let mut out = [0; 64 * 64 * 4];
+    GifDecoder::new(&gif[..len]).unwrap().decode_into(&mut out).unwrap();
+    let worst = out
+        .chunks_exact(4)
+        .zip(rgb.chunks_exact(3))
+        .flat_map(|(a, b)| (0..3).map(move |c| a[c].abs_diff(b[c])))
+        .max();
+    assert![worst.is_some_and(|d| d <= 16)];
+
+    let rgb16 = RasterByteSlice::dense(RasterFormat::RGB16, Extent2::new([1, 1]), &[0; 6]).unwrap();
+    assert_eq![Gif::encode(&rgb16, &mut gif), Err(ImageError::UnsupportedFormat)];
+    let err = Gif::encode(&raster, &mut gif[..100]).unwrap_err();
+    assert_eq![err, ImageError::InsufficientBuffer { needed: max, available: 100 }];
+}
@@ -0,0 +1,307 @@
+// devela/src/media/visual/image/format/gif/decoder.rs
+//
+//! Defines [`GifDecoder`].
+//
+
+use super::{GIF_APPLICATION, GIF_CONTROL, GIF_EXTENSION, GIF_HEADER_LEN, GIF_IMAGE};
+use super::{GIF_INTERLACED, GIF_LOOP_APPS, GIF_MAGIC_87, GIF_MAGIC_89, GIF_TABLE_FLAG};
+use super::{GIF_TRAILER, GifLzwDecoder, gif_interlaced_row, gif_rect_rows, gif_skip_blocks};
+use crate::ImageError::{
+    InsufficientBuffer, InvalidData, InvalidImageSize, InvalidMagicNumber, InvalidPixel,
+};
+use crate::{
+    Extent2, GifDisposal, GifFrame, ImageDecoder, ImageFrameSpan, ImageInfo, ImageResult,
+    RasterFormat, is,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// A streaming decoder for <abbr title="Graphics Interchange Format">GIF</abbr> images
+/// and animations.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// The decoder is always positioned at a frame, starting with the first one,
+/// whose metadata is returned by [`frame`][Self::frame],
+/// and is advanced by [`next_frame`][Self::next_frame].
+///
+/// The current frame can be decoded on its own as [`RGBA8`][RasterFormat::RGBA8]
+/// with the [`ImageDecoder`] methods, in its stored row order, or as palette indices
+/// with [`decode_indices`][Self::decode_indices]. Or it can be drawn over a canvas of the
+/// whole logical screen with [`compose`][Self::compose], which applies the disposal
+/// of the previous frame.
+///
+/// The decoder holds the 16 KiB of its <abbr title="Lempel–Ziv–Welch">LZW</abbr> dictionary.
+#[derive(Clone, Debug)]
+pub struct GifDecoder<'a> {
+    bytes: &'a [u8],
+    screen: Extent2<u32>,
+    /// The offset and entries of the global color table.
+    global: Option<(usize, usize)>,
+    background: u8,
+    loop_count: Option<u16>,
+    /* current frame */
+    frame: GifFrame,
+    span: ImageFrameSpan,
+    /// The offset and entries of the local color table.
+    local: Option<(usize, usize)>,
+    /// The offset after the image data.
+    end: usize,
+    /// The number of rows already decoded.
+    rows: u32,
+    lzw: GifLzwDecoder,
+    /// The disposal and area of the last composed frame.
+    composed: Option<(GifDisposal, [u32; 4])>,
+}
+
+impl<'a> GifDecoder<'a> {
+    /// Parses the header and the blocks up to the first frame.
+    pub fn new(bytes: &'a [u8]) -> ImageResult<Self> {
+        is![bytes.len() < GIF_HEADER_LEN, return Err(InvalidData)];
+        let magic = &bytes[..6];
+        is![magic != GIF_MAGIC_87 && magic != GIF_MAGIC_89, return Err(InvalidMagicNumber)];
+        let u16_at = |i: usize| u32::from(u16::from_le_bytes([bytes[i], bytes[i + 1]]));
+        let (w, h, flags) = (u16_at(6), u16_at(8), bytes[10]);
+        is![w == 0 || h == 0, return Err(InvalidImageSize(Some((w as usize, h as usize))))];
+        let global = (flags & GIF_TABLE_FLAG != 0).then_some((GIF_HEADER_LEN, 2 << (flags & 7)));
+        let pos = GIF_HEADER_LEN + global.map_or(0, |(_, len)| len * 3);
+        is![bytes.len() < pos, return Err(InvalidData)];
+        let frame = GifFrame::new(0, Extent2::new([0, 0]));
+        let mut decoder = Self {
+            bytes,
+            screen: Extent2::new([w, h]),
+            global,
+            background: bytes[11],
+            loop_count: None,
+            frame,
+            span: ImageFrameSpan::new_unchecked(frame.info, 0, 0),
+            local: None,
+            end: pos,
+            rows: 0,
+            lzw: GifLzwDecoder::new(),
+            composed: None,
+        };
+        is![!decoder.parse_frame(0)?, return Err(InvalidData)]; // no frames
+        Ok(decoder)
+    }
+
+    /// Returns the extent of the logical screen, with the format of a composed canvas.
+    #[must_use]
+    pub const fn screen_info(&self) -> ImageInfo {
+        ImageInfo { extent: self.screen, format: RasterFormat::RGBA8 }
+    }
+    /// Returns the background color of the global color table, if there's one.
+    ///
+    /// This color is informative; [`compose`][Self::compose] clears to transparent.
+    #[must_use]
+    pub fn background_color(&self) -> Option<[u8; 3]> {
+        self.table_color(self.global?, self.background)
+    }
+    /// Returns the number of times to repeat the animation, where 0 is forever,
+    /// if the file declares it before the current frame.
+    #[must_use]
+    pub const fn loop_count(&self) -> Option<u16> {
+        self.loop_count
+    }
+    /// Returns the metadata of the current frame.
+    #[must_use]
+    pub const fn frame(&self) -> GifFrame {
+        self.frame
+    }
+    /// Returns the bytes of the current frame, from its graphic control extension,
+    /// if any, to the end of its image data.
+    #[must_use]
+    pub const fn frame_span(&self) -> ImageFrameSpan {
+        self.span
+    }
+    /// Returns the RGB bytes of the color table of the current frame.
+    #[must_use]
+    pub fn palette(&self) -> &'a [u8] {
+        let (offset, len) = self.local.or(self.global).unwrap_or((0, 0));
+        &self.bytes[offset..offset + len * 3]
+    }
+
+    /// Advances to the next frame, returning its metadata,
+    /// or `None` once the trailer is reached.
+    ///
+    /// Any rows of the current frame not yet decoded are skipped.
+    pub fn next_frame(&mut self) -> ImageResult<Option<GifFrame>> {
+        let index = self.frame.info.index + 1;
+        if self.parse_frame(index)? {
+            Ok(Some(self.frame))
+        } else {
+            self.rows = self.frame.extent().dim[1];
+            Ok(None)
+        }
+    }
+
+    /// Decodes the next stored row of the current frame as palette indices,
+    /// into the start of `row`, which needs one byte per pixel.
+    ///
+    /// Returns the vertical position of the row, counting from the top of the frame,
+    /// or `None` once all the rows have been decoded.
+    pub fn decode_indices(&mut self, row: &mut [u8]) -> ImageResult<Option<u32>> {
+        let Some(y) = self.next_row() else { return Ok(None) };
+        let (len, available) = (self.frame.extent().dim[0] as usize, row.len());
+        let row = row.get_mut(..len).ok_or(InsufficientBuffer { needed: len, available })?;
+        for index in row {
+            *index = self.lzw.next_index(self.bytes)?;
+        }
+        self.rows += 1;
+        Ok(Some(y))
+    }
+
+    /// Draws the remaining rows of the current frame over a dense, top-down
+    /// [`RGBA8`][RasterFormat::RGBA8] canvas of the logical screen.
+    ///
+    /// First, the area of the last composed frame is disposed of, as it requested.
+    /// Then the frame is drawn, leaving its transparent pixels unchanged,
+    /// and clipping it to the screen.
+    ///
+    /// Restoring an area to a previous state needs a `backup` of the same length
+    /// as the canvas, passed on every call. Without one, such areas are kept.
+    pub fn compose(&mut self, canvas: &mut [u8], backup: Option<&mut [u8]>) -> ImageResult<()> {
+        let [sw, sh] = self.screen.dim;
+        let needed = sw as usize * sh as usize * 4;
+        for buf in [Some(&*canvas), backup.as_deref()].into_iter().flatten() {
+            is![
+                buf.len() < needed,
+                return Err(InsufficientBuffer { needed, available: buf.len() })
+            ];
+        }
+        if let Some((disposal, area)) = self.composed.take() {
+            for range in gif_rect_rows(area, [sw, sh]) {
+                match (disposal, backup.as_deref()) {
+                    (GifDisposal::Background, _) => canvas[range].fill(0),
+                    (GifDisposal::Previous, Some(backup)) => {
+                        canvas[range].copy_from_slice(&backup[range]);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let f = self.frame;
+        let [w, h] = f.extent().dim;
+        let area = [f.left, f.top, w, h];
+        if let (GifDisposal::Previous, Some(backup)) = (f.disposal, backup) {
+            for range in gif_rect_rows(area, [sw, sh]) {
+                backup[range].copy_from_slice(&canvas[range]);
+            }
+        }
+        while let Some(y) = self.next_row() {
+            let sy = f.top + y;
+            for x in 0..w {
+                let index = self.lzw.next_index(self.bytes)?;
+                let sx = f.left + x;
+                is![f.transparent == Some(index) || sx >= sw || sy >= sh, continue];
+                let at = (sy as usize * sw as usize + sx as usize) * 4;
+                canvas[at..at + 4].copy_from_slice(&self.color(index)?);
+            }
+            self.rows += 1;
+        }
+        self.composed = Some((f.disposal, area));
+        Ok(())
+    }
+
+    /* helpers */
+
+    /// Returns the color of a color table entry.
+    fn table_color(&self, (offset, len): (usize, usize), index: u8) -> Option<[u8; 3]> {
+        is![index as usize >= len, return None];
+        let rgb = &self.bytes[offset + index as usize * 3..][..3];
+        Some([rgb[0], rgb[1], rgb[2]])
+    }
+    /// Returns the RGBA color of an index of the current frame.
+    fn color(&self, index: u8) -> ImageResult<[u8; 4]> {
+        is![self.frame.transparent == Some(index), return Ok([0; 4])];
+        let table = self.local.or(self.global).ok_or(InvalidData)?;
+        let [r, g, b] = self.table_color(table, index).ok_or(InvalidPixel)?;
+        Ok([r, g, b, 255])
+    }
+
+    /// Parses the blocks from the end of the current frame up to the next one,
+    /// returning `false` at the trailer.
+    fn parse_frame(&mut self, index: u32) -> ImageResult<bool> {
+        let bytes = self.bytes;
+        let byte = |i: usize| bytes.get(i).copied().ok_or(InvalidData);
+        let u16_at =
+            |i: usize| Ok::<_, crate::ImageError>(u16::from_le_bytes([byte(i)?, byte(i + 1)?]));
+        let (mut pos, mut start) = (self.end, None);
+        let mut frame = GifFrame::new(index, Extent2::new([0, 0]));
+        loop {
+            match byte(pos)? {
+                GIF_EXTENSION => {
+                    let label = byte(pos + 1)?;
+                    if label == GIF_CONTROL && byte(pos + 2)? >= 4 {
+                        let flags = byte(pos + 3)?;
+                        frame.disposal = GifDisposal::from_bits(flags >> 2);
+                        frame.delay = u16_at(pos + 4)?;
+                        frame.transparent = (flags & 1 != 0).then_some(byte(pos + 6)?);
+                        start = Some(pos);
+                    } else if label == GIF_APPLICATION && byte(pos + 2)? == 11 {
+                        let app = bytes.get(pos + 3..pos + 14).ok_or(InvalidData)?;
+                        let sub = pos + 14;
+                        if GIF_LOOP_APPS.iter().any(|a| a[..] == *app)
+                            && byte(sub)? >= 3
+                            && byte(sub + 1)? == 1
+                        {
+                            self.loop_count = Some(u16_at(sub + 2)?);
+                        }
+                    }
+                    pos = gif_skip_blocks(bytes, pos + 2)?;
+                }
+                GIF_IMAGE => {
+                    let [left, top, w, h] = [1, 3, 5, 7].map(|i| u16_at(pos + i).map(u32::from));
+                    let (w, h) = (w?, h?);
+                    is![
+                        w == 0 || h == 0,
+                        return Err(InvalidImageSize(Some((w as usize, h as usize))))
+                    ];
+                    let flags = byte(pos + 9)?;
+                    (frame.left, frame.top) = (left?, top?);
+                    frame.info.image.extent = Extent2::new([w, h]);
+                    frame.interlaced = flags & GIF_INTERLACED != 0;
+                    let mut data = pos + 10;
+                    self.local = None;
+                    if flags & GIF_TABLE_FLAG != 0 {
+                        let len = 2 << (flags & 7);
+                        self.local = Some((data, len));
+                        data += len * 3;
+                    }
+                    is![self.local.or(self.global).is_none(), return Err(InvalidData)];
+                    // the minimum code size, then the data sub-blocks
+                    self.end = gif_skip_blocks(bytes, data + 1)?;
+                    let start = start.unwrap_or(pos);
+                    self.span = ImageFrameSpan::from_range(frame.info, start, self.end)
+                        .ok_or(InvalidData)?;
+                    self.lzw.start(bytes, data)?;
+                    (self.frame, self.rows) = (frame, 0);
+                    return Ok(true);
+                }
+                GIF_TRAILER => return Ok(false),
+                _ => return Err(InvalidData),
+            }
+        }
+    }
+}
+
+impl ImageDecoder for GifDecoder<'_> {
+    /// Returns the extent of the current frame, decoded as [`RGBA8`][RasterFormat::RGBA8].
+    fn image_info(&self) -> ImageInfo {
+        self.frame.info.image
+    }
+    fn next_row(&self) -> Option<u32> {
+        let h = self.frame.extent().dim[1];
+        is![self.rows >= h, return None];
+        Some(is![self.frame.interlaced, gif_interlaced_row(self.rows, h), self.rows])
+    }
+    fn decode_row(&mut self, row: &mut [u8]) -> ImageResult<Option<u32>> {
+        let (len, available) = (self.row_len(), row.len());
+        is![row.len() < len, return Err(InsufficientBuffer { needed: len, available })];
+        let Some(y) = self.decode_indices(row)? else { return Ok(None) };
+        // expand the indices in place, from the end
+        for x in (0..len / 4).rev() {
+            let rgba = self.color(row[x])?;
+            row[x * 4..x * 4 + 4].copy_from_slice(&rgba);
+        }
+        Ok(Some(y))
+    }
+}
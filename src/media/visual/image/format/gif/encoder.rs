@@ -0,0 +1,236 @@
+// devela/src/media/visual/image/format/gif/encoder.rs
+//
+//! Defines [`GifEncoder`].
+//
+
+use super::{GIF_APPLICATION, GIF_CONTROL, GIF_EXTENSION, GIF_HEADER_LEN, GIF_IMAGE};
+use super::{GIF_INTERLACED, GIF_LOOP_APPS, GIF_MAGIC_89, GIF_TABLE_FLAG, GIF_TRAILER};
+use super::{GifLzwEncoder, gif_interlaced_row, gif_lzw_len_max, gif_table_bits};
+use crate::ImageError::{InvalidData, InvalidImageSize, InvalidPixel};
+use crate::{EncodeCursor, Extent2, GifFrame, ImageFrameSpan, ImageResult, is};
+
+#[doc = crate::_tags!(image codec)]
+/// A streaming encoder for <abbr title="Graphics Interchange Format">GIF</abbr>
+/// images and animations, over caller-provided storage.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// Frames are given as palette indices, along with their [`GifFrame`] metadata,
+/// and an optional local color table that overrides the global one.
+/// To encode true color frames, quantize them first into a [`Palette`][crate::Palette],
+/// with or without dithering.
+///
+/// A file needs at most [`HEADER_LEN_MAX`][Self::HEADER_LEN_MAX] bytes,
+/// plus [`frame_len_max`][Self::frame_len_max] bytes per frame, plus 1 for the trailer.
+///
+/// The encoder holds the 20 KiB of its <abbr title="Lempel–Ziv–Welch">LZW</abbr> dictionary.
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, GifDecoder, GifDisposal, GifEncoder, GifFrame};
+/// let palette = [[0, 0, 0], [255, 255, 255]];
+/// let mut gif = [0; 256];
+/// let mut encoder = GifEncoder::new(&mut gif, Extent2::new([2, 2]), &palette, Some(0))?;
+/// for (index, pixels) in [[0, 1, 1, 0], [1, 0, 0, 1]].iter().enumerate() {
+///     let mut frame = GifFrame::new(index as u32, Extent2::new([2, 2]));
+///     (frame.delay, frame.disposal) = (50, GifDisposal::Background);
+///     encoder.add_frame(&frame, None, pixels)?;
+/// }
+/// let len = encoder.finish()?;
+///
+/// let mut decoder = GifDecoder::new(&gif[..len])?;
+/// assert_eq![(decoder.loop_count(), decoder.frame().delay_ms()), (Some(0), 500)];
+/// let mut canvas = [0; 2 * 2 * 4];
+/// decoder.compose(&mut canvas, None)?;
+/// assert_eq![canvas[4..8], [255, 255, 255, 255]];
+/// assert![decoder.next_frame()?.is_some()];
+/// decoder.compose(&mut canvas, None)?;
+/// assert_eq![canvas[..4], [255, 255, 255, 255]];
+/// assert![decoder.next_frame()?.is_none()];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct GifEncoder<'o> {
+    c: EncodeCursor<'o>,
+    screen: Extent2<u32>,
+    /// The number of colors of the global color table.
+    global_len: usize,
+    lzw: GifLzwEncoder,
+}
+
+impl<'o> GifEncoder<'o> {
+    /// The maximum length of the header, the global color table, and the loop extension.
+    pub const HEADER_LEN_MAX: usize = GIF_HEADER_LEN + 256 * 3 + 19;
+
+    /// Returns the maximum length of a frame of the given `extent`,
+    /// with a local color table.
+    pub const fn frame_len_max(extent: Extent2<u32>) -> ImageResult<usize> {
+        let [w, h] = extent.dim;
+        let len = (w as usize).checked_mul(h as usize);
+        // the graphic control extension, the image descriptor, and the color table
+        let headers = 8 + 10 + 256 * 3;
+        match len {
+            Some(pixels) => match gif_lzw_len_max(pixels) {
+                Some(lzw) => Ok(lzw + headers),
+                None => Err(InvalidImageSize(Some((w as usize, h as usize)))),
+            },
+            None => Err(InvalidImageSize(Some((w as usize, h as usize)))),
+        }
+    }
+
+    /// Writes the header of a file with a logical screen of `screen` pixels,
+    /// and the given global color table, which can be empty.
+    ///
+    /// A `loop_count` makes an animation repeat that number of times, or forever if it's 0.
+    pub fn new(
+        out: &'o mut [u8],
+        screen: Extent2<u32>,
+        palette: &[[u8; 3]],
+        loop_count: Option<u16>,
+    ) -> ImageResult<Self> {
+        Self::with_needed(out, screen, palette, loop_count, 0)
+    }
+
+    /// Writes the frame of `indices`, one byte per pixel, in top-down rows.
+    ///
+    /// The frame is given its own color table if `palette` is not `None`.
+    /// The graphic control extension is only written when the frame has a delay,
+    /// a disposal, or a transparent index.
+    ///
+    /// Returns the span of the written frame.
+    pub fn add_frame(
+        &mut self,
+        frame: &GifFrame,
+        palette: Option<&[[u8; 3]]>,
+        indices: &[u8],
+    ) -> ImageResult<ImageFrameSpan> {
+        let [w, h] = frame.extent().dim;
+        is![
+            indices.len() < w as usize * h as usize,
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        let rows = (0..h).flat_map(|n| {
+            let y = is![frame.interlaced, gif_interlaced_row(n, h), n] as usize;
+            indices[y * w as usize..][..w as usize].iter().copied()
+        });
+        self.write_frame(frame, palette, rows)
+    }
+
+    /// Writes the trailer, returning the length of the file.
+    pub fn finish(mut self) -> ImageResult<usize> {
+        self.c.byte(GIF_TRAILER)?;
+        Ok(self.c.pos())
+    }
+
+    /// Returns the extent of the logical screen.
+    pub const fn screen(&self) -> Extent2<u32> {
+        self.screen
+    }
+
+    /* helpers */
+
+    /// Writes the header, reporting `needed` bytes if the output is too short.
+    pub(super) fn with_needed(
+        out: &'o mut [u8],
+        screen: Extent2<u32>,
+        palette: &[[u8; 3]],
+        loop_count: Option<u16>,
+        needed: usize,
+    ) -> ImageResult<Self> {
+        let [w, h] = screen.dim;
+        is![
+            w == 0 || h == 0 || w > 0xFFFF || h > 0xFFFF,
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        is![palette.len() > 256, return Err(InvalidData)];
+        let mut c = EncodeCursor::new(out, needed);
+        c.bytes(&GIF_MAGIC_89)?;
+        c.bytes(&(w as u16).to_le_bytes())?;
+        c.bytes(&(h as u16).to_le_bytes())?;
+        if palette.is_empty() {
+            c.bytes(&[0, 0, 0])?;
+        } else {
+            let bits = gif_table_bits(palette.len());
+            // the color resolution is informative
+            c.bytes(&[GIF_TABLE_FLAG | (bits - 1) << 4 | (bits - 1), 0, 0])?;
+            Self::write_table(&mut c, palette, bits)?;
+        }
+        if let Some(count) = loop_count {
+            c.bytes(&[GIF_EXTENSION, GIF_APPLICATION, 11])?;
+            c.bytes(GIF_LOOP_APPS[0])?;
+            c.bytes(&[3, 1])?;
+            c.bytes(&count.to_le_bytes())?;
+            c.byte(0)?;
+        }
+        Ok(Self {
+            c,
+            screen,
+            global_len: palette.len(),
+            lzw: GifLzwEncoder::new(),
+        })
+    }
+
+    /// Writes a frame of `indices` in their stored order, as many as its pixels.
+    pub(super) fn write_frame(
+        &mut self,
+        frame: &GifFrame,
+        palette: Option<&[[u8; 3]]>,
+        indices: impl Iterator<Item = u8>,
+    ) -> ImageResult<ImageFrameSpan> {
+        let [w, h] = frame.extent().dim;
+        let (right, bottom) = (frame.left.saturating_add(w), frame.top.saturating_add(h));
+        is![
+            w == 0 || h == 0 || right > 0xFFFF || bottom > 0xFFFF,
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        let table_len = palette.map_or(self.global_len, <[_]>::len);
+        is![table_len == 0 || table_len > 256, return Err(InvalidData)];
+        is![frame.transparent.is_some_and(|t| t as usize >= table_len), return Err(InvalidPixel)];
+
+        let start = self.c.pos();
+        let bits = Self::write_headers(&mut self.c, frame, palette, self.global_len)?;
+        self.lzw.start(&mut self.c, bits)?;
+        for index in indices {
+            is![index as usize >= table_len, return Err(InvalidPixel)];
+            self.lzw.push(&mut self.c, index)?;
+        }
+        self.lzw.finish(&mut self.c)?;
+        ImageFrameSpan::from_range(frame.info, start, self.c.pos()).ok_or(InvalidData)
+    }
+    /// Writes a color table padded to `2^bits` entries.
+    fn write_table(c: &mut EncodeCursor<'_>, palette: &[[u8; 3]], bits: u8) -> ImageResult<()> {
+        for rgb in palette {
+            c.bytes(rgb)?;
+        }
+        c.zeros(((1 << bits) - palette.len()) * 3)
+    }
+    /// Writes the graphic control extension, the image descriptor, and the local
+    /// color table, returning the bits of the color table in use.
+    fn write_headers(
+        c: &mut EncodeCursor<'_>,
+        frame: &GifFrame,
+        palette: Option<&[[u8; 3]]>,
+        global_len: usize,
+    ) -> ImageResult<u8> {
+        if frame.delay != 0 || frame.disposal.to_bits() != 0 || frame.transparent.is_some() {
+            let flags = frame.disposal.to_bits() << 2 | u8::from(frame.transparent.is_some());
+            c.bytes(&[GIF_EXTENSION, GIF_CONTROL, 4, flags])?;
+            c.bytes(&frame.delay.to_le_bytes())?;
+            c.bytes(&[frame.transparent.unwrap_or(0), 0])?;
+        }
+        let [w, h] = frame.extent().dim;
+        c.byte(GIF_IMAGE)?;
+        for v in [frame.left, frame.top, w, h] {
+            c.bytes(&(v as u16).to_le_bytes())?;
+        }
+        let interlaced = is![frame.interlaced, GIF_INTERLACED, 0];
+        if let Some(palette) = palette {
+            let bits = gif_table_bits(palette.len());
+            c.byte(GIF_TABLE_FLAG | interlaced | (bits - 1))?;
+            Self::write_table(c, palette, bits)?;
+            Ok(bits)
+        } else {
+            c.byte(interlaced)?;
+            Ok(gif_table_bits(global_len))
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// devela/src/media/visual/image/format/gif/frame.rs
+//
+//! Defines [`GifDisposal`], [`GifFrame`].
+//
+
+use crate::{Extent2, ImageFrameInfo, ImageInfo, RasterFormat};
+
+#[doc = crate::_tags!(image codec)]
+/// What to do with the area of a <abbr title="Graphics Interchange Format">GIF</abbr>
+/// frame before drawing the next one.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GifDisposal {
+    /// No disposal specified, which is handled as [`Keep`][Self::Keep].
+    #[default]
+    Unspecified,
+    /// Leave the frame in place.
+    Keep,
+    /// Clear the frame area to transparent.
+    Background,
+    /// Restore the frame area to what it was before drawing the frame.
+    Previous,
+}
+impl GifDisposal {
+    /// Returns the disposal of the 3 bits of a graphic control extension.
+    ///
+    /// Reserved values are [`Unspecified`][Self::Unspecified].
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0b111 {
+            1 => Self::Keep,
+            2 => Self::Background,
+            3 => Self::Previous,
+            _ => Self::Unspecified,
+        }
+    }
+    /// Returns the 3 bits stored in a graphic control extension.
+    #[must_use]
+    pub const fn to_bits(self) -> u8 {
+        self as u8
+    }
+}
+
+#[doc = crate::_tags!(image codec)]
+/// The placement and timing of a <abbr title="Graphics Interchange Format">GIF</abbr> frame.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// Returned by the [`GifDecoder`][crate::GifDecoder] for each frame,
+/// and given to the [`GifEncoder`][crate::GifEncoder] for each frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GifFrame {
+    /// The extent of the frame, its decoded format, and its index in the file.
+    ///
+    /// The format is always [`RGBA8`][RasterFormat::RGBA8].
+    pub info: ImageFrameInfo,
+    /// The horizontal offset from the left of the logical screen.
+    pub left: u32,
+    /// The vertical offset from the top of the logical screen.
+    pub top: u32,
+    /// The time to show the frame before the next one, in hundredths of a second.
+    pub delay: u16,
+    /// What to do with the frame area before drawing the next frame.
+    pub disposal: GifDisposal,
+    /// The color index that leaves the pixels under it unchanged.
+    pub transparent: Option<u8>,
+    /// Whether the rows are stored interlaced, in 4 passes.
+    pub interlaced: bool,
+}
+impl GifFrame {
+    /// Returns an opaque frame of the given `extent` at the top-left corner, without delay.
+    #[must_use]
+    pub const fn new(index: u32, extent: Extent2<u32>) -> Self {
+        let image = ImageInfo { extent, format: RasterFormat::RGBA8 };
+        Self {
+            info: ImageFrameInfo { image, index },
+            left: 0,
+            top: 0,
+            delay: 0,
+            disposal: GifDisposal::Unspecified,
+            transparent: None,
+            interlaced: false,
+        }
+    }
+    /// Returns the extent of the frame.
+    pub const fn extent(&self) -> Extent2<u32> {
+        self.info.image.extent
+    }
+    /// Returns the delay in milliseconds.
+    #[must_use]
+    pub const fn delay_ms(&self) -> u32 {
+        self.delay as u32 * 10
+    }
+}
@@ -0,0 +1,28 @@
+// devela/src/media/visual/image/format/gif/mod.rs
+//
+//! The Graphics Interchange Format.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (GIF_*, GifLzw[De|En]coder, gif_*)
+
+mod decoder; // GifDecoder
+mod encoder; // GifEncoder
+mod frame; // GifDisposal, GifFrame
+mod namespace; // Gif
+
+crate::structural_mods! { // _mods, _crate_internals
+    _mods {
+        pub use super::{
+            decoder::*,
+            encoder::*,
+            frame::*,
+            namespace::*,
+        };
+    }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
+}
@@ -0,0 +1,132 @@
+// devela/src/media/visual/image/format/gif/namespace.rs
+//
+//! Defines [`Gif`].
+//
+
+use crate::ImageError::UnsupportedFormat;
+use crate::{
+    GifEncoder, GifFrame, ImageEncoder, ImageInfo, ImageResult, Palette, PaletteOctree,
+    RasterByteSlice, RasterFormat, is, raster_rows,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// Encoding and decoding for <abbr title="Graphics Interchange Format">GIF</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// GIF stores frames of palette indices, of up to 256 colors each,
+/// compressed with <abbr title="Lempel–Ziv–Welch">LZW</abbr>.
+/// Frames can be smaller than the logical screen, have a transparent index,
+/// and be shown in sequence with a delay, forming an animation.
+///
+/// | Content | Decoded format | Encoded from |
+/// | --- | --- | --- |
+/// | indices of up to 8 bits | [`RGBA8`], or palette indices | [`GRAY8`], [`RGB8`], [`RGBA8`] |
+///
+/// Decoding is done by [`GifDecoder`][crate::GifDecoder], frame by frame,
+/// either on its own or composed over a canvas of the logical screen.
+///
+/// Encoding a single image is done by the [`ImageEncoder`] implementation.
+/// Grayscale images are written with a grayscale color table.
+/// True color images with up to 256 colors keep their exact colors, and other
+/// images are quantized with a [`PaletteOctree`] and mapped to their nearest colors.
+/// Pixels with an alpha below 128 are written as transparent, which takes one color.
+/// Animations, and images quantized or dithered in other ways,
+/// are encoded by [`GifEncoder`].
+///
+/// Current limitations:
+/// - Plain text extensions are skipped, and comments are ignored.
+/// - Frames without any color table are rejected.
+///
+/// [`GRAY8`]: RasterFormat::GRAY8
+/// [`RGB8`]: RasterFormat::RGB8
+/// [`RGBA8`]: RasterFormat::RGBA8
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, Gif, GifDecoder, ImageDecoder, ImageEncoder};
+/// # use devela::{RasterByteSlice, RasterFormat};
+/// let pixels = [255, 0, 0, 255, 0, 0, 0, 0, 9, 9, 9, 255, 255, 0, 0, 255];
+/// let extent = Extent2::new([2, 2]);
+/// let raster = RasterByteSlice::dense(RasterFormat::RGBA8, extent, &pixels).unwrap();
+///
+/// let mut gif = [0; 1024];
+/// let len = Gif::encode(&raster, &mut gif)?;
+///
+/// let mut decoder = GifDecoder::new(&gif[..len])?;
+/// assert_eq![decoder.frame().transparent, Some(2)];
+/// let mut out = [0; 16];
+/// let decoded = decoder.decode_into(&mut out)?;
+/// assert_eq![decoded.bytes(), &pixels];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Gif;
+
+impl Gif {
+    /// The number of nodes of the octree used to quantize true color images.
+    const OCTREE_NODES: usize = 1024;
+
+    /// Returns the color table of an image with `n` bytes per pixel,
+    /// and its transparent index.
+    fn palette<B: AsRef<[u8]>>(
+        raster: &RasterByteSlice<B>,
+        n: usize,
+    ) -> ImageResult<(Palette<256>, Option<u8>)> {
+        let mut palette = Palette::<256>::new();
+        if n == 1 {
+            for gray in 0..=255 {
+                let _ = palette.push([gray; 3]);
+            }
+            return Ok((palette, None));
+        }
+        let opaque = |px: &[u8]| n < 4 || px[3] >= 128;
+        let transparent =
+            n == 4 && raster_rows(raster)?.flatten().skip(3).step_by(4).any(|&a| a < 128);
+        let max = 256 - usize::from(transparent);
+        let exact = raster_rows(raster)?.all(|row| {
+            row.chunks_exact(n)
+                .filter(|px| opaque(px))
+                .all(|px| palette.find_or_push([px[0], px[1], px[2]]).is_ok_and(|i| i < max))
+        });
+        if !exact {
+            let mut octree = PaletteOctree::<{ Self::OCTREE_NODES }>::new();
+            for row in raster_rows(raster)? {
+                for px in row.chunks_exact(n).filter(|px| opaque(px)) {
+                    octree.add([px[0], px[1], px[2]]);
+                }
+            }
+            palette = octree.palette(max);
+        }
+        let index = transparent.then(|| palette.push([0; 3]).unwrap_or(255) as u8);
+        Ok((palette, index))
+    }
+}
+
+impl ImageEncoder for Gif {
+    fn supports_format(format: RasterFormat) -> bool {
+        matches![format, RasterFormat::GRAY8 | RasterFormat::RGB8 | RasterFormat::RGBA8]
+    }
+    fn encoded_len_max(info: ImageInfo) -> ImageResult<usize> {
+        is![!Self::supports_format(info.format), return Err(UnsupportedFormat)];
+        let frame = GifEncoder::frame_len_max(info.extent)?;
+        Ok(GifEncoder::HEADER_LEN_MAX + frame + 1)
+    }
+    fn encode<B: AsRef<[u8]>>(raster: &RasterByteSlice<B>, out: &mut [u8]) -> ImageResult<usize> {
+        let (format, extent) = (raster.format(), raster.extent());
+        let needed = Self::encoded_len_max(ImageInfo { extent, format })?;
+        let n = format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+        let (palette, transparent) = Self::palette(raster, n)?;
+        let mut frame = GifFrame::new(0, extent);
+        frame.transparent = transparent;
+
+        let colors = palette.as_slice();
+        let mut encoder = GifEncoder::with_needed(out, extent, colors, None, needed)?;
+        let indices = raster_rows(raster)?.flat_map(|row| row.chunks_exact(n)).map(|px| match n {
+            1 => px[0],
+            _ if n == 4 && px[3] < 128 => transparent.unwrap_or(0),
+            _ => palette.nearest([px[0], px[1], px[2]]),
+        });
+        encoder.write_frame(&frame, None, indices)?;
+        encoder.finish()
+    }
+}
@@ -6,19 +6,32 @@
 #![doc = crate::_doc!(hr)]
 //
 
+mod _helper; // (EncodeCursor, raster_rows)
+
+mod codec; // ImageDecoder, ImageEncoder
+
+mod bmp; // Bmp, BmpDecoder
+mod gif; // Gif, GifDecoder, GifEncoder, GifFrame, GifDisposal
 // mod jpeg; // FUTURE
 mod netpbm; // Pnm
-// #[cfg(feature = "alloc"] // TEMP
-// mod png; // WIP
-// mod qoi; // WIP
+mod png; // Png, PngInfo
+mod qoi; // Qoi, QoiDecoder
+mod tga; // Tga, TgaDecoder
 
-crate::structural_mods! { // _mods
+crate::structural_mods! { // _mods, _crate_internals
     _mods {
         pub use super::{
+            codec::*,
+            bmp::_all::*,
+            gif::_all::*,
             // jpg::*,
             netpbm::_all::*,
-            // png::_all::*,
-            // qoi::*,
+            png::_all::*,
+            qoi::_all::*,
+            tga::_all::*,
         };
     }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
 }
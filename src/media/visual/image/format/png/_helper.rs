@@ -0,0 +1,332 @@
+// devela/src/media/visual/image/format/png/_helper.rs
+//
+//! Private PNG chunk, header, and filtering helpers.
+//
+// TOC
+// - constants
+// - struct PngChunk
+// - struct PngChunks
+// - struct PngHeader
+// - struct PngMeta
+// - filtering fns
+
+use crate::ImageError::{InvalidChecksum, InvalidData, InvalidImageSize, InvalidMagicNumber};
+use crate::{Crc32, ImageResult, RasterFormat, is};
+
+/* constants */
+
+/// The signature that starts every PNG datastream.
+pub(crate) const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// The chunk types used by the codec.
+pub(crate) const IHDR: [u8; 4] = *b"IHDR";
+pub(crate) const PLTE: [u8; 4] = *b"PLTE";
+pub(crate) const IDAT: [u8; 4] = *b"IDAT";
+pub(crate) const IEND: [u8; 4] = *b"IEND";
+const TRNS: [u8; 4] = *b"tRNS";
+const GAMA: [u8; 4] = *b"gAMA";
+pub(crate) const SRGB: [u8; 4] = *b"sRGB";
+
+/// The Adam7 passes, as `(x0, y0, dx, dy)`.
+pub(crate) const ADAM7: [(u32, u32, u32, u32); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+/* chunks */
+
+/// A chunk of a PNG datastream.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PngChunk<'a> {
+    pub(crate) kind: [u8; 4],
+    pub(crate) data: &'a [u8],
+}
+impl PngChunk<'_> {
+    /// Returns the `u32` stored big-endian at `at`.
+    fn u32_at(&self, at: usize) -> u32 {
+        u32::from_be_bytes([self.data[at], self.data[at + 1], self.data[at + 2], self.data[at + 3]])
+    }
+}
+
+/// Iterates the chunks after the signature, verifying their checksums.
+#[derive(Clone, Debug)]
+pub(crate) struct PngChunks<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+impl<'a> PngChunks<'a> {
+    /// Checks the signature of `bytes` and returns an iterator over its chunks.
+    pub(crate) fn new(bytes: &'a [u8]) -> ImageResult<Self> {
+        is![!bytes.starts_with(&PNG_SIGNATURE), return Err(InvalidMagicNumber)];
+        Ok(Self { bytes, pos: PNG_SIGNATURE.len() })
+    }
+    fn read(&mut self) -> ImageResult<PngChunk<'a>> {
+        let head = self.bytes.get(self.pos..self.pos + 8).ok_or(InvalidData)?;
+        let len = u32::from_be_bytes([head[0], head[1], head[2], head[3]]) as usize;
+        let end = self.pos.checked_add(12 + len).ok_or(InvalidData)?;
+        let chunk = self.bytes.get(self.pos + 4..end).ok_or(InvalidData)?;
+        let (body, crc) = chunk.split_at(4 + len);
+        is![
+            Crc32::checksum_bytes(body) != u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]),
+            return Err(InvalidChecksum)
+        ];
+        self.pos = end;
+        Ok(PngChunk {
+            kind: [body[0], body[1], body[2], body[3]],
+            data: &body[4..],
+        })
+    }
+}
+impl<'a> Iterator for PngChunks<'a> {
+    type Item = ImageResult<PngChunk<'a>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        is![self.pos >= self.bytes.len(), return None];
+        let chunk = self.read();
+        is![chunk.is_err(), self.pos = self.bytes.len()];
+        Some(chunk)
+    }
+}
+
+/* header */
+
+/// The contents of the `IHDR` chunk.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PngHeader {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) depth: u8,
+    pub(crate) color: u8,
+    pub(crate) interlaced: bool,
+}
+impl PngHeader {
+    fn parse(chunk: PngChunk) -> ImageResult<Self> {
+        is![chunk.kind != IHDR || chunk.data.len() != 13, return Err(InvalidData)];
+        let (width, height) = (chunk.u32_at(0), chunk.u32_at(4));
+        let d = chunk.data;
+        let (depth, color) = (d[8], d[9]);
+        let valid_depth = match color {
+            0 => matches![depth, 1 | 2 | 4 | 8 | 16],
+            3 => matches![depth, 1 | 2 | 4 | 8],
+            2 | 4 | 6 => matches![depth, 8 | 16],
+            _ => false,
+        };
+        is![!valid_depth || d[10] != 0 || d[11] != 0 || d[12] > 1, return Err(InvalidData)];
+        is![
+            width == 0 || height == 0 || width > i32::MAX as u32 || height > i32::MAX as u32,
+            return Err(InvalidImageSize(Some((width as usize, height as usize))))
+        ];
+        Ok(Self {
+            width,
+            height,
+            depth,
+            color,
+            interlaced: d[12] == 1,
+        })
+    }
+    /// Returns the encoded samples per pixel.
+    pub(crate) const fn channels(self) -> u32 {
+        match self.color {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        }
+    }
+    /// Returns the encoded bits per pixel.
+    pub(crate) const fn bits_per_pixel(self) -> u32 {
+        self.channels() * self.depth as u32
+    }
+    /// Returns the byte distance to the corresponding byte of the previous pixel.
+    pub(crate) const fn filter_step(self) -> usize {
+        is![self.bits_per_pixel() < 8, 1, self.bits_per_pixel() as usize / 8]
+    }
+    /// Returns the encoded bytes of a row of `width` pixels, without the filter byte.
+    pub(crate) const fn row_len(self, width: u32) -> usize {
+        (width as usize * self.bits_per_pixel() as usize).div_ceil(8)
+    }
+    /// Returns the extent of each pass, or of the whole image in the first one.
+    pub(crate) const fn passes(self) -> [(u32, u32); 7] {
+        let mut passes = [(0, 0); 7];
+        if self.interlaced {
+            let mut p = 0;
+            while p < 7 {
+                let (x0, y0, dx, dy) = ADAM7[p];
+                passes[p] = (
+                    (self.width + dx - 1 - x0) / dx * (self.width > x0) as u32,
+                    (self.height + dy - 1 - y0) / dy * (self.height > y0) as u32,
+                );
+                p += 1;
+            }
+        } else {
+            passes[0] = (self.width, self.height);
+        }
+        passes
+    }
+    /// Returns the length of the filtered, decompressed image data.
+    pub(crate) fn filtered_len(self) -> Option<usize> {
+        self.passes().iter().filter(|(w, _)| *w > 0).try_fold(0_usize, |len, &(w, h)| {
+            len.checked_add((self.row_len(w) + 1).checked_mul(h as usize)?)
+        })
+    }
+}
+
+/* metadata */
+
+/// The chunks that describe how to interpret the image data.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PngMeta {
+    pub(crate) header: PngHeader,
+    pub(crate) palette: [[u8; 3]; 256],
+    pub(crate) palette_len: usize,
+    /// Palette alpha, with entries past `alpha_len` being opaque.
+    pub(crate) alpha: [u8; 256],
+    pub(crate) alpha_len: usize,
+    /// The transparent gray or RGB sample values.
+    pub(crate) key: Option<[u16; 3]>,
+    /// The `gAMA` value, the encoding gamma times 100000.
+    ///
+    /// Never `0`, an invalid value whose chunk is ignored.
+    pub(crate) gamma: Option<u32>,
+    pub(crate) srgb: bool,
+}
+impl PngMeta {
+    /// Reads and validates every chunk of `bytes`.
+    pub(crate) fn read(bytes: &[u8]) -> ImageResult<Self> {
+        let mut chunks = PngChunks::new(bytes)?;
+        let header = PngHeader::parse(chunks.next().ok_or(InvalidData)??)?;
+        let mut meta = Self {
+            header,
+            palette: [[0; 3]; 256],
+            palette_len: 0,
+            alpha: [u8::MAX; 256],
+            alpha_len: 0,
+            key: None,
+            gamma: None,
+            srgb: false,
+        };
+        // 0: before IDAT, 1: in IDAT, 2: after IDAT
+        let mut idat = 0;
+        for chunk in chunks {
+            let chunk = chunk?;
+            match chunk.kind {
+                IHDR => return Err(InvalidData),
+                IEND => {
+                    is![
+                        idat == 0 || meta.header.color == 3 && meta.palette_len == 0,
+                        return Err(InvalidData)
+                    ];
+                    return Ok(meta);
+                }
+                IDAT => {
+                    is![idat == 2, return Err(InvalidData)];
+                    idat = 1;
+                    continue;
+                }
+                PLTE => meta.read_palette(chunk)?,
+                TRNS => meta.read_transparency(chunk)?,
+                GAMA if chunk.data.len() == 4 && chunk.u32_at(0) != 0 => {
+                    meta.gamma = Some(chunk.u32_at(0));
+                }
+                SRGB => meta.srgb = true,
+                // unknown critical chunks can't be skipped
+                kind if kind[0].is_ascii_uppercase() => return Err(InvalidData),
+                _ => {}
+            }
+            is![idat == 1, idat = 2];
+        }
+        Err(InvalidData)
+    }
+    fn read_palette(&mut self, chunk: PngChunk) -> ImageResult<()> {
+        let len = chunk.data.len();
+        is![
+            !len.is_multiple_of(3) || len == 0 || len > 768 || self.palette_len > 0,
+            return Err(InvalidData)
+        ];
+        for (entry, rgb) in self.palette.iter_mut().zip(chunk.data.chunks_exact(3)) {
+            *entry = [rgb[0], rgb[1], rgb[2]];
+        }
+        self.palette_len = len / 3;
+        Ok(())
+    }
+    fn read_transparency(&mut self, chunk: PngChunk) -> ImageResult<()> {
+        let d = chunk.data;
+        let sample = |i: usize| u16::from_be_bytes([d[i], d[i + 1]]);
+        match self.header.color {
+            0 if d.len() == 2 => self.key = Some([sample(0); 3]),
+            2 if d.len() == 6 => self.key = Some([sample(0), sample(2), sample(4)]),
+            3 if d.len() <= self.palette_len => {
+                self.alpha[..d.len()].copy_from_slice(d);
+                self.alpha_len = d.len();
+            }
+            _ => return Err(InvalidData),
+        }
+        Ok(())
+    }
+    /// Returns the format of the decoded samples.
+    pub(crate) const fn format(&self) -> RasterFormat {
+        let (wide, alpha) = (self.header.depth == 16, self.key.is_some() || self.alpha_len > 0);
+        match self.header.color {
+            0 | 4 if self.header.color == 4 || alpha => {
+                is![wide, RasterFormat::GRAYA16, RasterFormat::GRAYA8]
+            }
+            0 => is![wide, RasterFormat::GRAY16, RasterFormat::GRAY8],
+            2 if !alpha => is![wide, RasterFormat::RGB16, RasterFormat::RGB8],
+            3 if !alpha => RasterFormat::RGB8,
+            3 => RasterFormat::RGBA8,
+            _ => is![wide, RasterFormat::RGBA16, RasterFormat::RGBA8],
+        }
+    }
+}
+
+/* filtering */
+
+/// Returns the Paeth predictor of the left, up, and upper-left bytes.
+const fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = ((p - a as i16).abs(), (p - b as i16).abs(), (p - c as i16).abs());
+    is![pa <= pb && pa <= pc, a, is![pb <= pc, b, c]]
+}
+/// Returns the prediction of filter `kind` for byte `i` of a row.
+///
+/// An empty `prev` stands for the zeroed row above the first one.
+fn predict(kind: u8, row: &[u8], prev: &[u8], i: usize, step: usize) -> u8 {
+    let a = is![i >= step, row[i - step], 0];
+    let b = prev.get(i).copied().unwrap_or(0);
+    let c = is![i >= step, prev.get(i - step).copied().unwrap_or(0), 0];
+    match kind {
+        1 => a,
+        2 => b,
+        3 => ((u16::from(a) + u16::from(b)) / 2) as u8,
+        4 => paeth(a, b, c),
+        _ => 0,
+    }
+}
+/// Reverses filter `kind` over `row`, given the reconstructed previous row.
+pub(crate) fn unfilter(kind: u8, row: &mut [u8], prev: &[u8], step: usize) -> ImageResult<()> {
+    is![kind > 4, return Err(InvalidData)];
+    // left to right, since predictions use the reconstructed bytes to the left
+    for i in 0..row.len() {
+        row[i] = row[i].wrapping_add(predict(kind, row, prev, i, step));
+    }
+    Ok(())
+}
+/// Applies the filter that best suits `row`, and returns its kind.
+///
+/// Chooses the smallest sum of absolute signed differences.
+pub(crate) fn filter(row: &mut [u8], prev: &[u8], step: usize) -> u8 {
+    let cost = |kind| -> u32 {
+        let d = |i: usize| row[i].wrapping_sub(predict(kind, row, prev, i, step)) as i8;
+        (0..row.len()).map(|i| u32::from(d(i).unsigned_abs())).sum()
+    };
+    let kind = (0..5).min_by_key(|&k| cost(k)).unwrap_or(0);
+    // right to left, since predictions use the raw bytes to the left
+    for i in (0..row.len()).rev() {
+        row[i] = row[i].wrapping_sub(predict(kind, row, prev, i, step));
+    }
+    kind
+}
@@ -0,0 +1,166 @@
+// devela/src/media/visual/image/format/png/_test.rs
+
+use crate::{Boundary1d, Crc32, Extent2, ImageError, Png, RasterByteSlice, RasterFormat, RasterLayout};
+
+/* reference images, written by an independent encoder with one filter type per row */
+
+/// 3×2, 2-bit indexed, with a palette of 4 colors and 2 alpha entries.
+const INDEXED2: [u8; 107] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x02, 0x03, 0x00, 0x00, 0x00, 0xe0, 0x1a, 0x8e,
+    0x89, 0x00, 0x00, 0x00, 0x0c, 0x50, 0x4c, 0x54, 0x45, 0xff, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00,
+    0x00, 0xff, 0x0a, 0x14, 0x1e, 0x22, 0x88, 0x29, 0x04, 0x00, 0x00, 0x00, 0x02, 0x74, 0x52, 0x4e,
+    0x53, 0x00, 0x80, 0x9b, 0x2b, 0x4e, 0x18, 0x00, 0x00, 0x00, 0x0c, 0x49, 0x44, 0x41, 0x54, 0x78,
+    0xda, 0x63, 0x90, 0x60, 0x7c, 0x02, 0x00, 0x01, 0x32, 0x00, 0xfe, 0x06, 0xf2, 0x6e, 0x18, 0x00,
+    0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+/// 5×5, 16-bit gray, Adam7 interlaced, with a `gAMA` of 0.45455.
+const GRAY16_ADAM7: [u8; 142] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x05, 0x10, 0x00, 0x00, 0x00, 0x01, 0x8f, 0x93, 0x95,
+    0xec, 0x00, 0x00, 0x00, 0x04, 0x67, 0x41, 0x4d, 0x41, 0x00, 0x00, 0xb1, 0x8f, 0x0b, 0xfc, 0x61,
+    0x05, 0x00, 0x00, 0x00, 0x45, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x60, 0x60, 0x60, 0xd0,
+    0x5a, 0xc3, 0x70, 0x35, 0x86, 0x81, 0x83, 0x41, 0x34, 0x8c, 0xf1, 0xd5, 0x26, 0x86, 0xac, 0x75,
+    0x0d, 0x2c, 0x53, 0xa3, 0x18, 0xb8, 0x56, 0x2b, 0x30, 0x32, 0x96, 0x46, 0x8a, 0x86, 0x31, 0x65,
+    0xaf, 0xcb, 0x5e, 0xc7, 0x60, 0x1a, 0xee, 0xc0, 0xe4, 0xb5, 0x36, 0x34, 0x22, 0x81, 0x99, 0x71,
+    0x01, 0x2b, 0xd7, 0x6a, 0x6e, 0x20, 0xe4, 0x5a, 0x0d, 0x00, 0xed, 0xff, 0x11, 0x38, 0x09, 0x82,
+    0x4f, 0xa8, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+/// 9×3, 1-bit gray checkerboard, Adam7 interlaced.
+const GRAY1_ADAM7: [u8; 75] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x03, 0x01, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x76, 0x28,
+    0x4d, 0x00, 0x00, 0x00, 0x12, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x60, 0x80, 0x82, 0x0f,
+    0x8c, 0x1f, 0x18, 0x56, 0x35, 0x00, 0x00, 0x0b, 0x48, 0x03, 0x0c, 0xec, 0x06, 0x3f, 0x69, 0x00,
+    0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+/// 2×2 RGB with a `tRNS` color key, and its image data split across two `IDAT` chunks.
+const RGB8_KEY_SPLIT: [u8; 107] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x08, 0x02, 0x00, 0x00, 0x00, 0xfd, 0xd4, 0x9a,
+    0x73, 0x00, 0x00, 0x00, 0x06, 0x74, 0x52, 0x4e, 0x53, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0xc9,
+    0x4b, 0xab, 0xf5, 0x00, 0x00, 0x00, 0x05, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x60, 0x64,
+    0xa9, 0x74, 0x10, 0xa5, 0x00, 0x00, 0x00, 0x0f, 0x49, 0x44, 0x41, 0x54, 0x62, 0x66, 0x61, 0x65,
+    0x63, 0x04, 0x52, 0x6c, 0x6c, 0x6c, 0x00, 0x01, 0x20, 0x00, 0x2f, 0xc0, 0xe0, 0x64, 0xad, 0x00,
+    0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+/// Decodes a whole image, returning its format and the decoded length.
+fn decode(png: &[u8], out: &mut [u8]) -> (RasterFormat, usize) {
+    let mut scratch = [0; 256];
+    assert![Png::decode_scratch_len(png).unwrap() <= scratch.len()];
+    let raster = Png::decode(png, out, &mut scratch).unwrap();
+    (raster.format(), raster.bytes().len())
+}
+
+#[test]
+fn decode_indexed() {
+    let info = Png::info(&INDEXED2).unwrap();
+    assert_eq![(info.bit_depth, info.color_type, info.interlaced), (2, 3, false)];
+    assert_eq![info.image.extent, Extent2::new([3, 2])];
+    let mut out = [0; 24];
+    assert_eq![decode(&INDEXED2, &mut out), (RasterFormat::RGBA8, 24)];
+    assert_eq![
+        out,
+        [
+            255, 0, 0, 0, 0, 255, 0, 128, 0, 0, 255, 255, 10, 20, 30, 255, 0, 0, 255, 255, 0, 255,
+            0, 128,
+        ]
+    ];
+}
+#[test]
+fn decode_interlaced() {
+    let mut out = [0; 50];
+    assert_eq![decode(&GRAY16_ADAM7, &mut out), (RasterFormat::GRAY16, 50)];
+    for (i, s) in out.chunks_exact(2).enumerate() {
+        assert_eq![u16::from_ne_bytes([s[0], s[1]]), (i * 2731) as u16];
+    }
+    let info = Png::info(&GRAY16_ADAM7).unwrap();
+    assert![info.interlaced && !info.srgb];
+    #[cfg(feature = "color")]
+    assert![(info.gamma.unwrap().exp - 2.2).abs() < 1e-3];
+
+    // a gAMA of 0 is invalid, and ignored
+    let mut zero_gamma = GRAY16_ADAM7;
+    zero_gamma[41..45].fill(0);
+    let crc = Crc32::checksum_bytes(&zero_gamma[37..45]);
+    zero_gamma[45..49].copy_from_slice(&crc.to_be_bytes());
+    let info = Png::info(&zero_gamma).unwrap();
+    assert![!info.srgb];
+    #[cfg(feature = "color")]
+    assert_eq![info.gamma, None];
+
+    let mut out = [0; 27];
+    assert_eq![decode(&GRAY1_ADAM7, &mut out), (RasterFormat::GRAY8, 27)];
+    for (i, &v) in out.iter().enumerate() {
+        assert_eq![v, ((i % 9 + i / 9) % 2 * 255) as u8];
+    }
+}
+#[test]
+fn decode_color_key() {
+    let mut out = [0; 16];
+    assert_eq![decode(&RGB8_KEY_SPLIT, &mut out), (RasterFormat::RGBA8, 16)];
+    assert_eq![out, [1, 2, 3, 0, 4, 5, 6, 255, 1, 2, 3, 0, 7, 8, 9, 255]];
+}
+#[test]
+fn decode_errors() {
+    let (mut out, mut scratch) = ([0; 64], [0; 64]);
+    let mut bad = INDEXED2;
+    bad[0] = 0;
+    assert_eq![Png::info(&bad), Err(ImageError::InvalidMagicNumber)];
+    let mut bad = INDEXED2;
+    bad[20] ^= 1;
+    assert_eq![Png::info(&bad), Err(ImageError::InvalidChecksum)];
+    assert_eq![Png::info(&INDEXED2[..90]), Err(ImageError::InvalidData)];
+    let err = Png::decode(&INDEXED2, &mut out[..23], &mut scratch).unwrap_err();
+    assert_eq![err, ImageError::InsufficientBuffer { needed: 24, available: 23 }];
+    let err = Png::decode(&INDEXED2, &mut out, &mut scratch[..2]).unwrap_err();
+    assert_eq![err, ImageError::InsufficientBuffer { needed: 4, available: 2 }];
+}
+
+#[test]
+fn encode_round_trip() {
+    let samples: [u8; 96] = core::array::from_fn(|i| (i * 37 % 251) as u8);
+    let extent = Extent2::new([4, 3]);
+    for format in [
+        RasterFormat::GRAY8,
+        RasterFormat::GRAYA8,
+        RasterFormat::RGB8,
+        RasterFormat::RGBA8,
+        RasterFormat::GRAY16,
+        RasterFormat::GRAYA16,
+        RasterFormat::RGB16,
+        RasterFormat::RGBA16,
+    ] {
+        let raster = RasterByteSlice::dense(format, extent, &samples).unwrap();
+        let len = raster.bytes().len().min(raster.layout().min_len_bytes().unwrap());
+        let (mut png, mut scratch) = ([0; 256], [0; 128]);
+        assert![Png::encode_scratch_len(format, extent).unwrap() <= scratch.len()];
+        let n = Png::encode(&raster, &mut png, &mut scratch).unwrap();
+        assert![n <= Png::encoded_len_max(format, extent).unwrap()];
+        let info = Png::info(&png[..n]).unwrap();
+        assert_eq![(info.image.format, info.image.extent, info.srgb), (format, extent, true)];
+        let mut out = [0; 96];
+        let decoded = Png::decode(&png[..n], &mut out, &mut scratch).unwrap();
+        assert_eq![decoded.bytes(), &samples[..len]];
+    }
+}
+#[test]
+fn encode_layouts() {
+    // bottom-up rows, with 2 bytes of padding each
+    let rows = [7, 8, 9, 0, 0, 4, 5, 6, 0, 0, 1, 2, 3, 0, 0];
+    let layout = RasterLayout::interleaved(Extent2::new([3, 3]), 1, 5, Boundary1d::Lower);
+    let raster = RasterByteSlice::new(RasterFormat::GRAY8, layout, &rows).unwrap();
+    let (mut png, mut scratch, mut out) = ([0; 128], [0; 16], [0; 9]);
+    let n = Png::encode(&raster, &mut png, &mut scratch).unwrap();
+    Png::decode(&png[..n], &mut out, &mut scratch).unwrap();
+    assert_eq![out, [1, 2, 3, 4, 5, 6, 7, 8, 9]];
+    // unsupported formats and small buffers
+    let bgr = RasterByteSlice::dense(RasterFormat::BGR8, Extent2::new([1, 1]), &[0; 3]).unwrap();
+    assert_eq![Png::encode(&bgr, &mut png, &mut scratch), Err(ImageError::UnsupportedFormat)];
+    let needed = Png::encoded_len_max(RasterFormat::GRAY8, Extent2::new([3, 3])).unwrap();
+    assert_eq![
+        Png::encode(&raster, &mut png[..40], &mut scratch),
+        Err(ImageError::InsufficientBuffer { needed, available: 40 })
+    ];
+}
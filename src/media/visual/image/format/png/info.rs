@@ -0,0 +1,31 @@
+// devela/src/media/visual/image/format/png/info.rs
+//
+//! Defines [`PngInfo`].
+//
+
+#[cfg(feature = "color")]
+use crate::Gamma;
+use crate::ImageInfo;
+
+#[doc = crate::_tags!(image)]
+/// Metadata of a PNG image, as reported by [`Png::info`][crate::Png::info].
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PngInfo {
+    /// The image extent, and the format of the decoded samples.
+    pub image: ImageInfo,
+    /// The encoded bits per sample, or per palette index.
+    pub bit_depth: u8,
+    /// The encoded color type: `0` gray, `2` RGB, `3` indexed, `4` gray and alpha, `6` RGBA.
+    pub color_type: u8,
+    /// Whether the image data is Adam7 interlaced.
+    pub interlaced: bool,
+    /// Whether the image declares the sRGB color space with an `sRGB` chunk.
+    pub srgb: bool,
+    /// The decoding gamma, from the `sRGB` or the `gAMA` chunk.
+    ///
+    /// An `sRGB` chunk implies a gamma of `2.2`, and takes precedence.
+    #[cfg(feature = "color")]
+    #[cfg_attr(nightly_doc, doc(cfg(feature = "color")))]
+    pub gamma: Option<Gamma<f32>>,
+}
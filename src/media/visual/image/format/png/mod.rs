@@ -0,0 +1,24 @@
+// devela/src/media/visual/image/format/png/mod.rs
+//
+//! The Portable Network Graphics format.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (PngChunk, PngChunks, PngHeader, PngMeta, filter, unfilter)
+
+mod info; // PngInfo
+mod namespace; // Png
+
+crate::structural_mods! { // _mods, _crate_internals
+    _mods {
+        pub use super::{
+            info::*,
+            namespace::*,
+        };
+    }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
+}
@@ -0,0 +1,324 @@
+// devela/src/media/visual/image/format/png/namespace.rs
+//
+//! Defines [`Png`].
+//
+
+use super::{ADAM7, IDAT, IEND, IHDR, PNG_SIGNATURE, PngChunks, PngHeader, PngMeta, SRGB};
+use super::{filter, unfilter};
+use crate::ImageError::{InsufficientBuffer, InvalidData, InvalidImageSize, InvalidPixel};
+use crate::{
+    Boundary1d, Crc32, Deflate, DeflateError, Extent2, ImageError, ImageInfo, ImageResult, PngInfo,
+    RasterByteSlice, RasterFormat, RasterViewBytes, is,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// Encoding and decoding for <abbr title="Portable Network Graphics">PNG</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// Decoding supports every color type and bit depth, Adam7 interlacing,
+/// palettes, and `tRNS` transparency, producing these formats:
+///
+/// | Color type | Bit depths | Decoded format | With `tRNS` |
+/// | --- | --- | --- | --- |
+/// | gray | 1, 2, 4, 8 | [`GRAY8`], scaled | [`GRAYA8`] |
+/// | gray | 16 | [`GRAY16`] | [`GRAYA16`] |
+/// | RGB | 8, 16 | [`RGB8`], [`RGB16`] | [`RGBA8`], [`RGBA16`] |
+/// | indexed | 1, 2, 4, 8 | [`RGB8`] | [`RGBA8`] |
+/// | gray and alpha | 8, 16 | [`GRAYA8`], [`GRAYA16`] | — |
+/// | RGBA | 8, 16 | [`RGBA8`], [`RGBA16`] | — |
+///
+/// 16-bit samples are stored in native byte order.
+/// The `gAMA` and `sRGB` chunks are reported by [`Png::info`].
+///
+/// Encoding accepts those same formats, except indexed, writing non-interlaced
+/// images whose rows use the best fitting filter, and an `sRGB` chunk.
+///
+/// Both directions work over caller-provided buffers, plus a scratch buffer
+/// for the filtered image data, whose lengths can be queried beforehand.
+///
+/// Current limitations:
+/// - Ancillary chunks other than `tRNS`, `gAMA` and `sRGB` are ignored.
+/// - The compression is that of [`Deflate::encode_zlib`].
+///
+/// [`GRAY8`]: RasterFormat::GRAY8
+/// [`GRAYA8`]: RasterFormat::GRAYA8
+/// [`GRAY16`]: RasterFormat::GRAY16
+/// [`GRAYA16`]: RasterFormat::GRAYA16
+/// [`RGB8`]: RasterFormat::RGB8
+/// [`RGB16`]: RasterFormat::RGB16
+/// [`RGBA8`]: RasterFormat::RGBA8
+/// [`RGBA16`]: RasterFormat::RGBA16
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, Png, RasterByteSlice, RasterFormat};
+/// let pixels = [255, 0, 0, 255, 0, 255, 0, 128, 0, 0, 255, 0, 9, 9, 9, 255];
+/// let extent = Extent2::new([2, 2]);
+/// let raster = RasterByteSlice::dense(RasterFormat::RGBA8, extent, &pixels).unwrap();
+///
+/// let mut scratch = [0; 64];
+/// let mut png = [0; 256];
+/// let len = Png::encode(&raster, &mut png, &mut scratch)?;
+///
+/// let info = Png::info(&png[..len])?;
+/// assert_eq![info.image.extent, extent];
+/// let mut out = [0; 16];
+/// let decoded = Png::decode(&png[..len], &mut out, &mut scratch)?;
+/// assert_eq![decoded.format(), RasterFormat::RGBA8];
+/// assert_eq![decoded.bytes(), &pixels];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Png;
+
+impl Png {
+    /// The length of the chunks the encoder writes around the image data.
+    const ENCODED_OVERHEAD: usize = 8 + (12 + 13) + (12 + 1) + 12 + 12;
+
+    /* queries */
+
+    /// Reads the image metadata, validating the structure of the whole datastream.
+    pub fn info(bytes: &[u8]) -> ImageResult<PngInfo> {
+        let meta = PngMeta::read(bytes)?;
+        let h = meta.header;
+        Ok(PngInfo {
+            image: ImageInfo {
+                extent: Extent2::new([h.width, h.height]),
+                format: meta.format(),
+            },
+            bit_depth: h.depth,
+            color_type: h.color,
+            interlaced: h.interlaced,
+            srgb: meta.srgb,
+            #[cfg(feature = "color")]
+            gamma: is![
+                meta.srgb,
+                Some(crate::Gamma::new(2.2)),
+                meta.gamma.map(|g| crate::Gamma::new(100_000.0 / g as f32))
+            ],
+        })
+    }
+    /// Returns the number of bytes produced by [`Png::decode`].
+    pub fn decoded_len(bytes: &[u8]) -> ImageResult<usize> {
+        Self::decoded_len_meta(&PngMeta::read(bytes)?)
+    }
+    /// Returns the number of scratch bytes needed by [`Png::decode`].
+    pub fn decode_scratch_len(bytes: &[u8]) -> ImageResult<usize> {
+        let h = PngMeta::read(bytes)?.header;
+        h.filtered_len().ok_or(InvalidImageSize(Some((h.width as usize, h.height as usize))))
+    }
+    /// Returns the number of bytes [`Png::encode`] can write at most.
+    pub fn encoded_len_max(format: RasterFormat, extent: Extent2<u32>) -> ImageResult<usize> {
+        let filtered = Self::encode_scratch_len(format, extent)?;
+        let len = Deflate::encoded_len_max_zlib(filtered).checked_add(Self::ENCODED_OVERHEAD);
+        len.ok_or(InvalidImageSize(None))
+    }
+    /// Returns the number of scratch bytes needed by [`Png::encode`].
+    pub fn encode_scratch_len(format: RasterFormat, extent: Extent2<u32>) -> ImageResult<usize> {
+        Self::encode_header(format, extent)?.filtered_len().ok_or(InvalidImageSize(None))
+    }
+
+    /* decode */
+
+    /// Decodes a PNG image into `out`, using `scratch` for the filtered image data.
+    ///
+    /// Returns a dense raster over the written bytes, whose format is listed
+    /// in the [type documentation][Self].
+    ///
+    /// The buffers need at least [`decoded_len`][Self::decoded_len]
+    /// and [`decode_scratch_len`][Self::decode_scratch_len] bytes.
+    pub fn decode<'o>(
+        bytes: &[u8],
+        out: &'o mut [u8],
+        scratch: &mut [u8],
+    ) -> ImageResult<RasterByteSlice<&'o mut [u8]>> {
+        let meta = PngMeta::read(bytes)?;
+        let h = meta.header;
+        let needed = Self::decoded_len_meta(&meta)?;
+        is![out.len() < needed, return Err(InsufficientBuffer { needed, available: out.len() })];
+        let filtered = h.filtered_len().ok_or(InvalidImageSize(None))?;
+        is![
+            scratch.len() < filtered,
+            return Err(InsufficientBuffer { needed: filtered, available: scratch.len() })
+        ];
+        let data = PngChunks::new(bytes)?.map_while(Result::ok).filter(|c| c.kind == IDAT);
+        let data = data.flat_map(|c| c.data.iter().copied());
+        let len = Deflate::decode_zlib_iter(data, &mut scratch[..filtered]).map_err(|e| {
+            is![e == DeflateError::NotEnoughSpace, InvalidData, ImageError::Deflate(e)]
+        })?;
+        is![len != filtered, return Err(InvalidData)];
+
+        let format = meta.format();
+        let bpp = format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+        let (step, mut start) = (h.filter_step(), 0);
+        for (p, &(pw, ph)) in h.passes().iter().enumerate() {
+            is![pw == 0 || ph == 0, continue];
+            let (x0, y0, dx, dy) = is![h.interlaced, ADAM7[p], (0, 0, 1, 1)];
+            let row_len = h.row_len(pw);
+            for j in 0..ph {
+                let at = start + j as usize * (row_len + 1);
+                let (before, rest) = scratch.split_at_mut(at);
+                let prev = is![j == 0, &[][..], &before[at - row_len..]];
+                let (kind, row) = rest[..=row_len].split_first_mut().ok_or(InvalidData)?;
+                unfilter(*kind, row, prev, step)?;
+                let y = (y0 + j * dy) as usize;
+                for i in 0..pw {
+                    let o = (y * h.width as usize + (x0 + i * dx) as usize) * bpp;
+                    Self::expand(&meta, row, i as usize, &mut out[o..o + bpp])?;
+                }
+            }
+            start += ph as usize * (row_len + 1);
+        }
+        let extent = Extent2::new([h.width, h.height]);
+        RasterByteSlice::dense_mut(format, extent, &mut out[..needed])
+            .ok_or(InvalidImageSize(Some((h.width as usize, h.height as usize))))
+    }
+
+    /* encode */
+
+    /// Encodes a raster as a PNG image into `out`, using `scratch` for the filtered image data.
+    ///
+    /// The raster format must be one of the decoded formats listed
+    /// in the [type documentation][Self], except for indexed images.
+    ///
+    /// The buffers need at least [`encoded_len_max`][Self::encoded_len_max]
+    /// and [`encode_scratch_len`][Self::encode_scratch_len] bytes,
+    /// although compressible images need less output space.
+    ///
+    /// Returns the number of bytes written to `out`.
+    pub fn encode<B: AsRef<[u8]>>(
+        raster: &RasterByteSlice<B>,
+        out: &mut [u8],
+        scratch: &mut [u8],
+    ) -> ImageResult<usize> {
+        let (format, layout) = (raster.format(), raster.layout());
+        let h = Self::encode_header(format, layout.extent)?;
+        let filtered = h.filtered_len().ok_or(InvalidImageSize(None))?;
+        is![
+            scratch.len() < filtered,
+            return Err(InsufficientBuffer { needed: filtered, available: scratch.len() })
+        ];
+        let needed = Self::encoded_len_max(format, layout.extent)?;
+        is![
+            out.len() < Self::ENCODED_OVERHEAD,
+            return Err(InsufficientBuffer { needed, available: out.len() })
+        ];
+
+        // copy the rows top-down, with big-endian samples
+        let (bytes, row_len) = (raster.raster_bytes(), h.row_len(h.width));
+        for y in 0..h.height as usize {
+            let src_y = is![layout.row_start == Boundary1d::Lower, h.height as usize - 1 - y, y];
+            let src = src_y * layout.bytes_per_line as usize;
+            let src = bytes.get(src..src + row_len).ok_or(InvalidImageSize(None))?;
+            let dst = &mut scratch[y * (row_len + 1) + 1..][..row_len];
+            if h.depth == 16 {
+                for (d, s) in dst.chunks_exact_mut(2).zip(src.chunks_exact(2)) {
+                    d.copy_from_slice(&u16::from_ne_bytes([s[0], s[1]]).to_be_bytes());
+                }
+            } else {
+                dst.copy_from_slice(src);
+            }
+        }
+        // filter bottom-up, so that each row is predicted from the raw row above
+        for y in (0..h.height as usize).rev() {
+            let at = y * (row_len + 1);
+            let (before, rest) = scratch.split_at_mut(at);
+            let prev = is![y == 0, &[][..], &before[at - row_len..]];
+            let (kind, row) = rest[..=row_len].split_first_mut().ok_or(InvalidData)?;
+            *kind = filter(row, prev, h.filter_step());
+        }
+
+        out[..8].copy_from_slice(&PNG_SIGNATURE);
+        let mut ihdr = [0; 13];
+        ihdr[..4].copy_from_slice(&h.width.to_be_bytes());
+        ihdr[4..8].copy_from_slice(&h.height.to_be_bytes());
+        (ihdr[8], ihdr[9]) = (h.depth, h.color);
+        let mut pos = Self::write_chunk(out, 8, IHDR, &ihdr);
+        pos = Self::write_chunk(out, pos, SRGB, &[0]);
+        // compress in place, leaving room for the chunk header and the trailing chunks
+        let end = out.len() - 12 - 4;
+        let len = Deflate::encode_zlib(&scratch[..filtered], &mut out[pos + 8..end])
+            .map_err(|_| InsufficientBuffer { needed, available: out.len() })?;
+        is![len > i32::MAX as usize, return Err(InvalidImageSize(None))];
+        pos = Self::write_chunk_in_place(out, pos, IDAT, len);
+        Ok(Self::write_chunk(out, pos, IEND, &[]))
+    }
+
+    /* helpers */
+
+    fn decoded_len_meta(meta: &PngMeta) -> ImageResult<usize> {
+        let h = meta.header;
+        let bpp = meta.format().stored_bytes_per_pixel().unwrap_or(1) as usize;
+        (h.width as usize)
+            .checked_mul(h.height as usize)
+            .and_then(|px| px.checked_mul(bpp))
+            .ok_or(InvalidImageSize(Some((h.width as usize, h.height as usize))))
+    }
+    /// Returns the non-interlaced header for encoding a raster.
+    fn encode_header(format: RasterFormat, extent: Extent2<u32>) -> ImageResult<PngHeader> {
+        let (color, depth) = match format {
+            RasterFormat::GRAY8 => (0, 8),
+            RasterFormat::GRAYA8 => (4, 8),
+            RasterFormat::RGB8 => (2, 8),
+            RasterFormat::RGBA8 => (6, 8),
+            RasterFormat::GRAY16 => (0, 16),
+            RasterFormat::GRAYA16 => (4, 16),
+            RasterFormat::RGB16 => (2, 16),
+            RasterFormat::RGBA16 => (6, 16),
+            _ => return Err(ImageError::UnsupportedFormat),
+        };
+        let [width, height] = extent.dim;
+        is![
+            width == 0 || height == 0 || width > i32::MAX as u32 || height > i32::MAX as u32,
+            return Err(InvalidImageSize(Some((width as usize, height as usize))))
+        ];
+        Ok(PngHeader { width, height, depth, color, interlaced: false })
+    }
+    /// Writes pixel `i` of an unfiltered row in the decoded format.
+    fn expand(meta: &PngMeta, row: &[u8], i: usize, px: &mut [u8]) -> ImageResult<()> {
+        let h = meta.header;
+        let (n, depth) = (h.channels() as usize, h.depth as usize);
+        let sample = |c: usize| -> u16 {
+            let bit = (i * n + c) * depth;
+            let b = bit / 8;
+            match depth {
+                16 => u16::from_be_bytes([row[b], row[b + 1]]),
+                8 => u16::from(row[b]),
+                _ => u16::from(row[b] >> (8 - depth - bit % 8)) & ((1 << depth) - 1),
+            }
+        };
+        if h.color == 3 {
+            let index = sample(0) as usize;
+            is![index >= meta.palette_len, return Err(InvalidPixel)];
+            px[..3].copy_from_slice(&meta.palette[index]);
+            is![px.len() == 4, px[3] = meta.alpha[index]];
+            return Ok(());
+        }
+        let wide = depth == 16;
+        let mut put = |k: usize, v: u16| {
+            is![wide, px[k * 2..k * 2 + 2].copy_from_slice(&v.to_ne_bytes()), px[k] = v as u8];
+        };
+        for c in 0..n {
+            let v = sample(c);
+            put(c, is![depth < 8, v * 255 / ((1 << depth) - 1), v]);
+        }
+        if let Some(key) = meta.key {
+            let opaque = (0..n).any(|c| sample(c) != key[c]);
+            put(n, is![opaque, is![wide, u16::MAX, 255], 0]);
+        }
+        Ok(())
+    }
+    /// Writes a chunk with `data` at `pos`, returning the position after it.
+    fn write_chunk(out: &mut [u8], pos: usize, kind: [u8; 4], data: &[u8]) -> usize {
+        out[pos + 8..pos + 8 + data.len()].copy_from_slice(data);
+        Self::write_chunk_in_place(out, pos, kind, data.len())
+    }
+    /// Writes the framing of a chunk whose `len` data bytes are already at `pos + 8`.
+    fn write_chunk_in_place(out: &mut [u8], pos: usize, kind: [u8; 4], len: usize) -> usize {
+        out[pos..pos + 4].copy_from_slice(&(len as u32).to_be_bytes());
+        out[pos + 4..pos + 8].copy_from_slice(&kind);
+        let crc = Crc32::checksum_bytes(&out[pos + 4..pos + 8 + len]);
+        out[pos + 8 + len..pos + 12 + len].copy_from_slice(&crc.to_be_bytes());
+        pos + 12 + len
+    }
+}
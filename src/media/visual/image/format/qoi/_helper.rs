@@ -0,0 +1,33 @@
+// devela/src/media/visual/image/format/qoi/_helper.rs
+//
+//! Defines the QOI constants and the color index hash.
+//
+
+/// The 4 magic bytes that start every QOI image.
+pub(crate) const QOI_MAGIC: [u8; 4] = *b"qoif";
+/// The length of the header: magic, width, height, channels and colorspace.
+pub(crate) const QOI_HEADER_LEN: usize = 14;
+/// The 8 bytes that end every QOI image.
+pub(crate) const QOI_END: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+/// `00xxxxxx`: a pixel from the color index.
+pub(crate) const QOI_OP_INDEX: u8 = 0x00;
+/// `01drdgdb`: small differences from the previous pixel.
+pub(crate) const QOI_OP_DIFF: u8 = 0x40;
+/// `10dgdgdg drdgdbdg`: green difference, and red and blue differences relative to it.
+pub(crate) const QOI_OP_LUMA: u8 = 0x80;
+/// `11rrrrrr`: a run of 1 to 62 repetitions of the previous pixel.
+pub(crate) const QOI_OP_RUN: u8 = 0xC0;
+/// A full RGB pixel, keeping the previous alpha.
+pub(crate) const QOI_OP_RGB: u8 = 0xFE;
+/// A full RGBA pixel.
+pub(crate) const QOI_OP_RGBA: u8 = 0xFF;
+/// The mask of the 2-bit tags.
+pub(crate) const QOI_MASK_2: u8 = 0xC0;
+
+/// Returns the position of an RGBA pixel in the color index.
+#[inline(always)]
+pub(crate) const fn qoi_hash(px: [u8; 4]) -> usize {
+    let [r, g, b, a] = px;
+    (r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64
+}
@@ -0,0 +1,145 @@
+// devela/src/media/visual/image/format/qoi/_test.rs
+
+use crate::{
+    Boundary1d, Extent2, ImageDecoder, ImageEncoder, ImageError, ImageInfo, Qoi, QoiDecoder,
+    RasterByteSlice, RasterFormat, RasterLayout,
+};
+
+/// 3×2 RGBA, using every chunk type once.
+const EVERY_OP: [u8; 36] = [
+    b'q', b'o', b'i', b'f', 0, 0, 0, 3, 0, 0, 0, 2, 4, 0, // header
+    0xFE, 10, 20, 30,   // RGB
+    0x76, // DIFF: +1, -1, 0
+    0xA5, 0x5A, // LUMA: dg +5, dr-dg -3, db-dg +2
+    0x09, // INDEX of the first pixel
+    0xFF, 1, 2, 3, 4,    // RGBA
+    0xC0, // RUN of 1
+    0, 0, 0, 0, 0, 0, 0, 1, // end
+];
+const EVERY_OP_RGBA: [u8; 24] = [
+    10, 20, 30, 255, 11, 19, 30, 255, 13, 24, 37, 255, //
+    10, 20, 30, 255, 1, 2, 3, 4, 1, 2, 3, 4,
+];
+
+#[test]
+fn decode_every_op() {
+    let mut d = QoiDecoder::new(&EVERY_OP).unwrap();
+    let info = d.image_info();
+    assert_eq![
+        info,
+        ImageInfo {
+            extent: Extent2::new([3, 2]),
+            format: RasterFormat::RGBA8
+        }
+    ];
+    assert_eq![(d.row_len(), d.decoded_len()), (12, Ok(24))];
+    assert![!d.is_linear()];
+    let mut out = [0; 24];
+    assert_eq![d.decode_into(&mut out).unwrap().bytes(), &EVERY_OP_RGBA];
+    assert_eq![d.next_row(), None];
+
+    // the same stream, with 3 channels
+    let mut rgb = EVERY_OP;
+    rgb[12] = 3;
+    let mut d = QoiDecoder::new(&rgb).unwrap();
+    let mut row = [0; 9];
+    assert_eq![d.decode_row(&mut row), Ok(Some(0))];
+    assert_eq![row, [10, 20, 30, 11, 19, 30, 13, 24, 37]];
+    assert_eq![d.decode_row(&mut row), Ok(Some(1))];
+    assert_eq![row, [10, 20, 30, 1, 2, 3, 1, 2, 3]];
+    assert_eq![d.decode_row(&mut row), Ok(None)];
+}
+
+#[test]
+fn decode_errors() {
+    let mut bad = EVERY_OP;
+    bad[0] = b'Q';
+    assert_eq![QoiDecoder::new(&bad).unwrap_err(), ImageError::InvalidMagicNumber];
+    let mut bad = EVERY_OP;
+    bad[12] = 2;
+    assert_eq![QoiDecoder::new(&bad).unwrap_err(), ImageError::InvalidData];
+    let mut bad = EVERY_OP;
+    bad[7] = 0;
+    assert![matches![QoiDecoder::new(&bad), Err(ImageError::InvalidImageSize(_))]];
+    assert_eq![QoiDecoder::new(&EVERY_OP[..10]).unwrap_err(), ImageError::InvalidData];
+
+    let mut out = [0; 24];
+    let mut d = QoiDecoder::new(&EVERY_OP[..20]).unwrap();
+    assert_eq![d.decode_into(&mut out).unwrap_err(), ImageError::InvalidData];
+    let mut d = QoiDecoder::new(&EVERY_OP).unwrap();
+    let err = d.decode_into(&mut out[..23]).unwrap_err();
+    assert_eq![err, ImageError::InsufficientBuffer { needed: 24, available: 23 }];
+}
+
+/// Fills an image with gradients, long runs, repeated colors, and alpha changes.
+fn pattern(pixels: &mut [u8], ch: usize) {
+    for (i, px) in pixels.chunks_exact_mut(ch).enumerate() {
+        let v = match i % 300 {
+            0..100 => [i as u8, (i / 3) as u8, 200, 255], // smooth
+            100..200 => [40, 50, 60, 255],                // long run
+            200..250 => [(i * 37) as u8, (i * 91) as u8, (i * 13) as u8, (i * 7) as u8],
+            _ => [[9, 8, 7, 6], [1, 2, 3, 4]][i % 2], // indexed
+        };
+        px.copy_from_slice(&v[..ch]);
+    }
+}
+
+#[test]
+fn encode_round_trip() {
+    for (format, ch) in [(RasterFormat::RGB8, 3), (RasterFormat::RGBA8, 4)] {
+        let extent = Extent2::new([37, 23]);
+        let mut pixels = [0; 37 * 23 * 4];
+        let pixels = &mut pixels[..37 * 23 * ch];
+        pattern(pixels, ch);
+        let raster = RasterByteSlice::dense(format, extent, &*pixels).unwrap();
+        let max = Qoi::encoded_len_max(ImageInfo { extent, format }).unwrap();
+        assert_eq![max, 37 * 23 * (ch + 1) + 22];
+        let mut qoi = [0; 37 * 23 * 5 + 22];
+        let len = Qoi::encode(&raster, &mut qoi).unwrap();
+        assert![len < pixels.len()];
+
+        let mut out = [0; 37 * 23 * 4];
+        let mut d = QoiDecoder::new(&qoi[..len]).unwrap();
+        assert_eq![d.image_info(), ImageInfo { extent, format }];
+        assert_eq![d.decode_into(&mut out).unwrap().bytes(), &*pixels];
+
+        // too small output
+        let err = Qoi::encode(&raster, &mut qoi[..len - 1]).unwrap_err();
+        assert![matches![err, ImageError::InsufficientBuffer { .. }]];
+    }
+    let gray = RasterByteSlice::dense(RasterFormat::GRAY8, Extent2::new([1, 1]), &[0]).unwrap();
+    assert_eq![Qoi::encode(&gray, &mut [0; 64]).unwrap_err(), ImageError::UnsupportedFormat];
+    assert![!Qoi::supports_format(RasterFormat::GRAY8)];
+}
+
+#[test]
+fn decode_raster_layouts() {
+    let pixels = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+    let extent = Extent2::new([2, 2]);
+    let raster = RasterByteSlice::dense(RasterFormat::RGB8, extent, &pixels).unwrap();
+    let mut qoi = [0; 64];
+    let len = Qoi::encode(&raster, &mut qoi).unwrap();
+
+    // bottom-up, with 2 bytes of padding per row
+    let layout = RasterLayout::interleaved(extent, 3, 8, Boundary1d::Lower);
+    let mut bytes = [0xAA; 16];
+    let mut dst = RasterByteSlice::new_mut(RasterFormat::RGB8, layout, &mut bytes).unwrap();
+    QoiDecoder::new(&qoi[..len]).unwrap().decode_raster(&mut dst).unwrap();
+    assert_eq![bytes, [7, 8, 9, 10, 11, 12, 0xAA, 0xAA, 1, 2, 3, 4, 5, 6, 0xAA, 0xAA]];
+
+    // encoding reads the layout back
+    let src = RasterByteSlice::new(RasterFormat::RGB8, layout, &bytes).unwrap();
+    let mut qoi2 = [0; 64];
+    let len2 = Qoi::encode(&src, &mut qoi2).unwrap();
+    assert_eq![qoi[..len], qoi2[..len2]];
+
+    // mismatched destinations
+    let mut d = QoiDecoder::new(&qoi[..len]).unwrap();
+    let mut small = [0; 12];
+    let mut dst =
+        RasterByteSlice::dense_mut(RasterFormat::RGB8, Extent2::new([3, 1]), &mut small).unwrap();
+    assert![matches![d.decode_raster(&mut dst), Err(ImageError::InvalidImageSize(_))]];
+    let mut dst =
+        RasterByteSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([2, 2]), &mut small).unwrap();
+    assert_eq![d.decode_raster(&mut dst), Err(ImageError::UnsupportedFormat)];
+}
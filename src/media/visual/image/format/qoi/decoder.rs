@@ -0,0 +1,145 @@
+// devela/src/media/visual/image/format/qoi/decoder.rs
+//
+//! Defines [`QoiDecoder`].
+//
+
+use super::{QOI_HEADER_LEN, QOI_MAGIC, QOI_MASK_2, QOI_OP_DIFF, QOI_OP_INDEX, QOI_OP_LUMA};
+use super::{QOI_OP_RGB, QOI_OP_RGBA, QOI_OP_RUN, qoi_hash};
+use crate::ImageError::{InsufficientBuffer, InvalidData, InvalidImageSize, InvalidMagicNumber};
+use crate::{Extent2, ImageDecoder, ImageInfo, ImageResult, RasterFormat, is};
+
+#[doc = crate::_tags!(image codec)]
+/// A streaming decoder for <abbr title="Quite OK Image">QOI</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// Decodes rows top-down into [`RGB8`] or [`RGBA8`],
+/// depending on the channels declared by the header.
+///
+/// See [`Qoi`][crate::Qoi] for the format, and [`ImageDecoder`] for the decoding methods.
+///
+/// [`RGB8`]: RasterFormat::RGB8
+/// [`RGBA8`]: RasterFormat::RGBA8
+#[derive(Clone, Debug)]
+pub struct QoiDecoder<'a> {
+    bytes: &'a [u8],
+    info: ImageInfo,
+    linear: bool,
+    /// The position of the next chunk.
+    pos: usize,
+    /// The next row to decode.
+    y: u32,
+    /// The remaining repetitions of the previous pixel.
+    run: u8,
+    px: [u8; 4],
+    index: [[u8; 4]; 64],
+}
+
+impl<'a> QoiDecoder<'a> {
+    /// Parses the header of a QOI image.
+    pub fn new(bytes: &'a [u8]) -> ImageResult<Self> {
+        is![bytes.len() < QOI_HEADER_LEN, return Err(InvalidData)];
+        is![bytes[..4] != QOI_MAGIC, return Err(InvalidMagicNumber)];
+        let width = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        let height = u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        let format = match bytes[12] {
+            3 => RasterFormat::RGB8,
+            4 => RasterFormat::RGBA8,
+            _ => return Err(InvalidData),
+        };
+        is![bytes[13] > 1, return Err(InvalidData)];
+        is![
+            width == 0 || height == 0 || (width as usize).checked_mul(height as usize).is_none(),
+            return Err(InvalidImageSize(Some((width as usize, height as usize))))
+        ];
+        Ok(Self {
+            bytes,
+            info: ImageInfo { extent: Extent2::new([width, height]), format },
+            linear: bytes[13] == 1,
+            pos: QOI_HEADER_LEN,
+            y: 0,
+            run: 0,
+            px: [0, 0, 0, 255],
+            index: [[0; 4]; 64],
+        })
+    }
+    /// Whether the header declares linear color channels, instead of sRGB with linear alpha.
+    ///
+    /// This is informative only, and doesn't change the decoded samples.
+    #[must_use]
+    pub const fn is_linear(&self) -> bool {
+        self.linear
+    }
+
+    /// Returns the next byte of the data.
+    fn byte(&mut self) -> ImageResult<u8> {
+        let byte = *self.bytes.get(self.pos).ok_or(InvalidData)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+    /// Decodes the next pixel into `self.px`.
+    fn pixel(&mut self) -> ImageResult<()> {
+        if self.run > 0 {
+            self.run -= 1;
+            return Ok(());
+        }
+        let op = self.byte()?;
+        match op {
+            QOI_OP_RGB => {
+                self.px[0] = self.byte()?;
+                self.px[1] = self.byte()?;
+                self.px[2] = self.byte()?;
+            }
+            QOI_OP_RGBA => {
+                self.px = [self.byte()?, self.byte()?, self.byte()?, self.byte()?];
+            }
+            _ => match op & QOI_MASK_2 {
+                QOI_OP_INDEX => {
+                    self.px = self.index[op as usize];
+                    return Ok(()); // already indexed
+                }
+                QOI_OP_DIFF => {
+                    self.px[0] = self.px[0].wrapping_add((op >> 4) & 3).wrapping_sub(2);
+                    self.px[1] = self.px[1].wrapping_add((op >> 2) & 3).wrapping_sub(2);
+                    self.px[2] = self.px[2].wrapping_add(op & 3).wrapping_sub(2);
+                }
+                QOI_OP_LUMA => {
+                    let next = self.byte()?;
+                    let dg = (op & 0x3F).wrapping_sub(32);
+                    self.px[0] =
+                        self.px[0].wrapping_add(dg.wrapping_sub(8).wrapping_add(next >> 4));
+                    self.px[1] = self.px[1].wrapping_add(dg);
+                    self.px[2] =
+                        self.px[2].wrapping_add(dg.wrapping_sub(8).wrapping_add(next & 0x0F));
+                }
+                _ => {
+                    debug_assert_eq![op & QOI_MASK_2, QOI_OP_RUN];
+                    self.run = op & 0x3F; // this pixel is the first of the run
+                }
+            },
+        }
+        self.index[qoi_hash(self.px)] = self.px;
+        Ok(())
+    }
+}
+
+impl ImageDecoder for QoiDecoder<'_> {
+    fn image_info(&self) -> ImageInfo {
+        self.info
+    }
+    fn next_row(&self) -> Option<u32> {
+        is![self.y < self.info.extent.dim[1], Some(self.y), None]
+    }
+    fn decode_row(&mut self, row: &mut [u8]) -> ImageResult<Option<u32>> {
+        let Some(y) = self.next_row() else { return Ok(None) };
+        let ch = is![self.info.format == RasterFormat::RGBA8, 4, 3];
+        let len = self.row_len();
+        let available = row.len();
+        let row = row.get_mut(..len).ok_or(InsufficientBuffer { needed: len, available })?;
+        for px in row.chunks_exact_mut(ch) {
+            self.pixel()?;
+            px.copy_from_slice(&self.px[..ch]);
+        }
+        self.y += 1;
+        Ok(Some(y))
+    }
+}
@@ -0,0 +1,24 @@
+// devela/src/media/visual/image/format/qoi/mod.rs
+//
+//! The Quite OK Image format.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (QOI_*, qoi_hash)
+
+mod decoder; // QoiDecoder
+mod namespace; // Qoi
+
+crate::structural_mods! { // _mods, _crate_internals
+    _mods {
+        pub use super::{
+            decoder::*,
+            namespace::*,
+        };
+    }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
+}
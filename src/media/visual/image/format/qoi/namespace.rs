@@ -0,0 +1,134 @@
+// devela/src/media/visual/image/format/qoi/namespace.rs
+//
+//! Defines [`Qoi`].
+//
+
+use super::{QOI_END, QOI_HEADER_LEN, QOI_MAGIC, QOI_OP_DIFF, QOI_OP_INDEX, QOI_OP_LUMA};
+use super::{QOI_OP_RGB, QOI_OP_RGBA, QOI_OP_RUN, qoi_hash};
+use crate::ImageError::{InvalidImageSize, UnsupportedFormat};
+use crate::{
+    EncodeCursor, ImageEncoder, ImageInfo, ImageResult, RasterByteSlice, RasterFormat, is,
+    raster_rows,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// Encoding and decoding for <abbr title="Quite OK Image">QOI</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// QOI is a simple lossless format, that encodes a stream of pixels with
+/// runs, references to recently seen colors, and small differences.
+/// It compresses about as well as PNG, while being much faster.
+///
+/// | Channels | Decoded format | Encoded from |
+/// | --- | --- | --- |
+/// | 3 | [`RGB8`] | [`RGB8`] |
+/// | 4 | [`RGBA8`] | [`RGBA8`] |
+///
+/// Decoding is done by [`QoiDecoder`][crate::QoiDecoder], row by row.
+/// Encoding is done by the [`ImageEncoder`] implementation,
+/// which declares the sRGB colorspace.
+///
+/// Both directions work over caller-provided buffers.
+///
+/// [`RGB8`]: RasterFormat::RGB8
+/// [`RGBA8`]: RasterFormat::RGBA8
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, ImageDecoder, ImageEncoder, Qoi, QoiDecoder};
+/// # use devela::{RasterByteSlice, RasterFormat};
+/// let pixels = [255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 255, 128, 9, 9, 9, 255];
+/// let extent = Extent2::new([2, 2]);
+/// let raster = RasterByteSlice::dense(RasterFormat::RGBA8, extent, &pixels).unwrap();
+///
+/// let mut qoi = [0; 64];
+/// let len = Qoi::encode(&raster, &mut qoi)?;
+///
+/// let mut decoder = QoiDecoder::new(&qoi[..len])?;
+/// assert_eq![decoder.image_info().extent, extent];
+/// let mut out = [0; 16];
+/// let decoded = decoder.decode_into(&mut out)?;
+/// assert_eq![decoded.bytes(), &pixels];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Qoi;
+
+impl ImageEncoder for Qoi {
+    fn supports_format(format: RasterFormat) -> bool {
+        matches![format, RasterFormat::RGB8 | RasterFormat::RGBA8]
+    }
+    fn encoded_len_max(info: ImageInfo) -> ImageResult<usize> {
+        is![!Self::supports_format(info.format), return Err(UnsupportedFormat)];
+        let ch = is![info.format == RasterFormat::RGBA8, 4, 3];
+        let [w, h] = info.extent.dim;
+        (w as usize)
+            .checked_mul(h as usize)
+            .and_then(|px| px.checked_mul(ch + 1))
+            .and_then(|len| len.checked_add(QOI_HEADER_LEN + QOI_END.len()))
+            .ok_or(InvalidImageSize(Some((w as usize, h as usize))))
+    }
+    fn encode<B: AsRef<[u8]>>(raster: &RasterByteSlice<B>, out: &mut [u8]) -> ImageResult<usize> {
+        let (format, extent) = (raster.format(), raster.extent());
+        let needed = Self::encoded_len_max(ImageInfo { extent, format })?;
+        let [w, h] = extent.dim;
+        is![w == 0 || h == 0, return Err(InvalidImageSize(Some((w as usize, h as usize))))];
+        let ch = is![format == RasterFormat::RGBA8, 4, 3];
+
+        let mut c = EncodeCursor::new(out, needed);
+        c.bytes(&QOI_MAGIC)?;
+        c.bytes(&w.to_be_bytes())?;
+        c.bytes(&h.to_be_bytes())?;
+        c.bytes(&[ch as u8, 0])?;
+
+        let (mut prev, mut index, mut run) = ([0, 0, 0, 255], [[0_u8; 4]; 64], 0_u8);
+        for row in raster_rows(raster)? {
+            for px in row.chunks_exact(ch) {
+                let px = [px[0], px[1], px[2], is![ch == 4, px[3], 255]];
+                if px == prev {
+                    run += 1;
+                    is![run == 62, {
+                        c.byte(QOI_OP_RUN | (run - 1))?;
+                        run = 0;
+                    }];
+                    continue;
+                }
+                is![run > 0, {
+                    c.byte(QOI_OP_RUN | (run - 1))?;
+                    run = 0;
+                }];
+                let hash = qoi_hash(px);
+                if index[hash] == px {
+                    c.byte(QOI_OP_INDEX | hash as u8)?;
+                } else if px[3] != prev[3] {
+                    c.byte(QOI_OP_RGBA)?;
+                    c.bytes(&px)?;
+                } else {
+                    let dr = px[0].wrapping_sub(prev[0]) as i8;
+                    let dg = px[1].wrapping_sub(prev[1]) as i8;
+                    let db = px[2].wrapping_sub(prev[2]) as i8;
+                    let (dr_dg, db_dg) = (dr.wrapping_sub(dg), db.wrapping_sub(dg));
+                    let small = |d: i8| (-2..=1).contains(&d);
+                    if small(dr) && small(dg) && small(db) {
+                        let d = ((dr + 2) << 4 | (dg + 2) << 2 | (db + 2)) as u8;
+                        c.byte(QOI_OP_DIFF | d)?;
+                    } else if (-32..=31).contains(&dg)
+                        && (-8..=7).contains(&dr_dg)
+                        && (-8..=7).contains(&db_dg)
+                    {
+                        c.byte(QOI_OP_LUMA | (dg + 32) as u8)?;
+                        c.byte(((dr_dg + 8) as u8) << 4 | (db_dg + 8) as u8)?;
+                    } else {
+                        c.byte(QOI_OP_RGB)?;
+                        c.bytes(&px[..3])?;
+                    }
+                }
+                index[hash] = px;
+                prev = px;
+            }
+        }
+        is![run > 0, c.byte(QOI_OP_RUN | (run - 1))?];
+        c.bytes(&QOI_END)?;
+        Ok(c.pos())
+    }
+}
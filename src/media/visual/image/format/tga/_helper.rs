@@ -0,0 +1,106 @@
+// devela/src/media/visual/image/format/tga/_helper.rs
+//
+//! Defines [`TgaHeader`].
+//
+
+use crate::ImageError::{InvalidData, InvalidImageSize, UnsupportedFormat};
+use crate::{ImageResult, RasterFormat, is};
+
+/// The length of the fixed header.
+pub(crate) const TGA_HEADER_LEN: usize = 18;
+/// The TGA 2.0 footer, without extension or developer areas.
+pub(crate) const TGA_FOOTER: [u8; 26] = *b"\0\0\0\0\0\0\0\0TRUEVISION-XFILE.\0";
+/// The descriptor bit of images stored right-to-left.
+pub(crate) const TGA_RIGHT_TO_LEFT: u8 = 0x10;
+/// The descriptor bit of images stored top-down.
+pub(crate) const TGA_TOP_DOWN: u8 = 0x20;
+
+/// The parsed fixed header of a TGA image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TgaHeader {
+    /// The image type: `1`, `2`, `3` uncompressed, `9`, `10`, `11` run-length encoded.
+    pub kind: u8,
+    /// The index of the first color map entry.
+    pub cmap_first: u16,
+    /// The number of color map entries.
+    pub cmap_len: u16,
+    /// The bits per color map entry.
+    pub cmap_depth: u8,
+    /// The width in pixels.
+    pub width: u16,
+    /// The height in pixels.
+    pub height: u16,
+    /// The bits per stored pixel.
+    pub depth: u8,
+    /// The number of alpha bits, and the orientation bits.
+    pub descriptor: u8,
+    /// The offset of the color map.
+    pub cmap_offset: usize,
+    /// The offset of the image data.
+    pub data_offset: usize,
+}
+impl TgaHeader {
+    /// Parses and validates the header.
+    pub(crate) fn parse(bytes: &[u8]) -> ImageResult<Self> {
+        is![bytes.len() < TGA_HEADER_LEN, return Err(InvalidData)];
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let (cmap_type, kind) = (bytes[1], bytes[2]);
+        let h = Self {
+            kind,
+            cmap_first: u16_at(3),
+            cmap_len: u16_at(5),
+            cmap_depth: bytes[7],
+            width: u16_at(12),
+            height: u16_at(14),
+            depth: bytes[16],
+            descriptor: bytes[17],
+            cmap_offset: TGA_HEADER_LEN + bytes[0] as usize,
+            data_offset: 0,
+        };
+        is![cmap_type > 1 || !matches![kind & !8, 1..=3], return Err(UnsupportedFormat)];
+        let cmap_entry = is![cmap_type == 1, Self::depth_bytes(h.cmap_depth)?, 0];
+        let data_offset = h.cmap_offset + h.cmap_len as usize * cmap_entry;
+        is![data_offset > bytes.len(), return Err(InvalidData)];
+        let valid = match kind & !8 {
+            1 => cmap_type == 1 && h.depth == 8 && h.cmap_len > 0 && h.cmap_depth > 8,
+            2 => matches![h.depth, 15 | 16 | 24 | 32],
+            _ => matches![h.depth, 8 | 16],
+        };
+        is![!valid, return Err(UnsupportedFormat)];
+        is![
+            h.width == 0 || h.height == 0,
+            return Err(InvalidImageSize(Some((h.width as usize, h.height as usize))))
+        ];
+        Ok(Self { data_offset, ..h })
+    }
+    /// Returns the bytes used by an entry or pixel of `depth` bits.
+    pub(crate) fn depth_bytes(depth: u8) -> ImageResult<usize> {
+        match depth {
+            8 | 15 | 16 | 24 | 32 => Ok((depth as usize).div_ceil(8)),
+            _ => Err(UnsupportedFormat),
+        }
+    }
+    /// Whether the image data is run-length encoded.
+    pub(crate) const fn is_rle(&self) -> bool {
+        self.kind & 8 != 0
+    }
+    /// Whether the image is color-mapped.
+    pub(crate) const fn is_mapped(&self) -> bool {
+        self.kind & !8 == 1
+    }
+    /// Returns the number of alpha bits declared by the descriptor.
+    pub(crate) const fn alpha_bits(&self) -> u8 {
+        self.descriptor & 0x0F
+    }
+    /// Returns the format of the decoded samples.
+    pub(crate) const fn format(&self) -> RasterFormat {
+        let depth = is![self.is_mapped(), self.cmap_depth, self.depth];
+        match (self.kind & !8, depth) {
+            (3, 8) => RasterFormat::GRAY8,
+            (3, _) => RasterFormat::GRAYA8,
+            (1, 32) => RasterFormat::RGBA8,
+            (2, 16 | 32) if self.alpha_bits() > 0 => RasterFormat::RGBA8,
+            _ => RasterFormat::RGB8,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// devela/src/media/visual/image/format/tga/_test.rs
+
+use crate::{
+    Boundary1d, Extent2, ImageDecoder, ImageEncoder, ImageError, ImageInfo, ImageResult,
+    RasterByteSlice, RasterFormat, RasterLayout, Tga, TgaDecoder,
+};
+
+/// Writes a TGA file into `buf`, returning its length.
+fn tga(buf: &mut [u8], header: [u8; 18], rest: &[u8]) -> usize {
+    buf[..18].copy_from_slice(&header);
+    buf[18..18 + rest.len()].copy_from_slice(rest);
+    18 + rest.len()
+}
+/// Returns a header without color map.
+const fn header(kind: u8, (w, h): (u8, u8), depth: u8, descriptor: u8) -> [u8; 18] {
+    [0, 0, kind, 0, 0, 0, 0, 0, 0, 0, 0, 0, w, 0, h, 0, depth, descriptor]
+}
+
+/// Decodes a whole image into `out`, returning its info.
+fn decode(bytes: &[u8], out: &mut [u8]) -> ImageResult<ImageInfo> {
+    let mut d = TgaDecoder::new(bytes)?;
+    d.decode_into(out)?;
+    Ok(d.image_info())
+}
+
+#[test]
+fn decode_rle_across_rows() {
+    // 3×2 BGR, bottom-up, with a repeated packet spanning both rows
+    let mut buf = [0; 64];
+    let data = [0x83, 1, 2, 3, 0x01, 4, 5, 6, 7, 8, 9];
+    let len = tga(&mut buf, header(10, (3, 2), 24, 0), &data);
+    let mut out = [0; 18];
+    let info = decode(&buf[..len], &mut out).unwrap();
+    assert_eq![
+        info,
+        ImageInfo {
+            extent: Extent2::new([3, 2]),
+            format: RasterFormat::RGB8
+        }
+    ];
+    assert_eq![out, [3, 2, 1, 6, 5, 4, 9, 8, 7, 3, 2, 1, 3, 2, 1, 3, 2, 1]];
+
+    // the same pixels, uncompressed
+    let data = [1, 2, 3, 1, 2, 3, 1, 2, 3, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let len = tga(&mut buf, header(2, (3, 2), 24, 0), &data);
+    let mut out2 = [0; 18];
+    decode(&buf[..len], &mut out2).unwrap();
+    assert_eq![out, out2];
+
+    // grayscale, top-down
+    let len = tga(&mut buf, header(11, (2, 2), 8, 0x20), &[0x81, 7, 0x01, 8, 9]);
+    let mut out = [0; 4];
+    assert_eq![decode(&buf[..len], &mut out).unwrap().format, RasterFormat::GRAY8];
+    assert_eq![out, [7, 7, 8, 9]];
+}
+
+#[test]
+fn decode_color_mapped() {
+    // an image ID, a color map starting at 1 with 32-bit entries, right-to-left top-down
+    let mut h = header(1, (2, 1), 8, 0x30);
+    (h[0], h[1], h[3], h[5], h[7]) = (3, 1, 1, 2, 32);
+    let rest = [b'i', b'd', b'!', 10, 20, 30, 40, 50, 60, 70, 80, 1, 2];
+    let mut buf = [0; 64];
+    let len = tga(&mut buf, h, &rest);
+    let mut out = [0; 8];
+    assert_eq![decode(&buf[..len], &mut out).unwrap().format, RasterFormat::RGBA8];
+    assert_eq![out, [70, 60, 50, 80, 30, 20, 10, 40]];
+
+    // an index before the first entry
+    let len = tga(&mut buf, h, &[b'i', b'd', b'!', 10, 20, 30, 40, 50, 60, 70, 80, 0, 1]);
+    assert_eq![decode(&buf[..len], &mut out), Err(ImageError::InvalidPixel)];
+}
+
+#[test]
+fn decode_16_bit() {
+    let mut buf = [0; 64];
+    let mut out = [0; 8];
+    // 1-5-5-5 with an alpha bit
+    let len = tga(&mut buf, header(2, (2, 1), 16, 0x21), &[0x1F, 0x80, 0x00, 0x7C]);
+    assert_eq![decode(&buf[..len], &mut out).unwrap().format, RasterFormat::RGBA8];
+    assert_eq![out, [0, 0, 255, 255, 255, 0, 0, 0]];
+    // without alpha bits
+    let len = tga(&mut buf, header(2, (2, 1), 15, 0x20), &[0x1F, 0x80, 0xE0, 0x03]);
+    assert_eq![decode(&buf[..len], &mut out).unwrap().format, RasterFormat::RGB8];
+    assert_eq![out[..6], [0, 0, 255, 0, 255, 0]];
+    // gray and alpha
+    let len = tga(&mut buf, header(3, (1, 1), 16, 0x28), &[100, 200]);
+    assert_eq![decode(&buf[..len], &mut out).unwrap().format, RasterFormat::GRAYA8];
+    assert_eq![out[..2], [100, 200]];
+}
+
+#[test]
+fn decode_errors() {
+    let mut buf = [0; 64];
+    let mut out = [0; 18];
+    let len = tga(&mut buf, header(32, (1, 1), 8, 0), &[0]);
+    assert_eq![TgaDecoder::new(&buf[..len]).unwrap_err(), ImageError::UnsupportedFormat];
+    let len = tga(&mut buf, header(2, (1, 1), 12, 0), &[0, 0]);
+    assert_eq![TgaDecoder::new(&buf[..len]).unwrap_err(), ImageError::UnsupportedFormat];
+    let len = tga(&mut buf, header(2, (0, 1), 24, 0), &[]);
+    assert![matches![TgaDecoder::new(&buf[..len]), Err(ImageError::InvalidImageSize(_))]];
+    assert_eq![TgaDecoder::new(&buf[..17]).unwrap_err(), ImageError::InvalidData];
+    let len = tga(&mut buf, header(10, (3, 2), 24, 0), &[0x83, 1, 2, 3, 0x01, 4, 5]);
+    assert_eq![decode(&buf[..len], &mut out), Err(ImageError::InvalidData)];
+}
+
+#[test]
+fn encode_round_trip() {
+    let formats = [
+        (RasterFormat::GRAY8, 1),
+        (RasterFormat::GRAYA8, 2),
+        (RasterFormat::RGB8, 3),
+        (RasterFormat::RGBA8, 4),
+    ];
+    // a row of 300 pixels needs several packets
+    let extent = Extent2::new([300, 3]);
+    let mut pixels = [0; 300 * 3 * 4];
+    for (i, b) in pixels.iter_mut().enumerate() {
+        *b = match i % 1200 {
+            0..40 => 1,
+            40..50 => (i * 7) as u8,
+            50..900 => (i / 8) as u8,
+            _ => (i * 13) as u8,
+        };
+    }
+    for (format, n) in formats {
+        let pixels = &pixels[..900 * n];
+        let raster = RasterByteSlice::dense(format, extent, pixels).unwrap();
+        let max = Tga::encoded_len_max(ImageInfo { extent, format }).unwrap();
+        let mut tga = [0; 900 * 5 + 44];
+        let rle = Tga::encode(&raster, &mut tga).unwrap();
+        assert![rle <= max && rle < 900 * n + 44];
+        let mut out = [0; 900 * 4];
+        let mut d = TgaDecoder::new(&tga[..rle]).unwrap();
+        assert_eq![d.image_info(), ImageInfo { extent, format }];
+        assert_eq![d.decode_into(&mut out).unwrap().bytes(), pixels];
+        assert_eq![tga[rle - 18..rle], *b"TRUEVISION-XFILE.\0"];
+
+        let raw = Tga::encode_uncompressed(&raster, &mut tga).unwrap();
+        assert_eq![raw, 900 * n + 44];
+        let mut d = TgaDecoder::new(&tga[..raw]).unwrap();
+        assert_eq![d.decode_into(&mut out).unwrap().bytes(), pixels];
+    }
+    let rgb16 = RasterByteSlice::dense(RasterFormat::RGB16, Extent2::new([1, 1]), &[0; 6]).unwrap();
+    assert_eq![Tga::encode(&rgb16, &mut [0; 64]), Err(ImageError::UnsupportedFormat)];
+}
+
+#[test]
+fn encode_layouts() {
+    // bottom-up source with padding, decoded into a bottom-up destination
+    let extent = Extent2::new([2, 2]);
+    let layout = RasterLayout::interleaved(extent, 1, 3, Boundary1d::Lower);
+    let stored = [3, 4, 0xEE, 1, 2, 0xEE];
+    let raster = RasterByteSlice::new(RasterFormat::GRAY8, layout, &stored).unwrap();
+    let mut tga = [0; 64];
+    let len = Tga::encode(&raster, &mut tga).unwrap();
+
+    let mut out = [0; 4];
+    decode(&tga[..len], &mut out).unwrap();
+    assert_eq![out, [1, 2, 3, 4]];
+    let mut bytes = [0; 6];
+    let mut dst = RasterByteSlice::new_mut(RasterFormat::GRAY8, layout, &mut bytes).unwrap();
+    TgaDecoder::new(&tga[..len]).unwrap().decode_raster(&mut dst).unwrap();
+    assert_eq![bytes, [3, 4, 0, 1, 2, 0]];
+
+    let err = Tga::encode(&raster, &mut tga[..len - 1]).unwrap_err();
+    assert![matches![err, ImageError::InsufficientBuffer { .. }]];
+}
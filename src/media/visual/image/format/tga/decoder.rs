@@ -0,0 +1,123 @@
+// devela/src/media/visual/image/format/tga/decoder.rs
+//
+//! Defines [`TgaDecoder`].
+//
+
+use super::{TGA_RIGHT_TO_LEFT, TGA_TOP_DOWN, TgaHeader};
+use crate::ImageError::{InsufficientBuffer, InvalidData, InvalidPixel};
+use crate::{Extent2, ImageDecoder, ImageInfo, ImageResult, RasterFormat, is};
+
+#[doc = crate::_tags!(image codec)]
+/// A streaming decoder for <abbr title="Truevision Graphics Adapter">TGA</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// Decodes rows in their stored order, into the formats listed by [`Tga`][crate::Tga].
+///
+/// See [`ImageDecoder`] for the decoding methods.
+#[derive(Clone, Debug)]
+pub struct TgaDecoder<'a> {
+    bytes: &'a [u8],
+    header: TgaHeader,
+    info: ImageInfo,
+    /// The position of the next stored pixel or packet.
+    pos: usize,
+    /// The number of rows already decoded.
+    rows: u32,
+    /// The remaining pixels of the current run-length packet.
+    packet_left: u8,
+    /// Whether the current packet repeats a single pixel.
+    packet_repeat: bool,
+}
+
+impl<'a> TgaDecoder<'a> {
+    /// Parses the header of a TGA image.
+    pub fn new(bytes: &'a [u8]) -> ImageResult<Self> {
+        let header = TgaHeader::parse(bytes)?;
+        let extent = Extent2::new([u32::from(header.width), u32::from(header.height)]);
+        Ok(Self {
+            bytes,
+            header,
+            info: ImageInfo { extent, format: header.format() },
+            pos: header.data_offset,
+            rows: 0,
+            packet_left: 0,
+            packet_repeat: false,
+        })
+    }
+
+    /// Returns the stored pixel at the current position, and advances it.
+    fn stored(&mut self, len: usize) -> ImageResult<&'a [u8]> {
+        let bytes = self.bytes.get(self.pos..self.pos + len).ok_or(InvalidData)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+    /// Returns the next stored pixel, following the run-length packets if needed.
+    fn next_stored(&mut self, len: usize) -> ImageResult<&'a [u8]> {
+        if !self.header.is_rle() {
+            return self.stored(len);
+        }
+        if self.packet_left == 0 {
+            let head = self.stored(1)?[0];
+            (self.packet_left, self.packet_repeat) = ((head & 0x7F) + 1, head & 0x80 != 0);
+        }
+        self.packet_left -= 1;
+        let px = self.stored(len)?;
+        // rewind to the repeated pixel, unless it's the last one of the packet
+        is![self.packet_repeat && self.packet_left > 0, self.pos -= len];
+        Ok(px)
+    }
+    /// Writes a stored pixel or color map entry of `depth` bits in the decoded format.
+    fn expand(format: RasterFormat, depth: u8, px: &[u8], out: &mut [u8]) {
+        match depth {
+            8 => out[0] = px[0],
+            15 | 16 if format == RasterFormat::GRAYA8 => out[..2].copy_from_slice(&px[..2]),
+            15 | 16 => {
+                let v = u16::from_le_bytes([px[0], px[1]]);
+                let c = |shift: u16| ((v >> shift & 31) * 255 / 31) as u8;
+                out[..3].copy_from_slice(&[c(10), c(5), c(0)]);
+                is![out.len() == 4, out[3] = is![v & 0x8000 != 0, 255, 0]];
+            }
+            _ => {
+                out[..3].copy_from_slice(&[px[2], px[1], px[0]]);
+                is![out.len() == 4, out[3] = is![depth == 32, px[3], 255]];
+            }
+        }
+    }
+}
+
+impl ImageDecoder for TgaDecoder<'_> {
+    fn image_info(&self) -> ImageInfo {
+        self.info
+    }
+    fn next_row(&self) -> Option<u32> {
+        let h = self.info.extent.dim[1];
+        is![self.rows >= h, return None];
+        Some(is![self.header.descriptor & TGA_TOP_DOWN != 0, self.rows, h - 1 - self.rows])
+    }
+    fn decode_row(&mut self, row: &mut [u8]) -> ImageResult<Option<u32>> {
+        let Some(y) = self.next_row() else { return Ok(None) };
+        let (len, available) = (self.row_len(), row.len());
+        let row = row.get_mut(..len).ok_or(InsufficientBuffer { needed: len, available })?;
+        let h = self.header;
+        let (format, stored_len) = (self.info.format, TgaHeader::depth_bytes(h.depth)?);
+        let bpp = format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+        for px in row.chunks_exact_mut(bpp) {
+            let stored = self.next_stored(stored_len)?;
+            if h.is_mapped() {
+                let index = usize::from(stored[0]).checked_sub(usize::from(h.cmap_first));
+                let index = index.filter(|&i| i < usize::from(h.cmap_len)).ok_or(InvalidPixel)?;
+                let entry_len = TgaHeader::depth_bytes(h.cmap_depth)?;
+                let entry = &self.bytes[h.cmap_offset + index * entry_len..][..entry_len];
+                Self::expand(format, h.cmap_depth, entry, px);
+            } else {
+                Self::expand(format, h.depth, stored, px);
+            }
+        }
+        if h.descriptor & TGA_RIGHT_TO_LEFT != 0 {
+            row.reverse();
+            row.chunks_exact_mut(bpp).for_each(<[u8]>::reverse);
+        }
+        self.rows += 1;
+        Ok(Some(y))
+    }
+}
@@ -0,0 +1,24 @@
+// devela/src/media/visual/image/format/tga/mod.rs
+//
+//! The Truevision TGA format.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (TgaHeader, TGA_*)
+
+mod decoder; // TgaDecoder
+mod namespace; // Tga
+
+crate::structural_mods! { // _mods, _crate_internals
+    _mods {
+        pub use super::{
+            decoder::*,
+            namespace::*,
+        };
+    }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
+}
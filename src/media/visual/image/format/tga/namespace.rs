@@ -0,0 +1,177 @@
+// devela/src/media/visual/image/format/tga/namespace.rs
+//
+//! Defines [`Tga`].
+//
+
+use super::{TGA_FOOTER, TGA_HEADER_LEN, TGA_TOP_DOWN};
+use crate::ImageError::{InvalidImageSize, UnsupportedFormat};
+use crate::{
+    EncodeCursor, ImageEncoder, ImageInfo, ImageResult, RasterByteSlice, RasterFormat, is,
+    raster_rows,
+};
+
+#[doc = crate::_tags!(image codec)]
+/// Encoding and decoding for <abbr title="Truevision Graphics Adapter">TGA</abbr> images.
+#[doc = crate::_doc_meta!{location("media/visual/image")}]
+///
+/// TGA stores uncompressed or run-length encoded pixels, in any of the
+/// four corner orientations, which makes it a common format for sprites.
+///
+/// | Image type | Bit depths | Decoded format | Encoded from |
+/// | --- | --- | --- | --- |
+/// | gray | 8 | [`GRAY8`] | [`GRAY8`] |
+/// | gray and alpha | 16 | [`GRAYA8`] | [`GRAYA8`] |
+/// | true color | 15, 16, 24 | [`RGB8`] | [`RGB8`] |
+/// | true color, with alpha bits | 16, 32 | [`RGBA8`] | [`RGBA8`] |
+/// | color-mapped | 8, over 15 to 24-bit entries | [`RGB8`] | — |
+/// | color-mapped | 8, over 32-bit entries | [`RGBA8`] | — |
+///
+/// Every type can be uncompressed or run-length encoded,
+/// and packets may continue across rows.
+///
+/// Decoding is done by [`TgaDecoder`][crate::TgaDecoder], row by row.
+/// Encoding is done by the [`ImageEncoder`] implementation, which writes
+/// run-length encoded images, or by [`encode_uncompressed`][Self::encode_uncompressed].
+/// Both write top-down images, with a TGA 2.0 footer.
+///
+/// Current limitations:
+/// - The image ID, the extension area and the developer area are ignored.
+/// - Color-mapped images need 8-bit indices.
+///
+/// [`GRAY8`]: RasterFormat::GRAY8
+/// [`GRAYA8`]: RasterFormat::GRAYA8
+/// [`RGB8`]: RasterFormat::RGB8
+/// [`RGBA8`]: RasterFormat::RGBA8
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, ImageDecoder, ImageEncoder, RasterByteSlice, RasterFormat};
+/// # use devela::{Tga, TgaDecoder};
+/// let pixels = [7, 7, 7, 7, 7, 7, 1, 2, 3, 4, 5, 6];
+/// let extent = Extent2::new([2, 2]);
+/// let raster = RasterByteSlice::dense(RasterFormat::RGB8, extent, &pixels).unwrap();
+///
+/// let mut tga = [0; 64];
+/// let len = Tga::encode(&raster, &mut tga)?;
+///
+/// let mut decoder = TgaDecoder::new(&tga[..len])?;
+/// assert_eq![decoder.image_info().format, RasterFormat::RGB8];
+/// let mut out = [0; 12];
+/// let decoded = decoder.decode_into(&mut out)?;
+/// assert_eq![decoded.bytes(), &pixels];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Tga;
+
+impl Tga {
+    /// Encodes a raster as an uncompressed TGA image into `out`.
+    ///
+    /// Accepts the same formats as [`encode`][ImageEncoder::encode],
+    /// and never writes more than [`encoded_len_max`][ImageEncoder::encoded_len_max] bytes.
+    ///
+    /// Returns the number of bytes written.
+    pub fn encode_uncompressed<B: AsRef<[u8]>>(
+        raster: &RasterByteSlice<B>,
+        out: &mut [u8],
+    ) -> ImageResult<usize> {
+        Self::encode_with(raster, out, false)
+    }
+
+    /// Encodes a raster, with or without run-length encoding.
+    fn encode_with<B: AsRef<[u8]>>(
+        raster: &RasterByteSlice<B>,
+        out: &mut [u8],
+        rle: bool,
+    ) -> ImageResult<usize> {
+        let (format, extent) = (raster.format(), raster.extent());
+        let needed = Self::encoded_len_max(ImageInfo { extent, format })?;
+        let [w, h] = extent.dim;
+        is![
+            w == 0 || h == 0 || w > u32::from(u16::MAX) || h > u32::from(u16::MAX),
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        let n = format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+        let (kind, alpha_bits) = match format {
+            RasterFormat::GRAY8 => (3, 0),
+            RasterFormat::GRAYA8 => (3, 8),
+            RasterFormat::RGB8 => (2, 0),
+            _ => (2, 8),
+        };
+        let mut c = EncodeCursor::new(out, needed);
+        c.bytes(&[0, 0, kind | is![rle, 8, 0]])?;
+        c.zeros(9)?; // color map specification, and origin
+        c.bytes(&(w as u16).to_le_bytes())?;
+        c.bytes(&(h as u16).to_le_bytes())?;
+        c.bytes(&[n as u8 * 8, alpha_bits | TGA_TOP_DOWN])?;
+
+        // stored pixels are little-endian: BGR and BGRA
+        let stored = |row: &[u8], x: usize| {
+            let mut px = [0; 4];
+            px[..n].copy_from_slice(&row[x * n..][..n]);
+            is![n >= 3, px.swap(0, 2)];
+            px
+        };
+        for row in raster_rows(raster)? {
+            let (w, mut x) = (w as usize, 0);
+            if !rle {
+                while x < w {
+                    c.bytes(&stored(row, x)[..n])?;
+                    x += 1;
+                }
+                continue;
+            }
+            while x < w {
+                let px = stored(row, x);
+                let mut len = 1;
+                while x + len < w && len < 128 && stored(row, x + len) == px {
+                    len += 1;
+                }
+                if len > 1 {
+                    c.byte(0x80 | (len - 1) as u8)?;
+                    c.bytes(&px[..n])?;
+                } else {
+                    // extend the raw packet until a repeated pixel starts
+                    while x + len < w
+                        && len < 128
+                        && (x + len + 1 == w || stored(row, x + len) != stored(row, x + len + 1))
+                    {
+                        len += 1;
+                    }
+                    c.byte((len - 1) as u8)?;
+                    for i in x..x + len {
+                        c.bytes(&stored(row, i)[..n])?;
+                    }
+                }
+                x += len;
+            }
+        }
+        c.bytes(&TGA_FOOTER)?;
+        Ok(c.pos())
+    }
+}
+
+impl ImageEncoder for Tga {
+    fn supports_format(format: RasterFormat) -> bool {
+        matches![
+            format,
+            RasterFormat::GRAY8 | RasterFormat::GRAYA8 | RasterFormat::RGB8 | RasterFormat::RGBA8
+        ]
+    }
+    /// Returns the number of bytes [`encode`][Self::encode] can write at most,
+    /// which is also enough for [`encode_uncompressed`][Tga::encode_uncompressed].
+    fn encoded_len_max(info: ImageInfo) -> ImageResult<usize> {
+        is![!Self::supports_format(info.format), return Err(UnsupportedFormat)];
+        let n = info.format.stored_bytes_per_pixel().unwrap_or(1) as usize;
+        let [w, h] = info.extent.dim;
+        // each packet header covers at least one pixel
+        (w as usize)
+            .checked_mul(h as usize)
+            .and_then(|px| px.checked_mul(n + 1))
+            .and_then(|len| len.checked_add(TGA_HEADER_LEN + TGA_FOOTER.len()))
+            .ok_or(InvalidImageSize(Some((w as usize, h as usize))))
+    }
+    fn encode<B: AsRef<[u8]>>(raster: &RasterByteSlice<B>, out: &mut [u8]) -> ImageResult<usize> {
+        Self::encode_with(raster, out, true)
+    }
+}
@@ -8,7 +8,7 @@
 
 mod error;
 mod info; // ImageInfo, ImageFrame<Info|Span>
-pub mod format; // Pnm, WIP Jpeg, Png, Qoi…
+pub mod format; // Bmp, Gif, Png, Pnm, Qoi, Tga, Image[Decoder|Encoder]…
 pub mod raster; // Raster[[Buf|View][Bytes]], Raster<Sample|View>Packed, raster!
 
 #[cfg(feature = "term")]
@@ -32,6 +32,7 @@ crate::structural_mods! { // _mods, _pub_mods, _crate_internals, _hidden
     }
     _crate_internals {
         pub use super::{
+            format::_crate_internals::*,
             raster::_crate_internals::*,
         };
     }
@@ -0,0 +1,118 @@
+// devela/src/media/visual/image/raster/draw/_helper.rs
+//
+//! Vector and arc helpers shared by the vector rasterizers.
+//
+
+use crate::{Float, is};
+
+/// The flag of a path vertex that starts a contour.
+pub(super) const PATH_START: u8 = 1;
+/// The flag of the last vertex of a closed contour.
+pub(super) const PATH_CLOSED: u8 = 2;
+
+/// Returns whether both vectors have the same bits.
+pub(super) const fn v_eq(a: [f32; 2], b: [f32; 2]) -> bool {
+    a[0].to_bits() == b[0].to_bits() && a[1].to_bits() == b[1].to_bits()
+}
+pub(super) const fn v_add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+pub(super) const fn v_sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+pub(super) const fn v_scale(a: [f32; 2], s: f32) -> [f32; 2] {
+    [a[0] * s, a[1] * s]
+}
+pub(super) const fn v_dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+pub(super) const fn v_cross(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[1] - a[1] * b[0]
+}
+pub(super) const fn v_len(a: [f32; 2]) -> f32 {
+    Float(v_dot(a, a)).sqrt_hybrid().0
+}
+/// Returns the vector rotated a quarter turn, from `+x` towards `+y`.
+pub(super) const fn v_perp(a: [f32; 2]) -> [f32; 2] {
+    [-a[1], a[0]]
+}
+
+/// Returns the vector rotated the given number of quarter turns.
+pub(super) const fn v_turn(a: [f32; 2], quarters: usize) -> [f32; 2] {
+    match quarters % 4 {
+        0 => a,
+        1 => v_perp(a),
+        2 => [-a[0], -a[1]],
+        _ => [a[1], -a[0]],
+    }
+}
+
+/// The rotation that flattens circular arcs of some radius within a tolerance.
+///
+/// Rotations go from `+x` towards `+y`, which gives polygons of positive [`v_cross`] area.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ArcStep {
+    /// The number of steps in a quarter turn.
+    pub(super) quarter: usize,
+    cos: f32,
+    sin: f32,
+}
+impl ArcStep {
+    /// The maximum number of steps in a quarter turn.
+    const QUARTER_MAX: usize = 256;
+
+    pub(super) const fn new(radius: f32, tolerance: f32) -> Self {
+        const FRAC_PI_2: f32 = Float::<f32>::FRAC_PI_2.0;
+        // the sagitta of a chord spanning an angle θ is r(1 - cos(θ/2)) ≈ rθ²/8
+        let theta = Float(8.0 * tolerance / radius).sqrt_hybrid().0;
+        let steps = Float(FRAC_PI_2 / theta).const_ceil().0;
+        let quarter = is![steps >= 1.0, steps as usize, 1];
+        let quarter = is![quarter > Self::QUARTER_MAX, Self::QUARTER_MAX, quarter];
+        let step = Float(FRAC_PI_2 / quarter as f32);
+        Self {
+            quarter,
+            cos: step.cos_series(8).0,
+            sin: step.sin_series(8).0,
+        }
+    }
+    /// Returns the number of steps in a full turn.
+    pub(super) const fn turn_len(&self) -> usize {
+        self.quarter * 4
+    }
+    /// Returns `v` rotated one step.
+    pub(super) const fn rotate(&self, v: [f32; 2]) -> [f32; 2] {
+        [v[0] * self.cos - v[1] * self.sin, v[0] * self.sin + v[1] * self.cos]
+    }
+    /// Calls `f` with the unit vectors of a full turn, starting at `+x`.
+    pub(super) fn for_turn(&self, mut f: impl FnMut([f32; 2])) {
+        for quadrant in 0..4 {
+            let mut v = [1.0, 0.0];
+            for _ in 0..self.quarter {
+                f(v_turn(v, quadrant));
+                v = self.rotate(v);
+            }
+        }
+    }
+    /// Calls `f` with the unit vectors of a quarter turn, including both ends,
+    /// from the start of the given `quadrant`.
+    pub(super) fn for_quadrant(&self, quadrant: usize, mut f: impl FnMut([f32; 2])) {
+        let mut v = [1.0, 0.0];
+        for _ in 0..self.quarter {
+            f(v_turn(v, quadrant));
+            v = self.rotate(v);
+        }
+        f(v_turn([0.0, 1.0], quadrant));
+    }
+    /// Returns the number of steps needed to rotate `from` until it reaches `to`,
+    /// going from `+x` towards `+y`, not counting the final partial step.
+    pub(super) fn steps_between(&self, from: [f32; 2], to: [f32; 2]) -> usize {
+        let (mut v, mut steps) = (from, 0);
+        while steps < self.turn_len() {
+            let next = self.rotate(v);
+            // stop once `to` is reached or passed
+            is![v_cross(next, to) <= 0.0 && v_dot(next, to) > 0.0, break];
+            (v, steps) = (next, steps + 1);
+        }
+        steps
+    }
+}
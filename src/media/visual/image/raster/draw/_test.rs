@@ -218,3 +218,303 @@ mod line_iter {
         }
     }
 }
+
+/// Fills the `path` into a `W×H` coverage map.
+fn fill_map<const W: usize, const H: usize, const N: usize>(
+    path: &crate::RasterPath<N>,
+    rule: crate::RasterFillRule,
+) -> [[u8; W]; H] {
+    let grid = RasterGrid::try_new(ext![W, H]).unwrap();
+    let mut scratch = [0.0; 64];
+    let mut map = [[0; W]; H];
+    let mut last = None;
+    for span in path.fill(grid, rule, &mut scratch).unwrap() {
+        let [x, y] = span.start().dim;
+        assert![!span.is_empty() && !span.coverage().is_zero()];
+        assert![last.is_none_or(|last| (y, x) > last), "spans are ordered"];
+        last = Some((y, x + span.len() - 1));
+        for element in span.elements() {
+            let [x, y] = element.coord().dim;
+            map[y][x] = element.coverage().get();
+        }
+    }
+    map
+}
+/// Returns the covered area of a coverage map.
+fn area<const W: usize, const H: usize>(map: &[[u8; W]; H]) -> f32 {
+    map.as_flattened().iter().map(|&c| f32::from(c) / 255.0).sum()
+}
+/// Returns the sum of the coverages of a coverage map, in units of `1/255` cells.
+fn covered<const W: usize, const H: usize>(map: &[[u8; W]; H]) -> u32 {
+    map.as_flattened().iter().map(|&c| u32::from(c)).sum()
+}
+/// Asserts that `area` is that of a shape with curves of length `arc` flattened
+/// by inscribed chords, which lose less than the default tolerance along them.
+fn assert_flattened_area(area: f32, exact: f32, arc: f32) {
+    let lost = exact - area;
+    assert![lost > -0.02 && lost < arc * crate::RasterPath::<1>::TOLERANCE, "{area} ≉ {exact}"];
+}
+
+mod fill {
+    use super::*;
+    use crate::{NotEnoughSpace, RasterFillIter, RasterFillRule as Rule, RasterPath, RasterSpan};
+
+    #[test]
+    fn rect_edges_have_exact_coverage() {
+        let mut path = RasterPath::<8>::new();
+        path.rect(pos![0.5, 1.0], ext![3.0, 1.25]).unwrap();
+        let map = fill_map::<4, 3, 8>(&path, Rule::NonZero);
+        assert_eq![map, [[0; 4], [128, 255, 255, 128], [32, 64, 64, 32]]];
+        // the orientation doesn't matter
+        let mut path = RasterPath::<8>::new();
+        path.polygon(&[pos![0.5, 1.0], pos![0.5, 2.25], pos![3.5, 2.25], pos![3.5, 1.0]])
+            .unwrap();
+        assert_eq![fill_map::<4, 3, 8>(&path, Rule::NonZero), map];
+    }
+    #[test]
+    fn sloped_edges_cover_their_area() {
+        let mut path = RasterPath::<8>::new();
+        path.polygon(&[pos![1.0, 1.0], pos![14.0, 3.0], pos![6.5, 12.2]]).unwrap();
+        let map = fill_map::<16, 16, 8>(&path, Rule::NonZero);
+        // the area of the triangle, with an error below a quantization step per cell
+        let exact = (13.0 * 11.2 - 2.0 * 5.5) / 2.0;
+        assert![(area(&map) - exact).abs() < 0.2, "{}", area(&map)];
+        assert_eq![map[6][6], 255];
+        assert_eq![map[0], [0; 16]];
+        // a single row
+        let mut path = RasterPath::<8>::new();
+        path.polygon(&[pos![0.0, 0.0], pos![4.0, 0.0], pos![0.0, 1.0]]).unwrap();
+        let map = fill_map::<4, 1, 8>(&path, Rule::NonZero);
+        assert_eq![map, [[223, 159, 96, 32]]];
+    }
+    #[test]
+    fn clips_to_the_grid() {
+        let mut path = RasterPath::<8>::new();
+        path.rect(pos![-2.0, -2.0], ext![4.0, 4.0]).unwrap();
+        let map = fill_map::<4, 4, 8>(&path, Rule::NonZero);
+        assert_eq![map, [[255, 255, 0, 0], [255, 255, 0, 0], [0; 4], [0; 4]]];
+        // edges crossing both sides within a row
+        let mut path = RasterPath::<8>::new();
+        path.polygon(&[pos![-10.0, 0.0], pos![10.0, 0.0], pos![14.0, 2.0], pos![-6.0, 2.0]])
+            .unwrap();
+        assert_eq![fill_map::<4, 3, 8>(&path, Rule::NonZero), [[255; 4], [255; 4], [0; 4]]];
+        let mut path = RasterPath::<8>::new();
+        path.polygon(&[pos![-4.0, 0.0], pos![-1.0, 0.0], pos![-1.0, 4.0]]).unwrap();
+        assert_eq![fill_map::<4, 4, 8>(&path, Rule::NonZero), [[0; 4]; 4]];
+    }
+    #[test]
+    fn fill_rules() {
+        let mut path = RasterPath::<16>::new();
+        path.rect(pos![0.0, 0.0], ext![3.0, 3.0]).unwrap();
+        path.rect(pos![1.0, 1.0], ext![1.0, 1.0]).unwrap();
+        let (non_zero, even_odd) = (Rule::NonZero, Rule::EvenOdd);
+        assert_eq![fill_map::<3, 3, 16>(&path, non_zero)[1], [255; 3]];
+        assert_eq![fill_map::<3, 3, 16>(&path, even_odd)[1], [255, 0, 255]];
+        // a hole of the opposite direction
+        path.clear();
+        path.rect(pos![0.0, 0.0], ext![3.0, 3.0]).unwrap();
+        path.polygon(&[pos![1.0, 1.0], pos![1.0, 2.0], pos![2.0, 2.0], pos![2.0, 1.0]]).unwrap();
+        assert_eq![fill_map::<3, 3, 16>(&path, non_zero)[1], [255, 0, 255]];
+        assert_eq![fill_map::<3, 3, 16>(&path, even_odd)[1], [255, 0, 255]];
+    }
+    #[test]
+    fn scratch_and_empty_inputs() {
+        let grid = RasterGrid::try_new(ext![4, 4]).unwrap();
+        let mut path = RasterPath::<8>::new();
+        let mut scratch = [0.0; 6];
+        assert_eq![RasterFillIter::scratch_len(grid), 6];
+        let short = path.fill(grid, Rule::NonZero, &mut scratch[..5]).err();
+        assert_eq![short, Some(NotEnoughSpace(Some(6)))];
+        assert_eq![path.fill(grid, Rule::NonZero, &mut scratch).unwrap().next(), None];
+        path.rect(pos![0.0, 0.0], ext![2.0, 2.0]).unwrap();
+        let empty = RasterGrid::try_new(ext![0, 4]).unwrap();
+        assert_eq![path.fill(empty, Rule::NonZero, &mut scratch).unwrap().next(), None];
+        let mut spans = path.fill(grid, Rule::EvenOdd, &mut scratch).unwrap();
+        assert_eq![spans.next(), Some(RasterSpan::new(pos![0, 0], 2, Coverage8::FULL))];
+        assert_eq![spans.next(), Some(RasterSpan::new(pos![0, 1], 2, Coverage8::FULL))];
+        assert_eq![(spans.next(), spans.next()), (None, None)];
+    }
+}
+
+mod path {
+    use super::*;
+    use crate::{NotEnoughSpace, RasterFillRule as Rule, RasterPath};
+
+    #[test]
+    fn lines_and_contours() {
+        let mut path = RasterPath::<6>::new();
+        // without a current point, a contour starts at the first point
+        path.line_to(pos![1.0, 1.0]).unwrap();
+        path.line_to(pos![4.0, 1.0]).unwrap();
+        path.line_to(pos![4.0, 1.0]).unwrap(); // repeated
+        path.line_to(pos![4.0, 3.0]).unwrap();
+        assert_eq![path.points(), &[[1.0, 1.0], [4.0, 1.0], [4.0, 3.0]]];
+        // after closing, drawing starts at the start of the contour
+        path.close();
+        path.line_to(pos![1.0, 3.0]).unwrap();
+        assert_eq![path.points()[3..], [[1.0, 1.0], [1.0, 3.0]]];
+        assert_eq![path.bounds(), Some([pos![1.0, 1.0], pos![4.0, 3.0]])];
+        // a full path is unchanged
+        assert_eq![path.rect(pos![0.0, 0.0], ext![1.0, 1.0]), Err(NotEnoughSpace(Some(4)))];
+        path.move_to(pos![0.0, 0.0]).unwrap();
+        assert_eq![path.line_to(pos![1.0, 1.0]), Err(NotEnoughSpace(Some(1)))];
+        assert_eq![(path.len(), path.capacity()), (6, 6)];
+        path.clear();
+        assert![path.is_empty() && path.bounds().is_none()];
+    }
+    #[test]
+    fn curves_are_flattened_within_tolerance() {
+        let mut path = RasterPath::<256>::new();
+        path.move_to(pos![0.0, 0.0]).unwrap();
+        path.quad_to(pos![5.0, 10.0], pos![10.0, 0.0]).unwrap();
+        let quad = path.len() - 1;
+        assert![quad > 4 && quad.is_multiple_of(2)];
+        assert![path.points().contains(&[5.0, 5.0])];
+        assert_eq![path.points().last(), Some(&[10.0, 0.0])];
+        // the midpoints of the chords stay within the tolerance of the curve, y = 2x - x²/5
+        for w in path.points().windows(2) {
+            let (x, y) = ((w[0][0] + w[1][0]) / 2.0, (w[0][1] + w[1][1]) / 2.0);
+            assert![(2.0 * x - x * x / 5.0 - y).abs() < RasterPath::<1>::TOLERANCE];
+        }
+        // a coarser tolerance needs fewer segments
+        let mut coarse = RasterPath::<256>::new().with_tolerance(1.0);
+        coarse.move_to(pos![0.0, 0.0]).unwrap();
+        coarse.quad_to(pos![5.0, 10.0], pos![10.0, 0.0]).unwrap();
+        assert![coarse.len() - 1 < quad];
+        // a cubic S curve, symmetric around its center
+        path.clear();
+        path.move_to(pos![0.0, 0.0]).unwrap();
+        path.cubic_to(pos![10.0, 0.0], pos![0.0, 10.0], pos![10.0, 10.0]).unwrap();
+        let points = path.points();
+        for (a, b) in points.iter().zip(points.iter().rev()) {
+            assert![(a[0] + b[0] - 10.0).abs() < 1e-4 && (a[1] + b[1] - 10.0).abs() < 1e-4];
+        }
+    }
+    #[test]
+    fn shapes_cover_their_area() {
+        const PI: f32 = core::f32::consts::PI;
+        let mut path = RasterPath::<256>::new();
+        path.circle(pos![8.0, 8.0], 6.0).unwrap();
+        let [min, max] = path.bounds().unwrap();
+        assert_eq![(min, max), (pos![2.0, 2.0], pos![14.0, 14.0])];
+        let map = fill_map::<16, 16, 256>(&path, Rule::NonZero);
+        assert_flattened_area(area(&map), PI * 36.0, 2.0 * PI * 6.0);
+        assert_eq![(map[8][8], map[8][1], map[1][8]), (255, 0, 0)];
+
+        path.clear();
+        path.ellipse(pos![8.0, 4.0], ext![7.0, 3.0]).unwrap();
+        assert_eq![path.bounds(), Some([pos![1.0, 1.0], pos![15.0, 7.0]])];
+        let map = fill_map::<16, 8, 256>(&path, Rule::NonZero);
+        assert_flattened_area(area(&map), PI * 21.0, 2.0 * PI * 7.0);
+
+        path.clear();
+        path.rounded_rect(pos![1.0, 2.0], ext![12.0, 8.0], 3.0).unwrap();
+        assert_eq![path.bounds(), Some([pos![1.0, 2.0], pos![13.0, 10.0]])];
+        let map = fill_map::<16, 16, 256>(&path, Rule::NonZero);
+        let exact = 12.0 * 8.0 - (4.0 - PI) * 9.0;
+        assert_flattened_area(area(&map), exact, 2.0 * PI * 3.0);
+        assert_eq![(map[2][6], map[5][1]), (255, 255)];
+        assert![map[2][1] < 16]; // only grazed by the corner
+        // the radius is limited to half of the smallest side
+        path.clear();
+        path.rounded_rect(pos![0.0, 0.0], ext![4.0, 2.0], 5.0).unwrap();
+        assert_eq![path.bounds(), Some([pos![0.0, 0.0], pos![4.0, 2.0]])];
+    }
+}
+
+mod stroke {
+    use super::*;
+    use crate::{NotEnoughSpace, RasterFillRule as Rule, RasterLineCap as Cap};
+    use crate::{RasterLineJoin as Join, RasterPath, RasterStroke};
+
+    const PI: f32 = core::f32::consts::PI;
+
+    fn outline(path: &RasterPath<8>, stroke: RasterStroke) -> RasterPath<512> {
+        let mut out = RasterPath::new();
+        path.stroke(&stroke, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn caps() {
+        let mut path = RasterPath::<8>::new();
+        path.move_to(pos![3.0, 4.0]).unwrap();
+        path.line_to(pos![9.0, 4.0]).unwrap();
+        let butt = outline(&path, RasterStroke::new(2.0));
+        assert_eq![butt.bounds(), Some([pos![3.0, 3.0], pos![9.0, 5.0]])];
+        let map = fill_map::<12, 8, 512>(&butt, Rule::NonZero);
+        assert_eq![covered(&map), 12 * 255];
+        let square = outline(&path, RasterStroke::new(2.0).with_cap(Cap::Square));
+        assert_eq![covered(&fill_map::<12, 8, 512>(&square, Rule::NonZero)), 16 * 255];
+        let round = outline(&path, RasterStroke::new(2.0).with_cap(Cap::Round));
+        let [min, max] = round.bounds().unwrap();
+        assert![(min.dim[0] - 2.0).abs() < 1e-4 && (max.dim[0] - 10.0).abs() < 1e-4];
+        let map = fill_map::<12, 8, 512>(&round, Rule::NonZero);
+        assert_flattened_area(area(&map), 12.0 + PI, 2.0 * PI);
+        // single points become dots
+        path.clear();
+        path.move_to(pos![4.0, 4.0]).unwrap();
+        assert![outline(&path, RasterStroke::new(2.0)).is_empty()];
+        let dot = outline(&path, RasterStroke::new(4.0).with_cap(Cap::Round));
+        let map = fill_map::<12, 8, 512>(&dot, Rule::NonZero);
+        assert_flattened_area(area(&map), PI * 4.0, 2.0 * PI * 2.0);
+        let dot = outline(&path, RasterStroke::new(2.0).with_cap(Cap::Square));
+        assert_eq![dot.bounds(), Some([pos![3.0, 3.0], pos![5.0, 5.0]])];
+    }
+    #[test]
+    fn joins() {
+        // a right angle, turning at (8, 2)
+        let mut path = RasterPath::<8>::new();
+        path.move_to(pos![2.0, 2.0]).unwrap();
+        path.line_to(pos![8.0, 2.0]).unwrap();
+        path.line_to(pos![8.0, 8.0]).unwrap();
+        let stroke = RasterStroke::new(2.0);
+        let miter = fill_map::<12, 12, 512>(&outline(&path, stroke), Rule::NonZero);
+        let bevel = outline(&path, stroke.with_join(Join::Bevel));
+        let bevel = fill_map::<12, 12, 512>(&bevel, Rule::NonZero);
+        let round = outline(&path, stroke.with_join(Join::Round));
+        let round = fill_map::<12, 12, 512>(&round, Rule::NonZero);
+        // the outer corner cell
+        assert_eq![(miter[1][8], bevel[1][8]), (255, 128)];
+        assert_flattened_area(area(&round), 23.0 + PI / 4.0, PI / 2.0);
+        assert_eq![(covered(&miter), covered(&bevel)), (24 * 255, 23 * 255 + 128)];
+        // the inner corner is covered once
+        assert_eq![(miter[3][6], miter[2][7], miter[3][7]), (0, 255, 255)];
+        // a sharp angle exceeding the miter limit is beveled
+        path.clear();
+        path.move_to(pos![1.0, 4.0]).unwrap();
+        path.line_to(pos![11.0, 5.0]).unwrap();
+        path.line_to(pos![1.0, 6.0]).unwrap();
+        let sharp = outline(&path, stroke.with_miter_limit(20.0));
+        let limited = outline(&path, stroke);
+        assert![sharp.bounds().unwrap()[1].dim[0] > 15.0];
+        assert![limited.bounds().unwrap()[1].dim[0] < 12.0];
+        // going back on itself only joins with a round join
+        path.clear();
+        path.move_to(pos![2.0, 4.0]).unwrap();
+        path.line_to(pos![8.0, 4.0]).unwrap();
+        path.line_to(pos![4.0, 4.0]).unwrap();
+        assert_eq![outline(&path, stroke).bounds().unwrap()[1], pos![8.0, 5.0]];
+        let back = outline(&path, stroke.with_join(Join::Round));
+        assert![(back.bounds().unwrap()[1].dim[0] - 9.0).abs() < 1e-4];
+    }
+    #[test]
+    fn closed_contours() {
+        let mut path = RasterPath::<8>::new();
+        path.rect(pos![2.0, 2.0], ext![6.0, 6.0]).unwrap();
+        let out = outline(&path, RasterStroke::new(2.0).with_cap(Cap::Round));
+        assert_eq![out.bounds(), Some([pos![1.0, 1.0], pos![9.0, 9.0]])];
+        let map = fill_map::<10, 10, 512>(&out, Rule::NonZero);
+        assert_eq![covered(&map), (64 - 16) * 255];
+        assert_eq![(map[1][1], map[5][5], map[5][2]), (255, 0, 255)];
+        // an explicit closing vertex is the same
+        let mut explicit = RasterPath::<8>::new();
+        let corners = [[2.0, 2.0], [8.0, 2.0], [8.0, 8.0], [2.0, 8.0], [2.0, 2.0]];
+        explicit.polygon(&corners.map(Position2::new)).unwrap();
+        assert_eq![outline(&explicit, RasterStroke::new(2.0)), out];
+        // not enough space
+        let mut small = RasterPath::<8>::new();
+        assert_eq![path.stroke(&RasterStroke::new(2.0), &mut small), Err(NotEnoughSpace(Some(4)))];
+        assert![outline(&path, RasterStroke::new(0.0)).is_empty()];
+    }
+}
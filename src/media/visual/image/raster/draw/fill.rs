@@ -0,0 +1,303 @@
+// devela/src/media/visual/image/raster/draw/fill.rs
+//
+//! Defines [`RasterFillIter`], [`RasterFillRule`], [`RasterSpan`].
+//
+// > How much of each raster cell lies inside a polygon?
+
+use super::PATH_START;
+use crate::{Coverage8, Float, IteratorFused, NotEnoughSpace, Position2, RasterElement};
+use crate::{RasterGrid, is};
+
+#[doc = crate::_tags!(image geom)]
+/// The rule that decides which points are inside overlapping or self-intersecting contours.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster/draw")}]
+///
+/// Both rules count how many times the contours wind around a point,
+/// adding their crossings in one direction and subtracting those in the other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RasterFillRule {
+    /// Points with a winding number other than zero are inside.
+    ///
+    /// Contours of opposite directions cut holes into each other.
+    #[default]
+    NonZero,
+    /// Points with an odd winding number are inside.
+    ///
+    /// Overlapping contours cut holes into each other, whatever their directions.
+    EvenOdd,
+}
+
+impl RasterFillRule {
+    /// Returns the coverage of an accumulated signed area.
+    const fn coverage(self, area: f32) -> u8 {
+        let area = area.abs();
+        let covered = match self {
+            Self::NonZero => is![area > 1.0, 1.0, area],
+            Self::EvenOdd => {
+                let area = area % 2.0;
+                is![area > 1.0, 2.0 - area, area]
+            }
+        };
+        (covered * 255.0 + 0.5) as u8
+    }
+}
+
+#[doc = crate::_tags!(image)]
+/// A horizontal run of raster cells with the same coverage.
+#[doc = crate::_doc_meta!{
+    location("media/visual/image/raster/draw"),
+    #[cfg(target_pointer_width = "32")]
+    test_size_of(RasterSpan = 16),
+    #[cfg(target_pointer_width = "64")]
+    test_size_of(RasterSpan = 32),
+}]
+///
+/// Produced by [`RasterFillIter`].
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RasterSpan {
+    start: Position2<usize>,
+    len: usize,
+    coverage: Coverage8,
+}
+
+impl RasterSpan {
+    /// Creates a span of `len` cells, from `start` towards positive `x`.
+    pub const fn new(start: Position2<usize>, len: usize, coverage: Coverage8) -> Self {
+        Self { start, len, coverage }
+    }
+    /// Returns the coordinate of the first cell.
+    pub const fn start(self) -> Position2<usize> {
+        self.start
+    }
+    /// Returns the number of cells.
+    #[must_use]
+    pub const fn len(self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the span has no cells.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.len == 0
+    }
+    /// Returns the coverage shared by all the cells.
+    pub const fn coverage(self) -> Coverage8 {
+        self.coverage
+    }
+    /// Returns an iterator over the raster elements of each cell.
+    pub fn elements(self) -> impl Iterator<Item = RasterElement> {
+        let [x, y] = self.start.dim;
+        (x..x + self.len).map(move |x| RasterElement::new(Position2::new([x, y]), self.coverage))
+    }
+}
+
+#[doc = crate::_tags!(image iterator)]
+/// An iterator over the coverage spans of filled polygonal contours.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster/draw")}]
+///
+/// Created by [`RasterPath::fill`][crate::RasterPath::fill].
+///
+/// The spans are emitted row by row, from left to right,
+/// omitting uncovered cells and cells outside of the [`RasterGrid`].
+///
+/// Rasterization is independent of sample storage, color, paint, compositing,
+/// and physical raster layout.
+///
+/// # Algorithm
+///
+/// For each row, the signed area that every edge covers within each cell,
+/// and its change of winding to the right of the edge, are accumulated
+/// into a scratch row. Their running sum over the row is the exact winding-weighted
+/// area of each cell, which the [`RasterFillRule`] converts into coverage.
+///
+/// Edges outside the grid are clamped to its left or right side,
+/// which preserves the winding of every cell inside it.
+///
+/// Work is proportional to the covered rows times the number of edges,
+/// plus the covered cells.
+#[must_use]
+#[derive(Debug)]
+pub struct RasterFillIter<'a> {
+    points: &'a [[f32; 2]],
+    flags: &'a [u8],
+    grid: RasterGrid,
+    rule: RasterFillRule,
+    scratch: &'a mut [f32],
+    /// The covered columns, clipped to the grid.
+    columns: [usize; 2],
+    /// The current row, and the end of the covered rows.
+    rows: [usize; 2],
+    /// The next column to scan, or `None` if the row is not accumulated yet.
+    x: Option<usize>,
+    /// The running sum of the scanned cells.
+    area: f32,
+}
+
+impl<'a> RasterFillIter<'a> {
+    /// Returns the number of scratch elements needed to rasterize into `grid`.
+    #[must_use]
+    pub const fn scratch_len(grid: RasterGrid) -> usize {
+        grid.width() + 2
+    }
+
+    pub(super) fn new(
+        points: &'a [[f32; 2]],
+        flags: &'a [u8],
+        grid: RasterGrid,
+        rule: RasterFillRule,
+        scratch: &'a mut [f32],
+    ) -> Result<Self, NotEnoughSpace> {
+        let needed = Self::scratch_len(grid);
+        is![scratch.len() < needed, return Err(NotEnoughSpace(Some(needed)))];
+        let (mut min, mut max) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+        for p in points {
+            (min[0], min[1]) = (min[0].min(p[0]), min[1].min(p[1]));
+            (max[0], max[1]) = (max[0].max(p[0]), max[1].max(p[1]));
+        }
+        let [w, h] = grid.extent().dim;
+        let clip = |v: f32, len: usize| Float(v.clamp(0.0, len as f32));
+        let columns =
+            [clip(min[0], w).const_floor().0 as usize, clip(max[0], w).const_ceil().0 as usize];
+        let mut rows =
+            [clip(min[1], h).const_floor().0 as usize, clip(max[1], h).const_ceil().0 as usize];
+        is![points.is_empty() || grid.is_empty(), rows = [0, 0]];
+        Ok(Self {
+            points,
+            flags,
+            grid,
+            rule,
+            scratch,
+            columns,
+            rows,
+            x: None,
+            area: 0.0,
+        })
+    }
+
+    /// Returns the raster grid that clips the fill.
+    pub const fn grid(&self) -> RasterGrid {
+        self.grid
+    }
+    /// Returns the fill rule.
+    pub const fn rule(&self) -> RasterFillRule {
+        self.rule
+    }
+
+    /* private */
+
+    /// Accumulates the edges that cross the current row into the scratch row.
+    fn accumulate_row(&mut self) {
+        let [x0, x1] = self.columns;
+        self.scratch[x0..x1 + 2].fill(0.0);
+        let top = self.rows[0] as f32;
+        let width = self.grid.width() as f32;
+        let mut start = 0;
+        for (i, &a) in self.points.iter().enumerate() {
+            is![self.flags[i] & PATH_START != 0, start = i];
+            let next = self.flags.get(i + 1).is_some_and(|f| f & PATH_START == 0);
+            let b = self.points[is![next, i + 1, start]];
+            accumulate_edge(self.scratch, a, b, top, width);
+        }
+        (self.x, self.area) = (Some(x0), 0.0);
+    }
+}
+
+/// Accumulates the part of the edge from `a` to `b` that crosses the row at `top`,
+/// splitting it where it leaves the columns `[0, width]`.
+fn accumulate_edge(acc: &mut [f32], a: [f32; 2], b: [f32; 2], top: f32, width: f32) {
+    let (dir, p, q) = match a[1].partial_cmp(&b[1]) {
+        Some(core::cmp::Ordering::Less) => (1.0, a, b),
+        Some(core::cmp::Ordering::Greater) => (-1.0, b, a),
+        _ => return,
+    };
+    let (y0, y1) = (p[1].max(top), q[1].min(top + 1.0));
+    is![y0 >= y1, return];
+    let dxdy = (q[0] - p[0]) / (q[1] - p[1]);
+    let x_at = |y: f32| p[0] + (y - p[1]) * dxdy;
+    // the heights where the edge crosses the sides of the grid, in order
+    let mut cuts = [y0, y1, y1, y1];
+    let mut n = 1;
+    for side in [0.0, width] {
+        if dxdy != 0.0 {
+            let y = p[1] + (side - p[0]) / dxdy;
+            is![y > y0 && y < y1, {
+                cuts[n] = y;
+                n += 1;
+            }];
+        }
+    }
+    is![n == 3 && cuts[2] < cuts[1], cuts.swap(1, 2)];
+    cuts[n] = y1;
+    for piece in cuts[..=n].windows(2) {
+        let (ya, yb) = (piece[0], piece[1]);
+        is![ya >= yb, continue];
+        let (xa, xb) = (x_at(ya).clamp(0.0, width), x_at(yb).clamp(0.0, width));
+        accumulate_piece(acc, xa, xb, dir * (yb - ya));
+    }
+}
+
+/// Accumulates a piece of an edge within a row and within the grid,
+/// going from `x` to `x_next` over a signed height `d`.
+fn accumulate_piece(acc: &mut [f32], x: f32, x_next: f32, d: f32) {
+    let (x0, x1) = is![x < x_next, (x, x_next), (x_next, x)];
+    let x0_floor = Float(x0).const_floor().0;
+    let x1_ceil = Float(x1).const_ceil().0;
+    let (x0i, x1i) = (x0_floor as usize, x1_ceil as usize);
+    if x1i <= x0i + 1 {
+        // within a single cell: the area left of the midpoint goes to the next cell
+        let mid = 0.5 * (x + x_next) - x0_floor;
+        acc[x0i] += d - d * mid;
+        acc[x0i + 1] += d * mid;
+    } else {
+        // across several cells: a triangle, trapezoids, and a final triangle
+        let s = (x1 - x0).recip();
+        let x0f = x0 - x0_floor;
+        let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+        let x1f = x1 - x1_ceil + 1.0;
+        let am = 0.5 * s * x1f * x1f;
+        acc[x0i] += d * a0;
+        if x1i == x0i + 2 {
+            acc[x0i + 1] += d * (1.0 - a0 - am);
+        } else {
+            let a1 = s * (1.5 - x0f);
+            acc[x0i + 1] += d * (a1 - a0);
+            for cell in &mut acc[x0i + 2..x1i - 1] {
+                *cell += d * s;
+            }
+            let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+            acc[x1i - 1] += d * (1.0 - a2 - am);
+        }
+        acc[x1i] += d * am;
+    }
+}
+
+impl Iterator for RasterFillIter<'_> {
+    type Item = RasterSpan;
+
+    fn next(&mut self) -> Option<RasterSpan> {
+        let end = self.columns[1];
+        while self.rows[0] < self.rows[1] {
+            let Some(mut x) = self.x else {
+                self.accumulate_row();
+                continue;
+            };
+            while x < end {
+                self.area += self.scratch[x];
+                let (start, coverage) = (x, self.rule.coverage(self.area));
+                x += 1;
+                is![coverage == 0, continue];
+                while x < end {
+                    let area = self.area + self.scratch[x];
+                    is![self.rule.coverage(area) != coverage, break];
+                    (self.area, x) = (area, x + 1);
+                }
+                self.x = Some(x);
+                let start = Position2::new([start, self.rows[0]]);
+                return Some(RasterSpan::new(start, x - start.dim[0], Coverage8::new(coverage)));
+            }
+            (self.rows[0], self.x) = (self.rows[0] + 1, None);
+        }
+        None
+    }
+}
+impl IteratorFused for RasterFillIter<'_> {}
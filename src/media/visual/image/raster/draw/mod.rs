@@ -2,7 +2,8 @@
 //
 //! Rasterization of geometric primitives into covered raster cells.
 //!
-//! Rasterizers in this module convert geometry into [`RasterElement`] streams.
+//! Rasterizers in this module convert geometry into [`RasterElement`] streams,
+//! or into [`RasterSpan`]s of cells with the same coverage.
 //! They do not choose paint, mutate sample storage, or perform compositing.
 //!
 //! [`RasterElement`]: crate::RasterElement
@@ -11,20 +12,22 @@
 #[cfg(test)]
 mod _test;
 
-// mod ellipse; //
+mod _helper; // PATH_*, ArcStep, v_*
+mod fill; // Anti-aliased polygon coverage
 mod line; // Aliased raster-line traversal
-// mod path; //
-// mod rect; //
-// mod triangle; //
+mod path; // Vector paths flattened into polygons
+mod stroke; // Stroke outlines with caps and joins
 
-crate::structural_mods! { // _mods
+crate::structural_mods! { // _mods, _crate_internals
     _mods {
         pub use super::{
-            // ellipse::*,
+            fill::{RasterFillIter, RasterFillRule, RasterSpan},
             line::RasterLineIter,
-            // path::*,
-            // rect::*,
-            // triangle::*,
+            path::RasterPath,
+            stroke::{RasterLineCap, RasterLineJoin, RasterStroke},
         };
     }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
+    }
 }
@@ -0,0 +1,351 @@
+// devela/src/media/visual/image/raster/draw/path.rs
+//
+//! Defines [`RasterPath`].
+//
+// > Which polygons approximate a shape of lines and curves?
+
+use super::{ArcStep, PATH_CLOSED, PATH_START, v_eq, v_len};
+use crate::{
+    Extent2, Float, NotEnoughSpace, Position2, RasterFillIter, RasterFillRule, RasterGrid, is,
+};
+
+#[doc = crate::_tags!(image geom)]
+/// A vector path of up to `N` vertices, flattened into polygonal contours.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster/draw")}]
+///
+/// Coordinates are in the boundary space of a [`RasterGrid`], where the cell `[x, y]`
+/// covers `[x, x + 1) × [y, y + 1)`, and may lie outside of it.
+///
+/// Curves and arcs are flattened as they are added, into line segments that deviate
+/// from the exact shape by less than the [`tolerance`][Self::tolerance].
+/// Drawing without a current point starts a contour at the first given point.
+///
+/// A path is rasterized with [`fill`][Self::fill], or converted into the outline
+/// of its stroke with [`stroke`][Self::stroke], which is then filled.
+/// Contours are always filled as if they were closed.
+///
+/// Adding to a full path returns [`NotEnoughSpace`] and leaves the path unchanged.
+///
+/// # Example
+/// ```
+/// # use devela::{Coverage8, RasterFillRule, RasterGrid, RasterPath, ext, pos};
+/// let grid = RasterGrid::try_new(ext![4, 4]).unwrap();
+/// let mut path = RasterPath::<64>::new();
+/// path.rect(pos![0.5, 1.0], ext![3.0, 2.0])?;
+///
+/// let mut scratch = [0.0; 4 + 2];
+/// let mut spans = path.fill(grid, RasterFillRule::NonZero, &mut scratch).unwrap();
+/// let span = spans.next().unwrap();
+/// assert_eq![(span.start(), span.len(), span.coverage()), (pos![0, 1], 1, Coverage8::new(128))];
+/// let span = spans.next().unwrap();
+/// assert_eq![(span.start(), span.len(), span.coverage()), (pos![1, 1], 2, Coverage8::FULL)];
+/// # Ok::<(), devela::NotEnoughSpace>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RasterPath<const N: usize> {
+    points: [[f32; 2]; N],
+    flags: [u8; N],
+    len: usize,
+    /// The index of the first vertex of the last contour.
+    contour: usize,
+    tolerance: f32,
+}
+
+impl<const N: usize> Default for RasterPath<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> RasterPath<N> {
+    /// The default flattening tolerance, in cells.
+    pub const TOLERANCE: f32 = 0.1;
+
+    /// The maximum number of segments a single curve is flattened into.
+    const CURVE_SEGMENTS_MAX: usize = 128;
+
+    /// Returns an empty path with the default [`TOLERANCE`][Self::TOLERANCE].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            points: [[0.0; 2]; N],
+            flags: [0; N],
+            len: 0,
+            contour: 0,
+            tolerance: Self::TOLERANCE,
+        }
+    }
+    /// Returns the path with the given flattening `tolerance`, in cells.
+    ///
+    /// Non-positive tolerances are replaced by the default one.
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = is![tolerance > 0.0, tolerance, Self::TOLERANCE];
+        self
+    }
+
+    /* queries */
+
+    /// Returns the flattening tolerance, in cells.
+    #[must_use]
+    pub const fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+    /// Returns the number of vertices.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if there are no vertices.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the maximum number of vertices.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    /// Returns the vertices, as `[x, y]` pairs.
+    #[must_use]
+    pub fn points(&self) -> &[[f32; 2]] {
+        &self.points[..self.len]
+    }
+    /// Returns the smallest and largest coordinates of the vertices,
+    /// or `None` if the path is empty.
+    #[must_use]
+    pub fn bounds(&self) -> Option<[Position2<f32>; 2]> {
+        let (first, rest) = self.points().split_first()?;
+        let (mut min, mut max) = (*first, *first);
+        for p in rest {
+            (min[0], min[1]) = (min[0].min(p[0]), min[1].min(p[1]));
+            (max[0], max[1]) = (max[0].max(p[0]), max[1].max(p[1]));
+        }
+        Some([Position2::new(min), Position2::new(max)])
+    }
+    /// Removes all the vertices.
+    pub const fn clear(&mut self) {
+        (self.len, self.contour) = (0, 0);
+    }
+
+    /* drawing */
+
+    /// Starts a new contour at `to`.
+    pub fn move_to(&mut self, to: Position2<f32>) -> Result<(), NotEnoughSpace> {
+        is![self.len == N, return Err(NotEnoughSpace(Some(1)))];
+        self.contour = self.len;
+        self.push(to.dim, PATH_START);
+        Ok(())
+    }
+    /// Adds a line from the current point to `to`.
+    pub fn line_to(&mut self, to: Position2<f32>) -> Result<(), NotEnoughSpace> {
+        self.reserve(to.dim, 1)?;
+        self.push_line(to.dim);
+        Ok(())
+    }
+    /// Adds a quadratic Bézier curve from the current point to `to`.
+    pub fn quad_to(
+        &mut self,
+        ctrl: Position2<f32>,
+        to: Position2<f32>,
+    ) -> Result<(), NotEnoughSpace> {
+        let (c, to) = (ctrl.dim, to.dim);
+        let p = self.current().unwrap_or(c);
+        // the distance to the chord is below |p - 2c + to| / (4n²)
+        let dd = v_len([p[0] - 2.0 * c[0] + to[0], p[1] - 2.0 * c[1] + to[1]]);
+        let n = self.curve_segments(dd / 4.0);
+        self.reserve(c, n)?;
+        for i in 1..n {
+            let t = i as f32 / n as f32;
+            let (a, b, d) = ((1.0 - t) * (1.0 - t), 2.0 * t * (1.0 - t), t * t);
+            self.push_line([a * p[0] + b * c[0] + d * to[0], a * p[1] + b * c[1] + d * to[1]]);
+        }
+        self.push_line(to);
+        Ok(())
+    }
+    /// Adds a cubic Bézier curve from the current point to `to`.
+    pub fn cubic_to(
+        &mut self,
+        ctrl1: Position2<f32>,
+        ctrl2: Position2<f32>,
+        to: Position2<f32>,
+    ) -> Result<(), NotEnoughSpace> {
+        let (c1, c2, to) = (ctrl1.dim, ctrl2.dim, to.dim);
+        let p = self.current().unwrap_or(c1);
+        // the distance to the chord is below 3·max|second difference| / (4n²)
+        let dd1 = v_len([p[0] - 2.0 * c1[0] + c2[0], p[1] - 2.0 * c1[1] + c2[1]]);
+        let dd2 = v_len([c1[0] - 2.0 * c2[0] + to[0], c1[1] - 2.0 * c2[1] + to[1]]);
+        let n = self.curve_segments(0.75 * dd1.max(dd2));
+        self.reserve(c1, n)?;
+        for i in 1..n {
+            let t = i as f32 / n as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            let x = a * p[0] + b * c1[0] + c * c2[0] + d * to[0];
+            let y = a * p[1] + b * c1[1] + c * c2[1] + d * to[1];
+            self.push_line([x, y]);
+        }
+        self.push_line(to);
+        Ok(())
+    }
+    /// Closes the current contour, so that the next drawing starts at its first point.
+    ///
+    /// Closing only matters for strokes, which join the ends of closed contours
+    /// instead of capping them.
+    pub const fn close(&mut self) {
+        is![self.len > self.contour + 1, self.flags[self.len - 1] |= PATH_CLOSED];
+    }
+
+    /* shapes */
+
+    /// Adds a closed contour through the given `points`.
+    pub fn polygon(&mut self, points: &[Position2<f32>]) -> Result<(), NotEnoughSpace> {
+        is![points.is_empty(), return Ok(())];
+        self.reserve_contour(points.len())?;
+        self.push(points[0].dim, PATH_START);
+        for p in &points[1..] {
+            self.push_line(p.dim);
+        }
+        self.close();
+        Ok(())
+    }
+    /// Adds a closed rectangle with its top-left corner at `origin`.
+    pub fn rect(
+        &mut self,
+        origin: Position2<f32>,
+        size: Extent2<f32>,
+    ) -> Result<(), NotEnoughSpace> {
+        let ([x, y], [w, h]) = (origin.dim, size.dim);
+        let corners = [[x, y], [x + w, y], [x + w, y + h], [x, y + h]];
+        self.polygon(&corners.map(Position2::new))
+    }
+    /// Adds a closed rectangle with corners rounded by `radius`.
+    ///
+    /// The radius is limited to half of the smallest side.
+    pub fn rounded_rect(
+        &mut self,
+        origin: Position2<f32>,
+        size: Extent2<f32>,
+        radius: f32,
+    ) -> Result<(), NotEnoughSpace> {
+        let ([x, y], [w, h]) = (origin.dim, size.dim);
+        let r = radius.min(w.abs() / 2.0).min(h.abs() / 2.0);
+        is![r.is_nan() || r <= 0.0, return self.rect(origin, size)];
+        let step = ArcStep::new(r, self.tolerance);
+        self.reserve_contour((step.quarter + 1) * 4)?;
+        // the center of each corner, in the order of the quadrants of a turn
+        let (x0, y0) = (x.min(x + w) + r, y.min(y + h) + r);
+        let (x1, y1) = (x.max(x + w) - r, y.max(y + h) - r);
+        let centers = [[x1, y1], [x0, y1], [x0, y0], [x1, y0]];
+        for (quadrant, c) in centers.into_iter().enumerate() {
+            step.for_quadrant(quadrant, |u| {
+                let point = [c[0] + u[0] * r, c[1] + u[1] * r];
+                is![self.len == self.contour, self.push(point, PATH_START), self.push_line(point)];
+            });
+        }
+        self.close();
+        Ok(())
+    }
+    /// Adds a closed ellipse with the given `center` and `radii`.
+    pub fn ellipse(
+        &mut self,
+        center: Position2<f32>,
+        radii: Extent2<f32>,
+    ) -> Result<(), NotEnoughSpace> {
+        let ([cx, cy], [rx, ry]) = (center.dim, radii.dim);
+        let step = ArcStep::new(rx.abs().max(ry.abs()), self.tolerance);
+        self.reserve_contour(step.turn_len())?;
+        let mut flag = PATH_START;
+        step.for_turn(|u| {
+            self.push([cx + u[0] * rx, cy + u[1] * ry], flag);
+            flag = 0;
+        });
+        self.close();
+        Ok(())
+    }
+    /// Adds a closed circle with the given `center` and `radius`.
+    pub fn circle(&mut self, center: Position2<f32>, radius: f32) -> Result<(), NotEnoughSpace> {
+        self.ellipse(center, Extent2::new([radius, radius]))
+    }
+
+    /* rasterization */
+
+    /// Returns an iterator over the coverage spans of the filled path, clipped to `grid`.
+    ///
+    /// `scratch` holds the coverage of one row,
+    /// and needs at least [`RasterFillIter::scratch_len`] elements.
+    ///
+    /// # Errors
+    /// Returns [`NotEnoughSpace`] if `scratch` is too short.
+    pub fn fill<'a>(
+        &'a self,
+        grid: RasterGrid,
+        rule: RasterFillRule,
+        scratch: &'a mut [f32],
+    ) -> Result<RasterFillIter<'a>, NotEnoughSpace> {
+        RasterFillIter::new(self.points(), &self.flags[..self.len], grid, rule, scratch)
+    }
+
+    /* helpers */
+
+    /// Returns the current point.
+    fn current(&self) -> Option<[f32; 2]> {
+        is![self.len == 0, return None];
+        is![self.flags[self.len - 1] & PATH_CLOSED != 0, Some(self.points[self.contour]), {
+            Some(self.points[self.len - 1])
+        }]
+    }
+    /// Returns the vertices and flags of each contour.
+    pub(super) fn contours(&self) -> impl Iterator<Item = (&[[f32; 2]], bool)> {
+        let flags = &self.flags[..self.len];
+        let mut start = 0;
+        core::iter::from_fn(move || {
+            is![start >= flags.len(), return None];
+            let len = flags[start + 1..].iter().position(|f| f & PATH_START != 0);
+            let end = len.map_or(flags.len(), |len| start + 1 + len);
+            let contour = (&self.points[start..end], flags[end - 1] & PATH_CLOSED != 0);
+            start = end;
+            Some(contour)
+        })
+    }
+    /// Makes sure there's room for `n` lines, and a new contour starting at `first`
+    /// if there's no current point.
+    fn reserve(&mut self, first: [f32; 2], n: usize) -> Result<(), NotEnoughSpace> {
+        let current = self.current();
+        let closed = self.len > 0 && self.flags[self.len - 1] & PATH_CLOSED != 0;
+        let needed = n + usize::from(current.is_none() || closed);
+        is![self.len + needed > N, return Err(NotEnoughSpace(Some(needed)))];
+        match current {
+            None => self.push(first, PATH_START),
+            Some(start) if closed => {
+                self.contour = self.len;
+                self.push(start, PATH_START);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    /// Makes sure there's room for a new contour of `n` vertices, and starts it.
+    fn reserve_contour(&mut self, n: usize) -> Result<(), NotEnoughSpace> {
+        is![self.len + n > N, return Err(NotEnoughSpace(Some(n)))];
+        self.contour = self.len;
+        Ok(())
+    }
+    /// Returns the number of segments that flatten a curve whose chord distance
+    /// for a single segment is `deviation`.
+    fn curve_segments(&self, deviation: f32) -> usize {
+        let n = Float(Float(deviation / self.tolerance).sqrt_hybrid().0).const_ceil().0;
+        is![n >= 1.0, (n as usize).min(Self::CURVE_SEGMENTS_MAX), 1]
+    }
+    /// Adds a vertex, whose capacity has been checked.
+    const fn push(&mut self, point: [f32; 2], flag: u8) {
+        self.points[self.len] = point;
+        self.flags[self.len] = flag;
+        self.len += 1;
+    }
+    /// Adds a line vertex, skipping repeated points.
+    fn push_line(&mut self, point: [f32; 2]) {
+        is![v_eq(point, self.points[self.len - 1]), return];
+        self.push(point, 0);
+    }
+}
@@ -0,0 +1,301 @@
+// devela/src/media/visual/image/raster/draw/stroke.rs
+//
+//! Defines [`RasterStroke`], [`RasterLineCap`], [`RasterLineJoin`].
+//
+// > Which polygons cover the band of a given width around a path?
+
+use super::{ArcStep, v_add, v_cross, v_dot, v_eq, v_len, v_perp, v_scale, v_sub};
+use crate::{NotEnoughSpace, Position2, RasterPath, is};
+
+#[doc = crate::_tags!(image geom)]
+/// The shape of the ends of open stroked contours.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster/draw")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RasterLineCap {
+    /// The stroke ends exactly at the end point.
+    #[default]
+    Butt,
+    /// The stroke ends with a half circle around the end point.
+    Round,
+    /// The stroke extends half its width beyond the end point.
+    Square,
+}
+
+#[doc = crate::_tags!(image geom)]
+/// The shape of the outer corners of stroked contours.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster/draw")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RasterLineJoin {
+    /// The outer edges are extended until they meet,
+    /// or beveled if that exceeds the [miter limit][RasterStroke::miter_limit].
+    #[default]
+    Miter,
+    /// The corner is rounded with an arc centered on the vertex.
+    Round,
+    /// The corner is cut by a line between the outer edges.
+    Bevel,
+}
+
+#[doc = crate::_tags!(image geom)]
+/// The geometry of a stroke: its width, caps, and joins.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster/draw")}]
+///
+/// See [`RasterPath::stroke`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RasterStroke {
+    /// The width of the stroke, centered on the path, in cells.
+    pub width: f32,
+    /// The shape of the ends of open contours.
+    pub cap: RasterLineCap,
+    /// The shape of the corners.
+    pub join: RasterLineJoin,
+    /// The maximum ratio of the length of a miter to half the width,
+    /// beyond which it's beveled.
+    pub miter_limit: f32,
+}
+
+impl Default for RasterStroke {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl RasterStroke {
+    /// Returns a stroke of the given `width` with butt caps, and miter joins
+    /// with a miter limit of 4.
+    pub const fn new(width: f32) -> Self {
+        Self {
+            width,
+            cap: RasterLineCap::Butt,
+            join: RasterLineJoin::Miter,
+            miter_limit: 4.0,
+        }
+    }
+    /// Returns the stroke with the given `cap`.
+    #[must_use]
+    pub const fn with_cap(mut self, cap: RasterLineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+    /// Returns the stroke with the given `join`.
+    #[must_use]
+    pub const fn with_join(mut self, join: RasterLineJoin) -> Self {
+        self.join = join;
+        self
+    }
+    /// Returns the stroke with the given `miter_limit`.
+    #[must_use]
+    pub const fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+}
+
+impl<const N: usize> RasterPath<N> {
+    /// Adds the outline of the stroke of this path to `out`.
+    ///
+    /// The outline is made of a polygon for each segment, join, and cap,
+    /// all with the same orientation, so that filling it with
+    /// [`RasterFillRule::NonZero`][crate::RasterFillRule::NonZero] covers their union.
+    /// Closed contours are joined at their start, and open ones are capped.
+    /// Contours of a single point are drawn as dots by round and square caps.
+    ///
+    /// Arcs are flattened with the tolerance of `out`.
+    ///
+    /// # Errors
+    /// Returns [`NotEnoughSpace`] if `out` can't hold the outline,
+    /// in which case it holds part of it.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{RasterFillRule, RasterGrid, RasterLineCap, RasterPath, RasterStroke, ext, pos};
+    /// let mut path = RasterPath::<8>::new();
+    /// path.move_to(pos![1.0, 2.0])?;
+    /// path.line_to(pos![7.0, 2.0])?;
+    ///
+    /// let mut outline = RasterPath::<256>::new();
+    /// path.stroke(&RasterStroke::new(2.0).with_cap(RasterLineCap::Square), &mut outline)?;
+    /// assert_eq![outline.bounds(), Some([pos![0.0, 1.0], pos![8.0, 3.0]])];
+    ///
+    /// let grid = RasterGrid::try_new(ext![8, 4]).unwrap();
+    /// let mut scratch = [0.0; 8 + 2];
+    /// let spans = outline.fill(grid, RasterFillRule::NonZero, &mut scratch)?;
+    /// assert![spans.map(|s| (s.start().dim[1], s.len(), s.coverage().get()))
+    ///     .eq([(1, 8, 255), (2, 8, 255)])];
+    /// # Ok::<(), devela::NotEnoughSpace>(())
+    /// ```
+    pub fn stroke<const M: usize>(
+        &self,
+        stroke: &RasterStroke,
+        out: &mut RasterPath<M>,
+    ) -> Result<(), NotEnoughSpace> {
+        let hw = stroke.width / 2.0;
+        is![hw.is_nan() || hw <= 0.0, return Ok(())];
+        let s = Stroker { stroke, hw, arc: ArcStep::new(hw, out.tolerance()) };
+        for (points, closed) in self.contours() {
+            // a closing vertex that repeats the first one is implicit
+            let n = match points {
+                [first, .., last] if closed && v_eq(*first, *last) => points.len() - 1,
+                _ => points.len(),
+            };
+            let points = &points[..n];
+            if points.len() == 1 {
+                s.dot(out, points[0])?;
+                continue;
+            }
+            let segments = is![closed && n > 2, n, n - 1];
+            for i in 0..segments {
+                s.segment(out, points[i], points[(i + 1) % n])?;
+            }
+            let (joins, first) = is![closed && n > 2, (n, 0), (n.saturating_sub(2), 1)];
+            for i in first..first + joins {
+                let (a, v, b) = (points[(i + n - 1) % n], points[i % n], points[(i + 1) % n]);
+                s.join(out, a, v, b)?;
+            }
+            if !(closed && n > 2) {
+                s.cap(out, points[0], points[1], stroke.cap)?;
+                s.cap(out, points[n - 1], points[n - 2], stroke.cap)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Emits the polygons of a stroke.
+struct Stroker<'s> {
+    stroke: &'s RasterStroke,
+    /// Half the width.
+    hw: f32,
+    arc: ArcStep,
+}
+impl Stroker<'_> {
+    /// Returns the normal of the segment from `a` to `b`, scaled to `hw`,
+    /// or `None` for a degenerate segment.
+    fn normal(&self, a: [f32; 2], b: [f32; 2]) -> Option<[f32; 2]> {
+        let d = v_sub(b, a);
+        let len = v_len(d);
+        is![len > 0.0, Some(v_scale(v_perp(d), self.hw / len)), None]
+    }
+    /// Emits the band of a segment.
+    fn segment<const M: usize>(
+        &self,
+        out: &mut RasterPath<M>,
+        a: [f32; 2],
+        b: [f32; 2],
+    ) -> Result<(), NotEnoughSpace> {
+        let Some(n) = self.normal(a, b) else { return Ok(()) };
+        polygon(out, [v_add(a, n), v_add(b, n), v_sub(b, n), v_sub(a, n)])
+    }
+    /// Emits the join at `v`, between the segments from `a` and towards `b`.
+    fn join<const M: usize>(
+        &self,
+        out: &mut RasterPath<M>,
+        a: [f32; 2],
+        v: [f32; 2],
+        b: [f32; 2],
+    ) -> Result<(), NotEnoughSpace> {
+        let (Some(n0), Some(n1)) = (self.normal(a, v), self.normal(v, b)) else {
+            return Ok(());
+        };
+        let turn = v_cross(v_sub(v, a), v_sub(b, v));
+        if turn == 0.0 {
+            // going straight on needs no join, and going back only a round one
+            let back = v_dot(n0, n1) < 0.0 && self.stroke.join == RasterLineJoin::Round;
+            return is![back, self.cap(out, v, a, RasterLineCap::Round), Ok(())];
+        }
+        // the outer side is opposite to the turn
+        let (o0, o1) = is![turn > 0.0, (v_scale(n0, -1.0), v_scale(n1, -1.0)), (n0, n1)];
+        match self.stroke.join {
+            RasterLineJoin::Round => self.wedge(out, v, o0, o1),
+            RasterLineJoin::Miter => {
+                // the miter length over hw is 1/cos(θ/2) = sqrt(2 / (1 + cos θ))
+                let cos = v_dot(o0, o1) / (self.hw * self.hw);
+                let limit = self.stroke.miter_limit;
+                if 1.0 + cos > 0.0 && 2.0 / (1.0 + cos) <= limit * limit {
+                    let m = v_scale(v_add(o0, o1), 1.0 / (1.0 + cos));
+                    polygon(out, [v, v_add(v, o0), v_add(v, m), v_add(v, o1)])
+                } else {
+                    polygon(out, [v, v_add(v, o0), v_add(v, o1), v])
+                }
+            }
+            RasterLineJoin::Bevel => polygon(out, [v, v_add(v, o0), v_add(v, o1), v]),
+        }
+    }
+    /// Emits a `cap` at the end `p` of the segment coming from `from`.
+    fn cap<const M: usize>(
+        &self,
+        out: &mut RasterPath<M>,
+        p: [f32; 2],
+        from: [f32; 2],
+        cap: RasterLineCap,
+    ) -> Result<(), NotEnoughSpace> {
+        let Some(n) = self.normal(from, p) else { return Ok(()) };
+        let m = v_perp(v_scale(n, -1.0)); // the outward direction, scaled to hw
+        match cap {
+            RasterLineCap::Butt => Ok(()),
+            RasterLineCap::Round => {
+                // two quarter wedges, so that each turns less than half
+                self.wedge(out, p, n, m)?;
+                self.wedge(out, p, m, v_scale(n, -1.0))
+            }
+            RasterLineCap::Square => {
+                let (a, b) = (v_add(p, n), v_sub(p, n));
+                polygon(out, [a, v_add(a, m), v_add(b, m), b])
+            }
+        }
+    }
+    /// Emits a dot at a single point, for round and square caps.
+    fn dot<const M: usize>(
+        &self,
+        out: &mut RasterPath<M>,
+        p: [f32; 2],
+    ) -> Result<(), NotEnoughSpace> {
+        let hw = self.hw;
+        match self.stroke.cap {
+            RasterLineCap::Butt => Ok(()),
+            RasterLineCap::Round => out.circle(Position2::new(p), hw),
+            RasterLineCap::Square => polygon(
+                out,
+                [
+                    [p[0] - hw, p[1] - hw],
+                    [p[0] + hw, p[1] - hw],
+                    [p[0] + hw, p[1] + hw],
+                    [p[0] - hw, p[1] + hw],
+                ],
+            ),
+        }
+    }
+    /// Emits the circular sector around `c` between the offsets `a` and `b`,
+    /// which are less than half a turn apart.
+    fn wedge<const M: usize>(
+        &self,
+        out: &mut RasterPath<M>,
+        c: [f32; 2],
+        a: [f32; 2],
+        b: [f32; 2],
+    ) -> Result<(), NotEnoughSpace> {
+        // rotate in the positive direction, from the earlier offset to the later one
+        let (a, b) = is![v_cross(a, b) >= 0.0, (a, b), (b, a)];
+        let steps = self.arc.steps_between(a, b);
+        out.move_to(Position2::new(c))?;
+        let mut v = a;
+        for _ in 0..=steps {
+            out.line_to(Position2::new(v_add(c, v)))?;
+            v = self.arc.rotate(v);
+        }
+        out.line_to(Position2::new(v_add(c, b)))?;
+        out.close();
+        Ok(())
+    }
+}
+
+/// Emits a quadrilateral with a positive orientation.
+fn polygon<const M: usize>(
+    out: &mut RasterPath<M>,
+    mut points: [[f32; 2]; 4],
+) -> Result<(), NotEnoughSpace> {
+    let area = (0..4).map(|i| v_cross(points[i], points[(i + 1) % 4])).sum::<f32>();
+    is![area == 0.0, return Ok(())];
+    is![area < 0.0, points.reverse()];
+    out.polygon(&points.map(Position2::new))
+}
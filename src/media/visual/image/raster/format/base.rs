@@ -146,6 +146,17 @@ impl RasterFormat {
     }
 }
 
+/// # Crate internals
+impl RasterFormat {
+    /// Returns the field indices of red, green, blue, and alpha
+    /// of an unpacked direct-color format.
+    ///
+    /// Gray fields are repeated for each color. Padding fields are omitted.
+    pub(crate) const fn rgba_fields(self) -> Option<([u8; 3], Option<u8>)> {
+        self.channels.rgba_fields()
+    }
+}
+
 /// Alpha-channel interpretation.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub(crate) enum RasterAlpha {
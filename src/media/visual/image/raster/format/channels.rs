@@ -156,6 +156,26 @@ impl RasterChannels {
         }
     }
 
+    /// Returns the field indices of red, green, blue, and alpha
+    /// of an unpacked direct-color channel model.
+    ///
+    /// Gray fields are repeated for each color. Padding fields are omitted.
+    pub const fn rgba_fields(self) -> Option<([u8; 3], Option<u8>)> {
+        match self {
+            Self::Gray => Some(([0, 0, 0], None)),
+            Self::GrayAlpha => Some(([0, 0, 0], Some(1))),
+            Self::Rgb | Self::Rgbx => Some(([0, 1, 2], None)),
+            Self::Rgba => Some(([0, 1, 2], Some(3))),
+            Self::Argb => Some(([1, 2, 3], Some(0))),
+            Self::Xrgb => Some(([1, 2, 3], None)),
+            Self::Bgr | Self::Bgrx => Some(([2, 1, 0], None)),
+            Self::Bgra => Some(([2, 1, 0], Some(3))),
+            Self::Abgr => Some(([3, 2, 1], Some(0))),
+            Self::Xbgr => Some(([3, 2, 1], None)),
+            Self::Unknown | Self::Indexed | Self::Packed(_) => None,
+        }
+    }
+
     /// Returns whether this channel model stores palette indices.
     pub const fn is_indexed(self) -> bool {
         matches!(self, Self::Indexed)
@@ -36,6 +36,19 @@ impl RasterFormat {
     pub const GRAYA8: Self =
         Self::_new(Channels::GrayAlpha, Sample::U8, Transfer::Srgb, Alpha::Straight);
 
+    /* 16-bit */
+
+    /// 16-bit grayscale in sRGB space, with native-endian samples.
+    pub const GRAY16: Self = Self::_new(Channels::Gray, Sample::U16, Transfer::Srgb, Alpha::None);
+    /// 16-bit grayscale with straight alpha in sRGB space, with native-endian samples.
+    pub const GRAYA16: Self =
+        Self::_new(Channels::GrayAlpha, Sample::U16, Transfer::Srgb, Alpha::Straight);
+    /// 48-bit RGB in sRGB space, with native-endian 16-bit samples.
+    pub const RGB16: Self = Self::_new(Channels::Rgb, Sample::U16, Transfer::Srgb, Alpha::None);
+    /// 64-bit RGBA with straight alpha in sRGB space, with native-endian 16-bit samples.
+    pub const RGBA16: Self =
+        Self::_new(Channels::Rgba, Sample::U16, Transfer::Srgb, Alpha::Straight);
+
     /* byte-interleaved rgb */
 
     /// 24-bit RGB in sRGB space.
@@ -7,7 +7,7 @@ use crate::_impl_init;
 
 /// Primitive representation of raster samples.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-#[allow(dead_code, reason = "Not exposed: U16, U32, F64")]
+#[allow(dead_code, reason = "Not exposed: U32, F64")]
 pub(crate) enum RasterSampleFormat {
     /// Unknown or unspecified sample representation.
     #[default]
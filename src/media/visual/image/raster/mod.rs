@@ -17,7 +17,7 @@ mod format; // Raster sample and color-format semantics
 pub mod grid; // Logical raster-cell geometry and traversal
 mod layout; // Physical raster byte-storage layout
 // mod macros; // TODO Raster type generators
-// mod ops; // TODO Raster storage and image operations
+mod ops; // Raster compositing and image operations
 mod traits; // Typed and byte raster access contracts
 
 crate::structural_mods! { // _mods, _pub_mods, _crate_internals
@@ -30,7 +30,7 @@ crate::structural_mods! { // _mods, _pub_mods, _crate_internals
             format::RasterFormat,
             layout::RasterLayout,
             // macros::raster,
-            // ops::_all::*,
+            ops::_all::*,
             traits::{
                 RasterView, RasterBuf, Raster,
                 RasterViewBytes, RasterBufBytes,
@@ -0,0 +1,67 @@
+// devela/src/media/visual/image/raster/ops/_helper.rs
+//
+//! Helpers shared by the raster operations.
+//
+
+use crate::{Extent2, ImageError, ImageResult, Position2, is};
+
+/// Fails unless the extent `have` is the extent `want`.
+pub(super) fn check_extent(have: Extent2<u32>, want: Extent2<u32>) -> ImageResult<()> {
+    is![have == want, Ok(()), Err(invalid_size(want))]
+}
+/// Returns an invalid image size error carrying the expected `extent`.
+pub(super) const fn invalid_size(extent: Extent2<u32>) -> ImageError {
+    let [w, h] = extent.dim;
+    ImageError::InvalidImageSize(Some((w as usize, h as usize)))
+}
+
+/// The overlap of a source extent placed at some position over a destination extent.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Overlap {
+    /// The first covered column and row of the source.
+    pub(super) src: [usize; 2],
+    /// The first covered column and row of the destination.
+    pub(super) dst: [usize; 2],
+    /// The width and height of the overlap.
+    pub(super) len: [usize; 2],
+}
+impl Overlap {
+    /// Returns the overlap of `src` placed at `at` within `dst`, or `None` if they don't overlap.
+    pub(super) fn new(dst: Extent2<u32>, at: Position2<i32>, src: Extent2<u32>) -> Option<Self> {
+        let (mut o, mut empty) = (Self { src: [0; 2], dst: [0; 2], len: [0; 2] }, false);
+        for i in 0..2 {
+            let at = i64::from(at.dim[i]);
+            let start = at.max(0);
+            let end = (at + i64::from(src.dim[i])).min(i64::from(dst.dim[i]));
+            empty |= start >= end;
+            o.src[i] = (start - at) as usize;
+            o.dst[i] = start as usize;
+            o.len[i] = (end - start).max(0) as usize;
+        }
+        is![empty, None, Some(o)]
+    }
+    #[cfg(feature = "color")]
+    /// Returns the source and destination sample indices of each covered cell, row by row.
+    pub(super) fn indices(
+        self,
+        src_width: usize,
+        dst_width: usize,
+    ) -> impl Iterator<Item = [usize; 2]> {
+        (0..self.len[1]).flat_map(move |y| {
+            let s = (self.src[1] + y) * src_width + self.src[0];
+            let d = (self.dst[1] + y) * dst_width + self.dst[0];
+            (0..self.len[0]).map(move |x| [s + x, d + x])
+        })
+    }
+}
+
+#[cfg(feature = "color")]
+/// Returns `a` interpolated towards `b` by `t`, channel by channel.
+pub(super) const fn lerp4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
@@ -0,0 +1,294 @@
+// devela/src/media/visual/image/raster/ops/_test.rs
+
+use crate::{Extent2, ImageError, RasterFormat, RasterOps, RasterSlice, pos};
+
+const fn gray(w: u32, h: u32) -> (RasterFormat, Extent2<u32>) {
+    (RasterFormat::GRAY8, Extent2::new([w, h]))
+}
+
+#[cfg(feature = "color")]
+mod compose {
+    use super::*;
+    use crate::{Coverage8, RasterBlend, RasterCompose, RasterSpan, RgbaPre8};
+
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+    const HALF_BLUE: [f32; 4] = [0.0, 0.0, 0.5, 0.5];
+
+    fn px(c: [u8; 4]) -> RgbaPre8 {
+        RgbaPre8 { c }
+    }
+    fn quantize(c: [f32; 4]) -> [u8; 4] {
+        c.map(|v| (v * 255.0 + 0.5) as u8)
+    }
+
+    #[test]
+    fn operators() {
+        let q = |op: RasterCompose| quantize(op.compose(HALF_BLUE, RED));
+        assert_eq![q(RasterCompose::Clear), [0, 0, 0, 0]];
+        assert_eq![q(RasterCompose::Src), [0, 0, 128, 128]];
+        assert_eq![q(RasterCompose::Dst), [255, 0, 0, 255]];
+        assert_eq![q(RasterCompose::SrcOver), [128, 0, 128, 255]];
+        assert_eq![q(RasterCompose::DstOver), [255, 0, 0, 255]];
+        assert_eq![q(RasterCompose::SrcIn), [0, 0, 128, 128]];
+        assert_eq![q(RasterCompose::DstIn), [128, 0, 0, 128]];
+        assert_eq![q(RasterCompose::SrcOut), [0, 0, 0, 0]];
+        assert_eq![q(RasterCompose::DstOut), [128, 0, 0, 128]];
+        assert_eq![q(RasterCompose::SrcAtop), [128, 0, 128, 255]];
+        assert_eq![RasterCompose::default(), RasterCompose::SrcOver];
+    }
+    #[test]
+    fn blend_modes() {
+        let (a, b) = ([0.25, 0.5, 1.0, 1.0], [0.5, 0.5, 0.0, 1.0]);
+        let q = |mode: RasterBlend| quantize(mode.blend(a, b));
+        assert_eq![q(RasterBlend::Normal), quantize(a)];
+        assert_eq![q(RasterBlend::Multiply), [32, 64, 0, 255]];
+        assert_eq![q(RasterBlend::Screen), [159, 191, 255, 255]];
+        assert_eq![q(RasterBlend::Darken), [64, 128, 0, 255]];
+        assert_eq![q(RasterBlend::Lighten), [128, 128, 255, 255]];
+        assert_eq![q(RasterBlend::Difference), [64, 0, 255, 255]];
+        // blending into a transparent destination keeps the source
+        let s = RasterBlend::Multiply.blend(a, [0.0; 4]);
+        assert_eq![quantize(s), quantize(a)];
+    }
+
+    #[test]
+    fn clips_and_masks() {
+        let mut pixels = [px([0, 0, 0, 0]); 4];
+        let (f, e) = (RasterFormat::RGBA_PRE8, Extent2::new([2, 2]));
+        let mut dst = RasterSlice::dense_mut(f, e, &mut pixels).unwrap();
+        let white = [px([255, 255, 255, 255]); 4];
+        let src = RasterSlice::dense(f, e, &white).unwrap();
+        let mask = [Coverage8::FULL, Coverage8::new(128), Coverage8::ZERO, Coverage8::FULL];
+        RasterOps::compose(&mut dst, pos![-1, -1], &src, Some(&mask), RasterCompose::Src).unwrap();
+        assert_eq![pixels.map(|p| p.c[3]), [255, 0, 0, 0]];
+
+        let mut dst = RasterSlice::dense_mut(f, e, &mut pixels).unwrap();
+        RasterOps::compose(&mut dst, pos![0, 0], &src, Some(&mask), RasterCompose::Src).unwrap();
+        assert_eq![pixels.map(|p| p.c[3]), [255, 128, 0, 255]];
+
+        let mut dst = RasterSlice::dense_mut(f, e, &mut pixels).unwrap();
+        let err =
+            RasterOps::blend(&mut dst, pos![0, 0], &src, Some(&mask[..3]), RasterBlend::Normal);
+        assert_eq![err, Err(ImageError::InvalidImageSize(Some((2, 2))))];
+        assert![RasterOps::compose(&mut dst, pos![5, 0], &src, None, RasterCompose::Clear).is_ok()];
+    }
+    #[test]
+    fn spans() {
+        let mut pixels = [px([0, 0, 0, 255]); 4];
+        let mut dst =
+            RasterSlice::dense_mut(RasterFormat::RGBA_PRE8, Extent2::new([4, 1]), &mut pixels)
+                .unwrap();
+        let spans = [
+            RasterSpan::new(pos![1, 0], 8, Coverage8::new(128)),
+            RasterSpan::new(pos![0, 3], 2, Coverage8::FULL),
+        ];
+        RasterOps::compose_spans(&mut dst, spans, px([255, 255, 255, 255]), RasterCompose::SrcOver);
+        assert_eq![pixels.map(|p| p.c[0]), [0, 128, 128, 128]];
+    }
+}
+
+mod blit {
+    use super::*;
+    use crate::{Boundary1d, RasterByteSlice, RasterLayout};
+
+    #[test]
+    fn same_format_copies_rows() {
+        let src = RasterByteSlice::dense(RasterFormat::GRAY8, Extent2::new([2, 2]), &[1, 2, 3, 4])
+            .unwrap();
+        let mut bytes = [0; 9];
+        let mut dst =
+            RasterByteSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([3, 3]), &mut bytes)
+                .unwrap();
+        RasterOps::blit(&src, &mut dst, pos![2, -1]).unwrap();
+        assert_eq![bytes, [0, 0, 3, 0, 0, 0, 0, 0, 0]];
+    }
+    #[test]
+    fn converts_to_gray_luma() {
+        let rgb = [255, 0, 0, 0, 255, 0, 255, 255, 255];
+        let src = RasterByteSlice::dense(RasterFormat::RGB8, Extent2::new([3, 1]), &rgb).unwrap();
+        let mut bytes = [0; 3];
+        let mut dst =
+            RasterByteSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([3, 1]), &mut bytes)
+                .unwrap();
+        RasterOps::blit(&src, &mut dst, pos![0, 0]).unwrap();
+        assert_eq![bytes, [54, 182, 255]];
+    }
+    #[test]
+    fn widens_samples_and_honors_row_order() {
+        let src =
+            RasterByteSlice::dense(RasterFormat::GRAY8, Extent2::new([1, 2]), &[255, 0]).unwrap();
+        let layout = RasterLayout::interleaved(Extent2::new([1, 2]), 2, 4, Boundary1d::Lower);
+        let mut bytes = [9; 8];
+        let mut dst = RasterByteSlice::new_mut(RasterFormat::GRAY16, layout, &mut bytes).unwrap();
+        RasterOps::blit(&src, &mut dst, pos![0, 0]).unwrap();
+        // the bottom row is stored first, and padding is left untouched
+        assert_eq![bytes, [0, 0, 9, 9, 255, 255, 9, 9]];
+    }
+    #[test]
+    fn premultiplies_and_reorders() {
+        let rgba = [200, 100, 0, 128];
+        let src = RasterByteSlice::dense(RasterFormat::RGBA8, Extent2::new([1, 1]), &rgba).unwrap();
+        let mut bytes = [0; 4];
+        let mut dst =
+            RasterByteSlice::dense_mut(RasterFormat::BGRA_PRE8, Extent2::new([1, 1]), &mut bytes)
+                .unwrap();
+        RasterOps::blit(&src, &mut dst, pos![0, 0]).unwrap();
+        assert_eq![bytes, [0, 50, 100, 128]];
+        // and back
+        let src =
+            RasterByteSlice::dense(RasterFormat::BGRA_PRE8, Extent2::new([1, 1]), &bytes).unwrap();
+        let mut back = [0; 4];
+        let mut dst =
+            RasterByteSlice::dense_mut(RasterFormat::RGBA8, Extent2::new([1, 1]), &mut back)
+                .unwrap();
+        RasterOps::blit(&src, &mut dst, pos![0, 0]).unwrap();
+        assert_eq![back, [199, 100, 0, 128]];
+    }
+    #[test]
+    fn rejects_unsupported_formats() {
+        let src =
+            RasterByteSlice::dense(RasterFormat::INDEXED8, Extent2::new([1, 1]), &[0]).unwrap();
+        let mut bytes = [0; 1];
+        let mut dst =
+            RasterByteSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([1, 1]), &mut bytes)
+                .unwrap();
+        assert_eq![RasterOps::blit(&src, &mut dst, pos![0, 0]), Err(ImageError::UnsupportedFormat)];
+    }
+}
+
+mod resample {
+    use super::*;
+    use crate::RasterFilter;
+
+    #[test]
+    fn filters() {
+        assert_eq![RasterFilter::default(), RasterFilter::Bilinear];
+        assert_eq![RasterFilter::Nearest.weight(0.6).to_bits(), 0.0_f32.to_bits()];
+        assert_eq![RasterFilter::Lanczos3.weight(0.0).to_bits(), 1.0_f32.to_bits()];
+        assert![RasterFilter::Lanczos3.weight(1.0).abs() < 1e-6];
+        assert![RasterFilter::Lanczos3.weight(1.5) < 0.0];
+        assert_eq![RasterFilter::Lanczos3.weight(3.0).to_bits(), 0.0_f32.to_bits()];
+    }
+    #[test]
+    fn nearest_upscales() {
+        let (f, e) = gray(2, 1);
+        let src = RasterSlice::dense(f, e, &[10_u8, 20]).unwrap();
+        let mut samples = [0_u8; 8];
+        let mut dst = RasterSlice::dense_mut(f, Extent2::new([4, 2]), &mut samples).unwrap();
+        RasterOps::resample(&src, &mut dst, RasterFilter::Nearest);
+        assert_eq![samples, [10, 10, 20, 20, 10, 10, 20, 20]];
+    }
+    #[test]
+    fn preserves_constants() {
+        let (f, e) = gray(5, 3);
+        let src = RasterSlice::dense(f, e, &[77_u8; 15]).unwrap();
+        for (w, h) in [(9, 7), (2, 1), (5, 3)] {
+            let mut samples = [0_u8; 63];
+            let mut dst =
+                RasterSlice::dense_mut(f, Extent2::new([w, h]), &mut samples[..(w * h) as usize])
+                    .unwrap();
+            RasterOps::resample(&src, &mut dst, RasterFilter::Lanczos3);
+            assert![samples[..(w * h) as usize].iter().all(|&s| s == 77)];
+        }
+    }
+    #[test]
+    fn downscale_averages() {
+        let (f, e) = gray(4, 1);
+        let src = RasterSlice::dense(f, e, &[0_u8, 0, 200, 200]).unwrap();
+        let mut samples = [0_u8; 2];
+        let mut dst = RasterSlice::dense_mut(f, Extent2::new([2, 1]), &mut samples).unwrap();
+        // the triangle widens to span 2 source samples on each side
+        RasterOps::resample(&src, &mut dst, RasterFilter::Bilinear);
+        assert_eq![samples, [25, 175]];
+    }
+}
+
+mod transform {
+    use super::*;
+
+    const SAMPLES: [u8; 6] = [1, 2, 3, 4, 5, 6];
+
+    #[test]
+    fn crop() {
+        let (f, e) = gray(3, 2);
+        let src = RasterSlice::dense(f, e, &SAMPLES).unwrap();
+        let mut samples = [0_u8; 2];
+        let mut dst = RasterSlice::dense_mut(f, Extent2::new([2, 1]), &mut samples).unwrap();
+        RasterOps::crop(&src, pos![1, 1], &mut dst).unwrap();
+        assert_eq![samples, [5, 6]];
+        let mut dst = RasterSlice::dense_mut(f, Extent2::new([2, 1]), &mut samples).unwrap();
+        let err = RasterOps::crop(&src, pos![2, 0], &mut dst);
+        assert_eq![err, Err(ImageError::InvalidImageSize(Some((2, 1))))];
+    }
+    #[test]
+    fn flips() {
+        let (f, e) = gray(3, 2);
+        let mut samples = SAMPLES;
+        let mut raster = RasterSlice::dense_mut(f, e, &mut samples).unwrap();
+        RasterOps::flip_x(&mut raster);
+        assert_eq![raster.samples(), [3, 2, 1, 6, 5, 4]];
+        RasterOps::flip_y(&mut raster);
+        assert_eq![raster.samples(), [6, 5, 4, 3, 2, 1]];
+    }
+    #[test]
+    fn rotations() {
+        let (f, e) = gray(3, 2);
+        let src = RasterSlice::dense(f, e, &SAMPLES).unwrap();
+        let rotated = |turns, extent| {
+            let mut samples = [0_u8; 6];
+            let mut dst = RasterSlice::dense_mut(f, extent, &mut samples).unwrap();
+            RasterOps::rotate(&src, &mut dst, turns).map(|()| samples)
+        };
+        let (wide, tall) = (Extent2::new([3, 2]), Extent2::new([2, 3]));
+        assert_eq![rotated(0, wide), Ok(SAMPLES)];
+        assert_eq![rotated(1, tall), Ok([4, 1, 5, 2, 6, 3])];
+        assert_eq![rotated(2, wide), Ok([6, 5, 4, 3, 2, 1])];
+        assert_eq![rotated(3, tall), Ok([3, 6, 2, 5, 1, 4])];
+        assert_eq![rotated(4, wide), Ok(SAMPLES)];
+        assert_eq![rotated(1, wide), Err(ImageError::InvalidImageSize(Some((2, 3))))];
+    }
+}
+
+mod blur {
+    use super::*;
+    use crate::NotEnoughSpace;
+
+    #[test]
+    fn scratch() {
+        let (f, e) = gray(3, 5);
+        let mut samples = [0_u8; 15];
+        let mut raster = RasterSlice::dense_mut(f, e, &mut samples).unwrap();
+        assert_eq![RasterOps::blur_scratch_len(&raster), 5];
+        let err = RasterOps::box_blur(&mut raster, 1, &mut [0; 4]);
+        assert_eq![err, Err(NotEnoughSpace(Some(5)))];
+        let err = RasterOps::gaussian_blur(&mut raster, 1.0, &mut [0; 4]);
+        assert_eq![err, Err(NotEnoughSpace(Some(5)))];
+    }
+    #[test]
+    fn preserves_constants() {
+        let (f, e) = gray(7, 4);
+        let mut samples = [123_u8; 28];
+        let mut raster = RasterSlice::dense_mut(f, e, &mut samples).unwrap();
+        let mut scratch = [0; 7];
+        RasterOps::box_blur(&mut raster, 3, &mut scratch).unwrap();
+        RasterOps::gaussian_blur(&mut raster, 1.5, &mut scratch).unwrap();
+        RasterOps::gaussian_blur(&mut raster, 40.0, &mut scratch).unwrap();
+        assert![samples.iter().all(|&s| s == 123)];
+    }
+    #[test]
+    fn gaussian_is_symmetric() {
+        let (f, e) = gray(9, 9);
+        for sigma in [1.0, 20.0] {
+            let mut samples = [0_u8; 81];
+            samples[40] = 255;
+            let mut raster = RasterSlice::dense_mut(f, e, &mut samples).unwrap();
+            RasterOps::gaussian_blur(&mut raster, sigma, &mut [0; 9]).unwrap();
+            let at = |x: usize, y: usize| samples[y * 9 + x];
+            assert![at(4, 4) < 255];
+            assert![at(4, 4) >= at(5, 4) && at(5, 4) >= at(6, 4)];
+            for (x, y) in [(3, 4), (5, 4), (4, 3), (4, 5)] {
+                assert_eq![at(x, y), at(5, 4)];
+            }
+        }
+    }
+}
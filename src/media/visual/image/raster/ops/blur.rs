@@ -0,0 +1,175 @@
+// devela/src/media/visual/image/raster/ops/blur.rs
+//
+//! Defines blurring [`RasterOps`].
+//
+// > How does each sample average its neighbors, one axis at a time?
+
+use crate::{Float, NotEnoughSpace, RasterBuf, RasterOps, RasterPixel, is};
+
+/// # Blurring
+impl RasterOps {
+    /// The largest radius of a Gaussian blur computed with its exact kernel.
+    pub const GAUSSIAN_RADIUS_MAX: usize = 32;
+
+    /// Returns the number of scratch samples needed to blur a `raster`.
+    #[must_use]
+    pub fn blur_scratch_len<B: RasterBuf + ?Sized>(raster: &B) -> usize {
+        let [w, h] = raster.raster_extent().dim;
+        w.max(h) as usize
+    }
+
+    /// Blurs the raster in place, averaging each sample with its neighbors
+    /// up to `radius` samples away, first horizontally and then vertically.
+    ///
+    /// Samples beyond the edges repeat the closest edge samples.
+    /// Work is proportional to the samples, whatever the radius.
+    ///
+    /// # Errors
+    /// Returns [`NotEnoughSpace`] if the `scratch` is shorter than
+    /// [`blur_scratch_len`][Self::blur_scratch_len].
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Extent2, RasterFormat, RasterOps, RasterSlice};
+    /// let mut samples = [0_u8, 0, 90, 0, 0];
+    /// let mut raster = RasterSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([5, 1]), &mut samples)
+    ///     .unwrap();
+    /// RasterOps::box_blur(&mut raster, 1, &mut [0; 5])?;
+    /// assert_eq![samples, [0, 30, 30, 30, 0]];
+    /// # Ok::<(), devela::NotEnoughSpace>(())
+    /// ```
+    pub fn box_blur<P, B>(
+        raster: &mut B,
+        radius: usize,
+        scratch: &mut [P],
+    ) -> Result<(), NotEnoughSpace>
+    where
+        P: RasterPixel,
+        B: RasterBuf<Sample = P> + ?Sized,
+    {
+        let needed = Self::blur_scratch_len(raster);
+        is![scratch.len() < needed, return Err(NotEnoughSpace(Some(needed)))];
+        is![radius == 0, return Ok(())];
+        box_passes(raster, radius, scratch);
+        Ok(())
+    }
+
+    /// Blurs the raster in place with a Gaussian kernel of standard deviation `sigma`,
+    /// first horizontally and then vertically.
+    ///
+    /// Samples beyond the edges repeat the closest edge samples.
+    ///
+    /// The kernel spans 3 `sigma` on each side. Up to a radius of
+    /// [`GAUSSIAN_RADIUS_MAX`][Self::GAUSSIAN_RADIUS_MAX] it's computed exactly,
+    /// and beyond it's approximated by three successive box blurs, as SVG does,
+    /// which keeps work proportional to the samples.
+    ///
+    /// # Errors
+    /// Returns [`NotEnoughSpace`] if the `scratch` is shorter than
+    /// [`blur_scratch_len`][Self::blur_scratch_len].
+    pub fn gaussian_blur<P, B>(
+        raster: &mut B,
+        sigma: f32,
+        scratch: &mut [P],
+    ) -> Result<(), NotEnoughSpace>
+    where
+        P: RasterPixel,
+        B: RasterBuf<Sample = P> + ?Sized,
+    {
+        let needed = Self::blur_scratch_len(raster);
+        is![scratch.len() < needed, return Err(NotEnoughSpace(Some(needed)))];
+        is![sigma.is_nan() || sigma <= 0.0, return Ok(())];
+        let radius = Float(3.0 * sigma).const_ceil().0 as usize;
+        if radius > Self::GAUSSIAN_RADIUS_MAX {
+            // the box size whose three passes have about the same variance
+            const K: f32 = 3.0 * 2.506_628_3 / 4.0; // 3·√(2π)/4
+            let size = Float(sigma * K + 0.5).const_floor().0 as usize;
+            for _ in 0..3 {
+                box_passes(raster, size / 2, scratch);
+            }
+            return Ok(());
+        }
+        let mut weights = [0.0; Self::GAUSSIAN_RADIUS_MAX + 1];
+        let mut total = 0.0;
+        for (k, w) in weights[..=radius].iter_mut().enumerate() {
+            let x = Float(-((k * k) as f32) / (2.0 * sigma * sigma));
+            *w = x.exp_series(x.exp_series_terms()).0;
+            total += is![k == 0, *w, 2.0 * *w];
+        }
+        let weights = &mut weights[..=radius];
+        for w in weights.iter_mut() {
+            *w /= total;
+        }
+        separable(raster, scratch, |line, i| {
+            let (mut sum, len) = ([0.0; 4], line.len());
+            for (k, &w) in weights.iter().enumerate() {
+                let k = k as isize;
+                add(&mut sum, line[clamped(i, k, len)].to_channels(), w);
+                is![k > 0, add(&mut sum, line[clamped(i, -k, len)].to_channels(), w)];
+            }
+            sum
+        });
+        Ok(())
+    }
+}
+
+/// Applies a horizontal and a vertical box pass of some `radius`.
+fn box_passes<P: RasterPixel, B: RasterBuf<Sample = P> + ?Sized>(
+    raster: &mut B,
+    radius: usize,
+    scratch: &mut [P],
+) {
+    is![radius == 0, return];
+    let (r, n) = (radius as isize, (2 * radius + 1) as f32);
+    let mut sum = [0.0; 4];
+    separable(raster, scratch, |line, i| {
+        let len = line.len();
+        if i == 0 {
+            sum = [0.0; 4];
+            for k in -r..=r {
+                add(&mut sum, line[clamped(0, k, len)].to_channels(), 1.0);
+            }
+        } else {
+            add(&mut sum, line[clamped(i, r, len)].to_channels(), 1.0);
+            add(&mut sum, line[clamped(i, -r - 1, len)].to_channels(), -1.0);
+        }
+        sum.map(|s| s / n)
+    });
+}
+
+/// Replaces each row, and then each column, with `f` of each of its positions,
+/// given a copy of the original line.
+fn separable<P: RasterPixel, B: RasterBuf<Sample = P> + ?Sized>(
+    raster: &mut B,
+    scratch: &mut [P],
+    mut f: impl FnMut(&[P], usize) -> [f32; 4],
+) {
+    let [w, h] = raster.raster_extent().dim.map(|n| n as usize);
+    let samples = raster.raster_samples_mut();
+    is![w == 0 || h == 0, return];
+    for row in samples.chunks_exact_mut(w) {
+        scratch[..w].copy_from_slice(row);
+        for (x, sample) in row.iter_mut().enumerate() {
+            *sample = P::from_channels(f(&scratch[..w], x));
+        }
+    }
+    for x in 0..w {
+        for (y, s) in scratch[..h].iter_mut().enumerate() {
+            *s = samples[y * w + x];
+        }
+        for y in 0..h {
+            samples[y * w + x] = P::from_channels(f(&scratch[..h], y));
+        }
+    }
+}
+
+/// Returns the index `i + offset` clamped to a line of `len`.
+fn clamped(i: usize, offset: isize, len: usize) -> usize {
+    (i as isize + offset).clamp(0, len as isize - 1) as usize
+}
+/// Adds `c` scaled by `w` to `sum`.
+fn add(sum: &mut [f32; 4], c: [f32; 4], w: f32) {
+    for (s, c) in sum.iter_mut().zip(c) {
+        *s += w * c;
+    }
+}
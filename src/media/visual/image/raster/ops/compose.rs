@@ -0,0 +1,339 @@
+// devela/src/media/visual/image/raster/ops/compose.rs
+//
+//! Defines [`RasterCompose`], [`RasterBlend`], and compositing [`RasterOps`].
+//
+// > How does a premultiplied source combine with the destination below it?
+
+use crate::{Float, is};
+#[cfg(feature = "color")]
+use {
+    super::{Overlap, invalid_size, lerp4},
+    crate::{Coverage8, ImageResult, Position2, RasterBuf, RasterOps, RasterPixel},
+    crate::{RasterSpan, RasterView, Rgba},
+};
+
+#[doc = crate::_tags!(image)]
+/// A Porter–Duff compositing operator.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster")}]
+///
+/// Each operator combines premultiplied source and destination samples as
+/// `src · Fa + dst · Fb`, with factors that depend on their alphas `αs` and `αd`.
+///
+/// | Operator | Fa | Fb |
+/// | --- | --- | --- |
+/// | [`Clear`][Self::Clear] | 0 | 0 |
+/// | [`Src`][Self::Src] | 1 | 0 |
+/// | [`Dst`][Self::Dst] | 0 | 1 |
+/// | [`SrcOver`][Self::SrcOver] | 1 | 1 − αs |
+/// | [`DstOver`][Self::DstOver] | 1 − αd | 1 |
+/// | [`SrcIn`][Self::SrcIn] | αd | 0 |
+/// | [`DstIn`][Self::DstIn] | 0 | αs |
+/// | [`SrcOut`][Self::SrcOut] | 1 − αd | 0 |
+/// | [`DstOut`][Self::DstOut] | 0 | 1 − αs |
+/// | [`SrcAtop`][Self::SrcAtop] | αd | 1 − αs |
+/// | [`DstAtop`][Self::DstAtop] | 1 − αd | αs |
+/// | [`Xor`][Self::Xor] | 1 − αd | 1 − αs |
+/// | [`Plus`][Self::Plus] | 1 | 1 |
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RasterCompose {
+    /// Neither the source nor the destination.
+    Clear,
+    /// The source, replacing the destination.
+    Src,
+    /// The destination, ignoring the source.
+    Dst,
+    /// The source over the destination.
+    #[default]
+    SrcOver,
+    /// The destination over the source.
+    DstOver,
+    /// The source where the destination is.
+    SrcIn,
+    /// The destination where the source is.
+    DstIn,
+    /// The source where the destination is not.
+    SrcOut,
+    /// The destination where the source is not.
+    DstOut,
+    /// The source where the destination is, over the destination.
+    SrcAtop,
+    /// The destination where the source is, over the source.
+    DstAtop,
+    /// The source and the destination where they don't overlap.
+    Xor,
+    /// The sum of the source and the destination, saturated.
+    Plus,
+}
+
+impl RasterCompose {
+    /// Returns the premultiplied composition of `src` over `dst`.
+    ///
+    /// Both have their alpha as the 4th channel.
+    /// The result is clamped to `0.0..=1.0`.
+    pub const fn compose(self, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let (sa, da) = (src[3], dst[3]);
+        let (fa, fb) = match self {
+            Self::Clear => (0.0, 0.0),
+            Self::Src => (1.0, 0.0),
+            Self::Dst => (0.0, 1.0),
+            Self::SrcOver => (1.0, 1.0 - sa),
+            Self::DstOver => (1.0 - da, 1.0),
+            Self::SrcIn => (da, 0.0),
+            Self::DstIn => (0.0, sa),
+            Self::SrcOut => (1.0 - da, 0.0),
+            Self::DstOut => (0.0, 1.0 - sa),
+            Self::SrcAtop => (da, 1.0 - sa),
+            Self::DstAtop => (1.0 - da, sa),
+            Self::Xor => (1.0 - da, 1.0 - sa),
+            Self::Plus => (1.0, 1.0),
+        };
+        let mut out = [0.0; 4];
+        let mut i = 0;
+        while i < 4 {
+            out[i] = Float(src[i] * fa + dst[i] * fb).clamp(0.0, 1.0).0;
+            i += 1;
+        }
+        out
+    }
+}
+
+#[doc = crate::_tags!(image)]
+/// A separable blend mode, for compositing a source over a destination.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster")}]
+///
+/// Where both are opaque, the result is the blend function `B(dst, src)` of their
+/// straight colors. Elsewhere, it's mixed with each of them as with
+/// [`RasterCompose::SrcOver`], following the W3C *Compositing and Blending* model:
+/// ```text
+/// co = cs · (1 − αd) + cd · (1 − αs) + αs · αd · B(Cd, Cs)
+/// αo = αs + αd − αs · αd
+/// ```
+///
+/// Blending happens in the color space of the samples,
+/// so most modes give the expected results over sRGB-encoded colors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RasterBlend {
+    /// The source color.
+    #[default]
+    Normal,
+    /// The product of the colors, which darkens.
+    Multiply,
+    /// The complement of the product of the complements, which lightens.
+    Screen,
+    /// [`Multiply`][Self::Multiply] or [`Screen`][Self::Screen],
+    /// depending on the destination.
+    Overlay,
+    /// The darkest color.
+    Darken,
+    /// The lightest color.
+    Lighten,
+    /// Brightens the destination to reflect the source.
+    ColorDodge,
+    /// Darkens the destination to reflect the source.
+    ColorBurn,
+    /// [`Multiply`][Self::Multiply] or [`Screen`][Self::Screen], depending on the source.
+    HardLight,
+    /// A softer version of [`HardLight`][Self::HardLight].
+    SoftLight,
+    /// The absolute difference of the colors.
+    Difference,
+    /// Like [`Difference`][Self::Difference], with lower contrast.
+    Exclusion,
+}
+
+impl RasterBlend {
+    /// Returns the premultiplied blend of `src` over `dst`.
+    ///
+    /// Both have their alpha as the 4th channel.
+    pub const fn blend(self, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let (sa, da) = (src[3], dst[3]);
+        let mut out = [0.0; 4];
+        let mut i = 0;
+        while i < 3 {
+            let cs = is![sa > 0.0, src[i] / sa, 0.0];
+            let cd = is![da > 0.0, dst[i] / da, 0.0];
+            let mixed = sa * da * self.function(cd, cs);
+            out[i] = Float(src[i] * (1.0 - da) + dst[i] * (1.0 - sa) + mixed).clamp(0.0, 1.0).0;
+            i += 1;
+        }
+        out[3] = Float(sa + da - sa * da).clamp(0.0, 1.0).0;
+        out
+    }
+
+    /// Returns the blend function of the straight destination and source colors.
+    const fn function(self, cd: f32, cs: f32) -> f32 {
+        match self {
+            Self::Normal => cs,
+            Self::Multiply => cd * cs,
+            Self::Screen => cd + cs - cd * cs,
+            Self::Overlay => Self::HardLight.function(cs, cd),
+            Self::Darken => is![cd < cs, cd, cs],
+            Self::Lighten => is![cd > cs, cd, cs],
+            Self::ColorDodge => {
+                if cd <= 0.0 {
+                    0.0
+                } else if cs >= 1.0 {
+                    1.0
+                } else {
+                    Float(cd / (1.0 - cs)).min(1.0).0
+                }
+            }
+            Self::ColorBurn => {
+                if cd >= 1.0 {
+                    1.0
+                } else if cs <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - Float((1.0 - cd) / cs).min(1.0).0
+                }
+            }
+            Self::HardLight => {
+                let cs2 = 2.0 * cs;
+                is![cs <= 0.5, cd * cs2, Self::Screen.function(cd, cs2 - 1.0)]
+            }
+            Self::SoftLight => {
+                if cs <= 0.5 {
+                    cd - (1.0 - 2.0 * cs) * cd * (1.0 - cd)
+                } else {
+                    let d = is![cd <= 0.25, ((16.0 * cd - 12.0) * cd + 4.0) * cd, {
+                        Float(cd).sqrt_hybrid().0
+                    }];
+                    cd + (2.0 * cs - 1.0) * (d - cd)
+                }
+            }
+            Self::Difference => Float(cd - cs).abs().0,
+            Self::Exclusion => cd + cs - 2.0 * cd * cs,
+        }
+    }
+}
+
+/// # Compositing
+#[cfg(feature = "color")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "color")))]
+impl RasterOps {
+    /// Composites the premultiplied `src` raster placed at `at` into `dst`, with an `op`erator.
+    ///
+    /// Only the overlapping cells are composited.
+    ///
+    /// The optional `mask` has the coverage of each `src` sample, in the same order.
+    /// Each result is interpolated from the destination by its coverage,
+    /// so that uncovered cells are left unchanged whatever the operator.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`][crate::ImageError::InvalidImageSize]
+    /// with the extent of `src` if the mask has a different number of samples.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Coverage8, Extent2, RasterCompose, RasterFormat, RasterOps, RasterSlice};
+    /// # use devela::{RgbaPre8, pos};
+    /// let (red, blue) = (RgbaPre8 { c: [255, 0, 0, 255] }, RgbaPre8 { c: [0, 0, 128, 128] });
+    /// let mut pixels = [red; 4];
+    /// let (f, e) = (RasterFormat::RGBA_PRE8, Extent2::new([2, 2]));
+    /// let mut dst = RasterSlice::dense_mut(f, e, &mut pixels).unwrap();
+    /// let blues = [blue; 2];
+    /// let src = RasterSlice::dense(f, Extent2::new([1, 2]), &blues).unwrap();
+    /// let mask = [Coverage8::FULL, Coverage8::ZERO];
+    ///
+    /// RasterOps::compose(&mut dst, pos![1, 0], &src, Some(&mask), RasterCompose::SrcOver)?;
+    /// assert_eq![pixels.map(|p| p.c), [[255, 0, 0, 255], [127, 0, 128, 255],
+    ///     [255, 0, 0, 255], [255, 0, 0, 255]]];
+    /// # Ok::<(), devela::ImageError>(())
+    /// ```
+    pub fn compose<T, const LINEAR: bool, D, S>(
+        dst: &mut D,
+        at: Position2<i32>,
+        src: &S,
+        mask: Option<&[Coverage8]>,
+        op: RasterCompose,
+    ) -> ImageResult<()>
+    where
+        Rgba<T, LINEAR, true>: RasterPixel,
+        D: RasterBuf<Sample = Rgba<T, LINEAR, true>> + ?Sized,
+        S: RasterView<Sample = Rgba<T, LINEAR, true>> + ?Sized,
+    {
+        composite(dst, at, src, mask, |s, d| op.compose(s, d))
+    }
+
+    /// Blends the premultiplied `src` raster placed at `at` into `dst`, with a blend `mode`.
+    ///
+    /// Works like [`compose`][Self::compose], with a [`RasterBlend`] mode.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`][crate::ImageError::InvalidImageSize]
+    /// with the extent of `src` if the mask has a different number of samples.
+    pub fn blend<T, const LINEAR: bool, D, S>(
+        dst: &mut D,
+        at: Position2<i32>,
+        src: &S,
+        mask: Option<&[Coverage8]>,
+        mode: RasterBlend,
+    ) -> ImageResult<()>
+    where
+        Rgba<T, LINEAR, true>: RasterPixel,
+        D: RasterBuf<Sample = Rgba<T, LINEAR, true>> + ?Sized,
+        S: RasterView<Sample = Rgba<T, LINEAR, true>> + ?Sized,
+    {
+        composite(dst, at, src, mask, |s, d| mode.blend(s, d))
+    }
+
+    /// Composites a premultiplied `paint` into the cells of each span, with an `op`erator.
+    ///
+    /// Each result is interpolated from the destination by the coverage of its span.
+    /// Cells outside of `dst` are ignored.
+    ///
+    /// This paints the spans of a [`RasterFillIter`][crate::RasterFillIter].
+    pub fn compose_spans<T, const LINEAR: bool, D>(
+        dst: &mut D,
+        spans: impl IntoIterator<Item = RasterSpan>,
+        paint: Rgba<T, LINEAR, true>,
+        op: RasterCompose,
+    ) where
+        Rgba<T, LINEAR, true>: RasterPixel,
+        D: RasterBuf<Sample = Rgba<T, LINEAR, true>> + ?Sized,
+    {
+        let [w, h] = dst.raster_extent().dim.map(|n| n as usize);
+        let paint = paint.to_channels();
+        let samples = dst.raster_samples_mut();
+        for span in spans {
+            let [x, y] = span.start().dim;
+            is![y >= h || x >= w, continue];
+            let t = f32::from(span.coverage().get()) / 255.0;
+            for sample in &mut samples[y * w + x..][..span.len().min(w - x)] {
+                let d = sample.to_channels();
+                *sample = RasterPixel::from_channels(lerp4(d, op.compose(paint, d), t));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "color")]
+/// Combines each `src` sample placed at `at` into `dst` with `f`, through an optional `mask`.
+fn composite<P, D, S>(
+    dst: &mut D,
+    at: Position2<i32>,
+    src: &S,
+    mask: Option<&[Coverage8]>,
+    f: impl Fn([f32; 4], [f32; 4]) -> [f32; 4],
+) -> ImageResult<()>
+where
+    P: RasterPixel,
+    D: RasterBuf<Sample = P> + ?Sized,
+    S: RasterView<Sample = P> + ?Sized,
+{
+    let (extent, samples) = (src.raster_extent(), src.raster_samples());
+    is![mask.is_some_and(|m| m.len() != samples.len()), return Err(invalid_size(extent))];
+    let dst_extent = dst.raster_extent();
+    let Some(overlap) = Overlap::new(dst_extent, at, extent) else { return Ok(()) };
+    let dst = dst.raster_samples_mut();
+    let (sw, dw) = (extent.dim[0] as usize, dst_extent.dim[0] as usize);
+    for [s, d] in overlap.indices(sw, dw) {
+        let coverage = mask.map_or(255, |m| m[s].get());
+        is![coverage == 0, continue];
+        let (src, old) = (samples[s].to_channels(), dst[d].to_channels());
+        let new = f(src, old);
+        let new = is![coverage == 255, new, lerp4(old, new, f32::from(coverage) / 255.0)];
+        dst[d] = P::from_channels(new);
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+// devela/src/media/visual/image/raster/ops/convert.rs
+//
+//! Defines blitting [`RasterOps`] between byte rasters of different formats.
+//
+// > How does a pixel of one format read as a pixel of another?
+
+use super::{Overlap, invalid_size};
+#[cfg(feature = "color")]
+use crate::Gamma;
+use crate::ImageError::UnsupportedFormat;
+use crate::{Boundary1d, ImageResult, Position2, RasterBufBytes, RasterByteSlice};
+use crate::{RasterFormat, RasterLayout, RasterOps, RasterViewBytes, is};
+
+/// # Blitting
+impl RasterOps {
+    /// Copies the byte raster `src` placed at `at` into `dst`, converting its format.
+    ///
+    /// Only the overlapping cells are copied, honoring the row order and stride of both.
+    ///
+    /// Any unpacked direct-color format with 8-bit, 16-bit, or `f32` samples is supported.
+    /// Conversions unpremultiply and premultiply alpha, convert between sRGB and linear
+    /// transfers, reduce colors to their Rec. 709 luma, and fill missing alpha as opaque.
+    /// Padding fields are left unchanged.
+    ///
+    /// # Features
+    /// Converting between sRGB and linear transfers needs the `color` feature.
+    ///
+    /// # Errors
+    /// Returns [`UnsupportedFormat`][crate::ImageError::UnsupportedFormat]
+    /// if either format or their conversion is not supported, or
+    /// [`InvalidImageSize`][crate::ImageError::InvalidImageSize]
+    /// if either byte slice is shorter than its layout.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Extent2, RasterByteSlice, RasterFormat, RasterOps, pos};
+    /// let rgba = [255, 0, 0, 255, 0, 255, 0, 128];
+    /// let src = RasterByteSlice::dense(RasterFormat::RGBA8, Extent2::new([2, 1]), &rgba).unwrap();
+    /// let mut bgra = [0; 12];
+    /// let mut dst =
+    ///     RasterByteSlice::dense_mut(RasterFormat::BGRA_PRE8, Extent2::new([3, 1]), &mut bgra)
+    ///         .unwrap();
+    /// RasterOps::blit(&src, &mut dst, pos![1, 0])?;
+    /// assert_eq![bgra, [0, 0, 0, 0, 0, 0, 255, 255, 0, 128, 0, 128]];
+    /// # Ok::<(), devela::ImageError>(())
+    /// ```
+    pub fn blit<S: AsRef<[u8]>, D: AsRef<[u8]> + AsMut<[u8]>>(
+        src: &RasterByteSlice<S>,
+        dst: &mut RasterByteSlice<D>,
+        at: Position2<i32>,
+    ) -> ImageResult<()> {
+        let (from, to) = (PixelCodec::new(src.format())?, PixelCodec::new(dst.format())?);
+        #[cfg(not(feature = "color"))]
+        is![from.linear != to.linear, return Err(UnsupportedFormat)];
+        let same = src.format() == dst.format();
+        let (src_layout, dst_layout) = (src.layout(), dst.layout());
+        let src_bytes = src.raster_bytes();
+        check_len(src_layout, src_bytes.len())?;
+        check_len(dst_layout, dst.raster_len_bytes())?;
+        let Some(o) = Overlap::new(dst_layout.extent, at, src_layout.extent) else {
+            return Ok(());
+        };
+        let dst_bytes = dst.raster_bytes_mut();
+        let (sn, dn) = (from.pixel_len(), to.pixel_len());
+        for y in 0..o.len[1] {
+            let s = row_start(src_layout, o.src[1] + y) + o.src[0] * sn;
+            let d = row_start(dst_layout, o.dst[1] + y) + o.dst[0] * dn;
+            let (s, d) = (&src_bytes[s..][..o.len[0] * sn], &mut dst_bytes[d..][..o.len[0] * dn]);
+            if same {
+                d.copy_from_slice(s);
+                continue;
+            }
+            for (s, d) in s.chunks_exact(sn).zip(d.chunks_exact_mut(dn)) {
+                to.store(from.convert(from.load(s), &to), d);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fails if a byte slice of `len` is too short for the `layout`.
+fn check_len(layout: RasterLayout, len: usize) -> ImageResult<()> {
+    let short = layout.min_len_bytes().is_none_or(|min| len < min);
+    is![short, Err(invalid_size(layout.extent)), Ok(())]
+}
+/// Returns the offset of the logical row `y`.
+fn row_start(layout: RasterLayout, y: usize) -> usize {
+    let h = layout.extent.dim[1] as usize;
+    let line = is![layout.row_start == Boundary1d::Lower, h - 1 - y, y];
+    line * layout.bytes_per_line as usize
+}
+
+/// The pixel layout and meaning of a supported byte raster format.
+#[derive(Clone, Copy, Debug)]
+struct PixelCodec {
+    /// The field indices of red, green, and blue.
+    colors: [u8; 3],
+    /// The field index of alpha, which is ignored for opaque formats.
+    alpha: Option<u8>,
+    /// The bytes of each field: 1, 2, or 4 for `f32`.
+    sample: u8,
+    premultiplied: bool,
+    linear: bool,
+    /// The number of stored fields, including padding.
+    channels: u8,
+}
+impl PixelCodec {
+    fn new(format: RasterFormat) -> ImageResult<Self> {
+        let (colors, alpha) = format.rgba_fields().ok_or(UnsupportedFormat)?;
+        let sample = match (format.bits_per_sample(), format.is_float()) {
+            (Some(8), false) => 1,
+            (Some(16), false) => 2,
+            (Some(32), true) => 4,
+            _ => return Err(UnsupportedFormat),
+        };
+        Ok(Self {
+            colors,
+            alpha: is![format.has_alpha(), alpha, None],
+            sample,
+            premultiplied: format.is_premultiplied(),
+            linear: format.is_linear(),
+            channels: format.channel_count().ok_or(UnsupportedFormat)?,
+        })
+    }
+    /// Returns the stored bytes of a pixel.
+    const fn pixel_len(&self) -> usize {
+        self.channels as usize * self.sample as usize
+    }
+    /// Returns whether all color fields are the same gray field.
+    const fn is_gray(&self) -> bool {
+        self.colors[0] == self.colors[1] && self.colors[1] == self.colors[2]
+    }
+
+    /// Returns the straight normalized channels of a pixel.
+    fn load(&self, px: &[u8]) -> [f32; 4] {
+        let field = |i: u8| {
+            let b = &px[i as usize * self.sample as usize..];
+            match self.sample {
+                1 => f32::from(b[0]) / 255.0,
+                2 => f32::from(u16::from_ne_bytes([b[0], b[1]])) / 65_535.0,
+                _ => f32::from_ne_bytes([b[0], b[1], b[2], b[3]]),
+            }
+        };
+        let a = self.alpha.map_or(1.0, field);
+        let mut c = [field(self.colors[0]), field(self.colors[1]), field(self.colors[2]), a];
+        if self.premultiplied {
+            for v in &mut c[..3] {
+                *v = is![a > 0.0, *v / a, 0.0];
+            }
+        }
+        c
+    }
+    /// Returns straight channels converted to the transfer and colors of `to`.
+    fn convert(&self, mut c: [f32; 4], to: &Self) -> [f32; 4] {
+        #[cfg(feature = "color")]
+        if self.linear != to.linear {
+            const SRGB: Gamma<f32> = Gamma::new(2.4);
+            for v in &mut c[..3] {
+                *v = is![to.linear, SRGB.const_decode_srgb(*v), SRGB.const_encode_srgb(*v)];
+            }
+        }
+        if to.is_gray() && !self.is_gray() {
+            // Rec. 709 luma
+            let luma = 0.212_639 * c[0] + 0.715_169 * c[1] + 0.072_192 * c[2];
+            c[..3].fill(luma);
+        }
+        c
+    }
+    /// Stores straight normalized channels into a pixel.
+    fn store(&self, mut c: [f32; 4], px: &mut [u8]) {
+        if self.premultiplied {
+            let a = c[3];
+            for v in &mut c[..3] {
+                *v *= a;
+            }
+        }
+        let mut field = |i: u8, v: f32| {
+            let b = &mut px[i as usize * self.sample as usize..];
+            let q = |max: f32| (v.clamp(0.0, 1.0) * max + 0.5) as u32;
+            match self.sample {
+                1 => b[0] = q(255.0) as u8,
+                2 => b[..2].copy_from_slice(&(q(65_535.0) as u16).to_ne_bytes()),
+                _ => b[..4].copy_from_slice(&v.to_ne_bytes()),
+            }
+        };
+        for (&i, v) in self.colors.iter().zip(c) {
+            field(i, v);
+        }
+        if let Some(i) = self.alpha {
+            field(i, c[3]);
+        }
+    }
+}
@@ -1,14 +1,30 @@
 // devela/src/media/visual/image/raster/ops/mod.rs
 //
-//! Raster operations
+//! Raster operations: compositing, conversion, resampling, transforms, and blurs.
 //
 
-// mod rotate; // Generic dense raster rotation
+#[cfg(test)]
+mod _test;
+mod _helper; // Overlap, check_extent, invalid_size, lerp4
 
-crate::structural_mods! { // _mods
+mod blur; // Separable box and Gaussian blurs
+mod compose; // Porter–Duff compositing and blend modes
+mod convert; // Blitting between byte raster formats
+mod namespace; // RasterOps
+mod pixel; // Normalized sample channels
+mod resample; // Filtered resampling
+mod transform; // Crops, flips, and quarter-turn rotations
+
+crate::structural_mods! { // _mods, _crate_internals
     _mods {
-        // pub use super::{
-        //     rotate::*,
-        // };
+        pub use super::{
+            compose::{RasterBlend, RasterCompose},
+            namespace::RasterOps,
+            pixel::RasterPixel,
+            resample::RasterFilter,
+        };
+    }
+    _crate_internals {
+        pub(crate) use super::_helper::*;
     }
 }
@@ -0,0 +1,28 @@
+// devela/src/media/visual/image/raster/ops/namespace.rs
+//
+//! Defines [`RasterOps`].
+//
+
+#[doc = crate::_tags!(image namespace)]
+/// Raster compositing and image operations.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster")}]
+///
+/// All operations work over borrowed rasters and caller-provided scratch space,
+/// without allocating:
+/// - [compositing][Self::compose] of premultiplied `Rgba` samples, with the `color` feature,
+///   with a [`RasterCompose`][crate::RasterCompose] operator or a
+///   [`RasterBlend`][crate::RasterBlend] mode, optionally through a coverage mask.
+/// - [blitting][Self::blit] between byte rasters of different [`RasterFormat`]s.
+/// - [resampling][Self::resample] with a [`RasterFilter`][crate::RasterFilter].
+/// - [cropping][Self::crop], [flipping][Self::flip_x], and
+///   [rotating][Self::rotate] by multiples of 90°.
+/// - separable [box][Self::box_blur] and [Gaussian][Self::gaussian_blur] blurs.
+///
+/// Typed operations work over any [`RasterPixel`][crate::RasterPixel] sample,
+/// and fail with [`ImageError::InvalidImageSize`] when their rasters have incompatible
+/// extents, carrying the expected extent.
+///
+/// [`RasterFormat`]: crate::RasterFormat
+/// [`ImageError::InvalidImageSize`]: crate::ImageError::InvalidImageSize
+#[derive(Debug)]
+pub struct RasterOps;
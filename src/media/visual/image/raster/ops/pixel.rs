@@ -0,0 +1,108 @@
+// devela/src/media/visual/image/raster/ops/pixel.rs
+//
+//! Defines [`RasterPixel`].
+//
+// > How do raster operations read and write the channels of a sample?
+
+#[cfg(feature = "color")]
+use crate::{Rgb, Rgba};
+
+#[doc = crate::_tags!(image)]
+/// A raster sample with up to 4 channels that raster operations can filter and composite.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster")}]
+///
+/// Channels are exchanged as normalized `f32` values, where integer samples map
+/// their whole range to `0.0..=1.0`. Unused channels are `0.0`.
+///
+/// Raster operations treat channels as independent values, without any
+/// color meaning, except for compositing, which expects premultiplied
+/// `Rgba` samples with alpha as their 4th channel.
+///
+/// Filtering samples with straight alpha bleeds the color of transparent
+/// samples into their neighbors, so they are best filtered premultiplied.
+///
+/// # Features
+/// With the `color` feature enabled, it's also implemented for
+/// `Rgb` and `Rgba` colors of `u8`, `u16`, and `f32` channels.
+pub trait RasterPixel: Copy {
+    /// The number of meaningful channels, from 1 to 4.
+    const CHANNELS: usize;
+
+    /// Returns the normalized channels.
+    fn to_channels(self) -> [f32; 4];
+
+    /// Returns the sample with the given normalized channels.
+    ///
+    /// Integer samples are rounded and clamped to their range.
+    fn from_channels(channels: [f32; 4]) -> Self;
+}
+
+macro_rules! impl_raster_pixel {
+    () => {
+        impl_raster_pixel![int u8, u16];
+        impl_raster_pixel![@float f32];
+        impl_raster_pixel![@arrays u8, u16, f32];
+        #[cfg(feature = "color")]
+        impl_raster_pixel![@colors u8, u16, f32];
+    };
+    (int $($t:ty),+) => { $(
+        impl_raster_pixel![@scalar $t:
+            |v| f32::from(v) / <$t>::MAX as f32,
+            |c| (c.clamp(0.0, 1.0) * <$t>::MAX as f32 + 0.5) as $t];
+    )+ };
+    (@float $t:ty) => {
+        impl_raster_pixel![@scalar $t: |v| v, |c| c];
+    };
+    (@scalar $t:ty: |$v:ident| $to:expr, |$c:ident| $from:expr) => {
+        impl RasterPixel for $t {
+            const CHANNELS: usize = 1;
+            fn to_channels(self) -> [f32; 4] {
+                let $v = self;
+                [$to, 0.0, 0.0, 0.0]
+            }
+            fn from_channels(channels: [f32; 4]) -> Self {
+                let $c = channels[0];
+                $from
+            }
+        }
+    };
+    (@arrays $($t:ty),+) => { $(
+        impl_raster_pixel![@array $t: 1, 2, 3, 4];
+    )+ };
+    (@array $t:ty: $($n:literal),+) => { $(
+        impl RasterPixel for [$t; $n] {
+            const CHANNELS: usize = $n;
+            fn to_channels(self) -> [f32; 4] {
+                let mut channels = [0.0; 4];
+                for (c, v) in channels.iter_mut().zip(self) {
+                    *c = v.to_channels()[0];
+                }
+                channels
+            }
+            fn from_channels(channels: [f32; 4]) -> Self {
+                ::core::array::from_fn(|i| <$t>::from_channels([channels[i], 0.0, 0.0, 0.0]))
+            }
+        }
+    )+ };
+    (@colors $($t:ty),+) => { $(
+        impl<const LINEAR: bool> RasterPixel for Rgb<$t, LINEAR> {
+            const CHANNELS: usize = 3;
+            fn to_channels(self) -> [f32; 4] {
+                self.c.to_channels()
+            }
+            fn from_channels(channels: [f32; 4]) -> Self {
+                Self { c: <[$t; 3]>::from_channels(channels) }
+            }
+        }
+        impl<const LINEAR: bool, const PREMUL: bool> RasterPixel for Rgba<$t, LINEAR, PREMUL> {
+            const CHANNELS: usize = 4;
+            fn to_channels(self) -> [f32; 4] {
+                self.c.to_channels()
+            }
+            fn from_channels(channels: [f32; 4]) -> Self {
+                Self { c: <[$t; 4]>::from_channels(channels) }
+            }
+        }
+    )+ };
+}
+impl_raster_pixel!();
@@ -0,0 +1,153 @@
+// devela/src/media/visual/image/raster/ops/resample.rs
+//
+//! Defines [`RasterFilter`] and resampling [`RasterOps`].
+//
+// > Which weighted source samples does each resized sample take?
+
+use crate::{Float, RasterBuf, RasterOps, RasterPixel, RasterView, is};
+
+#[doc = crate::_tags!(image)]
+/// A reconstruction filter for resampling rasters.
+#[doc = crate::_doc_meta!{location("media/visual/image/raster")}]
+///
+/// When downscaling, every filter except [`Nearest`][Self::Nearest]
+/// is stretched by the scale factor, so that it averages all the source samples
+/// that each destination sample spans.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RasterFilter {
+    /// The closest source sample.
+    ///
+    /// Keeps hard edges, and aliases when downscaling.
+    Nearest,
+    /// Linear interpolation between the 2×2 closest source samples, a tent filter.
+    #[default]
+    Bilinear,
+    /// A windowed sinc filter over the 6×6 closest source samples.
+    ///
+    /// Keeps most detail, and may ring around hard edges.
+    Lanczos3,
+}
+
+impl RasterFilter {
+    /// Returns the radius of the kernel, in source samples when upscaling.
+    #[must_use]
+    pub const fn support(self) -> f32 {
+        match self {
+            Self::Nearest => 0.5,
+            Self::Bilinear => 1.0,
+            Self::Lanczos3 => 3.0,
+        }
+    }
+    /// Returns the weight of the kernel at a distance `x` from its center.
+    #[must_use]
+    pub const fn weight(self, x: f32) -> f32 {
+        let x = Float(x).abs().0;
+        match self {
+            Self::Nearest => is![x < 0.5, 1.0, 0.0],
+            Self::Bilinear => is![x < 1.0, 1.0 - x, 0.0],
+            Self::Lanczos3 => {
+                if x < 1e-6 {
+                    1.0
+                } else if x < 3.0 {
+                    const PI: f32 = Float::<f32>::PI.0;
+                    let (a, b) = (PI * x, PI * x / 3.0);
+                    (Float(a).sin_minimax().0 / a) * (Float(b).sin_minimax().0 / b)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// # Resampling
+impl RasterOps {
+    /// Resamples the whole `src` raster into the whole `dst` raster, with a `filter`.
+    ///
+    /// Samples beyond the edges of `src` repeat its closest edge samples.
+    /// Does nothing if `src` is empty.
+    ///
+    /// Work is proportional to the destination samples times the squared
+    /// support of the filter, which grows with the downscaling factor.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Extent2, RasterFilter, RasterFormat, RasterOps, RasterSlice};
+    /// let src = RasterSlice::dense(RasterFormat::GRAY8, Extent2::new([2, 1]), &[0_u8, 255])
+    ///     .unwrap();
+    /// let mut samples = [0_u8; 4];
+    /// let mut dst = RasterSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([4, 1]), &mut samples)
+    ///     .unwrap();
+    /// RasterOps::resample(&src, &mut dst, RasterFilter::Bilinear);
+    /// assert_eq![samples, [0, 64, 191, 255]];
+    /// ```
+    pub fn resample<P, S, D>(src: &S, dst: &mut D, filter: RasterFilter)
+    where
+        P: RasterPixel,
+        S: RasterView<Sample = P> + ?Sized,
+        D: RasterBuf<Sample = P> + ?Sized,
+    {
+        let [sw, sh] = src.raster_extent().dim.map(|n| n as usize);
+        let [dw, dh] = dst.raster_extent().dim.map(|n| n as usize);
+        is![sw == 0 || sh == 0, return];
+        let (samples, out) = (src.raster_samples(), dst.raster_samples_mut());
+        let (kx, ky) = (Axis::new(filter, sw, dw), Axis::new(filter, sh, dh));
+        for y in 0..dh {
+            let (y0, y1, cy) = ky.window(y);
+            for x in 0..dw {
+                let (x0, x1, cx) = kx.window(x);
+                let (mut sum, mut total) = ([0.0; 4], 0.0);
+                for j in y0..=y1 {
+                    let wy = ky.weight(j, cy);
+                    is![wy == 0.0, continue];
+                    let row = &samples[j.clamp(0, sh as isize - 1) as usize * sw..][..sw];
+                    for i in x0..=x1 {
+                        let w = wy * kx.weight(i, cx);
+                        is![w == 0.0, continue];
+                        let c = row[i.clamp(0, sw as isize - 1) as usize].to_channels();
+                        for (s, c) in sum.iter_mut().zip(c) {
+                            *s += w * c;
+                        }
+                        total += w;
+                    }
+                }
+                is![total == 0.0, continue];
+                out[y * dw + x] = P::from_channels(sum.map(|s| s / total));
+            }
+        }
+    }
+}
+
+/// The kernel of a filter along one axis.
+#[derive(Clone, Copy, Debug)]
+struct Axis {
+    filter: RasterFilter,
+    /// The source samples per destination sample.
+    scale: f32,
+    /// The stretch of the kernel, at least 1.
+    stretch: f32,
+}
+impl Axis {
+    fn new(filter: RasterFilter, src: usize, dst: usize) -> Self {
+        let scale = src as f32 / dst.max(1) as f32;
+        let stretch = is![filter == RasterFilter::Nearest || scale < 1.0, 1.0, scale];
+        Self { filter, scale, stretch }
+    }
+    /// Returns the first and last source samples under the kernel of the destination sample
+    /// `d`, and the position of its center.
+    fn window(&self, d: usize) -> (isize, isize, f32) {
+        let center = (d as f32 + 0.5) * self.scale - 0.5;
+        if self.filter == RasterFilter::Nearest {
+            let nearest = Float(center + 0.5).const_floor().0 as isize;
+            return (nearest, nearest, nearest as f32);
+        }
+        let radius = self.filter.support() * self.stretch;
+        let first = Float(center - radius).const_floor().0 as isize + 1;
+        let last = Float(center + radius).const_ceil().0 as isize - 1;
+        (first, last.max(first), center)
+    }
+    /// Returns the weight of the source sample `s` for a kernel at `center`.
+    fn weight(&self, s: isize, center: f32) -> f32 {
+        self.filter.weight((s as f32 - center) / self.stretch)
+    }
+}
@@ -0,0 +1,100 @@
+// devela/src/media/visual/image/raster/ops/transform.rs
+//
+//! Defines cropping, flipping, and rotating [`RasterOps`].
+//
+
+use super::{check_extent, invalid_size};
+use crate::{Extent2, ImageResult, Position2, RasterBuf, RasterOps, RasterView, is};
+
+/// # Transforms
+impl RasterOps {
+    /// Copies the region of `src` starting at `origin` with the extent of `dst` into `dst`.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`][crate::ImageError::InvalidImageSize]
+    /// with the extent of `dst` if the region doesn't fit within `src`.
+    pub fn crop<T: Copy, S, D>(src: &S, origin: Position2<u32>, dst: &mut D) -> ImageResult<()>
+    where
+        S: RasterView<Sample = T> + ?Sized,
+        D: RasterBuf<Sample = T> + ?Sized,
+    {
+        let ([sw, sh], [x, y]) = (src.raster_extent().dim, origin.dim);
+        let extent = dst.raster_extent();
+        let [w, h] = extent.dim;
+        let fits =
+            x.checked_add(w).is_some_and(|r| r <= sw) && y.checked_add(h).is_some_and(|b| b <= sh);
+        is![!fits, return Err(invalid_size(extent))];
+        let [sw, x, y, w] = [sw, x, y, w].map(|n| n as usize);
+        is![w == 0, return Ok(())];
+        let samples = src.raster_samples();
+        for (j, row) in dst.raster_samples_mut().chunks_exact_mut(w).enumerate() {
+            row.copy_from_slice(&samples[(y + j) * sw + x..][..w]);
+        }
+        Ok(())
+    }
+
+    /// Mirrors the raster horizontally in place, swapping its left and right.
+    pub fn flip_x<B: RasterBuf + ?Sized>(raster: &mut B) {
+        let w = raster.raster_extent().dim[0] as usize;
+        is![w == 0, return];
+        for row in raster.raster_samples_mut().chunks_exact_mut(w) {
+            row.reverse();
+        }
+    }
+    /// Mirrors the raster vertically in place, swapping its top and bottom.
+    ///
+    /// Flipping in both directions rotates the raster by 180°.
+    pub fn flip_y<B: RasterBuf + ?Sized>(raster: &mut B) {
+        let [w, h] = raster.raster_extent().dim.map(|n| n as usize);
+        let samples = raster.raster_samples_mut();
+        for y in 0..h / 2 {
+            let (top, bottom) = samples.split_at_mut((h - 1 - y) * w);
+            top[y * w..][..w].swap_with_slice(&mut bottom[..w]);
+        }
+    }
+
+    /// Copies `src` into `dst` rotated clockwise by a number of `quarter_turns`.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`][crate::ImageError::InvalidImageSize]
+    /// with the rotated extent of `src` if `dst` has a different extent.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::{Extent2, RasterFormat, RasterOps, RasterSlice};
+    /// // 1 2 3        4 1
+    /// // 4 5 6   →    5 2
+    /// //              6 3
+    /// let src = RasterSlice::dense(RasterFormat::GRAY8, Extent2::new([3, 2]), &[1_u8, 2, 3, 4, 5, 6])
+    ///     .unwrap();
+    /// let mut samples = [0; 6];
+    /// let mut dst = RasterSlice::dense_mut(RasterFormat::GRAY8, Extent2::new([2, 3]), &mut samples)
+    ///     .unwrap();
+    /// RasterOps::rotate(&src, &mut dst, 1)?;
+    /// assert_eq![samples, [4, 1, 5, 2, 6, 3]];
+    /// # Ok::<(), devela::ImageError>(())
+    /// ```
+    pub fn rotate<T: Copy, S, D>(src: &S, dst: &mut D, quarter_turns: u32) -> ImageResult<()>
+    where
+        S: RasterView<Sample = T> + ?Sized,
+        D: RasterBuf<Sample = T> + ?Sized,
+    {
+        let [w, h] = src.raster_extent().dim;
+        let turns = quarter_turns % 4;
+        check_extent(dst.raster_extent(), Extent2::new(is![turns.is_multiple_of(2), [w, h], [h, w]]))?;
+        let [w, h] = [w, h].map(|n| n as usize);
+        let (samples, out) = (src.raster_samples(), dst.raster_samples_mut());
+        for y in 0..h {
+            for x in 0..w {
+                let (dx, dy, dw) = match turns {
+                    0 => (x, y, w),
+                    1 => (h - 1 - y, x, h),
+                    2 => (w - 1 - x, h - 1 - y, w),
+                    _ => (y, w - 1 - x, h),
+                };
+                out[dy * dw + dx] = samples[y * w + x];
+            }
+        }
+        Ok(())
+    }
+}
@@ -41,6 +41,7 @@ __dbg! { slog! {
 /// - with_palette
 /// - palette_mut
 /// - encode_rgb
+/// - encode_indexed
 impl<const MAX_COLORS: usize> SixelEncoder<MAX_COLORS> {
     /// Create a new encoder with empty palette
     pub const fn new() -> Self {
@@ -138,6 +139,63 @@ impl<const MAX_COLORS: usize> SixelEncoder<MAX_COLORS> {
         off += unwrap![ok? Self::write_sixel_end(slice![mut buf, off,..])];
         Ok(off)
     }
+
+    /// Sixel encoding of palette indices, using the current palette as is.
+    ///
+    /// Each index refers to a color of the palette, as returned by the mapping methods of
+    /// a [`Palette`][crate::Palette] that the encoder palette was
+    /// [created from][SixelPalette::from_palette], with or without dithering.
+    /// Every color is rendered, including the first one.
+    ///
+    /// - RLE encoding is used when the same sixel character repeats 4 or more times.
+    #[rustfmt::skip]
+    pub fn encode_indexed(&mut self, indices: &[u8], width: usize, height: usize, buf: &mut [u8])
+        -> Result<usize, NotEnoughSpace> {
+        let mut off = Self::write_sixel_start_simple(buf)?;
+        self.width = width;
+        self.height = height;
+        let needed = off + self.palette.len() * 15; // the longest definitions
+        is![buf.len() < needed, return Err(NotEnoughSpace(Some(needed)))];
+        off += self.palette.write_definitions(&mut buf[off..]);
+
+        let colors = self.palette.len() as u16;
+        let mut band_y = 0;
+        while band_y < height {
+            let band_height = Cmp(height - band_y).min(6);
+            for idx in 0..colors {
+                off += Self::write_color_reference(&mut buf[off..], idx)?;
+                let (mut current_char, mut repeat_count) = (None, 0);
+                for x in 0..width {
+                    let mut sixel_bits = 0u8;
+                    for dy in 0..band_height {
+                        let i = (band_y + dy) * width + x;
+                        is![indices.get(i) == Some(&(idx as u8)), sixel_bits |= 1 << dy];
+                    }
+                    let sixel_char = SixelChar::from_bitmask(sixel_bits);
+                    if current_char == Some(sixel_char) && repeat_count < 255 {
+                        repeat_count += 1;
+                        continue;
+                    }
+                    if let Some(char) = current_char {
+                        off += Self::write_rle_run(&mut buf[off..], char, repeat_count)?;
+                    }
+                    (current_char, repeat_count) = (Some(sixel_char), 1);
+                }
+                if let Some(char) = current_char {
+                    off += Self::write_rle_run(&mut buf[off..], char, repeat_count)?;
+                }
+                // return to start for next color, or move to the next band
+                let next = is![idx != colors - 1, Some(b'$'), is![band_y + 6 < height, Some(b'-'), None]];
+                if let Some(byte) = next {
+                    *buf.get_mut(off).ok_or(NotEnoughSpace(Some(off + 1)))? = byte;
+                    off += 1;
+                }
+            }
+            band_y += 6;
+        }
+        off += Self::write_sixel_end(&mut buf[off..])?;
+        Ok(off)
+    }
 }
 
 /* helpers */
@@ -236,6 +294,10 @@ impl<const MAX_COLORS: usize> SixelEncoder<MAX_COLORS> {
     const fn write_color_reference(buffer: &mut [u8], index: u16) -> Result<usize, NotEnoughSpace> {
         is![buffer.len() < 2, return Err(NotEnoughSpace(Some(2)))];
         buffer[0] = b'#';
+        is![index == 0, {
+            buffer[1] = b'0';
+            return Ok(2);
+        }];
         let digits_written = Digits(index).write_digits10_nonzero(buffer, 1);
         is![digits_written == 0, return Err(NotEnoughSpace(Some(2)))];
         Ok(1 + digits_written)
@@ -3,7 +3,7 @@
 //! Defines [`SixelPalette`], [`SixelPaletteIter`].
 //
 
-use crate::{NotEnoughSpace, SixelColor};
+use crate::{NotEnoughSpace, Palette, SixelColor};
 
 #[doc = crate::_tags!(color term)]
 /// Palette of Sixel colors with fixed capacity.
@@ -34,6 +34,23 @@ impl<const CAP: usize> SixelPalette<CAP> {
         palette
     }
 
+    /// Create a palette from the colors of a [`Palette`], keeping their indices.
+    ///
+    /// Returns [`NotEnoughSpace`] if the palette has more than `CAP` colors.
+    pub const fn from_palette<const N: usize>(palette: &Palette<N>) -> Result<Self, NotEnoughSpace> {
+        if palette.len() > CAP { return Err(NotEnoughSpace(Some(palette.len()))); }
+        let mut sixel = Self::new();
+        let colors = palette.as_slice();
+        let mut i = 0;
+        while i < colors.len() {
+            let [r, g, b] = colors[i];
+            sixel.colors[i] = Some(SixelColor::from_rgb888(r, g, b));
+            i += 1;
+        }
+        sixel.len = colors.len();
+        Ok(sixel)
+    }
+
     /// Get the number of colors in the palette.
     pub const fn len(&self) -> usize { self.len }
 
@@ -199,6 +216,24 @@ impl<'a, const CAP: usize> Iterator for SixelPaletteIter<'a, CAP> {
 
 #[cfg(test)]
 mod tests {
+    use crate::{Palette, SixelColor, SixelEncoder, SixelPalette};
+
     #[test]
     fn write_definitions() {}
+
+    #[test]
+    fn from_palette_and_encode_indexed() {
+        let palette = Palette::<4>::from_colors(&[[255, 0, 0], [0, 0, 255]]).unwrap();
+        let sixel = SixelPalette::<2>::from_palette(&palette).unwrap();
+        assert_eq![sixel.get(1), Some(SixelColor::new_rgb(0, 0, 99))];
+        assert![SixelPalette::<1>::from_palette(&palette).is_err()];
+
+        // 5×2 pixels: red, then a blue column at x = 4
+        let indices = [0, 0, 0, 0, 1, 0, 0, 0, 0, 1];
+        let mut enc = SixelEncoder::with_palette(5, 2, sixel);
+        let mut buf = [0; 64];
+        let len = enc.encode_indexed(&indices, 5, 2, &mut buf).unwrap();
+        assert_eq![&buf[..len], b"\x1bP;1;q#;2;99;;#1;2;;;99#0!4B?$#1!4?B\x1b\\"];
+        assert![enc.encode_indexed(&indices, 5, 2, &mut buf[..len - 1]).is_err()];
+    }
 }
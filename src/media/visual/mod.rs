@@ -1,7 +1,7 @@
 // devela/src/media/visual/mod.rs
 //
 #![doc = crate::_DOC_MEDIA_VISUAL!()] // public
-#![doc = crate::_doc!(modules: crate::media; visual: color, draw, image)] // pattern, video
+#![doc = crate::_doc!(modules: crate::media; visual: color, draw, image, video)] // pattern
 #![doc = crate::_doc!(flat:"media")]
 #![doc = crate::_doc!(hr)]
 //
@@ -11,7 +11,8 @@ pub mod color;
 pub mod draw;
 pub mod image;
 // pub mod lattice; // WIP
-// pub mod video; //
+#[cfg(feature = "video")]
+pub mod video;
 
 crate::structural_mods! { // _pub_mods, _crate_internals
     _pub_mods {
@@ -19,10 +20,11 @@ crate::structural_mods! { // _pub_mods, _crate_internals
             color::_all::*,
             image::_all::*,
             // lattice::_all::*,
-            // video::_all::*,
         };
         #[cfg(feature = "draw")]
         pub use super::draw::_all::*;
+        #[cfg(feature = "video")]
+        pub use super::video::_all::*;
     }
     _crate_internals {
         pub(crate) use super::{
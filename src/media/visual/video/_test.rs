@@ -0,0 +1,96 @@
+// devela/src/media/visual/video/_test.rs
+
+use crate::{
+    Boundary1d, Extent2, ImageError, RasterByteSlice, RasterFormat, RasterLayout, TimeDelta,
+    VideoFrameRate, YuvFrame, YuvMatrix, YuvSubsampling,
+};
+
+#[test]
+fn frame_rate_timing() {
+    assert_eq![VideoFrameRate::new(30, 0), None];
+    assert_eq![VideoFrameRate::from_fps(0), None];
+    let r = VideoFrameRate::FPS_24;
+    assert_eq![r.frame_time(0), TimeDelta::ZERO];
+    assert_eq![r.frame_time(24), TimeDelta::from_secs(1)];
+    // 1/24 s is not a whole number of nanoseconds: times round up, durations still add up
+    assert_eq![r.frame_time(1).as_nanos(), 41_666_667];
+    let sum = (0..24).map(|i| r.frame_duration(i).as_nanos()).sum::<i128>();
+    assert_eq![sum, 1_000_000_000];
+    for i in [0, 1, 23, 24, 1001, 86_400 * 24] {
+        assert_eq![r.frame_at(r.frame_time(i)), i];
+    }
+    assert_eq![r.frame_at(TimeDelta::from_millis(-5)), 0];
+    assert_eq![VideoFrameRate::NTSC.frame_at(TimeDelta::from_secs(1001)), 30_000];
+}
+
+#[test]
+fn subsampling_extents() {
+    let odd = Extent2::new([5, 3]);
+    assert_eq![YuvSubsampling::Yuv420.chroma_extent(odd), Extent2::new([3, 2])];
+    assert_eq![YuvSubsampling::Yuv422.chroma_extent(odd), Extent2::new([3, 3])];
+    assert_eq![YuvSubsampling::Yuv444.chroma_extent(odd), odd];
+    assert_eq![YuvSubsampling::Yuv420.frame_len(odd), Some(15 + 2 * 6)];
+    assert_eq![YuvSubsampling::Yuv444.frame_len(Extent2::new([u32::MAX, u32::MAX])), None];
+}
+
+#[test]
+fn matrix_extremes() {
+    for m in [YuvMatrix::Bt601, YuvMatrix::Bt709] {
+        assert_eq![m.rgb_to_yuv([0, 0, 0]), [16, 128, 128]];
+        assert_eq![m.rgb_to_yuv([255, 255, 255]), [235, 128, 128]];
+        assert_eq![m.yuv_to_rgb([16, 128, 128]), [0, 0, 0]];
+        assert_eq![m.yuv_to_rgb([235, 128, 128]), [255, 255, 255]];
+        for rgb in [[255, 0, 0], [0, 255, 0], [0, 0, 255], [40, 90, 200]] {
+            let back = m.yuv_to_rgb(m.rgb_to_yuv(rgb));
+            for (a, b) in back.iter().zip(rgb) {
+                assert![a.abs_diff(b) <= 2, "{m:?} {rgb:?} → {back:?}"];
+            }
+        }
+    }
+    assert_ne![YuvMatrix::Bt601.rgb_to_yuv([0, 255, 0]), YuvMatrix::Bt709.rgb_to_yuv([0, 255, 0])];
+}
+
+#[test]
+fn frame_raster_round_trip_formats() {
+    let extent = Extent2::new([3, 3]);
+    let mut rgb = [0; 27];
+    for (i, px) in rgb.chunks_exact_mut(3).enumerate() {
+        px.copy_from_slice(&[i as u8 * 28, 100, 250 - i as u8 * 20]);
+    }
+    let src = RasterByteSlice::dense(RasterFormat::RGB8, extent, &rgb).unwrap();
+    let mut planes = [0; 9 * 3];
+    let mut frame = YuvFrame::new(extent, YuvSubsampling::Yuv444, &mut planes[..]).unwrap();
+    frame.from_raster(&src, YuvMatrix::Bt601).unwrap();
+
+    // into padded, bottom-up BGRA
+    let layout = RasterLayout::interleaved(extent, 4, 16, Boundary1d::Lower);
+    let mut bgra = [0; 48];
+    let mut dst = RasterByteSlice::new_mut(RasterFormat::BGRA8, layout, &mut bgra).unwrap();
+    frame.to_raster(&mut dst, YuvMatrix::Bt601).unwrap();
+    for (i, px) in rgb.chunks_exact(3).enumerate() {
+        let (x, y) = (i % 3, 2 - i / 3);
+        let out = &bgra[y * 16 + x * 4..][..4];
+        assert_eq![out[3], 255];
+        for (a, b) in [out[2], out[1], out[0]].iter().zip(px) {
+            assert![a.abs_diff(*b) <= 2, "pixel {i}: {out:?} {px:?}"];
+        }
+    }
+}
+
+#[test]
+fn frame_errors() {
+    let extent = Extent2::new([2, 2]);
+    let short = YuvFrame::new(extent, YuvSubsampling::Yuv420, [0; 5]);
+    assert_eq![short.unwrap_err(), ImageError::InsufficientBuffer { needed: 6, available: 5 }];
+    let frame = YuvFrame::new(extent, YuvSubsampling::Yuv420, [0; 6]).unwrap();
+    let mut gray = [0; 4];
+    let mut dst = RasterByteSlice::dense_mut(RasterFormat::GRAY8, extent, &mut gray).unwrap();
+    assert_eq![frame.to_raster(&mut dst, YuvMatrix::Bt709), Err(ImageError::UnsupportedFormat)];
+    let mut rgb = [0; 3];
+    let small = Extent2::new([1, 1]);
+    let mut dst = RasterByteSlice::dense_mut(RasterFormat::RGB8, small, &mut rgb).unwrap();
+    assert![matches![
+        frame.to_raster(&mut dst, YuvMatrix::Bt709),
+        Err(ImageError::InvalidImageSize(_))
+    ]];
+}
@@ -0,0 +1,46 @@
+// devela/src/media/visual/video/frame.rs
+//
+//! Defines [`VideoFrameSpan`].
+//
+
+use crate::{ImageFrameSpan, TimeDelta, VideoFrameRate};
+
+#[doc = crate::_tags!(image time)]
+/// The byte span of an encoded video frame, and when it's shown.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// The frames of a sequence are returned in order by its decoder,
+/// like [`Y4mDecoder`][crate::Y4mDecoder], and by its encoder as they're written,
+/// like [`Y4mEncoder`][crate::Y4mEncoder].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VideoFrameSpan {
+    /// The frame metadata, and its span in the encoded bytes.
+    pub span: ImageFrameSpan,
+    /// The presentation time, since the start of the sequence.
+    pub time: TimeDelta,
+    /// How long the frame is shown.
+    pub duration: TimeDelta,
+}
+
+impl VideoFrameSpan {
+    /// Returns the span of a frame shown at the given `rate`, timed by its index.
+    #[must_use]
+    pub const fn new(span: ImageFrameSpan, rate: VideoFrameRate) -> Self {
+        let index = span.frame().index as u64;
+        Self {
+            span,
+            time: rate.frame_time(index),
+            duration: rate.frame_duration(index),
+        }
+    }
+    /// Returns the zero-based index of the frame in its sequence.
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.span.frame().index
+    }
+    /// Returns the time when the next frame is shown.
+    #[must_use]
+    pub const fn end_time(&self) -> TimeDelta {
+        self.time.saturating_add(self.duration)
+    }
+}
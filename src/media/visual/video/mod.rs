@@ -1,10 +1,26 @@
 // devela/src/media/visual/video/mod.rs
 //
-#![doc = crate::_tags!(wip)]
 #![doc = crate::_DOC_MEDIA_VISUAL_VIDEO!()] // public
 #![doc = crate::_doc!(modules: crate::media::visual; video)]
 #![doc = crate::_doc!(flat:"media")]
 #![doc = crate::_doc!(hr)]
 //
 
-crate::structural_mods! {}
+#[cfg(test)]
+mod _test;
+
+mod frame; // VideoFrameSpan
+mod rate; // VideoFrameRate
+mod y4m; // Y4m, Y4mDecoder, Y4mEncoder, Y4mHeader, Y4mInterlace
+mod yuv; // YuvFrame, YuvMatrix, YuvSubsampling
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            frame::*,
+            rate::*,
+            y4m::_all::*,
+            yuv::_all::*,
+        };
+    }
+}
@@ -0,0 +1,95 @@
+// devela/src/media/visual/video/rate.rs
+//
+//! Defines [`VideoFrameRate`].
+//
+
+use crate::{TimeDelta, is};
+
+#[doc = crate::_tags!(time)]
+/// The number of frames shown per second, as an exact ratio.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// It maps frame indices to their presentation times and back.
+/// A frame is shown from the first nanosecond at or after its exact start.
+///
+/// # Example
+/// ```
+/// # use devela::{TimeDelta, VideoFrameRate};
+/// let ntsc = VideoFrameRate::NTSC;
+/// assert_eq![ntsc.frame_time(30), TimeDelta::new(1, 1_000_000)];
+/// assert_eq![ntsc.frame_at(TimeDelta::new(1, 1_000_000)), 30];
+/// assert_eq![ntsc.frame_at(TimeDelta::new(1, 999_999)), 29];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VideoFrameRate {
+    num: u32,
+    den: u32,
+}
+
+#[rustfmt::skip]
+impl VideoFrameRate {
+    /// 24 frames per second, of cinema.
+    pub const FPS_24: Self = Self { num: 24, den: 1 };
+    /// 25 frames per second, of PAL television.
+    pub const FPS_25: Self = Self { num: 25, den: 1 };
+    /// 30 frames per second.
+    pub const FPS_30: Self = Self { num: 30, den: 1 };
+    /// 50 frames per second.
+    pub const FPS_50: Self = Self { num: 50, den: 1 };
+    /// 60 frames per second.
+    pub const FPS_60: Self = Self { num: 60, den: 1 };
+    /// 24000/1001 frames per second, of cinema on NTSC television.
+    pub const NTSC_FILM: Self = Self { num: 24_000, den: 1001 };
+    /// 30000/1001 frames per second, of NTSC television.
+    pub const NTSC: Self = Self { num: 30_000, den: 1001 };
+
+    /// Returns the rate of `num` frames every `den` seconds, or `None` if either is zero.
+    #[must_use]
+    pub const fn new(num: u32, den: u32) -> Option<Self> {
+        is![num == 0 || den == 0, None, Some(Self { num, den })]
+    }
+    /// Returns the rate of `fps` frames per second, or `None` if it's zero.
+    #[must_use]
+    pub const fn from_fps(fps: u32) -> Option<Self> { Self::new(fps, 1) }
+
+    /// Returns the number of frames.
+    #[must_use]
+    pub const fn num(self) -> u32 { self.num }
+    /// Returns the number of seconds.
+    #[must_use]
+    pub const fn den(self) -> u32 { self.den }
+    /// Returns the frames per second.
+    #[must_use]
+    pub const fn fps(self) -> f64 { self.num as f64 / self.den as f64 }
+
+    /// Returns the presentation time of the frame at `index`, saturating.
+    #[must_use]
+    pub const fn frame_time(self, index: u64) -> TimeDelta {
+        let nanos = index as i128 * self.den as i128 * NANOS_PER_SEC;
+        delta_from_nanos((nanos + self.num as i128 - 1) / self.num as i128)
+    }
+    /// Returns how long the frame at `index` is shown.
+    ///
+    /// Frame durations can differ by a nanosecond, so that they add up exactly.
+    #[must_use]
+    pub const fn frame_duration(self, index: u64) -> TimeDelta {
+        let (start, end) = (self.frame_time(index), self.frame_time(index.saturating_add(1)));
+        end.saturating_sub(start)
+    }
+    /// Returns the index of the frame shown at `time`, the first one for negative times.
+    #[must_use]
+    pub const fn frame_at(self, time: TimeDelta) -> u64 {
+        is![!time.is_positive(), return 0];
+        let frames = time.as_nanos() * self.num as i128 / (self.den as i128 * NANOS_PER_SEC);
+        is![frames > u64::MAX as i128, u64::MAX, frames as u64]
+    }
+}
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Returns the time delta of a non-negative number of nanoseconds, saturating.
+const fn delta_from_nanos(nanos: i128) -> TimeDelta {
+    let secs = nanos / NANOS_PER_SEC;
+    is![secs > i64::MAX as i128, return TimeDelta::MAX];
+    TimeDelta::new(secs as i64, (nanos % NANOS_PER_SEC) as i32)
+}
@@ -0,0 +1,63 @@
+// devela/src/media/visual/video/y4m/_helper.rs
+//
+//! Defines (`Y4M_*`), (`y4m_*`).
+//
+
+use crate::{EncodeCursor, ImageResult};
+
+/// The signature that starts a stream.
+pub(super) const Y4M_MAGIC: &[u8] = b"YUV4MPEG2";
+/// The signature that starts a frame.
+pub(super) const Y4M_FRAME: &[u8] = b"FRAME";
+
+/// Returns the decimal number of a whole parameter value.
+pub(super) fn y4m_number(value: &[u8]) -> Option<u32> {
+    (!value.is_empty() && value.len() <= 10).then_some(())?;
+    value.iter().try_fold(0_u32, |n, &b| {
+        b.is_ascii_digit().then_some(())?;
+        n.checked_mul(10)?.checked_add(u32::from(b - b'0'))
+    })
+}
+/// Returns the two numbers of a `num:den` parameter value.
+pub(super) fn y4m_ratio(value: &[u8]) -> Option<[u32; 2]> {
+    let colon = value.iter().position(|&b| b == b':')?;
+    Some([y4m_number(&value[..colon])?, y4m_number(&value[colon + 1..])?])
+}
+
+/// Returns the parameters of the line at the start of `bytes`, after its signature,
+/// and the length of the line, with its newline.
+pub(super) fn y4m_line<'a>(bytes: &'a [u8], magic: &[u8]) -> Option<(&'a [u8], usize)> {
+    let line = bytes.iter().position(|&b| b == b'\n')?;
+    let params = bytes[..line].strip_prefix(magic)?;
+    is_separated(params).then_some((params, line + 1))
+}
+/// Returns whether the parameters are either none, or each preceded by a space.
+fn is_separated(params: &[u8]) -> bool {
+    params.first().is_none_or(|&b| b == b' ')
+}
+
+/// Writes a parameter of a tag and a decimal number.
+pub(super) fn y4m_write_number(c: &mut EncodeCursor<'_>, tag: u8, n: u32) -> ImageResult<()> {
+    c.bytes(&[b' ', tag])?;
+    write_digits(c, n)
+}
+/// Writes a parameter of a tag and a `num:den` ratio.
+pub(super) fn y4m_write_ratio(
+    c: &mut EncodeCursor<'_>,
+    tag: u8,
+    [num, den]: [u32; 2],
+) -> ImageResult<()> {
+    y4m_write_number(c, tag, num)?;
+    c.byte(b':')?;
+    write_digits(c, den)
+}
+fn write_digits(c: &mut EncodeCursor<'_>, n: u32) -> ImageResult<()> {
+    let (mut digits, mut len, mut n) = ([0; 10], 0, n);
+    loop {
+        digits[9 - len] = b'0' + (n % 10) as u8;
+        (n, len) = (n / 10, len + 1);
+        if n == 0 {
+            return c.bytes(&digits[10 - len..]);
+        }
+    }
+}
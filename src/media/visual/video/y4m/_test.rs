@@ -0,0 +1,106 @@
+// devela/src/media/visual/video/y4m/_test.rs
+
+use crate::{
+    Extent2, ImageError, VideoFrameRate, Y4m, Y4mDecoder, Y4mEncoder, Y4mHeader, Y4mInterlace,
+    YuvFrame, YuvSubsampling,
+};
+
+#[test]
+fn decode_header_params() {
+    let bytes = b"YUV4MPEG2 W3 H2 F25000:1001 It A10:11 C444 XYSCSS=444\nFRAME\n";
+    let (h, len) = Y4m::decode_header(bytes).unwrap();
+    assert_eq![len, bytes.len() - 6];
+    assert_eq![h.extent, Extent2::new([3, 2])];
+    assert_eq![(h.rate.num(), h.rate.den()), (25000, 1001)];
+    assert_eq![h.interlace, Y4mInterlace::TopFirst];
+    assert_eq![h.aspect, [10, 11]];
+    assert_eq![h.subsampling, YuvSubsampling::Yuv444];
+    assert_eq![h.frame_len(), Some(6 + 18)];
+
+    // the colorspace defaults to 4:2:0, and any of its sitings is accepted
+    for c in ["", " C420", " C420mpeg2", " C420paldv"] {
+        let bytes = format!["YUV4MPEG2 W2 H2 F1:1{c}\n"];
+        let (h, _) = Y4m::decode_header(bytes.as_bytes()).unwrap();
+        assert_eq![h.subsampling, YuvSubsampling::Yuv420];
+    }
+}
+
+#[test]
+fn decode_header_errors() {
+    use ImageError::{InvalidData, InvalidImageSize, InvalidMagicNumber, UnsupportedFormat};
+    let header = |s: &str| Y4m::decode_header(s.as_bytes()).map(|(h, _)| h);
+    assert_eq![header("YUV4MPEG W2 H2 F1:1\n"), Err(InvalidMagicNumber)];
+    assert_eq![header("YUV4MPEG2 W2 H2 F1:1"), Err(InvalidData)]; // no newline
+    assert_eq![header("YUV4MPEG2W2 H2 F1:1\n"), Err(InvalidData)];
+    assert_eq![header("YUV4MPEG2 W2 F1:1\n"), Err(InvalidData)];
+    assert_eq![header("YUV4MPEG2 W2 H2\n"), Err(InvalidData)];
+    assert_eq![header("YUV4MPEG2 W2 H2 F1:0\n"), Err(InvalidData)];
+    assert_eq![header("YUV4MPEG2 W-2 H2 F1:1\n"), Err(InvalidData)];
+    assert_eq![header("YUV4MPEG2 W0 H2 F1:1\n"), Err(InvalidImageSize(Some((0, 2))))];
+    assert_eq![header("YUV4MPEG2 W2 H2 F1:1 C420p10\n"), Err(UnsupportedFormat)];
+    assert_eq![header("YUV4MPEG2 W2 H2 F1:1 Iz\n"), Err(InvalidData)];
+}
+
+#[test]
+fn header_round_trip() {
+    let mut h = Y4mHeader::new(Extent2::new([1920, 1080]), VideoFrameRate::NTSC_FILM);
+    h.subsampling = YuvSubsampling::Yuv422;
+    h.interlace = Y4mInterlace::Mixed;
+    h.aspect = [1, 1];
+    let mut out = [0; 64];
+    let len = Y4m::encode_header(&h, &mut out).unwrap();
+    assert_eq![&out[..len], b"YUV4MPEG2 W1920 H1080 F24000:1001 Im A1:1 C422\n"];
+    assert_eq![Y4m::decode_header(&out[..len]), Ok((h, len))];
+    assert![matches![
+        Y4m::encode_header(&h, &mut out[..20]),
+        Err(ImageError::InsufficientBuffer { .. })
+    ]];
+}
+
+#[test]
+fn stream_pieces_match_encoder() {
+    let header = Y4mHeader::new(Extent2::new([3, 1]), VideoFrameRate::FPS_30);
+    let planes: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+    let frame = YuvFrame::new(header.extent, header.subsampling, planes).unwrap();
+
+    let mut whole = [0; 64];
+    let mut e = Y4mEncoder::new(&mut whole, header).unwrap();
+    let spans = [e.add_frame(&frame).unwrap(), e.add_frame(&frame).unwrap()];
+    let len = e.finish();
+
+    let mut pieces = [0; 64];
+    let mut pos = Y4m::encode_header(&header, &mut pieces).unwrap();
+    for span in spans {
+        assert_eq![span.span.offset(), pos];
+        pos += Y4m::encode_frame(&frame, &mut pieces[pos..]).unwrap();
+    }
+    assert_eq![&whole[..len], &pieces[..pos]];
+    assert_eq![spans[1].time, VideoFrameRate::FPS_30.frame_time(1)];
+}
+
+#[test]
+fn decoder_skips_frame_params_and_rejects_truncation() {
+    let mut bytes = b"YUV4MPEG2 W2 H1 F1:1 C444\nFRAME Ixyz\n".to_vec();
+    bytes.extend([9; 6]);
+    bytes.extend(b"FRAME\n");
+    bytes.extend([7; 5]);
+    let mut d = Y4mDecoder::new(&bytes).unwrap();
+    let (span, frame) = d.next_frame().unwrap().unwrap();
+    assert_eq![(span.span.len(), frame.planes()), (17, [&[9, 9][..], &[9, 9], &[9, 9]])];
+    assert_eq![d.next_frame().map(|f| f.is_some()), Err(ImageError::InvalidData)];
+    assert_eq![d.frames_decoded(), 1];
+    d.rewind();
+    assert_eq![d.map(|f| f.is_ok()).collect::<Vec<_>>(), [true, false]];
+}
+
+#[test]
+fn encoder_rejects_mismatched_frames() {
+    let header = Y4mHeader::new(Extent2::new([2, 2]), VideoFrameRate::FPS_25);
+    let mut out = [0; 64];
+    let mut e = Y4mEncoder::new(&mut out, header).unwrap();
+    let wide = YuvFrame::new(Extent2::new([4, 2]), YuvSubsampling::Yuv420, [0; 12]).unwrap();
+    assert_eq![e.add_frame(&wide), Err(ImageError::InvalidImageSize(Some((4, 2))))];
+    let full = YuvFrame::new(header.extent, YuvSubsampling::Yuv444, [0; 12]).unwrap();
+    assert_eq![e.add_frame(&full), Err(ImageError::UnsupportedFormat)];
+    assert![Y4mEncoder::new(&mut [0; 8], header).is_err()];
+}
@@ -0,0 +1,108 @@
+// devela/src/media/visual/video/y4m/decoder.rs
+//
+//! Defines [`Y4mDecoder`].
+//
+
+use super::{Y4M_FRAME, y4m_line};
+use crate::ImageError::{InsufficientBuffer, InvalidData};
+use crate::{ImageFrameSpan, ImageResult, VideoFrameSpan, Y4m, Y4mHeader, YuvFrame, is};
+
+/// A decoded frame, with its span in the stream.
+type DecodedFrame<'a> = (VideoFrameSpan, YuvFrame<&'a [u8]>);
+
+#[doc = crate::_tags!(image codec iterator)]
+/// A decoder for <abbr title="YUV for MPEG-2">YUV4MPEG2</abbr> video streams,
+/// frame by frame.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// Each frame is returned as a [`YuvFrame`] borrowed from the stream,
+/// along with its [`VideoFrameSpan`], timed by the frame rate of the header.
+/// It iterates over the same, stopping after the first error.
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, VideoFrameRate, Y4mDecoder, Y4mEncoder, Y4mHeader, YuvFrame};
+/// let header = Y4mHeader::new(Extent2::new([2, 2]), VideoFrameRate::FPS_50);
+/// let mut y4m = [0; 64];
+/// let mut encoder = Y4mEncoder::new(&mut y4m, header)?;
+/// for luma in [16, 235] {
+///     encoder.add_frame(&YuvFrame::new(header.extent, header.subsampling, [luma; 6])?)?;
+/// }
+/// let len = encoder.finish();
+///
+/// let mut decoder = Y4mDecoder::new(&y4m[..len])?;
+/// assert_eq![decoder.header(), header];
+/// let (span, frame) = decoder.next_frame()?.unwrap();
+/// assert_eq![(span.index(), span.duration.as_millis()), (0, 20)];
+/// assert_eq![frame.planes()[0], [16; 4]];
+/// let (span, frame) = decoder.next_frame()?.unwrap();
+/// assert_eq![(span.index(), span.time.as_millis()), (1, 20)];
+/// assert_eq![frame.planes()[0], [235; 4]];
+/// assert![decoder.next_frame()?.is_none()];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Y4mDecoder<'a> {
+    bytes: &'a [u8],
+    header: Y4mHeader,
+    /// The offset of the first frame.
+    start: usize,
+    /// The offset of the next frame.
+    pos: usize,
+    /// The index of the next frame.
+    index: u32,
+}
+
+impl<'a> Y4mDecoder<'a> {
+    /// Parses the header of the stream.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Y4m::decode_header`].
+    pub fn new(bytes: &'a [u8]) -> ImageResult<Self> {
+        let (header, start) = Y4m::decode_header(bytes)?;
+        Ok(Self { bytes, header, start, pos: start, index: 0 })
+    }
+
+    /// Returns the stream parameters.
+    #[must_use]
+    pub const fn header(&self) -> Y4mHeader {
+        self.header
+    }
+    /// Returns the number of frames already decoded.
+    #[must_use]
+    pub const fn frames_decoded(&self) -> u32 {
+        self.index
+    }
+
+    /// Decodes the next frame, or returns `None` at the end of the stream.
+    ///
+    /// # Errors
+    /// Returns [`InvalidData`] if the frame is malformed or truncated.
+    ///
+    /// [`InvalidData`]: crate::ImageError::InvalidData
+    pub fn next_frame(&mut self) -> ImageResult<Option<DecodedFrame<'a>>> {
+        let bytes = &self.bytes[self.pos..];
+        is![bytes.is_empty(), return Ok(None)];
+        let (_params, line) = y4m_line(bytes, Y4M_FRAME).ok_or(InvalidData)?;
+        let planes = &bytes[line..];
+        let header = self.header;
+        let frame = YuvFrame::new(header.extent, header.subsampling, planes)
+            .map_err(|e| is![matches![e, InsufficientBuffer { .. }], InvalidData, e])?;
+        let len = line + frame.bytes().len();
+        let info = header.frame_info(self.index);
+        let span = ImageFrameSpan::new(info, self.pos, len).ok_or(InvalidData)?;
+        (self.pos, self.index) = (self.pos + len, self.index + 1);
+        Ok(Some((VideoFrameSpan::new(span, header.rate), frame)))
+    }
+    /// Goes back to the first frame.
+    pub fn rewind(&mut self) {
+        (self.pos, self.index) = (self.start, 0);
+    }
+}
+
+impl<'a> Iterator for Y4mDecoder<'a> {
+    type Item = ImageResult<DecodedFrame<'a>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_frame().inspect_err(|_| self.pos = self.bytes.len()).transpose()
+    }
+}
@@ -0,0 +1,135 @@
+// devela/src/media/visual/video/y4m/encoder.rs
+//
+//! Defines [`Y4mEncoder`].
+//
+
+use crate::ImageError::{InvalidData, InvalidImageSize, UnsupportedFormat};
+use crate::{EncodeCursor, ImageFrameSpan, ImageResult, RasterByteSlice, VideoFrameSpan};
+use crate::{Extent2, Y4m, Y4mHeader, YuvFrame, YuvMatrix, YuvSubsampling, is};
+
+#[doc = crate::_tags!(image codec)]
+/// An encoder for <abbr title="YUV for MPEG-2">YUV4MPEG2</abbr> video streams,
+/// over caller-provided storage.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// The header is written on creation, and then each frame, either as a [`YuvFrame`]
+/// or converted from a raster. A stream needs the length of its header, plus
+/// [`Y4mHeader::frame_len`] bytes per frame.
+///
+/// To write a long stream piece by piece, like a capture to a file,
+/// see the methods of [`Y4m`]. The frames of an X11 surface can be captured
+/// from the raster returned by `XSurfaceFrame::raster`.
+///
+/// # Example
+/// Capturing the frames of an animation to hand them to a video encoder:
+/// ```
+/// # use devela::{Extent2, RasterByteSlice, RasterFormat, VideoFrameRate};
+/// # use devela::{Y4mDecoder, Y4mEncoder, Y4mHeader, YuvMatrix};
+/// let extent = Extent2::new([4, 2]);
+/// let header = Y4mHeader::new(extent, VideoFrameRate::FPS_60);
+/// let mut y4m = [0; 128];
+/// let mut encoder = Y4mEncoder::new(&mut y4m, header)?;
+/// let mut bgrx = [0; 4 * 2 * 4];
+/// for shade in [0, 128, 255] {
+///     bgrx.fill(shade);
+///     let surface = RasterByteSlice::dense(RasterFormat::BGRX8, extent, &bgrx).unwrap();
+///     let span = encoder.add_raster(&surface, YuvMatrix::Bt709)?;
+///     assert_eq![span.span.len(), header.frame_len().unwrap()];
+/// }
+/// let len = encoder.finish();
+/// assert_eq![Y4mDecoder::new(&y4m[..len])?.count(), 3];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Y4mEncoder<'o> {
+    c: EncodeCursor<'o>,
+    header: Y4mHeader,
+    /// The index of the next frame.
+    index: u32,
+}
+
+impl<'o> Y4mEncoder<'o> {
+    /// Writes the header of a stream with the given parameters.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Y4m::encode_header`].
+    pub fn new(out: &'o mut [u8], header: Y4mHeader) -> ImageResult<Self> {
+        let mut c = EncodeCursor::new(out, 0);
+        Y4m::write_header(&mut c, &header)?;
+        Ok(Self { c, header, index: 0 })
+    }
+
+    /// Returns the stream parameters.
+    #[must_use]
+    pub const fn header(&self) -> Y4mHeader {
+        self.header
+    }
+
+    /// Writes a frame, returning its span.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`] if the frame extent differs from the stream,
+    /// [`UnsupportedFormat`] if its subsampling does, or [`InsufficientBuffer`]
+    /// if the output is too short.
+    ///
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    /// [`UnsupportedFormat`]: crate::ImageError::UnsupportedFormat
+    /// [`InsufficientBuffer`]: crate::ImageError::InsufficientBuffer
+    pub fn add_frame<B: AsRef<[u8]>>(
+        &mut self,
+        frame: &YuvFrame<B>,
+    ) -> ImageResult<VideoFrameSpan> {
+        self.check_frame(frame.extent(), frame.subsampling())?;
+        let start = self.c.pos();
+        Y4m::write_frame_line(&mut self.c)?;
+        self.c.bytes(frame.bytes())?;
+        self.span(start)
+    }
+
+    /// Writes a frame converted from a raster, returning its span.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`] if the raster extent differs from the stream,
+    /// [`InsufficientBuffer`] if the output is too short,
+    /// or the errors of [`YuvFrame::from_raster`].
+    ///
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    /// [`InsufficientBuffer`]: crate::ImageError::InsufficientBuffer
+    pub fn add_raster<S: AsRef<[u8]>>(
+        &mut self,
+        raster: &RasterByteSlice<S>,
+        matrix: YuvMatrix,
+    ) -> ImageResult<VideoFrameSpan> {
+        self.check_frame(raster.extent(), self.header.subsampling)?;
+        let start = self.c.pos();
+        Y4m::write_raster(&mut self.c, raster, self.header.subsampling, matrix)?;
+        self.span(start)
+    }
+
+    /// Returns the length of the stream.
+    #[must_use]
+    pub fn finish(self) -> usize {
+        self.c.pos()
+    }
+
+    /* helpers */
+
+    /// Checks that a frame of the given `extent` and `subsampling` fits the stream.
+    fn check_frame(&self, extent: Extent2<u32>, subsampling: YuvSubsampling) -> ImageResult<()> {
+        let [w, h] = extent.dim;
+        is![
+            extent != self.header.extent,
+            return Err(InvalidImageSize(Some((w as usize, h as usize))))
+        ];
+        is![subsampling != self.header.subsampling, return Err(UnsupportedFormat)];
+        is![self.index == u32::MAX, return Err(InvalidData)];
+        Ok(())
+    }
+    /// Returns the span of the frame written since `start`, and counts it.
+    fn span(&mut self, start: usize) -> ImageResult<VideoFrameSpan> {
+        let info = self.header.frame_info(self.index);
+        let span = ImageFrameSpan::from_range(info, start, self.c.pos()).ok_or(InvalidData)?;
+        self.index += 1;
+        Ok(VideoFrameSpan::new(span, self.header.rate))
+    }
+}
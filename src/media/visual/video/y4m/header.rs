@@ -0,0 +1,120 @@
+// devela/src/media/visual/video/y4m/header.rs
+//
+//! Defines [`Y4mHeader`], [`Y4mInterlace`].
+//
+
+use crate::{Extent2, ImageFrameInfo, ImageInfo, RasterFormat, VideoFrameRate, YuvSubsampling};
+use crate::{is, unwrap};
+
+#[doc = crate::_tags!(image codec)]
+/// The field order of the frames of a
+/// <abbr title="YUV for MPEG-2">YUV4MPEG2</abbr> stream.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Y4mInterlace {
+    /// Progressive frames, with no fields.
+    #[default]
+    Progressive,
+    /// Interlaced frames, with the top field first.
+    TopFirst,
+    /// Interlaced frames, with the bottom field first.
+    BottomFirst,
+    /// A mix of both, declared by each frame.
+    Mixed,
+}
+impl Y4mInterlace {
+    /// Returns the field order of the value of an `I` parameter.
+    #[must_use]
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        Some(match byte {
+            b'p' => Self::Progressive,
+            b't' => Self::TopFirst,
+            b'b' => Self::BottomFirst,
+            b'm' => Self::Mixed,
+            _ => return None,
+        })
+    }
+    /// Returns the value of its `I` parameter.
+    #[must_use]
+    pub const fn to_byte(self) -> u8 {
+        match self {
+            Self::Progressive => b'p',
+            Self::TopFirst => b't',
+            Self::BottomFirst => b'b',
+            Self::Mixed => b'm',
+        }
+    }
+}
+
+#[doc = crate::_tags!(image codec)]
+/// The parameters of a <abbr title="YUV for MPEG-2">YUV4MPEG2</abbr> stream.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// It's read and written by [`Y4m`][crate::Y4m] at the start of a stream,
+/// and applies to all of its frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Y4mHeader {
+    /// The extent of the frames, in pixels.
+    pub extent: Extent2<u32>,
+    /// The chroma subsampling of the frames.
+    pub subsampling: YuvSubsampling,
+    /// The frame rate.
+    pub rate: VideoFrameRate,
+    /// The field order of the frames.
+    pub interlace: Y4mInterlace,
+    /// The pixel aspect ratio, or `[0, 0]` if unknown.
+    pub aspect: [u32; 2],
+}
+
+impl Y4mHeader {
+    /// Returns the parameters of progressive 4:2:0 frames of `extent` pixels,
+    /// shown at `rate`, with an unknown pixel aspect ratio.
+    #[must_use]
+    pub const fn new(extent: Extent2<u32>, rate: VideoFrameRate) -> Self {
+        Self {
+            extent,
+            subsampling: YuvSubsampling::Yuv420,
+            rate,
+            interlace: Y4mInterlace::Progressive,
+            aspect: [0, 0],
+        }
+    }
+    /// Returns the number of bytes of each frame without parameters,
+    /// or `None` if it overflows.
+    #[must_use]
+    pub const fn frame_len(&self) -> Option<usize> {
+        let planes = unwrap![some? self.subsampling.frame_len(self.extent)];
+        planes.checked_add(super::Y4M_FRAME.len() + 1)
+    }
+    /// Returns the metadata of the frame at `index`.
+    ///
+    /// Its format is [`UNKNOWN`][RasterFormat::UNKNOWN], since frames are planar Y′CbCr.
+    #[must_use]
+    pub const fn frame_info(&self, index: u32) -> ImageFrameInfo {
+        let image = ImageInfo { extent: self.extent, format: RasterFormat::UNKNOWN };
+        ImageFrameInfo { image, index }
+    }
+    /// Returns the value of its `C` parameter.
+    pub(super) const fn colorspace(&self) -> &'static [u8] {
+        match self.subsampling {
+            YuvSubsampling::Yuv420 => b"420jpeg",
+            YuvSubsampling::Yuv422 => b"422",
+            YuvSubsampling::Yuv444 => b"444",
+        }
+    }
+    /// Returns the subsampling of the value of a `C` parameter,
+    /// or `None` if it's not of 8-bit Y′CbCr.
+    pub(super) fn subsampling_of(colorspace: &[u8]) -> Option<YuvSubsampling> {
+        Some(match colorspace {
+            b"420jpeg" | b"420paldv" | b"420mpeg2" | b"420" => YuvSubsampling::Yuv420,
+            b"422" => YuvSubsampling::Yuv422,
+            b"444" => YuvSubsampling::Yuv444,
+            _ => return None,
+        })
+    }
+    /// Returns whether the pixel aspect ratio is known.
+    #[must_use]
+    pub const fn has_aspect(&self) -> bool {
+        is![self.aspect[0] == 0 || self.aspect[1] == 0, false, true]
+    }
+}
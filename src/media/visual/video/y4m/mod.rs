@@ -0,0 +1,27 @@
+// devela/src/media/visual/video/y4m/mod.rs
+//
+//! The YUV4MPEG2 raw video stream format.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // (Y4M_*, y4m_*)
+
+mod decoder; // Y4mDecoder
+mod encoder; // Y4mEncoder
+mod header; // Y4mHeader, Y4mInterlace
+mod namespace; // Y4m
+
+use _helper::*;
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            decoder::*,
+            encoder::*,
+            header::*,
+            namespace::*,
+        };
+    }
+}
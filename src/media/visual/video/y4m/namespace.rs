@@ -0,0 +1,184 @@
+// devela/src/media/visual/video/y4m/namespace.rs
+//
+//! Defines [`Y4m`].
+//
+
+use super::{Y4M_FRAME, Y4M_MAGIC, y4m_line, y4m_number, y4m_ratio};
+use super::{y4m_write_number, y4m_write_ratio};
+use crate::ImageError::{InvalidData, InvalidImageSize, InvalidMagicNumber, UnsupportedFormat};
+use crate::{EncodeCursor, Extent2, ImageResult, RasterByteSlice, VideoFrameRate, Y4mHeader};
+use crate::{Y4mInterlace, YuvFrame, YuvMatrix, YuvSubsampling, is};
+
+#[doc = crate::_tags!(image codec)]
+/// Encoding and decoding for <abbr title="YUV for MPEG-2">YUV4MPEG2</abbr> video streams.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// YUV4MPEG2, or Y4M, stores uncompressed planar [`YuvFrame`]s after a line
+/// of stream parameters, each frame after a line of its own parameters.
+/// It's read and written by most video encoders, which makes it a simple way
+/// to hand them captured frames.
+///
+/// The stream is decoded by [`Y4mDecoder`][crate::Y4mDecoder], and encoded
+/// over a single buffer by [`Y4mEncoder`][crate::Y4mEncoder]. To write a stream
+/// piece by piece, its header and each of its frames can be encoded on their own,
+/// with the methods of this type.
+///
+/// Current limitations:
+/// - Only 8-bit 4:2:0, 4:2:2 and 4:4:4 frames are supported.
+///   The chroma siting of 4:2:0 is read, but written as the one of `420jpeg`.
+/// - Frame parameters are skipped, and none are written.
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, VideoFrameRate, Y4m, Y4mHeader};
+/// let header = Y4mHeader::new(Extent2::new([320, 240]), VideoFrameRate::FPS_30);
+/// let mut out = [0; 64];
+/// let len = Y4m::encode_header(&header, &mut out)?;
+/// assert_eq![&out[..len], b"YUV4MPEG2 W320 H240 F30:1 Ip C420jpeg\n"];
+/// assert_eq![Y4m::decode_header(&out[..len])?, (header, len)];
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Debug)]
+pub struct Y4m;
+
+impl Y4m {
+    /// Decodes the header at the start of `bytes`, returning it along with its length.
+    ///
+    /// Unknown parameters are skipped. The extent and the frame rate are required.
+    ///
+    /// # Errors
+    /// Returns [`InvalidMagicNumber`] if the bytes don't start with a stream signature,
+    /// [`InvalidData`] if the header is malformed, [`InvalidImageSize`] if the extent
+    /// is empty, or [`UnsupportedFormat`] if the colorspace isn't supported.
+    ///
+    /// [`InvalidMagicNumber`]: crate::ImageError::InvalidMagicNumber
+    /// [`InvalidData`]: crate::ImageError::InvalidData
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    /// [`UnsupportedFormat`]: crate::ImageError::UnsupportedFormat
+    pub fn decode_header(bytes: &[u8]) -> ImageResult<(Y4mHeader, usize)> {
+        is![!bytes.starts_with(Y4M_MAGIC), return Err(InvalidMagicNumber)];
+        let (params, len) = y4m_line(bytes, Y4M_MAGIC).ok_or(InvalidData)?;
+        let (mut w, mut h, mut rate) = (None, None, None);
+        let mut header = Y4mHeader::new(Extent2::new([0, 0]), VideoFrameRate::FPS_25);
+        for param in params.split(|&b| b == b' ').filter(|p| !p.is_empty()) {
+            let value = &param[1..];
+            match param[0] {
+                b'W' => w = Some(y4m_number(value).ok_or(InvalidData)?),
+                b'H' => h = Some(y4m_number(value).ok_or(InvalidData)?),
+                b'F' => {
+                    let [num, den] = y4m_ratio(value).ok_or(InvalidData)?;
+                    rate = Some(VideoFrameRate::new(num, den).ok_or(InvalidData)?);
+                }
+                b'I' => {
+                    let byte = value.first().copied().ok_or(InvalidData)?;
+                    header.interlace = Y4mInterlace::from_byte(byte).ok_or(InvalidData)?;
+                }
+                b'A' => header.aspect = y4m_ratio(value).ok_or(InvalidData)?,
+                b'C' => {
+                    header.subsampling =
+                        Y4mHeader::subsampling_of(value).ok_or(UnsupportedFormat)?;
+                }
+                _ => {}
+            }
+        }
+        let (w, h) = (w.ok_or(InvalidData)?, h.ok_or(InvalidData)?);
+        is![w == 0 || h == 0, return Err(InvalidImageSize(Some((w as usize, h as usize))))];
+        header.extent = Extent2::new([w, h]);
+        header.rate = rate.ok_or(InvalidData)?;
+        header.frame_len().ok_or(InvalidImageSize(Some((w as usize, h as usize))))?;
+        Ok((header, len))
+    }
+
+    /// Encodes a header into `out`, returning the number of bytes written.
+    ///
+    /// The pixel aspect ratio is only written when it's known.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`] if the extent is empty or its frames overflow,
+    /// or [`InsufficientBuffer`] if `out` is too short.
+    ///
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    /// [`InsufficientBuffer`]: crate::ImageError::InsufficientBuffer
+    pub fn encode_header(header: &Y4mHeader, out: &mut [u8]) -> ImageResult<usize> {
+        let mut c = EncodeCursor::new(out, 0);
+        Self::write_header(&mut c, header)?;
+        Ok(c.pos())
+    }
+
+    /// Encodes a frame into `out`, returning the number of bytes written.
+    ///
+    /// The frame needs the extent and the subsampling of its stream header.
+    ///
+    /// # Errors
+    /// Returns [`InsufficientBuffer`] if `out` is too short.
+    ///
+    /// [`InsufficientBuffer`]: crate::ImageError::InsufficientBuffer
+    pub fn encode_frame<B: AsRef<[u8]>>(frame: &YuvFrame<B>, out: &mut [u8]) -> ImageResult<usize> {
+        let bytes = frame.bytes();
+        let mut c = EncodeCursor::new(out, Y4M_FRAME.len() + 1 + bytes.len());
+        Self::write_frame_line(&mut c)?;
+        c.bytes(bytes)?;
+        Ok(c.pos())
+    }
+
+    /// Converts a raster into a frame of the given `subsampling`, encoding it into `out`.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns [`InsufficientBuffer`] if `out` is too short,
+    /// or the errors of [`YuvFrame::from_raster`].
+    ///
+    /// [`InsufficientBuffer`]: crate::ImageError::InsufficientBuffer
+    pub fn encode_raster<S: AsRef<[u8]>>(
+        raster: &RasterByteSlice<S>,
+        subsampling: YuvSubsampling,
+        matrix: YuvMatrix,
+        out: &mut [u8],
+    ) -> ImageResult<usize> {
+        let planes = subsampling.frame_len(raster.extent()).unwrap_or(0);
+        let mut c = EncodeCursor::new(out, planes.saturating_add(Y4M_FRAME.len() + 1));
+        Self::write_raster(&mut c, raster, subsampling, matrix)?;
+        Ok(c.pos())
+    }
+
+    /* crate */
+
+    /// Writes a header.
+    pub(crate) fn write_header(c: &mut EncodeCursor<'_>, header: &Y4mHeader) -> ImageResult<()> {
+        let [w, h] = header.extent.dim;
+        let size = InvalidImageSize(Some((w as usize, h as usize)));
+        is![w == 0 || h == 0, return Err(size)];
+        header.frame_len().ok_or(size)?;
+        c.bytes(Y4M_MAGIC)?;
+        y4m_write_number(c, b'W', w)?;
+        y4m_write_number(c, b'H', h)?;
+        y4m_write_ratio(c, b'F', [header.rate.num(), header.rate.den()])?;
+        c.bytes(&[b' ', b'I', header.interlace.to_byte()])?;
+        if header.has_aspect() {
+            y4m_write_ratio(c, b'A', header.aspect)?;
+        }
+        c.bytes(b" C")?;
+        c.bytes(header.colorspace())?;
+        c.byte(b'\n')
+    }
+    /// Writes a frame converted from a raster.
+    pub(crate) fn write_raster<S: AsRef<[u8]>>(
+        c: &mut EncodeCursor<'_>,
+        raster: &RasterByteSlice<S>,
+        subsampling: YuvSubsampling,
+        matrix: YuvMatrix,
+    ) -> ImageResult<()> {
+        let extent = raster.extent();
+        let [w, h] = extent.dim;
+        let size = InvalidImageSize(Some((w as usize, h as usize)));
+        let len = subsampling.frame_len(extent).ok_or(size)?;
+        Self::write_frame_line(c)?;
+        YuvFrame::new(extent, subsampling, c.reserve(len)?)?.from_raster(raster, matrix)
+    }
+    /// Writes the line that starts a frame.
+    pub(crate) fn write_frame_line(c: &mut EncodeCursor<'_>) -> ImageResult<()> {
+        c.bytes(Y4M_FRAME)?;
+        c.byte(b'\n')
+    }
+}
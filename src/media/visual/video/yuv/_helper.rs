@@ -0,0 +1,25 @@
+// devela/src/media/visual/video/yuv/_helper.rs
+//
+//! Defines (`rgb_layout`), (`rgb_row_start`).
+//
+
+use crate::{Boundary1d, RasterFormat, RasterLayout, is};
+
+/// Returns the bytes per pixel of an 8-bit RGB raster format, the offsets of red,
+/// green and blue, and the offset of its alpha or padding, if any.
+pub(super) const fn rgb_layout(format: RasterFormat) -> Option<(usize, [usize; 3], Option<usize>)> {
+    Some(match format {
+        RasterFormat::RGB8 => (3, [0, 1, 2], None),
+        RasterFormat::RGBA8 | RasterFormat::RGBX8 => (4, [0, 1, 2], Some(3)),
+        RasterFormat::BGR8 => (3, [2, 1, 0], None),
+        RasterFormat::BGRA8 | RasterFormat::BGRX8 => (4, [2, 1, 0], Some(3)),
+        _ => return None,
+    })
+}
+
+/// Returns the offset of the row at `y`, counting from the top, of a valid layout.
+pub(super) const fn rgb_row_start(layout: RasterLayout, y: usize) -> usize {
+    let upward = matches![layout.row_start, Boundary1d::Lower];
+    let line = is![upward, layout.extent.dim[1] as usize - 1 - y, y];
+    line * layout.bytes_per_line as usize
+}
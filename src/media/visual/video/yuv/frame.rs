@@ -0,0 +1,253 @@
+// devela/src/media/visual/video/yuv/frame.rs
+//
+//! Defines [`YuvFrame`], [`YuvSubsampling`].
+//
+
+use super::_helper::{rgb_layout, rgb_row_start};
+use crate::ImageError::{InsufficientBuffer, InvalidImageSize, UnsupportedFormat};
+use crate::{Extent2, ImageResult, RasterBufBytes, RasterByteSlice, RasterViewBytes, YuvMatrix};
+use crate::{is, unwrap};
+
+#[doc = crate::_tags!(color)]
+/// The resolution of the chroma planes of a [`YuvFrame`], relative to its luma plane.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// Odd extents are rounded up, so that the last chroma sample
+/// of a row or a column covers fewer luma samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum YuvSubsampling {
+    /// 4:2:0, chroma at half the width and half the height.
+    #[default]
+    Yuv420,
+    /// 4:2:2, chroma at half the width.
+    Yuv422,
+    /// 4:4:4, chroma at the full resolution.
+    Yuv444,
+}
+
+impl YuvSubsampling {
+    /// Returns the horizontal and vertical number of luma samples of a chroma sample.
+    #[must_use]
+    pub const fn factors(self) -> [u32; 2] {
+        match self {
+            YuvSubsampling::Yuv420 => [2, 2],
+            YuvSubsampling::Yuv422 => [2, 1],
+            YuvSubsampling::Yuv444 => [1, 1],
+        }
+    }
+    /// Returns the extent of each chroma plane of a frame of the given `extent`.
+    pub const fn chroma_extent(self, extent: Extent2<u32>) -> Extent2<u32> {
+        let ([w, h], [fx, fy]) = (extent.dim, self.factors());
+        Extent2::new([w.div_ceil(fx), h.div_ceil(fy)])
+    }
+    /// Returns the number of bytes of the three planes of a frame of the given `extent`,
+    /// or `None` if it overflows.
+    #[must_use]
+    pub const fn frame_len(self, extent: Extent2<u32>) -> Option<usize> {
+        let ([w, h], [cw, ch]) = (extent.dim, self.chroma_extent(extent).dim);
+        let luma = unwrap![some?(w as usize).checked_mul(h as usize)];
+        let chroma = unwrap![some?(cw as usize).checked_mul(ch as usize)];
+        luma.checked_add(unwrap![some? chroma.checked_mul(2)])
+    }
+}
+
+#[doc = crate::_tags!(image color)]
+/// A planar Y′CbCr video frame of 8-bit samples.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// The luma plane is followed by the blue-difference and the red-difference
+/// chroma planes, each of them dense and top-down, as in YUV4MPEG2
+/// and the I420 layout.
+///
+/// It converts from and into byte rasters of [`RGB8`], [`RGBA8`], [`RGBX8`],
+/// [`BGR8`], [`BGRA8`] and [`BGRX8`] formats, of any row order and padding.
+/// Subsampled chroma is the average of the colors it covers, and is replicated
+/// back to each of them. Alpha is ignored, and written as opaque.
+///
+/// [`RGB8`]: crate::RasterFormat::RGB8
+/// [`RGBA8`]: crate::RasterFormat::RGBA8
+/// [`RGBX8`]: crate::RasterFormat::RGBX8
+/// [`BGR8`]: crate::RasterFormat::BGR8
+/// [`BGRA8`]: crate::RasterFormat::BGRA8
+/// [`BGRX8`]: crate::RasterFormat::BGRX8
+///
+/// # Example
+/// ```
+/// # use devela::{Extent2, RasterByteSlice, RasterFormat, YuvFrame, YuvMatrix, YuvSubsampling};
+/// let extent = Extent2::new([2, 2]);
+/// let rgb = [255, 0, 0, 255, 0, 0, 0, 0, 255, 0, 0, 255];
+/// let raster = RasterByteSlice::dense(RasterFormat::RGB8, extent, &rgb).unwrap();
+///
+/// let mut yuv = YuvFrame::new(extent, YuvSubsampling::Yuv420, [0; 6])?;
+/// yuv.from_raster(&raster, YuvMatrix::Bt601)?;
+/// assert_eq![yuv.planes(), [&[81, 81, 41, 41][..], &[165], &[175]]];
+///
+/// let mut back = [0; 12];
+/// let mut out = RasterByteSlice::dense_mut(RasterFormat::RGB8, extent, &mut back).unwrap();
+/// yuv.to_raster(&mut out, YuvMatrix::Bt601)?;
+/// assert_eq![back[..3], [151, 23, 150]]; // the luma of red, the chroma shared with blue
+/// # Ok::<(), devela::ImageError>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct YuvFrame<B> {
+    extent: Extent2<u32>,
+    subsampling: YuvSubsampling,
+    bytes: B,
+}
+
+impl<B> YuvFrame<B> {
+    /// Returns the extent of the luma plane.
+    pub const fn extent(&self) -> Extent2<u32> {
+        self.extent
+    }
+    /// Returns the chroma subsampling.
+    pub const fn subsampling(&self) -> YuvSubsampling {
+        self.subsampling
+    }
+    /// Returns the underlying storage.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+    /// Returns the extent of the luma plane, the width of the chroma planes,
+    /// and the subsampling factors.
+    fn dims(&self) -> ([usize; 2], usize, [usize; 2]) {
+        let [cw, _] = self.subsampling.chroma_extent(self.extent).dim;
+        let to_usize = |[a, b]: [u32; 2]| [a as usize, b as usize];
+        (to_usize(self.extent.dim), cw as usize, to_usize(self.subsampling.factors()))
+    }
+    /// Returns the length of the planes, and the length of the luma plane.
+    fn lens(&self) -> [usize; 2] {
+        let [w, h] = self.extent.dim;
+        let len = self.subsampling.frame_len(self.extent).unwrap_or(0);
+        [len, w as usize * h as usize]
+    }
+}
+
+impl<B: AsRef<[u8]>> YuvFrame<B> {
+    /// Returns a frame over the start of `bytes`.
+    ///
+    /// # Errors
+    /// Returns [`InvalidImageSize`] if the length of the frame overflows,
+    /// or [`InsufficientBuffer`] if `bytes` is shorter.
+    ///
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    /// [`InsufficientBuffer`]: crate::ImageError::InsufficientBuffer
+    pub fn new(extent: Extent2<u32>, subsampling: YuvSubsampling, bytes: B) -> ImageResult<Self> {
+        let [w, h] = extent.dim;
+        let size = InvalidImageSize(Some((w as usize, h as usize)));
+        let needed = subsampling.frame_len(extent).ok_or(size)?;
+        let available = bytes.as_ref().len();
+        is![available < needed, return Err(InsufficientBuffer { needed, available })];
+        Ok(Self { extent, subsampling, bytes })
+    }
+    /// Returns the bytes of the three planes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes.as_ref()[..self.lens()[0]]
+    }
+    /// Returns the luma, blue-difference and red-difference planes.
+    pub fn planes(&self) -> [&[u8]; 3] {
+        let [len, luma] = self.lens();
+        let (y, chroma) = self.bytes.as_ref()[..len].split_at(luma);
+        let (u, v) = chroma.split_at(chroma.len() / 2);
+        [y, u, v]
+    }
+
+    /// Converts the frame into a byte raster of the same extent.
+    ///
+    /// # Errors
+    /// Returns [`UnsupportedFormat`] if the raster format isn't supported,
+    /// or [`InvalidImageSize`] if its extent differs or its bytes are shorter than its layout.
+    ///
+    /// [`UnsupportedFormat`]: crate::ImageError::UnsupportedFormat
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    pub fn to_raster<D>(&self, dst: &mut RasterByteSlice<D>, matrix: YuvMatrix) -> ImageResult<()>
+    where
+        D: AsRef<[u8]> + AsMut<[u8]>,
+    {
+        let (layout, format) = (dst.layout(), dst.format());
+        let (n, rgb, opaque) = rgb_layout(format).ok_or(UnsupportedFormat)?;
+        self.check_raster(layout.extent, layout.min_len_bytes(), dst.raster_bytes().len())?;
+        let ([w, h], cw, [fx, fy]) = self.dims();
+        let [yp, up, vp] = self.planes();
+        let bytes = dst.raster_bytes_mut();
+        for y in 0..h {
+            let row = &mut bytes[rgb_row_start(layout, y)..][..w * n];
+            let chroma = y / fy * cw;
+            for (x, px) in row.chunks_exact_mut(n).enumerate() {
+                let c = chroma + x / fx;
+                let color = matrix.yuv_to_rgb([yp[y * w + x], up[c], vp[c]]);
+                (px[rgb[0]], px[rgb[1]], px[rgb[2]]) = (color[0], color[1], color[2]);
+                if let Some(a) = opaque {
+                    px[a] = 255;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that a raster has the frame extent, and enough bytes for its layout.
+    fn check_raster(
+        &self,
+        extent: Extent2<u32>,
+        needed: Option<usize>,
+        len: usize,
+    ) -> ImageResult<()> {
+        let [w, h] = self.extent.dim;
+        let size = InvalidImageSize(Some((w as usize, h as usize)));
+        is![extent != self.extent || needed.is_none_or(|needed| len < needed), Err(size), Ok(())]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> YuvFrame<B> {
+    /// Returns the luma, blue-difference and red-difference planes, exclusively.
+    pub fn planes_mut(&mut self) -> [&mut [u8]; 3] {
+        let [len, luma] = self.lens();
+        let (y, chroma) = self.bytes.as_mut()[..len].split_at_mut(luma);
+        let (u, v) = chroma.split_at_mut(chroma.len() / 2);
+        [y, u, v]
+    }
+
+    /// Converts a byte raster of the same extent into the frame.
+    ///
+    /// # Errors
+    /// Returns [`UnsupportedFormat`] if the raster format isn't supported,
+    /// or [`InvalidImageSize`] if its extent differs or its bytes are shorter than its layout.
+    ///
+    /// [`UnsupportedFormat`]: crate::ImageError::UnsupportedFormat
+    /// [`InvalidImageSize`]: crate::ImageError::InvalidImageSize
+    pub fn from_raster<S>(&mut self, src: &RasterByteSlice<S>, matrix: YuvMatrix) -> ImageResult<()>
+    where
+        S: AsRef<[u8]>,
+    {
+        let (layout, format) = (src.layout(), src.format());
+        let (n, rgb, _) = rgb_layout(format).ok_or(UnsupportedFormat)?;
+        let bytes = src.raster_bytes();
+        self.check_raster(layout.extent, layout.min_len_bytes(), bytes.len())?;
+        let ([w, h], cw, [fx, fy]) = self.dims();
+        let ch = h.div_ceil(fy);
+        let color = |x: usize, y: usize| {
+            let px = &bytes[rgb_row_start(layout, y) + x * n..][..n];
+            [px[rgb[0]] as i32, px[rgb[1]] as i32, px[rgb[2]] as i32]
+        };
+        let [yp, up, vp] = self.planes_mut();
+        for y in 0..h {
+            for x in 0..w {
+                yp[y * w + x] = matrix.luma(color(x, y));
+            }
+        }
+        for cy in 0..ch {
+            for cx in 0..cw {
+                let (mut sum, mut count) = ([0; 3], 0);
+                for y in cy * fy..((cy + 1) * fy).min(h) {
+                    for x in cx * fx..((cx + 1) * fx).min(w) {
+                        let c = color(x, y);
+                        (sum[0], sum[1], sum[2]) = (sum[0] + c[0], sum[1] + c[1], sum[2] + c[2]);
+                        count += 1;
+                    }
+                }
+                [up[cy * cw + cx], vp[cy * cw + cx]] = matrix.chroma(sum, count);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+// devela/src/media/visual/video/yuv/matrix.rs
+//
+//! Defines [`YuvMatrix`], (`YuvCoefficients`).
+//
+
+use crate::is;
+
+#[doc = crate::_tags!(color)]
+/// The coefficients that convert between RGB and Y′CbCr, the YUV of digital video.
+#[doc = crate::_doc_meta!{location("media/visual/video")}]
+///
+/// Conversions use the studio range of 8-bit video, where luma goes from 16 to 235
+/// and chroma from 16 to 240, centered at 128. Colors are converted as they're stored,
+/// without undoing their transfer function.
+///
+/// # Example
+/// ```
+/// # use devela::YuvMatrix;
+/// assert_eq![YuvMatrix::Bt601.rgb_to_yuv([255, 255, 255]), [235, 128, 128]];
+/// assert_eq![YuvMatrix::Bt709.rgb_to_yuv([255, 0, 0]), [63, 102, 240]];
+/// assert_eq![YuvMatrix::Bt709.yuv_to_rgb([16, 128, 128]), [0, 0, 0]];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum YuvMatrix {
+    /// ITU-R BT.601, of standard definition video.
+    #[default]
+    Bt601,
+    /// ITU-R BT.709, of high definition video.
+    Bt709,
+}
+
+impl YuvMatrix {
+    /// Returns the weights of red and blue in luma, green making up the rest.
+    #[must_use]
+    pub const fn luma_weights(self) -> [f64; 2] {
+        match self {
+            YuvMatrix::Bt601 => [0.299, 0.114],
+            YuvMatrix::Bt709 => [0.2126, 0.0722],
+        }
+    }
+
+    /// Returns the Y′CbCr samples of an RGB color.
+    #[must_use]
+    pub const fn rgb_to_yuv(self, rgb: [u8; 3]) -> [u8; 3] {
+        let rgb = [rgb[0] as i32, rgb[1] as i32, rgb[2] as i32];
+        let [cb, cr] = self.chroma(rgb, 1);
+        [self.luma(rgb), cb, cr]
+    }
+    /// Returns the RGB color of some Y′CbCr samples, clamped to the RGB cube.
+    #[must_use]
+    pub const fn yuv_to_rgb(self, yuv: [u8; 3]) -> [u8; 3] {
+        let k = self.coefficients();
+        let y = (yuv[0] as i32 - 16) * k.inv_y + ROUND;
+        let (cb, cr) = (yuv[1] as i32 - 128, yuv[2] as i32 - 128);
+        [
+            clamp_u8((y + k.r_cr * cr) >> SHIFT),
+            clamp_u8((y - k.g_cb * cb - k.g_cr * cr) >> SHIFT),
+            clamp_u8((y + k.b_cb * cb) >> SHIFT),
+        ]
+    }
+
+    /* crate */
+
+    /// Returns the luma of an RGB color.
+    pub(crate) const fn luma(self, rgb: [i32; 3]) -> u8 {
+        let k = self.coefficients();
+        (16 + ((k.y[0] * rgb[0] + k.y[1] * rgb[1] + k.y[2] * rgb[2] + ROUND) >> SHIFT)) as u8
+    }
+    /// Returns the chroma of the average of `count` RGB colors, given their `sum`.
+    pub(crate) const fn chroma(self, sum: [i32; 3], count: i32) -> [u8; 2] {
+        const fn weigh(c: [i32; 3], sum: [i32; 3], count: i32) -> u8 {
+            let den = count as i64 * (1 << SHIFT);
+            let v = c[0] as i64 * sum[0] as i64
+                + c[1] as i64 * sum[1] as i64
+                + c[2] as i64 * sum[2] as i64;
+            clamp_u8(128 + (v + den / 2).div_euclid(den) as i32)
+        }
+        let k = self.coefficients();
+        [weigh(k.cb, sum, count), weigh(k.cr, sum, count)]
+    }
+
+    /* private */
+
+    const fn coefficients(self) -> &'static YuvCoefficients {
+        match self {
+            YuvMatrix::Bt601 => &YuvCoefficients::BT601,
+            YuvMatrix::Bt709 => &YuvCoefficients::BT709,
+        }
+    }
+}
+
+/// The fractional bits of the fixed point coefficients.
+const SHIFT: u32 = 16;
+/// Half a unit, to round to the nearest integer.
+const ROUND: i32 = 1 << (SHIFT - 1);
+
+const fn clamp_u8(v: i32) -> u8 {
+    is![v < 0, 0, is![v > 255, 255, v as u8]]
+}
+
+/// The fixed point coefficients of a [`YuvMatrix`], scaled to the studio range.
+#[derive(Debug)]
+struct YuvCoefficients {
+    /// The weights of RGB in luma.
+    y: [i32; 3],
+    /// The weights of RGB in blue-difference chroma.
+    cb: [i32; 3],
+    /// The weights of RGB in red-difference chroma.
+    cr: [i32; 3],
+    /// The scale of luma back to RGB.
+    inv_y: i32,
+    /// The weights of chroma back to RGB.
+    r_cr: i32,
+    g_cb: i32,
+    g_cr: i32,
+    b_cb: i32,
+}
+impl YuvCoefficients {
+    const BT601: Self = Self::new(YuvMatrix::Bt601.luma_weights());
+    const BT709: Self = Self::new(YuvMatrix::Bt709.luma_weights());
+
+    const fn new([kr, kb]: [f64; 2]) -> Self {
+        const fn fix(v: f64) -> i32 {
+            (v * (1 << SHIFT) as f64 + is![v < 0.0, -0.5, 0.5]) as i32
+        }
+        let kg = 1.0 - kr - kb;
+        let (ys, cs) = (219.0 / 255.0, 224.0 / 255.0);
+        let (cb_den, cr_den) = (2.0 * (1.0 - kb), 2.0 * (1.0 - kr));
+        Self {
+            y: [fix(kr * ys), fix(kg * ys), fix(kb * ys)],
+            cb: [fix(-kr / cb_den * cs), fix(-kg / cb_den * cs), fix(0.5 * cs)],
+            cr: [fix(0.5 * cs), fix(-kg / cr_den * cs), fix(-kb / cr_den * cs)],
+            inv_y: fix(1.0 / ys),
+            r_cr: fix(cr_den / cs),
+            g_cb: fix(cb_den * kb / kg / cs),
+            g_cr: fix(cr_den * kr / kg / cs),
+            b_cb: fix(cb_den / cs),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// devela/src/media/visual/video/yuv/mod.rs
+//
+//! Planar Y′CbCr frames, and their conversion from and into RGB.
+//
+
+mod _helper; // (rgb_layout, rgb_row_start)
+
+mod frame; // YuvFrame, YuvSubsampling
+mod matrix; // YuvMatrix
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            frame::*,
+            matrix::*,
+        };
+    }
+}
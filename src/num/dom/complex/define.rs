@@ -0,0 +1,104 @@
+// devela/src/num/dom/complex/define.rs
+//
+//! Defines [`Complex`].
+//
+
+use crate::Float;
+
+#[doc = crate::_tags!(num)]
+/// A complex number in rectangular form.
+#[doc = crate::_doc_meta!{location("num/dom")}]
+///
+/// Most methods are *const* and implemented for `f32` and `f64`.
+///
+/// # Example
+/// ```
+/// # use devela::Complex;
+/// let a = Complex::new(1.0_f32, 2.0);
+/// let b = Complex::new(3.0, -1.0);
+/// assert_eq![a.mul(b), Complex::new(5.0, 5.0)];
+/// assert_eq![a * b.conj(), Complex::new(1.0, 7.0)];
+/// assert_eq![Complex::new(3.0_f64, 4.0).norm(), 5.0];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Hash)]
+pub struct Complex<T> {
+    /// The real part.
+    pub re: T,
+    /// The imaginary part.
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    /// Creates a complex number from its real and imaginary parts.
+    #[must_use]
+    pub const fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+
+macro_rules! impl_complex {
+    () => { impl_complex![f32, f64]; };
+    ($($f:ty),+) => { $( impl_complex![@$f]; )+ };
+    (@$f:ty) => {
+        #[rustfmt::skip]
+        impl Complex<$f> {
+            /// The additive identity.
+            pub const ZERO: Self = Self::new(0.0, 0.0);
+            /// The multiplicative identity.
+            pub const ONE: Self = Self::new(1.0, 0.0);
+            /// The imaginary unit.
+            pub const I: Self = Self::new(0.0, 1.0);
+
+            /// Creates a complex number with only a real part.
+            #[must_use]
+            pub const fn from_re(re: $f) -> Self { Self::new(re, 0.0) }
+
+            /// Creates a complex number from its magnitude and phase in radians.
+            #[must_use]
+            pub const fn from_polar(norm: $f, arg: $f) -> Self {
+                let (sin, cos) = Float(arg).sin_cos_minimax();
+                Self::new(norm * cos.0, norm * sin.0)
+            }
+
+            /// Returns the complex conjugate.
+            #[must_use]
+            pub const fn conj(self) -> Self { Self::new(self.re, -self.im) }
+
+            /// Returns the sum of `self` and `other`.
+            #[must_use]
+            pub const fn add(self, other: Self) -> Self {
+                Self::new(self.re + other.re, self.im + other.im)
+            }
+            /// Returns the difference of `self` and `other`.
+            #[must_use]
+            pub const fn sub(self, other: Self) -> Self {
+                Self::new(self.re - other.re, self.im - other.im)
+            }
+            /// Returns the product of `self` and `other`.
+            #[must_use]
+            pub const fn mul(self, other: Self) -> Self {
+                Self::new(self.re * other.re - self.im * other.im,
+                    self.re * other.im + self.im * other.re)
+            }
+            /// Returns the negation of both parts.
+            #[must_use]
+            pub const fn neg(self) -> Self { Self::new(-self.re, -self.im) }
+            /// Returns both parts multiplied by `factor`.
+            #[must_use]
+            pub const fn scale(self, factor: $f) -> Self {
+                Self::new(self.re * factor, self.im * factor)
+            }
+            /// Returns `self` multiplied by the imaginary unit.
+            #[must_use]
+            pub const fn mul_i(self) -> Self { Self::new(-self.im, self.re) }
+
+            /// Returns the squared magnitude, `re² + im²`.
+            #[must_use]
+            pub const fn norm_sqr(self) -> $f { self.re * self.re + self.im * self.im }
+            /// Returns the magnitude.
+            #[must_use]
+            pub const fn norm(self) -> $f { Float(self.norm_sqr()).sqrt_nr().0 }
+        }
+    };
+}
+impl_complex!();
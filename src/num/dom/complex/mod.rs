@@ -0,0 +1,13 @@
+// devela/src/num/dom/complex/mod.rs
+//
+//! Complex numbers.
+//
+
+mod define; // Complex
+mod ops; // core::ops impls
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::define::*;
+    }
+}
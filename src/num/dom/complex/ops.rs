@@ -0,0 +1,43 @@
+// devela/src/num/dom/complex/ops.rs
+//
+//! Implements the arithmetic operators for [`Complex`].
+//
+
+use crate::{Add, AddAssign, Complex, Mul, MulAssign, Neg, Sub, SubAssign};
+
+macro_rules! impl_complex_ops {
+    () => { impl_complex_ops![f32, f64]; };
+    ($($f:ty),+) => { $( impl_complex_ops![@$f]; )+ };
+    (@$f:ty) => {
+        impl Add for Complex<$f> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self { Complex::<$f>::add(self, other) }
+        }
+        impl Sub for Complex<$f> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self { Complex::<$f>::sub(self, other) }
+        }
+        impl Mul for Complex<$f> {
+            type Output = Self;
+            fn mul(self, other: Self) -> Self { Complex::<$f>::mul(self, other) }
+        }
+        impl Mul<$f> for Complex<$f> {
+            type Output = Self;
+            fn mul(self, factor: $f) -> Self { self.scale(factor) }
+        }
+        impl Neg for Complex<$f> {
+            type Output = Self;
+            fn neg(self) -> Self { Complex::<$f>::neg(self) }
+        }
+        impl AddAssign for Complex<$f> {
+            fn add_assign(&mut self, other: Self) { *self = Complex::<$f>::add(*self, other); }
+        }
+        impl SubAssign for Complex<$f> {
+            fn sub_assign(&mut self, other: Self) { *self = Complex::<$f>::sub(*self, other); }
+        }
+        impl MulAssign for Complex<$f> {
+            fn mul_assign(&mut self, other: Self) { *self = Complex::<$f>::mul(*self, other); }
+        }
+    };
+}
+impl_complex_ops!();
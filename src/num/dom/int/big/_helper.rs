@@ -0,0 +1,120 @@
+// devela/src/num/dom/int/big/_helper.rs
+//
+//! Primality, digit and operator helpers shared by the big integers.
+//
+
+use super::_limbs as limbs;
+use crate::is;
+
+/// The first 13 primes, used as Miller-Rabin bases.
+///
+/// Testing them all is deterministic for every `n < 3_317_044_064_679_887_385_961_981`.
+pub(super) const BASES: [u64; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// The product of the odd primes from 3 to 47, for trial division in a single step.
+pub(super) const SMALL_PRODUCT: u64 =
+    3 * 5 * 7 * 11 * 13 * 17 * 19 * 23 * 29 * 31 * 37 * 41 * 43 * 47;
+
+/// Returns `true` if `r` shares no factor with [`SMALL_PRODUCT`].
+pub(super) const fn coprime_small(r: u64) -> bool {
+    let (mut a, mut b) = (SMALL_PRODUCT, r);
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a == 1
+}
+
+/// The ASCII digits for radixes up to 36.
+pub(super) const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Returns the value of the ASCII digit `b` in `radix`, if it's valid.
+pub(super) const fn digit_value(b: u8, radix: u32) -> Option<u32> {
+    let d = match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'z' => b - b'a' + 10,
+        b'A'..=b'Z' => b - b'A' + 10,
+        _ => return None,
+    } as u32;
+    is![d < radix, Some(d), None]
+}
+
+/// Returns the maximum power of `radix` that fits in a `u64`, and its exponent.
+pub(super) const fn radix_chunk(radix: u32) -> (u64, usize) {
+    let (mut pow, mut exp) = (radix as u64, 1);
+    while let Some(next) = pow.checked_mul(radix as u64) {
+        pow = next;
+        exp += 1;
+    }
+    (pow, exp)
+}
+
+/// Returns the number of digits of the magnitude `a` in `radix`, consuming its value.
+pub(super) const fn count_digits(a: &mut [u64], radix: u32) -> usize {
+    let (pow, exp) = radix_chunk(radix);
+    let mut count = 0;
+    loop {
+        let mut r = limbs::div_small(a, pow);
+        if limbs::is_zero(a) {
+            loop {
+                count += 1;
+                r /= radix as u64;
+                is![r == 0, return count];
+            }
+        }
+        count += exp;
+    }
+}
+/// Writes the digits of the magnitude `a` in `radix` into `buf`, right-aligned before `end`,
+/// consuming its value.
+///
+/// Returns the number of digits written. `buf` must have room for all of them.
+pub(super) const fn write_digits(a: &mut [u64], buf: &mut [u8], end: usize, radix: u32) -> usize {
+    let (pow, exp) = radix_chunk(radix);
+    let mut i = end;
+    loop {
+        let mut r = limbs::div_small(a, pow);
+        let last = limbs::is_zero(a);
+        let mut k = 0;
+        while k < exp && (!last || r != 0 || k == 0) {
+            i -= 1;
+            buf[i] = DIGITS[(r % radix as u64) as usize];
+            r /= radix as u64;
+            k += 1;
+        }
+        is![last, return end - i];
+    }
+}
+
+/// Implements the arithmetic operators for every combination of owned and borrowed operands,
+/// by forwarding to private associated functions over references.
+#[cfg(feature = "alloc")]
+macro_rules! impl_big_ops {
+    ($T:ty: $($trait:ident, $fn:ident, $assign:ident, $assign_fn:ident, $impl:ident);+ $(;)?) => {$(
+        impl core::ops::$trait<&$T> for &$T {
+            type Output = $T;
+            fn $fn(self, rhs: &$T) -> $T { <$T>::$impl(self, rhs) }
+        }
+        impl core::ops::$trait<$T> for &$T {
+            type Output = $T;
+            fn $fn(self, rhs: $T) -> $T { <$T>::$impl(self, &rhs) }
+        }
+        impl core::ops::$trait<&$T> for $T {
+            type Output = $T;
+            fn $fn(self, rhs: &$T) -> $T { <$T>::$impl(&self, rhs) }
+        }
+        impl core::ops::$trait<$T> for $T {
+            type Output = $T;
+            fn $fn(self, rhs: $T) -> $T { <$T>::$impl(&self, &rhs) }
+        }
+        impl core::ops::$assign<&$T> for $T {
+            fn $assign_fn(&mut self, rhs: &$T) { *self = <$T>::$impl(self, rhs); }
+        }
+        impl core::ops::$assign<$T> for $T {
+            fn $assign_fn(&mut self, rhs: $T) { *self = <$T>::$impl(self, &rhs); }
+        }
+    )+};
+}
+#[cfg(feature = "alloc")]
+pub(super) use impl_big_ops;
@@ -0,0 +1,298 @@
+// devela/src/num/dom/int/big/_limbs.rs
+//
+//! Algorithms over little-endian slices of `u64` limbs,
+//! shared by the fixed and the allocated big integers.
+//
+// TOC
+// - len, bit_len, cmp, is_zero
+// - add_assign, sub_assign, mul_small, div_small
+// - shl_bits, shr_bits
+// - mul_acc
+// - div_rem
+// - mont_inv, mont_mul
+
+use crate::{Ordering, is, whilst};
+
+/// The number of bits per limb.
+pub(super) const BITS: u32 = 64;
+
+/* queries */
+
+/// Returns the number of significant limbs, ignoring the most significant zeros.
+pub(super) const fn len(a: &[u64]) -> usize {
+    let mut n = a.len();
+    while n > 0 && a[n - 1] == 0 {
+        n -= 1;
+    }
+    n
+}
+/// Returns the number of significant bits.
+pub(super) const fn bit_len(a: &[u64]) -> u32 {
+    let n = len(a);
+    is![n == 0, return 0];
+    (n as u32 - 1) * BITS + (BITS - a[n - 1].leading_zeros())
+}
+/// Returns `true` if every limb is zero.
+pub(super) const fn is_zero(a: &[u64]) -> bool {
+    len(a) == 0
+}
+/// Compares two magnitudes of possibly different lengths.
+pub(super) const fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    let (la, lb) = (len(a), len(b));
+    is![la != lb, return is![la < lb, Ordering::Less, Ordering::Greater]];
+    let mut i = la;
+    while i > 0 {
+        i -= 1;
+        is![a[i] != b[i], return is![a[i] < b[i], Ordering::Less, Ordering::Greater]];
+    }
+    Ordering::Equal
+}
+
+/* add, sub and small operands */
+
+/// Adds `b` to `a`, returning the carry out of `a`.
+///
+/// `b` must not be longer than `a`.
+pub(super) const fn add_assign(a: &mut [u64], b: &[u64]) -> bool {
+    let mut carry = false;
+    whilst! { i in 0..a.len(); {
+        let bi = is![i < b.len(), b[i], 0];
+        is![i >= b.len() && !carry, return false];
+        let (s1, c1) = a[i].overflowing_add(bi);
+        let (s2, c2) = s1.overflowing_add(carry as u64);
+        a[i] = s2;
+        carry = c1 || c2;
+    }}
+    carry
+}
+/// Subtracts `b` from `a`, returning the borrow out of `a`.
+///
+/// `b` must not be longer than `a`.
+pub(super) const fn sub_assign(a: &mut [u64], b: &[u64]) -> bool {
+    let mut borrow = false;
+    whilst! { i in 0..a.len(); {
+        let bi = is![i < b.len(), b[i], 0];
+        is![i >= b.len() && !borrow, return false];
+        let (d1, b1) = a[i].overflowing_sub(bi);
+        let (d2, b2) = d1.overflowing_sub(borrow as u64);
+        a[i] = d2;
+        borrow = b1 || b2;
+    }}
+    borrow
+}
+/// Multiplies `a` by `m` and adds `add`, returning the limb carried out of `a`.
+pub(super) const fn mul_small(a: &mut [u64], m: u64, add: u64) -> u64 {
+    let mut carry = add as u128;
+    whilst! { i in 0..a.len(); {
+        let p = a[i] as u128 * m as u128 + carry;
+        a[i] = p as u64;
+        carry = p >> BITS;
+    }}
+    carry as u64
+}
+/// Divides `a` by the non-zero `d` in place, returning the remainder.
+pub(super) const fn div_small(a: &mut [u64], d: u64) -> u64 {
+    let mut rem = 0_u128;
+    let mut i = a.len();
+    while i > 0 {
+        i -= 1;
+        let cur = (rem << BITS) | a[i] as u128;
+        a[i] = (cur / d as u128) as u64;
+        rem = cur % d as u128;
+    }
+    rem as u64
+}
+
+/* shifts */
+
+/// Shifts `a` left by `bits < 64`, returning the bits shifted out.
+pub(super) const fn shl_bits(a: &mut [u64], bits: u32) -> u64 {
+    is![bits == 0, return 0];
+    let mut carry = 0;
+    whilst! { i in 0..a.len(); {
+        let next = a[i] >> (BITS - bits);
+        a[i] = (a[i] << bits) | carry;
+        carry = next;
+    }}
+    carry
+}
+/// Shifts `a` right by `bits < 64`, shifting `top` in from the most significant side.
+pub(super) const fn shr_bits(a: &mut [u64], bits: u32, top: u64) {
+    is![bits == 0, return];
+    let mut carry = top << (BITS - bits);
+    let mut i = a.len();
+    while i > 0 {
+        i -= 1;
+        let next = a[i] << (BITS - bits);
+        a[i] = (a[i] >> bits) | carry;
+        carry = next;
+    }
+}
+
+/* multiplication */
+
+/// Accumulates the product `a · b` into `out`, truncated to its length.
+///
+/// Returns `true` if any non-zero part of the product didn't fit.
+pub(super) const fn mul_acc(out: &mut [u64], a: &[u64], b: &[u64]) -> bool {
+    let (la, lb, lo) = (len(a), len(b), out.len());
+    let mut overflow = false;
+    whilst! { i in 0..la; {
+        let ai = a[i];
+        if ai != 0 {
+            let mut carry = 0_u128;
+            whilst! { j in 0..lb; {
+                let k = i + j;
+                if k < lo {
+                    let p = ai as u128 * b[j] as u128 + out[k] as u128 + carry;
+                    out[k] = p as u64;
+                    carry = p >> BITS;
+                } else {
+                    is![b[j] != 0 || carry != 0, overflow = true];
+                    carry = 0;
+                }
+            }}
+            let mut k = i + lb;
+            while carry != 0 {
+                if k >= lo { overflow = true; break; }
+                let s = out[k] as u128 + carry;
+                out[k] = s as u64;
+                carry = s >> BITS;
+                k += 1;
+            }
+        }
+    }}
+    overflow
+}
+
+/* division */
+
+/// Returns the limb at `i` of `u` extended with the extra most significant `top` limb.
+const fn at(u: &[u64], top: u64, i: usize) -> u64 {
+    is![i == u.len(), top, u[i]]
+}
+/// Sets the limb at `i` of `u` extended with the extra most significant `top` limb.
+const fn set(u: &mut [u64], top: &mut u64, i: usize, v: u64) {
+    if i == u.len() {
+        *top = v;
+    } else {
+        u[i] = v;
+    }
+}
+
+/// Divides `u` by `v` using Knuth's algorithm D.
+///
+/// On return `u` holds the remainder, `v` is left shifted for normalization,
+/// and `q` holds the quotient, which must have room for `len(u) - len(v) + 1` limbs.
+///
+/// The significant part of `v` must be non-empty.
+pub(super) const fn div_rem(u: &mut [u64], v: &mut [u64], q: &mut [u64]) {
+    let n = len(v);
+    let ul = len(u);
+    whilst! { i in 0..q.len(); { q[i] = 0; }}
+    is![ul < n, return];
+    if n == 1 {
+        let r = div_small(u, v[0]);
+        whilst! { i in 0..ul; { is![i < q.len(), q[i] = u[i]]; u[i] = 0; }}
+        u[0] = r;
+        return;
+    }
+    let u = crate::Slice::range_to_mut(u, ul);
+    let v = crate::Slice::range_to_mut(v, n);
+
+    // normalize so that the top bit of the divisor is set
+    let s = v[n - 1].leading_zeros();
+    let _ = shl_bits(v, s);
+    let mut top = shl_bits(u, s);
+    let (vt, vs) = (v[n - 1] as u128, v[n - 2] as u128);
+    let b = 1_u128 << BITS;
+
+    let m = ul - n;
+    let mut j = m + 1;
+    while j > 0 {
+        j -= 1;
+        // estimate the quotient digit
+        let num = ((at(u, top, j + n) as u128) << BITS) | at(u, top, j + n - 1) as u128;
+        let mut qhat = num / vt;
+        let mut rhat = num % vt;
+        while qhat >= b || qhat * vs > ((rhat << BITS) | at(u, top, j + n - 2) as u128) {
+            qhat -= 1;
+            rhat += vt;
+            is![rhat >= b, break];
+        }
+        // multiply and subtract
+        let (mut borrow, mut carry) = (0_u64, 0_u128);
+        whilst! { i in 0..n; {
+            let p = qhat * v[i] as u128 + carry;
+            carry = p >> BITS;
+            let (d1, b1) = at(u, top, i + j).overflowing_sub(p as u64);
+            let (d2, b2) = d1.overflowing_sub(borrow);
+            set(u, &mut top, i + j, d2);
+            borrow = b1 as u64 + b2 as u64;
+        }}
+        let (d1, b1) = at(u, top, j + n).overflowing_sub(carry as u64);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        set(u, &mut top, j + n, d2);
+        // add back if the estimate was one too large
+        if b1 || b2 {
+            qhat -= 1;
+            let mut c = false;
+            whilst! { i in 0..n; {
+                let (s1, c1) = at(u, top, i + j).overflowing_add(v[i]);
+                let (s2, c2) = s1.overflowing_add(c as u64);
+                set(u, &mut top, i + j, s2);
+                c = c1 || c2;
+            }}
+            let last = at(u, top, j + n).wrapping_add(c as u64);
+            set(u, &mut top, j + n, last);
+        }
+        is![j < q.len(), q[j] = qhat as u64];
+    }
+    // denormalize the remainder
+    shr_bits(u, s, top);
+}
+
+/* montgomery */
+
+/// Returns `-n⁻¹ mod 2⁶⁴` for an odd `n0`.
+pub(super) const fn mont_inv(n0: u64) -> u64 {
+    // Newton iteration doubles the correct bits each step: 1, 2, 4 … 64
+    let mut x = 1_u64;
+    whilst! { _i in 0..6; { x = x.wrapping_mul(2_u64.wrapping_sub(n0.wrapping_mul(x))); }}
+    x.wrapping_neg()
+}
+/// Stores the Montgomery product `a · b · R⁻¹ mod n` in `out`, with `R = 2^(64·len)`.
+///
+/// All slices must have the length of `n`, which must be odd,
+/// and `a` and `b` must be less than `n`. `out` must not alias `a` or `b`.
+pub(super) const fn mont_mul(out: &mut [u64], a: &[u64], b: &[u64], n: &[u64], ninv: u64) {
+    let l = n.len();
+    whilst! { i in 0..l; { out[i] = 0; }}
+    let mut t_hi = 0_u64;
+    whilst! { i in 0..l; {
+        // t += a · b[i]
+        let mut c = 0_u128;
+        whilst! { j in 0..l; {
+            let p = out[j] as u128 + a[j] as u128 * b[i] as u128 + c;
+            out[j] = p as u64;
+            c = p >> BITS;
+        }}
+        let s = t_hi as u128 + c;
+        t_hi = s as u64;
+        let t_hi2 = (s >> BITS) as u64;
+        // t = (t + m · n) / 2⁶⁴
+        let m = out[0].wrapping_mul(ninv);
+        let mut c = (out[0] as u128 + m as u128 * n[0] as u128) >> BITS;
+        whilst! { j in 1..l; {
+            let p = out[j] as u128 + m as u128 * n[j] as u128 + c;
+            out[j - 1] = p as u64;
+            c = p >> BITS;
+        }}
+        let s = t_hi as u128 + c;
+        out[l - 1] = s as u64;
+        t_hi = t_hi2 + (s >> BITS) as u64;
+    }}
+    if t_hi != 0 || !matches!(cmp(out, n), Ordering::Less) {
+        let _ = sub_assign(out, n);
+    }
+}
@@ -0,0 +1,290 @@
+// devela/src/num/dom/int/big/_test.rs
+
+use crate::{FmtNum, IntWide, InvalidValue, format_buf};
+
+type U256 = IntWide<4>;
+
+/// A small xorshift generator for reproducible operands.
+fn limbs<const N: usize>(seed: &mut u64) -> [u64; N] {
+    core::array::from_fn(|_| {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    })
+}
+
+#[test]
+fn wide_arithmetic() {
+    let (a, b) = (u128::MAX / 3, 0xdead_beef_u128);
+    let (wa, wb) = (U256::from_u128(a), U256::from_u128(b));
+    assert_eq![(wa + wb).to_u128(), Some(a + b)];
+    assert_eq![(wa - wb).to_u128(), Some(a - b)];
+    assert_eq![(wa / wb).to_u128(), Some(a / b)];
+    assert_eq![(wa % wb).to_u128(), Some(a % b)];
+    assert_eq![(wa * wb) / wb, wa];
+    assert_eq![wb.checked_sub(wa), None];
+    assert_eq![U256::MAX.checked_add(U256::ONE), None];
+    assert_eq![U256::MAX.wrapping_add(U256::ONE), U256::ZERO];
+    assert_eq![wa.checked_div(U256::ZERO), None];
+    assert_eq![U256::MAX.checked_mul(U256::from_u64(2)), None];
+    assert_eq![U256::from_u64(2).checked_pow(255), Some(U256::ONE << 255)];
+    assert_eq![U256::from_u64(2).checked_pow(256), None];
+    let (lo, hi) = U256::MAX.widening_mul(U256::MAX);
+    assert_eq![(lo, hi), (U256::ONE, U256::MAX - U256::ONE)];
+    // shifts
+    let x = U256::from_u64(0b1011) << 190;
+    assert_eq![(x.bit_len(), x.trailing_zeros()), (194, 190)];
+    assert_eq![x >> 190, U256::from_u64(0b1011)];
+    assert_eq![x << 70, U256::ZERO];
+    assert_eq![!U256::ZERO, U256::MAX];
+    assert_eq![U256::MAX.resize::<2>(), None];
+    assert_eq![wa.resize::<2>().unwrap().to_u128(), Some(a)];
+    // division against the full product
+    let mut seed = 7;
+    for _ in 0..50 {
+        let (x, y) =
+            (IntWide::<8>::from_limbs(limbs(&mut seed)), U256::from_limbs(limbs(&mut seed)));
+        let y = y.resize::<8>().unwrap() >> (seed % 200) as u32;
+        let Some((q, r)) = x.div_rem(y) else { continue };
+        assert![r < y];
+        assert_eq![q * y + r, x];
+    }
+}
+
+#[test]
+fn wide_modular() {
+    // 2^127 - 1 is prime, so a^(p-1) = 1 (mod p)
+    let p = (U256::ONE << 127) - U256::ONE;
+    for a in [2, 3, 12345] {
+        assert_eq![U256::from_u64(a).pow_mod(p - U256::ONE, p), Some(U256::ONE)];
+    }
+    // the montgomery and the generic paths agree with u128 arithmetic
+    let m = 1_000_000_007_u128;
+    let expected = (0..20).fold(1, |acc, _| acc * 7 % m);
+    assert_eq![
+        U256::from_u64(7).pow_mod(U256::from_u64(20), U256::from_u128(m)).unwrap().to_u128(),
+        Some(expected)
+    ];
+    let even = 1 << 40;
+    let expected = (0..20).fold(1, |acc, _| acc * 7 % even);
+    assert_eq![
+        U256::from_u64(7).pow_mod(U256::from_u64(20), U256::from_u128(even)).unwrap().to_u128(),
+        Some(expected)
+    ];
+    assert_eq![U256::from_u64(7).pow_mod(U256::ZERO, U256::ONE), Some(U256::ZERO)];
+    assert_eq![U256::from_u64(7).pow_mod(U256::ONE, U256::ZERO), None];
+    // inverses and gcd
+    let inv = U256::from_u64(3).inv_mod(p).unwrap();
+    assert_eq![U256::from_u64(3).mul_mod(inv, p), Some(U256::ONE)];
+    assert_eq![U256::from_u64(6).inv_mod(U256::from_u64(9)), None];
+    assert_eq![U256::from_u64(84).gcd(U256::from_u64(36)), U256::from_u64(12)];
+    assert_eq![U256::from_u64(4).lcm(U256::from_u64(6)), Some(U256::from_u64(12))];
+    assert_eq![(p * U256::from_u64(6)).gcd(p * U256::from_u64(4)), p * U256::from_u64(2)];
+}
+
+#[test]
+fn wide_primes() {
+    for prime in [2, 97, 4_294_967_291, 18_446_744_073_709_551_557] {
+        assert![U256::from_u64(prime).is_prime(), "{prime}"];
+    }
+    // a carmichael number and a strong pseudoprime to the bases up to 23
+    for composite in [0, 1, 561, 4_294_967_297, 3_825_123_056_546_413_051] {
+        assert![!U256::from_u64(composite).is_prime(), "{composite}"];
+    }
+    let m89 = (U256::ONE << 89) - U256::ONE;
+    assert![m89.is_prime()];
+    assert![!(m89 * m89).is_prime()];
+}
+
+#[test]
+fn wide_text() {
+    let mut fb = [0; 80];
+    let n =
+        U256::from_str_radix("1_000_000_000_000_000_000_000_000_000_000_000_000_000", 10).unwrap();
+    assert_eq![n, U256::from_u64(10).checked_pow(39).unwrap()];
+    assert_eq![format_buf![? &mut fb, "{n}"], "1000000000000000000000000000000000000000"];
+    assert_eq![format_buf![? &mut fb, "{:#x}", U256::from_u64(0xABC)], "0xabc"];
+    assert_eq![format_buf![? &mut fb, "{:X}", U256::MAX >> 192], "FFFFFFFFFFFFFFFF"];
+    assert_eq![
+        format_buf![? &mut fb, "{:>6}|{:<4}|{:06}", U256::from_u64(42), U256::ONE, U256::from_u64(7)],
+        "    42|1   |000007"
+    ];
+    assert_eq![format_buf![? &mut fb, "{:b}", U256::from_u64(5)], "101"];
+    assert_eq![format_buf![? &mut fb, "{}", U256::ZERO], "0"];
+    assert_eq![U256::from_str_radix("zz", 36), Ok(U256::from_u64(1295))];
+    assert_eq![U256::from_str_radix("", 10), Err(InvalidValue)];
+    assert_eq![U256::from_str_radix("12a", 10), Err(InvalidValue)];
+    assert_eq![U256::from_str_radix("1", 37), Err(InvalidValue)];
+    assert_eq![IntWide::<1>::from_str_radix("18446744073709551616", 10), Err(InvalidValue)];
+    // conversions never truncate
+    assert_eq![U256::from(7_u8), U256::from_u64(7)];
+    assert_eq![IntWide::<1>::from(u64::MAX).to_u64(), Some(u64::MAX)];
+    assert_eq![IntWide::<1>::try_from(u128::MAX), Err(InvalidValue)];
+    assert_eq![IntWide::<1>::try_from(u128::from(u64::MAX)), Ok(IntWide::from(u64::MAX))];
+    assert_eq![IntWide::<0>::try_from(1_u128), Err(InvalidValue)];
+    assert_eq![IntWide::<0>::try_from(0_u128), Ok(IntWide::ZERO)];
+    assert_eq![IntWide::<2>::try_from(u128::MAX), Ok(IntWide::MAX)];
+    // FmtNum
+    let mut buf = [0; 80];
+    let len = FmtNum(U256::MAX).write(&mut buf, 2);
+    assert_eq![FmtNum(U256::MAX).measure().left as usize, len];
+    assert_eq![&buf[2..2 + len], format_buf![? &mut fb, "{}", U256::MAX].as_bytes()];
+    assert_eq![FmtNum(U256::MAX).write(&mut buf[..70], 0), 0];
+    let len = FmtNum(U256::from_u64(255)).write_radix(&mut buf, 0, 16);
+    assert_eq![&buf[..len], b"ff"];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn big_uint() {
+    use crate::{BigUint, IntWide};
+    // karatsuba and unbalanced products against the fixed schoolbook product
+    let mut seed = 3;
+    let a: [u64; 90] = limbs(&mut seed);
+    let b: [u64; 70] = limbs(&mut seed);
+    let (ba, bb) = (BigUint::from_limbs(a.to_vec()), BigUint::from_limbs(b.to_vec()));
+    let (mut wa, mut wb) = ([0; 160], [0; 160]);
+    wa[..90].copy_from_slice(&a);
+    wb[..70].copy_from_slice(&b);
+    let wide = IntWide::<160>::from_limbs(wa) * IntWide::<160>::from_limbs(wb);
+    assert_eq![(&ba * &bb).to_wide::<160>(), Some(wide)];
+    let short = BigUint::from_limbs(b[..33].to_vec());
+    let mut ws = [0; 160];
+    ws[..33].copy_from_slice(&b[..33]);
+    let wide = IntWide::<160>::from_limbs(wa) * IntWide::<160>::from_limbs(ws);
+    assert_eq![(&ba * &short).to_wide::<160>(), Some(wide)];
+    // division
+    let (q, r) = (&ba * &bb + &short).div_rem(&bb).unwrap();
+    assert_eq![(q, r), (ba.clone(), short.clone())];
+    assert_eq![ba.checked_div(&BigUint::ZERO), None];
+    assert_eq![bb.checked_sub(&ba), None];
+    // shifts and queries
+    let x = BigUint::from(1_u8) << 200;
+    assert_eq![(x.bit_len(), x.trailing_zeros()), (201, 200)];
+    assert_eq![&x >> 199, BigUint::from(2_u8)];
+    assert_eq![
+        (&x - &BigUint::from(1_u8)).sqrt_floor(),
+        (BigUint::from(1_u8) << 100) - BigUint::from(1_u8)
+    ];
+    assert_eq![x.sqrt_floor(), BigUint::from(1_u8) << 100];
+    // combinatorics
+    assert_eq![BigUint::factorial(20).to_u64(), Some(2_432_902_008_176_640_000)];
+    assert_eq![BigUint::combine(60, 30).to_u64(), Some(118_264_581_564_861_424)];
+    assert_eq![BigUint::combine(5, 6), BigUint::ZERO];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn big_uint_modular() {
+    use crate::BigUint;
+    // montgomery over many limbs against the generic path, with an even modulus
+    let p = BigUint::from_limbs([u64::MAX; 8].to_vec()); // odd
+    let (base, exp) = (BigUint::from(0x1234_5678_u64), BigUint::from(1_000_003_u64));
+    let odd = base.pow_mod(&exp, &p).unwrap();
+    let even = base.pow_mod(&exp, &(&p + &BigUint::from(1_u8))).unwrap();
+    let wide_p = p.to_wide::<8>().unwrap();
+    assert_eq![
+        odd.to_wide::<8>(),
+        base.to_wide::<8>().unwrap().pow_mod(exp.to_wide().unwrap(), wide_p)
+    ];
+    let wide_even = (&p + &BigUint::from(1_u8)).to_wide::<9>().unwrap();
+    assert_eq![
+        even.to_wide::<9>(),
+        base.to_wide::<9>().unwrap().pow_mod(exp.to_wide().unwrap(), wide_even)
+    ];
+    // inverses, gcd, lcm
+    let m = BigUint::from_str_radix("170141183460469231731687303715884105727", 10).unwrap();
+    let inv = BigUint::from(10_u8).inv_mod(&m).unwrap();
+    assert_eq![(&inv * &BigUint::from(10_u8)) % &m, BigUint::from(1_u8)];
+    assert_eq![BigUint::from(6_u8).inv_mod(&BigUint::from(9_u8)), None];
+    assert_eq![
+        (&m * &BigUint::from(6_u8)).gcd(&(&m * &BigUint::from(4_u8))),
+        &m * &BigUint::from(2_u8)
+    ];
+    assert_eq![BigUint::from(4_u8).lcm(&BigUint::from(6_u8)), BigUint::from(12_u8)];
+    // primes
+    assert![m.is_prime()];
+    assert![!(&m * &m).is_prime()];
+    assert![BigUint::from(4_294_967_291_u64).is_prime()];
+    assert![!BigUint::from(3_825_123_056_546_413_051_u64).is_prime()];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn big_text() {
+    use crate::{BigInt, BigUint};
+    let n = BigUint::factorial(50);
+    for radix in [2, 3, 10, 16, 36] {
+        assert_eq![BigUint::from_str_radix(&n.to_str_radix(radix), radix), Ok(n.clone())];
+    }
+    assert_eq![crate::format!("{:>8}", BigUint::from(255_u8)), "     255"];
+    assert_eq![crate::format!("{:#X}", BigUint::from(255_u8)), "0xFF"];
+    assert_eq![crate::format!("{}", BigUint::ZERO), "0"];
+    assert_eq![crate::format!("{:+}", BigInt::from(5)), "+5"];
+    assert_eq![crate::format!("{:05}", BigInt::from(-5)), "-0005"];
+    assert_eq![BigUint::from_str_radix("-1", 10), Err(InvalidValue)];
+    assert_eq![BigInt::from_str_radix("--1", 10), Err(InvalidValue)];
+    assert_eq![BigInt::from_str_radix("-ff", 16), Ok(BigInt::from(-255))];
+    let mut buf = [0; 8];
+    let len = FmtNum(&BigInt::from(-1234)).write(&mut buf, 1);
+    assert_eq![&buf[1..1 + len], b"-1234"];
+    assert_eq![FmtNum(&BigInt::from(-1234)).measure().total(), 5];
+    assert_eq![FmtNum(&BigUint::from(u64::MAX)).write(&mut buf, 0), 0];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn big_int() {
+    use crate::{BigInt, BigUint, Ordering, Sign};
+    let (a, b) = (BigInt::from(-17), BigInt::from(5));
+    assert_eq![a.div_rem(&b), Some((BigInt::from(-3), BigInt::from(-2)))];
+    assert_eq![a.div_rem_euclid(&b), Some((BigInt::from(-4), BigInt::from(3)))];
+    assert_eq![a.div_rem_euclid(&-&b), Some((BigInt::from(4), BigInt::from(3)))];
+    assert_eq![&a + &b, BigInt::from(-12)];
+    assert_eq![&b - &a, BigInt::from(22)];
+    assert_eq![&a * &b, BigInt::from(-85)];
+    assert_eq![a.pow(3), BigInt::from(-4913)];
+    assert_eq![(&a + &BigInt::from(17)).sign(), Sign::Zero];
+    assert_eq![-BigInt::ZERO, BigInt::ZERO];
+    assert_eq![a.cmp(&b), Ordering::Less];
+    assert![BigInt::from(-3) > BigInt::from(-4)];
+    assert_eq![BigInt::from(i128::MIN).to_i128(), Some(i128::MIN)];
+    assert_eq![(BigInt::from(i128::MIN) - BigInt::from(1)).to_i128(), None];
+    assert_eq![a.pow_mod(&BigUint::from(1_u8), &BigUint::from(5_u8)), Some(BigUint::from(3_u8))];
+    assert_eq![BigInt::from(-3).inv_mod(&BigUint::from(7_u8)), Some(BigUint::from(2_u8))];
+    assert_eq![BigUint::try_from(a), Err(InvalidValue)];
+    assert![!BigInt::from(-7).is_prime()];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn big_ratio() {
+    use crate::{BigInt, BigRatio, Sign};
+    let r = |s: &str| s.parse::<BigRatio>().unwrap();
+    assert_eq![r("1/2") + r("1/3"), r("5/6")];
+    assert_eq![r("1/2") - r("5/6"), r("-1/3")];
+    assert_eq![r("-2/3") * r("9/4"), r("-3/2")];
+    assert_eq![r("1/2") / r("-1/4"), BigRatio::from(-2)];
+    assert_eq![r("6/3"), BigRatio::from(2)];
+    assert_eq![r("0/5"), BigRatio::ZERO];
+    assert_eq![r("-7/2").floor(), BigInt::from(-4)];
+    assert_eq![r("-7/2").ceil(), BigInt::from(-3)];
+    assert_eq![r("-7/2").trunc(), BigInt::from(-3)];
+    assert_eq![r("-7/2").fract(), r("-1/2")];
+    assert_eq![r("2/3").pow(-2), Some(r("9/4"))];
+    assert_eq![BigRatio::ZERO.pow(-1), None];
+    assert_eq![r("-5/4").sign(), Sign::Negative];
+    assert![r("1/3") < r("1/2") && r("-1/2") < r("-1/3")];
+    assert_eq![r("3/4").denom(), 4_u8.into()];
+    assert_eq!["1/0".parse::<BigRatio>(), Err(crate::InvalidValue)];
+    assert_eq!["1/-2".parse::<BigRatio>(), Err(crate::InvalidValue)];
+    assert_eq![BigRatio::new(BigInt::from(3), BigInt::from(-6)), Some(r("-1/2"))];
+    // floats
+    assert_eq![BigRatio::from_f64(-0.375), Some(r("-3/8"))];
+    assert_eq![BigRatio::from_f64(1e300).unwrap().to_f64().to_bits(), 1e300_f64.to_bits()];
+    assert_eq![BigRatio::from_f64(f64::NAN), None];
+    assert_eq![r("1/3").to_f64().to_bits(), (1.0_f64 / 3.0).to_bits()];
+    assert_eq![r("-22/7").to_f64().to_bits(), (-22.0_f64 / 7.0).to_bits()];
+    assert_eq![crate::format!("{}|{:>5}", r("-4/6"), r("1/2")), "-2/3|  1/2"];
+}
@@ -0,0 +1,383 @@
+// devela/src/num/dom/int/big/int.rs
+//
+//! Defines [`BigInt`].
+//
+// TOC
+// - struct BigInt
+// - impl BigInt
+// - impl traits
+
+use super::{_helper, _helper::impl_big_ops};
+use crate::{
+    BigUint, Binary, Debug, Display, FmtNum, FmtNumShape, FmtResult, Formatter, FromStr,
+    InvalidValue, LowerHex, Octal, Ordering, Sign, String, UpperHex, is,
+};
+
+#[doc = crate::_tags!(num allocation)]
+/// An arbitrary-precision signed integer.
+#[doc = crate::_doc_meta!{location("num/dom/int")}]
+///
+/// It's stored as a sign and a [`BigUint`] magnitude, and `0` is never negative.
+///
+/// Division truncates towards zero, like the primitive integers,
+/// use [`div_rem_euclid`][Self::div_rem_euclid] for a non-negative remainder.
+///
+/// # Example
+/// ```
+/// # use devela::{BigInt, Sign};
+/// let a: BigInt = "-123456789012345678901234567890".parse().unwrap();
+/// let b = BigInt::from(1_000_000_007_i64);
+/// let (q, r) = a.div_rem(&b).unwrap();
+/// assert_eq![&q * &b + &r, a];
+/// assert_eq![r.sign(), Sign::Negative];
+/// assert_eq![a.div_rem_euclid(&b).unwrap().1.sign(), Sign::Positive];
+/// assert_eq![format!("{:x}", BigInt::from(-255)), "-ff"];
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    neg: bool,
+    mag: BigUint,
+}
+
+impl BigInt {
+    /// The value `0`.
+    pub const ZERO: Self = Self { neg: false, mag: BigUint::ZERO };
+
+    /* construction */
+
+    /// Returns a new integer from a `sign` and a `magnitude`.
+    ///
+    /// The result is `0` if either the sign or the magnitude are zero.
+    #[must_use]
+    pub fn from_parts(sign: Sign, magnitude: BigUint) -> Self {
+        match sign {
+            Sign::Zero => Self::ZERO,
+            _ => Self::with_neg(matches!(sign, Sign::Negative), magnitude),
+        }
+    }
+    /// Returns the sign and the magnitude, consuming the integer.
+    pub fn into_parts(self) -> (Sign, BigUint) {
+        (self.sign(), self.mag)
+    }
+    /// Returns a new integer from an `i128`.
+    #[must_use]
+    pub fn from_i128(value: i128) -> Self {
+        Self::with_neg(value < 0, BigUint::from_u128(value.unsigned_abs()))
+    }
+    /// Returns the value as an `i128` if it fits.
+    #[must_use]
+    pub fn to_i128(&self) -> Option<i128> {
+        let mag = self.mag.to_u128()?;
+        if self.neg {
+            is![mag <= i128::MIN.unsigned_abs(), Some((mag as i128).wrapping_neg()), None]
+        } else {
+            i128::try_from(mag).ok()
+        }
+    }
+    /// Returns the value as an `i64` if it fits.
+    #[must_use]
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|v| i64::try_from(v).ok())
+    }
+
+    /* queries */
+
+    /// Returns the sign.
+    pub fn sign(&self) -> Sign {
+        match (self.neg, self.mag.is_zero()) {
+            (_, true) => Sign::Zero,
+            (true, _) => Sign::Negative,
+            (false, _) => Sign::Positive,
+        }
+    }
+    /// Returns the magnitude.
+    #[must_use]
+    pub fn magnitude(&self) -> &BigUint {
+        &self.mag
+    }
+    /// Returns the magnitude, consuming the integer.
+    #[must_use]
+    pub fn into_magnitude(self) -> BigUint {
+        self.mag
+    }
+    /// Returns `true` if the value is `0`.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_zero()
+    }
+    /// Returns `true` if the value is less than `0`.
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.neg
+    }
+    /// Returns `true` if the value is greater than `0`.
+    #[must_use]
+    pub fn is_positive(&self) -> bool {
+        !self.neg && !self.mag.is_zero()
+    }
+    /// Returns the absolute value.
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self { neg: false, mag: self.mag.clone() }
+    }
+
+    /* arithmetic */
+
+    /// Returns the quotient and remainder of `self / rhs`, truncated towards zero,
+    /// or `None` if `rhs` is `0`.
+    ///
+    /// The remainder has the sign of `self`.
+    #[must_use]
+    pub fn div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        let (q, r) = self.mag.div_rem(&rhs.mag)?;
+        Some((Self::with_neg(self.neg != rhs.neg, q), Self::with_neg(self.neg, r)))
+    }
+    /// Returns the quotient and remainder of the euclidean division `self / rhs`,
+    /// or `None` if `rhs` is `0`.
+    ///
+    /// The remainder is never negative.
+    #[must_use]
+    pub fn div_rem_euclid(&self, rhs: &Self) -> Option<(Self, Self)> {
+        let (mut q, mut r) = self.div_rem(rhs)?;
+        if r.neg {
+            r = &r + &rhs.abs();
+            q = is![rhs.neg, &q + &Self::from(1), &q - &Self::from(1)];
+        }
+        Some((q, r))
+    }
+    /// Returns `self` to the power of `exp`.
+    #[must_use]
+    pub fn pow(&self, exp: u32) -> Self {
+        Self::with_neg(self.neg && exp % 2 == 1, self.mag.pow(exp))
+    }
+    /// Returns `self` to the power of `exp` modulo `modulus`, in `0..modulus`,
+    /// or `None` if `modulus` is `0`.
+    #[must_use]
+    pub fn pow_mod(&self, exp: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+        self.rem_modulus(modulus)?.pow_mod(exp, modulus)
+    }
+    /// Returns the modular multiplicative inverse of `self` modulo `modulus`,
+    /// in `0..modulus`, or `None` if it doesn't exist.
+    #[must_use]
+    pub fn inv_mod(&self, modulus: &BigUint) -> Option<BigUint> {
+        self.rem_modulus(modulus)?.inv_mod(modulus)
+    }
+    /// Returns the greatest common divisor of the magnitudes of `self` and `other`.
+    #[must_use]
+    pub fn gcd(&self, other: &Self) -> BigUint {
+        self.mag.gcd(&other.mag)
+    }
+    /// Returns the least common multiple of the magnitudes of `self` and `other`.
+    #[must_use]
+    pub fn lcm(&self, other: &Self) -> BigUint {
+        self.mag.lcm(&other.mag)
+    }
+    /// Returns `true` if the value is prime. Negative values are not.
+    ///
+    /// See [`BigUint::is_prime`].
+    #[must_use]
+    pub fn is_prime(&self) -> bool {
+        !self.neg && self.mag.is_prime()
+    }
+
+    /* text */
+
+    /// Parses an integer from a string in the given `radix`, from 2 to 36.
+    ///
+    /// An optional `+` or `-` sign and `_` digit separators are accepted.
+    ///
+    /// # Errors
+    /// Returns [`InvalidValue`] if the radix is out of range, the string has no digits,
+    /// or contains an invalid digit.
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, InvalidValue> {
+        let (neg, digits) = match src.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, src.strip_prefix('+').unwrap_or(src)),
+        };
+        is![digits.starts_with(['+', '-']), return Err(InvalidValue)];
+        Ok(Self::with_neg(neg, BigUint::from_str_radix(digits, radix)?))
+    }
+    /// Returns the digits in the given `radix`, from 2 to 36, in lowercase,
+    /// preceded by `-` if negative.
+    ///
+    /// # Panics
+    /// Panics if `radix` is out of range.
+    #[must_use]
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.mag.to_str_radix(radix);
+        is![self.neg, crate::format!("-{digits}"), digits]
+    }
+
+    /* helpers */
+
+    /// Returns a new integer, making sure `0` is not negative.
+    fn with_neg(neg: bool, mag: BigUint) -> Self {
+        Self { neg: neg && !mag.is_zero(), mag }
+    }
+    /// Returns `self` reduced modulo `modulus`, in `0..modulus`.
+    fn rem_modulus(&self, modulus: &BigUint) -> Option<BigUint> {
+        let r = self.mag.checked_rem(modulus)?;
+        Some(is![self.neg && !r.is_zero(), modulus - &r, r])
+    }
+
+    /* operator implementations */
+
+    fn add_impl(a: &Self, b: &Self) -> Self {
+        if a.neg == b.neg {
+            return Self::with_neg(a.neg, &a.mag + &b.mag);
+        }
+        match a.mag.cmp(&b.mag) {
+            Ordering::Less => Self::with_neg(b.neg, &b.mag - &a.mag),
+            _ => Self::with_neg(a.neg, &a.mag - &b.mag),
+        }
+    }
+    fn sub_impl(a: &Self, b: &Self) -> Self {
+        Self::add_impl(a, &-b)
+    }
+    fn mul_impl(a: &Self, b: &Self) -> Self {
+        Self::with_neg(a.neg != b.neg, &a.mag * &b.mag)
+    }
+    fn div_impl(a: &Self, b: &Self) -> Self {
+        a.div_rem(b).expect("attempt to divide by zero").0
+    }
+    fn rem_impl(a: &Self, b: &Self) -> Self {
+        a.div_rem(b).expect("attempt to calculate the remainder with a divisor of zero").1
+    }
+}
+
+/* traits */
+
+impl_big_ops![BigInt:
+    Add, add, AddAssign, add_assign, add_impl;
+    Sub, sub, SubAssign, sub_assign, sub_impl;
+    Mul, mul, MulAssign, mul_assign, mul_impl;
+    Div, div, DivAssign, div_assign, div_impl;
+    Rem, rem, RemAssign, rem_assign, rem_impl;
+];
+
+impl core::ops::Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::with_neg(!self.neg, self.mag.clone())
+    }
+}
+impl core::ops::Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::with_neg(!self.neg, self.mag)
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.mag.cmp(&other.mag),
+            (true, true) => other.mag.cmp(&self.mag),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    (signed $($t:ty),+) => {$(
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                Self::from_i128(value as i128)
+            }
+        }
+    )+};
+    (unsigned $($t:ty),+) => {$(
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                Self::from(BigUint::from(value))
+            }
+        }
+    )+};
+}
+impl_from![signed i8, i16, i32, i64, i128, isize];
+impl_from![unsigned u8, u16, u32, u64, u128, usize];
+impl From<BigUint> for BigInt {
+    fn from(mag: BigUint) -> Self {
+        Self { neg: false, mag }
+    }
+}
+impl TryFrom<BigInt> for BigUint {
+    type Error = InvalidValue;
+    /// Returns the magnitude, or [`InvalidValue`] if the value is negative.
+    fn try_from(value: BigInt) -> Result<Self, InvalidValue> {
+        is![value.neg, Err(InvalidValue), Ok(value.mag)]
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, InvalidValue> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+macro_rules! impl_fmt {
+    ($($trait:ident: $radix:literal, $prefix:literal, $upper:literal);+ $(;)?) => {$(
+        impl $trait for BigInt {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+                let mut digits = self.mag.to_str_radix($radix);
+                is![$upper, digits.make_ascii_uppercase()];
+                f.pad_integral(!self.neg, $prefix, &digits)
+            }
+        }
+    )+};
+}
+impl_fmt![
+    Binary: 2, "0b", false;
+    Octal: 8, "0o", false;
+    Display: 10, "", false;
+    LowerHex: 16, "0x", false;
+    UpperHex: 16, "0x", true;
+];
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+        write!(f, "BigInt({self})")
+    }
+}
+
+impl FmtNum<&BigInt> {
+    /// Writes the integer as ASCII digits in `radix` into `buf` starting at `pos`.
+    ///
+    /// Returns the number of bytes written, or `0` if the buffer is too small
+    /// or `radix` is not between 2 and 36.
+    ///
+    /// # Behavioral guarantees
+    /// - Negative values are preceded by the `'-'` sign.
+    /// - Digits above 9 are written in lowercase.
+    /// - The operation is atomic: on failure, nothing is written.
+    pub fn write_radix(self, buf: &mut [u8], pos: usize, radix: u32) -> usize {
+        let sign = usize::from(self.0.neg);
+        is![!(2..=36).contains(&radix), return 0];
+        let needed = _helper::count_digits(&mut self.0.mag.limbs().to_vec(), radix) + sign;
+        is![needed > buf.len().saturating_sub(pos), return 0];
+        is![self.0.neg, buf[pos] = b'-'];
+        FmtNum(&self.0.mag).write_radix(buf, pos + sign, radix) + sign
+    }
+    /// Writes the integer as ASCII decimal digits into `buf` starting at `pos`.
+    ///
+    /// Returns the number of bytes written, or `0` if the buffer is too small.
+    ///
+    /// # Behavioral guarantees
+    /// - Negative values are preceded by the `'-'` sign.
+    /// - The operation is atomic: on failure, nothing is written.
+    pub fn write(self, buf: &mut [u8], pos: usize) -> usize {
+        self.write_radix(buf, pos, 10)
+    }
+    /// Measures the decimal formatting of the integer.
+    #[must_use]
+    pub fn measure(self) -> FmtNumShape {
+        let shape = FmtNum(&self.0.mag).measure();
+        FmtNumShape::new(u16::from(self.0.neg), shape.left, 0)
+    }
+}
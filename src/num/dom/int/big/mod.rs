@@ -0,0 +1,33 @@
+// devela/src/num/dom/int/big/mod.rs
+//
+//! Multi-limb integers and rationals.
+//!
+//! - [`IntWide`] is a fixed-size unsigned integer that never allocates.
+//! - [`BigUint`] and [`BigInt`] grow as needed, and require the `alloc` feature.
+//! - [`BigRatio`] is an exact rational number on top of them.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // BASES, digits, primality screening
+mod _limbs; // little-endian limb algorithms
+
+mod wide; // IntWide
+#[cfg(feature = "alloc")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "alloc")))]
+mod int; // BigInt
+#[cfg(feature = "alloc")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "alloc")))]
+mod ratio; // BigRatio
+#[cfg(feature = "alloc")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "alloc")))]
+mod uint; // BigUint
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::wide::IntWide;
+        #[cfg(feature = "alloc")]
+        pub use super::{int::BigInt, ratio::BigRatio, uint::BigUint};
+    }
+}
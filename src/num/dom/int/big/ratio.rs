@@ -0,0 +1,313 @@
+// devela/src/num/dom/int/big/ratio.rs
+//
+//! Defines [`BigRatio`].
+//
+// TOC
+// - struct BigRatio
+// - impl BigRatio
+// - impl traits
+
+use super::_helper::impl_big_ops;
+use crate::{
+    BigInt, BigUint, Debug, Display, FmtResult, Formatter, FromStr, InvalidValue, Ordering, Sign,
+    is,
+};
+
+#[doc = crate::_tags!(num allocation)]
+/// An exact arbitrary-precision rational number.
+#[doc = crate::_doc_meta!{location("num/dom/int")}]
+///
+/// It's stored as a [`BigInt`] numerator and a positive [`BigUint`] denominator,
+/// always reduced to lowest terms, so that equal values have equal representations.
+///
+/// See also [`Ratio`][crate::Ratio] for a fixed-width ratio.
+///
+/// # Example
+/// ```
+/// # use devela::{BigInt, BigRatio};
+/// // the harmonic number H(30) is exact
+/// let mut h = BigRatio::from(0);
+/// for n in 1..=30 {
+///     h += BigRatio::new(1.into(), BigInt::from(n)).unwrap();
+/// }
+/// assert_eq![h.to_string(), "9304682830147/2329089562800"];
+/// assert_eq![h.floor(), BigInt::from(3)];
+///
+/// let third: BigRatio = "-2/6".parse().unwrap();
+/// assert_eq![third.to_string(), "-1/3"];
+/// assert_eq![third.recip(), Some(BigRatio::from(-3))];
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BigRatio {
+    num: BigInt,
+    den: BigUint,
+}
+
+impl Default for BigRatio {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl BigRatio {
+    /// The value `0`.
+    pub const ZERO: Self = Self { num: BigInt::ZERO, den: BigUint::ZERO };
+
+    /* construction */
+
+    /// Returns the reduced ratio `numerator / denominator`,
+    /// or `None` if the `denominator` is `0`.
+    #[must_use]
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Option<Self> {
+        is![denominator.is_zero(), return None];
+        let num = is![denominator.is_negative(), -numerator, numerator];
+        Some(Self::reduced(num, denominator.into_magnitude()))
+    }
+    /// Returns the exact value of a finite `f64`, or `None` if it's not finite.
+    #[must_use]
+    pub fn from_f64(value: f64) -> Option<Self> {
+        is![!value.is_finite(), return None];
+        let bits = value.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let frac = bits & ((1 << 52) - 1);
+        let (mant, exp) = is![exp == 0, (frac, -1074), (frac | (1 << 52), exp - 1075)];
+        let num = BigInt::from(mant);
+        let num = is![value.is_sign_negative(), -num, num];
+        Some(if exp >= 0 {
+            Self::from(num * BigInt::from(BigUint::from(1_u8) << exp as u32))
+        } else {
+            Self::reduced(num, BigUint::from(1_u8) << exp.unsigned_abs())
+        })
+    }
+    /// Returns the numerator and the denominator, consuming the ratio.
+    #[must_use]
+    pub fn into_parts(self) -> (BigInt, BigUint) {
+        let den = self.den_or_one();
+        (self.num, den)
+    }
+
+    /* queries */
+
+    /// Returns the numerator, which carries the sign.
+    #[must_use]
+    pub fn numer(&self) -> &BigInt {
+        &self.num
+    }
+    /// Returns the denominator, which is always positive.
+    #[must_use]
+    pub fn denom(&self) -> BigUint {
+        self.den_or_one()
+    }
+    /// Returns the sign.
+    pub fn sign(&self) -> Sign {
+        self.num.sign()
+    }
+    /// Returns `true` if the value is `0`.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+    /// Returns `true` if the value is an integer.
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.den.is_zero() || self.den.is_one()
+    }
+
+    /* arithmetic */
+
+    /// Returns the absolute value.
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self { num: self.num.abs(), den: self.den.clone() }
+    }
+    /// Returns the reciprocal, or `None` if the value is `0`.
+    #[must_use]
+    pub fn recip(&self) -> Option<Self> {
+        is![self.is_zero(), return None];
+        let num = BigInt::from_parts(self.sign(), self.den_or_one());
+        Some(Self::with_den(num, self.num.magnitude().clone()))
+    }
+    /// Returns `self` to the power of `exp`, or `None` if it's `0` and `exp` is negative.
+    #[must_use]
+    pub fn pow(&self, exp: i32) -> Option<Self> {
+        let base = is![exp < 0, self.recip()?, self.clone()];
+        let e = exp.unsigned_abs();
+        // powers of coprime numbers are coprime
+        Some(Self::with_den(base.num.pow(e), base.den_or_one().pow(e)))
+    }
+    /// Returns the largest integer less than or equal to the value.
+    #[must_use]
+    pub fn floor(&self) -> BigInt {
+        let den = BigInt::from(self.den_or_one());
+        self.num.div_rem_euclid(&den).expect("positive denominator").0
+    }
+    /// Returns the smallest integer greater than or equal to the value.
+    #[must_use]
+    pub fn ceil(&self) -> BigInt {
+        -(-self).floor()
+    }
+    /// Returns the integer part, rounding towards zero.
+    #[must_use]
+    pub fn trunc(&self) -> BigInt {
+        &self.num / &BigInt::from(self.den_or_one())
+    }
+    /// Returns the fractional part, `self - self.trunc()`, with the sign of `self`.
+    #[must_use]
+    pub fn fract(&self) -> Self {
+        Self {
+            num: &self.num % &BigInt::from(self.den_or_one()),
+            den: self.den.clone(),
+        }
+    }
+    /// Returns the nearest `f64`.
+    ///
+    /// The quotient is computed with 64 significant bits and then rounded,
+    /// so the last bit may differ from a correctly rounded result.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        is![self.is_zero(), return 0.0];
+        let den = self.den_or_one();
+        // scale the quotient to between 2^63 and 2^65
+        let shift = 64 - (self.num.magnitude().bit_len() as i64 - den.bit_len() as i64);
+        let q = if shift >= 0 {
+            (self.num.magnitude() << shift as u32) / den
+        } else {
+            self.num.magnitude() / &(den << shift.unsigned_abs() as u32)
+        };
+        let q = q.to_u128().expect("a quotient of at most 65 bits") as f64;
+        let value = ldexp(q, -shift);
+        is![self.num.is_negative(), -value, value]
+    }
+
+    /* helpers */
+
+    /// Returns the ratio reduced to lowest terms, for a non-zero `den`.
+    fn reduced(num: BigInt, den: BigUint) -> Self {
+        let g = num.magnitude().gcd(&den);
+        is![g.is_one(), return Self::with_den(num, den)];
+        let num = BigInt::from_parts(num.sign(), num.magnitude() / &g);
+        Self::with_den(num, den / g)
+    }
+    /// Returns the ratio, storing a denominator of `1` as zero for integers not to allocate it.
+    fn with_den(num: BigInt, den: BigUint) -> Self {
+        Self { num, den: is![den.is_one(), BigUint::ZERO, den] }
+    }
+    fn den_or_one(&self) -> BigUint {
+        is![self.den.is_zero(), BigUint::from(1_u8), self.den.clone()]
+    }
+
+    /* operator implementations */
+
+    fn add_impl(a: &Self, b: &Self) -> Self {
+        let (ad, bd) = (a.den_or_one(), b.den_or_one());
+        let num = &a.num * &BigInt::from(bd.clone()) + &b.num * &BigInt::from(ad.clone());
+        Self::reduced(num, ad * bd)
+    }
+    fn sub_impl(a: &Self, b: &Self) -> Self {
+        Self::add_impl(a, &-b)
+    }
+    fn mul_impl(a: &Self, b: &Self) -> Self {
+        Self::reduced(&a.num * &b.num, a.den_or_one() * b.den_or_one())
+    }
+    fn div_impl(a: &Self, b: &Self) -> Self {
+        let recip = b.recip().expect("attempt to divide by zero");
+        Self::mul_impl(a, &recip)
+    }
+}
+
+/* traits */
+
+impl_big_ops![BigRatio:
+    Add, add, AddAssign, add_assign, add_impl;
+    Sub, sub, SubAssign, sub_assign, sub_impl;
+    Mul, mul, MulAssign, mul_assign, mul_impl;
+    Div, div, DivAssign, div_assign, div_impl;
+];
+
+impl core::ops::Neg for &BigRatio {
+    type Output = BigRatio;
+    fn neg(self) -> BigRatio {
+        BigRatio { num: -&self.num, den: self.den.clone() }
+    }
+}
+impl core::ops::Neg for BigRatio {
+    type Output = BigRatio;
+    fn neg(self) -> BigRatio {
+        BigRatio { num: -self.num, den: self.den }
+    }
+}
+
+impl PartialOrd for BigRatio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for BigRatio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = &self.num * &BigInt::from(other.den_or_one());
+        let b = &other.num * &BigInt::from(self.den_or_one());
+        a.cmp(&b)
+    }
+}
+
+impl From<BigInt> for BigRatio {
+    fn from(num: BigInt) -> Self {
+        Self { num, den: BigUint::ZERO }
+    }
+}
+impl From<BigUint> for BigRatio {
+    fn from(num: BigUint) -> Self {
+        Self::from(BigInt::from(num))
+    }
+}
+macro_rules! impl_from {
+    ($($t:ty),+) => {$(
+        impl From<$t> for BigRatio {
+            fn from(value: $t) -> Self {
+                Self::from(BigInt::from(value))
+            }
+        }
+    )+};
+}
+impl_from![i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize];
+
+impl FromStr for BigRatio {
+    type Err = InvalidValue;
+    /// Parses a decimal integer, or a ratio of decimal integers separated by `/`.
+    fn from_str(s: &str) -> Result<Self, InvalidValue> {
+        match s.split_once('/') {
+            None => Ok(Self::from(s.parse::<BigInt>()?)),
+            Some((num, den)) => {
+                let den = den.parse::<BigUint>()?;
+                Self::new(num.parse()?, den.into()).ok_or(InvalidValue)
+            }
+        }
+    }
+}
+
+impl Display for BigRatio {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+        is![self.is_integer(), return Display::fmt(&self.num, f)];
+        f.pad(&crate::format!("{}/{}", self.num, self.den))
+    }
+}
+impl Debug for BigRatio {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+        write!(f, "BigRatio({}/{})", self.num, self.den_or_one())
+    }
+}
+
+/// Returns `x · 2^exp`.
+fn ldexp(mut x: f64, mut exp: i64) -> f64 {
+    const STEP: i64 = 1000;
+    let pow2 = |e: i64| f64::from_bits(((e + 1023) as u64) << 52);
+    while exp > STEP {
+        x *= pow2(STEP);
+        exp -= STEP;
+    }
+    while exp < -STEP {
+        x *= pow2(-STEP);
+        exp += STEP;
+    }
+    x * pow2(exp)
+}
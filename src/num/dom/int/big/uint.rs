@@ -0,0 +1,607 @@
+// devela/src/num/dom/int/big/uint.rs
+//
+//! Defines [`BigUint`].
+//
+// TOC
+// - struct BigUint
+// - impl BigUint
+// - impl traits
+// - helpers
+
+use super::{_helper, _helper::impl_big_ops, _limbs as limbs};
+use crate::{
+    Binary, Debug, Display, FmtNum, FmtNumShape, FmtResult, Formatter, FromStr, IntWide,
+    InvalidValue, LowerHex, Mem, Octal, Ordering, String, UpperHex, Vec, is, vec_ as vec,
+};
+
+/// The operand size in limbs from which multiplication switches to Karatsuba.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+#[doc = crate::_tags!(num allocation)]
+/// An arbitrary-precision unsigned integer.
+#[doc = crate::_doc_meta!{location("num/dom/int")}]
+///
+/// The magnitude is stored as a vector of little-endian 64-bit limbs,
+/// without most significant zero limbs, so that `0` doesn't allocate.
+///
+/// Multiplication switches from schoolbook to Karatsuba for large operands,
+/// division uses Knuth's algorithm D, and modular exponentiation
+/// uses Montgomery multiplication for odd moduli.
+///
+/// Arithmetic operators panic on underflow and on division by zero,
+/// use the `checked_*` methods to handle them.
+///
+/// See also [`IntWide`] for a fixed-size integer that doesn't allocate.
+///
+/// # Example
+/// ```
+/// # use devela::BigUint;
+/// let f = BigUint::factorial(30);
+/// assert_eq![f.to_string(), "265252859812191058636308480000000"];
+/// assert_eq![BigUint::combine(100, 50).to_string(), "100891344545564193334812497256"];
+///
+/// // Mersenne prime 2^127 - 1
+/// let m = (BigUint::from(1_u8) << 127) - BigUint::from(1_u8);
+/// assert![m.is_prime()];
+/// assert_eq![format!("{m:#x}"), "0x7fffffffffffffffffffffffffffffff"];
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    /// The value `0`.
+    pub const ZERO: Self = Self { limbs: Vec::new() };
+
+    /* construction */
+
+    /// Returns a new integer from its little-endian `limbs`.
+    #[must_use]
+    pub fn from_limbs(limbs: Vec<u64>) -> Self {
+        Self { limbs: normalized(limbs) }
+    }
+    /// Returns the little-endian limbs, without most significant zeros.
+    #[must_use]
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+    /// Returns the little-endian limbs, consuming the integer.
+    #[must_use]
+    pub fn into_limbs(self) -> Vec<u64> {
+        self.limbs
+    }
+    /// Returns a new integer from a `u64`.
+    #[must_use]
+    pub fn from_u64(value: u64) -> Self {
+        Self::from_limbs(vec![value])
+    }
+    /// Returns a new integer from a `u128`.
+    #[must_use]
+    pub fn from_u128(value: u128) -> Self {
+        Self::from_limbs(vec![value as u64, (value >> limbs::BITS) as u64])
+    }
+    /// Returns a new integer from a fixed-size one.
+    #[must_use]
+    pub fn from_wide<const L: usize>(value: IntWide<L>) -> Self {
+        Self::from_limbs(value.into_limbs().into())
+    }
+    /// Returns the value as a `u64` if it fits.
+    #[must_use]
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0]),
+            _ => None,
+        }
+    }
+    /// Returns the value as a `u128` if it fits.
+    #[must_use]
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(u128::from(self.limbs[0])),
+            2 => Some((u128::from(self.limbs[1]) << limbs::BITS) | u128::from(self.limbs[0])),
+            _ => None,
+        }
+    }
+    /// Returns the value as a fixed-size integer if it fits.
+    #[must_use]
+    pub fn to_wide<const L: usize>(&self) -> Option<IntWide<L>> {
+        is![self.limbs.len() > L, return None];
+        let mut out = [0; L];
+        out[..self.limbs.len()].copy_from_slice(&self.limbs);
+        Some(IntWide::from_limbs(out))
+    }
+
+    /* queries */
+
+    /// Returns `true` if the value is `0`.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+    /// Returns `true` if the value is `1`.
+    #[must_use]
+    pub fn is_one(&self) -> bool {
+        self.limbs == [1]
+    }
+    /// Returns `true` if the value is odd.
+    #[must_use]
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|l| l & 1 == 1)
+    }
+    /// Returns the number of significant bits.
+    #[must_use]
+    pub fn bit_len(&self) -> u64 {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => {
+                (self.limbs.len() as u64 - 1) * 64 + u64::from(limbs::BITS - top.leading_zeros())
+            }
+        }
+    }
+    /// Returns the number of trailing zero bits, or `0` if the value is `0`.
+    #[must_use]
+    pub fn trailing_zeros(&self) -> u64 {
+        let Some(i) = self.limbs.iter().position(|&l| l != 0) else { return 0 };
+        i as u64 * 64 + u64::from(self.limbs[i].trailing_zeros())
+    }
+    /// Returns the bit at `index`.
+    #[must_use]
+    pub fn bit(&self, index: u64) -> bool {
+        let i = (index / 64) as usize;
+        i < self.limbs.len() && (self.limbs[i] >> (index % 64)) & 1 == 1
+    }
+
+    /* arithmetic */
+
+    /// Returns `self - rhs`, or `None` on underflow.
+    #[must_use]
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        is![self < rhs, return None];
+        let mut limbs = self.limbs.clone();
+        let _ = limbs::sub_assign(&mut limbs, &rhs.limbs);
+        Some(Self::from_limbs(limbs))
+    }
+    /// Returns the quotient and remainder of `self / rhs`, or `None` if `rhs` is `0`.
+    #[must_use]
+    pub fn div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        is![rhs.is_zero(), return None];
+        is![self < rhs, return Some((Self::ZERO, self.clone()))];
+        let (mut rem, mut div) = (self.limbs.clone(), rhs.limbs.clone());
+        let mut quo = vec![0; rem.len() - div.len() + 1];
+        limbs::div_rem(&mut rem, &mut div, &mut quo);
+        Some((Self::from_limbs(quo), Self::from_limbs(rem)))
+    }
+    /// Returns `self / rhs`, or `None` if `rhs` is `0`.
+    #[must_use]
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.div_rem(rhs).map(|(q, _)| q)
+    }
+    /// Returns `self % rhs`, or `None` if `rhs` is `0`.
+    #[must_use]
+    pub fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        self.div_rem(rhs).map(|(_, r)| r)
+    }
+    /// Returns the quotient and remainder of dividing by a single limb, or `None` if it's `0`.
+    #[must_use]
+    pub fn div_rem_u64(&self, rhs: u64) -> Option<(Self, u64)> {
+        is![rhs == 0, return None];
+        let mut limbs = self.limbs.clone();
+        let rem = limbs::div_small(&mut limbs, rhs);
+        Some((Self::from_limbs(limbs), rem))
+    }
+    /// Returns `self` to the power of `exp`.
+    #[must_use]
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let (mut base, mut acc) = (self.clone(), Self::from_u64(1));
+        while exp > 0 {
+            is![exp & 1 == 1, acc = &acc * &base];
+            exp >>= 1;
+            is![exp > 0, base = &base * &base];
+        }
+        acc
+    }
+    /// Returns the integer square root, rounded down.
+    #[must_use]
+    pub fn sqrt_floor(&self) -> Self {
+        is![self.is_zero(), return Self::ZERO];
+        // Newton iteration from a power of two above the root
+        let mut x = Self::from_u64(1) << self.bit_len().div_ceil(2) as u32;
+        loop {
+            let y = (&x + &(self / &x)) >> 1;
+            is![y >= x, return x];
+            x = y;
+        }
+    }
+
+    /* combinatorics */
+
+    /// Returns the factorial of `n`.
+    #[must_use]
+    pub fn factorial(n: u64) -> Self {
+        let mut limbs = vec![1];
+        for i in 2..=n {
+            let carry = limbs::mul_small(&mut limbs, i, 0);
+            is![carry != 0, limbs.push(carry)];
+        }
+        Self { limbs }
+    }
+    /// Returns the number of combinations of `r` elements out of `n`, without repetition.
+    #[must_use]
+    pub fn combine(n: u64, r: u64) -> Self {
+        is![r > n, return Self::ZERO];
+        let r = r.min(n - r);
+        let mut acc = Self::from_u64(1);
+        // each partial product is itself a binomial coefficient, so the division is exact
+        for i in 0..r {
+            let carry = limbs::mul_small(&mut acc.limbs, n - i, 0);
+            is![carry != 0, acc.limbs.push(carry)];
+            let _ = limbs::div_small(&mut acc.limbs, i + 1);
+            acc.limbs = normalized(acc.limbs);
+        }
+        acc
+    }
+
+    /* modular */
+
+    /// Returns `self` to the power of `exp` modulo `modulus`, or `None` if `modulus` is `0`.
+    ///
+    /// Uses Montgomery multiplication for odd moduli.
+    #[must_use]
+    pub fn pow_mod(&self, exp: &Self, modulus: &Self) -> Option<Self> {
+        is![modulus.is_zero(), return None];
+        is![modulus.is_one(), return Some(Self::ZERO)];
+        let base = self.checked_rem(modulus)?;
+        let bits = exp.bit_len();
+        if modulus.is_odd() {
+            let n = &modulus.limbs;
+            let l = n.len();
+            let ninv = limbs::mont_inv(n[0]);
+            let to_mont = |a: &Self| {
+                let mut shifted = vec![0; l];
+                shifted.extend_from_slice(&a.limbs);
+                let r = Self::from_limbs(shifted).checked_rem(modulus).expect("non-zero modulus");
+                let mut padded = r.limbs;
+                padded.resize(l, 0);
+                padded
+            };
+            let base = to_mont(&base);
+            let mut acc = to_mont(&Self::from_u64(1));
+            let mut tmp = vec![0; l];
+            for i in (0..bits).rev() {
+                limbs::mont_mul(&mut tmp, &acc, &acc, n, ninv);
+                if exp.bit(i) {
+                    limbs::mont_mul(&mut acc, &tmp, &base, n, ninv);
+                } else {
+                    Mem::swap(&mut acc, &mut tmp);
+                }
+            }
+            let mut one = vec![0; l];
+            one[0] = 1;
+            limbs::mont_mul(&mut tmp, &acc, &one, n, ninv);
+            Some(Self::from_limbs(tmp))
+        } else {
+            let mut acc = Self::from_u64(1);
+            for i in (0..bits).rev() {
+                acc = &(&acc * &acc) % modulus;
+                is![exp.bit(i), acc = &(&acc * &base) % modulus];
+            }
+            Some(acc)
+        }
+    }
+    /// Returns the modular multiplicative inverse of `self` modulo `modulus`,
+    /// or `None` if it doesn't exist.
+    #[must_use]
+    pub fn inv_mod(&self, modulus: &Self) -> Option<Self> {
+        is![modulus.is_zero(), return None];
+        let (mut r0, mut r1) = (modulus.clone(), self.checked_rem(modulus)?);
+        let (mut t0, mut t1) = (Self::ZERO, Self::from_u64(1));
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1)?;
+            let qt = &(&q * &t1) % modulus;
+            let t2 = is![t0 >= qt, &t0 - &qt, &(&t0 + modulus) - &qt];
+            (r0, r1, t0, t1) = (r1, r, t1, t2);
+        }
+        is![r0.is_one(), Some(t0), None]
+    }
+    /// Returns the greatest common divisor of `self` and `other`.
+    #[must_use]
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = &a % &b;
+            (a, b) = (b, r);
+        }
+        a
+    }
+    /// Returns the least common multiple of `self` and `other`.
+    #[must_use]
+    pub fn lcm(&self, other: &Self) -> Self {
+        is![self.is_zero() || other.is_zero(), return Self::ZERO];
+        &(self / &self.gcd(other)) * other
+    }
+
+    /* primes */
+
+    /// Returns `true` if the value is prime.
+    ///
+    /// Values that fit in a `u32` are tested by [`Int::is_prime`][crate::Int::is_prime].
+    /// Larger ones are screened by trial division by small primes, followed by
+    /// the Miller-Rabin test with the first 13 prime bases, which is deterministic
+    /// below 3.3·10²⁴, and a strong probable prime test above it.
+    ///
+    /// See also [`IntWide::is_prime`].
+    #[must_use]
+    pub fn is_prime(&self) -> bool {
+        if let Some(n) = self.to_u64().and_then(|n| u32::try_from(n).ok()) {
+            return crate::Int(n).is_prime();
+        }
+        is![!self.is_odd(), return false];
+        let (_, r) = self.div_rem_u64(_helper::SMALL_PRODUCT).expect("non-zero divisor");
+        is![!_helper::coprime_small(r), return false];
+        let one = Self::from_u64(1);
+        let n1 = self - &one;
+        let s = n1.trailing_zeros();
+        let d = &n1 >> s as u32;
+        'bases: for base in _helper::BASES {
+            let mut x = Self::from_u64(base).pow_mod(&d, self).expect("non-zero modulus");
+            is![x == one || x == n1, continue];
+            for _ in 1..s {
+                x = &(&x * &x) % self;
+                is![x == n1, continue 'bases];
+                is![x == one, return false];
+            }
+            return false;
+        }
+        true
+    }
+
+    /* text */
+
+    /// Parses an integer from a string in the given `radix`, from 2 to 36.
+    ///
+    /// An optional `+` sign and `_` digit separators are accepted.
+    ///
+    /// # Errors
+    /// Returns [`InvalidValue`] if the radix is out of range, the string has no digits,
+    /// or contains an invalid digit.
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, InvalidValue> {
+        is![!(2..=36).contains(&radix), return Err(InvalidValue)];
+        let src = src.strip_prefix('+').unwrap_or(src);
+        let (_, exp) = _helper::radix_chunk(radix);
+        let mut limbs = Vec::with_capacity(src.len() / 16 + 1);
+        let (mut chunk, mut scale, mut digits) = (0, 1, 0);
+        for b in src.bytes() {
+            is![b == b'_', continue];
+            let d = _helper::digit_value(b, radix).ok_or(InvalidValue)?;
+            chunk = chunk * u64::from(radix) + u64::from(d);
+            scale *= u64::from(radix);
+            digits += 1;
+            if digits % exp == 0 {
+                push_mul_add(&mut limbs, scale, chunk);
+                (chunk, scale) = (0, 1);
+            }
+        }
+        is![digits == 0, return Err(InvalidValue)];
+        is![scale > 1, push_mul_add(&mut limbs, scale, chunk)];
+        Ok(Self::from_limbs(limbs))
+    }
+    /// Returns the digits in the given `radix`, from 2 to 36, in lowercase.
+    ///
+    /// # Panics
+    /// Panics if `radix` is out of range.
+    #[must_use]
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert![(2..=36).contains(&radix), "radix must be between 2 and 36"];
+        let count = _helper::count_digits(&mut self.limbs.clone(), radix);
+        let mut buf = vec![0; count];
+        let _ = _helper::write_digits(&mut self.limbs.clone(), &mut buf, count, radix);
+        String::from_utf8(buf).expect("ASCII digits")
+    }
+
+    /* operator implementations */
+
+    fn add_impl(a: &Self, b: &Self) -> Self {
+        Self { limbs: add_slices(&a.limbs, &b.limbs) }
+    }
+    fn sub_impl(a: &Self, b: &Self) -> Self {
+        a.checked_sub(b).expect("attempt to subtract with overflow")
+    }
+    fn mul_impl(a: &Self, b: &Self) -> Self {
+        Self { limbs: mul_slices(&a.limbs, &b.limbs) }
+    }
+    fn div_impl(a: &Self, b: &Self) -> Self {
+        a.checked_div(b).expect("attempt to divide by zero")
+    }
+    fn rem_impl(a: &Self, b: &Self) -> Self {
+        a.checked_rem(b).expect("attempt to calculate the remainder with a divisor of zero")
+    }
+}
+
+/* traits */
+
+impl_big_ops![BigUint:
+    Add, add, AddAssign, add_assign, add_impl;
+    Sub, sub, SubAssign, sub_assign, sub_impl;
+    Mul, mul, MulAssign, mul_assign, mul_impl;
+    Div, div, DivAssign, div_assign, div_impl;
+    Rem, rem, RemAssign, rem_assign, rem_impl;
+];
+
+impl core::ops::Shl<u32> for &BigUint {
+    type Output = BigUint;
+    fn shl(self, bits: u32) -> BigUint {
+        is![self.is_zero(), return BigUint::ZERO];
+        let mut limbs = vec![0; (bits / limbs::BITS) as usize];
+        limbs.extend_from_slice(&self.limbs);
+        let start = limbs.len() - self.limbs.len();
+        let carry = limbs::shl_bits(&mut limbs[start..], bits % limbs::BITS);
+        is![carry != 0, limbs.push(carry)];
+        BigUint { limbs }
+    }
+}
+impl core::ops::Shl<u32> for BigUint {
+    type Output = BigUint;
+    fn shl(self, bits: u32) -> BigUint {
+        &self << bits
+    }
+}
+impl core::ops::Shr<u32> for &BigUint {
+    type Output = BigUint;
+    fn shr(self, bits: u32) -> BigUint {
+        let step = (bits / limbs::BITS) as usize;
+        is![step >= self.limbs.len(), return BigUint::ZERO];
+        let mut limbs = self.limbs[step..].to_vec();
+        limbs::shr_bits(&mut limbs, bits % limbs::BITS, 0);
+        BigUint::from_limbs(limbs)
+    }
+}
+impl core::ops::Shr<u32> for BigUint {
+    type Output = BigUint;
+    fn shr(self, bits: u32) -> BigUint {
+        &self >> bits
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        limbs::cmp(&self.limbs, &other.limbs)
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty),+) => {$(
+        impl From<$t> for BigUint {
+            fn from(value: $t) -> Self {
+                Self::from_u128(value as u128)
+            }
+        }
+    )+};
+}
+impl_from![u8, u16, u32, u64, u128, usize];
+impl<const L: usize> From<IntWide<L>> for BigUint {
+    fn from(value: IntWide<L>) -> Self {
+        Self::from_wide(value)
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, InvalidValue> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+macro_rules! impl_fmt {
+    ($($trait:ident: $radix:literal, $prefix:literal, $upper:literal);+ $(;)?) => {$(
+        impl $trait for BigUint {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+                let mut digits = self.to_str_radix($radix);
+                is![$upper, digits.make_ascii_uppercase()];
+                f.pad_integral(true, $prefix, &digits)
+            }
+        }
+    )+};
+}
+impl_fmt![
+    Binary: 2, "0b", false;
+    Octal: 8, "0o", false;
+    Display: 10, "", false;
+    LowerHex: 16, "0x", false;
+    UpperHex: 16, "0x", true;
+];
+impl Debug for BigUint {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+        write!(f, "BigUint({self})")
+    }
+}
+
+impl FmtNum<&BigUint> {
+    /// Writes the integer as ASCII digits in `radix` into `buf` starting at `pos`.
+    ///
+    /// Returns the number of bytes written, or `0` if the buffer is too small
+    /// or `radix` is not between 2 and 36.
+    ///
+    /// # Behavioral guarantees
+    /// - Digits above 9 are written in lowercase.
+    /// - The operation is atomic: on failure, nothing is written.
+    pub fn write_radix(self, buf: &mut [u8], pos: usize, radix: u32) -> usize {
+        is![!(2..=36).contains(&radix), return 0];
+        let needed = _helper::count_digits(&mut self.0.limbs.clone(), radix);
+        is![needed > buf.len().saturating_sub(pos), return 0];
+        _helper::write_digits(&mut self.0.limbs.clone(), buf, pos + needed, radix)
+    }
+    /// Writes the integer as ASCII decimal digits into `buf` starting at `pos`.
+    ///
+    /// Returns the number of bytes written, or `0` if the buffer is too small.
+    ///
+    /// # Behavioral guarantees
+    /// - The operation is atomic: on failure, nothing is written.
+    pub fn write(self, buf: &mut [u8], pos: usize) -> usize {
+        self.write_radix(buf, pos, 10)
+    }
+    /// Measures the decimal formatting of the integer.
+    #[must_use]
+    pub fn measure(self) -> FmtNumShape {
+        let left = _helper::count_digits(&mut self.0.limbs.clone(), 10);
+        FmtNumShape::new(0, left as u16, 0)
+    }
+}
+
+/* helpers */
+
+/// Returns `limbs` without its most significant zeros.
+fn normalized(mut limbs: Vec<u64>) -> Vec<u64> {
+    limbs.truncate(limbs::len(&limbs));
+    limbs
+}
+/// Multiplies `limbs` by `mul` and adds `add`, growing it as needed.
+fn push_mul_add(limbs: &mut Vec<u64>, mul: u64, add: u64) {
+    let carry = limbs::mul_small(limbs, mul, add);
+    is![carry != 0, limbs.push(carry)];
+}
+/// Returns the sum of the magnitudes `a` and `b`.
+fn add_slices(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = is![a.len() < b.len(), (b, a), (a, b)];
+    let mut out = a.to_vec();
+    is![limbs::add_assign(&mut out, b), out.push(1)];
+    normalized(out)
+}
+/// Returns the product of the magnitudes `a` and `b`.
+///
+/// Splits both operands in halves recursively while the shorter one has at least
+/// [`KARATSUBA_THRESHOLD`] limbs, replacing four half-size products with three.
+fn mul_slices(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = (&a[..limbs::len(a)], &b[..limbs::len(b)]);
+    let (a, b) = is![a.len() < b.len(), (b, a), (a, b)];
+    is![b.is_empty(), return Vec::new()];
+    let mut out = vec![0; a.len() + b.len()];
+    if b.len() < KARATSUBA_THRESHOLD {
+        let _ = limbs::mul_acc(&mut out, a, b);
+    } else if b.len() <= a.len() / 2 {
+        // unbalanced operands: multiply by slices of the longer one
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            let p = mul_slices(chunk, b);
+            let _ = limbs::add_assign(&mut out[i * b.len()..], &p);
+        }
+    } else {
+        let m = a.len() / 2;
+        let ((a0, a1), (b0, b1)) = (a.split_at(m), b.split_at(m));
+        let z0 = mul_slices(a0, b0);
+        let z2 = mul_slices(a1, b1);
+        let mut z1 = mul_slices(&add_slices(a0, a1), &add_slices(b0, b1));
+        let _ = limbs::sub_assign(&mut z1, &z0);
+        let _ = limbs::sub_assign(&mut z1, &z2);
+        let _ = limbs::add_assign(&mut out, &z0);
+        let _ = limbs::add_assign(&mut out[m..], &z1[..limbs::len(&z1)]);
+        let _ = limbs::add_assign(&mut out[2 * m..], &z2);
+    }
+    normalized(out)
+}
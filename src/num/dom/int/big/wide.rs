@@ -0,0 +1,707 @@
+// devela/src/num/dom/int/big/wide.rs
+//
+//! Defines [`IntWide`].
+//
+// TOC
+// - struct IntWide
+// - impl IntWide
+// - impl traits
+
+use super::{_helper, _limbs as limbs};
+use crate::{
+    Binary, ConstInit, Debug, Display, FmtNum, FmtNumShape, FmtResult, Formatter, InvalidValue,
+    LowerHex, Octal, Ordering, UpperHex, is, unwrap, whilst,
+};
+
+#[doc = crate::_tags!(num)]
+/// A fixed-size unsigned integer made of `LIMBS` 64-bit limbs.
+#[doc = crate::_doc_meta!{location("num/dom/int")}]
+///
+/// It doesn't allocate, and almost all of its methods are `const`.
+/// The limbs are stored in little-endian order.
+///
+/// Arithmetic operators panic on overflow and on division by zero,
+/// use the `checked_*` and `wrapping_*` methods to handle them.
+///
+/// See also [`BigUint`][crate::BigUint] for an allocated integer without a fixed size.
+///
+/// # Example
+/// ```
+/// # use devela::IntWide;
+/// type U256 = IntWide<4>;
+/// let a = U256::from_u128(u128::MAX);
+/// let sq = a * a;
+/// assert_eq![sq.bit_len(), 256];
+/// assert_eq![sq / a, a];
+///
+/// // Fermat's little theorem
+/// let p = U256::from_str_radix("115792089237316195423570985008687907853269984665640564039457584007908834671663", 10).unwrap();
+/// let base = U256::from_u64(5);
+/// assert_eq![base.pow_mod(p - U256::ONE, p), Some(U256::ONE)];
+/// assert![p.is_prime()];
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntWide<const LIMBS: usize> {
+    limbs: [u64; LIMBS],
+}
+
+impl<const L: usize> ConstInit for IntWide<L> {
+    const INIT: Self = Self::ZERO;
+}
+impl<const L: usize> Default for IntWide<L> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const L: usize> IntWide<L> {
+    /// The number of bits.
+    pub const BITS: u32 = L as u32 * limbs::BITS;
+    /// The value `0`.
+    pub const ZERO: Self = Self { limbs: [0; L] };
+    /// The value `1`.
+    ///
+    /// It's `0` when `LIMBS == 0`.
+    pub const ONE: Self = {
+        let mut limbs = [0; L];
+        is![L > 0, limbs[0] = 1];
+        Self { limbs }
+    };
+    /// The largest value.
+    pub const MAX: Self = Self { limbs: [u64::MAX; L] };
+
+    /* construction */
+
+    /// Returns a new integer from its little-endian `limbs`.
+    #[must_use]
+    pub const fn from_limbs(limbs: [u64; L]) -> Self {
+        Self { limbs }
+    }
+    /// Returns the little-endian limbs.
+    #[must_use]
+    pub const fn limbs(&self) -> &[u64; L] {
+        &self.limbs
+    }
+    /// Returns the little-endian limbs, consuming the integer.
+    #[must_use]
+    pub const fn into_limbs(self) -> [u64; L] {
+        self.limbs
+    }
+    /// Returns a new integer from a `u64`, truncated if `LIMBS == 0`.
+    #[must_use]
+    pub const fn from_u64(value: u64) -> Self {
+        let mut limbs = [0; L];
+        is![L > 0, limbs[0] = value];
+        Self { limbs }
+    }
+    /// Returns a new integer from a `u128`, truncated if it doesn't fit.
+    #[must_use]
+    pub const fn from_u128(value: u128) -> Self {
+        let mut limbs = [0; L];
+        is![L > 0, limbs[0] = value as u64];
+        is![L > 1, limbs[1] = (value >> limbs::BITS) as u64];
+        Self { limbs }
+    }
+    /// Returns the value as a `u64` if it fits.
+    #[must_use]
+    pub const fn to_u64(&self) -> Option<u64> {
+        match limbs::len(&self.limbs) {
+            0 => Some(0),
+            1 => Some(self.limbs[0]),
+            _ => None,
+        }
+    }
+    /// Returns the value as a `u128` if it fits.
+    #[must_use]
+    pub const fn to_u128(&self) -> Option<u128> {
+        match limbs::len(&self.limbs) {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u128),
+            2 => Some(((self.limbs[1] as u128) << limbs::BITS) | self.limbs[0] as u128),
+            _ => None,
+        }
+    }
+    /// Returns the integer with a different number of limbs, or `None` if it doesn't fit.
+    #[must_use]
+    pub const fn resize<const M: usize>(&self) -> Option<IntWide<M>> {
+        is![limbs::len(&self.limbs) > M, return None];
+        let mut limbs = [0; M];
+        whilst! { i in 0..L; { is![i < M, limbs[i] = self.limbs[i]]; }}
+        Some(IntWide { limbs })
+    }
+
+    /* queries */
+
+    /// Returns `true` if the value is `0`.
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        limbs::is_zero(&self.limbs)
+    }
+    /// Returns `true` if the value is odd.
+    #[must_use]
+    pub const fn is_odd(&self) -> bool {
+        L > 0 && self.limbs[0] & 1 == 1
+    }
+    /// Returns the number of significant bits.
+    #[must_use]
+    pub const fn bit_len(&self) -> u32 {
+        limbs::bit_len(&self.limbs)
+    }
+    /// Returns the number of trailing zero bits, or [`BITS`][Self::BITS] if it's `0`.
+    #[must_use]
+    pub const fn trailing_zeros(&self) -> u32 {
+        whilst! { i in 0..L; {
+            is![self.limbs[i] != 0, return i as u32 * limbs::BITS + self.limbs[i].trailing_zeros()];
+        }}
+        Self::BITS
+    }
+    /// Returns the bit at `index`, or `false` if it's out of bounds.
+    #[must_use]
+    pub const fn bit(&self, index: u32) -> bool {
+        let i = (index / limbs::BITS) as usize;
+        i < L && (self.limbs[i] >> (index % limbs::BITS)) & 1 == 1
+    }
+    /// Compares two integers in a const context.
+    #[must_use]
+    pub const fn const_cmp(&self, other: &Self) -> Ordering {
+        limbs::cmp(&self.limbs, &other.limbs)
+    }
+
+    /* add, sub */
+
+    /// Returns `self + rhs` and whether it overflowed.
+    #[must_use]
+    pub const fn overflowing_add(mut self, rhs: Self) -> (Self, bool) {
+        let carry = limbs::add_assign(&mut self.limbs, &rhs.limbs);
+        (self, carry)
+    }
+    /// Returns `self + rhs`, or `None` on overflow.
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (sum, carry) = self.overflowing_add(rhs);
+        is![carry, None, Some(sum)]
+    }
+    /// Returns `self + rhs`, wrapping around on overflow.
+    #[must_use]
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+    /// Returns `self - rhs` and whether it underflowed.
+    #[must_use]
+    pub const fn overflowing_sub(mut self, rhs: Self) -> (Self, bool) {
+        let borrow = limbs::sub_assign(&mut self.limbs, &rhs.limbs);
+        (self, borrow)
+    }
+    /// Returns `self - rhs`, or `None` on underflow.
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (diff, borrow) = self.overflowing_sub(rhs);
+        is![borrow, None, Some(diff)]
+    }
+    /// Returns `self - rhs`, wrapping around on underflow.
+    #[must_use]
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    /* mul, div */
+
+    /// Returns `self * rhs` truncated to `LIMBS`, and whether it overflowed.
+    #[must_use]
+    pub const fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let mut out = Self::ZERO;
+        let overflow = limbs::mul_acc(&mut out.limbs, &self.limbs, &rhs.limbs);
+        (out, overflow)
+    }
+    /// Returns `self * rhs`, or `None` on overflow.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let (prod, overflow) = self.overflowing_mul(rhs);
+        is![overflow, None, Some(prod)]
+    }
+    /// Returns `self * rhs`, wrapping around on overflow.
+    #[must_use]
+    pub const fn wrapping_mul(self, rhs: Self) -> Self {
+        self.overflowing_mul(rhs).0
+    }
+    /// Returns the full product `self * rhs` as its low and high halves.
+    #[must_use]
+    pub const fn widening_mul(self, rhs: Self) -> (Self, Self) {
+        let mut buf = [[0; L]; 2];
+        let _ = limbs::mul_acc(buf.as_flattened_mut(), &self.limbs, &rhs.limbs);
+        (Self { limbs: buf[0] }, Self { limbs: buf[1] })
+    }
+    /// Returns the quotient and remainder of `self / rhs`, or `None` if `rhs` is `0`.
+    #[must_use]
+    pub const fn div_rem(self, rhs: Self) -> Option<(Self, Self)> {
+        is![rhs.is_zero(), return None];
+        let (mut rem, mut div, mut quo) = (self, rhs, Self::ZERO);
+        limbs::div_rem(&mut rem.limbs, &mut div.limbs, &mut quo.limbs);
+        Some((quo, rem))
+    }
+    /// Returns `self / rhs`, or `None` if `rhs` is `0`.
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        match self.div_rem(rhs) {
+            Some((q, _)) => Some(q),
+            None => None,
+        }
+    }
+    /// Returns `self % rhs`, or `None` if `rhs` is `0`.
+    #[must_use]
+    pub const fn checked_rem(self, rhs: Self) -> Option<Self> {
+        match self.div_rem(rhs) {
+            Some((_, r)) => Some(r),
+            None => None,
+        }
+    }
+    /// Returns the quotient and remainder of dividing by a single limb, or `None` if it's `0`.
+    #[must_use]
+    pub const fn div_rem_u64(mut self, rhs: u64) -> Option<(Self, u64)> {
+        is![rhs == 0, return None];
+        let rem = limbs::div_small(&mut self.limbs, rhs);
+        Some((self, rem))
+    }
+    /// Returns `self` to the power of `exp`, or `None` on overflow.
+    #[must_use]
+    pub const fn checked_pow(self, mut exp: u32) -> Option<Self> {
+        let (mut base, mut acc) = (self, Self::ONE);
+        while exp > 0 {
+            is![exp & 1 == 1, acc = unwrap![some? acc.checked_mul(base)]];
+            exp >>= 1;
+            is![exp > 0, base = unwrap![some? base.checked_mul(base)]];
+        }
+        Some(acc)
+    }
+
+    /* shifts */
+
+    /// Returns `self << bits`, discarding the bits shifted out,
+    /// or `0` if `bits >= BITS`.
+    #[must_use]
+    pub const fn shl(self, bits: u32) -> Self {
+        is![bits >= Self::BITS, return Self::ZERO];
+        let (step, rest) = ((bits / limbs::BITS) as usize, bits % limbs::BITS);
+        let mut limbs = [0; L];
+        whilst! { i in step, ..L; { limbs[i] = self.limbs[i - step]; }}
+        let _ = limbs::shl_bits(&mut limbs, rest);
+        Self { limbs }
+    }
+    /// Returns `self >> bits`, or `0` if `bits >= BITS`.
+    #[must_use]
+    pub const fn shr(self, bits: u32) -> Self {
+        is![bits >= Self::BITS, return Self::ZERO];
+        let (step, rest) = ((bits / limbs::BITS) as usize, bits % limbs::BITS);
+        let mut limbs = [0; L];
+        whilst! { i in step, ..L; { limbs[i - step] = self.limbs[i]; }}
+        limbs::shr_bits(&mut limbs, rest, 0);
+        Self { limbs }
+    }
+
+    /* modular */
+
+    /// Returns `(self * rhs) % modulus`, or `None` if `modulus` is `0`.
+    #[must_use]
+    pub const fn mul_mod(self, rhs: Self, modulus: Self) -> Option<Self> {
+        is![modulus.is_zero(), return None];
+        let mut buf = [[0; L]; 2];
+        let _ = limbs::mul_acc(buf.as_flattened_mut(), &self.limbs, &rhs.limbs);
+        Some(Self::reduce(&mut buf, modulus))
+    }
+    /// Returns `self` to the power of `exp` modulo `modulus`, or `None` if `modulus` is `0`.
+    ///
+    /// Uses Montgomery multiplication for odd moduli.
+    #[must_use]
+    pub const fn pow_mod(self, exp: Self, modulus: Self) -> Option<Self> {
+        is![modulus.is_zero(), return None];
+        is![limbs::len(&modulus.limbs) == 1 && modulus.limbs[0] == 1, return Some(Self::ZERO)];
+        let base = unwrap![some self.checked_rem(modulus)];
+        let bits = exp.bit_len();
+        if modulus.is_odd() {
+            let n = &modulus.limbs;
+            let ninv = limbs::mont_inv(n[0]);
+            let base = Self::to_mont(base, modulus);
+            let (mut acc, mut tmp) = (Self::to_mont(Self::ONE, modulus), Self::ZERO);
+            let mut i = bits;
+            while i > 0 {
+                i -= 1;
+                limbs::mont_mul(&mut tmp.limbs, &acc.limbs, &acc.limbs, n, ninv);
+                if exp.bit(i) {
+                    limbs::mont_mul(&mut acc.limbs, &tmp.limbs, &base.limbs, n, ninv);
+                } else {
+                    acc = tmp;
+                }
+            }
+            limbs::mont_mul(&mut tmp.limbs, &acc.limbs, &Self::ONE.limbs, n, ninv);
+            Some(tmp)
+        } else {
+            let mut acc = Self::ONE;
+            let mut i = bits;
+            while i > 0 {
+                i -= 1;
+                acc = unwrap![some acc.mul_mod(acc, modulus)];
+                is![exp.bit(i), acc = unwrap![some acc.mul_mod(base, modulus)]];
+            }
+            Some(acc)
+        }
+    }
+    /// Returns the modular multiplicative inverse of `self` modulo `modulus`,
+    /// or `None` if it doesn't exist.
+    #[must_use]
+    pub const fn inv_mod(self, modulus: Self) -> Option<Self> {
+        is![modulus.is_zero(), return None];
+        // extended euclid tracking the coefficients of `self` modulo `modulus`
+        let (mut r0, mut r1) = (modulus, unwrap![some self.checked_rem(modulus)]);
+        let (mut t0, mut t1) = (Self::ZERO, Self::ONE);
+        while !r1.is_zero() {
+            let (q, r) = unwrap![some r0.div_rem(r1)];
+            let qt = unwrap![some q.mul_mod(t1, modulus)];
+            let t2 = Self::sub_mod(t0, qt, modulus);
+            (r0, r1, t0, t1) = (r1, r, t1, t2);
+        }
+        is![limbs::len(&r0.limbs) == 1 && r0.limbs[0] == 1, Some(t0), None]
+    }
+    /// Returns the greatest common divisor of `self` and `other`.
+    #[must_use]
+    pub const fn gcd(self, other: Self) -> Self {
+        // binary gcd
+        is![self.is_zero(), return other];
+        is![other.is_zero(), return self];
+        let shift = Self::min_u32(self.trailing_zeros(), other.trailing_zeros());
+        let mut a = self.shr(self.trailing_zeros());
+        let mut b = other;
+        while !b.is_zero() {
+            b = b.shr(b.trailing_zeros());
+            if matches!(a.const_cmp(&b), Ordering::Greater) {
+                let t = a;
+                a = b;
+                b = t;
+            }
+            b = b.wrapping_sub(a);
+        }
+        a.shl(shift)
+    }
+    /// Returns the least common multiple of `self` and `other`, or `None` on overflow.
+    #[must_use]
+    pub const fn lcm(self, other: Self) -> Option<Self> {
+        is![self.is_zero() || other.is_zero(), return Some(Self::ZERO)];
+        let g = self.gcd(other);
+        unwrap![some self.checked_div(g)].checked_mul(other)
+    }
+
+    /* primes */
+
+    /// Returns `true` if the value is prime.
+    ///
+    /// Values that fit in a `u32` are tested by [`Int::is_prime`][crate::Int::is_prime].
+    /// Larger ones are screened by trial division by small primes, followed by
+    /// the Miller-Rabin test with the first [`PRIME_BASES`][Self::PRIME_BASES] prime bases,
+    /// which is deterministic below 3.3·10²⁴, and a strong probable prime test above it.
+    #[must_use]
+    pub const fn is_prime(&self) -> bool {
+        if let Some(n) = self.to_u64()
+            && n <= u32::MAX as u64
+        {
+            return crate::Int(n as u32).is_prime();
+        }
+        is![!self.is_odd(), return false];
+        let (_, r) = unwrap![some self.div_rem_u64(_helper::SMALL_PRODUCT)];
+        is![!_helper::coprime_small(r), return false];
+        let one = Self::ONE;
+        let n1 = self.wrapping_sub(one);
+        let s = n1.trailing_zeros();
+        let d = n1.shr(s);
+        whilst! { i in 0..Self::PRIME_BASES; {
+            let mut x = unwrap![some Self::from_u64(_helper::BASES[i]).pow_mod(d, *self)];
+            if !(x.eq_const(&one) || x.eq_const(&n1)) {
+                let mut r = 1;
+                let mut found = false;
+                while r < s {
+                    x = unwrap![some x.mul_mod(x, *self)];
+                    is![x.eq_const(&n1), { found = true; break; }];
+                    is![x.eq_const(&one), return false];
+                    r += 1;
+                }
+                is![!found, return false];
+            }
+        }}
+        true
+    }
+    /// The number of prime bases used by the Miller-Rabin test in [`is_prime`][Self::is_prime].
+    pub const PRIME_BASES: usize = _helper::BASES.len();
+
+    /* text */
+
+    /// Parses an integer from a string in the given `radix`, from 2 to 36.
+    ///
+    /// An optional `+` sign and `_` digit separators are accepted.
+    ///
+    /// # Errors
+    /// Returns [`InvalidValue`] if the radix is out of range, the string has no digits,
+    /// contains an invalid digit, or the value doesn't fit.
+    pub const fn from_str_radix(src: &str, radix: u32) -> Result<Self, InvalidValue> {
+        is![radix < 2 || radix > 36, return Err(InvalidValue)];
+        let bytes = src.as_bytes();
+        let mut i = is![!bytes.is_empty() && bytes[0] == b'+', 1, 0];
+        let mut out = Self::ZERO;
+        let mut digits = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            i += 1;
+            is![b == b'_', continue];
+            let d = unwrap![some_ok_or? _helper::digit_value(b, radix), InvalidValue];
+            is![
+                limbs::mul_small(&mut out.limbs, radix as u64, d as u64) != 0,
+                return Err(InvalidValue)
+            ];
+            digits += 1;
+        }
+        is![digits == 0, Err(InvalidValue), Ok(out)]
+    }
+
+    /* helpers */
+
+    const fn eq_const(&self, other: &Self) -> bool {
+        matches!(self.const_cmp(other), Ordering::Equal)
+    }
+    const fn min_u32(a: u32, b: u32) -> u32 {
+        is![a < b, a, b]
+    }
+    /// Returns `(a - b) mod m`, for `a, b < m`.
+    const fn sub_mod(a: Self, b: Self, m: Self) -> Self {
+        let (d, borrow) = a.overflowing_sub(b);
+        is![borrow, d.wrapping_add(m), d]
+    }
+    /// Reduces the double-width `buf` modulo the non-zero `modulus`.
+    const fn reduce(buf: &mut [[u64; L]; 2], modulus: Self) -> Self {
+        let mut div = modulus;
+        let mut quo = [[0; L]; 2];
+        limbs::div_rem(buf.as_flattened_mut(), &mut div.limbs, quo.as_flattened_mut());
+        Self { limbs: buf[0] }
+    }
+    /// Returns `a · R mod n`, the Montgomery form of `a < n`, with `R = 2^BITS`.
+    const fn to_mont(a: Self, modulus: Self) -> Self {
+        Self::reduce(&mut [[0; L], a.limbs], modulus)
+    }
+    /// Writes the digits in `radix`, most significant first, without allocating.
+    fn fmt_digits(&self, f: &mut Formatter, radix: u32, upper: bool) -> FmtResult<()> {
+        let (pow, exp) = _helper::radix_chunk(radix);
+        // each chunk holds at least 62 bits, so twice the limbs is enough room
+        let mut chunks = [[0; L]; 2];
+        let chunks = chunks.as_flattened_mut();
+        let (mut value, mut count) = (self.limbs, 0);
+        loop {
+            chunks[count] = limbs::div_small(&mut value, pow);
+            count += 1;
+            is![limbs::is_zero(&value) || count == chunks.len(), break];
+        }
+        let mut buf = [b'0'; 64];
+        let mut i = count;
+        while i > 0 {
+            i -= 1;
+            let n = _helper::write_digits(&mut [chunks[i]], &mut buf, exp, radix);
+            let start = is![i == count - 1, exp - n, 0];
+            is![upper, buf[..exp].make_ascii_uppercase()];
+            f.write_str(crate::Str::from_utf8(&buf[start..exp]).map_err(|_| crate::FmtError)?)?;
+            buf[..exp].fill(b'0');
+        }
+        Ok(())
+    }
+    /// Formats the value in `radix`, honoring the width, fill, alignment and flags.
+    fn fmt_radix(&self, f: &mut Formatter, radix: u32, prefix: &str, upper: bool) -> FmtResult<()> {
+        use crate::{FmtAlignment, FmtWrite};
+        is![L == 0, return f.pad_integral(true, prefix, "0")];
+        let digits = _helper::count_digits(&mut { self.limbs }, radix);
+        let prefix = is![f.alternate(), prefix, ""];
+        let sign = is![f.sign_plus(), "+", ""];
+        let pad = f.width().map_or(0, |w| w.saturating_sub(sign.len() + prefix.len() + digits));
+        if f.sign_aware_zero_pad() {
+            f.write_str(sign)?;
+            f.write_str(prefix)?;
+            for _ in 0..pad {
+                f.write_char('0')?;
+            }
+            return self.fmt_digits(f, radix, upper);
+        }
+        let (pre, post) = match f.align() {
+            Some(FmtAlignment::Left) => (0, pad),
+            Some(FmtAlignment::Center) => (pad / 2, pad - pad / 2),
+            _ => (pad, 0),
+        };
+        let fill = f.fill();
+        for _ in 0..pre {
+            f.write_char(fill)?;
+        }
+        f.write_str(sign)?;
+        f.write_str(prefix)?;
+        self.fmt_digits(f, radix, upper)?;
+        for _ in 0..post {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/* traits */
+
+impl<const L: usize> PartialOrd for IntWide<L> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<const L: usize> Ord for IntWide<L> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.const_cmp(other)
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty),+) => {$(
+        impl<const L: usize> From<$t> for IntWide<L> {
+            /// Fails to compile for `IntWide<0>`, that can't hold it.
+            fn from(value: $t) -> Self {
+                const { assert![L >= 1, "IntWide<0> can only hold 0"]; }
+                Self::from_u64(value as u64)
+            }
+        }
+    )+};
+}
+impl_from![u8, u16, u32, u64, usize];
+impl<const L: usize> TryFrom<u128> for IntWide<L> {
+    type Error = InvalidValue;
+    /// # Errors
+    /// Returns [`InvalidValue`] if the value doesn't fit,
+    /// which can only happen with less than 2 limbs.
+    fn try_from(value: u128) -> Result<Self, InvalidValue> {
+        let fits = L >= 2 || value >> (L as u32 * limbs::BITS) == 0;
+        is![fits, Ok(Self::from_u128(value)), Err(InvalidValue)]
+    }
+}
+
+impl<const L: usize> crate::FromStr for IntWide<L> {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, InvalidValue> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+macro_rules! impl_fmt {
+    ($($trait:ident: $radix:literal, $prefix:literal);+ $(;)?) => {$(
+        impl<const L: usize> $trait for IntWide<L> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+                self.fmt_radix(f, $radix, $prefix, false)
+            }
+        }
+    )+};
+}
+impl_fmt![Binary: 2, "0b"; Octal: 8, "0o"; Display: 10, ""; LowerHex: 16, "0x"];
+impl<const L: usize> Debug for IntWide<L> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+        write!(f, "IntWide<{L}>({self})")
+    }
+}
+impl<const L: usize> UpperHex for IntWide<L> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+        self.fmt_radix(f, 16, "0x", true)
+    }
+}
+
+macro_rules! impl_ops {
+    ($($trait:ident, $fn:ident, $assign:ident, $assign_fn:ident, $checked:ident, $msg:literal);+ $(;)?) => {$(
+        impl<const L: usize> core::ops::$trait for IntWide<L> {
+            type Output = Self;
+            fn $fn(self, rhs: Self) -> Self {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+        impl<const L: usize> core::ops::$assign for IntWide<L> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = core::ops::$trait::$fn(*self, rhs);
+            }
+        }
+    )+};
+}
+impl_ops![
+    Add, add, AddAssign, add_assign, checked_add, "attempt to add with overflow";
+    Sub, sub, SubAssign, sub_assign, checked_sub, "attempt to subtract with overflow";
+    Mul, mul, MulAssign, mul_assign, checked_mul, "attempt to multiply with overflow";
+    Div, div, DivAssign, div_assign, checked_div, "attempt to divide by zero";
+    Rem, rem, RemAssign, rem_assign, checked_rem,
+        "attempt to calculate the remainder with a divisor of zero";
+];
+
+macro_rules! impl_bit_ops {
+    ($($trait:ident, $fn:ident, $assign:ident, $assign_fn:ident, $op:tt);+ $(;)?) => {$(
+        impl<const L: usize> core::ops::$trait for IntWide<L> {
+            type Output = Self;
+            fn $fn(mut self, rhs: Self) -> Self {
+                for (a, b) in self.limbs.iter_mut().zip(rhs.limbs) { *a $op b; }
+                self
+            }
+        }
+        impl<const L: usize> core::ops::$assign for IntWide<L> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = core::ops::$trait::$fn(*self, rhs);
+            }
+        }
+    )+};
+}
+impl_bit_ops![
+    BitAnd, bitand, BitAndAssign, bitand_assign, &=;
+    BitOr, bitor, BitOrAssign, bitor_assign, |=;
+    BitXor, bitxor, BitXorAssign, bitxor_assign, ^=;
+];
+impl<const L: usize> core::ops::Not for IntWide<L> {
+    type Output = Self;
+    fn not(mut self) -> Self {
+        for a in &mut self.limbs {
+            *a = !*a;
+        }
+        self
+    }
+}
+impl<const L: usize> core::ops::Shl<u32> for IntWide<L> {
+    type Output = Self;
+    /// Discards the bits shifted out. Returns `0` if `bits >= BITS`.
+    fn shl(self, bits: u32) -> Self {
+        IntWide::shl(self, bits)
+    }
+}
+impl<const L: usize> core::ops::Shr<u32> for IntWide<L> {
+    type Output = Self;
+    /// Returns `0` if `bits >= BITS`.
+    fn shr(self, bits: u32) -> Self {
+        IntWide::shr(self, bits)
+    }
+}
+
+impl<const L: usize> FmtNum<IntWide<L>> {
+    /// Writes the integer as ASCII digits in `radix` into `buf` starting at `pos`.
+    ///
+    /// Returns the number of bytes written, or `0` if the buffer is too small
+    /// or `radix` is not between 2 and 36.
+    ///
+    /// # Behavioral guarantees
+    /// - Digits above 9 are written in lowercase.
+    /// - The operation is atomic: on failure, nothing is written.
+    pub const fn write_radix(self, buf: &mut [u8], pos: usize, radix: u32) -> usize {
+        is![radix < 2 || radix > 36, return 0];
+        let needed = _helper::count_digits(&mut { self.0.limbs }, radix);
+        is![needed > buf.len().saturating_sub(pos), return 0];
+        _helper::write_digits(&mut { self.0.limbs }, buf, pos + needed, radix)
+    }
+    /// Writes the integer as ASCII decimal digits into `buf` starting at `pos`.
+    ///
+    /// Returns the number of bytes written, or `0` if the buffer is too small.
+    ///
+    /// # Behavioral guarantees
+    /// - The operation is atomic: on failure, nothing is written.
+    pub const fn write(self, buf: &mut [u8], pos: usize) -> usize {
+        self.write_radix(buf, pos, 10)
+    }
+    /// Measures the decimal formatting of the integer.
+    pub const fn measure(self) -> FmtNumShape {
+        let left = _helper::count_digits(&mut { self.0.limbs }, 10);
+        FmtNumShape::new(0, left as u16, 0)
+    }
+}
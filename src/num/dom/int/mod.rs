@@ -16,6 +16,9 @@ mod gcd; // GcdReturn
 // mod primes; // WIP
 // mod recip; // DivRecip WIP
 
+#[cfg(feature = "int")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "int")))]
+mod big; // BigInt, BigRatio, BigUint, IntWide
 #[cfg(all(feature = "int", feature = "num"))]
 mod num_trait; // NumInt, NumRefInt
 #[cfg(feature = "int")]
@@ -31,6 +34,8 @@ crate::structural_mods! { // _mods, _crate_internals, _hidden
             // prim::*,
             // recip::*,
         };
+        #[cfg(feature = "int")]
+        pub use super::big::_all::*;
         #[cfg(all(feature = "int", feature = "num"))]
         pub use super::num_trait::*;
         #[cfg(feature = "int")]
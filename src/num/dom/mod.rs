@@ -8,7 +8,7 @@
 
 mod _helper; // _num_dom_impl_arith!, _num_dom_upcast_arith!, _num_dom_upcasted_mul_add!
 
-// mod complex;
+mod complex; // Complex
 mod frac;
 // mod laws;
 mod no; // NoNum
@@ -23,7 +23,7 @@ crate::structural_mods! { // _mods, _pub_mods, _crate_internals, _hidden
     _mods {
         #[doc(inline)]
         pub use super::{
-            // complex::_all::*,
+            complex::_all::*,
             frac::_all::*,
             // laws::_all::*,
             no::*,
@@ -0,0 +1,140 @@
+// devela/src/num/dom/real/fixed/_helper.rs
+//
+//! Integer-only math kernels shared by every [`Fixed`][crate::Fixed] representation.
+//!
+//! Values are `i128` in Q61 (61 fractional bits), wide enough to hold
+//! any 64-bit fixed-point value after scaling, so results don't depend
+//! on the backing primitive nor on the floating-point unit.
+//
+// TOC
+// - constants
+// - shift_round, mul
+// - sin_cos, atan, atan2
+// - exp
+
+use crate::{is, whilst};
+
+/// The number of fractional bits of the internal representation.
+pub(super) const Q: u32 = 61;
+/// The value `1` in Q61.
+pub(super) const ONE: i128 = 1 << Q;
+
+/* constants (rounded to nearest) */
+
+pub(super) const PI: i128 = 0x6487_ed51_10b4_611a;
+pub(super) const TAU: i128 = 0xc90f_daa2_2168_c235;
+pub(super) const FRAC_PI_2: i128 = 0x3243_f6a8_885a_308d;
+pub(super) const E: i128 = 0x56fc_2a2c_515d_a54d;
+pub(super) const LN_2: i128 = 0x162e_42fe_fa39_ef35;
+pub(super) const SQRT_2: i128 = 0x2d41_3ccc_fe77_9921;
+
+/* scaling */
+
+/// Returns `v · 2^s` rounded to nearest with ties away from zero,
+/// or `None` if it doesn't fit in 127 bits.
+pub(super) const fn shift_round(v: i128, s: i32) -> Option<i128> {
+    if s >= 0 {
+        is![s >= 127, return is![v == 0, Some(0), None]];
+        let r = v << s;
+        is![r >> s != v, None, Some(r)]
+    } else {
+        let n = s.unsigned_abs();
+        is![n >= 127, return Some(0)];
+        let half = 1_i128 << (n - 1);
+        let m = v.unsigned_abs() as i128;
+        let q = (m + half) >> n;
+        Some(is![v < 0, -q, q])
+    }
+}
+/// Returns the product of two Q61 values of magnitude less than `2^63`.
+pub(super) const fn mul(a: i128, b: i128) -> i128 {
+    let p = a * b;
+    let q = (p.unsigned_abs() as i128 + (1 << (Q - 1))) >> Q;
+    is![p < 0, -q, q]
+}
+/// Returns the quotient of two Q61 values, `a` of magnitude less than `2^65`.
+const fn div(a: i128, b: i128) -> i128 {
+    (a << Q) / b
+}
+
+/* trigonometry */
+
+/// Returns the sine and cosine of any angle in radians.
+pub(super) const fn sin_cos(x: i128) -> (i128, i128) {
+    // reduce to y in [-π/4, π/4) and a quadrant
+    let r = x.rem_euclid(TAU);
+    let q = (r + FRAC_PI_2 / 2) / FRAC_PI_2;
+    let y = r - q * FRAC_PI_2;
+    let y2 = mul(y, y);
+    // Taylor series, which converge quickly in the reduced range
+    let (mut s, mut st) = (y, y);
+    let (mut c, mut ct) = (ONE, ONE);
+    let mut k = 1;
+    while st != 0 || ct != 0 {
+        st = -mul(st, y2) / ((2 * k) * (2 * k + 1));
+        ct = -mul(ct, y2) / ((2 * k - 1) * (2 * k));
+        s += st;
+        c += ct;
+        k += 1;
+    }
+    match q & 3 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+/// Returns the arctangent of a value of magnitude at most `1`, in `[-π/4, π/4]`.
+const fn atan_unit(t: i128) -> i128 {
+    // halve the angle twice, with tan(a/2) = t / (1 + sqrt(1 + t²)),
+    // to reduce the argument below tan(π/16)
+    let mut t = t;
+    whilst! { _i in 0..2; {
+        let root = ((ONE + mul(t, t)) << Q).isqrt();
+        t = div(t, ONE + root);
+    }}
+    let t2 = mul(t, t);
+    let (mut sum, mut p) = (t, t);
+    let mut k = 1;
+    while p != 0 {
+        p = -mul(p, t2);
+        sum += p / (2 * k + 1);
+        k += 1;
+    }
+    sum * 4
+}
+/// Returns the arctangent of any value, in `[-π/2, π/2]`.
+pub(super) const fn atan(x: i128) -> i128 {
+    is![x.unsigned_abs() <= ONE as u128, return atan_unit(x)];
+    let a = atan_unit(div(ONE, x));
+    is![x > 0, FRAC_PI_2 - a, -FRAC_PI_2 - a]
+}
+/// Returns the four-quadrant arctangent of `y / x`, in `[-π, π]`.
+pub(super) const fn atan2(y: i128, x: i128) -> i128 {
+    let (ay, ax) = (y.unsigned_abs(), x.unsigned_abs());
+    is![ax == 0 && ay == 0, return 0];
+    // the angle of the first octant, from the smaller over the larger component
+    let (lo, hi) = is![ay <= ax, (ay, ax), (ax, ay)];
+    let shift = (128 - hi.leading_zeros()).saturating_sub(Q + 1);
+    let (lo, hi) = ((lo >> shift) as i128, (hi >> shift) as i128);
+    let a = atan_unit(div(lo, hi));
+    let a = is![ay <= ax, a, FRAC_PI_2 - a];
+    let a = is![x < 0, PI - a, a];
+    is![y < 0, -a, a]
+}
+
+/* exponential */
+
+/// Returns `(m, k)` so that `e^x = m · 2^k`, with `m` in Q61 between `1` and `2`.
+pub(super) const fn exp(x: i128) -> (i128, i128) {
+    let k = x.div_euclid(LN_2);
+    let r = x - k * LN_2;
+    let (mut sum, mut term) = (ONE, ONE);
+    let mut n = 1;
+    while term != 0 {
+        term = mul(term, r) / n;
+        sum += term;
+        n += 1;
+    }
+    (sum, k)
+}
@@ -0,0 +1,173 @@
+// devela/src/num/dom/real/fixed/_test.rs
+
+use crate::{
+    FixedI8, FixedI32, FixedI64, FixedU8, FixedU64, FmtNum, IntError, RatioI32, format_buf,
+};
+
+type Q16 = FixedI32<16>;
+
+fn q16(v: f64) -> Q16 {
+    Q16::from_f64(v).unwrap()
+}
+/// Asserts two floats are bit-identical.
+fn same(a: f64, b: f64) {
+    assert_eq![a.to_bits(), b.to_bits(), "{a} != {b}"];
+}
+
+#[test]
+fn construction() {
+    assert_eq![Q16::ONE.to_bits(), 1 << 16];
+    same(Q16::NEG_ONE.to_f64(), -1.0);
+    same(Q16::from_int(3).unwrap().to_f64(), 3.0);
+    assert_eq![Q16::from_int(1 << 15), None];
+    same(FixedU8::<4>::MAX.to_f64(), 15.9375);
+    same(FixedI8::<6>::MIN.to_f64(), -2.0);
+    // rounding to nearest, with ties away from zero
+    assert_eq![Q16::from_f64(1.5 / 65536.0).unwrap().to_bits(), 2];
+    assert_eq![Q16::from_f64(-1.5 / 65536.0).unwrap().to_bits(), -2];
+    assert_eq![Q16::from_f64(1.4 / 65536.0).unwrap().to_bits(), 1];
+    assert_eq![Q16::from_f64(40_000.0), None];
+    assert_eq![Q16::from_f64(f64::NAN), None];
+    same(Q16::from_f32(-0.25).unwrap().to_f32().into(), -0.25);
+    // constants
+    assert_eq![Q16::PI.to_bits(), 205_887];
+    assert_eq![FixedI64::<61>::PI.to_bits(), 0x6487_ed51_10b4_611a];
+    same(FixedU64::<62>::E.to_f64(), core::f64::consts::E);
+}
+
+#[test]
+fn rounding() {
+    let x = q16(-2.5);
+    assert_eq![x.floor(), q16(-3.0)];
+    assert_eq![x.ceil(), Some(q16(-2.0))];
+    assert_eq![x.round(), Some(q16(-3.0))];
+    assert_eq![x.trunc(), q16(-2.0)];
+    assert_eq![x.fract(), q16(-0.5)];
+    assert_eq![x.to_int(), -3];
+    assert_eq![x.to_int_round(), -3];
+    assert_eq![q16(2.5).to_int_round(), 3];
+    assert_eq![q16(2.25).round(), Some(q16(2.0))];
+    assert_eq![Q16::MAX.ceil(), None];
+    assert![q16(7.0).is_integer() && !q16(7.5).is_integer()];
+}
+
+#[test]
+fn arithmetic() {
+    let (a, b) = (q16(2.5), q16(-0.75));
+    assert_eq![a + b, q16(1.75)];
+    assert_eq![a - b, q16(3.25)];
+    assert_eq![a * b, q16(-1.875)];
+    assert_eq![a / b, Q16::from_bits(-218_454)]; // -3.333…, rounded down
+    assert_eq![-a, q16(-2.5)];
+    assert_eq![a % q16(1.0), q16(0.5)];
+    assert_eq![Q16::MAX.checked_add(Q16::DELTA), None];
+    assert_eq![Q16::MAX.wrapping_add(Q16::DELTA), Q16::MIN];
+    assert_eq![Q16::MIN.saturating_sub(Q16::ONE), Q16::MIN];
+    assert_eq![Q16::MIN.checked_neg(), None];
+    assert_eq![Q16::MIN.saturating_abs(), Q16::MAX];
+    assert_eq![b.signum(), Q16::NEG_ONE];
+    // overflow
+    let big = q16(300.0);
+    assert_eq![big.checked_mul(big), None];
+    assert_eq![big.saturating_mul(-big), Q16::MIN];
+    assert_eq![big.wrapping_mul(big).to_bits(), (90_000_i64 << 16) as i32];
+    assert_eq![big.checked_div(Q16::ZERO), None];
+    assert_eq![q16(0.001).saturating_div(q16(1e-4)), q16(0.001).div_floor(q16(1e-4)).unwrap()];
+    let mut c = a;
+    c *= a;
+    c -= Q16::ONE;
+    assert_eq![c, q16(5.25)];
+}
+
+#[test]
+fn rounding_modes() {
+    // 3 · 2^-16 × 0.5 = 1.5 · 2^-16
+    let (a, h) = (Q16::from_bits(3), q16(0.5));
+    assert_eq![a.mul_floor(h).unwrap().to_bits(), 1];
+    assert_eq![a.mul_ceil(h).unwrap().to_bits(), 2];
+    assert_eq![a.mul_round(h).unwrap().to_bits(), 2];
+    assert_eq![(-a).mul_floor(h).unwrap().to_bits(), -2];
+    assert_eq![(-a).mul_ceil(h).unwrap().to_bits(), -1];
+    assert_eq![(-a).mul_round(h).unwrap().to_bits(), -2];
+    // 1 / 3
+    let (one, three) = (Q16::ONE, q16(3.0));
+    assert_eq![one.div_floor(three).unwrap().to_bits(), 21845];
+    assert_eq![one.div_ceil(three).unwrap().to_bits(), 21846];
+    assert_eq![one.div_round(three).unwrap().to_bits(), 21845];
+    assert_eq![(-one).div_floor(three).unwrap().to_bits(), -21846];
+    assert_eq![one.div_ceil(-three).unwrap().to_bits(), -21845];
+    assert_eq![q16(2.0).div_round(three).unwrap().to_bits(), 43691];
+    assert_eq![one.div_round(Q16::ZERO), Err(IntError::NonZeroRequired)];
+    assert_eq![Q16::MAX.mul_round(q16(2.0)), Err(IntError::Overflow(None))];
+    // unsigned
+    let u = FixedU8::<4>::from_bits(3);
+    let uh = FixedU8::<4>::from_f64(0.5).unwrap();
+    assert_eq![u.mul_floor(uh).unwrap().to_bits(), 1];
+    assert_eq![u.mul_round(uh).unwrap().to_bits(), 2];
+}
+
+#[test]
+fn ratio_conversions() {
+    let r = RatioI32::new(1, 3).unwrap();
+    assert_eq![Q16::from_ratio_floor(r).unwrap().to_bits(), 21845];
+    assert_eq![Q16::from_ratio_ceil(r).unwrap().to_bits(), 21846];
+    assert_eq![Q16::from_ratio_round(r).unwrap().to_bits(), 21845];
+    // the rounding agrees with applying the ratio to a value
+    let v = q16(-7.3);
+    let by_ratio = r.apply_round(v.to_bits()).unwrap();
+    assert_eq![v.div_round(q16(3.0)).unwrap().to_bits(), by_ratio];
+    let back = q16(-1.25).to_ratio();
+    assert_eq![back.num_den(), (-81920, 65536)];
+    same(back.as_f64(), -1.25);
+    assert![Q16::from_ratio_floor(RatioI32::new(40_000, 1).unwrap()).is_err()];
+}
+
+#[test]
+fn math() {
+    let tol = |a: f64, b: f64, eps: f64| assert![(a - b).abs() <= eps, "{a} != {b}"];
+    let eps = 2.0 / 65536.0;
+    assert_eq![q16(2.0).sqrt().unwrap(), Q16::SQRT_2 - Q16::DELTA]; // rounded down
+    assert_eq![q16(6.25).sqrt(), Some(q16(2.5))];
+    assert_eq![q16(-1.0).sqrt(), None];
+    same(FixedU64::<32>::from_int(1 << 30).unwrap().sqrt().unwrap().to_f64(), 32768.0);
+    for i in -40..=40 {
+        let x = i as f64 * 0.3;
+        let (s, c) = q16(x).sin_cos();
+        tol(s.to_f64(), x.sin(), eps);
+        tol(c.to_f64(), x.cos(), eps);
+        tol(q16(x).atan().to_f64(), x.atan(), eps);
+        let e = (x / 4.0).exp();
+        tol(q16(x / 4.0).exp().unwrap().to_f64(), e, eps * (1.0 + e));
+    }
+    assert_eq![Q16::ZERO.sin_cos(), (Q16::ZERO, Q16::ONE)];
+    tol(q16(1.0).tan().unwrap().to_f64(), 1.0_f64.tan(), eps);
+    tol(q16(-1.0).atan2(q16(-1.0)).to_f64(), (-1.0_f64).atan2(-1.0), eps);
+    tol(q16(3.0).atan2(q16(0.0)).to_f64(), core::f64::consts::FRAC_PI_2, eps);
+    assert_eq![Q16::ZERO.atan2(q16(-2.0)), Q16::PI];
+    // saturates where π is not representable
+    assert_eq![FixedI8::<6>::ZERO.atan2(FixedI8::<6>::NEG_ONE), FixedI8::<6>::MAX];
+    assert_eq![q16(11.0).exp(), None];
+    assert_eq![q16(-12.0).exp(), Some(Q16::ZERO)];
+    assert_eq![Q16::ZERO.exp(), Some(Q16::ONE)];
+    let wide = FixedI64::<32>::from_int(20).unwrap().exp().unwrap();
+    tol(wide.to_f64() / 20.0_f64.exp(), 1.0, 1e-12);
+    // large angles
+    tol(FixedI64::<0>::MAX.sin().to_f64(), 0.0, 1.0);
+}
+
+#[test]
+fn formatting() {
+    let mut buf = [0u8; 32];
+    assert_eq![FmtNum(q16(-2.75)).as_str_into(&mut buf, 3), "-2.750"];
+    assert_eq![FmtNum(q16(-0.001)).as_str_into(&mut buf, 2), "-0.00"];
+    assert_eq![FmtNum(q16(12.5)).as_str_into(&mut buf, 0), "12"];
+    assert_eq![FmtNum(FixedU64::<63>::MAX).as_str_into(&mut buf, 5), "1.99999"];
+    assert_eq![FmtNum(q16(1.5)).measure(2).total(), 4];
+    assert_eq![FmtNum(q16(1.5)).write(&mut buf[..3], 0, 2), 0];
+    let mut buf = [0u8; 96];
+    assert_eq![format_buf![? &mut buf, "{}", q16(-1.5)], "-1.5"];
+    assert_eq![format_buf![? &mut buf, "{}", Q16::DELTA], "0.0000152587890625"];
+    assert_eq![format_buf![? &mut buf, "{:+08.2}", q16(3.125)], "+0003.12"];
+    assert_eq![format_buf![? &mut buf, "{}", q16(-4.0)], "-4"];
+    assert_eq![format_buf![? &mut buf, "{:?}", q16(0.5)], "Fixed<i32, 16>(0.5)"];
+}
@@ -0,0 +1,460 @@
+// devela/src/num/dom/real/fixed/define.rs
+//
+//! Defines [`Fixed`] and its primitive aliases.
+//
+// TOC
+// - struct Fixed
+// - aliases
+// - impl Fixed
+
+use super::_helper as q;
+use crate::IntError::{self, NonZeroRequired, Overflow};
+use crate::{Ratio, compile, is, unwrap};
+
+type FixedResult<T> = Result<T, IntError>;
+
+#[doc = crate::_tags!(num)]
+/// A binary fixed-point number with `FRAC` fractional bits stored in the primitive `I`.
+#[doc = crate::_doc_meta!{location("num/dom/real")}]
+///
+/// The value represented is `bits / 2^FRAC`. `FRAC` must leave room for the value `1`,
+/// that is, be less than the bits of `I`, minus one for signed primitives.
+///
+/// All the operations are done with integer arithmetic, including the
+/// square root, trigonometric and exponential approximations,
+/// so the results are bit-identical on every machine.
+///
+/// Multiplication and division are offered with the same rounding modes
+/// as [`Ratio`]'s `apply_*` methods: `_floor`, `_ceil` and `_round`,
+/// the latter rounding to nearest with ties away from zero.
+/// The `checked_`, `saturating_` and `wrapping_` variants, and the operators, round down.
+///
+/// # Example
+/// ```
+/// # use devela::{FixedI32, FmtNum};
+/// type Q16 = FixedI32<16>;
+/// let a = Q16::from_f64(2.5).unwrap();
+/// let b = Q16::from_int(-3).unwrap();
+/// assert_eq![(a * b).to_f64(), -7.5];
+/// assert_eq![a.sqrt().unwrap().to_bits(), 103621]; // ≈ 1.58113
+/// assert_eq![Q16::MAX.saturating_add(a), Q16::MAX];
+///
+/// let mut buf = [0u8; 16];
+/// assert_eq![FmtNum(Q16::PI).as_str_into(&mut buf, 4), "3.1415"];
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Fixed<I, const FRAC: u32> {
+    /// The underlying bits.
+    bits: I,
+}
+
+macro_rules! _impl_fixed_prim {
+    () => {
+        // primitive : unsigned : upcasted : is_signed
+        _impl_fixed_prim!(
+            i8:u8:i16:true, i16:u16:i32:true, i32:u32:i64:true, i64:u64:i128:true,
+            u8:u8:u16:false, u16:u16:u32:false, u32:u32:u64:false, u64:u64:u128:false,
+        );
+    };
+    ($($I:ty : $U:ty : $Up:ty : $is_signed:tt),+ $(,)?) => { $crate::paste! { $(
+        _impl_fixed_prim!(% [<Fixed $I:camel>], $I, $U, $Up, $crate::[<NonZero $I:camel>],
+            $is_signed);
+    )+ }};
+    (% $Name:ident, $I:ty, $U:ty, $Up:ty, $NonZero:ty, $is_signed:tt) => {
+        #[doc = crate::_tags!(num)]
+        #[doc = concat!("An `", stringify!($I), "` [`Fixed`] with `FRAC` fractional bits.")]
+        #[doc = crate::_doc_meta!{location("num/dom/real")}]
+        pub type $Name<const FRAC: u32> = Fixed<$I, FRAC>;
+
+        impl<const FRAC: u32> crate::ConstInit for Fixed<$I, FRAC> {
+            const INIT: Self = Self::ZERO;
+        }
+        impl<const FRAC: u32> Default for Fixed<$I, FRAC> {
+            fn default() -> Self { Self::ZERO }
+        }
+
+        #[rustfmt::skip]
+        impl<const FRAC: u32> Fixed<$I, FRAC> {
+            const _VALID: () = assert![FRAC < <$I>::BITS - $is_signed as u32,
+                "FRAC must leave room for the value 1"];
+            const _MASK: $I = ((1 as $U) << FRAC).wrapping_sub(1) as $I;
+
+            /* constants */
+
+            /// The number of fractional bits.
+            pub const FRAC_BITS: u32 = FRAC;
+            /// The number of integer bits, including the sign bit.
+            pub const INT_BITS: u32 = <$I>::BITS - FRAC;
+
+            /// The value `0`.
+            pub const ZERO: Self = Self::from_bits(0);
+            /// The value `1`.
+            pub const ONE: Self = Self::from_bits(1 << FRAC);
+            /// The smallest positive value, `2^-FRAC`.
+            pub const DELTA: Self = Self::from_bits(1);
+            /// The smallest value.
+            pub const MIN: Self = Self::from_bits(<$I>::MIN);
+            /// The largest value.
+            pub const MAX: Self = Self::from_bits(<$I>::MAX);
+
+            #[compile(same($is_signed, true))]
+            /// The value `-1`.
+            pub const NEG_ONE: Self = Self::from_bits(-1 << FRAC);
+
+            /// Archimedes’ constant (π), rounded to nearest.
+            ///
+            /// Fails to compile if it's not representable.
+            pub const PI: Self = Self::_from_q61_const(q::PI);
+            /// The full circle constant (τ = 2π), rounded to nearest.
+            ///
+            /// Fails to compile if it's not representable.
+            pub const TAU: Self = Self::_from_q61_const(q::TAU);
+            /// π/2, rounded to nearest.
+            ///
+            /// Fails to compile if it's not representable.
+            pub const FRAC_PI_2: Self = Self::_from_q61_const(q::FRAC_PI_2);
+            /// Euler’s number (e), rounded to nearest.
+            ///
+            /// Fails to compile if it's not representable.
+            pub const E: Self = Self::_from_q61_const(q::E);
+            /// ln(2), rounded to nearest.
+            pub const LN_2: Self = Self::_from_q61_const(q::LN_2);
+            /// √2, rounded to nearest.
+            ///
+            /// Fails to compile if it's not representable.
+            pub const SQRT_2: Self = Self::_from_q61_const(q::SQRT_2);
+
+            /* construction */
+
+            /// Returns the fixed-point number with the given underlying `bits`.
+            #[must_use]
+            pub const fn from_bits(bits: $I) -> Self {
+                let () = Self::_VALID;
+                Self { bits }
+            }
+            /// Returns the underlying bits.
+            #[must_use]
+            pub const fn to_bits(self) -> $I { self.bits }
+
+            /// Returns the integer `n`, or `None` if it's not representable.
+            #[must_use]
+            pub const fn from_int(n: $I) -> Option<Self> {
+                let bits = n << FRAC;
+                is![bits >> FRAC == n, Some(Self::from_bits(bits)), None]
+            }
+            /// Returns the largest integer less than or equal to the value.
+            #[must_use]
+            pub const fn to_int(self) -> $I { self.bits >> FRAC }
+            /// Returns the nearest integer, with ties away from zero.
+            #[must_use]
+            pub const fn to_int_round(self) -> $I {
+                Self::_div_round_up(self.bits as $Up, (1 as $Up) << FRAC) as $I
+            }
+
+            /// Returns the nearest value to `value`, with ties away from zero,
+            /// or `None` if it's not finite or it's out of range.
+            #[must_use]
+            pub const fn from_f64(value: f64) -> Option<Self> {
+                is![!value.is_finite(), return None];
+                let scaled = value * pow2_f64(FRAC as i32);
+                let t = scaled as i128;
+                let diff = scaled - t as f64;
+                let t = is![diff >= 0.5, t.saturating_add(1),
+                    is![diff <= -0.5, t.saturating_sub(1), t]];
+                is![t < <$I>::MIN as i128 || t > <$I>::MAX as i128, None,
+                    Some(Self::from_bits(t as $I))]
+            }
+            /// Returns the nearest value to `value`, with ties away from zero,
+            /// or `None` if it's not finite or it's out of range.
+            #[must_use]
+            pub const fn from_f32(value: f32) -> Option<Self> { Self::from_f64(value as f64) }
+            /// Returns the nearest `f64`.
+            #[must_use]
+            pub const fn to_f64(self) -> f64 { self.bits as f64 * pow2_f64(-(FRAC as i32)) }
+            /// Returns the nearest `f32`.
+            #[must_use]
+            pub const fn to_f32(self) -> f32 { self.bits as f32 * pow2_f32(-(FRAC as i32)) }
+
+            /// Returns the value of the `ratio`, rounding down.
+            ///
+            /// Can return `Overflow`.
+            pub const fn from_ratio_floor(ratio: Ratio<$I, $NonZero>) -> FixedResult<Self> {
+                Ok(Self::from_bits(unwrap![ok? ratio.apply_floor(Self::ONE.bits)]))
+            }
+            /// Returns the value of the `ratio`, rounding up.
+            ///
+            /// Can return `Overflow`.
+            pub const fn from_ratio_ceil(ratio: Ratio<$I, $NonZero>) -> FixedResult<Self> {
+                Ok(Self::from_bits(unwrap![ok? ratio.apply_ceil(Self::ONE.bits)]))
+            }
+            /// Returns the value of the `ratio`, rounding to nearest, with ties away from zero.
+            ///
+            /// Can return `Overflow`.
+            pub const fn from_ratio_round(ratio: Ratio<$I, $NonZero>) -> FixedResult<Self> {
+                Ok(Self::from_bits(unwrap![ok? ratio.apply_round(Self::ONE.bits)]))
+            }
+            /// Returns the exact value as the unreduced ratio `bits / 2^FRAC`.
+            #[must_use]
+            pub const fn to_ratio(self) -> Ratio<$I, $NonZero> {
+                Ratio::from_parts(self.bits, unwrap![some <$NonZero>::new(Self::ONE.bits)])
+            }
+
+            /* queries */
+
+            /// Returns `true` if the value is `0`.
+            #[must_use]
+            pub const fn is_zero(self) -> bool { self.bits == 0 }
+            /// Returns `true` if the value is greater than `0`.
+            #[must_use]
+            pub const fn is_positive(self) -> bool { self.bits > 0 }
+            /// Returns `true` if the value is less than `0`.
+            #[must_use]
+            #[allow(unused_comparisons, reason = "unsigned primitives")]
+            pub const fn is_negative(self) -> bool { self.bits < 0 }
+            /// Returns `true` if the value has no fractional part.
+            #[must_use]
+            pub const fn is_integer(self) -> bool { self.bits & Self::_MASK == 0 }
+
+            /* rounding */
+
+            /// Returns the largest integer less than or equal to the value.
+            #[must_use]
+            pub const fn floor(self) -> Self { Self::from_bits(self.bits & !Self::_MASK) }
+            /// Returns the smallest integer greater than or equal to the value,
+            /// or `None` if it's not representable.
+            #[must_use]
+            pub const fn ceil(self) -> Option<Self> {
+                is![self.is_integer(), return Some(self)];
+                self.floor().checked_add(Self::ONE)
+            }
+            /// Returns the nearest integer, with ties away from zero,
+            /// or `None` if it's not representable.
+            #[must_use]
+            pub const fn round(self) -> Option<Self> { Self::from_int(self.to_int_round()) }
+            /// Returns the integer part, rounding towards zero.
+            #[must_use]
+            pub const fn trunc(self) -> Self {
+                is![self.is_negative() && !self.is_integer(),
+                    Self::from_bits(self.floor().bits.wrapping_add(1 << FRAC)), self.floor()]
+            }
+            /// Returns the fractional part, `self - self.trunc()`, with the sign of `self`.
+            #[must_use]
+            pub const fn fract(self) -> Self {
+                Self::from_bits(self.bits.wrapping_sub(self.trunc().bits))
+            }
+
+            /* addition and subtraction */
+
+            /// Returns `self + rhs`, or `None` on overflow.
+            #[must_use]
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some(Self::from_bits(unwrap![some? self.bits.checked_add(rhs.bits)]))
+            }
+            /// Returns `self + rhs`, saturating at the bounds.
+            #[must_use]
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self::from_bits(self.bits.saturating_add(rhs.bits))
+            }
+            /// Returns `self + rhs`, wrapping around at the bounds.
+            #[must_use]
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                Self::from_bits(self.bits.wrapping_add(rhs.bits))
+            }
+            /// Returns `self - rhs`, or `None` on overflow.
+            #[must_use]
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                Some(Self::from_bits(unwrap![some? self.bits.checked_sub(rhs.bits)]))
+            }
+            /// Returns `self - rhs`, saturating at the bounds.
+            #[must_use]
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self::from_bits(self.bits.saturating_sub(rhs.bits))
+            }
+            /// Returns `self - rhs`, wrapping around at the bounds.
+            #[must_use]
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                Self::from_bits(self.bits.wrapping_sub(rhs.bits))
+            }
+
+            /* multiplication */
+
+            /// Returns `self * rhs`, rounding down.
+            ///
+            /// Can return `Overflow`.
+            pub const fn mul_floor(self, rhs: Self) -> FixedResult<Self> {
+                Self::_up_to_fixed(Self::_mul_up(self, rhs) >> FRAC)
+            }
+            /// Returns `self * rhs`, rounding up.
+            ///
+            /// Can return `Overflow`.
+            pub const fn mul_ceil(self, rhs: Self) -> FixedResult<Self> {
+                let p = Self::_mul_up(self, rhs);
+                Self::_up_to_fixed(Self::_div_ceil_up(p, (1 as $Up) << FRAC))
+            }
+            /// Returns `self * rhs`, rounding to nearest, with ties away from zero.
+            ///
+            /// Can return `Overflow`.
+            pub const fn mul_round(self, rhs: Self) -> FixedResult<Self> {
+                let p = Self::_mul_up(self, rhs);
+                Self::_up_to_fixed(Self::_div_round_up(p, (1 as $Up) << FRAC))
+            }
+            /// Returns `self * rhs` rounding down, or `None` on overflow.
+            #[must_use]
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                unwrap![ok_some self.mul_floor(rhs)]
+            }
+            /// Returns `self * rhs` rounding down, saturating at the bounds.
+            #[must_use]
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                Self::_up_to_fixed_saturating(Self::_mul_up(self, rhs) >> FRAC)
+            }
+            /// Returns `self * rhs` rounding down, wrapping around at the bounds.
+            #[must_use]
+            pub const fn wrapping_mul(self, rhs: Self) -> Self {
+                Self::from_bits((Self::_mul_up(self, rhs) >> FRAC) as $I)
+            }
+
+            /* division */
+
+            /// Returns `self / rhs`, rounding down.
+            ///
+            /// Can return `NonZeroRequired` or `Overflow`.
+            pub const fn div_floor(self, rhs: Self) -> FixedResult<Self> {
+                is![rhs.bits == 0, return Err(NonZeroRequired)];
+                let (n, d) = Self::_div_terms(self, rhs);
+                Self::_up_to_fixed(Self::_div_floor_up(n, d))
+            }
+            /// Returns `self / rhs`, rounding up.
+            ///
+            /// Can return `NonZeroRequired` or `Overflow`.
+            pub const fn div_ceil(self, rhs: Self) -> FixedResult<Self> {
+                is![rhs.bits == 0, return Err(NonZeroRequired)];
+                let (n, d) = Self::_div_terms(self, rhs);
+                Self::_up_to_fixed(Self::_div_ceil_up(n, d))
+            }
+            /// Returns `self / rhs`, rounding to nearest, with ties away from zero.
+            ///
+            /// Can return `NonZeroRequired` or `Overflow`.
+            pub const fn div_round(self, rhs: Self) -> FixedResult<Self> {
+                is![rhs.bits == 0, return Err(NonZeroRequired)];
+                let (n, d) = Self::_div_terms(self, rhs);
+                Self::_up_to_fixed(Self::_div_round_up(n, d))
+            }
+            /// Returns `self / rhs` rounding down, or `None` on overflow or if `rhs` is `0`.
+            #[must_use]
+            pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+                unwrap![ok_some self.div_floor(rhs)]
+            }
+            /// Returns `self / rhs` rounding down, saturating at the bounds.
+            ///
+            /// # Panics
+            /// Panics if `rhs` is `0`.
+            #[must_use]
+            pub const fn saturating_div(self, rhs: Self) -> Self {
+                let (n, d) = Self::_div_terms(self, rhs);
+                Self::_up_to_fixed_saturating(Self::_div_floor_up(n, d))
+            }
+            /// Returns `self / rhs` rounding down, wrapping around at the bounds.
+            ///
+            /// # Panics
+            /// Panics if `rhs` is `0`.
+            #[must_use]
+            pub const fn wrapping_div(self, rhs: Self) -> Self {
+                let (n, d) = Self::_div_terms(self, rhs);
+                Self::from_bits(Self::_div_floor_up(n, d) as $I)
+            }
+
+            /* signed */
+
+            #[compile(same($is_signed, true))]
+            /// Returns `-self`, or `None` on overflow.
+            #[must_use]
+            pub const fn checked_neg(self) -> Option<Self> {
+                Some(Self::from_bits(unwrap![some? self.bits.checked_neg()]))
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns `-self`, saturating at the bounds.
+            #[must_use]
+            pub const fn saturating_neg(self) -> Self {
+                Self::from_bits(self.bits.saturating_neg())
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns the absolute value, or `None` on overflow.
+            #[must_use]
+            pub const fn checked_abs(self) -> Option<Self> {
+                Some(Self::from_bits(unwrap![some? self.bits.checked_abs()]))
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns the absolute value, saturating at the bounds.
+            #[must_use]
+            pub const fn saturating_abs(self) -> Self {
+                Self::from_bits(self.bits.saturating_abs())
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns `-1`, `0` or `1` depending on the sign of the value.
+            #[must_use]
+            pub const fn signum(self) -> Self {
+                Self::from_bits(self.bits.signum() << FRAC)
+            }
+
+            /* helpers */
+
+            /// Returns a Q61 constant converted, for compile-time use.
+            const fn _from_q61_const(v: i128) -> Self {
+                unwrap![some Self::_from_q61(v)]
+            }
+            /// Returns the nearest value to a Q61 value, or `None` if it's out of range.
+            pub(super) const fn _from_q61(v: i128) -> Option<Self> {
+                let v = unwrap![some? q::shift_round(v, FRAC as i32 - q::Q as i32)];
+                is![v < <$I>::MIN as i128 || v > <$I>::MAX as i128, None,
+                    Some(Self::from_bits(v as $I))]
+            }
+            /// Returns the value in Q61.
+            pub(super) const fn _to_q61(self) -> i128 {
+                unwrap![some q::shift_round(self.bits as i128, q::Q as i32 - FRAC as i32)]
+            }
+
+            const fn _mul_up(a: Self, b: Self) -> $Up { a.bits as $Up * b.bits as $Up }
+            const fn _div_terms(a: Self, b: Self) -> ($Up, $Up) {
+                ((a.bits as $Up) << FRAC, b.bits as $Up)
+            }
+            // the upcasted terms are always far from the bounds, so these can't overflow
+            const fn _div_floor_up(n: $Up, d: $Up) -> $Up {
+                let (q, r) = (n / d, n % d);
+                is![r != 0 && ((r > 0) != (d > 0)), q - 1, q]
+            }
+            const fn _div_ceil_up(n: $Up, d: $Up) -> $Up {
+                let (q, r) = (n / d, n % d);
+                is![r != 0 && ((r > 0) == (d > 0)), q + 1, q]
+            }
+            #[allow(unused_comparisons, reason = "unsigned primitives")]
+            const fn _div_round_up(n: $Up, d: $Up) -> $Up {
+                let (q, r) = (n / d, n % d);
+                let (ar, ad) = (Self::_abs_up(r), Self::_abs_up(d));
+                let away = r != 0 && ar >= ad - ar;
+                is![away, is![(n < 0) == (d < 0), q + 1, q - 1], q]
+            }
+            #[allow(unused_comparisons, reason = "unsigned primitives")]
+            const fn _abs_up(x: $Up) -> $Up { is![x < 0, (0 as $Up).wrapping_sub(x), x] }
+            const fn _up_to_fixed(x: $Up) -> FixedResult<Self> {
+                is![x < <$I>::MIN as $Up || x > <$I>::MAX as $Up, Err(Overflow(None)),
+                    Ok(Self::from_bits(x as $I))]
+            }
+            const fn _up_to_fixed_saturating(x: $Up) -> Self {
+                is![x < <$I>::MIN as $Up, Self::MIN, is![x > <$I>::MAX as $Up, Self::MAX,
+                    Self::from_bits(x as $I)]]
+            }
+        }
+    };
+}
+_impl_fixed_prim!();
+
+/// Returns `2^exp` as an `f64`, for `exp` between -1022 and 1023.
+const fn pow2_f64(exp: i32) -> f64 {
+    f64::from_bits(((exp + 1023) as u64) << 52)
+}
+/// Returns `2^exp` as an `f32`, for `exp` between -126 and 127.
+const fn pow2_f32(exp: i32) -> f32 {
+    f32::from_bits(((exp + 127) as u32) << 23)
+}
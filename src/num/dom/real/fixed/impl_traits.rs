@@ -0,0 +1,158 @@
+// devela/src/num/dom/real/fixed/impl_traits.rs
+//
+//! Implements operators, formatting traits and [`FmtNum`] for [`Fixed`].
+//
+
+use crate::{
+    Debug, Display, Fixed, FmtNum, FmtNumConf, FmtNumShape, FmtResult, Formatter, Str, is, unwrap,
+    whilst,
+};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+macro_rules! _impl_fixed_traits {
+    () => {
+        // primitive : unsigned : is_signed
+        _impl_fixed_traits!(
+            i8:u8:true, i16:u16:true, i32:u32:true, i64:u64:true,
+            u8:u8:false, u16:u16:false, u32:u32:false, u64:u64:false,
+        );
+    };
+    ($($I:ty : $U:ty : $is_signed:tt),+ $(,)?) => { $(
+        _impl_fixed_traits!(%op $I: Add, add, AddAssign, add_assign, checked_add, "add");
+        _impl_fixed_traits!(%op $I: Sub, sub, SubAssign, sub_assign, checked_sub, "subtract");
+        _impl_fixed_traits!(%op $I: Mul, mul, MulAssign, mul_assign, checked_mul, "multiply");
+        _impl_fixed_traits!(%op $I: Div, div, DivAssign, div_assign, checked_div, "divide");
+        _impl_fixed_traits!(%neg $I: $is_signed);
+
+        impl<const FRAC: u32> Rem for Fixed<$I, FRAC> {
+            type Output = Self;
+            /// Returns the remainder of the truncated division, with the sign of `self`.
+            fn rem(self, rhs: Self) -> Self {
+                Self::from_bits(self.to_bits() % rhs.to_bits())
+            }
+        }
+        impl<const FRAC: u32> RemAssign for Fixed<$I, FRAC> {
+            fn rem_assign(&mut self, rhs: Self) { *self = *self % rhs; }
+        }
+
+        impl<const FRAC: u32> Display for Fixed<$I, FRAC> {
+            /// Writes the exact decimal value, or truncates it to the given precision.
+            fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+                // an exact expansion has at most FRAC < 64 fractional digits
+                let mut buf = [0u8; 192];
+                let fract = f.precision().map_or(FRAC as u16, |p| p.min(160) as u16);
+                let len = FmtNum(*self).write(&mut buf, 0, fract);
+                let mut s = unwrap![ok Str::from_utf8(&buf[..len])];
+                if f.precision().is_none() && fract > 0 {
+                    s = s.trim_end_matches('0').trim_end_matches('.');
+                }
+                f.pad_integral(!self.is_negative(), "", s.trim_start_matches('-'))
+            }
+        }
+        impl<const FRAC: u32> Debug for Fixed<$I, FRAC> {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult<()> {
+                write!(f, "Fixed<{}, {}>({})", stringify!($I), FRAC, self)
+            }
+        }
+
+        impl<const FRAC: u32> FmtNum<Fixed<$I, FRAC>> {
+            /// Writes the fixed-point number in decimal form into `buf`
+            /// starting at `pos`, using exactly `fract_len` fractional digits.
+            ///
+            /// Returns the number of bytes written, or `0` if the buffer is too small.
+            ///
+            /// # Behavioral guarantees
+            /// - The operation is atomic: on failure, nothing is written.
+            /// - Negative values are preceded by the `'-'` sign.
+            /// - Fractional digits are exact, truncated and zero-padded as needed.
+            pub const fn write(self, buf: &mut [u8], pos: usize, fract_len: u16) -> usize {
+                self.write_fmt(buf, pos, FmtNumConf::new().with_fract(fract_len))
+            }
+            /// Writes the fixed-point number in decimal form into `buf`
+            /// starting at `pos`, using the given formatting configuration.
+            ///
+            /// Returns the number of bytes written, or `0` if the buffer is too small.
+            ///
+            /// # Behavioral guarantees
+            /// - The operation is atomic: on failure, nothing is written.
+            /// - The emitted sign, any leading zero-padding, and the number of fractional digits
+            ///   are controlled by `conf`.
+            pub const fn write_fmt(self, buf: &mut [u8], mut pos: usize, conf: FmtNumConf)
+                -> usize {
+                use crate::{Digits, write_at};
+                let shape = self.measure_fmt(conf);
+                let needed = shape.total();
+                is![needed > buf.len().saturating_sub(pos), return 0];
+                let (neg, int, mut fract) = self._parts();
+                if shape.prefix > 0 { write_at![buf, +=pos, is![neg, b'-', b'+']]; }
+                let int_digits = Digits(int).count_digits10() as u16;
+                whilst! { _i in 0..(shape.left - int_digits); { write_at![buf, +=pos, b'0']; }}
+                pos += Digits(int).write_digits10(buf, pos);
+                if conf.fract > 0 {
+                    write_at![buf, +=pos, b'.'];
+                    let mask = (1_u128 << FRAC) - 1;
+                    whilst! { _i in 0..conf.fract; {
+                        fract *= 10;
+                        write_at![buf, +=pos, b'0' + (fract >> FRAC) as u8];
+                        fract &= mask;
+                    }}
+                }
+                needed
+            }
+            /// Returns the measured shape of the fixed-point number to be formatted.
+            pub const fn measure(self, fract_len: u16) -> FmtNumShape {
+                self.measure_fmt(FmtNumConf::new().with_fract(fract_len))
+            }
+            /// Returns the measured shape of the fixed-point number to be formatted,
+            /// using the given formatting configuration.
+            pub const fn measure_fmt(self, conf: FmtNumConf) -> FmtNumShape {
+                use crate::{Cmp, Digits};
+                let (neg, int, _) = self._parts();
+                let prefix = conf.sign.emit_sign(neg) as u16;
+                let int_digits = Digits(int).count_digits10() as u16;
+                let left = if conf.pad_sign && prefix > 0 { Cmp(int_digits + 1).max(conf.int) - 1 }
+                else { Cmp(int_digits).max(conf.int) };
+                FmtNumShape::new(prefix, left, conf.fract)
+            }
+            /// Formats the number into a provided buffer and returns it as a string slice.
+            ///
+            /// This operation is atomic: if the buffer is too small, nothing is written.
+            pub const fn as_str_into<'b>(&self, buf: &'b mut [u8], fract_len: u16) -> &'b str {
+                let len = self.write(buf, 0, fract_len);
+                unwrap![ok Str::from_utf8(crate::Slice::range_to(buf, len))]
+            }
+
+            /// Returns the sign, the integer part and the fractional bits of the magnitude.
+            #[allow(unused_comparisons, reason = "unsigned primitives")]
+            const fn _parts(self) -> (bool, u64, u128) {
+                let bits = self.0.to_bits();
+                let mag = is![bits < 0, (bits as $U).wrapping_neg(), bits as $U];
+                let int = (mag >> FRAC) as u64;
+                let fract = (mag as u128) & ((1_u128 << FRAC) - 1);
+                (bits < 0, int, fract)
+            }
+        }
+    )+};
+    (%op $I:ty: $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $checked:ident,
+     $verb:literal) => {
+        impl<const FRAC: u32> $Op for Fixed<$I, FRAC> {
+            type Output = Self;
+            fn $op(self, rhs: Self) -> Self {
+                self.$checked(rhs).expect(concat!["attempt to ", $verb, " with overflow"])
+            }
+        }
+        impl<const FRAC: u32> $OpAssign for Fixed<$I, FRAC> {
+            fn $op_assign(&mut self, rhs: Self) { *self = $Op::$op(*self, rhs); }
+        }
+    };
+    (%neg $I:ty: true) => {
+        impl<const FRAC: u32> Neg for Fixed<$I, FRAC> {
+            type Output = Self;
+            fn neg(self) -> Self { self.checked_neg().expect("attempt to negate with overflow") }
+        }
+    };
+    (%neg $I:ty: false) => {};
+}
+_impl_fixed_traits!();
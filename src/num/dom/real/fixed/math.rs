@@ -0,0 +1,101 @@
+// devela/src/num/dom/real/fixed/math.rs
+//
+//! Implements the square root, trigonometric and exponential functions for [`Fixed`].
+//
+
+use super::_helper as q;
+use crate::{Fixed, compile, is, unwrap};
+
+macro_rules! _impl_fixed_math {
+    () => {
+        // primitive : upcasted : is_signed
+        _impl_fixed_math!(
+            i8:i16:true, i16:i32:true, i32:i64:true, i64:i128:true,
+            u8:u16:false, u16:u32:false, u32:u64:false, u64:u128:false,
+        );
+    };
+    ($($I:ty : $Up:ty : $is_signed:tt),+ $(,)?) => { $(
+        #[rustfmt::skip]
+        impl<const FRAC: u32> Fixed<$I, FRAC> {
+            /// Returns the square root, rounded down, or `None` if the value is negative.
+            ///
+            /// The result is exact to the last bit.
+            #[must_use]
+            #[allow(unused_comparisons, reason = "unsigned primitives")]
+            pub const fn sqrt(self) -> Option<Self> {
+                is![self.to_bits() < 0, return None];
+                Some(Self::from_bits((((self.to_bits() as $Up) << FRAC).isqrt()) as $I))
+            }
+
+            /// Returns `e^self`, or `None` if it's not representable.
+            ///
+            /// Results smaller than half [`DELTA`][Self::DELTA] are rounded to `0`.
+            #[must_use]
+            pub const fn exp(self) -> Option<Self> {
+                let (m, k) = q::exp(self._to_q61());
+                // e^x = m · 2^k, where m has Q fractional bits
+                is![k > <$I>::BITS as i128, return None];
+                is![k < -(2 * q::Q as i128), return Some(Self::ZERO)];
+                let shift = k as i32 + FRAC as i32 - q::Q as i32;
+                let bits = unwrap![some? q::shift_round(m, shift)];
+                is![bits > <$I>::MAX as i128, None, Some(Self::from_bits(bits as $I))]
+            }
+
+            /* trigonometric */
+
+            #[compile(same($is_signed, true))]
+            /// Returns the sine of an angle in radians.
+            #[must_use]
+            pub const fn sin(self) -> Self { self.sin_cos().0 }
+            #[compile(same($is_signed, true))]
+            /// Returns the cosine of an angle in radians.
+            #[must_use]
+            pub const fn cos(self) -> Self { self.sin_cos().1 }
+            #[compile(same($is_signed, true))]
+            /// Returns both the sine and the cosine of an angle in radians.
+            ///
+            /// The angle is reduced modulo a rounded τ,
+            /// so the error grows slowly with the magnitude of the angle.
+            pub const fn sin_cos(self) -> (Self, Self) {
+                let (s, c) = q::sin_cos(self._to_q61());
+                (Self::_from_q61_clamp(s), Self::_from_q61_clamp(c))
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns the tangent of an angle in radians, or `None` if it's not representable.
+            #[must_use]
+            pub const fn tan(self) -> Option<Self> {
+                let (s, c) = q::sin_cos(self._to_q61());
+                is![c == 0, return None];
+                let t = unwrap![some? q::shift_round(s, q::Q as i32)] / c;
+                Self::_from_q61(t)
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns the arctangent in radians, between -π/2 and π/2.
+            #[must_use]
+            pub const fn atan(self) -> Self {
+                Self::_from_q61_clamp(q::atan(self._to_q61()))
+            }
+            #[compile(same($is_signed, true))]
+            /// Returns the four-quadrant arctangent of `self` (y) and `x` in radians,
+            /// between -π and π.
+            ///
+            /// The result saturates if π is not representable.
+            #[must_use]
+            pub const fn atan2(self, x: Self) -> Self {
+                Self::_from_q61_clamp(q::atan2(self._to_q61(), x._to_q61()))
+            }
+
+            /* helpers */
+
+            /// Returns the nearest value to a Q61 value, saturating at the bounds.
+            #[allow(dead_code, reason = "unused for unsigned primitives")]
+            const fn _from_q61_clamp(v: i128) -> Self {
+                match Self::_from_q61(v) {
+                    Some(x) => x,
+                    None => is![v < 0, Self::MIN, Self::MAX],
+                }
+            }
+        }
+    )+};
+}
+_impl_fixed_math!();
@@ -0,0 +1,18 @@
+// devela/src/num/dom/real/fixed/mod.rs
+//
+//! Binary fixed-point numbers.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // integer-only math kernels
+mod define; // Fixed, Fixed[I|U][8|16|32|64]
+mod impl_traits;
+mod math;
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::define::*;
+    }
+}
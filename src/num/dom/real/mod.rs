@@ -6,14 +6,14 @@
 #![doc = crate::_doc!(hr)]
 //
 
-// mod fixed; //
+mod fixed; // Fixed, Fixed[I|U][8|16|32|64]
 mod float; // FloatExt, (Float, FloatConst, f[32|64]_bits, fsize)
 
 crate::structural_mods! { // _mods, _crate_internals
     _mods {
         #[doc(inline)]
         pub use super::{
-            // fixed::_all::*,
+            fixed::_all::*,
             float::_all::*,
         };
     }
@@ -0,0 +1,44 @@
+// devela/src/num/prob/markov/_helper.rs
+//
+//! Linear solver shared by the dense and sparse chains.
+//
+
+use crate::whilst;
+
+/// The magnitude below which a pivot is considered zero.
+const PIVOT_EPSILON: f64 = 1e-12;
+
+/// Solves `a · x = b` in place by Gauss-Jordan elimination with partial pivoting.
+///
+/// `a` is an `n × n` and `b` an `n × m` row-major matrix. On success `b` holds `x`.
+///
+/// Returns `false` if `a` is singular, leaving both matrices in an unspecified state.
+pub(super) const fn solve(a: &mut [f64], b: &mut [f64], n: usize, m: usize) -> bool {
+    whilst! { col in 0..n; {
+        // choose the row with the largest pivot
+        let mut pivot = col;
+        let mut best = a[col * n + col].abs();
+        whilst! { row in col + 1, ..n; {
+            let v = a[row * n + col].abs();
+            if v > best { best = v; pivot = row; }
+        }}
+        if best < PIVOT_EPSILON { return false; }
+        if pivot != col {
+            whilst! { k in 0..n; { a.swap(col * n + k, pivot * n + k); }}
+            whilst! { k in 0..m; { b.swap(col * m + k, pivot * m + k); }}
+        }
+        // normalize the pivot row
+        let inv = 1.0 / a[col * n + col];
+        whilst! { k in 0..n; { a[col * n + k] *= inv; }}
+        whilst! { k in 0..m; { b[col * m + k] *= inv; }}
+        // eliminate the column from every other row
+        whilst! { row in 0..n; {
+            let f = a[row * n + col];
+            if row != col && f != 0.0 {
+                whilst! { k in 0..n; { a[row * n + k] -= f * a[col * n + k]; }}
+                whilst! { k in 0..m; { b[row * m + k] -= f * b[col * m + k]; }}
+            }
+        }}
+    }}
+    true
+}
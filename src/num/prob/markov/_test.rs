@@ -0,0 +1,122 @@
+// devela/src/num/prob/markov/_test.rs
+
+use crate::{IndexOutOfBounds, MarkovChain, Matrix, Pcg32, assert_approx_eq_all};
+
+/// Gambler's ruin over 5 states: 0 and 4 are absorbing, the rest move ±1 evenly.
+#[rustfmt::skip]
+const RUIN: MarkovChain<5, 25> = MarkovChain::from_weights(Matrix::new([
+    1, 0, 0, 0, 0,
+    1, 0, 1, 0, 0,
+    0, 1, 0, 1, 0,
+    0, 0, 1, 0, 1,
+    0, 0, 0, 0, 1,
+]));
+
+#[test]
+fn train_and_query() {
+    let mut c = MarkovChain::<3, 9>::new();
+    assert_eq![c.train(&[0, 1, 2, 1, 0, 1]), Ok(())];
+    assert_eq![c.weight(0, 1), Some(2)];
+    assert_eq![c.weight(1, 2), Some(1)];
+    assert_eq![c.row_total(1), Some(2)];
+    assert_eq![c.probability(1, 0), Some(0.5)];
+    assert_eq![c.weight(3, 0), None];
+    assert_eq![c.train(&[0, 5, 1]), Err(IndexOutOfBounds(Some(5)))];
+    assert_eq![c.weight(0, 1), Some(2)]; // unchanged after an error
+    assert_eq![c.observe(2, 2), Ok(())];
+    assert_eq![c.set_weight(2, 0, 7), Ok(())];
+    assert_eq![c.weights().at(2, 0), 7];
+    c.reset();
+    assert_eq![c.row_total(0), Some(0)];
+    // dead ends stay in place
+    assert_eq![c.probability(0, 0), Some(1.0)];
+    assert![c.is_absorbing(0)];
+}
+
+#[test]
+fn step_follows_weights() {
+    let mut c = MarkovChain::<3, 9>::new();
+    c.set_weight(0, 1, 3).unwrap();
+    c.set_weight(0, 2, 1).unwrap();
+    let mut rng = Pcg32::new(1, 1);
+    let mut counts = [0_i32; 3];
+    for _ in 0..4000 {
+        counts[c.step(0, &mut rng).unwrap()] += 1;
+    }
+    assert_eq![counts[0], 0];
+    assert![(counts[1] - 3000).abs() < 150, "{counts:?}"];
+    assert_eq![c.step(2, &mut rng), Some(2)];
+    assert_eq![c.step(3, &mut rng), None];
+    // a walk from 0 ends in one of the dead ends 1 or 2
+    let path: [usize; 3] = core::array::from_fn({
+        let mut walk = c.walk(0, &mut rng);
+        move |_| walk.next().unwrap()
+    });
+    assert![path[0] != 0 && path[0] == path[1] && path[1] == path[2]];
+}
+
+#[test]
+fn stationary() {
+    // a periodic two-state chain converges thanks to the lazy iteration
+    let flip = MarkovChain::<2, 4>::from_weights(Matrix::new([0, 1, 1, 0]));
+    let pi = flip.stationary(1000, 1e-12).unwrap();
+    assert_approx_eq_all![tolerance: 1e-9, pi[0], 0.5];
+    // P = [[0.9, 0.1], [0.5, 0.5]] has π = [5/6, 1/6]
+    let weather = MarkovChain::<2, 4>::from_weights(Matrix::new([9, 1, 5, 5]));
+    let pi = weather.stationary(10_000, 1e-14).unwrap();
+    assert_approx_eq_all![tolerance: 1e-9, pi[0], 5.0 / 6.0];
+    assert_approx_eq_all![tolerance: 1e-9, pi[1], 1.0 / 6.0];
+    assert_eq![weather.stationary(1, 1e-14), None];
+}
+
+#[test]
+fn absorbing() {
+    assert![RUIN.is_absorbing(0) && RUIN.is_absorbing(4) && !RUIN.is_absorbing(2)];
+    let b = RUIN.absorption_probabilities().unwrap();
+    assert_approx_eq_all![tolerance: 1e-12, b.at(1, 4), 0.25];
+    assert_approx_eq_all![tolerance: 1e-12, b.at(1, 0), 0.75];
+    assert_approx_eq_all![tolerance: 1e-12, b.at(2, 0), 0.5];
+    assert_eq![(b.at(0, 0), b.at(2, 2)), (1.0, 0.0)];
+    let t = RUIN.expected_steps().unwrap();
+    for (i, expected) in [0.0, 3.0, 4.0, 3.0, 0.0].into_iter().enumerate() {
+        assert_approx_eq_all![tolerance: 1e-12, t[i], expected];
+    }
+    // a closed transient class never gets absorbed
+    let mut cycle = MarkovChain::<3, 9>::from_weights(Matrix::new([0, 1, 0, 1, 0, 0, 0, 0, 1]));
+    assert_eq![cycle.expected_steps(), None];
+    cycle.set_weight(1, 2, 1).unwrap();
+    assert![cycle.expected_steps().is_some()];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sparse() {
+    use crate::{MarkovChainSparse, Vec};
+    let dense = MarkovChainSparse::from_dense(&RUIN);
+    assert_eq![(dense.state_count(), dense.transition_count()), (5, 8)];
+    assert_eq![dense.weight(2, 3), Some(1)];
+    assert_eq![dense.weight(2, 2), Some(0)];
+    assert_eq![dense.probability(3, 4), RUIN.probability(3, 4)];
+    let b = dense.absorption_probabilities().unwrap();
+    assert_approx_eq_all![tolerance: 1e-12, b[5 + 4], 0.25];
+    let t = dense.expected_steps().unwrap();
+    assert_approx_eq_all![tolerance: 1e-12, t[2], 4.0];
+    // the dense absorbing system is refused for too many states
+    let large = MarkovChainSparse::ABSORBING_STATES_MAX + 1;
+    let large = MarkovChainSparse::from_transitions(large, [(0, 1, 1)]).unwrap();
+    assert_eq![large.expected_steps(), None];
+    assert_eq![large.absorption_probabilities(), None];
+
+    let seqs: [&[usize]; 2] = [&[0, 1, 0, 1], &[1, 1, 0]];
+    let c = MarkovChainSparse::from_sequences(2, seqs).unwrap();
+    assert_eq![c.transitions(0).collect::<Vec<_>>(), [(1, 2)]];
+    assert_eq![c.transitions(1).collect::<Vec<_>>(), [(0, 2), (1, 1)]];
+    let pi = c.stationary(10_000, 1e-14).unwrap();
+    assert_approx_eq_all![tolerance: 1e-9, pi[0], 0.4];
+    let mut rng = Pcg32::new(5, 1);
+    assert_eq![c.step(0, &mut rng), Some(1)];
+    assert_eq![c.walk(1, &mut rng).take(10).count(), 10];
+
+    let bad: [&[usize]; 1] = [&[2]];
+    assert_eq![MarkovChainSparse::from_sequences(2, bad), Err(IndexOutOfBounds(Some(2)))];
+}
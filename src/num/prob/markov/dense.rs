@@ -0,0 +1,277 @@
+// devela/src/num/prob/markov/dense.rs
+//
+//! Defines [`MarkovChain`].
+//
+
+use super::_helper::solve;
+use crate::{ConstInit, IndexOutOfBounds, Iter, Matrix, Rand, is, whilst};
+
+#[doc = crate::_tags!(num rand)]
+/// A finite-state Markov chain over a static dense transition matrix.
+#[doc = crate::_doc_meta!{location("num/prob/markov")}]
+///
+/// `S` is the number of states and `LEN` must equal `S * S`,
+/// as required by the underlying [`Matrix`].
+///
+/// Transitions are stored as integer weights, such as observed counts.
+/// The probability of moving from `i` to `j` is the weight at `(i, j)`
+/// divided by the total weight of row `i`. A state without any outgoing weight
+/// is a dead end, and is treated as transitioning to itself with probability 1.
+///
+/// Integer weights make training exact and sampling reproducible across platforms.
+///
+/// # Example
+/// ```
+/// # use devela::{MarkovChain, Pcg32};
+/// const SUNNY: usize = 0; const RAINY: usize = 1;
+/// let mut weather = MarkovChain::<2, 4>::new();
+/// weather.train(&[SUNNY, SUNNY, SUNNY, RAINY, RAINY, SUNNY, SUNNY, RAINY]).unwrap();
+/// assert_eq![weather.weight(SUNNY, SUNNY), Some(3)];
+/// assert_eq![weather.probability(SUNNY, RAINY), Some(0.4)];
+///
+/// let pi = weather.stationary(1000, 1e-12).unwrap();
+/// assert![(pi[SUNNY] + pi[RAINY] - 1.0).abs() < 1e-9];
+///
+/// let mut rng = Pcg32::new(7, 1);
+/// let next = weather.step(SUNNY, &mut rng).unwrap();
+/// assert![next == SUNNY || next == RAINY];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MarkovChain<const S: usize, const LEN: usize> {
+    weights: Matrix<u32, S, S, LEN>,
+}
+
+impl<const S: usize, const LEN: usize> ConstInit for MarkovChain<S, LEN> {
+    const INIT: Self = Self::new();
+}
+
+impl<const S: usize, const LEN: usize> MarkovChain<S, LEN> {
+    /* construction */
+
+    /// Returns a chain with all weights set to zero.
+    ///
+    /// # Panics
+    /// Panics if `LEN != S * S`.
+    pub const fn new() -> Self {
+        Self { weights: Matrix::new([0; LEN]) }
+    }
+    /// Returns a chain with the given transition `weights`.
+    pub const fn from_weights(weights: Matrix<u32, S, S, LEN>) -> Self {
+        Self { weights }
+    }
+    /// Returns the transition weights.
+    pub const fn weights(&self) -> &Matrix<u32, S, S, LEN> {
+        &self.weights
+    }
+    /// Returns the transition weights, consuming the chain.
+    pub const fn into_weights(self) -> Matrix<u32, S, S, LEN> {
+        self.weights
+    }
+
+    /* training */
+
+    /// Clears all transition weights.
+    pub const fn reset(&mut self) {
+        self.weights = Matrix::new([0; LEN]);
+    }
+    /// Sets the weight of the transition from `from` to `to`.
+    ///
+    /// # Errors
+    /// Returns [`IndexOutOfBounds`] if either state is `>= S`.
+    pub const fn set_weight(
+        &mut self,
+        from: usize,
+        to: usize,
+        weight: u32,
+    ) -> Result<(), IndexOutOfBounds> {
+        is![from >= S, return Err(IndexOutOfBounds(Some(from)))];
+        is![to >= S, return Err(IndexOutOfBounds(Some(to)))];
+        self.weights.data[from * S + to] = weight;
+        Ok(())
+    }
+    /// Records one observed transition from `from` to `to`.
+    ///
+    /// The weight saturates at `u32::MAX`.
+    ///
+    /// # Errors
+    /// Returns [`IndexOutOfBounds`] if either state is `>= S`.
+    pub const fn observe(&mut self, from: usize, to: usize) -> Result<(), IndexOutOfBounds> {
+        is![from >= S, return Err(IndexOutOfBounds(Some(from)))];
+        is![to >= S, return Err(IndexOutOfBounds(Some(to)))];
+        let w = &mut self.weights.data[from * S + to];
+        *w = w.saturating_add(1);
+        Ok(())
+    }
+    /// Records every consecutive transition of an observed `sequence` of states.
+    ///
+    /// # Errors
+    /// Returns [`IndexOutOfBounds`] with the first state `>= S`,
+    /// in which case the chain is left unchanged.
+    pub const fn train(&mut self, sequence: &[usize]) -> Result<(), IndexOutOfBounds> {
+        whilst! { i in 0..sequence.len(); {
+            is![sequence[i] >= S, return Err(IndexOutOfBounds(Some(sequence[i])))];
+        }}
+        whilst! { i in 1..sequence.len(); {
+            let w = &mut self.weights.data[sequence[i - 1] * S + sequence[i]];
+            *w = w.saturating_add(1);
+        }}
+        Ok(())
+    }
+
+    /* queries */
+
+    /// Returns the number of states.
+    #[must_use]
+    pub const fn state_count(&self) -> usize {
+        S
+    }
+    /// Returns the weight of the transition from `from` to `to`,
+    /// or `None` if either state is out of bounds.
+    #[must_use]
+    pub const fn weight(&self, from: usize, to: usize) -> Option<u32> {
+        is![from >= S || to >= S, return None];
+        Some(self.weights.data[from * S + to])
+    }
+    /// Returns the total outgoing weight of `state`, or `None` if out of bounds.
+    #[must_use]
+    pub const fn row_total(&self, state: usize) -> Option<u64> {
+        is![state >= S, return None];
+        Some(self.total(state))
+    }
+    /// Returns the probability of transitioning from `from` to `to`,
+    /// or `None` if either state is out of bounds.
+    #[must_use]
+    pub const fn probability(&self, from: usize, to: usize) -> Option<f64> {
+        is![from >= S || to >= S, return None];
+        Some(self.prob(from, to))
+    }
+    /// Returns `true` if `state` can't transition to any other state.
+    ///
+    /// Returns `false` if `state` is out of bounds.
+    #[must_use]
+    pub const fn is_absorbing(&self, state: usize) -> bool {
+        is![state >= S, return false];
+        whilst! { to in 0..S; {
+            is![to != state && self.weights.data[state * S + to] != 0, return false];
+        }}
+        true
+    }
+
+    /* sampling */
+
+    /// Samples the state that follows `from`, or returns `None` if it's out of bounds.
+    pub fn step<R: Rand + ?Sized>(&self, from: usize, rng: &mut R) -> Option<usize> {
+        is![from >= S, return None];
+        let total = self.total(from);
+        is![total == 0, return Some(from)];
+        let mut r = rng.rand_below(total);
+        for to in 0..S {
+            let w = u64::from(self.weights.data[from * S + to]);
+            is![r < w, return Some(to)];
+            r -= w;
+        }
+        unreachable!()
+    }
+    /// Returns an endless iterator over the states visited after `start`.
+    ///
+    /// The iterator is empty if `start` is out of bounds.
+    pub fn walk<'a, R: Rand + ?Sized>(
+        &'a self,
+        start: usize,
+        rng: &'a mut R,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let first = self.step(start, rng);
+        Iter::successors(first, move |&s| self.step(s, rng))
+    }
+
+    /* analysis */
+
+    /// Returns the stationary distribution, by power iteration from the uniform distribution.
+    ///
+    /// Iterates the lazy chain `(I + P) / 2`, which has the same stationary
+    /// distributions but also converges for periodic chains. For reducible chains the
+    /// result is the limit reached from the uniform distribution.
+    ///
+    /// Returns `None` if `S == 0` or if the L1 change between iterations
+    /// doesn't fall below `tolerance` within `max_iterations`.
+    #[must_use]
+    pub const fn stationary(&self, max_iterations: u32, tolerance: f64) -> Option<[f64; S]> {
+        is![S == 0, return None];
+        let mut pi = [1.0 / S as f64; S];
+        let mut iter = 0;
+        while iter < max_iterations {
+            let mut next = [0.0; S];
+            whilst! { from in 0..S; {
+                let mass = pi[from] * 0.5;
+                next[from] += mass;
+                let total = self.total(from);
+                if total == 0 {
+                    next[from] += mass;
+                } else {
+                    let scale = mass / total as f64;
+                    whilst! { to in 0..S; {
+                        next[to] += scale * self.weights.data[from * S + to] as f64;
+                    }}
+                }
+            }}
+            let mut change = 0.0;
+            whilst! { i in 0..S; { change += (next[i] - pi[i]).abs(); }}
+            pi = next;
+            is![change < tolerance, return Some(pi)];
+            iter += 1;
+        }
+        None
+    }
+    /// Returns the probabilities of ending in each absorbing state.
+    ///
+    /// The element at `(i, j)` is the probability that a walk from `i` is eventually
+    /// absorbed in `j`. It's zero for every non-absorbing `j`,
+    /// and each absorbing state is absorbed in itself.
+    ///
+    /// Returns `None` if some non-absorbing state can't reach any absorbing state.
+    #[must_use]
+    pub const fn absorption_probabilities(&self) -> Option<Matrix<f64, S, S, LEN>> {
+        let mut a = [0.0; LEN];
+        self.absorbing_system(&mut a);
+        let mut b = [0.0; LEN];
+        whilst! { i in 0..S; { is![self.is_absorbing(i), b[i * S + i] = 1.0]; }}
+        is![solve(&mut a, &mut b, S, S), Some(Matrix::new(b)), None]
+    }
+    /// Returns the expected number of steps before being absorbed, from each state.
+    ///
+    /// It's zero for absorbing states.
+    ///
+    /// Returns `None` if some non-absorbing state can't reach any absorbing state.
+    #[must_use]
+    pub const fn expected_steps(&self) -> Option<[f64; S]> {
+        let mut a = [0.0; LEN];
+        self.absorbing_system(&mut a);
+        let mut b = [0.0; S];
+        whilst! { i in 0..S; { is![!self.is_absorbing(i), b[i] = 1.0]; }}
+        is![solve(&mut a, &mut b, S, 1), Some(b), None]
+    }
+
+    /* helpers */
+
+    const fn total(&self, state: usize) -> u64 {
+        let mut sum = 0;
+        whilst! { to in 0..S; { sum += self.weights.data[state * S + to] as u64; }}
+        sum
+    }
+    const fn prob(&self, from: usize, to: usize) -> f64 {
+        let total = self.total(from);
+        if total == 0 {
+            return if from == to { 1.0 } else { 0.0 };
+        }
+        self.weights.data[from * S + to] as f64 / total as f64
+    }
+    /// Fills `a` with `I - P` for non-absorbing rows, and with `I` for absorbing rows.
+    const fn absorbing_system(&self, a: &mut [f64; LEN]) {
+        whilst! { i in 0..S; {
+            a[i * S + i] = 1.0;
+            if !self.is_absorbing(i) {
+                whilst! { j in 0..S; { a[i * S + j] -= self.prob(i, j); }}
+            }
+        }}
+    }
+}
@@ -0,0 +1,41 @@
+// devela/src/num/prob/markov/mod.rs
+//
+#![doc = crate::_DOC_NUM_PROB_MARKOV!()] // public
+#![doc = crate::_doc!(modules: crate::num::prob; markov)]
+#![doc = crate::_doc!(flat:"num")]
+#![doc = crate::_doc!(hr)]
+//!
+//! Chains over a finite number of states, indexed from `0`,
+//! with transition weights such as observed counts.
+//!
+//! - [`MarkovChain`] uses a static dense [`Matrix`][crate::Matrix] and never allocates.
+//!   It requires the `alg` feature.
+//! - [`MarkovChainSparse`] uses dynamic compressed sparse rows, for large state spaces.
+//!   It requires the `alloc` feature.
+//!
+//! Both can be trained from observed sequences, sampled with any
+//! [`Rand`][crate::Rand] source, and analyzed for their stationary
+//! distribution and absorbing states.
+//
+
+#[cfg(all(test, feature = "alg"))]
+mod _test;
+
+#[cfg(any(feature = "alg", feature = "alloc"))]
+mod _helper; // solve
+
+#[cfg(feature = "alg")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "alg")))]
+mod dense; // MarkovChain
+#[cfg(feature = "alloc")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "alloc")))]
+mod sparse; // MarkovChainSparse
+
+crate::structural_mods! { // _mods
+    _mods {
+        #[cfg(feature = "alg")]
+        pub use super::dense::MarkovChain;
+        #[cfg(feature = "alloc")]
+        pub use super::sparse::MarkovChainSparse;
+    }
+}
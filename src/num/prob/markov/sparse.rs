@@ -0,0 +1,292 @@
+// devela/src/num/prob/markov/sparse.rs
+//
+//! Defines [`MarkovChainSparse`].
+//
+
+use super::_helper::solve;
+#[cfg(feature = "alg")]
+use crate::MarkovChain;
+use crate::{IndexOutOfBounds, Iter, Mem, Rand, Vec, is, vec_ as vec};
+
+#[doc = crate::_tags!(num rand allocation)]
+/// A finite-state Markov chain over a dynamic sparse transition matrix.
+#[doc = crate::_doc_meta!{location("num/prob/markov")}]
+///
+/// Transitions are stored in compressed sparse row form, the same layout as
+/// [`graph_csr!`][crate::graph_csr]: the outgoing transitions of each state occupy
+/// one contiguous range of targets, sorted by target, each with its integer weight.
+/// Only transitions with a non-zero weight are stored.
+///
+/// Probabilities, dead ends and absorbing states follow the same rules as
+/// [`MarkovChain`][crate::MarkovChain].
+///
+/// The chain is immutable after construction.
+///
+/// # Example
+/// ```
+/// # use devela::{MarkovChainSparse, Pcg32};
+/// // learn letter transitions from some words
+/// let words = ["banana", "bandana", "cabana"];
+/// let seqs = words.map(|w| w.bytes().map(usize::from).collect::<Vec<_>>());
+/// let chain = MarkovChainSparse::from_sequences(256, seqs.iter().map(|s| &s[..])).unwrap();
+/// assert_eq![chain.weight(b'a'.into(), b'n'.into()), Some(5)];
+/// assert_eq![chain.transitions(b'n'.into()).count(), 2]; // 'a', 'd'
+///
+/// let mut rng = Pcg32::new(3, 1);
+/// let text: Vec<u8> = chain.walk(b'b'.into(), &mut rng).take(8).map(|s| s as u8).collect();
+/// assert![text.iter().all(|c| b"abcdn".contains(c))];
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MarkovChainSparse {
+    /// The offset of the first transition of each state, plus the total at the end.
+    starts: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<u32>,
+}
+
+impl MarkovChainSparse {
+    /// The largest number of states of [`absorption_probabilities`] and [`expected_steps`].
+    ///
+    /// Both solve a dense `n × n` linear system, taking `O(n²)` memory and `O(n³)` time,
+    /// about 32 MiB and a few seconds at this limit.
+    ///
+    /// [`absorption_probabilities`]: Self::absorption_probabilities
+    /// [`expected_steps`]: Self::expected_steps
+    pub const ABSORBING_STATES_MAX: usize = 2048;
+
+    /* construction */
+
+    /// Returns a chain of `states` from weighted `(from, to, weight)` transitions.
+    ///
+    /// Weights of repeated transitions are added, saturating at `u32::MAX`.
+    /// Transitions with a zero weight are ignored.
+    ///
+    /// # Errors
+    /// Returns [`IndexOutOfBounds`] with the first state `>= states`.
+    pub fn from_transitions<I>(states: usize, transitions: I) -> Result<Self, IndexOutOfBounds>
+    where
+        I: IntoIterator<Item = (usize, usize, u32)>,
+    {
+        let mut list = Vec::new();
+        for (from, to, weight) in transitions {
+            is![from >= states, return Err(IndexOutOfBounds(Some(from)))];
+            is![to >= states, return Err(IndexOutOfBounds(Some(to)))];
+            is![weight != 0, list.push((from, to, weight))];
+        }
+        list.sort_unstable_by_key(|&(from, to, _)| (from, to));
+
+        let mut starts = vec![0; states + 1];
+        let (mut targets, mut weights) = (Vec::new(), Vec::<u32>::new());
+        let mut last = None;
+        for (from, to, weight) in list {
+            if last == Some((from, to)) {
+                let w = weights.last_mut().expect("a previous transition");
+                *w = w.saturating_add(weight);
+            } else {
+                starts[from + 1] += 1;
+                targets.push(to);
+                weights.push(weight);
+                last = Some((from, to));
+            }
+        }
+        for i in 0..states {
+            starts[i + 1] += starts[i];
+        }
+        Ok(Self { starts, targets, weights })
+    }
+    /// Returns a chain of `states` trained on every consecutive transition
+    /// of the observed `sequences`.
+    ///
+    /// # Errors
+    /// Returns [`IndexOutOfBounds`] with the first state `>= states`.
+    pub fn from_sequences<'a, I>(states: usize, sequences: I) -> Result<Self, IndexOutOfBounds>
+    where
+        I: IntoIterator<Item = &'a [usize]>,
+    {
+        let mut transitions = Vec::new();
+        for seq in sequences {
+            // check every state, including those of single-state sequences
+            for &s in seq {
+                is![s >= states, return Err(IndexOutOfBounds(Some(s)))];
+            }
+            transitions.extend(seq.windows(2).map(|w| (w[0], w[1], 1)));
+        }
+        Self::from_transitions(states, transitions)
+    }
+    /// Returns a sparse copy of a dense `chain`.
+    #[cfg(feature = "alg")]
+    #[cfg_attr(nightly_doc, doc(cfg(feature = "alg")))]
+    pub fn from_dense<const S: usize, const LEN: usize>(chain: &MarkovChain<S, LEN>) -> Self {
+        let data = &chain.weights().data;
+        let transitions = (0..LEN).map(|i| (i / S, i % S, data[i]));
+        Self::from_transitions(S, transitions).expect("states within bounds")
+    }
+
+    /* queries */
+
+    /// Returns the number of states.
+    #[must_use]
+    pub const fn state_count(&self) -> usize {
+        self.starts.len().saturating_sub(1)
+    }
+    /// Returns the number of stored transitions with a non-zero weight.
+    #[must_use]
+    pub const fn transition_count(&self) -> usize {
+        self.targets.len()
+    }
+    /// Returns an iterator over the `(to, weight)` transitions from `state`, sorted by `to`.
+    ///
+    /// The iterator is empty if `state` is out of bounds.
+    pub fn transitions(&self, state: usize) -> impl Iterator<Item = (usize, u32)> + '_ {
+        let (start, end) = self.bounds(state).unwrap_or((0, 0));
+        self.targets[start..end].iter().copied().zip(self.weights[start..end].iter().copied())
+    }
+    /// Returns the weight of the transition from `from` to `to`,
+    /// or `None` if either state is out of bounds.
+    #[must_use]
+    pub fn weight(&self, from: usize, to: usize) -> Option<u32> {
+        is![to >= self.state_count(), return None];
+        let (start, end) = self.bounds(from)?;
+        match self.targets[start..end].binary_search(&to) {
+            Ok(i) => Some(self.weights[start + i]),
+            Err(_) => Some(0),
+        }
+    }
+    /// Returns the total outgoing weight of `state`, or `None` if out of bounds.
+    #[must_use]
+    pub fn row_total(&self, state: usize) -> Option<u64> {
+        let (start, end) = self.bounds(state)?;
+        Some(self.weights[start..end].iter().map(|&w| u64::from(w)).sum())
+    }
+    /// Returns the probability of transitioning from `from` to `to`,
+    /// or `None` if either state is out of bounds.
+    #[must_use]
+    pub fn probability(&self, from: usize, to: usize) -> Option<f64> {
+        let weight = self.weight(from, to)?;
+        let total = self.row_total(from)?;
+        if total == 0 {
+            return Some(if from == to { 1.0 } else { 0.0 });
+        }
+        Some(f64::from(weight) / total as f64)
+    }
+    /// Returns `true` if `state` can't transition to any other state.
+    ///
+    /// Returns `false` if `state` is out of bounds.
+    #[must_use]
+    pub fn is_absorbing(&self, state: usize) -> bool {
+        self.bounds(state).is_some() && self.transitions(state).all(|(to, _)| to == state)
+    }
+
+    /* sampling */
+
+    /// Samples the state that follows `from`, or returns `None` if it's out of bounds.
+    pub fn step<R: Rand + ?Sized>(&self, from: usize, rng: &mut R) -> Option<usize> {
+        let total = self.row_total(from)?;
+        is![total == 0, return Some(from)];
+        let mut r = rng.rand_below(total);
+        for (to, w) in self.transitions(from) {
+            is![r < u64::from(w), return Some(to)];
+            r -= u64::from(w);
+        }
+        unreachable!()
+    }
+    /// Returns an endless iterator over the states visited after `start`.
+    ///
+    /// The iterator is empty if `start` is out of bounds.
+    pub fn walk<'a, R: Rand + ?Sized>(
+        &'a self,
+        start: usize,
+        rng: &'a mut R,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let first = self.step(start, rng);
+        Iter::successors(first, move |&s| self.step(s, rng))
+    }
+
+    /* analysis */
+
+    /// Returns the stationary distribution, by power iteration from the uniform distribution.
+    ///
+    /// See [`MarkovChain::stationary`][crate::MarkovChain::stationary] for the details.
+    #[must_use]
+    pub fn stationary(&self, max_iterations: u32, tolerance: f64) -> Option<Vec<f64>> {
+        let n = self.state_count();
+        is![n == 0, return None];
+        let mut pi = vec![1.0 / n as f64; n];
+        let mut next = vec![0.0; n];
+        for _ in 0..max_iterations {
+            next.fill(0.0);
+            for from in 0..n {
+                let mass = pi[from] * 0.5;
+                next[from] += mass;
+                let total = self.row_total(from).unwrap_or(0);
+                if total == 0 {
+                    next[from] += mass;
+                } else {
+                    let scale = mass / total as f64;
+                    for (to, w) in self.transitions(from) {
+                        next[to] += scale * f64::from(w);
+                    }
+                }
+            }
+            let change: f64 = pi.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+            Mem::swap(&mut pi, &mut next);
+            is![change < tolerance, return Some(pi)];
+        }
+        None
+    }
+    /// Returns the probabilities of ending in each absorbing state,
+    /// as an `n × n` row-major matrix.
+    ///
+    /// See [`MarkovChain::absorption_probabilities`][crate::MarkovChain::absorption_probabilities]
+    /// for the details.
+    ///
+    /// Unlike the rest of the chain, this solves a dense system of `n × n` elements.
+    /// Returns `None` above [`ABSORBING_STATES_MAX`][Self::ABSORBING_STATES_MAX] states.
+    #[must_use]
+    pub fn absorption_probabilities(&self) -> Option<Vec<f64>> {
+        let n = self.state_count();
+        is![n > Self::ABSORBING_STATES_MAX, return None];
+        let mut a = self.absorbing_system();
+        let mut b = vec![0.0; n * n];
+        for i in (0..n).filter(|&i| self.is_absorbing(i)) {
+            b[i * n + i] = 1.0;
+        }
+        is![solve(&mut a, &mut b, n, n), Some(b), None]
+    }
+    /// Returns the expected number of steps before being absorbed, from each state.
+    ///
+    /// See [`MarkovChain::expected_steps`][crate::MarkovChain::expected_steps] for the details.
+    ///
+    /// Unlike the rest of the chain, this solves a dense system of `n × n` elements.
+    /// Returns `None` above [`ABSORBING_STATES_MAX`][Self::ABSORBING_STATES_MAX] states.
+    #[must_use]
+    pub fn expected_steps(&self) -> Option<Vec<f64>> {
+        let n = self.state_count();
+        is![n > Self::ABSORBING_STATES_MAX, return None];
+        let mut a = self.absorbing_system();
+        let mut b: Vec<f64> = (0..n).map(|i| is![self.is_absorbing(i), 0.0, 1.0]).collect();
+        is![solve(&mut a, &mut b, n, 1), Some(b), None]
+    }
+
+    /* helpers */
+
+    fn bounds(&self, state: usize) -> Option<(usize, usize)> {
+        is![state >= self.state_count(), return None];
+        Some((self.starts[state], self.starts[state + 1]))
+    }
+    /// Returns the dense `I - P` for non-absorbing rows, and `I` for absorbing rows.
+    fn absorbing_system(&self) -> Vec<f64> {
+        let n = self.state_count();
+        let mut a = vec![0.0; n * n];
+        for i in 0..n {
+            a[i * n + i] = 1.0;
+            if !self.is_absorbing(i) {
+                let total = self.row_total(i).unwrap_or(0) as f64;
+                for (j, w) in self.transitions(i) {
+                    a[i * n + j] -= f64::from(w) / total;
+                }
+            }
+        }
+        a
+    }
+}
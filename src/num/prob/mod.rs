@@ -1,7 +1,7 @@
 // devela/src/num/prob/mod.rs
 //
 #![doc = crate::_DOC_NUM_PROB!()] // public
-#![doc = crate::_doc!(modules: crate::num; prob: rand)]
+#![doc = crate::_doc!(modules: crate::num; prob: markov, rand, stats)]
 #![doc = crate::_doc!(flat:"num")]
 #![doc = crate::_doc!(hr)]
 //!
@@ -11,9 +11,9 @@
 // mod dist; // Probability distributions
 // mod estim; // Estimation & inference
 // mod fit; // Model fitting
-// mod markov; // Stochastic matrices, chains, traces
+pub mod markov; // Stochastic matrices, chains, traces
 pub mod rand; // Random number generation
-// pub mod stats; // Descriptive statistics
+pub mod stats; // Descriptive statistics
 
 crate::structural_mods! { // _mods, _pub_mods, _crate_internals, _hidden
     _mods {
@@ -25,9 +25,9 @@ crate::structural_mods! { // _mods, _pub_mods, _crate_internals, _hidden
     }
     _pub_mods {
         pub use super::{
-            // markov::_all::*,
+            markov::_all::*,
             rand::_all::*,
-            // stats::_all::*,
+            stats::_all::*,
         };
     }
     _crate_internals {
@@ -44,7 +44,8 @@
 //! # Features
 //!
 //! - [`Pcg32`] and [`SplitMix64`] are always available.
-//! - The `rand` feature enables the extended PRNG palette and generator macros.
+//! - The `rand` feature enables the extended PRNG palette, generator macros,
+//!   and coherent noise generators.
 //! - The `std` feature enables [`StdRand`].
 //
 
@@ -54,11 +55,13 @@ mod _test;
 mod _helper; // (_impl_dep_rand_core)
 
 mod from; // FromRandTry, FromRand
-// mod noise; // Structured deterministic randomness
 mod prng; // concrete PRNGs
 mod rand; // RandSeedable, RandTry, Rand
 mod qual; // RandQualities
 
+#[cfg(feature = "rand")]
+#[cfg_attr(nightly_doc, doc(cfg(feature = "rand")))]
+mod noise; // Noise[Value|Perlin|Simplex|Worley|Fbm|Ridged|Turbulence|Warp]
 #[cfg(feature = "std")]
 mod std; // StdRand
 #[cfg(any(test, feature = "rand"))]
@@ -68,11 +71,12 @@ crate::structural_mods! { // _mods, _crate_internals, _hidden
     _mods {
         pub use super::{
             from::*,
-            // noise::*,
             prng::_all::*,
             rand::*,
             qual::*,
         };
+        #[cfg(feature = "rand")]
+        pub use super::noise::_all::*;
         #[cfg(feature = "std")]
         pub use super::std::StdRand;
         #[cfg(any(test, feature = "rand"))]
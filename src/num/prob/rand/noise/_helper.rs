@@ -0,0 +1,106 @@
+// devela/src/num/prob/rand/noise/_helper.rs
+//
+//! Lattice hashing, flooring and gradient helpers shared by the noise generators.
+//
+// All operations are plain integer and IEEE-754 arithmetic, without fused
+// operations or platform intrinsics, so the results are identical across targets.
+
+use crate::whilst;
+
+/// Odd multipliers used to decorrelate each lattice axis.
+const AXIS_MUL: [u32; 4] = [0x8DA6_B343, 0xD816_3841, 0xCB1A_B31F, 0x165E_6F9D];
+
+/// Returns a well-mixed 32-bit hash of a lattice `cell` for the given `seed`.
+pub(super) const fn hash<const N: usize>(seed: u32, cell: &[i32; N]) -> u32 {
+    let mut h = seed.wrapping_mul(0x9E37_79B9) ^ 0x85EB_CA6B;
+    whilst! { k in 0..N; {
+        h ^= (cell[k] as u32).wrapping_mul(AXIS_MUL[k]);
+        h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xE654_6B64);
+    }}
+    // murmur3 finalizer
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xC2B2_AE35);
+    h ^ (h >> 16)
+}
+
+/// Maps a hash to a value in `[-1, 1]`.
+pub(super) const fn hash_to_signed(h: u32) -> f32 {
+    (h >> 8) as f32 * (2.0 / 16_777_215.0) - 1.0
+}
+
+/// Maps a hash to a value in `[0, 1)`.
+pub(super) const fn hash_to_unit(h: u32) -> f32 {
+    (h >> 8) as f32 * (1.0 / 16_777_216.0)
+}
+
+/// Returns the largest integer less than or equal to `x`.
+pub(super) const fn floor(x: f32) -> i32 {
+    let i = x as i32;
+    if (i as f32) > x { i - 1 } else { i }
+}
+
+/// Quintic smoothstep `6t⁵ - 15t⁴ + 10t³`, with zero first and second derivatives at 0 and 1.
+pub(super) const fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Returns the dot product of the pseudo-random gradient selected by `h` with `d`.
+///
+/// - 1D: 16 magnitudes in `[-1, 1]`.
+/// - 2D: the 8 axis and diagonal directions.
+/// - 3D: the 12 edge midpoints of a cube.
+/// - 4D: the 32 edge midpoints of a tesseract.
+pub(super) const fn grad<const N: usize>(h: u32, d: &[f32; N]) -> f32 {
+    match N {
+        1 => {
+            let g = 1.0 + (h & 7) as f32;
+            let g = if h & 8 != 0 { -g } else { g };
+            g * d[0] * 0.125
+        }
+        2 => {
+            let (x, y) = (d[0], d[1]);
+            match h & 7 {
+                0 => x + y,
+                1 => -x + y,
+                2 => x - y,
+                3 => -x - y,
+                4 => x,
+                5 => -x,
+                6 => y,
+                _ => -y,
+            }
+        }
+        3 => {
+            let (x, y, z) = (d[0], d[1], d[2]);
+            match h & 15 {
+                0 | 12 => x + y,
+                1 | 14 => -x + y,
+                2 => x - y,
+                3 => -x - y,
+                4 => x + z,
+                5 => -x + z,
+                6 => x - z,
+                7 => -x - z,
+                8 => y + z,
+                9 | 13 => -y + z,
+                10 => y - z,
+                _ => -y - z,
+            }
+        }
+        _ => {
+            let zero = ((h >> 3) & 3) as usize;
+            let mut sum = 0.0;
+            let mut bit = 0;
+            whilst! { k in 0..N; {
+                if k != zero {
+                    let v = d[k];
+                    sum += if h & (1 << bit) != 0 { -v } else { v };
+                    bit += 1;
+                }
+            }}
+            sum
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// devela/src/num/prob/rand/noise/_test.rs
+
+use crate::{
+    NoiseFbm, NoisePerlin, NoiseRidged, NoiseSimplex, NoiseTurbulence, NoiseValue, NoiseWarp,
+    NoiseWorley, NoiseWorleyMetric, NoiseWorleyOutput, SignalAt, SignalMap,
+};
+
+/// Returns a deterministic pseudo-random point in `[-scale, scale)^N`.
+fn point<const N: usize>(i: u32, scale: f32) -> [f32; N] {
+    let mut p = [0.0; N];
+    let mut s = i.wrapping_mul(0x9E37_79B9) ^ 0x1234_5678;
+    for x in p.iter_mut() {
+        s ^= s << 13;
+        s ^= s >> 17;
+        s ^= s << 5;
+        *x = (s >> 8) as f32 / 16_777_216.0 * 2.0 * scale - scale;
+    }
+    p
+}
+
+/// Checks that `noise` stays in `range`, is not constant, and is continuous.
+fn check<const N: usize>(noise: &impl SignalAt<[f32; N], Sample = f32>, lo: f32, hi: f32) {
+    let (mut min, mut max) = (f32::MAX, f32::MIN);
+    for i in 0..2000 {
+        let p = point::<N>(i, 50.0);
+        let v = noise.at(p);
+        assert![(lo..=hi).contains(&v), "{v} out of range at {p:?}"];
+        min = min.min(v);
+        max = max.max(v);
+        let mut q = p;
+        q[0] += 1e-3;
+        assert![(noise.at(q) - v).abs() < 0.05, "discontinuity at {p:?}"];
+    }
+    assert![max - min > 0.2 * (hi - lo), "range too narrow: {min}..{max}"];
+}
+
+#[test]
+fn base_noises_range_and_continuity() {
+    macro_rules! check_all {
+        ($($n:literal),+) => { $(
+            check::<$n>(&NoiseValue::new(1), -1.0, 1.0);
+            check::<$n>(&NoisePerlin::new(2), -1.0, 1.0);
+            check::<$n>(&NoiseSimplex::new(3), -1.0, 1.0);
+        )+ };
+    }
+    check_all![1, 2, 3, 4];
+}
+
+#[test]
+fn deterministic_and_seeded() {
+    const P: [f32; 3] = [1.25, -7.5, 3.125];
+    const V: f32 = NoiseSimplex::new(5).sample(P);
+    assert_eq![NoiseSimplex::new(5).at(P).to_bits(), V.to_bits()];
+    assert_ne![NoiseSimplex::new(6).at(P).to_bits(), V.to_bits()];
+    assert_ne![NoiseValue::new(5).at(P).to_bits(), NoiseValue::new(6).at(P).to_bits()];
+    // gradient noise vanishes on the lattice
+    assert_eq![NoisePerlin::new(9).at([4.0, -3.0]).abs().to_bits(), 0];
+}
+
+#[test]
+fn worley() {
+    let f1 = NoiseWorley::new(4);
+    let f2 = f1.with_output(NoiseWorleyOutput::F2);
+    let edge = f1.with_output(NoiseWorleyOutput::F2MinusF1);
+    let cell = f1.with_output(NoiseWorleyOutput::CellValue);
+    for i in 0..500 {
+        let p = point::<2>(i, 20.0);
+        let (d1, d2) = (f1.at(p), f2.at(p));
+        assert![0.0 <= d1 && d1 <= d2 && d2 < 2.0];
+        assert![(edge.at(p) - (d2 - d1)).abs() < 1e-6];
+        assert![(-1.0..=1.0).contains(&cell.at(p))];
+        for metric in [NoiseWorleyMetric::Manhattan, NoiseWorleyMetric::Chebyshev] {
+            assert![f1.with_metric(metric).at(p) >= 0.0];
+        }
+    }
+    check::<3>(&f1, 0.0, 2.0);
+    check::<4>(&f1.with_metric(NoiseWorleyMetric::Chebyshev), 0.0, 1.0);
+    check::<1>(&f1.with_metric(NoiseWorleyMetric::Manhattan), 0.0, 1.0);
+}
+
+#[test]
+fn fractals() {
+    let fbm = NoiseFbm::new(NoisePerlin::new(1), 5);
+    check::<2>(&fbm, -1.0, 1.0);
+    check::<3>(&NoiseRidged::new(NoiseSimplex::new(1), 4), 0.0, 1.0);
+    check::<2>(&NoiseTurbulence::new(NoiseValue::new(1), 4).with_gain(0.6), 0.0, 1.0);
+    check::<2>(&NoiseWarp::new(fbm, NoiseSimplex::new(2), 0.5), -1.0, 1.0);
+    // a single octave equals the source
+    let one = NoiseFbm::new(NoisePerlin::new(1), 1);
+    assert_eq![one.at([0.3, 0.4]).to_bits(), NoisePerlin::new(1).at([0.3, 0.4]).to_bits()];
+    assert_eq![NoiseFbm::new(NoisePerlin::new(1), 0).at([0.3]).to_bits(), 0];
+}
+
+#[test]
+fn composes_with_signals() {
+    let unit = SignalMap::new(NoiseSimplex::new(1), |v: f32| v * 0.5 + 0.5);
+    check::<2>(&unit, 0.0, 1.0);
+}
@@ -0,0 +1,163 @@
+// devela/src/num/prob/rand/noise/cellular.rs
+//
+//! Defines [`NoiseWorley`], [`NoiseWorleyMetric`], [`NoiseWorleyOutput`].
+//
+
+use super::_helper::{floor, hash, hash_to_signed, hash_to_unit};
+use crate::{Float, SignalAt, whilst};
+
+#[doc = crate::_tags!(rand signal)]
+/// Seedable cellular (Worley) noise in 1 to 4 dimensions.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// Scatters one pseudo-random feature point in each integer lattice cell,
+/// and measures the distances from the sampled point to the nearest ones.
+///
+/// Distance outputs are non-negative and in lattice units,
+/// while [`CellValue`][NoiseWorleyOutput::CellValue] is in `[-1, 1]`.
+/// Results are the same on every platform for a given seed.
+///
+/// # Example
+/// ```
+/// # use devela::{NoiseWorley, NoiseWorleyMetric, NoiseWorleyOutput, SignalAt};
+/// let f1 = NoiseWorley::new(3);
+/// let edges = NoiseWorley::new(3).with_output(NoiseWorleyOutput::F2MinusF1);
+/// let p = [1.5, 2.25];
+/// assert![f1.at(p) >= 0.0 && edges.at(p) >= 0.0];
+/// let manhattan = f1.with_metric(NoiseWorleyMetric::Manhattan);
+/// assert![manhattan.at(p) >= f1.at(p)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoiseWorley {
+    /// The seed that places the feature points.
+    pub seed: u32,
+    /// The distance metric.
+    pub metric: NoiseWorleyMetric,
+    /// The quantity returned for each sample.
+    pub output: NoiseWorleyOutput,
+}
+
+#[doc = crate::_tags!(rand signal geom)]
+/// The distance metric used by [`NoiseWorley`].
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NoiseWorleyMetric {
+    /// Straight-line distance, giving rounded cells.
+    #[default]
+    Euclidean,
+    /// Sum of the absolute axis differences, giving diamond-like cells.
+    Manhattan,
+    /// Largest absolute axis difference, giving square-like cells.
+    Chebyshev,
+}
+
+#[doc = crate::_tags!(rand signal)]
+/// The quantity returned by [`NoiseWorley`].
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NoiseWorleyOutput {
+    /// The distance to the nearest feature point.
+    #[default]
+    F1,
+    /// The distance to the second nearest feature point.
+    F2,
+    /// The difference between `F2` and `F1`, which is zero along cell borders.
+    F2MinusF1,
+    /// A pseudo-random value in `[-1, 1]`, constant over each cell.
+    CellValue,
+}
+
+crate::_impl_init![Self::new(0) => NoiseWorley];
+crate::_impl_init![Self::Euclidean => NoiseWorleyMetric];
+crate::_impl_init![Self::F1 => NoiseWorleyOutput];
+
+impl NoiseWorley {
+    /// Returns a new Euclidean [`F1`][NoiseWorleyOutput::F1] cellular noise
+    /// with the given `seed`.
+    #[must_use]
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            metric: NoiseWorleyMetric::Euclidean,
+            output: NoiseWorleyOutput::F1,
+        }
+    }
+    /// Returns a copy with the given distance `metric`.
+    #[must_use]
+    pub const fn with_metric(self, metric: NoiseWorleyMetric) -> Self {
+        Self { metric, ..self }
+    }
+    /// Returns a copy with the given `output`.
+    #[must_use]
+    pub const fn with_output(self, output: NoiseWorleyOutput) -> Self {
+        Self { output, ..self }
+    }
+
+    /// Samples the noise at point `p`.
+    ///
+    /// Searches the `3^N` cells around `p`.
+    ///
+    /// # Panics
+    /// Fails to compile if `N` is not in `1..=4`.
+    #[must_use]
+    pub const fn sample<const N: usize>(&self, p: [f32; N]) -> f32 {
+        const {
+            assert![N >= 1 && N <= 4, "noise dimensions must be in 1..=4"];
+        }
+        let mut cell = [0_i32; N];
+        whilst! { k in 0..N; { cell[k] = floor(p[k]); }}
+
+        let (mut f1, mut f2) = (f32::MAX, f32::MAX);
+        let mut f1_hash = 0;
+        let mut neighbors = 1_usize;
+        whilst! { _k in 0..N; { neighbors *= 3; }}
+        whilst! { n in 0..neighbors; {
+            // decode the neighbor offset in {-1, 0, 1} for each axis
+            let mut c = cell;
+            let mut rest = n;
+            whilst! { k in 0..N; {
+                c[k] = c[k].wrapping_add((rest % 3) as i32 - 1);
+                rest /= 3;
+            }}
+            let h = hash(self.seed, &c);
+            let mut dist = 0.0;
+            whilst! { k in 0..N; {
+                let jitter = hash_to_unit(hash(h ^ k as u32, &c));
+                let delta = c[k] as f32 + jitter - p[k];
+                let delta = if delta < 0.0 { -delta } else { delta };
+                match self.metric {
+                    NoiseWorleyMetric::Euclidean => dist += delta * delta,
+                    NoiseWorleyMetric::Manhattan => dist += delta,
+                    NoiseWorleyMetric::Chebyshev => if delta > dist { dist = delta },
+                }
+            }}
+            if dist < f1 {
+                f2 = f1;
+                f1 = dist;
+                f1_hash = h;
+            } else if dist < f2 {
+                f2 = dist;
+            }
+        }}
+        if let NoiseWorleyMetric::Euclidean = self.metric {
+            f1 = Float(f1).sqrt_hybrid().0;
+            f2 = Float(f2).sqrt_hybrid().0;
+        }
+        match self.output {
+            NoiseWorleyOutput::F1 => f1,
+            NoiseWorleyOutput::F2 => f2,
+            NoiseWorleyOutput::F2MinusF1 => f2 - f1,
+            NoiseWorleyOutput::CellValue => hash_to_signed(f1_hash),
+        }
+    }
+}
+
+macro_rules! impl_signal_at {
+    ($($n:literal),+) => { $(
+        impl SignalAt<[f32; $n]> for NoiseWorley {
+            type Sample = f32;
+            fn at(&self, p: [f32; $n]) -> f32 { self.sample(p) }
+        }
+    )+ };
+}
+impl_signal_at![1, 2, 3, 4];
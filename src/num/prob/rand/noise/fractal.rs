@@ -0,0 +1,233 @@
+// devela/src/num/prob/rand/noise/fractal.rs
+//
+//! Defines [`NoiseFbm`], [`NoiseRidged`], [`NoiseTurbulence`], [`NoiseWarp`].
+//
+// TOC:
+// - struct NoiseFbm
+// - struct NoiseRidged
+// - struct NoiseTurbulence
+// - struct NoiseWarp
+// - helpers
+
+use crate::SignalAt;
+
+#[doc = crate::_tags!(rand signal)]
+/// Fractional Brownian motion: a sum of octaves of a noise source.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// Each octave multiplies the frequency by `lacunarity` and the amplitude by `gain`.
+/// The sum is normalized by the total amplitude, so a source in `[-1, 1]`
+/// gives an output in `[-1, 1]`.
+///
+/// # Example
+/// ```
+/// # use devela::{NoiseFbm, NoisePerlin, SignalAt};
+/// let terrain = NoiseFbm::new(NoisePerlin::new(1), 5);
+/// let h = terrain.at([12.3, 4.5]);
+/// assert![(-1.0..=1.0).contains(&h)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseFbm<S> {
+    /// The noise sampled at each octave.
+    pub source: S,
+    /// The number of octaves.
+    pub octaves: u32,
+    /// The frequency multiplier between octaves.
+    pub lacunarity: f32,
+    /// The amplitude multiplier between octaves.
+    pub gain: f32,
+}
+
+#[doc = crate::_tags!(rand signal)]
+/// Ridged multifractal noise: sharp crests where the source crosses zero.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// Each octave contributes `(1 - |n|)²`, weighted by the previous octave,
+/// so that detail accumulates along the ridges.
+/// A source in `[-1, 1]` gives an output in `[0, 1]`.
+///
+/// # Example
+/// ```
+/// # use devela::{NoiseRidged, NoiseSimplex, SignalAt};
+/// let mountains = NoiseRidged::new(NoiseSimplex::new(1), 4);
+/// assert![(0.0..=1.0).contains(&mountains.at([0.3, 0.9]))];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseRidged<S> {
+    /// The noise sampled at each octave.
+    pub source: S,
+    /// The number of octaves.
+    pub octaves: u32,
+    /// The frequency multiplier between octaves.
+    pub lacunarity: f32,
+    /// The amplitude multiplier between octaves.
+    pub gain: f32,
+}
+
+#[doc = crate::_tags!(rand signal)]
+/// Turbulence: a sum of octaves of the absolute value of a noise source.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// The absolute value creates creases at the zero crossings of each octave.
+/// A source in `[-1, 1]` gives an output in `[0, 1]`.
+///
+/// # Example
+/// ```
+/// # use devela::{NoisePerlin, NoiseTurbulence, SignalAt};
+/// let smoke = NoiseTurbulence::new(NoisePerlin::new(9), 6);
+/// assert![(0.0..=1.0).contains(&smoke.at([0.5, 0.5, 1.5]))];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseTurbulence<S> {
+    /// The noise sampled at each octave.
+    pub source: S,
+    /// The number of octaves.
+    pub octaves: u32,
+    /// The frequency multiplier between octaves.
+    pub lacunarity: f32,
+    /// The amplitude multiplier between octaves.
+    pub gain: f32,
+}
+
+#[doc = crate::_tags!(rand signal)]
+/// Domain warping: displaces the sampling point by another noise before sampling.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// Each coordinate of `p` is displaced by `strength` times the `warp` noise,
+/// sampled at an offset per axis so the displacements are uncorrelated.
+/// The output has the same range as `source`.
+///
+/// # Example
+/// ```
+/// # use devela::{NoiseFbm, NoisePerlin, NoiseWarp, SignalAt};
+/// let base = NoiseFbm::new(NoisePerlin::new(1), 4);
+/// let marble = NoiseWarp::new(base, NoisePerlin::new(2), 0.75);
+/// assert![(-1.0..=1.0).contains(&marble.at([3.2, 1.7]))];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseWarp<S, W> {
+    /// The noise sampled at the displaced point.
+    pub source: S,
+    /// The noise that displaces each coordinate.
+    pub warp: W,
+    /// The maximum displacement, in source units.
+    pub strength: f32,
+}
+
+macro_rules! impl_octaves {
+    ($($t:ident),+) => { $(
+        impl<S> $t<S> {
+            /// Returns a new fractal over `source` with the given number of `octaves`,
+            /// a lacunarity of `2` and a gain of `0.5`.
+            #[must_use]
+            pub const fn new(source: S, octaves: u32) -> Self {
+                Self { source, octaves, lacunarity: 2.0, gain: 0.5 }
+            }
+            /// Returns a copy with the given `lacunarity`.
+            #[must_use]
+            pub const fn with_lacunarity(self, lacunarity: f32) -> Self where S: Copy {
+                Self { lacunarity, ..self }
+            }
+            /// Returns a copy with the given `gain`.
+            #[must_use]
+            pub const fn with_gain(self, gain: f32) -> Self where S: Copy {
+                Self { gain, ..self }
+            }
+        }
+    )+ };
+}
+impl_octaves![NoiseFbm, NoiseRidged, NoiseTurbulence];
+
+impl<const N: usize, S: SignalAt<[f32; N], Sample = f32>> SignalAt<[f32; N]> for NoiseFbm<S> {
+    type Sample = f32;
+    fn at(&self, p: [f32; N]) -> f32 {
+        let (mut sum, mut norm) = (0.0, 0.0);
+        let (mut freq, mut amp) = (1.0, 1.0);
+        for octave in 0..self.octaves {
+            sum += amp * self.source.at(octave_point(p, freq, octave));
+            norm += amp;
+            freq *= self.lacunarity;
+            amp *= self.gain;
+        }
+        if norm > 0.0 { sum / norm } else { 0.0 }
+    }
+}
+
+impl<const N: usize, S: SignalAt<[f32; N], Sample = f32>> SignalAt<[f32; N]> for NoiseRidged<S> {
+    type Sample = f32;
+    fn at(&self, p: [f32; N]) -> f32 {
+        let (mut sum, mut norm) = (0.0, 0.0);
+        let (mut freq, mut amp) = (1.0, 1.0);
+        let mut weight = 1.0;
+        for octave in 0..self.octaves {
+            let r = 1.0 - self.source.at(octave_point(p, freq, octave)).abs();
+            let r = r * r * weight;
+            weight = r.clamp(0.0, 1.0);
+            sum += amp * r;
+            norm += amp;
+            freq *= self.lacunarity;
+            amp *= self.gain;
+        }
+        if norm > 0.0 { (sum / norm).clamp(0.0, 1.0) } else { 0.0 }
+    }
+}
+
+impl<const N: usize, S: SignalAt<[f32; N], Sample = f32>> SignalAt<[f32; N]>
+    for NoiseTurbulence<S>
+{
+    type Sample = f32;
+    fn at(&self, p: [f32; N]) -> f32 {
+        let (mut sum, mut norm) = (0.0, 0.0);
+        let (mut freq, mut amp) = (1.0, 1.0);
+        for octave in 0..self.octaves {
+            sum += amp * self.source.at(octave_point(p, freq, octave)).abs();
+            norm += amp;
+            freq *= self.lacunarity;
+            amp *= self.gain;
+        }
+        if norm > 0.0 { sum / norm } else { 0.0 }
+    }
+}
+
+impl<S, W> NoiseWarp<S, W> {
+    /// Returns a new domain warp of `source` by `warp`, with the given `strength`.
+    #[must_use]
+    pub const fn new(source: S, warp: W, strength: f32) -> Self {
+        Self { source, warp, strength }
+    }
+}
+impl<const N: usize, S, W> SignalAt<[f32; N]> for NoiseWarp<S, W>
+where
+    S: SignalAt<[f32; N], Sample = f32>,
+    W: SignalAt<[f32; N], Sample = f32>,
+{
+    type Sample = f32;
+    fn at(&self, p: [f32; N]) -> f32 {
+        let mut q = p;
+        for (k, q) in q.iter_mut().enumerate() {
+            *q += self.strength * self.warp.at(offset_point(p, k as u32 + 1));
+        }
+        self.source.at(q)
+    }
+}
+
+/* helpers */
+
+/// Irrational per axis offsets, so that octaves and warp axes don't share lattice points.
+const OFFSET: [f32; 4] = [17.319_508, 43.588_99, 61.237_244, 89.442_72];
+
+/// Returns `p` scaled by `freq` and shifted by a different offset for each `octave`.
+fn octave_point<const N: usize>(mut p: [f32; N], freq: f32, octave: u32) -> [f32; N] {
+    for p in p.iter_mut() {
+        *p *= freq;
+    }
+    offset_point(p, octave)
+}
+/// Returns `p` shifted by a different offset for each `index`.
+fn offset_point<const N: usize>(mut p: [f32; N], index: u32) -> [f32; N] {
+    let i = index as f32;
+    for (k, p) in p.iter_mut().enumerate() {
+        *p += i * OFFSET[k % 4];
+    }
+    p
+}
@@ -0,0 +1,193 @@
+// devela/src/num/prob/rand/noise/gradient.rs
+//
+//! Defines [`NoisePerlin`], [`NoiseSimplex`].
+//
+
+use super::_helper::{fade, floor, grad, hash};
+use crate::{Float, SignalAt, whilst};
+
+#[doc = crate::_tags!(rand signal)]
+/// Seedable gradient (Perlin) noise in 1 to 4 dimensions.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// Uses Ken Perlin's improved noise: pseudo-random gradients at each lattice
+/// point, blended with a quintic fade curve. The noise is zero at every lattice point.
+///
+/// The output is in `[-1, 1]`, and is the same on every platform for a given seed.
+///
+/// # Example
+/// ```
+/// # use devela::{NoisePerlin, SignalAt};
+/// let noise = NoisePerlin::new(7);
+/// assert_eq![noise.at([3.0, -2.0, 5.0]), 0.0];
+/// assert![noise.at([0.3, 0.7, 0.1]).abs() <= 1.0];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoisePerlin {
+    /// The seed that selects the lattice gradients.
+    pub seed: u32,
+}
+
+#[doc = crate::_tags!(rand signal)]
+/// Seedable simplex noise in 1 to 4 dimensions.
+#[doc = crate::_doc_meta!{location("num/prob/rand")}]
+///
+/// Sums radially attenuated gradient contributions from the `N + 1` corners
+/// of the simplex containing each point, which avoids the axis-aligned
+/// artifacts of [`NoisePerlin`] and scales better with the number of dimensions.
+///
+/// The output is in `[-1, 1]`, and is the same on every platform for a given seed.
+///
+/// # Example
+/// ```
+/// # use devela::{NoiseSimplex, SignalAt};
+/// let noise = NoiseSimplex::new(7);
+/// let v = noise.at([0.3, 0.7, 0.1, 2.5]);
+/// assert![(-1.0..=1.0).contains(&v)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoiseSimplex {
+    /// The seed that selects the lattice gradients.
+    pub seed: u32,
+}
+
+crate::_impl_init![Self::new(0) => NoisePerlin, NoiseSimplex];
+
+impl NoisePerlin {
+    /// Per dimension factors that normalize the output to `[-1, 1]`.
+    ///
+    /// They are the inverse of `√N / 2 · |g|`, the bound for gradients of length `|g|`.
+    const SCALE: [f32; 4] = [2.0, 1.0, 0.816_496_6, 0.577_350_3];
+
+    /// Returns a new Perlin noise with the given `seed`.
+    #[must_use]
+    pub const fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+
+    /// Samples the noise at point `p`.
+    ///
+    /// # Panics
+    /// Fails to compile if `N` is not in `1..=4`.
+    #[must_use]
+    pub const fn sample<const N: usize>(&self, p: [f32; N]) -> f32 {
+        const {
+            assert![N >= 1 && N <= 4, "noise dimensions must be in 1..=4"];
+        }
+        let mut cell = [0_i32; N];
+        let mut f = [0.0_f32; N];
+        let mut t = [0.0_f32; N];
+        whilst! { k in 0..N; {
+            cell[k] = floor(p[k]);
+            f[k] = p[k] - cell[k] as f32;
+            t[k] = fade(f[k]);
+        }}
+        let mut sum = 0.0;
+        whilst! { corner in 0..(1 << N); {
+            let (mut c, mut d) = (cell, f);
+            let mut w = 1.0;
+            whilst! { k in 0..N; {
+                if corner & (1 << k) != 0 {
+                    c[k] = c[k].wrapping_add(1);
+                    d[k] -= 1.0;
+                    w *= t[k];
+                } else {
+                    w *= 1.0 - t[k];
+                }
+            }}
+            sum += w * grad(hash(self.seed, &c), &d);
+        }}
+        sum * Self::SCALE[N - 1]
+    }
+}
+
+impl NoiseSimplex {
+    /// Per dimension skew factors `(√(N+1) - 1) / N`.
+    const SKEW: [f32; 4] = [0.414_213_57, 0.366_025_4, 1.0 / 3.0, 0.309_017];
+    /// Per dimension unskew factors `(1 - 1/√(N+1)) / N`.
+    const UNSKEW: [f32; 4] = [0.292_893_23, 0.211_324_87, 1.0 / 6.0, 0.138_196_6];
+    /// Per dimension factors that normalize the output to `[-1, 1]`.
+    ///
+    /// Measured empirically with some headroom; the result is clamped.
+    const SCALE: [f32; 4] = [77.0, 67.0, 73.0, 60.0];
+    /// The squared radius of influence of each corner.
+    const RADIUS2: f32 = 0.5;
+
+    /// Returns a new simplex noise with the given `seed`.
+    #[must_use]
+    pub const fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+
+    /// Samples the noise at point `p`.
+    ///
+    /// # Panics
+    /// Fails to compile if `N` is not in `1..=4`.
+    #[must_use]
+    pub const fn sample<const N: usize>(&self, p: [f32; N]) -> f32 {
+        const {
+            assert![N >= 1 && N <= 4, "noise dimensions must be in 1..=4"];
+        }
+        let (skew, unskew) = (Self::SKEW[N - 1], Self::UNSKEW[N - 1]);
+
+        // find the skewed cell and the offset from its origin
+        let mut s = 0.0;
+        whilst! { k in 0..N; { s += p[k]; }}
+        s *= skew;
+        let mut cell = [0_i32; N];
+        let mut t = 0.0;
+        whilst! { k in 0..N; {
+            cell[k] = floor(p[k] + s);
+            t += cell[k] as f32;
+        }}
+        t *= unskew;
+        let mut d0 = [0.0_f32; N];
+        whilst! { k in 0..N; { d0[k] = p[k] - (cell[k] as f32 - t); }}
+
+        // rank the axes by decreasing offset, which determines the simplex traversal
+        let mut order = [0_usize; N];
+        whilst! { k in 0..N; { order[k] = k; }}
+        whilst! { i in 1..N; {
+            let mut j = i;
+            while j > 0 && d0[order[j]] > d0[order[j - 1]] {
+                let tmp = order[j];
+                order[j] = order[j - 1];
+                order[j - 1] = tmp;
+                j -= 1;
+            }
+        }}
+
+        // sum the contributions of the N + 1 corners
+        let mut sum = 0.0;
+        let mut c = cell;
+        whilst! { v in 0..(N + 1); {
+            if v > 0 {
+                let axis = order[v - 1];
+                c[axis] = c[axis].wrapping_add(1);
+            }
+            let mut d = [0.0_f32; N];
+            let mut dist2 = 0.0;
+            whilst! { k in 0..N; {
+                d[k] = d0[k] - (c[k].wrapping_sub(cell[k])) as f32 + v as f32 * unskew;
+                dist2 += d[k] * d[k];
+            }}
+            let a = Self::RADIUS2 - dist2;
+            if a > 0.0 {
+                let a2 = a * a;
+                sum += a2 * a2 * grad(hash(self.seed, &c), &d);
+            }
+        }}
+        Float(sum * Self::SCALE[N - 1]).clamp(-1.0, 1.0).0
+    }
+}
+
+macro_rules! impl_signal_at {
+    ($($t:ty),+) => { $( impl_signal_at![@$t: 1, 2, 3, 4]; )+ };
+    (@$t:ty: $($n:literal),+) => { $(
+        impl SignalAt<[f32; $n]> for $t {
+            type Sample = f32;
+            fn at(&self, p: [f32; $n]) -> f32 { self.sample(p) }
+        }
+    )+ };
+}
+impl_signal_at![NoisePerlin, NoiseSimplex];
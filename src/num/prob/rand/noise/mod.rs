@@ -0,0 +1,43 @@
+// devela/src/num/prob/rand/noise/mod.rs
+//
+#![doc = crate::_DOC_NUM_PROB_RAND_NOISE!()] // private
+#![doc = crate::_doc!(modules: crate::num::prob::rand; noise)]
+#![doc = crate::_doc!(flat:"num")]
+#![doc = crate::_doc!(hr)]
+//!
+//! Coherent noise: seedable, stateless functions of space that vary smoothly.
+//!
+//! - [`NoiseValue`], [`NoisePerlin`], [`NoiseSimplex`] and [`NoiseWorley`]
+//!   are base noises in 1 to 4 dimensions.
+//! - [`NoiseFbm`], [`NoiseRidged`], [`NoiseTurbulence`] and [`NoiseWarp`]
+//!   combine any noise into fractal patterns.
+//!
+//! All of them implement [`SignalAt<[f32; N]>`][crate::SignalAt],
+//! so they compose with [`SignalMap`][crate::SignalMap],
+//! [`SignalZip`][crate::SignalZip] and the other signal adapters.
+//!
+//! Results only depend on the seed and the sampled point, and are computed
+//! with plain integer and floating-point arithmetic, so they are reproducible
+//! across platforms.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // hash, floor, fade, grad
+
+mod cellular; // NoiseWorley, NoiseWorleyMetric, NoiseWorleyOutput
+mod fractal; // NoiseFbm, NoiseRidged, NoiseTurbulence, NoiseWarp
+mod gradient; // NoisePerlin, NoiseSimplex
+mod value; // NoiseValue
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            cellular::*,
+            fractal::*,
+            gradient::*,
+            value::*,
+        };
+    }
+}
@@ -0,0 +1,206 @@
+// devela/src/num/prob/stats/_test.rs
+
+use crate::{
+    StatsBinScale, StatsBivariate, StatsEma, StatsHistogram, StatsMoments, StatsP2, StatsTDigest,
+    assert_approx_eq_all, is,
+};
+
+const DATA: [f64; 8] = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+const MOMENTS: StatsMoments<f64> = StatsMoments::<f64>::from_slice(&DATA);
+
+#[test]
+fn moments_basic() {
+    assert_eq![MOMENTS.count(), 8];
+    assert_eq![MOMENTS.mean(), Some(5.0)];
+    assert_approx_eq_all![tolerance: 1e-12, MOMENTS.variance().unwrap(), 4.0];
+    assert_approx_eq_all![tolerance: 1e-12, MOMENTS.sample_variance().unwrap(), 32.0 / 7.0];
+    assert_approx_eq_all![tolerance: 1e-12, MOMENTS.std_dev().unwrap(), 2.0];
+    assert_eq![(MOMENTS.min(), MOMENTS.max(), MOMENTS.range()), (Some(2.0), Some(9.0), Some(7.0))];
+    // reference values computed from the central moments m3 = 5.25, m4 = 44.5
+    assert_approx_eq_all![tolerance: 1e-12, MOMENTS.skewness().unwrap(), 0.65625];
+    assert_approx_eq_all![tolerance: 1e-12, MOMENTS.kurtosis().unwrap(), 44.5 / 16.0 - 3.0];
+}
+#[test]
+fn moments_empty_and_constant() {
+    let empty = StatsMoments::<f32>::new();
+    assert![empty.is_empty()];
+    assert_eq![(empty.mean(), empty.variance(), empty.min()), (None, None, None)];
+    let mut constant = StatsMoments::<f32>::new();
+    constant.push(3.0);
+    assert_eq![constant.sample_variance(), None];
+    constant.push(3.0);
+    assert_eq![(constant.variance(), constant.skewness()), (Some(0.0), None)];
+}
+#[test]
+fn moments_merge_matches_single_pass() {
+    let mut a = StatsMoments::<f64>::from_slice(&DATA[..3]);
+    let b = StatsMoments::<f64>::from_slice(&DATA[3..]);
+    a.merge(&b);
+    assert_eq![a.count(), MOMENTS.count()];
+    assert_approx_eq_all![tolerance: 1e-12, a.mean().unwrap(), MOMENTS.mean().unwrap()];
+    assert_approx_eq_all![tolerance: 1e-12, a.variance().unwrap(), MOMENTS.variance().unwrap()];
+    assert_approx_eq_all![tolerance: 1e-12, a.skewness().unwrap(), MOMENTS.skewness().unwrap()];
+    assert_approx_eq_all![tolerance: 1e-12, a.kurtosis().unwrap(), MOMENTS.kurtosis().unwrap()];
+    assert_eq![(a.min(), a.max()), (MOMENTS.min(), MOMENTS.max())];
+
+    let mut empty = StatsMoments::<f64>::new();
+    empty.merge(&b);
+    assert_eq![empty, b];
+}
+
+#[test]
+fn ema() {
+    assert![StatsEma::<f64>::new(0.0).is_none()];
+    assert![StatsEma::<f64>::new(1.5).is_none()];
+    assert_approx_eq_all![tolerance: 1e-12, StatsEma::<f64>::from_span(3).unwrap().alpha(), 0.5];
+    let mut ema = StatsEma::<f64>::new(0.5).unwrap();
+    assert_eq![ema.value(), None];
+    ema.push(10.0);
+    assert_eq![(ema.value(), ema.variance()), (Some(10.0), Some(0.0))];
+    ema.push(20.0);
+    assert_eq![(ema.value(), ema.variance()), (Some(15.0), Some(25.0))];
+    ema.reset();
+    assert_eq![ema.count(), 0];
+}
+
+#[test]
+fn p2_quantiles() {
+    let mut median = StatsP2::<f64>::new(0.5).unwrap();
+    assert_eq![median.quantile(), None];
+    median.push(3.0);
+    median.push(1.0);
+    median.push(2.0);
+    assert_eq![median.quantile(), Some(2.0)];
+    let mut p90 = StatsP2::<f64>::new(0.9).unwrap();
+    // a scrambled but deterministic permutation of 0..1000
+    for i in 0..1000_u32 {
+        let x = (i * 7919 % 1000) as f64;
+        median.push(x);
+        p90.push(x);
+    }
+    assert![(median.quantile().unwrap() - 500.0).abs() < 15.0];
+    assert![(p90.quantile().unwrap() - 900.0).abs() < 15.0];
+    assert![StatsP2::<f32>::new(-0.1).is_none()];
+}
+
+#[test]
+fn p2_ignores_nan() {
+    let mut clean = StatsP2::<f64>::new(0.5).unwrap();
+    let mut noisy = clean;
+    for i in 0..200_u32 {
+        let x = (i * 7919 % 200) as f64;
+        clean.push(x);
+        noisy.push(x);
+        is![i == 3 || i == 100, noisy.push(f64::NAN)];
+    }
+    assert_eq![noisy.count(), clean.count()];
+    assert_eq![noisy.quantile(), clean.quantile()];
+}
+
+#[test]
+fn tdigest_quantiles() {
+    let mut d = StatsTDigest::<f64, 50>::new();
+    assert_eq![d.quantile(0.5), None];
+    for i in 0..10_000_u32 {
+        d.push((i * 7919 % 10_000) as f64);
+    }
+    d.push(f64::NAN);
+    assert_eq![d.count(), 10_000];
+    assert_eq![(d.min(), d.max()), (Some(0.0), Some(9999.0))];
+    assert_eq![d.quantile(0.0), Some(0.0)];
+    assert_eq![d.quantile(1.0), Some(9999.0)];
+    assert![d.centroid_count() <= 50];
+    for q in [0.001, 0.01, 0.1, 0.5, 0.9, 0.99, 0.999] {
+        let expected = q * 9999.0;
+        let error = (d.quantile(q).unwrap() - expected).abs();
+        assert![error < 10_000.0 * 0.01, "q: {q}, error: {error}"];
+    }
+}
+#[test]
+fn tdigest_few_observations() {
+    let mut d = StatsTDigest::<f32, 4>::new();
+    d.push(5.0);
+    assert_eq![d.quantile(0.5), Some(5.0)];
+    d.push(1.0);
+    assert_eq![(d.quantile(0.0), d.quantile(1.0)), (Some(1.0), Some(5.0))];
+}
+
+#[test]
+fn histogram_linear() {
+    assert![StatsHistogram::<f64, 4>::new_linear(1.0, 1.0).is_none()];
+    assert![StatsHistogram::<f64, 0>::new_linear(0.0, 1.0).is_none()];
+    let mut h = StatsHistogram::<f64, 4>::new_linear(0.0, 8.0).unwrap();
+    for x in [-1.0, 0.0, 1.9, 2.0, 5.0, 7.99, 8.0, f64::NAN] {
+        h.push(x);
+    }
+    assert_eq![h.scale(), StatsBinScale::Linear];
+    assert_eq![h.counts(), &[2, 1, 1, 1]];
+    assert_eq![(h.underflow(), h.overflow(), h.total()), (1, 1, 7)];
+    assert_eq![h.bin_range(1), Some((2.0, 4.0))];
+    assert_eq![h.bin_range(4), None];
+    assert_eq![h.bin_index(6.0), Some(3)];
+    assert_eq![h.quantile(0.0), Some(0.0)];
+    assert_eq![h.quantile(0.4), Some(2.0)];
+    h.reset();
+    assert_eq![h.total(), 0];
+    assert_eq![h.quantile(0.5), None];
+}
+#[test]
+fn histogram_log() {
+    assert![StatsHistogram::<f64, 4>::new_log(0.0, 1.0).is_none()];
+    let mut h = StatsHistogram::<f64, 4>::new_log(1.0, 10_000.0).unwrap();
+    for x in [0.5, 1.0, 9.0, 11.0, 500.0, 9_999.0] {
+        h.push(x);
+    }
+    assert_eq![h.counts(), &[2, 1, 1, 1]];
+    assert_eq![h.underflow(), 1];
+    let (lo, hi) = h.bin_range(2).unwrap();
+    assert_approx_eq_all![tolerance: 1e-6, lo, 100.0];
+    assert_approx_eq_all![tolerance: 1e-6, hi, 1000.0];
+}
+
+#[test]
+fn bivariate() {
+    let mut b = StatsBivariate::<f64>::new();
+    assert_eq![b.correlation(), None];
+    for x in 0..10 {
+        b.push(x as f64, -2.0 * x as f64 + 5.0);
+    }
+    let fit = b.linear_fit().unwrap();
+    assert_approx_eq_all![tolerance: 1e-12, fit.slope, -2.0];
+    assert_approx_eq_all![tolerance: 1e-12, fit.intercept, 5.0];
+    assert_approx_eq_all![tolerance: 1e-12, fit.r_squared, 1.0];
+    assert_approx_eq_all![tolerance: 1e-12, fit.predict(20.0), -35.0];
+    assert_approx_eq_all![tolerance: 1e-12, b.correlation().unwrap(), -1.0];
+    assert_approx_eq_all![tolerance: 1e-12, b.covariance().unwrap(), -16.5];
+
+    let mut noisy = StatsBivariate::<f32>::new();
+    for (x, y) in [(1.0, 2.0), (2.0, 1.0), (3.0, 4.0), (4.0, 3.0)] {
+        noisy.push(x, y);
+    }
+    assert_approx_eq_all![tolerance: 1e-6, noisy.correlation().unwrap(), 0.6];
+}
+
+#[test]
+fn nan_is_ignored() {
+    let mut moments = StatsMoments::<f64>::new();
+    let mut ema = StatsEma::<f64>::new(0.5).unwrap();
+    let mut bivariate = StatsBivariate::<f64>::new();
+    let (mut clean_ema, mut clean_bivariate) = (ema, bivariate);
+    for (i, &x) in DATA.iter().enumerate() {
+        if i == 4 {
+            moments.push(f64::NAN);
+            ema.push(f64::NAN);
+            bivariate.push(f64::NAN, 1.0);
+            bivariate.push(1.0, f64::NAN);
+        }
+        moments.push(x);
+        ema.push(x);
+        bivariate.push(x, 2.0 * x);
+        clean_ema.push(x);
+        clean_bivariate.push(x, 2.0 * x);
+    }
+    assert_eq![moments, MOMENTS];
+    assert_eq![(ema, bivariate), (clean_ema, clean_bivariate)];
+    assert_eq![bivariate.count(), 8];
+}
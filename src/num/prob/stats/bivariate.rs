@@ -0,0 +1,132 @@
+// devela/src/num/prob/stats/bivariate.rs
+//
+//! Defines [`StatsBivariate`], [`StatsLinearFit`].
+//
+
+use crate::{Float, is};
+
+#[doc = crate::_tags!(num quant)]
+/// Single-pass covariance, correlation and linear regression of paired observations.
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+///
+/// # Example
+/// ```
+/// # use devela::StatsBivariate;
+/// let mut b = StatsBivariate::<f64>::new();
+/// for x in 0..10 { b.push(x as f64, 3.0 * x as f64 + 1.0); }
+/// let fit = b.linear_fit().unwrap();
+/// assert![(fit.slope - 3.0).abs() < 1e-12 && (fit.intercept - 1.0).abs() < 1e-12];
+/// assert![(b.correlation().unwrap() - 1.0).abs() < 1e-12];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatsBivariate<T> {
+    count: u64,
+    mean_x: T,
+    mean_y: T,
+    m2_x: T,
+    m2_y: T,
+    /// The sum of the products of the deviations from the means.
+    c_xy: T,
+}
+
+#[doc = crate::_tags!(num quant)]
+/// The least-squares line `y = slope · x + intercept` fitted by [`StatsBivariate`].
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatsLinearFit<T> {
+    /// The slope of the line.
+    pub slope: T,
+    /// The value of `y` at `x = 0`.
+    pub intercept: T,
+    /// The coefficient of determination (R²), in `[0, 1]`.
+    pub r_squared: T,
+}
+
+macro_rules! impl_bivariate {
+    () => { impl_bivariate![f32, f64]; };
+    ($($f:ty),+) => { $( impl_bivariate![@$f]; )+ };
+    (@$f:ty) => {
+        crate::_impl_init![Self::new() => StatsBivariate<$f>];
+
+        impl StatsBivariate<$f> {
+            /// Returns an empty accumulator.
+            pub const fn new() -> Self {
+                Self { count: 0, mean_x: 0.0, mean_y: 0.0, m2_x: 0.0, m2_y: 0.0, c_xy: 0.0 }
+            }
+
+            /// Clears all accumulated observations.
+            pub const fn reset(&mut self) { *self = Self::new(); }
+
+            /// Adds a new `(x, y)` observation.
+            ///
+            /// Observations with a `NaN` value are ignored.
+            pub const fn push(&mut self, x: $f, y: $f) {
+                is![x.is_nan() || y.is_nan(), return];
+                self.count += 1;
+                let n = self.count as $f;
+                let dx = x - self.mean_x;
+                self.mean_x += dx / n;
+                let dy = y - self.mean_y;
+                self.mean_y += dy / n;
+                self.m2_x += dx * (x - self.mean_x);
+                self.m2_y += dy * (y - self.mean_y);
+                self.c_xy += dx * (y - self.mean_y);
+            }
+
+            /// Returns the number of observations.
+            #[must_use]
+            pub const fn count(&self) -> u64 { self.count }
+
+            /// Returns the means of `x` and `y`, or `None` if empty.
+            #[must_use]
+            pub const fn mean(&self) -> Option<($f, $f)> {
+                is![self.count == 0, None, Some((self.mean_x, self.mean_y))]
+            }
+
+            /// Returns the population covariance, or `None` if empty.
+            #[must_use]
+            pub const fn covariance(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.c_xy / self.count as $f)]
+            }
+
+            /// Returns the sample covariance, or `None` with less than 2 observations.
+            #[must_use]
+            pub const fn sample_covariance(&self) -> Option<$f> {
+                is![self.count < 2, None, Some(self.c_xy / (self.count - 1) as $f)]
+            }
+
+            /// Returns the Pearson correlation coefficient, in `[-1, 1]`.
+            ///
+            /// Returns `None` if empty or if either variable is constant.
+            #[must_use]
+            pub const fn correlation(&self) -> Option<$f> {
+                is![self.count == 0 || self.m2_x == 0.0 || self.m2_y == 0.0, return None];
+                let r = self.c_xy / Float(self.m2_x * self.m2_y).sqrt_hybrid().0;
+                Some(Float(r).clamp(-1.0, 1.0).0)
+            }
+
+            /// Returns the least-squares fit of `y` over `x`.
+            ///
+            /// Returns `None` if empty or if `x` is constant.
+            #[must_use]
+            pub const fn linear_fit(&self) -> Option<StatsLinearFit<$f>> {
+                is![self.count == 0 || self.m2_x == 0.0, return None];
+                let slope = self.c_xy / self.m2_x;
+                let intercept = self.mean_y - slope * self.mean_x;
+                let r_squared = if self.m2_y == 0.0 {
+                    1.0
+                } else {
+                    Float(self.c_xy * self.c_xy / (self.m2_x * self.m2_y)).clamp(0.0, 1.0).0
+                };
+                Some(StatsLinearFit { slope, intercept, r_squared })
+            }
+        }
+
+        impl StatsLinearFit<$f> {
+            /// Returns the fitted `y` value at `x`.
+            #[must_use]
+            pub const fn predict(&self, x: $f) -> $f { self.slope * x + self.intercept }
+        }
+    };
+}
+impl_bivariate!();
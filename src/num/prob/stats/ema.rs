@@ -0,0 +1,107 @@
+// devela/src/num/prob/stats/ema.rs
+//
+//! Defines [`StatsEma`].
+//
+
+use crate::{Float, is};
+
+#[doc = crate::_tags!(num quant)]
+/// Exponential moving average and variance of a stream of observations.
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+///
+/// Each new observation `x` updates the average as `avg += alpha * (x - avg)`,
+/// so that larger smoothing factors (`alpha`) react faster to changes.
+///
+/// The first observation initializes the average directly.
+///
+/// # Example
+/// ```
+/// # use devela::StatsEma;
+/// let mut ema = StatsEma::<f32>::new(0.5).unwrap();
+/// ema.push(10.0);
+/// ema.push(20.0);
+/// assert_eq![ema.value(), Some(15.0)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatsEma<T> {
+    alpha: T,
+    value: T,
+    variance: T,
+    count: u64,
+}
+
+macro_rules! impl_ema {
+    () => { impl_ema![f32, f64]; };
+    ($($f:ty),+) => { $( impl_ema![@$f]; )+ };
+    (@$f:ty) => {
+        impl StatsEma<$f> {
+            /// Returns a new average with the given smoothing factor.
+            ///
+            /// Returns `None` if `alpha` is not in the range `(0, 1]`.
+            pub const fn new(alpha: $f) -> Option<Self> {
+                is![!(alpha > 0.0 && alpha <= 1.0), return None];
+                Some(Self { alpha, value: 0.0, variance: 0.0, count: 0 })
+            }
+
+            /// Returns a new average whose smoothing factor
+            /// corresponds to a simple moving average of `span` observations.
+            ///
+            /// The smoothing factor is `2 / (span + 1)`.
+            ///
+            /// Returns `None` if `span` is 0.
+            pub const fn from_span(span: u32) -> Option<Self> {
+                is![span == 0, return None];
+                Self::new(2.0 / (span as $f + 1.0))
+            }
+
+            /// Clears the accumulated state, keeping the smoothing factor.
+            pub const fn reset(&mut self) {
+                self.value = 0.0;
+                self.variance = 0.0;
+                self.count = 0;
+            }
+
+            /// Adds a new observation. `NaN` values are ignored.
+            pub const fn push(&mut self, x: $f) {
+                is![x.is_nan(), return];
+                if self.count == 0 {
+                    self.value = x;
+                } else {
+                    let diff = x - self.value;
+                    let incr = self.alpha * diff;
+                    self.value += incr;
+                    self.variance = (1.0 - self.alpha) * (self.variance + diff * incr);
+                }
+                self.count = self.count.saturating_add(1);
+            }
+
+            /// Returns the smoothing factor.
+            #[must_use]
+            pub const fn alpha(&self) -> $f { self.alpha }
+
+            /// Returns the number of observations, saturating at `u64::MAX`.
+            #[must_use]
+            pub const fn count(&self) -> u64 { self.count }
+
+            /// Returns the current average, or `None` if empty.
+            #[must_use]
+            pub const fn value(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.value)]
+            }
+
+            /// Returns the current exponentially weighted variance, or `None` if empty.
+            #[must_use]
+            pub const fn variance(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.variance)]
+            }
+
+            /// Returns the current exponentially weighted standard deviation,
+            /// or `None` if empty.
+            #[must_use]
+            pub const fn std_dev(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(Float(self.variance).sqrt_hybrid().0)]
+            }
+        }
+    };
+}
+impl_ema!();
@@ -0,0 +1,195 @@
+// devela/src/num/prob/stats/histogram.rs
+//
+//! Defines [`StatsHistogram`], [`StatsBinScale`].
+//
+
+use crate::{FloatExt, is};
+
+#[doc = crate::_tags!(num quant)]
+/// The spacing of the bins of a [`StatsHistogram`].
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StatsBinScale {
+    /// Bins of equal width.
+    #[default]
+    Linear,
+    /// Bins of equal width in logarithmic scale.
+    ///
+    /// Each bin spans the same ratio between its upper and lower edges.
+    Log,
+}
+
+#[doc = crate::_tags!(num quant)]
+/// A histogram of `N` fixed bins spanning a half-open range `[lo, hi)`.
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+///
+/// Observations below `lo` or at and above `hi` are counted
+/// separately as underflow and overflow. `NaN` values are ignored.
+///
+/// # Example
+/// ```
+/// # use devela::StatsHistogram;
+/// // frame times in seconds, from 1 ms to 1 s, in 3 decades.
+/// let mut h = StatsHistogram::<f64, 3>::new_log(0.001, 1.0).unwrap();
+/// for dt in [0.002, 0.016, 0.017, 0.033, 0.5, 2.0] { h.push(dt); }
+/// assert_eq![h.counts(), &[1, 3, 1]];
+/// assert_eq![h.overflow(), 1];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatsHistogram<T, const N: usize> {
+    counts: [u64; N],
+    under: u64,
+    over: u64,
+    lo: T,
+    hi: T,
+    scale: StatsBinScale,
+    /// The lower bound in the scaled domain.
+    scaled_lo: T,
+    /// The number of bins per scaled unit.
+    bins_per_unit: T,
+}
+
+macro_rules! impl_histogram {
+    () => { impl_histogram![f32, f64]; };
+    ($($f:ty),+) => { $( impl_histogram![@$f]; )+ };
+    (@$f:ty) => {
+        impl<const N: usize> StatsHistogram<$f, N> {
+            /// Returns a histogram with `N` bins of equal width over `[lo, hi)`.
+            ///
+            /// Returns `None` if `N` is 0, if the bounds are not finite, or if `lo >= hi`.
+            pub fn new_linear(lo: $f, hi: $f) -> Option<Self> {
+                is![N == 0 || !lo.is_finite() || !hi.is_finite() || lo >= hi, return None];
+                Some(Self::with_scale(lo, hi, StatsBinScale::Linear, lo, hi))
+            }
+
+            /// Returns a histogram with `N` logarithmically spaced bins over `[lo, hi)`.
+            ///
+            /// Returns `None` if `N` is 0, if the bounds are not finite,
+            /// if `lo` is not positive, or if `lo >= hi`.
+            pub fn new_log(lo: $f, hi: $f) -> Option<Self> {
+                is![N == 0 || lo.is_nan() || lo <= 0.0 || !hi.is_finite() || lo >= hi, return None];
+                Some(Self::with_scale(lo, hi, StatsBinScale::Log, FloatExt::ln(lo), FloatExt::ln(hi)))
+            }
+
+            fn with_scale(lo: $f, hi: $f, scale: StatsBinScale, s_lo: $f, s_hi: $f) -> Self {
+                Self {
+                    counts: [0; N], under: 0, over: 0, lo, hi, scale,
+                    scaled_lo: s_lo,
+                    bins_per_unit: N as $f / (s_hi - s_lo),
+                }
+            }
+
+            /// Clears all the counts, keeping the bins.
+            pub fn reset(&mut self) {
+                self.counts = [0; N];
+                self.under = 0;
+                self.over = 0;
+            }
+
+            /// Adds a new observation.
+            pub fn push(&mut self, x: $f) {
+                is![x.is_nan(), return];
+                if x < self.lo {
+                    self.under += 1;
+                } else if x >= self.hi {
+                    self.over += 1;
+                } else {
+                    let s = match self.scale {
+                        StatsBinScale::Linear => x,
+                        StatsBinScale::Log => FloatExt::ln(x),
+                    };
+                    let idx = FloatExt::floor((s - self.scaled_lo) * self.bins_per_unit) as usize;
+                    self.counts[idx.min(N - 1)] += 1;
+                }
+            }
+
+            /// Returns the bin spacing.
+            #[must_use]
+            pub const fn scale(&self) -> StatsBinScale { self.scale }
+
+            /// Returns the lower bound of the range.
+            #[must_use]
+            pub const fn lo(&self) -> $f { self.lo }
+
+            /// Returns the upper bound of the range.
+            #[must_use]
+            pub const fn hi(&self) -> $f { self.hi }
+
+            /// Returns the counts of every bin.
+            #[must_use]
+            pub const fn counts(&self) -> &[u64; N] { &self.counts }
+
+            /// Returns the number of observations below the range.
+            #[must_use]
+            pub const fn underflow(&self) -> u64 { self.under }
+
+            /// Returns the number of observations above the range.
+            #[must_use]
+            pub const fn overflow(&self) -> u64 { self.over }
+
+            /// Returns the number of observations, including underflow and overflow.
+            #[must_use]
+            pub const fn total(&self) -> u64 {
+                let mut sum = self.under + self.over;
+                let mut i = 0;
+                while i < N { sum += self.counts[i]; i += 1; }
+                sum
+            }
+
+            /// Returns the `[lower, upper)` edges of the bin at `index`,
+            /// or `None` if out of bounds.
+            #[must_use]
+            pub fn bin_range(&self, index: usize) -> Option<($f, $f)> {
+                is![index >= N, return None];
+                Some((self.edge(index), self.edge(index + 1)))
+            }
+
+            /// Returns the index of the bin that would count `x`,
+            /// or `None` if it's out of range or `NaN`.
+            #[must_use]
+            pub fn bin_index(&self, x: $f) -> Option<usize> {
+                is![x.is_nan() || x < self.lo || x >= self.hi, return None];
+                let s = match self.scale {
+                    StatsBinScale::Linear => x,
+                    StatsBinScale::Log => FloatExt::ln(x),
+                };
+                let idx = FloatExt::floor((s - self.scaled_lo) * self.bins_per_unit) as usize;
+                Some(idx.min(N - 1))
+            }
+
+            /// Returns the estimated value at the `q` quantile of the in-range observations,
+            /// interpolating uniformly inside each bin in the scaled domain.
+            ///
+            /// `q` is clamped to `[0, 1]`. Returns `None` if there are no in-range observations.
+            #[must_use]
+            pub fn quantile(&self, q: $f) -> Option<$f> {
+                let in_range = self.total() - self.under - self.over;
+                is![in_range == 0, return None];
+                let target = q.clamp(0.0, 1.0) * in_range as $f;
+                let mut cum = 0.0;
+                for (i, &c) in self.counts.iter().enumerate() {
+                    let c = c as $f;
+                    if c > 0.0 && cum + c >= target {
+                        let t = (target - cum) / c;
+                        let s = self.scaled_lo + (i as $f + t) / self.bins_per_unit;
+                        return Some(self.unscale(s));
+                    }
+                    cum += c;
+                }
+                Some(self.hi)
+            }
+
+            fn edge(&self, index: usize) -> $f {
+                is![index == N, return self.hi];
+                self.unscale(self.scaled_lo + index as $f / self.bins_per_unit)
+            }
+            fn unscale(&self, s: $f) -> $f {
+                match self.scale {
+                    StatsBinScale::Linear => s,
+                    StatsBinScale::Log => FloatExt::exp(s),
+                }
+            }
+        }
+    };
+}
+impl_histogram!();
@@ -0,0 +1,46 @@
+// devela/src/num/prob/stats/mod.rs
+//
+#![doc = crate::_DOC_NUM_PROB_STATS!()] // public
+#![doc = crate::_doc!(modules: crate::num::prob; stats)]
+#![doc = crate::_doc!(flat:"num")]
+#![doc = crate::_doc!(hr)]
+//!
+//! Single-pass estimators over streams of floating-point observations.
+//!
+//! Every estimator keeps its state in fixed storage, never allocates,
+//! and is usable in `no_std` environments.
+//! They are implemented for `f32` and `f64`.
+//!
+//! # Estimators
+//!
+//! - [`StatsMoments`] tracks count, mean, variance, skewness, kurtosis, min and max.
+//! - [`StatsEma`] tracks an exponential moving average and variance.
+//! - [`StatsP2`] estimates a single quantile with five markers.
+//! - [`StatsTDigest`] estimates arbitrary quantiles with bounded centroid storage.
+//! - [`StatsHistogram`] counts observations in linear or logarithmic bins.
+//! - [`StatsBivariate`] tracks covariance, correlation and a [`StatsLinearFit`].
+//!
+//! A typical use is frame-timing telemetry, where per-frame durations are
+//! pushed as seconds and summarized without retaining the whole history.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod bivariate; // StatsBivariate, StatsLinearFit
+mod ema; // StatsEma
+mod histogram; // StatsHistogram, StatsBinScale
+mod moments; // StatsMoments
+mod quantile; // StatsP2, StatsTDigest
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            bivariate::{StatsBivariate, StatsLinearFit},
+            ema::StatsEma,
+            histogram::{StatsBinScale, StatsHistogram},
+            moments::StatsMoments,
+            quantile::{StatsP2, StatsTDigest},
+        };
+    }
+}
@@ -0,0 +1,196 @@
+// devela/src/num/prob/stats/moments.rs
+//
+//! Defines [`StatsMoments`].
+//
+
+use crate::{Float, is};
+
+#[doc = crate::_tags!(num quant)]
+/// Single-pass central moments, extrema and count of a stream of observations.
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+///
+/// Uses Welford's update extended to the third and fourth central moments,
+/// which is numerically stable for long streams.
+/// Partial results from independent streams can be combined with [`merge`][Self::merge].
+///
+/// # Example
+/// ```
+/// # use devela::StatsMoments;
+/// let mut m = StatsMoments::<f64>::new();
+/// for x in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] { m.push(x); }
+/// assert_eq![m.count(), 8];
+/// assert_eq![m.mean(), Some(5.0)];
+/// assert![(m.variance().unwrap() - 4.0).abs() < 1e-12];
+/// assert![(m.std_dev().unwrap() - 2.0).abs() < 1e-12];
+/// assert_eq![(m.min(), m.max()), (Some(2.0), Some(9.0))];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatsMoments<T> {
+    count: u64,
+    mean: T,
+    m2: T,
+    m3: T,
+    m4: T,
+    min: T,
+    max: T,
+}
+
+macro_rules! impl_moments {
+    () => { impl_moments![f32, f64]; };
+    ($($f:ty),+) => { $( impl_moments![@$f]; )+ };
+    (@$f:ty) => {
+        crate::_impl_init![Self::new() => StatsMoments<$f>];
+
+        impl StatsMoments<$f> {
+            /// Returns an empty accumulator.
+            pub const fn new() -> Self {
+                Self { count: 0, mean: 0.0, m2: 0.0, m3: 0.0, m4: 0.0, min: 0.0, max: 0.0 }
+            }
+
+            /// Returns an accumulator over all the `values`.
+            pub const fn from_slice(values: &[$f]) -> Self {
+                let mut s = Self::new();
+                let mut i = 0;
+                while i < values.len() { s.push(values[i]); i += 1; }
+                s
+            }
+
+            /// Clears all accumulated observations.
+            pub const fn reset(&mut self) { *self = Self::new(); }
+
+            /// Adds a new observation. `NaN` values are ignored.
+            pub const fn push(&mut self, x: $f) {
+                is![x.is_nan(), return];
+                let n1 = self.count as $f;
+                self.count += 1;
+                let n = self.count as $f;
+                let delta = x - self.mean;
+                let delta_n = delta / n;
+                let delta_n2 = delta_n * delta_n;
+                let term1 = delta * delta_n * n1;
+                self.mean += delta_n;
+                self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0)
+                    + 6.0 * delta_n2 * self.m2 - 4.0 * delta_n * self.m3;
+                self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+                self.m2 += term1;
+                if self.count == 1 {
+                    self.min = x;
+                    self.max = x;
+                } else {
+                    is![x < self.min, self.min = x];
+                    is![x > self.max, self.max = x];
+                }
+            }
+
+            /// Combines the observations of `other` into `self`.
+            ///
+            /// The result is equivalent to having pushed both streams into one accumulator.
+            pub const fn merge(&mut self, other: &Self) {
+                if other.count == 0 { return; }
+                if self.count == 0 { *self = *other; return; }
+                let (na, nb) = (self.count as $f, other.count as $f);
+                let n = na + nb;
+                let delta = other.mean - self.mean;
+                let (d2, d3, d4) = (delta * delta, delta * delta * delta, delta * delta * delta * delta);
+
+                let m2 = self.m2 + other.m2 + d2 * na * nb / n;
+                let m3 = self.m3 + other.m3 + d3 * na * nb * (na - nb) / (n * n)
+                    + 3.0 * delta * (na * other.m2 - nb * self.m2) / n;
+                let m4 = self.m4 + other.m4
+                    + d4 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+                    + 6.0 * d2 * (na * na * other.m2 + nb * nb * self.m2) / (n * n)
+                    + 4.0 * delta * (na * other.m3 - nb * self.m3) / n;
+
+                self.mean += delta * nb / n;
+                self.m2 = m2;
+                self.m3 = m3;
+                self.m4 = m4;
+                self.count += other.count;
+                is![other.min < self.min, self.min = other.min];
+                is![other.max > self.max, self.max = other.max];
+            }
+
+            /// Returns the number of observations.
+            #[must_use]
+            pub const fn count(&self) -> u64 { self.count }
+
+            /// Returns `true` if there are no observations.
+            #[must_use]
+            pub const fn is_empty(&self) -> bool { self.count == 0 }
+
+            /// Returns the arithmetic mean, or `None` if empty.
+            #[must_use]
+            pub const fn mean(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.mean)]
+            }
+
+            /// Returns the smallest observation, or `None` if empty.
+            #[must_use]
+            pub const fn min(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.min)]
+            }
+
+            /// Returns the largest observation, or `None` if empty.
+            #[must_use]
+            pub const fn max(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.max)]
+            }
+
+            /// Returns the difference between the largest and smallest observations.
+            #[must_use]
+            pub const fn range(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.max - self.min)]
+            }
+
+            /// Returns the population variance, or `None` if empty.
+            #[must_use]
+            pub const fn variance(&self) -> Option<$f> {
+                is![self.count == 0, None, Some(self.m2 / self.count as $f)]
+            }
+
+            /// Returns the unbiased sample variance, or `None` with less than 2 observations.
+            #[must_use]
+            pub const fn sample_variance(&self) -> Option<$f> {
+                is![self.count < 2, None, Some(self.m2 / (self.count - 1) as $f)]
+            }
+
+            /// Returns the population standard deviation, or `None` if empty.
+            #[must_use]
+            pub const fn std_dev(&self) -> Option<$f> {
+                match self.variance() { Some(v) => Some(Float(v).sqrt_hybrid().0), None => None }
+            }
+
+            /// Returns the sample standard deviation,
+            /// or `None` with less than 2 observations.
+            #[must_use]
+            pub const fn sample_std_dev(&self) -> Option<$f> {
+                match self.sample_variance() {
+                    Some(v) => Some(Float(v).sqrt_hybrid().0),
+                    None => None,
+                }
+            }
+
+            /// Returns the population skewness.
+            ///
+            /// Returns `None` if empty or if all the observations are equal.
+            #[must_use]
+            pub const fn skewness(&self) -> Option<$f> {
+                if self.count == 0 || self.m2 == 0.0 { return None; }
+                let n = self.count as $f;
+                let m2_3_2 = self.m2 * Float(self.m2).sqrt_hybrid().0;
+                Some(Float(n).sqrt_hybrid().0 * self.m3 / m2_3_2)
+            }
+
+            /// Returns the population excess kurtosis (0 for a normal distribution).
+            ///
+            /// Returns `None` if empty or if all the observations are equal.
+            #[must_use]
+            pub const fn kurtosis(&self) -> Option<$f> {
+                if self.count == 0 || self.m2 == 0.0 { return None; }
+                let n = self.count as $f;
+                Some(n * self.m4 / (self.m2 * self.m2) - 3.0)
+            }
+        }
+    };
+}
+impl_moments!();
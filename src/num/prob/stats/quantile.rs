@@ -0,0 +1,359 @@
+// devela/src/num/prob/stats/quantile.rs
+//
+//! Defines [`StatsP2`], [`StatsTDigest`].
+//
+
+use crate::{Float, is, whilst};
+
+#[doc = crate::_tags!(num quant)]
+/// Streaming estimator of a single quantile using the P² algorithm.
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+///
+/// It keeps five markers whose heights are adjusted with piecewise-parabolic
+/// interpolation, so its memory use is constant regardless of the stream length.
+///
+/// See: Jain, R. and Chlamtac, I. (1985). *The P² algorithm for dynamic calculation
+/// of quantiles and histograms without storing observations*.
+///
+/// # Example
+/// ```
+/// # use devela::StatsP2;
+/// let mut median = StatsP2::<f64>::new(0.5).unwrap();
+/// for x in 1..=101 { median.push(x as f64); }
+/// assert_eq![median.quantile(), Some(51.0)];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatsP2<T> {
+    p: T,
+    /// Marker heights.
+    q: [T; 5],
+    /// Actual marker positions.
+    n: [T; 5],
+    /// Desired marker positions.
+    np: [T; 5],
+    /// Desired position increments.
+    dn: [T; 5],
+    count: u64,
+}
+
+#[doc = crate::_tags!(num quant)]
+/// Streaming estimator of arbitrary quantiles using a merging t-digest.
+#[doc = crate::_doc_meta!{location("num/prob/stats")}]
+///
+/// Observations are buffered and periodically merged into at most `N` weighted
+/// centroids, which are kept small near the tails, where quantiles are most sensitive.
+/// The compression parameter equals `N`.
+///
+/// See: Dunning, T. and Ertl, O. (2019). *Computing extremely accurate quantiles
+/// using t-digests*.
+///
+/// # Example
+/// ```
+/// # use devela::StatsTDigest;
+/// let mut digest = StatsTDigest::<f64, 32>::new();
+/// for x in 0..1000 { digest.push(x as f64); }
+/// let p99 = digest.quantile(0.99).unwrap();
+/// assert![(p99 - 989.5).abs() < 5.0];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatsTDigest<T, const N: usize> {
+    means: [T; N],
+    weights: [T; N],
+    len: usize,
+    buf: [T; N],
+    buf_len: usize,
+    /// The total weight of the merged centroids.
+    total: T,
+    count: u64,
+    min: T,
+    max: T,
+    /// The sine and cosine of the maximum angular span of a centroid in the `k₁` scale.
+    sin_span: T,
+    cos_span: T,
+}
+
+macro_rules! impl_quantile {
+    () => { impl_quantile![f32, f64]; };
+    ($($f:ty),+) => { $( impl_quantile![@$f]; )+ };
+    (@$f:ty) => {
+        /* StatsP2 */
+
+        impl StatsP2<$f> {
+            /// Returns an estimator of the `p` quantile (e.g. `0.5` for the median).
+            ///
+            /// Returns `None` if `p` is not in the range `[0, 1]`.
+            pub const fn new(p: $f) -> Option<Self> {
+                is![!(p >= 0.0 && p <= 1.0), return None];
+                Some(Self {
+                    p,
+                    q: [0.0; 5],
+                    n: [1.0, 2.0, 3.0, 4.0, 5.0],
+                    np: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+                    dn: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+                    count: 0,
+                })
+            }
+
+            /// Returns the estimated probability level.
+            #[must_use]
+            pub const fn p(&self) -> $f { self.p }
+
+            /// Returns the number of observations.
+            #[must_use]
+            pub const fn count(&self) -> u64 { self.count }
+
+            /// Adds a new observation. `NaN` values are ignored.
+            pub const fn push(&mut self, x: $f) {
+                is![x.is_nan(), return];
+                if self.count < 5 {
+                    self.q[self.count as usize] = x;
+                    self.count += 1;
+                    is![self.count == 5, Self::sort(&mut self.q, 5)];
+                    return;
+                }
+                self.count += 1;
+                let q = &mut self.q;
+                let k = if x < q[0] {
+                    q[0] = x; 0
+                } else if x < q[1] { 0
+                } else if x < q[2] { 1
+                } else if x < q[3] { 2
+                } else if x <= q[4] { 3
+                } else {
+                    q[4] = x; 3
+                };
+                whilst! { i in k + 1, ..5; { self.n[i] += 1.0; }}
+                whilst! { i in 0..5; { self.np[i] += self.dn[i]; }}
+
+                whilst! { i in 1..4; {
+                    let d = self.np[i] - self.n[i];
+                    if (d >= 1.0 && self.n[i + 1] - self.n[i] > 1.0)
+                        || (d <= -1.0 && self.n[i - 1] - self.n[i] < -1.0) {
+                        let s: $f = is![d >= 0.0, 1.0, -1.0];
+                        let qp = self.parabolic(i, s);
+                        self.q[i] = if self.q[i - 1] < qp && qp < self.q[i + 1] {
+                            qp
+                        } else {
+                            self.linear(i, s)
+                        };
+                        self.n[i] += s;
+                    }
+                }}
+            }
+
+            /// Returns the current estimate, or `None` if empty.
+            ///
+            /// With less than five observations the exact nearest-rank quantile is returned.
+            #[must_use]
+            pub const fn quantile(&self) -> Option<$f> {
+                match self.count {
+                    0 => None,
+                    1..5 => {
+                        let len = self.count as usize;
+                        let mut sorted = self.q;
+                        Self::sort(&mut sorted, len);
+                        let idx = Float(self.p * (len - 1) as $f).const_round().0 as usize;
+                        Some(sorted[idx])
+                    }
+                    _ => Some(self.q[2]),
+                }
+            }
+
+            const fn parabolic(&self, i: usize, d: $f) -> $f {
+                let (q, n) = (&self.q, &self.n);
+                q[i] + d / (n[i + 1] - n[i - 1])
+                    * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                        + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+            }
+            const fn linear(&self, i: usize, d: $f) -> $f {
+                let j = is![d > 0.0, i + 1, i - 1];
+                self.q[i] + d * (self.q[j] - self.q[i]) / (self.n[j] - self.n[i])
+            }
+            const fn sort(values: &mut [$f; 5], len: usize) {
+                whilst! { i in 1..len; {
+                    let mut j = i;
+                    while j > 0 && values[j - 1] > values[j] {
+                        values.swap(j - 1, j);
+                        j -= 1;
+                    }
+                }}
+            }
+        }
+
+        /* StatsTDigest */
+
+        impl<const N: usize> StatsTDigest<$f, N> {
+            /// Returns an empty digest.
+            ///
+            /// # Panics
+            /// Fails to compile if `N < 2`.
+            pub const fn new() -> Self {
+                const { assert![N >= 2, "StatsTDigest needs at least 2 centroids"]; }
+                let span = 2.0 * Float::<$f>::PI.0 / N as $f;
+                let (sin, cos) = Float(span).sin_cos_series(16);
+                Self {
+                    means: [0.0; N],
+                    weights: [0.0; N],
+                    len: 0,
+                    buf: [0.0; N],
+                    buf_len: 0,
+                    total: 0.0,
+                    count: 0,
+                    min: 0.0,
+                    max: 0.0,
+                    sin_span: sin.0,
+                    cos_span: cos.0,
+                }
+            }
+
+            /// Clears all accumulated observations.
+            pub const fn reset(&mut self) {
+                self.len = 0;
+                self.buf_len = 0;
+                self.total = 0.0;
+                self.count = 0;
+            }
+
+            /// Adds a new observation. `NaN` values are ignored.
+            pub const fn push(&mut self, x: $f) {
+                is![x.is_nan(), return];
+                if self.count == 0 {
+                    self.min = x;
+                    self.max = x;
+                } else {
+                    is![x < self.min, self.min = x];
+                    is![x > self.max, self.max = x];
+                }
+                self.count += 1;
+                self.buf[self.buf_len] = x;
+                self.buf_len += 1;
+                is![self.buf_len == N, self.flush()];
+            }
+
+            /// Returns the number of observations.
+            #[must_use]
+            pub const fn count(&self) -> u64 { self.count }
+
+            /// Returns `true` if there are no observations.
+            #[must_use]
+            pub const fn is_empty(&self) -> bool { self.count == 0 }
+
+            /// Returns the smallest observation, or `None` if empty.
+            #[must_use]
+            pub const fn min(&self) -> Option<$f> { is![self.count == 0, None, Some(self.min)] }
+
+            /// Returns the largest observation, or `None` if empty.
+            #[must_use]
+            pub const fn max(&self) -> Option<$f> { is![self.count == 0, None, Some(self.max)] }
+
+            /// Returns the number of merged centroids.
+            ///
+            /// This doesn't include buffered observations pending to be merged.
+            #[must_use]
+            pub const fn centroid_count(&self) -> usize { self.len }
+
+            /// Returns the estimated value at the `q` quantile, or `None` if empty.
+            ///
+            /// `q` is clamped to `[0, 1]`. Pending observations are merged first.
+            pub const fn quantile(&mut self, q: $f) -> Option<$f> {
+                is![self.buf_len > 0, self.flush()];
+                is![self.len == 0, return None];
+                let q = Float(q).clamp(0.0, 1.0).0;
+                let (means, weights, len) = (&self.means, &self.weights, self.len);
+                let index = q * self.total;
+
+                let first_half = weights[0] / 2.0;
+                if index < first_half {
+                    return Some(self.min + (means[0] - self.min) * index / first_half);
+                }
+                let mut cum = first_half;
+                whilst! { i in 0..(len - 1); {
+                    let dw = (weights[i] + weights[i + 1]) / 2.0;
+                    if cum + dw > index {
+                        let t = (index - cum) / dw;
+                        return Some(means[i] + (means[i + 1] - means[i]) * t);
+                    }
+                    cum += dw;
+                }}
+                let last = len - 1;
+                let t = Float((index - cum) / (weights[last] / 2.0)).clamp(0.0, 1.0).0;
+                Some(means[last] + (self.max - means[last]) * t)
+            }
+
+            /// Merges the buffered observations into the centroids.
+            pub const fn flush(&mut self) {
+                is![self.buf_len == 0, return];
+                Self::sort(&mut self.buf, self.buf_len);
+
+                let w_total = self.total + self.buf_len as $f;
+                let (mut out_means, mut out_weights) = ([0.0; N], [0.0; N]);
+                let mut out_len = 0;
+                // cursors over the sorted centroids and the sorted buffer
+                let (mut i, mut j) = (0, 0);
+                let (mut cur_m, mut cur_w) = self.next_item(&mut i, &mut j);
+                let mut cum = 0.0;
+                let mut limit = self.q_limit(0.0);
+
+                while i < self.len || j < self.buf_len {
+                    let (m, w) = self.next_item(&mut i, &mut j);
+                    if (cum + cur_w + w) / w_total <= limit || out_len == N - 1 {
+                        cur_w += w;
+                        cur_m += (m - cur_m) * w / cur_w;
+                    } else {
+                        out_means[out_len] = cur_m;
+                        out_weights[out_len] = cur_w;
+                        out_len += 1;
+                        cum += cur_w;
+                        limit = self.q_limit(cum / w_total);
+                        cur_m = m;
+                        cur_w = w;
+                    }
+                }
+                out_means[out_len] = cur_m;
+                out_weights[out_len] = cur_w;
+
+                self.means = out_means;
+                self.weights = out_weights;
+                self.len = out_len + 1;
+                self.total = w_total;
+                self.buf_len = 0;
+            }
+
+            /// Returns the next item in ascending order of mean, advancing its cursor.
+            const fn next_item(&self, i: &mut usize, j: &mut usize) -> ($f, $f) {
+                if *j >= self.buf_len || (*i < self.len && self.means[*i] <= self.buf[*j]) {
+                    *i += 1;
+                    (self.means[*i - 1], self.weights[*i - 1])
+                } else {
+                    *j += 1;
+                    (self.buf[*j - 1], 1.0)
+                }
+            }
+
+            /// Returns the upper quantile bound of a centroid starting at quantile `q0`.
+            ///
+            /// In the `k₁` scale, `k(q) = N/2π · asin(2q - 1)`, a centroid spans at most
+            /// one unit, so the bound is `(sin(asin(2q0 - 1) + 2π/N) + 1) / 2`.
+            const fn q_limit(&self, q0: $f) -> $f {
+                let s = 2.0 * q0 - 1.0;
+                is![s >= self.cos_span, return 1.0];
+                let c = 2.0 * Float(Float(q0 * (1.0 - q0)).max(0.0).0).sqrt_hybrid().0;
+                (s * self.cos_span + c * self.sin_span + 1.0) / 2.0
+            }
+
+            const fn sort(values: &mut [$f; N], len: usize) {
+                whilst! { i in 1..len; {
+                    let mut j = i;
+                    while j > 0 && values[j - 1] > values[j] {
+                        values.swap(j - 1, j);
+                        j -= 1;
+                    }
+                }}
+            }
+        }
+        impl<const N: usize> Default for StatsTDigest<$f, N> {
+            fn default() -> Self { Self::new() }
+        }
+    };
+}
+impl_quantile!();
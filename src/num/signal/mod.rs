@@ -11,6 +11,7 @@ mod emit; // Signal emitters and produced streams
 // mod filter; // Domain-generic filtering and smoothing
 mod phase; // Phase, cycles, and periodic progression
 mod sample; // Sampling, stepping, and discrete realization
+mod spectral; // Fourier transforms, windows, and spectral analysis
 mod transform; // Signal mapping, modulation, and transformation
 // mod weave; // Signal weaving, composition, and interconnection
 
@@ -22,6 +23,7 @@ crate::structural_mods! { // _mods, _pub_mods
             // filter::_all::*,
             phase::{Phase, PhaseStep, PhaseAccum},
             sample::{SignalAt},
+            spectral::_all::*,
             transform::{SignalClamp, SignalMap, SignalScale, SignalZip},
             // weave::_all::*,
         };
@@ -3,7 +3,7 @@
 //!
 //
 
-use crate::SignalNext;
+use crate::{Float, SignalNext, is};
 
 #[doc = crate::_tags!(num signal wave primitive)]
 /// A normalized cycle phase represented as wrapping `u32`.
@@ -59,6 +59,17 @@ impl PhaseStep {
     pub const fn raw(self) -> u32 {
         self.0
     }
+
+    /// Creates the step that cycles `freq` times per second at `sample_rate`.
+    ///
+    /// Negative frequencies step backwards, and a zero sample rate gives a zero step.
+    pub const fn from_freq(freq: f32, sample_rate: u32) -> Self {
+        is![sample_rate == 0, return Self::ZERO];
+        let turns = Float(freq as f64 / sample_rate as f64).rem_euclid(1.0).0;
+        Self((turns * (u32::MAX as f64 + 1.0)) as u64 as u32)
+    }
+    /// Returns this step as normalized `f32` turns in `[0, 1)`.
+    pub const fn as_f32(self) -> f32 { self.0 as f32 * (1.0 / (u32::MAX as f32 + 1.0)) }
 }
 
 #[doc = crate::_tags!(num signal wave)]
@@ -0,0 +1,43 @@
+// devela/src/num/signal/spectral/_helper.rs
+//
+//! Shared trigonometric helpers.
+//
+
+use crate::{Float, FloatConst};
+
+/// The number of Taylor terms, exact to `f64` precision within `[0, π/4]`.
+const TERMS: u32 = 11;
+
+/// Returns the cosine and sine of the angle `2π·k/n`, for `k < n`.
+///
+/// Reduces the angle to the first octant using exact integer arithmetic,
+/// so that the values are correctly rounded for any `n`.
+pub(super) const fn turn_cos_sin(k: usize, n: usize) -> (f64, f64) {
+    let eighths = k as u128 * 8;
+    let (octant, rem) = ((eighths / n as u128) as u8, (eighths % n as u128) as f64);
+    let n = n as f64;
+    if octant % 2 == 0 {
+        let (s, c) = octant_sin_cos(rem / n);
+        match octant {
+            0 => (c, s),
+            2 => (-s, c),
+            4 => (-c, -s),
+            _ => (s, -c),
+        }
+    } else {
+        // Measures the angle backwards from the next multiple of π/2.
+        let (s, c) = octant_sin_cos((n - rem) / n);
+        match octant {
+            1 => (s, c),
+            3 => (-c, s),
+            5 => (-s, -c),
+            _ => (c, -s),
+        }
+    }
+}
+
+/// Returns the sine and cosine of `fraction · π/4`, for `fraction` in `[0, 1]`.
+const fn octant_sin_cos(fraction: f64) -> (f64, f64) {
+    let x = Float(fraction * f64::FRAC_PI_4);
+    (x.sin_series(TERMS).0, x.cos_series(TERMS).0)
+}
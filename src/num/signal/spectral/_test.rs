@@ -0,0 +1,244 @@
+// devela/src/num/signal/spectral/_test.rs
+//
+// TOC
+// - helpers
+// - mod complex
+// - mod real
+// - mod window
+// - mod stft
+
+#![allow(clippy::float_cmp, reason = "the expected values are exact")]
+
+use super::*;
+use crate::Complex;
+
+/* helpers */
+
+/// A deterministic pseudo-random signal in `[-1, 1)`.
+fn noise(n: usize, seed: u32) -> impl Iterator<Item = f64> {
+    let mut state = seed;
+    (0..n).map(move |_| {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (state >> 8) as f64 / (1 << 23) as f64 - 1.0
+    })
+}
+
+/// Computes the forward transform of `input` by its definition.
+fn naive_dft(input: &[Complex<f64>], out: &mut [Complex<f64>]) {
+    let n = input.len();
+    for (k, x) in out.iter_mut().enumerate() {
+        *x = Complex::<f64>::ZERO;
+        for (t, s) in input.iter().enumerate() {
+            let angle = -2.0 * core::f64::consts::PI * ((k * t) % n) as f64 / n as f64;
+            *x += s.mul(Complex::new(angle.cos(), angle.sin()));
+        }
+    }
+}
+
+fn assert_close(a: &[Complex<f64>], b: &[Complex<f64>], tolerance: f64) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!(x.sub(*y).norm() < tolerance, "at {i}: {x:?} != {y:?}");
+    }
+}
+
+/* complex transforms */
+
+mod complex {
+    use super::*;
+
+    #[test]
+    fn matches_the_definition() {
+        let mut table = [Complex::<f64>::ZERO; 1000];
+        for n in [1, 2, 3, 4, 5, 6, 7, 8, 9, 12, 15, 16, 30, 31, 49, 60, 62, 64, 120, 256, 1000] {
+            let fft = Fft::<f64, _>::with_table(&mut table[..n]).unwrap();
+            let input: [_; 1000] = {
+                let mut noise = noise(2000, n as u32);
+                core::array::from_fn(|_| Complex::new(noise.next().unwrap(), noise.next().unwrap()))
+            };
+            let mut expected = [Complex::<f64>::ZERO; 1000];
+            naive_dft(&input[..n], &mut expected[..n]);
+            let mut data = input;
+            fft.forward(&mut data[..n]).unwrap();
+            assert_close(&data[..n], &expected[..n], 1e-10 * n as f64);
+            fft.inverse(&mut data[..n]).unwrap();
+            assert_close(&data[..n], &input[..n], 1e-12 * n as f64);
+        }
+    }
+    #[test]
+    fn twiddles_are_exact_at_quarter_turns() {
+        const FFT: Fft<f32, [Complex<f32>; 8]> = crate::unwrap![ok Fft::<f32, _>::new()];
+        let twiddles = FFT.twiddles();
+        assert_eq!(twiddles[0], Complex::new(1.0, 0.0));
+        assert_eq!(twiddles[2].re.abs(), 0.0);
+        assert_eq!(twiddles[2].im, -1.0);
+        assert_eq!(twiddles[4].re, -1.0);
+        assert_eq!(twiddles[1].re, core::f32::consts::FRAC_1_SQRT_2);
+    }
+    #[test]
+    fn transforms_f32() {
+        let fft = Fft::<f32, [Complex<f32>; 12]>::new().unwrap();
+        let mut data = [Complex::<f32>::ZERO; 12];
+        data[1] = Complex::<f32>::ONE;
+        fft.forward(&mut data).unwrap();
+        // A delayed impulse has unit magnitude at every bin.
+        assert!(data.iter().all(|c| (c.norm() - 1.0).abs() < 1e-6));
+        fft.inverse(&mut data).unwrap();
+        assert!((data[1].re - 1.0).abs() < 1e-6 && data[0].norm() < 1e-6);
+    }
+    #[test]
+    fn rejects_invalid_lengths() {
+        assert_eq!(Fft::<f32, [Complex<f32>; 0]>::new().err(), Some(FftError::InvalidLength));
+        assert_eq!(Fft::<f32, [Complex<f32>; 37]>::new().err(), Some(FftError::UnsupportedLength));
+        assert_eq!(Fft::<f64, [Complex<f64>; 74]>::new().err(), Some(FftError::UnsupportedLength));
+        let fft = Fft::<f32, [Complex<f32>; 4]>::new().unwrap();
+        let mut data = [Complex::<f32>::ZERO; 3];
+        assert_eq!(fft.forward(&mut data), Err(FftError::MismatchedLength));
+    }
+}
+
+/* real transforms */
+
+mod real {
+    use super::*;
+
+    #[test]
+    fn matches_the_complex_transform() {
+        let mut table = [Complex::<f64>::ZERO; 480];
+        for n in [2, 4, 6, 8, 10, 14, 16, 18, 60, 64, 128, 480] {
+            let fft = FftReal::<f64, _>::with_table(&mut table[..n]).unwrap();
+            assert_eq!(fft.bins(), n / 2 + 1);
+            let mut input = [0.0; 480];
+            input.iter_mut().zip(noise(n, n as u32)).for_each(|(x, s)| *x = s);
+            let mut expected = [Complex::<f64>::ZERO; 480];
+            let complex = input.map(Complex::<f64>::from_re);
+            naive_dft(&complex[..n], &mut expected[..n]);
+
+            let mut spectrum = [Complex::<f64>::ZERO; 241];
+            fft.forward(&input[..n], &mut spectrum).unwrap();
+            assert_close(&spectrum[..n / 2 + 1], &expected[..n / 2 + 1], 1e-10 * n as f64);
+
+            let mut output = [0.0; 480];
+            fft.inverse(&mut spectrum, &mut output[..n]).unwrap();
+            for (x, y) in output[..n].iter().zip(&input[..n]) {
+                assert!((x - y).abs() < 1e-12 * n as f64);
+            }
+        }
+    }
+    #[test]
+    fn rejects_invalid_lengths() {
+        assert_eq!(FftReal::<f32, [Complex<f32>; 6]>::new().map(|f| f.size()), Ok(6));
+        assert_eq!(FftReal::<f32, [Complex<f32>; 7]>::new().err(), Some(FftError::InvalidLength));
+        assert_eq!(FftReal::<f32, [Complex<f32>; 0]>::new().err(), Some(FftError::InvalidLength));
+        let fft = FftReal::<f32, [Complex<f32>; 8]>::new().unwrap();
+        let mut out = [Complex::<f32>::ZERO; 4];
+        assert_eq!(fft.forward(&[0.0; 8], &mut out), Err(FftError::MismatchedLength));
+    }
+    #[test]
+    fn convolves_and_correlates() {
+        let fft = FftReal::<f64, [Complex<f64>; 16]>::new().unwrap();
+        let (a, b) = ([1.0, 2.0, 3.0, 4.0, 5.0], [1.0, 0.0, -1.0]);
+        let mut scratch = [Complex::<f64>::ZERO; 18];
+        let mut out = [0.0; 8];
+
+        let len = fft.convolve(&a, &b, &mut out, &mut scratch).unwrap();
+        assert_eq!(len, 7);
+        let expected = [1.0, 2.0, 2.0, 2.0, 2.0, -4.0, -5.0];
+        assert!(out[..len].iter().zip(expected).all(|(x, e)| (x - e).abs() < 1e-12));
+
+        let len = fft.correlate(&a, &b, &mut out, &mut scratch).unwrap();
+        assert_eq!(len, 7);
+        let expected = [-1.0, -2.0, -2.0, -2.0, -2.0, 4.0, 5.0];
+        assert!(out[..len].iter().zip(expected).all(|(x, e)| (x - e).abs() < 1e-12));
+
+        // The autocorrelation peaks at lag zero.
+        let err = fft.correlate(&a, &a, &mut out, &mut scratch);
+        assert_eq!(err, Err(FftError::MismatchedLength));
+        let mut long = [0.0; 9];
+        let len = fft.correlate(&a, &a, &mut long, &mut scratch).unwrap();
+        assert_eq!(len, 9);
+        assert!((long[4] - 55.0).abs() < 1e-12);
+
+        assert_eq!(fft.convolve(&a, &[], &mut out, &mut scratch), Ok(0));
+        assert_eq!(
+            fft.convolve(&[0.0; 10], &b, &mut long, &mut scratch),
+            Err(FftError::MismatchedLength)
+        );
+        assert_eq!(
+            fft.convolve(&a, &b, &mut out, &mut scratch[..17]),
+            Err(FftError::MismatchedLength)
+        );
+    }
+}
+
+/* windows */
+
+mod window {
+    use super::*;
+
+    #[test]
+    fn computes_periodic_windows() {
+        let mut w = [0.0_f64; 8];
+        SpectralWindow::Hamming.fill_f64(&mut w);
+        assert!((w[0] - 0.08).abs() < 1e-15 && (w[4] - 1.0).abs() < 1e-15);
+        assert!((w[2] - 0.54).abs() < 1e-15 && (w[1] - w[7]).abs() < 1e-15);
+
+        SpectralWindow::BlackmanHarris.fill_f64(&mut w);
+        assert!((w[0] - 6e-5).abs() < 1e-12 && (w[4] - 1.0).abs() < 1e-12);
+
+        let mut frame = [2.0_f32; 4];
+        SpectralWindow::Rectangular.apply_f32(&mut frame);
+        assert_eq!(frame, [2.0; 4]);
+        SpectralWindow::Hann.apply_f32(&mut frame);
+        assert_eq!(frame, [0.0, 1.0, 2.0, 1.0]);
+        assert_eq!(SpectralWindow::Hann.at(4, 4), 0.0);
+    }
+    #[test]
+    fn coherent_gain_is_the_mean() {
+        for window in [
+            SpectralWindow::Rectangular,
+            SpectralWindow::Hann,
+            SpectralWindow::Hamming,
+            SpectralWindow::BlackmanHarris,
+        ] {
+            let sum: f64 = (0..64).map(|n| window.at(n, 64)).sum();
+            assert!((sum / 64.0 - window.coherent_gain()).abs() < 1e-12);
+        }
+    }
+}
+
+/* short-time transforms */
+
+mod stft {
+    use super::*;
+
+    #[test]
+    fn transforms_windowed_frames() {
+        let fft = FftReal::<f64, [Complex<f64>; 8]>::new().unwrap();
+        let mut window = [0.0; 8];
+        SpectralWindow::Hann.fill_f64(&mut window);
+        let stft = Stft::new(fft, window, 4).unwrap();
+        assert_eq!((stft.frame_len(), stft.hop()), (8, 4));
+        assert_eq!([0, 1, 8, 9, 12, 13].map(|n| stft.frames(n)), [0, 1, 1, 2, 2, 3]);
+
+        let signal: [f64; 13] = core::array::from_fn(|i| i as f64);
+        let mut frame = [Complex::<f64>::ZERO; 5];
+        stft.frame(&signal, 2, &mut frame).unwrap();
+        let mut expected = [Complex::<f64>::ZERO; 8];
+        let windowed: [_; 8] = core::array::from_fn(|i| {
+            Complex::<f64>::from_re(signal.get(8 + i).copied().unwrap_or(0.0) * window[i])
+        });
+        naive_dft(&windowed, &mut expected);
+        assert_close(&frame, &expected[..5], 1e-12);
+
+        let mut power = [0.0; 5];
+        stft.power(&signal, 2, &mut power, &mut frame).unwrap();
+        assert!((power[1] - expected[1].norm_sqr()).abs() < 1e-10);
+    }
+    #[test]
+    fn rejects_invalid_parameters() {
+        let fft = FftReal::<f32, [Complex<f32>; 8]>::new().unwrap();
+        assert_eq!(Stft::new(fft, [1.0; 8], 0).err(), Some(FftError::InvalidLength));
+        assert_eq!(Stft::new(fft, [1.0; 4], 2).err(), Some(FftError::MismatchedLength));
+    }
+}
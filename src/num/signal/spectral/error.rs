@@ -0,0 +1,22 @@
+// devela/src/num/signal/spectral/error.rs
+//
+//! Defines [`FftError`].
+//
+
+#[doc = crate::_tags!(num signal error)]
+/// Error from planning or running a Fourier transform.
+#[doc = crate::_doc_meta!{location("num/signal"), test_size_of(FftError = 1|8)}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FftError {
+    /// The transform length is zero, or odd for a real transform.
+    InvalidLength,
+    /// The transform length has a prime factor larger than 31.
+    UnsupportedLength,
+    /// A buffer length doesn't match the transform length.
+    MismatchedLength,
+}
+crate::impl_trait![fmt::Display+Error for FftError |self, f| match self {
+    Self::InvalidLength => f.write_str("invalid transform length"),
+    Self::UnsupportedLength => f.write_str("transform length has a prime factor above 31"),
+    Self::MismatchedLength => f.write_str("buffer length doesn't match the transform length"),
+}];
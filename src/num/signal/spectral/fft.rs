@@ -0,0 +1,288 @@
+// devela/src/num/signal/spectral/fft.rs
+//
+//! Defines [`Fft`].
+//
+// TOC
+// - struct Fft
+// - struct FftFactors
+// - transform functions
+
+use super::_helper::turn_cos_sin;
+use crate::{Complex, FftError, PhantomData, is, paste, unwrap};
+
+/// The largest prime factor of a supported transform length.
+const MAX_RADIX: usize = 31;
+
+#[doc = crate::_tags!(num signal)]
+/// In-place mixed-radix complex fast Fourier transform.
+#[doc = crate::_doc_meta!{location("num/signal")}]
+///
+/// Computes the discrete Fourier transform of any length whose prime factors
+/// are at most 31, with radix-4 and radix-2 butterflies for the factors of 2,
+/// and a generic butterfly for the odd ones. It's implemented for `f32` and `f64`.
+///
+/// The twiddle factors are precomputed into the table `B`, which is either
+/// an array built by the *const* [`new`][Self::new] constructor,
+/// or any mutable storage filled by [`with_table`][Self::with_table].
+///
+/// The forward transform is unnormalized, and the inverse is scaled by `1/N`.
+///
+/// # Example
+/// ```
+/// # use devela::{Complex, Fft, unwrap};
+/// const FFT: Fft<f32, [Complex<f32>; 4]> = unwrap![ok Fft::<f32, _>::new()];
+///
+/// let mut data = [1.0, 2.0, 3.0, 4.0].map(Complex::<f32>::from_re);
+/// FFT.forward(&mut data).unwrap();
+/// assert_eq![data[0], Complex::new(10.0, 0.0)];
+/// assert_eq![data[1], Complex::new(-2.0, 2.0)];
+///
+/// FFT.inverse(&mut data).unwrap();
+/// assert_eq![data.map(|c| c.re), [1.0, 2.0, 3.0, 4.0]];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Fft<T, B> {
+    table: B,
+    factors: FftFactors,
+    _float: PhantomData<T>,
+}
+
+/// The prime factors of a transform length, with pairs of 2 merged into 4.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(super) struct FftFactors {
+    radices: [u8; 48],
+    count: u8,
+}
+impl FftFactors {
+    /// Factorizes `len`.
+    pub(super) const fn new(len: usize) -> Result<Self, FftError> {
+        is![len == 0, return Err(FftError::InvalidLength)];
+        let mut factors = Self { radices: [0; 48], count: 0 };
+        let mut n = len;
+        while n.is_multiple_of(4) {
+            factors.push(4);
+            n /= 4;
+        }
+        is![n.is_multiple_of(2), {
+            factors.push(2);
+            n /= 2;
+        }];
+        let mut p = 3;
+        while p <= MAX_RADIX && p * p <= n {
+            while n.is_multiple_of(p) {
+                factors.push(p);
+                n /= p;
+            }
+            p += 2;
+        }
+        if n > 1 {
+            is![n > MAX_RADIX, return Err(FftError::UnsupportedLength)];
+            factors.push(n);
+        }
+        Ok(factors)
+    }
+    const fn push(&mut self, radix: usize) {
+        self.radices[self.count as usize] = radix as u8;
+        self.count += 1;
+    }
+    const fn get(&self, index: usize) -> usize {
+        self.radices[index] as usize
+    }
+    /// Returns the position where the DIF stages leave the output bin `k`.
+    const fn digit_reverse(&self, mut k: usize, len: usize) -> usize {
+        let (mut pos, mut span, mut f) = (0, len, 0);
+        while f < self.count as usize {
+            let radix = self.get(f);
+            span /= radix;
+            pos += (k % radix) * span;
+            k /= radix;
+            f += 1;
+        }
+        pos
+    }
+}
+
+impl<T, B: AsRef<[Complex<T>]>> Fft<T, B> {
+    /// Returns the transform length.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.table.as_ref().len()
+    }
+    /// Returns the twiddle factors, `e^(-2πik/N)` for every `k < N`.
+    #[must_use]
+    pub fn twiddles(&self) -> &[Complex<T>] {
+        self.table.as_ref()
+    }
+}
+
+macro_rules! impl_fft {
+    () => { impl_fft![f32, f64]; };
+    ($($f:ty),+) => { $( impl_fft![@$f]; )+ };
+    (@$f:ty) => { paste! {
+        impl<const N: usize> Fft<$f, [Complex<$f>; N]> {
+            /// Returns a transform of length `N`, with its twiddle table.
+            ///
+            /// # Errors
+            /// Returns [`FftError::InvalidLength`] if `N` is 0,
+            /// or [`FftError::UnsupportedLength`] if it has a prime factor above 31.
+            pub const fn new() -> Result<Self, FftError> {
+                let factors = unwrap![ok? FftFactors::new(N)];
+                let mut table = [Complex::<$f>::ZERO; N];
+                [<fill_twiddles_ $f>](&mut table);
+                Ok(Self { table, factors, _float: PhantomData })
+            }
+        }
+        impl<B: AsRef<[Complex<$f>]> + AsMut<[Complex<$f>]>> Fft<$f, B> {
+            /// Returns a transform with the length of `table`, after filling it.
+            ///
+            /// # Errors
+            /// Returns [`FftError::InvalidLength`] if `table` is empty,
+            /// or [`FftError::UnsupportedLength`] if its length has a prime factor above 31.
+            pub fn with_table(mut table: B) -> Result<Self, FftError> {
+                let factors = FftFactors::new(table.as_ref().len())?;
+                [<fill_twiddles_ $f>](table.as_mut());
+                Ok(Self { table, factors, _float: PhantomData })
+            }
+        }
+        impl<B: AsRef<[Complex<$f>]>> Fft<$f, B> {
+            /// Computes the forward transform of `data` in place.
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `data` isn't [`size`](Self::size) long.
+            pub fn forward(&self, data: &mut [Complex<$f>]) -> Result<(), FftError> {
+                is![data.len() != self.size(), return Err(FftError::MismatchedLength)];
+                [<fft_forward_ $f>](data, self.table.as_ref(), 1, &self.factors);
+                Ok(())
+            }
+            /// Computes the inverse transform of `data` in place, scaled by `1/N`.
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `data` isn't [`size`](Self::size) long.
+            pub fn inverse(&self, data: &mut [Complex<$f>]) -> Result<(), FftError> {
+                is![data.len() != self.size(), return Err(FftError::MismatchedLength)];
+                [<fft_inverse_ $f>](data, self.table.as_ref(), 1, &self.factors);
+                Ok(())
+            }
+        }
+
+        /// Fills `table` with `e^(-2πik/N)` for every `k < N`, where `N` is its length.
+        pub(super) const fn [<fill_twiddles_ $f>](table: &mut [Complex<$f>]) {
+            let n = table.len();
+            let mut k = 0;
+            while k < n {
+                let (cos, sin) = turn_cos_sin(k, n);
+                table[k] = Complex::new(cos as $f, -sin as $f);
+                k += 1;
+            }
+        }
+
+        /// Computes the forward transform of `data` in place.
+        ///
+        /// `table[t * stride]` must hold `e^(-2πit/N)`, and `factors` those of `N`.
+        pub(super) const fn [<fft_forward_ $f>](data: &mut [Complex<$f>],
+            table: &[Complex<$f>], stride: usize, factors: &FftFactors) {
+            let n = data.len();
+            // Decimation in frequency, one stage per factor.
+            let (mut len, mut f) = (n, 0);
+            while f < factors.count as usize {
+                let radix = factors.get(f);
+                let m = len / radix;
+                let step = n / len;
+                let mut base = 0;
+                while base < n {
+                    let mut j = 0;
+                    while j < m {
+                        [<butterfly_ $f>](data, base + j, m, radix, table, stride, j * step);
+                        j += 1;
+                    }
+                    base += len;
+                }
+                len = m;
+                f += 1;
+            }
+            // Moves each bin from its digit-reversed position, one cycle at a time.
+            let mut i = 0;
+            while i < n {
+                let mut k = factors.digit_reverse(i, n);
+                while k > i {
+                    k = factors.digit_reverse(k, n);
+                }
+                if k == i {
+                    let first = data[i];
+                    loop {
+                        let next = factors.digit_reverse(k, n);
+                        if next == i {
+                            data[k] = first;
+                            break;
+                        }
+                        data[k] = data[next];
+                        k = next;
+                    }
+                }
+                i += 1;
+            }
+        }
+
+        /// Computes the inverse transform of `data` in place, scaled by `1/N`.
+        pub(super) const fn [<fft_inverse_ $f>](data: &mut [Complex<$f>],
+            table: &[Complex<$f>], stride: usize, factors: &FftFactors) {
+            let mut i = 0;
+            while i < data.len() {
+                data[i] = data[i].conj();
+                i += 1;
+            }
+            [<fft_forward_ $f>](data, table, stride, factors);
+            let scale = 1.0 / data.len() as $f;
+            let mut i = 0;
+            while i < data.len() {
+                data[i] = data[i].conj().scale(scale);
+                i += 1;
+            }
+        }
+
+        /// Transforms the `radix` elements spaced by `m` from `start`,
+        /// and multiplies each output `p` by the twiddle `p * tw`.
+        const fn [<butterfly_ $f>](data: &mut [Complex<$f>], start: usize, m: usize,
+            radix: usize, table: &[Complex<$f>], stride: usize, tw: usize) {
+            match radix {
+                2 => {
+                    let (a, b) = (data[start], data[start + m]);
+                    data[start] = a.add(b);
+                    data[start + m] = a.sub(b).mul(table[tw * stride]);
+                }
+                4 => {
+                    let (a, b) = (data[start], data[start + m]);
+                    let (c, d) = (data[start + 2 * m], data[start + 3 * m]);
+                    let (ac_sum, ac_diff) = (a.add(c), a.sub(c));
+                    let (bd_sum, bd_diff) = (b.add(d), b.sub(d).mul_i());
+                    data[start] = ac_sum.add(bd_sum);
+                    data[start + m] = ac_diff.sub(bd_diff).mul(table[tw * stride]);
+                    data[start + 2 * m] = ac_sum.sub(bd_sum).mul(table[2 * tw * stride]);
+                    data[start + 3 * m] = ac_diff.add(bd_diff).mul(table[3 * tw * stride]);
+                }
+                _ => {
+                    let mut buf = [Complex::<$f>::ZERO; MAX_RADIX];
+                    let mut q = 0;
+                    while q < radix {
+                        buf[q] = data[start + q * m];
+                        q += 1;
+                    }
+                    // The twiddles of a length `radix` transform are spaced by `N / radix`.
+                    let span = data.len() / radix * stride;
+                    let mut p = 0;
+                    while p < radix {
+                        let mut acc = buf[0];
+                        let mut q = 1;
+                        while q < radix {
+                            acc = acc.add(buf[q].mul(table[(p * q) % radix * span]));
+                            q += 1;
+                        }
+                        data[start + p * m] = is![p == 0, acc, acc.mul(table[p * tw * stride])];
+                        p += 1;
+                    }
+                }
+            }
+        }
+    }};
+}
+impl_fft!();
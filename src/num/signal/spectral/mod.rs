@@ -0,0 +1,27 @@
+// devela/src/num/signal/spectral/mod.rs
+//
+//! Spectral analysis: Fourier transforms, windows and short-time spectra.
+//
+
+#[cfg(test)]
+mod _test;
+
+mod _helper; // turn_cos_sin
+
+mod error; // FftError
+mod fft; // Fft
+mod real; // FftReal
+mod stft; // Stft
+mod window; // SpectralWindow
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            error::*,
+            fft::Fft,
+            real::*,
+            stft::*,
+            window::*,
+        };
+    }
+}
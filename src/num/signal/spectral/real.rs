@@ -0,0 +1,235 @@
+// devela/src/num/signal/spectral/real.rs
+//
+//! Defines [`FftReal`].
+//
+
+use super::fft::{FftFactors, fft_forward_f32, fft_forward_f64, fft_inverse_f32, fft_inverse_f64};
+use super::fft::{fill_twiddles_f32, fill_twiddles_f64};
+use crate::{Complex, FftError, PhantomData, is, paste, unwrap};
+
+#[doc = crate::_tags!(num signal)]
+/// Fast Fourier transform of real signals.
+#[doc = crate::_doc_meta!{location("num/signal")}]
+///
+/// Transforms `N` real samples into the `N/2 + 1` non-negative frequency bins,
+/// packing them into a complex transform of half the length.
+/// The length must be even, and its half follow the limits of [`Fft`][crate::Fft].
+///
+/// The twiddle table `B` holds `N` factors, as for a complex transform of length `N`.
+///
+/// Also computes power spectra, and linear convolutions and correlations.
+///
+/// # Example
+/// ```
+/// # use devela::{Complex, FftReal, unwrap};
+/// const FFT: FftReal<f64, [Complex<f64>; 8]> = unwrap![ok FftReal::<f64, _>::new()];
+///
+/// // A cosine completing 2 cycles.
+/// let signal = [1.0, 0.0, -1.0, 0.0, 1.0, 0.0, -1.0, 0.0];
+/// let mut power = [0.0; 5];
+/// let mut scratch = [Complex::<f64>::ZERO; 5];
+/// FFT.power(&signal, &mut power, &mut scratch).unwrap();
+/// assert![power.iter().zip([0.0, 0.0, 16.0, 0.0, 0.0]).all(|(p, e)| (p - e).abs() < 1e-12)];
+/// assert_eq![FFT.bin_frequency(2, 8_000.0), 2_000.0];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FftReal<T, B> {
+    table: B,
+    /// The factors of half the length.
+    factors: FftFactors,
+    _float: PhantomData<T>,
+}
+
+impl<T, B: AsRef<[Complex<T>]>> FftReal<T, B> {
+    /// Returns the number of real samples transformed.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.table.as_ref().len()
+    }
+    /// Returns the number of frequency bins, `N/2 + 1`.
+    #[must_use]
+    pub fn bins(&self) -> usize {
+        self.size() / 2 + 1
+    }
+}
+
+macro_rules! impl_fft_real {
+    () => { impl_fft_real![f32, f64]; };
+    ($($f:ty),+) => { $( impl_fft_real![@$f]; )+ };
+    (@$f:ty) => { paste! {
+        impl<const N: usize> FftReal<$f, [Complex<$f>; N]> {
+            /// Returns a real transform of length `N`, with its twiddle table.
+            ///
+            /// # Errors
+            /// Returns [`FftError::InvalidLength`] if `N` is 0 or odd,
+            /// or [`FftError::UnsupportedLength`] if it has a prime factor above 31.
+            pub const fn new() -> Result<Self, FftError> {
+                is![N % 2 != 0, return Err(FftError::InvalidLength)];
+                let factors = unwrap![ok? FftFactors::new(N / 2)];
+                let mut table = [Complex::<$f>::ZERO; N];
+                [<fill_twiddles_ $f>](&mut table);
+                Ok(Self { table, factors, _float: PhantomData })
+            }
+        }
+        impl<B: AsRef<[Complex<$f>]> + AsMut<[Complex<$f>]>> FftReal<$f, B> {
+            /// Returns a real transform with the length of `table`, after filling it.
+            ///
+            /// # Errors
+            /// Returns [`FftError::InvalidLength`] if the length of `table` is 0 or odd,
+            /// or [`FftError::UnsupportedLength`] if it has a prime factor above 31.
+            pub fn with_table(mut table: B) -> Result<Self, FftError> {
+                let len = table.as_ref().len();
+                is![len % 2 != 0, return Err(FftError::InvalidLength)];
+                let factors = FftFactors::new(len / 2)?;
+                [<fill_twiddles_ $f>](table.as_mut());
+                Ok(Self { table, factors, _float: PhantomData })
+            }
+        }
+        impl<B: AsRef<[Complex<$f>]>> FftReal<$f, B> {
+            /// Returns the center frequency of `bin`, for signals sampled at `sample_rate`.
+            #[must_use]
+            pub fn bin_frequency(&self, bin: usize, sample_rate: $f) -> $f {
+                bin as $f * sample_rate / self.size() as $f
+            }
+
+            /// Transforms the real `input` into the first [`bins`](Self::bins) of `out`.
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `input` isn't
+            /// [`size`](Self::size) long, or `out` is shorter than the bins.
+            pub fn forward(&self, input: &[$f], out: &mut [Complex<$f>]) -> Result<(), FftError> {
+                is![input.len() != self.size() || out.len() < self.bins(),
+                    return Err(FftError::MismatchedLength)];
+                self.forward_by(|i| input[i], out);
+                Ok(())
+            }
+            /// Transforms the first [`bins`](Self::bins) of `spectrum` back into
+            /// the real `out`, scaled by `1/N`.
+            ///
+            /// The bins of `spectrum` are overwritten.
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `spectrum` is shorter than
+            /// the bins, or `out` isn't [`size`](Self::size) long.
+            pub fn inverse(&self, spectrum: &mut [Complex<$f>], out: &mut [$f])
+                -> Result<(), FftError> {
+                is![spectrum.len() < self.bins() || out.len() != self.size(),
+                    return Err(FftError::MismatchedLength)];
+                self.inverse_by(spectrum, |i, sample| out[i] = sample);
+                Ok(())
+            }
+
+            /// Computes the power spectrum of `input`, the squared magnitude of each bin.
+            ///
+            /// The `scratch` buffer must hold the [`bins`](Self::bins).
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `input` isn't
+            /// [`size`](Self::size) long, or `out` or `scratch` are shorter than the bins.
+            pub fn power(&self, input: &[$f], out: &mut [$f], scratch: &mut [Complex<$f>])
+                -> Result<(), FftError> {
+                is![out.len() < self.bins(), return Err(FftError::MismatchedLength)];
+                self.forward(input, scratch)?;
+                for (p, c) in out.iter_mut().zip(&scratch[..self.bins()]) {
+                    *p = c.norm_sqr();
+                }
+                Ok(())
+            }
+
+            /// Computes the linear convolution of `a` and `b` into `out`.
+            ///
+            /// Returns the length of the result, `a.len() + b.len() - 1`,
+            /// or 0 if any of them is empty.
+            /// The `scratch` buffer must hold twice the [`bins`](Self::bins).
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if the result is longer than
+            /// [`size`](Self::size) or `out`, or `scratch` is too short.
+            pub fn convolve(&self, a: &[$f], b: &[$f], out: &mut [$f],
+                scratch: &mut [Complex<$f>]) -> Result<usize, FftError> {
+                self.product(a, |i| b.get(i).copied().unwrap_or(0.0), b.len(), out, scratch)
+            }
+            /// Computes the linear cross-correlation of `a` and `b` into `out`.
+            ///
+            /// `out[k]` holds the sum of `a[n + k + 1 - b.len()] * b[n]` over every `n`,
+            /// so that lag zero is at index `b.len() - 1`.
+            /// Correlating a signal with itself gives its autocorrelation.
+            ///
+            /// Returns the length of the result, `a.len() + b.len() - 1`,
+            /// or 0 if any of them is empty.
+            /// The `scratch` buffer must hold twice the [`bins`](Self::bins).
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if the result is longer than
+            /// [`size`](Self::size) or `out`, or `scratch` is too short.
+            pub fn correlate(&self, a: &[$f], b: &[$f], out: &mut [$f],
+                scratch: &mut [Complex<$f>]) -> Result<usize, FftError> {
+                let reversed = |i: usize| is![i < b.len(), b[b.len() - 1 - i], 0.0];
+                self.product(a, reversed, b.len(), out, scratch)
+            }
+
+            /* private helpers */
+
+            /// Multiplies the spectra of `a` and `b`, given as a sample function,
+            /// and transforms the product back into `out`.
+            fn product(&self, a: &[$f], b: impl Fn(usize) -> $f, b_len: usize,
+                out: &mut [$f], scratch: &mut [Complex<$f>]) -> Result<usize, FftError> {
+                is![a.is_empty() || b_len == 0, return Ok(0)];
+                let (len, bins) = (a.len() + b_len - 1, self.bins());
+                is![len > self.size() || len > out.len() || scratch.len() < 2 * bins,
+                    return Err(FftError::MismatchedLength)];
+                let (sa, sb) = scratch.split_at_mut(bins);
+                self.forward_by(|i| a.get(i).copied().unwrap_or(0.0), sa);
+                self.forward_by(b, sb);
+                for (x, y) in sa.iter_mut().zip(&sb[..bins]) {
+                    *x = x.mul(*y);
+                }
+                self.inverse_by(sa, |i, sample| {
+                    if i < len {
+                        out[i] = sample;
+                    }
+                });
+                Ok(len)
+            }
+
+            /// Transforms the real samples returned by `sample` into the bins of `out`.
+            pub(super) fn forward_by(&self, sample: impl Fn(usize) -> $f, out: &mut [Complex<$f>]) {
+                let (table, half) = (self.table.as_ref(), self.size() / 2);
+                for (k, z) in out[..half].iter_mut().enumerate() {
+                    *z = Complex::new(sample(2 * k), sample(2 * k + 1));
+                }
+                [<fft_forward_ $f>](&mut out[..half], table, 2, &self.factors);
+                // Separates the spectra of the even and odd samples, and merges them.
+                for k in 0..=half / 2 {
+                    let (a, b) = (out[k], out[(half - k) % half]);
+                    let even = a.add(b.conj()).scale(0.5);
+                    let d = a.sub(b.conj());
+                    let odd = Complex::new(d.im, -d.re).scale(0.5);
+                    out[k] = even.add(table[k].mul(odd));
+                    out[half - k] = even.conj().add(table[half - k].mul(odd.conj()));
+                }
+            }
+            /// Transforms the bins of `spectrum` into real samples given to `sink`.
+            fn inverse_by(&self, spectrum: &mut [Complex<$f>], mut sink: impl FnMut(usize, $f)) {
+                let (table, half) = (self.table.as_ref(), self.size() / 2);
+                // Splits the spectra of the even and odd samples, and packs them.
+                for k in 0..=half / 2 {
+                    let (a, b) = (spectrum[k], spectrum[half - k]);
+                    let even = a.add(b.conj()).scale(0.5);
+                    let odd = a.sub(b.conj()).mul(table[k].conj()).scale(0.5);
+                    let (even2, odd2) = (even.conj(), b.sub(a.conj()).mul(table[half - k].conj()));
+                    spectrum[k] = even.add(odd.mul_i());
+                    if k != 0 {
+                        spectrum[half - k] = even2.add(odd2.scale(0.5).mul_i());
+                    }
+                }
+                [<fft_inverse_ $f>](&mut spectrum[..half], table, 2, &self.factors);
+                for (k, z) in spectrum[..half].iter().enumerate() {
+                    sink(2 * k, z.re);
+                    sink(2 * k + 1, z.im);
+                }
+            }
+        }
+    }};
+}
+impl_fft_real!();
@@ -0,0 +1,129 @@
+// devela/src/num/signal/spectral/stft.rs
+//
+//! Defines [`Stft`].
+//
+
+use crate::{Complex, FftError, FftReal, is};
+
+#[doc = crate::_tags!(num signal)]
+/// Short-time Fourier transform of real signals.
+#[doc = crate::_doc_meta!{location("num/signal")}]
+///
+/// Splits a signal into frames of the [`FftReal`] length, spaced by `hop`
+/// samples, multiplies each frame by the `window` coefficients and transforms it.
+/// Frames reaching past the end of the signal are padded with zeros.
+///
+/// The window is stored in `W`, usually filled by a [`SpectralWindow`][crate::SpectralWindow].
+///
+/// # Example
+/// ```
+/// # use devela::{Complex, FftReal, SpectralWindow, Stft};
+/// let fft = FftReal::<f32, [Complex<f32>; 16]>::new().unwrap();
+/// let mut window = [0.0; 16];
+/// SpectralWindow::Hann.fill_f32(&mut window);
+/// let stft = Stft::new(fft, window, 8).unwrap();
+///
+/// // A tone at the frequency of bin 4.
+/// let signal: [f32; 40] = core::array::from_fn(|i| (i as f32 * core::f32::consts::FRAC_PI_2).sin());
+/// assert_eq![stft.frames(signal.len()), 4];
+///
+/// let mut power = [0.0; 9];
+/// let mut scratch = [Complex::<f32>::ZERO; 9];
+/// stft.power(&signal, 1, &mut power, &mut scratch).unwrap();
+/// let peak = (0..9).max_by(|&a, &b| power[a].total_cmp(&power[b])).unwrap();
+/// assert_eq![peak, 4];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Stft<T, B, W> {
+    fft: FftReal<T, B>,
+    window: W,
+    hop: usize,
+}
+
+impl<T, B: AsRef<[Complex<T>]>, W: AsRef<[T]>> Stft<T, B, W> {
+    /// Returns a short-time transform with frames spaced by `hop` samples.
+    ///
+    /// # Errors
+    /// Returns [`FftError::InvalidLength`] if `hop` is 0,
+    /// or [`FftError::MismatchedLength`] if `window` isn't as long as a frame.
+    pub fn new(fft: FftReal<T, B>, window: W, hop: usize) -> Result<Self, FftError> {
+        is![hop == 0, return Err(FftError::InvalidLength)];
+        is![window.as_ref().len() != fft.size(), return Err(FftError::MismatchedLength)];
+        Ok(Self { fft, window, hop })
+    }
+
+    /// Returns the real transform of each frame.
+    #[must_use]
+    pub const fn fft(&self) -> &FftReal<T, B> {
+        &self.fft
+    }
+    /// Returns the window coefficients.
+    #[must_use]
+    pub fn window(&self) -> &[T] {
+        self.window.as_ref()
+    }
+    /// Returns the number of samples between the start of consecutive frames.
+    #[must_use]
+    pub const fn hop(&self) -> usize {
+        self.hop
+    }
+    /// Returns the number of samples of each frame.
+    #[must_use]
+    pub fn frame_len(&self) -> usize {
+        self.fft.size()
+    }
+
+    /// Returns the number of frames needed to cover `signal_len` samples.
+    #[must_use]
+    pub fn frames(&self, signal_len: usize) -> usize {
+        let len = self.frame_len();
+        match signal_len {
+            0 => 0,
+            n if n <= len => 1,
+            n => 1 + (n - len).div_ceil(self.hop),
+        }
+    }
+}
+
+macro_rules! impl_stft {
+    () => { impl_stft![f32, f64]; };
+    ($($f:ty),+) => { $( impl_stft![@$f]; )+ };
+    (@$f:ty) => {
+        impl<B: AsRef<[Complex<$f>]>, W: AsRef<[$f]>> Stft<$f, B, W> {
+            /// Transforms the frame at `index` of `signal` into the bins of `out`.
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `out` is shorter
+            /// than the [`bins`](FftReal::bins).
+            pub fn frame(&self, signal: &[$f], index: usize, out: &mut [Complex<$f>])
+                -> Result<(), FftError> {
+                is![out.len() < self.fft.bins(), return Err(FftError::MismatchedLength)];
+                let (start, window) = (index.saturating_mul(self.hop), self.window.as_ref());
+                let sample = |i| {
+                    let s = start.checked_add(i).and_then(|at| signal.get(at)).copied();
+                    s.unwrap_or(0.0) * window[i]
+                };
+                self.fft.forward_by(sample, out);
+                Ok(())
+            }
+            /// Computes the power spectrum of the frame at `index` of `signal`.
+            ///
+            /// The `scratch` buffer must hold the [`bins`](FftReal::bins).
+            ///
+            /// # Errors
+            /// Returns [`FftError::MismatchedLength`] if `out` or `scratch`
+            /// are shorter than the bins.
+            pub fn power(&self, signal: &[$f], index: usize, out: &mut [$f],
+                scratch: &mut [Complex<$f>]) -> Result<(), FftError> {
+                let bins = self.fft.bins();
+                is![out.len() < bins, return Err(FftError::MismatchedLength)];
+                self.frame(signal, index, scratch)?;
+                for (p, c) in out.iter_mut().zip(&scratch[..bins]) {
+                    *p = c.norm_sqr();
+                }
+                Ok(())
+            }
+        }
+    };
+}
+impl_stft!();
@@ -0,0 +1,89 @@
+// devela/src/num/signal/spectral/window.rs
+//
+//! Defines [`SpectralWindow`].
+//
+
+use super::_helper::turn_cos_sin;
+
+#[doc = crate::_tags!(num signal)]
+/// Window functions that taper a frame before its spectral analysis.
+#[doc = crate::_doc_meta!{location("num/signal")}]
+///
+/// The windows are periodic, as used with the discrete Fourier transform:
+/// a window of length `L` is the symmetric window of length `L + 1` without
+/// its last coefficient. Overlapping periodic Hann windows at a hop of `L/2`
+/// sum to a constant.
+///
+/// # Example
+/// ```
+/// # use devela::SpectralWindow;
+/// let mut window = [0.0_f32; 4];
+/// SpectralWindow::Hann.fill_f32(&mut window);
+/// assert_eq![window, [0.0, 0.5, 1.0, 0.5]];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SpectralWindow {
+    /// Leaves the frame unchanged, for the best frequency resolution.
+    Rectangular,
+    /// The raised cosine `0.5 - 0.5·cos(2πn/L)`, a good general default.
+    #[default]
+    Hann,
+    /// The raised cosine `0.54 - 0.46·cos(2πn/L)`, with a lower first sidelobe.
+    Hamming,
+    /// The 4-term Blackman-Harris window, with sidelobes under -92 dB.
+    BlackmanHarris,
+}
+
+#[rustfmt::skip]
+impl SpectralWindow {
+    /// Returns the coefficients of the cosine terms.
+    const fn terms(self) -> [f64; 4] {
+        match self {
+            Self::Rectangular => [1.0, 0.0, 0.0, 0.0],
+            Self::Hann => [0.5, 0.5, 0.0, 0.0],
+            Self::Hamming => [0.54, 0.46, 0.0, 0.0],
+            Self::BlackmanHarris => [0.358_75, 0.488_29, 0.141_28, 0.011_68],
+        }
+    }
+
+    /// Returns the coefficient `n` of a window of length `len`.
+    ///
+    /// Returns 0 if `n >= len`.
+    #[must_use]
+    pub const fn at(self, n: usize, len: usize) -> f64 {
+        if n >= len { return 0.0; }
+        let [a0, a1, a2, a3] = self.terms();
+        let mut value = a0;
+        if a1 != 0.0 { value -= a1 * turn_cos_sin(n, len).0; }
+        if a2 != 0.0 { value += a2 * turn_cos_sin(2 * n % len, len).0; }
+        if a3 != 0.0 { value -= a3 * turn_cos_sin(3 * n % len, len).0; }
+        value
+    }
+
+    /// Returns the mean of the coefficients, by which the window scales
+    /// the amplitude of a sinusoid at the center of a bin.
+    #[must_use]
+    pub const fn coherent_gain(self) -> f64 { self.terms()[0] }
+
+    /// Fills `window` with the coefficients of a window of its length.
+    pub const fn fill_f32(self, window: &mut [f32]) {
+        let mut n = 0;
+        while n < window.len() { window[n] = self.at(n, window.len()) as f32; n += 1; }
+    }
+    /// Fills `window` with the coefficients of a window of its length.
+    pub const fn fill_f64(self, window: &mut [f64]) {
+        let mut n = 0;
+        while n < window.len() { window[n] = self.at(n, window.len()); n += 1; }
+    }
+
+    /// Multiplies `frame` by a window of its length.
+    pub const fn apply_f32(self, frame: &mut [f32]) {
+        let mut n = 0;
+        while n < frame.len() { frame[n] *= self.at(n, frame.len()) as f32; n += 1; }
+    }
+    /// Multiplies `frame` by a window of its length.
+    pub const fn apply_f64(self, frame: &mut [f64]) {
+        let mut n = 0;
+        while n < frame.len() { frame[n] *= self.at(n, frame.len()); n += 1; }
+    }
+}
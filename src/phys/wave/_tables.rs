@@ -0,0 +1,126 @@
+// devela/src/phys/wave/_tables.rs
+//
+//! Scaling filters of the orthogonal wavelets.
+//
+// Computed by spectral factorization at high precision, and given in the order
+// where the approximation `a[k]` is the sum of `h[i] * x[2k + i]`.
+// The symlets pick the roots that match the usual published filters.
+
+/* Daubechies, by number of taps */
+
+pub(super) const D4: [f64; 4] = [
+    0.482_962_913_144_534_16, 0.836_516_303_737_807_9, 0.224_143_868_042_013_4,
+    -0.129_409_522_551_260_37,
+];
+pub(super) const D6: [f64; 6] = [
+    0.332_670_552_950_082_63, 0.806_891_509_311_092_5, 0.459_877_502_118_491_54,
+    -0.135_011_020_010_254_58, -0.085_441_273_882_026_66, 0.035_226_291_885_709_53,
+];
+pub(super) const D8: [f64; 8] = [
+    0.230_377_813_308_896_5, 0.714_846_570_552_915_7, 0.630_880_767_929_858_9,
+    -0.027_983_769_416_859_854, -0.187_034_811_719_093_09, 0.030_841_381_835_560_764,
+    0.032_883_011_666_885_2, -0.010_597_401_785_069_032,
+];
+pub(super) const D10: [f64; 10] = [
+    0.160_102_397_974_192_93, 0.603_829_269_797_189_6, 0.724_308_528_437_772_9,
+    0.138_428_145_901_320_74, -0.242_294_887_066_382_03, -0.032_244_869_584_638_375,
+    0.077_571_493_840_045_72, -0.006_241_490_212_798_274, -0.012_580_751_999_081_999,
+    0.003_335_725_285_473_771_2,
+];
+pub(super) const D12: [f64; 12] = [
+    0.111_540_743_350_109_47, 0.494_623_890_398_453_06, 0.751_133_908_021_095_4,
+    0.315_250_351_709_197_63, -0.226_264_693_965_439_83, -0.129_766_867_567_261_94,
+    0.097_501_605_587_323_04, 0.027_522_865_530_305_727, -0.031_582_039_317_486_03,
+    0.000_553_842_201_161_496_1, 0.004_777_257_510_945_511, -0.001_077_301_085_308_479_6,
+];
+pub(super) const D14: [f64; 14] = [
+    0.077_852_054_085_009_18, 0.396_539_319_481_917_3, 0.729_132_090_846_235_1,
+    0.469_782_287_405_193_1, -0.143_906_003_928_564_98, -0.224_036_184_993_874_98,
+    0.071_309_219_266_830_26, 0.080_612_609_151_083_08, -0.038_029_936_935_014_41,
+    -0.016_574_541_630_666_88, 0.012_550_998_556_099_84, 0.000_429_577_972_921_366_5,
+    -0.001_801_640_704_047_490_8, 0.000_353_713_799_974_520_24,
+];
+pub(super) const D16: [f64; 16] = [
+    0.054_415_842_243_104_01, 0.312_871_590_914_299_95, 0.675_630_736_297_289_8,
+    0.585_354_683_654_206_7, -0.015_829_105_256_349_306, -0.284_015_542_961_546_9,
+    0.000_472_484_573_913_282_8, 0.128_747_426_620_478_47, -0.017_369_301_001_807_547,
+    -0.044_088_253_930_794_755, 0.013_981_027_917_398_282, 0.008_746_094_047_405_777,
+    -0.004_870_352_993_451_574, -0.000_391_740_373_376_947_05, 0.000_675_449_406_450_569_3,
+    -0.000_117_476_784_124_769_53,
+];
+pub(super) const D18: [f64; 18] = [
+    0.038_077_947_363_878_345, 0.243_834_674_612_590_34, 0.604_823_123_690_111_2,
+    0.657_288_078_051_300_5, 0.133_197_385_825_007_56, -0.293_273_783_279_174_9,
+    -0.096_840_783_222_976_46, 0.148_540_749_338_106_38, 0.030_725_681_479_333_38,
+    -0.067_632_829_061_329_97, 0.000_250_947_114_831_451_97, 0.022_361_662_123_679_096,
+    -0.004_723_204_757_751_397, -0.004_281_503_682_463_43, 0.001_847_646_883_056_226_5,
+    0.000_230_385_763_523_195_97, -0.000_251_963_188_942_710_1, 0.000_039_347_320_316_271_6,
+];
+pub(super) const D20: [f64; 20] = [
+    0.026_670_057_900_555_554, 0.188_176_800_077_691_5, 0.527_201_188_931_725_6,
+    0.688_459_039_453_603_5, 0.281_172_343_660_577_5, -0.249_846_424_327_315_38,
+    -0.195_946_274_377_377_05, 0.127_369_340_335_793_25, 0.093_057_364_603_572_35,
+    -0.071_394_147_166_397_08, -0.029_457_536_821_875_813, 0.033_212_674_059_341,
+    0.003_606_553_566_956_169_7, -0.010_733_175_483_330_575, 0.001_395_351_747_052_901,
+    0.001_992_405_295_185_056, -0.000_685_856_694_959_711_6, -0.000_116_466_855_129_285_45,
+    0.000_093_588_670_320_069_59, -0.000_013_264_202_894_521_244,
+];
+/* symlets, by vanishing moments */
+
+pub(super) const SYM2: [f64; 4] = [
+    0.482_962_913_144_534_16, 0.836_516_303_737_807_9, 0.224_143_868_042_013_4,
+    -0.129_409_522_551_260_37,
+];
+pub(super) const SYM3: [f64; 6] = [
+    0.332_670_552_950_082_63, 0.806_891_509_311_092_5, 0.459_877_502_118_491_54,
+    -0.135_011_020_010_254_58, -0.085_441_273_882_026_66, 0.035_226_291_885_709_53,
+];
+pub(super) const SYM4: [f64; 8] = [
+    0.032_223_100_604_051_466, -0.012_603_967_262_031_304, -0.099_219_543_576_633_53,
+    0.297_857_795_605_306_06, 0.803_738_751_805_132_1, 0.497_618_667_632_775,
+    -0.029_635_527_646_002_493, -0.075_765_714_789_502_21,
+];
+pub(super) const SYM5: [f64; 10] = [
+    0.019_538_882_735_249_827, -0.021_101_834_024_689_042, -0.175_328_089_908_056_23,
+    0.016_602_105_764_510_85, 0.633_978_963_456_792, 0.723_407_690_404_040_7,
+    0.199_397_533_976_855_58, -0.039_134_249_302_313_844, 0.029_519_490_925_706_26,
+    0.027_333_068_344_998_768,
+];
+pub(super) const SYM6: [f64; 12] = [
+    -0.007_800_708_325_032_38, 0.001_767_711_864_254_007_7, 0.044_724_901_770_781_39,
+    -0.021_060_292_512_370_85, -0.072_637_522_786_376_58, 0.337_929_421_728_165_8,
+    0.787_641_141_028_651, 0.491_055_941_927_973_75, -0.048_311_742_585_698_06,
+    -0.117_990_111_148_520_02, 0.003_490_712_084_222_162_6, 0.015_404_109_327_044_824,
+];
+pub(super) const SYM7: [f64; 14] = [
+    0.010_268_176_708_464_817, 0.004_010_244_871_522_395_5, -0.107_808_237_703_289_72,
+    -0.140_047_240_442_933_65, 0.288_629_631_750_647_9, 0.767_764_317_004_882_9,
+    0.536_101_917_090_569_2, 0.017_441_255_086_835_708, -0.049_552_834_937_042_83,
+    0.067_892_693_501_220_57, 0.030_515_513_165_877_885, -0.012_636_303_403_240_567,
+    -0.001_047_384_888_679_738, 0.002_681_814_568_260_147,
+];
+pub(super) const SYM8: [f64; 16] = [
+    0.001_889_950_332_767_689, -0.000_302_920_514_724_133_1, -0.014_952_258_337_062_199,
+    0.003_808_752_013_894_489_6, 0.049_137_179_673_730_29, -0.027_219_029_917_103_486,
+    -0.051_945_838_107_881_8, 0.364_441_894_836_178_95, 0.777_185_751_699_628,
+    0.481_359_651_259_053_4, -0.061_273_359_067_811_076, -0.143_294_238_351_272_67,
+    0.007_607_487_324_976_609, 0.031_695_087_811_525_99, -0.000_542_132_331_800_010_7,
+    -0.003_382_415_951_005_002_8,
+];
+pub(super) const SYM9: [f64; 18] = [
+    0.001_069_490_032_908_612, -0.000_473_154_498_680_043_54, -0.010_264_064_027_633_121,
+    0.008_859_267_493_400_267, 0.062_077_789_302_885_746, -0.018_233_770_779_395_506,
+    -0.191_550_831_297_284_34, 0.035_272_488_035_271_04, 0.617_338_449_140_934_2,
+    0.717_897_082_764_412_4, 0.238_760_914_607_305_17, -0.054_568_958_430_833_35,
+    0.000_583_462_746_124_981_9, 0.030_224_878_858_275_187, -0.011_528_210_207_679_187,
+    -0.013_271_967_781_817_134, 0.000_619_780_888_985_507_1, 0.001_400_915_525_914_656_2,
+];
+pub(super) const SYM10: [f64; 20] = [
+    -0.000_459_329_421_004_652_06, 0.000_057_036_083_618_495_01, 0.004_593_173_585_311_792,
+    -0.000_804_358_932_016_451_3, -0.020_354_939_812_311_11, 0.005_764_912_033_581_15,
+    0.049_994_972_077_375_154, -0.031_990_056_882_428_11, -0.035_536_740_473_819_585,
+    0.383_826_761_067_076_3, 0.769_510_037_021_097_9, 0.471_690_666_938_442_9,
+    -0.070_880_535_783_231_57, -0.159_494_278_884_910_6, 0.011_609_893_903_711_319,
+    0.045_927_239_231_091_51, -0.001_465_382_581_304_610_4, -0.008_641_299_277_022_15,
+    0.000_095_632_670_722_852_73, 0.000_770_159_809_114_459_9,
+];
@@ -8,8 +8,10 @@
 //   - trait WaveletTransformVec
 //   - struct WaveletUnitVec
 // - impls for WaveletHaar
+// - impls for WaveletDaubechies, WaveletSymlet, WaveletCdf97
 
-use crate::{Vec, WaveletHaar, WaveletUnitRole, vec_ as vec};
+use crate::{Vec, WaveletHaar, WaveletTransform, WaveletUnitRole, vec_ as vec};
+use crate::{WaveletCdf97, WaveletDaubechies, WaveletSymlet, is};
 
 #[allow(unused_imports, reason = "!std: abs")]
 use crate::FloatExt;
@@ -116,3 +118,39 @@ impl WaveletTransformVec for WaveletHaar {
         output
     }
 }
+
+/* impls for WaveletDaubechies, WaveletSymlet, WaveletCdf97 */
+
+macro_rules! impl_vec {
+    ($($wavelet:ty),+) => { $(
+        impl WaveletCompressionVec for $wavelet {
+            fn compress(&self, coeffs: &[f64], tolerance: f64) -> Vec<f64> {
+                coeffs.iter().map(|&c| if c.abs() < tolerance { 0.0 } else { c }).collect()
+            }
+        }
+        /// Decomposes as many levels as the length is divisible by 2.
+        impl WaveletTransformVec for $wavelet {
+            fn forward(&self, input: &[f64]) -> Vec<f64> {
+                let mut output = input.to_vec();
+                let mut scratch = vec![0.0; output.len()];
+                let levels = max_levels(output.len());
+                // can't fail, with a valid number of levels and enough scratch
+                let _ = self.decompose(&mut output, levels, &mut scratch);
+                output
+            }
+            fn inverse(&self, coeffs: &[f64]) -> Vec<f64> {
+                let mut output = coeffs.to_vec();
+                let mut scratch = vec![0.0; output.len()];
+                let levels = max_levels(output.len());
+                let _ = self.reconstruct(&mut output, levels, &mut scratch);
+                output
+            }
+        }
+    )+ };
+}
+impl_vec![WaveletDaubechies, WaveletSymlet, WaveletCdf97];
+
+/// Returns the number of times `len` is divisible by 2.
+fn max_levels(len: usize) -> usize {
+    is![len == 0, 0, len.trailing_zeros() as usize]
+}
@@ -0,0 +1,19 @@
+// devela/src/phys/wave/error.rs
+//
+//! Defines [`WaveletError`].
+//
+
+#[doc = crate::_tags!(wave error)]
+/// Error from a wavelet transform over caller buffers.
+#[doc = crate::_doc_meta!{location("phys/wave"), test_size_of(WaveletError = 16|128)}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WaveletError {
+    /// A length or extent isn't divisible by 2 for every requested level.
+    InvalidLength,
+    /// The scratch buffer is shorter than the given number of samples.
+    NotEnoughSpace(usize),
+}
+crate::impl_trait![fmt::Display+Error for WaveletError |self, f| match self {
+    Self::InvalidLength => f.write_str("length not divisible by 2 for every level"),
+    Self::NotEnoughSpace(n) => write!(f, "scratch buffer shorter than {n} samples"),
+}];
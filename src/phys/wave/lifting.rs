@@ -0,0 +1,106 @@
+// devela/src/phys/wave/lifting.rs
+//
+//! Defines [`WaveletCdf97`].
+//
+
+use super::transform::check_level;
+use crate::{WaveletError, WaveletTransform, paste};
+
+#[doc = crate::_tags!(wave)]
+/// The Cohen-Daubechies-Feauveau 9/7 biorthogonal wavelet, computed by lifting.
+#[doc = crate::_doc_meta!{location("phys/wave")}]
+///
+/// Its 9 and 7 tap analysis filters are symmetric, so extending the signal
+/// by mirroring it at its ends avoids the artifacts of wrapping around.
+/// This is the irreversible wavelet of JPEG 2000, well suited for images.
+///
+/// The lifting scheme applies two predict and two update steps in place,
+/// and scales the approximation by `ζ` and the detail by `1/ζ`,
+/// so that the approximation of a constant signal is `√2` times it.
+///
+/// - <https://en.wikipedia.org/wiki/Cohen%E2%80%93Daubechies%E2%80%93Feauveau_wavelet>.
+///
+/// # Example
+/// ```
+/// # use devela::{WaveletCdf97, WaveletTransform};
+/// let mut data = [3.0_f32; 8];
+/// let mut scratch = [0.0; 8];
+/// WaveletCdf97.decompose(&mut data, 1, &mut scratch)?;
+/// assert![data[4..].iter().all(|d| d.abs() < 1e-5)];
+/// assert![(data[0] - 3.0 * core::f32::consts::SQRT_2).abs() < 1e-5];
+///
+/// WaveletCdf97.reconstruct(&mut data, 1, &mut scratch)?;
+/// assert![data.iter().all(|x| (x - 3.0).abs() < 1e-5)];
+/// # Ok::<(), devela::WaveletError>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WaveletCdf97;
+
+impl WaveletCdf97 {
+    /// The coefficient of the first predict step.
+    pub const ALPHA: f64 = -1.586_134_342_059_924;
+    /// The coefficient of the first update step.
+    pub const BETA: f64 = -0.052_980_118_572_961;
+    /// The coefficient of the second predict step.
+    pub const GAMMA: f64 = 0.882_911_075_530_934;
+    /// The coefficient of the second update step.
+    pub const DELTA: f64 = 0.443_506_852_043_971;
+    /// The scaling factor `ζ`.
+    pub const ZETA: f64 = 1.149_604_398_860_241;
+}
+
+macro_rules! impl_cdf97 {
+    () => { impl_cdf97![f32, f64]; };
+    ($($f:ty),+) => { $( impl_cdf97![@$f]; )+ };
+    (@$f:ty) => { paste! {
+        impl WaveletTransform<$f> for WaveletCdf97 {
+            fn forward_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                let (n, half) = (data.len(), check_level(data.len(), scratch.len())?);
+                let (s, d) = scratch[..n].split_at_mut(half);
+                for (i, pair) in data.chunks_exact(2).enumerate() {
+                    (s[i], d[i]) = (pair[0], pair[1]);
+                }
+                [<predict_ $f>](d, s, Self::ALPHA as $f);
+                [<update_ $f>](s, d, Self::BETA as $f);
+                [<predict_ $f>](d, s, Self::GAMMA as $f);
+                [<update_ $f>](s, d, Self::DELTA as $f);
+                s.iter_mut().for_each(|x| *x *= Self::ZETA as $f);
+                d.iter_mut().for_each(|x| *x /= Self::ZETA as $f);
+                data.copy_from_slice(&scratch[..n]);
+                Ok(())
+            }
+            fn inverse_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                let (n, half) = (data.len(), check_level(data.len(), scratch.len())?);
+                scratch[..n].copy_from_slice(data);
+                let (s, d) = scratch[..n].split_at_mut(half);
+                s.iter_mut().for_each(|x| *x /= Self::ZETA as $f);
+                d.iter_mut().for_each(|x| *x *= Self::ZETA as $f);
+                [<update_ $f>](s, d, -Self::DELTA as $f);
+                [<predict_ $f>](d, s, -Self::GAMMA as $f);
+                [<update_ $f>](s, d, -Self::BETA as $f);
+                [<predict_ $f>](d, s, -Self::ALPHA as $f);
+                for (i, pair) in data.chunks_exact_mut(2).enumerate() {
+                    (pair[0], pair[1]) = (s[i], d[i]);
+                }
+                Ok(())
+            }
+        }
+
+        /// Adds to each odd sample its even neighbors by `k`, mirroring at the end.
+        fn [<predict_ $f>](odd: &mut [$f], even: &[$f], k: $f) {
+            let last = even.len().saturating_sub(1);
+            for (i, x) in odd.iter_mut().enumerate() {
+                *x += k * (even[i] + even[(i + 1).min(last)]);
+            }
+        }
+        /// Adds to each even sample its odd neighbors by `k`, mirroring at the start.
+        fn [<update_ $f>](even: &mut [$f], odd: &[$f], k: $f) {
+            for (i, x) in even.iter_mut().enumerate() {
+                *x += k * (odd[i.saturating_sub(1)] + odd[i]);
+            }
+        }
+    }};
+}
+impl_cdf97!();
@@ -9,7 +9,14 @@
 #[cfg(test)]
 mod _test;
 
+#[rustfmt::skip]
+mod _tables; // D4..D20, SYM2..SYM10
+
+mod error; // WaveletError
+mod lifting; // WaveletCdf97
+mod ortho; // WaveletDaubechies, WaveletSymlet
 mod shared; // WaveletHaar, WaveletUnitRole
+mod transform; // WaveletTransform
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(nightly_doc, doc(cfg(feature = "alloc")))]
@@ -17,7 +24,7 @@ mod alloc;
 
 crate::structural_mods! { // _mods
     _mods {
-        pub use super::shared::*;
+        pub use super::{error::*, lifting::*, ortho::*, shared::*, transform::*};
 
         #[cfg(feature = "alloc")]
         pub use super::alloc::*;
@@ -0,0 +1,222 @@
+// devela/src/phys/wave/ortho.rs
+//
+//! Defines [`WaveletDaubechies`], [`WaveletSymlet`].
+//
+// TOC
+// - enum WaveletDaubechies
+// - enum WaveletSymlet
+// - transform functions
+
+use super::{_tables::*, transform::check_level};
+use crate::{WaveletError, WaveletTransform, is, paste};
+
+#[doc = crate::_tags!(wave)]
+/// Daubechies orthogonal wavelets, named by their number of taps.
+#[doc = crate::_doc_meta!{location("phys/wave")}]
+///
+/// A wavelet of `2N` taps has `N` vanishing moments: its detail coefficients
+/// are zero over polynomials of degree below `N`. More taps give smoother
+/// wavelets and better energy compaction, at the cost of wider support.
+///
+/// The transform is orthonormal, and periodic: the signal wraps around its ends,
+/// so the coefficients preserve its energy and it's perfectly reconstructed.
+///
+/// - <https://en.wikipedia.org/wiki/Daubechies_wavelet>.
+///
+/// # Example
+/// ```
+/// # use devela::{WaveletDaubechies, WaveletTransform};
+/// let mut data = [1.0_f64, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+/// let mut scratch = [0.0; 8];
+/// WaveletDaubechies::D4.decompose(&mut data, 2, &mut scratch)?;
+/// // The details of the interior of a ramp vanish.
+/// assert![data[4].abs() < 1e-12 && data[5].abs() < 1e-12];
+///
+/// WaveletDaubechies::D4.reconstruct(&mut data, 2, &mut scratch)?;
+/// assert![(data[7] - 8.0).abs() < 1e-12];
+/// # Ok::<(), devela::WaveletError>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WaveletDaubechies {
+    /// 4 taps, 2 vanishing moments.
+    #[default]
+    D4,
+    /// 6 taps, 3 vanishing moments.
+    D6,
+    /// 8 taps, 4 vanishing moments.
+    D8,
+    /// 10 taps, 5 vanishing moments.
+    D10,
+    /// 12 taps, 6 vanishing moments.
+    D12,
+    /// 14 taps, 7 vanishing moments.
+    D14,
+    /// 16 taps, 8 vanishing moments.
+    D16,
+    /// 18 taps, 9 vanishing moments.
+    D18,
+    /// 20 taps, 10 vanishing moments.
+    D20,
+}
+
+impl WaveletDaubechies {
+    /// Returns the scaling filter, the coefficients of the approximation.
+    ///
+    /// The detail filter `g` is derived from it as `g[i] = (-1)^i · h[taps - 1 - i]`.
+    #[must_use]
+    pub const fn coefficients(self) -> &'static [f64] {
+        match self {
+            Self::D4 => &D4,
+            Self::D6 => &D6,
+            Self::D8 => &D8,
+            Self::D10 => &D10,
+            Self::D12 => &D12,
+            Self::D14 => &D14,
+            Self::D16 => &D16,
+            Self::D18 => &D18,
+            Self::D20 => &D20,
+        }
+    }
+    /// Returns the number of taps of the filters.
+    #[must_use]
+    pub const fn taps(self) -> usize {
+        self.coefficients().len()
+    }
+    /// Returns the number of vanishing moments of the wavelet.
+    #[must_use]
+    pub const fn vanishing_moments(self) -> usize {
+        self.taps() / 2
+    }
+}
+
+#[doc = crate::_tags!(wave)]
+/// Symlets, the least asymmetric orthogonal wavelets, named by their vanishing moments.
+#[doc = crate::_doc_meta!{location("phys/wave")}]
+///
+/// They have the same support and vanishing moments as the [`WaveletDaubechies`]
+/// of twice as many taps, with a nearly linear phase that shifts features less,
+/// which suits images better.
+///
+/// Like them, the transform is orthonormal and periodic.
+///
+/// - <https://en.wikipedia.org/wiki/Symlet>.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WaveletSymlet {
+    /// 2 vanishing moments, 4 taps. The same filters as [`WaveletDaubechies::D4`].
+    Sym2,
+    /// 3 vanishing moments, 6 taps. The same filters as [`WaveletDaubechies::D6`].
+    Sym3,
+    /// 4 vanishing moments, 8 taps.
+    #[default]
+    Sym4,
+    /// 5 vanishing moments, 10 taps.
+    Sym5,
+    /// 6 vanishing moments, 12 taps.
+    Sym6,
+    /// 7 vanishing moments, 14 taps.
+    Sym7,
+    /// 8 vanishing moments, 16 taps.
+    Sym8,
+    /// 9 vanishing moments, 18 taps.
+    Sym9,
+    /// 10 vanishing moments, 20 taps.
+    Sym10,
+}
+
+impl WaveletSymlet {
+    /// Returns the scaling filter, the coefficients of the approximation.
+    ///
+    /// The detail filter `g` is derived from it as `g[i] = (-1)^i · h[taps - 1 - i]`.
+    #[must_use]
+    pub const fn coefficients(self) -> &'static [f64] {
+        match self {
+            Self::Sym2 => &SYM2,
+            Self::Sym3 => &SYM3,
+            Self::Sym4 => &SYM4,
+            Self::Sym5 => &SYM5,
+            Self::Sym6 => &SYM6,
+            Self::Sym7 => &SYM7,
+            Self::Sym8 => &SYM8,
+            Self::Sym9 => &SYM9,
+            Self::Sym10 => &SYM10,
+        }
+    }
+    /// Returns the number of taps of the filters.
+    #[must_use]
+    pub const fn taps(self) -> usize {
+        self.coefficients().len()
+    }
+    /// Returns the number of vanishing moments of the wavelet.
+    #[must_use]
+    pub const fn vanishing_moments(self) -> usize {
+        self.taps() / 2
+    }
+}
+
+/* transform functions */
+
+macro_rules! impl_ortho {
+    () => { impl_ortho![f32, f64]; };
+    ($($f:ty),+) => { $( impl_ortho![@$f]; )+ };
+    (@$f:ty) => { paste! {
+        impl WaveletTransform<$f> for WaveletDaubechies {
+            fn forward_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                [<ortho_forward_ $f>](self.coefficients(), data, scratch)
+            }
+            fn inverse_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                [<ortho_inverse_ $f>](self.coefficients(), data, scratch)
+            }
+        }
+        impl WaveletTransform<$f> for WaveletSymlet {
+            fn forward_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                [<ortho_forward_ $f>](self.coefficients(), data, scratch)
+            }
+            fn inverse_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                [<ortho_inverse_ $f>](self.coefficients(), data, scratch)
+            }
+        }
+
+        /// Correlates `data` with the scaling filter `h` and its detail filter,
+        /// wrapping around, and keeps every other output.
+        fn [<ortho_forward_ $f>](h: &[f64], data: &mut [$f], scratch: &mut [$f])
+            -> Result<(), WaveletError> {
+            let (n, half) = (data.len(), check_level(data.len(), scratch.len())?);
+            let taps = h.len();
+            for k in 0..half {
+                let (mut approx, mut detail) = (0.0, 0.0);
+                for (i, &c) in h.iter().enumerate() {
+                    let x = data[(2 * k + i) % n];
+                    let g = h[taps - 1 - i];
+                    approx += c as $f * x;
+                    detail += is![i.is_multiple_of(2), g, -g] as $f * x;
+                }
+                scratch[k] = approx;
+                scratch[half + k] = detail;
+            }
+            data.copy_from_slice(&scratch[..n]);
+            Ok(())
+        }
+        /// Accumulates the transposed filters of each coefficient, wrapping around.
+        fn [<ortho_inverse_ $f>](h: &[f64], data: &mut [$f], scratch: &mut [$f])
+            -> Result<(), WaveletError> {
+            let (n, half) = (data.len(), check_level(data.len(), scratch.len())?);
+            let taps = h.len();
+            scratch[..n].fill(0.0);
+            for k in 0..half {
+                let (approx, detail) = (data[k], data[half + k]);
+                for (i, &c) in h.iter().enumerate() {
+                    let g = h[taps - 1 - i];
+                    let g = is![i.is_multiple_of(2), g, -g];
+                    scratch[(2 * k + i) % n] += c as $f * approx + g as $f * detail;
+                }
+            }
+            data.copy_from_slice(&scratch[..n]);
+            Ok(())
+        }
+    }};
+}
+impl_ortho!();
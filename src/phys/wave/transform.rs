@@ -0,0 +1,240 @@
+// devela/src/phys/wave/transform.rs
+//
+//! Defines [`WaveletTransform`].
+//
+// TOC
+// - trait WaveletTransform
+// - impls for WaveletHaar
+// - helpers
+
+use crate::{RasterBuf, WaveletError, WaveletHaar, is};
+
+#[doc = crate::_tags!(wave)]
+/// Wavelet transforms over caller buffers, without allocating.
+#[doc = crate::_doc_meta!{location("phys/wave")}]
+///
+/// Each level splits a signal into its approximation, stored in the first half,
+/// and its detail, stored in the second half. Further levels recurse over the
+/// approximation, so `data.len()` must be divisible by `2^levels`.
+///
+/// Every method needs a `scratch` buffer of at least as many samples as `data`,
+/// or `width.max(2 * height)` samples for the 2D methods.
+///
+/// It's implemented for `f32` and `f64` by [`WaveletHaar`], [`WaveletDaubechies`],
+/// [`WaveletSymlet`] and [`WaveletCdf97`].
+///
+/// [`WaveletDaubechies`]: crate::WaveletDaubechies
+/// [`WaveletSymlet`]: crate::WaveletSymlet
+/// [`WaveletCdf97`]: crate::WaveletCdf97
+pub trait WaveletTransform<T: Copy> {
+    /// Computes one level of the forward transform of `data` in place.
+    ///
+    /// # Errors
+    /// Returns [`WaveletError::InvalidLength`] if the length of `data` is odd,
+    /// or [`WaveletError::NotEnoughSpace`] if `scratch` is shorter than `data`.
+    fn forward_level(&self, data: &mut [T], scratch: &mut [T]) -> Result<(), WaveletError>;
+
+    /// Computes one level of the inverse transform of `data` in place.
+    ///
+    /// # Errors
+    /// Returns [`WaveletError::InvalidLength`] if the length of `data` is odd,
+    /// or [`WaveletError::NotEnoughSpace`] if `scratch` is shorter than `data`.
+    fn inverse_level(&self, data: &mut [T], scratch: &mut [T]) -> Result<(), WaveletError>;
+
+    /* provided */
+
+    /// Decomposes `data` in place by `levels` levels of the forward transform.
+    ///
+    /// # Errors
+    /// Returns [`WaveletError::InvalidLength`] if the length of `data` isn't
+    /// divisible by `2^levels`, or [`WaveletError::NotEnoughSpace`] if `scratch`
+    /// is shorter than `data`.
+    fn decompose(
+        &self,
+        data: &mut [T],
+        levels: usize,
+        scratch: &mut [T],
+    ) -> Result<(), WaveletError> {
+        check_levels(data.len(), levels)?;
+        check_scratch(scratch.len(), data.len())?;
+        let len = data.len();
+        for level in 0..levels {
+            self.forward_level(&mut data[..len >> level], scratch)?;
+        }
+        Ok(())
+    }
+
+    /// Reconstructs `data` in place from `levels` levels of the inverse transform.
+    ///
+    /// # Errors
+    /// Returns [`WaveletError::InvalidLength`] if the length of `data` isn't
+    /// divisible by `2^levels`, or [`WaveletError::NotEnoughSpace`] if `scratch`
+    /// is shorter than `data`.
+    fn reconstruct(
+        &self,
+        data: &mut [T],
+        levels: usize,
+        scratch: &mut [T],
+    ) -> Result<(), WaveletError> {
+        check_levels(data.len(), levels)?;
+        check_scratch(scratch.len(), data.len())?;
+        let len = data.len();
+        for level in (0..levels).rev() {
+            self.inverse_level(&mut data[..len >> level], scratch)?;
+        }
+        Ok(())
+    }
+
+    /// Decomposes `raster` in place by `levels` levels of the forward 2D transform.
+    ///
+    /// Each level transforms the rows and then the columns of the upper-left
+    /// approximation left by the previous one, leaving the next approximation
+    /// in its upper-left quarter, surrounded by the horizontal, vertical and
+    /// diagonal details.
+    ///
+    /// # Errors
+    /// Returns [`WaveletError::InvalidLength`] if the width or the height aren't
+    /// divisible by `2^levels`, or [`WaveletError::NotEnoughSpace`] if `scratch`
+    /// is shorter than `width.max(2 * height)`.
+    fn decompose_2d<R>(
+        &self,
+        raster: &mut R,
+        levels: usize,
+        scratch: &mut [T],
+    ) -> Result<(), WaveletError>
+    where
+        R: RasterBuf<Sample = T> + ?Sized,
+    {
+        let [w, h] = raster.raster_extent().dim.map(|n| n as usize);
+        check_2d(w, h, levels, scratch.len())?;
+        let samples = raster.raster_samples_mut();
+        for level in 0..levels {
+            let (lw, lh) = (w >> level, h >> level);
+            for row in samples.chunks_exact_mut(w).take(lh) {
+                self.forward_level(&mut row[..lw], scratch)?;
+            }
+            for x in 0..lw {
+                by_column(samples, w, x, lh, scratch, |c, s| self.forward_level(c, s))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reconstructs `raster` in place from `levels` levels of the inverse 2D transform.
+    ///
+    /// # Errors
+    /// Returns [`WaveletError::InvalidLength`] if the width or the height aren't
+    /// divisible by `2^levels`, or [`WaveletError::NotEnoughSpace`] if `scratch`
+    /// is shorter than `width.max(2 * height)`.
+    fn reconstruct_2d<R>(
+        &self,
+        raster: &mut R,
+        levels: usize,
+        scratch: &mut [T],
+    ) -> Result<(), WaveletError>
+    where
+        R: RasterBuf<Sample = T> + ?Sized,
+    {
+        let [w, h] = raster.raster_extent().dim.map(|n| n as usize);
+        check_2d(w, h, levels, scratch.len())?;
+        let samples = raster.raster_samples_mut();
+        for level in (0..levels).rev() {
+            let (lw, lh) = (w >> level, h >> level);
+            for x in 0..lw {
+                by_column(samples, w, x, lh, scratch, |c, s| self.inverse_level(c, s))?;
+            }
+            for row in samples.chunks_exact_mut(w).take(lh) {
+                self.inverse_level(&mut row[..lw], scratch)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* impls for WaveletHaar */
+
+macro_rules! impl_haar {
+    () => { impl_haar![f32, f64]; };
+    ($($f:ty),+) => { $( impl_haar![@$f]; )+ };
+    (@$f:ty) => {
+        /// Uses the averages and half differences of each pair, like [`WaveletTransformVec`].
+        ///
+        /// [`WaveletTransformVec`]: crate::WaveletTransformVec
+        impl WaveletTransform<$f> for WaveletHaar {
+            fn forward_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                let half = check_level(data.len(), scratch.len())?;
+                for i in 0..half {
+                    let (a, b) = (data[2 * i], data[2 * i + 1]);
+                    scratch[i] = (a + b) / 2.0;
+                    scratch[half + i] = (a - b) / 2.0;
+                }
+                data.copy_from_slice(&scratch[..data.len()]);
+                Ok(())
+            }
+            fn inverse_level(&self, data: &mut [$f], scratch: &mut [$f])
+                -> Result<(), WaveletError> {
+                let half = check_level(data.len(), scratch.len())?;
+                for i in 0..half {
+                    let (average, difference) = (data[i], data[half + i]);
+                    scratch[2 * i] = average + difference;
+                    scratch[2 * i + 1] = average - difference;
+                }
+                data.copy_from_slice(&scratch[..data.len()]);
+                Ok(())
+            }
+        }
+    };
+}
+impl_haar!();
+
+/* helpers */
+
+/// Checks a single level over `len` samples, and returns half of it.
+pub(super) const fn check_level(len: usize, scratch: usize) -> Result<usize, WaveletError> {
+    is![!len.is_multiple_of(2), return Err(WaveletError::InvalidLength)];
+    is![scratch < len, return Err(WaveletError::NotEnoughSpace(len))];
+    Ok(len / 2)
+}
+
+/// Checks that `len` is divisible by `2^levels`.
+const fn check_levels(len: usize, levels: usize) -> Result<(), WaveletError> {
+    is![levels == 0, return Ok(())];
+    match 1_usize.checked_shl(levels as u32) {
+        Some(step) if levels < usize::BITS as usize && len.is_multiple_of(step) => Ok(()),
+        _ => Err(WaveletError::InvalidLength),
+    }
+}
+
+const fn check_scratch(len: usize, needed: usize) -> Result<(), WaveletError> {
+    is![len < needed, return Err(WaveletError::NotEnoughSpace(needed))];
+    Ok(())
+}
+
+/// Checks a 2D transform of `levels` over a `w`×`h` raster.
+fn check_2d(w: usize, h: usize, levels: usize, scratch: usize) -> Result<(), WaveletError> {
+    check_levels(w, levels)?;
+    check_levels(h, levels)?;
+    check_scratch(scratch, w.max(2 * h))
+}
+
+/// Gathers the first `len` samples of column `x` into `scratch`, calls `f` on them
+/// with the rest of the `scratch`, and scatters them back.
+fn by_column<T: Copy>(
+    samples: &mut [T],
+    width: usize,
+    x: usize,
+    len: usize,
+    scratch: &mut [T],
+    f: impl FnOnce(&mut [T], &mut [T]) -> Result<(), WaveletError>,
+) -> Result<(), WaveletError> {
+    let (column, rest) = scratch.split_at_mut(len);
+    for (y, c) in column.iter_mut().enumerate() {
+        *c = samples[y * width + x];
+    }
+    f(column, rest)?;
+    for (y, c) in column.iter().enumerate() {
+        samples[y * width + x] = *c;
+    }
+    Ok(())
+}
@@ -12,7 +12,7 @@
 
 #![allow(nonstandard_style)]
 
-use crate::{c_char, c_int, c_long, c_uint, c_ulong, c_void};
+use crate::{c_char, c_int, c_long, c_size_t, c_ssize_t, c_uchar, c_uint, c_ulong, c_void};
 
 /// The handle for the PCM device.
 #[repr(C)]
@@ -123,6 +123,106 @@ pub(crate) const SND_PCM_FORMAT_S24_3LE: snd_pcm_format_t = 32;
 // pub(crate) const SND_PCM_FORMAT_U20: snd_pcm_format_t = 27;
 pub(crate) type snd_pcm_format_t = c_int;
 
+/* raw MIDI */
+
+/// The handle for the raw MIDI device.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct snd_rawmidi_t {
+    _unused: [u8; 0],
+}
+
+// pub(crate) const SND_RAWMIDI_APPEND: c_int = 0x0001;
+// pub(crate) const SND_RAWMIDI_NONBLOCK: c_int = 0x0002;
+
+/* sequencer */
+
+/// The handle for the sequencer.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct snd_seq_t {
+    _unused: [u8; 0],
+}
+/// Sequencer client information container.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct snd_seq_client_info_t {
+    _unused: [u8; 0],
+}
+/// Sequencer port information container.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct snd_seq_port_info_t {
+    _unused: [u8; 0],
+}
+/// Converter between MIDI byte streams and sequencer events.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct snd_midi_event_t {
+    _unused: [u8; 0],
+}
+
+/// The address of a sequencer client port.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct snd_seq_addr_t {
+    pub(crate) client: c_uchar,
+    pub(crate) port: c_uchar,
+}
+/// A sequencer event.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct snd_seq_event_t {
+    pub(crate) r#type: c_uchar,
+    pub(crate) flags: c_uchar,
+    pub(crate) tag: c_uchar,
+    pub(crate) queue: c_uchar,
+    /// Either a tick, or seconds and nanoseconds.
+    pub(crate) time: [c_uint; 2],
+    pub(crate) source: snd_seq_addr_t,
+    pub(crate) dest: snd_seq_addr_t,
+    /// The event data union, whose largest member is the packed variable-length pointer.
+    pub(crate) data: [c_uint; 3],
+}
+
+/// Open the sequencer for output.
+pub(crate) const SND_SEQ_OPEN_OUTPUT: c_int = 1;
+/// Open the sequencer for input.
+pub(crate) const SND_SEQ_OPEN_INPUT: c_int = 2;
+/// Open the sequencer for both output and input.
+pub(crate) const SND_SEQ_OPEN_DUPLEX: c_int = SND_SEQ_OPEN_OUTPUT | SND_SEQ_OPEN_INPUT;
+// pub(crate) const SND_SEQ_NONBLOCK: c_int = 0x0001;
+
+/// The port can be read from.
+pub(crate) const SND_SEQ_PORT_CAP_READ: c_uint = 1 << 0;
+/// The port can be written to.
+pub(crate) const SND_SEQ_PORT_CAP_WRITE: c_uint = 1 << 1;
+// pub(crate) const SND_SEQ_PORT_CAP_SYNC_READ: c_uint = 1 << 2;
+// pub(crate) const SND_SEQ_PORT_CAP_SYNC_WRITE: c_uint = 1 << 3;
+// pub(crate) const SND_SEQ_PORT_CAP_DUPLEX: c_uint = 1 << 4;
+/// The port allows read subscriptions.
+pub(crate) const SND_SEQ_PORT_CAP_SUBS_READ: c_uint = 1 << 5;
+/// The port allows write subscriptions.
+pub(crate) const SND_SEQ_PORT_CAP_SUBS_WRITE: c_uint = 1 << 6;
+/// The port is hidden from routing.
+pub(crate) const SND_SEQ_PORT_CAP_NO_EXPORT: c_uint = 1 << 7;
+
+/// The port understands generic MIDI messages.
+pub(crate) const SND_SEQ_PORT_TYPE_MIDI_GENERIC: c_uint = 1 << 1;
+// pub(crate) const SND_SEQ_PORT_TYPE_HARDWARE: c_uint = 1 << 16;
+// pub(crate) const SND_SEQ_PORT_TYPE_SOFTWARE: c_uint = 1 << 17;
+// pub(crate) const SND_SEQ_PORT_TYPE_SYNTHESIZER: c_uint = 1 << 18;
+// pub(crate) const SND_SEQ_PORT_TYPE_PORT: c_uint = 1 << 19;
+/// The port belongs to an application.
+pub(crate) const SND_SEQ_PORT_TYPE_APPLICATION: c_uint = 1 << 20;
+
+/// The destination client of events sent to every subscriber.
+pub(crate) const SND_SEQ_ADDRESS_SUBSCRIBERS: c_uchar = 254;
+/// An unknown port address.
+pub(crate) const SND_SEQ_ADDRESS_UNKNOWN: c_uchar = 253;
+/// The queue of events delivered immediately.
+pub(crate) const SND_SEQ_QUEUE_DIRECT: c_uchar = 253;
+
 #[rustfmt::skip]
 #[cfg(ffi_alsa··)]
 #[link(name = "asound")]
@@ -185,6 +285,104 @@ unsafe extern "C" {
     /// Free hardware parameters.
     pub(crate) fn snd_pcm_hw_params_free(obj: *mut snd_pcm_hw_params_t);
 
+    /// Open a raw MIDI input and/or output handle.
+    pub(crate) fn snd_rawmidi_open(input: *mut *mut snd_rawmidi_t, output: *mut *mut snd_rawmidi_t,
+        name: *const c_char, mode: c_int) -> c_int;
+    /// Close a raw MIDI handle.
+    pub(crate) fn snd_rawmidi_close(rawmidi: *mut snd_rawmidi_t) -> c_int;
+    /// Set the blocking mode of a raw MIDI handle.
+    pub(crate) fn snd_rawmidi_nonblock(rawmidi: *mut snd_rawmidi_t, nonblock: c_int) -> c_int;
+    /// Read MIDI bytes.
+    pub(crate) fn snd_rawmidi_read(rawmidi: *mut snd_rawmidi_t, buffer: *mut c_void,
+        size: c_size_t) -> c_ssize_t;
+    /// Write MIDI bytes.
+    pub(crate) fn snd_rawmidi_write(rawmidi: *mut snd_rawmidi_t, buffer: *const c_void,
+        size: c_size_t) -> c_ssize_t;
+    /// Wait until the written MIDI bytes have been transmitted.
+    pub(crate) fn snd_rawmidi_drain(rawmidi: *mut snd_rawmidi_t) -> c_int;
+
+    /// Open a sequencer handle.
+    pub(crate) fn snd_seq_open(seq: *mut *mut snd_seq_t, name: *const c_char, streams: c_int,
+        mode: c_int) -> c_int;
+    /// Close a sequencer handle.
+    pub(crate) fn snd_seq_close(seq: *mut snd_seq_t) -> c_int;
+    /// Set the blocking mode of a sequencer handle.
+    pub(crate) fn snd_seq_nonblock(seq: *mut snd_seq_t, nonblock: c_int) -> c_int;
+    /// Get the client id of a sequencer handle.
+    pub(crate) fn snd_seq_client_id(seq: *mut snd_seq_t) -> c_int;
+    /// Set the name of the sequencer client.
+    pub(crate) fn snd_seq_set_client_name(seq: *mut snd_seq_t, name: *const c_char) -> c_int;
+    /// Create a port with the given capabilities and type. Returns the port number.
+    pub(crate) fn snd_seq_create_simple_port(seq: *mut snd_seq_t, name: *const c_char,
+        caps: c_uint, r#type: c_uint) -> c_int;
+    /// Delete a port created with `snd_seq_create_simple_port`.
+    pub(crate) fn snd_seq_delete_simple_port(seq: *mut snd_seq_t, port: c_int) -> c_int;
+    /// Subscribe a port to the events of a source port.
+    pub(crate) fn snd_seq_connect_from(seq: *mut snd_seq_t, my_port: c_int, src_client: c_int,
+        src_port: c_int) -> c_int;
+    /// Subscribe a destination port to the events of a port.
+    pub(crate) fn snd_seq_connect_to(seq: *mut snd_seq_t, my_port: c_int, dest_client: c_int,
+        dest_port: c_int) -> c_int;
+    /// Remove the subscription of a port to a source port.
+    pub(crate) fn snd_seq_disconnect_from(seq: *mut snd_seq_t, my_port: c_int,
+        src_client: c_int, src_port: c_int) -> c_int;
+    /// Remove the subscription of a destination port to a port.
+    pub(crate) fn snd_seq_disconnect_to(seq: *mut snd_seq_t, my_port: c_int,
+        dest_client: c_int, dest_port: c_int) -> c_int;
+
+    /// Allocate client information.
+    pub(crate) fn snd_seq_client_info_malloc(ptr: *mut *mut snd_seq_client_info_t) -> c_int;
+    /// Free client information.
+    pub(crate) fn snd_seq_client_info_free(obj: *mut snd_seq_client_info_t);
+    /// Set the client id of client information.
+    pub(crate) fn snd_seq_client_info_set_client(info: *mut snd_seq_client_info_t, client: c_int);
+    /// Get the client id of client information.
+    pub(crate) fn snd_seq_client_info_get_client(info: *const snd_seq_client_info_t) -> c_int;
+    /// Get the client name of client information.
+    pub(crate) fn snd_seq_client_info_get_name(info: *mut snd_seq_client_info_t)
+        -> *const c_char;
+    /// Query the client following the one of the given client information.
+    pub(crate) fn snd_seq_query_next_client(seq: *mut snd_seq_t,
+        info: *mut snd_seq_client_info_t) -> c_int;
+    /// Allocate port information.
+    pub(crate) fn snd_seq_port_info_malloc(ptr: *mut *mut snd_seq_port_info_t) -> c_int;
+    /// Free port information.
+    pub(crate) fn snd_seq_port_info_free(obj: *mut snd_seq_port_info_t);
+    /// Set the client id of port information.
+    pub(crate) fn snd_seq_port_info_set_client(info: *mut snd_seq_port_info_t, client: c_int);
+    /// Set the port number of port information.
+    pub(crate) fn snd_seq_port_info_set_port(info: *mut snd_seq_port_info_t, port: c_int);
+    /// Get the port number of port information.
+    pub(crate) fn snd_seq_port_info_get_port(info: *const snd_seq_port_info_t) -> c_int;
+    /// Get the port name of port information.
+    pub(crate) fn snd_seq_port_info_get_name(info: *const snd_seq_port_info_t) -> *const c_char;
+    /// Get the capabilities of port information.
+    pub(crate) fn snd_seq_port_info_get_capability(info: *const snd_seq_port_info_t) -> c_uint;
+    /// Query the port following the one of the given port information.
+    pub(crate) fn snd_seq_query_next_port(seq: *mut snd_seq_t, info: *mut snd_seq_port_info_t)
+        -> c_int;
+
+    /// Output an event bypassing the output buffer.
+    pub(crate) fn snd_seq_event_output_direct(seq: *mut snd_seq_t, ev: *mut snd_seq_event_t)
+        -> c_int;
+    /// Retrieve an event, owned by the input buffer.
+    pub(crate) fn snd_seq_event_input(seq: *mut snd_seq_t, ev: *mut *mut snd_seq_event_t)
+        -> c_int;
+
+    /// Create a MIDI event converter with a buffer for system exclusive data.
+    pub(crate) fn snd_midi_event_new(bufsize: c_size_t, rdev: *mut *mut snd_midi_event_t)
+        -> c_int;
+    /// Free a MIDI event converter.
+    pub(crate) fn snd_midi_event_free(dev: *mut snd_midi_event_t);
+    /// Enable (`on == 0`) or disable running status when decoding.
+    pub(crate) fn snd_midi_event_no_status(dev: *mut snd_midi_event_t, on: c_int);
+    /// Encode MIDI bytes into an event. Returns the number of bytes consumed.
+    pub(crate) fn snd_midi_event_encode(dev: *mut snd_midi_event_t, buf: *const c_uchar,
+        count: c_long, ev: *mut snd_seq_event_t) -> c_long;
+    /// Decode an event into MIDI bytes. Returns the number of bytes written.
+    pub(crate) fn snd_midi_event_decode(dev: *mut snd_midi_event_t, buf: *mut c_uchar,
+        count: c_long, ev: *const snd_seq_event_t) -> c_long;
+
     /// Return a string describing an ALSA error code.
     pub(crate) fn snd_strerror(errnum: c_int) -> *const c_char;
 }
@@ -26,3 +26,63 @@ fn alsa_list_pcm_devices() -> Result<(), AlsaError> {
     assert!(count > 0);
     Ok(())
 }
+
+#[test]
+fn alsa_seq_addr_and_would_block() {
+    use crate::AlsaSeqAddr;
+    assert_eq!(crate::format_buf![&mut [0; 8], "{}", AlsaSeqAddr::new(128, 3)], Ok("128:3"));
+    assert!(AlsaError::Code(-11).is_would_block());
+    assert!(!AlsaError::Code(-2).is_would_block());
+}
+#[test]
+#[cfg(ffi_alsa··)]
+fn alsa_list_rawmidi_devices() -> Result<(), AlsaError> {
+    // a headless box may have no MIDI hardware at all
+    Alsa::for_each_rawmidi_device(|dev| {
+        assert!(!dev.id.is_empty());
+        Ok(())
+    })
+}
+#[test]
+#[cfg(ffi_alsa··)]
+fn alsa_seq_virtual_port_loopback() -> Result<(), AlsaError> {
+    use crate::{AudioDeviceDir, MidiMessage};
+    let mut seq = Alsa::open_seq(c"devela test")?;
+    let input = seq.create_input_port(c"in")?;
+    let output = seq.create_output_port(c"out")?;
+    let mut found = 0;
+    let client = seq.client();
+    seq.for_each_port(|port| {
+        if port.addr.client == client {
+            let dir = if port.name == "in" {
+                AudioDeviceDir::Playback
+            } else {
+                AudioDeviceDir::Capture
+            };
+            assert_eq!(port.dir, dir);
+            assert_eq!(port.client_name, "devela test");
+            found += 1;
+        }
+        Ok(())
+    })?;
+    assert_eq!(found, 2);
+
+    seq.connect_to(output, seq.addr(input))?;
+    let note = MidiMessage::NoteOn { channel: 2, note: 64, velocity: 90 };
+    seq.send(output, &note)?;
+    seq.send(output, &MidiMessage::SysEx(&[0x7D, 1, 2, 3]))?;
+    let mut buf = [0; 16];
+    let (from, len) = seq.receive(&mut buf)?;
+    assert_eq!(from, seq.addr(output));
+    assert_eq!(MidiMessage::read(&buf[..len]), Ok((note, 3)));
+    let (_, len) = seq.receive(&mut buf)?;
+    assert_eq!(&buf[..len], &[0xF0, 0x7D, 1, 2, 3, 0xF7]);
+
+    seq.disconnect_to(output, seq.addr(input))?;
+    seq.set_nonblocking(true)?;
+    seq.send(output, &note)?;
+    assert!(seq.receive(&mut buf).is_err_and(|e| e.is_would_block()));
+    seq.delete_port(input)?;
+    seq.delete_port(output)?;
+    Ok(())
+}
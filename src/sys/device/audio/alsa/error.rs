@@ -9,7 +9,7 @@ use crate::{_alsa_raw as _raw, c_int, impl_trait};
 use crate::{PcmLayout, PcmSample, PcmSpec};
 
 #[doc = crate::_tags!(audio linux error)]
-/// ALSA PCM error.
+/// ALSA error.
 #[doc = crate::_doc_meta!{
     location("sys/device/audio"),
     #[cfg(target_pointer_width = "64")]
@@ -38,8 +38,12 @@ pub enum AlsaError {
     NoProgress,
     /// ALSA reported more frames than were requested.
     FrameCountExceeded { read_or_written: usize, requested: usize },
+    /// ALSA reported more bytes than were requested.
+    ByteCountExceeded { read_or_written: usize, requested: usize },
     /// Too many channels for the fixed channel-pointer buffer.
     TooManyChannels { count: usize, max: usize },
+    /// The MIDI message has a field out of range.
+    InvalidMidi,
     /// Native ALSA support was not compiled in.
     Unavailable,
 }
@@ -53,6 +57,11 @@ impl AlsaError {
     pub const fn result(code: c_int) -> Result<(), Self> {
         if code < 0 { Err(Self::Code(code)) } else { Ok(()) }
     }
+    /// Returns whether a non-blocking operation had nothing to do (`EAGAIN`).
+    #[must_use]
+    pub const fn is_would_block(&self) -> bool {
+        matches![self, Self::Code(-11)]
+    }
     /// The stream is not configured.
     #[must_use]
     pub const fn unconfigured() -> Self {
@@ -98,6 +107,11 @@ impl AlsaError {
     pub const fn frame_count_exceeded(read_or_written: usize, requested: usize) -> Self {
         Self::FrameCountExceeded { read_or_written, requested }
     }
+    /// ALSA reported more bytes than were requested.
+    #[must_use]
+    pub const fn byte_count_exceeded(read_or_written: usize, requested: usize) -> Self {
+        Self::ByteCountExceeded { read_or_written, requested }
+    }
     /// Too many channels for the fixed channel-pointer buffer.
     #[must_use]
     pub const fn too_many_channels(count: usize, max: usize) -> Self {
@@ -133,10 +147,13 @@ impl_trait![fmt::Display+Error for AlsaError |self, f| match *self {
     Self::IncompleteFrames =>
         f.write_str("PCM buffer does not contain complete frames"),
     Self::NoProgress =>
-        f.write_str("ALSA PCM operation made no progress"),
+        f.write_str("ALSA operation made no progress"),
     Self::FrameCountExceeded { read_or_written, requested } =>
         write![f, "ALSA PCM operation exceeded requested frames: got {read_or_written}, requested {requested}"],
+    Self::ByteCountExceeded { read_or_written, requested } =>
+        write![f, "ALSA operation exceeded requested bytes: got {read_or_written}, requested {requested}"],
     Self::TooManyChannels { count, max } =>
         write![f, "Too many PCM channels: got {count}, maximum supported here is {max}"],
+    Self::InvalidMidi => f.write_str("MIDI message has a field out of range"),
     Self::Unavailable => f.write_str("ALSA native library `asound` was not found)"),
 }];
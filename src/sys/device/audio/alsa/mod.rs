@@ -22,6 +22,8 @@ mod hint;
 mod error; // AlsaError
 mod namespace; // Alsa
 mod pcm; // AlsaPcmHandle
+mod rawmidi; // AlsaRawMidiHandle
+mod seq; // AlsaSeqHandle, AlsaSeqAddr, AlsaSeqPort
 
 crate::structural_mods! { // _mods, _crate_internals
     _mods {
@@ -29,6 +31,8 @@ crate::structural_mods! { // _mods, _crate_internals
             error::*,
             namespace::*,
             pcm::*,
+            rawmidi::*,
+            seq::*,
         };
     }
     _crate_internals {
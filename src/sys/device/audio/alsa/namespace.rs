@@ -4,9 +4,10 @@
 //
 
 use super::_raw;
-use crate::{AlsaError, AlsaPcmHandle, AudioDevice, AudioDeviceDir, AudioStreamDir};
+use crate::{AlsaError, AlsaPcmHandle, AlsaRawMidiHandle, AlsaSeqHandle};
 #[cfg(ffi_alsa··)]
 use crate::{AlsaHintList, AlsaHintValue};
+use crate::{AudioDevice, AudioDeviceDir, AudioStreamDir};
 #[cfg(all(ffi_alsa··, feature = "alloc"))]
 use crate::{AudioDeviceCow, Cow, ToString, Vec};
 use crate::{CStr, Ptr};
@@ -97,11 +98,87 @@ impl Alsa {
     /// Returns [`AlsaError`] if ALSA fails to enumerate device hints
     /// or if the callback returns an error.
     pub fn for_each_pcm_device(
+        f: impl FnMut(AudioDevice<'_>) -> Result<(), AlsaError>,
+    ) -> Result<(), AlsaError> {
+        Self::for_each_hint(c"pcm", f)
+    }
+
+    /* MIDI */
+
+    /// Opens an ALSA raw MIDI device, like `c"hw:1,0,0"`.
+    ///
+    /// A [`Playback`][AudioStreamDir::Playback] stream sends MIDI to the device,
+    /// and a [`Capture`][AudioStreamDir::Capture] stream receives MIDI from it.
+    pub fn open_rawmidi(id: &CStr, dir: AudioStreamDir) -> Result<AlsaRawMidiHandle, AlsaError> {
+        unsafe {
+            let mut handle = Ptr::null_mut();
+            let (input, output) = match dir {
+                AudioStreamDir::Capture => (&raw mut handle, Ptr::null_mut()),
+                AudioStreamDir::Playback => (Ptr::null_mut(), &raw mut handle),
+            };
+            AlsaError::result(_raw::snd_rawmidi_open(input, output, id.as_ptr(), 0))?;
+            Ok(AlsaRawMidiHandle::from_raw(handle, dir))
+        }
+    }
+
+    /// Returns the ALSA raw MIDI device hints as owned device descriptions.
+    ///
+    /// This is the allocating counterpart to
+    /// [`for_each_rawmidi_device`](Self::for_each_rawmidi_device).
+    ///
+    /// # Errors
+    /// Returns [`AlsaError`] if ALSA fails to enumerate device hints.
+    #[cfg(feature = "alloc")]
+    pub fn rawmidi_devices() -> Result<Vec<AudioDeviceCow<'static>>, AlsaError> {
+        let mut out = Vec::new();
+        Self::for_each_rawmidi_device(|dev| {
+            out.push(AudioDeviceCow {
+                id: Cow::Owned(dev.id.to_string()),
+                name: dev.name.map(|s| Cow::Owned(s.to_string())),
+                desc: dev.desc.map(|s| Cow::Owned(s.to_string())),
+                dir: dev.dir,
+            });
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    /// Calls `f` for each ALSA raw MIDI device hint.
+    ///
+    /// The callback receives a backend-neutral [`AudioDevice`] whose `id`
+    /// can be passed back to [`open_rawmidi`](Self::open_rawmidi).
+    ///
+    /// Like [`for_each_pcm_device`](Self::for_each_pcm_device),
+    /// this method does not allocate Rust-owned storage.
+    ///
+    /// # Errors
+    /// Returns [`AlsaError`] if ALSA fails to enumerate device hints
+    /// or if the callback returns an error.
+    pub fn for_each_rawmidi_device(
+        f: impl FnMut(AudioDevice<'_>) -> Result<(), AlsaError>,
+    ) -> Result<(), AlsaError> {
+        Self::for_each_hint(c"rawmidi", f)
+    }
+
+    /// Opens a new ALSA sequencer client named `name`.
+    ///
+    /// The client can create ports, enumerate the ports of other clients,
+    /// and subscribe them to each other. A port created this way is virtual:
+    /// it doesn't need any MIDI hardware.
+    pub fn open_seq(name: &CStr) -> Result<AlsaSeqHandle, AlsaError> {
+        AlsaSeqHandle::open(name)
+    }
+
+    /* helpers */
+
+    /// Calls `f` for each device hint of the `iface` interface.
+    fn for_each_hint(
+        iface: &CStr,
         mut f: impl FnMut(AudioDevice<'_>) -> Result<(), AlsaError>,
     ) -> Result<(), AlsaError> {
         unsafe {
             let mut hints = Ptr::null_mut();
-            AlsaError::result(_raw::snd_device_name_hint(-1, c"pcm".as_ptr(), &mut hints))?;
+            AlsaError::result(_raw::snd_device_name_hint(-1, iface.as_ptr(), &mut hints))?;
             let hints = AlsaHintList { raw: hints };
             let mut p = hints.raw;
             while !p.is_null() && !(*p).is_null() {
@@ -136,5 +213,10 @@ crate::items! {
         pub fn open_default_capture() -> Result<AlsaPcmHandle, AlsaError> { _unavailable!() }
         pub fn for_each_pcm_device(f: impl FnMut(AudioDevice<'_>) -> Result<(), AlsaError>)
             -> Result<(), AlsaError> { _unavailable!() }
+        pub fn open_rawmidi(id: &CStr, dir: AudioStreamDir)
+            -> Result<AlsaRawMidiHandle, AlsaError> { _unavailable!() }
+        pub fn for_each_rawmidi_device(f: impl FnMut(AudioDevice<'_>) -> Result<(), AlsaError>)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn open_seq(name: &CStr) -> Result<AlsaSeqHandle, AlsaError> { _unavailable!() }
     }
 }
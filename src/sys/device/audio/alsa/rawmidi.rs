@@ -0,0 +1,136 @@
+// devela/src/sys/device/audio/alsa/rawmidi.rs
+//
+//! Defines [`AlsaRawMidiHandle`].
+//
+
+use super::_raw;
+use crate::{AlsaError, AudioStreamDir, MidiMessage, c_int, c_ssize_t, is};
+
+#[doc = crate::_tags!(audio linux guard)]
+/// Owned ALSA raw MIDI stream handle.
+#[doc = crate::_doc_meta!{
+    location("sys/device/audio"),
+    #[cfg(target_pointer_width = "32")]
+    test_size_of(AlsaRawMidiHandle = 8|64),
+    #[cfg(target_pointer_width = "64")]
+    test_size_of(AlsaRawMidiHandle = 16|128),
+}]
+///
+/// Carries the bytes of a MIDI port in one direction, as they are on the wire.
+/// Use a [`MidiParser`][crate::MidiParser] to get messages from the bytes read.
+///
+/// Opened with [`Alsa::open_rawmidi`][crate::Alsa::open_rawmidi].
+#[derive(Debug)]
+pub struct AlsaRawMidiHandle {
+    raw: *mut _raw::snd_rawmidi_t,
+    dir: AudioStreamDir,
+}
+
+unsafe impl Send for AlsaRawMidiHandle {}
+#[cfg(ffi_alsa··)]
+impl Drop for AlsaRawMidiHandle {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = _raw::snd_rawmidi_close(self.raw);
+        }
+    }
+}
+
+impl AlsaRawMidiHandle {
+    /// Returns the direction of the stream.
+    ///
+    /// Playback streams are written to, and capture streams are read from.
+    #[must_use]
+    pub const fn dir(&self) -> AudioStreamDir {
+        self.dir
+    }
+}
+
+#[cfg(ffi_alsa··)]
+impl AlsaRawMidiHandle {
+    /// Sets whether reads and writes return [`AlsaError::is_would_block`]
+    /// errors instead of waiting.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<(), AlsaError> {
+        unsafe { AlsaError::result(_raw::snd_rawmidi_nonblock(self.raw, nonblocking as c_int)) }
+    }
+
+    /// Reads the available bytes into `buf`, waiting for some unless non-blocking.
+    /// Returns the number of bytes read.
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, AlsaError> {
+        unsafe {
+            let read = _raw::snd_rawmidi_read(self.raw, buf.as_mut_ptr().cast(), buf.len());
+            Self::io_result(read)
+        }
+    }
+
+    /// Writes bytes from `data`. Returns the number of bytes written.
+    pub fn write(&mut self, data: &[u8]) -> Result<usize, AlsaError> {
+        unsafe {
+            let written = _raw::snd_rawmidi_write(self.raw, data.as_ptr().cast(), data.len());
+            Self::io_result(written)
+        }
+    }
+    /// Writes all the bytes of `data`.
+    pub fn write_all(&mut self, mut data: &[u8]) -> Result<(), AlsaError> {
+        while !data.is_empty() {
+            let n = self.write(data)?;
+            is![n == 0, return Err(AlsaError::NoProgress)];
+            is![n > data.len(), return Err(AlsaError::byte_count_exceeded(n, data.len()))];
+            data = &data[n..];
+        }
+        Ok(())
+    }
+    /// Writes a complete `message`, with its status byte.
+    ///
+    /// # Errors
+    /// Returns [`AlsaError::InvalidMidi`] if a field of the message is out of range.
+    pub fn write_message(&mut self, message: &MidiMessage) -> Result<(), AlsaError> {
+        is![!message.is_valid(), return Err(AlsaError::InvalidMidi)];
+        if let MidiMessage::SysEx(data) = message {
+            self.write_all(&[0xF0])?;
+            self.write_all(data)?;
+            return self.write_all(&[0xF7]);
+        }
+        let mut buf = [0; 3];
+        let len = message.encode(&mut buf).map_err(|_| AlsaError::InvalidMidi)?;
+        self.write_all(&buf[..len])
+    }
+
+    /// Waits until the written bytes have been transmitted.
+    pub fn drain(&mut self) -> Result<(), AlsaError> {
+        unsafe { AlsaError::result(_raw::snd_rawmidi_drain(self.raw)) }
+    }
+}
+
+// Public ALSA surface preserved when the native backend is unavailable.
+#[cfg(not(ffi_alsa··))]
+crate::items! {
+    macro_rules! _unavailable { () => { Err(AlsaError::Unavailable) }; }
+    #[allow(missing_docs, unused_variables)]
+    impl AlsaRawMidiHandle {
+        pub fn set_nonblocking(&mut self, nonblocking: bool)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, AlsaError> { _unavailable!() }
+        pub fn write(&mut self, data: &[u8]) -> Result<usize, AlsaError> { _unavailable!() }
+        pub fn write_all(&mut self, data: &[u8]) -> Result<(), AlsaError> { _unavailable!() }
+        pub fn write_message(&mut self, message: &MidiMessage)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn drain(&mut self) -> Result<(), AlsaError> { _unavailable!() }
+    }
+}
+
+/* private helpers */
+
+#[cfg(ffi_alsa··)]
+impl AlsaRawMidiHandle {
+    pub(super) const unsafe fn from_raw(
+        raw: *mut _raw::snd_rawmidi_t,
+        dir: AudioStreamDir,
+    ) -> Self {
+        Self { raw, dir }
+    }
+    /// Converts a byte count or negative error code.
+    fn io_result(n: c_ssize_t) -> Result<usize, AlsaError> {
+        is![n < 0, Err(AlsaError::code(n as c_int)), Ok(n as usize)]
+    }
+}
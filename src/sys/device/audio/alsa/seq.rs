@@ -0,0 +1,409 @@
+// devela/src/sys/device/audio/alsa/seq.rs
+//
+//! Defines [`AlsaSeqHandle`], [`AlsaSeqAddr`], [`AlsaSeqPort`].
+//
+// TOC
+// - struct AlsaSeqAddr
+// - struct AlsaSeqPort
+// - struct AlsaSeqHandle
+// - internal helpers
+
+use super::_raw;
+use crate::{AlsaError, AudioDeviceDir, CStr, MidiMessage, Ptr, c_int, c_long, c_uint, is};
+
+#[doc = crate::_tags!(audio linux)]
+/// The address of an ALSA sequencer port.
+#[doc = crate::_doc_meta!{
+    location("sys/device/audio"),
+    test_size_of(AlsaSeqAddr = 2|16),
+}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AlsaSeqAddr {
+    /// The client number.
+    pub client: u8,
+    /// The port number in the client.
+    pub port: u8,
+}
+impl AlsaSeqAddr {
+    /// Returns a new address.
+    #[must_use]
+    pub const fn new(client: u8, port: u8) -> Self {
+        Self { client, port }
+    }
+}
+crate::impl_trait![fmt::Display for AlsaSeqAddr |self, f| {
+    crate::write![f, "{}:{}", self.client, self.port]
+}];
+
+#[doc = crate::_tags!(audio linux)]
+/// A port of an ALSA sequencer client, as seen during enumeration.
+#[doc = crate::_doc_meta!{location("sys/device/audio")}]
+///
+/// See [`AlsaSeqHandle::for_each_port`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AlsaSeqPort<'a> {
+    /// The address of the port.
+    pub addr: AlsaSeqAddr,
+    /// The name of the client owning the port.
+    pub client_name: &'a str,
+    /// The name of the port.
+    pub name: &'a str,
+    /// Whether the port can be captured from, played to, or both.
+    ///
+    /// Controllers are usually [`Capture`][AudioDeviceDir::Capture] ports,
+    /// and synthesizers [`Playback`][AudioDeviceDir::Playback] ports.
+    pub dir: AudioDeviceDir,
+}
+
+#[doc = crate::_tags!(audio linux guard)]
+/// Owned ALSA sequencer client handle.
+#[doc = crate::_doc_meta!{
+    location("sys/device/audio"),
+    #[cfg(target_pointer_width = "64")]
+    test_size_of(AlsaSeqHandle = 32|256),
+}]
+///
+/// A client owns ports that other clients can subscribe to,
+/// or that can be subscribed to the ports of other clients.
+/// Messages are sent from a port to all of its subscribers,
+/// and received from all the ports a port is subscribed to.
+///
+/// Opened with [`Alsa::open_seq`][crate::Alsa::open_seq].
+#[derive(Debug)]
+pub struct AlsaSeqHandle {
+    raw: *mut _raw::snd_seq_t,
+    /// Converts sent MIDI bytes into events.
+    encoder: *mut _raw::snd_midi_event_t,
+    /// Converts received events into MIDI bytes.
+    decoder: *mut _raw::snd_midi_event_t,
+    client: u8,
+}
+
+unsafe impl Send for AlsaSeqHandle {}
+#[cfg(ffi_alsa··)]
+impl Drop for AlsaSeqHandle {
+    fn drop(&mut self) {
+        unsafe {
+            is![!self.encoder.is_null(), _raw::snd_midi_event_free(self.encoder)];
+            is![!self.decoder.is_null(), _raw::snd_midi_event_free(self.decoder)];
+            let _ = _raw::snd_seq_close(self.raw);
+        }
+    }
+}
+
+impl AlsaSeqHandle {
+    /// The capacity for the system exclusive data of a sent event.
+    ///
+    /// Longer system exclusive messages are sent in several events.
+    pub const SYSEX_CHUNK: usize = 256;
+
+    /// Returns the client number.
+    #[must_use]
+    pub const fn client(&self) -> u8 {
+        self.client
+    }
+    /// Returns the address of the `port` of this client.
+    #[must_use]
+    pub const fn addr(&self, port: u8) -> AlsaSeqAddr {
+        AlsaSeqAddr::new(self.client, port)
+    }
+}
+
+#[cfg(ffi_alsa··)]
+impl AlsaSeqHandle {
+    /// Sets whether [`receive`][Self::receive] returns
+    /// an [`AlsaError::is_would_block`] error instead of waiting.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<(), AlsaError> {
+        unsafe { AlsaError::result(_raw::snd_seq_nonblock(self.raw, nonblocking as c_int)) }
+    }
+
+    /* ports */
+
+    /// Creates a port that receives MIDI, which other clients can subscribe to.
+    /// Returns its port number.
+    pub fn create_input_port(&mut self, name: &CStr) -> Result<u8, AlsaError> {
+        self.create_port(name, _raw::SND_SEQ_PORT_CAP_WRITE | _raw::SND_SEQ_PORT_CAP_SUBS_WRITE)
+    }
+    /// Creates a port that sends MIDI, which other clients can subscribe to.
+    /// Returns its port number.
+    pub fn create_output_port(&mut self, name: &CStr) -> Result<u8, AlsaError> {
+        self.create_port(name, _raw::SND_SEQ_PORT_CAP_READ | _raw::SND_SEQ_PORT_CAP_SUBS_READ)
+    }
+    /// Deletes a `port` of this client, removing its subscriptions.
+    pub fn delete_port(&mut self, port: u8) -> Result<(), AlsaError> {
+        unsafe { AlsaError::result(_raw::snd_seq_delete_simple_port(self.raw, port as c_int)) }
+    }
+
+    /// Calls `f` for each port of every client that can be subscribed to,
+    /// including the ports of this client.
+    ///
+    /// The names are borrowed from ALSA for the duration of the call,
+    /// and ports whose names aren't valid UTF-8 are skipped.
+    ///
+    /// # Errors
+    /// Returns [`AlsaError`] if ALSA fails to allocate the query,
+    /// or if the callback returns an error.
+    pub fn for_each_port(
+        &mut self,
+        mut f: impl FnMut(AlsaSeqPort<'_>) -> Result<(), AlsaError>,
+    ) -> Result<(), AlsaError> {
+        const READ: c_uint = _raw::SND_SEQ_PORT_CAP_READ | _raw::SND_SEQ_PORT_CAP_SUBS_READ;
+        const WRITE: c_uint = _raw::SND_SEQ_PORT_CAP_WRITE | _raw::SND_SEQ_PORT_CAP_SUBS_WRITE;
+        unsafe {
+            let client_info = ClientInfo::new()?;
+            let port_info = PortInfo::new()?;
+            _raw::snd_seq_client_info_set_client(client_info.raw, -1);
+            while _raw::snd_seq_query_next_client(self.raw, client_info.raw) >= 0 {
+                let client = _raw::snd_seq_client_info_get_client(client_info.raw);
+                let client_name = _raw::snd_seq_client_info_get_name(client_info.raw);
+                let Some(client_name) = str_from_ptr(client_name) else { continue };
+                _raw::snd_seq_port_info_set_client(port_info.raw, client);
+                _raw::snd_seq_port_info_set_port(port_info.raw, -1);
+                while _raw::snd_seq_query_next_port(self.raw, port_info.raw) >= 0 {
+                    let caps = _raw::snd_seq_port_info_get_capability(port_info.raw);
+                    is![caps & _raw::SND_SEQ_PORT_CAP_NO_EXPORT != 0, continue];
+                    let dir = match (caps & READ == READ, caps & WRITE == WRITE) {
+                        (true, true) => AudioDeviceDir::Duplex,
+                        (true, false) => AudioDeviceDir::Capture,
+                        (false, true) => AudioDeviceDir::Playback,
+                        (false, false) => continue,
+                    };
+                    let name = _raw::snd_seq_port_info_get_name(port_info.raw);
+                    let Some(name) = str_from_ptr(name) else { continue };
+                    let port = _raw::snd_seq_port_info_get_port(port_info.raw);
+                    let addr = AlsaSeqAddr::new(client as u8, port as u8);
+                    f(AlsaSeqPort { addr, client_name, name, dir })?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /* subscriptions */
+
+    /// Subscribes this client's `port` to the messages sent by `src`.
+    pub fn connect_from(&mut self, port: u8, src: AlsaSeqAddr) -> Result<(), AlsaError> {
+        unsafe {
+            let (client, src_port) = (src.client as c_int, src.port as c_int);
+            AlsaError::result(_raw::snd_seq_connect_from(self.raw, port as c_int, client, src_port))
+        }
+    }
+    /// Subscribes `dest` to the messages sent by this client's `port`.
+    pub fn connect_to(&mut self, port: u8, dest: AlsaSeqAddr) -> Result<(), AlsaError> {
+        unsafe {
+            let (client, dest_port) = (dest.client as c_int, dest.port as c_int);
+            AlsaError::result(_raw::snd_seq_connect_to(self.raw, port as c_int, client, dest_port))
+        }
+    }
+    /// Removes the subscription of this client's `port` to `src`.
+    pub fn disconnect_from(&mut self, port: u8, src: AlsaSeqAddr) -> Result<(), AlsaError> {
+        unsafe {
+            let (client, src_port) = (src.client as c_int, src.port as c_int);
+            let p = port as c_int;
+            AlsaError::result(_raw::snd_seq_disconnect_from(self.raw, p, client, src_port))
+        }
+    }
+    /// Removes the subscription of `dest` to this client's `port`.
+    pub fn disconnect_to(&mut self, port: u8, dest: AlsaSeqAddr) -> Result<(), AlsaError> {
+        unsafe {
+            let (client, dest_port) = (dest.client as c_int, dest.port as c_int);
+            let p = port as c_int;
+            AlsaError::result(_raw::snd_seq_disconnect_to(self.raw, p, client, dest_port))
+        }
+    }
+
+    /* messages */
+
+    /// Sends `message` from this client's `port` to its subscribers.
+    ///
+    /// # Errors
+    /// Returns [`AlsaError::InvalidMidi`] if a field of the message is out of range.
+    pub fn send(&mut self, port: u8, message: &MidiMessage) -> Result<(), AlsaError> {
+        is![!message.is_valid(), return Err(AlsaError::InvalidMidi)];
+        if let MidiMessage::SysEx(data) = message {
+            self.send_bytes(port, &[0xF0])?;
+            self.send_bytes(port, data)?;
+            return self.send_bytes(port, &[0xF7]);
+        }
+        let mut buf = [0; 3];
+        let len = message.encode(&mut buf).map_err(|_| AlsaError::InvalidMidi)?;
+        self.send_bytes(port, &buf[..len])
+    }
+    /// Sends the messages of a MIDI byte stream from this client's `port`
+    /// to its subscribers.
+    ///
+    /// A message may be split across calls, and running status is followed.
+    pub fn send_bytes(&mut self, port: u8, mut bytes: &[u8]) -> Result<(), AlsaError> {
+        while !bytes.is_empty() {
+            let mut ev = _raw::snd_seq_event_t::default();
+            let len = bytes.len().min(c_long::MAX as usize) as c_long;
+            let n =
+                unsafe { _raw::snd_midi_event_encode(self.encoder, bytes.as_ptr(), len, &mut ev) };
+            is![n < 0, return Err(AlsaError::code(n as c_int))];
+            is![n == 0, return Err(AlsaError::NoProgress)];
+            bytes = &bytes[(n as usize).min(bytes.len())..];
+            is![ev.r#type == SND_SEQ_EVENT_NONE, continue];
+            ev.source.port = port;
+            ev.dest = _raw::snd_seq_addr_t {
+                client: _raw::SND_SEQ_ADDRESS_SUBSCRIBERS,
+                port: _raw::SND_SEQ_ADDRESS_UNKNOWN,
+            };
+            ev.queue = _raw::SND_SEQ_QUEUE_DIRECT;
+            unsafe {
+                AlsaError::result(_raw::snd_seq_event_output_direct(self.raw, &mut ev))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Receives the next MIDI message sent to any port of this client,
+    /// waiting for one unless non-blocking.
+    ///
+    /// Writes the message bytes into `buf`, with their status byte,
+    /// and returns the address of the sender with the number of bytes written.
+    /// Use [`MidiMessage::read`] to get the message from the bytes.
+    ///
+    /// Events without a MIDI equivalent are skipped.
+    /// A long system exclusive message may arrive in several parts.
+    ///
+    /// # Errors
+    /// Returns [`AlsaError`] if ALSA fails to receive, or if `buf` is too short.
+    pub fn receive(&mut self, buf: &mut [u8]) -> Result<(AlsaSeqAddr, usize), AlsaError> {
+        const ENOENT: c_long = 2;
+        loop {
+            unsafe {
+                let mut ev = Ptr::null_mut();
+                let code = _raw::snd_seq_event_input(self.raw, &mut ev);
+                is![code < 0, return Err(AlsaError::code(code))];
+                is![ev.is_null(), continue];
+                let len = buf.len().min(c_long::MAX as usize) as c_long;
+                let n = _raw::snd_midi_event_decode(self.decoder, buf.as_mut_ptr(), len, ev);
+                is![n == -ENOENT, continue];
+                is![n < 0, return Err(AlsaError::code(n as c_int))];
+                let source = (*ev).source;
+                return Ok((AlsaSeqAddr::new(source.client, source.port), n as usize));
+            }
+        }
+    }
+}
+
+// Public ALSA surface preserved when the native backend is unavailable.
+#[cfg(not(ffi_alsa··))]
+crate::items! {
+    macro_rules! _unavailable { () => { Err(AlsaError::Unavailable) }; }
+    #[allow(missing_docs, unused_variables)]
+    impl AlsaSeqHandle {
+        pub fn set_nonblocking(&mut self, nonblocking: bool)
+            -> Result<(), AlsaError> { _unavailable!() }
+        /* ports */
+        pub fn create_input_port(&mut self, name: &CStr) -> Result<u8, AlsaError> {
+            _unavailable!() }
+        pub fn create_output_port(&mut self, name: &CStr) -> Result<u8, AlsaError> {
+            _unavailable!() }
+        pub fn delete_port(&mut self, port: u8) -> Result<(), AlsaError> { _unavailable!() }
+        pub fn for_each_port(&mut self, f: impl FnMut(AlsaSeqPort<'_>) -> Result<(), AlsaError>)
+            -> Result<(), AlsaError> { _unavailable!() }
+        /* subscriptions */
+        pub fn connect_from(&mut self, port: u8, src: AlsaSeqAddr)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn connect_to(&mut self, port: u8, dest: AlsaSeqAddr)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn disconnect_from(&mut self, port: u8, src: AlsaSeqAddr)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn disconnect_to(&mut self, port: u8, dest: AlsaSeqAddr)
+            -> Result<(), AlsaError> { _unavailable!() }
+        /* messages */
+        pub fn send(&mut self, port: u8, message: &MidiMessage)
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn send_bytes(&mut self, port: u8, bytes: &[u8])
+            -> Result<(), AlsaError> { _unavailable!() }
+        pub fn receive(&mut self, buf: &mut [u8])
+            -> Result<(AlsaSeqAddr, usize), AlsaError> { _unavailable!() }
+    }
+}
+
+/* internal helpers */
+
+/// The type of an incomplete encoded event.
+#[cfg(ffi_alsa··)]
+const SND_SEQ_EVENT_NONE: u8 = 0;
+
+#[cfg(ffi_alsa··)]
+impl AlsaSeqHandle {
+    /// Opens a duplex sequencer client named `name`.
+    pub(super) fn open(name: &CStr) -> Result<Self, AlsaError> {
+        unsafe {
+            let mut raw = Ptr::null_mut();
+            let streams = _raw::SND_SEQ_OPEN_DUPLEX;
+            AlsaError::result(_raw::snd_seq_open(&mut raw, c"default".as_ptr(), streams, 0))?;
+            // from here on, dropping the handle releases what was acquired
+            let mut seq = Self {
+                raw,
+                encoder: Ptr::null_mut(),
+                decoder: Ptr::null_mut(),
+                client: 0,
+            };
+            AlsaError::result(_raw::snd_seq_set_client_name(raw, name.as_ptr()))?;
+            let client = _raw::snd_seq_client_id(raw);
+            AlsaError::result(client)?;
+            seq.client = client as u8;
+            AlsaError::result(_raw::snd_midi_event_new(Self::SYSEX_CHUNK, &mut seq.encoder))?;
+            AlsaError::result(_raw::snd_midi_event_new(0, &mut seq.decoder))?;
+            // always write the status byte, so that each message stands alone
+            _raw::snd_midi_event_no_status(seq.decoder, 1);
+            Ok(seq)
+        }
+    }
+    /// Creates a MIDI application port with the given capabilities.
+    fn create_port(&mut self, name: &CStr, caps: c_uint) -> Result<u8, AlsaError> {
+        let kind = _raw::SND_SEQ_PORT_TYPE_MIDI_GENERIC | _raw::SND_SEQ_PORT_TYPE_APPLICATION;
+        let port = unsafe { _raw::snd_seq_create_simple_port(self.raw, name.as_ptr(), caps, kind) };
+        AlsaError::result(port)?;
+        Ok(port as u8)
+    }
+}
+
+/// Returns the string of a nul-terminated pointer, if it's valid UTF-8.
+#[cfg(ffi_alsa··)]
+unsafe fn str_from_ptr<'a>(ptr: *const crate::c_char) -> Option<&'a str> {
+    is![ptr.is_null(), return None];
+    unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+}
+
+#[cfg(ffi_alsa··)]
+struct ClientInfo {
+    raw: *mut _raw::snd_seq_client_info_t,
+}
+#[cfg(ffi_alsa··)]
+impl ClientInfo {
+    fn new() -> Result<Self, AlsaError> {
+        let mut raw = Ptr::null_mut();
+        unsafe { AlsaError::result(_raw::snd_seq_client_info_malloc(&mut raw))? };
+        Ok(Self { raw })
+    }
+}
+#[cfg(ffi_alsa··)]
+impl Drop for ClientInfo {
+    fn drop(&mut self) {
+        unsafe { _raw::snd_seq_client_info_free(self.raw) }
+    }
+}
+
+#[cfg(ffi_alsa··)]
+struct PortInfo {
+    raw: *mut _raw::snd_seq_port_info_t,
+}
+#[cfg(ffi_alsa··)]
+impl PortInfo {
+    fn new() -> Result<Self, AlsaError> {
+        let mut raw = Ptr::null_mut();
+        unsafe { AlsaError::result(_raw::snd_seq_port_info_malloc(&mut raw))? };
+        Ok(Self { raw })
+    }
+}
+#[cfg(ffi_alsa··)]
+impl Drop for PortInfo {
+    fn drop(&mut self) {
+        unsafe { _raw::snd_seq_port_info_free(self.raw) }
+    }
+}
@@ -5,6 +5,7 @@
 
 #[cfg(ffi_xcb_shm··)]
 use crate::XShmBuffer;
+use crate::{Boundary1d, Extent2, RasterByteSlice, RasterFormat, RasterLayout};
 use crate::{XCpuBuffer, XDisplay, XError, XImageMode, XImageStore, XWindow, is};
 
 #[doc = crate::_tags!(unix runtime)]
@@ -129,6 +130,18 @@ impl<'a> XSurfaceFrame<'a> {
     }
     /// Returns the mutable surface bytes for direct rendering.
     pub fn bytes_mut(&mut self) -> &mut [u8] { self.surface.bytes_mut() }
+
+    #[must_use] /// Returns the surface as a [`BGRX8`] byte raster, e.g. to capture the frame.
+    ///
+    /// Returns `None` unless the pixels are stored as 32-bit little-endian XRGB.
+    ///
+    /// [`BGRX8`]: crate::RasterFormat::BGRX8
+    pub fn raster(&self) -> Option<RasterByteSlice<&[u8]>> {
+        is! { cfg!(target_endian = "big") || self.bits_per_pixel != 32, return None }
+        let extent = Extent2::new([self.width() as u32, self.height() as u32]);
+        let layout = RasterLayout::interleaved(extent, 4, self.bytes_per_line, Boundary1d::Upper);
+        RasterByteSlice::new(RasterFormat::BGRX8, layout, self.surface.bytes())
+    }
 }
 
 /// Retained X11 image backing, either CPU-owned or MIT-SHM-backed.
@@ -0,0 +1,276 @@
+// devela/src/sys/device/midi/_test.rs
+
+use crate::SmfWriter;
+use crate::{MidiEncoder, MidiError, MidiMessage as M, MidiParser, Smf, SmfDivision};
+use crate::{SmfEvent, SmfEventKind as E, SmfFormat, SmfMeta, SmfTempo, SmfTempoMap, SmfTrack};
+
+#[test]
+fn messages_round_trip() {
+    let messages = [
+        M::NoteOff { channel: 15, note: 0, velocity: 127 },
+        M::PolyPressure { channel: 1, note: 2, pressure: 3 },
+        M::ControlChange { channel: 2, controller: 7, value: 100 },
+        M::ProgramChange { channel: 3, program: 42 },
+        M::ChannelPressure { channel: 4, pressure: 5 },
+        M::PitchBend { channel: 5, value: 0x3FFF },
+        M::SysEx(&[0x7E, 0x7F, 0x09, 0x01]),
+        M::TimeCode(0x35),
+        M::SongPosition(0x1234),
+        M::SongSelect(9),
+        M::TuneRequest,
+        M::TimingClock,
+        M::Reset,
+    ];
+    for message in messages {
+        let mut buf = [0; 8];
+        let len = message.encode(&mut buf).unwrap();
+        assert_eq!(len, message.encoded_len());
+        assert_eq!(M::read(&buf[..len]), Ok((message, len)), "{message:?}");
+    }
+    let bend = M::PitchBend { channel: 0, value: M::PITCH_BEND_CENTER };
+    let mut buf = [0; 3];
+    assert_eq!(bend.encode(&mut buf), Ok(3));
+    assert_eq!(buf, [0xE0, 0x00, 0x40]);
+    assert_eq!(bend.channel(), Some(0));
+    assert_eq!(M::Start.channel(), None);
+}
+#[test]
+fn messages_reject_invalid_data() {
+    let mut buf = [0; 3];
+    let invalid = M::NoteOn { channel: 16, note: 60, velocity: 1 };
+    assert_eq!(invalid.encode(&mut buf), Err(MidiError::InvalidData));
+    let invalid = M::ControlChange { channel: 0, controller: 128, value: 1 };
+    assert_eq!(invalid.encode(&mut buf), Err(MidiError::InvalidData));
+    let short = M::SysEx(&[1, 2]).encode(&mut buf);
+    assert_eq!(short, Err(MidiError::InsufficientBuffer { needed: 4, available: 3 }));
+
+    assert_eq!(M::read(&[60, 100]), Err(MidiError::MissingStatus));
+    assert_eq!(M::read(&[0x90, 60]), Err(MidiError::Truncated));
+    assert_eq!(M::read(&[0xF4]), Err(MidiError::InvalidStatus(0xF4)));
+    assert_eq!(M::read(&[0xF0, 1, 2]), Err(MidiError::Truncated));
+    assert_eq!(M::read(&[0x90, 60, 0x80]), Err(MidiError::InvalidStatus(0x80)));
+}
+#[test]
+fn parser_follows_running_status() {
+    let mut sysex = [0; 4];
+    let mut parser = MidiParser::new(&mut sysex);
+    let mut seen = [None; 8];
+    let mut count = 0;
+    // a stray data byte, a note with running status interrupted by a clock,
+    // a program change, and a system common message cancelling the status
+    let bytes = [64, 0x91, 60, 0xF8, 90, 62, 0, 0xC1, 5, 6, 0xF3, 1, 2];
+    for byte in bytes {
+        if let Some(message) = parser.push(byte).unwrap() {
+            seen[count] = Some(message.status());
+            count += 1;
+        }
+    }
+    assert_eq!(
+        &seen[..count],
+        &[Some(0xF8), Some(0x91), Some(0x91), Some(0xC1), Some(0xC1), Some(0xF3)]
+    );
+    assert_eq!(parser.running_status(), None);
+
+    assert_eq!(parser.push(0xF0), Ok(None));
+    for byte in [1, 2, 3] {
+        assert_eq!(parser.push(byte), Ok(None));
+    }
+    assert_eq!(parser.push(0xF7), Ok(Some(M::SysEx(&[1, 2, 3]))));
+    // an overflowing SysEx is reported, and an interrupted one discarded
+    for byte in [0xF0, 1, 2, 3, 4, 5] {
+        assert_eq!(parser.push(byte), Ok(None));
+    }
+    assert_eq!(parser.push(0xF7), Err(MidiError::InsufficientBuffer { needed: 5, available: 4 }));
+    for byte in [0xF0, 1, 0x80, 60] {
+        assert_eq!(parser.push(byte), Ok(None));
+    }
+    assert_eq!(parser.push(0), Ok(Some(M::NoteOff { channel: 0, note: 60, velocity: 0 })));
+    assert_eq!(parser.push(0xF7), Ok(None));
+}
+#[test]
+fn encoder_uses_running_status() {
+    let mut encoder = MidiEncoder::new();
+    let mut buf = [0; 32];
+    let mut len = 0;
+    let messages = [
+        M::NoteOn { channel: 2, note: 60, velocity: 80 },
+        M::TimingClock,
+        M::NoteOn { channel: 2, note: 64, velocity: 80 },
+        M::SongSelect(1),
+        M::NoteOn { channel: 2, note: 67, velocity: 80 },
+        M::NoteOn { channel: 3, note: 67, velocity: 80 },
+    ];
+    for message in &messages {
+        len += encoder.encode(message, &mut buf[len..]).unwrap();
+    }
+    let expected = [0x92, 60, 80, 0xF8, 64, 80, 0xF3, 1, 0x92, 67, 80, 0x93, 67, 80];
+    assert_eq!(&buf[..len], &expected);
+
+    let mut sysex = [0; 0];
+    let mut parser = MidiParser::new(&mut sysex);
+    let mut decoded = 0;
+    for &byte in &buf[..len] {
+        if let Some(message) = parser.push(byte).unwrap() {
+            assert_eq!(message, messages[decoded]);
+            decoded += 1;
+        }
+    }
+    assert_eq!(decoded, messages.len());
+}
+
+#[test]
+fn smf_reads_running_status_and_meta_events() {
+    #[rustfmt::skip]
+    let bytes = [
+        b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0, 96,
+        b'M', b'T', b'r', b'k', 0, 0, 0, 31,
+        0x00, 0xFF, 0x51, 3, 0x07, 0xA1, 0x20,  // tempo 500_000
+        0x00, 0x90, 60, 100,                    // note on
+        0x60, 60, 0,                            // running status, 96 ticks later
+        0x81, 0x00, 0xF0, 2, 0x7D, 0xF7,        // sysex, 128 ticks later
+        0x00, 0xFF, 0x03, 1, b'A',              // track name
+        0x00, 0xFF, 0x2F, 0,                    // end of track
+        0x00, 0x90,                             // ignored after the end
+    ];
+    let smf = Smf::read(&bytes).unwrap();
+    assert_eq!((smf.format(), smf.division()), (SmfFormat::Single, SmfDivision::Ticks(96)));
+    let track = smf.track(0).unwrap();
+    let mut events = track.events().map(Result::unwrap);
+    assert_eq!(events.next(), Some(SmfEvent::new(0, E::Meta(SmfMeta::Tempo(500_000)))));
+    let on = M::NoteOn { channel: 0, note: 60, velocity: 100 };
+    assert_eq!(events.next(), Some(SmfEvent::new(0, E::Midi(on))));
+    let off = M::NoteOn { channel: 0, note: 60, velocity: 0 };
+    assert_eq!(events.next(), Some(SmfEvent::new(96, E::Midi(off))));
+    assert_eq!(events.next(), Some(SmfEvent::new(128, E::SysEx(&[0x7D, 0xF7]))));
+    let name = SmfMeta::Text { kind: SmfMeta::TRACK_NAME, text: b"A" };
+    assert_eq!(events.next(), Some(SmfEvent::new(0, E::Meta(name))));
+    assert_eq!(events.next(), Some(SmfEvent::new(0, E::Meta(SmfMeta::EndOfTrack))));
+    assert_eq!(events.next(), None);
+}
+#[test]
+fn smf_rejects_malformed_files() {
+    assert_eq!(Smf::read(b"MThd"), Err(MidiError::InvalidHeader));
+    let mut header = *b"MThd\0\0\0\x06\0\x03\0\x01\0\x60";
+    assert_eq!(Smf::read(&header), Err(MidiError::UnsupportedFormat(3)));
+    header[9] = 0;
+    assert_eq!(Smf::read(&header), Err(MidiError::InvalidTrackCount));
+    header[13] = 0;
+    assert_eq!(Smf::read(&header), Err(MidiError::InvalidHeader));
+
+    let events = |bytes: &'static [u8]| SmfTrack::new(bytes).events().last().unwrap();
+    assert_eq!(events(&[0, 60, 100]), Err(MidiError::MissingStatus));
+    assert_eq!(events(&[0, 0x90, 60]), Err(MidiError::Truncated));
+    assert_eq!(events(&[0, 0xF8]), Err(MidiError::InvalidStatus(0xF8)));
+    assert_eq!(events(&[0x80, 0x80, 0x80, 0x80, 0]), Err(MidiError::InvalidVarLen));
+    assert_eq!(events(&[0, 0xFF, 0x51, 2, 1, 2]), Err(MidiError::InvalidMeta(0x51)));
+    assert_eq!(events(&[0, 0xFF, 0x01, 5, 1]), Err(MidiError::Truncated));
+}
+#[test]
+fn smf_writes_and_reads_back() {
+    let mut buf = [0; 256];
+    let mut writer = SmfWriter::new(&mut buf, SmfFormat::Independent, SmfDivision::Ticks(240));
+    let name = SmfMeta::Text { kind: SmfMeta::TRACK_NAME, text: b"drums" };
+    let time = SmfMeta::TimeSignature {
+        numerator: 6,
+        denominator_pow2: 3,
+        clocks_per_click: 36,
+        thirty_seconds_per_quarter: 8,
+    };
+    let key = SmfMeta::KeySignature { sharps: -3, minor: true };
+    let on = M::NoteOn { channel: 9, note: 36, velocity: 127 };
+    let bend = M::PitchBend { channel: 9, value: 0x1000 };
+    let written = [
+        SmfEvent::new(0, E::Meta(name)),
+        SmfEvent::new(0, E::Meta(time)),
+        SmfEvent::new(0, E::Meta(key)),
+        SmfEvent::new(0, E::Midi(on)),
+        SmfEvent::new(200_000, E::Midi(on)),
+        SmfEvent::new(1, E::Midi(bend)),
+        SmfEvent::new(0, E::SysEx(&[1, 2])),
+        SmfEvent::new(0, E::Escape(&[3, 0xF7])),
+        SmfEvent::new(0, E::Midi(on)),
+    ];
+    for event in written {
+        writer.event(event.delta, event.kind).unwrap();
+    }
+    writer.begin_track();
+    writer.event(5, E::Meta(SmfMeta::EndOfTrack)).unwrap();
+    assert_eq!(writer.event(0, E::Midi(M::Stop)), Err(MidiError::InvalidStatus(0xFC)));
+    assert_eq!(writer.event(0x1000_0000, E::Midi(on)), Err(MidiError::InvalidVarLen));
+    let len = writer.finish().unwrap();
+
+    let smf = Smf::read(&buf[..len]).unwrap();
+    assert_eq!((smf.format(), smf.track_count()), (SmfFormat::Independent, 2));
+    let mut tracks = smf.tracks();
+    let mut events = tracks.next().unwrap().events().map(Result::unwrap);
+    for expected in written {
+        assert_eq!(events.next(), Some(expected));
+    }
+    assert_eq!(events.next(), Some(SmfEvent::new(0, E::Meta(SmfMeta::EndOfTrack))));
+    assert_eq!(events.next(), None);
+    let mut events = tracks.next().unwrap().events().map(Result::unwrap);
+    assert_eq!(events.next(), Some(SmfEvent::new(5, E::Meta(SmfMeta::EndOfTrack))));
+    assert_eq!(events.next(), None);
+    assert_eq!(tracks.next(), None);
+}
+#[test]
+fn smf_writer_checks_tracks_and_space() {
+    let mut buf = [0; 64];
+    let mut writer = SmfWriter::new(&mut buf, SmfFormat::Single, SmfDivision::Ticks(96));
+    writer.end_track();
+    assert_eq!(writer.finish(), Err(MidiError::InvalidTrackCount));
+    let mut writer = SmfWriter::new(&mut buf, SmfFormat::Single, SmfDivision::Ticks(96));
+    writer.begin_track();
+    writer.begin_track();
+    assert_eq!(writer.finish(), Err(MidiError::InvalidTrackCount));
+
+    let mut short = [0; 20];
+    let mut writer = SmfWriter::new(&mut short, SmfFormat::Single, SmfDivision::Ticks(96));
+    writer.event(0, E::Midi(M::ProgramChange { channel: 0, program: 1 })).unwrap();
+    assert_eq!(writer.finish(), Err(MidiError::InsufficientBuffer { needed: 29, available: 20 }));
+}
+#[test]
+fn tempo_maps_convert_ticks_to_time() {
+    let mut buf = [0; 128];
+    let mut writer = SmfWriter::new(&mut buf, SmfFormat::Simultaneous, SmfDivision::Ticks(100));
+    writer.event(200, E::Meta(SmfMeta::Tempo(250_000))).unwrap();
+    writer.begin_track();
+    writer.event(100, E::Meta(SmfMeta::Tempo(1_000_000))).unwrap();
+    writer.event(200, E::Meta(SmfMeta::Tempo(2_000_000))).unwrap();
+    let len = writer.finish().unwrap();
+    let smf = Smf::read(&buf[..len]).unwrap();
+
+    let mut tempos = [SmfTempo::default(); 2];
+    let error = smf.tempo_map(&mut tempos).map(|_| ());
+    assert_eq!(error, Err(MidiError::InsufficientBuffer { needed: 3, available: 2 }));
+    let mut tempos = [SmfTempo::default(); 3];
+    let map = smf.tempo_map(&mut tempos).unwrap();
+    // merged from both tracks, in order
+    let ticks = [map.tempos()[0].tick, map.tempos()[1].tick, map.tempos()[2].tick];
+    assert_eq!(ticks, [100, 200, 300]);
+    assert_eq!((map.tempo_at(99), map.tempo_at(250)), (500_000, 250_000));
+    for (tick, micros) in
+        [(0, 0), (100, 500_000), (200, 1_500_000), (300, 1_750_000), (350, 2_750_000)]
+    {
+        assert_eq!(map.tick_to_micros(tick), micros, "{tick}");
+        assert_eq!(map.micros_to_tick(micros), tick, "{micros}");
+    }
+    assert_eq!(map.tick_to_nanos(1), 5_000_000);
+    #[cfg(feature = "time")]
+    assert_eq!(map.tick_to_time(350), crate::TimeDelta::from_millis(2_750));
+
+    let smpte = SmfTempoMap::new(SmfDivision::Smpte { fps: 25, ticks_per_frame: 40 }, &[]);
+    assert_eq!(smpte.tick_to_micros(1_000), 1_000_000);
+    assert_eq!(smpte.micros_to_tick(500_000), 500);
+    let drop = SmfTempoMap::new(SmfDivision::Smpte { fps: 29, ticks_per_frame: 1 }, &[]);
+    assert_eq!(drop.tick_to_micros(30_000), 1_001_000_000);
+}
+#[test]
+fn divisions_round_trip() {
+    for division in [SmfDivision::Ticks(480), SmfDivision::Smpte { fps: 29, ticks_per_frame: 80 }] {
+        assert_eq!(division.to_raw().and_then(SmfDivision::from_raw), Some(division));
+    }
+    assert_eq!(SmfDivision::Smpte { fps: 25, ticks_per_frame: 40 }.to_raw(), Some(0xE728));
+    assert_eq!(SmfDivision::from_raw(0xE900), None);
+    assert_eq!(SmfDivision::Ticks(0x8000).to_raw(), None);
+}
@@ -0,0 +1,72 @@
+// devela/src/sys/device/midi/error.rs
+//
+//! Defines [`MidiError`].
+//
+
+use crate::{Debug, Display, Error, FmtResult, Formatter, write};
+
+#[doc = crate::_tags!(audio error_composite)]
+/// An error encountered while parsing or encoding MIDI data.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MidiError {
+    /// The input ended in the middle of a message, event or chunk.
+    Truncated,
+
+    /// A data byte was found with no status in effect.
+    MissingStatus,
+
+    /// A status byte is undefined or not allowed in its context.
+    InvalidStatus(u8),
+
+    /// A data byte or a message field is out of range.
+    InvalidData,
+
+    /// A variable-length quantity is longer than 4 bytes or out of range.
+    InvalidVarLen,
+
+    /// The file header is missing or malformed.
+    InvalidHeader,
+
+    /// The file format is not 0, 1 or 2.
+    UnsupportedFormat(u16),
+
+    /// The number of tracks doesn't match the header or the format.
+    InvalidTrackCount,
+
+    /// A meta event of the given type has a malformed payload.
+    InvalidMeta(u8),
+
+    /// The output buffer is too short.
+    InsufficientBuffer {
+        /// The number of elements needed.
+        needed: usize,
+        /// The number of elements available.
+        available: usize,
+    },
+}
+impl Error for MidiError {}
+impl Display for MidiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult<()> {
+        match *self {
+            Self::Truncated => f.write_str("truncated MIDI data"),
+            Self::MissingStatus => f.write_str("MIDI data byte without a status"),
+            Self::InvalidStatus(status) => write!(f, "invalid MIDI status byte {status:#04X}"),
+            Self::InvalidData => f.write_str("invalid MIDI data byte"),
+            Self::InvalidVarLen => f.write_str("invalid MIDI variable-length quantity"),
+            Self::InvalidHeader => f.write_str("invalid MIDI file header"),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported MIDI file format {format}"),
+            Self::InvalidTrackCount => f.write_str("invalid MIDI file track count"),
+            Self::InvalidMeta(kind) => write!(f, "invalid MIDI meta event {kind:#04X}"),
+            Self::InsufficientBuffer { needed, available } => {
+                write!(f, "insufficient MIDI buffer: needed {needed}, available {available}")
+            }
+        }
+    }
+}
+impl MidiError {
+    pub(crate) const fn insufficient_buffer(needed: usize, available: usize) -> Self {
+        Self::InsufficientBuffer { needed, available }
+    }
+}
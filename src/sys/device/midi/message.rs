@@ -0,0 +1,339 @@
+// devela/src/sys/device/midi/message.rs
+//
+//! Defines [`MidiMessage`].
+//
+
+use crate::{MidiError, is};
+
+#[doc = crate::_tags!(audio protocol)]
+/// A MIDI 1.0 message.
+#[doc = crate::_doc_meta!{
+    location("sys/device/midi"),
+    #[cfg(target_pointer_width = "64")]
+    test_size_of(MidiMessage = 24|192),
+}]
+///
+/// Channels are numbered from 0 to 15, and data values range from 0 to 127,
+/// or to 16383 for the 14-bit values.
+///
+/// A system exclusive message borrows its data bytes,
+/// without the `0xF0` and `0xF7` delimiters.
+///
+/// # Example
+/// ```
+/// # use devela::MidiMessage;
+/// let on = MidiMessage::NoteOn { channel: 9, note: 36, velocity: 100 };
+/// let mut buf = [0; 3];
+/// assert_eq![on.encode(&mut buf), Ok(3)];
+/// assert_eq![buf, [0x99, 36, 100]];
+/// assert_eq![MidiMessage::read(&buf), Ok((on, 3))];
+///
+/// // a note on without velocity is a note off
+/// assert![MidiMessage::NoteOn { channel: 0, note: 60, velocity: 0 }.is_note_off()];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MidiMessage<'a> {
+    /* channel voice */
+    /// Releases a note.
+    NoteOff {
+        /// The channel.
+        channel: u8,
+        /// The note number, with middle C at 60.
+        note: u8,
+        /// The release velocity.
+        velocity: u8,
+    },
+    /// Starts a note.
+    NoteOn {
+        /// The channel.
+        channel: u8,
+        /// The note number, with middle C at 60.
+        note: u8,
+        /// The velocity, where 0 releases the note.
+        velocity: u8,
+    },
+    /// Changes the pressure of a held note.
+    PolyPressure {
+        /// The channel.
+        channel: u8,
+        /// The note number.
+        note: u8,
+        /// The pressure.
+        pressure: u8,
+    },
+    /// Changes the value of a controller.
+    ControlChange {
+        /// The channel.
+        channel: u8,
+        /// The controller number.
+        controller: u8,
+        /// The controller value.
+        value: u8,
+    },
+    /// Selects a program.
+    ProgramChange {
+        /// The channel.
+        channel: u8,
+        /// The program number.
+        program: u8,
+    },
+    /// Changes the pressure of the whole channel.
+    ChannelPressure {
+        /// The channel.
+        channel: u8,
+        /// The pressure.
+        pressure: u8,
+    },
+    /// Bends the pitch of the channel.
+    PitchBend {
+        /// The channel.
+        channel: u8,
+        /// The 14-bit bend, centered at [`PITCH_BEND_CENTER`][Self::PITCH_BEND_CENTER].
+        value: u16,
+    },
+
+    /* system common */
+    /// System exclusive data, without the delimiters.
+    SysEx(&'a [u8]),
+    /// A MIDI time code quarter frame.
+    TimeCode(u8),
+    /// Moves the song position, in sixteenth notes.
+    SongPosition(u16),
+    /// Selects a song.
+    SongSelect(u8),
+    /// Asks analog synthesizers to tune their oscillators.
+    TuneRequest,
+
+    /* system real-time */
+    /// A timing clock, sent 24 times per quarter note.
+    TimingClock,
+    /// Starts the sequence from the beginning.
+    Start,
+    /// Continues the sequence from the current position.
+    Continue,
+    /// Stops the sequence.
+    Stop,
+    /// Tells the receiver that the connection is alive.
+    ActiveSensing,
+    /// Resets the receiver to its initial state.
+    Reset,
+}
+
+impl<'a> MidiMessage<'a> {
+    /// The centered value of [`PitchBend`][Self::PitchBend].
+    pub const PITCH_BEND_CENTER: u16 = 0x2000;
+
+    /// Returns the status byte.
+    ///
+    /// For channel messages it includes the channel, masked to 4 bits.
+    #[must_use]
+    pub const fn status(&self) -> u8 {
+        let (kind, channel) = match *self {
+            Self::NoteOff { channel, .. } => (0x80, channel),
+            Self::NoteOn { channel, .. } => (0x90, channel),
+            Self::PolyPressure { channel, .. } => (0xA0, channel),
+            Self::ControlChange { channel, .. } => (0xB0, channel),
+            Self::ProgramChange { channel, .. } => (0xC0, channel),
+            Self::ChannelPressure { channel, .. } => (0xD0, channel),
+            Self::PitchBend { channel, .. } => (0xE0, channel),
+            Self::SysEx(_) => return 0xF0,
+            Self::TimeCode(_) => return 0xF1,
+            Self::SongPosition(_) => return 0xF2,
+            Self::SongSelect(_) => return 0xF3,
+            Self::TuneRequest => return 0xF6,
+            Self::TimingClock => return 0xF8,
+            Self::Start => return 0xFA,
+            Self::Continue => return 0xFB,
+            Self::Stop => return 0xFC,
+            Self::ActiveSensing => return 0xFE,
+            Self::Reset => return 0xFF,
+        };
+        kind | (channel & 0x0F)
+    }
+    /// Returns the channel of a channel message.
+    #[must_use]
+    pub const fn channel(&self) -> Option<u8> {
+        is![self.is_channel(), Some(self.status() & 0x0F), None]
+    }
+    /// Returns whether it's a channel voice message.
+    #[must_use]
+    pub const fn is_channel(&self) -> bool {
+        self.status() < 0xF0
+    }
+    /// Returns whether it's a system real-time message,
+    /// which may be interleaved within any other message.
+    #[must_use]
+    pub const fn is_realtime(&self) -> bool {
+        self.status() >= 0xF8
+    }
+    /// Returns whether it releases a note, including a note on without velocity.
+    #[must_use]
+    pub const fn is_note_off(&self) -> bool {
+        matches![self, Self::NoteOff { .. } | Self::NoteOn { velocity: 0, .. }]
+    }
+
+    /// Returns whether every field is within range.
+    #[must_use]
+    pub const fn is_valid(&self) -> bool {
+        const fn data(bytes: &[u8]) -> bool {
+            let mut i = 0;
+            while i < bytes.len() {
+                is![bytes[i] > 0x7F, return false];
+                i += 1;
+            }
+            true
+        }
+        match *self {
+            Self::NoteOff { channel, note: a, velocity: b }
+            | Self::NoteOn { channel, note: a, velocity: b }
+            | Self::PolyPressure { channel, note: a, pressure: b }
+            | Self::ControlChange { channel, controller: a, value: b } => {
+                channel < 16 && data(&[a, b])
+            }
+            Self::ProgramChange { channel, program: a }
+            | Self::ChannelPressure { channel, pressure: a } => channel < 16 && a < 0x80,
+            Self::PitchBend { channel, value } => channel < 16 && value < 0x4000,
+            Self::SysEx(bytes) => data(bytes),
+            Self::TimeCode(a) | Self::SongSelect(a) => a < 0x80,
+            Self::SongPosition(value) => value < 0x4000,
+            _ => true,
+        }
+    }
+
+    /// Returns the number of data bytes that follow `status`,
+    /// or `None` if it's undefined or starts a system exclusive message.
+    #[must_use]
+    pub const fn data_len(status: u8) -> Option<usize> {
+        match status {
+            0x80..=0xBF | 0xE0..=0xEF | 0xF2 => Some(2),
+            0xC0..=0xDF | 0xF1 | 0xF3 => Some(1),
+            0xF6 | 0xF8 | 0xFA..=0xFC | 0xFE | 0xFF => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bytes of the encoded message, including its status.
+    #[must_use]
+    pub const fn encoded_len(&self) -> usize {
+        match self {
+            Self::SysEx(bytes) => bytes.len() + 2,
+            _ => match Self::data_len(self.status()) {
+                Some(len) => len + 1,
+                None => 1,
+            },
+        }
+    }
+
+    /// Encodes the message with its status byte into `buf`,
+    /// returning the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InvalidData`] if a field is out of range,
+    /// or [`MidiError::InsufficientBuffer`] if `buf` is too short.
+    pub const fn encode(&self, buf: &mut [u8]) -> Result<usize, MidiError> {
+        let len = self.encoded_len();
+        is![!self.is_valid(), return Err(MidiError::InvalidData)];
+        is![buf.len() < len, return Err(MidiError::insufficient_buffer(len, buf.len()))];
+        buf[0] = self.status();
+        self.encode_data(buf);
+        Ok(len)
+    }
+
+    /// Reads one message starting with its status byte from `bytes`,
+    /// returning it with the number of bytes read.
+    ///
+    /// A system exclusive message must be terminated by `0xF7`.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::MissingStatus`] if `bytes` starts with a data byte,
+    /// [`MidiError::InvalidStatus`] for an undefined or misplaced status,
+    /// or [`MidiError::Truncated`] if the message is incomplete.
+    pub const fn read(bytes: &'a [u8]) -> Result<(Self, usize), MidiError> {
+        let Some(&status) = bytes.first() else { return Err(MidiError::Truncated) };
+        is![status < 0x80, return Err(MidiError::MissingStatus)];
+        if status == 0xF0 {
+            let mut end = 1;
+            while end < bytes.len() {
+                match bytes[end] {
+                    0xF7 => {
+                        let (data, _) = bytes.split_at(end);
+                        let (_, data) = data.split_at(1);
+                        return Ok((Self::SysEx(data), end + 1));
+                    }
+                    0x80.. => return Err(MidiError::InvalidStatus(bytes[end])),
+                    _ => end += 1,
+                }
+            }
+            return Err(MidiError::Truncated);
+        }
+        let Some(len) = Self::data_len(status) else {
+            return Err(MidiError::InvalidStatus(status));
+        };
+        is![bytes.len() <= len, return Err(MidiError::Truncated)];
+        let data = [is![len > 0, bytes[1], 0], is![len > 1, bytes[2], 0]];
+        is![data[0] > 0x7F, return Err(MidiError::InvalidStatus(data[0]))];
+        is![data[1] > 0x7F, return Err(MidiError::InvalidStatus(data[1]))];
+        match Self::from_parts(status, data) {
+            Some(message) => Ok((message, len + 1)),
+            None => Err(MidiError::InvalidStatus(status)),
+        }
+    }
+
+    /* crate internals */
+
+    /// Returns the message of a defined status other than SysEx, with its data bytes.
+    pub(crate) const fn from_parts(status: u8, data: [u8; 2]) -> Option<Self> {
+        let (channel, [a, b]) = (status & 0x0F, data);
+        let wide = (b as u16) << 7 | a as u16;
+        Some(match status {
+            0x80..=0x8F => Self::NoteOff { channel, note: a, velocity: b },
+            0x90..=0x9F => Self::NoteOn { channel, note: a, velocity: b },
+            0xA0..=0xAF => Self::PolyPressure { channel, note: a, pressure: b },
+            0xB0..=0xBF => Self::ControlChange { channel, controller: a, value: b },
+            0xC0..=0xCF => Self::ProgramChange { channel, program: a },
+            0xD0..=0xDF => Self::ChannelPressure { channel, pressure: a },
+            0xE0..=0xEF => Self::PitchBend { channel, value: wide },
+            0xF1 => Self::TimeCode(a),
+            0xF2 => Self::SongPosition(wide),
+            0xF3 => Self::SongSelect(a),
+            0xF6 => Self::TuneRequest,
+            0xF8 => Self::TimingClock,
+            0xFA => Self::Start,
+            0xFB => Self::Continue,
+            0xFC => Self::Stop,
+            0xFE => Self::ActiveSensing,
+            0xFF => Self::Reset,
+            _ => return None,
+        })
+    }
+
+    /// Writes the bytes after the status into `buf`, which must be long enough.
+    pub(crate) const fn encode_data(&self, buf: &mut [u8]) {
+        match *self {
+            Self::NoteOff { note: a, velocity: b, .. }
+            | Self::NoteOn { note: a, velocity: b, .. }
+            | Self::PolyPressure { note: a, pressure: b, .. }
+            | Self::ControlChange { controller: a, value: b, .. } => {
+                buf[1] = a;
+                buf[2] = b;
+            }
+            Self::ProgramChange { program: a, .. }
+            | Self::ChannelPressure { pressure: a, .. }
+            | Self::TimeCode(a)
+            | Self::SongSelect(a) => buf[1] = a,
+            Self::PitchBend { value, .. } | Self::SongPosition(value) => {
+                buf[1] = (value & 0x7F) as u8;
+                buf[2] = (value >> 7) as u8;
+            }
+            Self::SysEx(bytes) => {
+                let mut i = 0;
+                while i < bytes.len() {
+                    buf[1 + i] = bytes[i];
+                    i += 1;
+                }
+                buf[1 + bytes.len()] = 0xF7;
+            }
+            _ => {}
+        }
+    }
+}
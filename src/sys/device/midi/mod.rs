@@ -1,9 +1,30 @@
 // devela/src/sys/device/midi/mod.rs
 //
-//! Midi functionality.
+#![doc = crate::_DOC_SYS_DEVICE_MIDI!()] // public
+#![doc = crate::_doc!(modules: crate::sys::device; midi)]
+#![doc = crate::_doc!(flat:"sys")]
+#![doc = crate::_doc!(hr)]
+//!
+//! Models MIDI 1.0 messages, parses and encodes them over byte streams
+//! with running status, and reads and writes Standard MIDI Files.
 //
+// Everything works over borrowed bytes, without allocating.
+
+#[cfg(test)]
+mod _test;
+
+mod error; // MidiError
+mod message; // MidiMessage
+mod smf; // Smf, SmfDivision, SmfEvent*, SmfFormat, SmfMeta, SmfTempo*, SmfTrack, SmfWriter
+mod stream; // MidiEncoder, MidiParser
 
 crate::structural_mods! { // _mods
     _mods {
+        pub use super::{
+            error::*,
+            message::*,
+            smf::_all::*,
+            stream::*,
+        };
     }
 }
@@ -0,0 +1,49 @@
+// devela/src/sys/device/midi/smf/_helper.rs
+//
+//! Variable-length quantities.
+//
+
+use crate::MidiError;
+
+/// The largest value of a variable-length quantity.
+pub(super) const VLQ_MAX: u32 = 0x0FFF_FFFF;
+
+/// Reads a variable-length quantity at `*pos`, advancing it.
+pub(super) const fn read_vlq(bytes: &[u8], pos: &mut usize) -> Result<u32, MidiError> {
+    let mut value = 0;
+    let mut i = 0;
+    while i < 4 {
+        if *pos >= bytes.len() {
+            return Err(MidiError::Truncated);
+        }
+        let byte = bytes[*pos];
+        *pos += 1;
+        value = value << 7 | (byte & 0x7F) as u32;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+        i += 1;
+    }
+    Err(MidiError::InvalidVarLen)
+}
+
+/// Encodes `value` as a variable-length quantity,
+/// returning its bytes and how many are used.
+pub(super) const fn encode_vlq(value: u32) -> Result<([u8; 4], usize), MidiError> {
+    if value > VLQ_MAX {
+        return Err(MidiError::InvalidVarLen);
+    }
+    let mut len = 1;
+    while len < 4 && value >> (7 * len) != 0 {
+        len += 1;
+    }
+    let mut bytes = [0; 4];
+    let mut i = 0;
+    while i < len {
+        let shift = 7 * (len - 1 - i);
+        let more = if i + 1 < len { 0x80 } else { 0 };
+        bytes[i] = (value >> shift) as u8 & 0x7F | more;
+        i += 1;
+    }
+    Ok((bytes, len))
+}
@@ -0,0 +1,296 @@
+// devela/src/sys/device/midi/smf/event.rs
+//
+//! Defines [`SmfDivision`], [`SmfEvent`], [`SmfEventKind`], [`SmfFormat`], [`SmfMeta`].
+//
+
+use crate::{MidiError, MidiMessage, is};
+
+#[doc = crate::_tags!(audio)]
+/// The format of a Standard MIDI File.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SmfFormat {
+    /// Format 0: a single multi-channel track.
+    #[default]
+    Single = 0,
+    /// Format 1: tracks played simultaneously, sharing the tempo map.
+    Simultaneous = 1,
+    /// Format 2: independent single-track patterns, each with its own tempo map.
+    Independent = 2,
+}
+impl SmfFormat {
+    /// Returns the format of its number, if supported.
+    #[must_use]
+    pub const fn from_number(number: u16) -> Option<Self> {
+        match number {
+            0 => Some(Self::Single),
+            1 => Some(Self::Simultaneous),
+            2 => Some(Self::Independent),
+            _ => None,
+        }
+    }
+    /// Returns the format number.
+    #[must_use]
+    pub const fn number(self) -> u16 {
+        self as u16
+    }
+}
+
+#[doc = crate::_tags!(audio time)]
+/// The meaning of the delta times of a Standard MIDI File.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SmfDivision {
+    /// Ticks per quarter note, timed by the tempo map.
+    Ticks(u16),
+    /// Ticks per SMPTE frame, in absolute time.
+    Smpte {
+        /// Frames per second: 24, 25, 29 (for 29.97 drop frame) or 30.
+        fps: u8,
+        /// Ticks per frame.
+        ticks_per_frame: u8,
+    },
+}
+impl Default for SmfDivision {
+    /// Returns 480 ticks per quarter note.
+    fn default() -> Self {
+        Self::Ticks(480)
+    }
+}
+impl SmfDivision {
+    /// Returns the division of the raw header field, if valid.
+    #[must_use]
+    pub const fn from_raw(raw: u16) -> Option<Self> {
+        if raw & 0x8000 == 0 {
+            return is![raw == 0, None, Some(Self::Ticks(raw))];
+        }
+        let [fps, ticks_per_frame] = raw.to_be_bytes();
+        let fps = (fps as i8).unsigned_abs();
+        let valid = matches![fps, 24 | 25 | 29 | 30] && ticks_per_frame > 0;
+        is![valid, Some(Self::Smpte { fps, ticks_per_frame }), None]
+    }
+    /// Returns the raw header field, if valid.
+    #[must_use]
+    pub const fn to_raw(self) -> Option<u16> {
+        match self {
+            Self::Ticks(ticks) if ticks > 0 && ticks < 0x8000 => Some(ticks),
+            Self::Smpte { fps: fps @ (24 | 25 | 29 | 30), ticks_per_frame }
+                if ticks_per_frame > 0 =>
+            {
+                Some(u16::from_be_bytes([(fps as i8).wrapping_neg() as u8, ticks_per_frame]))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// An event of a track of a Standard MIDI File.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SmfEvent<'a> {
+    /// The ticks since the previous event of the track.
+    pub delta: u32,
+    /// What happens.
+    pub kind: SmfEventKind<'a>,
+}
+impl<'a> SmfEvent<'a> {
+    /// Returns a new event.
+    #[must_use]
+    pub const fn new(delta: u32, kind: SmfEventKind<'a>) -> Self {
+        Self { delta, kind }
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// The kind of an [`SmfEvent`].
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SmfEventKind<'a> {
+    /// A channel message.
+    Midi(MidiMessage<'a>),
+    /// A system exclusive packet, following `0xF0`,
+    /// which ends with `0xF7` unless it continues in escape events.
+    SysEx(&'a [u8]),
+    /// Arbitrary bytes to send, following `0xF7`,
+    /// including the continuation of a system exclusive packet.
+    Escape(&'a [u8]),
+    /// A meta event.
+    Meta(SmfMeta<'a>),
+}
+
+#[doc = crate::_tags!(audio)]
+/// A meta event of a Standard MIDI File.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SmfMeta<'a> {
+    /// The number of the sequence (`0x00`).
+    SequenceNumber(u16),
+    /// Text of one of the text types from `0x01` to `0x0F`.
+    Text {
+        /// The text type, like [`TRACK_NAME`][Self::TRACK_NAME].
+        kind: u8,
+        /// The text, usually ASCII or UTF-8.
+        text: &'a [u8],
+    },
+    /// The channel of the following events (`0x20`).
+    ChannelPrefix(u8),
+    /// The output port of the track (`0x21`).
+    Port(u8),
+    /// The end of the track (`0x2F`).
+    EndOfTrack,
+    /// The tempo, in microseconds per quarter note (`0x51`).
+    Tempo(u32),
+    /// The SMPTE start time of the track: hours, minutes, seconds, frames
+    /// and hundredths of a frame (`0x54`).
+    SmpteOffset([u8; 5]),
+    /// The time signature (`0x58`).
+    TimeSignature {
+        /// The beats per bar.
+        numerator: u8,
+        /// The beat unit, as a power of two.
+        denominator_pow2: u8,
+        /// The MIDI clocks per metronome click.
+        clocks_per_click: u8,
+        /// The thirty-second notes per quarter note.
+        thirty_seconds_per_quarter: u8,
+    },
+    /// The key signature (`0x59`).
+    KeySignature {
+        /// The sharps if positive, or flats if negative.
+        sharps: i8,
+        /// Whether the key is minor.
+        minor: bool,
+    },
+    /// Sequencer-specific data (`0x7F`).
+    SequencerSpecific(&'a [u8]),
+    /// Any other meta event.
+    Unknown {
+        /// The meta event type.
+        kind: u8,
+        /// The payload.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> SmfMeta<'a> {
+    /// The text type of free text.
+    pub const TEXT: u8 = 0x01;
+    /// The text type of a copyright notice.
+    pub const COPYRIGHT: u8 = 0x02;
+    /// The text type of the name of a sequence or track.
+    pub const TRACK_NAME: u8 = 0x03;
+    /// The text type of the name of an instrument.
+    pub const INSTRUMENT: u8 = 0x04;
+    /// The text type of a lyric syllable.
+    pub const LYRIC: u8 = 0x05;
+    /// The text type of a marker.
+    pub const MARKER: u8 = 0x06;
+    /// The text type of a cue point.
+    pub const CUE: u8 = 0x07;
+
+    /// Returns the meta event type.
+    #[must_use]
+    pub const fn kind(&self) -> u8 {
+        match *self {
+            Self::SequenceNumber(_) => 0x00,
+            Self::Text { kind, .. } | Self::Unknown { kind, .. } => kind,
+            Self::ChannelPrefix(_) => 0x20,
+            Self::Port(_) => 0x21,
+            Self::EndOfTrack => 0x2F,
+            Self::Tempo(_) => 0x51,
+            Self::SmpteOffset(_) => 0x54,
+            Self::TimeSignature { .. } => 0x58,
+            Self::KeySignature { .. } => 0x59,
+            Self::SequencerSpecific(_) => 0x7F,
+        }
+    }
+
+    /// Returns the tempo in beats per minute, if it's a tempo event.
+    #[must_use]
+    pub const fn bpm(&self) -> Option<f64> {
+        match *self {
+            Self::Tempo(micros) => Some(60_000_000.0 / micros as f64),
+            _ => None,
+        }
+    }
+
+    /// Parses the meta event of type `kind` from its payload.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InvalidMeta`] if the payload of a known type
+    /// has the wrong length or an invalid value.
+    pub const fn parse(kind: u8, data: &'a [u8]) -> Result<Self, MidiError> {
+        let invalid = Err(MidiError::InvalidMeta(kind));
+        Ok(match (kind, data) {
+            (0x00, &[hi, lo]) => Self::SequenceNumber(u16::from_be_bytes([hi, lo])),
+            (0x01..=0x0F, text) => Self::Text { kind, text },
+            (0x20, &[channel]) if channel < 16 => Self::ChannelPrefix(channel),
+            (0x21, &[port]) => Self::Port(port),
+            (0x2F, &[]) => Self::EndOfTrack,
+            (0x51, &[a, b, c]) => {
+                let micros = u32::from_be_bytes([0, a, b, c]);
+                is![micros == 0, return invalid, Self::Tempo(micros)]
+            }
+            (0x54, &[h, m, s, f, ff]) => Self::SmpteOffset([h, m, s, f, ff]),
+            (0x58, &[n, d, c, b]) => Self::TimeSignature {
+                numerator: n,
+                denominator_pow2: d,
+                clocks_per_click: c,
+                thirty_seconds_per_quarter: b,
+            },
+            (0x59, &[sharps, minor]) if minor < 2 => {
+                Self::KeySignature { sharps: sharps as i8, minor: minor == 1 }
+            }
+            (0x7F, data) => Self::SequencerSpecific(data),
+            (0x00 | 0x20 | 0x21 | 0x2F | 0x51 | 0x54 | 0x58 | 0x59, _) => return invalid,
+            (_, data) => Self::Unknown { kind, data },
+        })
+    }
+
+    /// Returns the payload, using `fixed` to hold the bytes of fixed-size events.
+    pub(crate) const fn payload<'s>(&'s self, fixed: &'s mut [u8; 5]) -> &'s [u8]
+    where
+        'a: 's,
+    {
+        let len = match *self {
+            Self::Text { text: data, .. }
+            | Self::SequencerSpecific(data)
+            | Self::Unknown { data, .. } => return data,
+            Self::SequenceNumber(n) => {
+                [fixed[0], fixed[1]] = n.to_be_bytes();
+                2
+            }
+            Self::ChannelPrefix(b) | Self::Port(b) => {
+                fixed[0] = b;
+                1
+            }
+            Self::EndOfTrack => 0,
+            Self::Tempo(micros) => {
+                let [_, a, b, c] = micros.to_be_bytes();
+                [fixed[0], fixed[1], fixed[2]] = [a, b, c];
+                3
+            }
+            Self::SmpteOffset(bytes) => {
+                *fixed = bytes;
+                5
+            }
+            Self::TimeSignature {
+                numerator,
+                denominator_pow2,
+                clocks_per_click,
+                thirty_seconds_per_quarter,
+            } => {
+                [fixed[0], fixed[1], fixed[2], fixed[3]] =
+                    [numerator, denominator_pow2, clocks_per_click, thirty_seconds_per_quarter];
+                4
+            }
+            Self::KeySignature { sharps, minor } => {
+                [fixed[0], fixed[1]] = [sharps as u8, minor as u8];
+                2
+            }
+        };
+        fixed.split_at(len).0
+    }
+}
@@ -0,0 +1,23 @@
+// devela/src/sys/device/midi/smf/mod.rs
+//
+//! Standard MIDI Files.
+//
+// See: <https://midi.org/standard-midi-files-specification>
+
+mod _helper; // variable-length quantities
+
+mod event; // SmfDivision, SmfEvent, SmfEventKind, SmfFormat, SmfMeta
+mod read; // Smf, SmfEvents, SmfTrack, SmfTracks
+mod tempo; // SmfTempo, SmfTempoMap
+mod write; // SmfWriter
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            event::*,
+            read::*,
+            tempo::*,
+            write::*,
+        };
+    }
+}
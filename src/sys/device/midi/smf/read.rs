@@ -0,0 +1,264 @@
+// devela/src/sys/device/midi/smf/read.rs
+//
+//! Defines [`Smf`], [`SmfEvents`], [`SmfTrack`], [`SmfTracks`].
+//
+
+use super::_helper::read_vlq;
+use crate::{MidiError, MidiMessage, SmfDivision, SmfEvent, SmfEventKind, SmfFormat, SmfMeta};
+use crate::{SmfTempo, SmfTempoMap, is};
+
+#[doc = crate::_tags!(audio parser)]
+/// A Standard MIDI File, borrowed from its bytes.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+///
+/// Reading validates the header and the chunk structure.
+/// The events of each track are parsed lazily while iterating them.
+///
+/// Chunks of unknown type are skipped, as the specification requires.
+///
+/// # Example
+/// ```
+/// # use devela::{MidiMessage, Smf, SmfDivision, SmfEventKind, SmfFormat, SmfWriter};
+/// let mut buf = [0; 64];
+/// let mut writer = SmfWriter::new(&mut buf, SmfFormat::Single, SmfDivision::Ticks(96));
+/// let on = MidiMessage::NoteOn { channel: 0, note: 60, velocity: 64 };
+/// writer.event(0, SmfEventKind::Midi(on)).unwrap();
+/// let len = writer.finish().unwrap();
+///
+/// let smf = Smf::read(&buf[..len]).unwrap();
+/// assert_eq![(smf.format(), smf.track_count()), (SmfFormat::Single, 1)];
+/// let track = smf.tracks().next().unwrap();
+/// let event = track.events().next().unwrap().unwrap();
+/// assert_eq![event.kind, SmfEventKind::Midi(on)];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Smf<'a> {
+    /// The bytes of the chunks after the header.
+    chunks: &'a [u8],
+    format: SmfFormat,
+    division: SmfDivision,
+    track_count: u16,
+}
+
+impl<'a> Smf<'a> {
+    /// The type of the header chunk.
+    pub const HEADER_MAGIC: [u8; 4] = *b"MThd";
+    /// The type of a track chunk.
+    pub const TRACK_MAGIC: [u8; 4] = *b"MTrk";
+
+    /// Reads a Standard MIDI File from `bytes`.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InvalidHeader`] if the header is missing or malformed,
+    /// [`MidiError::UnsupportedFormat`] for an unknown format,
+    /// [`MidiError::Truncated`] if a chunk is truncated,
+    /// or [`MidiError::InvalidTrackCount`] if the number of tracks
+    /// doesn't match the header, or isn't 1 for format 0.
+    pub fn read(bytes: &'a [u8]) -> Result<Self, MidiError> {
+        let (magic, len) = chunk_header(bytes, 0).ok_or(MidiError::InvalidHeader)?;
+        is![magic != Self::HEADER_MAGIC || len < 6, return Err(MidiError::InvalidHeader)];
+        let header = bytes.get(8..8 + len).ok_or(MidiError::InvalidHeader)?;
+        let number = u16::from_be_bytes([header[0], header[1]]);
+        let format = SmfFormat::from_number(number).ok_or(MidiError::UnsupportedFormat(number))?;
+        let track_count = u16::from_be_bytes([header[2], header[3]]);
+        let division = SmfDivision::from_raw(u16::from_be_bytes([header[4], header[5]]));
+        let division = division.ok_or(MidiError::InvalidHeader)?;
+        let chunks = &bytes[8 + len..];
+
+        let (mut at, mut tracks) = (0, 0_usize);
+        // trailing padding too short for a chunk is ignored
+        while at + 8 <= chunks.len() {
+            let (magic, len) = chunk_header(chunks, at).ok_or(MidiError::Truncated)?;
+            at = at
+                .checked_add(8 + len)
+                .filter(|&end| end <= chunks.len())
+                .ok_or(MidiError::Truncated)?;
+            is![magic == Self::TRACK_MAGIC, tracks += 1];
+        }
+        let expected = is![format == SmfFormat::Single, 1, usize::from(track_count)];
+        is![
+            tracks != expected || tracks != usize::from(track_count),
+            return Err(MidiError::InvalidTrackCount)
+        ];
+        Ok(Self { chunks, format, division, track_count })
+    }
+
+    /// Returns the format.
+    #[must_use]
+    pub const fn format(&self) -> SmfFormat {
+        self.format
+    }
+    /// Returns the time division.
+    #[must_use]
+    pub const fn division(&self) -> SmfDivision {
+        self.division
+    }
+    /// Returns the number of tracks.
+    #[must_use]
+    pub const fn track_count(&self) -> u16 {
+        self.track_count
+    }
+    /// Returns an iterator over the tracks.
+    #[must_use]
+    pub const fn tracks(&self) -> SmfTracks<'a> {
+        SmfTracks { chunks: self.chunks, at: 0 }
+    }
+    /// Returns the track at `index`.
+    #[must_use]
+    pub fn track(&self, index: usize) -> Option<SmfTrack<'a>> {
+        self.tracks().nth(index)
+    }
+
+    /// Gathers the tempo changes of every track into `buf`, and returns its tempo map.
+    ///
+    /// For [`SmfFormat::Independent`] files use [`SmfTrack::tempo_map`] instead,
+    /// since each track has its own tempo map.
+    ///
+    /// # Errors
+    /// Returns the errors of parsing the events, or [`MidiError::InsufficientBuffer`]
+    /// if `buf` can't hold every tempo change.
+    pub fn tempo_map<'m>(&self, buf: &'m mut [SmfTempo]) -> Result<SmfTempoMap<'m>, MidiError> {
+        SmfTempoMap::from_tracks(self.division, self.tracks(), buf)
+    }
+}
+
+#[doc = crate::_tags!(audio iterator)]
+/// An iterator over the tracks of an [`Smf`].
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[derive(Clone, Debug)]
+pub struct SmfTracks<'a> {
+    chunks: &'a [u8],
+    at: usize,
+}
+impl<'a> Iterator for SmfTracks<'a> {
+    type Item = SmfTrack<'a>;
+    fn next(&mut self) -> Option<SmfTrack<'a>> {
+        while let Some((magic, len)) = chunk_header(self.chunks, self.at) {
+            let data = &self.chunks[self.at + 8..][..len];
+            self.at += 8 + len;
+            is![magic == Smf::TRACK_MAGIC, return Some(SmfTrack { data })];
+        }
+        None
+    }
+}
+
+#[doc = crate::_tags!(audio)]
+/// A track of an [`Smf`].
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SmfTrack<'a> {
+    data: &'a [u8],
+}
+impl<'a> SmfTrack<'a> {
+    /// Returns a track over the bytes of its chunk, without the chunk header.
+    #[must_use]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+    /// Returns the bytes of the chunk, without the chunk header.
+    #[must_use]
+    pub const fn data(&self) -> &'a [u8] {
+        self.data
+    }
+    /// Returns an iterator over the events.
+    #[must_use]
+    pub const fn events(&self) -> SmfEvents<'a> {
+        SmfEvents { data: self.data, at: 0, running: 0, done: false }
+    }
+    /// Gathers the tempo changes of this track into `buf`, and returns its tempo map.
+    ///
+    /// # Errors
+    /// Returns the errors of parsing the events, or [`MidiError::InsufficientBuffer`]
+    /// if `buf` can't hold every tempo change.
+    pub fn tempo_map<'m>(
+        &self,
+        division: SmfDivision,
+        buf: &'m mut [SmfTempo],
+    ) -> Result<SmfTempoMap<'m>, MidiError> {
+        SmfTempoMap::from_tracks(division, [*self], buf)
+    }
+}
+
+#[doc = crate::_tags!(audio iterator parser)]
+/// An iterator over the events of an [`SmfTrack`].
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+///
+/// Follows running status, and stops after the end of the track,
+/// or after the first error.
+#[derive(Clone, Debug)]
+pub struct SmfEvents<'a> {
+    data: &'a [u8],
+    at: usize,
+    /// The running status, or 0.
+    running: u8,
+    done: bool,
+}
+impl<'a> SmfEvents<'a> {
+    fn read_event(&mut self) -> Result<SmfEvent<'a>, MidiError> {
+        let data = self.data;
+        let delta = read_vlq(data, &mut self.at)?;
+        let &first = data.get(self.at).ok_or(MidiError::Truncated)?;
+        let kind = match first {
+            0xFF => {
+                let &kind = data.get(self.at + 1).ok_or(MidiError::Truncated)?;
+                self.at += 2;
+                let payload = self.payload()?;
+                is![kind > 0x7F, return Err(MidiError::InvalidMeta(kind))];
+                let meta = SmfMeta::parse(kind, payload)?;
+                is![matches![meta, SmfMeta::EndOfTrack], self.done = true];
+                SmfEventKind::Meta(meta)
+            }
+            0xF0 | 0xF7 => {
+                self.at += 1;
+                let payload = self.payload()?;
+                is![first == 0xF0, SmfEventKind::SysEx(payload), SmfEventKind::Escape(payload)]
+            }
+            0xF1.. => return Err(MidiError::InvalidStatus(first)),
+            _ => {
+                let status = if first >= 0x80 {
+                    self.at += 1;
+                    self.running = first;
+                    first
+                } else {
+                    is![self.running == 0, return Err(MidiError::MissingStatus)];
+                    self.running
+                };
+                let len = is![(0xC0..0xE0).contains(&status), 1, 2];
+                let bytes = data.get(self.at..self.at + len).ok_or(MidiError::Truncated)?;
+                is![bytes.iter().any(|&b| b > 0x7F), return Err(MidiError::InvalidData)];
+                self.at += len;
+                let data = [bytes[0], bytes.get(1).copied().unwrap_or(0)];
+                let message = MidiMessage::from_parts(status, data);
+                let message = message.ok_or(MidiError::InvalidStatus(status))?;
+                return Ok(SmfEvent::new(delta, SmfEventKind::Midi(message)));
+            }
+        };
+        // system exclusive and meta events cancel the running status
+        self.running = 0;
+        Ok(SmfEvent::new(delta, kind))
+    }
+    /// Reads a length-prefixed payload.
+    fn payload(&mut self) -> Result<&'a [u8], MidiError> {
+        let len = read_vlq(self.data, &mut self.at)? as usize;
+        let payload = self.data.get(self.at..self.at + len).ok_or(MidiError::Truncated)?;
+        self.at += len;
+        Ok(payload)
+    }
+}
+impl<'a> Iterator for SmfEvents<'a> {
+    type Item = Result<SmfEvent<'a>, MidiError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        is![self.done || self.at >= self.data.len(), return None];
+        let event = self.read_event();
+        is![event.is_err(), self.done = true];
+        Some(event)
+    }
+}
+
+/// Returns the type and length of the chunk at `at`, if its header fits.
+fn chunk_header(bytes: &[u8], at: usize) -> Option<([u8; 4], usize)> {
+    let header = bytes.get(at..at + 8)?;
+    let magic = [header[0], header[1], header[2], header[3]];
+    let len = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+    Some((magic, len as usize))
+}
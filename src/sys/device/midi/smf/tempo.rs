@@ -0,0 +1,193 @@
+// devela/src/sys/device/midi/smf/tempo.rs
+//
+//! Defines [`SmfTempo`], [`SmfTempoMap`].
+//
+
+#[cfg(feature = "time")]
+use crate::TimeDelta;
+use crate::{MidiError, SmfDivision, SmfEventKind, SmfMeta, SmfTrack, is};
+
+#[doc = crate::_tags!(audio time)]
+/// A tempo change of a Standard MIDI File.
+#[doc = crate::_doc_meta!{
+    location("sys/device/midi"),
+    test_size_of(SmfTempo = 16|128),
+}]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SmfTempo {
+    /// The absolute tick at which the tempo changes.
+    pub tick: u64,
+    /// The new tempo, in microseconds per quarter note.
+    pub micros_per_quarter: u32,
+}
+impl SmfTempo {
+    /// The tempo in effect before any change: 120 beats per minute.
+    pub const DEFAULT_MICROS: u32 = 500_000;
+
+    /// Returns a new tempo change.
+    #[must_use]
+    pub const fn new(tick: u64, micros_per_quarter: u32) -> Self {
+        Self { tick, micros_per_quarter }
+    }
+}
+
+#[doc = crate::_tags!(audio time)]
+/// Converts between the ticks and the time of a Standard MIDI File.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+///
+/// With [`SmfDivision::Ticks`], each tick lasts a fraction of the quarter note
+/// of the tempo in effect, which is [`SmfTempo::DEFAULT_MICROS`] until the first change.
+/// With [`SmfDivision::Smpte`], ticks are absolute and the tempo changes are ignored.
+///
+/// Times are rounded down, and computed from the start so that rounding errors
+/// don't accumulate.
+///
+/// # Example
+/// ```
+/// # use devela::{SmfDivision, SmfTempo, SmfTempoMap};
+/// // 120 bpm, and 60 bpm from the second beat
+/// let tempos = [SmfTempo::new(96, 1_000_000)];
+/// let map = SmfTempoMap::new(SmfDivision::Ticks(96), &tempos);
+/// assert_eq![map.tick_to_micros(96), 500_000];
+/// assert_eq![map.tick_to_micros(144), 1_000_000];
+/// assert_eq![map.micros_to_tick(1_000_000), 144];
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SmfTempoMap<'m> {
+    division: SmfDivision,
+    tempos: &'m [SmfTempo],
+}
+
+impl<'m> SmfTempoMap<'m> {
+    /// Returns a tempo map over `tempos`, which must be sorted by tick.
+    ///
+    /// Of several changes at the same tick, the last one applies.
+    #[must_use]
+    pub const fn new(division: SmfDivision, tempos: &'m [SmfTempo]) -> Self {
+        Self { division, tempos }
+    }
+
+    /// Gathers the tempo changes of `tracks` into `buf`, sorted by tick,
+    /// and returns their tempo map.
+    ///
+    /// # Errors
+    /// Returns the errors of parsing the events, or [`MidiError::InsufficientBuffer`]
+    /// if `buf` can't hold every tempo change.
+    pub fn from_tracks<'a>(
+        division: SmfDivision,
+        tracks: impl IntoIterator<Item = SmfTrack<'a>>,
+        buf: &'m mut [SmfTempo],
+    ) -> Result<Self, MidiError> {
+        let mut len = 0;
+        for track in tracks {
+            let mut tick = 0_u64;
+            for event in track.events() {
+                let event = event?;
+                tick += u64::from(event.delta);
+                let SmfEventKind::Meta(SmfMeta::Tempo(micros)) = event.kind else { continue };
+                if len < buf.len() {
+                    // keep the changes sorted, after those at the same tick
+                    let at = buf[..len].partition_point(|t| t.tick <= tick);
+                    buf.copy_within(at..len, at + 1);
+                    buf[at] = SmfTempo::new(tick, micros);
+                }
+                len += 1;
+            }
+        }
+        is![len > buf.len(), return Err(MidiError::insufficient_buffer(len, buf.len()))];
+        Ok(Self::new(division, &buf[..len]))
+    }
+
+    /// Returns the time division.
+    #[must_use]
+    pub const fn division(&self) -> SmfDivision {
+        self.division
+    }
+    /// Returns the tempo changes.
+    #[must_use]
+    pub const fn tempos(&self) -> &'m [SmfTempo] {
+        self.tempos
+    }
+    /// Returns the tempo in effect at `tick`, in microseconds per quarter note.
+    #[must_use]
+    pub const fn tempo_at(&self, tick: u64) -> u32 {
+        let mut tempo = SmfTempo::DEFAULT_MICROS;
+        let mut i = 0;
+        while i < self.tempos.len() && self.tempos[i].tick <= tick {
+            tempo = self.tempos[i].micros_per_quarter;
+            i += 1;
+        }
+        tempo
+    }
+
+    /// Returns the time at `tick`, in nanoseconds.
+    #[must_use]
+    pub const fn tick_to_nanos(&self, tick: u64) -> u64 {
+        let nanos = match self.division {
+            SmfDivision::Ticks(per_quarter) => {
+                // the sum of ticks times microseconds per quarter
+                let (mut sum, mut from, mut tempo) = (0_u128, 0, SmfTempo::DEFAULT_MICROS as u128);
+                let mut i = 0;
+                while i < self.tempos.len() && self.tempos[i].tick <= tick {
+                    let change = self.tempos[i];
+                    sum += (change.tick - from) as u128 * tempo;
+                    (from, tempo) = (change.tick, change.micros_per_quarter as u128);
+                    i += 1;
+                }
+                sum += (tick - from) as u128 * tempo;
+                sum * 1_000 / is![per_quarter == 0, 1, per_quarter as u128]
+            }
+            SmfDivision::Smpte { fps, ticks_per_frame } => {
+                let (num, den) = smpte_rate(fps);
+                let per_sec = num * is![ticks_per_frame == 0, 1, ticks_per_frame as u128];
+                tick as u128 * 1_000_000_000 * den / per_sec
+            }
+        };
+        is![nanos > u64::MAX as u128, u64::MAX, nanos as u64]
+    }
+    /// Returns the time at `tick`, in microseconds.
+    #[must_use]
+    pub const fn tick_to_micros(&self, tick: u64) -> u64 {
+        self.tick_to_nanos(tick) / 1_000
+    }
+    /// Returns the time at `tick`.
+    #[must_use]
+    #[cfg(feature = "time")]
+    #[cfg_attr(nightly_doc, doc(cfg(feature = "time")))]
+    pub const fn tick_to_time(&self, tick: u64) -> TimeDelta {
+        let nanos = self.tick_to_nanos(tick);
+        TimeDelta::new((nanos / 1_000_000_000) as i64, (nanos % 1_000_000_000) as i32)
+    }
+
+    /// Returns the last tick at or before the time of `micros` microseconds.
+    #[must_use]
+    pub const fn micros_to_tick(&self, micros: u64) -> u64 {
+        let tick = match self.division {
+            SmfDivision::Ticks(per_quarter) => {
+                // the target and the elapsed time, in ticks times microseconds per quarter
+                let target = micros as u128 * per_quarter as u128;
+                let (mut sum, mut from, mut tempo) = (0_u128, 0, SmfTempo::DEFAULT_MICROS as u128);
+                let mut i = 0;
+                while i < self.tempos.len() {
+                    let change = self.tempos[i];
+                    let span = (change.tick - from) as u128 * tempo;
+                    is![sum + span > target, break];
+                    sum += span;
+                    (from, tempo) = (change.tick, change.micros_per_quarter as u128);
+                    i += 1;
+                }
+                from as u128 + (target - sum) / is![tempo == 0, 1, tempo]
+            }
+            SmfDivision::Smpte { fps, ticks_per_frame } => {
+                let (num, den) = smpte_rate(fps);
+                micros as u128 * num * ticks_per_frame as u128 / (1_000_000 * den)
+            }
+        };
+        is![tick > u64::MAX as u128, u64::MAX, tick as u64]
+    }
+}
+
+/// Returns the frame rate as a fraction, with 29 meaning 29.97 drop frame.
+const fn smpte_rate(fps: u8) -> (u128, u128) {
+    is![fps == 29, (30_000, 1_001), (is![fps == 0, 1, fps as u128], 1)]
+}
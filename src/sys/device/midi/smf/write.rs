@@ -0,0 +1,190 @@
+// devela/src/sys/device/midi/smf/write.rs
+//
+//! Defines [`SmfWriter`].
+//
+
+use super::_helper::encode_vlq;
+use crate::{MidiError, Smf, SmfDivision, SmfEventKind, SmfFormat, SmfMeta, is};
+
+#[doc = crate::_tags!(audio codec)]
+/// Writes a Standard MIDI File into a byte buffer.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+///
+/// Events are appended to the open track, using running status.
+/// Writing an event outside a track begins a new one, and ending a track
+/// appends its end-of-track event if it wasn't written already.
+///
+/// Writing goes on even when the buffer is full, so that
+/// [`finish`][Self::finish] can report the length needed.
+///
+/// # Example
+/// ```
+/// # use devela::{MidiMessage as M, Smf, SmfDivision, SmfEventKind as E, SmfFormat, SmfMeta};
+/// # use devela::SmfWriter;
+/// let mut buf = [0; 128];
+/// let mut writer = SmfWriter::new(&mut buf, SmfFormat::Simultaneous, SmfDivision::Ticks(480));
+/// // the tempo track
+/// writer.event(0, E::Meta(SmfMeta::Tempo(400_000))).unwrap();
+/// writer.end_track();
+/// // the notes track
+/// writer.event(0, E::Midi(M::NoteOn { channel: 0, note: 60, velocity: 80 })).unwrap();
+/// writer.event(480, E::Midi(M::NoteOn { channel: 0, note: 60, velocity: 0 })).unwrap();
+/// let len = writer.finish().unwrap();
+///
+/// let smf = Smf::read(&buf[..len]).unwrap();
+/// assert_eq![smf.track_count(), 2];
+/// let mut tempos = [Default::default(); 4];
+/// let map = smf.tempo_map(&mut tempos).unwrap();
+/// assert_eq![map.tick_to_micros(480), 400_000];
+/// ```
+#[derive(Debug)]
+pub struct SmfWriter<'b> {
+    buf: &'b mut [u8],
+    /// The number of bytes written, which may exceed the buffer.
+    len: usize,
+    format: SmfFormat,
+    division: SmfDivision,
+    tracks: u16,
+    /// The offset of the open track chunk.
+    track: Option<usize>,
+    /// The running status, or 0.
+    running: u8,
+    /// Whether a track was written beyond the format limit.
+    overflow: bool,
+}
+
+impl<'b> SmfWriter<'b> {
+    /// Starts writing a file of the given `format` and `division` into `buf`.
+    pub fn new(buf: &'b mut [u8], format: SmfFormat, division: SmfDivision) -> Self {
+        let mut writer = Self {
+            buf,
+            len: 0,
+            format,
+            division,
+            tracks: 0,
+            track: None,
+            running: 0,
+            overflow: false,
+        };
+        writer.put(&Smf::HEADER_MAGIC);
+        writer.put(&6_u32.to_be_bytes());
+        writer.put(&format.number().to_be_bytes());
+        writer.put(&[0; 2]); // track count
+        writer.put(&division.to_raw().unwrap_or(0).to_be_bytes());
+        writer
+    }
+
+    /// Returns the number of bytes written so far, which may exceed the buffer.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns whether nothing but the header has been written.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 14
+    }
+    /// Returns the number of tracks begun so far.
+    #[must_use]
+    pub const fn track_count(&self) -> u16 {
+        self.tracks
+    }
+
+    /// Begins a new track, ending the open one.
+    pub fn begin_track(&mut self) {
+        self.end_track();
+        is![self.format == SmfFormat::Single && self.tracks > 0, self.overflow = true];
+        self.tracks = self.tracks.saturating_add(1);
+        self.track = Some(self.len);
+        self.running = 0;
+        self.put(&Smf::TRACK_MAGIC);
+        self.put(&[0; 4]); // chunk length
+    }
+
+    /// Appends an event `delta` ticks after the previous one of the track.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InvalidVarLen`] if `delta` or a length is too large,
+    /// [`MidiError::InvalidStatus`] for a MIDI message that isn't a channel message,
+    /// or [`MidiError::InvalidData`] if a field is out of range.
+    /// Nothing is written on error.
+    pub fn event(&mut self, delta: u32, kind: SmfEventKind) -> Result<(), MidiError> {
+        let (delta, delta_len) = encode_vlq(delta)?;
+        let mut fixed = [0; 5];
+        let (prefix, payload): (&[u8], &[u8]) = match &kind {
+            SmfEventKind::Midi(message) => {
+                is![!message.is_channel(), return Err(MidiError::InvalidStatus(message.status()))];
+                is![!message.is_valid(), return Err(MidiError::InvalidData)];
+                is![self.track.is_none(), self.begin_track()];
+                self.put(&delta[..delta_len]);
+                let status = message.status();
+                is![status != self.running, self.put(&[status])];
+                self.running = status;
+                let mut bytes = [0; 3];
+                message.encode_data(&mut bytes);
+                self.put(&bytes[1..message.encoded_len()]);
+                return Ok(());
+            }
+            SmfEventKind::SysEx(data) => (&[0xF0], data),
+            SmfEventKind::Escape(data) => (&[0xF7], data),
+            SmfEventKind::Meta(meta) => {
+                is![meta.kind() > 0x7F, return Err(MidiError::InvalidMeta(meta.kind()))];
+                (&[0xFF, meta.kind()], meta.payload(&mut fixed))
+            }
+        };
+        let (len, len_len) = encode_vlq(u32::try_from(payload.len()).unwrap_or(u32::MAX))?;
+        is![self.track.is_none(), self.begin_track()];
+        self.put(&delta[..delta_len]);
+        self.put(prefix);
+        self.put(&len[..len_len]);
+        self.put(payload);
+        self.running = 0;
+        if matches![kind, SmfEventKind::Meta(SmfMeta::EndOfTrack)] {
+            self.close_track();
+        }
+        Ok(())
+    }
+
+    /// Ends the open track, if any, appending its end-of-track event.
+    pub fn end_track(&mut self) {
+        if self.track.is_some() {
+            self.put(&[0, 0xFF, 0x2F, 0]);
+            self.close_track();
+        }
+    }
+
+    /// Ends the open track, and returns the length of the file.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InvalidTrackCount`] if there are no tracks,
+    /// or more than one for format 0, [`MidiError::InvalidHeader`] if the division
+    /// is invalid, or [`MidiError::InsufficientBuffer`] if `buf` is too short.
+    pub fn finish(mut self) -> Result<usize, MidiError> {
+        self.end_track();
+        is![self.tracks == 0 || self.overflow, return Err(MidiError::InvalidTrackCount)];
+        is![self.division.to_raw().is_none(), return Err(MidiError::InvalidHeader)];
+        let (len, available) = (self.len, self.buf.len());
+        is![len > available, return Err(MidiError::insufficient_buffer(len, available))];
+        self.buf[10..12].copy_from_slice(&self.tracks.to_be_bytes());
+        Ok(len)
+    }
+
+    /* helpers */
+
+    /// Writes the length of the open track into its chunk header, and closes it.
+    fn close_track(&mut self) {
+        if let Some(start) = self.track.take() {
+            let len = u32::try_from(self.len - start - 8).unwrap_or(u32::MAX);
+            if let Some(dst) = self.buf.get_mut(start + 4..start + 8) {
+                dst.copy_from_slice(&len.to_be_bytes());
+            }
+        }
+    }
+    /// Writes as many `bytes` as fit, while counting all of them.
+    fn put(&mut self, bytes: &[u8]) {
+        if let Some(dst) = self.buf.get_mut(self.len..self.len + bytes.len()) {
+            dst.copy_from_slice(bytes);
+        }
+        self.len += bytes.len();
+    }
+}
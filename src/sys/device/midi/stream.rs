@@ -0,0 +1,196 @@
+// devela/src/sys/device/midi/stream.rs
+//
+//! Defines [`MidiParser`], [`MidiEncoder`].
+//
+
+use crate::{_impl_init, MidiError, MidiMessage, is};
+
+#[doc = crate::_tags!(audio parser)]
+/// Parses a MIDI 1.0 byte stream, one byte at a time.
+#[doc = crate::_doc_meta!{location("sys/device/midi")}]
+///
+/// Follows running status, and returns real-time messages as soon as they
+/// arrive, even in the middle of another message.
+///
+/// System exclusive data is gathered in a borrowed buffer. A system exclusive
+/// message interrupted by another status is discarded, and data bytes without
+/// a status in effect are skipped, as when joining a stream mid-message.
+///
+/// # Example
+/// ```
+/// # use devela::{MidiMessage as M, MidiParser};
+/// let mut sysex = [0; 16];
+/// let mut parser = MidiParser::new(&mut sysex);
+/// let mut notes = 0;
+/// // two notes with running status, with a timing clock in between
+/// for byte in [0x90, 60, 100, 64, 0xF8, 100] {
+///     match parser.push(byte) {
+///         Ok(Some(M::NoteOn { velocity: 100, .. })) => notes += 1,
+///         Ok(Some(message)) => assert_eq![message, M::TimingClock],
+///         Ok(None) => (),
+///         Err(e) => panic!("{e}"),
+///     }
+/// }
+/// assert_eq![notes, 2];
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct MidiParser<'b> {
+    sysex: &'b mut [u8],
+    /// The number of SysEx bytes received, which may exceed the buffer.
+    sysex_len: usize,
+    in_sysex: bool,
+    /// The status of the message being received, or 0.
+    status: u8,
+    data: [u8; 2],
+    len: u8,
+}
+
+impl<'b> MidiParser<'b> {
+    /// Returns a parser that gathers system exclusive data into `sysex`.
+    #[must_use]
+    pub const fn new(sysex: &'b mut [u8]) -> Self {
+        Self {
+            sysex,
+            sysex_len: 0,
+            in_sysex: false,
+            status: 0,
+            data: [0; 2],
+            len: 0,
+        }
+    }
+    /// Returns the running status in effect, if any.
+    #[must_use]
+    pub const fn running_status(&self) -> Option<u8> {
+        is![self.status >= 0x80 && self.status < 0xF0, Some(self.status), None]
+    }
+    /// Forgets the running status and any partial message.
+    pub const fn reset(&mut self) {
+        (self.status, self.len, self.in_sysex) = (0, 0, false);
+    }
+
+    /// Feeds the next `byte`, and returns the message it completes, if any.
+    ///
+    /// Undefined status bytes are skipped, and cancel the running status.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InsufficientBuffer`] when a system exclusive message
+    /// ends that didn't fit in the buffer.
+    pub fn push(&mut self, byte: u8) -> Result<Option<MidiMessage<'_>>, MidiError> {
+        if byte >= 0xF8 {
+            return Ok(MidiMessage::from_parts(byte, [0; 2]));
+        }
+        if byte < 0x80 {
+            if self.in_sysex {
+                is![self.sysex_len < self.sysex.len(), self.sysex[self.sysex_len] = byte];
+                self.sysex_len += 1;
+                return Ok(None);
+            }
+            let Some(needed) = MidiMessage::data_len(self.status) else { return Ok(None) };
+            self.data[self.len as usize] = byte;
+            self.len += 1;
+            is![usize::from(self.len) < needed, return Ok(None)];
+            self.len = 0;
+            let message = MidiMessage::from_parts(self.status, self.data);
+            // only channel messages keep their status running
+            is![self.status >= 0xF0, self.status = 0];
+            return Ok(message);
+        }
+        self.len = 0;
+        self.status = 0;
+        match byte {
+            0xF0 => (self.in_sysex, self.sysex_len) = (true, 0),
+            0xF7 if self.in_sysex => {
+                self.in_sysex = false;
+                let (len, available) = (self.sysex_len, self.sysex.len());
+                is![len > available, return Err(MidiError::insufficient_buffer(len, available))];
+                return Ok(Some(MidiMessage::SysEx(&self.sysex[..len])));
+            }
+            _ => {
+                self.in_sysex = false;
+                match MidiMessage::data_len(byte) {
+                    Some(0) => return Ok(MidiMessage::from_parts(byte, [0; 2])),
+                    Some(_) => self.status = byte,
+                    None => {}
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[doc = crate::_tags!(audio codec)]
+/// Encodes MIDI 1.0 messages into a byte stream, using running status.
+#[doc = crate::_doc_meta!{
+    location("sys/device/midi"),
+    test_size_of(MidiEncoder = 1|8),
+}]
+///
+/// The status byte of a channel message is omitted when it repeats the last one.
+/// System common and exclusive messages cancel the running status,
+/// while real-time messages leave it untouched.
+///
+/// # Example
+/// ```
+/// # use devela::{MidiEncoder, MidiMessage as M};
+/// let mut encoder = MidiEncoder::new();
+/// let mut buf = [0; 8];
+/// let mut len = encoder.encode(&M::NoteOn { channel: 0, note: 60, velocity: 90 }, &mut buf).unwrap();
+/// len += encoder.encode(&M::NoteOn { channel: 0, note: 60, velocity: 0 }, &mut buf[len..]).unwrap();
+/// assert_eq![&buf[..len], &[0x90, 60, 90, 60, 0]];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MidiEncoder {
+    /// The running status, or 0.
+    running: u8,
+}
+_impl_init![Self::new() => MidiEncoder];
+
+impl MidiEncoder {
+    /// Returns an encoder without a running status.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { running: 0 }
+    }
+    /// Forgets the running status, so that the next status byte is always sent.
+    ///
+    /// Useful to resynchronize receivers that may have missed it.
+    pub const fn reset(&mut self) {
+        self.running = 0;
+    }
+
+    /// Encodes `message` into `buf`, returning the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns [`MidiError::InvalidData`] if a field is out of range,
+    /// or [`MidiError::InsufficientBuffer`] if `buf` is too short.
+    pub const fn encode(
+        &mut self,
+        message: &MidiMessage,
+        buf: &mut [u8],
+    ) -> Result<usize, MidiError> {
+        let status = message.status();
+        if status == self.running {
+            is![!message.is_valid(), return Err(MidiError::InvalidData)];
+            let len = message.encoded_len() - 1;
+            is![buf.len() < len, return Err(MidiError::insufficient_buffer(len, buf.len()))];
+            let mut full = [0; 3];
+            message.encode_data(&mut full);
+            let mut i = 0;
+            while i < len {
+                buf[i] = full[i + 1];
+                i += 1;
+            }
+            return Ok(len);
+        }
+        let len = match message.encode(buf) {
+            Ok(len) => len,
+            Err(e) => return Err(e),
+        };
+        if status < 0xF0 {
+            self.running = status;
+        } else if status < 0xF8 {
+            self.running = 0;
+        }
+        Ok(len)
+    }
+}
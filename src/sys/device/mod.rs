@@ -1,7 +1,7 @@
 // devela/src/sys/device/mod.rs
 //
 #![doc = crate::_DOC_SYS_DEVICE!()] // public
-#![doc = crate::_doc!(modules: crate::sys; device: audio, display)] // gpu, midi
+#![doc = crate::_doc!(modules: crate::sys; device: audio, display, midi)] // gpu
 #![doc = crate::_doc!(flat:"sys")]
 #![doc = crate::_doc!(hr)]
 #![doc = crate::_QUO_SYS_DEVICE!()]
@@ -16,14 +16,14 @@
 
 pub mod audio; // {alsa}
 pub mod display; // {x11}
-// pub mod midi; //
+pub mod midi;
 
 crate::structural_mods! { // _pub_mods, _crate_internals
     _pub_mods {
         pub use super::{
             audio::_all::*,
             display::_all::*,
-            // midi::_all::*,
+            midi::_all::*,
         };
     }
     _crate_internals {
@@ -1,7 +1,7 @@
 // devela/src/text/layout/wrap/_test.rs
 
 use super::*;
-use crate::{TextIndex, assert_eq};
+use crate::{TextIndex, assert_eq, is};
 
 #[test]
 fn segments_from_chars_splits_boxes_glue_and_hard_breaks() {
@@ -118,6 +118,38 @@ fn wrap_iter_width_zero_returns_none() {
     let mut wrap = TextWrapIter::new(&segs[..len], 0);
     assert_eq![wrap.next_line(), None];
 }
+#[test]
+fn segments_from_measured_sums_box_widths() {
+    let widths = |c: char| is![c == ' ', 3, is![c == 'i', 2, 6]];
+    let mut segs = [TextSegment::INIT; 8];
+    let len = TextSegment::segments_from_measured(
+        "hi  fit\r\nx".chars().map(|c| (c, widths(c))),
+        &mut segs,
+    );
+    assert_eq![len, 5];
+    assert_eq![segs[0].span.units, 8];
+    assert_eq![segs[1].kind, TextSegmentKind::Glue];
+    assert_eq![segs[1].span.end(), TextIndex(4)];
+    assert_eq![segs[1].span.units, 3];
+    assert_eq![segs[2].span.units, 14];
+    assert_eq![segs[3].kind, TextSegmentKind::HardBreak];
+    assert_eq![segs[3].span.end(), TextIndex(9)];
+    assert_eq![segs[4].span.start(), TextIndex(9)];
+}
+#[test]
+fn wrap_iter_uses_glue_units() {
+    let mut segs = [TextSegment::INIT; 8];
+    let measured = "ab cd ef".chars().map(|c| (c, is![c == ' ', 3, 5]));
+    let len = TextSegment::segments_from_measured(measured, &mut segs);
+    let mut wrap = TextWrapIter::new(&segs[..len], 23);
+    let line1 = wrap.next_line().unwrap();
+    assert_eq![line1.span.end(), TextIndex(5)];
+    assert_eq![line1.span.units, 23];
+    let mut wrap = TextWrapIter::new(&segs[..len], 22);
+    let line1 = wrap.next_line().unwrap();
+    assert_eq![line1.span.end(), TextIndex(2)];
+    assert_eq![line1.span.units, 10];
+}
 // TEMP DELETE TODO
 // #[test]
 // fn wraps_simple_paragraph() {
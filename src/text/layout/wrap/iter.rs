@@ -77,6 +77,9 @@ impl TextLine {
 /// `TextWrapIter` applies simple prose wrapping over semantic segments:
 /// boxes stay together when possible, glue collapses between boxes, and hard
 /// breaks force a line boundary.
+///
+/// Glue between boxes on the same line takes its own units, so that segments
+/// measured in pixels wrap the same way as segments measured in cells.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct TextWrapIter<'a> {
     segments: &'a [TextSegment],
@@ -154,7 +157,7 @@ impl<'a> TextWrapIter<'a> {
             line_units = 0;
             last_end = line_start;
         }
-        let mut pending_glue = false;
+        let mut pending_glue: TextUnit = 0;
         while i < self.segments.len() {
             let seg = self.segments[i];
             match seg.kind {
@@ -171,12 +174,11 @@ impl<'a> TextWrapIter<'a> {
                     return Some(TextLine::new(span, carry, break_kind));
                 }
                 TextSegmentKind::Glue => {
-                    pending_glue = line_units > 0;
+                    pending_glue = is! { line_units > 0, seg.span.units, 0 };
                     i += 1;
                 }
                 TextSegmentKind::Box => {
-                    let glue_units = if pending_glue { 1 } else { 0 };
-                    let needed = glue_units + seg.span.units;
+                    let needed = pending_glue + seg.span.units;
                     if line_units > 0 && line_units + needed > self.width {
                         break_kind = Break::Between;
                         break;
@@ -202,7 +204,7 @@ impl<'a> TextWrapIter<'a> {
                     }
                     line_units += needed;
                     last_end = seg.span.end();
-                    pending_glue = false;
+                    pending_glue = 0;
                     i += 1;
                 }
             }
@@ -171,6 +171,62 @@ impl TextSegment {
         }
         seg_len
     }
+
+    /// Fills `out` with paragraph-oriented segments from chars with measured widths.
+    ///
+    /// Follows the same policy as [`segments_from_chars`][Self::segments_from_chars],
+    /// while each box takes the sum of the units of its chars,
+    /// and each glue the units of its first whitespace char.
+    ///
+    /// This lets proportional widths, like shaped glyph advances, drive the wrapping.
+    ///
+    /// Returns the initialized prefix length.
+    pub fn segments_from_measured<I>(measured: I, out: &mut [TextSegment]) -> usize
+    where
+        I: IntoIterator<Item = (char, TextUnit)>,
+    {
+        let (mut seg_len, mut char_i) = (0, 0);
+        let (mut run_start, mut run_units) = (0, 0);
+        let mut run_kind: Option<TextSegmentKind> = None;
+        let mut chars = measured.into_iter().peekable();
+        while let Some((ch, units)) = chars.next() {
+            if ch == '\n' || ch == '\r' {
+                if let Some(kind) = run_kind.take() {
+                    let span = TextLayoutSpan::from_prim(run_start, char_i, run_units);
+                    is! { !push_segment(out, &mut seg_len, span, kind), return seg_len }
+                }
+                let break_start = char_i;
+                char_i += 1;
+                if ch == '\r' && chars.next_if(|(c, _)| *c == '\n').is_some() {
+                    char_i += 1;
+                }
+                let span = TextLayoutSpan::from_prim(break_start, char_i, 0);
+                is! { !push_segment(out, &mut seg_len, span, Kind::HardBreak), return seg_len }
+                continue;
+            }
+            let kind = is! { ch.is_whitespace(), Kind::Glue, Kind::Box };
+            match run_kind {
+                Some(prev) if prev.eq(kind) => {
+                    is! { kind.is_box(), run_units += units }
+                }
+                _ => {
+                    if let Some(prev) = run_kind {
+                        let span = TextLayoutSpan::from_prim(run_start, char_i, run_units);
+                        is! { !push_segment(out, &mut seg_len, span, prev), return seg_len }
+                    }
+                    run_start = char_i;
+                    run_units = units;
+                    run_kind = Some(kind);
+                }
+            }
+            char_i += 1;
+        }
+        if let Some(kind) = run_kind {
+            let span = TextLayoutSpan::from_prim(run_start, char_i, run_units);
+            let _ = push_segment(out, &mut seg_len, span, kind);
+        }
+        seg_len
+    }
 }
 
 const fn push_segment(
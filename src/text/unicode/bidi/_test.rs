@@ -0,0 +1,141 @@
+// devela/src/text/unicode/bidi/_test.rs
+
+use crate::{Bidi, BidiClass as C, BidiLevel, MismatchedCapacity};
+
+/// Resolves the levels of `text`.
+fn levels<const N: usize>(text: &str, paragraph: Option<BidiLevel>) -> (u8, [u8; N]) {
+    let (mut classes, mut levels) = ([C::L; N], [BidiLevel::LTR; N]);
+    let level = Bidi::resolve(text, paragraph, &mut classes, &mut levels).unwrap();
+    (level.get(), levels.map(BidiLevel::get))
+}
+
+#[test]
+fn classes() {
+    let chars = ['a', 'א', 'ب', '5', '+', '$', ',', '٣', '\u{300}', '\u{200B}', '\n', '\t', ' '];
+    let classes = [C::L, C::R, C::AL, C::EN, C::ES, C::ET, C::CS, C::AN, C::NSM, C::BN, C::B];
+    for (c, class) in chars.iter().zip(classes.iter().chain(&[C::S, C::WS])) {
+        assert_eq![C::of(*c), *class, "{c:?}"];
+    }
+    let controls = ['\u{202A}', '\u{202D}', '\u{202B}', '\u{202E}', '\u{202C}'];
+    let classes = [C::LRE, C::LRO, C::RLE, C::RLO, C::PDF];
+    assert_eq![controls.map(C::of), classes];
+    let isolates = ['\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}'];
+    assert_eq![isolates.map(C::of), [C::LRI, C::RLI, C::FSI, C::PDI]];
+    assert_eq![(C::of('!'), C::of('\u{10FFFF}')), (C::ON, C::BN)];
+    // the defaults of unassigned code points
+    assert_eq![(C::of('\u{05FF}'), C::of('\u{074B}'), C::of('\u{E0080}')), (C::R, C::AL, C::BN)];
+    assert_eq![C::of('\u{40000}'), C::L];
+}
+
+#[test]
+fn paragraph_level() {
+    assert_eq![Bidi::paragraph_level("12 abc"), Some(BidiLevel::LTR)];
+    assert_eq![Bidi::paragraph_level("12 אבג abc"), Some(BidiLevel::RTL)];
+    assert_eq![Bidi::paragraph_level("\u{2067}א\u{2069}c"), Some(BidiLevel::LTR)];
+    assert_eq![Bidi::paragraph_level("12\nא"), None];
+    assert_eq![levels::<3>("\u{2067}א\u{2069}", None).0, 0];
+}
+
+#[test]
+fn weak_types() {
+    // numbers after right-to-left letters
+    assert_eq![levels("א 12", None), (1, [1, 1, 2, 2])];
+    // arabic letters make european numbers arabic
+    assert_eq![levels("ب12", None), (1, [1, 2, 2])];
+    // separators between numbers, and terminators next to numbers
+    assert_eq![levels("1,2", Some(BidiLevel::RTL)), (1, [2, 2, 2])];
+    assert_eq![levels("$1", Some(BidiLevel::RTL)), (1, [2, 2])];
+    assert_eq![levels("1+-2", Some(BidiLevel::RTL)), (1, [2, 1, 1, 2])];
+    // european numbers after left-to-right letters
+    assert_eq![levels("a 1", Some(BidiLevel::RTL)), (1, [2, 2, 2])];
+    // nonspacing marks follow their base
+    assert_eq![levels("א\u{300}a", None), (1, [1, 1, 2])];
+}
+
+#[test]
+fn neutral_types() {
+    assert_eq![levels("abc אבג def", None), (0, [0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0])];
+    assert_eq![levels("א, ב", None), (1, [1, 1, 1, 1])];
+    assert_eq![levels("a!", Some(BidiLevel::RTL)), (1, [2, 1])];
+}
+
+#[test]
+fn bracket_pairs() {
+    // the closing bracket follows the pair instead of the embedding direction
+    assert_eq![levels("a(b)", Some(BidiLevel::RTL)), (1, [2, 2, 2, 2])];
+    // brackets with the embedding direction inside
+    assert_eq![levels("a(ב)", None), (0, [0, 0, 1, 0])];
+    // brackets with the opposite direction inside, and no context before
+    assert_eq![levels("א(b)", None), (1, [1, 1, 2, 1])];
+    // mismatched and canonically equivalent brackets
+    assert_eq![levels("a(b]", Some(BidiLevel::RTL)), (1, [2, 2, 2, 1])];
+    assert_eq![levels("a\u{2329}b\u{3009}", Some(BidiLevel::RTL)), (1, [2, 2, 2, 2])];
+}
+
+#[test]
+fn explicit_formatting() {
+    // an isolate takes no part in its surroundings
+    assert_eq![levels("a\u{2067}ב\u{2069}c", None), (0, [0, 0, 1, 0, 0])];
+    assert_eq![levels("a\u{2068}ב\u{2069}c", None), (0, [0, 0, 1, 0, 0])];
+    assert_eq![levels("א\u{2066}b\u{2069}ג", None), (1, [1, 1, 2, 1, 1])];
+    // an override turns letters strong, and the pop gets the previous level
+    assert_eq![levels("\u{202E}abc\u{202C}", None), (0, [0, 1, 1, 1, 1])];
+    assert_eq![levels("\u{202B}a\u{202C}", None), (0, [0, 2, 2])];
+    // an overridden isolate acts as a strong character
+    assert_eq![levels("\u{202E}a\u{2066}b\u{2069}", None), (0, [0, 1, 1, 2, 1])];
+    // unmatched pops are ignored
+    assert_eq![levels("a\u{2069}\u{202C}b", None), (0, [0, 0, 0, 0])];
+}
+
+#[test]
+fn embedding_overflow() {
+    let mut text = [0_u8; 130 * 3 + 1];
+    let mut at = 0;
+    for _ in 0..130 {
+        at += '\u{202A}'.encode_utf8(&mut text[at..]).len();
+    }
+    text[at] = b'a';
+    let text = core::str::from_utf8(&text).unwrap();
+    let (_, levels) = levels::<131>(text, None);
+    // left-to-right embeddings reach the maximum even level
+    assert_eq![levels[130], 124];
+}
+
+#[test]
+fn paragraphs() {
+    assert_eq![levels("א\nb", None), (1, [1, 1, 0])];
+    assert_eq![levels("a\nב", None), (0, [0, 0, 1])];
+    assert_eq![levels("", None), (0, [0; 0])];
+}
+
+#[test]
+fn line_levels() {
+    let (_, resolved) = levels::<4>("\u{202A}a \u{202C}", Some(BidiLevel::RTL));
+    assert_eq![resolved, [1, 2, 2, 2]];
+    let mut line = resolved.map(|l| BidiLevel::new(l).unwrap());
+    Bidi::line_levels("\u{202A}a \u{202C}", BidiLevel::RTL, &mut line);
+    assert_eq![line.map(BidiLevel::get), [1, 2, 1, 1]];
+
+    let (_, resolved) = levels::<4>("ab\tc", Some(BidiLevel::RTL));
+    let mut line = resolved.map(|l| BidiLevel::new(l).unwrap());
+    Bidi::line_levels("ab\tc", BidiLevel::RTL, &mut line);
+    assert_eq![line.map(BidiLevel::get), [2, 2, 1, 2]];
+}
+
+#[test]
+fn visual_order() {
+    let levels = [0, 1, 1, 2, 2, 1, 0].map(|l| BidiLevel::new(l).unwrap());
+    let mut order = [0; 7];
+    Bidi::visual_order(&levels, &mut order).unwrap();
+    assert_eq![order, [0, 5, 3, 4, 2, 1, 6]];
+    let error = Bidi::visual_order(&levels, &mut order[..3]);
+    assert_eq![error, Err(MismatchedCapacity::too_small(3, 7))];
+}
+
+#[test]
+fn errors() {
+    let (mut classes, mut levels) = ([C::L; 2], [BidiLevel::LTR; 3]);
+    let error = Bidi::resolve("abc", None, &mut classes, &mut levels);
+    assert_eq![error, Err(MismatchedCapacity::too_small(2, 3))];
+    assert_eq![BidiLevel::new(127), None];
+}
@@ -0,0 +1,165 @@
+// devela/src/text/unicode/bidi/class.rs
+//
+//! Defines [`BidiClass`].
+//
+
+use super::table::BIDI_CLASS_RANGES;
+use crate::{_impl_init, is};
+
+#[doc = crate::_tags!(text)]
+/// The bidirectional character type of a Unicode scalar.
+#[doc = crate::_doc_meta!{
+    location("text/unicode/bidi"),
+    test_size_of(BidiClass = 1|8; niche Option),
+}]
+///
+/// See [UAX #9, Table 4](https://www.unicode.org/reports/tr9/#Table_Bidirectional_Character_Types).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BidiClass {
+    /* strong */
+    /// Left-to-right, like most letters.
+    #[default]
+    L,
+    /// Right-to-left, like Hebrew letters.
+    R,
+    /// Right-to-left Arabic, like Arabic, Syriac and Thaana letters.
+    AL,
+
+    /* weak */
+    /// European number, like ASCII digits.
+    EN,
+    /// European number separator, like plus and minus signs.
+    ES,
+    /// European number terminator, like currency and percent signs.
+    ET,
+    /// Arabic number, like Arabic-Indic digits.
+    AN,
+    /// Common number separator, like colons, commas and periods.
+    CS,
+    /// Nonspacing mark, like combining accents.
+    NSM,
+    /// Boundary neutral, like most format and control characters.
+    BN,
+
+    /* neutral */
+    /// Paragraph separator, like line feeds.
+    B,
+    /// Segment separator, like tabs.
+    S,
+    /// Whitespace, like spaces.
+    WS,
+    /// Other neutrals, like most punctuation and symbols.
+    ON,
+
+    /* explicit formatting */
+    /// Left-to-right embedding (`U+202A`).
+    LRE,
+    /// Left-to-right override (`U+202D`).
+    LRO,
+    /// Right-to-left embedding (`U+202B`).
+    RLE,
+    /// Right-to-left override (`U+202E`).
+    RLO,
+    /// Pop directional format (`U+202C`).
+    PDF,
+    /// Left-to-right isolate (`U+2066`).
+    LRI,
+    /// Right-to-left isolate (`U+2067`).
+    RLI,
+    /// First strong isolate (`U+2068`).
+    FSI,
+    /// Pop directional isolate (`U+2069`).
+    PDI,
+}
+_impl_init![Self::L => BidiClass];
+
+impl BidiClass {
+    /// Every class, in declaration order.
+    const ALL: [Self; 23] = {
+        use BidiClass as C;
+        [
+            C::L,
+            C::R,
+            C::AL,
+            C::EN,
+            C::ES,
+            C::ET,
+            C::AN,
+            C::CS,
+            C::NSM,
+            C::BN,
+            C::B,
+            C::S,
+            C::WS,
+            C::ON,
+            C::LRE,
+            C::LRO,
+            C::RLE,
+            C::RLO,
+            C::PDF,
+            C::LRI,
+            C::RLI,
+            C::FSI,
+            C::PDI,
+        ]
+    };
+
+    /// Returns the bidirectional class of `c`.
+    ///
+    /// Unassigned code points get the default class of their block.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::BidiClass;
+    /// assert_eq![BidiClass::of('a'), BidiClass::L];
+    /// assert_eq![BidiClass::of('א'), BidiClass::R];
+    /// assert_eq![BidiClass::of('7'), BidiClass::EN];
+    /// ```
+    #[must_use]
+    pub const fn of(c: char) -> Self {
+        let scalar = c as u32;
+        // find the last range starting at or before the scalar
+        let (mut lo, mut hi) = (0, BIDI_CLASS_RANGES.len());
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            is![BIDI_CLASS_RANGES[mid] >> 8 <= scalar, lo = mid, hi = mid];
+        }
+        Self::ALL[(BIDI_CLASS_RANGES[lo] & 0xFF) as usize]
+    }
+
+    /// Returns whether this is a strong class: `L`, `R` or `AL`.
+    #[must_use]
+    pub const fn is_strong(self) -> bool {
+        matches![self, Self::L | Self::R | Self::AL]
+    }
+    /// Returns whether this is a strong right-to-left class: `R` or `AL`.
+    #[must_use]
+    pub const fn is_rtl(self) -> bool {
+        matches![self, Self::R | Self::AL]
+    }
+    /// Returns whether this is an isolate initiator: `LRI`, `RLI` or `FSI`.
+    #[must_use]
+    pub const fn is_isolate_initiator(self) -> bool {
+        matches![self, Self::LRI | Self::RLI | Self::FSI]
+    }
+    /// Returns whether this is an isolate initiator or `PDI`.
+    #[must_use]
+    pub const fn is_isolate_control(self) -> bool {
+        matches![self, Self::LRI | Self::RLI | Self::FSI | Self::PDI]
+    }
+    /// Returns whether this class is ignored when resolving levels:
+    /// an embedding or override control, `PDF`, or `BN`.
+    ///
+    /// These are the classes removed by rule X9.
+    #[must_use]
+    pub const fn is_removed(self) -> bool {
+        matches![self, Self::LRE | Self::LRO | Self::RLE | Self::RLO | Self::PDF | Self::BN]
+    }
+    /// Returns whether this is a neutral or isolate formatting class,
+    /// as handled by rules N1 and N2.
+    #[must_use]
+    pub const fn is_neutral(self) -> bool {
+        matches![self, Self::B | Self::S | Self::WS | Self::ON] || self.is_isolate_control()
+    }
+}
@@ -0,0 +1,47 @@
+// devela/src/text/unicode/bidi/level.rs
+//
+//! Defines [`BidiLevel`].
+//
+
+use crate::_impl_init;
+
+#[doc = crate::_tags!(text)]
+/// A bidirectional embedding level.
+#[doc = crate::_doc_meta!{
+    location("text/unicode/bidi"),
+    test_size_of(BidiLevel = 1|8),
+}]
+///
+/// Even levels are left-to-right, and odd levels are right-to-left.
+/// Explicit embeddings nest up to [`MAX_EXPLICIT`][Self::MAX_EXPLICIT],
+/// and resolved levels reach one more.
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BidiLevel(pub(super) u8);
+_impl_init![Self::LTR => BidiLevel];
+
+#[rustfmt::skip]
+impl BidiLevel {
+    /// The left-to-right paragraph level.
+    pub const LTR: Self = Self(0);
+    /// The right-to-left paragraph level.
+    pub const RTL: Self = Self(1);
+    /// The maximum explicit embedding depth.
+    pub const MAX_EXPLICIT: u8 = 125;
+    /// The maximum resolved level.
+    pub const MAX: u8 = Self::MAX_EXPLICIT + 1;
+
+    /// Returns the given level, or `None` if it is greater than [`MAX`][Self::MAX].
+    pub const fn new(level: u8) -> Option<Self> {
+        if level <= Self::MAX { Some(Self(level)) } else { None }
+    }
+    /// Returns the level number.
+    #[must_use]
+    pub const fn get(self) -> u8 { self.0 }
+    /// Returns whether this level is left-to-right.
+    #[must_use]
+    pub const fn is_ltr(self) -> bool { self.0.is_multiple_of(2) }
+    /// Returns whether this level is right-to-left.
+    #[must_use]
+    pub const fn is_rtl(self) -> bool { self.0 % 2 == 1 }
+}
@@ -0,0 +1,31 @@
+// devela/src/text/unicode/bidi/mod.rs
+//
+#![doc = crate::_DOC_TEXT_UNICODE_BIDI!()] // public
+#![doc = crate::_doc!(modules: crate::text::unicode; bidi)]
+#![doc = crate::_doc!(flat:"text")]
+#![doc = crate::_doc!(hr)]
+//!
+//! Embedding levels and visual ordering of mixed-direction text as
+//! specified by [UAX #9, "Unicode Bidirectional Algorithm"][0].
+//!
+//! [0]: https://www.unicode.org/reports/tr9/
+//
+
+#[cfg(test)]
+mod _test;
+
+mod class; // BidiClass
+mod level; // BidiLevel
+mod namespace; // Bidi
+mod resolve; // the resolution of embedding levels
+mod table; // BIDI_CLASS_RANGES
+
+crate::structural_mods! { // _mods
+    _mods {
+        pub use super::{
+            class::*,
+            level::*,
+            namespace::*,
+        };
+    }
+}
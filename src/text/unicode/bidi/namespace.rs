@@ -0,0 +1,267 @@
+// devela/src/text/unicode/bidi/namespace.rs
+//
+//! Defines [`Bidi`].
+//
+
+use super::BidiLevel;
+use super::resolve::{Paragraph, first_strong};
+use crate::{BidiClass, MismatchedCapacity, is};
+
+#[doc = crate::_tags!(text namespace)]
+/// Unicode bidirectional algorithm.
+#[doc = crate::_doc_meta!{location("text/unicode/bidi")}]
+///
+/// Resolves the embedding levels of mixed-direction text, as specified by
+/// [UAX #9](https://www.unicode.org/reports/tr9/), and reorders its lines
+/// from logical into visual order.
+///
+/// Every method works over caller-provided buffers with one element per
+/// Unicode scalar of the text, and performs no allocation.
+///
+/// The usual steps are:
+/// 1. [`resolve`][Self::resolve] the levels of the whole text.
+/// 2. Break it into lines, measuring the text in logical order.
+/// 3. Adjust the [`line_levels`][Self::line_levels] of each line.
+/// 4. Get its [`visual_order`][Self::visual_order], mirroring the characters of
+///    right-to-left levels with [`mirrored`][Self::mirrored].
+///
+/// # Example
+/// ```
+/// # use devela::{Bidi, BidiClass, BidiLevel};
+/// let text = "abc אבג def";
+/// let (mut classes, mut levels) = ([BidiClass::L; 11], [BidiLevel::LTR; 11]);
+/// let level = Bidi::resolve(text, None, &mut classes, &mut levels).unwrap();
+/// assert_eq![level, BidiLevel::LTR];
+/// assert_eq![levels.map(BidiLevel::get), [0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0]];
+///
+/// let mut order = [0; 11];
+/// Bidi::visual_order(&levels, &mut order).unwrap();
+/// assert_eq![order, [0, 1, 2, 3, 6, 5, 4, 7, 8, 9, 10]];
+/// ```
+#[derive(Debug)]
+pub struct Bidi;
+
+impl Bidi {
+    /// Returns the level of a paragraph from its first strong character,
+    /// ignoring isolates, or `None` if it has none (rules P2 and P3).
+    ///
+    /// Only the first paragraph of `text` is considered.
+    #[must_use]
+    pub fn paragraph_level(text: &str) -> Option<BidiLevel> {
+        let mut depth = 0_usize;
+        for c in text.chars() {
+            match BidiClass::of(c) {
+                class if class.is_strong() && depth == 0 => {
+                    return Some(is![class.is_rtl(), BidiLevel::RTL, BidiLevel::LTR]);
+                }
+                BidiClass::LRI | BidiClass::RLI | BidiClass::FSI => depth += 1,
+                BidiClass::PDI => depth = depth.saturating_sub(1),
+                BidiClass::B => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Resolves the embedding levels of every Unicode scalar of `text` into `levels`.
+    ///
+    /// Each paragraph, up to and including its separator, is resolved on its own,
+    /// with the `paragraph` level if given, or else with its [`paragraph_level`],
+    /// defaulting to left-to-right.
+    /// Returns the level of the first paragraph.
+    ///
+    /// The levels are those of the whole paragraph as a single line:
+    /// once broken into lines, apply [`line_levels`] to each of them.
+    ///
+    /// `classes` is used as scratch space, and is left with the resolved classes.
+    ///
+    /// Explicit embedding and override controls, and boundary neutrals,
+    /// get the level of the preceding character.
+    ///
+    /// # Errors
+    /// Returns [`MismatchedCapacity`] if `classes` or `levels` are shorter
+    /// than the number of scalars of `text`.
+    ///
+    /// [`paragraph_level`]: Self::paragraph_level
+    /// [`line_levels`]: Self::line_levels
+    pub fn resolve(
+        text: &str,
+        paragraph: Option<BidiLevel>,
+        classes: &mut [BidiClass],
+        levels: &mut [BidiLevel],
+    ) -> Result<BidiLevel, MismatchedCapacity> {
+        let len = text.chars().count();
+        let capacity = classes.len().min(levels.len());
+        is![capacity < len, return Err(MismatchedCapacity::too_small(capacity, len))];
+        for (class, c) in classes.iter_mut().zip(text.chars()) {
+            *class = BidiClass::of(c);
+        }
+        let (mut first_level, mut start, mut byte) = (None, 0, 0);
+        while start < len {
+            let mut end = start;
+            let mut end_byte = byte;
+            for c in text[byte..].chars() {
+                end += 1;
+                end_byte += c.len_utf8();
+                is![classes[end - 1] == BidiClass::B, break];
+            }
+            let classes = &mut classes[start..end];
+            let level = paragraph.map_or_else(
+                || first_strong(classes, 0, false).map_or(0, |c| is![c.is_rtl(), 1, 0]),
+                |l| l.0.min(BidiLevel::MAX_EXPLICIT),
+            );
+            first_level.get_or_insert(BidiLevel(level));
+            let levels = &mut levels[start..end];
+            let text = &text[byte..end_byte];
+            Paragraph { text, classes, levels, level }.resolve();
+            (start, byte) = (end, end_byte);
+        }
+        Ok(first_level.unwrap_or(paragraph.unwrap_or_default()))
+    }
+
+    /// Resets the levels of the trailing whitespace of a `line`, and of its
+    /// separators with their preceding whitespace, to the `paragraph` level (rule L1).
+    ///
+    /// `levels` holds the levels resolved for the scalars of the line.
+    /// Scalars beyond its length are ignored.
+    pub fn line_levels(line: &str, paragraph: BidiLevel, levels: &mut [BidiLevel]) {
+        let mut trailing = None;
+        for (i, c) in line.chars().enumerate().take(levels.len()) {
+            let class = BidiClass::of(c);
+            if matches![class, BidiClass::S | BidiClass::B] {
+                levels[trailing.unwrap_or(i)..=i].fill(paragraph);
+                trailing = None;
+            } else if class == BidiClass::WS || class.is_isolate_control() || class.is_removed() {
+                trailing.get_or_insert(i);
+            } else {
+                trailing = None;
+            }
+        }
+        if let Some(start) = trailing {
+            let end = line.chars().count().min(levels.len());
+            levels[start..end].fill(paragraph);
+        }
+    }
+
+    /// Writes into `order` the logical index of each visual position of a line
+    /// with the given `levels` (rule L2).
+    ///
+    /// Every sequence at or above each odd level is reversed,
+    /// from the highest level down to the lowest odd one.
+    ///
+    /// # Errors
+    /// Returns [`MismatchedCapacity`] if `order` is shorter than `levels`.
+    pub fn visual_order(
+        levels: &[BidiLevel],
+        order: &mut [usize],
+    ) -> Result<(), MismatchedCapacity> {
+        let len = levels.len();
+        is![order.len() < len, return Err(MismatchedCapacity::too_small(order.len(), len))];
+        for (i, o) in order[..len].iter_mut().enumerate() {
+            *o = i;
+        }
+        let highest = levels.iter().map(|l| l.0).max().unwrap_or(0);
+        let lowest_odd = levels.iter().map(|l| l.0 | 1).min().unwrap_or(1);
+        let mut level = highest;
+        while level >= lowest_odd {
+            let mut i = 0;
+            while i < len {
+                if levels[i].0 >= level {
+                    let start = i;
+                    while i < len && levels[i].0 >= level {
+                        i += 1;
+                    }
+                    order[start..i].reverse();
+                } else {
+                    i += 1;
+                }
+            }
+            level -= 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the mirrored glyph of `c`, to be displayed at right-to-left levels (rule L4).
+    ///
+    /// Covers the paired brackets, and the common comparison signs and quotation marks.
+    ///
+    /// # Example
+    /// ```
+    /// # use devela::Bidi;
+    /// assert_eq![Bidi::mirrored('('), Some(')')];
+    /// assert_eq![Bidi::mirrored('«'), Some('»')];
+    /// assert_eq![Bidi::mirrored('a'), None];
+    /// ```
+    #[must_use]
+    pub const fn mirrored(c: char) -> Option<char> {
+        let mut i = 0;
+        while i < MIRRORED.len() {
+            let (a, b) = MIRRORED[i];
+            is![a == c, return Some(b)];
+            is![b == c, return Some(a)];
+            i += 1;
+        }
+        None
+    }
+
+    /// Returns the canonical opening bracket of a paired bracket,
+    /// and whether `c` is the opening one (BD14, BD15).
+    pub(super) const fn paired_bracket(c: char) -> Option<(char, bool)> {
+        let c = match c {
+            '\u{2329}' => '\u{3008}',
+            '\u{232A}' => '\u{3009}',
+            _ => c,
+        };
+        let mut i = 0;
+        while i < BRACKETS.len() {
+            let (open, close) = BRACKETS[i];
+            is![open == c, return Some((open, true))];
+            is![close == c, return Some((open, false))];
+            i += 1;
+        }
+        None
+    }
+}
+
+/// The paired brackets of `BidiBrackets.txt`, without canonical duplicates.
+#[rustfmt::skip]
+const BRACKETS: [(char, char); 59] = [
+    ('(', ')'), ('[', ']'), ('{', '}'), ('\u{0F3A}', '\u{0F3B}'), ('\u{0F3C}', '\u{0F3D}'),
+    ('\u{169B}', '\u{169C}'), ('\u{2045}', '\u{2046}'), ('\u{207D}', '\u{207E}'),
+    ('\u{208D}', '\u{208E}'), ('\u{2308}', '\u{2309}'), ('\u{230A}', '\u{230B}'),
+    ('\u{2768}', '\u{2769}'), ('\u{276A}', '\u{276B}'), ('\u{276C}', '\u{276D}'),
+    ('\u{276E}', '\u{276F}'), ('\u{2770}', '\u{2771}'), ('\u{2772}', '\u{2773}'),
+    ('\u{2774}', '\u{2775}'), ('\u{27C5}', '\u{27C6}'), ('\u{27E6}', '\u{27E7}'),
+    ('\u{27E8}', '\u{27E9}'), ('\u{27EA}', '\u{27EB}'), ('\u{27EC}', '\u{27ED}'),
+    ('\u{27EE}', '\u{27EF}'), ('\u{2983}', '\u{2984}'), ('\u{2985}', '\u{2986}'),
+    ('\u{2987}', '\u{2988}'), ('\u{2989}', '\u{298A}'), ('\u{298B}', '\u{298C}'),
+    ('\u{298D}', '\u{2990}'), ('\u{298F}', '\u{298E}'), ('\u{2991}', '\u{2992}'),
+    ('\u{2993}', '\u{2994}'), ('\u{2995}', '\u{2996}'), ('\u{2997}', '\u{2998}'),
+    ('\u{29D8}', '\u{29D9}'), ('\u{29DA}', '\u{29DB}'), ('\u{29FC}', '\u{29FD}'),
+    ('\u{2E22}', '\u{2E23}'), ('\u{2E24}', '\u{2E25}'), ('\u{2E26}', '\u{2E27}'),
+    ('\u{2E28}', '\u{2E29}'), ('\u{3008}', '\u{3009}'), ('\u{300A}', '\u{300B}'),
+    ('\u{300C}', '\u{300D}'), ('\u{300E}', '\u{300F}'), ('\u{3010}', '\u{3011}'),
+    ('\u{3014}', '\u{3015}'), ('\u{3016}', '\u{3017}'), ('\u{3018}', '\u{3019}'),
+    ('\u{301A}', '\u{301B}'), ('\u{FE59}', '\u{FE5A}'), ('\u{FE5B}', '\u{FE5C}'),
+    ('\u{FE5D}', '\u{FE5E}'), ('\u{FF08}', '\u{FF09}'), ('\u{FF3B}', '\u{FF3D}'),
+    ('\u{FF5B}', '\u{FF5D}'), ('\u{FF5F}', '\u{FF60}'), ('\u{FF62}', '\u{FF63}'),
+];
+
+/// The mirrored pairs of glyphs.
+#[rustfmt::skip]
+const MIRRORED: [(char, char); 66] = {
+    let mut pairs = [('\0', '\0'); 66];
+    let mut i = 0;
+    while i < BRACKETS.len() {
+        pairs[i] = BRACKETS[i];
+        i += 1;
+    }
+    let more = [('\u{2329}', '\u{232A}'), ('<', '>'), ('«', '»'), ('\u{2039}', '\u{203A}'), ('\u{2264}', '\u{2265}'),
+        ('\u{226A}', '\u{226B}'), ('\u{2282}', '\u{2283}')];
+    let mut j = 0;
+    while j < more.len() {
+        pairs[i + j] = more[j];
+        j += 1;
+    }
+    pairs
+};
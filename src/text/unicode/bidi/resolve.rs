@@ -0,0 +1,498 @@
+// devela/src/text/unicode/bidi/resolve.rs
+//
+//! Resolves the embedding levels of a paragraph.
+//
+// The rules are applied without allocating, over the caller's buffers:
+// - `classes` holds the working class of every character, except for isolate
+//   controls, which keep their class so that isolating run sequences can be
+//   followed at any time.
+// - `levels` holds the explicit embedding level of every character, and the
+//   `FLAG` bit, which marks:
+//   - an isolate control under a directional override (until its sequence is resolved),
+//     or one that resolves against its embedding direction (afterwards);
+//   - any other character whose original class was NSM.
+// The implicit levels are only applied once every sequence is resolved,
+// since level runs are found by comparing explicit levels.
+
+use super::{BidiClass as C, BidiLevel};
+use crate::{Bidi, is};
+
+/// The bit of a level reserved to flag its character.
+const FLAG: u8 = 0x80;
+/// The maximum depth of the bracket stack (BD16).
+const BRACKET_DEPTH: usize = 63;
+
+/// Returns the first strong class from `start`, skipping isolates.
+///
+/// If `isolate`, stops at a `PDI` closing the isolate that `start` is in.
+pub(super) fn first_strong(classes: &[C], start: usize, isolate: bool) -> Option<C> {
+    let (mut i, mut depth) = (start, 0_usize);
+    while i < classes.len() {
+        match classes[i] {
+            c if c.is_strong() && depth == 0 => return Some(c),
+            C::LRI | C::RLI | C::FSI => depth += 1,
+            C::PDI if depth > 0 => depth -= 1,
+            C::PDI if isolate => return None,
+            C::B => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Returns the least level greater than `level` in the given direction.
+const fn next_level(level: u8, rtl: bool) -> u8 {
+    is![rtl, (level + 1) | 1, (level + 2) & !1]
+}
+/// Returns the strong direction of a class: `L`, or `R` for `R`, `AL`, `EN` and `AN`.
+const fn strong(class: C) -> Option<C> {
+    match class {
+        C::L => Some(C::L),
+        C::R | C::AL | C::EN | C::AN => Some(C::R),
+        _ => None,
+    }
+}
+/// Returns the direction of a level.
+const fn direction(level: u8) -> C {
+    is![level % 2 == 1, C::R, C::L]
+}
+
+/// A paragraph being resolved.
+pub(super) struct Paragraph<'b> {
+    pub text: &'b str,
+    pub classes: &'b mut [C],
+    pub levels: &'b mut [BidiLevel],
+    pub level: u8,
+}
+
+impl Paragraph<'_> {
+    /// Applies every rule, leaving the resolved levels.
+    pub fn resolve(&mut self) {
+        self.explicit();
+        let mut i = self.next_kept_from(0);
+        while let Some(s) = i {
+            is![self.seq_prev(s).is_none(), self.sequence(s)];
+            i = self.next_kept(s);
+        }
+        self.implicit();
+    }
+
+    /* navigation */
+
+    fn level(&self, i: usize) -> u8 {
+        self.levels[i].0 & !FLAG
+    }
+    fn flagged(&self, i: usize) -> bool {
+        self.levels[i].0 & FLAG != 0
+    }
+    fn set_flag(&mut self, i: usize, flag: bool) {
+        self.levels[i].0 = self.level(i) | is![flag, FLAG, 0];
+    }
+    /// Returns the working class, resolving overridden isolate controls.
+    fn class(&self, i: usize) -> C {
+        let class = self.classes[i];
+        is![class.is_isolate_control() && self.flagged(i), direction(self.level(i)), class]
+    }
+    fn next_kept_from(&self, mut i: usize) -> Option<usize> {
+        while i < self.classes.len() {
+            is![!self.classes[i].is_removed(), return Some(i)];
+            i += 1;
+        }
+        None
+    }
+    fn next_kept(&self, i: usize) -> Option<usize> {
+        self.next_kept_from(i + 1)
+    }
+    fn prev_kept(&self, mut i: usize) -> Option<usize> {
+        while i > 0 {
+            i -= 1;
+            is![!self.classes[i].is_removed(), return Some(i)];
+        }
+        None
+    }
+    /// Returns the `PDI` matching the isolate initiator at `i` (BD9).
+    fn matching_pdi(&self, i: usize) -> Option<usize> {
+        let (mut j, mut depth) = (i + 1, 0_usize);
+        while j < self.classes.len() {
+            match self.classes[j] {
+                C::LRI | C::RLI | C::FSI => depth += 1,
+                C::PDI if depth == 0 => return Some(j),
+                C::PDI => depth -= 1,
+                _ => {}
+            }
+            j += 1;
+        }
+        None
+    }
+    /// Returns the isolate initiator matching the `PDI` at `i` (BD9).
+    fn matching_initiator(&self, mut i: usize) -> Option<usize> {
+        let mut depth = 0_usize;
+        while i > 0 {
+            i -= 1;
+            match self.classes[i] {
+                C::PDI => depth += 1,
+                C::LRI | C::RLI | C::FSI if depth == 0 => return Some(i),
+                C::LRI | C::RLI | C::FSI => depth -= 1,
+                _ => {}
+            }
+        }
+        None
+    }
+    /// Returns the next character of the isolating run sequence of `i` (BD13).
+    fn seq_next(&self, i: usize) -> Option<usize> {
+        let j = self.next_kept(i);
+        if let Some(j) = j
+            && self.level(j) == self.level(i)
+        {
+            return Some(j);
+        }
+        is![!self.classes[i].is_isolate_initiator(), return None];
+        self.matching_pdi(i).filter(|&p| self.level(p) == self.level(i))
+    }
+    /// Returns the previous character of the isolating run sequence of `i` (BD13).
+    fn seq_prev(&self, i: usize) -> Option<usize> {
+        if let Some(j) = self.prev_kept(i)
+            && self.level(j) == self.level(i)
+        {
+            return Some(j);
+        }
+        is![self.classes[i] != C::PDI, return None];
+        self.matching_initiator(i).filter(|&k| self.level(k) == self.level(i))
+    }
+
+    /* rules */
+
+    /// Applies rules X1 to X8.
+    fn explicit(&mut self) {
+        #[derive(Clone, Copy)]
+        struct Entry {
+            level: u8,
+            override_: Option<C>,
+            isolate: bool,
+        }
+        let mut stack = [Entry { level: 0, override_: None, isolate: false }; 128];
+        stack[0].level = self.level;
+        let mut depth = 1;
+        let (mut overflow_isolates, mut overflow_embeddings, mut valid_isolates) = (0, 0, 0);
+        for i in 0..self.classes.len() {
+            let class = self.classes[i];
+            let top = stack[depth - 1];
+            let flag = is![top.override_.is_some(), FLAG, 0];
+            match class {
+                C::RLE | C::LRE | C::RLO | C::LRO => {
+                    self.levels[i].0 = top.level;
+                    let level = next_level(top.level, matches![class, C::RLE | C::RLO]);
+                    if level <= BidiLevel::MAX_EXPLICIT
+                        && overflow_isolates == 0
+                        && overflow_embeddings == 0
+                    {
+                        let override_ = match class {
+                            C::RLO => Some(C::R),
+                            C::LRO => Some(C::L),
+                            _ => None,
+                        };
+                        stack[depth] = Entry { level, override_, isolate: false };
+                        depth += 1;
+                    } else if overflow_isolates == 0 {
+                        overflow_embeddings += 1;
+                    }
+                }
+                C::RLI | C::LRI | C::FSI => {
+                    self.levels[i].0 = top.level | flag;
+                    let rtl = match class {
+                        C::RLI => true,
+                        C::LRI => false,
+                        _ => first_strong(self.classes, i + 1, true).is_some_and(C::is_rtl),
+                    };
+                    let level = next_level(top.level, rtl);
+                    if level <= BidiLevel::MAX_EXPLICIT
+                        && overflow_isolates == 0
+                        && overflow_embeddings == 0
+                    {
+                        valid_isolates += 1;
+                        stack[depth] = Entry { level, override_: None, isolate: true };
+                        depth += 1;
+                    } else {
+                        overflow_isolates += 1;
+                    }
+                }
+                C::PDI => {
+                    if overflow_isolates > 0 {
+                        overflow_isolates -= 1;
+                    } else if valid_isolates > 0 {
+                        overflow_embeddings = 0;
+                        while !stack[depth - 1].isolate {
+                            depth -= 1;
+                        }
+                        depth -= 1;
+                        valid_isolates -= 1;
+                    }
+                    let top = stack[depth - 1];
+                    self.levels[i].0 = top.level | is![top.override_.is_some(), FLAG, 0];
+                }
+                C::PDF => {
+                    self.levels[i].0 = top.level;
+                    if overflow_isolates > 0 {
+                    } else if overflow_embeddings > 0 {
+                        overflow_embeddings -= 1;
+                    } else if !top.isolate && depth >= 2 {
+                        depth -= 1;
+                    }
+                }
+                C::B => self.levels[i].0 = self.level,
+                C::BN => self.levels[i].0 = top.level,
+                _ => {
+                    self.levels[i].0 = top.level;
+                    if let Some(strong) = top.override_ {
+                        self.classes[i] = strong;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves the isolating run sequence starting at `start` (X10, W1-W7, N0-N2).
+    fn sequence(&mut self, start: usize) {
+        let level = self.level(start);
+        let before = self.prev_kept(start).map_or(self.level, |i| self.level(i));
+        let sos = direction(level.max(before));
+        let mut end = start;
+        while let Some(i) = self.seq_next(end) {
+            end = i;
+        }
+        let after = if self.classes[end].is_isolate_initiator() {
+            self.level
+        } else {
+            self.next_kept(end).map_or(self.level, |i| self.level(i))
+        };
+        let eos = direction(level.max(after));
+        self.weak(start, sos);
+        self.brackets(start, direction(level), sos);
+        self.neutral(start, direction(level), sos, eos);
+    }
+
+    /// Applies rules W1 to W7.
+    fn weak(&mut self, start: usize, sos: C) {
+        // W1, W2, W3
+        let (mut i, mut prev, mut last_strong) = (Some(start), sos, sos);
+        while let Some(j) = i {
+            let mut class = self.class(j);
+            if class == C::NSM {
+                self.set_flag(j, true);
+                class = is![prev.is_isolate_control(), C::ON, prev];
+                self.classes[j] = class;
+            }
+            prev = class;
+            is![class.is_strong(), last_strong = class];
+            if class == C::EN && last_strong == C::AL {
+                self.classes[j] = C::AN;
+            } else if class == C::AL {
+                self.classes[j] = C::R;
+            }
+            i = self.seq_next(j);
+        }
+        // W4
+        let (mut i, mut prev) = (Some(start), sos);
+        while let Some(j) = i {
+            let class = self.class(j);
+            i = self.seq_next(j);
+            if matches![class, C::ES | C::CS] {
+                let number = match (prev, class) {
+                    (C::EN, _) => Some(C::EN),
+                    (C::AN, C::CS) => Some(C::AN),
+                    _ => None,
+                };
+                if number.is_some() && i.map(|n| self.class(n)) == number {
+                    self.classes[j] = prev;
+                }
+            }
+            prev = self.class(j);
+        }
+        // W5
+        let (mut i, mut prev) = (Some(start), sos);
+        while let Some(j) = i {
+            if self.class(j) != C::ET {
+                prev = self.class(j);
+                i = self.seq_next(j);
+                continue;
+            }
+            let mut last = j;
+            while let Some(n) = self.seq_next(last).filter(|&n| self.class(n) == C::ET) {
+                last = n;
+            }
+            i = self.seq_next(last);
+            if prev == C::EN || i.is_some_and(|n| self.class(n) == C::EN) {
+                let mut k = j;
+                loop {
+                    self.classes[k] = C::EN;
+                    is![k == last, break];
+                    k = self.seq_next(k).unwrap_or(last);
+                }
+            }
+            prev = self.class(last);
+        }
+        // W6, W7
+        let (mut i, mut last_strong) = (Some(start), sos);
+        while let Some(j) = i {
+            match self.class(j) {
+                C::ES | C::ET | C::CS => self.classes[j] = C::ON,
+                C::EN if last_strong == C::L => self.classes[j] = C::L,
+                class @ (C::L | C::R) => last_strong = class,
+                _ => {}
+            }
+            i = self.seq_next(j);
+        }
+    }
+
+    /// Applies rule N0 to the bracket pairs.
+    fn brackets(&mut self, start: usize, embedding: C, sos: C) {
+        let mut stack = [('\0', 0); BRACKET_DEPTH];
+        let mut pairs = [(0, 0); BRACKET_DEPTH];
+        let (mut depth, mut count) = (0, 0);
+        let mut chars = CharCursor::new(self.text);
+        let mut i = Some(start);
+        while let Some(j) = i {
+            i = self.seq_next(j);
+            is![self.class(j) != C::ON, continue];
+            let Some((open, is_open)) = Bidi::paired_bracket(chars.at(j)) else { continue };
+            if is_open {
+                is![depth == BRACKET_DEPTH, break];
+                stack[depth] = (open, j);
+                depth += 1;
+            } else if let Some(k) = stack[..depth].iter().rposition(|&(c, _)| c == open) {
+                pairs[count] = (stack[k].1, j);
+                (depth, count) = (k, count + 1);
+                // resolve in the order of their opening brackets once no pair is open
+                if depth == 0 || count == BRACKET_DEPTH {
+                    pairs[..count].sort_unstable();
+                    for &(o, c) in &pairs[..count] {
+                        self.bracket_pair(o, c, embedding, sos);
+                    }
+                    count = 0;
+                }
+            }
+        }
+        pairs[..count].sort_unstable();
+        for &(o, c) in &pairs[..count] {
+            self.bracket_pair(o, c, embedding, sos);
+        }
+    }
+    fn bracket_pair(&mut self, open: usize, close: usize, embedding: C, sos: C) {
+        let (mut i, mut inside) = (self.seq_next(open), None);
+        while let Some(j) = i.filter(|&j| j < close) {
+            match strong(self.class(j)) {
+                Some(class) if class == embedding => {
+                    inside = Some(embedding);
+                    break;
+                }
+                Some(class) => inside = Some(class),
+                None => {}
+            }
+            i = self.seq_next(j);
+        }
+        let Some(inside) = inside else { return };
+        let dir = if inside == embedding {
+            embedding
+        } else {
+            let (mut i, mut context) = (self.seq_prev(open), sos);
+            while let Some(j) = i {
+                if let Some(class) = strong(self.class(j)) {
+                    context = class;
+                    break;
+                }
+                i = self.seq_prev(j);
+            }
+            is![context == inside, inside, embedding]
+        };
+        for bracket in [open, close] {
+            self.classes[bracket] = dir;
+            let mut i = self.seq_next(bracket);
+            while let Some(j) =
+                i.filter(|&j| self.flagged(j) && !self.classes[j].is_isolate_control())
+            {
+                self.classes[j] = dir;
+                i = self.seq_next(j);
+            }
+        }
+    }
+
+    /// Applies rules N1 and N2.
+    fn neutral(&mut self, start: usize, embedding: C, sos: C, eos: C) {
+        let (mut i, mut before) = (Some(start), sos);
+        while let Some(j) = i {
+            let class = self.class(j);
+            if !class.is_neutral() {
+                // an overridden isolate control adds no level
+                is![self.classes[j].is_isolate_control(), self.set_flag(j, false)];
+                before = strong(class).unwrap_or(before);
+                i = self.seq_next(j);
+                continue;
+            }
+            let mut end = Some(j);
+            while let Some(k) = end.filter(|&k| self.class(k).is_neutral()) {
+                end = self.seq_next(k);
+            }
+            let after = end.and_then(|k| strong(self.class(k))).unwrap_or(eos);
+            let dir = is![before == after, before, embedding];
+            let mut k = j;
+            loop {
+                if self.classes[k].is_isolate_control() {
+                    self.set_flag(k, dir != direction(self.level(k)));
+                } else {
+                    self.classes[k] = dir;
+                }
+                match self.seq_next(k) {
+                    Some(n) if Some(n) != end => k = n,
+                    _ => break,
+                }
+            }
+            before = dir;
+            i = end;
+        }
+    }
+
+    /// Applies rules I1 and I2, and levels the removed characters.
+    fn implicit(&mut self) {
+        let mut prev = self.level;
+        for i in 0..self.classes.len() {
+            let (class, level) = (self.classes[i], self.level(i));
+            let level = if class.is_removed() {
+                prev
+            } else if class.is_isolate_control() {
+                level + is![self.flagged(i), 1, 0]
+            } else {
+                let odd = level % 2 == 1;
+                match class {
+                    C::L => level + is![odd, 1, 0],
+                    C::R => level + is![odd, 0, 1],
+                    C::EN | C::AN => level + is![odd, 1, 2],
+                    _ => level,
+                }
+            };
+            self.levels[i].0 = level;
+            prev = level;
+        }
+    }
+}
+
+/// Returns the characters of a text by increasing index.
+struct CharCursor<'a> {
+    text: &'a str,
+    chars: core::str::Chars<'a>,
+    index: usize,
+}
+impl<'a> CharCursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, chars: text.chars(), index: 0 }
+    }
+    /// Returns the character at `index`, or `'\0'` if out of bounds.
+    fn at(&mut self, index: usize) -> char {
+        if index < self.index {
+            *self = Self::new(self.text);
+        }
+        let c = self.chars.nth(index - self.index);
+        self.index = index + 1;
+        c.unwrap_or_default()
+    }
+}
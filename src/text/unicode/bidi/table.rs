@@ -0,0 +1,163 @@
+// devela/src/text/unicode/bidi/table.rs
+//
+//! Defines [`BIDI_CLASS_RANGES`].
+//
+// Generated from the Unicode Character Database 14.0.0, with the default
+// classes of DerivedBidiClass.txt applied to unassigned code points.
+
+/// The ranges of code points sharing a bidirectional class.
+///
+/// Each entry holds the first code point of a range in its upper 24 bits,
+/// and the index of its [`BidiClass`][super::BidiClass] in the lower 8 bits.
+/// A range lasts until the start of the next one.
+#[allow(clippy::unreadable_literal)] #[rustfmt::skip]
+pub(super) static BIDI_CLASS_RANGES: [u32; 1184] = [
+    0x00000009, 0x0000090B, 0x00000A0A, 0x00000B0B, 0x00000C0C, 0x00000D0A, 0x00000E09, 0x00001C0A,
+    0x00001F0B, 0x0000200C, 0x0000210D, 0x00002305, 0x0000260D, 0x00002B04, 0x00002C07, 0x00002D04,
+    0x00002E07, 0x00003003, 0x00003A07, 0x00003B0D, 0x00004100, 0x00005B0D, 0x00006100, 0x00007B0D,
+    0x00007F09, 0x0000850A, 0x00008609, 0x0000A007, 0x0000A10D, 0x0000A205, 0x0000A60D, 0x0000AA00,
+    0x0000AB0D, 0x0000AD09, 0x0000AE0D, 0x0000B005, 0x0000B203, 0x0000B40D, 0x0000B500, 0x0000B60D,
+    0x0000B903, 0x0000BA00, 0x0000BB0D, 0x0000C000, 0x0000D70D, 0x0000D800, 0x0000F70D, 0x0000F800,
+    0x0002B90D, 0x0002BB00, 0x0002C20D, 0x0002D000, 0x0002D20D, 0x0002E000, 0x0002E50D, 0x0002EE00,
+    0x0002EF0D, 0x00030008, 0x00037000, 0x0003740D, 0x00037600, 0x00037E0D, 0x00037F00, 0x0003840D,
+    0x00038600, 0x0003870D, 0x00038800, 0x0003F60D, 0x0003F700, 0x00048308, 0x00048A00, 0x00058A0D,
+    0x00058B00, 0x00058D0D, 0x00058F05, 0x00059001, 0x00059108, 0x0005BE01, 0x0005BF08, 0x0005C001,
+    0x0005C108, 0x0005C301, 0x0005C408, 0x0005C601, 0x0005C708, 0x0005C801, 0x00060006, 0x0006060D,
+    0x00060802, 0x00060905, 0x00060B02, 0x00060C07, 0x00060D02, 0x00060E0D, 0x00061008, 0x00061B02,
+    0x00064B08, 0x00066006, 0x00066A05, 0x00066B06, 0x00066D02, 0x00067008, 0x00067102, 0x0006D608,
+    0x0006DD06, 0x0006DE0D, 0x0006DF08, 0x0006E502, 0x0006E708, 0x0006E90D, 0x0006EA08, 0x0006EE02,
+    0x0006F003, 0x0006FA02, 0x00071108, 0x00071202, 0x00073008, 0x00074B02, 0x0007A608, 0x0007B102,
+    0x0007C001, 0x0007EB08, 0x0007F401, 0x0007F60D, 0x0007FA01, 0x0007FD08, 0x0007FE01, 0x00081608,
+    0x00081A01, 0x00081B08, 0x00082401, 0x00082508, 0x00082801, 0x00082908, 0x00082E01, 0x00085908,
+    0x00085C01, 0x00086002, 0x00089006, 0x00089202, 0x00089808, 0x0008A002, 0x0008CA08, 0x0008E206,
+    0x0008E308, 0x00090300, 0x00093A08, 0x00093B00, 0x00093C08, 0x00093D00, 0x00094108, 0x00094900,
+    0x00094D08, 0x00094E00, 0x00095108, 0x00095800, 0x00096208, 0x00096400, 0x00098108, 0x00098200,
+    0x0009BC08, 0x0009BD00, 0x0009C108, 0x0009C500, 0x0009CD08, 0x0009CE00, 0x0009E208, 0x0009E400,
+    0x0009F205, 0x0009F400, 0x0009FB05, 0x0009FC00, 0x0009FE08, 0x0009FF00, 0x000A0108, 0x000A0300,
+    0x000A3C08, 0x000A3D00, 0x000A4108, 0x000A4300, 0x000A4708, 0x000A4900, 0x000A4B08, 0x000A4E00,
+    0x000A5108, 0x000A5200, 0x000A7008, 0x000A7200, 0x000A7508, 0x000A7600, 0x000A8108, 0x000A8300,
+    0x000ABC08, 0x000ABD00, 0x000AC108, 0x000AC600, 0x000AC708, 0x000AC900, 0x000ACD08, 0x000ACE00,
+    0x000AE208, 0x000AE400, 0x000AF105, 0x000AF200, 0x000AFA08, 0x000B0000, 0x000B0108, 0x000B0200,
+    0x000B3C08, 0x000B3D00, 0x000B3F08, 0x000B4000, 0x000B4108, 0x000B4500, 0x000B4D08, 0x000B4E00,
+    0x000B5508, 0x000B5700, 0x000B6208, 0x000B6400, 0x000B8208, 0x000B8300, 0x000BC008, 0x000BC100,
+    0x000BCD08, 0x000BCE00, 0x000BF30D, 0x000BF905, 0x000BFA0D, 0x000BFB00, 0x000C0008, 0x000C0100,
+    0x000C0408, 0x000C0500, 0x000C3C08, 0x000C3D00, 0x000C3E08, 0x000C4100, 0x000C4608, 0x000C4900,
+    0x000C4A08, 0x000C4E00, 0x000C5508, 0x000C5700, 0x000C6208, 0x000C6400, 0x000C780D, 0x000C7F00,
+    0x000C8108, 0x000C8200, 0x000CBC08, 0x000CBD00, 0x000CCC08, 0x000CCE00, 0x000CE208, 0x000CE400,
+    0x000D0008, 0x000D0200, 0x000D3B08, 0x000D3D00, 0x000D4108, 0x000D4500, 0x000D4D08, 0x000D4E00,
+    0x000D6208, 0x000D6400, 0x000D8108, 0x000D8200, 0x000DCA08, 0x000DCB00, 0x000DD208, 0x000DD500,
+    0x000DD608, 0x000DD700, 0x000E3108, 0x000E3200, 0x000E3408, 0x000E3B00, 0x000E3F05, 0x000E4000,
+    0x000E4708, 0x000E4F00, 0x000EB108, 0x000EB200, 0x000EB408, 0x000EBD00, 0x000EC808, 0x000ECE00,
+    0x000F1808, 0x000F1A00, 0x000F3508, 0x000F3600, 0x000F3708, 0x000F3800, 0x000F3908, 0x000F3A0D,
+    0x000F3E00, 0x000F7108, 0x000F7F00, 0x000F8008, 0x000F8500, 0x000F8608, 0x000F8800, 0x000F8D08,
+    0x000F9800, 0x000F9908, 0x000FBD00, 0x000FC608, 0x000FC700, 0x00102D08, 0x00103100, 0x00103208,
+    0x00103800, 0x00103908, 0x00103B00, 0x00103D08, 0x00103F00, 0x00105808, 0x00105A00, 0x00105E08,
+    0x00106100, 0x00107108, 0x00107500, 0x00108208, 0x00108300, 0x00108508, 0x00108700, 0x00108D08,
+    0x00108E00, 0x00109D08, 0x00109E00, 0x00135D08, 0x00136000, 0x0013900D, 0x00139A00, 0x0014000D,
+    0x00140100, 0x0016800C, 0x00168100, 0x00169B0D, 0x00169D00, 0x00171208, 0x00171500, 0x00173208,
+    0x00173400, 0x00175208, 0x00175400, 0x00177208, 0x00177400, 0x0017B408, 0x0017B600, 0x0017B708,
+    0x0017BE00, 0x0017C608, 0x0017C700, 0x0017C908, 0x0017D400, 0x0017DB05, 0x0017DC00, 0x0017DD08,
+    0x0017DE00, 0x0017F00D, 0x0017FA00, 0x0018000D, 0x00180B08, 0x00180E09, 0x00180F08, 0x00181000,
+    0x00188508, 0x00188700, 0x0018A908, 0x0018AA00, 0x00192008, 0x00192300, 0x00192708, 0x00192900,
+    0x00193208, 0x00193300, 0x00193908, 0x00193C00, 0x0019400D, 0x00194100, 0x0019440D, 0x00194600,
+    0x0019DE0D, 0x001A0000, 0x001A1708, 0x001A1900, 0x001A1B08, 0x001A1C00, 0x001A5608, 0x001A5700,
+    0x001A5808, 0x001A5F00, 0x001A6008, 0x001A6100, 0x001A6208, 0x001A6300, 0x001A6508, 0x001A6D00,
+    0x001A7308, 0x001A7D00, 0x001A7F08, 0x001A8000, 0x001AB008, 0x001ACF00, 0x001B0008, 0x001B0400,
+    0x001B3408, 0x001B3500, 0x001B3608, 0x001B3B00, 0x001B3C08, 0x001B3D00, 0x001B4208, 0x001B4300,
+    0x001B6B08, 0x001B7400, 0x001B8008, 0x001B8200, 0x001BA208, 0x001BA600, 0x001BA808, 0x001BAA00,
+    0x001BAB08, 0x001BAE00, 0x001BE608, 0x001BE700, 0x001BE808, 0x001BEA00, 0x001BED08, 0x001BEE00,
+    0x001BEF08, 0x001BF200, 0x001C2C08, 0x001C3400, 0x001C3608, 0x001C3800, 0x001CD008, 0x001CD300,
+    0x001CD408, 0x001CE100, 0x001CE208, 0x001CE900, 0x001CED08, 0x001CEE00, 0x001CF408, 0x001CF500,
+    0x001CF808, 0x001CFA00, 0x001DC008, 0x001E0000, 0x001FBD0D, 0x001FBE00, 0x001FBF0D, 0x001FC200,
+    0x001FCD0D, 0x001FD000, 0x001FDD0D, 0x001FE000, 0x001FED0D, 0x001FF000, 0x001FFD0D, 0x001FFF00,
+    0x0020000C, 0x00200B09, 0x00200E00, 0x00200F01, 0x0020100D, 0x0020280C, 0x0020290A, 0x00202A0E,
+    0x00202B10, 0x00202C12, 0x00202D0F, 0x00202E11, 0x00202F07, 0x00203005, 0x0020350D, 0x00204407,
+    0x0020450D, 0x00205F0C, 0x00206009, 0x00206613, 0x00206714, 0x00206815, 0x00206916, 0x00206A09,
+    0x00207003, 0x00207100, 0x00207403, 0x00207A04, 0x00207C0D, 0x00207F00, 0x00208003, 0x00208A04,
+    0x00208C0D, 0x00208F00, 0x0020A005, 0x0020D008, 0x0020F100, 0x0021000D, 0x00210200, 0x0021030D,
+    0x00210700, 0x0021080D, 0x00210A00, 0x0021140D, 0x00211500, 0x0021160D, 0x00211900, 0x00211E0D,
+    0x00212400, 0x0021250D, 0x00212600, 0x0021270D, 0x00212800, 0x0021290D, 0x00212A00, 0x00212E05,
+    0x00212F00, 0x00213A0D, 0x00213C00, 0x0021400D, 0x00214500, 0x00214A0D, 0x00214E00, 0x0021500D,
+    0x00216000, 0x0021890D, 0x00218C00, 0x0021900D, 0x00221204, 0x00221305, 0x0022140D, 0x00233600,
+    0x00237B0D, 0x00239500, 0x0023960D, 0x00242700, 0x0024400D, 0x00244B00, 0x0024600D, 0x00248803,
+    0x00249C00, 0x0024EA0D, 0x0026AC00, 0x0026AD0D, 0x00280000, 0x0029000D, 0x002B7400, 0x002B760D,
+    0x002B9600, 0x002B970D, 0x002C0000, 0x002CE50D, 0x002CEB00, 0x002CEF08, 0x002CF200, 0x002CF90D,
+    0x002D0000, 0x002D7F08, 0x002D8000, 0x002DE008, 0x002E000D, 0x002E5E00, 0x002E800D, 0x002E9A00,
+    0x002E9B0D, 0x002EF400, 0x002F000D, 0x002FD600, 0x002FF00D, 0x002FFC00, 0x0030000C, 0x0030010D,
+    0x00300500, 0x0030080D, 0x00302100, 0x00302A08, 0x00302E00, 0x0030300D, 0x00303100, 0x0030360D,
+    0x00303800, 0x00303D0D, 0x00304000, 0x00309908, 0x00309B0D, 0x00309D00, 0x0030A00D, 0x0030A100,
+    0x0030FB0D, 0x0030FC00, 0x0031C00D, 0x0031E400, 0x00321D0D, 0x00321F00, 0x0032500D, 0x00326000,
+    0x00327C0D, 0x00327F00, 0x0032B10D, 0x0032C000, 0x0032CC0D, 0x0032D000, 0x0033770D, 0x00337B00,
+    0x0033DE0D, 0x0033E000, 0x0033FF0D, 0x00340000, 0x004DC00D, 0x004E0000, 0x00A4900D, 0x00A4C700,
+    0x00A60D0D, 0x00A61000, 0x00A66F08, 0x00A6730D, 0x00A67408, 0x00A67E0D, 0x00A68000, 0x00A69E08,
+    0x00A6A000, 0x00A6F008, 0x00A6F200, 0x00A7000D, 0x00A72200, 0x00A7880D, 0x00A78900, 0x00A80208,
+    0x00A80300, 0x00A80608, 0x00A80700, 0x00A80B08, 0x00A80C00, 0x00A82508, 0x00A82700, 0x00A8280D,
+    0x00A82C08, 0x00A82D00, 0x00A83805, 0x00A83A00, 0x00A8740D, 0x00A87800, 0x00A8C408, 0x00A8C600,
+    0x00A8E008, 0x00A8F200, 0x00A8FF08, 0x00A90000, 0x00A92608, 0x00A92E00, 0x00A94708, 0x00A95200,
+    0x00A98008, 0x00A98300, 0x00A9B308, 0x00A9B400, 0x00A9B608, 0x00A9BA00, 0x00A9BC08, 0x00A9BE00,
+    0x00A9E508, 0x00A9E600, 0x00AA2908, 0x00AA2F00, 0x00AA3108, 0x00AA3300, 0x00AA3508, 0x00AA3700,
+    0x00AA4308, 0x00AA4400, 0x00AA4C08, 0x00AA4D00, 0x00AA7C08, 0x00AA7D00, 0x00AAB008, 0x00AAB100,
+    0x00AAB208, 0x00AAB500, 0x00AAB708, 0x00AAB900, 0x00AABE08, 0x00AAC000, 0x00AAC108, 0x00AAC200,
+    0x00AAEC08, 0x00AAEE00, 0x00AAF608, 0x00AAF700, 0x00AB6A0D, 0x00AB6C00, 0x00ABE508, 0x00ABE600,
+    0x00ABE808, 0x00ABE900, 0x00ABED08, 0x00ABEE00, 0x00FB1D01, 0x00FB1E08, 0x00FB1F01, 0x00FB2904,
+    0x00FB2A01, 0x00FB5002, 0x00FD3E0D, 0x00FD5002, 0x00FDCF0D, 0x00FDD009, 0x00FDF002, 0x00FDFD0D,
+    0x00FE0008, 0x00FE100D, 0x00FE1A00, 0x00FE2008, 0x00FE300D, 0x00FE5007, 0x00FE510D, 0x00FE5207,
+    0x00FE5300, 0x00FE540D, 0x00FE5507, 0x00FE560D, 0x00FE5F05, 0x00FE600D, 0x00FE6204, 0x00FE640D,
+    0x00FE6700, 0x00FE680D, 0x00FE6905, 0x00FE6B0D, 0x00FE6C00, 0x00FE7002, 0x00FEFF09, 0x00FF0000,
+    0x00FF010D, 0x00FF0305, 0x00FF060D, 0x00FF0B04, 0x00FF0C07, 0x00FF0D04, 0x00FF0E07, 0x00FF1003,
+    0x00FF1A07, 0x00FF1B0D, 0x00FF2100, 0x00FF3B0D, 0x00FF4100, 0x00FF5B0D, 0x00FF6600, 0x00FFE005,
+    0x00FFE20D, 0x00FFE505, 0x00FFE700, 0x00FFE80D, 0x00FFEF00, 0x00FFF009, 0x00FFF90D, 0x00FFFE09,
+    0x01000000, 0x0101010D, 0x01010200, 0x0101400D, 0x01018D00, 0x0101900D, 0x01019D00, 0x0101A00D,
+    0x0101A100, 0x0101FD08, 0x0101FE00, 0x0102E008, 0x0102E103, 0x0102FC00, 0x01037608, 0x01037B00,
+    0x01080001, 0x01091F0D, 0x01092001, 0x010A0108, 0x010A0401, 0x010A0508, 0x010A0701, 0x010A0C08,
+    0x010A1001, 0x010A3808, 0x010A3B01, 0x010A3F08, 0x010A4001, 0x010AE508, 0x010AE701, 0x010B390D,
+    0x010B4001, 0x010D0002, 0x010D2408, 0x010D2802, 0x010D3006, 0x010D3A02, 0x010D4001, 0x010E6006,
+    0x010E7F01, 0x010EAB08, 0x010EAD01, 0x010EC002, 0x010F0001, 0x010F3002, 0x010F4608, 0x010F5102,
+    0x010F7001, 0x010F8208, 0x010F8601, 0x01100000, 0x01100108, 0x01100200, 0x01103808, 0x01104700,
+    0x0110520D, 0x01106600, 0x01107008, 0x01107100, 0x01107308, 0x01107500, 0x01107F08, 0x01108200,
+    0x0110B308, 0x0110B700, 0x0110B908, 0x0110BB00, 0x0110C208, 0x0110C300, 0x01110008, 0x01110300,
+    0x01112708, 0x01112C00, 0x01112D08, 0x01113500, 0x01117308, 0x01117400, 0x01118008, 0x01118200,
+    0x0111B608, 0x0111BF00, 0x0111C908, 0x0111CD00, 0x0111CF08, 0x0111D000, 0x01122F08, 0x01123200,
+    0x01123408, 0x01123500, 0x01123608, 0x01123800, 0x01123E08, 0x01123F00, 0x0112DF08, 0x0112E000,
+    0x0112E308, 0x0112EB00, 0x01130008, 0x01130200, 0x01133B08, 0x01133D00, 0x01134008, 0x01134100,
+    0x01136608, 0x01136D00, 0x01137008, 0x01137500, 0x01143808, 0x01144000, 0x01144208, 0x01144500,
+    0x01144608, 0x01144700, 0x01145E08, 0x01145F00, 0x0114B308, 0x0114B900, 0x0114BA08, 0x0114BB00,
+    0x0114BF08, 0x0114C100, 0x0114C208, 0x0114C400, 0x0115B208, 0x0115B600, 0x0115BC08, 0x0115BE00,
+    0x0115BF08, 0x0115C100, 0x0115DC08, 0x0115DE00, 0x01163308, 0x01163B00, 0x01163D08, 0x01163E00,
+    0x01163F08, 0x01164100, 0x0116600D, 0x01166D00, 0x0116AB08, 0x0116AC00, 0x0116AD08, 0x0116AE00,
+    0x0116B008, 0x0116B600, 0x0116B708, 0x0116B800, 0x01171D08, 0x01172000, 0x01172208, 0x01172600,
+    0x01172708, 0x01172C00, 0x01182F08, 0x01183800, 0x01183908, 0x01183B00, 0x01193B08, 0x01193D00,
+    0x01193E08, 0x01193F00, 0x01194308, 0x01194400, 0x0119D408, 0x0119D800, 0x0119DA08, 0x0119DC00,
+    0x0119E008, 0x0119E100, 0x011A0108, 0x011A0700, 0x011A0908, 0x011A0B00, 0x011A3308, 0x011A3900,
+    0x011A3B08, 0x011A3F00, 0x011A4708, 0x011A4800, 0x011A5108, 0x011A5700, 0x011A5908, 0x011A5C00,
+    0x011A8A08, 0x011A9700, 0x011A9808, 0x011A9A00, 0x011C3008, 0x011C3700, 0x011C3808, 0x011C3E00,
+    0x011C9208, 0x011CA800, 0x011CAA08, 0x011CB100, 0x011CB208, 0x011CB400, 0x011CB508, 0x011CB700,
+    0x011D3108, 0x011D3700, 0x011D3A08, 0x011D3B00, 0x011D3C08, 0x011D3E00, 0x011D3F08, 0x011D4600,
+    0x011D4708, 0x011D4800, 0x011D9008, 0x011D9200, 0x011D9508, 0x011D9600, 0x011D9708, 0x011D9800,
+    0x011EF308, 0x011EF500, 0x011FD50D, 0x011FDD05, 0x011FE10D, 0x011FF200, 0x016AF008, 0x016AF500,
+    0x016B3008, 0x016B3700, 0x016F4F08, 0x016F5000, 0x016F8F08, 0x016F9300, 0x016FE20D, 0x016FE300,
+    0x016FE408, 0x016FE500, 0x01BC9D08, 0x01BC9F00, 0x01BCA009, 0x01BCA400, 0x01CF0008, 0x01CF2E00,
+    0x01CF3008, 0x01CF4700, 0x01D16708, 0x01D16A00, 0x01D17309, 0x01D17B08, 0x01D18300, 0x01D18508,
+    0x01D18C00, 0x01D1AA08, 0x01D1AE00, 0x01D1E90D, 0x01D1EB00, 0x01D2000D, 0x01D24208, 0x01D2450D,
+    0x01D24600, 0x01D3000D, 0x01D35700, 0x01D6DB0D, 0x01D6DC00, 0x01D7150D, 0x01D71600, 0x01D74F0D,
+    0x01D75000, 0x01D7890D, 0x01D78A00, 0x01D7C30D, 0x01D7C400, 0x01D7CE03, 0x01D80000, 0x01DA0008,
+    0x01DA3700, 0x01DA3B08, 0x01DA6D00, 0x01DA7508, 0x01DA7600, 0x01DA8408, 0x01DA8500, 0x01DA9B08,
+    0x01DAA000, 0x01DAA108, 0x01DAB000, 0x01E00008, 0x01E00700, 0x01E00808, 0x01E01900, 0x01E01B08,
+    0x01E02200, 0x01E02308, 0x01E02500, 0x01E02608, 0x01E02B00, 0x01E13008, 0x01E13700, 0x01E2AE08,
+    0x01E2AF00, 0x01E2EC08, 0x01E2F000, 0x01E2FF05, 0x01E30000, 0x01E80001, 0x01E8D008, 0x01E8D701,
+    0x01E94408, 0x01E94B01, 0x01EC7002, 0x01ECC001, 0x01ED0002, 0x01ED5001, 0x01EE0002, 0x01EEF00D,
+    0x01EEF202, 0x01EF0001, 0x01F0000D, 0x01F02C00, 0x01F0300D, 0x01F09400, 0x01F0A00D, 0x01F0AF00,
+    0x01F0B10D, 0x01F0C000, 0x01F0C10D, 0x01F0D000, 0x01F0D10D, 0x01F0F600, 0x01F10003, 0x01F10B0D,
+    0x01F11000, 0x01F12F0D, 0x01F13000, 0x01F16A0D, 0x01F17000, 0x01F1AD0D, 0x01F1AE00, 0x01F2600D,
+    0x01F26600, 0x01F3000D, 0x01F6D800, 0x01F6DD0D, 0x01F6ED00, 0x01F6F00D, 0x01F6FD00, 0x01F7000D,
+    0x01F77400, 0x01F7800D, 0x01F7D900, 0x01F7E00D, 0x01F7EC00, 0x01F7F00D, 0x01F7F100, 0x01F8000D,
+    0x01F80C00, 0x01F8100D, 0x01F84800, 0x01F8500D, 0x01F85A00, 0x01F8600D, 0x01F88800, 0x01F8900D,
+    0x01F8AE00, 0x01F8B00D, 0x01F8B200, 0x01F9000D, 0x01FA5400, 0x01FA600D, 0x01FA6E00, 0x01FA700D,
+    0x01FA7500, 0x01FA780D, 0x01FA7D00, 0x01FA800D, 0x01FA8700, 0x01FA900D, 0x01FAAD00, 0x01FAB00D,
+    0x01FABB00, 0x01FAC00D, 0x01FAC600, 0x01FAD00D, 0x01FADA00, 0x01FAE00D, 0x01FAE800, 0x01FAF00D,
+    0x01FAF700, 0x01FB000D, 0x01FB9300, 0x01FB940D, 0x01FBCB00, 0x01FBF003, 0x01FBFA00, 0x01FFFE09,
+    0x02000000, 0x02FFFE09, 0x03000000, 0x03FFFE09, 0x04000000, 0x04FFFE09, 0x05000000, 0x05FFFE09,
+    0x06000000, 0x06FFFE09, 0x07000000, 0x07FFFE09, 0x08000000, 0x08FFFE09, 0x09000000, 0x09FFFE09,
+    0x0A000000, 0x0AFFFE09, 0x0B000000, 0x0BFFFE09, 0x0C000000, 0x0CFFFE09, 0x0D000000, 0x0DFFFE09,
+    0x0E010008, 0x0E01F009, 0x0E100000, 0x0EFFFE09, 0x0F000000, 0x0FFFFE09, 0x10000000, 0x10FFFE09,
+];
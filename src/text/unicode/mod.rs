@@ -1,11 +1,12 @@
 // devela/src/text/unicode/mod.rs
 //
 #![doc = crate::_DOC_TEXT_UNICODE!()] // public
-#![doc = crate::_doc!(modules: crate::text; unicode: grapheme, scalar)]
+#![doc = crate::_doc!(modules: crate::text; unicode: bidi, grapheme, scalar)]
 #![doc = crate::_doc!(flat:"text")]
 #![doc = crate::_doc!(extends: char)]
 //
 
+pub mod bidi; // Bidi, BidiClass, BidiLevel
 // mod case;
 #[cfg(feature = "grapheme")]
 #[cfg_attr(nightly_doc, doc(cfg(feature = "grapheme")))]
@@ -26,6 +27,7 @@ crate::structural_mods! { // _mods, _pub_mods
     }
     _pub_mods {
         pub use super::{
+            bidi::_all::*,
             scalar::_all::*,
         };
         #[cfg(feature = "grapheme")]
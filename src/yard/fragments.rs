@@ -297,6 +297,7 @@ its own flat view of all its public children.";
     _DOC_SYS_DEVICE_AUDIO       = "Audio device backends and stream interfaces.";
     _DOC_SYS_DEVICE_DISPLAY     = "Display backends for windows, surfaces, and events.";
     _DOC_SYS_DEVICE_DISPLAY_X11 = "X11 display backend.";
+    _DOC_SYS_DEVICE_MIDI        = "MIDI messages, byte streams, and Standard MIDI Files.";
     _DOC_SYS_ENV         = "Process environment inspection and manipulation.";
     _DOC_SYS_FS          = "Filesystem abstractions.";
     _DOC_SYS_FS_PATH     = "Cross-platform path manipulation.";
@@ -343,6 +344,7 @@ its own flat view of all its public children.";
     _DOC_TEXT_STR        = "String types, views, and storage invariants.";
     _DOC_TEXT_TRANSLIT   = "Lossy script, alphabet, and symbol approximations.";
     _DOC_TEXT_UNICODE          = "Unicode text units, properties, and algorithms.";
+    _DOC_TEXT_UNICODE_BIDI     = "Unicode bidirectional levels and visual ordering.";
     _DOC_TEXT_UNICODE_SCALAR   = "Unicode scalar values and operations.";
     _DOC_TEXT_UNICODE_GRAPHEME = concat!["Unicode", $crate::_ABBR_EGC!(), "s."];
 
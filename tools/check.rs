@@ -85,7 +85,7 @@ const SUB_MODULES: &[&str] = &[
     // lang
         "glsl", "js",
     // media
-        "audio", "color", "draw", "font", "image", "video",
+        "audio", "color", "doc", "draw", "font", "image", "motion", "video",
     // num
         "int", "lin", "rand",
     // org